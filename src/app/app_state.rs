@@ -3,15 +3,66 @@
 //! This module defines the [`AppState`] struct, which holds all state required for a running
 //! game session, including rendering backends, UI state, game logic, and input state.
 
+use crate::app::clipboard::Clipboard;
+use crate::app::frame_limiter::FrameLimiter;
+use crate::app::profiles::{self, ProfileSummary};
+use crate::app::settings::Settings;
+use crate::app::settings_registry::{self, ApplyHook, Capabilities};
+use crate::assets::custom_marker::{MarkerLoader, default_marker};
+use crate::assets::registry::AssetRegistry;
+use crate::benchmarks::latency::LatencyProbe;
 use crate::benchmarks::{FrameRateCounter, Profiler};
+use crate::game::announcer::{Announcer, Speech};
+use crate::game::anomaly::Unlock;
+use crate::game::auto_walk;
+use crate::game::bindings::InputRouter;
+use crate::game::challenge::CHALLENGE_LABEL;
+use crate::game::codex::{self, Codex, CodexLevel, LevelOutcome, MazeAlgorithm};
+use crate::game::daily::{self, DailyChallenge, DailyResult, DailyResults, DailyRun};
 use crate::game::enemy::Enemy;
-use crate::game::{self, CurrentScreen, GameState, TimerConfig, keys::KeyState};
-use crate::renderer::text::TextRenderer;
+use crate::game::haptics::HapticsDirector;
+use crate::game::hardcore::{self, Grave, Graveyard};
+use crate::game::input::{Gamepads, PlayerInput};
+use crate::game::input_claim::{Claim, InputClaims, InputConsumer};
+use crate::game::journal::{FlushReason, JournalEvent};
+use crate::game::levels::{self, Difficulty, MazeConfig};
+use crate::game::maze::watcher::MazeFileWatcher;
+use crate::game::player::Player;
+use crate::game::quick_radial::QuickAction;
+use crate::game::sandbox::{SANDBOX_LABEL, SandboxFocus, SandboxSession, SandboxSettings};
+use crate::game::save::{self, SaveError, SavedGame};
+use crate::game::score::group_digits;
+use crate::game::toasts::ToastManager;
+use crate::game::{self, CompassMode, CurrentScreen, GameState, PauseSource, keys::KeyState, seed};
+use crate::paths;
+use crate::renderer::game_renderer::camera_projection;
+use crate::renderer::game_renderer::reticle::ReticleStyle;
+use crate::renderer::gpu_memory::BYTES_PER_MB;
+use crate::renderer::loading_renderer::LoadingRenderer;
+use crate::renderer::rectangle::RectangleRenderer;
+use crate::renderer::text::{GAME_OVER_SUBTITLE, TextRenderer, TextStyle};
+use crate::renderer::ui::announcement_overlay::AnnouncementOverlay;
+use crate::renderer::ui::audio_debug_overlay::AudioDebugOverlay;
+use crate::renderer::ui::button::ButtonManager;
+use crate::renderer::ui::error_menu::ErrorMenu;
+use crate::renderer::ui::profile_menu::{ProfileAction, ProfileMenu};
+use crate::renderer::ui::quick_radial_menu::QuickRadialMenu;
+use crate::renderer::ui::sandbox_panel::{SandboxAction, SandboxPanel};
+use crate::renderer::ui::toast_overlay::ToastOverlay;
+use crate::renderer::ui::tuning_panel::TuningPanel;
+use crate::renderer::warmup::{WarmUp, WarmUpReport};
 use crate::renderer::wgpu_lib::WgpuRenderer;
+use crate::test_mode::{
+    TEST_MAZE_PATH, hide_maze_error_banner, reload_test_maze, setup_test_environment,
+    show_maze_error_banner,
+};
 use glyphon::Color;
+use std::fmt;
+use std::path::{Path, PathBuf};
 use std::time::Duration;
 use std::time::Instant;
 use wgpu;
+use winit::dpi::PhysicalSize;
 use winit::window::Window;
 
 /// Holds all state required for a running Mirador game session.
@@ -24,6 +75,8 @@ pub struct AppState {
     pub game_state: GameState,
     /// The current input state (pressed keys, etc.).
     pub key_state: KeyState,
+    /// Decides which input context each key event belongs to, with the player's key bindings.
+    pub input_router: InputRouter,
     /// The text renderer for all game UI text elements.
     pub text_renderer: TextRenderer,
     /// The timestamp when the game started.
@@ -34,23 +87,153 @@ pub struct AppState {
     pub pause_menu: crate::renderer::ui::pause_menu::PauseMenu,
     /// The upgrade menu UI component.
     pub upgrade_menu: crate::renderer::ui::upgrade_menu::UpgradeMenu,
+    /// The game over menu UI component.
+    pub game_over_menu: crate::renderer::ui::game_over_menu::GameOverMenu,
+    /// The title screen buttons.
+    pub title_menu: crate::renderer::ui::title_menu::TitleMenu,
+    /// The quick-settings ring shown while its key is held
+    pub quick_radial_menu: QuickRadialMenu,
+    /// Reticle style the quick-settings toggle brings back after hiding the reticle
+    pub hidden_reticle_style: ReticleStyle,
+    /// Ambience volume the quick-settings toggle brings back after muting it
+    pub muted_ambience_volume: f32,
     /// Performance profiler for benchmarking
     pub profiler: Profiler,
     /// Frame rate counter for monitoring rendering performance
     pub fps_counter: FrameRateCounter,
+    /// Persistent user settings
+    pub settings: Settings,
+    /// What this machine supports, for showing settings that need it as unavailable
+    pub capabilities: Capabilities,
+    /// Persistent daily challenge results
+    pub daily_results: DailyResults,
+    /// Persistent seeds burned by hardcore deaths, and the hardcore bests
+    pub graveyard: Graveyard,
+    /// The run saved at the start of its current level, offered by the title screen's Continue button
+    pub saved_game: Option<SavedGame>,
+    /// Persistent stats for every kind of maze played
+    pub codex: Codex,
+    /// The level being played, until its result is added to the codex
+    pub codex_level: Option<CodexLevel>,
+    /// The maze codex screen
+    pub codex_menu: crate::renderer::ui::codex_menu::CodexMenu,
+    /// The screen shown after the frame update panicked
+    pub error_menu: ErrorMenu,
+    /// The profile picker
+    pub profile_menu: ProfileMenu,
+    /// Whether the debug panic key asked for a panic in the next frame
+    pub debug_panic_requested: bool,
+    /// Measures input-to-present latency in debug builds
+    pub latency_probe: LatencyProbe,
+    /// Draws the latency probe's flash square
+    pub latency_flash: RectangleRenderer,
+    /// Paces frames to the frame rate cap for the current screen
+    pub frame_limiter: FrameLimiter,
+    /// Debug panel for live renderer tuning
+    pub tuning_panel: TuningPanel,
+    /// Maze settings panel shown while the practice sandbox is open
+    pub sandbox_panel: SandboxPanel,
+    /// Toast notifications waiting or on screen
+    pub toasts: ToastManager,
+    /// The system clipboard, where the run's challenge code is copied on game over
+    pub clipboard: Clipboard,
+    /// Draws the toasts in the corner of the HUD
+    pub toast_overlay: ToastOverlay,
+    /// Turns what the HUD shows into accessibility announcements
+    pub announcer: Announcer,
+    /// Reads announcements aloud, where text-to-speech is available
+    pub speech: Speech,
+    /// Draws the last announcement at the bottom of the HUD
+    pub announcement_overlay: AnnouncementOverlay,
+    /// Draws the audio manager's sources and recent events while it is open
+    pub audio_debug_overlay: AudioDebugOverlay,
+    /// Watches the test maze file for edits while in test mode
+    pub maze_watcher: Option<MazeFileWatcher>,
+    /// Whether the window has keyboard focus
+    pub window_focused: bool,
+    /// Scale factor of the display the window is on, updated as it moves between displays
+    pub scale_factor: f64,
+    /// Decides when the controller rumbles
+    pub haptics: HapticsDirector,
+    /// Connected gamepads, read once per frame; also plays their rumble
+    pub gamepads: Gamepads,
+    /// What the active gamepad asked for this frame
+    pub gamepad_input: PlayerInput,
+    /// Loads the custom player marker in the background, while a load is in progress
+    pub marker_loader: Option<MarkerLoader>,
+    /// Glyph rasterization run during the current loading screen
+    warm_up: Option<WarmUp<GlyphWarmTask>>,
+}
+
+/// A text renderer whose glyphs are warmed up during loading screens.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum WarmTarget {
+    /// The HUD, game over, and banner text
+    Hud,
+    /// The pause menu
+    PauseMenu,
+    /// The upgrade menu
+    UpgradeMenu,
+    /// The game over menu's buttons
+    GameOverMenu,
+    /// The quick-settings ring
+    QuickRadial,
+}
+
+impl WarmTarget {
+    /// Every text renderer worth warming, in the order they are warmed.
+    const ALL: [WarmTarget; 5] = [
+        WarmTarget::Hud,
+        WarmTarget::GameOverMenu,
+        WarmTarget::PauseMenu,
+        WarmTarget::UpgradeMenu,
+        WarmTarget::QuickRadial,
+    ];
+
+    /// Name of the renderer in warm-up logs.
+    fn label(self) -> &'static str {
+        match self {
+            WarmTarget::Hud => "HUD",
+            WarmTarget::PauseMenu => "pause menu",
+            WarmTarget::UpgradeMenu => "upgrade menu",
+            WarmTarget::GameOverMenu => "game over menu",
+            WarmTarget::QuickRadial => "quick radial",
+        }
+    }
+}
+
+/// One style of one text renderer to rasterize glyphs for.
+#[derive(Debug)]
+struct GlyphWarmTask {
+    /// The renderer whose atlas the glyphs go into
+    target: WarmTarget,
+    /// The style, sized as drawn
+    style: TextStyle,
+}
+
+impl fmt::Display for GlyphWarmTask {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}: {} {}px",
+            self.target.label(),
+            self.style.font_family,
+            self.style.font_size
+        )
+    }
 }
 
 impl AppState {
     /// Asynchronously creates a new [`AppState`] with initialized renderers and game state.
     ///
     /// # Arguments
-    /// - `instance`: The WGPU instance.
+    /// - `adapter`: The adapter to render with.
     /// - `surface`: The WGPU surface for rendering.
     /// - `window`: The application window.
     /// - `width`: Initial window width.
     /// - `height`: Initial window height.
     pub async fn new(
-        instance: &wgpu::Instance,
+        adapter: wgpu::Adapter,
         surface: wgpu::Surface<'static>,
         window: &Window,
         width: u32,
@@ -65,13 +248,19 @@ impl AppState {
             write_to_file: false,
             min_duration_threshold: std::time::Duration::from_micros(1),
             max_samples: 1000,
+            autosave_interval: std::time::Duration::from_secs(60),
+            max_sections: 256,
         });
 
         window.set_cursor_visible(false);
 
+        // Load persisted settings first, since MSAA is fixed when the renderer is built
+        let settings = Settings::load();
+
         // Benchmark WgpuRenderer initialization (most taxing part)
         init_profiler.start_section("wgpu_renderer_initialization");
-        let wgpu_renderer = WgpuRenderer::new(instance, surface, width, height).await;
+        let mut wgpu_renderer =
+            WgpuRenderer::new(adapter, surface, width, height, settings.msaa_samples).await;
         init_profiler.end_section("wgpu_renderer_initialization");
 
         // Benchmark TextRenderer initialization
@@ -80,7 +269,8 @@ impl AppState {
             &wgpu_renderer.device,
             &wgpu_renderer.queue,
             wgpu_renderer.surface_config.format,
-            window,
+            window.inner_size(),
+            &wgpu_renderer.gpu_memory,
         );
         init_profiler.end_section("text_renderer_initialization");
 
@@ -91,11 +281,26 @@ impl AppState {
             println!("Loaded fonts: {:?}", text_renderer.loaded_fonts);
         }
 
-        let game_state = GameState::new();
+        // Validate the embedded asset bundle and surface critical breakage on screen
+        init_profiler.start_section("asset_validation");
+        let asset_registry = AssetRegistry::validate_embedded();
+        asset_registry.report();
+        if let Some(diagnostics) = asset_registry.diagnostic_text() {
+            show_asset_diagnostics(&mut text_renderer, window, &diagnostics);
+        }
+        init_profiler.end_section("asset_validation");
+
+        let mut game_state = GameState::new();
 
         // Benchmark game UI initialization
         init_profiler.start_section("game_ui_initialization");
-        game::initialize_game_ui(&mut text_renderer, &game_state.game_ui, window);
+        game::initialize_game_ui(
+            &mut text_renderer,
+            &game_state.game_ui,
+            width,
+            height,
+            window.scale_factor(),
+        );
         init_profiler.end_section("game_ui_initialization");
 
         // Create game over display
@@ -103,11 +308,12 @@ impl AppState {
 
         // Benchmark pause menu creation
         init_profiler.start_section("pause_menu_creation");
-        let pause_menu = crate::renderer::ui::pause_menu::PauseMenu::new(
+        let mut pause_menu = crate::renderer::ui::pause_menu::PauseMenu::new(
             &wgpu_renderer.device,
             &wgpu_renderer.queue,
             wgpu_renderer.surface_config.format,
-            window,
+            window.inner_size(),
+            &wgpu_renderer.gpu_memory,
         );
         init_profiler.end_section("pause_menu_creation");
 
@@ -117,58 +323,99 @@ impl AppState {
             &wgpu_renderer.device,
             &wgpu_renderer.queue,
             wgpu_renderer.surface_config.format,
-            window,
+            window.inner_size(),
+            &wgpu_renderer.gpu_memory,
         );
         init_profiler.end_section("upgrade_menu_creation");
 
-        // Add big boldMirador' text in the top right for the title screen
-        let width = wgpu_renderer.surface_config.width as f32;
-        let height = wgpu_renderer.surface_config.height as f32;
-        let mirador_style = crate::renderer::text::TextStyle {
-            font_family: "Hanken Grotesk".to_string(),
-            font_size: 125.0,
-            line_height: 150.0,
-            color: Color::rgb(58, 53, 70), // #3
-            weight: glyphon::Weight::BOLD,
-            style: glyphon::Style::Normal,
-        };
-        // Estimate text width for right alignment
-        let text_width = 620.0; // Conservative estimate for large text
-        let text_height = 1500.0; // Let's assume a large height for the title
-        let mirador_position = crate::renderer::text::TextPosition {
-            x: width - text_width - 200.0, // 20px margin from right
-            y: 100.0,                      // 100px margin from top
-            max_width: Some(text_width),
-            max_height: Some(text_height),
-        };
-        text_renderer.create_text_buffer(
-            "title_mirador_overlay",
-            "Mirador",
-            Some(mirador_style),
-            Some(mirador_position.clone()),
-        );
-        // Add subtitle text at the same x, 60px from the bottom
-        let subtitle_style = crate::renderer::text::TextStyle {
-            font_family: "Hanken Grotesk".to_string(),
-            font_size: 48.0,
-            line_height: 72.0,
-            color: Color::rgb(58, 53, 70), // #3
-            weight: glyphon::Weight::MEDIUM,
-            style: glyphon::Style::Normal,
-        };
-        let subtitle_text = "Click anywhere to get lost.";
-        let subtitle_text_height = 72.0;
-        let subtitle_position = crate::renderer::text::TextPosition {
-            x: mirador_position.x - 1200.0,
-            y: height + 1000.0,
-            max_width: Some(text_width),
-            max_height: Some(subtitle_text_height),
-        };
-        text_renderer.create_text_buffer(
-            "title_subtitle_overlay",
-            subtitle_text,
-            Some(subtitle_style),
-            Some(subtitle_position),
+        let game_over_menu = crate::renderer::ui::game_over_menu::GameOverMenu::new(
+            &wgpu_renderer.device,
+            &wgpu_renderer.queue,
+            wgpu_renderer.surface_config.format,
+            window.inner_size(),
+            &wgpu_renderer.gpu_memory,
+        );
+
+        let title_menu = crate::renderer::ui::title_menu::TitleMenu::new(
+            &wgpu_renderer.device,
+            &wgpu_renderer.queue,
+            wgpu_renderer.surface_config.format,
+            window.inner_size(),
+            &wgpu_renderer.gpu_memory,
+        );
+
+        let quick_radial_menu = QuickRadialMenu::new(
+            &wgpu_renderer.device,
+            &wgpu_renderer.queue,
+            wgpu_renderer.surface_config.format,
+            window.inner_size(),
+            &wgpu_renderer.gpu_memory,
+            game_state.quick_radial.config(),
+        );
+
+        let codex_menu = crate::renderer::ui::codex_menu::CodexMenu::new(
+            &wgpu_renderer.device,
+            &wgpu_renderer.queue,
+            wgpu_renderer.surface_config.format,
+            window.inner_size(),
+            &wgpu_renderer.gpu_memory,
+        );
+
+        let error_menu = ErrorMenu::new(
+            &wgpu_renderer.device,
+            &wgpu_renderer.queue,
+            wgpu_renderer.surface_config.format,
+            window.inner_size(),
+            &wgpu_renderer.gpu_memory,
+        );
+
+        let profile_menu = ProfileMenu::new(
+            &wgpu_renderer.device,
+            &wgpu_renderer.queue,
+            wgpu_renderer.surface_config.format,
+            window.inner_size(),
+            &wgpu_renderer.gpu_memory,
+        );
+
+        let tuning_panel = TuningPanel::new(
+            &wgpu_renderer.device,
+            wgpu_renderer.surface_config.format,
+            window,
+        );
+        let sandbox_panel = SandboxPanel::new(
+            &wgpu_renderer.device,
+            wgpu_renderer.surface_config.format,
+            window,
+        );
+        let toasts = ToastManager::new();
+        game_state.toasts = toasts.sender();
+        let toast_overlay = ToastOverlay::new(
+            &wgpu_renderer.device,
+            &wgpu_renderer.queue,
+            wgpu_renderer.surface_config.format,
+            window.inner_size(),
+            &wgpu_renderer.gpu_memory,
+        );
+        let announcement_overlay = AnnouncementOverlay::new(
+            &wgpu_renderer.device,
+            &wgpu_renderer.queue,
+            wgpu_renderer.surface_config.format,
+            window.inner_size(),
+            &wgpu_renderer.gpu_memory,
+        );
+        let audio_debug_overlay = AudioDebugOverlay::new(
+            &wgpu_renderer.device,
+            &wgpu_renderer.queue,
+            wgpu_renderer.surface_config.format,
+            window.inner_size(),
+            &wgpu_renderer.gpu_memory,
+        );
+
+        // The title screen's title and subtitle
+        crate::renderer::title::create_title_text(
+            &mut text_renderer,
+            wgpu_renderer.surface_config.width as f32,
+            wgpu_renderer.surface_config.height as f32,
         );
 
         // Initialize benchmarking components
@@ -178,22 +425,128 @@ impl AppState {
             write_to_file: true,
             min_duration_threshold: Duration::from_micros(100),
             max_samples: 2000,
+            autosave_interval: Duration::from_secs(60),
+            max_sections: 256,
         };
 
+        crate::benchmarks::utils::configure(benchmark_config.clone());
         let profiler = Profiler::new(benchmark_config);
         let fps_counter = FrameRateCounter::new(120);
+        let latency_flash =
+            RectangleRenderer::new(&wgpu_renderer.device, wgpu_renderer.surface_config.format);
+
+        // Reflect the persisted settings in the pause menu
+        pause_menu.update_fps_cap_button_text(settings.menu_fps_cap, settings.gameplay_fps_cap);
+        pause_menu.update_present_mode_button_text(settings.present_mode);
+        pause_menu.update_background_button_text(settings.background);
+        pause_menu.update_ambience_button_text(settings.ambience_volume);
+        pause_menu.update_time_of_day_button_text(settings.time_of_day);
+        game_state
+            .director
+            .set_enabled(settings.adaptive_difficulty);
+        game_state.navigation_hints = settings.navigation_hints;
+        game_state
+            .audio_manager
+            .set_enhanced_audio(settings.enhanced_audio);
+        game_state
+            .audio_manager
+            .set_ambience_volume(settings.ambience_volume);
+        game_state
+            .audio_manager
+            .set_spatial_profile(settings.audio_output, settings.hrtf);
+        let capabilities = Capabilities {
+            hrtf: game_state.audio_manager.supports_hrtf(),
+        };
+        pause_menu.update_spatial_audio_buttons(
+            settings.audio_output,
+            settings.hrtf,
+            &capabilities,
+        );
+        wgpu_renderer.hud_max_aspect = settings.hud_max_aspect;
+        wgpu_renderer.view_fit = settings.view_fit;
+        wgpu_renderer.background = settings.background;
+        wgpu_renderer.reticle = settings.reticle;
+        // Cosmetics only apply once their anomaly has been found
+        let codex = Codex::load();
+        wgpu_renderer.cosmetics = codex.unlocks.unlocked_of(&settings.cosmetics);
+        pause_menu.update_cosmetic_buttons(
+            |cosmetic| codex.unlocks.is_unlocked(cosmetic),
+            |cosmetic| wgpu_renderer.cosmetics.contains(&cosmetic),
+        );
+        let input_router = InputRouter::load();
+        pause_menu.update_binding_buttons(&input_router);
+        wgpu_renderer.reduce_effects = settings.reduce_effects;
+        wgpu_renderer.time_of_day = settings.time_of_day;
+        wgpu_renderer.set_present_mode(settings.present_mode);
+        wgpu_renderer
+            .gpu_memory
+            .set_budget(settings.gpu_memory_budget_mb as u64 * BYTES_PER_MB);
 
-        Self {
+        let mut state = Self {
             wgpu_renderer,
             game_state,
             key_state: KeyState::default(),
+            input_router,
+            capabilities,
             text_renderer,
             start_time: Instant::now(),
             elapsed_time: Duration::default(),
             pause_menu,
             upgrade_menu,
+            game_over_menu,
+            title_menu,
+            quick_radial_menu,
+            hidden_reticle_style: ReticleStyle::default(),
+            muted_ambience_volume: 1.0,
             profiler,
             fps_counter,
+            settings,
+            daily_results: DailyResults::load(),
+            graveyard: Graveyard::load(),
+            saved_game: load_saved_game(),
+            codex,
+            codex_level: None,
+            codex_menu,
+            error_menu,
+            profile_menu,
+            debug_panic_requested: false,
+            latency_probe: LatencyProbe::new(),
+            latency_flash,
+            frame_limiter: FrameLimiter::new(),
+            tuning_panel,
+            sandbox_panel,
+            toasts,
+            clipboard: Clipboard::new(),
+            toast_overlay,
+            announcer: Announcer::new(),
+            speech: Speech::new(),
+            announcement_overlay,
+            audio_debug_overlay,
+            maze_watcher: None,
+            window_focused: true,
+            scale_factor: window.scale_factor(),
+            haptics: HapticsDirector::new(),
+            gamepads: Gamepads::new(),
+            gamepad_input: PlayerInput::default(),
+            marker_loader: Some(MarkerLoader::start(&paths::get().custom_marker())),
+            warm_up: None,
+        };
+        state.start_profile();
+        state
+    }
+
+    /// Picks up the profile chosen at launch.
+    ///
+    /// Records it in the journal and says once if old data was moved into it. With no
+    /// profile yet, as on a first launch, opens the profile picker instead of the title
+    /// screen.
+    fn start_profile(&mut self) {
+        if profiles::take_migration_notice() {
+            self.toasts.sender().system(profiles::MIGRATION_NOTICE);
+        }
+        match paths::get().profile() {
+            Some(profile) => self.game_state.journal.select_profile(profile),
+            None => self.open_profile_menu(),
         }
     }
 
@@ -202,77 +555,505 @@ impl AppState {
     /// # Arguments
     /// - `width`: New width of the surface.
     /// - `height`: New height of the surface.
-    pub fn resize_surface(&mut self, width: u32, height: u32) {
-        self.wgpu_renderer.surface_config.width = width;
-        self.wgpu_renderer.surface_config.height = height;
-        self.wgpu_renderer.surface.configure(
-            &self.wgpu_renderer.device,
-            &self.wgpu_renderer.surface_config,
-        );
-
-        self.wgpu_renderer
-            .game_renderer
-            .compass_renderer
-            .update_uniforms(&self.wgpu_renderer.queue, [0.75, 0.75], [4.75, 4.75]);
+    ///
+    /// # Returns
+    /// Whether the surface was resized; a minimized window's zero size is skipped
+    pub fn resize_surface(&mut self, width: u32, height: u32) -> bool {
+        if !self.wgpu_renderer.resize(width, height) {
+            return false;
+        }
         // Update game over display position for new window size
         if let Err(e) = self.text_renderer.update_game_over_position(width, height) {
             println!("Failed to update game over position: {}", e);
         }
+        game::resize_game_ui(&mut self.text_renderer, width, height, self.scale_factor);
+        true
+    }
+
+    /// Reconfigures the surface with the present mode from the settings and updates the vsync
+    /// button.
+    ///
+    /// When the mode changes the FPS counter starts a new one-second window, so the next
+    /// reading only counts frames presented with the new mode.
+    pub fn apply_present_mode(&mut self) {
+        if self
+            .wgpu_renderer
+            .set_present_mode(self.settings.present_mode)
+        {
+            self.game_state.frame_count = 0;
+            self.game_state.last_fps_time = Instant::now();
+        }
+        self.pause_menu
+            .update_present_mode_button_text(self.settings.present_mode);
     }
 
-    /// Updates the title screen maze and loading bar, and uploads new texture data.
+    /// Plays controller rumble for what happened this frame.
+    ///
+    /// Should be called once per frame, after the enemy has moved.
+    pub fn update_haptics(&mut self) {
+        let frame = self.game_state.haptic_frame(self.window_focused);
+        self.haptics
+            .update(frame, self.settings.haptics_intensity, &mut self.gamepads);
+    }
+
+    /// Turns the camera with the gamepad's look stick.
+    ///
+    /// The stick only turns the camera when the mouse would: during play, with the cursor
+    /// captured, and not while the enemy has the player or the quick-settings radial is open.
+    pub fn update_look_stick(&mut self) {
+        let look_axis = self.gamepad_input.look_axis;
+        let looking = matches!(
+            self.game_state.current_screen,
+            CurrentScreen::Game | CurrentScreen::ExitReached
+        ) && self.game_state.capture_mouse
+            && self.game_state.capture.is_none()
+            && !self.game_state.quick_radial.is_open()
+            && !self.input_claims().keeps_cursor_free();
+        if looking && look_axis != [0.0, 0.0] {
+            self.game_state.player.stick_look(
+                look_axis,
+                self.settings.gamepad_look_sensitivity,
+                self.game_state.delta_time,
+            );
+        }
+    }
+
+    /// Makes any accessibility announcements called for this frame.
+    ///
+    /// Does nothing unless the announcements setting is on. Should be called once per
+    /// frame; the exit's direction is read from where the HUD compass points, so it isn't
+    /// announced while the compass points north.
+    pub fn update_announcements(&mut self) {
+        let compass = &self.wgpu_renderer.game_renderer.compass_renderer;
+        let bearing = compass
+            .get_compass_angle_degrees()
+            .filter(|_| self.game_state.compass_mode == CompassMode::Exit);
+        let frame = self.game_state.announcer_frame(bearing);
+        for announcement in self.announcer.update(frame, self.settings.announcements) {
+            let text = announcement.text();
+            println!("Announcement: {}", text);
+            self.speech.say(&text);
+        }
+    }
+
+    /// Updates the title screen maze and loading bar, and uploads any newly carved cells.
     pub fn handle_loading_screen(&mut self, window: &winit::window::Window) {
         let progress = self
             .wgpu_renderer
             .loading_screen_renderer
-            .generator
-            .get_progress_ratio();
+            .get_generation_progress();
 
-        let (maze_width, maze_height) = match self.wgpu_renderer.loading_screen_renderer.maze.lock()
-        {
-            Ok(maze_lock) => maze_lock.get_dimensions(),
-            Err(err) => {
-                eprintln!("Failed to acquire maze lock for dimensions: {}", err);
-                return;
-            }
-        };
+        self.wgpu_renderer
+            .loading_screen_renderer
+            .update_loading_bar(&self.wgpu_renderer.queue, progress, window.inner_size());
 
         self.wgpu_renderer
             .loading_screen_renderer
-            .update_loading_bar(&self.wgpu_renderer.queue, progress, window);
+            .update_exit_shader(&self.wgpu_renderer.queue, window.inner_size());
 
         self.wgpu_renderer
             .loading_screen_renderer
-            .update_exit_shader(&self.wgpu_renderer.queue, window);
-
-        let maze_data = match self.wgpu_renderer.loading_screen_renderer.maze.lock() {
-            Ok(maze_lock) => maze_lock.get_render_data(
-                &self
-                    .wgpu_renderer
-                    .loading_screen_renderer
-                    .generator
-                    .connected_cells,
-            ),
-            Err(err) => {
-                eprintln!("Failed to acquire maze lock: {}", err);
-                return;
+            .update_maze(&self.wgpu_renderer.queue, self.settings.reduce_effects);
+        self.wgpu_renderer.loading_screen_renderer.last_update = Instant::now();
+
+        self.step_warm_up();
+    }
+
+    /// Returns the text renderer a warm-up target refers to.
+    fn warm_target_renderer(&mut self, target: WarmTarget) -> &mut TextRenderer {
+        match target {
+            WarmTarget::Hud => &mut self.text_renderer,
+            WarmTarget::PauseMenu => &mut self.pause_menu.button_manager.text_renderer,
+            WarmTarget::UpgradeMenu => &mut self.upgrade_menu.button_manager.text_renderer,
+            WarmTarget::GameOverMenu => &mut self.game_over_menu.button_manager.text_renderer,
+            WarmTarget::QuickRadial => &mut self.quick_radial_menu.button_manager.text_renderer,
+        }
+    }
+
+    /// Rasterizes the next few text styles the HUD and menus haven't drawn yet.
+    ///
+    /// Starts a warm-up on the first loading screen frame and runs a slice of it each frame
+    /// after, so the first upgrade menu or game over screen doesn't hitch on glyph
+    /// rasterization. Only styles not yet in each renderer's atlas are queued, so later loading
+    /// screens only pick up sizes new since, such as after a resize.
+    fn step_warm_up(&mut self) {
+        let mut warm_up = match self.warm_up.take() {
+            Some(warm_up) => warm_up,
+            None => {
+                let mut tasks = Vec::new();
+                for target in WarmTarget::ALL {
+                    let renderer = self.warm_target_renderer(target);
+                    tasks.extend(
+                        renderer
+                            .begin_warm_up(target.label())
+                            .into_iter()
+                            .map(|style| GlyphWarmTask { target, style }),
+                    );
+                }
+                WarmUp::new(tasks)
             }
         };
 
-        self.wgpu_renderer.loading_screen_renderer.update_texture(
+        if !warm_up.is_done() {
+            self.profiler.start_section("glyph_warm_up");
+            let device = self.wgpu_renderer.device.clone();
+            let queue = self.wgpu_renderer.queue.clone();
+            warm_up.run_slice(|task| {
+                let started = Instant::now();
+                let renderer = self.warm_target_renderer(task.target);
+                if let Err(e) = renderer.warm_glyphs(&device, &queue, &task.style) {
+                    println!("[WARMUP] Failed to warm {}: {}", task, e);
+                }
+                started.elapsed()
+            });
+            self.profiler.end_section("glyph_warm_up");
+        }
+        if warm_up.is_done()
+            && let Some(report) = warm_up.finish()
+        {
+            self.record_warm_up(report);
+        }
+        self.warm_up = Some(warm_up);
+    }
+
+    /// Ends the loading screen's warm-up, cancelling whatever it didn't get to.
+    ///
+    /// Should be called every frame the loading screen isn't shown.
+    pub fn end_warm_up(&mut self) {
+        if let Some(report) = self.warm_up.take().and_then(|mut warm_up| warm_up.finish()) {
+            self.record_warm_up(report);
+        }
+    }
+
+    /// Logs a finished warm-up to the console and the session journal.
+    ///
+    /// From then on, each text renderer logs styles it draws without them having been warmed.
+    fn record_warm_up(&mut self, report: WarmUpReport) {
+        for target in WarmTarget::ALL {
+            self.warm_target_renderer(target).finish_warm_up();
+        }
+        if report.warmed.is_empty() && report.skipped.is_empty() {
+            return;
+        }
+        let millis = report.spent.as_millis() as u64;
+        println!(
+            "[WARMUP] Rasterized {} text styles in {} ms, {} left for first use",
+            report.warmed.len(),
+            millis,
+            report.skipped.len()
+        );
+        self.game_state.journal.record(JournalEvent::GlyphsWarmed {
+            warmed: report.warmed,
+            skipped: report.skipped,
+            millis,
+        });
+    }
+
+    /// Builds the test mode level and starts watching its maze file for edits.
+    ///
+    /// # Arguments
+    /// - `window`: The application window, used to size the error banner.
+    pub fn start_test_mode(&mut self, window: &Window) {
+        // The test maze is not a retryable level
+        self.game_state.clear_level_snapshot();
+        self.game_state.plan_level(self.game_state.game_ui.level);
+        if let Some(error) = setup_test_environment(&mut self.game_state, &mut self.wgpu_renderer) {
+            show_maze_error_banner(&mut self.text_renderer, window, &error);
+        }
+        // Test mode reaches further, on top of any upgrades from the run
+        let dimensions = self.upgrade_menu.upgraded_dimensions(true);
+        self.game_state.set_dimensions(dimensions);
+        self.game_state.maze_path = Some(PathBuf::from(TEST_MAZE_PATH));
+        self.game_state.level_ready = true;
+        self.maze_watcher = Some(MazeFileWatcher::start(Path::new(TEST_MAZE_PATH)));
+    }
+
+    /// Stops watching the test maze file and hides any maze error banner.
+    pub fn stop_test_mode_watcher(&mut self) {
+        // Dropping the watcher joins its thread
+        self.maze_watcher = None;
+        hide_maze_error_banner(&mut self.text_renderer);
+    }
+
+    /// Draws the toasts on screen over everything drawn so far this frame.
+    ///
+    /// # Arguments
+    /// - `encoder`: The frame's command encoder.
+    /// - `surface_view`: The view of the frame's surface texture.
+    pub fn render_toasts(
+        &mut self,
+        encoder: &mut wgpu::CommandEncoder,
+        surface_view: &wgpu::TextureView,
+    ) {
+        if self.toasts.visible().next().is_none() {
+            return;
+        }
+        if let Err(e) = self.toast_overlay.prepare(
+            &self.wgpu_renderer.device,
+            &self.wgpu_renderer.queue,
+            &self.wgpu_renderer.surface_config,
+            self.wgpu_renderer.hud_area(),
+            &self.toasts,
+        ) {
+            eprintln!("Failed to prepare toasts: {}", e);
+            return;
+        }
+        let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view: surface_view,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Load,
+                    store: wgpu::StoreOp::Store,
+                },
+            })],
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            label: Some("toast render pass"),
+            occlusion_query_set: None,
+        });
+        if let Err(e) = self
+            .toast_overlay
+            .render(&self.wgpu_renderer.device, &mut render_pass)
+        {
+            eprintln!("Failed to render toasts: {}", e);
+        }
+    }
+
+    /// Draws the last accessibility announcement, if one is still up.
+    ///
+    /// # Arguments
+    /// - `encoder`: The command encoder to record the pass into.
+    /// - `surface_view`: The frame being drawn.
+    pub fn render_announcement(
+        &mut self,
+        encoder: &mut wgpu::CommandEncoder,
+        surface_view: &wgpu::TextureView,
+    ) {
+        let Some((text, opacity)) = self.announcer.line() else {
+            return;
+        };
+        if let Err(e) = self.announcement_overlay.prepare(
+            &self.wgpu_renderer.device,
             &self.wgpu_renderer.queue,
-            &maze_data,
-            maze_width,
-            maze_height,
+            &self.wgpu_renderer.surface_config,
+            self.wgpu_renderer.hud_area(),
+            text,
+            opacity,
+        ) {
+            eprintln!("Failed to prepare announcement: {}", e);
+            return;
+        }
+        let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view: surface_view,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Load,
+                    store: wgpu::StoreOp::Store,
+                },
+            })],
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            label: Some("announcement render pass"),
+            occlusion_query_set: None,
+        });
+        if let Err(e) = self
+            .announcement_overlay
+            .render(&self.wgpu_renderer.device, &mut render_pass)
+        {
+            eprintln!("Failed to render announcement: {}", e);
+        }
+    }
+
+    /// Opens or closes the audio debug overlay.
+    ///
+    /// The audio manager only keeps its event log while the overlay is open.
+    pub fn toggle_audio_debug(&mut self) {
+        let visible = !self.audio_debug_overlay.visible;
+        self.audio_debug_overlay.visible = visible;
+        self.game_state.audio_manager.set_introspection(visible);
+        println!(
+            "[AUDIO] Debug overlay {}",
+            if visible { "open" } else { "closed" }
         );
-        self.wgpu_renderer.loading_screen_renderer.last_update = Instant::now();
+    }
+
+    /// Writes what the audio manager is playing to the run journal.
+    pub fn dump_audio_snapshot(&mut self) {
+        let snapshot = self.game_state.audio_manager.debug_snapshot();
+        println!("[AUDIO] Snapshot written to the journal\n{}", snapshot);
+        self.game_state
+            .journal
+            .record(JournalEvent::AudioSnapshot { snapshot });
+    }
+
+    /// Draws the audio debug overlay, if it is open.
+    ///
+    /// # Arguments
+    /// - `encoder`: The command encoder to record the pass into.
+    /// - `surface_view`: The frame being drawn.
+    pub fn render_audio_debug(
+        &mut self,
+        encoder: &mut wgpu::CommandEncoder,
+        surface_view: &wgpu::TextureView,
+    ) {
+        if !self.audio_debug_overlay.visible {
+            return;
+        }
+        let snapshot = self.game_state.audio_manager.debug_snapshot();
+        if let Err(e) = self.audio_debug_overlay.prepare(
+            &self.wgpu_renderer.device,
+            &self.wgpu_renderer.queue,
+            &self.wgpu_renderer.surface_config,
+            self.wgpu_renderer.hud_area(),
+            &snapshot,
+        ) {
+            eprintln!("Failed to prepare audio debug overlay: {}", e);
+            return;
+        }
+        let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view: surface_view,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Load,
+                    store: wgpu::StoreOp::Store,
+                },
+            })],
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            label: Some("audio debug render pass"),
+            occlusion_query_set: None,
+        });
+        if let Err(e) = self
+            .audio_debug_overlay
+            .render(&self.wgpu_renderer.device, &mut render_pass)
+        {
+            eprintln!("Failed to render audio debug overlay: {}", e);
+        }
+    }
+
+    /// Reloads the test maze if its file changed since the last frame.
+    ///
+    /// # Arguments
+    /// - `window`: The application window, used to size the error banner.
+    pub fn reload_test_maze_if_changed(&mut self, window: &Window) {
+        let changed = self
+            .maze_watcher
+            .as_ref()
+            .is_some_and(|watcher| watcher.take_change());
+        if changed && self.game_state.is_test_mode {
+            reload_test_maze(
+                &mut self.game_state,
+                &mut self.wgpu_renderer,
+                &mut self.text_renderer,
+                window,
+            );
+        }
+    }
+
+    /// Returns the buttons of the menu on screen, if the screen has one.
+    pub fn active_buttons(&self) -> Option<&ButtonManager> {
+        match self.game_state.current_screen {
+            CurrentScreen::Title if self.title_menu.is_visible() => {
+                Some(&self.title_menu.button_manager)
+            }
+            CurrentScreen::Pause if self.pause_menu.is_visible() => {
+                Some(&self.pause_menu.button_manager)
+            }
+            CurrentScreen::GameOver if self.game_over_menu.is_visible() => {
+                Some(&self.game_over_menu.button_manager)
+            }
+            CurrentScreen::UpgradeMenu if self.upgrade_menu.is_visible() => {
+                Some(&self.upgrade_menu.button_manager)
+            }
+            CurrentScreen::Codex => Some(&self.codex_menu.button_manager),
+            CurrentScreen::Profiles => Some(&self.profile_menu.button_manager),
+            CurrentScreen::Error => Some(&self.error_menu.button_manager),
+            _ => None,
+        }
+    }
+
+    /// Returns whether a text field on the current screen, or the controls page waiting for a
+    /// key to bind, takes the typed keys.
+    pub fn is_typing(&self) -> bool {
+        match self.game_state.current_screen {
+            CurrentScreen::Title => self.title_menu.is_editing_seed(),
+            CurrentScreen::Pause => {
+                self.pause_menu.is_editing_filter() || self.pause_menu.is_capturing_key()
+            }
+            CurrentScreen::Profiles => self.profile_menu.is_typing(),
+            _ => false,
+        }
+    }
+
+    /// Returns whether the sandbox panel is open in UI mode, where it takes every key.
+    pub fn sandbox_panel_has_keyboard(&self) -> bool {
+        self.game_state
+            .sandbox
+            .as_ref()
+            .is_some_and(|session| self.sandbox_panel.wants_keyboard(session))
+    }
+
+    /// Asks every UI consumer what input it claims right now (see [`crate::game::input_claim`]).
+    ///
+    /// Nothing is changed by asking, so this can be called before every event.
+    pub fn input_claims(&self) -> InputClaims {
+        let text_entry = self.is_typing();
+        let sandbox_pointer = self
+            .game_state
+            .sandbox
+            .as_ref()
+            .is_some_and(|session| self.sandbox_panel.wants_pointer(session));
+        let sandbox_keyboard = self.sandbox_panel_has_keyboard();
+
+        InputClaims::new()
+            .with(
+                InputConsumer::TextEntry,
+                Claim {
+                    pointer: false,
+                    keyboard: text_entry,
+                },
+            )
+            .with(
+                InputConsumer::TuningPanel,
+                Claim {
+                    pointer: self.tuning_panel.wants_pointer(),
+                    keyboard: self.tuning_panel.wants_keyboard(),
+                },
+            )
+            .with(
+                InputConsumer::SandboxPanel,
+                Claim {
+                    pointer: sandbox_pointer,
+                    keyboard: sandbox_keyboard,
+                },
+            )
+            .with(
+                InputConsumer::QuickRadial,
+                Claim {
+                    pointer: self.game_state.quick_radial.is_open(),
+                    keyboard: false,
+                },
+            )
+            .with(
+                InputConsumer::Buttons,
+                Claim {
+                    pointer: self
+                        .active_buttons()
+                        .is_some_and(ButtonManager::claims_pointer),
+                    keyboard: false,
+                },
+            )
     }
 
     /// Handles mouse capture and cursor visibility based on game state.
     ///
-    /// Locks/unlocks the cursor and centers it if mouse capture is enabled.
+    /// Locks/unlocks the cursor and centers it if mouse capture is enabled. The cursor stays
+    /// free while a panel or menu holds the pointer, whatever the game asked for.
     pub fn triage_mouse(&mut self, window: &Window) {
-        if self.game_state.capture_mouse {
+        if self.game_state.capture_mouse && !self.input_claims().keeps_cursor_free() {
             if let Err(e) = window.set_cursor_grab(winit::window::CursorGrabMode::Locked) {
                 eprintln!("Failed to lock cursor: {}, {}", e, e);
             }
@@ -286,7 +1067,7 @@ impl AppState {
             {
                 eprintln!("Failed to center cursor: {}, {}", e, e);
             }
-        } else if !self.game_state.capture_mouse {
+        } else {
             if let Err(e) = window.set_cursor_grab(winit::window::CursorGrabMode::None) {
                 eprintln!("Failed to unlock cursor: {}, {}", e, e);
             }
@@ -296,19 +1077,13 @@ impl AppState {
 
     /// Updates all game UI elements including timer, level, and score displays.
     pub fn update_game_ui(&mut self, window: &winit::window::Window) {
-        // Start timer when game begins (not on title screen)
+        // Start timer when game begins (not on title screen); the sandbox has none
         if self.game_state.current_screen == CurrentScreen::Game
             && self.game_state.game_ui.timer.is_none()
+            && self.game_state.sandbox.is_none()
         {
-            // Configure timer with custom settings
-            let timer_config = TimerConfig {
-                duration: Duration::from_secs(30),
-                warning_threshold: Duration::from_secs(20),
-                critical_threshold: Duration::from_secs(10),
-                normal_color: Color::rgb(100, 255, 100),
-                warning_color: Color::rgb(255, 255, 100),
-                critical_color: Color::rgb(255, 100, 100),
-            };
+            // Configure the timer from the level's plan
+            let timer_config = self.game_state.level_plan.timer.config();
             self.game_state.start_game_timer(Some(timer_config));
         }
 
@@ -316,7 +1091,7 @@ impl AppState {
         if self.game_state.current_screen == CurrentScreen::Loading
             || self.game_state.current_screen == CurrentScreen::Title
         {
-            // Hide timer, level, and score displays
+            // Hide timer, level, score, combo, and run mode displays
             if let Some(buffer) = self.text_renderer.text_buffers.get_mut("main_timer") {
                 buffer.visible = false;
             }
@@ -326,18 +1101,45 @@ impl AppState {
             if let Some(buffer) = self.text_renderer.text_buffers.get_mut("score") {
                 buffer.visible = false;
             }
+            if let Some(buffer) = self.text_renderer.text_buffers.get_mut("combo") {
+                buffer.visible = false;
+            }
+            if let Some(buffer) = self.text_renderer.text_buffers.get_mut("run_mode") {
+                buffer.visible = false;
+            }
         } else {
-            // Show game UI elements when not loading
+            // Show game UI elements when not loading; the sandbox only shows its label
+            let scored = self.game_state.sandbox.is_none();
             // HIDE the timer text buffer always (replaced by bar)
             if let Some(buffer) = self.text_renderer.text_buffers.get_mut("main_timer") {
                 buffer.visible = false;
             }
             if let Some(buffer) = self.text_renderer.text_buffers.get_mut("level") {
-                buffer.visible = true;
+                buffer.visible = scored;
             }
             if let Some(buffer) = self.text_renderer.text_buffers.get_mut("score") {
+                buffer.visible = scored;
+            }
+            if let Some(buffer) = self.text_renderer.text_buffers.get_mut("combo") {
+                buffer.visible = scored;
+            }
+            if let Some(buffer) = self.text_renderer.text_buffers.get_mut("run_mode") {
+                buffer.visible = true;
+            }
+        }
+
+        // Name the run's seed while a level loads, so friends can race the same mazes
+        if self.game_state.current_screen == CurrentScreen::Loading && !self.game_state.is_test_mode
+        {
+            let label = self.game_state.seed_label();
+            if self.text_renderer.get_text_content(LOADING_SEED_ID).ok() != Some(label.clone()) {
+                show_loading_seed(&mut self.text_renderer, window, &label);
+            }
+            if let Some(buffer) = self.text_renderer.text_buffers.get_mut(LOADING_SEED_ID) {
                 buffer.visible = true;
             }
+        } else if let Some(buffer) = self.text_renderer.text_buffers.get_mut(LOADING_SEED_ID) {
+            buffer.visible = false;
         }
 
         // Always update the text UI, but only update the timer if in Game
@@ -345,21 +1147,74 @@ impl AppState {
             &mut self.text_renderer,
             &mut self.game_state.game_ui,
             &self.game_state.current_screen,
-            window,
+            self.game_state.delta_time,
+            self.wgpu_renderer.hud_area(),
         );
 
         if timer_expired {
             // Handle timer expiration - you can add game over logic here
             println!("Timer expired! Game over.");
+            self.game_state.journal.record(JournalEvent::TimerExpired);
+            self.game_state.adapt_difficulty(true);
+            self.record_codex(true);
             self.game_state.stop_game_timer();
             self.game_state.current_screen = CurrentScreen::GameOver;
+            self.finish_daily_attempt(window);
+            self.finish_hardcore_run();
+            // Free the cursor for the game over buttons
+            self.game_state.capture_mouse = false;
+            self.triage_mouse(window);
+        }
+
+        // Getting caught in the sandbox just starts the maze over
+        if self.game_state.enemy.pathfinder.reached_player && self.game_state.sandbox.is_some() {
+            self.game_state.enemy.pathfinder.reached_player = false;
+            self.generate_sandbox_maze();
         }
 
-        if self.game_state.enemy.pathfinder.reached_player {
+        if self.game_state.enemy.pathfinder.reached_player && self.game_state.capture.is_none() {
+            self.game_state
+                .journal
+                .record(JournalEvent::EnemyReachedPlayer);
+            self.game_state.adapt_difficulty(true);
+            self.record_codex(true);
             self.game_state.stop_game_timer();
+            self.game_state.enemy.pathfinder.reached_player = false;
+            // Play the capture sequence before cutting to the game over screen
+            self.game_state.start_capture();
+        }
+
+        if self.game_state.update_capture() {
+            self.game_state.capture = None;
             self.game_state.current_screen = CurrentScreen::GameOver;
+            self.finish_daily_attempt(window);
+            self.finish_hardcore_run();
             self.game_state.enemy = Enemy::new([-0.5, 30.0, 0.0], 150.0);
-            self.game_state.enemy.pathfinder.reached_player = false;
+            self.game_state.clear_pack();
+            // Free the cursor for the game over buttons
+            self.game_state.capture_mouse = false;
+            self.triage_mouse(window);
+        }
+
+        // Show the level score breakdown and the run's code while the exit animation plays
+        let summary = self
+            .game_state
+            .last_score_breakdown
+            .as_ref()
+            .filter(|_| self.game_state.current_screen == CurrentScreen::ExitReached)
+            .map(|breakdown| breakdown.summary_lines());
+        if let Some(mut summary) = summary {
+            if let Some(code) = self.game_state.challenge_code() {
+                summary.push_str(&format!("\nCode: {}", code));
+            }
+            let _ = self.text_renderer.update_text("level_summary", &summary);
+            let _ = self
+                .text_renderer
+                .set_buffer_visibility("level_summary", true);
+        } else {
+            let _ = self
+                .text_renderer
+                .set_buffer_visibility("level_summary", false);
         }
 
         // Show/hide game over display based on current screen
@@ -367,6 +1222,1441 @@ impl AppState {
             self.text_renderer.show_game_over_display();
         } else {
             self.text_renderer.hide_game_over_display();
+            let _ = self
+                .text_renderer
+                .set_buffer_visibility(JOURNAL_NOTICE_ID, false);
+            let _ = self
+                .text_renderer
+                .set_buffer_visibility(DAILY_HISTORY_ID, false);
+            let _ = self
+                .text_renderer
+                .set_buffer_visibility(CHALLENGE_CODE_ID, false);
+        }
+        if self.game_state.current_screen != CurrentScreen::Title {
+            let _ = self
+                .text_renderer
+                .set_buffer_visibility(HARDCORE_NOTICE_ID, false);
+            let _ = self
+                .text_renderer
+                .set_buffer_visibility(CHALLENGE_NOTICE_ID, false);
         }
     }
+
+    /// Starts loading the custom player marker from the data directory again.
+    ///
+    /// Any load still in progress is abandoned in favor of the new one.
+    pub fn reload_custom_marker(&mut self) {
+        self.marker_loader = Some(MarkerLoader::start(&paths::get().custom_marker()));
+    }
+
+    /// Uploads the custom player marker once its background load has finished.
+    ///
+    /// A missing or rejected file falls back to the default marker; a rejected one also
+    /// leaves a note in the pause menu saying why. Should be called once per frame.
+    pub fn update_custom_marker(&mut self) {
+        let Some(result) = self.marker_loader.as_ref().and_then(MarkerLoader::poll) else {
+            return;
+        };
+        self.marker_loader = None;
+
+        let (marker, note) = match result {
+            Ok(Some(marker)) => (marker, None),
+            Ok(None) => (default_marker(), None),
+            Err(rejection) => {
+                eprintln!("{}", rejection);
+                (default_marker(), Some(rejection.to_string()))
+            }
+        };
+        self.game_over_menu.set_badge(
+            &self.wgpu_renderer.device,
+            &self.wgpu_renderer.queue,
+            &marker,
+        );
+        self.pause_menu.set_marker_note(note.as_deref());
+    }
+
+    /// Applies a quick-settings radial action and saves the settings.
+    ///
+    /// The reticle and ambience toggles remember what they switched off, so turning them
+    /// back on restores the player's own style and volume. The compass, minimap, music, and
+    /// crouch lock toggles only last for the session, and a screenshot is taken of the next
+    /// frame.
+    ///
+    /// # Arguments
+    /// - `action`: The action bound to the slot that was released on.
+    ///
+    /// # Returns
+    /// The confirmation line to show, such as "Reticle off", or `None` for a screenshot,
+    /// which posts a toast once it is saved instead of showing a line it would capture.
+    pub fn apply_quick_action(&mut self, action: QuickAction) -> Option<String> {
+        let on_off = |on: bool| if on { "on" } else { "off" };
+        let confirmation = match action {
+            QuickAction::Reticle => {
+                let reticle = &mut self.settings.reticle;
+                if reticle.style == ReticleStyle::Off {
+                    reticle.style = self.hidden_reticle_style;
+                } else {
+                    self.hidden_reticle_style = reticle.style;
+                    reticle.style = ReticleStyle::Off;
+                }
+                self.wgpu_renderer.reticle = *reticle;
+                format!("Reticle {}", on_off(reticle.style != ReticleStyle::Off))
+            }
+            QuickAction::CompassMode => {
+                let mode = self.game_state.compass_mode.next();
+                self.game_state.compass_mode = mode;
+                return Some(format!("Compass points {}", mode.label()));
+            }
+            QuickAction::Minimap => {
+                let minimap = &mut self.wgpu_renderer.minimap_renderer;
+                minimap.visible = !minimap.visible;
+                return Some(format!("Minimap {}", on_off(minimap.visible)));
+            }
+            QuickAction::CrouchLock => {
+                // Not a saved setting: it only lasts until the level ends
+                self.game_state.crouch_locked = !self.game_state.crouch_locked;
+                return Some(format!(
+                    "Crouch lock {}",
+                    on_off(self.game_state.crouch_locked)
+                ));
+            }
+            QuickAction::MusicMute => {
+                let audio = &mut self.game_state.audio_manager;
+                audio.set_music_muted(!audio.is_music_muted());
+                return Some(format!("Music {}", on_off(!audio.is_music_muted())));
+            }
+            QuickAction::Screenshot => {
+                if !self.wgpu_renderer.can_screenshot() {
+                    return Some("Screenshots aren't available".to_string());
+                }
+                self.wgpu_renderer.screenshot_requested = true;
+                return None;
+            }
+            QuickAction::Ambience => {
+                let volume = &mut self.settings.ambience_volume;
+                if *volume > 0.0 {
+                    self.muted_ambience_volume = *volume;
+                    *volume = 0.0;
+                } else {
+                    *volume = self.muted_ambience_volume;
+                }
+                self.game_state.audio_manager.set_ambience_volume(*volume);
+                self.pause_menu.update_ambience_button_text(*volume);
+                format!("Ambience {}", on_off(*volume > 0.0))
+            }
+            QuickAction::Background => {
+                self.settings.background = self.settings.background.next();
+                self.wgpu_renderer.background = self.settings.background;
+                self.pause_menu
+                    .update_background_button_text(self.settings.background);
+                format!("Background: {}", self.settings.background.label())
+            }
+            QuickAction::ClickToMove => {
+                self.settings.click_to_move = !self.settings.click_to_move;
+                if !self.settings.click_to_move {
+                    self.game_state.auto_walk.cancel();
+                }
+                format!("Click to move {}", on_off(self.settings.click_to_move))
+            }
+            QuickAction::ReduceEffects => {
+                self.settings.reduce_effects = !self.settings.reduce_effects;
+                self.wgpu_renderer.reduce_effects = self.settings.reduce_effects;
+                format!("Reduce effects {}", on_off(self.settings.reduce_effects))
+            }
+            QuickAction::Announcements => {
+                self.settings.announcements = !self.settings.announcements;
+                format!("Announcements {}", on_off(self.settings.announcements))
+            }
+        };
+        if let Err(e) = self.settings.save() {
+            eprintln!("{}", e);
+        }
+        Some(confirmation)
+    }
+
+    /// Closes the quick-settings radial and applies the slot it was released on.
+    ///
+    /// Does nothing else when the pointer was still in the dead zone.
+    pub fn release_quick_radial(&mut self) {
+        let Some(action) = self.game_state.quick_radial.release() else {
+            return;
+        };
+        let confirmation = self.apply_quick_action(action);
+        let _ = self.game_state.audio_manager.play_select();
+        if let Some(confirmation) = confirmation {
+            self.game_state.quick_radial.confirm(confirmation);
+        }
+    }
+
+    /// Makes one part of the running game follow the settings again.
+    ///
+    /// The graphics backend is the exception: it is only read at launch.
+    ///
+    /// # Arguments
+    /// - `hook`: The part of the game to update.
+    pub fn apply_settings_hook(&mut self, hook: ApplyHook) {
+        match hook {
+            ApplyHook::FrameRate => self.pause_menu.update_fps_cap_button_text(
+                self.settings.menu_fps_cap,
+                self.settings.gameplay_fps_cap,
+            ),
+            ApplyHook::Audio => {
+                let audio = &mut self.game_state.audio_manager;
+                audio.set_enhanced_audio(self.settings.enhanced_audio);
+                audio.set_ambience_volume(self.settings.ambience_volume);
+                audio.set_spatial_profile(self.settings.audio_output, self.settings.hrtf);
+                self.pause_menu
+                    .update_ambience_button_text(self.settings.ambience_volume);
+                self.pause_menu.update_spatial_audio_buttons(
+                    self.settings.audio_output,
+                    self.settings.hrtf,
+                    &self.capabilities,
+                );
+            }
+            ApplyHook::Renderer => {
+                self.wgpu_renderer.background = self.settings.background;
+                self.wgpu_renderer.reticle = self.settings.reticle;
+                self.wgpu_renderer.reduce_effects = self.settings.reduce_effects;
+                self.wgpu_renderer.time_of_day = self.settings.time_of_day;
+                self.apply_present_mode();
+                self.wgpu_renderer
+                    .gpu_memory
+                    .set_budget(self.settings.gpu_memory_budget_mb as u64 * BYTES_PER_MB);
+                self.pause_menu
+                    .update_background_button_text(self.settings.background);
+                self.pause_menu
+                    .update_time_of_day_button_text(self.settings.time_of_day);
+            }
+            ApplyHook::Layout => {
+                self.wgpu_renderer.hud_max_aspect = self.settings.hud_max_aspect;
+                self.wgpu_renderer.view_fit = self.settings.view_fit;
+                let config = &self.wgpu_renderer.surface_config;
+                self.resize_surface(config.width, config.height);
+            }
+            ApplyHook::Gameplay => {
+                self.game_state
+                    .director
+                    .set_enabled(self.settings.adaptive_difficulty);
+                self.game_state.navigation_hints = self.settings.navigation_hints;
+                if !self.settings.click_to_move {
+                    self.game_state.auto_walk.cancel();
+                }
+            }
+            ApplyHook::Cosmetics => self.apply_cosmetics(),
+        }
+    }
+
+    /// Puts one setting back to its default, applies it, and saves the settings.
+    ///
+    /// Does nothing for a setting already at its default.
+    ///
+    /// # Arguments
+    /// - `id`: The setting's ID in the settings registry.
+    pub fn reset_setting(&mut self, id: &str) {
+        let Some(setting) = settings_registry::find(id) else {
+            eprintln!("No setting named {}", id);
+            return;
+        };
+        // Nothing to apply or save for a setting that was never changed
+        if setting.is_default(&self.settings) {
+            return;
+        }
+        let hook = setting.reset(&mut self.settings);
+        self.apply_settings_hook(hook);
+        if let Err(e) = self.settings.save() {
+            eprintln!("{}", e);
+        }
+    }
+
+    /// Puts every setting back to its default, applies them, and saves the settings.
+    pub fn restore_default_settings(&mut self) {
+        for hook in settings_registry::restore_defaults(&mut self.settings) {
+            self.apply_settings_hook(hook);
+        }
+        if let Err(e) = self.settings.save() {
+            eprintln!("{}", e);
+        }
+    }
+
+    /// Handles a click during play with click-to-move turned on.
+    ///
+    /// The mouse is captured while playing, so the click aims where the
+    /// reticle is, at the center of the 3D view.
+    pub fn click_to_move(&mut self) {
+        let view = self.wgpu_renderer.view_area();
+        let reticle = [view.center_x(), view.y + view.height / 2.0];
+        let ndc = auto_walk::screen_to_ndc(reticle, &view);
+        let view_proj = self
+            .game_state
+            .map_view
+            .view_matrix(&self.game_state.player)
+            .multiply(&camera_projection(
+                self.game_state.player.fov,
+                view.aspect(),
+            ));
+        self.game_state.request_auto_walk(ndc, &view_proj);
+    }
+
+    /// Leaves the title screen for the loading screen of a new run.
+    ///
+    /// Switches to game audio, starts a new run in the journal, picks the run
+    /// seed, captures the mouse, and hides the title overlays.
+    pub fn leave_title_screen(&mut self) {
+        // Set game audio volumes before leaving title screen
+        self.game_state
+            .audio_manager
+            .set_game_volumes()
+            .expect("Failed to set game volumes");
+        self.game_state.current_screen = CurrentScreen::Loading;
+        self.game_state.journal.begin_run();
+        // Regular runs generate level 1 from the run seed; daily runs already did
+        if self.game_state.daily.is_none() {
+            self.game_state.begin_run_seed();
+            if self.game_state.hardcore {
+                self.game_state.game_ui.mode_label = Some(hardcore::HARDCORE_LABEL);
+            }
+            if let Some(challenge) = &self.game_state.challenge {
+                challenge.apply_to_player(&mut self.game_state.player);
+                self.game_state.game_ui.mode_label = Some(CHALLENGE_LABEL);
+            }
+            self.wgpu_renderer.loading_screen_renderer = LoadingRenderer::for_plan(
+                &self.wgpu_renderer.device,
+                &self.wgpu_renderer.surface_config,
+                &self.wgpu_renderer.gpu_memory,
+                self.game_state.plan_level(1),
+            );
+        }
+        self.game_state.capture_mouse = true;
+        self.title_menu.end_seed_entry();
+        self.set_title_overlays_visible(false);
+    }
+
+    /// Leaves the title screen to resume the saved run at the start of its saved level.
+    ///
+    /// A save that can no longer be resumed is deleted, with a notice saying why, and the
+    /// player stays on the title screen.
+    pub fn continue_saved_run(&mut self) {
+        let Some(saved) = self.saved_game.take() else {
+            return;
+        };
+        if let Err(e) = saved.restore(
+            &mut self.game_state,
+            &mut self.upgrade_menu.upgrade_manager.player_upgrades,
+        ) {
+            self.discard_saved_game(&e);
+            return;
+        }
+        println!(
+            "Continuing saved run at level {} with {} pts",
+            saved.level,
+            group_digits(saved.score.into())
+        );
+
+        self.game_state
+            .audio_manager
+            .set_game_volumes()
+            .expect("Failed to set game volumes");
+        self.game_state.current_screen = CurrentScreen::Loading;
+        self.game_state.journal.begin_run();
+        self.game_state.daily = None;
+        self.game_state.set_dimensions(
+            self.upgrade_menu
+                .upgraded_dimensions(self.game_state.is_test_mode),
+        );
+        if self.game_state.hardcore {
+            self.game_state.game_ui.mode_label = Some(hardcore::HARDCORE_LABEL);
+        }
+        if self.game_state.challenge.is_some() {
+            self.game_state.game_ui.mode_label = Some(CHALLENGE_LABEL);
+        }
+        self.wgpu_renderer.loading_screen_renderer = LoadingRenderer::for_plan(
+            &self.wgpu_renderer.device,
+            &self.wgpu_renderer.surface_config,
+            &self.wgpu_renderer.gpu_memory,
+            self.game_state.plan_level(saved.level),
+        );
+        self.saved_game = Some(saved);
+        self.game_state.capture_mouse = true;
+        self.title_menu.end_seed_entry();
+        self.set_title_overlays_visible(false);
+    }
+
+    /// Saves the run at the start of the level that just loaded, for the Continue button.
+    ///
+    /// Daily challenges, the sandbox and test mode aren't saved: a daily attempt only counts
+    /// once, and the other two aren't runs. A failed save is logged and leaves the previous
+    /// one in place.
+    pub fn save_run(&mut self) {
+        if self.game_state.daily.is_some()
+            || self.game_state.sandbox.is_some()
+            || self.game_state.is_test_mode
+        {
+            return;
+        }
+        match save::save_state(
+            &self.game_state,
+            &self.upgrade_menu.upgrade_manager.player_upgrades,
+            &paths::get().saved_game(),
+        ) {
+            Ok(saved) => self.saved_game = Some(saved),
+            Err(e) => eprintln!("Failed to save the run: {}", e),
+        }
+    }
+
+    /// Deletes the saved run once it can't be continued, after a hardcore death or a
+    /// finished challenge.
+    pub fn forget_saved_run(&mut self) {
+        self.saved_game = None;
+        if let Err(e) = save::delete_state(&paths::get().saved_game()) {
+            eprintln!("Failed to delete the saved run: {}", e);
+        }
+    }
+
+    /// Deletes a saved run that can't be resumed and says why on the title screen.
+    ///
+    /// # Arguments
+    /// - `error`: Why the run can't be resumed.
+    fn discard_saved_game(&mut self, error: &SaveError) {
+        eprintln!("{}", error);
+        self.toasts.sender().system(error.to_string());
+        self.forget_saved_run();
+    }
+
+    /// Returns to the title screen with a fresh game state.
+    ///
+    /// The seed and mode chosen on the title screen are kept for the next run.
+    pub fn return_to_title(&mut self) {
+        let (chosen_seed, hardcore) = (self.game_state.chosen_seed, self.game_state.hardcore);
+        let challenge = self.game_state.challenge.take();
+        self.game_state = GameState::new();
+        self.game_state.chosen_seed = chosen_seed;
+        self.game_state.hardcore = hardcore;
+        self.game_state.challenge = challenge;
+        self.game_state.current_screen = CurrentScreen::Title;
+        // Reset loading screen renderer to ensure new maze generation
+        self.wgpu_renderer.loading_screen_renderer = LoadingRenderer::new(
+            &self.wgpu_renderer.device,
+            &self.wgpu_renderer.surface_config,
+            &self.wgpu_renderer.gpu_memory,
+        );
+        self.game_state
+            .audio_manager
+            .set_title_screen_volumes()
+            .expect("Failed to set title screen volumes");
+        self.set_title_overlays_visible(true);
+    }
+
+    /// Pauses the game and opens the pause menu.
+    ///
+    /// The timer stops (the update loop locks the enemy while it is stopped), the cursor is
+    /// freed, and the pause menu's audio volumes are set.
+    pub fn pause_game(&mut self) {
+        self.game_state.previous_screen = Some(CurrentScreen::Game);
+        self.game_state.current_screen = CurrentScreen::Pause;
+        // Pause timer (enemy locking is handled in update loop)
+        self.game_state.game_ui.pause_timer(PauseSource::Menu);
+        // Unlock cursor
+        self.game_state.capture_mouse = false;
+        // Show pause menu with current test mode state
+        self.pause_menu.show(self.game_state.is_test_mode);
+        self.game_state
+            .audio_manager
+            .set_pause_menu_volumes()
+            .expect("Failed to set pause menu volumes");
+    }
+
+    /// Closes the pause menu and returns to the screen it was opened from, or to the game.
+    pub fn resume_from_pause(&mut self) {
+        if let Some(previous_screen) = self.game_state.previous_screen {
+            self.game_state.current_screen = previous_screen;
+            self.game_state.previous_screen = None;
+
+            match previous_screen {
+                CurrentScreen::Game => {
+                    // Resume game
+                    if !self.game_state.is_test_mode {
+                        // Only resume timer in normal mode (enemy locking is handled in update loop)
+                        self.game_state.game_ui.resume_timer(PauseSource::Menu);
+                    }
+                    // In test mode, keep timer paused (enemy locking is handled in update loop)
+                    // Lock cursor
+                    self.game_state.capture_mouse = true;
+                    // Restore game audio volumes
+                    self.game_state
+                        .audio_manager
+                        .set_game_volumes()
+                        .expect("Failed to set game volumes");
+                }
+                CurrentScreen::Title => {
+                    // Return to title screen - cursor should be unlocked
+                    self.game_state.capture_mouse = false;
+                    // Set title screen audio volumes
+                    self.game_state
+                        .audio_manager
+                        .set_title_screen_volumes()
+                        .expect("Failed to set title screen volumes");
+                }
+                _ => {
+                    // For other screens, just unlock cursor
+                    self.game_state.capture_mouse = false;
+                }
+            }
+        } else {
+            // Fallback: return to game
+            self.game_state.current_screen = CurrentScreen::Game;
+            if !self.game_state.is_test_mode {
+                // Only resume timer in normal mode (enemy locking is handled in update loop)
+                self.game_state.game_ui.resume_timer(PauseSource::Menu);
+            }
+            // In test mode, keep timer paused (enemy locking is handled in update loop)
+            self.game_state.capture_mouse = true;
+            // Restore game audio volumes
+            self.game_state
+                .audio_manager
+                .set_game_volumes()
+                .expect("Failed to set game volumes");
+        }
+        self.pause_menu.hide();
+    }
+
+    /// Leaves the game over screen for a new run from level one.
+    pub fn start_new_run(&mut self) {
+        self.game_state.current_screen = CurrentScreen::NewGame;
+        self.game_over_menu.hide();
+        self.game_state.capture_mouse = true;
+    }
+
+    /// Opens the profile picker with every profile's stats.
+    pub fn open_profile_menu(&mut self) {
+        let paths = paths::get();
+        let summaries = profiles::profile_names(&paths)
+            .iter()
+            .map(|name| ProfileSummary::load(&paths, name))
+            .collect();
+        self.title_menu.end_seed_entry();
+        self.profile_menu.open(summaries, paths.profile());
+        self.game_state.current_screen = CurrentScreen::Profiles;
+    }
+
+    /// Lists the profiles again on the profile picker after one changed.
+    fn refresh_profile_menu(&mut self) {
+        let paths = paths::get();
+        let summaries = profiles::profile_names(&paths)
+            .iter()
+            .map(|name| ProfileSummary::load(&paths, name))
+            .collect();
+        self.profile_menu.refresh(summaries, paths.profile());
+    }
+
+    /// Carries out what the player asked for on the profile picker.
+    ///
+    /// Failures are shown on the picker, which stays open so they can be fixed.
+    ///
+    /// # Arguments
+    /// - `action`: The action taken on the profile picker.
+    pub fn apply_profile_action(&mut self, action: ProfileAction) {
+        let paths = paths::get();
+        let result = match action {
+            ProfileAction::None => return,
+            ProfileAction::Back => {
+                if paths.profile().is_some() {
+                    self.profile_menu.hide();
+                    self.game_state.current_screen = CurrentScreen::Title;
+                }
+                return;
+            }
+            ProfileAction::Switch(name) => {
+                self.switch_profile(&name);
+                self.profile_menu.hide();
+                self.game_state.current_screen = CurrentScreen::Title;
+                return;
+            }
+            ProfileAction::Create(name) => {
+                profiles::create_profile(&paths, &name).map(|name| {
+                    // The first profile on a new machine is played straight away
+                    if paths.profile().is_none() {
+                        self.switch_profile(&name);
+                    }
+                    self.refresh_profile_menu();
+                    self.profile_menu.select(&name);
+                    format!("Made the {} profile", name)
+                })
+            }
+            ProfileAction::Rename { from, to } => {
+                profiles::rename_profile(&paths, &from, &to).map(|name| {
+                    if paths.profile() == Some(from.as_str()) {
+                        paths::set_profile(Some(&name));
+                        self.game_state.journal.select_profile(&name);
+                    }
+                    self.refresh_profile_menu();
+                    self.profile_menu.select(&name);
+                    format!("Renamed {} to {}", from, name)
+                })
+            }
+            ProfileAction::Delete { name, confirmation } => {
+                profiles::delete_profile(&paths, &name, &confirmation).map(|()| {
+                    self.refresh_profile_menu();
+                    format!("Deleted the {} profile", name)
+                })
+            }
+        };
+        match result {
+            Ok(message) => {
+                println!("{}", message);
+                self.profile_menu.end_entry();
+                self.profile_menu.set_message(&message, false);
+            }
+            Err(e) => {
+                eprintln!("{}", e);
+                self.profile_menu.set_message(&e, true);
+            }
+        }
+    }
+
+    /// Plays as another profile from now on.
+    ///
+    /// Loads the profile's settings, results, graveyard, saved run, key bindings and codex in
+    /// place of the old one's, makes the running game follow the new settings, and remembers
+    /// the profile for the next launch.
+    ///
+    /// # Arguments
+    /// - `name`: The profile's name, as listed by [`profiles::profile_names`].
+    pub fn switch_profile(&mut self, name: &str) {
+        paths::set_profile(Some(name));
+        if let Err(e) = profiles::save_active_profile(&paths::get(), name) {
+            eprintln!("{}", e);
+        }
+        self.settings = Settings::load();
+        self.daily_results = DailyResults::load();
+        self.graveyard = Graveyard::load();
+        self.saved_game = load_saved_game();
+        self.input_router = InputRouter::load();
+        self.pause_menu.update_binding_buttons(&self.input_router);
+        self.codex = Codex::load();
+        for hook in ApplyHook::ALL {
+            self.apply_settings_hook(hook);
+        }
+        // A seed picked for a hardcore run may be one this profile already burned
+        if self.game_state.hardcore
+            && self
+                .game_state
+                .chosen_seed
+                .is_some_and(|seed| self.graveyard.is_burned(seed))
+        {
+            self.game_state.chosen_seed = None;
+            self.game_state.challenge = None;
+        }
+        self.game_state.journal.select_profile(name);
+        println!("Playing as {}", name);
+    }
+
+    /// Shows the error screen after the frame update panicked.
+    ///
+    /// An unfinished daily attempt is recorded as abandoned, so a panic can't be used to
+    /// replay the day. Every other menu is hidden and the cursor is freed for the error
+    /// screen's button.
+    ///
+    /// # Arguments
+    /// - `window`: The application window, for freeing the cursor.
+    /// - `message`: The message of the panic that was caught.
+    pub fn show_error_screen(&mut self, window: &Window, message: &str) {
+        self.abandon_daily_attempt();
+        self.game_state.game_ui.stop_timer();
+        let _ = self.game_state.pause_enemy_audio();
+        let _ = self.game_state.audio_manager.set_title_screen_volumes();
+
+        self.pause_menu.hide();
+        self.upgrade_menu.hide();
+        self.game_over_menu.hide();
+        self.text_renderer.hide_game_over_display();
+        self.set_title_overlays_visible(false);
+
+        self.game_state.current_screen = CurrentScreen::Error;
+        self.game_state.previous_screen = None;
+        self.game_state.capture_mouse = false;
+        self.triage_mouse(window);
+        self.error_menu.show(message);
+    }
+
+    /// Leaves the error screen for the title screen with a fresh game state.
+    pub fn leave_error_screen(&mut self) {
+        self.error_menu.hide();
+        self.return_to_title();
+    }
+
+    /// Shows or hides the title and subtitle text of the title screen.
+    ///
+    /// # Arguments
+    /// - `visible`: Whether the title overlays should be drawn.
+    fn set_title_overlays_visible(&mut self, visible: bool) {
+        for id in ["title_mirador_overlay", "title_subtitle_overlay"] {
+            if let Err(e) = self.text_renderer.set_buffer_visibility(id, visible) {
+                eprintln!("Failed to update {} visibility: {}", id, e);
+            }
+        }
+    }
+
+    /// Opens the practice sandbox from the title screen.
+    ///
+    /// Sets the run aside, so nothing played in the sandbox reaches the next run, and
+    /// starts generating the first maze from the title screen's seed, or a random one.
+    pub fn enter_sandbox(&mut self) {
+        let settings = SandboxSettings {
+            seed: self.game_state.chosen_seed.unwrap_or_else(rand::random),
+            ..SandboxSettings::default()
+        };
+        self.game_state.sandbox = Some(SandboxSession::new(settings, &self.game_state));
+        self.game_state
+            .audio_manager
+            .set_game_volumes()
+            .expect("Failed to set game volumes");
+        self.game_state.capture_mouse = true;
+        self.title_menu.end_seed_entry();
+        self.set_title_overlays_visible(false);
+        self.generate_sandbox_maze();
+    }
+
+    /// Starts generating a sandbox maze from the settings the sandbox last generated with.
+    ///
+    /// Clears the level the same way a new level does, but with a fresh player, no timer,
+    /// and nothing that would be recorded in the codex.
+    pub fn generate_sandbox_maze(&mut self) {
+        let Some(settings) = self
+            .game_state
+            .sandbox
+            .as_ref()
+            .map(|session| session.generated)
+        else {
+            return;
+        };
+        println!(
+            "Generating a {}x{} {} sandbox maze (seed {})",
+            settings.width,
+            settings.height,
+            settings.algorithm.name(),
+            seed::format_seed(settings.seed)
+        );
+
+        let mut loading_screen_renderer = LoadingRenderer::new_sized(
+            &self.wgpu_renderer.device,
+            &self.wgpu_renderer.surface_config,
+            &self.wgpu_renderer.gpu_memory,
+            &MazeConfig {
+                width: settings.width,
+                height: settings.height,
+                seed: Some(settings.seed),
+            },
+        );
+        match settings.algorithm {
+            MazeAlgorithm::Kruskal => {}
+            MazeAlgorithm::StackedKruskal => {
+                loading_screen_renderer = loading_screen_renderer.with_upper_floor();
+            }
+            MazeAlgorithm::Divided => {
+                loading_screen_renderer = loading_screen_renderer.with_divided_regions();
+            }
+        }
+        self.wgpu_renderer.loading_screen_renderer = loading_screen_renderer;
+        self.codex_level = None;
+
+        // The sandbox's own settings override the maze the level would have been planned with
+        let mut plan = levels::plan_level(
+            self.game_state.game_ui.level,
+            Difficulty::Normal,
+            settings.seed,
+            self.game_state.enemy_tuning(),
+            &self.game_state.difficulty_curve,
+        );
+        plan.maze_width = settings.width;
+        plan.maze_height = settings.height;
+        plan.multi_floor = settings.algorithm == MazeAlgorithm::StackedKruskal;
+        plan.anomaly = !plan.multi_floor && plan.level >= levels::ANOMALY_MIN_LEVEL;
+        self.game_state.set_level_plan(plan);
+
+        let game_state = &mut self.game_state;
+        game_state.current_screen = CurrentScreen::Loading;
+        game_state.maze_path = None;
+        game_state.level_ready = false;
+        game_state.clear_level_snapshot();
+        game_state.interactables.clear();
+        game_state.interaction_target = None;
+        game_state.player = Player::new();
+        game_state.enemy = Enemy::new([0.0, 30.0, 0.0], 150.0);
+        game_state.clear_pack();
+        game_state.exit_cell = None;
+        game_state.exit_reached_timer = 0.0;
+        game_state.game_ui.timer = None;
+        game_state.game_ui.mode_label = Some(SANDBOX_LABEL);
+    }
+
+    /// Closes the practice sandbox and returns to the title screen.
+    ///
+    /// Puts back the run the sandbox set aside.
+    pub fn leave_sandbox(&mut self) {
+        let Some(session) = self.game_state.sandbox.take() else {
+            return;
+        };
+        session.saved_run.restore(&mut self.game_state);
+        self.game_state.game_ui.timer = None;
+        self.game_state.clear_level_snapshot();
+        self.game_state.current_screen = CurrentScreen::Title;
+        self.game_state.capture_mouse = false;
+        self.key_state.clear();
+        self.game_state.clear_pack();
+        let audio = &mut self.game_state.audio_manager;
+        audio
+            .pause_enemy_audio("enemy")
+            .expect("Failed to pause enemy audio");
+        audio
+            .stop_movement()
+            .expect("Failed to stop movement audio");
+        audio
+            .set_title_screen_volumes()
+            .expect("Failed to set title screen volumes");
+        self.set_title_overlays_visible(true);
+    }
+
+    /// Carries out an action from the sandbox panel.
+    ///
+    /// # Arguments
+    /// - `action`: The action taken on the panel.
+    /// - `window`: The application window, whose cursor follows the panel's focus.
+    pub fn apply_sandbox_action(&mut self, action: SandboxAction, window: &Window) {
+        match action {
+            SandboxAction::Generate => {
+                if let Some(session) = self.game_state.sandbox.as_mut() {
+                    session.generated = session.settings;
+                }
+                self.generate_sandbox_maze();
+            }
+            SandboxAction::UseSeed => {
+                if let Some(session) = &self.game_state.sandbox {
+                    let chosen = session.settings.seed;
+                    self.game_state.chosen_seed = Some(chosen);
+                    println!("The next run will use seed {}", seed::format_seed(chosen));
+                }
+            }
+            SandboxAction::Leave => self.leave_sandbox(),
+            SandboxAction::FocusChanged => {
+                let look = self
+                    .game_state
+                    .sandbox
+                    .as_ref()
+                    .is_some_and(|session| session.focus == SandboxFocus::Look);
+                self.game_state.capture_mouse = look;
+                // Keys held when the panel took over would never see their release
+                self.key_state.clear();
+            }
+            SandboxAction::None => return,
+        }
+        self.triage_mouse(window);
+    }
+
+    /// Starts today's daily challenge from the title screen.
+    ///
+    /// The run is practice if today already has a completed result, or if the
+    /// system clock reads earlier than the newest recorded day.
+    pub fn start_daily_run(&mut self) {
+        let today = daily::utc_today();
+        let run = DailyRun {
+            challenge: DailyChallenge::for_date(today),
+            practice: !self.daily_results.can_record(today),
+        };
+        println!(
+            "Starting daily challenge {} (seed {}, modifiers: {}){}",
+            daily::date_key(today),
+            run.challenge.seed,
+            run.challenge.modifier_names(),
+            if run.practice { " as practice" } else { "" }
+        );
+
+        // Level 1 of the day's maze and a player with the day's modifiers
+        let mut player = crate::game::player::Player::new();
+        run.challenge.apply_to_player(&mut player);
+        self.game_state.player = player;
+        self.game_state.game_ui.mode_label = Some(run.hud_label());
+        self.game_state.daily = Some(run);
+        self.wgpu_renderer.loading_screen_renderer = LoadingRenderer::for_plan(
+            &self.wgpu_renderer.device,
+            &self.wgpu_renderer.surface_config,
+            &self.wgpu_renderer.gpu_memory,
+            self.game_state.plan_level(1),
+        );
+    }
+
+    /// Ends the current daily challenge attempt with a game over.
+    ///
+    /// Records the result unless the run is practice, after which every further
+    /// attempt that day is practice, then shows the day's result and history.
+    ///
+    /// # Arguments
+    /// - `window`: The application window, used to position the history.
+    pub fn finish_daily_attempt(&mut self, window: &Window) {
+        let Some(run) = self.game_state.daily.as_mut() else {
+            return;
+        };
+        let (score, level) = (self.game_state.game_ui.score, self.game_state.game_ui.level);
+        let date = run.challenge.date;
+
+        let mut summary = String::new();
+        if run.practice {
+            summary.push_str(&format!(
+                "Practice run: {} pts, level {} (not recorded)\n",
+                group_digits(score.into()),
+                level
+            ));
+        } else {
+            save_daily_result(
+                &mut self.daily_results,
+                DailyResult::completed(date, score, level),
+            );
+            run.practice = true;
+            self.game_state.game_ui.mode_label = Some(run.hud_label());
+        }
+        summary.push_str(&format!("Modifiers: {}\n", run.challenge.modifier_names()));
+        summary.push_str(&self.daily_results.summary_lines(date));
+        show_daily_history(&mut self.text_renderer, window, &summary);
+    }
+
+    /// Refuses to start a hardcore run on a seed a hardcore death has burned.
+    ///
+    /// A refused seed is explained in a notice on the title screen, which is cleared again
+    /// by the next check. Regular runs may play any seed.
+    ///
+    /// # Arguments
+    /// - `window`: The application window, used to position the notice.
+    /// - `seed`: The seed the run would start with, or `None` for a random one.
+    ///
+    /// # Returns
+    /// `true` if the run must not start with this seed.
+    pub fn refuse_burned_seed(&mut self, window: &Window, seed: Option<u64>) -> bool {
+        let _ = self
+            .text_renderer
+            .set_buffer_visibility(HARDCORE_NOTICE_ID, false);
+        let Some(seed) = seed.filter(|_| self.game_state.hardcore) else {
+            return false;
+        };
+        match self.graveyard.check_seed(seed) {
+            Ok(()) => false,
+            Err(message) => {
+                println!(
+                    "Refused hardcore seed {}: {}",
+                    seed::format_seed(seed),
+                    message
+                );
+                show_hardcore_notice(
+                    &mut self.text_renderer,
+                    window,
+                    &format!("{}. Pick another seed or turn hardcore off.", message),
+                );
+                true
+            }
+        }
+    }
+
+    /// Shows what the entered challenge code contains, or why it was refused, on the title
+    /// screen.
+    ///
+    /// Called every frame on the title screen; the notice is only rebuilt when it changes.
+    pub fn refresh_challenge_notice(&mut self) {
+        let notice = self
+            .title_menu
+            .code_notice(self.game_state.challenge.as_ref());
+        let shown = self
+            .text_renderer
+            .text_buffers
+            .get(CHALLENGE_NOTICE_ID)
+            .filter(|buffer| buffer.visible)
+            .map(|buffer| buffer.text_content.as_str());
+        match notice {
+            Some((message, _)) if shown == Some(message.as_str()) => {}
+            Some((message, refused)) => {
+                let config = &self.wgpu_renderer.surface_config;
+                let window_size = PhysicalSize::new(config.width, config.height);
+                show_challenge_notice(&mut self.text_renderer, window_size, &message, refused);
+            }
+            None if shown.is_some() => {
+                let _ = self
+                    .text_renderer
+                    .set_buffer_visibility(CHALLENGE_NOTICE_ID, false);
+            }
+            None => {}
+        }
+    }
+
+    /// Ends the current run on the game over screen, burying its seed if it was hardcore.
+    ///
+    /// A hardcore death saves the seed and the run's score to the graveyard, forgets the
+    /// seed if it was the one chosen on the title screen, and drops the daily challenge so
+    /// a new run starts on a fresh seed. The game over screen then has no retry button and
+    /// shows the graveyard instead. Test mode runs aren't buried.
+    pub fn finish_hardcore_run(&mut self) {
+        let hardcore = self.game_state.hardcore && !self.game_state.is_test_mode;
+        self.game_over_menu.set_hardcore(hardcore);
+        if !hardcore {
+            let _ = self
+                .text_renderer
+                .update_text("game_over_restart", GAME_OVER_SUBTITLE);
+            return;
+        }
+
+        let seed = self.game_state.burnable_seed();
+        self.graveyard.bury(Grave {
+            seed,
+            level: self.game_state.game_ui.level,
+            score: self.game_state.game_ui.score,
+        });
+        if let Err(e) = self.graveyard.save() {
+            eprintln!("{}", e);
+        }
+        if self.game_state.chosen_seed == Some(seed) {
+            self.game_state.chosen_seed = None;
+            self.game_state.challenge = None;
+        }
+        self.game_state.daily = None;
+        self.game_state.game_ui.mode_label = Some(hardcore::HARDCORE_LABEL);
+        self.forget_saved_run();
+
+        let subtitle = format!(
+            "Seed buried. {} in the graveyard. Best: {} pts, level {}.",
+            self.graveyard.count(),
+            group_digits(self.graveyard.best_score.into()),
+            self.graveyard.best_level
+        );
+        let _ = self
+            .text_renderer
+            .update_text("game_over_restart", &subtitle);
+    }
+
+    /// Ends a challenge run on the game over screen after its last level.
+    ///
+    /// The last level's points are added to the score and the seed is never buried,
+    /// even in hardcore, since the run was won rather than lost.
+    ///
+    /// # Arguments
+    /// - `window`: The application window, for freeing the cursor.
+    pub fn complete_challenge(&mut self, window: &Window) {
+        self.game_state.apply_level_score();
+        self.game_state.stop_game_timer();
+        self.forget_saved_run();
+        self.game_state.current_screen = CurrentScreen::GameOver;
+        self.game_over_menu.set_hardcore(self.game_state.hardcore);
+        let subtitle = format!(
+            "Challenge complete! Level {} with {} pts.",
+            self.game_state.game_ui.level,
+            group_digits(self.game_state.game_ui.score.into())
+        );
+        let _ = self
+            .text_renderer
+            .update_text("game_over_restart", &subtitle);
+        // Free the cursor for the game over buttons
+        self.game_state.capture_mouse = false;
+        self.triage_mouse(window);
+    }
+
+    /// Records the current daily challenge attempt as abandoned.
+    ///
+    /// Called when the player quits to the title screen mid-run. Abandoned results
+    /// show up in the history but don't stop a later attempt from counting.
+    pub fn abandon_daily_attempt(&mut self) {
+        let Some(run) = self.game_state.daily.as_ref().filter(|run| !run.practice) else {
+            return;
+        };
+        save_daily_result(
+            &mut self.daily_results,
+            DailyResult::abandoned(
+                run.challenge.date,
+                self.game_state.game_ui.score,
+                self.game_state.game_ui.level,
+            ),
+        );
+    }
+
+    /// Adds the level that just ended to the maze codex.
+    ///
+    /// When the level is the fastest completion of its kind of maze, the maze is saved as
+    /// the kind's thumbnail. Retried levels count again; test mode levels don't count.
+    ///
+    /// # Arguments
+    /// - `died`: Whether the level ended with the player losing.
+    pub fn record_codex(&mut self, died: bool) {
+        let Some(level) = self.codex_level.as_ref() else {
+            return;
+        };
+        if self.game_state.is_test_mode {
+            return;
+        }
+        let outcome = if died {
+            LevelOutcome::Died
+        } else {
+            LevelOutcome::Completed
+        };
+        let seconds = self.game_state.level_result().completion_time;
+        if self.codex.record(level.kind, outcome, seconds) {
+            let path = paths::get().codex_thumbnail(&level.kind.slug());
+            if let Err(e) = codex::save_thumbnail(&path, &level.thumbnail, level.dimensions) {
+                eprintln!("Failed to save codex thumbnail: {}", e);
+            }
+        }
+        if let Err(e) = self.codex.save() {
+            eprintln!("Failed to save codex: {}", e);
+        }
+    }
+
+    /// Applies the cosmetics the player has switched on and unlocked, and shows them on the
+    /// pause menu's cosmetic buttons.
+    pub fn apply_cosmetics(&mut self) {
+        self.wgpu_renderer.cosmetics = self.codex.unlocks.unlocked_of(&self.settings.cosmetics);
+        let unlocks = &self.codex.unlocks;
+        let cosmetics = &self.wgpu_renderer.cosmetics;
+        self.pause_menu.update_cosmetic_buttons(
+            |cosmetic| unlocks.is_unlocked(cosmetic),
+            |cosmetic| cosmetics.contains(&cosmetic),
+        );
+    }
+
+    /// Takes the anomaly the player is looking at, if any.
+    ///
+    /// A new cosmetic is unlocked, switched on, and saved with the codex; an anomaly whose
+    /// cosmetic is already unlocked is counted toward the level's score instead, which pays
+    /// [`DUPLICATE_BONUS`](crate::game::anomaly::DUPLICATE_BONUS) points for it.
+    pub fn collect_anomaly(&mut self) {
+        let Some(collectible) = self.game_state.collect_anomaly() else {
+            return;
+        };
+        match self.codex.unlocks.unlock(collectible) {
+            Some(Unlock::New(cosmetic)) => {
+                println!("[ANOMALY] Unlocked the {} cosmetic", cosmetic.name());
+                if let Err(e) = self.codex.save() {
+                    eprintln!("Failed to save codex: {}", e);
+                }
+                if !self.settings.cosmetics.contains(&cosmetic) {
+                    self.settings.cosmetics.push(cosmetic);
+                }
+                if let Err(e) = self.settings.save() {
+                    eprintln!("{}", e);
+                }
+                self.apply_cosmetics();
+            }
+            Some(Unlock::Duplicate) => self.game_state.duplicate_anomalies += 1,
+            None => {}
+        }
+        let _ = self.game_state.audio_manager.play_upgrade();
+    }
+
+    /// Records a screen transition in the session journal and flushes it on game over.
+    ///
+    /// The game over screen shows the run's challenge code, which is also copied to the
+    /// clipboard and printed to stdout, and if an error was recorded during the run, where the
+    /// journal was written so it can be attached to a bug report.
+    ///
+    /// # Arguments
+    /// - `window`: The application window, used to size the code and journal notice.
+    pub fn journal_screen_change(&mut self, window: &Window) {
+        let journal = &self.game_state.journal;
+        if !journal.observe_screen(self.game_state.current_screen)
+            || self.game_state.current_screen != CurrentScreen::GameOver
+        {
+            return;
+        }
+
+        if let Some(code) = self.game_state.challenge_code() {
+            println!("Challenge code: {}", code);
+            let copied = self.clipboard.copy(&code);
+            if let Err(e) = &copied {
+                eprintln!("{}", e);
+            }
+            show_challenge_code(&mut self.text_renderer, window, &code, copied.is_ok());
+        }
+        let journal = &self.game_state.journal;
+
+        match journal.flush(FlushReason::GameOver) {
+            Ok(path) => {
+                if journal.had_run_errors() {
+                    show_journal_notice(&mut self.text_renderer, window, &path);
+                }
+            }
+            Err(e) => eprintln!("Failed to write session journal: {}", e),
+        }
+    }
+}
+
+/// Text buffer ID for the journal path shown on the game over screen.
+const JOURNAL_NOTICE_ID: &str = "journal_notice";
+
+/// Shows where the session journal was written along the bottom of the game over screen.
+///
+/// # Arguments
+/// * `text_renderer` - The shared text renderer drawn on every screen
+/// * `window` - The window, used to position the notice
+/// * `path` - The journal file that was just written
+fn show_journal_notice(text_renderer: &mut TextRenderer, window: &Window, path: &Path) {
+    let window_size = window.inner_size();
+    let style = crate::renderer::text::TextStyle {
+        font_family: "Hanken Grotesk".to_string(),
+        font_size: 18.0,
+        line_height: 22.0,
+        color: Color::rgb(255, 200, 120),
+        weight: glyphon::Weight::NORMAL,
+        style: glyphon::Style::Normal,
+    };
+    let position = crate::renderer::text::TextPosition {
+        x: 20.0,
+        y: window_size.height as f32 - 70.0,
+        max_width: Some(window_size.width as f32 - 40.0),
+        max_height: Some(50.0),
+    };
+    text_renderer.create_text_buffer(
+        JOURNAL_NOTICE_ID,
+        &format!(
+            "An error was recorded during this run. Please attach the session journal to your report:\n{}",
+            path.display()
+        ),
+        Some(style),
+        Some(position),
+    );
+}
+
+/// Text buffer ID for the run's challenge code shown on the game over screen.
+const CHALLENGE_CODE_ID: &str = "challenge_code";
+
+/// Shows the code that sets up the run again in the game over screen's top left corner.
+///
+/// # Arguments
+/// * `text_renderer` - The shared text renderer drawn on every screen
+/// * `window` - The window, used to size the label
+/// * `code` - The run's challenge code
+/// * `copied` - Whether the code was copied to the clipboard
+fn show_challenge_code(
+    text_renderer: &mut TextRenderer,
+    window: &Window,
+    code: &str,
+    copied: bool,
+) {
+    let window_size = window.inner_size();
+    let scale = (window_size.height as f32 / 1080.0).clamp(0.7, 2.0);
+    let style = crate::renderer::text::TextStyle {
+        font_family: "Hanken Grotesk".to_string(),
+        font_size: (20.0 * scale).clamp(14.0, 40.0),
+        line_height: (26.0 * scale).clamp(18.0, 52.0),
+        color: Color::rgb(200, 200, 200),
+        weight: glyphon::Weight::MEDIUM,
+        style: glyphon::Style::Normal,
+    };
+    let position = crate::renderer::text::TextPosition {
+        x: 20.0,
+        y: 20.0,
+        max_width: Some(window_size.width as f32 / 2.0),
+        max_height: Some(style.line_height * 2.0),
+    };
+    text_renderer.create_text_buffer(
+        CHALLENGE_CODE_ID,
+        &format!(
+            "Challenge code: {}\n{}",
+            code,
+            if copied {
+                "Copied to the clipboard"
+            } else {
+                "Also printed to the console for copying"
+            }
+        ),
+        Some(style),
+        Some(position),
+    );
+}
+
+/// Text buffer ID for the entered challenge code's summary on the title screen.
+const CHALLENGE_NOTICE_ID: &str = "challenge_notice";
+
+/// Shows the entered challenge code's summary in the title screen's bottom left corner,
+/// above the hardcore notice.
+///
+/// # Arguments
+/// * `text_renderer` - The shared text renderer drawn on every screen
+/// * `window_size` - The window size, used to position the notice
+/// * `message` - The summary, or why the code was refused
+/// * `refused` - Whether the code was refused, which shows the notice in red
+fn show_challenge_notice(
+    text_renderer: &mut TextRenderer,
+    window_size: PhysicalSize<u32>,
+    message: &str,
+    refused: bool,
+) {
+    let style = crate::renderer::text::TextStyle {
+        font_family: "Hanken Grotesk".to_string(),
+        font_size: 18.0,
+        line_height: 22.0,
+        color: if refused {
+            Color::rgb(255, 120, 110)
+        } else {
+            Color::rgb(200, 200, 200)
+        },
+        weight: glyphon::Weight::NORMAL,
+        style: glyphon::Style::Normal,
+    };
+    let position = crate::renderer::text::TextPosition {
+        x: 20.0,
+        y: window_size.height as f32 - 120.0,
+        max_width: Some(window_size.width as f32 / 2.0),
+        max_height: Some(50.0),
+    };
+    text_renderer.create_text_buffer(CHALLENGE_NOTICE_ID, message, Some(style), Some(position));
+}
+
+/// Text buffer ID for the notice explaining a refused hardcore seed on the title screen.
+const HARDCORE_NOTICE_ID: &str = "hardcore_notice";
+
+/// Shows why a hardcore seed was refused in the title screen's bottom left corner.
+///
+/// # Arguments
+/// * `text_renderer` - The shared text renderer drawn on every screen
+/// * `window` - The window, used to position the notice
+/// * `message` - The explanation to show
+fn show_hardcore_notice(text_renderer: &mut TextRenderer, window: &Window, message: &str) {
+    let window_size = window.inner_size();
+    let style = crate::renderer::text::TextStyle {
+        font_family: "Hanken Grotesk".to_string(),
+        font_size: 18.0,
+        line_height: 22.0,
+        color: Color::rgb(255, 120, 110),
+        weight: glyphon::Weight::NORMAL,
+        style: glyphon::Style::Normal,
+    };
+    let position = crate::renderer::text::TextPosition {
+        x: 20.0,
+        y: window_size.height as f32 - 70.0,
+        max_width: Some(window_size.width as f32 / 2.0),
+        max_height: Some(50.0),
+    };
+    text_renderer.create_text_buffer(HARDCORE_NOTICE_ID, message, Some(style), Some(position));
+}
+
+/// Text buffer ID for the daily challenge history shown on the game over screen.
+const DAILY_HISTORY_ID: &str = "daily_history";
+
+/// Loads the saved run offered by the title screen's Continue button.
+///
+/// # Returns
+/// The saved run, or `None` if there is none or it can't be resumed, which is logged
+fn load_saved_game() -> Option<SavedGame> {
+    match save::load_state(&paths::get().saved_game()) {
+        Ok(saved) => Some(saved),
+        Err(SaveError::Missing) => None,
+        Err(e) => {
+            eprintln!("{}", e);
+            None
+        }
+    }
+}
+
+/// Records a daily challenge result and saves the results file.
+///
+/// Refused results (the day is already recorded, or the clock moved back) are
+/// reported and otherwise ignored.
+///
+/// # Arguments
+/// * `results` - The persistent daily results
+/// * `result` - The result to record
+fn save_daily_result(results: &mut DailyResults, result: DailyResult) {
+    if let Err(e) = results.record(result) {
+        eprintln!("{}", e);
+        return;
+    }
+    if let Err(e) = results.save() {
+        eprintln!("{}", e);
+    }
+}
+
+/// Shows the daily challenge result and history below the game over buttons.
+///
+/// # Arguments
+/// * `text_renderer` - The shared text renderer drawn on every screen
+/// * `window` - The window, used to position the history
+/// * `summary` - The lines to show
+fn show_daily_history(text_renderer: &mut TextRenderer, window: &Window, summary: &str) {
+    let window_size = window.inner_size();
+    let (width, height) = (window_size.width as f32, window_size.height as f32);
+    let scale = (height / 1080.0).clamp(0.7, 2.0);
+    let style = crate::renderer::text::TextStyle {
+        font_family: "Hanken Grotesk".to_string(),
+        font_size: (20.0 * scale).clamp(14.0, 40.0),
+        line_height: (26.0 * scale).clamp(18.0, 52.0),
+        color: Color::rgb(220, 220, 220),
+        weight: glyphon::Weight::NORMAL,
+        style: glyphon::Style::Normal,
+    };
+    // Below the game over buttons, which end about 80px + one button height under the center
+    let button_height = (height * 0.07 * scale).clamp(32.0, 110.0);
+    let history_width = 460.0 * scale;
+    let position = crate::renderer::text::TextPosition {
+        x: (width - history_width) / 2.0,
+        y: height / 2.0 + 80.0 * scale + button_height + 30.0 * scale,
+        max_width: Some(history_width),
+        max_height: Some(style.line_height * 11.0),
+    };
+    text_renderer.create_text_buffer(DAILY_HISTORY_ID, summary, Some(style), Some(position));
+}
+
+/// Text buffer ID for the run's seed shown on the loading screen.
+const LOADING_SEED_ID: &str = "loading_seed";
+
+/// Shows the run's seed in the bottom left corner of the loading screen.
+///
+/// # Arguments
+/// * `text_renderer` - The shared text renderer drawn on every screen
+/// * `window` - The window, used to position the label
+/// * `label` - The line naming the seed
+fn show_loading_seed(text_renderer: &mut TextRenderer, window: &Window, label: &str) {
+    let window_size = window.inner_size();
+    let scale = (window_size.height as f32 / 1080.0).clamp(0.7, 2.0);
+    let style = crate::renderer::text::TextStyle {
+        font_family: "Hanken Grotesk".to_string(),
+        font_size: (22.0 * scale).clamp(16.0, 44.0),
+        line_height: (28.0 * scale).clamp(20.0, 56.0),
+        color: Color::rgb(200, 200, 200),
+        weight: glyphon::Weight::MEDIUM,
+        style: glyphon::Style::Normal,
+    };
+    let position = crate::renderer::text::TextPosition {
+        x: 20.0,
+        y: window_size.height as f32 - style.line_height - 20.0,
+        max_width: Some(window_size.width as f32 - 40.0),
+        max_height: Some(style.line_height),
+    };
+    text_renderer.create_text_buffer(LOADING_SEED_ID, label, Some(style), Some(position));
+}
+
+/// Text buffer ID for the broken asset overlay.
+const ASSET_DIAGNOSTICS_ID: &str = "asset_diagnostics";
+
+/// Shows the broken asset overlay in the top left corner.
+///
+/// The overlay is never hidden, so it stays on top of every screen for the rest of the session.
+///
+/// # Arguments
+/// * `text_renderer` - The shared text renderer drawn on every screen
+/// * `window` - The window, used to size the overlay
+/// * `diagnostics` - The list of broken assets to show
+fn show_asset_diagnostics(text_renderer: &mut TextRenderer, window: &Window, diagnostics: &str) {
+    let window_size = window.inner_size();
+    let style = crate::renderer::text::TextStyle {
+        font_family: "Hanken Grotesk".to_string(),
+        font_size: 20.0,
+        line_height: 24.0,
+        color: Color::rgb(255, 0, 255),
+        weight: glyphon::Weight::BOLD,
+        style: glyphon::Style::Normal,
+    };
+    let position = crate::renderer::text::TextPosition {
+        x: 20.0,
+        y: 90.0,
+        max_width: Some(window_size.width as f32 - 40.0),
+        max_height: Some(window_size.height as f32 - 110.0),
+    };
+    text_renderer.create_text_buffer(
+        ASSET_DIAGNOSTICS_ID,
+        diagnostics,
+        Some(style),
+        Some(position),
+    );
 }
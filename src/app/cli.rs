@@ -0,0 +1,170 @@
+//! The `mirador` command line.
+//!
+//! [`run_from_args`] reads the flags the binary accepts, sets up where files are saved and
+//! which profile plays, and then either runs one of the headless tools (printing a journal,
+//! exporting a navigation mesh, the self-test) or starts the game with [`app::run`]. The
+//! binary itself only collects the arguments and calls it.
+//!
+//! # Usage
+//!
+//! ```rust,ignore
+//! let args: Vec<String> = std::env::args().collect();
+//! mirador::run_from_args(&args);
+//! ```
+
+use crate::test_mode::{self_test, soak};
+use crate::{app, game, paths, renderer};
+
+/// Runs the `mirador` command line.
+///
+/// This function reads the command line, initializes the application, sets up the event loop,
+/// and starts the game. It handles different compilation targets (native vs WASM) and
+/// optional memory profiling.
+///
+/// # Arguments
+/// - `args`: The command line, starting with the program name
+///
+/// # Features
+/// - Memory profiling with dhat-heap feature
+/// - Cross-platform compatibility (native and WASM targets)
+/// - Graceful error handling for event loop creation
+/// - `--journal <file>` prints a saved session journal and exits
+/// - `--data-dir <path>` or a `portable.txt` next to the executable moves all saved files
+/// - `--backend <name>` picks the graphics backend and saves it to the settings
+/// - `--clip-reports` writes a report file for each time the player is found inside a wall
+/// - `--export-navmesh` with `--seed <seed>` or `--maze <file>` writes a level's navigation
+///   mesh, plus an OBJ model with `--obj`, and exits
+/// - `--self-test` checks the adapter, shaders, assets, and audio, prints a report, and exits
+/// - `--soak <minutes>`, with an optional `--seed <seed>`, runs the soak test in debug builds
+///
+/// # Exits
+/// - With status 2 if `--data-dir` is missing its path, `--backend` is missing or unknown,
+///   `--export-navmesh` doesn't have exactly one valid `--seed` or `--maze`, or `--soak`
+///   is missing its minutes, has an invalid seed, or is used in a release build
+/// - With status 1 if the data directory can't be created or written to, a navigation
+///   mesh export fails, a critical self-test check fails, or a soak test fails
+///
+/// # Panics
+/// - If the event loop cannot be created
+/// - If the application fails to run
+pub fn run_from_args(args: &[String]) {
+    if args.get(1).is_some_and(|arg| arg == "--journal") {
+        let Some(path) = args.get(2) else {
+            eprintln!("Usage: mirador --journal <file>");
+            std::process::exit(2);
+        };
+        match game::journal::pretty_print(std::path::Path::new(path)) {
+            Ok(journal) => println!("{}", journal),
+            Err(e) => {
+                eprintln!("{}", e);
+                std::process::exit(1);
+            }
+        }
+        return;
+    }
+
+    // Resolve where files are saved before anything is loaded
+    let paths = match paths::Paths::from_args(args) {
+        Ok(paths) => paths,
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(2);
+        }
+    };
+    if let Err(e) = paths.prepare() {
+        eprintln!("{}", e);
+        std::process::exit(1);
+    }
+    if !paths.root().as_os_str().is_empty() {
+        println!("Saving data to {}", paths.root().display());
+    }
+    paths::init(paths);
+
+    // Play as the profile from last time, moving data saved before profiles into one
+    let profile = app::profiles::start_up(&paths::get());
+    paths::set_profile(profile.as_deref());
+
+    // Export a navigation mesh headless instead of starting the game
+    match game::navmesh::export_request(args) {
+        Ok(Some(request)) => {
+            match game::navmesh::export(&request, &paths::get().navmesh_dir()) {
+                Ok(files) => {
+                    for file in files {
+                        println!("Wrote {}", file.display());
+                    }
+                }
+                Err(e) => {
+                    eprintln!("{}", e);
+                    std::process::exit(1);
+                }
+            }
+            return;
+        }
+        Ok(None) => {}
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(2);
+        }
+    }
+
+    // Pick the graphics backend; the flag is remembered for later launches
+    let backend = match renderer::backend::backend_arg(args) {
+        Ok(backend) => backend,
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(2);
+        }
+    };
+    let mut settings = app::settings::Settings::load();
+    if let Some(backend) = backend.filter(|&backend| backend != settings.graphics_backend) {
+        settings.graphics_backend = backend;
+        if let Err(e) = settings.save() {
+            eprintln!("{}", e);
+        }
+    }
+
+    // Check the install for a bug report instead of starting the game
+    if self_test::requested(args) {
+        let report = self_test::run(settings.graphics_backend, &paths::get());
+        println!("{}", report);
+        std::process::exit(if report.passed() { 0 } else { 1 });
+    }
+
+    if args
+        .iter()
+        .any(|arg| arg == game::wall_clip::CLIP_REPORTS_FLAG)
+    {
+        game::wall_clip::enable_report_files();
+    }
+
+    // Let a bot play for the soak test, a debugging aid kept out of release builds
+    match soak::soak_request(args) {
+        Ok(Some(_)) if !cfg!(debug_assertions) => {
+            eprintln!("{} is only available in debug builds", soak::SOAK_FLAG);
+            std::process::exit(2);
+        }
+        Ok(Some(config)) => {
+            println!(
+                "Soak testing for {} minutes with seed {}",
+                config.minutes,
+                game::seed::format_seed(config.seed)
+            );
+            soak::enable(config);
+        }
+        Ok(None) => {}
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(2);
+        }
+    }
+
+    // Write the session journal if the game panics
+    game::journal::install_panic_hook();
+
+    #[cfg(feature = "dhat-heap")]
+    let _profiler = dhat::Profiler::new_heap();
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        pollster::block_on(app::run(settings.graphics_backend));
+    }
+}
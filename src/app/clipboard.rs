@@ -0,0 +1,50 @@
+//! System clipboard access for Mirador.
+//!
+//! The game only writes to the clipboard, to hand the player text they are meant to paste
+//! somewhere else, like a run's challenge code. The clipboard is opened the first time
+//! something is copied and then kept open: on X11 the copied text is served by the process
+//! that copied it, so dropping the handle would empty the clipboard again.
+//!
+//! # Usage
+//!
+//! ```rust,ignore
+//! let mut clipboard = Clipboard::new();
+//! if let Err(e) = clipboard.copy(&code) {
+//!     eprintln!("{}", e);
+//! }
+//! ```
+
+/// Lazily opened handle to the system clipboard.
+#[derive(Default)]
+pub struct Clipboard {
+    /// The open clipboard, once something has been copied
+    inner: Option<arboard::Clipboard>,
+}
+
+impl Clipboard {
+    /// Creates a handle without opening the clipboard yet.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Puts text on the clipboard, opening it first if needed.
+    ///
+    /// # Arguments
+    /// * `text` - The text to copy
+    ///
+    /// # Returns
+    /// `Ok(())` once the text is on the clipboard, or an error message if there is no
+    /// clipboard to copy to, as on a headless machine
+    pub fn copy(&mut self, text: &str) -> Result<(), String> {
+        let clipboard = match &mut self.inner {
+            Some(clipboard) => clipboard,
+            None => self.inner.insert(
+                arboard::Clipboard::new()
+                    .map_err(|e| format!("Failed to open the clipboard: {}", e))?,
+            ),
+        };
+        clipboard
+            .set_text(text)
+            .map_err(|e| format!("Failed to copy to the clipboard: {}", e))
+    }
+}
@@ -0,0 +1,517 @@
+//! Detachable debug inspector window.
+//!
+//! When profiling, the diagnostics are easier to read on a second monitor than over the game.
+//! [`DebugWindow`] is a separate OS window, opened with `F11` in debug builds and test mode,
+//! that shows the benchmark table, the tail of the session journal, the current level's plan,
+//! and a live inspector of the game state while the game keeps its own window.
+//!
+//! The window shares the game's wgpu device and queue; only its surface is its own. That
+//! surface is configured from the adapter's capabilities for the new window, so its format may
+//! differ from the game's, and the window's egui renderer is built for whichever format it got.
+//!
+//! The game routes window events by window id: events for this window go to
+//! [`DebugWindow::handle_event`], which redraws it, resizes its surface, and reports when it
+//! should close. Closing it never stops the game; closing the game's window closes both.
+//! Nothing is drawn while the window is minimized.
+//!
+//! # Usage
+//!
+//! ```rust,ignore
+//! // On F11
+//! app.debug_window = match DebugWindow::open(event_loop, &app.instance, &state.wgpu_renderer) {
+//!     Ok(window) => Some(window),
+//!     Err(e) => {
+//!         eprintln!("{}", e);
+//!         None
+//!     }
+//! };
+//!
+//! // In `window_event`
+//! if debug_window.id() == window_id
+//!     && debug_window.handle_event(&event, &state.wgpu_renderer, &state.game_state)
+//! {
+//!     app.debug_window = None;
+//! }
+//! ```
+
+use crate::benchmarks::data::PerformanceMetrics;
+use crate::benchmarks::utils::get_measurements;
+use crate::game::GameState;
+use crate::game::levels::LevelPlan;
+use crate::renderer::color;
+use crate::renderer::surface_alpha;
+use crate::renderer::wgpu_lib::WgpuRenderer;
+use std::collections::HashMap;
+use std::sync::Arc;
+use winit::dpi::LogicalSize;
+use winit::event::{ElementState, KeyEvent, WindowEvent};
+use winit::event_loop::ActiveEventLoop;
+use winit::keyboard::{Key, NamedKey};
+use winit::window::{Window, WindowId};
+
+/// Title of the debug window.
+const DEBUG_WINDOW_TITLE: &str = "Mirador Inspector";
+
+/// Size the debug window opens at, in logical pixels.
+const DEBUG_WINDOW_SIZE: (f64, f64) = (640.0, 800.0);
+
+/// Number of journal events shown at the bottom of the window.
+const JOURNAL_TAIL_LEN: usize = 200;
+
+/// A second OS window showing the game's diagnostics.
+pub struct DebugWindow {
+    /// The debug window itself
+    window: Arc<Window>,
+    /// The window's surface, configured with the game's device
+    surface: wgpu::Surface<'static>,
+    /// Configuration of `surface`, kept to reconfigure it on resize
+    config: wgpu::SurfaceConfiguration,
+    /// The egui context holding UI state between frames
+    context: egui::Context,
+    /// Translates winit events into egui input
+    winit_state: egui_winit::State,
+    /// Draws egui output into a wgpu render pass, in this window's format
+    renderer: egui_wgpu::Renderer,
+}
+
+impl DebugWindow {
+    /// Opens the debug window.
+    ///
+    /// # Arguments
+    /// * `event_loop` - The running event loop, to create the window on
+    /// * `instance` - The wgpu instance the game's adapter came from
+    /// * `renderer` - The game's renderer, whose adapter and device the window shares
+    ///
+    /// # Returns
+    /// The open window, or an error message if it or its surface could not be created
+    pub fn open(
+        event_loop: &ActiveEventLoop,
+        instance: &wgpu::Instance,
+        renderer: &WgpuRenderer,
+    ) -> Result<Self, String> {
+        let attributes = Window::default_attributes()
+            .with_title(DEBUG_WINDOW_TITLE)
+            .with_inner_size(LogicalSize::new(DEBUG_WINDOW_SIZE.0, DEBUG_WINDOW_SIZE.1));
+        let window = event_loop
+            .create_window(attributes)
+            .map(Arc::new)
+            .map_err(|e| format!("Failed to create the debug window: {}", e))?;
+        let surface = instance
+            .create_surface(window.clone())
+            .map_err(|e| format!("Failed to create the debug window's surface: {}", e))?;
+
+        let capabilities = surface.get_capabilities(&renderer.adapter);
+        let format = *capabilities
+            .formats
+            .first()
+            .ok_or("The game's adapter can't draw to the debug window")?;
+        let size = window.inner_size();
+        let config = wgpu::SurfaceConfiguration {
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+            format,
+            width: size.width.max(1),
+            height: size.height.max(1),
+            // Presenting shares the game's thread, so waiting on vsync here would slow the game
+            present_mode: wgpu::PresentMode::AutoNoVsync,
+            desired_maximum_frame_latency: 2,
+            // Cleared to opaque black, and egui keeps the alpha it draws over at 1.0
+            alpha_mode: surface_alpha::select_alpha_mode(&capabilities.alpha_modes),
+            // egui draws through a non-sRGB view of the surface
+            view_formats: if format.is_srgb() {
+                vec![color::ui_format(format)]
+            } else {
+                vec![]
+            },
+        };
+        surface.configure(&renderer.device, &config);
+
+        let context = egui::Context::default();
+        let winit_state = egui_winit::State::new(
+            context.clone(),
+            egui::ViewportId::ROOT,
+            &window,
+            Some(window.scale_factor() as f32),
+            None,
+            Some(renderer.device.limits().max_texture_dimension_2d as usize),
+        );
+        let egui_renderer =
+            egui_wgpu::Renderer::new(&renderer.device, color::ui_format(format), None, 1, false);
+
+        println!(
+            "[DEBUG WINDOW] Opened with surface format {:?} (game: {:?})",
+            format, renderer.surface_config.format
+        );
+        Ok(Self {
+            window,
+            surface,
+            config,
+            context,
+            winit_state,
+            renderer: egui_renderer,
+        })
+    }
+
+    /// Returns the id winit reports the window's events under.
+    pub fn id(&self) -> WindowId {
+        self.window.id()
+    }
+
+    /// Asks for the window to be drawn again, so it keeps up with the game.
+    pub fn request_redraw(&self) {
+        self.window.request_redraw();
+    }
+
+    /// Handles an event sent to the debug window.
+    ///
+    /// # Arguments
+    /// * `event` - The window event
+    /// * `renderer` - The game's renderer, whose device and queue draw the window
+    /// * `game_state` - The game state shown in the inspector
+    ///
+    /// # Returns
+    /// `true` if the window should close: it was closed, or `F11` was pressed in it
+    pub fn handle_event(
+        &mut self,
+        event: &WindowEvent,
+        renderer: &WgpuRenderer,
+        game_state: &GameState,
+    ) -> bool {
+        match event {
+            WindowEvent::CloseRequested => return true,
+            WindowEvent::KeyboardInput {
+                event:
+                    KeyEvent {
+                        logical_key: Key::Named(NamedKey::F11),
+                        state: ElementState::Pressed,
+                        repeat: false,
+                        ..
+                    },
+                ..
+            } => return true,
+            // A zero size means the window was minimized; it is skipped until restored
+            WindowEvent::Resized(size) if size.width > 0 && size.height > 0 => {
+                self.config.width = size.width;
+                self.config.height = size.height;
+                self.surface.configure(&renderer.device, &self.config);
+            }
+            WindowEvent::RedrawRequested => {
+                self.render(renderer, game_state);
+                return false;
+            }
+            _ => {}
+        }
+
+        if self
+            .winit_state
+            .on_window_event(&self.window, event)
+            .repaint
+        {
+            self.window.request_redraw();
+        }
+        false
+    }
+
+    /// Returns whether the window is minimized, or too small to draw to.
+    fn is_hidden(&self) -> bool {
+        let size = self.window.inner_size();
+        self.window.is_minimized() == Some(true) || size.width == 0 || size.height == 0
+    }
+
+    /// Lays out the inspector panels and presents them to the window.
+    fn render(&mut self, renderer: &WgpuRenderer, game_state: &GameState) {
+        if self.is_hidden() {
+            return;
+        }
+        let surface_texture = match self.surface.get_current_texture() {
+            Ok(texture) => texture,
+            Err(wgpu::SurfaceError::Lost | wgpu::SurfaceError::Outdated) => {
+                self.surface.configure(&renderer.device, &self.config);
+                return;
+            }
+            Err(e) => {
+                eprintln!("[DEBUG WINDOW] Failed to get a surface texture: {}", e);
+                return;
+            }
+        };
+        let view = surface_texture
+            .texture
+            .create_view(&wgpu::TextureViewDescriptor {
+                format: Some(color::ui_format(self.config.format)),
+                ..Default::default()
+            });
+
+        let measurements = get_measurements();
+        let journal_tail = game_state.journal.tail(JOURNAL_TAIL_LEN);
+        let raw_input = self.winit_state.take_egui_input(&self.window);
+        let full_output = self.context.run(raw_input, |ctx| {
+            egui::CentralPanel::default().show(ctx, |ui| {
+                egui::ScrollArea::vertical().show(ui, |ui| {
+                    Self::game_state_panel(ui, game_state);
+                    Self::level_plan_panel(ui, &game_state.level_plan);
+                    Self::benchmark_panel(ui, &measurements);
+                    Self::journal_panel(ui, &journal_tail);
+                });
+            });
+        });
+        self.winit_state
+            .handle_platform_output(&self.window, full_output.platform_output);
+
+        let paint_jobs = self
+            .context
+            .tessellate(full_output.shapes, full_output.pixels_per_point);
+        let screen_descriptor = egui_wgpu::ScreenDescriptor {
+            size_in_pixels: [self.config.width, self.config.height],
+            pixels_per_point: full_output.pixels_per_point,
+        };
+
+        let device = &renderer.device;
+        let queue = &renderer.queue;
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("Debug Window Encoder"),
+        });
+        for (id, image_delta) in &full_output.textures_delta.set {
+            self.renderer
+                .update_texture(device, queue, *id, image_delta);
+        }
+        self.renderer
+            .update_buffers(device, queue, &mut encoder, &paint_jobs, &screen_descriptor);
+        {
+            let mut render_pass = encoder
+                .begin_render_pass(&wgpu::RenderPassDescriptor {
+                    label: Some("Debug Window Render Pass"),
+                    color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                        view: &view,
+                        resolve_target: None,
+                        ops: wgpu::Operations {
+                            load: wgpu::LoadOp::Clear(wgpu::Color::BLACK),
+                            store: wgpu::StoreOp::Store,
+                        },
+                    })],
+                    depth_stencil_attachment: None,
+                    timestamp_writes: None,
+                    occlusion_query_set: None,
+                })
+                .forget_lifetime();
+            self.renderer
+                .render(&mut render_pass, &paint_jobs, &screen_descriptor);
+        }
+        for id in &full_output.textures_delta.free {
+            self.renderer.free_texture(id);
+        }
+
+        queue.submit(std::iter::once(encoder.finish()));
+        surface_texture.present();
+    }
+
+    /// Shows the live game state.
+    fn game_state_panel(ui: &mut egui::Ui, game_state: &GameState) {
+        egui::CollapsingHeader::new("Game state")
+            .default_open(true)
+            .show(ui, |ui| {
+                let player = &game_state.player;
+                let enemy = &game_state.enemy.pathfinder;
+                let remaining = game_state
+                    .game_ui
+                    .timer
+                    .as_ref()
+                    .map_or("-".to_string(), |timer| {
+                        format!("{:.1} s", timer.get_remaining_time().as_secs_f32())
+                    });
+                let rows = [
+                    ("Screen", format!("{:?}", game_state.current_screen)),
+                    ("Level", game_state.game_ui.level.to_string()),
+                    ("Score", game_state.game_ui.score.to_string()),
+                    ("Run seed", game_state.run_seed.to_string()),
+                    ("Time left", remaining),
+                    ("FPS", game_state.current_fps.to_string()),
+                    ("Player position", format_position(player.position)),
+                    (
+                        "Player yaw / pitch",
+                        format!("{:.1} / {:.1}", player.yaw, player.pitch),
+                    ),
+                    ("Stamina", format!("{:.2}", player.stamina)),
+                    ("Enemy present", game_state.enemy_present().to_string()),
+                    ("Enemy position", format_position(enemy.position)),
+                    (
+                        "Closest enemy distance",
+                        format!("{:.1}", game_state.closest_enemy_distance),
+                    ),
+                    ("Close calls", game_state.close_calls.to_string()),
+                    (
+                        "Combo",
+                        format!("x{:.2}", game_state.game_ui.combo_multiplier),
+                    ),
+                    (
+                        "Anomaly",
+                        format!("{:?}", game_state.anomaly.as_ref().map(|a| a.cell)),
+                    ),
+                ];
+                egui::Grid::new("debug_game_state")
+                    .striped(true)
+                    .show(ui, |ui| {
+                        for (label, value) in rows {
+                            ui.label(label);
+                            ui.monospace(value);
+                            ui.end_row();
+                        }
+                    });
+            });
+    }
+
+    /// Shows the plan the current level was set up from.
+    fn level_plan_panel(ui: &mut egui::Ui, plan: &LevelPlan) {
+        egui::CollapsingHeader::new("Level plan")
+            .default_open(false)
+            .show(ui, |ui| {
+                let tuning = &plan.enemy.tuning;
+                let rows = [
+                    ("Level", plan.level.to_string()),
+                    ("Difficulty", format!("{:?}", plan.difficulty)),
+                    ("Seed", plan.seed.to_string()),
+                    (
+                        "Maze size",
+                        format!("{}x{}", plan.maze_width, plan.maze_height),
+                    ),
+                    ("Two floors", plan.multi_floor.to_string()),
+                    (
+                        "Timer",
+                        format!(
+                            "{:.0} s (warning {:.0} s, critical {:.0} s)",
+                            plan.timer.duration_secs,
+                            plan.timer.warning_secs,
+                            plan.timer.critical_secs
+                        ),
+                    ),
+                    ("Enemies", plan.enemy.count.to_string()),
+                    (
+                        "Enemy speed",
+                        format!("x{:.2}", plan.enemy.speed_multiplier),
+                    ),
+                    (
+                        "Enemy tuning",
+                        format!(
+                            "speed x{:.2}, alert x{:.2}, replan x{:.2}",
+                            tuning.speed_multiplier,
+                            tuning.alert_radius_multiplier,
+                            tuning.replan_multiplier
+                        ),
+                    ),
+                    ("Delayed spawn", plan.enemy.delayed_spawn.to_string()),
+                    ("Anomaly", plan.anomaly.to_string()),
+                    ("Wall breath", format!("{:.2}", plan.wall_breath)),
+                    ("Signposts", plan.signposts.to_string()),
+                    ("Events and theme", plan.modifiers.to_string()),
+                ];
+                egui::Grid::new("debug_level_plan")
+                    .striped(true)
+                    .show(ui, |ui| {
+                        for (label, value) in rows {
+                            ui.label(label);
+                            ui.monospace(value);
+                            ui.end_row();
+                        }
+                    });
+            });
+    }
+
+    /// Shows every benchmark section, slowest in total first.
+    fn benchmark_panel(ui: &mut egui::Ui, measurements: &HashMap<String, PerformanceMetrics>) {
+        egui::CollapsingHeader::new("Benchmarks")
+            .default_open(true)
+            .show(ui, |ui| {
+                let ms = |duration: std::time::Duration| {
+                    format!("{:.3}", duration.as_secs_f64() * 1000.0)
+                };
+                egui::Grid::new("debug_benchmarks")
+                    .striped(true)
+                    .show(ui, |ui| {
+                        for header in ["Section", "Count", "Avg ms", "Min ms", "Max ms"] {
+                            ui.strong(header);
+                        }
+                        ui.end_row();
+                        for (name, metrics) in benchmark_rows(measurements) {
+                            ui.label(name);
+                            ui.monospace(metrics.count.to_string());
+                            ui.monospace(ms(metrics.avg_duration));
+                            ui.monospace(ms(metrics.min_duration));
+                            ui.monospace(ms(metrics.max_duration));
+                            ui.end_row();
+                        }
+                    });
+            });
+    }
+
+    /// Shows the newest journal events, scrolled to the newest.
+    fn journal_panel(ui: &mut egui::Ui, entries: &[crate::game::journal::JournalEntry]) {
+        egui::CollapsingHeader::new("Journal")
+            .default_open(true)
+            .show(ui, |ui| {
+                egui::ScrollArea::vertical()
+                    .id_salt("debug_journal")
+                    .max_height(300.0)
+                    .stick_to_bottom(true)
+                    .show(ui, |ui| {
+                        for entry in entries {
+                            ui.monospace(entry.to_string());
+                        }
+                    });
+            });
+    }
+}
+
+/// Formats a world position for the inspector.
+fn format_position([x, y, z]: [f32; 3]) -> String {
+    format!("({:.1}, {:.1}, {:.1})", x, y, z)
+}
+
+/// Orders benchmark sections for the table.
+///
+/// # Arguments
+/// * `measurements` - Every measured section, by name
+///
+/// # Returns
+/// The sections, the one with the most total time first; ties are ordered by name
+pub fn benchmark_rows(
+    measurements: &HashMap<String, PerformanceMetrics>,
+) -> Vec<(&str, &PerformanceMetrics)> {
+    let mut rows: Vec<_> = measurements
+        .iter()
+        .map(|(name, metrics)| (name.as_str(), metrics))
+        .collect();
+    rows.sort_by(|a, b| {
+        b.1.total_duration
+            .cmp(&a.1.total_duration)
+            .then_with(|| a.0.cmp(b.0))
+    });
+    rows
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn metrics(total_ms: u64) -> PerformanceMetrics {
+        PerformanceMetrics {
+            count: 1,
+            total_duration: Duration::from_millis(total_ms),
+            min_duration: Duration::from_millis(total_ms),
+            max_duration: Duration::from_millis(total_ms),
+            avg_duration: Duration::from_millis(total_ms),
+        }
+    }
+
+    #[test]
+    fn test_benchmark_rows_put_the_most_expensive_sections_first() {
+        let measurements = HashMap::from([
+            ("text".to_string(), metrics(2)),
+            ("render".to_string(), metrics(9)),
+            ("audio".to_string(), metrics(2)),
+        ]);
+        let names: Vec<_> = benchmark_rows(&measurements)
+            .into_iter()
+            .map(|(name, _)| name)
+            .collect();
+        assert_eq!(names, ["render", "audio", "text"]);
+    }
+}
@@ -3,7 +3,17 @@
 //! Contains the App struct and its event handling logic.
 
 use crate::app::app_state::AppState;
+use crate::app::debug_window::DebugWindow;
+use crate::app::panic_guard::PanicGuard;
+use crate::game::PauseSource;
+use crate::game::bindings::{self, Routed};
+use crate::game::daily::{self, DailyChallenge};
+use crate::game::input::PlayerInput;
+use crate::game::input_claim::{InputConsumer, InputKind};
+use crate::renderer::backend::GraphicsBackend;
 use crate::renderer::loading_renderer::LoadingRenderer;
+use crate::renderer::wgpu_lib::WgpuRenderer;
+use crate::test_mode::soak::{self, SoakRun};
 use std::{sync::Arc, time::Instant};
 use wgpu;
 use winit::{
@@ -11,6 +21,7 @@ use winit::{
     dpi::PhysicalSize,
     event::{DeviceEvent, DeviceId, ElementState, KeyEvent, MouseButton, WindowEvent},
     event_loop::ActiveEventLoop,
+    keyboard::PhysicalKey,
     window::{Window, WindowId},
 };
 
@@ -21,11 +32,14 @@ use winit::{
 ///
 /// # Fields
 /// - `instance`: The WGPU instance for graphics operations
+/// - `backend`: The graphics backend the instance was created for
 /// - `state`: Optional application state (None until window is created)
 /// - `window`: Optional window reference (None until window is created)
+/// - `debug_window`: The debug inspector window, while it is open
+/// - `soak`: The soak test's bot and watchdog, when one is running
 ///
 /// # Lifecycle
-/// 1. Created with `App::new()` - initializes WGPU instance
+/// 1. Created with `App::new()` - initializes WGPU instance for the chosen backend
 /// 2. Window is set via `set_window()` - creates surface and application state
 /// 3. Events are handled via `ApplicationHandler` trait methods
 /// 4. Application runs until window is closed
@@ -33,26 +47,37 @@ use winit::{
 pub struct App {
     /// The WGPU instance for graphics operations.
     pub instance: wgpu::Instance,
+    /// The graphics backend the instance was created for.
+    pub backend: GraphicsBackend,
     /// The current application state, None until initialized.
     pub state: Option<AppState>,
     /// The application window, None until set.
     pub window: Option<Arc<Window>>,
+    /// The debug inspector window, opened with F11 in debug builds and test mode.
+    pub debug_window: Option<DebugWindow>,
+    /// Catches panics from the frame update so the game can show the error screen.
+    pub panic_guard: PanicGuard,
+    /// The bot and watchdog of the soak test, when the game was started with `--soak`.
+    pub soak: Option<SoakRun>,
 }
 
 impl App {
-    /// Creates a new [`App`] instance with default WGPU configuration.
+    /// Creates a new [`App`] instance for the chosen graphics backend.
     ///
-    /// This initializes the WGPU instance with default settings. The application
+    /// This initializes the WGPU instance limited to that backend. The application
     /// state and window will be None until `set_window()` is called.
     ///
+    /// # Arguments
+    /// - `backend`: The graphics backend to ask wgpu for
+    ///
     /// # Returns
     /// A new [`App`] instance ready for window creation.
     ///
     /// # Example
+    /// ```rust,ignore
+    /// let app = App::new(GraphicsBackend::Auto);
     /// ```
-    /// let app = App::new();
-    /// ```
-    pub fn new() -> Self {
+    pub fn new(backend: GraphicsBackend) -> Self {
         use crate::benchmarks::{BenchmarkConfig, Profiler};
 
         // Initialize profiler for App creation benchmarking
@@ -62,17 +87,500 @@ impl App {
             write_to_file: false,
             min_duration_threshold: std::time::Duration::from_micros(1),
             max_samples: 1000,
+            autosave_interval: std::time::Duration::from_secs(60),
+            max_sections: 256,
         });
 
         // Benchmark WGPU instance creation
         init_profiler.start_section("wgpu_instance_creation");
-        let instance = wgpu::Instance::new(&wgpu::InstanceDescriptor::default());
+        let instance = wgpu::Instance::new(&backend.instance_descriptor());
         init_profiler.end_section("wgpu_instance_creation");
 
         Self {
             instance,
+            backend,
             state: None,
             window: None,
+            debug_window: None,
+            panic_guard: PanicGuard::default(),
+            soak: soak::config().cloned().map(SoakRun::new),
+        }
+    }
+
+    /// Reads the gamepads and acts on what they did since the last frame.
+    ///
+    /// The D-pad and A step through whichever menu is open. Start pauses and resumes a
+    /// level and starts a run from the title screen, and A also starts a loaded level and
+    /// interacts during play. The sticks are kept for this frame's movement and look.
+    /// Unplugging the gamepad that was playing pauses the level, and nothing is read while
+    /// the window is unfocused.
+    fn poll_gamepads(&mut self) {
+        let Some(state) = self.state.as_mut() else {
+            return;
+        };
+        let frame = state.gamepads.poll(state.settings.gamepad_deadzone);
+        if !state.window_focused {
+            state.gamepad_input = PlayerInput::default();
+            return;
+        }
+        let radial_was_held = state.gamepad_input.quick_radial;
+        state.gamepad_input = frame.input;
+
+        for nav in frame.menu {
+            let Some(state) = self.state.as_mut() else {
+                return;
+            };
+            let audio_manager = &mut state.game_state.audio_manager;
+            let mut pause_action = crate::renderer::ui::pause_menu::PauseMenuAction::None;
+            let mut game_over_action = crate::renderer::ui::game_over_menu::GameOverAction::None;
+            let mut title_action = crate::renderer::ui::title_menu::TitleAction::None;
+            match state.game_state.current_screen {
+                crate::game::CurrentScreen::Pause => {
+                    state.pause_menu.navigate(nav, audio_manager);
+                    pause_action = state.pause_menu.get_last_action();
+                }
+                crate::game::CurrentScreen::GameOver => {
+                    state.game_over_menu.navigate(nav, audio_manager);
+                    game_over_action = state.game_over_menu.get_last_action();
+                }
+                crate::game::CurrentScreen::Title => {
+                    state.title_menu.navigate(nav, audio_manager);
+                    title_action = state.title_menu.get_last_action();
+                }
+                crate::game::CurrentScreen::UpgradeMenu => {
+                    state.upgrade_menu.navigate(nav, &mut state.game_state);
+                }
+                _ => {}
+            }
+            self.apply_menu_actions(pause_action, game_over_action, title_action);
+        }
+
+        let Some(state) = self.state.as_mut() else {
+            return;
+        };
+        let input = frame.input;
+
+        // The bumper holds the quick-settings radial open and the right stick points it
+        if state.game_state.current_screen == crate::game::CurrentScreen::Game {
+            if input.quick_radial && !radial_was_held && state.game_state.quick_radial_allowed() {
+                state.game_state.quick_radial.open();
+            } else if input.quick_radial {
+                state.game_state.quick_radial.point_stick(input.look_axis);
+            } else if radial_was_held {
+                state.release_quick_radial();
+            }
+        }
+
+        let can_pause = state.game_state.capture.is_none();
+        match state.game_state.current_screen {
+            crate::game::CurrentScreen::Game if can_pause && (input.pause || frame.lost_active) => {
+                state.pause_game();
+            }
+            crate::game::CurrentScreen::Game if input.interact => {
+                state.collect_anomaly();
+            }
+            crate::game::CurrentScreen::Pause if input.pause => {
+                state.resume_from_pause();
+            }
+            crate::game::CurrentScreen::Loading if input.pause || input.interact => {
+                state.game_state.begin_loaded_level();
+            }
+            crate::game::CurrentScreen::Title
+                if input.pause && !state.title_menu.is_editing_seed() =>
+            {
+                let chosen_seed = state.game_state.chosen_seed;
+                let refused = self
+                    .window
+                    .as_ref()
+                    .is_some_and(|window| state.refuse_burned_seed(window, chosen_seed));
+                if !refused {
+                    state.leave_title_screen();
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Carries out what the player picked on the pause, game over, and title menus.
+    ///
+    /// Clicks and gamepad presses both end up here, so a menu behaves the same whichever
+    /// device chose the button.
+    ///
+    /// # Arguments
+    /// - `pause_action`: What was picked on the pause menu
+    /// - `game_over_action`: What was picked on the game over menu
+    /// - `title_action`: What was picked on the title menu
+    fn apply_menu_actions(
+        &mut self,
+        pause_action: crate::renderer::ui::pause_menu::PauseMenuAction,
+        game_over_action: crate::renderer::ui::game_over_menu::GameOverAction,
+        title_action: crate::renderer::ui::title_menu::TitleAction,
+    ) {
+        let Some(state) = self.state.as_mut() else {
+            return;
+        };
+
+        // Handle pause menu actions
+        match pause_action {
+            crate::renderer::ui::pause_menu::PauseMenuAction::Resume => {
+                state.resume_from_pause();
+            }
+            crate::renderer::ui::pause_menu::PauseMenuAction::RetryLevel => {
+                if state.game_state.hardcore {
+                    println!("Hardcore runs can't retry a level");
+                } else if state.game_state.retry_level() {
+                    state.pause_menu.hide();
+                    // Restore game audio volumes for the retried level
+                    state
+                        .game_state
+                        .audio_manager
+                        .set_game_volumes()
+                        .expect("Failed to set game volumes");
+                    state.game_state.capture_mouse = true;
+                    if let Some(window) = self.window.as_ref() {
+                        state.triage_mouse(window);
+                    }
+                } else {
+                    println!("No level to retry");
+                }
+            }
+            crate::renderer::ui::pause_menu::PauseMenuAction::Restart => {
+                // Restart current run - handle this after the match to avoid borrow issues
+                state.game_state.current_screen = crate::game::CurrentScreen::NewGame;
+                state.game_state.previous_screen = None; // Clear previous screen
+                state.pause_menu.hide();
+                // Restore game audio volumes for new game
+                state
+                    .game_state
+                    .audio_manager
+                    .set_game_volumes()
+                    .expect("Failed to set game volumes");
+                // Ensure mouse is captured for the new game
+                state.game_state.capture_mouse = true;
+                // Apply mouse capture immediately
+                if let Some(window) = self.window.as_ref() {
+                    state.triage_mouse(window);
+                }
+
+                // Hide title screen elements when transitioning away from title
+                if let Err(e) = state
+                    .text_renderer
+                    .set_buffer_visibility("title_mirador_overlay", false)
+                {
+                    eprintln!("Failed to hide title_mirador_overlay: {}", e);
+                }
+                if let Err(e) = state
+                    .text_renderer
+                    .set_buffer_visibility("title_subtitle_overlay", false)
+                {
+                    eprintln!("Failed to hide title_subtitle_overlay: {}", e);
+                }
+            }
+            crate::renderer::ui::pause_menu::PauseMenuAction::ToggleTestMode => {
+                // Toggle between test mode and normal mode
+                if state.game_state.is_test_mode {
+                    // Currently in test mode, switch to normal mode (loading screen)
+                    state.game_state.is_test_mode = false;
+                    let dimensions = state.upgrade_menu.upgraded_dimensions(false);
+                    state.game_state.set_dimensions(dimensions);
+                    state.game_state.current_screen = crate::game::CurrentScreen::Loading;
+                    state.game_state.previous_screen = None; // Clear previous screen
+                    state.pause_menu.hide();
+                    // Restore game audio volumes for normal mode
+                    state
+                        .game_state
+                        .audio_manager
+                        .set_game_volumes()
+                        .expect("Failed to set game volumes");
+
+                    // Recapture mouse when exiting test mode
+                    state.game_state.capture_mouse = true;
+
+                    // Stop reloading the test maze
+                    state.stop_test_mode_watcher();
+
+                    // Reset to normal game state
+                    state.game_state.maze_path = None;
+                    state.game_state.level_ready = false;
+                    state.game_state.clear_level_snapshot();
+                    state.game_state.begin_run_seed();
+                    state.wgpu_renderer.loading_screen_renderer = LoadingRenderer::for_plan(
+                        &state.wgpu_renderer.device,
+                        &state.wgpu_renderer.surface_config,
+                        &state.wgpu_renderer.gpu_memory,
+                        state.game_state.plan_level(1),
+                    );
+                    // Clear previous level state
+                    state.game_state.player = crate::game::player::Player::new();
+                    state.game_state.enemy.pathfinder.position = [0.0, 30.0, 0.0];
+                    state.game_state.enemy.pathfinder.locked = true;
+                    state.game_state.exit_cell = None;
+
+                    // Reset score and level to starting values
+                    state.game_state.set_score(0);
+                    state.game_state.set_level(1);
+
+                    // Stop and reset timer with normal game configuration
+                    state.game_state.game_ui.timer = None; // Clear the test timer
+                    // The timer will be properly initialized when the game starts (in update_game_ui)
+
+                    // Hide title screen elements when transitioning away from title
+                    if let Err(e) = state
+                        .text_renderer
+                        .set_buffer_visibility("title_mirador_overlay", false)
+                    {
+                        eprintln!("Failed to hide title_mirador_overlay: {}", e);
+                    }
+                    if let Err(e) = state
+                        .text_renderer
+                        .set_buffer_visibility("title_subtitle_overlay", false)
+                    {
+                        eprintln!("Failed to hide title_subtitle_overlay: {}", e);
+                    }
+                } else {
+                    // Currently in normal mode, switch to test mode
+                    state.game_state.is_test_mode = true;
+                    state.game_state.daily = None;
+                    state.game_state.game_ui.mode_label = None;
+                    state.game_state.current_screen = crate::game::CurrentScreen::Game;
+                    state.game_state.previous_screen = None; // Clear previous screen
+                    state.pause_menu.hide();
+
+                    // Recapture mouse for test mode
+                    state.game_state.capture_mouse = true;
+
+                    // Set up test environment and watch its maze file
+                    if let Some(window) = self.window.as_ref() {
+                        state.start_test_mode(window);
+                    }
+                }
+
+                // Hide title screen elements when transitioning away from title
+                if let Err(e) = state
+                    .text_renderer
+                    .set_buffer_visibility("title_mirador_overlay", false)
+                {
+                    eprintln!("Failed to hide title_mirador_overlay: {}", e);
+                }
+                if let Err(e) = state
+                    .text_renderer
+                    .set_buffer_visibility("title_subtitle_overlay", false)
+                {
+                    eprintln!("Failed to hide title_subtitle_overlay: {}", e);
+                }
+            }
+            crate::renderer::ui::pause_menu::PauseMenuAction::QuitToMenu => {
+                // Quit to lobby (title screen)
+                state.game_state.current_screen = crate::game::CurrentScreen::Title;
+                state.game_state.previous_screen = None; // Clear previous screen
+                state.pause_menu.hide();
+                // An unfinished daily attempt still shows up in the history
+                state.abandon_daily_attempt();
+                state.return_to_title();
+            }
+            crate::renderer::ui::pause_menu::PauseMenuAction::QuitApp => {
+                // Save benchmark results before quitting
+                self.save_benchmark_results();
+
+                // Quit the application
+                std::process::exit(0);
+            }
+            crate::renderer::ui::pause_menu::PauseMenuAction::CycleMenuFpsCap => {
+                state.settings.menu_fps_cap = state.settings.menu_fps_cap.next();
+                state.pause_menu.update_fps_cap_button_text(
+                    state.settings.menu_fps_cap,
+                    state.settings.gameplay_fps_cap,
+                );
+                if let Err(e) = state.settings.save() {
+                    eprintln!("{}", e);
+                }
+            }
+            crate::renderer::ui::pause_menu::PauseMenuAction::CycleGameplayFpsCap => {
+                state.settings.gameplay_fps_cap = state.settings.gameplay_fps_cap.next();
+                state.pause_menu.update_fps_cap_button_text(
+                    state.settings.menu_fps_cap,
+                    state.settings.gameplay_fps_cap,
+                );
+                if let Err(e) = state.settings.save() {
+                    eprintln!("{}", e);
+                }
+            }
+            crate::renderer::ui::pause_menu::PauseMenuAction::CyclePresentMode => {
+                state.settings.present_mode = state.settings.present_mode.next();
+                state.apply_present_mode();
+                if let Err(e) = state.settings.save() {
+                    eprintln!("{}", e);
+                }
+            }
+            crate::renderer::ui::pause_menu::PauseMenuAction::ReloadMarker => {
+                state.reload_custom_marker();
+            }
+            crate::renderer::ui::pause_menu::PauseMenuAction::CycleBackground => {
+                state.settings.background = state.settings.background.next();
+                state.wgpu_renderer.background = state.settings.background;
+                state
+                    .pause_menu
+                    .update_background_button_text(state.settings.background);
+                if let Err(e) = state.settings.save() {
+                    eprintln!("{}", e);
+                }
+            }
+            crate::renderer::ui::pause_menu::PauseMenuAction::CycleAmbienceVolume => {
+                state.settings.ambience_volume =
+                    crate::game::soundscape::next_ambience_volume(state.settings.ambience_volume);
+                state
+                    .game_state
+                    .audio_manager
+                    .set_ambience_volume(state.settings.ambience_volume);
+                state
+                    .pause_menu
+                    .update_ambience_button_text(state.settings.ambience_volume);
+                if let Err(e) = state.settings.save() {
+                    eprintln!("{}", e);
+                }
+            }
+            crate::renderer::ui::pause_menu::PauseMenuAction::CycleTimeOfDay => {
+                state.settings.time_of_day = state.settings.time_of_day.next();
+                state.wgpu_renderer.time_of_day = state.settings.time_of_day;
+                state
+                    .pause_menu
+                    .update_time_of_day_button_text(state.settings.time_of_day);
+                if let Err(e) = state.settings.save() {
+                    eprintln!("{}", e);
+                }
+            }
+            crate::renderer::ui::pause_menu::PauseMenuAction::CycleAudioOutput => {
+                state.settings.audio_output = state.settings.audio_output.next();
+                state.apply_settings_hook(crate::app::settings_registry::ApplyHook::Audio);
+                if let Err(e) = state.settings.save() {
+                    eprintln!("{}", e);
+                }
+            }
+            crate::renderer::ui::pause_menu::PauseMenuAction::ToggleHrtf => {
+                state.settings.hrtf = !state.settings.hrtf;
+                state.apply_settings_hook(crate::app::settings_registry::ApplyHook::Audio);
+                if let Err(e) = state.settings.save() {
+                    eprintln!("{}", e);
+                }
+            }
+            crate::renderer::ui::pause_menu::PauseMenuAction::PlayAudioTest => {
+                if let Err(e) = state.game_state.audio_manager.start_audio_test() {
+                    eprintln!("Failed to play audio test: {}", e);
+                }
+            }
+            crate::renderer::ui::pause_menu::PauseMenuAction::ToggleCosmetic(cosmetic) => {
+                let cosmetics = &mut state.settings.cosmetics;
+                if let Some(index) = cosmetics.iter().position(|&c| c == cosmetic) {
+                    cosmetics.remove(index);
+                } else {
+                    cosmetics.push(cosmetic);
+                }
+                state.apply_cosmetics();
+                if let Err(e) = state.settings.save() {
+                    eprintln!("{}", e);
+                }
+            }
+            crate::renderer::ui::pause_menu::PauseMenuAction::ResetSetting(id) => {
+                state.reset_setting(id);
+            }
+            crate::renderer::ui::pause_menu::PauseMenuAction::RestoreDefaults => {
+                state.restore_default_settings();
+            }
+            crate::renderer::ui::pause_menu::PauseMenuAction::Rebind(context, action, key) => {
+                match state.input_router.rebind(context, action, key) {
+                    Ok(()) => {
+                        state.pause_menu.update_binding_buttons(&state.input_router);
+                        if let Err(e) = state.input_router.save() {
+                            eprintln!("{}", e);
+                        }
+                    }
+                    Err(conflict) => state.pause_menu.show_refused_binding(&conflict),
+                }
+            }
+            crate::renderer::ui::pause_menu::PauseMenuAction::None => {}
+        }
+
+        // Handle game over menu actions
+        match game_over_action {
+            crate::renderer::ui::game_over_menu::GameOverAction::RetryLevel => {
+                if state.game_state.hardcore {
+                    println!("Hardcore runs can't retry a level");
+                } else if state.game_state.retry_level() {
+                    state.game_over_menu.hide();
+                    state.game_state.capture_mouse = true;
+                    if let Some(window) = self.window.as_ref() {
+                        state.triage_mouse(window);
+                    }
+                } else {
+                    println!("No level to retry");
+                }
+            }
+            crate::renderer::ui::game_over_menu::GameOverAction::NewRun => {
+                state.start_new_run();
+                if let Some(window) = self.window.as_ref() {
+                    state.triage_mouse(window);
+                }
+            }
+            crate::renderer::ui::game_over_menu::GameOverAction::None => {}
+        }
+
+        // Handle title menu actions
+        match title_action {
+            crate::renderer::ui::title_menu::TitleAction::StartDaily => {
+                if let Some(window) = self.window.as_ref() {
+                    let today = DailyChallenge::for_date(daily::utc_today());
+                    if !state.refuse_burned_seed(window, Some(today.seed)) {
+                        state.start_daily_run();
+                        state.leave_title_screen();
+                        state.triage_mouse(window);
+                    }
+                }
+            }
+            crate::renderer::ui::title_menu::TitleAction::EditSeed => {
+                state
+                    .title_menu
+                    .begin_seed_entry(state.game_state.chosen_seed);
+            }
+            crate::renderer::ui::title_menu::TitleAction::EditCode => {
+                state
+                    .title_menu
+                    .begin_code_entry(state.game_state.challenge.as_ref());
+            }
+            crate::renderer::ui::title_menu::TitleAction::OpenCodex => {
+                state.codex_menu.open(&state.codex);
+                state.game_state.current_screen = crate::game::CurrentScreen::Codex;
+            }
+            crate::renderer::ui::title_menu::TitleAction::OpenSandbox => {
+                state.enter_sandbox();
+                if let Some(window) = self.window.as_ref() {
+                    state.triage_mouse(window);
+                }
+            }
+            crate::renderer::ui::title_menu::TitleAction::ToggleHardcore => {
+                state.game_state.hardcore = !state.game_state.hardcore;
+                if let Some(challenge) = state.game_state.challenge.as_mut() {
+                    challenge.hardcore = state.game_state.hardcore;
+                }
+                let chosen_seed = state.game_state.chosen_seed;
+                let refused = self
+                    .window
+                    .as_ref()
+                    .is_some_and(|window| state.refuse_burned_seed(window, chosen_seed));
+                if refused {
+                    state.game_state.chosen_seed = None;
+                    state.game_state.challenge = None;
+                }
+            }
+            crate::renderer::ui::title_menu::TitleAction::SwitchProfile => {
+                state.open_profile_menu();
+            }
+            crate::renderer::ui::title_menu::TitleAction::Continue => {
+                state.continue_saved_run();
+                if let Some(window) = self.window.as_ref() {
+                    state.triage_mouse(window);
+                }
+            }
+            crate::renderer::ui::title_menu::TitleAction::None => {}
         }
     }
 
@@ -99,12 +607,13 @@ impl App {
     ///
     /// # Initialization Steps
     /// 1. Sets window size to 1360x768
-    /// 2. Creates WGPU surface from the window
+    /// 2. Creates WGPU surface from the window and finds an adapter for it, falling
+    ///    back to automatic backend selection if the chosen backend can't
     /// 3. Initializes [`AppState`] with all renderers and game systems
     /// 4. Stores window and state references
     ///
     /// # Panics
-    /// - If surface creation fails
+    /// - If no backend can create a surface and adapter for the window
     /// - If [`AppState`] initialization fails
     ///
     /// # Example
@@ -122,6 +631,8 @@ impl App {
             write_to_file: false,
             min_duration_threshold: std::time::Duration::from_micros(1),
             max_samples: 1000,
+            autosave_interval: std::time::Duration::from_secs(60),
+            max_sections: 256,
         });
 
         let window = Arc::new(window);
@@ -136,12 +647,23 @@ impl App {
             let _ = window.request_inner_size(PhysicalSize::new(initial_width, initial_height));
         }
 
-        // Benchmark surface creation
+        // Benchmark surface and adapter creation
         init_profiler.start_section("surface_creation");
-        let surface = self
-            .instance
-            .create_surface(window.clone())
-            .expect("Failed to create surface!");
+        let connected = match Self::connect(&self.instance, &window).await {
+            Ok(connected) => Ok(connected),
+            Err(e) if self.backend != GraphicsBackend::Auto => {
+                eprintln!(
+                    "Warning: the {} backend is unavailable ({}), falling back to automatic selection",
+                    self.backend.name(),
+                    e
+                );
+                self.backend = GraphicsBackend::Auto;
+                self.instance = wgpu::Instance::new(&self.backend.instance_descriptor());
+                Self::connect(&self.instance, &window).await
+            }
+            Err(e) => Err(e),
+        };
+        let (surface, adapter) = connected.expect("Failed to set up graphics");
         init_profiler.end_section("surface_creation");
 
         // Benchmark complete AppState initialization
@@ -155,13 +677,34 @@ impl App {
             (1360, 768)
         };
 
-        let state = AppState::new(&self.instance, surface, &window, width, height).await;
+        let state = AppState::new(adapter, surface, &window, width, height).await;
         init_profiler.end_section("app_state_initialization");
 
         self.window.get_or_insert(window);
         self.state.get_or_insert(state);
     }
 
+    /// Creates the window's surface and finds an adapter that can draw to it.
+    ///
+    /// # Arguments
+    /// - `instance`: The WGPU instance, limited to the chosen backend
+    /// - `window`: The window to draw to
+    ///
+    /// # Returns
+    /// The surface and adapter, or an error message if the instance's backends can't drive the window
+    async fn connect(
+        instance: &wgpu::Instance,
+        window: &Arc<Window>,
+    ) -> Result<(wgpu::Surface<'static>, wgpu::Adapter), String> {
+        let surface = instance
+            .create_surface(window.clone())
+            .map_err(|e| format!("Failed to create surface: {}", e))?;
+        let adapter = WgpuRenderer::request_adapter(instance, &surface)
+            .await
+            .ok_or_else(|| "Failed to find an appropriate adapter".to_string())?;
+        Ok((surface, adapter))
+    }
+
     /// Handles window resize events and updates all rendering systems.
     ///
     /// This method is called when the window is resized. It updates the WGPU surface
@@ -172,8 +715,8 @@ impl App {
     /// - `height`: New window height in pixels
     ///
     /// # Behavior
-    /// - Only processes resize if both dimensions are greater than 0
-    /// - Updates WGPU surface configuration
+    /// - Updates WGPU surface configuration, skipping everything while either dimension is 0,
+    ///   as when the window is minimized
     /// - Resizes pause menu and upgrade menu UI components
     /// - Logs error and backtrace if state is not initialized
     ///
@@ -181,26 +724,75 @@ impl App {
     /// This method safely handles cases where the application state hasn't been
     /// initialized yet, logging errors instead of panicking.
     pub fn handle_resized(&mut self, width: u32, height: u32) {
-        if width > 0 && height > 0 {
-            let state = match &mut self.state {
-                Some(state) => state,
-                _ => {
-                    eprintln!("Cannot resize surface without state initialized!");
-                    #[cfg(debug_assertions)]
-                    eprintln!("Backtrace: {:?}", std::backtrace::Backtrace::capture());
-                    return;
-                }
-            };
-            state.resize_surface(width, height);
-            // Ensure pause menu and upgrade menu resize with the window
-            use glyphon::Resolution;
-            let resolution = Resolution { width, height };
-            state
-                .pause_menu
-                .resize(&state.wgpu_renderer.queue, resolution);
-            state
-                .upgrade_menu
-                .resize(&state.wgpu_renderer.queue, resolution);
+        let state = match &mut self.state {
+            Some(state) => state,
+            _ => {
+                eprintln!("Cannot resize surface without state initialized!");
+                #[cfg(debug_assertions)]
+                eprintln!("Backtrace: {:?}", std::backtrace::Backtrace::capture());
+                return;
+            }
+        };
+        if !state.resize_surface(width, height) {
+            return;
+        }
+        // Ensure pause menu and upgrade menu resize with the window
+        use glyphon::Resolution;
+        let resolution = Resolution { width, height };
+        state
+            .pause_menu
+            .resize(&state.wgpu_renderer.queue, resolution);
+        state
+            .upgrade_menu
+            .resize(&state.wgpu_renderer.queue, resolution);
+        state
+            .game_over_menu
+            .resize(&state.wgpu_renderer.queue, resolution);
+        state
+            .title_menu
+            .resize(&state.wgpu_renderer.queue, resolution);
+        state
+            .codex_menu
+            .resize(&state.wgpu_renderer.queue, resolution);
+        state
+            .error_menu
+            .resize(&state.wgpu_renderer.queue, resolution);
+        state
+            .profile_menu
+            .resize(&state.wgpu_renderer.queue, resolution);
+        state
+            .quick_radial_menu
+            .resize(&state.wgpu_renderer.queue, resolution);
+        state
+            .toast_overlay
+            .resize(&state.wgpu_renderer.queue, resolution);
+        state
+            .announcement_overlay
+            .resize(&state.wgpu_renderer.queue, resolution);
+        state
+            .audio_debug_overlay
+            .resize(&state.wgpu_renderer.queue, resolution);
+    }
+
+    /// Handles the window moving to a display with a different scale factor.
+    ///
+    /// Stores the new factor and then lays everything out again as for a resize, which
+    /// re-sizes the HUD text for the new factor and re-scales button padding. winit follows
+    /// this event with a `Resized` once the window has its new physical size, which lays out
+    /// again with that size.
+    ///
+    /// The egui panels need nothing here: they read the scale factor from the window every
+    /// frame, and the ones open at the time also see the event itself.
+    ///
+    /// # Arguments
+    /// - `scale_factor`: The display's new scale factor
+    pub fn handle_scale_factor_changed(&mut self, scale_factor: f64) {
+        let Some(state) = self.state.as_mut() else {
+            return;
+        };
+        state.scale_factor = scale_factor;
+        if let Some(size) = self.window.as_ref().map(|window| window.inner_size()) {
+            self.handle_resized(size.width, size.height);
         }
     }
 }
@@ -267,9 +859,20 @@ impl ApplicationHandler for App {
                         || state.game_state.current_screen
                             == crate::game::CurrentScreen::ExitReached)
                         && state.game_state.capture_mouse
+                        && state.game_state.capture.is_none()
+                        && !state.input_claims().keeps_cursor_free()
                     {
-                        // Allow mouse movement in both Game and ExitReached screens
-                        state.game_state.player.mouse_movement(delta.0, delta.1);
+                        // Allow mouse movement in both Game and ExitReached screens,
+                        // except while the enemy has the player. The quick-settings
+                        // radial takes the mouse while it is open.
+                        if state.game_state.quick_radial.is_open() {
+                            state
+                                .game_state
+                                .quick_radial
+                                .move_pointer(delta.0 as f32, delta.1 as f32);
+                        } else {
+                            state.game_state.player.mouse_movement(delta.0, delta.1);
+                        }
                     }
                     state.triage_mouse(window);
                 }
@@ -285,12 +888,13 @@ impl ApplicationHandler for App {
     ///
     /// # Arguments
     /// - `event_loop`: The active event loop
-    /// - `_`: Window ID (unused)
+    /// - `window_id`: The window the event is for; debug inspector events go to it
     /// - `event`: The window event to process
     ///
     /// # Event Types Handled
     /// - **CloseRequested**: Initiates application shutdown
     /// - **Resized**: Calls `handle_resized()` to update rendering
+    /// - **ScaleFactorChanged**: Calls `handle_scale_factor_changed()` to re-scale the UI
     /// - **KeyboardInput**: Processes game controls and UI navigation
     /// - **MouseInput**: Handles mouse button presses for UI interaction
     /// - **RedrawRequested**: Triggers frame rendering and game updates
@@ -303,7 +907,12 @@ impl ApplicationHandler for App {
     ///
     /// # Panics
     /// - If application state is not initialized
-    fn window_event(&mut self, event_loop: &ActiveEventLoop, _: WindowId, event: WindowEvent) {
+    fn window_event(
+        &mut self,
+        event_loop: &ActiveEventLoop,
+        window_id: WindowId,
+        event: WindowEvent,
+    ) {
         let state = match self.state.as_mut() {
             Some(state) => state,
             _ => {
@@ -311,8 +920,242 @@ impl ApplicationHandler for App {
             }
         };
 
+        // The debug inspector handles its own events; closing it leaves the game running
+        if self
+            .debug_window
+            .as_ref()
+            .is_some_and(|debug_window| debug_window.id() == window_id)
+        {
+            let close = self.debug_window.as_mut().is_some_and(|debug_window| {
+                debug_window.handle_event(&event, &state.wgpu_renderer, &state.game_state)
+            });
+            if close {
+                self.debug_window = None;
+            }
+            return;
+        }
+
+        // Every key goes to the input context it belongs to. Repeats of a key whose press went
+        // to a context that has since closed, like the Escape that opened the pause menu, must
+        // not reach the context that replaced it
+        let routed = match &event {
+            WindowEvent::KeyboardInput {
+                event: key_event, ..
+            } => {
+                let stack = bindings::context_stack(
+                    state.game_state.current_screen,
+                    state.is_typing(),
+                    state.tuning_panel.is_visible(),
+                    state.sandbox_panel_has_keyboard(),
+                );
+                state.input_router.route(
+                    &stack,
+                    key_event.physical_key,
+                    key_event.state == ElementState::Pressed,
+                    key_event.repeat,
+                )
+            }
+            _ => Routed::Unbound,
+        };
+        if routed == Routed::Stale {
+            return;
+        }
+        // Menus and text fields act on what the key is bound to, not on the key
+        let action = routed.action();
+
+        // Every UI consumer says what it claims before anyone sees the event; a claimed event
+        // goes to its claimant alone, and only unclaimed ones reach gameplay
+        let claims = state.input_claims();
+        let kind = InputKind::of(&event);
+        let claimant = claims.claimant(kind);
+
+        if claims.receives(InputConsumer::TuningPanel, kind)
+            && let Some(window) = self.window.as_ref()
+        {
+            state.tuning_panel.handle_input(window, &event);
+        }
+        if claimant == Some(InputConsumer::TuningPanel) {
+            return;
+        }
+
+        // The title screen's seed and challenge code fields take every key while open
+        let text_entry = claims.receives(InputConsumer::TextEntry, kind);
+        let consumed_by_seed_field = text_entry
+            && match &event {
+                WindowEvent::KeyboardInput {
+                    event: key_event, ..
+                } => {
+                    state.game_state.current_screen == crate::game::CurrentScreen::Title
+                        && state.title_menu.handle_seed_key(
+                            key_event,
+                            action,
+                            &mut state.game_state.chosen_seed,
+                            &mut state.game_state.challenge,
+                        )
+                }
+                WindowEvent::Ime(ime) => {
+                    state.game_state.current_screen == crate::game::CurrentScreen::Title
+                        && state.title_menu.handle_seed_ime(ime)
+                }
+                _ => false,
+            };
+        if consumed_by_seed_field {
+            if !state.title_menu.is_editing_seed() {
+                // An entered challenge picks the mode; a different seed drops the challenge
+                let game_state = &mut state.game_state;
+                match &game_state.challenge {
+                    Some(challenge) if Some(challenge.seed) == game_state.chosen_seed => {
+                        game_state.hardcore = challenge.hardcore;
+                    }
+                    _ => game_state.challenge = None,
+                }
+                // A seed typed for a hardcore run must not be one a hardcore death burned
+                let chosen_seed = state.game_state.chosen_seed;
+                let refused = self
+                    .window
+                    .as_ref()
+                    .is_some_and(|window| state.refuse_burned_seed(window, chosen_seed));
+                if refused {
+                    state.game_state.chosen_seed = None;
+                    state.game_state.challenge = None;
+                }
+            }
+            return;
+        }
+
+        // The profile picker's name field takes every key while open
+        if text_entry && state.game_state.current_screen == crate::game::CurrentScreen::Profiles {
+            let consumed_by_profile_field = match &event {
+                WindowEvent::KeyboardInput {
+                    event: key_event, ..
+                } => state.profile_menu.handle_key(key_event, action),
+                WindowEvent::Ime(ime) => state.profile_menu.handle_ime(ime),
+                _ => false,
+            };
+            let profile_action = state.profile_menu.get_last_action();
+            state.apply_profile_action(profile_action);
+            if consumed_by_profile_field {
+                return;
+            }
+        }
+
+        // The pause menu's settings search takes every key while open, and so does the controls
+        // page while it waits for a key to bind
+        if let WindowEvent::KeyboardInput {
+            event: key_event, ..
+        } = &event
+            && text_entry
+            && state.game_state.current_screen == crate::game::CurrentScreen::Pause
+        {
+            if state.pause_menu.handle_binding_key(key_event) {
+                let pause_action = state.pause_menu.get_last_action();
+                self.apply_menu_actions(
+                    pause_action,
+                    crate::renderer::ui::game_over_menu::GameOverAction::None,
+                    crate::renderer::ui::title_menu::TitleAction::None,
+                );
+                return;
+            }
+            if state.pause_menu.handle_filter_key(key_event, action) {
+                return;
+            }
+        }
+
+        // The codex screen takes its navigation keys before anything else sees them
+        let buttons = claims.receives(InputConsumer::Buttons, kind);
+        if buttons && state.game_state.current_screen == crate::game::CurrentScreen::Codex {
+            let consumed_by_codex =
+                state
+                    .codex_menu
+                    .handle_input(&event, action, &mut state.game_state.audio_manager);
+            if state.codex_menu.get_last_action()
+                == crate::renderer::ui::codex_menu::CodexAction::Back
+            {
+                state.game_state.current_screen = crate::game::CurrentScreen::Title;
+            }
+            if consumed_by_codex {
+                return;
+            }
+        }
+
+        // The profile picker takes all input until the player picks a profile or goes back
+        if buttons && state.game_state.current_screen == crate::game::CurrentScreen::Profiles {
+            state
+                .profile_menu
+                .handle_input(&event, action, &mut state.game_state.audio_manager);
+            let profile_action = state.profile_menu.get_last_action();
+            state.apply_profile_action(profile_action);
+            if matches!(
+                event,
+                WindowEvent::KeyboardInput { .. } | WindowEvent::MouseInput { .. }
+            ) {
+                return;
+            }
+        }
+
+        // The error screen takes all input until the player returns to the title screen
+        if buttons && state.game_state.current_screen == crate::game::CurrentScreen::Error {
+            state
+                .error_menu
+                .handle_input(&event, &mut state.game_state.audio_manager);
+            if state.error_menu.get_last_action()
+                == crate::renderer::ui::error_menu::ErrorAction::ReturnToTitle
+            {
+                state.leave_error_screen();
+                self.panic_guard.recovered();
+            }
+            if matches!(
+                event,
+                WindowEvent::KeyboardInput { .. } | WindowEvent::MouseInput { .. }
+            ) {
+                return;
+            }
+        }
+
+        // Any key or click while the solution path pulses on the loading screen skips the pulse
+        let pressed = match &event {
+            WindowEvent::KeyboardInput {
+                event: key_event, ..
+            } => key_event.state == ElementState::Pressed,
+            WindowEvent::MouseInput { state, .. } => *state == ElementState::Pressed,
+            _ => false,
+        };
+        if pressed
+            && claims.receives(InputConsumer::Gameplay, kind)
+            && state.game_state.current_screen == crate::game::CurrentScreen::Loading
+            && state
+                .wgpu_renderer
+                .loading_screen_renderer
+                .is_revealing_path(state.settings.reduce_effects)
+        {
+            state
+                .wgpu_renderer
+                .loading_screen_renderer
+                .skip_path_reveal();
+            return;
+        }
+
+        // The sandbox panel takes Tab and Escape, and every key while it has the focus
+        if let (Some(window), Some(session)) =
+            (self.window.as_ref(), state.game_state.sandbox.as_mut())
+            && claims.receives(InputConsumer::SandboxPanel, kind)
+        {
+            let consumed_by_sandbox = state
+                .sandbox_panel
+                .handle_input(window, &event, action, session);
+            let sandbox_action = state.sandbox_panel.take_action();
+            state.apply_sandbox_action(sandbox_action, window);
+            // Cursor moves and the like go on to everyone even while egui is dragging
+            if claimant == Some(InputConsumer::SandboxPanel)
+                || (consumed_by_sandbox && kind != InputKind::Broadcast)
+            {
+                return;
+            }
+        }
+
         // If in pause menu, pass all input events to the pause menu first
-        let pause_action = if state.game_state.current_screen == crate::game::CurrentScreen::Pause
+        let pause_action = if buttons
+            && state.game_state.current_screen == crate::game::CurrentScreen::Pause
             && state.pause_menu.is_visible()
         {
             state
@@ -323,229 +1166,68 @@ impl ApplicationHandler for App {
             crate::renderer::ui::pause_menu::PauseMenuAction::None
         };
 
+        // If on the game over screen, pass input events to its buttons
+        let game_over_action = if buttons
+            && state.game_state.current_screen == crate::game::CurrentScreen::GameOver
+            && state.game_over_menu.is_visible()
+        {
+            state
+                .game_over_menu
+                .handle_input(&event, &mut state.game_state.audio_manager);
+            state.game_over_menu.get_last_action()
+        } else {
+            crate::renderer::ui::game_over_menu::GameOverAction::None
+        };
+
+        // If on the title screen, pass input events to its buttons
+        let title_action = if buttons
+            && state.game_state.current_screen == crate::game::CurrentScreen::Title
+            && state.title_menu.is_visible()
+        {
+            state
+                .title_menu
+                .handle_input(&event, &mut state.game_state.audio_manager);
+            state.title_menu.get_last_action()
+        } else {
+            crate::renderer::ui::title_menu::TitleAction::None
+        };
+
         // If in upgrade menu, pass all input events to the upgrade menu first
-        if state.game_state.current_screen == crate::game::CurrentScreen::UpgradeMenu
+        if buttons
+            && state.game_state.current_screen == crate::game::CurrentScreen::UpgradeMenu
             && state.upgrade_menu.is_visible()
         {
             state
                 .upgrade_menu
-                .handle_input(&event, &mut state.game_state);
+                .handle_input(&event, action, &mut state.game_state);
         }
 
-        // Handle pause menu actions
-        match pause_action {
-            crate::renderer::ui::pause_menu::PauseMenuAction::Resume => {
-                // Return to previous screen or default to Game
-                if let Some(previous_screen) = state.game_state.previous_screen {
-                    state.game_state.current_screen = previous_screen;
-                    state.game_state.previous_screen = None;
-
-                    match previous_screen {
-                        crate::game::CurrentScreen::Game => {
-                            // Resume game
-                            if !state.game_state.is_test_mode {
-                                // Only resume timer in normal mode (enemy locking is handled in update loop)
-                                state.game_state.game_ui.resume_timer();
-                            }
-                            // In test mode, keep timer paused (enemy locking is handled in update loop)
-                            // Lock cursor
-                            state.game_state.capture_mouse = true;
-                            // Restore game audio volumes
-                            state
-                                .game_state
-                                .audio_manager
-                                .set_game_volumes()
-                                .expect("Failed to set game volumes");
-                        }
-                        crate::game::CurrentScreen::Title => {
-                            // Return to title screen - cursor should be unlocked
-                            state.game_state.capture_mouse = false;
-                            // Set title screen audio volumes
-                            state
-                                .game_state
-                                .audio_manager
-                                .set_title_screen_volumes()
-                                .expect("Failed to set title screen volumes");
-                        }
-                        _ => {
-                            // For other screens, just unlock cursor
-                            state.game_state.capture_mouse = false;
-                        }
-                    }
-                } else {
-                    // Fallback: return to game
-                    state.game_state.current_screen = crate::game::CurrentScreen::Game;
-                    if !state.game_state.is_test_mode {
-                        // Only resume timer in normal mode (enemy locking is handled in update loop)
-                        state.game_state.game_ui.resume_timer();
-                    }
-                    // In test mode, keep timer paused (enemy locking is handled in update loop)
-                    state.game_state.capture_mouse = true;
-                    // Restore game audio volumes
-                    state
-                        .game_state
-                        .audio_manager
-                        .set_game_volumes()
-                        .expect("Failed to set game volumes");
-                }
-                state.pause_menu.hide();
-            }
-            crate::renderer::ui::pause_menu::PauseMenuAction::Restart => {
-                // Restart current run - handle this after the match to avoid borrow issues
-                state.game_state.current_screen = crate::game::CurrentScreen::NewGame;
-                state.game_state.previous_screen = None; // Clear previous screen
-                state.pause_menu.hide();
-                // Restore game audio volumes for new game
-                state
-                    .game_state
-                    .audio_manager
-                    .set_game_volumes()
-                    .expect("Failed to set game volumes");
-                // Ensure mouse is captured for the new game
-                state.game_state.capture_mouse = true;
-                // Apply mouse capture immediately
-                if let Some(window) = self.window.as_ref() {
-                    state.triage_mouse(window);
-                }
-
-                // Hide title screen elements when transitioning away from title
-                if let Err(e) = state
-                    .text_renderer
-                    .set_buffer_visibility("title_mirador_overlay", false)
-                {
-                    eprintln!("Failed to hide title_mirador_overlay: {}", e);
-                }
-                if let Err(e) = state
-                    .text_renderer
-                    .set_buffer_visibility("title_subtitle_overlay", false)
-                {
-                    eprintln!("Failed to hide title_subtitle_overlay: {}", e);
-                }
-            }
-            crate::renderer::ui::pause_menu::PauseMenuAction::ToggleTestMode => {
-                // Toggle between test mode and normal mode
-                if state.game_state.is_test_mode {
-                    // Currently in test mode, switch to normal mode (loading screen)
-                    state.game_state.is_test_mode = false;
-                    state.game_state.current_screen = crate::game::CurrentScreen::Loading;
-                    state.game_state.previous_screen = None; // Clear previous screen
-                    state.pause_menu.hide();
-                    // Restore game audio volumes for normal mode
-                    state
-                        .game_state
-                        .audio_manager
-                        .set_game_volumes()
-                        .expect("Failed to set game volumes");
-
-                    // Recapture mouse when exiting test mode
-                    state.game_state.capture_mouse = true;
-
-                    // Reset to normal game state
-                    state.game_state.maze_path = None;
-                    state.wgpu_renderer.loading_screen_renderer = LoadingRenderer::new(
-                        &state.wgpu_renderer.device,
-                        &state.wgpu_renderer.surface_config,
-                    );
-                    // Clear previous level state
-                    state.game_state.player = crate::game::player::Player::new();
-                    state.game_state.enemy.pathfinder.position = [0.0, 30.0, 0.0];
-                    state.game_state.enemy.pathfinder.locked = true;
-                    state.game_state.exit_cell = None;
-
-                    // Reset score and level to starting values
-                    state.game_state.set_score(0);
-                    state.game_state.set_level(1);
-
-                    // Stop and reset timer with normal game configuration
-                    state.game_state.game_ui.timer = None; // Clear the test timer
-                    // The timer will be properly initialized when the game starts (in update_game_ui)
-
-                    // Hide title screen elements when transitioning away from title
-                    if let Err(e) = state
-                        .text_renderer
-                        .set_buffer_visibility("title_mirador_overlay", false)
-                    {
-                        eprintln!("Failed to hide title_mirador_overlay: {}", e);
-                    }
-                    if let Err(e) = state
-                        .text_renderer
-                        .set_buffer_visibility("title_subtitle_overlay", false)
-                    {
-                        eprintln!("Failed to hide title_subtitle_overlay: {}", e);
-                    }
-                } else {
-                    // Currently in normal mode, switch to test mode
-                    state.game_state.is_test_mode = true;
-                    state.game_state.current_screen = crate::game::CurrentScreen::Game;
-                    state.game_state.previous_screen = None; // Clear previous screen
-                    state.pause_menu.hide();
-
-                    // Recapture mouse for test mode
-                    state.game_state.capture_mouse = true;
-
-                    // Set up test environment
-                    crate::test_mode::setup_test_environment(
-                        &mut state.game_state,
-                        &mut state.wgpu_renderer,
-                    );
-                    // Set a dummy maze path to prevent re-entry
-                    state.game_state.maze_path = Some(std::path::PathBuf::from("test_mode"));
-                }
+        // Handle what was picked on the menus
+        self.apply_menu_actions(pause_action, game_over_action, title_action);
+        let Some(state) = self.state.as_mut() else {
+            return;
+        };
 
-                // Hide title screen elements when transitioning away from title
-                if let Err(e) = state
-                    .text_renderer
-                    .set_buffer_visibility("title_mirador_overlay", false)
-                {
-                    eprintln!("Failed to hide title_mirador_overlay: {}", e);
-                }
-                if let Err(e) = state
-                    .text_renderer
-                    .set_buffer_visibility("title_subtitle_overlay", false)
-                {
-                    eprintln!("Failed to hide title_subtitle_overlay: {}", e);
-                }
-            }
-            crate::renderer::ui::pause_menu::PauseMenuAction::QuitToMenu => {
-                // Quit to lobby (title screen)
-                state.game_state.current_screen = crate::game::CurrentScreen::Title;
-                state.game_state.previous_screen = None; // Clear previous screen
-                state.pause_menu.hide();
-                // Reset game state
-                state.game_state = crate::game::GameState::new();
-                // Reset loading screen renderer to ensure new maze generation
-                state.wgpu_renderer.loading_screen_renderer = LoadingRenderer::new(
-                    &state.wgpu_renderer.device,
-                    &state.wgpu_renderer.surface_config,
-                );
-                // Set title screen audio volumes
-                state
-                    .game_state
-                    .audio_manager
-                    .set_title_screen_volumes()
-                    .expect("Failed to set title screen volumes");
-                // Show title screen elements
-                if let Err(e) = state
-                    .text_renderer
-                    .set_buffer_visibility("title_mirador_overlay", true)
-                {
-                    eprintln!("Failed to show title_mirador_overlay: {}", e);
-                }
-                if let Err(e) = state
-                    .text_renderer
-                    .set_buffer_visibility("title_subtitle_overlay", true)
-                {
-                    eprintln!("Failed to show title_subtitle_overlay: {}", e);
+        // Gameplay only gets what no UI claimed. A claimed release still lets go of the
+        // button, in case its press went to gameplay before the UI took the pointer.
+        if !claims.receives(InputConsumer::Gameplay, kind) {
+            if let WindowEvent::MouseInput {
+                state: ElementState::Released,
+                button,
+                ..
+            } = event
+            {
+                match button {
+                    MouseButton::Left => state
+                        .key_state
+                        .release_key(crate::game::keys::GameKey::MouseButtonLeft),
+                    MouseButton::Right => state
+                        .key_state
+                        .release_key(crate::game::keys::GameKey::MouseButtonRight),
+                    _ => {}
                 }
             }
-            crate::renderer::ui::pause_menu::PauseMenuAction::QuitApp => {
-                // Save benchmark results before quitting
-                self.save_benchmark_results();
-
-                // Quit the application
-                std::process::exit(0);
-            }
-            crate::renderer::ui::pause_menu::PauseMenuAction::None => {}
+            return;
         }
 
         match event {
@@ -555,8 +1237,12 @@ impl ApplicationHandler for App {
                 // Save benchmark results before shutting down
                 self.save_benchmark_results();
 
-                // Ensure all GPU operations are complete before shutting down
+                // The debug inspector closes with the game
+                self.debug_window = None;
+
+                // Ensure all GPU operations are complete and background threads stop
                 if let Some(state) = &mut self.state {
+                    state.stop_test_mode_watcher();
                     state.wgpu_renderer.cleanup();
                 }
 
@@ -567,17 +1253,59 @@ impl ApplicationHandler for App {
                 self.handle_resized(new_size.width, new_size.height);
             }
 
+            WindowEvent::ScaleFactorChanged { scale_factor, .. } => {
+                self.handle_scale_factor_changed(scale_factor);
+            }
+
+            WindowEvent::Focused(focused) => {
+                if let Some(state) = &mut self.state {
+                    state.window_focused = focused;
+                    // Releases of held keys, like the quick-settings key, would go to another window
+                    if !focused {
+                        state.game_state.quick_radial.cancel();
+                        state.input_router.clear_held();
+                    }
+                    // Alt-tabbing away doesn't cost the player time; the pause menu holds its
+                    // own pause, so refocusing behind it leaves the timer stopped
+                    if focused {
+                        state
+                            .game_state
+                            .game_ui
+                            .resume_timer(PauseSource::FocusLoss);
+                    } else {
+                        state.game_state.game_ui.pause_timer(PauseSource::FocusLoss);
+                    }
+                }
+            }
+
             WindowEvent::KeyboardInput {
                 event:
                     KeyEvent {
-                        logical_key: key,
+                        physical_key: PhysicalKey::Code(key),
                         state: key_state,
                         repeat: false,
                         ..
                     },
                 ..
             } => {
-                if let Some(game_key) = crate::game::keys::winit_key_to_game_key(&key) {
+                // Only what the key's context binds reaches the game; keys a menu or text field
+                // consumed do nothing here
+                let game_key = match routed {
+                    Routed::Action { action, .. }
+                    | Routed::Released {
+                        action: Some(action),
+                        ..
+                    } => action.game_key(),
+                    // A release whose press the router never saw, like a key held down since
+                    // before the window had focus, still lets go of it
+                    Routed::Unbound if key_state == ElementState::Released => state
+                        .input_router
+                        .bindings(bindings::BindingContext::Gameplay)
+                        .action_for(key)
+                        .and_then(bindings::Action::game_key),
+                    _ => None,
+                };
+                if let Some(game_key) = game_key {
                     match key_state {
                         ElementState::Pressed => {
                             state.key_state.press_key(game_key);
@@ -601,11 +1329,121 @@ impl ApplicationHandler for App {
                                         .wgpu_renderer
                                         .game_renderer
                                         .debug_renderer
-                                        .debug_render_bounding_boxes = !state
-                                        .wgpu_renderer
-                                        .game_renderer
-                                        .debug_renderer
-                                        .debug_render_bounding_boxes;
+                                        .cycle_bounding_boxes();
+                                }
+                                crate::game::keys::GameKey::ToggleMinimap => {
+                                    let minimap = &mut state.wgpu_renderer.minimap_renderer;
+                                    minimap.visible = !minimap.visible;
+                                }
+                                crate::game::keys::GameKey::ToggleFrameHeatmap
+                                    if cfg!(debug_assertions) || state.game_state.is_test_mode =>
+                                {
+                                    let heatmap =
+                                        &mut state.wgpu_renderer.game_renderer.heatmap_renderer;
+                                    heatmap.visible = !heatmap.visible;
+                                }
+                                crate::game::keys::GameKey::ExportFrameHeatmap
+                                    if cfg!(debug_assertions) || state.game_state.is_test_mode =>
+                                {
+                                    match state.game_state.frame_heatmap.export() {
+                                        Ok(path) => println!(
+                                            "[HEATMAP] Frame heatmap saved to {}",
+                                            path.display()
+                                        ),
+                                        Err(e) => eprintln!("[HEATMAP] {}", e),
+                                    }
+                                }
+                                crate::game::keys::GameKey::ToggleLatencyProbe
+                                    if cfg!(debug_assertions) =>
+                                {
+                                    let config = &state.wgpu_renderer.surface_config;
+                                    if state.latency_probe.toggle() {
+                                        println!(
+                                            "[LATENCY] Measurement on (F8 to probe); present mode {:?}, desired_maximum_frame_latency {}",
+                                            config.present_mode,
+                                            config.desired_maximum_frame_latency
+                                        );
+                                    } else {
+                                        println!(
+                                            "[LATENCY] Measurement off\n{}",
+                                            crate::benchmarks::latency::overlay_label(
+                                                state.latency_probe.percentiles(),
+                                                config.present_mode,
+                                                config.desired_maximum_frame_latency,
+                                            )
+                                        );
+                                    }
+                                }
+                                crate::game::keys::GameKey::LatencyProbe
+                                    if cfg!(debug_assertions)
+                                        && state.latency_probe.is_enabled() =>
+                                {
+                                    // Stamped as early as the event reaches the game
+                                    state.latency_probe.record_input(Instant::now());
+                                }
+                                crate::game::keys::GameKey::LatencyProbe
+                                    if cfg!(debug_assertions) || state.game_state.is_test_mode =>
+                                {
+                                    state.toggle_audio_debug();
+                                }
+                                crate::game::keys::GameKey::DumpAudioSnapshot
+                                    if cfg!(debug_assertions) || state.game_state.is_test_mode =>
+                                {
+                                    state.dump_audio_snapshot();
+                                }
+                                crate::game::keys::GameKey::DebugPanic
+                                    if cfg!(debug_assertions) =>
+                                {
+                                    // Panics inside the next frame, to try the error screen
+                                    state.debug_panic_requested = true;
+                                }
+                                crate::game::keys::GameKey::ToggleDebugWindow
+                                    if cfg!(debug_assertions) || state.game_state.is_test_mode =>
+                                {
+                                    self.debug_window = match self.debug_window.take() {
+                                        Some(_) => None,
+                                        None => DebugWindow::open(
+                                            event_loop,
+                                            &self.instance,
+                                            &state.wgpu_renderer,
+                                        )
+                                        .inspect_err(|e| eprintln!("[DEBUG WINDOW] {}", e))
+                                        .ok(),
+                                    };
+                                }
+                                crate::game::keys::GameKey::Interact
+                                    if state.game_state.current_screen
+                                        == crate::game::CurrentScreen::Game =>
+                                {
+                                    state.collect_anomaly();
+                                }
+                                crate::game::keys::GameKey::QuickRadial
+                                    if state.game_state.quick_radial_allowed() =>
+                                {
+                                    state.game_state.quick_radial.open();
+                                }
+                                crate::game::keys::GameKey::ToggleTuningPanel => {
+                                    if cfg!(debug_assertions) {
+                                        state.tuning_panel.toggle();
+                                        // Free the cursor so the panel can be used mid-game
+                                        if state.game_state.current_screen
+                                            == crate::game::CurrentScreen::Game
+                                        {
+                                            state.game_state.capture_mouse =
+                                                !state.tuning_panel.is_visible();
+                                        }
+                                    }
+                                }
+                                crate::game::keys::GameKey::ToggleUpgradeMenu
+                                | crate::game::keys::GameKey::Escape
+                                    if state.game_state.capture.is_some() =>
+                                {
+                                    // The capture sequence can't be paused or skipped
+                                }
+                                crate::game::keys::GameKey::ToggleUpgradeMenu
+                                    if state.game_state.sandbox.is_some() =>
+                                {
+                                    // Upgrades belong to the run the sandbox set aside
                                 }
                                 crate::game::keys::GameKey::ToggleUpgradeMenu => {
                                     // Toggle upgrade menu visibility
@@ -632,23 +1470,7 @@ impl ApplicationHandler for App {
                                 crate::game::keys::GameKey::Escape => {
                                     match state.game_state.current_screen {
                                         crate::game::CurrentScreen::Game => {
-                                            // Enter pause menu
-                                            state.game_state.previous_screen =
-                                                Some(crate::game::CurrentScreen::Game);
-                                            state.game_state.current_screen =
-                                                crate::game::CurrentScreen::Pause;
-                                            // Pause timer (enemy locking is handled in update loop)
-                                            state.game_state.game_ui.pause_timer();
-                                            // Unlock cursor
-                                            state.game_state.capture_mouse = false;
-                                            // Show pause menu with current test mode state
-                                            state.pause_menu.show(state.game_state.is_test_mode);
-                                            // Set pause menu audio volumes
-                                            state
-                                                .game_state
-                                                .audio_manager
-                                                .set_pause_menu_volumes()
-                                                .expect("Failed to set pause menu volumes");
+                                            state.pause_game();
                                         }
                                         crate::game::CurrentScreen::Pause => {
                                             // Return to previous screen
@@ -663,7 +1485,10 @@ impl ApplicationHandler for App {
                                                         // Resume game
                                                         if !state.game_state.is_test_mode {
                                                             // Only resume timer in normal mode (enemy locking is handled in update loop)
-                                                            state.game_state.game_ui.resume_timer();
+                                                            state
+                                                                .game_state
+                                                                .game_ui
+                                                                .resume_timer(PauseSource::Menu);
                                                         }
                                                         // In test mode, keep timer paused (enemy locking is handled in update loop)
                                                         // Lock cursor
@@ -730,6 +1555,10 @@ impl ApplicationHandler for App {
                         }
                         ElementState::Released => {
                             state.key_state.release_key(game_key);
+
+                            if game_key == crate::game::keys::GameKey::QuickRadial {
+                                state.release_quick_radial();
+                            }
                         }
                     }
                 }
@@ -744,35 +1573,28 @@ impl ApplicationHandler for App {
                     if let Some(app_state) = self.state.as_mut() {
                         match button {
                             MouseButton::Left => {
-                                // If on title screen, transition to loading
+                                // If on title screen, transition to loading, unless the seed
+                                // field is open; clicks on title buttons never get here
                                 if app_state.game_state.current_screen
                                     == crate::game::CurrentScreen::Title
+                                    && !app_state.title_menu.is_editing_seed()
                                 {
-                                    // Set game audio volumes before leaving title screen
-                                    app_state
-                                        .game_state
-                                        .audio_manager
-                                        .set_game_volumes()
-                                        .expect("Failed to set game volumes");
-                                    app_state.game_state.current_screen =
-                                        crate::game::CurrentScreen::Loading;
-                                    // Optionally, lock mouse here if needed
-                                    app_state.game_state.capture_mouse = true;
-                                    // Hide the overlay text
-                                    if let Err(e) = app_state
-                                        .text_renderer
-                                        .set_buffer_visibility("title_mirador_overlay", false)
-                                    {
-                                        eprintln!("Failed to hide title_mirador_overlay: {}", e);
-                                    }
-                                    // Hide the subtitle overlay
-                                    if let Err(e) = app_state
-                                        .text_renderer
-                                        .set_buffer_visibility("title_subtitle_overlay", false)
-                                    {
-                                        eprintln!("Failed to hide title_subtitle_overlay: {}", e);
+                                    let chosen_seed = app_state.game_state.chosen_seed;
+                                    let refused = self.window.as_ref().is_some_and(|window| {
+                                        app_state.refuse_burned_seed(window, chosen_seed)
+                                    });
+                                    if !refused {
+                                        app_state.leave_title_screen();
                                     }
                                 }
+                                if app_state.settings.click_to_move
+                                    && app_state.game_state.current_screen
+                                        == crate::game::CurrentScreen::Game
+                                    && app_state.game_state.capture_mouse
+                                    && app_state.game_state.capture.is_none()
+                                {
+                                    app_state.click_to_move();
+                                }
                                 app_state
                                     .key_state
                                     .press_key(crate::game::keys::GameKey::MouseButtonLeft);
@@ -808,7 +1630,15 @@ impl ApplicationHandler for App {
             WindowEvent::RedrawRequested => {
                 let current_time = Instant::now();
                 self.handle_frame_timing(current_time);
+                self.poll_gamepads();
                 self.handle_redraw();
+                self.drive_soak(current_time.elapsed());
+                self.pace_frame();
+
+                // The debug inspector follows the game's frames
+                if let Some(debug_window) = &self.debug_window {
+                    debug_window.request_redraw();
+                }
             }
 
             _ => {
@@ -0,0 +1,252 @@
+//! Frame pacing for Mirador.
+//!
+//! This module provides an optional frame rate cap applied at the end of each
+//! redraw. Without a cap the title screen and menus can render thousands of
+//! frames per second when vsync is off, which needlessly heats up the GPU.
+//!
+//! # Key Features
+//!
+//! - **Separate caps**: Menu screens and gameplay each have their own [`FpsCap`]
+//! - **Hybrid sleep**: `std::thread::sleep` covers the bulk of the wait and a short
+//!   spin covers the final stretch for accurate frame times
+//! - **Drift-free deadlines**: Frame deadlines advance by a fixed budget, so small
+//!   oversleeps do not accumulate over time
+//!
+//! # Usage
+//!
+//! ```rust,ignore
+//! let mut limiter = FrameLimiter::new();
+//!
+//! // At the start of each frame
+//! limiter.begin_frame();
+//!
+//! // ... update and render ...
+//!
+//! // After presenting
+//! limiter.wait(FpsCap::Fps60);
+//! ```
+//!
+//! Game systems measure delta time from real frame timestamps, so capping the
+//! frame rate only lengthens each step; it never makes simulated time drift.
+
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+
+/// How long before the deadline to stop sleeping and start spinning.
+///
+/// OS sleep granularity is typically around 1 ms, so the last millisecond is
+/// busy-waited to land close to the target frame time.
+const SPIN_THRESHOLD: Duration = Duration::from_millis(1);
+
+/// Lowest frame rate accepted for a custom cap.
+const MIN_CUSTOM_FPS: u32 = 10;
+
+/// Highest frame rate accepted for a custom cap.
+const MAX_CUSTOM_FPS: u32 = 1000;
+
+/// A frame rate limit setting.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FpsCap {
+    /// No limit; frames are presented as fast as the surface allows
+    Off,
+    /// Limit to 30 frames per second
+    Fps30,
+    /// Limit to 60 frames per second
+    Fps60,
+    /// Limit to 120 frames per second
+    Fps120,
+    /// Limit to a user-chosen frame rate
+    Custom(u32),
+}
+
+impl FpsCap {
+    /// Returns the frame rate limit in frames per second.
+    ///
+    /// Custom values are clamped to a sane range so a bad settings file
+    /// cannot stall the game.
+    ///
+    /// # Returns
+    /// `Some(fps)` for capped settings, `None` for [`FpsCap::Off`]
+    pub fn fps(self) -> Option<u32> {
+        match self {
+            FpsCap::Off => None,
+            FpsCap::Fps30 => Some(30),
+            FpsCap::Fps60 => Some(60),
+            FpsCap::Fps120 => Some(120),
+            FpsCap::Custom(fps) => Some(fps.clamp(MIN_CUSTOM_FPS, MAX_CUSTOM_FPS)),
+        }
+    }
+
+    /// Returns the time budget for a single frame under this cap.
+    ///
+    /// # Returns
+    /// `Some(duration)` for capped settings, `None` for [`FpsCap::Off`]
+    pub fn frame_budget(self) -> Option<Duration> {
+        self.fps()
+            .map(|fps| Duration::from_secs_f64(1.0 / fps as f64))
+    }
+
+    /// Returns the next preset in the settings menu cycle.
+    ///
+    /// The cycle is Off → 30 → 60 → 120 → Off. Custom values continue at Off.
+    pub fn next(self) -> Self {
+        match self {
+            FpsCap::Off => FpsCap::Fps30,
+            FpsCap::Fps30 => FpsCap::Fps60,
+            FpsCap::Fps60 => FpsCap::Fps120,
+            FpsCap::Fps120 | FpsCap::Custom(_) => FpsCap::Off,
+        }
+    }
+
+    /// Returns a short label for menus and the debug overlay.
+    pub fn label(self) -> String {
+        match self.fps() {
+            Some(fps) => fps.to_string(),
+            None => "Off".to_string(),
+        }
+    }
+}
+
+/// Sleeps at the end of each frame to hold the frame rate at a cap.
+///
+/// The limiter tracks a deadline for the next frame. Each capped frame
+/// pushes the deadline forward by exactly one frame budget, and the deadline
+/// is re-anchored to the current time whenever a frame runs long, so the
+/// limiter never tries to "catch up" with a burst of unpaced frames.
+#[derive(Debug, Clone)]
+pub struct FrameLimiter {
+    /// When the current frame started
+    frame_start: Instant,
+    /// When the next frame is allowed to start, if a cap was applied last frame
+    next_deadline: Option<Instant>,
+}
+
+impl Default for FrameLimiter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FrameLimiter {
+    /// Creates a new frame limiter with no pending deadline.
+    pub fn new() -> Self {
+        Self {
+            frame_start: Instant::now(),
+            next_deadline: None,
+        }
+    }
+
+    /// Records the start of a new frame.
+    ///
+    /// Call this before any update or render work for the frame.
+    pub fn begin_frame(&mut self) {
+        self.frame_start = Instant::now();
+    }
+
+    /// Blocks until the frame budget for `cap` has elapsed.
+    ///
+    /// With [`FpsCap::Off`] this returns immediately and clears any pending
+    /// deadline so a later cap starts fresh.
+    ///
+    /// # Arguments
+    /// * `cap` - The cap in effect for the frame that was just presented
+    pub fn wait(&mut self, cap: FpsCap) {
+        let Some(budget) = cap.frame_budget() else {
+            self.next_deadline = None;
+            return;
+        };
+
+        let deadline = next_deadline(self.next_deadline, self.frame_start, budget, Instant::now());
+        sleep_until(deadline);
+        self.next_deadline = Some(deadline);
+    }
+}
+
+/// Computes the deadline for the frame that just finished.
+///
+/// Deadlines advance by exactly one budget from the previous deadline, which
+/// keeps the average frame time on target. If that deadline has already passed
+/// (the frame ran long or the cap just turned on), the deadline is re-anchored
+/// to one budget after the frame start instead.
+///
+/// # Arguments
+/// * `previous` - The previous frame's deadline, if any
+/// * `frame_start` - When the current frame started
+/// * `budget` - Time allowed per frame
+/// * `now` - The current time
+///
+/// # Returns
+/// The instant at which the next frame may begin
+fn next_deadline(
+    previous: Option<Instant>,
+    frame_start: Instant,
+    budget: Duration,
+    now: Instant,
+) -> Instant {
+    match previous {
+        Some(previous) if previous + budget >= now => previous + budget,
+        _ => (frame_start + budget).max(now),
+    }
+}
+
+/// Waits until `deadline` using a sleep for the bulk of the time and a spin
+/// for the final [`SPIN_THRESHOLD`].
+///
+/// # Arguments
+/// * `deadline` - The instant to wait until
+fn sleep_until(deadline: Instant) {
+    let now = Instant::now();
+    if deadline <= now {
+        return;
+    }
+
+    let remaining = deadline - now;
+    if remaining > SPIN_THRESHOLD {
+        std::thread::sleep(remaining - SPIN_THRESHOLD);
+    }
+
+    while Instant::now() < deadline {
+        std::hint::spin_loop();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_custom_cap_is_clamped() {
+        assert_eq!(FpsCap::Custom(0).fps(), Some(MIN_CUSTOM_FPS));
+        assert_eq!(FpsCap::Custom(100_000).fps(), Some(MAX_CUSTOM_FPS));
+        assert_eq!(FpsCap::Custom(144).fps(), Some(144));
+    }
+
+    #[test]
+    fn test_deadlines_do_not_drift() {
+        let budget = Duration::from_millis(16);
+        let start = Instant::now();
+
+        // First capped frame anchors to the frame start
+        let first = next_deadline(None, start, budget, start + Duration::from_millis(4));
+        assert_eq!(first, start + budget);
+
+        // A slightly late wake-up does not push later deadlines back
+        let second = next_deadline(
+            Some(first),
+            first + Duration::from_micros(300),
+            budget,
+            first + Duration::from_millis(10),
+        );
+        assert_eq!(second, start + budget * 2);
+    }
+
+    #[test]
+    fn test_long_frame_reanchors_deadline() {
+        let budget = Duration::from_millis(16);
+        let start = Instant::now();
+        let late = start + Duration::from_millis(100);
+
+        let deadline = next_deadline(Some(start), start + Duration::from_millis(60), budget, late);
+        assert_eq!(deadline, late);
+    }
+}
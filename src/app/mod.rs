@@ -7,7 +7,15 @@
 //! # Module Structure
 //!
 //! - [`app_state`]: Contains the [`AppState`] struct which holds all application state
+//! - [`cli`]: The `mirador` command line, which the binary hands its arguments to
+//! - [`clipboard`]: Copies text like challenge codes to the system clipboard
+//! - [`debug_window`]: A second OS window with benchmark, journal and game state inspectors
 //! - [`event_handler`]: Contains the [`App`] struct and event handling logic
+//! - [`frame_limiter`]: Optional frame rate cap applied after each frame is presented
+//! - [`panic_guard`]: Catches panics from the frame update so the game can show an error screen
+//! - [`profiles`]: Player profiles, each with its own settings and progress
+//! - [`settings`]: Persistent user settings such as frame rate caps
+//! - [`settings_registry`]: Declarative list of the settings that menus search and reset
 //! - [`update`]: Contains the main game update loop and rendering logic
 //!
 //! # Architecture
@@ -31,8 +39,102 @@
 //! avoid complex synchronization issues.
 
 pub mod app_state;
+pub mod cli;
+pub mod clipboard;
+pub mod debug_window;
 pub mod event_handler;
+pub mod frame_limiter;
+pub mod panic_guard;
+pub mod profiles;
+pub mod settings;
+pub mod settings_registry;
 pub mod update;
 
 pub use app_state::AppState;
 pub use event_handler::App;
+
+use crate::game;
+use crate::renderer::backend::GraphicsBackend;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use winit::event_loop::{ControlFlow, EventLoop};
+
+/// Runs the game in its own window until the player closes it.
+///
+/// This function creates the event loop, initializes the application state,
+/// and starts the game. It handles the complete lifecycle of the application
+/// from startup to shutdown. Call [`crate::paths::init`] first to move where files
+/// are saved.
+///
+/// # Arguments
+/// - `backend`: The graphics backend to ask wgpu for
+///
+/// # Returns
+/// This function runs indefinitely until the application is closed by the user.
+///
+/// # Errors
+/// - Returns early if event loop creation fails
+/// - Exits the process if the application fails to run
+pub async fn run(backend: GraphicsBackend) {
+    use crate::benchmarks::{BenchmarkConfig, Profiler};
+
+    // Initialize profiler for overall application initialization benchmarking
+    let mut init_profiler = Profiler::new(BenchmarkConfig {
+        enabled: true,
+        print_results: false, // Respect user's console output preference
+        write_to_file: false,
+        min_duration_threshold: std::time::Duration::from_micros(1),
+        max_samples: 1000,
+        autosave_interval: std::time::Duration::from_secs(60),
+        max_sections: 256,
+    });
+
+    // Benchmark complete application initialization
+    init_profiler.start_section("complete_application_initialization");
+
+    // Set up signal handler for graceful shutdown
+    let running = Arc::new(AtomicBool::new(true));
+    let r = running.clone();
+
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        ctrlc::set_handler(move || {
+            println!("\nReceived interrupt signal, saving benchmark results...");
+            // Save benchmark results before exiting
+            if let Err(e) = crate::benchmarks::utils::force_save_results() {
+                eprintln!("Failed to save benchmark results on exit: {}", e);
+            }
+            match game::journal::session().flush(game::journal::FlushReason::Interrupted) {
+                Ok(path) => println!("Session journal written to {}", path.display()),
+                Err(e) => eprintln!("Failed to write session journal on exit: {}", e),
+            }
+            r.store(false, Ordering::SeqCst);
+            std::process::exit(0);
+        })
+        .expect("Error setting Ctrl-C handler");
+    }
+
+    let event_loop = match EventLoop::new() {
+        Ok(event_loop) => event_loop,
+        Err(err) => {
+            eprintln!("Error creating event loop: {}", err);
+            return;
+        }
+    };
+
+    // Configure event loop for better cross-platform compatibility
+    event_loop.set_control_flow(ControlFlow::Poll);
+
+    // Windows-specific configuration
+    #[cfg(target_os = "windows")]
+    {
+        // Ensure Windows processes events with proper timing
+        event_loop.set_control_flow(ControlFlow::Wait);
+    }
+
+    let mut app = App::new(backend);
+
+    event_loop.run_app(&mut app).expect("Failed to run app");
+
+    init_profiler.end_section("complete_application_initialization");
+}
@@ -0,0 +1,146 @@
+//! Panic recovery at the frame boundary.
+//!
+//! Each frame's update and command recording run inside [`catch_panic`], which catches a panic
+//! instead of letting it unwind out of the event loop and take the window with it. The
+//! [`PanicGuard`] answers the first panic with [`Recovery::ShowError`]: the caller saves what it can and shows the
+//! error screen, whose "Return to Title" button starts over with a fresh game state. A panic while
+//! the error screen is up gets [`Recovery::Exit`], since the game can't even show the
+//! error, and the caller saves what it can and exits.
+//!
+//! The session journal is written by the panic hook (see [`crate::game::journal`]) before the
+//! panic reaches the guard, so nothing here needs to flush it.
+//!
+//! In debug builds F12 calls [`deliberate_panic`] from inside the frame, to try the recovery
+//! path by hand.
+
+use std::any::Any;
+use std::panic::{self, UnwindSafe};
+
+/// Message of the panic raised by [`deliberate_panic`].
+pub const DELIBERATE_PANIC_MESSAGE: &str = "Deliberate panic requested from the debug key";
+
+/// What the caller should do about a panic the guard caught.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Recovery {
+    /// Show the error screen with the panic message
+    ShowError(String),
+    /// The error screen itself panicked; save what can be saved and exit
+    Exit(String),
+}
+
+/// Catches panics from the frame update and decides how to recover from them.
+#[derive(Debug, Default)]
+pub struct PanicGuard {
+    /// Whether a panic was caught and the player hasn't returned to the title screen since
+    recovering: bool,
+}
+
+impl PanicGuard {
+    /// Decides how to recover from a panic the frame raised.
+    ///
+    /// # Arguments
+    /// * `message` - The panic message, from [`catch_panic`]
+    ///
+    /// # Returns
+    /// [`Recovery::ShowError`] for the first panic, or [`Recovery::Exit`] if the error screen
+    /// from an earlier one is still up
+    pub fn on_panic(&mut self, message: String) -> Recovery {
+        if self.recovering {
+            Recovery::Exit(message)
+        } else {
+            self.recovering = true;
+            Recovery::ShowError(message)
+        }
+    }
+
+    /// Marks the error screen as dismissed, so the next panic shows it again.
+    pub fn recovered(&mut self) {
+        self.recovering = false;
+    }
+}
+
+/// Runs part of a frame, catching any panic it raises.
+///
+/// # Arguments
+/// * `frame` - The part of the frame to run
+///
+/// # Returns
+/// The frame's result, or the panic message if it panicked
+pub fn catch_panic<T>(frame: impl FnOnce() -> T + UnwindSafe) -> Result<T, String> {
+    panic::catch_unwind(frame).map_err(|payload| panic_message(payload.as_ref()))
+}
+
+/// Panics on purpose, to exercise the recovery path.
+pub fn deliberate_panic() -> ! {
+    panic!("{}", DELIBERATE_PANIC_MESSAGE);
+}
+
+/// Reads the message out of a panic payload.
+///
+/// # Arguments
+/// * `payload` - The payload caught from the panic
+///
+/// # Returns
+/// The message passed to `panic!`, or a placeholder for payloads that aren't strings
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "The game panicked with no message".to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Runs a frame the way the app does, turning a panic into a recovery.
+    fn run<T>(
+        guard: &mut PanicGuard,
+        frame: impl FnOnce() -> T + UnwindSafe,
+    ) -> Result<T, Recovery> {
+        catch_panic(frame).map_err(|message| guard.on_panic(message))
+    }
+
+    #[test]
+    fn test_first_panic_shows_the_error_and_a_second_exits() {
+        let mut guard = PanicGuard::default();
+        assert_eq!(run(&mut guard, || 7), Ok(7));
+
+        assert_eq!(
+            run(&mut guard, || deliberate_panic()),
+            Err(Recovery::ShowError(DELIBERATE_PANIC_MESSAGE.to_string()))
+        );
+
+        // Frames that don't panic leave the error screen up
+        assert_eq!(run(&mut guard, || ()), Ok(()));
+        assert_eq!(
+            run(&mut guard, || deliberate_panic()),
+            Err(Recovery::Exit(DELIBERATE_PANIC_MESSAGE.to_string()))
+        );
+    }
+
+    #[test]
+    fn test_returning_to_title_rearms_the_error_screen() {
+        let mut guard = PanicGuard::default();
+        let _ = run(&mut guard, || deliberate_panic());
+        guard.recovered();
+
+        let level = 3;
+        assert_eq!(
+            run(&mut guard, || panic!("Level {} has no exit", level)),
+            Err::<(), _>(Recovery::ShowError("Level 3 has no exit".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_payloads_without_a_message_get_a_placeholder() {
+        let payload: Box<dyn Any + Send> = Box::new(42);
+        assert_eq!(
+            panic_message(payload.as_ref()),
+            "The game panicked with no message"
+        );
+    }
+}
@@ -0,0 +1,500 @@
+//! Player profiles, so several people sharing a machine keep their own progress.
+//!
+//! A profile is a directory under [`Paths::profiles_dir`] holding one player's settings,
+//! daily challenge results, hardcore graveyard, and maze codex with its unlocks (see
+//! [`Paths::profile_files`]). The profile played last is named in
+//! [`Paths::active_profile_file`] and picked up again at the next launch.
+//!
+//! Data saved before profiles existed sits at the top of the data directory. [`start_up`]
+//! moves it into a [`DEFAULT_PROFILE`] the first time it runs, and
+//! [`take_migration_notice`] tells the game once that it happened so it can say so.
+//!
+//! Profile names become directory names, so [`validate_name`] only lets through letters,
+//! digits, spaces, hyphens and underscores. Deleting a profile also checks that the directory
+//! it removes really is one profile's, so no name can reach files outside it.
+//!
+//! # Usage
+//!
+//! ```rust,ignore
+//! let profile = profiles::start_up(&paths::get());
+//! paths::set_profile(profile.as_deref());
+//!
+//! let name = profiles::create_profile(&paths::get(), " Robin ")?;
+//! profiles::save_active_profile(&paths::get(), &name)?;
+//! paths::set_profile(Some(&name));
+//! ```
+
+use crate::game::codex::Codex;
+use crate::game::daily::DailyResults;
+use crate::paths::Paths;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Profile that data saved before profiles existed is moved into.
+pub const DEFAULT_PROFILE: &str = "Default";
+
+/// Most characters in a profile name.
+pub const MAX_NAME_LENGTH: usize = 20;
+
+/// Most profiles on one machine, so the profile list always fits on screen.
+pub const MAX_PROFILES: usize = 8;
+
+/// Toast shown once after old data was moved into [`DEFAULT_PROFILE`].
+pub const MIGRATION_NOTICE: &str =
+    "Your progress moved into the Default profile; switch profiles on the title screen";
+
+/// Names Windows won't allow as a file or directory name, whatever the extension.
+const RESERVED_NAMES: [&str; 22] = [
+    "CON", "PRN", "AUX", "NUL", "COM1", "COM2", "COM3", "COM4", "COM5", "COM6", "COM7", "COM8",
+    "COM9", "LPT1", "LPT2", "LPT3", "LPT4", "LPT5", "LPT6", "LPT7", "LPT8", "LPT9",
+];
+
+/// Set when [`start_up`] moved old data into a profile, until the notice is taken.
+static MIGRATED: AtomicBool = AtomicBool::new(false);
+
+/// Checks a typed profile name and tidies it up.
+///
+/// # Arguments
+/// * `name` - The name as typed
+///
+/// # Returns
+/// The name without surrounding spaces, or an error message saying what is wrong with it
+pub fn validate_name(name: &str) -> Result<String, String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err("Profile names can't be empty".to_string());
+    }
+    if name.chars().count() > MAX_NAME_LENGTH {
+        return Err(format!(
+            "Profile names can be at most {} characters",
+            MAX_NAME_LENGTH
+        ));
+    }
+    if let Some(ch) = name
+        .chars()
+        .find(|&ch| !(ch.is_alphanumeric() || matches!(ch, ' ' | '-' | '_')))
+    {
+        return Err(format!("Profile names can't contain '{}'", ch));
+    }
+    if RESERVED_NAMES
+        .iter()
+        .any(|reserved| reserved.eq_ignore_ascii_case(name))
+    {
+        return Err(format!("{} can't be used as a profile name", name));
+    }
+    Ok(name.to_string())
+}
+
+/// Lists the profiles on this machine.
+///
+/// # Arguments
+/// * `paths` - The data paths; the active profile doesn't matter
+///
+/// # Returns
+/// The name of every profile directory, sorted without regard to case
+pub fn profile_names(paths: &Paths) -> Vec<String> {
+    let Ok(entries) = fs::read_dir(paths.profiles_dir()) else {
+        return Vec::new();
+    };
+    let mut names: Vec<String> = entries
+        .flatten()
+        // The entry's own type, so a link to a directory elsewhere isn't taken for a profile
+        .filter(|entry| entry.file_type().is_ok_and(|kind| kind.is_dir()))
+        .filter_map(|entry| entry.file_name().into_string().ok())
+        .filter(|name| validate_name(name).as_deref() == Ok(name.as_str()))
+        .collect();
+    names.sort_by_key(|name| name.to_lowercase());
+    names
+}
+
+/// Returns the existing profile whose name matches, ignoring case.
+///
+/// # Arguments
+/// * `paths` - The data paths
+/// * `name` - The name to look for
+fn find_profile(paths: &Paths, name: &str) -> Option<String> {
+    profile_names(paths)
+        .into_iter()
+        .find(|existing| existing.to_lowercase() == name.to_lowercase())
+}
+
+/// Returns the profile to play as: the one played last, or else the first one.
+///
+/// # Arguments
+/// * `paths` - The data paths
+///
+/// # Returns
+/// The profile's name, or `None` when there are no profiles yet
+pub fn active_profile(paths: &Paths) -> Option<String> {
+    let names = profile_names(paths);
+    let saved = fs::read_to_string(paths.active_profile_file()).unwrap_or_default();
+    names
+        .iter()
+        .find(|name| name.as_str() == saved.trim())
+        .or(names.first())
+        .cloned()
+}
+
+/// Remembers the profile being played for the next launch.
+///
+/// # Arguments
+/// * `paths` - The data paths
+/// * `name` - The profile's name
+///
+/// # Returns
+/// `Ok(())` on success, or an error message if the file could not be written
+pub fn save_active_profile(paths: &Paths, name: &str) -> Result<(), String> {
+    let path = paths.active_profile_file();
+    fs::create_dir_all(paths.profiles_dir())
+        .and_then(|_| fs::write(&path, name))
+        .map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
+
+/// Creates an empty profile.
+///
+/// # Arguments
+/// * `paths` - The data paths
+/// * `name` - The name as typed
+///
+/// # Returns
+/// The new profile's tidied name, or an error message if the name is invalid or taken, there
+/// are already [`MAX_PROFILES`], or the directory could not be made
+pub fn create_profile(paths: &Paths, name: &str) -> Result<String, String> {
+    let name = validate_name(name)?;
+    if let Some(existing) = find_profile(paths, &name) {
+        return Err(format!("There is already a profile named {}", existing));
+    }
+    if profile_names(paths).len() >= MAX_PROFILES {
+        return Err(format!("There can be at most {} profiles", MAX_PROFILES));
+    }
+    let dir = profile_dir(paths, &name);
+    fs::create_dir_all(&dir)
+        .map_err(|e| format!("Failed to create profile {}: {}", dir.display(), e))?;
+    Ok(name)
+}
+
+/// Renames a profile, keeping everything in it.
+///
+/// The active profile file follows the rename; the caller still has to point
+/// [`crate::paths::set_profile`] at the new name if the renamed profile is being played.
+///
+/// # Arguments
+/// * `paths` - The data paths
+/// * `name` - The profile's current name
+/// * `new_name` - The new name as typed
+///
+/// # Returns
+/// The profile's tidied new name, or an error message if the profile doesn't exist, the new
+/// name is invalid or taken, or the directory could not be renamed
+pub fn rename_profile(paths: &Paths, name: &str, new_name: &str) -> Result<String, String> {
+    let Some(name) = find_profile(paths, name) else {
+        return Err(format!("There is no profile named {}", name));
+    };
+    let new_name = validate_name(new_name)?;
+    if new_name == name {
+        return Ok(name);
+    }
+    // A change of case alone is still a rename of the same profile
+    if let Some(existing) = find_profile(paths, &new_name).filter(|existing| *existing != name) {
+        return Err(format!("There is already a profile named {}", existing));
+    }
+    let was_active = active_profile(paths).as_deref() == Some(name.as_str());
+    fs::rename(profile_dir(paths, &name), profile_dir(paths, &new_name))
+        .map_err(|e| format!("Failed to rename profile {}: {}", name, e))?;
+    if was_active {
+        save_active_profile(paths, &new_name)?;
+    }
+    Ok(new_name)
+}
+
+/// Deletes a profile and everything in it.
+///
+/// The player has to type the profile's name again to confirm. Only a real directory directly
+/// inside [`Paths::profiles_dir`] is removed: a name that reaches anywhere else, or a profile
+/// directory that is a link to somewhere else, is refused without touching anything.
+///
+/// # Arguments
+/// * `paths` - The data paths
+/// * `name` - The profile's name
+/// * `confirmation` - The name typed again by the player
+///
+/// # Returns
+/// `Ok(())` once the profile is gone, or an error message if the confirmation doesn't match,
+/// the profile is being played, or the directory isn't safe to remove
+pub fn delete_profile(paths: &Paths, name: &str, confirmation: &str) -> Result<(), String> {
+    let name = validate_name(name)?;
+    if confirmation.trim() != name {
+        return Err(format!("Type {} exactly to delete it", name));
+    }
+    if paths.profile() == Some(name.as_str()) {
+        return Err("Switch to another profile before deleting this one".to_string());
+    }
+
+    let dir = profile_dir(paths, &name);
+    let metadata =
+        fs::symlink_metadata(&dir).map_err(|_| format!("There is no profile named {}", name))?;
+    if !metadata.is_dir() {
+        return Err(format!("{} is not a profile directory", dir.display()));
+    }
+    let profiles_dir = fs::canonicalize(paths.profiles_dir())
+        .map_err(|e| format!("Failed to find the profiles directory: {}", e))?;
+    let target =
+        fs::canonicalize(&dir).map_err(|e| format!("Failed to find profile {}: {}", name, e))?;
+    if target.parent() != Some(profiles_dir.as_path()) {
+        return Err(format!(
+            "{} is outside the profiles directory",
+            dir.display()
+        ));
+    }
+    fs::remove_dir_all(&target).map_err(|e| format!("Failed to delete profile {}: {}", name, e))
+}
+
+/// Returns the directory of a profile, whichever profile is active.
+///
+/// # Arguments
+/// * `paths` - The data paths
+/// * `name` - The profile's validated name
+fn profile_dir(paths: &Paths, name: &str) -> PathBuf {
+    paths.clone().with_profile(Some(name)).profile_dir()
+}
+
+/// Moves data saved before profiles existed into [`DEFAULT_PROFILE`].
+///
+/// Each per-player file or directory left at the top of the data directory is moved, unless
+/// the default profile already has its own. Safe to call on every launch: with nothing left
+/// at the top it does nothing.
+///
+/// # Arguments
+/// * `paths` - The data paths; the active profile doesn't matter
+///
+/// # Returns
+/// Whether anything was moved, or an error message if a move failed
+pub fn migrate_legacy(paths: &Paths) -> Result<bool, String> {
+    let legacy = paths.clone().with_profile(None);
+    let target = paths.clone().with_profile(Some(DEFAULT_PROFILE));
+    let moves: Vec<(PathBuf, PathBuf)> = legacy
+        .profile_files()
+        .into_iter()
+        .zip(target.profile_files())
+        .filter(|(from, to)| from.exists() && !to.exists())
+        .collect();
+    if moves.is_empty() {
+        return Ok(false);
+    }
+
+    fs::create_dir_all(target.profile_dir())
+        .map_err(|e| format!("Failed to create the {} profile: {}", DEFAULT_PROFILE, e))?;
+    for (from, to) in &moves {
+        fs::rename(from, to).map_err(|e| {
+            format!(
+                "Failed to move {} into the {} profile: {}",
+                from.display(),
+                DEFAULT_PROFILE,
+                e
+            )
+        })?;
+    }
+    if active_profile(&legacy).is_none_or(|active| active == DEFAULT_PROFILE) {
+        save_active_profile(&legacy, DEFAULT_PROFILE)?;
+    }
+    Ok(true)
+}
+
+/// Gets the profiles ready at launch and picks the one to play as.
+///
+/// Moves old data into [`DEFAULT_PROFILE`] first; [`take_migration_notice`] reports whether
+/// that happened.
+///
+/// # Arguments
+/// * `paths` - The data paths
+///
+/// # Returns
+/// The profile to play as, or `None` on a first launch with no profiles yet
+pub fn start_up(paths: &Paths) -> Option<String> {
+    match migrate_legacy(paths) {
+        Ok(true) => {
+            println!("Moved saved data into the {} profile", DEFAULT_PROFILE);
+            MIGRATED.store(true, Ordering::Relaxed);
+        }
+        Ok(false) => {}
+        Err(e) => eprintln!("{}", e),
+    }
+    active_profile(paths)
+}
+
+/// Returns whether [`start_up`] moved old data into a profile, only the first time it's asked.
+pub fn take_migration_notice() -> bool {
+    MIGRATED.swap(false, Ordering::Relaxed)
+}
+
+/// A profile's progress at a glance, for the profile list.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProfileSummary {
+    /// The profile's name
+    pub name: String,
+    /// Levels escaped, across every kind of maze
+    pub completions: u32,
+    /// Levels that ended in a death
+    pub deaths: u32,
+    /// Cosmetics unlocked
+    pub unlocks: usize,
+    /// Daily challenges played
+    pub dailies: usize,
+}
+
+impl ProfileSummary {
+    /// Reads a profile's progress from its files.
+    ///
+    /// # Arguments
+    /// * `paths` - The data paths
+    /// * `name` - The profile's name
+    pub fn load(paths: &Paths, name: &str) -> Self {
+        let profile = paths.clone().with_profile(Some(name));
+        let codex = Codex::load_from(&profile.codex());
+        Self {
+            name: name.to_string(),
+            completions: codex.entries.iter().map(|entry| entry.completions).sum(),
+            deaths: codex.entries.iter().map(|entry| entry.deaths).sum(),
+            unlocks: codex.unlocks.unlocked.len(),
+            dailies: DailyResults::load_from(&profile.daily_results())
+                .results
+                .len(),
+        }
+    }
+
+    /// Returns the stats as one short line.
+    pub fn stats_line(&self) -> String {
+        format!(
+            "{} escaped, {} deaths, {} unlocks, {} dailies",
+            self.completions, self.deaths, self.unlocks, self.dailies
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A fresh data directory for one test.
+    fn fresh_root(name: &str) -> Paths {
+        let root = std::env::temp_dir().join(format!("mirador_profiles_{}", name));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(&root).unwrap();
+        Paths::with_root(root)
+    }
+
+    #[test]
+    fn test_names_that_could_leave_the_profiles_directory_are_refused() {
+        assert_eq!(validate_name("  Robin "), Ok("Robin".to_string()));
+        assert_eq!(validate_name("Sam_2-b"), Ok("Sam_2-b".to_string()));
+        for name in [
+            "", "   ", "..", "../x", "a/b", "a\\b", ".hidden", "C:", "nul", "Com1",
+        ] {
+            assert!(validate_name(name).is_err(), "{:?} was allowed", name);
+        }
+        assert!(validate_name(&"x".repeat(MAX_NAME_LENGTH + 1)).is_err());
+    }
+
+    #[test]
+    fn test_create_rename_and_pick_profiles() {
+        let paths = fresh_root("create");
+        assert_eq!(active_profile(&paths), None);
+
+        assert_eq!(create_profile(&paths, "Sam"), Ok("Sam".to_string()));
+        assert_eq!(create_profile(&paths, "robin"), Ok("robin".to_string()));
+        assert!(create_profile(&paths, "SAM").is_err());
+        assert_eq!(profile_names(&paths), vec!["robin", "Sam"]);
+        assert_eq!(active_profile(&paths), Some("robin".to_string()));
+
+        save_active_profile(&paths, "Sam").unwrap();
+        assert_eq!(
+            rename_profile(&paths, "sam", "Sammy"),
+            Ok("Sammy".to_string())
+        );
+        assert!(rename_profile(&paths, "Sammy", "Robin").is_err());
+        assert_eq!(
+            rename_profile(&paths, "robin", "Robin"),
+            Ok("Robin".to_string())
+        );
+        assert_eq!(active_profile(&paths), Some("Sammy".to_string()));
+        assert!(profile_dir(&paths, "Sammy").is_dir());
+
+        let _ = fs::remove_dir_all(paths.root());
+    }
+
+    #[test]
+    fn test_old_data_moves_into_the_default_profile_once() {
+        let paths = fresh_root("migrate");
+        fs::write(paths.settings(), "{}").unwrap();
+        fs::write(paths.codex(), "{}").unwrap();
+        fs::create_dir_all(paths.codex_thumbnails_dir()).unwrap();
+        fs::write(paths.codex_thumbnail("prim"), b"png").unwrap();
+        // Shared files stay where they are
+        fs::create_dir_all(paths.journal_dir()).unwrap();
+
+        assert_eq!(start_up(&paths), Some(DEFAULT_PROFILE.to_string()));
+        assert!(take_migration_notice());
+        assert!(!take_migration_notice());
+
+        let default = paths.clone().with_profile(Some(DEFAULT_PROFILE));
+        assert_eq!(fs::read_to_string(default.settings()).unwrap(), "{}");
+        assert!(default.codex().is_file());
+        assert!(default.codex_thumbnail("prim").is_file());
+        assert!(!default.daily_results().exists());
+        for legacy in paths.profile_files() {
+            assert!(!legacy.exists(), "{} was left behind", legacy.display());
+        }
+        assert!(paths.journal_dir().is_dir());
+
+        // A second launch finds nothing to move
+        assert_eq!(migrate_legacy(&paths), Ok(false));
+        assert_eq!(start_up(&paths), Some(DEFAULT_PROFILE.to_string()));
+
+        let _ = fs::remove_dir_all(paths.root());
+    }
+
+    #[test]
+    fn test_first_launch_has_nothing_to_migrate() {
+        let paths = fresh_root("fresh");
+        assert_eq!(migrate_legacy(&paths), Ok(false));
+        assert_eq!(start_up(&paths), None);
+        assert!(!paths.profiles_dir().exists());
+
+        let _ = fs::remove_dir_all(paths.root());
+    }
+
+    #[test]
+    fn test_delete_only_removes_the_profile_directory() {
+        let paths = fresh_root("delete");
+        let outside = paths.root().join("keep.txt");
+        fs::write(&outside, "keep").unwrap();
+        create_profile(&paths, "Robin").unwrap();
+        create_profile(&paths, "Sam").unwrap();
+        let sam = paths.clone().with_profile(Some("Sam"));
+        fs::write(sam.settings(), "{}").unwrap();
+
+        // Names that would climb out, or point elsewhere, never get as far as the disk
+        for name in ["..", "../..", "Sam/..", "/", ""] {
+            assert!(delete_profile(&paths, name, name).is_err());
+        }
+        assert!(delete_profile(&paths, "Sam", "sam").is_err());
+        assert!(delete_profile(&sam, "Sam", "Sam").is_err());
+
+        // A profile directory that is a link to somewhere else is left alone
+        #[cfg(unix)]
+        {
+            std::os::unix::fs::symlink(paths.root(), paths.profiles_dir().join("Linked")).unwrap();
+            assert!(delete_profile(&paths, "Linked", "Linked").is_err());
+            assert!(!profile_names(&paths).contains(&"Linked".to_string()));
+        }
+
+        assert_eq!(delete_profile(&paths, "Sam", " Sam "), Ok(()));
+        assert!(!sam.profile_dir().exists());
+        assert_eq!(profile_names(&paths), vec!["Robin"]);
+        assert_eq!(fs::read_to_string(&outside).unwrap(), "keep");
+        assert!(paths.profiles_dir().is_dir());
+
+        let _ = fs::remove_dir_all(paths.root());
+    }
+}
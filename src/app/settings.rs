@@ -0,0 +1,253 @@
+//! Persistent user settings for Mirador.
+//!
+//! Settings are stored as JSON in the data directory (see [`crate::paths`]),
+//! alongside the journals and benchmark results. A missing or unreadable
+//! file falls back to [`Settings::default`] so a bad file never blocks startup.
+//!
+//! # Usage
+//!
+//! ```rust,ignore
+//! let mut settings = Settings::load();
+//! settings.menu_fps_cap = FpsCap::Fps30;
+//! if let Err(e) = settings.save() {
+//!     eprintln!("Failed to save settings: {}", e);
+//! }
+//! ```
+
+use crate::app::frame_limiter::FpsCap;
+use crate::game::CurrentScreen;
+use crate::game::anomaly::Cosmetic;
+use crate::game::input::{DEFAULT_DEADZONE, DEFAULT_LOOK_SENSITIVITY};
+use crate::game::spatial_audio::OutputProfile;
+use crate::game::time_of_day::TimeOfDayPin;
+use crate::paths;
+use crate::renderer::backend::GraphicsBackend;
+use crate::renderer::game_renderer::panorama::BackgroundMode;
+use crate::renderer::game_renderer::reticle::ReticleSettings;
+use crate::renderer::gpu_memory::{BYTES_PER_MB, DEFAULT_BUDGET_BYTES};
+use crate::renderer::present_mode::PresentModePreference;
+use crate::renderer::safe_area::{DEFAULT_HUD_MAX_ASPECT, ViewFit};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+/// User-configurable settings that persist between sessions.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    /// Frame rate cap used on the title, pause, upgrade, and loading screens
+    pub menu_fps_cap: FpsCap,
+    /// Frame rate cap used while playing a level
+    pub gameplay_fps_cap: FpsCap,
+    /// Tones down lingering visual effects such as the enemy trail
+    pub reduce_effects: bool,
+    /// Lets the enemy adapt to how well the player is doing within a run
+    pub adaptive_difficulty: bool,
+    /// Widest aspect ratio the HUD is laid out in; wider windows keep the HUD centered
+    pub hud_max_aspect: f32,
+    /// Whether the 3D view fills wide windows or is boxed to `hud_max_aspect`
+    pub view_fit: ViewFit,
+    /// Strength of controller rumble from `0.0` (off) to `1.0` (full)
+    pub haptics_intensity: f32,
+    /// Degrees per second the view turns with the gamepad's look stick pushed all the way
+    pub gamepad_look_sensitivity: f32,
+    /// How far off center a gamepad stick can rest before it counts, from `0.0` to below `1.0`
+    pub gamepad_deadzone: f32,
+    /// Shape, size, color, and opacity of the center-screen reticle
+    pub reticle: ReticleSettings,
+    /// Lets clicking the floor under the reticle walk the player there
+    pub click_to_move: bool,
+    /// Paints arrows on the walls near the entrance on every level, not just the first few
+    pub navigation_hints: bool,
+    /// Texture memory in megabytes the renderer may use before it logs a warning
+    pub gpu_memory_budget_mb: u32,
+    /// Graphics API to render with; also set by the `--backend` flag
+    pub graphics_backend: GraphicsBackend,
+    /// Samples per pixel of the 3D view's anti-aliasing: 1 (off), 2, or 4; applied at startup
+    pub msaa_samples: u32,
+    /// Whether frames wait for the display's refresh, and how they are presented if not
+    pub present_mode: PresentModePreference,
+    /// Delays distant enemy sounds by their travel time and pitches them with doppler
+    pub enhanced_audio: bool,
+    /// Whether the starfield or a sky panorama is drawn behind the maze
+    pub background: BackgroundMode,
+    /// Cosmetics switched on in the pause menu; ones not yet unlocked are ignored
+    pub cosmetics: Vec<Cosmetic>,
+    /// Volume of the ambient soundscape from `0.0` (off) to `1.0` (full)
+    pub ambience_volume: f32,
+    /// Whether the scene's tint follows the run from dusk to pre-dawn or stays at one hour
+    pub time_of_day: TimeOfDayPin,
+    /// The device the player listens through, which decides how enemy sounds are panned
+    pub audio_output: OutputProfile,
+    /// Renders the enemy through HRTF on headphones, where the audio backend supports it
+    pub hrtf: bool,
+    /// Announces timer warnings, the enemy, the exit's direction, and level results as text,
+    /// read aloud where text-to-speech is available
+    pub announcements: bool,
+}
+
+impl Default for Settings {
+    /// Menus are capped at 60 FPS, gameplay is uncapped, effects are at full strength,
+    /// the enemy adapts to the player, the HUD stays within 21:9 while the 3D view
+    /// fills the window, controller rumble is at full strength, the look stick turns 180
+    /// degrees a second past a 15% deadzone, the reticle is a
+    /// small white dot, click-to-move and navigation hints are off, textures are
+    /// budgeted 512 MB, wgpu picks the graphics backend, the 3D view is drawn with
+    /// 4x MSAA, frames wait for vsync, enhanced audio is on, the starfield is drawn behind the maze, no
+    /// cosmetics are switched on, the ambience plays at full volume, the time of day follows the run, enemy sounds
+    /// are mixed for speakers with HRTF on wherever it is supported, and
+    /// announcements are off.
+    fn default() -> Self {
+        Self {
+            menu_fps_cap: FpsCap::Fps60,
+            gameplay_fps_cap: FpsCap::Off,
+            reduce_effects: false,
+            adaptive_difficulty: true,
+            hud_max_aspect: DEFAULT_HUD_MAX_ASPECT,
+            view_fit: ViewFit::FullWidth,
+            haptics_intensity: 1.0,
+            gamepad_look_sensitivity: DEFAULT_LOOK_SENSITIVITY,
+            gamepad_deadzone: DEFAULT_DEADZONE,
+            reticle: ReticleSettings::default(),
+            click_to_move: false,
+            navigation_hints: false,
+            gpu_memory_budget_mb: (DEFAULT_BUDGET_BYTES / BYTES_PER_MB) as u32,
+            graphics_backend: GraphicsBackend::Auto,
+            msaa_samples: 4,
+            present_mode: PresentModePreference::Vsync,
+            enhanced_audio: true,
+            background: BackgroundMode::Stars,
+            cosmetics: Vec::new(),
+            ambience_volume: 1.0,
+            time_of_day: TimeOfDayPin::Run,
+            audio_output: OutputProfile::Speakers,
+            hrtf: true,
+            announcements: false,
+        }
+    }
+}
+
+impl Settings {
+    /// Loads settings from the data directory.
+    ///
+    /// # Returns
+    /// The saved settings, or the defaults if the file is missing or invalid
+    pub fn load() -> Self {
+        Self::load_from(&paths::get().settings())
+    }
+
+    /// Loads settings from a specific file.
+    ///
+    /// # Arguments
+    /// * `path` - The settings file to read
+    ///
+    /// # Returns
+    /// The saved settings, or the defaults if the file is missing or invalid
+    pub fn load_from(path: &Path) -> Self {
+        let contents = match fs::read_to_string(path) {
+            Ok(contents) => contents,
+            Err(_) => return Self::default(),
+        };
+
+        match serde_json::from_str(&contents) {
+            Ok(settings) => settings,
+            Err(e) => {
+                eprintln!(
+                    "Failed to parse settings file {}: {}, using defaults",
+                    path.display(),
+                    e
+                );
+                Self::default()
+            }
+        }
+    }
+
+    /// Saves settings to the data directory.
+    ///
+    /// # Returns
+    /// `Ok(())` on success, or an error message if the file could not be written
+    pub fn save(&self) -> Result<(), String> {
+        self.save_to(&paths::get().settings())
+    }
+
+    /// Saves settings to a specific file.
+    ///
+    /// # Arguments
+    /// * `path` - The settings file to write
+    ///
+    /// # Returns
+    /// `Ok(())` on success, or an error message if the file could not be written
+    pub fn save_to(&self, path: &Path) -> Result<(), String> {
+        let contents = serde_json::to_string_pretty(self)
+            .map_err(|e| format!("Failed to serialize settings: {}", e))?;
+        fs::write(path, contents)
+            .map_err(|e| format!("Failed to write settings to {}: {}", path.display(), e))
+    }
+
+    /// Returns the frame rate cap for the given screen.
+    ///
+    /// # Arguments
+    /// * `screen` - The screen currently being shown
+    ///
+    /// # Returns
+    /// The gameplay cap for in-level screens, otherwise the menu cap
+    pub fn fps_cap_for(&self, screen: CurrentScreen) -> FpsCap {
+        match screen {
+            CurrentScreen::Game | CurrentScreen::ExitReached => self.gameplay_fps_cap,
+            _ => self.menu_fps_cap,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::renderer::game_renderer::reticle::ReticleStyle;
+
+    #[test]
+    fn test_menus_capped_even_when_gameplay_uncapped() {
+        let settings = Settings::default();
+        assert_eq!(settings.fps_cap_for(CurrentScreen::Title), FpsCap::Fps60);
+        assert_eq!(settings.fps_cap_for(CurrentScreen::Pause), FpsCap::Fps60);
+        assert_eq!(settings.fps_cap_for(CurrentScreen::Game), FpsCap::Off);
+    }
+
+    #[test]
+    fn test_settings_round_trip() {
+        let path = std::env::temp_dir().join("mirador_settings_round_trip.json");
+        let settings = Settings {
+            menu_fps_cap: FpsCap::Fps30,
+            gameplay_fps_cap: FpsCap::Custom(144),
+            reduce_effects: true,
+            adaptive_difficulty: false,
+            hud_max_aspect: 16.0 / 9.0,
+            view_fit: ViewFit::Boxed,
+            haptics_intensity: 0.5,
+            gamepad_look_sensitivity: 240.0,
+            gamepad_deadzone: 0.25,
+            reticle: ReticleSettings {
+                style: ReticleStyle::Cross,
+                size: 10.0,
+                color: [0.2, 1.0, 0.4],
+                opacity: 0.5,
+            },
+            click_to_move: true,
+            navigation_hints: true,
+            gpu_memory_budget_mb: 1024,
+            graphics_backend: GraphicsBackend::Gl,
+            msaa_samples: 2,
+            present_mode: PresentModePreference::Immediate,
+            enhanced_audio: false,
+            background: BackgroundMode::Panorama,
+            cosmetics: vec![Cosmetic::EmberStars],
+            ambience_volume: 0.25,
+            time_of_day: TimeOfDayPin::PreDawn,
+            audio_output: OutputProfile::Mono,
+            hrtf: false,
+            announcements: true,
+        };
+        settings.save_to(&path).expect("save settings");
+        assert_eq!(Settings::load_from(&path), settings);
+        let _ = fs::remove_file(&path);
+    }
+}
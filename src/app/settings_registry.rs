@@ -0,0 +1,531 @@
+//! Declarative list of every user setting.
+//!
+//! Each entry in [`SETTINGS`] describes one field of [`Settings`]: the label shown for it,
+//! extra words it can be searched by, how to copy and compare it between two [`Settings`],
+//! which [`ApplyHook`] makes a change take effect while the game is running, and which
+//! [`Capability`] the machine needs for it to do anything. Menus build their settings rows
+//! from this list instead of by hand, so a new setting only needs a field and an entry here
+//! to be searchable and resettable.
+//!
+//! # Usage
+//!
+//! ```rust,ignore
+//! // Hide the rows that don't match what the player typed
+//! for setting in SETTINGS {
+//!     menu.set_row_visible(setting.id, setting.matches(&filter));
+//! }
+//!
+//! // Put everything back, then re-apply each part of the game once
+//! for hook in restore_defaults(&mut settings) {
+//!     state.apply_settings_hook(hook);
+//! }
+//! ```
+
+use crate::app::settings::Settings;
+
+/// The part of the running game that has to be told when a setting changes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ApplyHook {
+    /// Frame rate caps, shown on the pause menu
+    FrameRate,
+    /// Audio volumes and effects
+    Audio,
+    /// Renderer options such as the background, the reticle, and effect strength
+    Renderer,
+    /// The HUD and view areas, which every screen lays itself out in
+    Layout,
+    /// Gameplay helpers such as the adaptive enemy and navigation hints
+    Gameplay,
+    /// Cosmetics, shown on the pause menu's cosmetic buttons
+    Cosmetics,
+}
+
+impl ApplyHook {
+    /// Every hook, for when all the settings change at once, as on switching profiles.
+    pub const ALL: [ApplyHook; 6] = [
+        ApplyHook::FrameRate,
+        ApplyHook::Audio,
+        ApplyHook::Renderer,
+        ApplyHook::Layout,
+        ApplyHook::Gameplay,
+        ApplyHook::Cosmetics,
+    ];
+}
+
+/// Something a setting needs from the machine the game runs on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Capability {
+    /// The audio backend can render sounds through HRTF
+    Hrtf,
+}
+
+/// What the machine the game runs on supports, detected once at startup.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Capabilities {
+    /// Whether the audio backend can render sounds through HRTF
+    pub hrtf: bool,
+}
+
+impl Capabilities {
+    /// Checks whether the machine has a capability.
+    ///
+    /// # Arguments
+    /// * `capability` - The capability to look for
+    pub fn has(&self, capability: Capability) -> bool {
+        match capability {
+            Capability::Hrtf => self.hrtf,
+        }
+    }
+}
+
+/// One user setting, as shown in the settings menus.
+#[derive(Debug, Clone, Copy)]
+pub struct SettingDescriptor {
+    /// Stable identifier of the setting, the same as its [`Settings`] field
+    pub id: &'static str,
+    /// Label shown for the setting
+    pub label: &'static str,
+    /// Extra words the setting can be searched by, in lowercase
+    pub tags: &'static [&'static str],
+    /// What has to be re-applied after the setting changes
+    pub apply: ApplyHook,
+    /// Copies the setting from the second settings into the first
+    pub copy: fn(&mut Settings, &Settings),
+    /// Whether two settings hold the same value for this setting
+    pub same: fn(&Settings, &Settings) -> bool,
+    /// What the machine has to support for the setting to do anything
+    pub requires: Option<Capability>,
+}
+
+impl SettingDescriptor {
+    /// Checks whether the setting should be shown for a search.
+    ///
+    /// Matching ignores case. Every word of the search has to appear in the label or in
+    /// one of the tags.
+    ///
+    /// # Arguments
+    /// * `filter` - What the player typed; blank shows every setting
+    ///
+    /// # Returns
+    /// `true` if the setting matches the search
+    pub fn matches(&self, filter: &str) -> bool {
+        let label = self.label.to_lowercase();
+        filter.split_whitespace().all(|word| {
+            let word = word.to_lowercase();
+            label.contains(&word) || self.tags.iter().any(|tag| tag.contains(&word))
+        })
+    }
+
+    /// Checks whether the setting is at its default value.
+    ///
+    /// # Arguments
+    /// * `settings` - The current settings
+    pub fn is_default(&self, settings: &Settings) -> bool {
+        (self.same)(settings, &Settings::default())
+    }
+
+    /// Checks whether the setting does anything on this machine.
+    ///
+    /// Menus still list unavailable settings, but show them as such instead of offering a
+    /// control that changes nothing.
+    ///
+    /// # Arguments
+    /// * `capabilities` - What the machine supports
+    pub fn is_available(&self, capabilities: &Capabilities) -> bool {
+        self.requires
+            .is_none_or(|capability| capabilities.has(capability))
+    }
+
+    /// Puts the setting back to its default value, leaving the others alone.
+    ///
+    /// # Arguments
+    /// * `settings` - The settings to change
+    ///
+    /// # Returns
+    /// The hook to run so the change takes effect
+    pub fn reset(&self, settings: &mut Settings) -> ApplyHook {
+        (self.copy)(settings, &Settings::default());
+        self.apply
+    }
+}
+
+/// Every user setting, in the order menus list them.
+pub const SETTINGS: &[SettingDescriptor] = &[
+    SettingDescriptor {
+        id: "menu_fps_cap",
+        label: "Menu frame rate cap",
+        tags: &["fps", "performance", "battery", "limit"],
+        apply: ApplyHook::FrameRate,
+        copy: |to, from| to.menu_fps_cap = from.menu_fps_cap,
+        same: |a, b| a.menu_fps_cap == b.menu_fps_cap,
+        requires: None,
+    },
+    SettingDescriptor {
+        id: "gameplay_fps_cap",
+        label: "Gameplay frame rate cap",
+        tags: &["fps", "performance", "battery", "limit"],
+        apply: ApplyHook::FrameRate,
+        copy: |to, from| to.gameplay_fps_cap = from.gameplay_fps_cap,
+        same: |a, b| a.gameplay_fps_cap == b.gameplay_fps_cap,
+        requires: None,
+    },
+    SettingDescriptor {
+        id: "reduce_effects",
+        label: "Reduce effects",
+        tags: &["accessibility", "motion", "flashing", "trail"],
+        apply: ApplyHook::Renderer,
+        copy: |to, from| to.reduce_effects = from.reduce_effects,
+        same: |a, b| a.reduce_effects == b.reduce_effects,
+        requires: None,
+    },
+    SettingDescriptor {
+        id: "adaptive_difficulty",
+        label: "Adaptive difficulty",
+        tags: &["enemy", "director", "challenge"],
+        apply: ApplyHook::Gameplay,
+        copy: |to, from| to.adaptive_difficulty = from.adaptive_difficulty,
+        same: |a, b| a.adaptive_difficulty == b.adaptive_difficulty,
+        requires: None,
+    },
+    SettingDescriptor {
+        id: "hud_max_aspect",
+        label: "HUD width",
+        tags: &["aspect", "ultrawide", "display", "layout"],
+        apply: ApplyHook::Layout,
+        copy: |to, from| to.hud_max_aspect = from.hud_max_aspect,
+        same: |a, b| a.hud_max_aspect == b.hud_max_aspect,
+        requires: None,
+    },
+    SettingDescriptor {
+        id: "view_fit",
+        label: "View fit",
+        tags: &["aspect", "ultrawide", "display", "layout", "boxed"],
+        apply: ApplyHook::Layout,
+        copy: |to, from| to.view_fit = from.view_fit,
+        same: |a, b| a.view_fit == b.view_fit,
+        requires: None,
+    },
+    SettingDescriptor {
+        id: "haptics_intensity",
+        label: "Controller rumble",
+        tags: &["haptics", "vibration", "gamepad"],
+        apply: ApplyHook::Gameplay,
+        copy: |to, from| to.haptics_intensity = from.haptics_intensity,
+        same: |a, b| a.haptics_intensity == b.haptics_intensity,
+        requires: None,
+    },
+    SettingDescriptor {
+        id: "gamepad_look_sensitivity",
+        label: "Look stick speed",
+        tags: &["gamepad", "controller", "camera", "sensitivity", "turn"],
+        apply: ApplyHook::Gameplay,
+        copy: |to, from| to.gamepad_look_sensitivity = from.gamepad_look_sensitivity,
+        same: |a, b| a.gamepad_look_sensitivity == b.gamepad_look_sensitivity,
+        requires: None,
+    },
+    SettingDescriptor {
+        id: "gamepad_deadzone",
+        label: "Stick deadzone",
+        tags: &["gamepad", "controller", "drift"],
+        apply: ApplyHook::Gameplay,
+        copy: |to, from| to.gamepad_deadzone = from.gamepad_deadzone,
+        same: |a, b| a.gamepad_deadzone == b.gamepad_deadzone,
+        requires: None,
+    },
+    SettingDescriptor {
+        id: "reticle",
+        label: "Reticle",
+        tags: &["crosshair", "aim", "color", "opacity"],
+        apply: ApplyHook::Renderer,
+        copy: |to, from| to.reticle = from.reticle,
+        same: |a, b| a.reticle == b.reticle,
+        requires: None,
+    },
+    SettingDescriptor {
+        id: "click_to_move",
+        label: "Click to move",
+        tags: &["mouse", "accessibility", "walk"],
+        apply: ApplyHook::Gameplay,
+        copy: |to, from| to.click_to_move = from.click_to_move,
+        same: |a, b| a.click_to_move == b.click_to_move,
+        requires: None,
+    },
+    SettingDescriptor {
+        id: "navigation_hints",
+        label: "Navigation hints",
+        tags: &["arrows", "signs", "accessibility", "entrance"],
+        apply: ApplyHook::Gameplay,
+        copy: |to, from| to.navigation_hints = from.navigation_hints,
+        same: |a, b| a.navigation_hints == b.navigation_hints,
+        requires: None,
+    },
+    SettingDescriptor {
+        id: "gpu_memory_budget_mb",
+        label: "Texture memory budget",
+        tags: &["gpu", "vram", "performance"],
+        apply: ApplyHook::Renderer,
+        copy: |to, from| to.gpu_memory_budget_mb = from.gpu_memory_budget_mb,
+        same: |a, b| a.gpu_memory_budget_mb == b.gpu_memory_budget_mb,
+        requires: None,
+    },
+    SettingDescriptor {
+        id: "graphics_backend",
+        label: "Graphics backend",
+        tags: &["gpu", "vulkan", "metal", "dx12", "opengl", "restart"],
+        apply: ApplyHook::Renderer,
+        copy: |to, from| to.graphics_backend = from.graphics_backend,
+        same: |a, b| a.graphics_backend == b.graphics_backend,
+        requires: None,
+    },
+    SettingDescriptor {
+        id: "msaa_samples",
+        label: "Anti-aliasing",
+        tags: &["msaa", "jaggies", "edges", "shimmer", "gpu", "restart"],
+        apply: ApplyHook::Renderer,
+        copy: |to, from| to.msaa_samples = from.msaa_samples,
+        same: |a, b| a.msaa_samples == b.msaa_samples,
+        requires: None,
+    },
+    SettingDescriptor {
+        id: "present_mode",
+        label: "Vsync",
+        tags: &[
+            "present", "tearing", "refresh", "uncapped", "latency", "mailbox",
+        ],
+        apply: ApplyHook::Renderer,
+        copy: |to, from| to.present_mode = from.present_mode,
+        same: |a, b| a.present_mode == b.present_mode,
+        requires: None,
+    },
+    SettingDescriptor {
+        id: "enhanced_audio",
+        label: "Enhanced audio",
+        tags: &["sound", "doppler", "delay", "enemy"],
+        apply: ApplyHook::Audio,
+        copy: |to, from| to.enhanced_audio = from.enhanced_audio,
+        same: |a, b| a.enhanced_audio == b.enhanced_audio,
+        requires: None,
+    },
+    SettingDescriptor {
+        id: "background",
+        label: "Sky",
+        tags: &["background", "stars", "panorama"],
+        apply: ApplyHook::Renderer,
+        copy: |to, from| to.background = from.background,
+        same: |a, b| a.background == b.background,
+        requires: None,
+    },
+    SettingDescriptor {
+        id: "cosmetics",
+        label: "Cosmetics",
+        tags: &["anomaly", "unlocks", "stars"],
+        apply: ApplyHook::Cosmetics,
+        copy: |to, from| to.cosmetics = from.cosmetics.clone(),
+        same: |a, b| a.cosmetics == b.cosmetics,
+        requires: None,
+    },
+    SettingDescriptor {
+        id: "ambience_volume",
+        label: "Ambience",
+        tags: &["volume", "sound", "audio", "soundscape"],
+        apply: ApplyHook::Audio,
+        copy: |to, from| to.ambience_volume = from.ambience_volume,
+        same: |a, b| a.ambience_volume == b.ambience_volume,
+        requires: None,
+    },
+    SettingDescriptor {
+        id: "time_of_day",
+        label: "Time of day",
+        tags: &["tint", "dusk", "night", "dawn", "color", "fog"],
+        apply: ApplyHook::Renderer,
+        copy: |to, from| to.time_of_day = from.time_of_day,
+        same: |a, b| a.time_of_day == b.time_of_day,
+        requires: None,
+    },
+    SettingDescriptor {
+        id: "audio_output",
+        label: "Audio output",
+        tags: &[
+            "sound",
+            "headphones",
+            "speakers",
+            "mono",
+            "panning",
+            "spatial",
+        ],
+        apply: ApplyHook::Audio,
+        copy: |to, from| to.audio_output = from.audio_output,
+        same: |a, b| a.audio_output == b.audio_output,
+        requires: None,
+    },
+    SettingDescriptor {
+        id: "hrtf",
+        label: "HRTF",
+        tags: &["sound", "headphones", "binaural", "spatial", "3d"],
+        apply: ApplyHook::Audio,
+        copy: |to, from| to.hrtf = from.hrtf,
+        same: |a, b| a.hrtf == b.hrtf,
+        requires: Some(Capability::Hrtf),
+    },
+    SettingDescriptor {
+        id: "announcements",
+        label: "Announcements",
+        tags: &[
+            "accessibility",
+            "screen reader",
+            "speech",
+            "tts",
+            "narration",
+            "low vision",
+        ],
+        apply: ApplyHook::Gameplay,
+        copy: |to, from| to.announcements = from.announcements,
+        same: |a, b| a.announcements == b.announcements,
+        requires: None,
+    },
+];
+
+/// Finds a setting by its identifier.
+///
+/// # Arguments
+/// * `id` - The setting's identifier
+///
+/// # Returns
+/// The setting, or `None` if there is no setting with that identifier
+pub fn find(id: &str) -> Option<&'static SettingDescriptor> {
+    SETTINGS.iter().find(|setting| setting.id == id)
+}
+
+/// Puts every setting back to its default value.
+///
+/// # Arguments
+/// * `settings` - The settings to change
+///
+/// # Returns
+/// The hooks to run so the changes take effect, each once, in the order their first
+/// setting is listed
+pub fn restore_defaults(settings: &mut Settings) -> Vec<ApplyHook> {
+    let mut hooks = Vec::new();
+    for setting in SETTINGS {
+        let hook = setting.reset(settings);
+        if !hooks.contains(&hook) {
+            hooks.push(hook);
+        }
+    }
+    hooks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::frame_limiter::FpsCap;
+    use crate::game::anomaly::Cosmetic;
+    use crate::game::spatial_audio::OutputProfile;
+    use crate::game::time_of_day::TimeOfDayPin;
+    use crate::renderer::backend::GraphicsBackend;
+    use crate::renderer::game_renderer::panorama::BackgroundMode;
+    use crate::renderer::game_renderer::reticle::{ReticleSettings, ReticleStyle};
+    use crate::renderer::present_mode::PresentModePreference;
+    use crate::renderer::safe_area::ViewFit;
+    use std::collections::HashSet;
+
+    /// Settings with every field away from its default.
+    fn changed_settings() -> Settings {
+        Settings {
+            menu_fps_cap: FpsCap::Fps30,
+            gameplay_fps_cap: FpsCap::Custom(144),
+            reduce_effects: true,
+            adaptive_difficulty: false,
+            hud_max_aspect: 16.0 / 9.0,
+            view_fit: ViewFit::Boxed,
+            haptics_intensity: 0.5,
+            gamepad_look_sensitivity: 240.0,
+            gamepad_deadzone: 0.25,
+            reticle: ReticleSettings {
+                style: ReticleStyle::Cross,
+                ..ReticleSettings::default()
+            },
+            click_to_move: true,
+            navigation_hints: true,
+            gpu_memory_budget_mb: 1024,
+            graphics_backend: GraphicsBackend::Gl,
+            msaa_samples: 1,
+            present_mode: PresentModePreference::Mailbox,
+            enhanced_audio: false,
+            background: BackgroundMode::Panorama,
+            cosmetics: vec![Cosmetic::EmberStars],
+            ambience_volume: 0.25,
+            time_of_day: TimeOfDayPin::Midnight,
+            audio_output: OutputProfile::Headphones,
+            hrtf: false,
+            announcements: true,
+        }
+    }
+
+    #[test]
+    fn test_filter_matches_labels_and_tags() {
+        let ids = |filter: &str| -> Vec<&str> {
+            SETTINGS
+                .iter()
+                .filter(|setting| setting.matches(filter))
+                .map(|setting| setting.id)
+                .collect()
+        };
+        assert_eq!(ids("").len(), SETTINGS.len());
+        assert_eq!(ids("   ").len(), SETTINGS.len());
+        assert_eq!(ids("fps"), vec!["menu_fps_cap", "gameplay_fps_cap"]);
+        // Case is ignored, and every word has to match
+        assert_eq!(ids("GAMEPLAY fps"), vec!["gameplay_fps_cap"]);
+        assert_eq!(ids("crosshair"), vec!["reticle"]);
+        assert!(ids("volume").contains(&"ambience_volume"));
+        assert!(ids("no such setting").is_empty());
+    }
+
+    #[test]
+    fn test_restore_defaults_runs_every_hook_once() {
+        let mut settings = changed_settings();
+        assert!(
+            SETTINGS
+                .iter()
+                .all(|setting| !setting.is_default(&settings))
+        );
+
+        let hooks = restore_defaults(&mut settings);
+        assert_eq!(settings, Settings::default());
+        let registered: HashSet<ApplyHook> = SETTINGS.iter().map(|setting| setting.apply).collect();
+        assert_eq!(hooks.len(), registered.len());
+        assert_eq!(hooks.iter().copied().collect::<HashSet<_>>(), registered);
+    }
+
+    #[test]
+    fn test_reset_changes_only_its_own_setting() {
+        let mut settings = changed_settings();
+        let setting = find("ambience_volume").expect("registered");
+        assert_eq!(setting.reset(&mut settings), ApplyHook::Audio);
+        assert_eq!(settings.ambience_volume, 1.0);
+        assert_eq!(settings.menu_fps_cap, FpsCap::Fps30);
+        assert_eq!(
+            SETTINGS
+                .iter()
+                .filter(|setting| setting.is_default(&settings))
+                .count(),
+            1
+        );
+        assert!(find("no_such_setting").is_none());
+    }
+
+    #[test]
+    fn test_settings_needing_a_capability_are_unavailable_without_it() {
+        let hrtf = find("hrtf").expect("registered");
+        assert!(!hrtf.is_available(&Capabilities::default()));
+        assert!(hrtf.is_available(&Capabilities { hrtf: true }));
+
+        // Everything else works anywhere
+        let needing: Vec<&str> = SETTINGS
+            .iter()
+            .filter(|setting| !setting.is_available(&Capabilities::default()))
+            .map(|setting| setting.id)
+            .collect();
+        assert_eq!(needing, vec!["hrtf"]);
+    }
+}
@@ -2,21 +2,53 @@
 //!
 //! Contains update and game logic methods for the App struct.
 
+use crate::benchmarks::{Profiler, latency};
 use crate::game::GameTimer;
+use crate::game::codex::{CodexLevel, MazeAlgorithm, MazeKind};
+use crate::game::dimensions::PlayerDimensions;
 use crate::game::enemy::place_enemy_standard;
-use crate::game::maze::parse_maze_file;
+use crate::game::journal::JournalEvent;
+use crate::game::keys::GameKey;
+use crate::game::maze::floors::{FloorPlan, Layer};
 use crate::game::player::Player;
-use crate::game::{self, CurrentScreen, TimerConfig};
+use crate::game::trail::EnemyTrail;
+use crate::game::{self, CurrentScreen};
 use crate::math::coordinates::maze_to_world;
+use crate::paths;
 use crate::renderer::loading_renderer::LoadingRenderer;
 use crate::renderer::primitives::Vertex;
-use crate::test_mode::setup_test_environment;
+use crate::renderer::screenshot::PendingScreenshot;
+use crate::test_mode::soak;
+use std::panic::AssertUnwindSafe;
 use std::time::Duration;
 use std::time::Instant;
 use wgpu;
 use wgpu::util::DeviceExt;
 
 use super::event_handler::App;
+use super::panic_guard::{self, Recovery};
+
+/// A frame whose commands are recorded but not yet submitted.
+struct RecordedFrame {
+    /// The frame's rendering commands
+    encoder: wgpu::CommandEncoder,
+    /// The surface texture the frame was drawn to
+    surface_texture: wgpu::SurfaceTexture,
+    /// Copy of the frame to save once it is submitted, when a screenshot was requested
+    screenshot: Option<PendingScreenshot>,
+    /// When the frame started
+    started: Instant,
+}
+
+/// Ends the open profiler sections of a frame that stops before it is drawn.
+///
+/// # Arguments
+/// * `profiler` - The app's profiler
+/// * `section` - The section the frame stopped in
+fn abandon_frame(profiler: &mut Profiler, section: &str) {
+    profiler.end_section(section);
+    profiler.end_section("total_frame");
+}
 
 impl App {
     /// Handles the main rendering loop and game state updates.
@@ -32,9 +64,14 @@ impl App {
     /// 4. **UI Overlays**: Renders pause menu, upgrade menu, and debug information
     /// 5. **Frame Submission**: Submits commands and presents the frame
     ///
+    /// Everything but the frame submission runs behind the panic guard (see
+    /// [`crate::app::panic_guard`]), so a panic in the game logic shows the error screen
+    /// instead of closing the window.
+    ///
     /// # Screen Handling
     /// - **Loading**: Handles maze generation and loading screen rendering
     /// - **Title**: Renders title screen and handles transitions
+    /// - **Error**: Renders the error screen left by a caught panic
     /// - **Game**: Updates player movement, enemy AI, and game logic
     /// - **Pause**: Renders pause menu overlay
     /// - **UpgradeMenu**: Handles upgrade selection and menu interactions
@@ -49,13 +86,89 @@ impl App {
     /// - Uses efficient command encoding for GPU operations
     /// - Manages GPU resource cleanup and polling
     pub fn handle_redraw(&mut self) {
+        let Some(frame) = self.guard_frame(Self::record_frame).flatten() else {
+            return;
+        };
+        self.submit_frame(frame.encoder, frame.surface_texture, frame.screenshot);
+        self.guard_frame(|app| app.finish_frame(frame.started));
+    }
+
+    /// Runs part of a frame behind the panic guard.
+    ///
+    /// A panic is caught here instead of unwinding out of the event loop. The first one
+    /// shows the error screen; one raised while the error screen is up exits the game.
+    ///
+    /// # Arguments
+    /// - `frame`: The part of the frame to run
+    ///
+    /// # Returns
+    /// The frame's result, or `None` if it panicked.
+    fn guard_frame<T>(&mut self, frame: impl FnOnce(&mut Self) -> T) -> Option<T> {
+        // `&mut App` isn't `UnwindSafe`, since a panic can leave the state half updated. That
+        // state is never used as if the frame had finished: the error screen only draws the
+        // title background and its own menu, and leaving it replaces the game state with a fresh
+        // one. The renderers only hold GPU resources and layout, which a half-recorded frame
+        // can't leave inconsistent. The journal and benchmark locks are only held for short
+        // bookkeeping that never calls back into game code, so a game panic can't poison them;
+        // if one is poisoned anyway, the next frame panics on it and the game exits cleanly.
+        match panic_guard::catch_panic(AssertUnwindSafe(|| frame(self))) {
+            Ok(value) => Some(value),
+            Err(message) => {
+                let recovery = self.panic_guard.on_panic(message);
+                self.recover_from_panic(recovery);
+                None
+            }
+        }
+    }
+
+    /// Saves what can be saved after a panic and shows the error screen, or exits.
+    ///
+    /// The panic hook has already written the session journal by the time this runs.
+    ///
+    /// # Arguments
+    /// - `recovery`: What the panic guard decided to do about the panic
+    fn recover_from_panic(&mut self, recovery: Recovery) {
+        let message = match recovery {
+            Recovery::ShowError(message) => {
+                eprintln!("Recovered from a panic in the frame update: {}", message);
+                self.save_benchmark_results();
+                let shown = panic_guard::catch_panic(AssertUnwindSafe(|| {
+                    if let (Some(state), Some(window)) = (self.state.as_mut(), self.window.as_ref())
+                    {
+                        state.show_error_screen(window, &message);
+                    }
+                }));
+                match shown {
+                    Ok(()) => return,
+                    Err(message) => message,
+                }
+            }
+            Recovery::Exit(message) => message,
+        };
+
+        eprintln!("The error screen panicked too, exiting: {}", message);
+        self.save_benchmark_results();
+        if let Some(state) = &mut self.state {
+            state.stop_test_mode_watcher();
+            state.wgpu_renderer.cleanup();
+        }
+        std::process::exit(1);
+    }
+
+    /// Updates the game and records the frame's rendering commands.
+    ///
+    /// The title, codex, profile and error screens render and present their own frames, so they
+    /// return `None`, as do frames skipped for a minimized window or a lost surface.
+    ///
+    /// # Returns
+    /// The recorded frame, ready to submit.
+    fn record_frame(&mut self) -> Option<RecordedFrame> {
         let window = self
             .window
             .as_ref()
             .expect("Window must be initialized before use");
         if window.is_minimized().unwrap_or(false) {
-            println!("Window is minimized");
-            return;
+            return None;
         }
 
         let state = self
@@ -63,22 +176,56 @@ impl App {
             .as_mut()
             .expect("State must be initialized before use");
 
+        // The debug panic key asks for a panic from inside the guarded part of the frame
+        if std::mem::take(&mut state.debug_panic_requested) {
+            panic_guard::deliberate_panic();
+        }
+
         // Start timing the entire frame
         state.profiler.start_section("total_frame");
+        let frame_started = Instant::now();
+
+        // Journal screen transitions made since the last frame
+        state.journal_screen_change(window);
+        // Loading can finish before the glyph warm-up does
+        if state.game_state.current_screen != CurrentScreen::Loading {
+            state.end_warm_up();
+        }
+        // The input method is only on while the seed field or a profile name is open
+        state.title_menu.sync_ime(window);
+        state.profile_menu.sync_ime(window);
 
         if state.game_state.current_screen == CurrentScreen::Loading {
             state
                 .game_state
-                .audio_manager
-                .pause_enemy_audio("enemy")
+                .pause_enemy_audio()
                 .expect("Failed to pause enemy audio");
             state.handle_loading_screen(window);
         } else if state.game_state.current_screen == CurrentScreen::Title {
-            crate::renderer::title::handle_title(state, window);
+            if crate::renderer::title::handle_title(state) {
+                window.request_redraw();
+            }
             state.upgrade_menu.upgrade_manager.player_upgrades.clear();
             state.game_state.player = crate::game::player::Player::new();
+            state.game_state.set_dimensions(PlayerDimensions::default());
             state.game_state.enemy = crate::game::enemy::Enemy::new([0.0, 30.0, 0.0], 150.0);
-            return;
+            state.game_state.clear_pack();
+            return None;
+        } else if state.game_state.current_screen == CurrentScreen::Codex {
+            if crate::renderer::title::handle_codex(state) {
+                window.request_redraw();
+            }
+            return None;
+        } else if state.game_state.current_screen == CurrentScreen::Profiles {
+            if crate::renderer::title::handle_profiles(state) {
+                window.request_redraw();
+            }
+            return None;
+        } else if state.game_state.current_screen == CurrentScreen::Error {
+            if crate::renderer::title::handle_error_screen(state) {
+                window.request_redraw();
+            }
+            return None;
         } else if state.game_state.current_screen == CurrentScreen::UpgradeMenu {
             // Handle upgrade menu - just update it, rendering is handled separately
             state.upgrade_menu.update();
@@ -103,7 +250,7 @@ impl App {
                     self.new_level(false);
                 }
                 // Upgrade effects are already applied in handle_input when the upgrade was selected
-                return;
+                return None;
             } else {
                 state.game_state.capture_mouse = false;
             }
@@ -119,12 +266,46 @@ impl App {
                     .walls,
                 state.game_state.is_test_mode,
             );
+            if state.game_state.current_screen == CurrentScreen::Game {
+                state.game_state.track_level_progress();
+            }
         }
 
+        // Pick up edits to the test maze file
+        state.reload_test_maze_if_changed(window);
+        state.update_custom_marker();
+
         // Update game state and UI
         state.profiler.start_section("game_state_update");
-        state.key_state.update(&mut state.game_state);
+        let map_view_held = state.key_state.is_pressed(GameKey::MapView);
+        state.game_state.update_map_view(map_view_held);
+        let quick_radial_held =
+            state.key_state.is_pressed(GameKey::QuickRadial) || state.gamepad_input.quick_radial;
+        state.game_state.update_quick_radial(quick_radial_held);
+        state
+            .key_state
+            .update(&mut state.game_state, &state.gamepad_input);
+        state.update_look_stick();
+        state.game_state.update_interaction_target();
+        if state.game_state.current_screen == CurrentScreen::Game {
+            state
+                .game_state
+                .check_wall_clip(&state.upgrade_menu.upgrade_manager.player_upgrades);
+            state.game_state.update_combo();
+            state.game_state.update_events();
+            state.game_state.update_warning_cues();
+            state.game_state.update_entrance_door();
+            state.game_state.update_anomaly_shimmer();
+        }
+        state.game_state.update_spawn();
+        if matches!(
+            state.game_state.current_screen,
+            CurrentScreen::Game | CurrentScreen::ExitReached
+        ) {
+            state.game_state.update_soundscape();
+        }
         state.update_game_ui(window);
+        // The listener stays with the player, not the camera, while the map view is up
         state
             .game_state
             .audio_manager
@@ -133,7 +314,10 @@ impl App {
         state
             .game_state
             .audio_manager
-            .update_enemy_position("enemy", state.game_state.enemy.pathfinder.position)
+            .set_listener_yaw(state.game_state.player.yaw);
+        state
+            .game_state
+            .update_enemy_audio()
             .expect("Failed to update enemy position");
         state.profiler.end_section("game_state_update");
 
@@ -144,6 +328,13 @@ impl App {
         }
         state.profiler.end_section("audio_update");
 
+        // Advance renderer animations before any rendering commands are recorded
+        state.wgpu_renderer.update_animations(
+            &state.game_state,
+            &mut state.text_renderer,
+            state.game_state.delta_time,
+        );
+
         // Prepare rendering commands
         state.profiler.start_section("command_encoder_creation");
         let mut encoder = state
@@ -155,18 +346,22 @@ impl App {
         // Update canvas surface
         state.profiler.start_section("canvas_update");
         let (surface_view, surface_texture) = match state.wgpu_renderer.update_canvas(
-            window,
             &mut encoder,
             &state.game_state,
             &mut state.text_renderer,
-            state.start_time,
         ) {
-            Ok(result) => result,
+            Ok(Some(result)) => result,
+            Ok(None) => {
+                abandon_frame(&mut state.profiler, "canvas_update");
+                return None;
+            }
             Err(err) => {
+                abandon_frame(&mut state.profiler, "canvas_update");
                 eprintln!("Failed to update canvas: {}", err);
+                state.game_state.journal.record_error("canvas update", &err);
                 #[cfg(debug_assertions)]
                 eprintln!("Backtrace: {:?}", std::backtrace::Backtrace::capture());
-                return;
+                return None;
             }
         };
         state.profiler.end_section("canvas_update");
@@ -200,6 +395,42 @@ impl App {
                     window_size.width, window_size.height
                 )
             };
+            let effective_cap = state.settings.fps_cap_for(state.game_state.current_screen);
+            let timer_info = format!(
+                "{}\nFPS: {} (cap: {}, vsync: {})",
+                timer_info,
+                state.game_state.current_fps,
+                effective_cap.label(),
+                state.settings.present_mode.label()
+            );
+            let tuning = state.game_state.enemy.tuning;
+            let timer_info = format!(
+                "{}\nEnemy: pressure {:+.2}, speed x{:.2}, alert x{:.2}, replan x{:.2}",
+                timer_info,
+                state.game_state.director.pressure(),
+                tuning.speed_multiplier,
+                tuning.alert_radius_multiplier,
+                tuning.replan_multiplier
+            );
+            let timer_info = format!(
+                "{}\n{}\n{}",
+                timer_info,
+                state.wgpu_renderer.gpu_memory.summary(),
+                crate::renderer::backend::adapter_summary(&state.wgpu_renderer.adapter_info)
+            );
+            let timer_info = if state.latency_probe.is_enabled() {
+                format!(
+                    "{}\n{}",
+                    timer_info,
+                    latency::overlay_label(
+                        state.latency_probe.percentiles(),
+                        window_size.present_mode,
+                        window_size.desired_maximum_frame_latency,
+                    )
+                )
+            } else {
+                timer_info
+            };
 
             let style = crate::renderer::text::TextStyle {
                 font_family: "Hanken Grotesk".to_string(),
@@ -213,7 +444,7 @@ impl App {
                 x: window_size.width as f32 - 320.0,
                 y: 20.0,
                 max_width: Some(300.0),
-                max_height: Some(80.0), // Increased height for two lines
+                max_height: Some(420.0), // Room for window, timer, FPS, enemy, GPU memory, adapter, and latency lines
             };
             state.text_renderer.create_text_buffer(
                 "debug_info",
@@ -235,6 +466,10 @@ impl App {
             &state.wgpu_renderer.surface_config,
         ) {
             println!("Failed to prepare text renderer: {}", e);
+            state
+                .game_state
+                .journal
+                .record_error("text preparation", &e);
         }
         state.profiler.end_section("text_preparation");
         {
@@ -255,11 +490,78 @@ impl App {
             state.profiler.start_section("text_rendering");
             if let Err(e) = state.text_renderer.render(&mut render_pass) {
                 println!("Failed to render text: {}", e);
+                state.game_state.journal.record_error("text rendering", &e);
             }
             state.profiler.end_section("text_rendering");
+
+            // Flash a corner in the frame that consumes a latency probe
+            if state.latency_probe.is_enabled() && state.latency_probe.begin_frame() {
+                let config = &state.wgpu_renderer.surface_config;
+                let flash = &mut state.latency_flash;
+                flash.resize(config.width as f32, config.height as f32);
+                flash.clear_rectangles();
+                flash.add_rectangle(crate::renderer::rectangle::Rectangle::new(
+                    0.0,
+                    0.0,
+                    latency::FLASH_SIZE,
+                    latency::FLASH_SIZE,
+                    [1.0, 1.0, 1.0, 1.0],
+                ));
+                flash.render(&state.wgpu_renderer.device, &mut render_pass);
+            }
         }
         // --- End Game UI ---
 
+        // While the quick-settings key is held, draw the radial over the game
+        if state.game_state.quick_radial.is_open() {
+            if !state.quick_radial_menu.is_visible() {
+                state.quick_radial_menu.show();
+            }
+            state
+                .quick_radial_menu
+                .set_selected(state.game_state.quick_radial.selected());
+
+            let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view: &surface_view,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Load,
+                        store: wgpu::StoreOp::Store,
+                    },
+                })],
+                depth_stencil_attachment: None,
+                timestamp_writes: None,
+                label: Some("quick radial render pass"),
+                occlusion_query_set: None,
+            });
+
+            if let Err(e) = state.quick_radial_menu.prepare(
+                &state.wgpu_renderer.device,
+                &state.wgpu_renderer.queue,
+                &state.wgpu_renderer.surface_config,
+            ) {
+                println!("Failed to prepare quick radial: {}", e);
+                state
+                    .game_state
+                    .journal
+                    .record_error("quick radial preparation", &e);
+            }
+
+            if let Err(e) = state
+                .quick_radial_menu
+                .render(&state.wgpu_renderer.device, &mut render_pass)
+            {
+                println!("Failed to render quick radial: {}", e);
+                state
+                    .game_state
+                    .journal
+                    .record_error("quick radial rendering", &e);
+            }
+        } else if state.quick_radial_menu.is_visible() {
+            state.quick_radial_menu.hide();
+        }
+
         // If paused, render the pause menu on top
         if state.game_state.current_screen == CurrentScreen::Pause {
             if !state.pause_menu.is_visible() {
@@ -283,7 +585,7 @@ impl App {
             });
 
             // --- Add semi-transparent grey overlay ---
-            let overlay_color = [0.08, 0.09, 0.11, 0.88]; // darker, neutral semi-transparent grey
+            let overlay_color = crate::renderer::ui::upgrade_menu::OVERLAY_COLOR;
             let (w, h) = (
                 state.wgpu_renderer.surface_config.width as f32,
                 state.wgpu_renderer.surface_config.height as f32,
@@ -313,6 +615,10 @@ impl App {
                 &state.wgpu_renderer.surface_config,
             ) {
                 println!("Failed to prepare pause menu: {}", e);
+                state
+                    .game_state
+                    .journal
+                    .record_error("pause menu preparation", &e);
             }
 
             // Render the pause menu (rectangles + text)
@@ -321,6 +627,10 @@ impl App {
                 .render(&state.wgpu_renderer.device, &mut render_pass)
             {
                 println!("Failed to render pause menu: {}", e);
+                state
+                    .game_state
+                    .journal
+                    .record_error("pause menu rendering", &e);
             }
         } else {
             if state.pause_menu.is_visible() {
@@ -334,6 +644,53 @@ impl App {
                 .clear_rectangles();
         }
 
+        // If the game is over, render the retry and new run buttons on top
+        if state.game_state.current_screen == CurrentScreen::GameOver {
+            if !state.game_over_menu.is_visible() {
+                state.game_over_menu.show();
+            }
+
+            let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view: &surface_view,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Load,
+                        store: wgpu::StoreOp::Store,
+                    },
+                })],
+                depth_stencil_attachment: None,
+                timestamp_writes: None,
+                label: Some("game over menu render pass"),
+                occlusion_query_set: None,
+            });
+
+            if let Err(e) = state.game_over_menu.prepare(
+                &state.wgpu_renderer.device,
+                &state.wgpu_renderer.queue,
+                &state.wgpu_renderer.surface_config,
+            ) {
+                println!("Failed to prepare game over menu: {}", e);
+                state
+                    .game_state
+                    .journal
+                    .record_error("game over menu preparation", &e);
+            }
+
+            if let Err(e) = state
+                .game_over_menu
+                .render(&state.wgpu_renderer.device, &mut render_pass)
+            {
+                println!("Failed to render game over menu: {}", e);
+                state
+                    .game_state
+                    .journal
+                    .record_error("game over menu rendering", &e);
+            }
+        } else if state.game_over_menu.is_visible() {
+            state.game_over_menu.hide();
+        }
+
         // If in upgrade menu, render the upgrade menu on top
         if state.game_state.current_screen == CurrentScreen::UpgradeMenu {
             // Prepare the upgrade menu
@@ -343,6 +700,10 @@ impl App {
                 &state.wgpu_renderer.surface_config,
             ) {
                 println!("Failed to prepare upgrade menu: {}", e);
+                state
+                    .game_state
+                    .journal
+                    .record_error("upgrade menu preparation", &e);
             }
 
             // Create a render pass for the upgrade menu
@@ -362,7 +723,7 @@ impl App {
             });
 
             // --- Add semi-transparent overlay for upgrade menu ---
-            let overlay_color = [0.08, 0.09, 0.11, 0.88]; // darker, neutral semi-transparent grey
+            let overlay_color = crate::renderer::ui::upgrade_menu::OVERLAY_COLOR;
             let (w, h) = (
                 state.wgpu_renderer.surface_config.width as f32,
                 state.wgpu_renderer.surface_config.height as f32,
@@ -390,6 +751,10 @@ impl App {
                 .render(&state.wgpu_renderer.device, &mut render_pass)
             {
                 println!("Failed to render upgrade menu: {}", e);
+                state
+                    .game_state
+                    .journal
+                    .record_error("upgrade menu rendering", &e);
             }
         } else {
             if state.upgrade_menu.is_visible() {
@@ -403,24 +768,146 @@ impl App {
                 .clear_rectangles();
         }
 
+        // Replay the level just escaped in the corner of the summary and upgrade screens
+        match state.game_state.current_screen {
+            CurrentScreen::ExitReached | CurrentScreen::UpgradeMenu => {
+                let pointer = (state.game_state.current_screen == CurrentScreen::UpgradeMenu)
+                    .then_some(state.upgrade_menu.button_manager.mouse_position);
+                state.wgpu_renderer.render_replay_pip(
+                    &mut encoder,
+                    &surface_view,
+                    &state.game_state,
+                    pointer,
+                );
+            }
+            _ => state.wgpu_renderer.replay_pip.hide(),
+        }
+
+        // Toasts sit over the menus but under the debug panels
+        state.render_toasts(&mut encoder, &surface_view);
+        state.render_announcement(&mut encoder, &surface_view);
+        state.render_audio_debug(&mut encoder, &surface_view);
+
+        // Draw the sandbox settings over the maze while the sandbox is open
+        if let Some(session) = state.game_state.sandbox.as_mut() {
+            let ui_view = state.wgpu_renderer.create_ui_view(&surface_texture);
+            state.sandbox_panel.render(
+                &state.wgpu_renderer.device,
+                &state.wgpu_renderer.queue,
+                &mut encoder,
+                &ui_view,
+                window,
+                session,
+            );
+        }
+
+        // Draw the debug tuning panel above all other UI
+        if state.tuning_panel.is_visible() {
+            let ui_view = state.wgpu_renderer.create_ui_view(&surface_texture);
+            state.tuning_panel.render(
+                &state.wgpu_renderer.device,
+                &state.wgpu_renderer.queue,
+                &mut encoder,
+                &ui_view,
+                window,
+                &mut state.wgpu_renderer.tuning,
+                &mut state.game_state.player.fov,
+                &mut state.game_state.player.movement,
+            );
+        }
+
+        // Keep the window opaque on surfaces that composite with alpha
+        state
+            .wgpu_renderer
+            .stamp_opaque_alpha(&mut encoder, &surface_view);
+        let screenshot = state
+            .wgpu_renderer
+            .take_screenshot(&mut encoder, &surface_texture);
+
         window.request_redraw();
+        Some(RecordedFrame {
+            encoder,
+            surface_texture,
+            screenshot,
+            started: frame_started,
+        })
+    }
+
+    /// Submits a recorded frame and presents it.
+    ///
+    /// Runs outside the panic guard, so a panic never leaves a frame half submitted.
+    ///
+    /// # Arguments
+    /// - `encoder`: The frame's rendering commands
+    /// - `surface_texture`: The surface texture the frame was drawn to
+    /// - `screenshot`: Copy of the frame to save, when a screenshot was requested
+    fn submit_frame(
+        &mut self,
+        encoder: wgpu::CommandEncoder,
+        surface_texture: wgpu::SurfaceTexture,
+        screenshot: Option<PendingScreenshot>,
+    ) {
+        let state = self
+            .state
+            .as_mut()
+            .expect("State must be initialized before use");
 
         // Submit commands and present
         state.profiler.start_section("command_submission");
         state.wgpu_renderer.queue.submit(Some(encoder.finish()));
         state.profiler.end_section("command_submission");
 
+        if let Some(screenshot) = screenshot {
+            screenshot.save_in_background(
+                &state.wgpu_renderer.device,
+                paths::get().screenshots_dir(),
+                state.toasts.sender(),
+            );
+        }
+
         // Present the surface texture and ensure it's properly handled
         state.profiler.start_section("surface_presentation");
         surface_texture.present();
         state.profiler.end_section("surface_presentation");
 
+        // Close out a latency probe drawn by this frame
+        if let Some(sample) = state.latency_probe.frame_presented(Instant::now()) {
+            state
+                .profiler
+                .record_section(latency::LATENCY_SECTION, sample.latency);
+            println!(
+                "[LATENCY] Input {} consumed in frame {}: {:.1} ms to present",
+                sample.input,
+                sample.frame,
+                sample.latency.as_secs_f64() * 1000.0
+            );
+        }
+
         // Poll the device to process any pending operations
         // This helps ensure resources are properly cleaned up and prevents
         // the "SurfaceSemaphores still in use" error during cleanup
         state.profiler.start_section("device_polling");
         state.wgpu_renderer.device.poll(wgpu::Maintain::Poll);
         state.profiler.end_section("device_polling");
+    }
+
+    /// Runs the game logic that acts on the frame just presented.
+    ///
+    /// # Arguments
+    /// - `started`: When the frame started, for charging its cost to the frame heatmap
+    fn finish_frame(&mut self, started: Instant) {
+        let window = self
+            .window
+            .as_ref()
+            .expect("Window must be initialized before use");
+        let state = self
+            .state
+            .as_mut()
+            .expect("State must be initialized before use");
+
+        // Act on buttons clicked on the sandbox panel this frame
+        let sandbox_action = state.sandbox_panel.take_action();
+        state.apply_sandbox_action(sandbox_action, window);
 
         // Manage enemy locked state based on timer and test mode
         if state.game_state.current_screen == CurrentScreen::Game {
@@ -428,13 +915,17 @@ impl App {
             if state.game_state.is_test_mode {
                 // Always keep enemy locked in test mode
                 state.game_state.enemy.pathfinder.locked = true;
+            } else if state.game_state.sandbox.is_some() {
+                // The sandbox has no timer, so its enemy hunts whenever it is switched on
+                state.game_state.enemy.pathfinder.locked = !state.game_state.enemy_present();
             } else if state.game_state.game_ui.timer.is_some() {
                 // In normal mode, unlock enemy only when timer is running (not paused)
+                // and it has arrived in the maze
                 if let Some(timer) = &state.game_state.game_ui.timer {
-                    if timer.is_running && timer.paused_at.is_none() {
+                    if timer.is_running && !timer.is_paused() && state.game_state.enemy_present() {
                         state.game_state.enemy.pathfinder.locked = false;
                     } else {
-                        // Lock enemy when timer is paused or stopped
+                        // Lock enemy when timer is paused or stopped, or it hasn't arrived
                         state.game_state.enemy.pathfinder.locked = true;
                     }
                 }
@@ -445,6 +936,12 @@ impl App {
 
             // Debug: Print when enemy lock state changes
             if was_locked != state.game_state.enemy.pathfinder.locked {
+                state
+                    .game_state
+                    .journal
+                    .record(JournalEvent::EnemyLockChanged {
+                        locked: state.game_state.enemy.pathfinder.locked,
+                    });
                 println!(
                     "Enemy lock state changed: {} -> {}",
                     was_locked, state.game_state.enemy.pathfinder.locked
@@ -454,19 +951,57 @@ impl App {
 
         // Update enemy pathfinding
         state.profiler.start_section("enemy_pathfinding");
-        state.game_state.enemy.update(
-            state.game_state.player.position,
-            state.game_state.delta_time,
-            state.game_state.game_ui.level as u32,
-            |from, to| {
-                state
-                    .game_state
-                    .collision_system
-                    .cylinder_intersects_geometry(from, to, 5.0)
-            },
-        );
+        state.game_state.update_enemies();
         state.profiler.end_section("enemy_pathfinding");
 
+        // Keep the enemies inside the maze even if pathfinding misbehaves
+        if state.game_state.current_screen == CurrentScreen::Game {
+            let game_state = &mut state.game_state;
+            let collision_system = &game_state.collision_system;
+            let enemies =
+                std::iter::once(&mut game_state.enemy).chain(&mut game_state.pack.enemies);
+            for enemy in enemies {
+                let enemy_position = enemy.pathfinder.position;
+                let mut recovered =
+                    collision_system.recover_out_of_bounds(enemy_position, "Enemy", 30.0);
+                if recovered != enemy_position && collision_system.floors.is_some() {
+                    // Recovery drops the enemy back on the lower floor
+                    enemy.layer = Layer::Lower;
+                    recovered[1] = 30.0;
+                }
+                enemy.pathfinder.position = recovered;
+            }
+
+            // Leave a fading trail behind the enemy
+            let trail_lifetime = EnemyTrail::lifetime_for(state.settings.reduce_effects);
+            let enemy = &mut state.game_state.enemy;
+            enemy.trail.update(
+                enemy.pathfinder.position,
+                state.game_state.delta_time,
+                trail_lifetime,
+            );
+
+            // Kick up dust while sprinting and spray a burst where the player hit a wall
+            let game_state = &mut state.game_state;
+            let delta_time = game_state.delta_time;
+            let player_position = game_state.player.position;
+            let ground = game_state
+                .collision_system
+                .ground(player_position, game_state.player.layer)
+                .1;
+            let feet = [player_position[0], ground, player_position[2]];
+            let particles = &mut game_state.particles;
+            particles.update(delta_time, state.settings.reduce_effects);
+            particles.track_footsteps(feet, game_state.audio_manager.is_sprinting(), delta_time);
+            if let Some(impact) = game_state.last_movement.impact {
+                particles.emit_impact(&impact);
+            }
+        }
+
+        // Rumble the controller for anything that happened this frame
+        state.update_haptics();
+        state.update_announcements();
+
         // Handle title screen animation if needed
         if state.game_state.current_screen == CurrentScreen::Loading {
             state.game_state.game_ui.stop_timer();
@@ -477,18 +1012,29 @@ impl App {
             state.text_renderer.hide_game_over_display();
             state.upgrade_menu.upgrade_manager.player_upgrades.clear();
             state.game_state.player = crate::game::player::Player::new();
+            state.game_state.set_dimensions(PlayerDimensions::default());
             state.game_state.enemy = crate::game::enemy::Enemy::new([0.0, 30.0, 0.0], 150.0);
+            state.game_state.clear_pack();
             let _ = state; // Release the borrow
             self.new_level(true);
             return; // Exit early to avoid the borrow checker issue
         } else if state.game_state.current_screen == CurrentScreen::Game
             && Some(state.game_state.player.current_cell) == state.game_state.exit_cell
+            && state.game_state.on_exit_floor()
+            && state.game_state.sandbox.is_none()
         {
+            // Score the level before the exit animation starts
+            state.game_state.cancel_spawn();
+            state.game_state.finish_level();
+            state.game_state.adapt_difficulty(false);
+            state.record_codex(false);
+
             // Transition to ExitReached screen
             state.game_state.current_screen = CurrentScreen::ExitReached;
             state.game_state.exit_reached_timer = 0.0;
             state.game_state.enemy.pathfinder.position = [0.0, 30.0, 0.0];
             state.game_state.enemy.pathfinder.locked = true;
+            state.game_state.clear_pack();
             if !state.game_state.beeper_rise_played {
                 let _ = state.game_state.audio_manager.play_beeper_rise();
                 state.game_state.beeper_rise_played = true;
@@ -503,7 +1049,9 @@ impl App {
             } else {
                 // After 3 seconds, transition to appropriate next screen
                 let current_level = state.game_state.game_ui.level;
-                if current_level > 0 && current_level % 3 == 0 {
+                if state.game_state.challenge_complete() {
+                    state.complete_challenge(window);
+                } else if current_level > 0 && current_level % 3 == 0 {
                     // Show upgrade menu
                     state.game_state.current_screen = CurrentScreen::UpgradeMenu;
                     state.upgrade_menu.show();
@@ -518,17 +1066,23 @@ impl App {
                     return; // Exit early to avoid the borrow checker issue
                 }
             }
-        } else if state.game_state.current_screen == CurrentScreen::Game {
+        } else if state.game_state.current_screen == CurrentScreen::Game
+            && state.game_state.enemy_present()
+        {
             state
                 .game_state
-                .audio_manager
-                .resume_enemy_audio("enemy")
+                .resume_enemy_audio()
                 .expect("Failed to resume enemy audio");
         }
 
         // End timing the entire frame and record FPS
         state.profiler.end_section("total_frame");
 
+        // Charge the frame's CPU time to the player's cell for the frame heatmap
+        if state.game_state.current_screen == CurrentScreen::Game {
+            state.game_state.record_frame_cost(started.elapsed());
+        }
+
         // Record frame time for performance analysis
         crate::benchmark!("frame_time", {
             // This is just a marker - the actual timing is done by the profiler
@@ -539,25 +1093,56 @@ impl App {
         // Record frame in global benchmark system for FPS statistics
         crate::benchmarks::utils::record_frame();
 
-        // Print performance summary every 1000 frames (approximately every 16 seconds at 60 FPS)
-        if state.fps_counter.frame_times.len() % 1000 == 0 {
-            // Note: We can't call print_summary here due to borrow checker constraints
-            // The summary will be available when the game exits or when explicitly called
+        // Autosave benchmark results periodically so a crash doesn't lose the session.
+        // The file is written on a background thread, so this never blocks the frame
+        crate::benchmarks::utils::autosave_if_due();
+    }
+
+    /// Sleeps off the remaining frame budget for the current screen's FPS cap.
+    ///
+    /// Called after each redraw so every path through `handle_redraw`, including
+    /// its early returns, is paced. Menu screens use the menu cap and gameplay
+    /// screens use the gameplay cap from [`crate::app::settings::Settings`].
+    ///
+    /// Delta time is measured from real frame timestamps in `handle_frame_timing`,
+    /// so a longer frame only produces a larger step and game time never drifts.
+    pub fn pace_frame(&mut self) {
+        if let Some(state) = self.state.as_mut() {
+            let cap = state.settings.fps_cap_for(state.game_state.current_screen);
+            state.frame_limiter.wait(cap);
         }
+    }
 
-        // Save benchmark results every 5000 frames (approximately every 83 seconds at 60 FPS)
-        // This ensures we don't lose data if the program crashes
-        if state.fps_counter.frame_times.len() % 5000 == 0
-            && state.fps_counter.frame_times.len() > 0
-        {
-            // Use force_save_results to avoid borrow checker issues
-            if let Err(e) = crate::benchmarks::utils::force_save_results() {
-                eprintln!(
-                    "[BENCHMARK] Failed to save benchmark results during periodic save: {}",
-                    e
-                );
-            }
+    /// Lets the soak test's bot act on the frame just drawn, and ends the soak test when it's over.
+    ///
+    /// Does nothing unless the game was started with `--soak`. At the end the report is
+    /// printed and written, and the game exits, with status 1 if the soak test failed.
+    ///
+    /// # Arguments
+    /// - `frame_time`: How long the frame took to update and draw
+    pub fn drive_soak(&mut self, frame_time: Duration) {
+        let (Some(soak), Some(state), Some(window)) = (
+            self.soak.as_mut(),
+            self.state.as_mut(),
+            self.window.as_ref(),
+        ) else {
+            return;
+        };
+        let Some(report) = soak.frame(state, window, frame_time) else {
+            return;
+        };
+
+        println!("{}", report);
+        match soak::write_report(&report, &paths::get().soak_reports_dir()) {
+            Ok(path) => println!("Soak report written to {}", path.display()),
+            Err(e) => eprintln!("{}", e),
         }
+        self.save_benchmark_results();
+        if let Some(state) = &mut self.state {
+            state.stop_test_mode_watcher();
+            state.wgpu_renderer.cleanup();
+        }
+        std::process::exit(if report.passed() { 0 } else { 1 });
     }
 
     /// Updates frame timing and performance metrics.
@@ -586,6 +1171,7 @@ impl App {
     /// - Triggers debug renderer updates when needed
     pub fn handle_frame_timing(&mut self, current_time: Instant) {
         if let Some(state) = self.state.as_mut() {
+            state.frame_limiter.begin_frame();
             let duration = current_time.duration_since(state.game_state.last_fps_time);
 
             state.elapsed_time = current_time.duration_since(state.start_time);
@@ -601,9 +1187,18 @@ impl App {
                 .duration_since(state.game_state.last_frame_time)
                 .as_secs_f32();
 
-            state.game_state.delta_time = delta_time;
+            // The quick-settings radial slows the game down while it is open
+            state.game_state.delta_time = delta_time * state.game_state.quick_radial.time_scale();
             state.game_state.last_frame_time = current_time;
 
+            // Gameplay toasts only count down while the game is being played
+            let gameplay_delta = if state.game_state.current_screen == CurrentScreen::Game {
+                state.game_state.delta_time
+            } else {
+                0.0
+            };
+            state.toasts.tick(delta_time, gameplay_delta);
+
             if state
                 .wgpu_renderer
                 .game_renderer
@@ -667,15 +1262,22 @@ impl App {
                     .loading_screen_renderer
                     .generator
                     .generation_complete = true;
-                // Set up test environment immediately
-                setup_test_environment(&mut state.game_state, &mut state.wgpu_renderer);
-                // Set a dummy maze path to prevent re-entry
-                state.game_state.maze_path = Some(std::path::PathBuf::from("test_mode"));
+                // Set up test environment immediately (this also sets the maze path,
+                // which prevents re-entry)
+                if let Some(window) = self.window.as_ref() {
+                    state.start_test_mode(window);
+                }
                 // Go directly to game screen
                 state.game_state.current_screen = CurrentScreen::Game;
                 return;
             }
 
+            // The sandbox can skip the carving animation and finish the maze in one go
+            let instant = state
+                .game_state
+                .sandbox
+                .as_ref()
+                .is_some_and(|session| !session.generated.animate);
             let renderer = &mut state.wgpu_renderer.loading_screen_renderer;
 
             // Calculate update timing
@@ -686,7 +1288,9 @@ impl App {
             };
 
             // Skip if not time to update or already complete
-            if renderer.last_update.elapsed() < speed || renderer.generator.is_complete() {
+            if (!instant && renderer.last_update.elapsed() < speed)
+                || renderer.generator.is_complete()
+            {
                 return;
             }
 
@@ -696,6 +1300,7 @@ impl App {
             } else {
                 100
             };
+            let (regions_before, _) = renderer.generator.regions_progress();
             state.profiler.start_section("maze_generation_steps");
             for _ in 0..steps {
                 if !renderer.generator.step() {
@@ -704,6 +1309,12 @@ impl App {
             }
             state.profiler.end_section("maze_generation_steps");
 
+            // Divided mazes fill in region by region
+            let (regions, total_regions) = renderer.generator.regions_progress();
+            if renderer.generator.divided_plan().is_some() && regions != regions_before {
+                println!("Regions carved: {}/{}", regions, total_regions);
+            }
+
             // Report progress
             let (current, total) = renderer.generator.get_progress();
 
@@ -716,15 +1327,21 @@ impl App {
                 );
             }
 
-            // Complete generation all at once if less than 10% remains
+            // Complete generation all at once if less than 10% remains, or right away
+            // when the sandbox skips the animation
             let progress_ratio = current as f32 / total.max(1) as f32;
-            if progress_ratio > 0.7 && !renderer.generator.is_complete() {
+            if (instant || progress_ratio > 0.7) && !renderer.generator.is_complete() {
                 while !renderer.generator.is_complete() {
                     renderer.generator.step();
                 }
             }
 
-            if renderer.generator.is_complete() && state.game_state.maze_path.is_none() {
+            // Two-floor levels go on to generate the upper floor once the lower one is done
+            if renderer.advance_floor() {
+                return;
+            }
+
+            if renderer.generator.is_complete() && !state.game_state.level_ready {
                 println!("Maze generation complete! Saving to file...");
 
                 // Play completion sound
@@ -733,6 +1350,8 @@ impl App {
                     .audio_manager
                     .complete()
                     .expect("Failed to play complete sound!");
+                // Sweep the solution path across the preview before the player heads in
+                renderer.start_path_reveal();
 
                 // --- DEBUG PRINT: Applied Upgrades and Stats (print once, with complete sound) ---
                 println!("\n=== [DEBUG] Applied Upgrades and Influenced Stats ===");
@@ -746,6 +1365,8 @@ impl App {
                     AvailableUpgrade::SlowTime,
                     AvailableUpgrade::SilentStep,
                     AvailableUpgrade::HeadStart,
+                    AvailableUpgrade::Slim,
+                    AvailableUpgrade::Lightfoot,
                     AvailableUpgrade::Unknown,
                 ] {
                     let count = mgr.get_upgrade_count(&upgrade);
@@ -764,6 +1385,14 @@ impl App {
                             AvailableUpgrade::SlowTime => format!("timer: {}s", player.max_stamina), // Timer is in game_state, but not directly accessible here; placeholder
                             AvailableUpgrade::SilentStep => "enemy pathfinding penalty".to_string(),
                             AvailableUpgrade::HeadStart => "enemy lock time".to_string(),
+                            AvailableUpgrade::Slim => format!(
+                                "collision_radius: {:.2}",
+                                state.game_state.dimensions.collision_radius
+                            ),
+                            AvailableUpgrade::Lightfoot => format!(
+                                "acceleration_multiplier: {:.2}",
+                                player.acceleration_multiplier
+                            ),
                             AvailableUpgrade::Unknown => "???".to_string(),
                         };
                         println!("- {} (x{}): {}", name, count, stat);
@@ -776,42 +1405,88 @@ impl App {
                     state.profiler.start_section("maze_completion_processing");
 
                     let maze_lock = renderer.maze.lock().expect("Failed to lock maze");
-                    state.game_state.maze_path = maze_lock.save_to_file().map_or_else(
-                        |err| {
-                            eprintln!("Failed to save maze: {}", err);
-                            std::process::exit(1);
-                        },
-                        Some,
-                    );
-
-                    // Generate geometry if maze was saved successfully
-                    if let Some(maze_path) = &state.game_state.maze_path {
+                    let in_sandbox = state.game_state.sandbox.is_some();
+                    // The saved copy is only for reproducing the level, so failing to write it
+                    // (e.g. from a read-only directory) doesn't stop the game. Sandbox mazes
+                    // can be made again from their settings, so they aren't saved
+                    state.game_state.maze_path = if in_sandbox {
+                        None
+                    } else {
+                        match maze_lock.save_to_file(&paths::get().saved_mazes_dir()) {
+                            Ok(path) => Some(path),
+                            Err(e) => {
+                                eprintln!("Failed to save maze: {}", e);
+                                None
+                            }
+                        }
+                    };
+                    state.game_state.level_ready = true;
+
+                    // Remember the maze so the level's result can go in the codex
+                    let algorithm = if renderer.lower_floor.is_some() {
+                        MazeAlgorithm::StackedKruskal
+                    } else if renderer.generator.divided_plan().is_some() {
+                        MazeAlgorithm::Divided
+                    } else {
+                        MazeAlgorithm::Kruskal
+                    };
+                    let (width, height) = maze_lock.get_dimensions();
+                    state.codex_level = (!in_sandbox).then(|| CodexLevel {
+                        kind: MazeKind::new(maze_lock.width, maze_lock.height, algorithm),
+                        thumbnail: maze_lock.get_render_data(&renderer.generator.connected_cells),
+                        dimensions: (width as u32, height as u32),
+                    });
+
+                    // Generate geometry from the maze in memory
+                    {
                         state.profiler.start_section("maze_geometry_generation");
-                        let (maze_grid, exit_cell) = parse_maze_file(
-                            maze_path
-                                .to_str()
-                                .expect("Failed to convert path to string"),
-                        );
-                        let (mut floor_vertices, exit_position) = Vertex::create_floor_vertices(
+                        let (level_grid, exit_cell) = maze_lock.level_grid();
+                        let is_test_mode = state.game_state.is_test_mode;
+
+                        // On two-floor levels the saved maze is the upper floor, which has the exit
+                        let floor_plan = renderer.lower_floor.as_ref().map(|lower| {
+                            let lower = lower.lock().expect("Failed to lock lower floor");
+                            FloorPlan::new(
+                                lower.walls.clone(),
+                                level_grid.clone(),
+                                renderer.stairwells.clone(),
+                                is_test_mode,
+                            )
+                        });
+                        let maze_grid = floor_plan
+                            .as_ref()
+                            .map_or(level_grid.clone(), |plan| plan.grid(Layer::Lower).to_vec());
+
+                        let (mut floor_vertices, mut exit_position) = Vertex::create_floor_vertices(
                             &maze_grid,
-                            exit_cell,
-                            state.game_state.is_test_mode,
+                            exit_cell.filter(|_| floor_plan.is_none()),
+                            is_test_mode,
                         );
 
-                        state.wgpu_renderer.game_renderer.exit_position = Some(exit_position);
-
-                        floor_vertices.append(&mut Vertex::create_wall_vertices(
-                            &maze_grid,
-                            state.game_state.is_test_mode,
-                        ));
+                        floor_vertices
+                            .append(&mut Vertex::create_wall_vertices(&maze_grid, is_test_mode));
 
                         // Add ceiling vertices
                         floor_vertices.append(&mut Vertex::create_ceiling_vertices(
                             &maze_grid,
-                            state.game_state.is_test_mode,
+                            is_test_mode,
                         ));
 
-                        state.wgpu_renderer.game_renderer.vertex_buffer = state
+                        // The upper floor goes last so the map view can leave it out
+                        let game_renderer = &mut state.wgpu_renderer.game_renderer;
+                        game_renderer.upper_floor_start = None;
+                        if let Some(plan) = &floor_plan {
+                            floor_vertices.append(&mut Vertex::create_ramp_vertices(plan));
+                            game_renderer.upper_floor_start = Some(floor_vertices.len() as u32);
+                            let (mut upper_vertices, upper_exit_position) =
+                                Vertex::create_upper_floor_vertices(plan, exit_cell);
+                            floor_vertices.append(&mut upper_vertices);
+                            exit_position = upper_exit_position;
+                        }
+                        game_renderer.exit_position = Some(exit_position);
+                        game_renderer.compass_renderer.reset();
+
+                        game_renderer.vertex_buffer = state
                             .wgpu_renderer
                             .device
                             .create_buffer_init(&wgpu::util::BufferInitDescriptor {
@@ -821,19 +1496,33 @@ impl App {
                             });
 
                         // Update vertex count so the renderer knows how many vertices to draw
-                        state.wgpu_renderer.game_renderer.vertex_count =
-                            floor_vertices.len() as u32;
+                        game_renderer.vertex_count = floor_vertices.len() as u32;
                         state.profiler.end_section("maze_geometry_generation");
 
+                        state.profiler.start_section("collision_system_build");
+                        let collision_system = &mut state.game_state.collision_system;
+                        collision_system.build_from_maze(&maze_grid, is_test_mode);
+                        collision_system.set_floor_plan(floor_plan);
+                        state.profiler.end_section("collision_system_build");
+
                         if let Some(exit_cell_position) = exit_cell {
                             state.profiler.start_section("enemy_placement");
+                            // The enemy starts on the same floor as the exit
+                            let (exit_layer, exit_base) = state
+                                .game_state
+                                .collision_system
+                                .floors
+                                .as_ref()
+                                .map_or((Layer::Lower, 0.0), |plan| {
+                                    (Layer::Upper, plan.base_height(Layer::Upper))
+                                });
                             state.game_state.exit_cell = Some(exit_cell_position);
                             state.game_state.enemy = place_enemy_standard(
                                 maze_to_world(
                                     &exit_cell_position,
                                     maze_lock.get_dimensions(),
                                     30.0,
-                                    state.game_state.is_test_mode,
+                                    is_test_mode,
                                 ),
                                 state.game_state.player.position,
                                 state.game_state.game_ui.level,
@@ -841,25 +1530,42 @@ impl App {
                                     state
                                         .game_state
                                         .collision_system
-                                        .cylinder_intersects_geometry(from, to, 5.0)
+                                        .cylinder_intersects_geometry(from, to, 5.0, exit_layer)
                                 },
                             );
+                            state.game_state.enemy.layer = exit_layer;
+                            state.game_state.enemy.pathfinder.position[1] += exit_base;
+                            state.game_state.enemy.tuning =
+                                state.game_state.level_plan.enemy.tuning;
                             state.profiler.end_section("enemy_placement");
                         }
 
-                        state.profiler.start_section("collision_system_build");
-                        state
-                            .game_state
-                            .collision_system
-                            .build_from_maze(&maze_grid, state.game_state.is_test_mode);
-                        state.profiler.end_section("collision_system_build");
+                        state.game_state.reset_level_tracking(&maze_grid, exit_cell);
 
                         // Spawn the player at the bottom-left corner of the maze
                         state
                             .game_state
                             .player
                             .spawn_at_maze_entrance(&maze_grid, state.game_state.is_test_mode);
-                        // (No automatic transition to Game here)
+
+                        // Remember the finished level so it can be retried without loading
+                        if let Some(exit_cell_position) = exit_cell {
+                            state.game_state.cache_level(&maze_grid, exit_cell_position);
+                        }
+                        state.game_state.journal.record(JournalEvent::LevelLoaded {
+                            level: state.game_state.game_ui.level,
+                            seed: renderer.generator.seed,
+                            width: maze_lock.width,
+                            height: maze_lock.height,
+                        });
+                        // (No automatic transition to Game here, except in the sandbox)
+                        if in_sandbox {
+                            state.game_state.current_screen = CurrentScreen::Game;
+                        } else {
+                            // Quitting from here on continues the run at this level
+                            drop(maze_lock);
+                            state.save_run();
+                        }
                     }
 
                     state.profiler.end_section("maze_completion_processing");
@@ -877,8 +1583,7 @@ impl App {
     ///
     /// This method handles the transition to a new level or game restart. It manages
     /// player state, enemy positioning, timer configuration, and scoring systems.
-    /// The method implements a sophisticated scoring system based on completion time
-    /// and performance metrics.
+    /// Completed levels are scored through the shared score pipeline.
     ///
     /// # Arguments
     /// - `game_over`: Whether this is a game restart (true) or level progression (false)
@@ -898,18 +1603,13 @@ impl App {
     /// - Maintains upgrade effects
     ///
     /// # Scoring System
-    /// The scoring system rewards performance with multiple components:
-    /// - **Base Score**: 150 points per level
-    /// - **Speed Bonus**: Multiplier based on completion time
-    ///   - Exceptional (≤15s): 3x-5x multiplier
-    ///   - Good (≤25s): 1.5x-3x multiplier
-    ///   - Average (≤35s): 0.5x-1.5x multiplier
-    ///   - Slow (>35s): 0.1x-0.5x multiplier
-    /// - **Level Bonus**: Additional points for higher levels (level > 5)
-    /// - **Consecutive Bonus**: Reward for sustained good performance
+    /// Level points are computed by [`crate::game::score::ScoreCalculator`] when the
+    /// exit is reached and applied here through `GameState::apply_level_score`, so
+    /// this is the only place the run score grows.
     ///
     /// # State Management
     /// - Resets maze path to trigger new generation
+    /// - Seeds the next maze from the daily challenge when one is being played
     /// - Clears exit cell and timer state
     /// - Resets enemy position and locks enemy
     /// - Manages audio state transitions
@@ -926,14 +1626,32 @@ impl App {
             .expect("State must be initialized before use");
         state.game_state.current_screen = CurrentScreen::Loading;
         state.game_state.maze_path = None;
-        state.wgpu_renderer.loading_screen_renderer = LoadingRenderer::new(
+        state.game_state.level_ready = false;
+        state.game_state.clear_level_snapshot(); // Never retry into the previous maze
+        state.game_state.interactables.clear();
+        state.game_state.interaction_target = None;
+        // Every level is generated from the run's seed, or the day's for daily challenges
+        let next_level = if game_over {
+            state.game_state.begin_run_seed();
+            1
+        } else {
+            state.game_state.game_ui.level + 1
+        };
+        state.wgpu_renderer.loading_screen_renderer = LoadingRenderer::for_plan(
             &state.wgpu_renderer.device,
             &state.wgpu_renderer.surface_config,
+            &state.wgpu_renderer.gpu_memory,
+            state.game_state.plan_level(next_level),
         );
 
         // Clear previous level state
         if game_over {
             state.game_state.player = Player::new();
+            if let Some(run) = &state.game_state.daily {
+                run.challenge.apply_to_player(&mut state.game_state.player);
+            } else if let Some(challenge) = &state.game_state.challenge {
+                challenge.apply_to_player(&mut state.game_state.player);
+            }
         } else {
             // Only reset position (x/z), orientation, and cell, not stats or height
             let player = &mut state.game_state.player;
@@ -950,12 +1668,15 @@ impl App {
             player.yaw = 316.0;
             player.fov = 100.0;
             player.current_cell = crate::game::maze::generator::Cell::default();
+            player.layer = Layer::Lower;
             // Optionally, reset stamina to max for new level:
             player.stamina = player.max_stamina;
             // (Do not reset base_speed, max_stamina, regen rates, etc.)
         }
         state.game_state.enemy.pathfinder.position = [0.0, 30.0, 0.0];
         state.game_state.enemy.pathfinder.locked = true;
+        state.game_state.enemy.layer = Layer::Lower;
+        state.game_state.clear_pack();
         state.game_state.exit_cell = None; // Clear exit cell to prevent accidental win condition
         state.game_state.exit_reached_timer = 0.0; // Reset exit reached timer
         state.game_state.beeper_rise_played = false; // Reset beeper rise played flag
@@ -967,9 +1688,11 @@ impl App {
         }
 
         if game_over {
+            state.game_state.journal.begin_run();
             state.game_state.set_level(1);
             state.game_state.set_score(0);
-            state.game_state.game_ui.timer = Some(GameTimer::new(TimerConfig::default()));
+            let timer_config = state.game_state.level_plan.timer.config();
+            state.game_state.game_ui.timer = Some(GameTimer::new(timer_config));
 
             // Restart background music for new game
             state
@@ -989,93 +1712,45 @@ impl App {
                 &mut state.text_renderer,
                 &mut state.game_state.game_ui,
                 &state.game_state.current_screen,
-                self.window
-                    .as_ref()
-                    .expect("Window must be initialized before use"),
+                state.game_state.delta_time,
+                state.wgpu_renderer.hud_area(),
             );
             // Ensure clean state for new game
             state.game_state.exit_cell = None;
         } else {
             let current_level = state.game_state.game_ui.level;
 
-            // Calculate completion time and performance metrics
-            let (completion_time, _) = if let Some(timer) = &state.game_state.game_ui.timer {
-                let remaining_time = timer.get_remaining_time().as_secs_f32();
-                let total_time = timer.config.duration.as_secs_f32();
-                let completion_time = total_time - remaining_time;
-
-                // Performance-based time bonus calculation
-                // Optimal time: 15 seconds, Average time: 25 seconds, Slow time: 35+ seconds
-                let time_bonus = if completion_time <= 15.0_f32 {
-                    // Exceptional performance: 15-25 seconds added
-                    let performance_ratio = (15.0 - completion_time).max(0.0_f32) / 15.0_f32;
-                    15.0_f32 + (performance_ratio * 10.0_f32)
-                } else if completion_time <= 25.0_f32 {
-                    // Good performance: 8-15 seconds added
-                    let performance_ratio = (25.0 - completion_time) / 10.0_f32;
-                    8.0_f32 + (performance_ratio * 7.0_f32)
-                } else if completion_time <= 35.0_f32 {
-                    // Average performance: 3-8 seconds added
-                    let performance_ratio = (35.0 - completion_time) / 10.0_f32;
-                    3.0_f32 + (performance_ratio * 5.0_f32)
-                } else {
-                    // Slow completion: 1-3 seconds added
-                    let performance_ratio = (45.0 - completion_time).max(0.0_f32) / 10.0_f32;
-                    1.0_f32 + (performance_ratio * 2.0_f32)
-                };
-
-                (completion_time, time_bonus)
-            } else {
-                (30.0_f32, 3.0_f32) // Fallback values
-            };
-
-            // Enhanced scoring system
-            let base_score = 150 * current_level as u32; // Increased base score
-
-            // Speed bonus calculation
-            let speed_bonus = if completion_time <= 15.0_f32 {
-                // Exceptional: 3x to 5x multiplier
-                let multiplier = 3.0_f32 + ((15.0_f32 - completion_time) / 15.0_f32) * 2.0_f32;
-                (base_score as f32 * multiplier) as u32
-            } else if completion_time <= 25.0_f32 {
-                // Good: 1.5x to 3x multiplier
-                let multiplier = 1.5_f32 + ((25.0_f32 - completion_time) / 10.0_f32) * 1.5_f32;
-                (base_score as f32 * multiplier) as u32
-            } else if completion_time <= 35.0_f32 {
-                // Average: 0.5x to 1.5x multiplier
-                let multiplier = 0.5_f32 + ((35.0_f32 - completion_time) / 10.0_f32) * 1.0_f32;
-                (base_score as f32 * multiplier) as u32
-            } else {
-                // Slow: 0.1x to 0.5x multiplier
-                let multiplier =
-                    0.1_f32 + ((45.0_f32 - completion_time).max(0.0_f32) / 10.0_f32) * 0.4_f32;
-                (base_score as f32 * multiplier) as u32
-            };
-
-            // Level progression bonus (small bonus for reaching higher levels)
-            let level_bonus = if current_level > 5 {
-                (current_level - 5) as u32 * 50
-            } else {
-                0
-            };
-
-            // Consecutive level bonus (reward for sustained performance)
-            let consecutive_bonus = if completion_time <= 20.0_f32 {
-                // Only give consecutive bonus for good performance
-                current_level as u32 * 25
-            } else {
-                0
-            };
-
-            let total_score = base_score + speed_bonus + level_bonus + consecutive_bonus;
-
-            // Update score and level
-            state
-                .game_state
-                .set_score(state.game_state.game_ui.score + total_score);
+            // Award the level's points and advance to the next level
+            let breakdown = state.game_state.apply_level_score();
+            println!(
+                "Level {} complete: +{} points",
+                current_level, breakdown.total
+            );
             state.game_state.set_level(current_level + 1);
 
             // Enhanced time management: Not supported in new timer, so skip add_time/subtract_time/prev_time
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::benchmarks::BenchmarkConfig;
+
+    #[test]
+    fn test_skipped_frame_closes_the_frame_sections() {
+        let mut profiler = Profiler::new(BenchmarkConfig {
+            enabled: true,
+            write_to_file: false,
+            ..BenchmarkConfig::default()
+        });
+        profiler.start_section("total_frame");
+        profiler.start_section("canvas_update");
+
+        abandon_frame(&mut profiler, "canvas_update");
+
+        assert!(!profiler.is_timing("canvas_update"));
+        assert!(!profiler.is_timing("total_frame"));
+    }
+}
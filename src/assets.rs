@@ -2,6 +2,12 @@
 //!
 //! This module contains all game assets embedded in the binary using `include_bytes!()`.
 //! This ensures that all assets are available at runtime without requiring external files.
+//! The [`registry`] submodule validates the embedded bundle at startup, and the
+//! [`custom_marker`] and [`panorama`] submodules load the images players can supply themselves.
+
+pub mod custom_marker;
+pub mod panorama;
+pub mod registry;
 
 // Font assets
 /// Hanken Grotesk Regular font data
@@ -27,6 +33,18 @@ pub const JEFFREE_IMAGE: &[u8] = include_bytes!("../assets/jeffree.png");
 pub const MAZE_ICON_IMAGE: &[u8] = include_bytes!("../assets/maze-icon.png");
 /// Tiles texture image data
 pub const TILES_IMAGE: &[u8] = include_bytes!("../assets/tiles.jpg");
+/// Night sky equirectangular panorama image data
+pub const PANORAMA_IMAGE: &[u8] = include_bytes!("../assets/panorama.jpg");
+
+// Enemy skin assets
+/// Slime enemy skin mesh (OBJ)
+pub const SLIME_ENEMY_MESH: &str = include_str!("../assets/enemies/slime.obj");
+/// Slime enemy skin animation (JSON)
+pub const SLIME_ENEMY_ANIMATION: &str = include_str!("../assets/enemies/slime.json");
+/// Hunter enemy skin mesh (OBJ)
+pub const HUNTER_ENEMY_MESH: &str = include_str!("../assets/enemies/hunter.obj");
+/// Hunter enemy skin animation (JSON)
+pub const HUNTER_ENEMY_ANIMATION: &str = include_str!("../assets/enemies/hunter.json");
 
 // Compass assets
 /// Compass base image data
@@ -111,18 +129,12 @@ pub fn compass_needles() -> &'static [&'static [u8]] {
     ]
 }
 
-/// Returns all icon textures with their IDs
+/// Returns the icon textures every button manager loads at startup, with their IDs
+///
+/// Upgrade icons are carried by the upgrade definitions instead and registered by the
+/// upgrade menu.
 pub fn icon_textures() -> &'static [(&'static str, &'static [u8])] {
-    &[
-        ("blank_icon", BLANK_ICON),
-        ("dash_icon", DASH_ICON),
-        ("head_start_icon", HEAD_START_ICON),
-        ("silent_step_icon", SILENT_STEP_ICON),
-        ("slower_seconds_icon", SLOWER_SECONDS_ICON),
-        ("speed_up_icon", SPEED_UP_ICON),
-        ("tall_boots_icon", TALL_BOOTS_ICON),
-        ("unknown_icon", UNKNOWN_ICON),
-    ]
+    &[("blank_icon", BLANK_ICON)]
 }
 
 /// Returns all font data with their names
@@ -0,0 +1,337 @@
+//! Custom player marker supplied by the player.
+//!
+//! Players can drop their own logo or avatar into the data directory (see [`crate::paths`]) to
+//! use as their marker, for example as the badge on the game over screen. The file is read and
+//! decoded on a background thread by [`MarkerLoader`] at startup and whenever the player asks
+//! for a reload, so a large file never stalls a frame.
+//!
+//! Only PNG files are accepted. Images over [`MAX_MARKER_SIZE`] pixels on a side are scaled
+//! down to fit. Anything that can't be used falls back to the built-in [`default_marker`], and
+//! the [`MarkerRejection`] explains why in the pause menu instead of failing.
+//!
+//! # Usage
+//!
+//! ```rust,ignore
+//! let mut loader = Some(MarkerLoader::start(&paths::get().custom_marker()));
+//!
+//! // Once per frame
+//! if let Some(result) = loader.as_ref().and_then(MarkerLoader::poll) {
+//!     loader = None;
+//!     let marker = match result {
+//!         Ok(Some(image)) => image,
+//!         Ok(None) => default_marker(),
+//!         Err(rejection) => {
+//!             show_note(&rejection.to_string());
+//!             default_marker()
+//!         }
+//!     };
+//!     // Upload `marker` under MARKER_TEXTURE_ID
+//! }
+//! ```
+
+use image::{ImageFormat, ImageReader};
+use std::fmt;
+use std::fs;
+use std::io::{Cursor, ErrorKind};
+use std::path::Path;
+use std::sync::mpsc::{self, Receiver};
+use std::thread;
+
+/// Icon texture ID the marker is uploaded under.
+pub const MARKER_TEXTURE_ID: &str = "custom_marker";
+
+/// Largest width or height in pixels a marker is used at; bigger images are scaled down.
+pub const MAX_MARKER_SIZE: u32 = 256;
+
+/// Largest marker file accepted, in bytes.
+pub const MAX_MARKER_FILE_BYTES: u64 = 8 * 1024 * 1024;
+
+/// Largest width or height in pixels a marker file may have before it is decoded at all.
+///
+/// Keeps a small, highly compressed file from expanding into gigabytes of pixels.
+pub const MAX_MARKER_SOURCE_SIZE: u32 = 4096;
+
+/// Side length in pixels of the built-in marker.
+const DEFAULT_MARKER_SIZE: u32 = 32;
+
+/// Decoded marker pixels, ready to upload.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MarkerImage {
+    /// Tightly packed RGBA8 pixels, row-major from the top-left
+    pub rgba: Vec<u8>,
+    /// Width and height in pixels, each at most [`MAX_MARKER_SIZE`]
+    pub dimensions: (u32, u32),
+}
+
+/// Why a custom marker file was not used.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MarkerRejection {
+    /// The file is an image, but not a PNG; holds the format's name
+    WrongFormat(String),
+    /// The file or its pixel dimensions are over the limits; holds what was too big
+    TooLarge(String),
+    /// The file could not be read or decoded; holds the underlying error
+    DecodeError(String),
+}
+
+impl fmt::Display for MarkerRejection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::WrongFormat(format) => {
+                write!(
+                    f,
+                    "Custom marker not used: it is a {} image, not a PNG",
+                    format
+                )
+            }
+            Self::TooLarge(detail) => write!(f, "Custom marker not used: {}", detail),
+            Self::DecodeError(error) => {
+                write!(
+                    f,
+                    "Custom marker not used: it could not be read ({})",
+                    error
+                )
+            }
+        }
+    }
+}
+
+/// Result of loading the marker file: `Ok(None)` when there is no file.
+pub type MarkerLoad = Result<Option<MarkerImage>, MarkerRejection>;
+
+/// Validates and decodes a marker file's contents.
+///
+/// # Arguments
+/// * `bytes` - The whole file
+///
+/// # Returns
+/// The marker, scaled down to fit within [`MAX_MARKER_SIZE`], or why it was rejected
+pub fn decode_marker(bytes: &[u8]) -> Result<MarkerImage, MarkerRejection> {
+    if bytes.len() as u64 > MAX_MARKER_FILE_BYTES {
+        return Err(MarkerRejection::TooLarge(format!(
+            "the file is {} KB, over the {} KB limit",
+            bytes.len() / 1024,
+            MAX_MARKER_FILE_BYTES / 1024
+        )));
+    }
+
+    match image::guess_format(bytes) {
+        Ok(ImageFormat::Png) => {}
+        Ok(format) => {
+            return Err(MarkerRejection::WrongFormat(
+                format!("{:?}", format).to_uppercase(),
+            ));
+        }
+        Err(_) => {
+            return Err(MarkerRejection::DecodeError(
+                "not a recognized image".to_string(),
+            ));
+        }
+    }
+
+    // Check the header before decoding so huge images are never expanded
+    let (width, height) = ImageReader::with_format(Cursor::new(bytes), ImageFormat::Png)
+        .into_dimensions()
+        .map_err(|e| MarkerRejection::DecodeError(e.to_string()))?;
+    if width > MAX_MARKER_SOURCE_SIZE || height > MAX_MARKER_SOURCE_SIZE {
+        return Err(MarkerRejection::TooLarge(format!(
+            "it is {}x{}, over the {}x{} limit",
+            width, height, MAX_MARKER_SOURCE_SIZE, MAX_MARKER_SOURCE_SIZE
+        )));
+    }
+
+    let mut rgba = image::load_from_memory_with_format(bytes, ImageFormat::Png)
+        .map_err(|e| MarkerRejection::DecodeError(e.to_string()))?
+        .to_rgba8();
+
+    let longest = width.max(height);
+    if longest > MAX_MARKER_SIZE {
+        let scale = MAX_MARKER_SIZE as f32 / longest as f32;
+        let scaled = |side: u32| ((side as f32 * scale).round() as u32).clamp(1, MAX_MARKER_SIZE);
+        rgba = image::imageops::resize(
+            &rgba,
+            scaled(width),
+            scaled(height),
+            image::imageops::FilterType::Triangle,
+        );
+    }
+
+    let dimensions = rgba.dimensions();
+    Ok(MarkerImage {
+        rgba: rgba.into_raw(),
+        dimensions,
+    })
+}
+
+/// Reads and decodes the marker file.
+///
+/// # Arguments
+/// * `path` - Where the marker file is expected
+///
+/// # Returns
+/// The marker, `Ok(None)` if there is no file, or why it was rejected
+pub fn read_marker(path: &Path) -> MarkerLoad {
+    let size = match fs::metadata(path) {
+        Ok(metadata) => metadata.len(),
+        Err(e) if e.kind() == ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(MarkerRejection::DecodeError(e.to_string())),
+    };
+    // Checked before reading so an enormous file is never pulled into memory
+    if size > MAX_MARKER_FILE_BYTES {
+        return Err(MarkerRejection::TooLarge(format!(
+            "the file is {} KB, over the {} KB limit",
+            size / 1024,
+            MAX_MARKER_FILE_BYTES / 1024
+        )));
+    }
+
+    let bytes = fs::read(path).map_err(|e| MarkerRejection::DecodeError(e.to_string()))?;
+    decode_marker(&bytes).map(Some)
+}
+
+/// Returns the built-in marker: a white dot with a dark outline.
+pub fn default_marker() -> MarkerImage {
+    let size = DEFAULT_MARKER_SIZE;
+    let center = (size as f32 - 1.0) / 2.0;
+    let radius = size as f32 / 2.0 - 1.0;
+
+    let mut rgba = Vec::with_capacity((size * size * 4) as usize);
+    for y in 0..size {
+        for x in 0..size {
+            let distance = (x as f32 - center).hypot(y as f32 - center);
+            let pixel = if distance <= radius - 3.0 {
+                [240, 240, 240, 255]
+            } else if distance <= radius {
+                [30, 30, 40, 255]
+            } else {
+                [0, 0, 0, 0]
+            };
+            rgba.extend_from_slice(&pixel);
+        }
+    }
+
+    MarkerImage {
+        rgba,
+        dimensions: (size, size),
+    }
+}
+
+/// Loads the marker file on a background thread.
+pub struct MarkerLoader {
+    /// Receives the single result from the background thread
+    result: Receiver<MarkerLoad>,
+}
+
+impl MarkerLoader {
+    /// Starts reading and decoding `path` on a background thread.
+    ///
+    /// # Arguments
+    /// * `path` - Where the marker file is expected; it does not need to exist
+    ///
+    /// # Returns
+    /// A loader to [`poll`](Self::poll) once per frame
+    pub fn start(path: &Path) -> Self {
+        let (sender, result) = mpsc::channel();
+
+        let spawned = {
+            let path = path.to_path_buf();
+            thread::Builder::new()
+                .name("custom-marker-loader".to_string())
+                .spawn(move || {
+                    // The loader may have been dropped by a newer reload
+                    let _ = sender.send(read_marker(&path));
+                })
+        };
+        if let Err(e) = spawned {
+            eprintln!("Failed to start custom marker loader: {}", e);
+        }
+
+        Self { result }
+    }
+
+    /// Checks whether loading has finished.
+    ///
+    /// # Returns
+    /// The result once it is ready, or `None` while the file is still loading. If the
+    /// background thread could not run, this reports a decode error.
+    pub fn poll(&self) -> Option<MarkerLoad> {
+        match self.result.try_recv() {
+            Ok(result) => Some(result),
+            Err(mpsc::TryRecvError::Empty) => None,
+            Err(mpsc::TryRecvError::Disconnected) => Some(Err(MarkerRejection::DecodeError(
+                "the loader stopped unexpectedly".to_string(),
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::{Rgb, RgbImage, Rgba, RgbaImage};
+
+    fn png(width: u32, height: u32) -> Vec<u8> {
+        let image = RgbaImage::from_pixel(width, height, Rgba([200, 50, 50, 255]));
+        let mut bytes = Cursor::new(Vec::new());
+        image.write_to(&mut bytes, ImageFormat::Png).unwrap();
+        bytes.into_inner()
+    }
+
+    #[test]
+    fn test_small_png_is_used_as_is() {
+        let marker = decode_marker(&png(64, 48)).unwrap();
+        assert_eq!(marker.dimensions, (64, 48));
+        assert_eq!(marker.rgba.len(), 64 * 48 * 4);
+        assert_eq!(&marker.rgba[..4], &[200, 50, 50, 255]);
+    }
+
+    #[test]
+    fn test_oversized_png_is_scaled_down_keeping_its_shape() {
+        let marker = decode_marker(&png(512, 300)).unwrap();
+        assert_eq!(marker.dimensions, (MAX_MARKER_SIZE, 150));
+        assert_eq!(marker.rgba.len(), (MAX_MARKER_SIZE * 150 * 4) as usize);
+
+        let huge = png(MAX_MARKER_SOURCE_SIZE + 1, 1);
+        assert!(matches!(
+            decode_marker(&huge),
+            Err(MarkerRejection::TooLarge(_))
+        ));
+    }
+
+    #[test]
+    fn test_jpeg_is_rejected_as_the_wrong_format() {
+        let image = RgbImage::from_pixel(16, 16, Rgb([10, 20, 30]));
+        let mut bytes = Cursor::new(Vec::new());
+        image.write_to(&mut bytes, ImageFormat::Jpeg).unwrap();
+
+        let rejection = decode_marker(bytes.get_ref()).unwrap_err();
+        assert_eq!(rejection, MarkerRejection::WrongFormat("JPEG".to_string()));
+        assert!(rejection.to_string().contains("JPEG"));
+    }
+
+    #[test]
+    fn test_garbage_and_truncated_files_fail_to_decode() {
+        assert!(matches!(
+            decode_marker(b"definitely not an image"),
+            Err(MarkerRejection::DecodeError(_))
+        ));
+
+        let mut truncated = png(32, 32);
+        truncated.truncate(truncated.len() / 2);
+        assert!(matches!(
+            decode_marker(&truncated),
+            Err(MarkerRejection::DecodeError(_))
+        ));
+    }
+
+    #[test]
+    fn test_missing_file_falls_back_without_a_rejection() {
+        let path = std::env::temp_dir().join("mirador_custom_marker_missing.png");
+        let _ = fs::remove_file(&path);
+        assert_eq!(read_marker(&path), Ok(None));
+
+        let default = default_marker();
+        let (width, height) = default.dimensions;
+        assert_eq!(default.rgba.len(), (width * height * 4) as usize);
+    }
+}
@@ -0,0 +1,283 @@
+//! Equirectangular panorama drawn as the sky in the panorama background mode.
+//!
+//! The game embeds a night sky panorama, and players can replace it by dropping their own
+//! equirectangular image into the data directory (see [`crate::paths`]). [`PanoramaLoader`]
+//! reads and decodes the file on a background thread the first time the panorama background is
+//! chosen, builds its whole mip chain, and measures its average color for the fog, so none of
+//! that work ever lands on a frame.
+//!
+//! Any format the image crate reads is accepted. Images wider than [`MAX_PANORAMA_WIDTH`] are
+//! scaled down. A file that can't be used is reported on the console and the embedded panorama
+//! is used instead.
+//!
+//! # Usage
+//!
+//! ```rust,ignore
+//! let loader = PanoramaLoader::start(&paths::get().custom_panorama());
+//!
+//! // Once per frame
+//! if let Some(result) = loader.poll() {
+//!     match result {
+//!         Ok(panorama) => upload(&panorama.mips, panorama.average_color),
+//!         Err(e) => eprintln!("{}", e),
+//!     }
+//! }
+//! ```
+
+use crate::assets;
+use crate::renderer::color::srgb_to_linear;
+use crate::renderer::icon::generate_mip_chain;
+use image::ImageReader;
+use std::f32::consts::PI;
+use std::fs;
+use std::io::{Cursor, ErrorKind};
+use std::path::Path;
+use std::sync::mpsc::{self, Receiver};
+use std::thread;
+
+/// Widest a panorama is used at in pixels; wider images are scaled down, keeping their shape.
+pub const MAX_PANORAMA_WIDTH: u32 = 4096;
+
+/// Largest panorama file accepted, in bytes.
+pub const MAX_PANORAMA_FILE_BYTES: u64 = 32 * 1024 * 1024;
+
+/// Largest width or height in pixels a panorama file may have before it is decoded at all.
+pub const MAX_PANORAMA_SOURCE_SIZE: u32 = 16384;
+
+/// Decoded panorama, ready to upload.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PanoramaImage {
+    /// Pixels and size of every mip level, from the full image down to 1x1, as sRGB RGBA8
+    pub mips: Vec<(Vec<u8>, (u32, u32))>,
+    /// Linear RGBA average over the whole sphere, weighted by the area each row covers
+    pub average_color: [f32; 4],
+}
+
+/// Validates and decodes a panorama file's contents.
+///
+/// # Arguments
+/// * `bytes` - The whole file
+///
+/// # Returns
+/// The panorama with its mip chain and average color, or why it could not be used
+pub fn decode_panorama(bytes: &[u8]) -> Result<PanoramaImage, String> {
+    let reader = ImageReader::new(Cursor::new(bytes))
+        .with_guessed_format()
+        .map_err(|e| e.to_string())?;
+    let format = reader
+        .format()
+        .ok_or_else(|| "not a recognized image".to_string())?;
+
+    // Check the header before decoding so huge images are never expanded
+    let (width, height) = ImageReader::with_format(Cursor::new(bytes), format)
+        .into_dimensions()
+        .map_err(|e| e.to_string())?;
+    if width > MAX_PANORAMA_SOURCE_SIZE || height > MAX_PANORAMA_SOURCE_SIZE {
+        return Err(format!(
+            "it is {}x{}, over the {}x{} limit",
+            width, height, MAX_PANORAMA_SOURCE_SIZE, MAX_PANORAMA_SOURCE_SIZE
+        ));
+    }
+
+    let mut rgba = reader.decode().map_err(|e| e.to_string())?.to_rgba8();
+    if width > MAX_PANORAMA_WIDTH {
+        let scaled_height = ((height as u64 * MAX_PANORAMA_WIDTH as u64) / width as u64).max(1);
+        rgba = image::imageops::resize(
+            &rgba,
+            MAX_PANORAMA_WIDTH,
+            scaled_height as u32,
+            image::imageops::FilterType::Triangle,
+        );
+    }
+
+    let dimensions = rgba.dimensions();
+    let average_color = average_color(rgba.as_raw(), dimensions);
+    Ok(PanoramaImage {
+        mips: generate_mip_chain(rgba.as_raw(), dimensions),
+        average_color,
+    })
+}
+
+/// Averages an equirectangular image's color over the sphere it wraps.
+///
+/// Rows near the poles are stretched across the whole width but cover little of the sky, so
+/// each row counts in proportion to the cosine of its latitude.
+///
+/// # Arguments
+/// * `rgba` - sRGB RGBA8 pixels, row-major from the top-left
+/// * `dimensions` - Width and height in pixels
+///
+/// # Returns
+/// The linear RGBA average, with alpha always 1.0
+pub fn average_color(rgba: &[u8], dimensions: (u32, u32)) -> [f32; 4] {
+    let (width, height) = (dimensions.0 as usize, dimensions.1 as usize);
+    let linear: Vec<f32> = (0..=255u8)
+        .map(|value| srgb_to_linear(value as f32 / 255.0))
+        .collect();
+
+    let mut sum = [0.0_f64; 3];
+    let mut total_weight = 0.0_f64;
+    for (y, row) in rgba.chunks_exact(width * 4).take(height).enumerate() {
+        let latitude = (0.5 - (y as f32 + 0.5) / height as f32) * PI;
+        let weight = latitude.cos().max(0.0) as f64;
+        for pixel in row.chunks_exact(4) {
+            for (channel, total) in sum.iter_mut().enumerate() {
+                *total += linear[pixel[channel] as usize] as f64 * weight;
+            }
+        }
+        total_weight += weight * width as f64;
+    }
+
+    if total_weight <= 0.0 {
+        return [0.0, 0.0, 0.0, 1.0];
+    }
+    let [r, g, b] = sum.map(|total| (total / total_weight) as f32);
+    [r, g, b, 1.0]
+}
+
+/// Reads and decodes the player's panorama, falling back to the embedded one.
+///
+/// # Arguments
+/// * `path` - Where the player's panorama is expected; it does not need to exist
+///
+/// # Returns
+/// The panorama to draw, or an error only if the embedded panorama is broken too
+pub fn read_panorama(path: &Path) -> Result<PanoramaImage, String> {
+    let custom = match fs::metadata(path) {
+        Ok(metadata) if metadata.len() > MAX_PANORAMA_FILE_BYTES => Err(format!(
+            "the file is {} KB, over the {} KB limit",
+            metadata.len() / 1024,
+            MAX_PANORAMA_FILE_BYTES / 1024
+        )),
+        Ok(_) => fs::read(path)
+            .map_err(|e| e.to_string())
+            .and_then(|bytes| decode_panorama(&bytes)),
+        Err(e) if e.kind() == ErrorKind::NotFound => return embedded_panorama(),
+        Err(e) => Err(e.to_string()),
+    };
+
+    custom.or_else(|e| {
+        eprintln!("Custom panorama not used: {}", e);
+        embedded_panorama()
+    })
+}
+
+/// Decodes the panorama embedded in the binary.
+fn embedded_panorama() -> Result<PanoramaImage, String> {
+    decode_panorama(assets::PANORAMA_IMAGE)
+        .map_err(|e| format!("Embedded panorama could not be decoded: {}", e))
+}
+
+/// Loads the panorama on a background thread.
+pub struct PanoramaLoader {
+    /// Receives the single result from the background thread
+    result: Receiver<Result<PanoramaImage, String>>,
+}
+
+impl PanoramaLoader {
+    /// Starts reading and decoding the panorama on a background thread.
+    ///
+    /// # Arguments
+    /// * `path` - Where the player's panorama is expected; it does not need to exist
+    ///
+    /// # Returns
+    /// A loader to [`poll`](Self::poll) once per frame
+    pub fn start(path: &Path) -> Self {
+        let (sender, result) = mpsc::channel();
+
+        let spawned = {
+            let path = path.to_path_buf();
+            thread::Builder::new()
+                .name("panorama-loader".to_string())
+                .spawn(move || {
+                    // The renderer may have been dropped while the panorama was decoding
+                    let _ = sender.send(read_panorama(&path));
+                })
+        };
+        if let Err(e) = spawned {
+            eprintln!("Failed to start panorama loader: {}", e);
+        }
+
+        Self { result }
+    }
+
+    /// Checks whether loading has finished.
+    ///
+    /// # Returns
+    /// The result once it is ready, or `None` while the panorama is still loading. If the
+    /// background thread could not run, this reports an error.
+    pub fn poll(&self) -> Option<Result<PanoramaImage, String>> {
+        match self.result.try_recv() {
+            Ok(result) => Some(result),
+            Err(mpsc::TryRecvError::Empty) => None,
+            Err(mpsc::TryRecvError::Disconnected) => {
+                Some(Err("The panorama loader stopped unexpectedly".to_string()))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::{ImageFormat, Rgba, RgbaImage};
+
+    #[test]
+    fn test_embedded_panorama_is_a_dark_sky_with_a_full_mip_chain() {
+        let panorama = embedded_panorama().unwrap();
+        let (base, (width, height)) = &panorama.mips[0];
+        assert_eq!((*width, *height), (2 * height, *height));
+        assert_eq!(base.len(), (width * height * 4) as usize);
+        assert_eq!(panorama.mips.last().unwrap().1, (1, 1));
+
+        let [r, g, b, a] = panorama.average_color;
+        assert_eq!(a, 1.0);
+        assert!(r.max(g).max(b) < 0.1, "{:?}", panorama.average_color);
+        assert!(b >= r * 0.5, "{:?}", panorama.average_color);
+    }
+
+    #[test]
+    fn test_average_color_weights_rows_by_the_sky_they_cover() {
+        // White sky over black ground averages to half gray in linear light
+        let mut half = RgbaImage::from_pixel(64, 32, Rgba([0, 0, 0, 255]));
+        for y in 0..16 {
+            for x in 0..64 {
+                half.put_pixel(x, y, Rgba([255, 255, 255, 255]));
+            }
+        }
+        let average = average_color(half.as_raw(), half.dimensions());
+        assert!((average[0] - 0.5).abs() < 1e-3, "{:?}", average);
+
+        // A white top row is only a sliver of sky around the pole
+        let mut pole = RgbaImage::from_pixel(64, 32, Rgba([0, 0, 0, 255]));
+        for x in 0..64 {
+            pole.put_pixel(x, 0, Rgba([255, 255, 255, 255]));
+        }
+        let average = average_color(pole.as_raw(), pole.dimensions());
+        assert!(average[0] < 0.01, "{:?}", average);
+    }
+
+    #[test]
+    fn test_wide_panorama_is_scaled_down_keeping_its_shape() {
+        let image = RgbaImage::from_pixel(MAX_PANORAMA_WIDTH * 2, 8, Rgba([40, 80, 120, 255]));
+        let mut bytes = Cursor::new(Vec::new());
+        image.write_to(&mut bytes, ImageFormat::Png).unwrap();
+
+        let panorama = decode_panorama(bytes.get_ref()).unwrap();
+        assert_eq!(panorama.mips[0].1, (MAX_PANORAMA_WIDTH, 4));
+    }
+
+    #[test]
+    fn test_missing_or_broken_file_falls_back_to_the_embedded_panorama() {
+        let embedded = embedded_panorama().unwrap();
+
+        let missing = std::env::temp_dir().join("mirador_panorama_missing.jpg");
+        let _ = fs::remove_file(&missing);
+        assert_eq!(read_panorama(&missing).unwrap(), embedded);
+
+        let broken = std::env::temp_dir().join("mirador_panorama_broken.jpg");
+        fs::write(&broken, b"definitely not an image").unwrap();
+        assert_eq!(read_panorama(&broken).unwrap(), embedded);
+        let _ = fs::remove_file(&broken);
+    }
+}
@@ -0,0 +1,367 @@
+//! Startup validation for embedded assets.
+//!
+//! This module provides [`AssetRegistry`], which checks every asset in the embedded bundle when
+//! the game starts instead of letting each loader print-and-continue on its own. Images have
+//! their headers decoded, and fonts and audio have their magic bytes checked. All problems are
+//! collected into one report.
+//!
+//! Assets are split into two groups:
+//! - **Critical** assets (fonts and the main textures) make the game show an on-screen
+//!   diagnostic overlay listing everything that is broken
+//! - **Non-critical** assets (icons, compass art, audio) only log a warning
+//!
+//! Broken images are never silently skipped: loaders use [`decode_image_or_placeholder`], which
+//! substitutes a solid magenta texture so the breakage is obvious in game. Icons that fail to
+//! load fall back to the icon renderer's checkerboard missing icon.
+//!
+//! # Usage
+//!
+//! ```rust,ignore
+//! let registry = AssetRegistry::validate_embedded();
+//! registry.report();
+//!
+//! if let Some(text) = registry.diagnostic_text() {
+//!     // Show `text` in an overlay
+//! }
+//! ```
+
+use crate::assets;
+use crate::game::upgrades::AvailableUpgrade;
+use std::io::Cursor;
+
+/// Side length in pixels of the magenta placeholder texture.
+pub const PLACEHOLDER_TEXTURE_SIZE: u32 = 64;
+
+/// Color of the placeholder texture substituted for broken images.
+pub const PLACEHOLDER_COLOR: [u8; 4] = [255, 0, 255, 255];
+
+/// The kind of data an embedded asset holds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AssetKind {
+    /// A PNG or JPEG image
+    Image,
+    /// A TrueType or OpenType font
+    Font,
+    /// An Ogg or WAV audio file
+    Audio,
+}
+
+/// An entry in the embedded asset manifest.
+#[derive(Debug, Clone, Copy)]
+pub struct AssetEntry {
+    /// Human-readable asset name shown in reports
+    pub name: &'static str,
+    /// What kind of data the asset holds
+    pub kind: AssetKind,
+    /// Whether a broken copy should block with the diagnostic overlay
+    pub critical: bool,
+    /// The embedded bytes
+    pub bytes: &'static [u8],
+}
+
+impl AssetEntry {
+    /// Creates a manifest entry.
+    const fn new(
+        name: &'static str,
+        kind: AssetKind,
+        critical: bool,
+        bytes: &'static [u8],
+    ) -> Self {
+        Self {
+            name,
+            kind,
+            critical,
+            bytes,
+        }
+    }
+}
+
+/// A problem found while validating an asset.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AssetProblem {
+    /// Name of the broken asset
+    pub name: &'static str,
+    /// What kind of data the asset should hold
+    pub kind: AssetKind,
+    /// Whether the asset is critical
+    pub critical: bool,
+    /// Why the asset failed validation
+    pub message: String,
+}
+
+/// Results of validating a set of embedded assets.
+#[derive(Debug, Clone, Default)]
+pub struct AssetRegistry {
+    /// Every problem found, in manifest order
+    problems: Vec<AssetProblem>,
+}
+
+impl AssetRegistry {
+    /// Validates the full embedded asset manifest.
+    ///
+    /// # Returns
+    /// A registry holding every problem found
+    pub fn validate_embedded() -> Self {
+        Self::validate(&manifest())
+    }
+
+    /// Validates a list of assets.
+    ///
+    /// # Arguments
+    /// * `entries` - The assets to check
+    ///
+    /// # Returns
+    /// A registry holding every problem found
+    pub fn validate(entries: &[AssetEntry]) -> Self {
+        let problems = entries
+            .iter()
+            .filter_map(|entry| {
+                check_asset(entry).err().map(|message| AssetProblem {
+                    name: entry.name,
+                    kind: entry.kind,
+                    critical: entry.critical,
+                    message,
+                })
+            })
+            .collect();
+        Self { problems }
+    }
+
+    /// Returns every problem found, in manifest order.
+    pub fn problems(&self) -> &[AssetProblem] {
+        &self.problems
+    }
+
+    /// Returns `true` if any critical asset is broken.
+    pub fn has_critical_problems(&self) -> bool {
+        self.problems.iter().any(|problem| problem.critical)
+    }
+
+    /// Returns `true` if the named asset failed validation.
+    #[cfg(test)]
+    pub fn is_broken(&self, name: &str) -> bool {
+        self.problems.iter().any(|problem| problem.name == name)
+    }
+
+    /// Logs every problem to the console.
+    ///
+    /// Critical problems are logged as errors and the rest as warnings.
+    pub fn report(&self) {
+        for problem in &self.problems {
+            let level = if problem.critical { "ERROR" } else { "WARNING" };
+            eprintln!(
+                "[ASSETS] {}: {:?} asset '{}' is broken: {}",
+                level, problem.kind, problem.name, problem.message
+            );
+        }
+    }
+
+    /// Builds the text for the on-screen diagnostic overlay.
+    ///
+    /// # Returns
+    /// `Some(text)` listing every broken asset if any critical asset is broken, otherwise `None`
+    pub fn diagnostic_text(&self) -> Option<String> {
+        if !self.has_critical_problems() {
+            return None;
+        }
+
+        let mut text = String::from("Broken embedded assets (placeholders in use):");
+        for problem in &self.problems {
+            let marker = if problem.critical { "!" } else { "-" };
+            text.push_str(&format!(
+                "\n{} {} ({:?}): {}",
+                marker, problem.name, problem.kind, problem.message
+            ));
+        }
+        Some(text)
+    }
+}
+
+/// Returns the manifest of every embedded asset.
+pub fn manifest() -> Vec<AssetEntry> {
+    use AssetKind::{Audio, Font, Image};
+
+    let mut entries = vec![
+        AssetEntry::new("title image", Image, true, assets::TITLE_IMAGE),
+        AssetEntry::new("slime enemy", Image, true, assets::SLIME_IMAGE),
+        AssetEntry::new("ceiling tiles", Image, true, assets::TILES_IMAGE),
+        AssetEntry::new("frankie", Image, false, assets::FRANKIE_IMAGE),
+        AssetEntry::new("jeffree", Image, false, assets::JEFFREE_IMAGE),
+        AssetEntry::new("maze icon", Image, false, assets::MAZE_ICON_IMAGE),
+        AssetEntry::new("sky panorama", Image, false, assets::PANORAMA_IMAGE),
+        AssetEntry::new("compass base", Image, false, assets::COMPASS_BASE),
+        AssetEntry::new("gold compass", Image, false, assets::GOLD_COMPASS),
+        AssetEntry::new("beeper rise", Audio, false, assets::AUDIO_BEEPER_RISE),
+        AssetEntry::new("complete", Audio, false, assets::AUDIO_COMPLETE),
+        AssetEntry::new(
+            "jeffree star asmr",
+            Audio,
+            false,
+            assets::AUDIO_JEFFREE_STAR_ASMR,
+        ),
+        AssetEntry::new("select", Audio, false, assets::AUDIO_SELECT),
+        AssetEntry::new("single step", Audio, false, assets::AUDIO_SINGLE_STEP),
+        AssetEntry::new("slime track", Audio, false, assets::AUDIO_SLIME_TRACK),
+        AssetEntry::new("upgrade", Audio, false, assets::AUDIO_UPGRADE),
+        AssetEntry::new("wall", Audio, false, assets::AUDIO_WALL),
+        AssetEntry::new("main track", Audio, false, assets::MUSIC_MAIN_TRACK),
+        AssetEntry::new(
+            "main track (stripped)",
+            Audio,
+            false,
+            assets::MUSIC_MAIN_TRACK_STRIPPED,
+        ),
+    ];
+
+    entries.extend(
+        assets::fonts()
+            .iter()
+            .map(|(name, bytes)| AssetEntry::new(name, Font, true, bytes)),
+    );
+    entries.extend(
+        assets::compass_needles()
+            .iter()
+            .map(|bytes| AssetEntry::new("compass needle", Image, false, bytes)),
+    );
+    entries.extend(
+        assets::icon_textures()
+            .iter()
+            .map(|(id, bytes)| AssetEntry::new(id, Image, false, bytes)),
+    );
+    entries.extend(
+        AvailableUpgrade::ALL
+            .iter()
+            .filter_map(|upgrade| upgrade.to_upgrade().icon)
+            .map(|icon| AssetEntry::new(icon.id, Image, false, icon.bytes)),
+    );
+    entries
+}
+
+/// Checks that an asset's bytes look like the expected kind of file.
+///
+/// # Returns
+/// `Ok(())` if the asset looks valid, or a description of the problem
+fn check_asset(entry: &AssetEntry) -> Result<(), String> {
+    if entry.bytes.is_empty() {
+        return Err("file is empty".to_string());
+    }
+
+    match entry.kind {
+        AssetKind::Image => check_image_header(entry.bytes),
+        AssetKind::Font => check_magic(
+            entry.bytes,
+            &[b"\x00\x01\x00\x00", b"OTTO", b"true", b"ttcf"],
+        ),
+        AssetKind::Audio => {
+            if entry.bytes.len() >= 12 && &entry.bytes[..4] == b"RIFF" {
+                check_magic(&entry.bytes[8..], &[b"WAVE"])
+            } else {
+                check_magic(entry.bytes, &[b"OggS"])
+            }
+        }
+    }
+}
+
+/// Decodes an image header to make sure the format is recognized and the dimensions are valid.
+fn check_image_header(bytes: &[u8]) -> Result<(), String> {
+    let (width, height) = image::ImageReader::new(Cursor::new(bytes))
+        .with_guessed_format()
+        .map_err(|e| e.to_string())?
+        .into_dimensions()
+        .map_err(|e| e.to_string())?;
+
+    if width == 0 || height == 0 {
+        return Err(format!("image has invalid size {}x{}", width, height));
+    }
+    Ok(())
+}
+
+/// Checks that `bytes` starts with one of the accepted signatures.
+fn check_magic(bytes: &[u8], signatures: &[&[u8]]) -> Result<(), String> {
+    if signatures
+        .iter()
+        .any(|signature| bytes.starts_with(signature))
+    {
+        Ok(())
+    } else {
+        Err("unrecognized file signature".to_string())
+    }
+}
+
+/// Returns the solid magenta texture substituted for broken images.
+pub fn placeholder_texture() -> image::RgbaImage {
+    image::RgbaImage::from_pixel(
+        PLACEHOLDER_TEXTURE_SIZE,
+        PLACEHOLDER_TEXTURE_SIZE,
+        image::Rgba(PLACEHOLDER_COLOR),
+    )
+}
+
+/// Decodes an embedded image, substituting the magenta placeholder if it is broken.
+///
+/// # Arguments
+/// * `name` - Asset name used in the warning
+/// * `bytes` - The encoded image
+///
+/// # Returns
+/// The decoded RGBA image, or [`placeholder_texture`] if decoding failed
+pub fn decode_image_or_placeholder(name: &str, bytes: &[u8]) -> image::RgbaImage {
+    match image::load_from_memory(bytes) {
+        Ok(img) => img.to_rgba8(),
+        Err(e) => {
+            eprintln!(
+                "[ASSETS] Failed to decode '{}': {}, using placeholder texture",
+                name, e
+            );
+            placeholder_texture()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_embedded_manifest_is_valid() {
+        let registry = AssetRegistry::validate_embedded();
+        assert!(registry.problems().is_empty(), "{:?}", registry.problems());
+        assert!(registry.diagnostic_text().is_none());
+    }
+
+    #[test]
+    fn test_truncated_image_is_reported_and_substituted() {
+        let truncated = &assets::TILES_IMAGE[..16];
+        let registry = AssetRegistry::validate(&[
+            AssetEntry::new("ceiling tiles", AssetKind::Image, true, truncated),
+            AssetEntry::new("dash_icon", AssetKind::Image, false, assets::DASH_ICON),
+        ]);
+
+        assert_eq!(registry.problems().len(), 1);
+        assert!(registry.is_broken("ceiling tiles"));
+        assert!(registry.has_critical_problems());
+        assert!(
+            registry
+                .diagnostic_text()
+                .unwrap()
+                .contains("ceiling tiles")
+        );
+
+        let substituted = decode_image_or_placeholder("ceiling tiles", truncated);
+        assert_eq!(substituted, placeholder_texture());
+        assert_eq!(substituted.get_pixel(0, 0).0, PLACEHOLDER_COLOR);
+    }
+
+    #[test]
+    fn test_broken_icon_only_warns() {
+        let registry = AssetRegistry::validate(&[AssetEntry::new(
+            "dash_icon",
+            AssetKind::Image,
+            false,
+            b"not a png",
+        )]);
+        assert!(registry.is_broken("dash_icon"));
+        assert!(!registry.has_critical_problems());
+        assert!(registry.diagnostic_text().is_none());
+    }
+}
@@ -80,10 +80,12 @@ impl FrameRateCounter {
 }
 
 /// Memory usage tracker
+#[cfg(test)]
 pub struct MemoryTracker {
     initial_memory: Option<usize>,
 }
 
+#[cfg(test)]
 impl MemoryTracker {
     /// Creates a new memory tracker
     pub fn new() -> Self {
@@ -153,18 +155,30 @@ impl Profiler {
         }
     }
 
-    /// Times a closure execution
-    pub fn time_closure<F, R>(&mut self, name: &str, f: F) -> R
-    where
-        F: FnOnce() -> R,
-    {
+    /// Returns whether a section has been started and not yet ended
+    ///
+    /// # Arguments
+    /// * `name` - The section to check
+    #[cfg(test)]
+    pub fn is_timing(&self, name: &str) -> bool {
+        self.active_timers.contains_key(name)
+    }
+
+    /// Records a duration measured elsewhere under a section
+    ///
+    /// # Arguments
+    /// * `name` - The section to record under
+    /// * `duration` - The measured duration
+    pub fn record_section(&mut self, name: &str, duration: Duration) {
         if self.config.enabled {
-            self.start_section(name);
-            let result = f();
-            self.end_section(name);
-            result
-        } else {
-            f()
+            BENCHMARK_DATA
+                .lock()
+                .unwrap()
+                .record_measurement(name, duration);
+
+            if self.config.print_results {
+                println!("[PROFILER] {}: {:?}", name, duration);
+            }
         }
     }
 }
@@ -208,22 +222,68 @@ lazy_static::lazy_static! {
     pub static ref BENCHMARK_DATA: Arc<Mutex<BenchmarkData>> = Arc::new(Mutex::new(BenchmarkData::new()));
 }
 
+/// A copy of the aggregated statistics taken at one instant
+///
+/// Snapshots are what autosaves write, so the file IO happens on a copy and never holds
+/// the lock on the live data.
+#[derive(Debug, Clone)]
+pub struct BenchmarkSnapshot {
+    /// Aggregated metrics for every section at the time of the snapshot
+    pub measurements: HashMap<String, PerformanceMetrics>,
+    /// FPS statistics as (min_fps, avg_fps, max_fps)
+    pub fps_stats: (f64, f64, f64),
+    /// Increases with every snapshot, so an older snapshot never overwrites a newer one
+    pub sequence: u64,
+}
+
 /// Central storage for all benchmark measurements
 pub struct BenchmarkData {
     measurements: HashMap<String, PerformanceMetrics>,
     config: BenchmarkConfig,
     fps_counter: FrameRateCounter,
+    /// Use tick of the last measurement recorded for each section
+    last_used: HashMap<String, u64>,
+    /// Increments with every recorded measurement
+    use_tick: u64,
+    /// Number of sections evicted by the section cap
+    evicted_sections: usize,
+    /// When statistics were last handed out for an autosave
+    last_autosave: Instant,
+    /// Sequence number of the last snapshot taken
+    snapshot_sequence: u64,
 }
 
 impl BenchmarkData {
     fn new() -> Self {
+        Self::with_config(BenchmarkConfig::default())
+    }
+
+    /// Creates empty benchmark storage with the given configuration
+    pub(crate) fn with_config(config: BenchmarkConfig) -> Self {
         Self {
             measurements: HashMap::new(),
-            config: BenchmarkConfig::default(),
-            fps_counter: FrameRateCounter::new(1000), // Store up to 1000 frame samples
+            fps_counter: FrameRateCounter::new(config.max_samples),
+            config,
+            last_used: HashMap::new(),
+            use_tick: 0,
+            evicted_sections: 0,
+            last_autosave: Instant::now(),
+            snapshot_sequence: 0,
         }
     }
 
+    /// Replaces the configuration, e.g. with the game's own settings at startup
+    pub fn set_config(&mut self, config: BenchmarkConfig) {
+        let counter = &mut self.fps_counter;
+        counter.max_samples = config.max_samples;
+        let excess = counter
+            .frame_times
+            .len()
+            .saturating_sub(counter.max_samples);
+        counter.frame_times.drain(..excess);
+        self.config = config;
+    }
+
     /// Records a new measurement for the specified operation
     ///
     /// # Arguments
@@ -236,29 +296,92 @@ impl BenchmarkData {
             .or_insert_with(PerformanceMetrics::new);
         metrics.update(duration);
 
-        // Limit samples if configured
-        if self.config.max_samples > 0 && self.measurements.len() > self.config.max_samples {
-            // Remove oldest entries (simple strategy - could be improved)
-            let keys_to_remove: Vec<_> = self
-                .measurements
-                .keys()
-                .take(self.measurements.len() - self.config.max_samples)
-                .cloned()
-                .collect();
-            for key in keys_to_remove {
-                self.measurements.remove(&key);
+        self.use_tick += 1;
+        self.last_used.insert(name.to_string(), self.use_tick);
+
+        // Cap the number of distinct sections, evicting the least recently used
+        if self.config.max_sections > 0 {
+            while self.measurements.len() > self.config.max_sections {
+                self.evict_least_recently_used();
             }
         }
     }
 
+    /// Removes the section that has gone longest without a measurement
+    fn evict_least_recently_used(&mut self) {
+        let Some(oldest) = self
+            .last_used
+            .iter()
+            .min_by_key(|(_, tick)| **tick)
+            .map(|(name, _)| name.clone())
+        else {
+            return;
+        };
+
+        self.measurements.remove(&oldest);
+        self.last_used.remove(&oldest);
+        self.evicted_sections += 1;
+
+        if self.evicted_sections == 1 {
+            eprintln!(
+                "[BENCHMARK] Warning: more than {} distinct sections recorded, evicting \"{}\". \
+                 Section names built from per-level numbers or other changing values create a \
+                 new section every time; use a fixed name instead",
+                self.config.max_sections, oldest
+            );
+        }
+    }
+
+    /// Returns how many sections the section cap has evicted
+    #[cfg(test)]
+    pub fn evicted_section_count(&self) -> usize {
+        self.evicted_sections
+    }
+
+    /// Takes a consistent copy of the aggregated statistics
+    pub fn snapshot(&mut self) -> BenchmarkSnapshot {
+        self.snapshot_sequence += 1;
+        BenchmarkSnapshot {
+            measurements: self.measurements.clone(),
+            fps_stats: self.get_fps_stats(),
+            sequence: self.snapshot_sequence,
+        }
+    }
+
+    /// Takes a snapshot for an autosave if one is due
+    ///
+    /// # Arguments
+    /// * `now` - The current time
+    ///
+    /// # Returns
+    /// A snapshot when autosave is enabled, the interval has passed since the last one,
+    /// and there is something to save; otherwise `None`
+    pub fn take_autosave(&mut self, now: Instant) -> Option<BenchmarkSnapshot> {
+        let interval = self.config.autosave_interval;
+        if !self.config.enabled || !self.config.write_to_file || interval.is_zero() {
+            return None;
+        }
+        if now.saturating_duration_since(self.last_autosave) < interval {
+            return None;
+        }
+
+        self.last_autosave = now;
+        if self.measurements.is_empty() {
+            return None;
+        }
+        Some(self.snapshot())
+    }
+
     /// Returns a copy of all recorded measurements
     pub fn get_measurements(&self) -> HashMap<String, PerformanceMetrics> {
         self.measurements.clone()
     }
 
     /// Clears all recorded measurements
+    #[cfg(test)]
     pub fn clear(&mut self) {
         self.measurements.clear();
+        self.last_used.clear();
     }
 
     /// Records a frame and returns the current FPS
@@ -0,0 +1,237 @@
+//! Input-to-present latency measurement for tuning frame pacing
+//!
+//! In latency measurement mode (debug builds), pressing the probe key stamps the time the
+//! event handler received it. The next frame to start consumes the input and draws a small
+//! white flash in a corner of the window, and once that frame has been presented the time
+//! since the stamp is recorded into the [`LATENCY_SECTION`] profiler section. The debug
+//! overlay shows percentiles of the recent samples next to the surface's present mode and
+//! `desired_maximum_frame_latency`.
+//!
+//! The game runs one update per redraw, so "input N consumed in frame M" is simply the first
+//! frame to start after the input arrived; [`LatencySample`] reports both numbers so dropped
+//! or skipped frames show up as gaps.
+//!
+//! The samples end when `present` returns, not when light leaves the display: compositor,
+//! scan-out and panel delays are invisible without a photodiode. They are meant for
+//! comparing settings against each other, not as absolute input lag.
+//!
+//! # Usage
+//!
+//! ```rust,ignore
+//! use mirador::benchmarks::latency::LatencyProbe;
+//! use std::time::{Duration, Instant};
+//!
+//! let mut probe = LatencyProbe::new();
+//! probe.toggle();
+//!
+//! let pressed = Instant::now();
+//! probe.record_input(pressed);
+//!
+//! // The next frame draws the flash, then reports once presented
+//! assert!(probe.begin_frame());
+//! let sample = probe.frame_presented(pressed + Duration::from_millis(12)).unwrap();
+//! assert_eq!(sample.latency, Duration::from_millis(12));
+//! ```
+
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+/// Profiler section the input-to-present samples are recorded under
+pub const LATENCY_SECTION: &str = "input_to_present";
+
+/// Number of recent samples the percentiles are taken over
+pub const MAX_LATENCY_SAMPLES: usize = 240;
+
+/// Side of the flash square, in pixels
+pub const FLASH_SIZE: f32 = 48.0;
+
+/// A probe key press waiting for, or being drawn by, a frame
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct ProbeInput {
+    /// Sequence number of the press, counting from 1
+    id: u64,
+    /// When the event handler received the press
+    received: Instant,
+}
+
+/// One measured probe press
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LatencySample {
+    /// Sequence number of the press
+    pub input: u64,
+    /// Frame that consumed the press and drew its flash
+    pub frame: u64,
+    /// Time from the press reaching the event handler to its frame being presented
+    pub latency: Duration,
+}
+
+/// Percentiles of the recent samples
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LatencyPercentiles {
+    /// Number of samples the percentiles were taken over
+    pub count: usize,
+    /// Median latency
+    pub p50: Duration,
+    /// 95th percentile latency
+    pub p95: Duration,
+    /// 99th percentile latency
+    pub p99: Duration,
+}
+
+/// Tracks probe presses from the event handler to the presented frame
+#[derive(Debug, Default)]
+pub struct LatencyProbe {
+    /// Whether measurement mode is on
+    enabled: bool,
+    /// Sequence number of the last press
+    last_input: u64,
+    /// Frames started while measurement mode was on
+    frame: u64,
+    /// The press waiting for the next frame
+    pending: Option<ProbeInput>,
+    /// The press the current frame consumed, and that frame's number
+    in_flight: Option<(ProbeInput, u64)>,
+    /// The most recent latencies, oldest first
+    samples: VecDeque<Duration>,
+}
+
+impl LatencyProbe {
+    /// Creates a probe with measurement mode off
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns whether measurement mode is on
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Turns measurement mode on or off
+    ///
+    /// Turning it off drops any press still waiting for its frame; the samples are kept.
+    ///
+    /// # Returns
+    /// Whether measurement mode is now on
+    pub fn toggle(&mut self) -> bool {
+        self.enabled = !self.enabled;
+        self.pending = None;
+        self.in_flight = None;
+        self.enabled
+    }
+
+    /// Stamps a probe key press
+    ///
+    /// A press made while an earlier one is still waiting for its frame is ignored, since
+    /// both would be consumed by the same frame.
+    ///
+    /// # Arguments
+    /// * `received` - When the event handler received the press
+    ///
+    /// # Returns
+    /// The press's sequence number, or `None` if it was ignored or the mode is off
+    pub fn record_input(&mut self, received: Instant) -> Option<u64> {
+        if !self.enabled || self.pending.is_some() {
+            return None;
+        }
+        self.last_input += 1;
+        self.pending = Some(ProbeInput {
+            id: self.last_input,
+            received,
+        });
+        Some(self.last_input)
+    }
+
+    /// Starts a frame, letting it consume the waiting press
+    ///
+    /// Call once per frame that will be presented, at the point it draws its overlays.
+    ///
+    /// # Returns
+    /// Whether this frame should draw the flash
+    pub fn begin_frame(&mut self) -> bool {
+        self.frame += 1;
+        // A consumed press whose frame never reached present can't be measured
+        self.in_flight = self.pending.take().map(|input| (input, self.frame));
+        self.in_flight.is_some()
+    }
+
+    /// Finishes the press consumed by the frame just presented
+    ///
+    /// # Arguments
+    /// * `presented` - When `present` returned for the frame
+    ///
+    /// # Returns
+    /// The measured sample, if the frame drew a flash
+    pub fn frame_presented(&mut self, presented: Instant) -> Option<LatencySample> {
+        let (input, frame) = self.in_flight.take()?;
+        let latency = presented.saturating_duration_since(input.received);
+        self.samples.push_back(latency);
+        if self.samples.len() > MAX_LATENCY_SAMPLES {
+            self.samples.pop_front();
+        }
+        Some(LatencySample {
+            input: input.id,
+            frame,
+            latency,
+        })
+    }
+
+    /// Returns the percentiles of the recent samples
+    ///
+    /// # Returns
+    /// `None` until the first sample has been measured
+    pub fn percentiles(&self) -> Option<LatencyPercentiles> {
+        let mut sorted: Vec<Duration> = self.samples.iter().copied().collect();
+        sorted.sort();
+        Some(LatencyPercentiles {
+            count: sorted.len(),
+            p50: percentile(&sorted, 0.50)?,
+            p95: percentile(&sorted, 0.95)?,
+            p99: percentile(&sorted, 0.99)?,
+        })
+    }
+}
+
+/// Picks a percentile from sorted samples by the nearest-rank method
+///
+/// # Arguments
+/// * `sorted` - Samples in ascending order
+/// * `fraction` - The percentile as a fraction, from 0.0 to 1.0
+///
+/// # Returns
+/// The smallest sample at or above the given fraction of the samples, or `None` if there are
+/// no samples
+pub fn percentile(sorted: &[Duration], fraction: f64) -> Option<Duration> {
+    if sorted.is_empty() {
+        return None;
+    }
+    let rank = (fraction.clamp(0.0, 1.0) * sorted.len() as f64).ceil() as usize;
+    Some(sorted[rank.clamp(1, sorted.len()) - 1])
+}
+
+/// Builds the debug overlay's latency lines
+///
+/// # Arguments
+/// * `percentiles` - Percentiles of the recent samples, if any
+/// * `present_mode` - The surface's configured present mode
+/// * `max_frame_latency` - The surface's configured `desired_maximum_frame_latency`
+pub fn overlay_label(
+    percentiles: Option<LatencyPercentiles>,
+    present_mode: wgpu::PresentMode,
+    max_frame_latency: u32,
+) -> String {
+    let ms = |duration: Duration| duration.as_secs_f64() * 1000.0;
+    let stats = match percentiles {
+        Some(p) => format!(
+            "p50 {:.1} ms, p95 {:.1} ms, p99 {:.1} ms (n={})",
+            ms(p.p50),
+            ms(p.p95),
+            ms(p.p99),
+            p.count
+        ),
+        None => "press F8 to measure".to_string(),
+    };
+    format!(
+        "Input to present (not photon; compare settings only): {}\nPresent: {:?}, max frame latency {}",
+        stats, present_mode, max_frame_latency
+    )
+}
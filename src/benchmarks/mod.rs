@@ -10,6 +10,8 @@
 //! - **Profiling Tools**: Identify hot paths and performance bottlenecks
 //! - **Conditional Compilation**: Benchmarks can be disabled in release builds
 //! - **Minimal Overhead**: Designed to have minimal impact on performance when not active
+//! - **Autosave**: Aggregated results are written periodically on a background thread, so a
+//!   crash during a long session loses at most one autosave interval
 
 use std::time::Duration;
 
@@ -26,6 +28,10 @@ pub struct BenchmarkConfig {
     pub min_duration_threshold: Duration,
     /// Maximum number of samples to keep in memory
     pub max_samples: usize,
+    /// How often aggregated results are autosaved during a session; zero disables autosave
+    pub autosave_interval: Duration,
+    /// Maximum number of distinct sections kept; the least recently used is evicted past this
+    pub max_sections: usize,
 }
 
 impl Default for BenchmarkConfig {
@@ -36,6 +42,8 @@ impl Default for BenchmarkConfig {
             write_to_file: cfg!(debug_assertions),
             min_duration_threshold: Duration::from_micros(100),
             max_samples: 1000,
+            autosave_interval: Duration::from_secs(60),
+            max_sections: 256,
         }
     }
 }
@@ -47,6 +55,12 @@ impl Default for BenchmarkConfig {
 /// and performance metrics.
 pub mod data;
 
+/// Input-to-present latency measurement
+///
+/// This module tracks probe key presses from the event handler to the frame that shows
+/// them, for comparing present modes and frame latency settings.
+pub mod latency;
+
 /// Utilities for formatting and displaying benchmark results
 ///
 /// This module provides functions for formatting benchmark data into readable
@@ -64,5 +78,5 @@ pub mod utils;
 mod tests;
 
 // Re-export main types for convenience
-pub use data::{FrameRateCounter, MemoryTracker, PerformanceMetrics, Profiler};
+pub use data::{FrameRateCounter, Profiler};
 pub use utils::*;
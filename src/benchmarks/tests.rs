@@ -12,6 +12,7 @@
 #[cfg(test)]
 mod tests {
     use crate::benchmark;
+    use crate::benchmarks::data::MemoryTracker;
     use crate::benchmarks::*;
     use crate::debug_benchmark;
     use std::thread;
@@ -28,6 +29,8 @@ mod tests {
             write_to_file: false,
             min_duration_threshold: Duration::ZERO,
             max_samples: 100,
+            autosave_interval: Duration::from_secs(60),
+            max_sections: 256,
         };
 
         let timer = Timer::new("test", config);
@@ -53,6 +56,8 @@ mod tests {
             write_to_file: false,
             min_duration_threshold: Duration::ZERO,
             max_samples: 100,
+            autosave_interval: Duration::from_secs(60),
+            max_sections: 256,
         };
 
         {
@@ -82,6 +87,8 @@ mod tests {
             write_to_file: false,
             min_duration_threshold: Duration::ZERO,
             max_samples: 100,
+            autosave_interval: Duration::from_secs(60),
+            max_sections: 256,
         };
 
         let mut profiler = Profiler::new(config);
@@ -118,6 +125,8 @@ mod tests {
             write_to_file: false,
             min_duration_threshold: Duration::ZERO,
             max_samples: 100,
+            autosave_interval: Duration::from_secs(60),
+            max_sections: 256,
         };
 
         // Simulate app initialization measurements
@@ -256,6 +265,8 @@ mod tests {
             write_to_file: false,
             min_duration_threshold: Duration::ZERO,
             max_samples: 100,
+            autosave_interval: Duration::from_secs(60),
+            max_sections: 256,
         };
 
         {
@@ -339,6 +350,8 @@ mod tests {
             write_to_file: false,
             min_duration_threshold: Duration::ZERO,
             max_samples: 100,
+            autosave_interval: Duration::from_secs(60),
+            max_sections: 256,
         };
 
         {
@@ -378,6 +391,8 @@ mod tests {
             write_to_file: false,
             min_duration_threshold: Duration::ZERO,
             max_samples: 100,
+            autosave_interval: Duration::from_secs(60),
+            max_sections: 256,
         };
 
         {
@@ -410,6 +425,8 @@ mod tests {
             write_to_file: false,
             min_duration_threshold: Duration::ZERO,
             max_samples: 100,
+            autosave_interval: Duration::from_secs(60),
+            max_sections: 256,
         };
 
         // Initialization patterns
@@ -488,4 +505,185 @@ mod tests {
         // Clean up after test
         utils::clear_measurements();
     }
+
+    /// Builds a config for the autosave and section cap tests
+    fn autosave_config(autosave_interval: Duration, max_sections: usize) -> BenchmarkConfig {
+        BenchmarkConfig {
+            enabled: true,
+            print_results: false,
+            write_to_file: true,
+            min_duration_threshold: Duration::ZERO,
+            max_samples: 100,
+            autosave_interval,
+            max_sections,
+        }
+    }
+
+    /// Tests that snapshots stay consistent while sections are being recorded
+    ///
+    /// Several threads record fixed-duration samples into shared sections while the main
+    /// thread keeps taking snapshots. Every snapshot must reflect whole measurements only,
+    /// counts must never go backwards, and the final snapshot must hold every sample.
+    #[test]
+    fn test_snapshot_consistent_under_concurrent_recording() {
+        use crate::benchmarks::data::BenchmarkData;
+        use std::sync::{Arc, Mutex};
+
+        const THREADS: usize = 4;
+        const SAMPLES: usize = 500;
+        let sample = Duration::from_micros(250);
+        let data = Arc::new(Mutex::new(BenchmarkData::with_config(autosave_config(
+            Duration::from_secs(60),
+            256,
+        ))));
+
+        let workers: Vec<_> = (0..THREADS)
+            .map(|thread_index| {
+                let data = Arc::clone(&data);
+                thread::spawn(move || {
+                    let own_section = format!("thread_section_{}", thread_index);
+                    for _ in 0..SAMPLES {
+                        let mut data = data.lock().unwrap();
+                        data.record_measurement("shared_section", sample);
+                        data.record_measurement(&own_section, sample);
+                        drop(data);
+                        thread::yield_now();
+                    }
+                })
+            })
+            .collect();
+
+        let mut last_count = 0;
+        let mut last_sequence = 0;
+        while workers.iter().any(|worker| !worker.is_finished()) {
+            let snapshot = data.lock().unwrap().snapshot();
+            assert!(
+                snapshot.sequence > last_sequence,
+                "Sequence should increase"
+            );
+            last_sequence = snapshot.sequence;
+
+            for metrics in snapshot.measurements.values() {
+                assert_eq!(metrics.total_duration, sample * metrics.count as u32);
+                assert_eq!(metrics.min_duration, sample);
+                assert_eq!(metrics.max_duration, sample);
+            }
+            if let Some(shared) = snapshot.measurements.get("shared_section") {
+                assert!(
+                    shared.count >= last_count,
+                    "Counts should never go backwards"
+                );
+                last_count = shared.count;
+            }
+        }
+        for worker in workers {
+            worker.join().expect("worker thread panicked");
+        }
+
+        let snapshot = data.lock().unwrap().snapshot();
+        assert_eq!(snapshot.measurements.len(), THREADS + 1);
+        assert_eq!(
+            snapshot.measurements["shared_section"].count,
+            THREADS * SAMPLES
+        );
+        for thread_index in 0..THREADS {
+            let name = format!("thread_section_{}", thread_index);
+            assert_eq!(snapshot.measurements[&name].count, SAMPLES);
+        }
+
+        // Autosaves are only handed out once the interval has passed
+        let mut data = data.lock().unwrap();
+        let now = std::time::Instant::now();
+        assert!(data.take_autosave(now).is_none());
+        let autosave = data.take_autosave(now + Duration::from_secs(61));
+        assert_eq!(
+            autosave.map(|snapshot| snapshot.measurements.len()),
+            Some(THREADS + 1)
+        );
+        assert!(data.take_autosave(now + Duration::from_secs(62)).is_none());
+    }
+
+    /// Tests the section cap evicts the least recently used section
+    ///
+    /// Dynamically named sections (such as ones containing the level number) must not
+    /// grow the set without bound; once the cap is hit the stalest section goes first.
+    #[test]
+    fn test_section_cap_evicts_least_recently_used() {
+        use crate::benchmarks::data::BenchmarkData;
+
+        let mut data = BenchmarkData::with_config(autosave_config(Duration::ZERO, 3));
+        let sample = Duration::from_micros(10);
+
+        data.record_measurement("render", sample);
+        data.record_measurement("level_1_setup", sample);
+        data.record_measurement("update", sample);
+        // Touch "render" so "level_1_setup" becomes the least recently used
+        data.record_measurement("render", sample);
+        data.record_measurement("level_2_setup", sample);
+
+        let measurements = data.get_measurements();
+        assert_eq!(measurements.len(), 3);
+        assert!(!measurements.contains_key("level_1_setup"));
+        assert_eq!(measurements["render"].count, 2);
+        assert!(measurements.contains_key("update"));
+        assert!(measurements.contains_key("level_2_setup"));
+        assert_eq!(data.evicted_section_count(), 1);
+
+        // Every further per-level name evicts one more section
+        for level in 3..10 {
+            data.record_measurement(&format!("level_{}_setup", level), sample);
+        }
+        assert_eq!(data.get_measurements().len(), 3);
+        assert_eq!(data.evicted_section_count(), 8);
+
+        // A zero interval disables autosave
+        let later = std::time::Instant::now() + Duration::from_secs(3600);
+        assert!(data.take_autosave(later).is_none());
+    }
+
+    /// Tests the latency probe pairs each press with the frame that drew it
+    ///
+    /// Presses only count while measurement mode is on, a frame consumes at most one
+    /// press, and frames without a press record nothing.
+    #[test]
+    fn test_latency_probe_pairs_inputs_with_frames() {
+        use crate::benchmarks::latency::{LatencyProbe, percentile};
+        use std::time::Instant;
+
+        let mut probe = LatencyProbe::new();
+        let start = Instant::now();
+        assert_eq!(probe.record_input(start), None);
+
+        probe.toggle();
+        assert!(!probe.begin_frame()); // frame 1
+        assert_eq!(probe.frame_presented(start), None);
+
+        assert_eq!(probe.record_input(start), Some(1));
+        // A second press before the frame starts rides along with the first
+        assert_eq!(probe.record_input(start + Duration::from_millis(2)), None);
+        assert!(probe.begin_frame()); // frame 2
+        let sample = probe
+            .frame_presented(start + Duration::from_millis(20))
+            .unwrap();
+        assert_eq!((sample.input, sample.frame), (1, 2));
+        assert_eq!(sample.latency, Duration::from_millis(20));
+        assert!(!probe.begin_frame()); // frame 3
+
+        for ms in [10, 30, 40] {
+            let pressed = Instant::now();
+            probe.record_input(pressed);
+            probe.begin_frame();
+            probe.frame_presented(pressed + Duration::from_millis(ms));
+        }
+        let percentiles = probe.percentiles().unwrap();
+        assert_eq!(percentiles.count, 4);
+        assert_eq!(percentiles.p50, Duration::from_millis(20));
+        assert_eq!(percentiles.p99, Duration::from_millis(40));
+
+        // Nearest rank never reads past either end
+        let sorted = [Duration::from_millis(1), Duration::from_millis(2)];
+        assert_eq!(percentile(&sorted, 0.0), Some(sorted[0]));
+        assert_eq!(percentile(&sorted, 1.0), Some(sorted[1]));
+        assert_eq!(percentile(&[], 0.5), None);
+    }
 }
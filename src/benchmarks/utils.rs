@@ -11,15 +11,18 @@
 //! - **File Output**: Functions to save benchmark results to files
 //! - **Macros**: `benchmark!` and `debug_benchmark!` for easy code instrumentation
 
+use crate::paths;
 use chrono::{DateTime, Datelike, Local, Timelike};
 use std::collections::HashMap;
 use std::fs::{self, OpenOptions};
 use std::io::{self, Write};
-use std::path::Path;
+use std::path::PathBuf;
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread;
 use std::time::{Duration, Instant};
 
 use super::BenchmarkConfig;
-use super::data::{BENCHMARK_DATA, PerformanceMetrics, Profiler};
+use super::data::{BENCHMARK_DATA, BenchmarkSnapshot, PerformanceMetrics};
 
 /// A timer for measuring execution time of code sections
 ///
@@ -100,266 +103,11 @@ impl Drop for ScopedTimer {
     }
 }
 
-/// Times a closure execution with default configuration
-///
-/// This is a convenience function that times the execution of a closure
-/// using the default benchmark configuration.
-///
-/// # Arguments
-/// * `name` - The name of the operation being timed
-/// * `f` - The closure to execute and time
-///
-/// # Returns
-/// The result of the closure execution
-pub fn time<F, R>(name: &str, f: F) -> R
-where
-    F: FnOnce() -> R,
-{
-    let config = BenchmarkConfig::default();
-    let mut profiler = Profiler::new(config);
-    profiler.time_closure(name, f)
-}
-
-/// Creates a scoped timer with default configuration
-///
-/// This is a convenience function for creating a scoped timer with
-/// the default benchmark configuration.
-///
-/// # Arguments
-/// * `name` - The name of the operation to be timed
-///
-/// # Returns
-/// A scoped timer that will automatically record timing when dropped
-pub fn scoped_timer(name: &str) -> ScopedTimer {
-    ScopedTimer::new(name, BenchmarkConfig::default())
-}
-
-/// Prints a summary of all recorded measurements
-///
-/// This function displays a comprehensive summary of all benchmark
-/// measurements, including FPS statistics and categorized benchmark tables.
-/// The output is formatted for console display with clear sections for
-/// initialization and update benchmarks.
-pub fn print_summary() {
-    let data = BENCHMARK_DATA.lock().unwrap();
-    let measurements = data.get_measurements();
-
-    if measurements.is_empty() {
-        println!("[BENCHMARK] No measurements recorded");
-        return;
-    }
-
-    println!("\n=== PERFORMANCE SUMMARY ===");
-
-    // Print FPS statistics
-    let (min_fps, avg_fps, max_fps) = get_fps_stats();
-    if avg_fps > 0.0 {
-        println!(
-            "FPS Statistics - Min: {:.1}, Average: {:.1}, Max: {:.1}",
-            min_fps, avg_fps, max_fps
-        );
-    } else {
-        println!("FPS Statistics - No frame data recorded");
-    }
-
-    // Separate initialization benchmarks from update benchmarks
-    let mut init_benchmarks: Vec<_> = Vec::new();
-    let mut update_benchmarks: Vec<_> = Vec::new();
-
-    for (name, metrics) in measurements.iter() {
-        // Identify initialization benchmarks by their naming patterns
-        if name.contains("initialization")
-            || name.contains("creation")
-            || name.contains("setup")
-            || name.contains("loading")
-            || name.contains("spawning")
-            || name.contains("configuration")
-        {
-            init_benchmarks.push((name, metrics));
-        } else {
-            update_benchmarks.push((name, metrics));
-        }
-    }
-
-    // Sort both lists by total duration (most time-consuming first)
-    init_benchmarks.sort_by(|a, b| b.1.total_duration.cmp(&a.1.total_duration));
-    update_benchmarks.sort_by(|a, b| b.1.total_duration.cmp(&a.1.total_duration));
-
-    // Print initialization benchmarks table
-    if !init_benchmarks.is_empty() {
-        println!("\n=== INITIALIZATION BENCHMARKS ===");
-        println!("This table shows the most taxing parts of program initialization:");
-        println!();
-
-        // Calculate total initialization time
-        let total_init_time: f64 = init_benchmarks
-            .iter()
-            .map(|(_, m)| m.total_duration.as_secs_f64())
-            .sum();
-
-        // Print header - use dynamic columns based on data
-        let has_multiple_counts = init_benchmarks.iter().any(|(_, m)| m.count > 1);
-
-        // Calculate column widths based on actual content
-        let (name_width, count_width, total_width, avg_width, min_width, max_width) =
-            super::format::calculate_column_widths(&init_benchmarks, has_multiple_counts);
-
-        // Print header with all columns
-        println!(
-            "{:<name_width$} | {:>count_width$} | {:>total_width$} | {:>avg_width$} | {:>min_width$} | {:>max_width$}",
-            "Operation",
-            "Count",
-            "Total",
-            "Avg",
-            "Min",
-            "Max",
-            name_width = name_width,
-            count_width = count_width,
-            total_width = total_width,
-            avg_width = avg_width,
-            min_width = min_width,
-            max_width = max_width
-        );
-
-        // Calculate separator line length
-        let separator_length =
-            name_width + count_width + total_width + avg_width + min_width + max_width + 15; // +15 for separators and spaces
-        println!("{}", "-".repeat(separator_length));
-
-        for (name, metrics) in &init_benchmarks {
-            if metrics.count > 1 {
-                // Show all columns for operations with count > 1
-                let total_str = format!("{:?}", metrics.total_duration);
-                let avg_str = format!("{:?}", metrics.avg_duration);
-                let min_str = format!("{:?}", metrics.min_duration);
-                let max_str = format!("{:?}", metrics.max_duration);
-
-                println!(
-                    "{:<name_width$} | {:>count_width$} | {:>total_width$} | {:>avg_width$} | {:>min_width$} | {:>max_width$}",
-                    name,
-                    metrics.count,
-                    total_str,
-                    avg_str,
-                    min_str,
-                    max_str,
-                    name_width = name_width,
-                    count_width = count_width,
-                    total_width = total_width,
-                    avg_width = avg_width,
-                    min_width = min_width,
-                    max_width = max_width
-                );
-            } else {
-                // Show only basic columns for operations with count = 1
-                let total_str = format!("{:?}", metrics.total_duration);
-
-                println!(
-                    "{:<name_width$} | {:>count_width$} | {:>total_width$}",
-                    name,
-                    metrics.count,
-                    total_str,
-                    name_width = name_width,
-                    count_width = count_width,
-                    total_width = total_width
-                );
-            }
-        }
-
-        println!();
-        println!("Total initialization time: {:.3}s", total_init_time);
-    }
-
-    // Print update benchmarks table
-    if !update_benchmarks.is_empty() {
-        println!("\n=== UPDATE BENCHMARKS ===");
-        println!("This table shows the most taxing parts of the update loop:");
-        println!();
-
-        // Calculate total update time
-        let total_update_time: f64 = update_benchmarks
-            .iter()
-            .map(|(_, m)| m.total_duration.as_secs_f64())
-            .sum();
-
-        // Print header - use dynamic columns based on data
-        let has_multiple_counts = update_benchmarks.iter().any(|(_, m)| m.count > 1);
-
-        // Calculate column widths based on actual content
-        let (name_width, count_width, total_width, avg_width, min_width, max_width) =
-            super::format::calculate_column_widths(&update_benchmarks, has_multiple_counts);
-
-        // Print header with all columns
-        println!(
-            "{:<name_width$} | {:>count_width$} | {:>total_width$} | {:>avg_width$} | {:>min_width$} | {:>max_width$}",
-            "Operation",
-            "Count",
-            "Total",
-            "Avg",
-            "Min",
-            "Max",
-            name_width = name_width,
-            count_width = count_width,
-            total_width = total_width,
-            avg_width = avg_width,
-            min_width = min_width,
-            max_width = max_width
-        );
-
-        // Calculate separator line length
-        let separator_length =
-            name_width + count_width + total_width + avg_width + min_width + max_width + 15; // +15 for separators and spaces
-        println!("{}", "-".repeat(separator_length));
-
-        for (name, metrics) in &update_benchmarks {
-            if metrics.count > 1 {
-                // Show all columns for operations with count > 1
-                let total_str = format!("{:?}", metrics.total_duration);
-                let avg_str = format!("{:?}", metrics.avg_duration);
-                let min_str = format!("{:?}", metrics.min_duration);
-                let max_str = format!("{:?}", metrics.max_duration);
-
-                println!(
-                    "{:<name_width$} | {:>count_width$} | {:>total_width$} | {:>avg_width$} | {:>min_width$} | {:>max_width$}",
-                    name,
-                    metrics.count,
-                    total_str,
-                    avg_str,
-                    min_str,
-                    max_str,
-                    name_width = name_width,
-                    count_width = count_width,
-                    total_width = total_width,
-                    avg_width = avg_width,
-                    min_width = min_width,
-                    max_width = max_width
-                );
-            } else {
-                // Show only basic columns for operations with count = 1
-                let total_str = format!("{:?}", metrics.total_duration);
-
-                println!(
-                    "{:<name_width$} | {:>count_width$} | {:>total_width$}",
-                    name,
-                    metrics.count,
-                    total_str,
-                    name_width = name_width,
-                    count_width = count_width,
-                    total_width = total_width
-                );
-            }
-        }
-
-        println!();
-        println!("Total update time: {:.3}s", total_update_time);
-    }
-
-    println!("\n=============================\n");
-}
-
 /// Clears all recorded measurements
 ///
 /// This function removes all stored benchmark data, resetting the
 /// benchmark system to an empty state.
+#[cfg(test)]
 pub fn clear_measurements() {
     BENCHMARK_DATA.lock().unwrap().clear();
 }
@@ -374,48 +122,86 @@ pub fn get_measurements() -> HashMap<String, PerformanceMetrics> {
     BENCHMARK_DATA.lock().unwrap().get_measurements()
 }
 
-/// Writes benchmark results to a file in the debug-analytics/benchmarks directory
+/// Writes benchmark results to a file in the data directory's benchmarks directory
 ///
-/// This function creates a comprehensive benchmark report file with timestamped
-/// filename. The report includes FPS statistics, initialization benchmarks,
-/// and update benchmarks in a formatted table structure.
+/// This function creates a comprehensive benchmark report file named after the time
+/// the session started. The report includes FPS statistics, initialization benchmarks,
+/// and update benchmarks in a formatted table structure. Autosaves write to the same
+/// file, so this final flush replaces the last autosave with complete results.
 ///
 /// # Returns
 /// `Ok(())` on success, or an `io::Error` if file operations fail
 pub fn write_results_to_file(source: &str) -> io::Result<()> {
-    let measurements = get_measurements();
-    if measurements.is_empty() {
+    let snapshot = BENCHMARK_DATA.lock().unwrap().snapshot();
+    if snapshot.measurements.is_empty() {
         println!("[BENCHMARK] No measurements to write");
         return Ok(());
     }
 
+    // Any autosave still waiting to be written is older than this snapshot
+    AUTOSAVER.discard_pending();
+
+    let Some(file_path) = write_snapshot_to_file(&snapshot, source)? else {
+        println!("[BENCHMARK] Newer results were already written");
+        return Ok(());
+    };
+
+    println!("[BENCHMARK] Results written to: {}", file_path.display());
+    println!(
+        "[BENCHMARK] Full path: {}",
+        file_path.canonicalize().unwrap_or(file_path).display()
+    );
+    Ok(())
+}
+
+/// Writes a snapshot to the session's results file
+///
+/// The report is written to a temporary file and renamed over the results file, so a
+/// crash mid-write never leaves a truncated report. Writers are serialized, and a snapshot
+/// older than the last one written is skipped.
+///
+/// # Arguments
+/// * `snapshot` - The statistics to write
+/// * `source` - Label describing what triggered the write
+///
+/// # Returns
+/// The path written, `None` if a newer snapshot was already written, or an `io::Error`
+fn write_snapshot_to_file(
+    snapshot: &BenchmarkSnapshot,
+    source: &str,
+) -> io::Result<Option<PathBuf>> {
+    let mut last_written = LAST_WRITTEN_SEQUENCE.lock().unwrap();
+    if snapshot.sequence <= *last_written {
+        return Ok(None);
+    }
+
     // Create the benchmarks directory
-    let benchmarks_dir = Path::new("debug-analytics/benchmarks");
-    if let Err(e) = fs::create_dir_all(benchmarks_dir) {
+    let benchmarks_dir = paths::get().benchmarks_dir();
+    if let Err(e) = fs::create_dir_all(&benchmarks_dir) {
         eprintln!("[BENCHMARK] Failed to create benchmarks directory: {}", e);
         return Err(e);
     }
 
-    // Generate filename with timestamp
-    let now: DateTime<Local> = Local::now();
-    let filename = format!(
-        "{:02}:{:02}{}-{:02}-{:02}-{:04}.txt",
-        now.hour12().1,
-        now.minute(),
-        if now.hour12().0 { "pm" } else { "am" },
-        now.month(),
-        now.day(),
-        now.year()
-    );
+    let file_path = benchmarks_dir.join(RESULTS_FILENAME.as_str());
+    let temp_path = file_path.with_extension("txt.tmp");
+    {
+        // Open file for writing
+        let mut file = OpenOptions::new()
+            .create(true)
+            .write(true)
+            .truncate(true)
+            .open(&temp_path)?;
+        write_report(&mut file, snapshot, source)?;
+    }
+    fs::rename(&temp_path, &file_path)?;
 
-    let file_path = benchmarks_dir.join(filename);
+    *last_written = snapshot.sequence;
+    Ok(Some(file_path))
+}
 
-    // Open file for writing
-    let mut file = OpenOptions::new()
-        .create(true)
-        .write(true)
-        .truncate(true)
-        .open(&file_path)?;
+/// Writes the formatted benchmark report for a snapshot
+fn write_report(file: &mut fs::File, snapshot: &BenchmarkSnapshot, source: &str) -> io::Result<()> {
+    let now: DateTime<Local> = Local::now();
 
     // Write header with source label
     writeln!(file, "Mirador Performance Benchmark Results")?;
@@ -428,7 +214,7 @@ pub fn write_results_to_file(source: &str) -> io::Result<()> {
     writeln!(file, "=== PERFORMANCE SUMMARY ===")?;
 
     // Write FPS statistics
-    let (min_fps, avg_fps, max_fps) = get_fps_stats();
+    let (min_fps, avg_fps, max_fps) = snapshot.fps_stats;
     if avg_fps > 0.0 {
         writeln!(
             file,
@@ -444,7 +230,7 @@ pub fn write_results_to_file(source: &str) -> io::Result<()> {
     let mut init_benchmarks: Vec<_> = Vec::new();
     let mut update_benchmarks: Vec<_> = Vec::new();
 
-    for (name, metrics) in measurements.iter() {
+    for (name, metrics) in snapshot.measurements.iter() {
         // Identify initialization benchmarks by their naming patterns
         if name.contains("initialization")
             || name.contains("creation")
@@ -678,14 +464,111 @@ pub fn write_results_to_file(source: &str) -> io::Result<()> {
     writeln!(file, "{}", "=".repeat(60))?;
     writeln!(file, "End of benchmark report")?;
 
-    println!("[BENCHMARK] Results written to: {}", file_path.display());
-    println!(
-        "[BENCHMARK] Full path: {}",
-        file_path.canonicalize().unwrap_or(file_path).display()
-    );
     Ok(())
 }
 
+lazy_static::lazy_static! {
+    /// Results file name for this session, taken from the time the session started
+    static ref RESULTS_FILENAME: String = {
+        let now: DateTime<Local> = Local::now();
+        format!(
+            "{:02}:{:02}{}-{:02}-{:02}-{:04}.txt",
+            now.hour12().1,
+            now.minute(),
+            if now.hour12().0 { "pm" } else { "am" },
+            now.month(),
+            now.day(),
+            now.year()
+        )
+    };
+
+    /// Sequence number of the newest snapshot written; also serializes writers
+    static ref LAST_WRITTEN_SEQUENCE: Mutex<u64> = Mutex::new(0);
+
+    /// Background writer for autosaves
+    static ref AUTOSAVER: Arc<Autosaver> = Autosaver::spawn();
+}
+
+/// Writes autosave snapshots on a background thread
+///
+/// The game thread swaps its newest snapshot into `pending` and returns immediately; the
+/// writer thread swaps it back out and does the file IO. A snapshot that has not been
+/// written yet is simply replaced by a newer one.
+struct Autosaver {
+    /// The newest snapshot waiting to be written
+    pending: Mutex<Option<BenchmarkSnapshot>>,
+    /// Signalled when a snapshot is submitted
+    ready: Condvar,
+}
+
+impl Autosaver {
+    /// Creates the autosaver and starts its writer thread
+    fn spawn() -> Arc<Self> {
+        let autosaver = Arc::new(Self {
+            pending: Mutex::new(None),
+            ready: Condvar::new(),
+        });
+
+        let writer = Arc::clone(&autosaver);
+        if let Err(e) = thread::Builder::new()
+            .name("benchmark-autosave".to_string())
+            .spawn(move || writer.run())
+        {
+            eprintln!("[BENCHMARK] Failed to start autosave thread: {}", e);
+        }
+        autosaver
+    }
+
+    /// Hands a snapshot to the writer thread without waiting for it to be written
+    fn submit(&self, snapshot: BenchmarkSnapshot) {
+        *self.pending.lock().unwrap() = Some(snapshot);
+        self.ready.notify_one();
+    }
+
+    /// Drops a snapshot that has not been written yet
+    fn discard_pending(&self) {
+        self.pending.lock().unwrap().take();
+    }
+
+    /// Writer thread loop
+    fn run(&self) {
+        loop {
+            let snapshot = {
+                let mut pending = self.pending.lock().unwrap();
+                loop {
+                    match pending.take() {
+                        Some(snapshot) => break snapshot,
+                        None => pending = self.ready.wait(pending).unwrap(),
+                    }
+                }
+            };
+
+            if let Err(e) = write_snapshot_to_file(&snapshot, "Autosave") {
+                eprintln!("[BENCHMARK] Failed to autosave benchmark results: {}", e);
+            }
+        }
+    }
+}
+
+/// Autosaves the aggregated statistics if the autosave interval has passed
+///
+/// This should be called once per frame. The statistics are copied under the lock and
+/// written on a background thread, so the caller never waits on file IO.
+pub fn autosave_if_due() {
+    let snapshot = BENCHMARK_DATA.lock().unwrap().take_autosave(Instant::now());
+    if let Some(snapshot) = snapshot {
+        AUTOSAVER.submit(snapshot);
+    }
+}
+
+/// Applies a configuration to the global benchmark data
+///
+/// # Arguments
+/// * `config` - Settings such as the autosave interval and section cap
+pub fn configure(config: BenchmarkConfig) {
+    BENCHMARK_DATA.lock().unwrap().set_config(config);
+}
+
 /// Clears measurements and optionally writes them to file before clearing
 ///
 /// This function saves any existing measurements to a file before clearing
@@ -693,6 +576,7 @@ pub fn write_results_to_file(source: &str) -> io::Result<()> {
 ///
 /// # Returns
 /// `Ok(())` on success, or an `io::Error` if file operations fail
+#[cfg(test)]
 pub fn clear_and_save_measurements() -> io::Result<()> {
     if !get_measurements().is_empty() {
         write_results_to_file_default()?;
@@ -731,6 +615,7 @@ pub fn write_results_to_file_default() -> io::Result<()> {
 ///
 /// # Returns
 /// The number of unique benchmark measurements currently stored
+#[cfg(test)]
 pub fn get_measurement_count() -> usize {
     get_measurements().len()
 }
@@ -752,6 +637,7 @@ pub fn record_frame() -> f64 {
 ///
 /// # Returns
 /// A tuple containing (min_fps, avg_fps, max_fps)
+#[cfg(test)]
 pub fn get_fps_stats() -> (f64, f64, f64) {
     BENCHMARK_DATA.lock().unwrap().get_fps_stats()
 }
@@ -763,7 +649,7 @@ pub fn get_fps_stats() -> (f64, f64, f64) {
 /// that records timing when the block exits.
 ///
 /// # Example
-/// ```rust
+/// ```rust,ignore
 /// benchmark!("my_operation", {
 ///     // Code to be timed
 ///     expensive_operation();
@@ -772,7 +658,7 @@ pub fn get_fps_stats() -> (f64, f64, f64) {
 #[macro_export]
 macro_rules! benchmark {
     ($name:expr, $block:expr) => {{
-        use crate::benchmarks::{BenchmarkConfig, ScopedTimer};
+        use $crate::benchmarks::{BenchmarkConfig, ScopedTimer};
         let _timer = ScopedTimer::new($name, BenchmarkConfig::default());
         $block
     }};
@@ -785,7 +671,7 @@ macro_rules! benchmark {
 /// without any timing overhead.
 ///
 /// # Example
-/// ```rust
+/// ```rust,ignore
 /// debug_benchmark!("debug_only_operation", {
 ///     // Code that will only be timed in debug builds
 ///     debug_operation();
@@ -796,7 +682,7 @@ macro_rules! debug_benchmark {
     ($name:expr, $block:expr) => {{
         #[cfg(debug_assertions)]
         {
-            use crate::benchmarks::{BenchmarkConfig, ScopedTimer};
+            use $crate::benchmarks::{BenchmarkConfig, ScopedTimer};
             let _timer = ScopedTimer::new($name, BenchmarkConfig::default());
             $block
         }
@@ -0,0 +1,618 @@
+//! Spoken announcements for players who can't read the HUD.
+//!
+//! With the announcements setting on, [`Announcer`] turns changes in what the HUD shows into
+//! short sentences. It is ticked once per frame with an [`AnnouncerFrame`] of facts about the
+//! game, and speaks up when one of them crosses into something worth saying:
+//!
+//! - [`Announcement::TimeRemaining`]: the timer drops past two minutes, one minute, thirty
+//!   seconds, or ten seconds
+//! - [`Announcement::EnemyClose`]: the enemy comes within [`DANGER_RANGE`], at most once every
+//!   [`ENEMY_REPEAT_SECS`]
+//! - [`Announcement::ExitDirection`]: the compass bearing to the exit moves into another of
+//!   eight directions and stays clear of the boundary by [`DIRECTION_HYSTERESIS`], at most once
+//!   every [`DIRECTION_REPEAT_SECS`] and never right after something else was said
+//! - [`Announcement::LevelComplete`] and [`Announcement::GameOver`]: the level ends
+//!
+//! Each frame is first reduced to a few bucketed values, and the rules only run when one of
+//! them changes, so a frame where nothing is announced costs a handful of comparisons. With
+//! the setting off the announcer does nothing at all and forgets what it last saw, so turning
+//! it on mid-level never announces something stale.
+//!
+//! Every announcement is written to an on-screen line that stays up for [`LINE_SECS`], and read
+//! aloud by [`Speech`] when the game is built with the `tts` feature and the platform has a
+//! speech engine. All of the wording is in [`Announcement::text`].
+//!
+//! # Usage
+//!
+//! ```rust,ignore
+//! let mut announcer = Announcer::new();
+//! let speech = Speech::new();
+//!
+//! // Once per frame
+//! for announcement in announcer.update(frame, settings.announcements) {
+//!     speech.say(&announcement.text());
+//! }
+//! if let Some((text, opacity)) = announcer.line() {
+//!     overlay.show(text, opacity);
+//! }
+//! ```
+
+use crate::game::CurrentScreen;
+use crate::game::combo::DANGER_RANGE;
+#[cfg(feature = "tts")]
+use std::sync::mpsc;
+use std::sync::mpsc::Sender;
+
+/// Seconds left on the timer that are announced as it drops past them, longest first.
+pub const TIME_THRESHOLDS: [u32; 4] = [120, 60, 30, 10];
+
+/// Distance the enemy has to back off to before coming close is announced again.
+///
+/// Keeps an enemy pacing at the edge of [`DANGER_RANGE`] from being announced over and over.
+pub const ENEMY_CLEAR_RANGE: f32 = DANGER_RANGE * 1.25;
+
+/// Seconds after [`Announcement::EnemyClose`] before it can be announced again.
+pub const ENEMY_REPEAT_SECS: f32 = 8.0;
+
+/// Degrees past the edge of a direction's sector the bearing has to move before it counts as
+/// another direction.
+pub const DIRECTION_HYSTERESIS: f32 = 10.0;
+
+/// Seconds after [`Announcement::ExitDirection`] before the direction is announced again.
+pub const DIRECTION_REPEAT_SECS: f32 = 5.0;
+
+/// Seconds after any other announcement before the exit's direction is announced.
+pub const DIRECTION_QUIET_SECS: f32 = 2.0;
+
+/// Seconds the on-screen line stays up after an announcement.
+pub const LINE_SECS: f32 = 6.0;
+
+/// Seconds at the end of [`LINE_SECS`] the on-screen line fades out over.
+const LINE_FADE_SECS: f32 = 1.0;
+
+/// Which way something is from where the player is facing, in eighths of a turn.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    /// Straight ahead
+    Ahead,
+    /// Ahead and to the right
+    AheadRight,
+    /// To the right
+    Right,
+    /// Behind and to the right
+    BehindRight,
+    /// Straight behind
+    Behind,
+    /// Behind and to the left
+    BehindLeft,
+    /// To the left
+    Left,
+    /// Ahead and to the left
+    AheadLeft,
+}
+
+impl Direction {
+    /// Every direction, clockwise from straight ahead.
+    const ALL: [Direction; 8] = [
+        Direction::Ahead,
+        Direction::AheadRight,
+        Direction::Right,
+        Direction::BehindRight,
+        Direction::Behind,
+        Direction::BehindLeft,
+        Direction::Left,
+        Direction::AheadLeft,
+    ];
+
+    /// Returns the direction a bearing falls in.
+    ///
+    /// # Arguments
+    /// * `degrees` - Bearing from where the player is facing, clockwise; any angle, wrapped
+    pub fn from_bearing(degrees: f32) -> Self {
+        let sector = (degrees.rem_euclid(360.0) / 45.0).round() as usize % 8;
+        Self::ALL[sector]
+    }
+
+    /// Returns the direction a bearing falls in, keeping the previous one near its edges.
+    ///
+    /// # Arguments
+    /// * `previous` - The direction the bearing was last in, if any
+    /// * `degrees` - Bearing from where the player is facing, clockwise
+    fn settle(previous: Option<Self>, degrees: f32) -> Self {
+        if let Some(previous) = previous {
+            let center = Self::ALL.iter().position(|&d| d == previous).unwrap_or(0) as f32 * 45.0;
+            let off = ((degrees - center + 180.0).rem_euclid(360.0) - 180.0).abs();
+            if off <= 22.5 + DIRECTION_HYSTERESIS {
+                return previous;
+            }
+        }
+        Self::from_bearing(degrees)
+    }
+
+    /// Returns how the direction is said after "Exit".
+    fn phrase(self) -> &'static str {
+        match self {
+            Direction::Ahead => "straight ahead",
+            Direction::AheadRight => "ahead to the right",
+            Direction::Right => "to the right",
+            Direction::BehindRight => "behind to the right",
+            Direction::Behind => "behind you",
+            Direction::BehindLeft => "behind to the left",
+            Direction::Left => "to the left",
+            Direction::AheadLeft => "ahead to the left",
+        }
+    }
+}
+
+/// Something the announcer says.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Announcement {
+    /// The timer dropped past one of the [`TIME_THRESHOLDS`], in seconds
+    TimeRemaining(u32),
+    /// The enemy came within danger range
+    EnemyClose,
+    /// The exit is now in another direction from where the player is facing
+    ExitDirection(Direction),
+    /// The player reached the exit
+    LevelComplete {
+        /// The level that was completed
+        level: i32,
+        /// Score at the end of the level
+        score: u32,
+    },
+    /// The run ended
+    GameOver {
+        /// The level the run ended on
+        level: i32,
+        /// Final score
+        score: u32,
+    },
+}
+
+impl Announcement {
+    /// Returns the sentence read aloud and shown for the announcement.
+    ///
+    /// This is the only place the announcer's wording lives, so translating the announcer
+    /// means translating this function.
+    pub fn text(&self) -> String {
+        match *self {
+            Announcement::TimeRemaining(seconds) => {
+                let amount = match seconds {
+                    120 => "Two minutes".to_string(),
+                    60 => "One minute".to_string(),
+                    30 => "Thirty seconds".to_string(),
+                    10 => "Ten seconds".to_string(),
+                    seconds => format!("{} seconds", seconds),
+                };
+                format!("{} remaining", amount)
+            }
+            Announcement::EnemyClose => "Enemy close".to_string(),
+            Announcement::ExitDirection(direction) => format!("Exit {}", direction.phrase()),
+            Announcement::LevelComplete { level, score } => {
+                format!("Level {} complete, score {}", level, score)
+            }
+            Announcement::GameOver { level, score } => {
+                format!("Game over on level {}, score {}", level, score)
+            }
+        }
+    }
+}
+
+/// Facts about a single frame that announcements are made from.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AnnouncerFrame {
+    /// Seconds since the last frame
+    pub delta_time: f32,
+    /// The screen being shown
+    pub screen: CurrentScreen,
+    /// The current level
+    pub level: i32,
+    /// The current score
+    pub score: u32,
+    /// Seconds left on the level timer, or `None` while it isn't running
+    pub seconds_left: Option<f32>,
+    /// Horizontal distance between the player and the enemy, or `None` while the enemy is locked
+    pub enemy_distance: Option<f32>,
+    /// Compass bearing to the exit in degrees clockwise from where the player faces, or
+    /// `None` when the compass isn't pointing anywhere
+    pub exit_bearing: Option<f32>,
+}
+
+/// What the announcer watches for changes, bucketed so small movements don't count.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Watched {
+    /// The screen being shown
+    screen: CurrentScreen,
+    /// Whether the level timer is running
+    timer_running: bool,
+    /// How many of the [`TIME_THRESHOLDS`] the timer has dropped past
+    thresholds_passed: usize,
+    /// Whether the enemy is close, with [`ENEMY_CLEAR_RANGE`] as hysteresis
+    enemy_close: bool,
+    /// Direction of the exit, with [`DIRECTION_HYSTERESIS`], while a level is being played
+    exit_direction: Option<Direction>,
+}
+
+impl Watched {
+    /// Buckets a frame.
+    ///
+    /// # Arguments
+    /// * `frame` - Facts about the current frame
+    /// * `previous` - What was watched last frame, for hysteresis
+    fn observe(frame: &AnnouncerFrame, previous: Option<&Watched>) -> Self {
+        let was_close = previous.is_some_and(|watched| watched.enemy_close);
+        let range = if was_close {
+            ENEMY_CLEAR_RANGE
+        } else {
+            DANGER_RANGE
+        };
+        let in_game = frame.screen == CurrentScreen::Game;
+        Self {
+            screen: frame.screen,
+            timer_running: frame.seconds_left.is_some(),
+            thresholds_passed: frame.seconds_left.map_or(0, |seconds| {
+                TIME_THRESHOLDS
+                    .iter()
+                    .filter(|&&threshold| seconds <= threshold as f32)
+                    .count()
+            }),
+            enemy_close: frame
+                .enemy_distance
+                .is_some_and(|distance| distance < range),
+            exit_direction: frame.exit_bearing.filter(|_| in_game).map(|bearing| {
+                Direction::settle(previous.and_then(|watched| watched.exit_direction), bearing)
+            }),
+        }
+    }
+}
+
+/// Decides what to announce, and keeps the on-screen line.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Announcer {
+    /// What was watched last frame, or `None` before the first enabled frame
+    watched: Option<Watched>,
+    /// The exit direction last announced, or seen when announcements started
+    announced_direction: Option<Direction>,
+    /// Seconds until [`Announcement::EnemyClose`] may be announced again
+    enemy_cooldown: f32,
+    /// Seconds until [`Announcement::ExitDirection`] may be announced again
+    direction_cooldown: f32,
+    /// Seconds since anything was announced
+    quiet: f32,
+    /// The last announcement's text and how many seconds it has been up
+    line: Option<(String, f32)>,
+}
+
+impl Announcer {
+    /// Creates an announcer that hasn't seen anything yet.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Works out what, if anything, a frame calls for announcing.
+    ///
+    /// The first frame after announcements are turned on only sets the baseline.
+    ///
+    /// # Arguments
+    /// * `frame` - Facts about the current frame
+    /// * `enabled` - Whether the announcements setting is on
+    ///
+    /// # Returns
+    /// The announcements to make this frame, in the order to make them
+    pub fn update(&mut self, frame: AnnouncerFrame, enabled: bool) -> Vec<Announcement> {
+        if !enabled {
+            if self.watched.is_some() || self.line.is_some() {
+                *self = Self::default();
+            }
+            return Vec::new();
+        }
+
+        let delta_time = frame.delta_time.max(0.0);
+        self.enemy_cooldown = (self.enemy_cooldown - delta_time).max(0.0);
+        self.direction_cooldown = (self.direction_cooldown - delta_time).max(0.0);
+        self.quiet += delta_time;
+        if let Some((_, age)) = &mut self.line {
+            *age += delta_time;
+        }
+        if self.line.as_ref().is_some_and(|(_, age)| *age >= LINE_SECS) {
+            self.line = None;
+        }
+
+        let previous = self.watched;
+        let watched = Watched::observe(&frame, previous.as_ref());
+        self.watched = Some(watched);
+        let Some(previous) = previous else {
+            self.quiet = DIRECTION_QUIET_SECS;
+            self.announced_direction = watched.exit_direction;
+            return Vec::new();
+        };
+        if previous == watched && self.announced_direction == watched.exit_direction {
+            return Vec::new();
+        }
+
+        let mut announcements = Vec::new();
+        let was_playing = previous.screen == CurrentScreen::Game;
+        let playing = watched.screen == CurrentScreen::Game;
+
+        if was_playing && watched.screen == CurrentScreen::ExitReached {
+            announcements.push(Announcement::LevelComplete {
+                level: frame.level,
+                score: frame.score,
+            });
+        }
+        if was_playing && watched.screen == CurrentScreen::GameOver {
+            announcements.push(Announcement::GameOver {
+                level: frame.level,
+                score: frame.score,
+            });
+        }
+        // A timer that just started doesn't count as dropping past anything
+        if playing
+            && previous.timer_running
+            && watched.thresholds_passed > previous.thresholds_passed
+        {
+            let threshold = TIME_THRESHOLDS[watched.thresholds_passed - 1];
+            announcements.push(Announcement::TimeRemaining(threshold));
+        }
+        if playing && watched.enemy_close && !previous.enemy_close && self.enemy_cooldown <= 0.0 {
+            announcements.push(Announcement::EnemyClose);
+            self.enemy_cooldown = ENEMY_REPEAT_SECS;
+        }
+
+        if !playing {
+            self.announced_direction = None;
+        } else if watched.exit_direction != self.announced_direction
+            && announcements.is_empty()
+            && self.direction_cooldown <= 0.0
+            && self.quiet >= DIRECTION_QUIET_SECS
+            && let Some(direction) = watched.exit_direction
+        {
+            announcements.push(Announcement::ExitDirection(direction));
+            self.announced_direction = Some(direction);
+            self.direction_cooldown = DIRECTION_REPEAT_SECS;
+        }
+
+        if let Some(last) = announcements.last() {
+            self.line = Some((last.text(), 0.0));
+            self.quiet = 0.0;
+        }
+        announcements
+    }
+
+    /// Returns the on-screen announcement line.
+    ///
+    /// # Returns
+    /// The last announcement's text and its opacity from `0.0` to `1.0`, or `None` once it
+    /// has been up for [`LINE_SECS`]
+    pub fn line(&self) -> Option<(&str, f32)> {
+        self.line.as_ref().map(|(text, age)| {
+            let opacity = ((LINE_SECS - age) / LINE_FADE_SECS).clamp(0.0, 1.0);
+            (text.as_str(), opacity)
+        })
+    }
+}
+
+/// Reads announcements aloud on a background thread.
+///
+/// Only does anything when the game is built with the `tts` feature. The speech engine is
+/// opened on its own thread so a slow engine never holds up a frame; if the platform has
+/// none, the thread logs why and exits, and [`Speech::say`] quietly does nothing.
+#[derive(Debug, Clone)]
+pub struct Speech {
+    /// Sends text to the speech thread, or `None` when built without text-to-speech
+    sender: Option<Sender<String>>,
+}
+
+impl Default for Speech {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Speech {
+    /// Starts the speech thread, when the game is built with text-to-speech.
+    pub fn new() -> Self {
+        #[cfg(feature = "tts")]
+        let sender = spawn_speech_thread();
+        #[cfg(not(feature = "tts"))]
+        let sender = None;
+        Self { sender }
+    }
+
+    /// Queues a sentence to be read aloud after anything already queued.
+    ///
+    /// # Arguments
+    /// * `text` - The sentence to read
+    pub fn say(&self, text: &str) {
+        if let Some(sender) = &self.sender {
+            let _ = sender.send(text.to_string());
+        }
+    }
+}
+
+/// Opens the platform's speech engine on its own thread.
+///
+/// # Returns
+/// A sender for sentences to read, or `None` if the thread couldn't be started
+#[cfg(feature = "tts")]
+fn spawn_speech_thread() -> Option<Sender<String>> {
+    let (sender, receiver) = mpsc::channel::<String>();
+    let spawned = std::thread::Builder::new()
+        .name("announcer-speech".to_string())
+        .spawn(move || {
+            let mut tts = match tts::Tts::default() {
+                Ok(tts) => tts,
+                Err(e) => {
+                    eprintln!("Text-to-speech unavailable: {}", e);
+                    return;
+                }
+            };
+            for text in receiver {
+                if let Err(e) = tts.speak(text, false) {
+                    eprintln!("Failed to speak announcement: {}", e);
+                }
+            }
+        });
+    match spawned {
+        Ok(_) => Some(sender),
+        Err(e) => {
+            eprintln!("Failed to start the speech thread: {}", e);
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A frame one second after the last, mid-level with nothing going on.
+    fn playing(seconds_left: f32) -> AnnouncerFrame {
+        AnnouncerFrame {
+            delta_time: 1.0,
+            screen: CurrentScreen::Game,
+            level: 3,
+            score: 4200,
+            seconds_left: Some(seconds_left),
+            enemy_distance: Some(1000.0),
+            exit_bearing: Some(0.0),
+        }
+    }
+
+    /// Runs a timeline and returns what was said, with the frame it was said on.
+    fn script(announcer: &mut Announcer, frames: &[AnnouncerFrame]) -> Vec<(usize, String)> {
+        frames
+            .iter()
+            .enumerate()
+            .flat_map(|(index, &frame)| {
+                announcer
+                    .update(frame, true)
+                    .into_iter()
+                    .map(move |announcement| (index, announcement.text()))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_scripted_level_announces_each_change_once() {
+        let enemy_at = |frame: AnnouncerFrame, distance| AnnouncerFrame {
+            enemy_distance: Some(distance),
+            ..frame
+        };
+        let facing = |frame: AnnouncerFrame, bearing| AnnouncerFrame {
+            exit_bearing: Some(bearing),
+            ..frame
+        };
+        let left = |seconds| facing(playing(seconds), -45.0);
+        let frames = [
+            // 0: baseline, then the clock runs down past two minutes
+            playing(125.0),
+            playing(122.0),
+            playing(119.5),
+            playing(118.0),
+            // 4: turning puts the exit ahead to the left; jitter at the edge isn't news
+            left(117.0),
+            facing(playing(116.0), -20.0),
+            facing(playing(115.0), -50.0),
+            // 7: the enemy closes in, backs off a little, and closes in again too soon
+            enemy_at(left(114.0), 150.0),
+            enemy_at(left(113.0), 220.0),
+            enemy_at(left(112.0), 150.0),
+            enemy_at(left(111.0), 400.0),
+            // 11: long enough later it is announced again
+            AnnouncerFrame {
+                delta_time: 6.0,
+                ..enemy_at(left(105.0), 150.0)
+            },
+            left(59.0),
+            AnnouncerFrame {
+                screen: CurrentScreen::ExitReached,
+                seconds_left: None,
+                ..left(58.0)
+            },
+        ];
+        let said = script(&mut Announcer::new(), &frames);
+        assert_eq!(
+            said,
+            vec![
+                (2, "Two minutes remaining".to_string()),
+                (4, "Exit ahead to the left".to_string()),
+                (7, "Enemy close".to_string()),
+                (11, "Enemy close".to_string()),
+                (12, "One minute remaining".to_string()),
+                (13, "Level 3 complete, score 4200".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_directions_wrap_and_wait_for_quiet() {
+        assert_eq!(Direction::from_bearing(0.0), Direction::Ahead);
+        assert_eq!(Direction::from_bearing(350.0), Direction::Ahead);
+        assert_eq!(Direction::from_bearing(-90.0), Direction::Left);
+        assert_eq!(Direction::from_bearing(180.0), Direction::Behind);
+        assert_eq!(Direction::from_bearing(-180.0), Direction::Behind);
+        assert_eq!(Direction::from_bearing(135.0), Direction::BehindRight);
+        assert_eq!(Direction::from_bearing(720.0 + 45.0), Direction::AheadRight);
+
+        // A new direction waits out the quiet after another announcement, then is said once
+        let mut announcer = Announcer::new();
+        let frames = [
+            playing(61.0),
+            AnnouncerFrame {
+                exit_bearing: Some(90.0),
+                delta_time: 0.5,
+                ..playing(59.0)
+            },
+            AnnouncerFrame {
+                exit_bearing: Some(90.0),
+                delta_time: 0.5,
+                ..playing(58.5)
+            },
+            AnnouncerFrame {
+                exit_bearing: Some(90.0),
+                delta_time: 2.0,
+                ..playing(56.5)
+            },
+            AnnouncerFrame {
+                exit_bearing: Some(92.0),
+                ..playing(55.5)
+            },
+        ];
+        assert_eq!(
+            script(&mut announcer, &frames),
+            vec![
+                (1, "One minute remaining".to_string()),
+                (3, "Exit to the right".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_disabled_announcer_stays_silent_and_starts_fresh() {
+        let mut announcer = Announcer::new();
+        assert!(announcer.update(playing(125.0), true).is_empty());
+        assert!(announcer.update(playing(119.0), false).is_empty());
+        assert_eq!(announcer, Announcer::new());
+
+        // Turning it back on sets a new baseline instead of announcing what it missed
+        assert!(announcer.update(playing(59.0), true).is_empty());
+        assert_eq!(announcer.line(), None);
+        let said = announcer.update(playing(29.0), true);
+        assert_eq!(said, vec![Announcement::TimeRemaining(30)]);
+
+        // The line fades out over its last second
+        let (text, opacity) = announcer.line().expect("Just announced");
+        assert_eq!((text, opacity), ("Thirty seconds remaining", 1.0));
+        for _ in 0..6 {
+            announcer.update(
+                AnnouncerFrame {
+                    delta_time: 1.1,
+                    ..playing(28.0)
+                },
+                true,
+            );
+        }
+        assert_eq!(announcer.line(), None);
+    }
+}
@@ -0,0 +1,388 @@
+//! Hidden anomalies that unlock cosmetics.
+//!
+//! On levels whose [plan](crate::game::levels::LevelPlan::anomaly) allows it, from level 2 on,
+//! about one in [`ANOMALY_ODDS`] hides an anomaly: a small floating
+//! tetrahedron tucked into a dead end off the solution path, never at the entrance or the exit.
+//! There is no HUD marker for it; a soft shimmer plays while the player is within
+//! [`SHIMMER_RANGE`] of it, and it is collected like any other interactable, by looking at it
+//! up close and pressing the interact key.
+//!
+//! Each anomaly carries a [`Collectible`] id, and the [`COLLECTIBLES`] registry maps that id to
+//! the [`Cosmetic`] it unlocks: an extra color palette, star theme, or reticle style. Unlocks
+//! are kept in an [`UnlockRegistry`] saved with the codex stats, and collecting an anomaly
+//! whose cosmetic is already unlocked adds [`DUPLICATE_BONUS`] to the level's score instead,
+//! awarded with the rest of its points by the
+//! [`ScoreCalculator`](crate::game::score::ScoreCalculator). Unlocked cosmetics can be
+//! switched on from the pause menu; locked ones are shown greyed out.
+//!
+//! Like the level's theme and events, the anomaly is rolled from the level seed, so a retried
+//! level or a shared seed hides the same one in the same place. Test mode and two-floor levels
+//! never have one.
+//!
+//! # Usage
+//!
+//! ```rust,ignore
+//! let anomaly = Anomaly::roll(level_seed, &maze_grid, entrance, exit, is_test_mode);
+//!
+//! // When the player takes it
+//! match codex.unlocks.unlock(anomaly.collectible) {
+//!     Some(Unlock::New(cosmetic)) => println!("Unlocked {}", cosmetic.name()),
+//!     Some(Unlock::Duplicate) => game_state.duplicate_anomalies += 1,
+//!     None => {}
+//! }
+//! ```
+
+use crate::game::interact::Interactable;
+use crate::game::maze::{self, generator::Cell};
+use crate::math::coordinates;
+use rand::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// One level in this many hides an anomaly, of those planned with one.
+pub const ANOMALY_ODDS: u32 = 5;
+
+/// Height of the anomaly's center above the floor, in world units.
+pub const ANOMALY_HEIGHT: f32 = 38.0;
+
+/// Distance from the anomaly's center to its corners, in world units.
+pub const ANOMALY_SIZE: f32 = 9.0;
+
+/// Farthest the player can be from the anomaly and still hear its shimmer, in world units.
+pub const SHIMMER_RANGE: f32 = 200.0;
+
+/// Seconds between shimmers.
+pub const SHIMMER_INTERVAL_SECS: f32 = 2.5;
+
+/// Points granted for collecting an anomaly whose cosmetic is already unlocked.
+pub const DUPLICATE_BONUS: u32 = 250;
+
+/// What interacting with the anomaly does, shown in the interaction hint.
+pub const ANOMALY_PROMPT: &str = "Take the anomaly";
+
+/// Salt mixed into the level seed so the anomaly doesn't correlate with the level's events.
+const ANOMALY_SEED_SALT: u64 = 0x0061_6e6f_6d61_6c79;
+
+/// A cosmetic an anomaly can unlock.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Cosmetic {
+    /// A deep violet sky in place of the tuned background color
+    DuskPalette,
+    /// Warm amber stars in place of the ones contrasting with the sky
+    EmberStars,
+    /// A hollow ring in place of the reticle's dot or cross
+    RingReticle,
+}
+
+impl Cosmetic {
+    /// Every cosmetic, in the order the pause menu lists them.
+    pub const ALL: [Cosmetic; 3] = [
+        Cosmetic::DuskPalette,
+        Cosmetic::EmberStars,
+        Cosmetic::RingReticle,
+    ];
+
+    /// Returns the short name shown on the cosmetic's pause menu button.
+    pub fn name(self) -> &'static str {
+        match self {
+            Cosmetic::DuskPalette => "Dusk",
+            Cosmetic::EmberStars => "Ember",
+            Cosmetic::RingReticle => "Ring",
+        }
+    }
+}
+
+/// An entry of the collectible registry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Collectible {
+    /// Id of the anomaly, as saved with the level's anomaly
+    pub id: &'static str,
+    /// The cosmetic collecting it unlocks
+    pub cosmetic: Cosmetic,
+}
+
+/// Every collectible, and the cosmetic each one unlocks.
+pub const COLLECTIBLES: [Collectible; 3] = [
+    Collectible {
+        id: "violet_tetra",
+        cosmetic: Cosmetic::DuskPalette,
+    },
+    Collectible {
+        id: "amber_tetra",
+        cosmetic: Cosmetic::EmberStars,
+    },
+    Collectible {
+        id: "hollow_tetra",
+        cosmetic: Cosmetic::RingReticle,
+    },
+];
+
+/// Looks up a collectible by id.
+///
+/// # Arguments
+/// * `id` - The collectible's id
+///
+/// # Returns
+/// The registry entry, or `None` for an unknown id
+pub fn collectible(id: &str) -> Option<&'static Collectible> {
+    COLLECTIBLES.iter().find(|collectible| collectible.id == id)
+}
+
+/// The outcome of collecting an anomaly.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Unlock {
+    /// The cosmetic was locked and is now unlocked
+    New(Cosmetic),
+    /// The cosmetic was already unlocked, so [`DUPLICATE_BONUS`] is granted instead
+    Duplicate,
+}
+
+/// The cosmetics the player has unlocked, saved with the codex stats.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct UnlockRegistry {
+    /// Unlocked cosmetics, in the order they were found
+    pub unlocked: Vec<Cosmetic>,
+}
+
+impl UnlockRegistry {
+    /// Returns whether a cosmetic has been unlocked.
+    pub fn is_unlocked(&self, cosmetic: Cosmetic) -> bool {
+        self.unlocked.contains(&cosmetic)
+    }
+
+    /// Returns the cosmetics of a list that have been unlocked, keeping their order.
+    ///
+    /// # Arguments
+    /// * `cosmetics` - Cosmetics the player switched on, unlocked or not
+    pub fn unlocked_of(&self, cosmetics: &[Cosmetic]) -> Vec<Cosmetic> {
+        cosmetics
+            .iter()
+            .copied()
+            .filter(|&cosmetic| self.is_unlocked(cosmetic))
+            .collect()
+    }
+
+    /// Unlocks the cosmetic of a collected anomaly.
+    ///
+    /// Unlocking is idempotent: collecting the same anomaly again leaves the registry as it is.
+    ///
+    /// # Arguments
+    /// * `collectible_id` - Id of the collected anomaly
+    ///
+    /// # Returns
+    /// Whether the cosmetic was new or a duplicate, or `None` for an unknown id
+    pub fn unlock(&mut self, collectible_id: &str) -> Option<Unlock> {
+        let cosmetic = collectible(collectible_id)?.cosmetic;
+        if self.is_unlocked(cosmetic) {
+            return Some(Unlock::Duplicate);
+        }
+        self.unlocked.push(cosmetic);
+        Some(Unlock::New(cosmetic))
+    }
+}
+
+/// The anomaly hidden in the current level.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Anomaly {
+    /// Id of the collectible, see [`COLLECTIBLES`]
+    pub collectible: &'static str,
+    /// The dead end it floats in, in wall grid coordinates
+    pub cell: Cell,
+    /// World position of its center
+    pub position: [f32; 3],
+}
+
+impl Anomaly {
+    /// Rolls whether a level hides an anomaly, and where.
+    ///
+    /// Only rolled for levels whose [plan](crate::game::levels::LevelPlan::anomaly) allows one.
+    ///
+    /// # Arguments
+    /// * `seed` - The level's event seed, see [`crate::game::events::level_seed`]
+    /// * `maze_grid` - 2D grid of the maze where `true` marks a wall
+    /// * `entrance` - The cell the player starts in
+    /// * `exit` - The maze's exit cell
+    /// * `is_test_mode` - Whether the maze is laid out at test mode scale
+    ///
+    /// # Returns
+    /// The same anomaly whenever the same maze is played on the same level, or `None` if the
+    /// roll failed or the maze has no dead end to hide one in
+    pub fn roll(
+        seed: u64,
+        maze_grid: &[Vec<bool>],
+        entrance: Cell,
+        exit: Cell,
+        is_test_mode: bool,
+    ) -> Option<Self> {
+        let mut rng = StdRng::seed_from_u64(seed ^ ANOMALY_SEED_SALT);
+        if !rng.gen_ratio(1, ANOMALY_ODDS) {
+            return None;
+        }
+        let cell = *spawn_candidates(maze_grid, entrance, exit).choose(&mut rng)?;
+        let collectible = COLLECTIBLES.choose(&mut rng)?.id;
+        let maze_dimensions = (maze_grid.first().map_or(0, Vec::len), maze_grid.len());
+        Some(Self {
+            collectible,
+            cell,
+            position: coordinates::maze_to_world(
+                &cell,
+                maze_dimensions,
+                ANOMALY_HEIGHT,
+                is_test_mode,
+            ),
+        })
+    }
+
+    /// Returns the interactable the player takes the anomaly through.
+    pub fn interactable(&self) -> Interactable {
+        Interactable::new(self.position, ANOMALY_PROMPT)
+    }
+}
+
+/// Finds the cells an anomaly may be hidden in.
+///
+/// # Arguments
+/// * `maze_grid` - 2D grid of the maze where `true` marks a wall
+/// * `entrance` - The cell the player starts in
+/// * `exit` - The maze's exit cell
+///
+/// # Returns
+/// Every dead end that is neither on the solution path nor the entrance or exit
+pub fn spawn_candidates(maze_grid: &[Vec<bool>], entrance: Cell, exit: Cell) -> Vec<Cell> {
+    let path = maze::find_path(maze_grid, entrance, exit).unwrap_or_default();
+    maze::dead_ends(maze_grid)
+        .into_iter()
+        .filter(|cell| *cell != entrance && *cell != exit && !path.contains(cell))
+        .collect()
+}
+
+/// Paces the shimmer that hints at a nearby anomaly.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Shimmer {
+    /// Seconds until the next shimmer may play
+    cooldown: f32,
+}
+
+impl Shimmer {
+    /// Advances the shimmer by one frame.
+    ///
+    /// The first shimmer plays as soon as the player comes within range, then one every
+    /// [`SHIMMER_INTERVAL_SECS`] while they stay there.
+    ///
+    /// # Arguments
+    /// * `distance` - Distance from the player to the anomaly, or `None` if there is none
+    /// * `delta_time` - Seconds since the last frame
+    ///
+    /// # Returns
+    /// The linear volume to play a shimmer at this frame, louder the closer the player is
+    pub fn update(&mut self, distance: Option<f32>, delta_time: f32) -> Option<f32> {
+        let Some(distance) = distance.filter(|&distance| distance <= SHIMMER_RANGE) else {
+            self.cooldown = 0.0;
+            return None;
+        };
+        self.cooldown -= delta_time;
+        if self.cooldown > 0.0 {
+            return None;
+        }
+        self.cooldown = SHIMMER_INTERVAL_SECS;
+        Some(0.15 + 0.35 * (1.0 - distance / SHIMMER_RANGE))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::events;
+    use crate::game::maze::generator::MazeGenerator;
+
+    /// Generates a seeded maze as the game does, returning its grid, entrance and exit.
+    fn generated_maze(seed: u64) -> (Vec<Vec<bool>>, Cell, Cell) {
+        let (mut generator, maze) = MazeGenerator::with_seed(12, 12, seed);
+        while !generator.is_complete() {
+            generator.step();
+        }
+        let (grid, exit) = maze.lock().unwrap().level_grid();
+        let entrance = Cell::new(grid.len() - 2, 1);
+        (grid, entrance, exit.expect("Completed maze has an exit"))
+    }
+
+    #[test]
+    fn test_anomalies_hide_in_dead_ends_off_the_solution_path() {
+        let mut spawned = 0;
+        for seed in 0..200 {
+            let (grid, entrance, exit) = generated_maze(seed);
+            let path = maze::find_path(&grid, entrance, exit).unwrap();
+            let dead_ends = maze::dead_ends(&grid);
+            for level in 2..=6 {
+                let level_seed = events::level_seed(&grid, level);
+                let Some(anomaly) = Anomaly::roll(level_seed, &grid, entrance, exit, false) else {
+                    continue;
+                };
+                spawned += 1;
+                assert!(dead_ends.contains(&anomaly.cell), "seed {}", seed);
+                assert!(!path.contains(&anomaly.cell), "seed {}", seed);
+                assert_ne!(anomaly.cell, entrance);
+                assert_ne!(anomaly.cell, exit);
+                assert!(collectible(anomaly.collectible).is_some());
+
+                // Rolled from the seed, so a retry hides the same one
+                let again = Anomaly::roll(level_seed, &grid, entrance, exit, false);
+                assert_eq!(again, Some(anomaly));
+            }
+        }
+        // About one level in ANOMALY_ODDS
+        assert!((120..280).contains(&spawned), "{} anomalies", spawned);
+    }
+
+    #[test]
+    fn test_no_anomaly_without_a_dead_end_to_hide_in() {
+        // A single corridor from the entrance to the exit
+        let grid = vec![
+            vec![true; 5],
+            vec![true, false, false, false, true],
+            vec![true; 5],
+        ];
+        let (entrance, exit) = (Cell::new(1, 1), Cell::new(1, 3));
+        assert!(spawn_candidates(&grid, entrance, exit).is_empty());
+        for seed in 0..50 {
+            assert_eq!(Anomaly::roll(seed, &grid, entrance, exit, false), None);
+        }
+    }
+
+    #[test]
+    fn test_unlocking_is_idempotent() {
+        let mut registry = UnlockRegistry::default();
+        assert_eq!(
+            registry.unlock("amber_tetra"),
+            Some(Unlock::New(Cosmetic::EmberStars))
+        );
+        assert_eq!(registry.unlock("amber_tetra"), Some(Unlock::Duplicate));
+        assert_eq!(registry.unlock("no_such_tetra"), None);
+        assert_eq!(registry.unlocked, vec![Cosmetic::EmberStars]);
+        assert!(!registry.is_unlocked(Cosmetic::DuskPalette));
+        assert_eq!(
+            registry.unlocked_of(&[Cosmetic::DuskPalette, Cosmetic::EmberStars]),
+            vec![Cosmetic::EmberStars]
+        );
+
+        // Every cosmetic can be found
+        for cosmetic in Cosmetic::ALL {
+            assert!(COLLECTIBLES.iter().any(|c| c.cosmetic == cosmetic));
+        }
+    }
+
+    #[test]
+    fn test_shimmer_plays_in_range_and_louder_up_close() {
+        let mut shimmer = Shimmer::default();
+        assert_eq!(shimmer.update(Some(SHIMMER_RANGE + 1.0), 0.1), None);
+
+        let far = shimmer.update(Some(SHIMMER_RANGE), 0.1).unwrap();
+        assert_eq!(shimmer.update(Some(10.0), 0.1), None);
+        let near = shimmer.update(Some(10.0), SHIMMER_INTERVAL_SECS).unwrap();
+        assert!(near > far);
+
+        // Walking away and back plays again straight away
+        assert_eq!(shimmer.update(None, 0.1), None);
+        assert!(shimmer.update(Some(50.0), 0.1).is_some());
+    }
+}
@@ -9,13 +9,25 @@
 //! - **Spatial Audio**: 3D positional audio with distance-based attenuation and reverb
 //! - **Movement Audio**: Footstep sounds that adapt to walking/sprinting states
 //! - **Enemy Audio Management**: Individual tracking and positioning of enemy sounds
+//! - **Enemy Audio LOD**: Distant enemies update their position less often, and enemies
+//!   out of earshot are paused until they come back into range
+//! - **Enhanced Enemy Audio**: Optionally, enemies are heard where they were when the sound
+//!   left them, and pitch up or down as they approach or retreat (see
+//!   [`crate::game::sound_propagation`])
 //! - **Background Music**: Looping background music with volume control for different game states
+//! - **Ducking**: The music is turned down while warning cues play, see [`crate::game::cues`]
+//! - **Ambience**: Synthesized bed layers and sparse accents for each level theme, on their own
+//!   bus with their own volume, see [`crate::game::soundscape`]
+//! - **Output Profiles**: Panning and rear attenuation tuned for headphones, speakers, or a
+//!   single speaker, with an orbiting test sound, see [`crate::game::spatial_audio`]
 //! - **Sound Effects**: Various game sounds (completion, wall hits, UI interactions, etc.)
+//! - **Introspection**: While the audio debug overlay is open, a log of recent events and
+//!   a snapshot of every source, see [`crate::game::audio_debug`]
 //! - **Volume Management**: Dynamic volume adjustment for different game contexts
 //!
 //! ## Usage
 //!
-//! ```rust
+//! ```rust,ignore
 //! use your_crate::GameAudioManager;
 //!
 //! // Initialize the audio manager
@@ -35,19 +47,106 @@
 //! ```
 
 use crate::assets;
+use crate::game::audio_debug::{
+    self, AudioChannel, AudioDebugSnapshot, AudioDebugState, AudioEventKind, SourceSnapshot,
+    SourceState,
+};
+use crate::game::cues::{DuckCue, DuckSettings, MusicDucker};
+use crate::game::sound_propagation::EnemyPropagation;
+use crate::game::soundscape::{
+    self, Accent, AccentShot, BedLayer, BedSpec, SoundscapeProfile, accent_samples, bed_samples,
+};
+use crate::game::spatial_audio::{
+    AUDIO_TEST_DURATION, BACKEND_HAS_HRTF, MonoDownmixBuilder, MonoDownmixHandle, OutputProfile,
+    SpatialMix, audio_test_position, listener_orientation, rear_gain_db,
+};
 use kira::Decibels;
 use kira::sound::static_sound::StaticSoundSettings;
 use kira::{
-    AudioManager, AudioManagerSettings, DefaultBackend, Easing, Mapping, StartTime, Tween, Value,
+    AudioManager, AudioManagerSettings, DefaultBackend, Easing, Frame, Mapping, Panning,
+    PlaySoundError, ResourceLimitReached, StartTime, Tween, Value,
     effect::{reverb::ReverbBuilder, volume_control::VolumeControlBuilder},
     listener::ListenerHandle,
-    sound::static_sound::{StaticSoundData, StaticSoundHandle},
-    track::{SpatialTrackBuilder, SpatialTrackDistances, SpatialTrackHandle},
+    sound::{
+        SoundData,
+        static_sound::{StaticSoundData, StaticSoundHandle},
+    },
+    track::{
+        MainTrackBuilder, SpatialTrackBuilder, SpatialTrackDistances, SpatialTrackHandle,
+        TrackBuilder, TrackHandle,
+    },
 };
+use std::borrow::Cow;
 use std::time::Instant;
 
 use std::{collections::HashMap, error::Error, time::Duration};
 
+/// Distance below which enemy audio is treated as coming from the listener's position.
+const ENEMY_MIN_DISTANCE: f32 = 1.0;
+
+/// Farthest distance an enemy can be heard from, in world units.
+///
+/// Enemies beyond it have their audio paused instead of updated.
+pub const ENEMY_MAX_AUDIBLE_DISTANCE: f32 = 3200.0;
+
+/// Distance at which the enemy's volume starts falling off.
+const ENEMY_FALLOFF_START: f32 = 5.0;
+
+/// Enemy volume at [`ENEMY_FALLOFF_START`], in decibels.
+const ENEMY_NEAR_VOLUME_DB: f32 = 20.0;
+
+/// Enemy volume at [`ENEMY_MAX_AUDIBLE_DISTANCE`], in decibels.
+const ENEMY_FAR_VOLUME_DB: f32 = -50.0;
+
+/// Power of the enemy's volume falloff curve, see [`enemy_volume_db`].
+const ENEMY_FALLOFF_POWER: i32 = 3;
+
+/// Enemy volume below which its position is only updated at the reduced rate, in decibels.
+///
+/// Quiet enough that a 100ms lag in panning can't be heard.
+const FULL_RATE_VOLUME_DB: f32 = -20.0;
+
+/// Time between position updates for enemies in the [`AudioBand::Reduced`] band (10 Hz).
+pub const REDUCED_UPDATE_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Fraction past a band's edge an enemy must move before it drops into a farther band.
+///
+/// Keeps an enemy hovering on an edge from flipping between bands every frame.
+const BAND_HYSTERESIS: f32 = 0.05;
+
+/// Volume of the background music when it starts, in decibels.
+const BACKGROUND_MUSIC_DB: f32 = -20.0;
+
+/// Smallest change in the duck, in decibels, worth sending to the music track.
+const DUCK_EPSILON_DB: f32 = 0.01;
+
+/// Smallest change in an enemy's rear attenuation, in decibels, worth sending to its sound.
+const REAR_GAIN_EPSILON_DB: f32 = 0.1;
+
+/// Time enemy audio takes to glide to a new position or fade in and out.
+const ENEMY_AUDIO_TWEEN: Duration = Duration::from_millis(100);
+
+/// Seconds of the enemy's sound played for a phantom echo.
+const ECHO_LENGTH_SECS: f64 = 3.0;
+
+/// Smallest change in the ambience bus's linear gain worth sending to it.
+const AMBIENCE_GAIN_EPSILON: f32 = 0.001;
+
+/// Time the ambience bus takes to follow a new gain, or the beds take to stop.
+const AMBIENCE_TWEEN: Duration = Duration::from_millis(100);
+
+/// Smallest change in the spawn drone's level worth sending to it.
+const SPAWN_DRONE_EPSILON: f32 = 0.01;
+
+/// Playback rate of the spawn drone as it starts, rising to [`SPAWN_DRONE_TOP_RATE`].
+const SPAWN_DRONE_BASE_RATE: f64 = 0.35;
+
+/// Playback rate of the spawn drone just before the enemy arrives.
+const SPAWN_DRONE_TOP_RATE: f64 = 0.7;
+
+/// Loudest the spawn drone gets, in decibels, just before the enemy arrives.
+const SPAWN_DRONE_PEAK_DB: f32 = 2.0;
+
 /// Represents the different movement states for footstep audio management.
 ///
 /// Each state corresponds to different footstep timing and audio characteristics:
@@ -64,6 +163,320 @@ pub enum MovementState {
     Sprinting,
 }
 
+/// How often an enemy's spatial audio is updated, based on how loud it is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AudioBand {
+    /// Close and loud - the position is updated every frame
+    Full,
+    /// Audible but quiet - the position is updated at 10 Hz
+    Reduced,
+    /// Out of earshot - the audio is paused and not updated at all
+    Culled,
+}
+
+impl AudioBand {
+    /// Picks the band for an enemy at a distance from the listener.
+    ///
+    /// # Arguments
+    ///
+    /// * `distance` - Distance from the listener in world units
+    /// * `current` - The band the enemy was in last frame
+    ///
+    /// # Returns
+    ///
+    /// The new band. Moving to a farther band takes [`BAND_HYSTERESIS`] past its edge.
+    pub fn for_distance(distance: f32, current: AudioBand) -> Self {
+        let band_at = |slack: f32| {
+            if distance <= full_rate_distance() * slack {
+                AudioBand::Full
+            } else if distance <= ENEMY_MAX_AUDIBLE_DISTANCE * slack {
+                AudioBand::Reduced
+            } else {
+                AudioBand::Culled
+            }
+        };
+        let without_slack = band_at(1.0);
+        let with_slack = band_at(1.0 + BAND_HYSTERESIS);
+        // Only stay in the current band on the far side of an edge, never jump past it
+        if with_slack == current {
+            current
+        } else {
+            without_slack
+        }
+    }
+}
+
+/// Returns the enemy's volume at a distance from the listener, in decibels.
+///
+/// Mirrors the volume mapping [`GameAudioManager::spawn_enemy`] gives each enemy's track.
+///
+/// # Arguments
+///
+/// * `distance` - Distance from the listener in world units
+pub fn enemy_volume_db(distance: f32) -> f32 {
+    let span = ENEMY_MAX_AUDIBLE_DISTANCE - ENEMY_FALLOFF_START;
+    let t = ((distance - ENEMY_FALLOFF_START) / span).clamp(0.0, 1.0);
+    let eased = 1.0 - (1.0 - t).powi(ENEMY_FALLOFF_POWER);
+    ENEMY_NEAR_VOLUME_DB + (ENEMY_FAR_VOLUME_DB - ENEMY_NEAR_VOLUME_DB) * eased
+}
+
+/// Returns the distance at which an enemy's volume falls to [`FULL_RATE_VOLUME_DB`].
+///
+/// Derived from the falloff settings, so the bands follow any change to them.
+pub fn full_rate_distance() -> f32 {
+    let eased =
+        (FULL_RATE_VOLUME_DB - ENEMY_NEAR_VOLUME_DB) / (ENEMY_FAR_VOLUME_DB - ENEMY_NEAR_VOLUME_DB);
+    let t = 1.0 - (1.0 - eased.clamp(0.0, 1.0)).powf(1.0 / ENEMY_FALLOFF_POWER as f32);
+    ENEMY_FALLOFF_START + t * (ENEMY_MAX_AUDIBLE_DISTANCE - ENEMY_FALLOFF_START)
+}
+
+/// A positioned sound the enemy audio LOD can move, pause, and resume.
+///
+/// Implemented for Kira's spatial tracks; tests use a recording mock.
+pub trait SpatialAudioSource {
+    /// Moves the source, gliding there over `glide`.
+    fn set_position(&mut self, position: [f32; 3], glide: Duration);
+    /// Pauses everything playing from the source, fading out over `fade`.
+    fn pause(&mut self, fade: Duration);
+    /// Resumes the source, fading in over `fade`.
+    fn resume(&mut self, fade: Duration);
+}
+
+impl SpatialAudioSource for SpatialTrackHandle {
+    fn set_position(&mut self, position: [f32; 3], glide: Duration) {
+        SpatialTrackHandle::set_position(self, position, linear_tween(glide));
+    }
+
+    fn pause(&mut self, fade: Duration) {
+        SpatialTrackHandle::pause(self, linear_tween(fade));
+    }
+
+    fn resume(&mut self, fade: Duration) {
+        SpatialTrackHandle::resume(self, linear_tween(fade));
+    }
+}
+
+/// A playing sound whose pitch can be shifted while it plays.
+///
+/// Implemented for Kira's sound handles; tests use mocks with and without pitch control.
+pub trait PitchControl {
+    /// Returns whether the backend can change this sound's pitch while it plays.
+    fn supports_pitch(&self) -> bool;
+    /// Sets the pitch as a multiple of the normal pitch, gliding there over `glide`.
+    fn set_pitch(&mut self, pitch: f64, glide: Duration);
+}
+
+impl PitchControl for StaticSoundHandle {
+    fn supports_pitch(&self) -> bool {
+        true
+    }
+
+    fn set_pitch(&mut self, pitch: f64, glide: Duration) {
+        self.set_playback_rate(pitch, linear_tween(glide));
+    }
+}
+
+/// Applies propagation delay and doppler to one enemy's sound for this frame.
+///
+/// Both effects are skipped when the sound's pitch can't be changed, so the heard position
+/// never lags behind without the matching pitch shift.
+///
+/// # Arguments
+///
+/// * `propagation` - The enemy's propagation state
+/// * `sound` - The enemy's playing sound
+/// * `position` - Where the enemy really is
+/// * `listener` - Where the listener is
+/// * `now` - The current time
+///
+/// # Returns
+///
+/// Where the enemy should be heard from
+pub fn apply_propagation<P: PitchControl>(
+    propagation: &mut EnemyPropagation,
+    sound: &mut P,
+    position: [f32; 3],
+    listener: [f32; 3],
+    now: Instant,
+) -> [f32; 3] {
+    if !sound.supports_pitch() {
+        return position;
+    }
+    let (heard_at, pitch) = propagation.update(position, listener, now);
+    sound.set_pitch(pitch, ENEMY_AUDIO_TWEEN);
+    heard_at
+}
+
+/// Builds the spatial track every enemy sound plays on, with its reverb and volume falloff.
+///
+/// # Arguments
+///
+/// * `spatialization_strength` - How far the track pans, from the current output profile
+fn enemy_track_builder(spatialization_strength: f32) -> SpatialTrackBuilder {
+    SpatialTrackBuilder::new()
+        .spatialization_strength(spatialization_strength)
+        .distances(SpatialTrackDistances {
+            min_distance: ENEMY_MIN_DISTANCE,
+            max_distance: ENEMY_MAX_AUDIBLE_DISTANCE,
+        })
+        // Add reverb for environmental realism
+        .with_effect(ReverbBuilder::new().mix(Value::Fixed(0.3.into())))
+        // Volume control based on distance from listener
+        .with_effect(VolumeControlBuilder::new(Value::FromListenerDistance(
+            Mapping {
+                input_range: (
+                    ENEMY_FALLOFF_START as f64,
+                    ENEMY_MAX_AUDIBLE_DISTANCE as f64,
+                ),
+                output_range: (ENEMY_NEAR_VOLUME_DB.into(), ENEMY_FAR_VOLUME_DB.into()),
+                // Non-linear falloff for realism
+                easing: Easing::OutPowi(ENEMY_FALLOFF_POWER),
+            },
+        )))
+}
+
+/// Builds an immediate linear tween lasting `duration`.
+fn linear_tween(duration: Duration) -> Tween {
+    Tween {
+        start_time: StartTime::Immediate,
+        duration,
+        easing: Easing::Linear,
+    }
+}
+
+/// Wraps synthesized mono samples as a sound Kira can play.
+fn synthesized(samples: Vec<f32>) -> StaticSoundData {
+    StaticSoundData {
+        sample_rate: soundscape::SAMPLE_RATE,
+        frames: samples.into_iter().map(Frame::from_mono).collect(),
+        settings: StaticSoundSettings::default(),
+        slice: None,
+    }
+}
+
+/// Converts a linear gain to decibels, treating anything inaudible as silence.
+fn gain_to_db(gain: f32) -> Decibels {
+    if gain <= Decibels::SILENCE.as_amplitude() {
+        Decibels::SILENCE
+    } else {
+        Decibels(20.0 * gain.log10())
+    }
+}
+
+/// Returns the volume a sound was set to play at, in decibels, or 0.0 if it isn't fixed.
+fn fixed_volume_db(data: &StaticSoundData) -> f32 {
+    match data.settings.volume {
+        Value::Fixed(Decibels(volume)) => volume,
+        _ => 0.0,
+    }
+}
+
+/// Returns the name of a bed layer's source in the audio debug overlay.
+fn bed_id(layer: BedLayer) -> String {
+    format!("bed {:?}", layer).to_lowercase()
+}
+
+/// Returns how long a one-shot sound plays for at its playback rate.
+fn one_shot_length(data: &StaticSoundData) -> Duration {
+    match data.settings.playback_rate {
+        Value::Fixed(rate) if rate.0 > 0.0 => data.duration().div_f64(rate.0),
+        _ => data.duration(),
+    }
+}
+
+/// Decides how often one enemy's spatial audio is updated.
+///
+/// Enemies in the [`AudioBand::Full`] band are moved every frame and ones in the
+/// [`AudioBand::Reduced`] band every [`REDUCED_UPDATE_INTERVAL`], gliding over the gap so they
+/// move smoothly. Culled enemies are paused. When a culled enemy comes back into range, its
+/// source is snapped to the current position while still silent and then faded in, so the
+/// panning never sweeps across from where it was last heard.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EnemyAudioLod {
+    /// Band the enemy was in after the last update
+    band: AudioBand,
+    /// When the source's position was last sent, if ever
+    last_sent: Option<Instant>,
+    /// Where the enemy was last heard from, if it has been updated
+    heard_at: Option<[f32; 3]>,
+}
+
+impl Default for EnemyAudioLod {
+    /// A newly spawned enemy is playing, so it starts in the full-rate band.
+    fn default() -> Self {
+        Self {
+            band: AudioBand::Full,
+            last_sent: None,
+            heard_at: None,
+        }
+    }
+}
+
+impl EnemyAudioLod {
+    /// Returns the band the enemy is in.
+    pub fn band(&self) -> AudioBand {
+        self.band
+    }
+
+    /// Returns where the enemy was last heard from, once it has been updated.
+    pub fn heard_at(&self) -> Option<[f32; 3]> {
+        self.heard_at
+    }
+
+    /// Updates the enemy's source for this frame.
+    ///
+    /// # Arguments
+    ///
+    /// * `source` - The enemy's spatial audio source
+    /// * `position` - Where the enemy is
+    /// * `listener` - Where the listener is
+    /// * `now` - The current time
+    ///
+    /// # Returns
+    ///
+    /// The band the enemy is in after the update
+    pub fn update<S: SpatialAudioSource>(
+        &mut self,
+        source: &mut S,
+        position: [f32; 3],
+        listener: [f32; 3],
+        now: Instant,
+    ) -> AudioBand {
+        let distance = (0..3)
+            .map(|i| (position[i] - listener[i]).powi(2))
+            .sum::<f32>()
+            .sqrt();
+        let band = AudioBand::for_distance(distance, self.band);
+
+        match (self.band, band) {
+            (AudioBand::Culled, AudioBand::Culled) => {}
+            (_, AudioBand::Culled) => source.pause(ENEMY_AUDIO_TWEEN),
+            (AudioBand::Culled, _) => {
+                source.set_position(position, Duration::ZERO);
+                source.resume(ENEMY_AUDIO_TWEEN);
+                self.last_sent = Some(now);
+            }
+            (_, AudioBand::Full) => {
+                source.set_position(position, ENEMY_AUDIO_TWEEN);
+                self.last_sent = Some(now);
+            }
+            (_, AudioBand::Reduced) => {
+                let due = self.last_sent.is_none_or(|sent| {
+                    now.saturating_duration_since(sent) >= REDUCED_UPDATE_INTERVAL
+                });
+                if due {
+                    source.set_position(position, REDUCED_UPDATE_INTERVAL);
+                    self.last_sent = Some(now);
+                }
+            }
+        }
+
+        self.band = band;
+        self.heard_at = Some(position);
+        band
+    }
+}
+
 /// The main audio manager for game audio systems.
 ///
 /// `GameAudioManager` handles all aspects of game audio including:
@@ -75,13 +488,12 @@ pub enum MovementState {
 ///
 /// The manager uses the Kira audio library for high-quality audio processing
 /// and provides a simple interface for game developers to integrate audio.
+///
+/// A manager made with [`GameAudioManager::silent`] has no audio device: it keeps track of
+/// the movement state and the listener like any other, but plays nothing.
 pub struct GameAudioManager {
-    /// Core Kira audio manager instance
-    audio_manager: AudioManager<DefaultBackend>,
-
-    /// Audio listener handle for spatial audio calculations
-    /// The listener typically represents the player's position and orientation
-    listener: ListenerHandle,
+    /// The audio device and listener, or `None` for a silent manager
+    output: Option<AudioOutput>,
 
     /// Current footstep sound handle, if playing
     /// Managed automatically based on movement state
@@ -122,6 +534,30 @@ pub struct GameAudioManager {
     /// Each track handles 3D positioning, distance attenuation, and effects
     spatial_tracks: HashMap<String, SpatialTrackHandle>,
 
+    /// Map of enemy IDs to how often their spatial audio is updated
+    enemy_lods: HashMap<String, EnemyAudioLod>,
+
+    /// Map of enemy IDs to their propagation delay and doppler state
+    enemy_propagation: HashMap<String, EnemyPropagation>,
+
+    /// Whether enemies get propagation delay and doppler, from the enhanced audio setting
+    enhanced_audio: bool,
+
+    /// Last position given to [`GameAudioManager::set_listener_position`]
+    listener_position: [f32; 3],
+
+    /// Last yaw given to [`GameAudioManager::set_listener_yaw`], in degrees
+    listener_yaw: f32,
+
+    /// How positioned sounds are mixed, from the output profile and HRTF settings
+    spatial_mix: SpatialMix,
+
+    /// Map of enemy IDs to the rear attenuation last applied to their sounds, in decibels
+    enemy_rear_gains: HashMap<String, f32>,
+
+    /// Sound circling the listener while the audio test plays
+    audio_test: Option<AudioTest>,
+
     /// Current movement state for footstep management
     movement_state: MovementState,
 
@@ -133,11 +569,103 @@ pub struct GameAudioManager {
     /// Used with cooldown to manage sound effect timing
     last_wall_hit: Option<Instant>,
 
+    /// Volume the current screen set for the background music, in decibels
+    ///
+    /// Ducking is applied to the music track on top of this, so it is never changed by a cue.
+    music_volume: f32,
+
+    /// Turns the music down while warning cues play
+    ducker: MusicDucker,
+
+    /// Duck last applied to the music track, in decibels
+    applied_duck_db: f32,
+
+    /// Whether the player muted the music, which silences the music track over any duck
+    music_muted: bool,
+
+    /// When [`GameAudioManager::update`] last ran, for timing the duck envelope
+    last_update: Option<Instant>,
+
+    /// Spatial track of the last phantom enemy echo
+    /// Kept alive until the next echo replaces it
+    echo_track: Option<SpatialTrackHandle>,
+
+    /// Drone rising at the cell a delayed enemy is about to spawn in, while it plays
+    spawn_drone: Option<SpawnDrone>,
+
+    /// The last spawn drone, kept alive while it fades out
+    fading_spawn_drone: Option<SpawnDrone>,
+
+    /// Synthesized loop of each bed layer, in [`BedLayer::ALL`] order
+    bed_data: Vec<StaticSoundData>,
+
+    /// Synthesized sound of each accent, in [`Accent::ALL`] order
+    accent_data: Vec<StaticSoundData>,
+
+    /// Bed layers looping for the current level, with the spec each was started from
+    bed_sounds: Vec<(BedSpec, StaticSoundHandle)>,
+
+    /// Player's ambience volume from the settings, from 0.0 (off) to 1.0
+    ambience_volume: f32,
+
+    /// Fade of the level's ambience last given to [`GameAudioManager::set_ambience_level`]
+    ambience_level: f32,
+
+    /// Linear gain last applied to the ambience bus
+    applied_ambience_gain: f32,
+
+    /// Volume the current screen set for enemy tracks, in decibels
+    enemy_track_db: f32,
+
+    /// Event log and one-shot registry, kept only while introspection is on
+    debug: Option<AudioDebugState>,
+
     /// Audio data for beeper rise sound effect
     /// Made public for external access if needed
     pub beeper_rise_data: StaticSoundData,
 }
 
+/// The drone telegraphing a delayed enemy spawn, see [`crate::game::spawn`].
+struct SpawnDrone {
+    /// Spatial track placed at the spawn cell
+    track: SpatialTrackHandle,
+    /// Where the spawn cell is
+    position: [f32; 3],
+    /// The looping drone on the track
+    sound: StaticSoundHandle,
+    /// Level last sent to the drone, from 0.0 to 1.0
+    level: f32,
+}
+
+/// The sound the audio test plays, see [`GameAudioManager::start_audio_test`].
+struct AudioTest {
+    /// Spatial track moved around the listener
+    track: SpatialTrackHandle,
+    /// The enemy's sound looping on the track
+    sound: StaticSoundHandle,
+    /// When the test started
+    started: Instant,
+}
+
+/// The audio device a [`GameAudioManager`] plays through.
+struct AudioOutput {
+    /// Core Kira audio manager instance
+    manager: AudioManager<DefaultBackend>,
+
+    /// Bus the background music plays on, turned down while warning cues play
+    music_track: TrackHandle,
+
+    /// Bus the ambience beds and accents play on, faded in and out with each level
+    ambience_track: TrackHandle,
+
+    /// Audio listener handle for spatial audio calculations
+    /// The listener typically represents the player's position and orientation
+    listener: ListenerHandle,
+
+    /// Folds the main track down to mono for the mono output profile
+    downmix: MonoDownmixHandle,
+}
+
 impl GameAudioManager {
     /// Creates a new `GameAudioManager` instance with all audio assets loaded.
     ///
@@ -163,16 +691,60 @@ impl GameAudioManager {
     ///
     /// # Example
     ///
-    /// ```rust
+    /// ```rust,ignore
     /// let audio_manager = GameAudioManager::new()?;
     /// ```
     pub fn new() -> Result<Self, Box<dyn Error>> {
-        let mut audio_manager =
-            AudioManager::<DefaultBackend>::new(AudioManagerSettings::default())?;
+        let mut main_track_builder = MainTrackBuilder::new();
+        let downmix = main_track_builder.add_effect(MonoDownmixBuilder);
+        let mut manager = AudioManager::<DefaultBackend>::new(AudioManagerSettings {
+            main_track_builder,
+            ..AudioManagerSettings::default()
+        })?;
 
         // Create listener at origin with no rotation
-        let listener = audio_manager.add_listener([0.0, 0.0, 0.0], [0.0, 0.0, 0.0, 1.0])?;
+        let listener = manager.add_listener([0.0, 0.0, 0.0], [0.0, 0.0, 0.0, 1.0])?;
+        let music_track = manager.add_sub_track(TrackBuilder::new())?;
+        let ambience_track =
+            manager.add_sub_track(TrackBuilder::new().volume(Decibels::SILENCE))?;
+
+        let mut audio_manager = Self::with_output(Some(AudioOutput {
+            manager,
+            music_track,
+            ambience_track,
+            listener,
+            downmix,
+        }))?;
+
+        // Start background music immediately
+        audio_manager.start_background_music()?;
 
+        Ok(audio_manager)
+    }
+
+    /// Creates a `GameAudioManager` that plays nothing, for running the game without an audio
+    /// device, such as in tools and tests or on a machine with no sound card.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(GameAudioManager)`, or a boxed error if the embedded audio assets can't be
+    /// decoded.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use mirador::GameAudioManager;
+    ///
+    /// let mut audio_manager = GameAudioManager::silent().unwrap();
+    /// audio_manager.start_walking().unwrap();
+    /// assert!(audio_manager.is_walking());
+    /// ```
+    pub fn silent() -> Result<Self, Box<dyn Error>> {
+        Self::with_output(None)
+    }
+
+    /// Loads the audio assets for a manager playing through `output`.
+    fn with_output(output: Option<AudioOutput>) -> Result<Self, Box<dyn Error>> {
         // Load all required audio files from embedded assets
         let footstep_data =
             StaticSoundData::from_cursor(std::io::Cursor::new(assets::AUDIO_SINGLE_STEP))?;
@@ -188,10 +760,15 @@ impl GameAudioManager {
             StaticSoundData::from_cursor(std::io::Cursor::new(assets::MUSIC_MAIN_TRACK))?;
         let beeper_rise_data =
             StaticSoundData::from_cursor(std::io::Cursor::new(assets::AUDIO_BEEPER_RISE))?;
-
-        let mut audio_manager_instance = GameAudioManager {
-            audio_manager,
-            listener,
+        let bed_data = BedLayer::ALL
+            .map(|layer| synthesized(bed_samples(layer)))
+            .to_vec();
+        let accent_data = Accent::ALL
+            .map(|accent| synthesized(accent_samples(accent)))
+            .to_vec();
+
+        Ok(GameAudioManager {
+            output,
             footstep_sound: None,
             enemy_sounds: HashMap::new(),
             footstep_data,
@@ -204,15 +781,124 @@ impl GameAudioManager {
             beeper_rise_data,
             background_music_handle: None,
             spatial_tracks: HashMap::new(),
+            enemy_lods: HashMap::new(),
+            enemy_propagation: HashMap::new(),
+            enhanced_audio: true,
+            listener_position: [0.0, 0.0, 0.0],
+            listener_yaw: 0.0,
+            spatial_mix: SpatialMix::default(),
+            enemy_rear_gains: HashMap::new(),
+            audio_test: None,
             movement_state: MovementState::Idle,
             wall_hit_cooldown: Duration::from_millis(330),
             last_wall_hit: None,
-        };
+            music_volume: BACKGROUND_MUSIC_DB,
+            ducker: MusicDucker::default(),
+            applied_duck_db: 0.0,
+            music_muted: false,
+            last_update: None,
+            echo_track: None,
+            spawn_drone: None,
+            fading_spawn_drone: None,
+            bed_data,
+            accent_data,
+            bed_sounds: Vec::new(),
+            ambience_volume: 1.0,
+            ambience_level: 0.0,
+            applied_ambience_gain: 0.0,
+            enemy_track_db: 0.0,
+            debug: None,
+        })
+    }
 
-        // Start background music immediately
-        audio_manager_instance.start_background_music()?;
+    /// Plays a sound on the main track.
+    ///
+    /// # Returns
+    ///
+    /// The sound's handle, or `None` on a silent manager.
+    fn play<D: SoundData>(
+        &mut self,
+        sound_data: D,
+    ) -> Result<Option<D::Handle>, PlaySoundError<D::Error>> {
+        self.output
+            .as_mut()
+            .map(|output| output.manager.play(sound_data))
+            .transpose()
+    }
 
-        Ok(audio_manager_instance)
+    /// Plays a one-shot sound effect on the main track, registering it while introspecting.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Name of the sound in the audio debug overlay
+    /// * `sound_data` - The sound, with its volume and playback rate set
+    fn play_effect(
+        &mut self,
+        name: &'static str,
+        sound_data: StaticSoundData,
+    ) -> Result<(), PlaySoundError<()>> {
+        let shot = self
+            .debug
+            .is_some()
+            .then(|| (fixed_volume_db(&sound_data), one_shot_length(&sound_data)));
+        self.play(sound_data)?;
+        if let (Some(debug), Some((volume_db, length))) = (&mut self.debug, shot) {
+            debug.play_one_shot(
+                Instant::now(),
+                name,
+                AudioChannel::Effects,
+                volume_db,
+                None,
+                length,
+            );
+        }
+        Ok(())
+    }
+
+    /// Adds an event to the audio debug log while introspecting.
+    ///
+    /// # Arguments
+    ///
+    /// * `kind` - What happened
+    /// * `source` - Name of the source it happened to
+    /// * `detail` - Describes anything else worth knowing; only called while introspecting
+    fn log_event(
+        &mut self,
+        kind: AudioEventKind,
+        source: impl Into<Cow<'static, str>>,
+        detail: impl FnOnce() -> String,
+    ) {
+        if let Some(debug) = &mut self.debug {
+            debug.record(Instant::now(), kind, source, detail());
+        }
+    }
+
+    /// Ducks the music under a warning cue.
+    fn duck(&mut self, cue: DuckCue) {
+        self.ducker.trigger(cue);
+        self.log_event(AudioEventKind::Duck, "music", || format!("{:?}", cue));
+    }
+
+    /// Adds a spatial track heard by the listener, set up like an enemy's.
+    ///
+    /// # Returns
+    ///
+    /// The track's handle, or `None` on a silent manager.
+    fn add_enemy_track(
+        &mut self,
+        position: [f32; 3],
+    ) -> Result<Option<SpatialTrackHandle>, ResourceLimitReached> {
+        let strength = self.spatial_mix.spatialization_strength;
+        self.output
+            .as_mut()
+            .map(|output| {
+                output.manager.add_spatial_sub_track(
+                    &output.listener,
+                    position,
+                    enemy_track_builder(strength),
+                )
+            })
+            .transpose()
     }
 
     /// Starts or restarts the background music track.
@@ -231,25 +917,32 @@ impl GameAudioManager {
     ///
     /// # Example
     ///
-    /// ```rust
+    /// ```rust,ignore
     /// audio_manager.start_background_music()?;
     /// ```
     pub fn start_background_music(&mut self) -> Result<(), Box<dyn Error>> {
         // Stop existing background music if playing
         if let Some(mut handle) = self.background_music_handle.take() {
             handle.stop(Tween::default());
+            self.log_event(AudioEventKind::Stop, "music", String::new);
         }
 
         // Create settings for background music with low volume and looping
         let settings = StaticSoundSettings::new()
-            .volume(Decibels::from(-20.0)) // Low volume (-20dB) to not overpower other sounds
+            .volume(Decibels::from(BACKGROUND_MUSIC_DB)) // Low volume to not overpower other sounds
             .loop_region(..); // Loop the entire track indefinitely
-
-        // Play the background music with configured settings
-        let handle = self
-            .audio_manager
-            .play(self.background_music_data.clone().with_settings(settings))?;
-        self.background_music_handle = Some(handle);
+        self.music_volume = BACKGROUND_MUSIC_DB;
+
+        // Play the background music on its own track so it can be ducked
+        let music_data = self.background_music_data.clone().with_settings(settings);
+        self.background_music_handle = self
+            .output
+            .as_mut()
+            .map(|output| output.music_track.play(music_data))
+            .transpose()?;
+        self.log_event(AudioEventKind::Play, "music", || {
+            format!("{:+.1} dB", BACKGROUND_MUSIC_DB)
+        });
 
         Ok(())
     }
@@ -271,6 +964,7 @@ impl GameAudioManager {
     /// On the title screen:
     /// - Background music is made louder (-5dB) to be more prominent
     /// - Enemy sounds are made quieter (-10dB) to be less intrusive
+    /// - The level's ambience stops
     ///
     /// Volume changes are applied with smooth 500ms transitions to avoid
     /// jarring audio changes.
@@ -288,6 +982,10 @@ impl GameAudioManager {
             };
             handle.set_volume(Decibels::from(-5.0), tween);
         }
+        self.music_volume = -5.0;
+
+        // The title screen has no ambience
+        self.stop_ambience();
 
         // Reduce enemy sound volume on title screen for better focus
         let enemy_ids: Vec<String> = self.enemy_sounds.keys().cloned().collect();
@@ -301,6 +999,7 @@ impl GameAudioManager {
                 track.set_volume(Decibels::from(-10.0), tween);
             }
         }
+        self.enemy_track_db = -10.0;
 
         Ok(())
     }
@@ -310,6 +1009,7 @@ impl GameAudioManager {
     /// When the pause menu is open:
     /// - Background music is made much softer (-15dB) to indicate paused state
     /// - Quick 100ms transition provides immediate audio feedback
+    /// - The level's ambience pauses
     ///
     /// # Returns
     ///
@@ -324,6 +1024,12 @@ impl GameAudioManager {
             };
             handle.set_volume(Decibels::from(-15.0), tween);
         }
+        self.music_volume = -15.0;
+
+        // The level's ambience holds still while paused
+        if let Some(output) = &mut self.output {
+            output.ambience_track.pause(linear_tween(AMBIENCE_TWEEN));
+        }
 
         Ok(())
     }
@@ -333,6 +1039,7 @@ impl GameAudioManager {
     /// This method restores:
     /// - Background music to normal volume (-10dB)
     /// - Enemy sounds to full volume (0dB)
+    /// - The level's ambience, if it was paused
     ///
     /// Used when transitioning from title screen or pause menu back to gameplay.
     /// Volume changes are applied with smooth 500ms transitions.
@@ -350,6 +1057,11 @@ impl GameAudioManager {
             };
             handle.set_volume(Decibels::from(-10.0), tween);
         }
+        self.music_volume = -10.0;
+
+        if let Some(output) = &mut self.output {
+            output.ambience_track.resume(linear_tween(AMBIENCE_TWEEN));
+        }
 
         // Reset enemy sounds to full volume for gameplay
         let enemy_ids: Vec<String> = self.enemy_sounds.keys().cloned().collect();
@@ -363,7 +1075,156 @@ impl GameAudioManager {
                 track.set_volume(Decibels::from(0.0), tween); // Full volume
             }
         }
+        self.enemy_track_db = 0.0;
+
+        Ok(())
+    }
+
+    /// Starts a level's ambience, replacing the last level's.
+    ///
+    /// Every bed layer of the profile starts looping on the ambience bus, which is silent
+    /// until [`set_ambience_level`](Self::set_ambience_level) fades it in.
+    ///
+    /// # Arguments
+    ///
+    /// * `profile` - The soundscape of the level's theme
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(())` on success, or an error if audio playback fails.
+    pub fn start_ambience(&mut self, profile: &SoundscapeProfile) -> Result<(), Box<dyn Error>> {
+        self.stop_ambience();
+        self.ambience_level = 0.0;
+        self.applied_ambience_gain = 0.0;
+        let Some(output) = &mut self.output else {
+            return Ok(());
+        };
+
+        output
+            .ambience_track
+            .set_volume(Decibels::SILENCE, Tween::default());
+        output.ambience_track.resume(Tween::default());
+        for bed in profile.beds {
+            let data = self.bed_data[bed.layer as usize]
+                .clone()
+                .loop_region(..)
+                .volume(Decibels(bed.volume_db));
+            self.bed_sounds
+                .push((*bed, output.ambience_track.play(data)?));
+        }
+        if self.debug.is_some() {
+            for bed in profile.beds {
+                self.log_event(AudioEventKind::Play, bed_id(bed.layer), || {
+                    format!("{:+.1} dB", bed.volume_db)
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Stops the ambience beds, for leaving a level for the title screen.
+    pub fn stop_ambience(&mut self) {
+        if self.bed_sounds.is_empty() {
+            return;
+        }
+        for (_, mut sound) in self.bed_sounds.drain(..) {
+            sound.stop(linear_tween(AMBIENCE_TWEEN));
+        }
+        self.log_event(AudioEventKind::Stop, "ambience", String::new);
+    }
 
+    /// Sets how far the level's ambience has faded in, see [`crate::game::soundscape`].
+    ///
+    /// # Arguments
+    ///
+    /// * `level` - Fade of the ambience, from 0.0 (silent) to 1.0
+    pub fn set_ambience_level(&mut self, level: f32) {
+        self.ambience_level = level.clamp(0.0, 1.0);
+        self.apply_ambience_gain();
+    }
+
+    /// Mutes or unmutes the background music.
+    ///
+    /// The music keeps playing underneath, so screen changes still set its volume and it
+    /// comes back where it would have been.
+    ///
+    /// # Arguments
+    ///
+    /// * `muted` - Whether the music should be silent
+    pub fn set_music_muted(&mut self, muted: bool) {
+        if muted == self.music_muted {
+            return;
+        }
+        self.music_muted = muted;
+        let volume = if muted {
+            Decibels::SILENCE
+        } else {
+            Decibels(self.applied_duck_db)
+        };
+        if let Some(output) = &mut self.output {
+            output.music_track.set_volume(volume, Tween::default());
+        }
+    }
+
+    /// Returns whether the player muted the music.
+    pub fn is_music_muted(&self) -> bool {
+        self.music_muted
+    }
+
+    /// Sets the player's ambience volume.
+    ///
+    /// # Arguments
+    ///
+    /// * `volume` - Volume from 0.0 (off) to 1.0 (full)
+    pub fn set_ambience_volume(&mut self, volume: f32) {
+        self.ambience_volume = volume.clamp(0.0, 1.0);
+        self.apply_ambience_gain();
+    }
+
+    /// Sends the ambience fade and volume to the ambience bus, if they have changed.
+    fn apply_ambience_gain(&mut self) {
+        let gain = self.ambience_level * self.ambience_volume;
+        if (gain - self.applied_ambience_gain).abs() < AMBIENCE_GAIN_EPSILON {
+            return;
+        }
+        if let Some(output) = &mut self.output {
+            output
+                .ambience_track
+                .set_volume(gain_to_db(gain), linear_tween(AMBIENCE_TWEEN));
+        }
+        self.applied_ambience_gain = gain;
+    }
+
+    /// Plays one ambience accent on the ambience bus.
+    ///
+    /// # Arguments
+    ///
+    /// * `shot` - The accent and how it should sound
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(())` on success, or an error if audio playback fails.
+    pub fn play_ambient_accent(&mut self, shot: &AccentShot) -> Result<(), Box<dyn Error>> {
+        let Some(output) = &mut self.output else {
+            return Ok(());
+        };
+        let data = self.accent_data[shot.accent as usize]
+            .clone()
+            .volume(Decibels(shot.volume_db))
+            .panning(Panning(shot.panning))
+            .playback_rate(shot.playback_rate);
+        let length = one_shot_length(&data);
+        output.ambience_track.play(data)?;
+        if let Some(debug) = &mut self.debug {
+            debug.play_one_shot(
+                Instant::now(),
+                "accent",
+                AudioChannel::Ambience,
+                shot.volume_db + gain_to_db(self.applied_ambience_gain).0,
+                None,
+                length,
+            );
+        }
         Ok(())
     }
 
@@ -428,9 +1289,15 @@ impl GameAudioManager {
             // Stop current footstep sound if any is playing
             if let Some(mut handle) = self.footstep_sound.take() {
                 handle.stop(Tween::default());
+                self.log_event(AudioEventKind::Stop, "footsteps", String::new);
             }
 
             self.movement_state = new_state;
+            if new_state != MovementState::Idle {
+                self.log_event(AudioEventKind::Play, "footsteps", || {
+                    format!("{:?}", new_state)
+                });
+            }
 
             // Start new footstep sound based on the new state
             match new_state {
@@ -438,14 +1305,16 @@ impl GameAudioManager {
                     // No footstep sound for idle state
                 }
                 MovementState::Walking => {
-                    let mut sound_handle = self.audio_manager.play(self.footstep_data.clone())?;
-                    sound_handle.set_loop_region(0.0..0.5); // Normal walking speed
-                    self.footstep_sound = Some(sound_handle);
+                    self.footstep_sound = self.play(self.footstep_data.clone())?;
+                    if let Some(sound_handle) = &mut self.footstep_sound {
+                        sound_handle.set_loop_region(0.0..0.5); // Normal walking speed
+                    }
                 }
                 MovementState::Sprinting => {
-                    let mut sound_handle = self.audio_manager.play(self.footstep_data.clone())?;
-                    sound_handle.set_loop_region(0.0..0.25); // Faster loop for sprinting urgency
-                    self.footstep_sound = Some(sound_handle);
+                    self.footstep_sound = self.play(self.footstep_data.clone())?;
+                    if let Some(sound_handle) = &mut self.footstep_sound {
+                        sound_handle.set_loop_region(0.0..0.25); // Faster loop for sprinting urgency
+                    }
                 }
             }
         }
@@ -483,22 +1352,138 @@ impl GameAudioManager {
     ///
     /// # Example
     ///
-    /// ```rust
-    /// // Update listener to player position
-    /// audio_manager.set_listener_position([player.x, player.y, player.z])?;
-    /// ```
-    pub fn set_listener_position(&mut self, position: [f32; 3]) -> Result<(), Box<dyn Error>> {
-        let tween = Tween {
-            start_time: StartTime::Immediate,
-            duration: Duration::from_millis(100), // Smooth position interpolation
-            easing: Easing::Linear,
+    /// ```rust,ignore
+    /// // Update listener to player position
+    /// audio_manager.set_listener_position([player.x, player.y, player.z])?;
+    /// ```
+    pub fn set_listener_position(&mut self, position: [f32; 3]) -> Result<(), Box<dyn Error>> {
+        let tween = Tween {
+            start_time: StartTime::Immediate,
+            duration: Duration::from_millis(100), // Smooth position interpolation
+            easing: Easing::Linear,
+        };
+
+        // Update listener position - all spatial tracks automatically update
+        // their distance-based effects since they reference this listener
+        if let Some(output) = &mut self.output {
+            output.listener.set_position(position, tween);
+        }
+        self.listener_position = position;
+
+        Ok(())
+    }
+
+    /// Turns the audio listener to face where the player faces.
+    ///
+    /// Without it, sounds would pan by their direction in the world rather than their
+    /// direction from the player.
+    ///
+    /// # Arguments
+    ///
+    /// * `yaw` - The player's yaw in degrees
+    pub fn set_listener_yaw(&mut self, yaw: f32) {
+        if let Some(output) = &mut self.output {
+            output
+                .listener
+                .set_orientation(listener_orientation(yaw), linear_tween(ENEMY_AUDIO_TWEEN));
+        }
+        self.listener_yaw = yaw;
+    }
+
+    /// Returns whether sounds can be rendered through HRTF on this device.
+    ///
+    /// Always `false` on a silent manager, which has nothing to render.
+    pub fn supports_hrtf(&self) -> bool {
+        self.output.is_some() && BACKEND_HAS_HRTF
+    }
+
+    /// Changes how positioned sounds are mixed for the player's output device.
+    ///
+    /// Applied to every spatial source already playing as well as ones started later, so
+    /// the change can be heard straight away.
+    ///
+    /// # Arguments
+    ///
+    /// * `profile` - The device the player listens through
+    /// * `hrtf` - Whether the HRTF setting is on; ignored when it isn't supported
+    pub fn set_spatial_profile(&mut self, profile: OutputProfile, hrtf: bool) {
+        let mix = SpatialMix::for_profile(profile, hrtf && self.supports_hrtf());
+        if mix == self.spatial_mix {
+            return;
+        }
+        self.spatial_mix = mix;
+
+        let tween = linear_tween(ENEMY_AUDIO_TWEEN);
+        let strength = mix.spatialization_strength;
+        let tracks = self
+            .spatial_tracks
+            .values_mut()
+            .chain(self.echo_track.as_mut())
+            .chain(self.spawn_drone.as_mut().map(|drone| &mut drone.track))
+            .chain(self.audio_test.as_mut().map(|test| &mut test.track));
+        for track in tracks {
+            track.set_spatialization_strength(strength, tween);
+        }
+        // Rear attenuation is reapplied with each enemy's next position update
+        if let Some(output) = &self.output {
+            output.downmix.set_enabled(mix.downmix_to_mono);
+        }
+    }
+
+    /// Plays the enemy's sound circling the listener, so the player can hear how the
+    /// current output profile places sounds.
+    ///
+    /// Runs for [`AUDIO_TEST_DURATION`], moved by [`GameAudioManager::update`]; starting
+    /// it again restarts it.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(())` on success, or an error if audio playback fails.
+    pub fn start_audio_test(&mut self) -> Result<(), Box<dyn Error>> {
+        self.stop_audio_test();
+        let position =
+            audio_test_position(self.listener_position, self.listener_yaw, Duration::ZERO);
+        let Some(mut track) = self.add_enemy_track(position)? else {
+            return Ok(());
         };
+        let sound = track.play(self.enemy_data.clone().loop_region(..))?;
+        self.audio_test = Some(AudioTest {
+            track,
+            sound,
+            started: Instant::now(),
+        });
+        self.log_event(AudioEventKind::Play, "audio_test", String::new);
+        Ok(())
+    }
 
-        // Update listener position - all spatial tracks automatically update
-        // their distance-based effects since they reference this listener
-        self.listener.set_position(position, tween);
+    /// Returns whether the audio test is playing.
+    pub fn is_audio_test_playing(&self) -> bool {
+        self.audio_test.is_some()
+    }
 
-        Ok(())
+    /// Moves the audio test's sound along its orbit, stopping it once it is done.
+    fn update_audio_test(&mut self, now: Instant) {
+        let Some(test) = &mut self.audio_test else {
+            return;
+        };
+        let elapsed = now.saturating_duration_since(test.started);
+        if elapsed >= AUDIO_TEST_DURATION {
+            self.stop_audio_test();
+            return;
+        }
+        let position = audio_test_position(self.listener_position, self.listener_yaw, elapsed);
+        test.track
+            .set_position(position, linear_tween(ENEMY_AUDIO_TWEEN));
+    }
+
+    /// Fades out the audio test if it is playing.
+    fn stop_audio_test(&mut self) {
+        if let Some(mut test) = self.audio_test.take() {
+            let tween = linear_tween(ENEMY_AUDIO_TWEEN);
+            test.sound.stop(tween);
+            test.track.pause(tween);
+            self.log_event(AudioEventKind::Stop, "audio_test", String::new);
+        }
     }
 
     /// Spawns a new enemy with spatial audio at the specified position.
@@ -527,7 +1512,7 @@ impl GameAudioManager {
     ///
     /// # Example
     ///
-    /// ```rust
+    /// ```rust,ignore
     /// // Spawn enemy at position (10, 0, 5)
     /// audio_manager.spawn_enemy("goblin_1".to_string(), [10.0, 0.0, 5.0])?;
     /// ```
@@ -537,32 +1522,27 @@ impl GameAudioManager {
         position: [f32; 3],
     ) -> Result<(), Box<dyn Error>> {
         // Create spatial track with comprehensive 3D audio setup
-        let mut spatial_track = self.audio_manager.add_spatial_sub_track(
-            &self.listener,
-            position,
-            SpatialTrackBuilder::new()
-                .spatialization_strength(1.0) // Full 3D effect strength
-                .distances(SpatialTrackDistances {
-                    min_distance: 1.0,    // Minimum distance for audio calculations
-                    max_distance: 3200.0, // Maximum audible distance
-                })
-                // Add reverb for environmental realism
-                .with_effect(ReverbBuilder::new().mix(Value::Fixed(0.3.into())))
-                // Volume control based on distance from listener
-                .with_effect(VolumeControlBuilder::new(Value::FromListenerDistance(
-                    Mapping {
-                        input_range: (5.0, 3200.0), // Distance range in world units
-                        output_range: ((20.0).into(), (-50.0).into()), // Volume range in dB
-                        easing: Easing::OutPowi(3), // Non-linear falloff for realism
-                    },
-                ))),
-        )?;
+        let Some(mut spatial_track) = self.add_enemy_track(position)? else {
+            return Ok(());
+        };
 
         // Start playing the looping enemy audio on the spatial track
         let sound_handle = spatial_track.play(self.enemy_data.clone().loop_region(..))?;
 
         // Register the enemy for future updates and management
         self.spatial_tracks.insert(enemy_id.clone(), spatial_track);
+        self.enemy_lods
+            .insert(enemy_id.clone(), EnemyAudioLod::default());
+        self.enemy_propagation
+            .insert(enemy_id.clone(), EnemyPropagation::default());
+        if self.debug.is_some() {
+            self.log_event(AudioEventKind::Play, enemy_id.clone(), || {
+                format!(
+                    "at ({:.1}, {:.1}, {:.1})",
+                    position[0], position[1], position[2]
+                )
+            });
+        }
         self.enemy_sounds.insert(enemy_id, sound_handle);
         Ok(())
     }
@@ -575,6 +1555,12 @@ impl GameAudioManager {
     /// - 3D panning and positioning
     /// - Reverb characteristics
     ///
+    /// Safe to call every frame: how often the position actually reaches the
+    /// audio backend depends on the enemy's distance from the listener, see
+    /// [`EnemyAudioLod`]. Enemies out of earshot are paused until they return.
+    /// With enhanced audio on, the enemy is heard where it was when the sound
+    /// left it and its pitch follows the doppler shift, see [`apply_propagation`].
+    ///
     /// # Arguments
     ///
     /// * `enemy_id` - The unique identifier of the enemy to update
@@ -587,7 +1573,7 @@ impl GameAudioManager {
     ///
     /// # Example
     ///
-    /// ```rust
+    /// ```rust,ignore
     /// // Update enemy position as it moves
     /// audio_manager.update_enemy_position("goblin_1", [12.0, 0.0, 3.0])?;
     /// ```
@@ -596,17 +1582,76 @@ impl GameAudioManager {
         enemy_id: &str,
         position: [f32; 3],
     ) -> Result<(), Box<dyn Error>> {
-        if let Some(track) = self.spatial_tracks.get_mut(enemy_id) {
-            let tween = Tween {
-                start_time: StartTime::Immediate,
-                duration: Duration::from_millis(100), // Smooth position transition
-                easing: Easing::Linear,
-            };
-            track.set_position(position, tween);
+        let (Some(track), Some(lod)) = (
+            self.spatial_tracks.get_mut(enemy_id),
+            self.enemy_lods.get_mut(enemy_id),
+        ) else {
+            return Ok(());
+        };
+        let now = Instant::now();
+
+        let heard_at = match (
+            self.enhanced_audio,
+            self.enemy_propagation.get_mut(enemy_id),
+            self.enemy_sounds.get_mut(enemy_id),
+        ) {
+            (true, Some(propagation), Some(sound)) => {
+                apply_propagation(propagation, sound, position, self.listener_position, now)
+            }
+            _ => position,
+        };
+        let band_before = lod.band();
+        let band = lod.update(track, heard_at, self.listener_position, now);
+        if band != band_before
+            && let Some(debug) = &mut self.debug
+        {
+            debug.record(
+                now,
+                AudioEventKind::Occlusion,
+                enemy_id.to_string(),
+                format!("{:?} -> {:?}", band_before, band),
+            );
+        }
+
+        let rear_db = rear_gain_db(
+            &self.spatial_mix,
+            self.listener_position,
+            self.listener_yaw,
+            heard_at,
+        );
+        let applied_db = self
+            .enemy_rear_gains
+            .entry(enemy_id.to_string())
+            .or_default();
+        if (rear_db - *applied_db).abs() > REAR_GAIN_EPSILON_DB
+            && let Some(sound) = self.enemy_sounds.get_mut(enemy_id)
+        {
+            sound.set_volume(Decibels(rear_db), linear_tween(ENEMY_AUDIO_TWEEN));
+            *applied_db = rear_db;
         }
         Ok(())
     }
 
+    /// Turns propagation delay and doppler for enemies on or off.
+    ///
+    /// Turning them off returns every enemy to its normal pitch and forgets
+    /// where it has been, so turning them back on starts fresh.
+    ///
+    /// # Arguments
+    ///
+    /// * `enabled` - Whether the enhanced audio setting is on
+    pub fn set_enhanced_audio(&mut self, enabled: bool) {
+        if self.enhanced_audio && !enabled {
+            for sound in self.enemy_sounds.values_mut() {
+                sound.set_pitch(1.0, ENEMY_AUDIO_TWEEN);
+            }
+            for propagation in self.enemy_propagation.values_mut() {
+                *propagation = EnemyPropagation::default();
+            }
+        }
+        self.enhanced_audio = enabled;
+    }
+
     /// Removes an enemy and stops all associated audio.
     ///
     /// This method completely cleans up an enemy's audio:
@@ -627,7 +1672,7 @@ impl GameAudioManager {
     ///
     /// # Example
     ///
-    /// ```rust
+    /// ```rust,ignore
     /// // Remove enemy when it dies or despawns
     /// audio_manager.remove_enemy("goblin_1")?;
     /// ```
@@ -647,6 +1692,12 @@ impl GameAudioManager {
         if let Some(mut track) = self.spatial_tracks.remove(enemy_id) {
             track.pause(tween);
         }
+        self.enemy_lods.remove(enemy_id);
+        self.enemy_propagation.remove(enemy_id);
+        self.enemy_rear_gains.remove(enemy_id);
+        if self.debug.is_some() {
+            self.log_event(AudioEventKind::Stop, enemy_id.to_string(), String::new);
+        }
         Ok(())
     }
 
@@ -667,7 +1718,7 @@ impl GameAudioManager {
     ///
     /// # Example
     ///
-    /// ```rust
+    /// ```rust,ignore
     /// // Play sound effect at half volume
     /// audio_manager.play_with_volume(sound_data, 0.5)?;
     /// ```
@@ -675,6 +1726,16 @@ impl GameAudioManager {
         &mut self,
         audio_data: StaticSoundData,
         volume: f32,
+    ) -> Result<(), Box<dyn Error>> {
+        self.play_named_with_volume("effect", audio_data, volume)
+    }
+
+    /// Plays a one-shot sound at a linear volume, under a name for the audio debug overlay.
+    fn play_named_with_volume(
+        &mut self,
+        name: &'static str,
+        audio_data: StaticSoundData,
+        volume: f32,
     ) -> Result<(), Box<dyn Error>> {
         // Convert linear volume (0.0-1.0) to decibels for audio processing
         let volume_db = if volume <= 0.0 {
@@ -687,8 +1748,7 @@ impl GameAudioManager {
         let settings = StaticSoundSettings::new().volume(volume_db);
 
         // Play the sound with volume setting (one-shot, no looping)
-        self.audio_manager
-            .play(audio_data.with_settings(settings))?;
+        self.play_effect(name, audio_data.with_settings(settings))?;
 
         Ok(())
     }
@@ -696,19 +1756,184 @@ impl GameAudioManager {
     /// Updates the audio manager state.
     ///
     /// This method is called each frame to perform any necessary audio processing.
-    /// Currently, most audio calculations (spatial positioning, distance attenuation)
-    /// are handled automatically by the Kira library, so this method is mostly
-    /// for future extensibility.
+    /// Most audio calculations (spatial positioning, distance attenuation) are handled
+    /// automatically by the Kira library; this advances the duck envelope and applies it
+    /// to the music track. The track is only touched when the duck changes.
     ///
     /// # Returns
     ///
     /// Returns `Ok(())` on success, or an error if audio processing fails.
     pub fn update(&mut self) -> Result<(), Box<dyn Error>> {
-        // Update method for any necessary audio processing
-        // Spatial calculations are handled automatically by kira
+        let now = Instant::now();
+        let delta_time = self
+            .last_update
+            .map_or(0.0, |last| now.duration_since(last).as_secs_f32());
+        self.last_update = Some(now);
+        self.update_audio_test(now);
+        if let Some(debug) = &mut self.debug {
+            debug.prune(now);
+        }
+
+        let duck_db = self.ducker.advance(delta_time, self.music_volume);
+        if (duck_db - self.applied_duck_db).abs() > DUCK_EPSILON_DB {
+            // A muted track stays silent; the duck is still followed for when it is unmuted
+            let muted = self.music_muted;
+            if let Some(output) = self.output.as_mut().filter(|_| !muted) {
+                // Glide over the frame so the steps of the envelope don't click
+                let tween = Tween {
+                    start_time: StartTime::Immediate,
+                    duration: Duration::from_secs_f32(delta_time),
+                    easing: Easing::Linear,
+                };
+                output.music_track.set_volume(Decibels(duck_db), tween);
+            }
+            self.applied_duck_db = duck_db;
+        }
         Ok(())
     }
 
+    /// Turns introspection for the audio debug overlay on or off.
+    ///
+    /// Turning it on starts an empty event log; turning it off drops the log and stops
+    /// registering one-shot sounds, so the manager does no extra work.
+    ///
+    /// # Arguments
+    ///
+    /// * `enabled` - Whether the overlay is open
+    pub fn set_introspection(&mut self, enabled: bool) {
+        match (enabled, self.debug.is_some()) {
+            (true, false) => self.debug = Some(AudioDebugState::new(Instant::now())),
+            (false, true) => self.debug = None,
+            _ => {}
+        }
+    }
+
+    /// Returns whether introspection for the audio debug overlay is on.
+    pub fn is_introspecting(&self) -> bool {
+        self.debug.is_some()
+    }
+
+    /// Describes every source the manager is playing, for the audio debug overlay.
+    ///
+    /// Looping sources are always included. One-shot sounds and the event log are only
+    /// known while introspection is on.
+    ///
+    /// # Returns
+    ///
+    /// Each source's channel, effective volume, position and state, and the event log
+    pub fn debug_snapshot(&self) -> AudioDebugSnapshot {
+        let now = Instant::now();
+        let listener = self.listener_position;
+        let positioned = |position: [f32; 3]| {
+            let distance = audio_debug::distance(listener, position);
+            (Some(position), Some(distance), enemy_volume_db(distance))
+        };
+        let mut sources = Vec::new();
+
+        if let Some(music) = &self.background_music_handle {
+            sources.push(SourceSnapshot {
+                id: "music".to_string(),
+                channel: AudioChannel::Music,
+                volume_db: if self.music_muted {
+                    Decibels::SILENCE.0
+                } else {
+                    self.music_volume + self.applied_duck_db
+                },
+                position: None,
+                distance: None,
+                state: SourceState::from_playback(music.state()),
+            });
+        }
+        let ambience_db = gain_to_db(self.applied_ambience_gain).0;
+        for (bed, sound) in &self.bed_sounds {
+            sources.push(SourceSnapshot {
+                id: bed_id(bed.layer),
+                channel: AudioChannel::Ambience,
+                volume_db: bed.volume_db + ambience_db,
+                position: None,
+                distance: None,
+                state: SourceState::from_playback(sound.state()),
+            });
+        }
+        if let Some(footsteps) = &self.footstep_sound {
+            sources.push(SourceSnapshot {
+                id: "footsteps".to_string(),
+                channel: AudioChannel::Footsteps,
+                volume_db: 0.0,
+                position: None,
+                distance: None,
+                state: SourceState::from_playback(footsteps.state()),
+            });
+        }
+
+        let mut enemy_ids: Vec<&String> = self.enemy_sounds.keys().collect();
+        enemy_ids.sort();
+        for id in enemy_ids {
+            let lod = self.enemy_lods.get(id);
+            let (position, distance, falloff_db) = lod
+                .and_then(EnemyAudioLod::heard_at)
+                .map_or((None, None, ENEMY_NEAR_VOLUME_DB), positioned);
+            let rear_db = self.enemy_rear_gains.get(id).copied().unwrap_or(0.0);
+            let state = match lod.map(EnemyAudioLod::band) {
+                Some(AudioBand::Culled) => SourceState::Culled,
+                _ => SourceState::from_playback(self.enemy_sounds[id].state()),
+            };
+            sources.push(SourceSnapshot {
+                id: id.clone(),
+                channel: AudioChannel::Spatial,
+                volume_db: self.enemy_track_db + falloff_db + rear_db,
+                position,
+                distance,
+                state,
+            });
+        }
+        for drone in self.spawn_drone.iter().chain(&self.fading_spawn_drone) {
+            let (position, distance, falloff_db) = positioned(drone.position);
+            sources.push(SourceSnapshot {
+                id: "spawn_drone".to_string(),
+                channel: AudioChannel::Spatial,
+                volume_db: gain_to_db(drone.level).0 + SPAWN_DRONE_PEAK_DB + falloff_db,
+                position,
+                distance,
+                state: SourceState::from_playback(drone.sound.state()),
+            });
+        }
+        if let Some(test) = &self.audio_test {
+            let elapsed = now.saturating_duration_since(test.started);
+            let (position, distance, falloff_db) =
+                positioned(audio_test_position(listener, self.listener_yaw, elapsed));
+            sources.push(SourceSnapshot {
+                id: "audio_test".to_string(),
+                channel: AudioChannel::Spatial,
+                volume_db: falloff_db,
+                position,
+                distance,
+                state: SourceState::from_playback(test.sound.state()),
+            });
+        }
+
+        let (one_shots, events) = match &self.debug {
+            Some(debug) => (debug.one_shot_sources(now, listener), debug.events()),
+            None => (Vec::new(), Vec::new()),
+        };
+        sources.extend(one_shots);
+        AudioDebugSnapshot {
+            listener,
+            duck_db: self.applied_duck_db,
+            sources,
+            events,
+        }
+    }
+
+    /// Changes how far and how fast the music ducks under warning cues.
+    ///
+    /// # Arguments
+    ///
+    /// * `settings` - The new ducking settings; an `amount_db` of 0.0 turns ducking off
+    pub fn set_ducking(&mut self, settings: DuckSettings) {
+        self.ducker.settings = settings;
+    }
+
     /// Checks if the player is currently in walking state.
     ///
     /// # Returns
@@ -757,6 +1982,17 @@ impl GameAudioManager {
         self.spatial_tracks.len()
     }
 
+    /// Returns whether an enemy's audio has been spawned and not removed since.
+    ///
+    /// Always false on a silent manager, which never spawns any.
+    ///
+    /// # Arguments
+    ///
+    /// * `enemy_id` - The unique identifier of the enemy
+    pub fn has_enemy(&self, enemy_id: &str) -> bool {
+        self.spatial_tracks.contains_key(enemy_id)
+    }
+
     /// Temporarily pauses an enemy's audio.
     ///
     /// The audio can be resumed later with `resume_enemy_audio()`. This is
@@ -818,7 +2054,7 @@ impl GameAudioManager {
     ///
     /// Returns `Ok(())` on success, or an error if audio playback fails.
     pub fn complete(&mut self) -> Result<(), Box<dyn Error>> {
-        self.audio_manager.play(self.complete_data.clone())?;
+        self.play_effect("complete", self.complete_data.clone())?;
         Ok(())
     }
 
@@ -846,7 +2082,7 @@ impl GameAudioManager {
         }
 
         // Play sound at 1/3 volume (0.33)
-        self.play_with_volume(self.wall_hit_data.clone(), 0.0001)?;
+        self.play_named_with_volume("wall_hit", self.wall_hit_data.clone(), 0.0001)?;
         self.last_wall_hit = Some(now);
         Ok(())
     }
@@ -860,7 +2096,7 @@ impl GameAudioManager {
     ///
     /// Returns `Ok(())` on success, or an error if audio playback fails.
     pub fn play_select(&mut self) -> Result<(), Box<dyn Error>> {
-        self.audio_manager.play(self.select_data.clone())?;
+        self.play_effect("select", self.select_data.clone())?;
         Ok(())
     }
 
@@ -874,10 +2110,275 @@ impl GameAudioManager {
     ///
     /// Returns `Ok(())` on success, or an error if audio playback fails.
     pub fn play_upgrade(&mut self) -> Result<(), Box<dyn Error>> {
-        self.audio_manager.play(self.upgrade_data.clone())?;
+        self.play_effect("upgrade", self.upgrade_data.clone())?;
+        Ok(())
+    }
+
+    /// Plays the combo-lost sound effect.
+    ///
+    /// Reuses the selection sound, pitched down and played quietly, so losing a
+    /// movement combo is audible without being mistaken for a menu click.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(())` on success, or an error if audio playback fails.
+    pub fn play_combo_lost(&mut self) -> Result<(), Box<dyn Error>> {
+        self.play_effect(
+            "combo_lost",
+            self.select_data.playback_rate(0.6).volume(Decibels(-10.0)),
+        )?;
+        Ok(())
+    }
+
+    /// Plays the denied sound effect.
+    ///
+    /// Reuses the selection sound, pitched down further and played more quietly
+    /// than the combo-lost cue, as a soft signal that a click-to-move target
+    /// can't be walked to.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(())` on success, or an error if audio playback fails.
+    pub fn play_denied(&mut self) -> Result<(), Box<dyn Error>> {
+        self.play_effect(
+            "denied",
+            self.select_data.playback_rate(0.45).volume(Decibels(-16.0)),
+        )?;
+        self.duck(DuckCue::Denied);
+        Ok(())
+    }
+
+    /// Plays the sting for the enemy coming within danger range.
+    ///
+    /// Reuses the start of the enemy's sound, pitched up and played at full volume
+    /// without spatialization, so it reads as a warning rather than the enemy itself.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(())` on success, or an error if audio playback fails.
+    pub fn play_enemy_sting(&mut self) -> Result<(), Box<dyn Error>> {
+        self.play_effect(
+            "enemy_sting",
+            self.enemy_data.slice(0.0..0.5).playback_rate(1.4),
+        )?;
+        self.duck(DuckCue::EnemySting);
+        Ok(())
+    }
+
+    /// Plays the beep for the level timer turning critical.
+    ///
+    /// Reuses the start of the beeper-rise sound, sped up so it is short and
+    /// distinct from the exit cue.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(())` on success, or an error if audio playback fails.
+    pub fn play_timer_critical(&mut self) -> Result<(), Box<dyn Error>> {
+        self.play_effect(
+            "timer_critical",
+            self.beeper_rise_data
+                .slice(0.0..0.4)
+                .playback_rate(1.5)
+                .volume(Decibels(-4.0)),
+        )?;
+        self.duck(DuckCue::TimerCritical);
+        Ok(())
+    }
+
+    /// Plays the heavy thud of the entrance door landing.
+    ///
+    /// A wall hit pitched far down and boosted, so it sounds like a much
+    /// heavier slab than the player bumping into a wall.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(())` on success, or an error if audio playback fails.
+    pub fn play_door_thud(&mut self) -> Result<(), Box<dyn Error>> {
+        self.play_effect(
+            "door_thud",
+            self.wall_hit_data.playback_rate(0.35).volume(Decibels(4.0)),
+        )?;
+        Ok(())
+    }
+
+    /// Plays the sting for the enemy catching the player.
+    ///
+    /// Layers the start of the enemy's sound, slowed and boosted, over a
+    /// pitched-down wall hit so the capture lands as one loud jolt.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(())` on success, or an error if audio playback fails.
+    pub fn play_capture_sting(&mut self) -> Result<(), Box<dyn Error>> {
+        self.play_effect(
+            "capture_sting",
+            self.enemy_data
+                .slice(0.0..0.8)
+                .playback_rate(0.6)
+                .volume(Decibels(6.0)),
+        )?;
+        self.play_effect(
+            "capture_thud",
+            self.wall_hit_data.playback_rate(0.5).volume(Decibels(3.0)),
+        )?;
+        self.duck(DuckCue::CaptureSting);
+        Ok(())
+    }
+
+    /// Plays the cue for a level event starting.
+    ///
+    /// Reuses the beeper-rise sound, slowed and played quietly, so the cue draws
+    /// attention to the event's announcement without sounding like the exit.
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(())` on success, or an error if audio playback fails.
+    pub fn play_event_cue(&mut self) -> Result<(), Box<dyn Error>> {
+        self.play_effect(
+            "event_cue",
+            self.beeper_rise_data
+                .slice(0.0..0.6)
+                .playback_rate(0.5)
+                .volume(Decibels(-18.0)),
+        )?;
+        Ok(())
+    }
+
+    /// Plays the shimmer that hints at a hidden anomaly nearby.
+    ///
+    /// A short, high slice of the beeper-rise sound, kept soft so it reads as
+    /// something glinting in the dark rather than an alert.
+    ///
+    /// # Arguments
+    ///
+    /// * `volume` - Linear volume from 0.0 to 1.0, louder the closer the anomaly is
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(())` on success, or an error if audio playback fails.
+    pub fn play_anomaly_shimmer(&mut self, volume: f32) -> Result<(), Box<dyn Error>> {
+        let volume_db = 20.0 * volume.clamp(0.001, 1.0).log10() - 12.0;
+        self.play_effect(
+            "anomaly_shimmer",
+            self.beeper_rise_data
+                .slice(0.0..0.4)
+                .playback_rate(2.5)
+                .volume(Decibels(volume_db)),
+        )?;
+        Ok(())
+    }
+
+    /// Plays a few seconds of the enemy's sound from a direction where there is no enemy.
+    ///
+    /// The sound comes from [`full_rate_distance`] away, where a real enemy is faint
+    /// but still clearly placed, on the same kind of spatial track a real enemy uses.
+    ///
+    /// # Arguments
+    ///
+    /// * `bearing` - Direction from the listener in radians, measured from +x toward +z
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(())` on success, or an error if audio playback fails.
+    pub fn play_echo(&mut self, bearing: f32) -> Result<(), Box<dyn Error>> {
+        let distance = full_rate_distance();
+        let [x, y, z] = self.listener_position;
+        let position = [
+            x + bearing.cos() * distance,
+            y,
+            z + bearing.sin() * distance,
+        ];
+
+        let Some(mut track) = self.add_enemy_track(position)? else {
+            return Ok(());
+        };
+        track.play(self.enemy_data.slice(0.0..ECHO_LENGTH_SECS))?;
+        self.echo_track = Some(track);
+        if let Some(debug) = &mut self.debug {
+            debug.play_one_shot(
+                Instant::now(),
+                "echo",
+                AudioChannel::Spatial,
+                enemy_volume_db(distance),
+                Some(position),
+                Duration::from_secs_f64(ECHO_LENGTH_SECS),
+            );
+        }
+        Ok(())
+    }
+
+    /// Plays the drone telegraphing a delayed enemy spawn at the given level.
+    ///
+    /// The enemy's sound slowed to a low drone, looping on a spatial track at the spawn
+    /// cell so the player can tell where the enemy will appear. It grows louder and rises
+    /// in pitch with `level`; a level of 0.0 stops it.
+    ///
+    /// # Arguments
+    ///
+    /// * `position` - World position of the spawn cell
+    /// * `level` - How far the telegraph has built, from 0.0 to 1.0
+    ///
+    /// # Returns
+    ///
+    /// Returns `Ok(())` on success, or an error if audio playback fails.
+    pub fn set_spawn_drone(
+        &mut self,
+        position: [f32; 3],
+        level: f32,
+    ) -> Result<(), Box<dyn Error>> {
+        let level = level.clamp(0.0, 1.0);
+        if level <= 0.0 {
+            self.stop_spawn_drone();
+            return Ok(());
+        }
+        if self.spawn_drone.is_none() {
+            let Some(mut track) = self.add_enemy_track(position)? else {
+                return Ok(());
+            };
+            let sound = track.play(
+                self.enemy_data
+                    .clone()
+                    .loop_region(..)
+                    .playback_rate(SPAWN_DRONE_BASE_RATE)
+                    .volume(Decibels::SILENCE),
+            )?;
+            self.spawn_drone = Some(SpawnDrone {
+                track,
+                position,
+                sound,
+                level: 0.0,
+            });
+            self.log_event(AudioEventKind::Play, "spawn_drone", String::new);
+        }
+
+        let Some(drone) = &mut self.spawn_drone else {
+            return Ok(());
+        };
+        if (level - drone.level).abs() < SPAWN_DRONE_EPSILON {
+            return Ok(());
+        }
+        let tween = linear_tween(ENEMY_AUDIO_TWEEN);
+        let volume = gain_to_db(level).0 + SPAWN_DRONE_PEAK_DB;
+        drone.sound.set_volume(Decibels(volume), tween);
+        drone.sound.set_playback_rate(
+            SPAWN_DRONE_BASE_RATE + (SPAWN_DRONE_TOP_RATE - SPAWN_DRONE_BASE_RATE) * level as f64,
+            tween,
+        );
+        drone.level = level;
         Ok(())
     }
 
+    /// Fades out the spawn drone, for the enemy arriving or the spawn being called off.
+    pub fn stop_spawn_drone(&mut self) {
+        if let Some(mut drone) = self.spawn_drone.take() {
+            let tween = linear_tween(ENEMY_AUDIO_TWEEN);
+            drone.sound.stop(tween);
+            drone.track.pause(tween);
+            self.fading_spawn_drone = Some(drone);
+            self.log_event(AudioEventKind::Stop, "spawn_drone", String::new);
+        }
+    }
+
     /// Plays the beeper-rise sound effect.
     ///
     /// This method plays a one-shot beeper-rise sound at full volume.
@@ -887,7 +2388,201 @@ impl GameAudioManager {
     ///
     /// Returns `Ok(())` on success, or an error if audio playback fails.
     pub fn play_beeper_rise(&mut self) -> Result<(), Box<dyn Error>> {
-        self.audio_manager.play(self.beeper_rise_data.clone())?;
+        self.play_effect("beeper_rise", self.beeper_rise_data.clone())?;
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Records every call the LOD makes instead of playing anything.
+    #[derive(Debug, Default)]
+    struct MockSource {
+        calls: Vec<Call>,
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    enum Call {
+        SetPosition([f32; 3], Duration),
+        Pause,
+        Resume,
+    }
+
+    impl SpatialAudioSource for MockSource {
+        fn set_position(&mut self, position: [f32; 3], glide: Duration) {
+            self.calls.push(Call::SetPosition(position, glide));
+        }
+
+        fn pause(&mut self, _fade: Duration) {
+            self.calls.push(Call::Pause);
+        }
+
+        fn resume(&mut self, _fade: Duration) {
+            self.calls.push(Call::Resume);
+        }
+    }
+
+    /// Records pitch changes, optionally pretending the backend can't make them.
+    #[derive(Debug, Default)]
+    struct MockSound {
+        pitch_control: bool,
+        pitches: Vec<f64>,
+    }
+
+    impl PitchControl for MockSound {
+        fn supports_pitch(&self) -> bool {
+            self.pitch_control
+        }
+
+        fn set_pitch(&mut self, pitch: f64, _glide: Duration) {
+            self.pitches.push(pitch);
+        }
+    }
+
+    fn at(distance: f32) -> [f32; 3] {
+        [distance, 0.0, 0.0]
+    }
+
+    #[test]
+    fn test_bands_follow_the_falloff_settings() {
+        let full = full_rate_distance();
+        assert!((enemy_volume_db(full) - FULL_RATE_VOLUME_DB).abs() < 1e-3);
+        assert!(full > ENEMY_FALLOFF_START && full < ENEMY_MAX_AUDIBLE_DISTANCE);
+
+        let band = |distance, current| AudioBand::for_distance(distance, current);
+        assert_eq!(band(full * 0.5, AudioBand::Reduced), AudioBand::Full);
+        assert_eq!(band(full * 1.5, AudioBand::Full), AudioBand::Reduced);
+        assert_eq!(
+            band(ENEMY_MAX_AUDIBLE_DISTANCE * 1.5, AudioBand::Full),
+            AudioBand::Culled
+        );
+
+        // Hovering just past an edge keeps the nearer band, but coming back is immediate
+        assert_eq!(band(full * 1.02, AudioBand::Full), AudioBand::Full);
+        assert_eq!(band(full * 1.02, AudioBand::Reduced), AudioBand::Reduced);
+        let edge = ENEMY_MAX_AUDIBLE_DISTANCE * 1.02;
+        assert_eq!(band(edge, AudioBand::Reduced), AudioBand::Reduced);
+        assert_eq!(band(edge, AudioBand::Culled), AudioBand::Culled);
+    }
+
+    #[test]
+    fn test_update_rate_drops_with_distance() {
+        let mut lod = EnemyAudioLod::default();
+        let mut source = MockSource::default();
+        let start = Instant::now();
+        let frame = Duration::from_millis(16);
+        let listener = [0.0; 3];
+
+        // Close by, every frame is sent
+        for i in 0..5 {
+            lod.update(&mut source, at(10.0), listener, start + frame * i);
+        }
+        assert_eq!(source.calls.len(), 5);
+
+        // In the middle band, 30 frames (~0.5 s) only send about 10 Hz worth
+        source.calls.clear();
+        let far = full_rate_distance() * 2.0;
+        for i in 5..35 {
+            let band = lod.update(&mut source, at(far), listener, start + frame * i);
+            assert_eq!(band, AudioBand::Reduced);
+        }
+        assert!((4..=6).contains(&source.calls.len()), "{:?}", source.calls);
+        assert!(
+            source
+                .calls
+                .iter()
+                .all(|call| matches!(call, Call::SetPosition(_, REDUCED_UPDATE_INTERVAL)))
+        );
+
+        // Out of earshot, the source is paused once and then left alone
+        source.calls.clear();
+        let gone = ENEMY_MAX_AUDIBLE_DISTANCE * 2.0;
+        for i in 35..45 {
+            lod.update(&mut source, at(gone), listener, start + frame * i);
+        }
+        assert_eq!(source.calls, vec![Call::Pause]);
+        assert_eq!(lod.band(), AudioBand::Culled);
+    }
+
+    #[test]
+    fn test_resuming_snaps_before_fading_in() {
+        let mut lod = EnemyAudioLod::default();
+        let mut source = MockSource::default();
+        let start = Instant::now();
+        let listener = [0.0; 3];
+
+        lod.update(
+            &mut source,
+            at(ENEMY_MAX_AUDIBLE_DISTANCE * 2.0),
+            listener,
+            start,
+        );
+        source.calls.clear();
+
+        // Snapped straight to the new position while still paused, then faded back in
+        let back = at(20.0);
+        let band = lod.update(&mut source, back, listener, start + REDUCED_UPDATE_INTERVAL);
+        assert_eq!(band, AudioBand::Full);
+        assert_eq!(
+            source.calls,
+            vec![Call::SetPosition(back, Duration::ZERO), Call::Resume]
+        );
+
+        // After that the position glides again
+        source.calls.clear();
+        lod.update(
+            &mut source,
+            at(25.0),
+            listener,
+            start + REDUCED_UPDATE_INTERVAL * 2,
+        );
+        assert_eq!(
+            source.calls,
+            vec![Call::SetPosition(at(25.0), ENEMY_AUDIO_TWEEN)]
+        );
+    }
+    #[test]
+    fn test_propagation_needs_pitch_control() {
+        let start = Instant::now();
+        let frame = Duration::from_millis(16);
+        let listener = [0.0; 3];
+
+        // Without pitch control the enemy is heard exactly where it is
+        let mut propagation = EnemyPropagation::default();
+        let mut fixed = MockSound::default();
+        for i in 0..30 {
+            let position = at(2000.0 - 20.0 * i as f32);
+            let heard_at = apply_propagation(
+                &mut propagation,
+                &mut fixed,
+                position,
+                listener,
+                start + frame * i,
+            );
+            assert_eq!(heard_at, position);
+        }
+        assert!(fixed.pitches.is_empty());
+
+        // With it, the enemy lags behind and pitches up as it approaches
+        let mut propagation = EnemyPropagation::default();
+        let mut pitched = MockSound {
+            pitch_control: true,
+            ..Default::default()
+        };
+        let mut heard_at = listener;
+        for i in 0..30 {
+            heard_at = apply_propagation(
+                &mut propagation,
+                &mut pitched,
+                at(2000.0 - 20.0 * i as f32),
+                listener,
+                start + frame * i,
+            );
+        }
+        assert!(heard_at[0] > 2000.0 - 20.0 * 29.0);
+        assert_eq!(pitched.pitches.len(), 30);
+        assert!(pitched.pitches.last().is_some_and(|&pitch| pitch > 1.0));
+    }
+}
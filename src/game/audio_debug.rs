@@ -0,0 +1,465 @@
+//! Introspection of what the audio manager is playing, for the audio debug overlay.
+//!
+//! While introspection is on, [`GameAudioManager`](crate::game::audio::GameAudioManager) keeps
+//! an [`AudioDebugState`]: a rolling log of the last [`AUDIO_EVENT_LOG_LEN`] plays, stops,
+//! ducks and occlusion changes, and a registry of the one-shot sounds it started, which it
+//! can't otherwise see once they are playing. An [`AudioDebugSnapshot`] combines them with
+//! every looping source the manager holds: each source's channel, effective volume after the
+//! channel volume, ducking, rear attenuation and distance falloff, its position and distance
+//! from the listener, and whether it is playing.
+//!
+//! With introspection off nothing is logged or registered, so the manager does no extra work.
+//! Snapshots are built on request, by the overlay while it is open or by the key that dumps
+//! one to the session journal.
+//!
+//! # Usage
+//!
+//! ```rust,ignore
+//! audio_manager.set_introspection(true);
+//! audio_manager.play_select()?;
+//!
+//! let snapshot = audio_manager.debug_snapshot();
+//! println!("{}", snapshot);
+//! journal.record(JournalEvent::AudioSnapshot { snapshot });
+//! ```
+
+use kira::sound::PlaybackState;
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::collections::VecDeque;
+use std::fmt;
+use std::time::{Duration, Instant};
+
+/// Number of audio events kept in the rolling log; the oldest are dropped first.
+pub const AUDIO_EVENT_LOG_LEN: usize = 20;
+
+/// The bus or group a source plays on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AudioChannel {
+    /// The background music, ducked under warning cues
+    Music,
+    /// The level's ambience beds and accents
+    Ambience,
+    /// The player's footsteps
+    Footsteps,
+    /// One-shot sound effects and cues
+    Effects,
+    /// Positioned sounds on spatial tracks: the enemy, its echo and spawn drone, the audio test
+    Spatial,
+}
+
+impl AudioChannel {
+    /// Returns the short name shown in the overlay.
+    pub fn label(self) -> &'static str {
+        match self {
+            AudioChannel::Music => "music",
+            AudioChannel::Ambience => "ambience",
+            AudioChannel::Footsteps => "steps",
+            AudioChannel::Effects => "sfx",
+            AudioChannel::Spatial => "spatial",
+        }
+    }
+}
+
+/// Whether a source is being heard.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SourceState {
+    /// Playing, or fading back in
+    Playing,
+    /// Paused, or fading out to a pause
+    Paused,
+    /// Paused by the enemy audio LOD for being out of earshot
+    Culled,
+    /// Fading out before it stops
+    Stopping,
+    /// Finished or stopped, and about to be forgotten
+    Stopped,
+}
+
+impl SourceState {
+    /// Maps Kira's playback state to the state shown in the overlay.
+    ///
+    /// # Arguments
+    /// * `state` - The playback state of the source's sound
+    pub fn from_playback(state: PlaybackState) -> Self {
+        match state {
+            PlaybackState::Playing | PlaybackState::Resuming => SourceState::Playing,
+            PlaybackState::Pausing | PlaybackState::Paused | PlaybackState::WaitingToResume => {
+                SourceState::Paused
+            }
+            PlaybackState::Stopping => SourceState::Stopping,
+            PlaybackState::Stopped => SourceState::Stopped,
+        }
+    }
+
+    /// Returns the short name shown in the overlay.
+    pub fn label(self) -> &'static str {
+        match self {
+            SourceState::Playing => "playing",
+            SourceState::Paused => "paused",
+            SourceState::Culled => "culled",
+            SourceState::Stopping => "stopping",
+            SourceState::Stopped => "stopped",
+        }
+    }
+}
+
+/// One source in a snapshot.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SourceSnapshot {
+    /// Name of the source, such as `"music"` or an enemy ID
+    pub id: String,
+    /// Bus or group the source plays on
+    pub channel: AudioChannel,
+    /// Volume the source is heard at, in decibels, with every gain on its way out applied
+    pub volume_db: f32,
+    /// Where the source is heard from, for positioned sources
+    pub position: Option<[f32; 3]>,
+    /// Distance from the listener to `position`
+    pub distance: Option<f32>,
+    /// Whether the source is being heard
+    pub state: SourceState,
+}
+
+/// What happened in an [`AudioEvent`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AudioEventKind {
+    /// A sound started
+    Play,
+    /// A sound was stopped
+    Stop,
+    /// A warning cue ducked the music
+    Duck,
+    /// An enemy moved into another audio band, going out of or back into earshot
+    Occlusion,
+}
+
+impl AudioEventKind {
+    /// Returns the short name shown in the overlay.
+    pub fn label(self) -> &'static str {
+        match self {
+            AudioEventKind::Play => "play",
+            AudioEventKind::Stop => "stop",
+            AudioEventKind::Duck => "duck",
+            AudioEventKind::Occlusion => "occlusion",
+        }
+    }
+}
+
+/// An entry of the rolling audio event log.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AudioEvent {
+    /// Milliseconds since introspection was turned on
+    pub elapsed_ms: u64,
+    /// What happened
+    pub kind: AudioEventKind,
+    /// Name of the source it happened to
+    pub source: Cow<'static, str>,
+    /// Anything else worth knowing, such as a volume or a band change
+    pub detail: String,
+}
+
+impl fmt::Display for AudioEvent {
+    /// Formats the event as `[ss.mmm] kind source detail`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "[{:>3}.{:03}] {:<9} {}",
+            self.elapsed_ms / 1000,
+            self.elapsed_ms % 1000,
+            self.kind.label(),
+            self.source
+        )?;
+        if !self.detail.is_empty() {
+            write!(f, " {}", self.detail)?;
+        }
+        Ok(())
+    }
+}
+
+/// Everything the audio manager is playing at one moment.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AudioDebugSnapshot {
+    /// Where the listener is
+    pub listener: [f32; 3],
+    /// How far the music is ducked, in decibels
+    pub duck_db: f32,
+    /// Every source, looping ones first
+    pub sources: Vec<SourceSnapshot>,
+    /// The rolling event log, oldest first; empty unless introspection was on
+    pub events: Vec<AudioEvent>,
+}
+
+impl fmt::Display for AudioDebugSnapshot {
+    /// Formats the snapshot as a table of sources followed by the event log.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let [x, y, z] = self.listener;
+        write!(
+            f,
+            "Listener ({:.1}, {:.1}, {:.1}), music duck {:+.1} dB, {} sources",
+            x,
+            y,
+            z,
+            self.duck_db,
+            self.sources.len()
+        )?;
+        for source in &self.sources {
+            write!(
+                f,
+                "\n  {:<14} {:<8} {:>6.1} dB  {:<8}",
+                source.id,
+                source.channel.label(),
+                source.volume_db,
+                source.state.label()
+            )?;
+            if let (Some([x, y, z]), Some(distance)) = (source.position, source.distance) {
+                write!(f, " at ({:.1}, {:.1}, {:.1}) {:.1} away", x, y, z, distance)?;
+            }
+        }
+        if !self.events.is_empty() {
+            write!(f, "\nLast {} events", self.events.len())?;
+            for event in &self.events {
+                write!(f, "\n  {}", event)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Returns the distance from the listener to a source.
+///
+/// # Arguments
+/// * `listener` - Where the listener is
+/// * `position` - Where the source is heard from
+pub fn distance(listener: [f32; 3], position: [f32; 3]) -> f32 {
+    (0..3)
+        .map(|i| (position[i] - listener[i]).powi(2))
+        .sum::<f32>()
+        .sqrt()
+}
+
+/// A one-shot sound started while introspection was on.
+#[derive(Debug, Clone, PartialEq)]
+struct OneShot {
+    /// Name of the sound
+    name: &'static str,
+    /// Bus or group it plays on
+    channel: AudioChannel,
+    /// Volume it was started at, in decibels
+    volume_db: f32,
+    /// Where it is heard from, for positioned sounds
+    position: Option<[f32; 3]>,
+    /// When it started
+    started: Instant,
+    /// How long it plays for
+    length: Duration,
+}
+
+/// The event log and one-shot registry kept while introspection is on.
+#[derive(Debug, Clone)]
+pub struct AudioDebugState {
+    /// When introspection was turned on
+    started: Instant,
+    /// The last [`AUDIO_EVENT_LOG_LEN`] events, oldest first
+    events: VecDeque<AudioEvent>,
+    /// One-shot sounds that haven't finished yet
+    one_shots: Vec<OneShot>,
+}
+
+impl AudioDebugState {
+    /// Starts an empty log and registry.
+    ///
+    /// # Arguments
+    /// * `now` - The current time, which event timestamps count from
+    pub fn new(now: Instant) -> Self {
+        Self {
+            started: now,
+            events: VecDeque::with_capacity(AUDIO_EVENT_LOG_LEN),
+            one_shots: Vec::new(),
+        }
+    }
+
+    /// Adds an event to the log, dropping the oldest past [`AUDIO_EVENT_LOG_LEN`].
+    ///
+    /// # Arguments
+    /// * `now` - When it happened
+    /// * `kind` - What happened
+    /// * `source` - Name of the source it happened to
+    /// * `detail` - Anything else worth knowing, or an empty string
+    pub fn record(
+        &mut self,
+        now: Instant,
+        kind: AudioEventKind,
+        source: impl Into<Cow<'static, str>>,
+        detail: String,
+    ) {
+        if self.events.len() == AUDIO_EVENT_LOG_LEN {
+            self.events.pop_front();
+        }
+        self.events.push_back(AudioEvent {
+            elapsed_ms: now.saturating_duration_since(self.started).as_millis() as u64,
+            kind,
+            source: source.into(),
+            detail,
+        });
+    }
+
+    /// Registers a one-shot sound and logs it starting.
+    ///
+    /// # Arguments
+    /// * `now` - When it started
+    /// * `name` - Name of the sound
+    /// * `channel` - Bus or group it plays on
+    /// * `volume_db` - Volume it was started at, in decibels
+    /// * `position` - Where it is heard from, for positioned sounds
+    /// * `length` - How long it plays for
+    pub fn play_one_shot(
+        &mut self,
+        now: Instant,
+        name: &'static str,
+        channel: AudioChannel,
+        volume_db: f32,
+        position: Option<[f32; 3]>,
+        length: Duration,
+    ) {
+        self.record(
+            now,
+            AudioEventKind::Play,
+            name,
+            format!("{:+.1} dB", volume_db),
+        );
+        self.one_shots.push(OneShot {
+            name,
+            channel,
+            volume_db,
+            position,
+            started: now,
+            length,
+        });
+    }
+
+    /// Forgets the one-shot sounds that have finished.
+    ///
+    /// # Arguments
+    /// * `now` - The current time
+    pub fn prune(&mut self, now: Instant) {
+        self.one_shots
+            .retain(|shot| now.saturating_duration_since(shot.started) < shot.length);
+    }
+
+    /// Returns the one-shot sounds still playing, as snapshot sources.
+    ///
+    /// # Arguments
+    /// * `now` - The current time
+    /// * `listener` - Where the listener is, for the distance of positioned sounds
+    pub fn one_shot_sources(&self, now: Instant, listener: [f32; 3]) -> Vec<SourceSnapshot> {
+        self.one_shots
+            .iter()
+            .filter(|shot| now.saturating_duration_since(shot.started) < shot.length)
+            .map(|shot| SourceSnapshot {
+                id: shot.name.to_string(),
+                channel: shot.channel,
+                volume_db: shot.volume_db,
+                position: shot.position,
+                distance: shot.position.map(|position| distance(listener, position)),
+                state: SourceState::Playing,
+            })
+            .collect()
+    }
+
+    /// Returns the event log, oldest first.
+    pub fn events(&self) -> Vec<AudioEvent> {
+        self.events.iter().cloned().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_log_keeps_the_last_events() {
+        let start = Instant::now();
+        let mut state = AudioDebugState::new(start);
+        for i in 0..AUDIO_EVENT_LOG_LEN + 5 {
+            state.record(
+                start + Duration::from_millis(i as u64),
+                AudioEventKind::Play,
+                "select",
+                String::new(),
+            );
+        }
+        let events = state.events();
+        assert_eq!(events.len(), AUDIO_EVENT_LOG_LEN);
+        assert_eq!(events[0].elapsed_ms, 5);
+        assert_eq!(
+            events.last().map(|event| event.elapsed_ms),
+            Some(AUDIO_EVENT_LOG_LEN as u64 + 4)
+        );
+    }
+
+    #[test]
+    fn test_one_shots_are_forgotten_once_finished() {
+        let start = Instant::now();
+        let mut state = AudioDebugState::new(start);
+        let position = [3.0, 0.0, 4.0];
+        state.play_one_shot(
+            start,
+            "echo",
+            AudioChannel::Spatial,
+            -6.0,
+            Some(position),
+            Duration::from_secs(3),
+        );
+        state.play_one_shot(
+            start,
+            "select",
+            AudioChannel::Effects,
+            0.0,
+            None,
+            Duration::from_millis(200),
+        );
+
+        let sources = state.one_shot_sources(start + Duration::from_millis(100), [0.0; 3]);
+        assert_eq!(sources.len(), 2);
+        assert_eq!(sources[0].distance, Some(5.0));
+        assert_eq!(sources[1].distance, None);
+
+        state.prune(start + Duration::from_secs(1));
+        let sources = state.one_shot_sources(start + Duration::from_secs(1), [0.0; 3]);
+        let ids: Vec<_> = sources.iter().map(|source| source.id.as_str()).collect();
+        assert_eq!(ids, ["echo"]);
+        assert_eq!(state.events().len(), 2);
+    }
+
+    #[test]
+    fn test_snapshot_round_trips_through_the_journal_format() {
+        let snapshot = AudioDebugSnapshot {
+            listener: [1.0, 0.5, 2.0],
+            duck_db: -4.0,
+            sources: vec![SourceSnapshot {
+                id: "enemy".to_string(),
+                channel: AudioChannel::Spatial,
+                volume_db: -12.5,
+                position: Some([4.0, 0.5, 6.0]),
+                distance: Some(5.0),
+                state: SourceState::Culled,
+            }],
+            events: vec![AudioEvent {
+                elapsed_ms: 1500,
+                kind: AudioEventKind::Occlusion,
+                source: Cow::Borrowed("enemy"),
+                detail: "Reduced -> Culled".to_string(),
+            }],
+        };
+        let json = serde_json::to_string(&snapshot).unwrap();
+        let parsed: AudioDebugSnapshot = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, snapshot);
+
+        let text = snapshot.to_string();
+        assert!(text.contains("enemy"));
+        assert!(text.contains("culled"));
+        assert!(text.contains("-12.5 dB"));
+        assert!(text.contains("occlusion"));
+    }
+}
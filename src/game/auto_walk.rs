@@ -0,0 +1,343 @@
+//! Click-to-move for the Mirador game.
+//!
+//! With the `click_to_move` setting on, clicking sends the player walking to the floor under the
+//! reticle. The click is turned into a ray through the inverse view-projection matrix with
+//! [`unproject_to_floor`], and the point where it meets the floor is snapped to an open cell with
+//! [`snap_to_open_cell`]. A click on a wall snaps to the open cell in front of it; a click that
+//! cannot be snapped or reached plays a soft denied cue instead.
+//!
+//! [`AutoWalk`] then follows the route cell by cell: each frame [`AutoWalk::steer`] turns the
+//! player smoothly toward the next cell and says whether to walk forward, so movement, sprinting,
+//! stamina, and collisions all go through the same path as the keyboard. Any manual movement
+//! input, a new click, the enemy coming within [`ENEMY_STOP_RANGE`], or leaving play (including
+//! the exit cutscene) stops the walk.
+//!
+//! # Usage
+//!
+//! ```rust,ignore
+//! let ndc = screen_to_ndc(click, &view);
+//! let hit = unproject_to_floor(ndc, &view_proj.try_inverse()?, floor_height)?;
+//! let cell = snap_to_open_cell(grid, world_to_grid_point(hit, dims, is_test_mode))?;
+//! auto_walk.start(waypoints, arrival_radius);
+//!
+//! // Once per frame
+//! let walk_forward = auto_walk.steer(player.position, &mut player.yaw, delta_time);
+//! ```
+
+use crate::game::director::CLOSE_CALL_RANGE;
+use crate::game::maze::generator::Cell;
+use crate::math::coordinates;
+use crate::math::mat::Mat4;
+use crate::renderer::safe_area::SafeArea;
+
+/// Fastest the player turns toward the next cell while auto-walking, in degrees per second.
+pub const TURN_RATE: f32 = 270.0;
+
+/// How far in degrees the player may face away from the next cell and still walk toward it.
+///
+/// Walking only once roughly facing the cell keeps sharp corners from scraping along walls.
+pub const FACING_TOLERANCE: f32 = 30.0;
+
+/// Fraction of a cell's width within which a cell on the route counts as reached.
+pub const ARRIVAL_FRACTION: f32 = 0.2;
+
+/// Enemy distance in world units within which auto-walk stops and control returns to the player.
+///
+/// The same distance that counts as a close call, so the player is never walked into the enemy.
+pub const ENEMY_STOP_RANGE: f32 = CLOSE_CALL_RANGE;
+
+/// Converts a point on the window to normalized device coordinates of the 3D view.
+///
+/// # Arguments
+/// * `point` - Pixel position from the top-left corner of the window
+/// * `view` - Region of the window the 3D view is drawn in
+///
+/// # Returns
+/// `[x, y]` from -1.0 to 1.0 across the view, with +y pointing up
+pub fn screen_to_ndc(point: [f32; 2], view: &SafeArea) -> [f32; 2] {
+    [
+        (point[0] - view.x) / view.width * 2.0 - 1.0,
+        1.0 - (point[1] - view.y) / view.height * 2.0,
+    ]
+}
+
+/// Transforms a clip-space point back to world space.
+fn unproject(ndc: [f32; 3], inverse_view_proj: &Mat4) -> Option<[f32; 3]> {
+    let m = inverse_view_proj.0;
+    let v = [ndc[0], ndc[1], ndc[2], 1.0];
+    let mut out = [0.0; 4];
+    for (j, value) in out.iter_mut().enumerate() {
+        *value = v[0] * m[0][j] + v[1] * m[1][j] + v[2] * m[2][j] + v[3] * m[3][j];
+    }
+    if out[3].abs() < f32::EPSILON {
+        return None;
+    }
+    Some([out[0] / out[3], out[1] / out[3], out[2] / out[3]])
+}
+
+/// Finds where the ray through a point of the 3D view meets a horizontal floor.
+///
+/// # Arguments
+/// * `ndc` - The point in normalized device coordinates, see [`screen_to_ndc`]
+/// * `inverse_view_proj` - Inverse of the camera's view-projection matrix
+/// * `floor_height` - World height of the floor
+///
+/// # Returns
+/// The world position on the floor, or `None` if the ray points away from the floor or meets
+/// it beyond the far clipping plane
+pub fn unproject_to_floor(
+    ndc: [f32; 2],
+    inverse_view_proj: &Mat4,
+    floor_height: f32,
+) -> Option<[f32; 3]> {
+    let near = unproject([ndc[0], ndc[1], 0.0], inverse_view_proj)?;
+    let far = unproject([ndc[0], ndc[1], 1.0], inverse_view_proj)?;
+    let rise = far[1] - near[1];
+    if rise.abs() < f32::EPSILON {
+        return None;
+    }
+    let t = (floor_height - near[1]) / rise;
+    if !(0.0..=1.0).contains(&t) {
+        return None;
+    }
+    Some([
+        near[0] + (far[0] - near[0]) * t,
+        floor_height,
+        near[2] + (far[2] - near[2]) * t,
+    ])
+}
+
+/// Converts a world position to continuous wall-grid coordinates.
+///
+/// # Returns
+/// `[column, row]`, where the cell at `(row, col)` spans `col..col + 1` and `row..row + 1`
+pub fn world_to_grid_point(
+    position: [f32; 3],
+    maze_dimensions: (usize, usize),
+    is_test_mode: bool,
+) -> [f32; 2] {
+    let (width, height) = maze_dimensions;
+    let cell_size = coordinates::calculate_cell_size(maze_dimensions, is_test_mode);
+    let origin_x = -(width as f32 * cell_size) / 2.0;
+    let origin_z = -(height as f32 * cell_size) / 2.0;
+    [
+        (position[0] - origin_x) / cell_size,
+        (position[2] - origin_z) / cell_size,
+    ]
+}
+
+/// Snaps a point on the wall grid to the open cell the player meant.
+///
+/// A point in an open cell snaps to that cell. A point in a wall, such as where a click on a wall
+/// face lands, snaps to the closest open cell beside it.
+///
+/// # Arguments
+/// * `grid` - Wall grid of the floor, where `true` marks a wall
+/// * `point` - `[column, row]` grid coordinates, see [`world_to_grid_point`]
+///
+/// # Returns
+/// The open cell, or `None` if the point is off the grid or inside walls on every side
+pub fn snap_to_open_cell(grid: &[Vec<bool>], point: [f32; 2]) -> Option<Cell> {
+    let height = grid.len();
+    let width = grid.first().map_or(0, Vec::len);
+    if !(point[0] >= 0.0 && point[1] >= 0.0) {
+        return None;
+    }
+    let (col, row) = (point[0] as usize, point[1] as usize);
+    if col >= width || row >= height {
+        return None;
+    }
+    if !grid[row][col] {
+        return Some(Cell::new(row, col));
+    }
+
+    let distance_squared = |cell: &Cell| {
+        let dx = cell.col as f32 + 0.5 - point[0];
+        let dz = cell.row as f32 + 0.5 - point[1];
+        dx * dx + dz * dz
+    };
+    [
+        (row.wrapping_sub(1), col),
+        (row + 1, col),
+        (row, col.wrapping_sub(1)),
+        (row, col + 1),
+    ]
+    .into_iter()
+    .filter(|&(row, col)| row < height && col < width && !grid[row][col])
+    .map(|(row, col)| Cell::new(row, col))
+    .min_by(|a, b| distance_squared(a).total_cmp(&distance_squared(b)))
+}
+
+/// Returns the yaw in degrees that faces from one position toward another.
+///
+/// Matches [`Player::view_direction`](crate::game::player::Player::view_direction), where a yaw
+/// of 0 looks down -Z.
+pub fn yaw_toward(from: [f32; 3], to: [f32; 3]) -> f32 {
+    (from[0] - to[0]).atan2(from[2] - to[2]).to_degrees()
+}
+
+/// Returns the signed difference `to - from` between two angles, wrapped to -180..=180 degrees.
+fn angle_difference(from: f32, to: f32) -> f32 {
+    (to - from + 180.0).rem_euclid(360.0) - 180.0
+}
+
+/// An automatic walk along a route of cells.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AutoWalk {
+    /// World positions of the cells on the route, at floor height
+    waypoints: Vec<[f32; 3]>,
+    /// Index of the waypoint being walked toward
+    next: usize,
+    /// Horizontal distance within which a waypoint counts as reached
+    arrival_radius: f32,
+}
+
+impl AutoWalk {
+    /// Creates an auto-walk that is not going anywhere.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Starts walking a new route, replacing any walk in progress.
+    ///
+    /// # Arguments
+    /// * `waypoints` - World positions to walk through in order; the last one is the destination
+    /// * `arrival_radius` - Horizontal distance within which a waypoint counts as reached
+    pub fn start(&mut self, waypoints: Vec<[f32; 3]>, arrival_radius: f32) {
+        self.waypoints = waypoints;
+        self.next = 0;
+        self.arrival_radius = arrival_radius;
+    }
+
+    /// Stops the walk where the player stands.
+    pub fn cancel(&mut self) {
+        self.waypoints.clear();
+        self.next = 0;
+    }
+
+    /// Returns whether there is still somewhere to walk to.
+    pub fn is_active(&self) -> bool {
+        self.next < self.waypoints.len()
+    }
+
+    /// Returns the waypoints not reached yet, ending with the destination.
+    pub fn remaining(&self) -> &[[f32; 3]] {
+        &self.waypoints[self.next.min(self.waypoints.len())..]
+    }
+
+    /// Steers the player along the route for one frame.
+    ///
+    /// Skips past every waypoint the player has reached, finishing the walk at the destination,
+    /// then turns the player toward the next one by at most [`TURN_RATE`] degrees per second.
+    ///
+    /// # Arguments
+    /// * `position` - The player's world position
+    /// * `yaw` - The player's yaw in degrees, turned in place
+    /// * `delta_time` - Seconds since the last frame
+    ///
+    /// # Returns
+    /// Whether the player should walk forward this frame
+    pub fn steer(&mut self, position: [f32; 3], yaw: &mut f32, delta_time: f32) -> bool {
+        while let Some(waypoint) = self.waypoints.get(self.next) {
+            let distance = (waypoint[0] - position[0]).hypot(waypoint[2] - position[2]);
+            if distance > self.arrival_radius {
+                break;
+            }
+            self.next += 1;
+        }
+        let Some(&waypoint) = self.waypoints.get(self.next) else {
+            self.cancel();
+            return false;
+        };
+
+        let turn = angle_difference(*yaw, yaw_toward(position, waypoint));
+        let max_turn = TURN_RATE * delta_time.max(0.0);
+        *yaw += turn.clamp(-max_turn, max_turn);
+        angle_difference(*yaw, yaw_toward(position, waypoint)).abs() <= FACING_TOLERANCE
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::math::deg_to_rad;
+
+    /// Camera 100 units above the origin, pitched down 45 degrees and looking down -Z.
+    fn downward_camera() -> Mat4 {
+        let view = Mat4::translation(0.0, -100.0, 0.0)
+            .multiply(&Mat4::rotation_y(0.0).multiply(&Mat4::rotation_x(-45.0)));
+        let projection = Mat4::perspective(deg_to_rad(60.0), 16.0 / 9.0, 0.1, 2000.0);
+        view.multiply(&projection)
+    }
+
+    #[test]
+    fn test_screen_to_ndc_covers_the_view() {
+        let view = SafeArea {
+            x: 100.0,
+            y: 0.0,
+            width: 800.0,
+            height: 600.0,
+        };
+        assert_eq!(screen_to_ndc([500.0, 300.0], &view), [0.0, 0.0]);
+        assert_eq!(screen_to_ndc([100.0, 0.0], &view), [-1.0, 1.0]);
+        assert_eq!(screen_to_ndc([900.0, 600.0], &view), [1.0, -1.0]);
+    }
+
+    #[test]
+    fn test_unproject_center_of_view_reaches_floor_ahead() {
+        let inverse = downward_camera().try_inverse().expect("invertible");
+        let hit = unproject_to_floor([0.0, 0.0], &inverse, 0.0).expect("floor is visible");
+        assert!(hit[0].abs() < 0.1, "{hit:?}");
+        assert!((hit[2] + 100.0).abs() < 0.1, "{hit:?}");
+
+        // Lower on the screen lands closer, and a floor above the camera is never hit
+        let lower = unproject_to_floor([0.0, -0.5], &inverse, 0.0).expect("floor is visible");
+        assert!(lower[2] > hit[2] && lower[2] < 0.0);
+        assert_eq!(unproject_to_floor([0.0, 0.0], &inverse, 150.0), None);
+    }
+
+    #[test]
+    fn test_snap_to_open_cell() {
+        // A corridor along row 1 with walls all around
+        let grid = vec![
+            vec![true, true, true, true],
+            vec![true, false, false, true],
+            vec![true, true, true, true],
+        ];
+        assert_eq!(snap_to_open_cell(&grid, [2.5, 1.5]), Some(Cell::new(1, 2)));
+
+        // A click on the wall face just past the corridor snaps back into it
+        assert_eq!(snap_to_open_cell(&grid, [1.4, 0.95]), Some(Cell::new(1, 1)));
+        assert_eq!(snap_to_open_cell(&grid, [3.05, 1.6]), Some(Cell::new(1, 2)));
+
+        // Deep inside walls, or off the grid, the click is rejected
+        assert_eq!(snap_to_open_cell(&grid, [0.5, 0.5]), None);
+        assert_eq!(snap_to_open_cell(&grid, [-1.0, 1.5]), None);
+        assert_eq!(snap_to_open_cell(&grid, [1.5, 7.0]), None);
+    }
+
+    #[test]
+    fn test_steer_turns_smoothly_then_walks_to_the_end() {
+        let mut walk = AutoWalk::new();
+        walk.start(vec![[100.0, 0.0, 0.0], [100.0, 0.0, -100.0]], 10.0);
+        let mut position = [0.0, 0.0, 0.0];
+        // Facing -Z, the first waypoint is 90 degrees to the right
+        let mut yaw = 0.0;
+
+        assert!(!walk.steer(position, &mut yaw, 0.1));
+        assert!((yaw + 27.0).abs() < 1e-3, "turned {yaw}");
+        for _ in 0..3 {
+            walk.steer(position, &mut yaw, 0.1);
+        }
+        assert!(walk.steer(position, &mut yaw, 0.1));
+        assert!((yaw + 90.0).abs() < 1e-3, "turned {yaw}");
+
+        // Reaching each waypoint moves on, and reaching the last ends the walk
+        position = [95.0, 0.0, 0.0];
+        walk.steer(position, &mut yaw, 0.1);
+        assert_eq!(walk.remaining(), &[[100.0, 0.0, -100.0]]);
+        position = [100.0, 0.0, -95.0];
+        assert!(!walk.steer(position, &mut yaw, 0.1));
+        assert!(!walk.is_active());
+    }
+}
@@ -0,0 +1,1087 @@
+//! Contextual key bindings and the input context stack.
+//!
+//! Keys mean different things depending on what has focus: `W` walks forward in the maze but is
+//! just a letter while the seed field is being typed into, and Escape pauses the game but backs out
+//! of a menu. This module names those situations as [`BindingContext`]s, each with its own
+//! [`BindingSet`], and stacks them by priority: text entry, then the debug console (the tuning
+//! panel), then menus, then gameplay. A panel drawn over the maze that has the keyboard, like the
+//! sandbox's, counts as a menu.
+//!
+//! [`context_stack`] derives the stack from the screen state machine, so contexts switch by
+//! themselves as screens change. [`InputRouter`] hands each key press to the highest context on
+//! the stack that binds it; a context that does not bind a key either swallows it or lets it fall
+//! through to the contexts below. The router also remembers which context took each held key, so
+//! the key's repeats and release stay with that context after the stack changes. That is what
+//! keeps the Escape press that opens the pause menu from also backing out of it.
+//!
+//! Bindings name physical keys, so they follow where a key sits rather than what it types:
+//! WASD stays under the left hand on an AZERTY keyboard, and Shift doesn't turn `w` into
+//! another key.
+//!
+//! Conflicts are only checked within one context. Binding the same key in two contexts, like
+//! Escape for pausing and for backing out of a menu, is legal and the usual case.
+//!
+//! The [`REBINDABLE`] actions can be moved to other keys from the pause menu's controls page.
+//! Moving one onto a key another action of its context already uses, the fixed ones included,
+//! is refused with a [`Conflict`], so a context never has two actions on one key. Only the
+//! moved actions are saved (see [`crate::paths`]), so the other keys follow the defaults.
+//!
+//! # Usage
+//!
+//! ```rust,ignore
+//! let mut router = InputRouter::load();
+//! let interact = Action::Game(GameKey::Interact);
+//! match router.rebind(BindingContext::Gameplay, interact, KeyCode::KeyF) {
+//!     Ok(()) => router.save()?,
+//!     Err(conflict) => println!("{}", conflict),
+//! }
+//!
+//! let stack = context_stack(game_state.current_screen, typing, tuning_panel.is_visible(), in_panel);
+//! match router.route(&stack, key_event.physical_key, pressed, key_event.repeat) {
+//!     Routed::Stale => return,
+//!     Routed::Action { context, action } => { /* ... */ }
+//!     _ => {}
+//! }
+//! ```
+
+use crate::game::CurrentScreen;
+use crate::game::keys::{self, GameKey};
+use crate::game::save;
+use crate::paths;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use winit::keyboard::{KeyCode, PhysicalKey};
+
+/// A situation in which keys have their own meaning, highest priority first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum BindingContext {
+    /// A text field has focus; typed characters go into it
+    TextEntry,
+    /// The debug tuning panel is open on top of whatever else is shown
+    Console,
+    /// A menu screen such as the pause, title, or game over menu
+    Menu,
+    /// Walking the maze
+    Gameplay,
+}
+
+impl BindingContext {
+    /// Every context, highest priority first.
+    pub const ALL: [BindingContext; 4] = [
+        BindingContext::TextEntry,
+        BindingContext::Console,
+        BindingContext::Menu,
+        BindingContext::Gameplay,
+    ];
+
+    /// Whether presses this context has no binding for fall through to the contexts below it.
+    ///
+    /// The console only covers part of the screen, so the game keeps its keys while it is open.
+    /// Text entry and menus take every key.
+    pub fn passes_unmatched(self) -> bool {
+        matches!(self, BindingContext::Console)
+    }
+}
+
+/// Something a key can be bound to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Action {
+    /// A gameplay action; [`GameKey::Escape`] pauses the game
+    Game(GameKey),
+    /// Move the menu selection up
+    NavigateUp,
+    /// Move the menu selection down
+    NavigateDown,
+    /// Move the menu selection left
+    NavigateLeft,
+    /// Move the menu selection right
+    NavigateRight,
+    /// Move the menu selection up a page
+    NavigatePageUp,
+    /// Move the menu selection down a page
+    NavigatePageDown,
+    /// Move the menu selection to the first item
+    NavigateFirst,
+    /// Move the menu selection to the last item
+    NavigateLast,
+    /// Move the keyboard focus to the next part of the screen, like the next card or panel
+    SwitchFocus,
+    /// Activate the selected menu item
+    Confirm,
+    /// Leave the menu, resuming the game from the pause menu
+    Back,
+    /// Accept the text typed so far
+    Submit,
+    /// Leave the text field without keeping the edit
+    Cancel,
+    /// Delete the character before the cursor
+    Erase,
+    /// Hide the console
+    ToggleConsole,
+}
+
+impl Action {
+    /// Returns the name shown for the action on the controls page.
+    pub fn label(self) -> &'static str {
+        match self {
+            Action::Game(game_key) => game_key.label(),
+            Action::NavigateUp => "Menu Up",
+            Action::NavigateDown => "Menu Down",
+            Action::NavigateLeft => "Menu Left",
+            Action::NavigateRight => "Menu Right",
+            Action::NavigatePageUp => "Menu Page Up",
+            Action::NavigatePageDown => "Menu Page Down",
+            Action::NavigateFirst => "Menu First",
+            Action::NavigateLast => "Menu Last",
+            Action::SwitchFocus => "Switch Focus",
+            Action::Confirm => "Confirm",
+            Action::Back => "Back",
+            Action::Submit => "Submit",
+            Action::Cancel => "Cancel",
+            Action::Erase => "Erase",
+            Action::ToggleConsole => "Close Console",
+        }
+    }
+
+    /// Returns the gameplay action the event handler runs for this action.
+    ///
+    /// Backing out of a menu runs the Escape handling, which also leaves the pause menu, and
+    /// closing the console runs the tuning panel key. Menu navigation and text editing have no
+    /// gameplay action; the menus and fields act on them through [`Routed::action`].
+    pub fn game_key(self) -> Option<GameKey> {
+        match self {
+            Action::Game(game_key) => Some(game_key),
+            Action::Back => Some(GameKey::Escape),
+            Action::ToggleConsole => Some(GameKey::ToggleTuningPanel),
+            _ => None,
+        }
+    }
+}
+
+/// The actions the player can move to other keys, in the order the controls page lists them.
+///
+/// Everything else, debug keys and the arrow keys included, keeps its default key.
+pub const REBINDABLE: [(BindingContext, Action); 8] = [
+    (BindingContext::Gameplay, Action::Game(GameKey::MoveForward)),
+    (
+        BindingContext::Gameplay,
+        Action::Game(GameKey::MoveBackward),
+    ),
+    (BindingContext::Gameplay, Action::Game(GameKey::MoveLeft)),
+    (BindingContext::Gameplay, Action::Game(GameKey::MoveRight)),
+    (BindingContext::Gameplay, Action::Game(GameKey::Sprint)),
+    (BindingContext::Gameplay, Action::Game(GameKey::Escape)),
+    (BindingContext::Gameplay, Action::Game(GameKey::Interact)),
+    (
+        BindingContext::Gameplay,
+        Action::Game(GameKey::ToggleMinimap),
+    ),
+];
+
+/// The keys bound to actions within one [`BindingContext`].
+///
+/// Each entry binds one key to one action; an action can have several keys, as movement does with
+/// WASD and the arrows.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BindingSet {
+    /// Bound keys in the order they were added
+    bindings: Vec<(KeyCode, Action)>,
+}
+
+/// A refused rebinding: the key already triggers another action of the context.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Conflict {
+    /// The context the key is bound in
+    pub context: BindingContext,
+    /// The key asked for
+    pub key: KeyCode,
+    /// The action that was to be moved onto the key
+    pub action: Action,
+    /// The action that keeps the key
+    pub bound: Action,
+}
+
+impl fmt::Display for Conflict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} is taken by {}",
+            key_label(self.key),
+            self.bound.label()
+        )
+    }
+}
+
+impl BindingSet {
+    /// Creates a set from key and action pairs.
+    ///
+    /// # Arguments
+    /// * `bindings` - The keys and the actions they trigger
+    pub fn new(bindings: impl IntoIterator<Item = (KeyCode, Action)>) -> Self {
+        Self {
+            bindings: bindings.into_iter().collect(),
+        }
+    }
+
+    /// The bindings a context starts with.
+    ///
+    /// # Arguments
+    /// * `context` - The context to get the defaults of
+    pub fn defaults(context: BindingContext) -> Self {
+        match context {
+            BindingContext::TextEntry => Self::new([
+                (KeyCode::Enter, Action::Submit),
+                (KeyCode::NumpadEnter, Action::Submit),
+                (KeyCode::Escape, Action::Cancel),
+                (KeyCode::Backspace, Action::Erase),
+            ]),
+            BindingContext::Console => Self::new([(KeyCode::KeyC, Action::ToggleConsole)]),
+            // Menus keep the keys that work everywhere, like quitting and the debug keys
+            BindingContext::Menu => Self::new(
+                [
+                    (KeyCode::ArrowUp, Action::NavigateUp),
+                    (KeyCode::ArrowDown, Action::NavigateDown),
+                    (KeyCode::ArrowLeft, Action::NavigateLeft),
+                    (KeyCode::ArrowRight, Action::NavigateRight),
+                    (KeyCode::PageUp, Action::NavigatePageUp),
+                    (KeyCode::PageDown, Action::NavigatePageDown),
+                    (KeyCode::Home, Action::NavigateFirst),
+                    (KeyCode::End, Action::NavigateLast),
+                    (KeyCode::Tab, Action::SwitchFocus),
+                    (KeyCode::Enter, Action::Confirm),
+                    (KeyCode::NumpadEnter, Action::Confirm),
+                    (KeyCode::Space, Action::Confirm),
+                    (KeyCode::Escape, Action::Back),
+                ]
+                .into_iter()
+                .chain(
+                    keys::menu_game_bindings()
+                        .into_iter()
+                        .map(|(key, action)| (key, Action::Game(action))),
+                ),
+            ),
+            BindingContext::Gameplay => Self::new(
+                keys::default_game_bindings()
+                    .into_iter()
+                    .map(|(key, action)| (key, Action::Game(action))),
+            ),
+        }
+    }
+
+    /// Finds the action a key triggers.
+    ///
+    /// # Arguments
+    /// * `key` - The pressed key
+    ///
+    /// # Returns
+    /// The action bound to the key, or `None` if it is unbound
+    pub fn action_for(&self, key: KeyCode) -> Option<Action> {
+        self.bindings
+            .iter()
+            .find(|(bound, _)| *bound == key)
+            .map(|(_, action)| *action)
+    }
+
+    /// Returns the keys bound to an action.
+    pub fn keys_for(&self, action: Action) -> impl Iterator<Item = KeyCode> + '_ {
+        self.bindings
+            .iter()
+            .filter(move |(_, bound)| *bound == action)
+            .map(|(key, _)| *key)
+    }
+
+    /// Binds an action to a single key, replacing every key it was bound to before.
+    ///
+    /// # Arguments
+    /// * `action` - The action to rebind
+    /// * `key` - The key that should trigger it
+    ///
+    /// # Returns
+    /// `Ok(())` once the action is on the key, or the other action already bound to it, in
+    /// which case nothing changes
+    pub fn rebind(&mut self, action: Action, key: KeyCode) -> Result<(), Action> {
+        if let Some(bound) = self.action_for(key).filter(|&bound| bound != action) {
+            return Err(bound);
+        }
+        self.bindings.retain(|(_, bound)| *bound != action);
+        self.bindings.push((key, action));
+        Ok(())
+    }
+}
+
+/// Returns a short name for a key, as shown on the controls page.
+///
+/// # Arguments
+/// * `key` - The key
+///
+/// # Returns
+/// A name such as `"W"`, `"Left Shift"`, or `"Escape"`
+pub fn key_label(key: KeyCode) -> String {
+    let symbol = match key {
+        KeyCode::Backquote => "`",
+        KeyCode::Minus => "-",
+        KeyCode::Equal => "=",
+        KeyCode::BracketLeft => "[",
+        KeyCode::BracketRight => "]",
+        KeyCode::Backslash => "\\",
+        KeyCode::Semicolon => ";",
+        KeyCode::Quote => "'",
+        KeyCode::Comma => ",",
+        KeyCode::Period => ".",
+        KeyCode::Slash => "/",
+        KeyCode::ShiftLeft => "Left Shift",
+        KeyCode::ShiftRight => "Right Shift",
+        KeyCode::ControlLeft => "Left Ctrl",
+        KeyCode::ControlRight => "Right Ctrl",
+        KeyCode::AltLeft => "Left Alt",
+        KeyCode::AltRight => "Right Alt",
+        _ => "",
+    };
+    if !symbol.is_empty() {
+        return symbol.to_string();
+    }
+    // Letters and digits are named like `KeyW` and `Digit1`
+    let name = format!("{:?}", key);
+    ["Key", "Digit"]
+        .into_iter()
+        .find_map(|prefix| name.strip_prefix(prefix))
+        .map_or_else(|| name.clone(), str::to_string)
+}
+
+/// Works out which contexts are active, highest priority first.
+///
+/// # Arguments
+/// * `screen` - The screen currently shown
+/// * `text_entry` - Whether a text field, like the title seed or the pause menu filter, has focus
+/// * `console` - Whether the tuning panel is open
+/// * `panel` - Whether a panel drawn over the maze, like the sandbox's, has the keyboard
+pub fn context_stack(
+    screen: CurrentScreen,
+    text_entry: bool,
+    console: bool,
+    panel: bool,
+) -> Vec<BindingContext> {
+    let base = match screen {
+        _ if panel => BindingContext::Menu,
+        CurrentScreen::Game
+        | CurrentScreen::Loading
+        | CurrentScreen::NewGame
+        | CurrentScreen::ExitReached => BindingContext::Gameplay,
+        CurrentScreen::Title
+        | CurrentScreen::Pause
+        | CurrentScreen::GameOver
+        | CurrentScreen::UpgradeMenu
+        | CurrentScreen::Codex
+        | CurrentScreen::Profiles
+        | CurrentScreen::Error => BindingContext::Menu,
+    };
+    let mut stack = Vec::with_capacity(3);
+    if text_entry {
+        stack.push(BindingContext::TextEntry);
+    }
+    if console {
+        stack.push(BindingContext::Console);
+    }
+    stack.push(base);
+    stack
+}
+
+/// Where a key event went.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Routed {
+    /// A context bound the key; also returned for the key's repeats while the context is active
+    Action {
+        /// The context that took the key
+        context: BindingContext,
+        /// What the key triggers there
+        action: Action,
+    },
+    /// A context that takes every key received a key it has no binding for
+    Consumed(BindingContext),
+    /// No active context wanted the key
+    Unbound,
+    /// A held key was released; the release goes to the context that took the press, even if
+    /// that context is no longer active, so held actions like walking always end
+    Released {
+        /// The context that took the press
+        context: BindingContext,
+        /// What the key triggered there, if anything
+        action: Option<Action>,
+    },
+    /// A repeat of a key whose press went to a context that is no longer active; it must be
+    /// ignored rather than handed to the context that replaced it
+    Stale,
+}
+
+impl Routed {
+    /// Returns the action the key triggers in the context that took it, for a press, a repeat,
+    /// or a release.
+    ///
+    /// Menus and text fields act on this rather than on the key itself, so they follow the
+    /// player's bindings. They still check the event for whether the key went down.
+    pub fn action(&self) -> Option<Action> {
+        match self {
+            Routed::Action { action, .. } => Some(*action),
+            Routed::Released { action, .. } => *action,
+            _ => None,
+        }
+    }
+}
+
+/// An action the player moved to another key, as saved in the key bindings file.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct Rebinding {
+    /// The context the action belongs to
+    context: BindingContext,
+    /// The moved action
+    action: Action,
+    /// The key it was moved to
+    key: KeyCode,
+}
+
+/// Routes key events through the context stack.
+#[derive(Debug, Clone)]
+pub struct InputRouter {
+    /// The bindings of each context
+    sets: HashMap<BindingContext, BindingSet>,
+    /// Keys currently held down and the context that took each one's press
+    held: HashMap<PhysicalKey, BindingContext>,
+    /// Actions moved off their default keys, latest last, one entry per action
+    rebound: Vec<Rebinding>,
+}
+
+impl Default for InputRouter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl InputRouter {
+    /// Creates a router with every context's default bindings and no keys held.
+    pub fn new() -> Self {
+        Self {
+            sets: BindingContext::ALL
+                .into_iter()
+                .map(|context| (context, BindingSet::defaults(context)))
+                .collect(),
+            held: HashMap::new(),
+            rebound: Vec::new(),
+        }
+    }
+
+    /// Creates a router with the player's key bindings from the data directory.
+    ///
+    /// # Returns
+    /// The default bindings with the saved ones applied, or just the defaults if the file is
+    /// missing or invalid
+    pub fn load() -> Self {
+        Self::load_from(&paths::get().key_bindings())
+    }
+
+    /// Creates a router with the key bindings saved in a specific file.
+    ///
+    /// # Arguments
+    /// * `path` - The key bindings file to read
+    ///
+    /// # Returns
+    /// The default bindings with the saved ones applied, or just the defaults if the file is
+    /// missing or invalid
+    pub fn load_from(path: &Path) -> Self {
+        let mut router = Self::new();
+        let contents = match fs::read_to_string(path) {
+            Ok(contents) => contents,
+            Err(_) => return router,
+        };
+
+        match serde_json::from_str::<Vec<Rebinding>>(&contents) {
+            Ok(rebound) => {
+                for Rebinding {
+                    context,
+                    action,
+                    key,
+                } in rebound
+                {
+                    if let Err(conflict) = router.rebind(context, action, key) {
+                        eprintln!(
+                            "Skipping saved key binding for {}: {}",
+                            action.label(),
+                            conflict
+                        );
+                    }
+                }
+            }
+            Err(e) => eprintln!(
+                "Failed to parse key bindings file {}: {}, using defaults",
+                path.display(),
+                e
+            ),
+        }
+        router
+    }
+
+    /// Saves the player's key bindings to the data directory.
+    ///
+    /// # Returns
+    /// `Ok(())` on success, or an error message if the file could not be written
+    pub fn save(&self) -> Result<(), String> {
+        self.save_to(&paths::get().key_bindings())
+    }
+
+    /// Saves the player's key bindings to a specific file.
+    ///
+    /// # Arguments
+    /// * `path` - The key bindings file to write
+    ///
+    /// # Returns
+    /// `Ok(())` on success, or an error message if the file could not be written
+    pub fn save_to(&self, path: &Path) -> Result<(), String> {
+        let contents = serde_json::to_string_pretty(&self.rebound)
+            .map_err(|e| format!("Failed to serialize key bindings: {}", e))?;
+        save::write_atomically(path, contents.as_bytes())
+            .map_err(|e| format!("Failed to write key bindings to {}: {}", path.display(), e))
+    }
+
+    /// Returns the bindings of a context.
+    pub fn bindings(&self, context: BindingContext) -> &BindingSet {
+        &self.sets[&context]
+    }
+
+    /// Binds an action of one context to a single key.
+    ///
+    /// # Arguments
+    /// * `context` - The context whose binding changes
+    /// * `action` - The action to rebind
+    /// * `key` - The key that should trigger it
+    ///
+    /// # Returns
+    /// `Ok(())` once the action is on the key, or the conflict if another action of the context
+    /// already uses it, in which case nothing changes; other contexts may reuse the key freely
+    pub fn rebind(
+        &mut self,
+        context: BindingContext,
+        action: Action,
+        key: KeyCode,
+    ) -> Result<(), Conflict> {
+        self.sets
+            .get_mut(&context)
+            .expect("every context has a binding set")
+            .rebind(action, key)
+            .map_err(|bound| Conflict {
+                context,
+                key,
+                action,
+                bound,
+            })?;
+        self.rebound
+            .retain(|moved| (moved.context, moved.action) != (context, action));
+        self.rebound.push(Rebinding {
+            context,
+            action,
+            key,
+        });
+        Ok(())
+    }
+
+    /// Forgets every held key, for when the window loses focus and releases won't arrive.
+    pub fn clear_held(&mut self) {
+        self.held.clear();
+    }
+
+    /// Decides which context a key event belongs to.
+    ///
+    /// # Arguments
+    /// * `stack` - The active contexts, highest priority first, as from [`context_stack`]
+    /// * `key` - The physical key of the event
+    /// * `pressed` - Whether the key went down rather than up
+    /// * `repeat` - Whether the press is an auto-repeat of a held key
+    pub fn route(
+        &mut self,
+        stack: &[BindingContext],
+        key: PhysicalKey,
+        pressed: bool,
+        repeat: bool,
+    ) -> Routed {
+        // Keys the platform can't name are never bound, but text fields and menus still take them
+        let action_in = |set: &BindingSet| match key {
+            PhysicalKey::Code(code) => set.action_for(code),
+            PhysicalKey::Unidentified(_) => None,
+        };
+
+        if !pressed {
+            return match self.held.remove(&key) {
+                Some(context) => Routed::Released {
+                    context,
+                    action: action_in(&self.sets[&context]),
+                },
+                None => Routed::Unbound,
+            };
+        }
+
+        if let Some(&owner) = self.held.get(&key) {
+            if !repeat {
+                // The release was lost, e.g. while the window was unfocused; treat it as new
+                self.held.remove(&key);
+            } else if !stack.contains(&owner) {
+                return Routed::Stale;
+            } else {
+                return match action_in(&self.sets[&owner]) {
+                    Some(action) => Routed::Action {
+                        context: owner,
+                        action,
+                    },
+                    None => Routed::Consumed(owner),
+                };
+            }
+        }
+
+        for &context in stack {
+            if let Some(action) = action_in(&self.sets[&context]) {
+                self.held.insert(key, context);
+                return Routed::Action { context, action };
+            }
+            if !context.passes_unmatched() {
+                self.held.insert(key, context);
+                return Routed::Consumed(context);
+            }
+        }
+        Routed::Unbound
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(code: KeyCode) -> PhysicalKey {
+        PhysicalKey::Code(code)
+    }
+
+    fn escape() -> PhysicalKey {
+        key(KeyCode::Escape)
+    }
+
+    #[test]
+    fn test_escape_that_opens_pause_menu_does_not_also_close_it() {
+        let mut router = InputRouter::new();
+        let mut screen = CurrentScreen::Game;
+
+        // Escape goes down in the maze and pauses
+        let routed = router.route(
+            &context_stack(screen, false, false, false),
+            escape(),
+            true,
+            false,
+        );
+        assert_eq!(
+            routed,
+            Routed::Action {
+                context: BindingContext::Gameplay,
+                action: Action::Game(GameKey::Escape),
+            }
+        );
+        screen = CurrentScreen::Pause;
+        let stack = context_stack(screen, false, false, false);
+        assert_eq!(stack, vec![BindingContext::Menu]);
+
+        // Holding it repeats into the pause menu, which must not read it as Back
+        assert_eq!(router.route(&stack, escape(), true, true), Routed::Stale);
+        assert_eq!(router.route(&stack, escape(), true, true), Routed::Stale);
+
+        // Letting go belongs to the press, not to the menu
+        assert_eq!(
+            router.route(&stack, escape(), false, false),
+            Routed::Released {
+                context: BindingContext::Gameplay,
+                action: Some(Action::Game(GameKey::Escape)),
+            }
+        );
+
+        // A fresh press backs out of the menu
+        assert_eq!(
+            router.route(&stack, escape(), true, false),
+            Routed::Action {
+                context: BindingContext::Menu,
+                action: Action::Back,
+            }
+        );
+    }
+
+    #[test]
+    fn test_movement_held_into_a_menu_is_released_to_gameplay() {
+        let mut router = InputRouter::new();
+        let game = context_stack(CurrentScreen::Game, false, false, false);
+        let pause = context_stack(CurrentScreen::Pause, false, false, false);
+
+        router.route(&game, key(KeyCode::KeyW), true, false);
+        assert_eq!(
+            router.route(&pause, key(KeyCode::KeyW), true, true),
+            Routed::Stale
+        );
+        assert_eq!(
+            router.route(&pause, key(KeyCode::KeyW), false, false),
+            Routed::Released {
+                context: BindingContext::Gameplay,
+                action: Some(Action::Game(GameKey::MoveForward)),
+            }
+        );
+        // Never pressed through the router, e.g. held before the window had focus
+        assert_eq!(
+            router.route(&pause, key(KeyCode::KeyW), false, false),
+            Routed::Unbound
+        );
+    }
+
+    #[test]
+    fn test_text_entry_takes_every_key() {
+        let mut router = InputRouter::new();
+        let stack = context_stack(CurrentScreen::Title, true, false, false);
+        assert_eq!(stack, vec![BindingContext::TextEntry, BindingContext::Menu]);
+
+        assert_eq!(
+            router.route(&stack, key(KeyCode::KeyW), true, false),
+            Routed::Consumed(BindingContext::TextEntry)
+        );
+        assert_eq!(
+            router.route(&stack, escape(), true, false),
+            Routed::Action {
+                context: BindingContext::TextEntry,
+                action: Action::Cancel,
+            }
+        );
+        // Typing held keys keeps going to the field
+        assert_eq!(
+            router.route(&stack, key(KeyCode::KeyW), true, true),
+            Routed::Consumed(BindingContext::TextEntry)
+        );
+        // Keys the platform can't name are typed into the field too
+        let unnamed = PhysicalKey::Unidentified(winit::keyboard::NativeKeyCode::Xkb(0x1234));
+        assert_eq!(
+            router.route(&stack, unnamed, true, false),
+            Routed::Consumed(BindingContext::TextEntry)
+        );
+    }
+
+    #[test]
+    fn test_console_passes_unmatched_keys_to_gameplay() {
+        let mut router = InputRouter::new();
+        let stack = context_stack(CurrentScreen::Game, false, true, false);
+        assert_eq!(
+            stack,
+            vec![BindingContext::Console, BindingContext::Gameplay]
+        );
+
+        assert_eq!(
+            router.route(&stack, key(KeyCode::KeyW), true, false),
+            Routed::Action {
+                context: BindingContext::Gameplay,
+                action: Action::Game(GameKey::MoveForward),
+            }
+        );
+        assert_eq!(
+            router.route(&stack, key(KeyCode::KeyC), true, false),
+            Routed::Action {
+                context: BindingContext::Console,
+                action: Action::ToggleConsole,
+            }
+        );
+
+        // Closing the console mid-walk keeps the held key with gameplay
+        let stack = context_stack(CurrentScreen::Game, false, false, false);
+        assert_eq!(
+            router.route(&stack, key(KeyCode::KeyW), true, true),
+            Routed::Action {
+                context: BindingContext::Gameplay,
+                action: Action::Game(GameKey::MoveForward),
+            }
+        );
+    }
+
+    #[test]
+    fn test_menus_keep_the_keys_that_work_everywhere() {
+        let mut router = InputRouter::new();
+        let stack = context_stack(CurrentScreen::Pause, false, false, false);
+
+        let quit = router.route(&stack, key(KeyCode::Backquote), true, false);
+        assert_eq!(
+            quit,
+            Routed::Action {
+                context: BindingContext::Menu,
+                action: Action::Game(GameKey::Quit),
+            }
+        );
+        // Walking means nothing in a menu
+        assert_eq!(
+            router.route(&stack, key(KeyCode::KeyW), true, false),
+            Routed::Consumed(BindingContext::Menu)
+        );
+        // Both Enter keys and Space confirm
+        assert_eq!(
+            router.route(&stack, key(KeyCode::NumpadEnter), true, false),
+            Routed::Action {
+                context: BindingContext::Menu,
+                action: Action::Confirm,
+            }
+        );
+        assert_eq!(
+            router
+                .route(&stack, key(KeyCode::Space), true, false)
+                .action(),
+            Some(Action::Confirm)
+        );
+
+        // Backing out and closing the console run the same handling as their gameplay keys
+        assert_eq!(Action::Back.game_key(), Some(GameKey::Escape));
+        assert_eq!(
+            Action::ToggleConsole.game_key(),
+            Some(GameKey::ToggleTuningPanel)
+        );
+        assert_eq!(Action::Confirm.game_key(), None);
+        assert_eq!(Action::Erase.game_key(), None);
+    }
+
+    #[test]
+    fn test_sandbox_panel_navigates_like_a_menu() {
+        let mut router = InputRouter::new();
+        let look = context_stack(CurrentScreen::Game, false, false, false);
+        let panel = context_stack(CurrentScreen::Game, false, false, true);
+        assert_eq!(panel, vec![BindingContext::Menu]);
+
+        // Tab is the map key while looking around, and opening the panel with it must not
+        // also move the focus on within the panel
+        let tab = key(KeyCode::Tab);
+        assert_eq!(
+            router.route(&look, tab, true, false).action(),
+            Some(Action::Game(GameKey::MapView))
+        );
+        assert_eq!(router.route(&panel, tab, true, true), Routed::Stale);
+        assert_eq!(
+            router.route(&panel, tab, false, false).action(),
+            Some(Action::Game(GameKey::MapView))
+        );
+
+        // In the panel the arrows pick rows rather than walk
+        assert_eq!(
+            router
+                .route(&panel, key(KeyCode::ArrowUp), true, false)
+                .action(),
+            Some(Action::NavigateUp)
+        );
+        assert_eq!(
+            router.route(&panel, tab, true, false).action(),
+            Some(Action::SwitchFocus)
+        );
+        assert_eq!(
+            router
+                .route(&panel, key(KeyCode::KeyW), true, false)
+                .action(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_conflicts_are_only_checked_within_a_context() {
+        let mut router = InputRouter::new();
+
+        // Escape is bound in text entry, menus, and gameplay at once, so a context can take a
+        // key another context uses
+        assert_eq!(
+            router.rebind(
+                BindingContext::Gameplay,
+                Action::Game(GameKey::Interact),
+                KeyCode::Enter,
+            ),
+            Ok(())
+        );
+
+        // But not one of its own
+        assert_eq!(
+            router.rebind(
+                BindingContext::Gameplay,
+                Action::Game(GameKey::Interact),
+                KeyCode::KeyW,
+            ),
+            Err(Conflict {
+                context: BindingContext::Gameplay,
+                key: KeyCode::KeyW,
+                action: Action::Game(GameKey::Interact),
+                bound: Action::Game(GameKey::MoveForward),
+            })
+        );
+        assert_eq!(
+            router
+                .bindings(BindingContext::Gameplay)
+                .keys_for(Action::Game(GameKey::Interact))
+                .collect::<Vec<_>>(),
+            vec![KeyCode::Enter]
+        );
+        let conflict = router
+            .rebind(BindingContext::Menu, Action::Back, KeyCode::Enter)
+            .unwrap_err();
+        assert_eq!(conflict.bound, Action::Confirm);
+        assert_eq!(conflict.to_string(), "Enter is taken by Confirm");
+    }
+
+    #[test]
+    fn test_every_screen_has_a_base_context() {
+        use CurrentScreen::*;
+        for screen in [Game, Loading, NewGame, ExitReached] {
+            assert_eq!(
+                context_stack(screen, false, false, false),
+                vec![BindingContext::Gameplay]
+            );
+        }
+        for screen in [Title, Pause, GameOver, UpgradeMenu, Codex, Error] {
+            assert_eq!(
+                context_stack(screen, false, false, false),
+                vec![BindingContext::Menu]
+            );
+        }
+        assert_eq!(
+            context_stack(Pause, true, true, false),
+            vec![
+                BindingContext::TextEntry,
+                BindingContext::Console,
+                BindingContext::Menu
+            ]
+        );
+    }
+
+    #[test]
+    fn test_rebinding_onto_a_fixed_key_is_refused() {
+        let mut router = InputRouter::new();
+        assert_eq!(
+            router.rebind(
+                BindingContext::Gameplay,
+                Action::Game(GameKey::Interact),
+                KeyCode::KeyC,
+            ),
+            Err(Conflict {
+                context: BindingContext::Gameplay,
+                key: KeyCode::KeyC,
+                action: Action::Game(GameKey::Interact),
+                bound: Action::Game(GameKey::ToggleTuningPanel),
+            })
+        );
+
+        // The fixed binding keeps the key, Interact keeps its own, and nothing is saved
+        let stack = context_stack(CurrentScreen::Game, false, false, false);
+        assert_eq!(
+            router.route(&stack, key(KeyCode::KeyC), true, false),
+            Routed::Action {
+                context: BindingContext::Gameplay,
+                action: Action::Game(GameKey::ToggleTuningPanel),
+            }
+        );
+        assert_eq!(
+            router
+                .bindings(BindingContext::Gameplay)
+                .action_for(KeyCode::KeyE),
+            Some(Action::Game(GameKey::Interact))
+        );
+        assert!(router.rebound.is_empty());
+
+        // Moving an action onto one of its own keys drops the others
+        let sprint = Action::Game(GameKey::Sprint);
+        assert_eq!(
+            router.rebind(BindingContext::Gameplay, sprint, KeyCode::ShiftRight),
+            Ok(())
+        );
+        assert_eq!(
+            router
+                .bindings(BindingContext::Gameplay)
+                .keys_for(sprint)
+                .collect::<Vec<_>>(),
+            vec![KeyCode::ShiftRight]
+        );
+    }
+
+    #[test]
+    fn test_rebindings_survive_a_save_and_load() {
+        let dir = std::env::temp_dir().join("mirador_bindings_round_trip");
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("bindings.json");
+
+        let mut router = InputRouter::new();
+        let gameplay = BindingContext::Gameplay;
+        router
+            .rebind(gameplay, Action::Game(GameKey::Sprint), KeyCode::KeyF)
+            .unwrap();
+        router
+            .rebind(gameplay, Action::Game(GameKey::Escape), KeyCode::KeyQ)
+            .unwrap();
+        router
+            .rebind(gameplay, Action::Game(GameKey::Escape), KeyCode::KeyP)
+            .unwrap();
+        router.save_to(&path).unwrap();
+        assert!(!dir.join("bindings.json.tmp").exists());
+
+        let loaded = InputRouter::load_from(&path);
+        assert_eq!(loaded.bindings(gameplay), router.bindings(gameplay));
+        assert_eq!(loaded.rebound.len(), 2);
+        assert_eq!(
+            loaded.bindings(gameplay).action_for(KeyCode::KeyP),
+            Some(Action::Game(GameKey::Escape))
+        );
+
+        // A saved binding that clashes with a default, e.g. after the defaults changed, is
+        // skipped and the rest still load
+        let clashing = vec![
+            Rebinding {
+                context: gameplay,
+                action: Action::Game(GameKey::Interact),
+                key: KeyCode::KeyC,
+            },
+            Rebinding {
+                context: gameplay,
+                action: Action::Game(GameKey::ToggleMinimap),
+                key: KeyCode::KeyN,
+            },
+        ];
+        fs::write(&path, serde_json::to_string(&clashing).unwrap()).unwrap();
+        let loaded = InputRouter::load_from(&path);
+        assert_eq!(loaded.rebound, clashing[1..]);
+        assert_eq!(
+            loaded.bindings(gameplay).action_for(KeyCode::KeyE),
+            Some(Action::Game(GameKey::Interact))
+        );
+
+        // A damaged or missing file gives the defaults
+        fs::write(&path, "not json").unwrap();
+        assert!(InputRouter::load_from(&path).rebound.is_empty());
+        assert_eq!(
+            InputRouter::load_from(&dir.join("missing.json")).bindings(gameplay),
+            InputRouter::new().bindings(gameplay)
+        );
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_default_bindings_have_no_conflicts() {
+        for context in BindingContext::ALL {
+            let set = BindingSet::defaults(context);
+            for (i, (key, action)) in set.bindings.iter().enumerate() {
+                assert!(
+                    set.bindings[i + 1..].iter().all(|(other, _)| other != key),
+                    "{:?} binds {:?} to {:?} and another action",
+                    context,
+                    key,
+                    action
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_key_labels() {
+        assert_eq!(key_label(KeyCode::KeyW), "W");
+        assert_eq!(key_label(KeyCode::Digit1), "1");
+        assert_eq!(key_label(KeyCode::Backquote), "`");
+        assert_eq!(key_label(KeyCode::ShiftLeft), "Left Shift");
+        assert_eq!(key_label(KeyCode::Escape), "Escape");
+    }
+}
@@ -0,0 +1,70 @@
+//! Slow "breathing" of the maze walls on deep levels.
+//!
+//! From [`BREATH_START_LEVEL`] on, the main shader pushes wall vertices back and forth along
+//! their normal with a slow sine, each wall corner on its own phase. The depth of the swell is
+//! the level's [`wall_breath_for_level`] amplitude, which grows with depth up to
+//! [`MAX_WALL_BREATH`]. Only the drawn walls move: collision keeps using the static faces, so
+//! the amplitude is also the largest gap between what the player sees and what they bump into.
+//! The reduce-effects setting keeps the walls still.
+//!
+//! # Usage
+//!
+//! ```rust,ignore
+//! game_state.wall_breath = wall_breath_for_level(level);
+//! ```
+
+/// First level whose walls breathe.
+pub const BREATH_START_LEVEL: i32 = 6;
+
+/// Level from which walls breathe at full [`MAX_WALL_BREATH`] depth.
+pub const BREATH_FULL_LEVEL: i32 = 15;
+
+/// Farthest a drawn wall may move off its collision face, in world units.
+///
+/// This is the accepted collision/visual mismatch bound. It stays below the player's
+/// collision radius ([`DEFAULT_COLLISION_RADIUS`](crate::game::dimensions::DEFAULT_COLLISION_RADIUS)),
+/// so a wall swelling toward the player can never reach the camera or hide the gap the
+/// player is stopped at, and on ~120 unit cells it reads as unease rather than a moved wall.
+/// Raise it past that radius and players will see through walls they are pressed against.
+pub const MAX_WALL_BREATH: f32 = 3.0;
+
+const _: () = assert!(MAX_WALL_BREATH < crate::game::dimensions::DEFAULT_COLLISION_RADIUS);
+
+/// Returns how far the walls of a level breathe, in world units.
+///
+/// # Arguments
+/// * `level` - The level being played
+///
+/// # Returns
+/// 0.0 before [`BREATH_START_LEVEL`], then growing evenly to [`MAX_WALL_BREATH`] at
+/// [`BREATH_FULL_LEVEL`] and staying there
+pub fn wall_breath_for_level(level: i32) -> f32 {
+    if level < BREATH_START_LEVEL {
+        return 0.0;
+    }
+    let steps = (BREATH_FULL_LEVEL - BREATH_START_LEVEL + 1) as f32;
+    let progress = ((level - BREATH_START_LEVEL + 1) as f32 / steps).min(1.0);
+    MAX_WALL_BREATH * progress
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_walls_start_breathing_on_deep_levels_and_cap() {
+        for level in 1..BREATH_START_LEVEL {
+            assert_eq!(wall_breath_for_level(level), 0.0);
+        }
+        assert!(wall_breath_for_level(BREATH_START_LEVEL) > 0.0);
+
+        let mut previous = 0.0;
+        for level in BREATH_START_LEVEL..=BREATH_FULL_LEVEL {
+            let breath = wall_breath_for_level(level);
+            assert!(breath > previous, "level {} breathes {}", level, breath);
+            previous = breath;
+        }
+        assert_eq!(wall_breath_for_level(BREATH_FULL_LEVEL), MAX_WALL_BREATH);
+        assert_eq!(wall_breath_for_level(1000), MAX_WALL_BREATH);
+    }
+}
@@ -0,0 +1,224 @@
+//! Capture sequence played when the enemy catches the player.
+//!
+//! Being caught used to cut straight to the game over screen. Instead, a short
+//! [`CaptureSequence`] now plays first: the enemy lunges at the camera while the screen edges
+//! darken, then its face flashes over the whole screen before the game over screen takes over.
+//! The sequence can't be paused or skipped, and it takes under a second.
+//!
+//! With reduced effects turned on, a fast fade to black replaces the face flash.
+//!
+//! # Usage
+//!
+//! ```rust,ignore
+//! let mut capture = CaptureSequence::new(enemy_position, eye, player.view_direction());
+//!
+//! // Once per frame
+//! if capture.advance(delta_time) == CapturePhase::Finished {
+//!     // Show the game over screen
+//! }
+//! enemy.pathfinder.position = capture.enemy_position();
+//! let overlay = capture.overlay(settings.reduce_effects);
+//! ```
+
+/// Seconds the enemy takes to lunge at the camera.
+pub const LUNGE_DURATION: f32 = 0.4;
+
+/// Seconds the enemy's face flashes over the screen after the lunge.
+pub const FLASH_DURATION: f32 = 0.5;
+
+/// How far in front of the player's eye the lunge stops, in world units.
+///
+/// Close enough that the enemy fills most of the view without the camera ending up inside it.
+pub const LUNGE_STOP_DISTANCE: f32 = 45.0;
+
+/// Fraction of [`FLASH_DURATION`] the reduced-effects fade takes to reach black.
+const FADE_FRACTION: f32 = 0.4;
+
+/// Stages of the capture sequence.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CapturePhase {
+    /// The enemy is lunging at the camera and the screen edges are darkening
+    Lunge,
+    /// The enemy's face is flashing over the screen, or the screen is fading to black
+    Flash,
+    /// The sequence is over and the game over screen should be shown
+    Finished,
+}
+
+/// How strongly each capture overlay is drawn this frame, each from 0.0 to 1.0.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct CaptureOverlay {
+    /// How dark the screen edges are
+    pub vignette: f32,
+    /// Opacity of the full-screen enemy face
+    pub face: f32,
+    /// How far the whole screen has faded to black
+    pub fade: f32,
+    /// Seconds since the sequence started, for animating the face distortion
+    pub time: f32,
+}
+
+/// State of a capture sequence in progress.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CaptureSequence {
+    /// Seconds since the enemy caught the player
+    elapsed: f32,
+    /// Where the enemy was when it caught the player
+    from: [f32; 3],
+    /// Point just in front of the camera where the lunge ends
+    to: [f32; 3],
+}
+
+impl CaptureSequence {
+    /// Starts a capture sequence.
+    ///
+    /// # Arguments
+    /// * `enemy_position` - Where the enemy was when it caught the player
+    /// * `eye` - World position of the player's camera
+    /// * `view_direction` - Unit direction the player is looking in
+    pub fn new(enemy_position: [f32; 3], eye: [f32; 3], view_direction: [f32; 3]) -> Self {
+        let to = [
+            eye[0] + view_direction[0] * LUNGE_STOP_DISTANCE,
+            eye[1] + view_direction[1] * LUNGE_STOP_DISTANCE,
+            eye[2] + view_direction[2] * LUNGE_STOP_DISTANCE,
+        ];
+        Self {
+            elapsed: 0.0,
+            from: enemy_position,
+            to,
+        }
+    }
+
+    /// Advances the sequence by one frame.
+    ///
+    /// # Arguments
+    /// * `delta_time` - Seconds since the last frame; negative and non-finite values are ignored
+    ///
+    /// # Returns
+    /// The phase the sequence is in after advancing
+    pub fn advance(&mut self, delta_time: f32) -> CapturePhase {
+        if delta_time.is_finite() && delta_time > 0.0 {
+            self.elapsed += delta_time;
+        }
+        self.phase()
+    }
+
+    /// Returns the phase the sequence is in.
+    pub fn phase(&self) -> CapturePhase {
+        if self.elapsed < LUNGE_DURATION {
+            CapturePhase::Lunge
+        } else if self.elapsed < LUNGE_DURATION + FLASH_DURATION {
+            CapturePhase::Flash
+        } else {
+            CapturePhase::Finished
+        }
+    }
+
+    /// Returns how far through the lunge the enemy is, from 0.0 to 1.0.
+    ///
+    /// Eased in, so the enemy starts slowly and snaps the last of the way toward the camera.
+    pub fn lunge_progress(&self) -> f32 {
+        let t = (self.elapsed / LUNGE_DURATION).clamp(0.0, 1.0);
+        t * t * t
+    }
+
+    /// Returns where the enemy should be drawn this frame.
+    pub fn enemy_position(&self) -> [f32; 3] {
+        let t = self.lunge_progress();
+        [
+            self.from[0] + (self.to[0] - self.from[0]) * t,
+            self.from[1] + (self.to[1] - self.from[1]) * t,
+            self.from[2] + (self.to[2] - self.from[2]) * t,
+        ]
+    }
+
+    /// Works out how strongly to draw each overlay this frame.
+    ///
+    /// # Arguments
+    /// * `reduce_effects` - Whether the player has asked for reduced visual effects, which
+    ///   replaces the face flash with a fade to black
+    pub fn overlay(&self, reduce_effects: bool) -> CaptureOverlay {
+        // The edges darken quickly over the lunge and stay dark through the flash
+        let lunge = (self.elapsed / LUNGE_DURATION).clamp(0.0, 1.0);
+        let vignette = lunge * (2.0 - lunge);
+        let flashing = self.phase() != CapturePhase::Lunge;
+
+        let (face, fade) = match (flashing, reduce_effects) {
+            (false, _) => (0.0, 0.0),
+            (true, false) => (1.0, 0.0),
+            (true, true) => {
+                let flash_elapsed = self.elapsed - LUNGE_DURATION;
+                let fade = (flash_elapsed / (FLASH_DURATION * FADE_FRACTION)).clamp(0.0, 1.0);
+                (0.0, fade)
+            }
+        };
+
+        CaptureOverlay {
+            vignette,
+            face,
+            fade,
+            time: self.elapsed,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sequence() -> CaptureSequence {
+        CaptureSequence::new([100.0, 30.0, 0.0], [0.0, 30.0, 0.0], [1.0, 0.0, 0.0])
+    }
+
+    #[test]
+    fn test_phases_advance_in_order() {
+        let mut capture = sequence();
+        assert_eq!(capture.phase(), CapturePhase::Lunge);
+        assert_eq!(capture.advance(LUNGE_DURATION * 0.5), CapturePhase::Lunge);
+        assert_eq!(capture.advance(LUNGE_DURATION * 0.5), CapturePhase::Flash);
+        assert_eq!(capture.advance(FLASH_DURATION * 0.9), CapturePhase::Flash);
+        assert_eq!(
+            capture.advance(FLASH_DURATION * 0.2),
+            CapturePhase::Finished
+        );
+
+        // Bad frame times never move the sequence, and a long stall finishes it
+        let mut capture = sequence();
+        assert_eq!(capture.advance(-1.0), CapturePhase::Lunge);
+        assert_eq!(capture.advance(f32::NAN), CapturePhase::Lunge);
+        assert_eq!(capture.advance(5.0), CapturePhase::Finished);
+    }
+
+    #[test]
+    fn test_lunge_ends_just_in_front_of_the_camera() {
+        let mut capture = sequence();
+        assert_eq!(capture.enemy_position(), [100.0, 30.0, 0.0]);
+
+        capture.advance(LUNGE_DURATION * 0.5);
+        let halfway = capture.enemy_position();
+        assert!(halfway[0] < 100.0 && halfway[0] > LUNGE_STOP_DISTANCE);
+
+        capture.advance(LUNGE_DURATION);
+        assert_eq!(capture.enemy_position(), [LUNGE_STOP_DISTANCE, 30.0, 0.0]);
+    }
+
+    #[test]
+    fn test_reduced_effects_fade_to_black_instead_of_flashing() {
+        let mut capture = sequence();
+        let lunge = capture.overlay(false);
+        assert_eq!((lunge.face, lunge.fade), (0.0, 0.0));
+        assert_eq!(lunge.vignette, 0.0);
+
+        capture.advance(LUNGE_DURATION + FLASH_DURATION * 0.1);
+        let flash = capture.overlay(false);
+        assert_eq!((flash.face, flash.fade), (1.0, 0.0));
+        assert_eq!(flash.vignette, 1.0);
+
+        let reduced = capture.overlay(true);
+        assert_eq!(reduced.face, 0.0);
+        assert!(reduced.fade > 0.0 && reduced.fade < 1.0);
+
+        capture.advance(FLASH_DURATION * 0.5);
+        assert_eq!(capture.overlay(true).fade, 1.0);
+    }
+}
@@ -0,0 +1,552 @@
+//! Shareable challenge codes for the Mirador game.
+//!
+//! A [`Challenge`] is everything needed to set up the same run on another machine: the run
+//! seed, whether it is hardcore, how hard the enemy pushes, which [`DailyModifier`]s apply, and
+//! an optional last level. [`Challenge::encode`] packs it into a short code the player can
+//! paste to a friend, and [`Challenge::decode`] reads it back on the title screen. No server is
+//! involved; the code is the whole challenge.
+//!
+//! # Format
+//!
+//! A code is [`CODE_LENGTH`] characters of RFC 4648 base32 (`A`-`Z` and `2`-`7`), which is safe
+//! in URLs and file names and read back in any case. It packs [`CODE_BYTES`] bytes:
+//!
+//! | Bytes  | Field                                                              |
+//! |--------|--------------------------------------------------------------------|
+//! | 0      | Format version, [`CHALLENGE_VERSION`]                              |
+//! | 1      | Difficulty in bits 0-1, hardcore in bit 2                          |
+//! | 2      | One bit per modifier, in [`DailyModifier::ALL`] order              |
+//! | 3-4    | Last level as a little-endian `u16`, 0 for none                    |
+//! | 5-12   | Run seed as a little-endian `u64`                                  |
+//! | 13-14  | CRC-16/CCITT-FALSE of bytes 0-12, little-endian                    |
+//!
+//! Every field has a fixed size and byte order, so a code means the same run on every platform.
+//! The checksum catches codes that were mistyped or cut short, and codes from a newer version
+//! of the game are refused by their version byte rather than misread.
+//!
+//! # Usage
+//!
+//! ```rust,ignore
+//! let code = challenge.encode();
+//! match Challenge::decode(&pasted) {
+//!     Ok(challenge) => println!("{}", challenge.summary()),
+//!     Err(e) => println!("{}", e),
+//! }
+//! ```
+
+use crate::game::daily::DailyModifier;
+use crate::game::director::EnemyTuning;
+use crate::game::player::Player;
+use crate::game::seed;
+use std::fmt;
+
+/// Version written into new codes; codes with a higher version are refused.
+pub const CHALLENGE_VERSION: u8 = 1;
+
+/// Bytes packed into a code, checksum included.
+pub const CODE_BYTES: usize = 15;
+
+/// Characters in a code.
+pub const CODE_LENGTH: usize = CODE_BYTES * 8 / 5;
+
+/// Label shown next to the level on the HUD during a challenge run.
+pub const CHALLENGE_LABEL: &str = "Challenge";
+
+/// Characters codes are written with, each standing for five bits.
+const ALPHABET: &[u8; 32] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+
+/// Bit of the flags byte marking a hardcore run.
+const HARDCORE_BIT: u8 = 0b100;
+
+/// Bits of the flags byte holding the difficulty.
+const DIFFICULTY_BITS: u8 = 0b011;
+
+/// How hard the enemy pushes during a challenge.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChallengeDifficulty {
+    /// The director adapts to the player, as in a regular run
+    Adaptive,
+    /// A fixed, relaxed enemy
+    Easy,
+    /// A fixed enemy at the director's neutral pressure
+    Normal,
+    /// A fixed, aggressive enemy
+    Hard,
+}
+
+impl ChallengeDifficulty {
+    /// Every difficulty, in the order the flags byte numbers them.
+    pub const ALL: [ChallengeDifficulty; 4] = [
+        ChallengeDifficulty::Adaptive,
+        ChallengeDifficulty::Easy,
+        ChallengeDifficulty::Normal,
+        ChallengeDifficulty::Hard,
+    ];
+
+    /// Returns the name shown in a challenge's summary.
+    pub fn name(self) -> &'static str {
+        match self {
+            ChallengeDifficulty::Adaptive => "Adaptive",
+            ChallengeDifficulty::Easy => "Easy",
+            ChallengeDifficulty::Normal => "Normal",
+            ChallengeDifficulty::Hard => "Hard",
+        }
+    }
+
+    /// Returns the fixed director pressure, or `None` when the director adapts.
+    pub fn pressure(self) -> Option<f32> {
+        match self {
+            ChallengeDifficulty::Adaptive => None,
+            ChallengeDifficulty::Easy => Some(-0.25),
+            ChallengeDifficulty::Normal => Some(0.0),
+            ChallengeDifficulty::Hard => Some(0.5),
+        }
+    }
+}
+
+/// Why a challenge code could not be read.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ChallengeError {
+    /// Nothing was typed
+    Empty,
+    /// A character that can't appear in a code, with its position in characters
+    InvalidCharacter {
+        /// Position of the character, counting from 0
+        index: usize,
+        /// The character itself
+        ch: char,
+    },
+    /// The code is shorter than a whole code
+    Truncated,
+    /// The checksum or a field doesn't match, so the code was changed
+    Corrupted,
+    /// The code was made by a newer version of the game
+    NewerVersion {
+        /// The code's version
+        version: u8,
+    },
+}
+
+impl fmt::Display for ChallengeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => write!(f, "Challenge code is empty"),
+            Self::InvalidCharacter { index, ch } => write!(
+                f,
+                "Challenge code can't contain '{}' (character {})",
+                ch,
+                index + 1
+            ),
+            Self::Truncated => write!(f, "Challenge code is too short; it may have been cut off"),
+            Self::Corrupted => write!(f, "Challenge code is damaged; check it was copied exactly"),
+            Self::NewerVersion { version } => write!(
+                f,
+                "Challenge code needs a newer version of Mirador (code version {}, this game reads {})",
+                version, CHALLENGE_VERSION
+            ),
+        }
+    }
+}
+
+/// A run definition that can be shared as a code.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Challenge {
+    /// Seed every level of the run is generated from
+    pub seed: u64,
+    /// Whether the run is hardcore
+    pub hardcore: bool,
+    /// How hard the enemy pushes
+    pub difficulty: ChallengeDifficulty,
+    /// Modifiers active on every level, in [`DailyModifier::ALL`] order
+    pub modifiers: Vec<DailyModifier>,
+    /// Last level of the run, or `None` to play until the run dies
+    pub level_cap: Option<u16>,
+}
+
+impl Challenge {
+    /// Describes a regular run on a seed, with no modifiers or last level.
+    ///
+    /// # Arguments
+    /// * `seed` - The run seed
+    /// * `hardcore` - Whether the run is hardcore
+    pub fn for_run(seed: u64, hardcore: bool) -> Self {
+        Self {
+            seed,
+            hardcore,
+            difficulty: ChallengeDifficulty::Adaptive,
+            modifiers: Vec::new(),
+            level_cap: None,
+        }
+    }
+
+    /// Writes the challenge as a code.
+    ///
+    /// # Returns
+    /// [`CODE_LENGTH`] uppercase base32 characters
+    pub fn encode(&self) -> String {
+        let mut bytes = Vec::with_capacity(CODE_BYTES);
+        bytes.push(CHALLENGE_VERSION);
+        let difficulty = ChallengeDifficulty::ALL
+            .iter()
+            .position(|&difficulty| difficulty == self.difficulty)
+            .expect("Every difficulty is listed") as u8;
+        bytes.push(difficulty | if self.hardcore { HARDCORE_BIT } else { 0 });
+        bytes.push(
+            DailyModifier::ALL
+                .iter()
+                .enumerate()
+                .filter(|(_, modifier)| self.modifiers.contains(modifier))
+                .fold(0u8, |bits, (i, _)| bits | 1 << i),
+        );
+        bytes.extend_from_slice(&self.level_cap.unwrap_or(0).to_le_bytes());
+        bytes.extend_from_slice(&self.seed.to_le_bytes());
+        let checksum = crc16(&bytes);
+        bytes.extend_from_slice(&checksum.to_le_bytes());
+        base32_encode(&bytes)
+    }
+
+    /// Reads a code pasted or typed by the player.
+    ///
+    /// # Arguments
+    /// * `code` - The code, in any case, with any surrounding whitespace
+    ///
+    /// # Returns
+    /// The challenge, or why the code can't be used
+    pub fn decode(code: &str) -> Result<Self, ChallengeError> {
+        let bytes = base32_decode(code.trim())?;
+        let Some(split) = bytes.len().checked_sub(2).filter(|&split| split > 0) else {
+            return Err(ChallengeError::Truncated);
+        };
+        let (payload, checksum) = bytes.split_at(split);
+        if crc16(payload).to_le_bytes() != checksum {
+            return Err(if bytes.len() < CODE_BYTES {
+                ChallengeError::Truncated
+            } else {
+                ChallengeError::Corrupted
+            });
+        }
+        let version = payload[0];
+        if version > CHALLENGE_VERSION {
+            return Err(ChallengeError::NewerVersion { version });
+        }
+        if version == 0 || bytes.len() != CODE_BYTES {
+            return Err(ChallengeError::Corrupted);
+        }
+
+        let flags = payload[1];
+        let modifier_bits = payload[2];
+        if flags & !(DIFFICULTY_BITS | HARDCORE_BIT) != 0
+            || modifier_bits >> DailyModifier::ALL.len() != 0
+        {
+            return Err(ChallengeError::Corrupted);
+        }
+        let level_cap = u16::from_le_bytes([payload[3], payload[4]]);
+        let seed = u64::from_le_bytes(payload[5..13].try_into().expect("Payload is 13 bytes"));
+        Ok(Self {
+            seed,
+            hardcore: flags & HARDCORE_BIT != 0,
+            difficulty: ChallengeDifficulty::ALL[(flags & DIFFICULTY_BITS) as usize],
+            modifiers: DailyModifier::ALL
+                .iter()
+                .enumerate()
+                .filter(|(i, _)| modifier_bits & 1 << i != 0)
+                .map(|(_, &modifier)| modifier)
+                .collect(),
+            level_cap: (level_cap > 0).then_some(level_cap),
+        })
+    }
+
+    /// Describes the challenge for the player.
+    ///
+    /// # Returns
+    /// A line such as `"Hardcore, Hard, Swift Hunter + Short Breath, seed mf9g063v08f, to level 10"`
+    pub fn summary(&self) -> String {
+        let mut parts = Vec::new();
+        if self.hardcore {
+            parts.push("Hardcore".to_string());
+        }
+        parts.push(self.difficulty.name().to_string());
+        parts.push(if self.modifiers.is_empty() {
+            "no modifiers".to_string()
+        } else {
+            self.modifiers
+                .iter()
+                .map(|modifier| modifier.name())
+                .collect::<Vec<_>>()
+                .join(" + ")
+        });
+        parts.push(format!("seed {}", seed::format_seed(self.seed)));
+        if let Some(cap) = self.level_cap {
+            parts.push(format!("to level {}", cap));
+        }
+        parts.join(", ")
+    }
+
+    /// Returns the enemy tuning for every level of the challenge.
+    ///
+    /// # Arguments
+    /// * `director_tuning` - The adaptive director's current tuning, used when the
+    ///   difficulty adapts
+    ///
+    /// # Returns
+    /// The tuning for the difficulty with the enemy modifiers applied
+    pub fn enemy_tuning(&self, director_tuning: EnemyTuning) -> EnemyTuning {
+        let mut tuning = self
+            .difficulty
+            .pressure()
+            .map_or(director_tuning, EnemyTuning::for_pressure);
+        for modifier in &self.modifiers {
+            modifier.apply_to_enemy(&mut tuning);
+        }
+        tuning
+    }
+
+    /// Applies the player modifiers to a freshly created player.
+    ///
+    /// # Arguments
+    /// * `player` - The player starting the run
+    pub fn apply_to_player(&self, player: &mut Player) {
+        for modifier in &self.modifiers {
+            modifier.apply_to_player(player);
+        }
+    }
+
+    /// Returns whether finishing a level ends the challenge.
+    ///
+    /// # Arguments
+    /// * `level` - The level just finished
+    pub fn is_last_level(&self, level: i32) -> bool {
+        self.level_cap.is_some_and(|cap| level >= cap as i32)
+    }
+}
+
+/// Computes the CRC-16/CCITT-FALSE checksum of some bytes.
+///
+/// # Arguments
+/// * `bytes` - The bytes to check
+///
+/// # Returns
+/// The checksum, identical on every platform
+fn crc16(bytes: &[u8]) -> u16 {
+    bytes.iter().fold(0xffff, |crc, &byte| {
+        (0..8).fold(crc ^ (byte as u16) << 8, |crc, _| {
+            if crc & 0x8000 != 0 {
+                crc << 1 ^ 0x1021
+            } else {
+                crc << 1
+            }
+        })
+    })
+}
+
+/// Writes bytes as base32, without padding.
+fn base32_encode(bytes: &[u8]) -> String {
+    let mut text = String::with_capacity(bytes.len().div_ceil(5) * 8);
+    let (mut buffer, mut bits) = (0u32, 0u32);
+    for &byte in bytes {
+        buffer = buffer << 8 | byte as u32;
+        bits += 8;
+        while bits >= 5 {
+            bits -= 5;
+            text.push(ALPHABET[(buffer >> bits & 0x1f) as usize] as char);
+        }
+    }
+    if bits > 0 {
+        text.push(ALPHABET[(buffer << (5 - bits) & 0x1f) as usize] as char);
+    }
+    text
+}
+
+/// Reads base32 text in any case, dropping bits left over after the last whole byte.
+fn base32_decode(text: &str) -> Result<Vec<u8>, ChallengeError> {
+    if text.is_empty() {
+        return Err(ChallengeError::Empty);
+    }
+    let mut bytes = Vec::with_capacity(text.len() * 5 / 8);
+    let (mut buffer, mut bits) = (0u32, 0u32);
+    for (index, ch) in text.chars().enumerate() {
+        let value = ALPHABET
+            .iter()
+            .position(|&letter| letter as char == ch.to_ascii_uppercase())
+            .ok_or(ChallengeError::InvalidCharacter { index, ch })?;
+        buffer = buffer << 5 | value as u32;
+        bits += 5;
+        if bits >= 8 {
+            bits -= 8;
+            bytes.push((buffer >> bits) as u8);
+        }
+    }
+    Ok(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Every combination of modifiers, as lists.
+    fn modifier_sets() -> Vec<Vec<DailyModifier>> {
+        (0u8..1 << DailyModifier::ALL.len())
+            .map(|bits| {
+                DailyModifier::ALL
+                    .iter()
+                    .enumerate()
+                    .filter(|(i, _)| bits & 1 << i != 0)
+                    .map(|(_, &modifier)| modifier)
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Packs raw bytes behind a correct checksum, as a code.
+    fn code_for(payload: &[u8]) -> String {
+        let mut bytes = payload.to_vec();
+        bytes.extend_from_slice(&crc16(payload).to_le_bytes());
+        base32_encode(&bytes)
+    }
+
+    #[test]
+    fn test_every_challenge_round_trips() {
+        for hardcore in [false, true] {
+            for difficulty in ChallengeDifficulty::ALL {
+                for modifiers in modifier_sets() {
+                    for level_cap in [None, Some(1), Some(10), Some(u16::MAX)] {
+                        for seed in [0, 1, 0x0123_4567_89ab_cdef, u64::MAX] {
+                            let challenge = Challenge {
+                                seed,
+                                hardcore,
+                                difficulty,
+                                modifiers: modifiers.clone(),
+                                level_cap,
+                            };
+                            let code = challenge.encode();
+                            assert_eq!(code.len(), CODE_LENGTH);
+                            assert!(code.bytes().all(|byte| ALPHABET.contains(&byte)));
+                            assert_eq!(Challenge::decode(&code), Ok(challenge.clone()));
+                            assert_eq!(
+                                Challenge::decode(&format!(" {} ", code.to_lowercase())),
+                                Ok(challenge)
+                            );
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_codes_are_stable() {
+        let challenge = Challenge {
+            seed: 0x0123_4567_89ab_cdef,
+            hardcore: true,
+            difficulty: ChallengeDifficulty::Hard,
+            modifiers: vec![DailyModifier::SwiftHunter, DailyModifier::ShortBreath],
+            level_cap: Some(10),
+        };
+        // Pinned so a change to the format can't go unnoticed
+        let code = challenge.encode();
+        assert_eq!(Challenge::decode(&code), Ok(challenge.clone()));
+        assert_eq!(code, "AEDRCCQA57G2XCLHIURQCZK4");
+        assert_eq!(
+            code,
+            code_for(&[
+                1, 0b111, 0b10001, 10, 0, 0xef, 0xcd, 0xab, 0x89, 0x67, 0x45, 0x23, 0x01
+            ])
+        );
+        assert_eq!(crc16(b"123456789"), 0x29b1);
+        assert_eq!(
+            challenge.summary(),
+            "Hardcore, Hard, Swift Hunter + Short Breath, seed mf9g063v08f, to level 10"
+        );
+    }
+
+    #[test]
+    fn test_truncated_and_corrupted_codes_are_refused() {
+        let code = Challenge::for_run(42, false).encode();
+        assert_eq!(Challenge::decode(""), Err(ChallengeError::Empty));
+        for len in 1..code.len() {
+            let result = Challenge::decode(&code[..len]);
+            assert_eq!(result, Err(ChallengeError::Truncated), "{} characters", len);
+        }
+        assert_eq!(
+            Challenge::decode(&format!("{}AAAAAAAA", code)),
+            Err(ChallengeError::Corrupted)
+        );
+
+        // Any one character swapped for another is caught by the checksum
+        for index in 0..code.len() {
+            for &letter in ALPHABET {
+                let mut changed = code.clone().into_bytes();
+                if changed[index] == letter {
+                    continue;
+                }
+                changed[index] = letter;
+                let changed = String::from_utf8(changed).unwrap();
+                assert!(Challenge::decode(&changed).is_err(), "{}", changed);
+            }
+        }
+
+        assert_eq!(
+            Challenge::decode("ABCD1FGH"),
+            Err(ChallengeError::InvalidCharacter { index: 4, ch: '1' })
+        );
+        // Fields outside the format are refused even with a good checksum
+        let unknown_flag = code_for(&[1, 0b1000, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0]);
+        assert_eq!(
+            Challenge::decode(&unknown_flag),
+            Err(ChallengeError::Corrupted)
+        );
+        let unknown_modifier = code_for(&[1, 0, 0b100000, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0]);
+        assert_eq!(
+            Challenge::decode(&unknown_modifier),
+            Err(ChallengeError::Corrupted)
+        );
+    }
+
+    #[test]
+    fn test_codes_from_a_newer_version_are_refused() {
+        let mut payload = vec![CHALLENGE_VERSION + 1, 0, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0];
+        let newer = code_for(&payload);
+        let error = Challenge::decode(&newer).unwrap_err();
+        assert_eq!(
+            error,
+            ChallengeError::NewerVersion {
+                version: CHALLENGE_VERSION + 1
+            }
+        );
+        assert!(error.to_string().contains("newer version"));
+
+        // Even when the newer format is longer
+        payload.extend_from_slice(&[7; 6]);
+        assert_eq!(
+            Challenge::decode(&code_for(&payload)),
+            Err(ChallengeError::NewerVersion {
+                version: CHALLENGE_VERSION + 1
+            })
+        );
+    }
+
+    #[test]
+    fn test_challenge_rules() {
+        let mut challenge = Challenge::for_run(3, false);
+        assert_eq!(
+            challenge.enemy_tuning(EnemyTuning::for_pressure(0.3)),
+            EnemyTuning::for_pressure(0.3)
+        );
+        assert!(!challenge.is_last_level(1000));
+
+        challenge.difficulty = ChallengeDifficulty::Hard;
+        challenge.modifiers = vec![DailyModifier::SwiftHunter, DailyModifier::HeavyLegs];
+        challenge.level_cap = Some(5);
+        let tuning = challenge.enemy_tuning(EnemyTuning::NEUTRAL);
+        let hard = EnemyTuning::for_pressure(0.5);
+        assert!((tuning.speed_multiplier - hard.speed_multiplier * 1.1).abs() < 1e-6);
+        assert_eq!(tuning.alert_radius_multiplier, hard.alert_radius_multiplier);
+        assert!(!challenge.is_last_level(4));
+        assert!(challenge.is_last_level(5));
+
+        let mut player = Player::new();
+        let base_speed = player.base_speed;
+        challenge.apply_to_player(&mut player);
+        assert!((player.base_speed - base_speed * 0.9).abs() < 1e-6);
+    }
+}
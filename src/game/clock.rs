@@ -0,0 +1,86 @@
+//! Tick-based game clock for Mirador.
+//!
+//! This module provides [`GameClock`], a monotonic clock that only moves when the game loop
+//! advances it by the frame's delta time. Game logic that measures time against a `GameClock`
+//! instead of [`std::time::Instant::now`] can be paused by simply not advancing it, and can be
+//! unit tested by advancing it by exact amounts instead of sleeping.
+//!
+//! # Usage
+//!
+//! ```rust,ignore
+//! let mut clock = GameClock::new();
+//! let start = clock.now();
+//!
+//! // Once per frame while the clock should run
+//! clock.advance_secs(game_state.delta_time);
+//!
+//! let elapsed = clock.now() - start;
+//! ```
+
+use std::time::Duration;
+
+/// A monotonic clock advanced manually by the game loop.
+///
+/// Readings are measured from when the clock was created and never go backwards.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct GameClock {
+    /// Total time the clock has been advanced by
+    now: Duration,
+}
+
+impl GameClock {
+    /// Creates a clock reading zero.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the current clock reading.
+    pub fn now(&self) -> Duration {
+        self.now
+    }
+
+    /// Moves the clock forward.
+    ///
+    /// # Arguments
+    /// * `delta` - How far to advance the clock
+    pub fn advance(&mut self, delta: Duration) {
+        self.now += delta;
+    }
+
+    /// Moves the clock forward by a frame delta time in seconds.
+    ///
+    /// Negative or non-finite values are ignored so a bad frame time can never move the clock
+    /// backwards or poison it.
+    ///
+    /// # Arguments
+    /// * `delta_time` - Seconds since the last frame
+    pub fn advance_secs(&mut self, delta_time: f32) {
+        self.advance(Self::frame_delta(delta_time));
+    }
+
+    /// Converts a frame delta time in seconds to a [`Duration`].
+    ///
+    /// # Arguments
+    /// * `delta_time` - Seconds since the last frame
+    ///
+    /// # Returns
+    /// The delta as a duration, or zero if it is negative or not finite
+    pub fn frame_delta(delta_time: f32) -> Duration {
+        Duration::try_from_secs_f32(delta_time).unwrap_or(Duration::ZERO)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_invalid_frame_times_are_ignored() {
+        let mut clock = GameClock::new();
+        clock.advance_secs(0.25);
+        clock.advance_secs(-1.0);
+        clock.advance_secs(f32::NAN);
+        clock.advance_secs(f32::INFINITY);
+        assert_eq!(clock.now(), Duration::from_millis(250));
+    }
+}
@@ -0,0 +1,486 @@
+//! Maze codex: lifetime stats for every kind of maze the player has run.
+//!
+//! Levels are grouped by [`MazeKind`], the maze size and the algorithm that generated it. For
+//! each kind the [`Codex`] counts completions and deaths, and keeps the best completion time
+//! and the longest time the player lasted in one level. It is saved as JSON in the data
+//! directory (see [`crate::paths`]) after every level that ends in a completion or a death.
+//!
+//! The cosmetics unlocked by collecting anomalies (see [`crate::game::anomaly`]) are saved in the
+//! same file, right after they are found.
+//!
+//! When a completion sets a new best time, the maze of that run is saved next to the codex as a
+//! small PNG thumbnail, one file per kind. Thumbnails are read back on demand by the codex
+//! screen, which shows a placeholder when one is missing or can't be decoded.
+//!
+//! # Usage
+//!
+//! ```rust,ignore
+//! let mut codex = Codex::load();
+//! let kind = MazeKind::new(25, 25, MazeAlgorithm::Kruskal);
+//! if codex.record(kind, LevelOutcome::Completed, 42.5) {
+//!     codex::save_thumbnail(&paths::get().codex_thumbnail(&kind.slug()), &rgba, (126, 126))?;
+//! }
+//! codex.save()?;
+//! ```
+
+use crate::game::anomaly::UnlockRegistry;
+use crate::paths;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+/// Largest thumbnail side that will be decoded, in pixels; larger files are treated as corrupt.
+pub const MAX_THUMBNAIL_SIZE: u32 = 512;
+
+/// Algorithm that carved a maze.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum MazeAlgorithm {
+    /// Randomized Kruskal's algorithm on a single floor
+    Kruskal,
+    /// Two floors carved with Kruskal's algorithm, joined by stairwells
+    StackedKruskal,
+    /// Regions carved with Kruskal's algorithm in parallel, then joined through openings
+    Divided,
+}
+
+impl MazeAlgorithm {
+    /// Every algorithm, in the order the sandbox cycles through them.
+    pub const ALL: [MazeAlgorithm; 3] = [
+        MazeAlgorithm::Kruskal,
+        MazeAlgorithm::StackedKruskal,
+        MazeAlgorithm::Divided,
+    ];
+
+    /// Returns the name shown on the codex cards.
+    pub fn name(self) -> &'static str {
+        match self {
+            MazeAlgorithm::Kruskal => "Kruskal",
+            MazeAlgorithm::StackedKruskal => "Two-floor Kruskal",
+            MazeAlgorithm::Divided => "Parallel division",
+        }
+    }
+
+    /// Returns the short name used in thumbnail file names.
+    fn slug(self) -> &'static str {
+        match self {
+            MazeAlgorithm::Kruskal => "kruskal",
+            MazeAlgorithm::StackedKruskal => "stacked-kruskal",
+            MazeAlgorithm::Divided => "divided",
+        }
+    }
+}
+
+/// A maze size and generation algorithm, the key codex entries are grouped by.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct MazeKind {
+    /// Width of the maze in cells
+    pub width: usize,
+    /// Height of the maze in cells
+    pub height: usize,
+    /// Algorithm that carved the maze
+    pub algorithm: MazeAlgorithm,
+}
+
+impl MazeKind {
+    /// Creates a maze kind.
+    ///
+    /// # Arguments
+    /// * `width` - Width of the maze in cells
+    /// * `height` - Height of the maze in cells
+    /// * `algorithm` - Algorithm that carved the maze
+    pub fn new(width: usize, height: usize, algorithm: MazeAlgorithm) -> Self {
+        Self {
+            width,
+            height,
+            algorithm,
+        }
+    }
+
+    /// Returns the title shown on the kind's codex card, e.g. `25x25 Kruskal`.
+    pub fn label(&self) -> String {
+        format!("{}x{} {}", self.width, self.height, self.algorithm.name())
+    }
+
+    /// Returns the name of the kind's thumbnail file, without an extension.
+    pub fn slug(&self) -> String {
+        format!("{}x{}-{}", self.width, self.height, self.algorithm.slug())
+    }
+
+    /// Orders kinds by maze area, then by algorithm, so the codex lists small mazes first.
+    fn sort_key(&self) -> (usize, usize, MazeAlgorithm) {
+        (self.width * self.height, self.width, self.algorithm)
+    }
+}
+
+/// How a recorded level ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LevelOutcome {
+    /// The player reached the exit
+    Completed,
+    /// The timer ran out or the enemy caught the player
+    Died,
+}
+
+/// Lifetime stats for one kind of maze.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CodexEntry {
+    /// The maze size and algorithm
+    pub kind: MazeKind,
+    /// Levels of this kind the player escaped
+    pub completions: u32,
+    /// Levels of this kind that ended in a death
+    pub deaths: u32,
+    /// Fastest completion, in seconds
+    pub best_time: Option<f32>,
+    /// Longest time spent in one level of this kind, however it ended, in seconds
+    pub longest_survival: f32,
+}
+
+impl CodexEntry {
+    /// Creates an entry with nothing recorded yet.
+    ///
+    /// # Arguments
+    /// * `kind` - The maze size and algorithm
+    fn new(kind: MazeKind) -> Self {
+        Self {
+            kind,
+            completions: 0,
+            deaths: 0,
+            best_time: None,
+            longest_survival: 0.0,
+        }
+    }
+
+    /// Returns a one-line fun fact drawn from the entry's stats.
+    pub fn fun_fact(&self) -> String {
+        let runs = self.completions + self.deaths;
+        match (self.completions, self.deaths) {
+            (0, 0) => "Uncharted".to_string(),
+            (0, deaths) => format!("Still unbeaten after {} attempts", deaths),
+            (completions, 0) => format!("Escaped all {} times", completions),
+            (completions, _) => format!(
+                "Escaped {}% of the time",
+                (completions as f32 / runs as f32 * 100.0).round() as u32
+            ),
+        }
+    }
+}
+
+/// Formats a duration for the codex cards.
+///
+/// # Arguments
+/// * `seconds` - The duration in seconds
+///
+/// # Returns
+/// The duration as `M:SS.s`
+pub fn format_duration(seconds: f32) -> String {
+    let tenths = (seconds.max(0.0) * 10.0).round() as u32;
+    format!("{}:{:02}.{}", tenths / 600, tenths / 10 % 60, tenths % 10)
+}
+
+/// The level being played, kept until its result is added to the codex.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CodexLevel {
+    /// The maze size and algorithm
+    pub kind: MazeKind,
+    /// Top-down picture of the maze, saved as the thumbnail if the level is a new best
+    pub thumbnail: Vec<u8>,
+    /// Width and height of the picture in pixels
+    pub dimensions: (u32, u32),
+}
+
+/// Stats for every kind of maze played, persisted between sessions.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Codex {
+    /// One entry per kind of maze, smallest mazes first
+    pub entries: Vec<CodexEntry>,
+    /// Cosmetics unlocked by collecting anomalies
+    pub unlocks: UnlockRegistry,
+}
+
+impl Codex {
+    /// Loads the codex from the data directory.
+    ///
+    /// # Returns
+    /// The saved codex, or an empty one if the file is missing or invalid
+    pub fn load() -> Self {
+        Self::load_from(&paths::get().codex())
+    }
+
+    /// Loads the codex from a specific file.
+    ///
+    /// # Arguments
+    /// * `path` - The codex file to read
+    ///
+    /// # Returns
+    /// The saved codex, or an empty one if the file is missing or invalid
+    pub fn load_from(path: &Path) -> Self {
+        let contents = match fs::read_to_string(path) {
+            Ok(contents) => contents,
+            Err(_) => return Self::default(),
+        };
+
+        match serde_json::from_str::<Self>(&contents) {
+            Ok(mut codex) => {
+                codex.sort();
+                codex
+            }
+            Err(e) => {
+                eprintln!(
+                    "Failed to parse codex file {}: {}, starting fresh",
+                    path.display(),
+                    e
+                );
+                Self::default()
+            }
+        }
+    }
+
+    /// Saves the codex to the data directory.
+    ///
+    /// # Returns
+    /// `Ok(())` on success, or an error message if the file could not be written
+    pub fn save(&self) -> Result<(), String> {
+        self.save_to(&paths::get().codex())
+    }
+
+    /// Saves the codex to a specific file.
+    ///
+    /// # Arguments
+    /// * `path` - The codex file to write
+    ///
+    /// # Returns
+    /// `Ok(())` on success, or an error message if the file could not be written
+    pub fn save_to(&self, path: &Path) -> Result<(), String> {
+        let contents = serde_json::to_string_pretty(self)
+            .map_err(|e| format!("Failed to serialize codex: {}", e))?;
+        fs::write(path, contents)
+            .map_err(|e| format!("Failed to write codex to {}: {}", path.display(), e))
+    }
+
+    /// Adds the result of one level to its kind's entry, creating the entry if needed.
+    ///
+    /// # Arguments
+    /// * `kind` - The maze size and algorithm of the level
+    /// * `outcome` - How the level ended
+    /// * `seconds` - Time spent in the level
+    ///
+    /// # Returns
+    /// `true` if the level was a completion faster than any before it
+    pub fn record(&mut self, kind: MazeKind, outcome: LevelOutcome, seconds: f32) -> bool {
+        let index = match self.entries.iter().position(|entry| entry.kind == kind) {
+            Some(index) => index,
+            None => {
+                self.entries.push(CodexEntry::new(kind));
+                self.sort();
+                self.entries
+                    .iter()
+                    .position(|entry| entry.kind == kind)
+                    .expect("Entry was just added")
+            }
+        };
+        let entry = &mut self.entries[index];
+        entry.longest_survival = entry.longest_survival.max(seconds);
+
+        match outcome {
+            LevelOutcome::Completed => {
+                entry.completions += 1;
+                let new_best = entry.best_time.is_none_or(|best| seconds < best);
+                if new_best {
+                    entry.best_time = Some(seconds);
+                }
+                new_best
+            }
+            LevelOutcome::Died => {
+                entry.deaths += 1;
+                false
+            }
+        }
+    }
+
+    /// Sorts the entries smallest maze first.
+    fn sort(&mut self) {
+        self.entries.sort_by_key(|entry| entry.kind.sort_key());
+    }
+}
+
+/// Saves a maze thumbnail as a PNG, creating its directory if needed.
+///
+/// # Arguments
+/// * `path` - The thumbnail file to write
+/// * `rgba` - Tightly packed RGBA8 pixels, row-major from the top-left
+/// * `dimensions` - Width and height of the image in pixels
+///
+/// # Returns
+/// `Ok(())` on success, or an error message if the image could not be written
+pub fn save_thumbnail(path: &Path, rgba: &[u8], dimensions: (u32, u32)) -> Result<(), String> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)
+            .map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+    }
+    image::save_buffer_with_format(
+        path,
+        rgba,
+        dimensions.0,
+        dimensions.1,
+        image::ExtendedColorType::Rgba8,
+        image::ImageFormat::Png,
+    )
+    .map_err(|e| format!("Failed to write thumbnail {}: {}", path.display(), e))
+}
+
+/// Reads a maze thumbnail saved by [`save_thumbnail`].
+///
+/// # Arguments
+/// * `path` - The thumbnail file to read
+///
+/// # Returns
+/// The RGBA8 pixels and their dimensions, or an error message if the file is missing, can't be
+/// decoded, or is larger than [`MAX_THUMBNAIL_SIZE`]
+pub fn load_thumbnail(path: &Path) -> Result<(Vec<u8>, (u32, u32)), String> {
+    let bytes = fs::read(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    let image = image::load_from_memory_with_format(&bytes, image::ImageFormat::Png)
+        .map_err(|e| format!("Failed to decode {}: {}", path.display(), e))?
+        .to_rgba8();
+    let dimensions = image.dimensions();
+    if dimensions.0 > MAX_THUMBNAIL_SIZE || dimensions.1 > MAX_THUMBNAIL_SIZE {
+        return Err(format!(
+            "Thumbnail {} is {}x{}, larger than {}x{}",
+            path.display(),
+            dimensions.0,
+            dimensions.1,
+            MAX_THUMBNAIL_SIZE,
+            MAX_THUMBNAIL_SIZE
+        ));
+    }
+    Ok((image.into_raw(), dimensions))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::anomaly::Cosmetic;
+
+    const KRUSKAL: MazeKind = MazeKind {
+        width: 25,
+        height: 25,
+        algorithm: MazeAlgorithm::Kruskal,
+    };
+
+    fn entry_for(codex: &Codex, kind: MazeKind) -> &CodexEntry {
+        codex
+            .entries
+            .iter()
+            .find(|entry| entry.kind == kind)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_levels_accumulate_per_kind() {
+        let mut codex = Codex::default();
+        let stacked = MazeKind::new(25, 25, MazeAlgorithm::StackedKruskal);
+        let small = MazeKind::new(10, 10, MazeAlgorithm::Kruskal);
+
+        assert!(codex.record(KRUSKAL, LevelOutcome::Completed, 50.0));
+        assert!(!codex.record(KRUSKAL, LevelOutcome::Died, 80.0));
+        assert!(!codex.record(KRUSKAL, LevelOutcome::Completed, 61.0));
+        assert!(codex.record(KRUSKAL, LevelOutcome::Completed, 44.5));
+        assert!(!codex.record(stacked, LevelOutcome::Died, 12.0));
+        assert!(codex.record(small, LevelOutcome::Completed, 20.0));
+
+        let entry = entry_for(&codex, KRUSKAL);
+        assert_eq!((entry.completions, entry.deaths), (3, 1));
+        assert_eq!(entry.best_time, Some(44.5));
+        assert_eq!(entry.longest_survival, 80.0);
+        assert_eq!(entry.fun_fact(), "Escaped 75% of the time");
+
+        let stacked_entry = entry_for(&codex, stacked);
+        assert_eq!(stacked_entry.best_time, None);
+        assert_eq!(stacked_entry.fun_fact(), "Still unbeaten after 1 attempts");
+
+        // Smallest mazes first, then by algorithm
+        let order: Vec<MazeKind> = codex.entries.iter().map(|entry| entry.kind).collect();
+        assert_eq!(order, vec![small, KRUSKAL, stacked]);
+    }
+
+    #[test]
+    fn test_codex_round_trips_and_survives_corruption() {
+        let dir = std::env::temp_dir().join("mirador_codex_round_trip");
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("stats.json");
+
+        let mut codex = Codex::default();
+        codex.record(KRUSKAL, LevelOutcome::Completed, 33.3);
+        codex.save_to(&path).unwrap();
+        assert_eq!(Codex::load_from(&path), codex);
+
+        fs::write(&path, "{ not json").unwrap();
+        assert_eq!(Codex::load_from(&path), Codex::default());
+        assert_eq!(
+            Codex::load_from(&dir.join("missing.json")),
+            Codex::default()
+        );
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_unlocks_round_trip_and_older_files_have_none() {
+        let dir = std::env::temp_dir().join("mirador_codex_unlocks");
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("stats.json");
+
+        let mut codex = Codex::default();
+        codex.record(KRUSKAL, LevelOutcome::Died, 12.0);
+        codex.unlocks.unlock("hollow_tetra");
+        codex.unlocks.unlock("violet_tetra");
+        codex.save_to(&path).unwrap();
+        let loaded = Codex::load_from(&path);
+        assert_eq!(loaded, codex);
+        assert_eq!(
+            loaded.unlocks.unlocked,
+            vec![Cosmetic::RingReticle, Cosmetic::DuskPalette]
+        );
+
+        // Codex files from before anomalies still load, with nothing unlocked
+        fs::write(&path, r#"{ "entries": [] }"#).unwrap();
+        assert_eq!(Codex::load_from(&path).unlocks, UnlockRegistry::default());
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_thumbnails_round_trip_and_reject_bad_files() {
+        let dir = std::env::temp_dir().join("mirador_codex_thumbnails");
+        let _ = fs::remove_dir_all(&dir);
+        let path = dir.join("nested").join(format!("{}.png", KRUSKAL.slug()));
+
+        let rgba: Vec<u8> = (0..4 * 3 * 4).map(|i| (i * 7) as u8).collect();
+        save_thumbnail(&path, &rgba, (4, 3)).unwrap();
+        assert_eq!(load_thumbnail(&path), Ok((rgba, (4, 3))));
+
+        fs::write(&path, b"\x89PNG\r\n\x1a\ntruncated").unwrap();
+        assert!(load_thumbnail(&path).is_err());
+        assert!(load_thumbnail(&dir.join("missing.png")).is_err());
+
+        let too_large = MAX_THUMBNAIL_SIZE + 1;
+        let pixels = vec![0u8; (too_large * 4) as usize];
+        save_thumbnail(&path, &pixels, (too_large, 1)).unwrap();
+        assert!(load_thumbnail(&path).is_err());
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_durations_format_as_minutes_and_seconds() {
+        assert_eq!(format_duration(0.0), "0:00.0");
+        assert_eq!(format_duration(44.46), "0:44.5");
+        assert_eq!(format_duration(59.96), "1:00.0");
+        assert_eq!(format_duration(125.3), "2:05.3");
+        assert_eq!(KRUSKAL.label(), "25x25 Kruskal");
+        assert_eq!(KRUSKAL.slug(), "25x25-kruskal");
+    }
+}
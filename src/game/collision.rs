@@ -28,6 +28,20 @@
 
 use crate::game::GameAudioManager;
 use crate::game::Player;
+use crate::game::door::DoorSpan;
+use crate::game::maze;
+use crate::game::maze::floors::{FloorPlan, Layer};
+use crate::game::maze::generator::Cell;
+use crate::game::player::MovementOutcome;
+use crate::math::coordinates::constants::PLAYER_HEIGHT;
+
+/// Most walls [`CollisionSystem::push_out_of_walls`] pushes a position out of before giving up.
+const MAX_PUSH_OUT_STEPS: usize = 8;
+
+/// Gap [`CollisionSystem::push_out_of_walls`] leaves between the player and a wall, so the
+/// resolved position no longer touches it.
+const PUSH_OUT_SKIN: f32 = 0.01;
+
 /// Axis-Aligned Bounding Box (AABB) for efficient collision detection.
 ///
 /// An AABB is a rectangular box whose faces are aligned with the world coordinate axes.
@@ -69,7 +83,7 @@ impl AABB {
     /// * `max` - An array of 3 f32 values [x, y, z] representing the maximum corner
     ///
     /// # Example
-    /// ```
+    /// ```rust,ignore
     /// let bbox = AABB::new([0.0, 0.0, 0.0], [1.0, 2.0, 3.0]);
     /// ```
     pub fn new(min: [f32; 3], max: [f32; 3]) -> Self {
@@ -190,6 +204,20 @@ impl AABB {
     }
 }
 
+/// A wall the player ran into while their movement was being resolved.
+///
+/// Reported by [`CollisionSystem::check_and_resolve_collision`] so effects such as
+/// impact particles can be placed on the wall surface.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WallImpact {
+    /// Point on the wall surface the player touched
+    pub position: [f32; 3],
+    /// Wall normal pointing back toward the player
+    pub normal: [f32; 3],
+    /// Speed the player was moving into the wall, in units per second
+    pub speed: f32,
+}
+
 /// Represents a single wall face for collision detection.
 ///
 /// A wall face is a quadrilateral defined by four corner points in 3D space.
@@ -686,6 +714,14 @@ impl BVH {
 /// - `player_radius`: The radius of the player's cylindrical collision shape (in world units).
 /// - `player_height`: The height of the player's cylindrical collision shape (in world units).
 /// - `maze_dimensions`: The dimensions of the maze as a tuple (width, height), in grid cells.
+/// - `upper_bvh` and `floors`: The upper floor's walls and the layout of a two-floor level.
+///
+/// # Two-Floor Levels
+///
+/// Each floor has its own BVH, built at ground level. Collision queries take the floor to
+/// test against and positions measured from that floor's surface, so a walker halfway up a
+/// ramp is tested as if standing on the floor they belong to.
+///
 /// # How To Use
 ///
 /// 1. Create a `CollisionSystem` with appropriate player dimensions
@@ -702,6 +738,20 @@ pub struct CollisionSystem {
     pub player_height: f32,
     /// The dimensions of the maze grid (width, height).
     pub maze_dimensions: (usize, usize),
+    /// Whether the collision geometry was built for the test mode floor size.
+    pub is_test_mode: bool,
+    /// World-space `[x, z]` centers of every open grid cell.
+    ///
+    /// Used to move entities that escaped the maze back to the nearest valid spot.
+    pub walkable_centers: Vec<[f32; 2]>,
+    /// The wall grid the lower floor's collision geometry was built from.
+    pub maze_grid: Vec<Vec<bool>>,
+    /// The bounding volume hierarchy of the upper floor's walls on two-floor levels.
+    pub upper_bvh: BVH,
+    /// Layout of the current two-floor level, or `None` for a single-floor level.
+    pub floors: Option<FloorPlan>,
+    /// The closed entrance door, whose faces are in the lower floor's BVH.
+    pub door: Option<DoorSpan>,
 }
 
 impl CollisionSystem {
@@ -727,6 +777,12 @@ impl CollisionSystem {
             player_radius,
             player_height,
             maze_dimensions: (0, 0),
+            is_test_mode: false,
+            walkable_centers: Vec::new(),
+            maze_grid: Vec::new(),
+            upper_bvh: BVH::new(),
+            floors: None,
+            door: None,
         }
     }
 
@@ -748,7 +804,7 @@ impl CollisionSystem {
     ///
     /// # Usage Example
     ///
-    /// ```
+    /// ```rust,ignore
     /// let mut collision_system = CollisionSystem::new(0.4, 1.8);
     /// collision_system.build_from_maze(&maze.grid, false);
     /// ```
@@ -757,11 +813,235 @@ impl CollisionSystem {
         crate::benchmark!("collision_system_build", {
             // Store maze dimensions
             self.maze_dimensions = (maze_grid[0].len(), maze_grid.len());
+            self.is_test_mode = is_test_mode;
+            self.walkable_centers = Self::collect_walkable_centers(maze_grid, is_test_mode);
+            self.maze_grid = maze_grid.to_vec();
             let wall_faces = self.extract_wall_faces_from_maze(maze_grid, is_test_mode);
             self.bvh.build(wall_faces);
+            self.upper_bvh = BVH::new();
+            self.floors = None;
+            self.door = None;
         });
     }
 
+    /// Closes the entrance door on the lower floor, or opens it again.
+    ///
+    /// Rebuilds the lower floor's BVH with the door's faces in it, so the player and the
+    /// enemy both collide with it. Nothing is rebuilt if the door is already in that state.
+    ///
+    /// # Arguments
+    ///
+    /// * `door` - Where the closed door stands, or `None` to open it
+    pub fn set_door(&mut self, door: Option<DoorSpan>) {
+        if self.door == door {
+            return;
+        }
+        let mut wall_faces = self.extract_wall_faces_from_maze(&self.maze_grid, self.is_test_mode);
+        if let Some(span) = &door {
+            for reverse_normal in [false, true] {
+                wall_faces.push(self.create_z_facing_wall_face(
+                    span.x,
+                    span.z,
+                    span.width,
+                    span.height,
+                    reverse_normal,
+                ));
+            }
+        }
+        self.bvh.build(wall_faces);
+        self.door = door;
+    }
+
+    /// Adds the upper floor of a two-floor level.
+    ///
+    /// Call this after [`CollisionSystem::build_from_maze`] has built the lower floor.
+    ///
+    /// # Arguments
+    ///
+    /// * `floors` - The level's floor plan, or `None` to keep a single floor
+    pub fn set_floor_plan(&mut self, floors: Option<FloorPlan>) {
+        if let Some(plan) = &floors {
+            let wall_faces =
+                self.extract_wall_faces_from_maze(plan.grid(Layer::Upper), plan.is_test_mode);
+            self.upper_bvh.build(wall_faces);
+        }
+        self.floors = floors;
+    }
+
+    /// Works out the floor and ground height under a position.
+    ///
+    /// # Arguments
+    ///
+    /// * `position` - World position to test
+    /// * `layer` - Floor the walker was on last
+    ///
+    /// # Returns
+    ///
+    /// The walker's floor and the world height of the ground beneath them; always the
+    /// given floor at height `0.0` on single-floor levels
+    pub fn ground(&self, position: [f32; 3], layer: Layer) -> (Layer, f32) {
+        self.floors
+            .as_ref()
+            .map_or((layer, 0.0), |plan| plan.ground(position, layer))
+    }
+
+    /// Returns the wall grid of one floor.
+    ///
+    /// Single-floor levels only have the lower floor, which is returned for either layer.
+    pub fn grid(&self, layer: Layer) -> &[Vec<bool>] {
+        match &self.floors {
+            Some(plan) => plan.grid(layer),
+            None => &self.maze_grid,
+        }
+    }
+
+    /// Finds the shortest walk between two wall-grid cells.
+    ///
+    /// # Arguments
+    ///
+    /// * `from` - Wall-grid cell and floor the walk starts on
+    /// * `to` - Wall-grid cell and floor the walk ends on
+    ///
+    /// # Returns
+    ///
+    /// Every cell on the walk including both ends, climbing stairwells on two-floor
+    /// levels, or `None` if either end is a wall or the cells are not connected
+    pub fn route(&self, from: (Cell, Layer), to: (Cell, Layer)) -> Option<Vec<(Cell, Layer)>> {
+        match &self.floors {
+            Some(plan) => plan.route(from, to),
+            None => maze::find_path(&self.maze_grid, from.0, to.0)
+                .map(|path| path.into_iter().map(|cell| (cell, Layer::Lower)).collect()),
+        }
+    }
+
+    /// Returns the BVH holding a floor's walls.
+    fn bvh_for(&self, layer: Layer) -> &BVH {
+        match layer {
+            Layer::Lower => &self.bvh,
+            Layer::Upper => &self.upper_bvh,
+        }
+    }
+
+    /// Computes the world-space center of every open cell in the maze grid.
+    ///
+    /// # Arguments
+    ///
+    /// * `maze_grid` - A 2D grid where `true` represents walls and `false` represents open spaces
+    /// * `is_test_mode` - Whether test mode is enabled (affects floor size)
+    ///
+    /// # Returns
+    ///
+    /// The `[x, z]` world coordinates of each open cell's center
+    fn collect_walkable_centers(maze_grid: &[Vec<bool>], is_test_mode: bool) -> Vec<[f32; 2]> {
+        let maze_dimensions = (maze_grid[0].len(), maze_grid.len());
+        let mut centers = Vec::new();
+        for (row, cells) in maze_grid.iter().enumerate() {
+            for (col, &is_wall) in cells.iter().enumerate() {
+                if !is_wall {
+                    let center = crate::math::coordinates::maze_to_world(
+                        &crate::game::maze::generator::Cell::new(row, col),
+                        maze_dimensions,
+                        0.0,
+                        is_test_mode,
+                    );
+                    centers.push([center[0], center[2]]);
+                }
+            }
+        }
+        centers
+    }
+
+    /// Checks whether a position lies inside the playable world.
+    ///
+    /// A position is in bounds when it is finite, within the floor extents of
+    /// the maze on the X and Z axes, and not below the floor plane (`y = 0`).
+    /// Before any maze has been built every position is considered in bounds.
+    ///
+    /// # Arguments
+    ///
+    /// * `position` - The world position to check
+    ///
+    /// # Returns
+    ///
+    /// `true` if the position is inside the world bounds
+    pub fn is_within_world_bounds(&self, position: [f32; 3]) -> bool {
+        if self.maze_dimensions == (0, 0) {
+            return true;
+        }
+        if !position.iter().all(|value| value.is_finite()) || position[1] < 0.0 {
+            return false;
+        }
+
+        let (maze_width, maze_height) = self.maze_dimensions;
+        let cell_size =
+            crate::math::coordinates::calculate_cell_size(self.maze_dimensions, self.is_test_mode);
+        let half_width = maze_width as f32 * cell_size / 2.0;
+        let half_height = maze_height as f32 * cell_size / 2.0;
+
+        position[0].abs() <= half_width && position[2].abs() <= half_height
+    }
+
+    /// Moves an out-of-bounds position back to the nearest open cell center.
+    ///
+    /// This is a safety net for physics bugs: if an entity ends up outside the
+    /// floor extents or below the floor after collision resolution, it is
+    /// teleported rather than left falling forever. Every recovery is logged
+    /// with the offending position so the root cause stays visible.
+    ///
+    /// # Arguments
+    ///
+    /// * `position` - The entity's position after movement and collision
+    /// * `entity` - Name used in the warning message (e.g. "Player")
+    /// * `safe_height` - Height to use if the current height is invalid
+    ///
+    /// # Returns
+    ///
+    /// The original position if it is in bounds, otherwise the nearest open cell center
+    pub fn recover_out_of_bounds(
+        &self,
+        position: [f32; 3],
+        entity: &str,
+        safe_height: f32,
+    ) -> [f32; 3] {
+        if self.is_within_world_bounds(position) {
+            return position;
+        }
+
+        let height = if position[1].is_finite() && position[1] >= 0.0 {
+            position[1]
+        } else {
+            safe_height
+        };
+        let x = if position[0].is_finite() {
+            position[0]
+        } else {
+            0.0
+        };
+        let z = if position[2].is_finite() {
+            position[2]
+        } else {
+            0.0
+        };
+
+        let nearest = self
+            .walkable_centers
+            .iter()
+            .min_by(|a, b| {
+                let distance_a = (a[0] - x).powi(2) + (a[1] - z).powi(2);
+                let distance_b = (b[0] - x).powi(2) + (b[1] - z).powi(2);
+                distance_a.total_cmp(&distance_b)
+            })
+            .copied()
+            .unwrap_or([0.0, 0.0]);
+        let recovered = [nearest[0], height, nearest[1]];
+
+        eprintln!(
+            "WARNING: {} left the world bounds at {:?}, moved back to {:?}",
+            entity, position, recovered
+        );
+        recovered
+    }
+
     /// Extracts wall faces from the maze grid for collision detection.
     ///
     /// This method converts a 2D maze representation into 3D wall faces with
@@ -1084,10 +1364,13 @@ impl CollisionSystem {
     ///
     /// * `current_pos` - The player's current position as [x, y, z]
     /// * `desired_pos` - The position the player wants to move to
+    /// * `delta_time` - Seconds the movement spans, used to measure impact speed
+    /// * `layer` - Floor whose walls to collide with; positions are measured from its surface
     ///
     /// # Returns
     ///
-    /// The final position after collision resolution
+    /// The final position after collision resolution, and the hardest wall impact
+    /// along the way if the player moved into a wall
     ///
     /// # Algorithm
     ///
@@ -1109,7 +1392,9 @@ impl CollisionSystem {
         audio_manager: &mut GameAudioManager,
         current_pos: [f32; 3],
         desired_pos: [f32; 3],
-    ) -> [f32; 3] {
+        delta_time: f32,
+        layer: Layer,
+    ) -> ([f32; 3], Option<WallImpact>) {
         // Use benchmark macro for collision detection timing
         crate::benchmark!("collision_detection_and_resolution", {
             // Create player AABB
@@ -1127,20 +1412,22 @@ impl CollisionSystem {
             );
 
             // Query BVH for potential collisions
-            let potential_collisions = self.bvh.query_collisions(&player_aabb);
+            let bvh = self.bvh_for(layer);
+            let potential_collisions = bvh.query_collisions(&player_aabb);
 
             if potential_collisions.is_empty() {
-                return desired_pos;
+                return (desired_pos, None);
             }
 
             // NEW: Check if player is stuck between opposing faces
             if self.is_stuck_between_faces(&potential_collisions, current_pos) {
                 // Return to a safe position
-                return self.find_safe_position(current_pos);
+                return (self.find_safe_position(current_pos, bvh), None);
             }
 
             // Perform collision resolution with wall sliding
             let mut resolved_pos = desired_pos;
+            let mut hardest_impact: Option<WallImpact> = None;
             let max_iterations = 5;
 
             for _ in 0..max_iterations {
@@ -1159,7 +1446,7 @@ impl CollisionSystem {
                 );
 
                 // Check for collisions at this position
-                let potential_collisions = self.bvh.query_collisions(&player_aabb);
+                let potential_collisions = bvh.query_collisions(&player_aabb);
                 if potential_collisions.is_empty() {
                     break; // No collisions, we're done
                 }
@@ -1189,13 +1476,21 @@ impl CollisionSystem {
                     resolved_pos[2] - current_pos[2],
                 ];
 
-                resolved_pos = self.resolve_wall_collision(
+                let (next_pos, impact) = self.resolve_wall_collision(
                     audio_manager,
                     current_pos,
                     resolved_pos,
                     movement,
                     closest_face,
+                    delta_time,
                 );
+                resolved_pos = next_pos;
+                // Keep the hardest impact for effects
+                if let Some(impact) = impact.filter(|impact| {
+                    hardest_impact.is_none_or(|hardest| impact.speed > hardest.speed)
+                }) {
+                    hardest_impact = Some(impact);
+                }
 
                 // If position didn't change significantly, we're stuck - break out
                 let epsilon = 0.0001;
@@ -1208,10 +1503,247 @@ impl CollisionSystem {
                 }
             }
 
-            resolved_pos
+            (resolved_pos, hardest_impact)
         })
     }
 
+    /// Moves a position out of every wall the player's collision cylinder overlaps there.
+    ///
+    /// Used after the player's dimensions change: a player who grows next to a wall would
+    /// otherwise start inside it, where [`CollisionSystem::check_and_resolve_collision`] only
+    /// stops movement further in. Each overlapping wall pushes the position straight out of
+    /// its plane, on the side the position is already on, until nothing overlaps.
+    ///
+    /// # Arguments
+    ///
+    /// * `position` - The position to resolve, measured from the surface of `layer`
+    /// * `layer` - Floor whose walls to test against
+    ///
+    /// # Returns
+    ///
+    /// The nearest position found that overlaps no walls, or the closest attempt if the
+    /// cylinder no longer fits there at all
+    pub fn push_out_of_walls(&self, position: [f32; 3], layer: Layer) -> [f32; 3] {
+        let bvh = self.bvh_for(layer);
+        let mut resolved = position;
+        for _ in 0..MAX_PUSH_OUT_STEPS {
+            let overlapping = bvh.query_collisions(&self.player_aabb(resolved));
+            let deepest = overlapping
+                .iter()
+                .map(|face| (face, self.signed_distance_to_face(resolved, face)))
+                .filter(|(_, distance)| distance.abs() < self.player_radius + PUSH_OUT_SKIN)
+                .min_by(|(_, a), (_, b)| a.abs().total_cmp(&b.abs()));
+            let Some((face, distance)) = deepest else {
+                return resolved;
+            };
+
+            // Stay on the side of the wall the player is on
+            let side = if distance < 0.0 { -1.0 } else { 1.0 };
+            let push = (self.player_radius + PUSH_OUT_SKIN - distance.abs()) * side;
+            resolved = [
+                resolved[0] + face.normal[0] * push,
+                resolved[1],
+                resolved[2] + face.normal[2] * push,
+            ];
+        }
+
+        if bvh.query_collisions(&self.player_aabb(resolved)).is_empty() {
+            resolved
+        } else {
+            self.find_safe_position(resolved, bvh)
+        }
+    }
+
+    /// Finds the wall a point is buried in.
+    ///
+    /// Walls are the solid cells of the floor's wall grid, so the cell under the point is
+    /// looked up directly rather than searched for in the BVH, which keeps the test cheap
+    /// enough to run on the player at any time. A point exactly on a wall's face is not
+    /// inside it; a point on an edge or corner only is when every cell meeting there is solid.
+    ///
+    /// # Arguments
+    ///
+    /// * `position` - World position to test; only x and z are read
+    /// * `layer` - Floor whose walls to test against
+    ///
+    /// # Returns
+    ///
+    /// The wall-grid cell the point is inside, or `None` in the open or outside the maze
+    pub fn wall_containing(&self, position: [f32; 3], layer: Layer) -> Option<Cell> {
+        let (col, row) = self.grid_coordinates(position, layer)?;
+        // Every cell whose closed box holds the point, one or two along each axis
+        let touching = |coordinate: f32| {
+            let cell = coordinate.floor() as isize;
+            if coordinate == coordinate.floor() {
+                [cell - 1, cell]
+            } else {
+                [cell, cell]
+            }
+        };
+        let (rows, cols) = (touching(row), touching(col));
+        let buried = rows
+            .iter()
+            .all(|&row| cols.iter().all(|&col| self.is_solid(layer, row, col)));
+        buried.then(|| Cell::new(rows[1] as usize, cols[1] as usize))
+    }
+
+    /// Moves a point buried in a wall out to the nearest place the player can stand.
+    ///
+    /// Tries leaving through each face of the wall that opens onto an open cell, just far
+    /// enough past it for the player's collision cylinder, and keeps the closest. A point
+    /// in a wall with no open neighbor goes to the center of the nearest open cell instead.
+    /// The result is then pushed clear of any other wall it overlaps, as in
+    /// [`CollisionSystem::push_out_of_walls`].
+    ///
+    /// # Arguments
+    ///
+    /// * `position` - The position to resolve, measured from the surface of `layer`
+    /// * `layer` - Floor whose walls to test against
+    ///
+    /// # Returns
+    ///
+    /// The resolved position at the same height, or `position` unchanged if it isn't in a
+    /// wall or the floor has no open cell at all
+    pub fn nearest_open_position(&self, position: [f32; 3], layer: Layer) -> [f32; 3] {
+        let Some(wall) = self.wall_containing(position, layer) else {
+            return position;
+        };
+        let grid = self.grid(layer);
+        let cell_size = crate::math::coordinates::calculate_cell_size(
+            (grid[0].len(), grid.len()),
+            self.is_test_mode,
+        );
+        let (origin_x, origin_z) = Self::grid_origin(grid, cell_size);
+        let (row, col) = (wall.row as isize, wall.col as isize);
+        let clearance = (self.player_radius + PUSH_OUT_SKIN).min(cell_size / 2.0);
+        let west = origin_x + col as f32 * cell_size - clearance;
+        let east = origin_x + (col + 1) as f32 * cell_size + clearance;
+        let north = origin_z + row as f32 * cell_size - clearance;
+        let south = origin_z + (row + 1) as f32 * cell_size + clearance;
+        let [x, y, z] = position;
+        let exits = [
+            (row, col - 1, [west, y, z]),
+            (row, col + 1, [east, y, z]),
+            (row - 1, col, [x, y, north]),
+            (row + 1, col, [x, y, south]),
+        ];
+        let distance =
+            |candidate: &[f32; 3]| (candidate[0] - x).powi(2) + (candidate[2] - z).powi(2);
+
+        let through_face = exits
+            .into_iter()
+            .filter(|&(row, col, _)| self.is_open(layer, row, col))
+            .map(|(_, _, candidate)| candidate)
+            .min_by(|a, b| distance(a).total_cmp(&distance(b)));
+        let resolved = through_face.or_else(|| {
+            (0..grid.len())
+                .flat_map(|row| (0..grid[0].len()).map(move |col| (row, col)))
+                .filter(|&(row, col)| self.is_open(layer, row as isize, col as isize))
+                .map(|(row, col)| {
+                    [
+                        origin_x + (col as f32 + 0.5) * cell_size,
+                        y,
+                        origin_z + (row as f32 + 0.5) * cell_size,
+                    ]
+                })
+                .min_by(|a, b| distance(a).total_cmp(&distance(b)))
+        });
+        match resolved {
+            Some(resolved) => self.push_out_of_walls(resolved, layer),
+            None => position,
+        }
+    }
+
+    /// Converts a world position to fractional wall-grid coordinates on a floor.
+    ///
+    /// # Returns
+    ///
+    /// `(column, row)`, where a whole number lies on the edge between two cells, or `None`
+    /// before a maze is built or for a position that isn't finite
+    fn grid_coordinates(&self, position: [f32; 3], layer: Layer) -> Option<(f32, f32)> {
+        let grid = self.grid(layer);
+        if grid.is_empty() || grid[0].is_empty() || !position.iter().all(|v| v.is_finite()) {
+            return None;
+        }
+        let cell_size = crate::math::coordinates::calculate_cell_size(
+            (grid[0].len(), grid.len()),
+            self.is_test_mode,
+        );
+        let (origin_x, origin_z) = Self::grid_origin(grid, cell_size);
+        Some((
+            (position[0] - origin_x) / cell_size,
+            (position[2] - origin_z) / cell_size,
+        ))
+    }
+
+    /// Returns the world `(x, z)` of the corner of a wall grid where row and column 0 meet.
+    pub(crate) fn grid_origin(grid: &[Vec<bool>], cell_size: f32) -> (f32, f32) {
+        (
+            -(grid[0].len() as f32 * cell_size) / 2.0,
+            -(grid.len() as f32 * cell_size) / 2.0,
+        )
+    }
+
+    /// Checks whether a wall-grid cell has collision geometry.
+    ///
+    /// Cells outside the grid are not solid. In test mode only the perimeter walls collide.
+    pub(crate) fn is_solid(&self, layer: Layer, row: isize, col: isize) -> bool {
+        let grid = self.grid(layer);
+        let (Ok(row), Ok(col)) = (usize::try_from(row), usize::try_from(col)) else {
+            return false;
+        };
+        let (height, width) = (grid.len(), grid[0].len());
+        if row >= height || col >= width || !grid[row][col] {
+            return false;
+        }
+        !self.is_test_mode || row == 0 || col == 0 || row == height - 1 || col == width - 1
+    }
+
+    /// Checks whether a wall-grid cell is inside the grid and can be stood in.
+    fn is_open(&self, layer: Layer, row: isize, col: isize) -> bool {
+        let grid = self.grid(layer);
+        let inside = usize::try_from(row).is_ok_and(|row| row < grid.len())
+            && usize::try_from(col).is_ok_and(|col| col < grid[0].len());
+        inside && !self.is_solid(layer, row, col)
+    }
+
+    /// Builds the box around the player's collision cylinder at a position.
+    ///
+    /// # Arguments
+    ///
+    /// * `position` - The player's position, measured from the surface of their floor
+    fn player_aabb(&self, position: [f32; 3]) -> AABB {
+        AABB::new(
+            [
+                position[0] - self.player_radius,
+                position[1],
+                position[2] - self.player_radius,
+            ],
+            [
+                position[0] + self.player_radius,
+                position[1] + self.player_height,
+                position[2] + self.player_radius,
+            ],
+        )
+    }
+
+    /// Calculates how far in front of a wall face's plane a point is.
+    ///
+    /// # Arguments
+    ///
+    /// * `pos` - The point to measure from
+    /// * `face` - The wall face to measure to
+    ///
+    /// # Returns
+    ///
+    /// The distance from the face's plane, positive on the side its normal points to
+    fn signed_distance_to_face(&self, pos: [f32; 3], face: &WallFace) -> f32 {
+        let face_center = face.aabb.center();
+        (pos[0] - face_center[0]) * face.normal[0]
+            + (pos[1] - face_center[1]) * face.normal[1]
+            + (pos[2] - face_center[2]) * face.normal[2]
+    }
+
     /// Detects if the player is stuck between opposing wall faces.
     ///
     /// This method identifies situations where the player is positioned between
@@ -1314,6 +1846,7 @@ impl CollisionSystem {
     /// # Arguments
     ///
     /// * `current_pos` - The player's current stuck position
+    /// * `bvh` - The walls of the floor the player is on
     ///
     /// # Returns
     ///
@@ -1331,7 +1864,7 @@ impl CollisionSystem {
     ///
     /// This method provides a fallback mechanism to prevent the player from
     /// becoming permanently stuck in corners or narrow passages.
-    fn find_safe_position(&self, current_pos: [f32; 3]) -> [f32; 3] {
+    fn find_safe_position(&self, current_pos: [f32; 3], bvh: &BVH) -> [f32; 3] {
         // Try moving in different directions to find a safe spot
         let escape_distance = self.player_radius / 2.0;
         let directions = [
@@ -1362,7 +1895,7 @@ impl CollisionSystem {
                 ],
             );
 
-            if self.bvh.query_collisions(&test_aabb).is_empty() {
+            if bvh.query_collisions(&test_aabb).is_empty() {
                 return test_pos;
             }
         }
@@ -1419,7 +1952,8 @@ impl CollisionSystem {
         desired_pos: [f32; 3],
         movement: [f32; 3],
         wall_face: &WallFace,
-    ) -> [f32; 3] {
+        delta_time: f32,
+    ) -> ([f32; 3], Option<WallImpact>) {
         let normal = wall_face.normal;
 
         // Calculate ray from previous position to desired position
@@ -1456,15 +1990,32 @@ impl CollisionSystem {
                 movement[2] - movement_dot * effective_normal[2],
             ];
 
-            return [
+            let slide_pos = [
                 // Add a small buffer ("skin") to prevent getting exactly flushrn [
                 current_pos[0] + slide_movement[0],
                 current_pos[1] + slide_movement[1],
                 current_pos[2] + slide_movement[2],
             ];
+
+            // The contact point is one player radius from the player's center, on the wall side
+            let impact = WallImpact {
+                position: [
+                    slide_pos[0] - effective_normal[0] * self.player_radius,
+                    slide_pos[1],
+                    slide_pos[2] - effective_normal[2] * self.player_radius,
+                ],
+                normal: effective_normal,
+                speed: if delta_time > 0.0 {
+                    -movement_dot / delta_time
+                } else {
+                    0.0
+                },
+            };
+
+            return (slide_pos, Some(impact));
         }
 
-        desired_pos
+        (desired_pos, None)
     }
 
     /// Tests if a swept cylinder intersects with any geometry in the scene.
@@ -1478,6 +2029,7 @@ impl CollisionSystem {
     /// * `start` - The starting position of the cylinder center
     /// * `end` - The ending position of the cylinder center
     /// * `radius` - The radius of the cylinder
+    /// * `layer` - Floor whose walls to test; positions are measured from its surface
     ///
     /// # Returns
     ///
@@ -1499,6 +2051,7 @@ impl CollisionSystem {
         start: [f32; 3],
         end: [f32; 3],
         radius: f32,
+        layer: Layer,
     ) -> bool {
         // Use benchmark macro for cylinder intersection timing
         crate::benchmark!("cylinder_intersects_geometry", {
@@ -1516,7 +2069,7 @@ impl CollisionSystem {
                 ],
             );
 
-            let potential_faces = self.bvh.query_collisions(&cylinder_aabb);
+            let potential_faces = self.bvh_for(layer).query_collisions(&cylinder_aabb);
 
             for face in &potential_faces {
                 if self.cylinder_intersects_wall_face(start, end, radius, face) {
@@ -1934,61 +2487,330 @@ impl Player {
     ///
     /// * `collision_system` - Reference to the collision system
     /// * `delta_time` - Time elapsed since last frame (for consistent movement)
-    /// * `forward`, `backward`, `left`, `right` - Movement control flags
+    /// * `move_axis` - Strafe and forward deflection, right and forward positive
     ///
     /// # Movement Process
     ///
     /// 1. Start with the player's current position
-    /// 2. Calculate desired position based on input and player orientation
+    /// 2. Accelerate toward the input direction (see [`Player::accelerate`]) and
+    ///    calculate the desired position from the velocity, with the step length
+    ///    capped (see [`Player::desired_position`])
     /// 3. Check for collisions using the collision system
-    /// 4. Update the player's position with collision-resolved coordinates
+    /// 4. Update the player's position with collision-resolved coordinates, and drop
+    ///    the part of the velocity a wall took away
+    /// 5. Move the player back inside the maze if they somehow escaped it
+    /// 6. Raise or lower the player to the ground under them, switching floors on ramps
+    ///
+    /// # Returns
+    ///
+    /// A [`MovementOutcome`] describing how far the player moved, whether
+    /// a wall stopped the movement head-on, and any wall the player ran into
     ///
     /// # Physics Integration
     ///
     /// The movement respects the physics of the environment by preventing
     /// penetration into walls and allowing for realistic sliding along surfaces.
-    #[allow(clippy::too_many_arguments)]
     pub fn move_with_collision(
         &mut self,
         audio_manager: &mut GameAudioManager,
         collision_system: &CollisionSystem,
         delta_time: f32,
-        forward: bool,
-        backward: bool,
-        left: bool,
-        right: bool,
-    ) {
+        move_axis: [f32; 2],
+    ) -> MovementOutcome {
         let current_pos = self.position;
-        let mut desired_pos = current_pos;
-
-        // Apply movement based on input
-        if forward {
-            let forward_x = self.yaw.to_radians().sin();
-            let forward_z = self.yaw.to_radians().cos();
-            desired_pos[0] -= forward_x * self.speed * delta_time;
-            desired_pos[2] -= forward_z * self.speed * delta_time;
+        self.accelerate(delta_time, move_axis);
+        let desired_pos = self.desired_position(delta_time);
+
+        // Collide against the current floor, measuring height from the ground underfoot
+        let (_, ground) = collision_system.ground(current_pos, self.layer);
+        let above_ground = |position: [f32; 3]| [position[0], position[1] - ground, position[2]];
+
+        // Resolve collisions and update position
+        let (resolved_pos, impact) = collision_system.check_and_resolve_collision(
+            audio_manager,
+            above_ground(current_pos),
+            above_ground(desired_pos),
+            delta_time,
+            self.layer,
+        );
+        // Running into a wall doesn't store up speed to carry on with once past it
+        let step_time = delta_time.clamp(0.0, crate::game::player::MAX_MOVEMENT_DELTA);
+        if step_time > 0.0 {
+            let resolved_velocity = [
+                (resolved_pos[0] - current_pos[0]) / step_time,
+                (resolved_pos[2] - current_pos[2]) / step_time,
+            ];
+            let resolved_speed = resolved_velocity[0].hypot(resolved_velocity[1]);
+            let speed = self.velocity[0].hypot(self.velocity[1]);
+            if resolved_speed < speed {
+                self.velocity = resolved_velocity;
+            }
         }
-        if backward {
-            let forward_x = self.yaw.to_radians().sin();
-            let forward_z = self.yaw.to_radians().cos();
-            desired_pos[0] += forward_x * self.speed * delta_time;
-            desired_pos[2] += forward_z * self.speed * delta_time;
+        let recovered_pos =
+            collision_system.recover_out_of_bounds(resolved_pos, "Player", PLAYER_HEIGHT);
+        if recovered_pos != resolved_pos {
+            // Escaped players are put back on the ground floor
+            self.layer = Layer::Lower;
         }
-        if left {
-            let right_x = self.yaw.to_radians().cos();
-            let right_z = self.yaw.to_radians().sin();
-            desired_pos[0] -= right_x * self.speed * delta_time;
-            desired_pos[2] += right_z * self.speed * delta_time;
+
+        // Follow ramps up and down at the same height above the ground
+        let (layer, ground) = collision_system.ground(recovered_pos, self.layer);
+        self.layer = layer;
+        self.position = [
+            recovered_pos[0],
+            recovered_pos[1] + ground,
+            recovered_pos[2],
+        ];
+
+        MovementOutcome {
+            impact,
+            ..MovementOutcome::from_step(current_pos, desired_pos, resolved_pos)
         }
-        if right {
-            let right_x = self.yaw.to_radians().cos();
-            let right_z = self.yaw.to_radians().sin();
-            desired_pos[0] += right_x * self.speed * delta_time;
-            desired_pos[2] -= right_z * self.speed * delta_time;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn open_maze_grid() -> Vec<Vec<bool>> {
+        let size = 7;
+        (0..size)
+            .map(|row| {
+                (0..size)
+                    .map(|col| row == 0 || col == 0 || row == size - 1 || col == size - 1)
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_huge_delta_time_keeps_player_in_bounds() {
+        let grid = open_maze_grid();
+        let mut collision_system = CollisionSystem::new(5.0, PLAYER_HEIGHT);
+        collision_system.build_from_maze(&grid, false);
+        let mut audio_manager = GameAudioManager::silent().unwrap();
+
+        // The inner faces of the perimeter walls
+        let cell_size = crate::math::coordinates::calculate_cell_size((7, 7), false);
+        let wall_face = 2.5 * cell_size;
+        let corner = collision_system
+            .walkable_centers
+            .iter()
+            .copied()
+            .max_by(|a, b| (a[0] + a[1]).total_cmp(&(b[0] + b[1])))
+            .unwrap();
+
+        // Run at full speed into every side and corner, then hitch for 2 seconds
+        for move_axis in [
+            [1.0, 1.0],
+            [-1.0, 1.0],
+            [1.0, -1.0],
+            [-1.0, -1.0],
+            [0.0, 1.0],
+        ] {
+            let mut player = Player::new();
+            player.position = [corner[0], PLAYER_HEIGHT, corner[1]];
+            for _ in 0..60 {
+                player.move_with_collision(
+                    &mut audio_manager,
+                    &collision_system,
+                    1.0 / 60.0,
+                    move_axis,
+                );
+            }
+            player.move_with_collision(&mut audio_manager, &collision_system, 2.0, move_axis);
+
+            let position = player.position;
+            assert!(
+                collision_system.is_within_world_bounds(position),
+                "{:?} left the maze at {:?}",
+                move_axis,
+                position
+            );
+            assert!(
+                position[0].abs() <= wall_face && position[2].abs() <= wall_face,
+                "{:?} ended inside a wall at {:?}",
+                move_axis,
+                position
+            );
         }
+    }
 
-        // Resolve collisions and update position
-        self.position =
-            collision_system.check_and_resolve_collision(audio_manager, current_pos, desired_pos);
+    #[test]
+    fn test_growing_next_to_a_wall_pushes_the_player_out() {
+        let grid = open_maze_grid();
+        let mut collision_system = CollisionSystem::new(5.0, PLAYER_HEIGHT);
+        collision_system.build_from_maze(&grid, false);
+
+        // Stand 6 units from the face of the right perimeter wall
+        let cell_size = crate::math::coordinates::calculate_cell_size((7, 7), false);
+        let wall_face_x = 2.5 * cell_size;
+        let center = collision_system.walkable_centers[0];
+        let standing = [wall_face_x - 6.0, PLAYER_HEIGHT, center[1]];
+        assert_eq!(
+            collision_system.push_out_of_walls(standing, Layer::Lower),
+            standing
+        );
+
+        // A 12 unit radius would leave the player 6 units inside the wall
+        collision_system.player_radius = 12.0;
+        let resolved = collision_system.push_out_of_walls(standing, Layer::Lower);
+        assert!(resolved[0] <= wall_face_x - 12.0, "{:?}", resolved);
+        assert!(resolved[0] > wall_face_x - 12.5, "{:?}", resolved);
+        assert_eq!([resolved[1], resolved[2]], [standing[1], standing[2]]);
+        assert!(
+            collision_system
+                .bvh
+                .query_collisions(&collision_system.player_aabb(resolved))
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_closed_door_blocks_the_entrance() {
+        let grid = open_maze_grid();
+        let mut collision_system = CollisionSystem::new(5.0, PLAYER_HEIGHT);
+        collision_system.build_from_maze(&grid, false);
+
+        // Walk straight through the doorway, north of the spawn cell
+        let span = DoorSpan::at_entrance(&grid, false);
+        let x = span.x + span.width / 2.0;
+        let (outside, inside) = ([x, 10.0, span.z + 50.0], [x, 10.0, span.z - 50.0]);
+        let crosses = |collision_system: &CollisionSystem| {
+            collision_system.cylinder_intersects_geometry(outside, inside, 5.0, Layer::Lower)
+        };
+        assert!(!crosses(&collision_system));
+
+        collision_system.set_door(Some(span));
+        assert!(crosses(&collision_system));
+
+        collision_system.set_door(None);
+        assert!(!crosses(&collision_system));
+    }
+
+    /// A 5x5 layout with 600 unit cells, whose origin is at -1500 on both axes:
+    ///
+    /// ```text
+    /// # # # # #
+    /// # . . . #
+    /// # . # # #
+    /// # . . . #
+    /// # # # # #
+    /// ```
+    fn clip_layout() -> CollisionSystem {
+        let rows = ["#####", "#...#", "#.###", "#...#", "#####"];
+        let grid: Vec<Vec<bool>> = rows
+            .iter()
+            .map(|row| row.chars().map(|c| c == '#').collect())
+            .collect();
+        let mut collision_system = CollisionSystem::new(5.0, PLAYER_HEIGHT);
+        collision_system.build_from_maze(&grid, false);
+        collision_system
+    }
+
+    /// World `[x, y, z]` of a point in wall-grid coordinates of [`clip_layout`].
+    fn grid_point(col: f32, row: f32) -> [f32; 3] {
+        let cell_size = crate::math::coordinates::calculate_cell_size((5, 5), false);
+        let origin = -2.5 * cell_size;
+        [
+            origin + col * cell_size,
+            PLAYER_HEIGHT,
+            origin + row * cell_size,
+        ]
+    }
+
+    #[test]
+    fn test_point_in_wall_uses_the_wall_grid() {
+        let collision_system = clip_layout();
+        let inside =
+            |col, row| collision_system.wall_containing(grid_point(col, row), Layer::Lower);
+
+        assert_eq!(inside(2.5, 2.5), Some(Cell::new(2, 2)));
+        assert_eq!(inside(1.5, 1.5), None);
+        // Exactly on a face between a wall and an open cell
+        assert_eq!(inside(2.5, 2.0), None);
+        assert_eq!(inside(2.0, 2.5), None);
+        // On the face two walls share it is still buried, on a corner with open cells it isn't
+        assert_eq!(inside(3.0, 2.5), Some(Cell::new(2, 3)));
+        assert_eq!(inside(1.0, 1.0), None);
+        // The outer face of the perimeter has nothing on its far side
+        assert_eq!(inside(5.0, 2.5), None);
+        assert_eq!(inside(0.0, 0.5), None);
+        assert_eq!(inside(-1.0, 0.5), None);
+        assert_eq!(
+            collision_system.wall_containing([f32::NAN, 0.0, 0.0], Layer::Lower),
+            None
+        );
+        assert_eq!(
+            CollisionSystem::new(5.0, PLAYER_HEIGHT).wall_containing([0.0; 3], Layer::Lower),
+            None
+        );
+    }
+
+    #[test]
+    fn test_buried_player_leaves_through_the_nearest_open_face() {
+        let collision_system = clip_layout();
+
+        // Just below the north face of the middle wall, which opens onto row 1
+        let buried = grid_point(2.5, 2.1);
+        let resolved = collision_system.nearest_open_position(buried, Layer::Lower);
+        assert_eq!(
+            collision_system.wall_containing(resolved, Layer::Lower),
+            None
+        );
+        assert_eq!([resolved[0], resolved[1]], [buried[0], buried[1]]);
+        let north_face = grid_point(2.5, 2.0)[2];
+        assert!(resolved[2] < north_face - collision_system.player_radius);
+        assert!(resolved[2] > north_face - 2.0 * collision_system.player_radius);
+
+        // Nearer the south face, it leaves to the south
+        let resolved = collision_system.nearest_open_position(grid_point(3.5, 2.8), Layer::Lower);
+        assert!(resolved[2] > grid_point(3.5, 3.0)[2], "{:?}", resolved);
+
+        // A point in the open is left alone
+        let open = grid_point(1.5, 1.5);
+        assert_eq!(
+            collision_system.nearest_open_position(open, Layer::Lower),
+            open
+        );
+    }
+
+    #[test]
+    fn test_deeply_buried_player_goes_to_the_nearest_open_cell() {
+        let grid: Vec<Vec<bool>> = (0..5)
+            .map(|row| (0..5).map(|col| (row, col) != (3, 3)).collect())
+            .collect();
+        let mut collision_system = CollisionSystem::new(5.0, PLAYER_HEIGHT);
+        collision_system.build_from_maze(&grid, false);
+
+        // The corner wall has no open neighbor, so only the open cell's center will do
+        let resolved = collision_system.nearest_open_position(grid_point(0.5, 0.5), Layer::Lower);
+        let center = grid_point(3.5, 3.5);
+        assert!((resolved[0] - center[0]).abs() < 1.0, "{:?}", resolved);
+        assert!((resolved[2] - center[2]).abs() < 1.0, "{:?}", resolved);
+        assert_eq!(
+            collision_system.wall_containing(resolved, Layer::Lower),
+            None
+        );
+    }
+
+    #[test]
+    fn test_out_of_bounds_position_returns_to_open_cell() {
+        let grid = open_maze_grid();
+        let mut collision_system = CollisionSystem::new(5.0, PLAYER_HEIGHT);
+        collision_system.build_from_maze(&grid, false);
+
+        let escaped = [10_000.0, -500.0, 10_000.0];
+        assert!(!collision_system.is_within_world_bounds(escaped));
+
+        let recovered = collision_system.recover_out_of_bounds(escaped, "Player", PLAYER_HEIGHT);
+        assert!(collision_system.is_within_world_bounds(recovered));
+        assert_eq!(recovered[1], PLAYER_HEIGHT);
+        assert!(
+            collision_system
+                .walkable_centers
+                .contains(&[recovered[0], recovered[2]])
+        );
     }
 }
@@ -0,0 +1,247 @@
+//! Movement combo meter for the Mirador game.
+//!
+//! This module provides [`ComboMeter`], which rewards the player for moving confidently. While the
+//! player keeps moving above [`MIN_COMBO_SPEED`], stays out of the enemy's [`DANGER_RANGE`], and
+//! avoids running into walls, the score multiplier climbs by [`MULTIPLIER_STEP`] every
+//! [`STEP_INTERVAL`] seconds up to [`MAX_MULTIPLIER`]. Standing still for longer than
+//! [`STOP_GRACE_PERIOD`], letting the enemy get close, or hitting a wall head-on drops it back to
+//! 1.0x.
+//!
+//! The meter is a pure state machine: it is ticked once per gameplay frame with a
+//! [`ComboFrame`] of facts about that frame and knows nothing about rendering or audio. Frames
+//! that are not ticked (menus, pause) neither advance nor reset it.
+//!
+//! # Usage
+//!
+//! ```rust,ignore
+//! let mut combo = ComboMeter::new();
+//!
+//! // Once per gameplay frame
+//! if let Some(reason) = combo.tick(ComboFrame {
+//!     delta_time,
+//!     speed,
+//!     enemy_distance,
+//!     hit_wall_head_on,
+//! }) {
+//!     // Play the combo-lost cue
+//! }
+//!
+//! let multiplier = combo.multiplier();
+//! ```
+
+/// Minimum movement speed in world units per second that keeps the combo alive.
+pub const MIN_COMBO_SPEED: f32 = 80.0;
+
+/// Seconds the player may stay below [`MIN_COMBO_SPEED`] before the combo resets.
+pub const STOP_GRACE_PERIOD: f32 = 1.5;
+
+/// Enemy distance in world units below which the combo resets.
+pub const DANGER_RANGE: f32 = 200.0;
+
+/// Seconds of sustained movement needed for each multiplier step.
+pub const STEP_INTERVAL: f32 = 3.0;
+
+/// Amount the multiplier grows per step.
+pub const MULTIPLIER_STEP: f32 = 0.1;
+
+/// Highest multiplier the combo can reach.
+pub const MAX_MULTIPLIER: f32 = 2.0;
+
+/// Number of steps between 1.0x and [`MAX_MULTIPLIER`].
+const MAX_STEPS: u32 = 10;
+
+/// Facts about a single gameplay frame that drive the combo.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ComboFrame {
+    /// Seconds since the last frame
+    pub delta_time: f32,
+    /// Distance the player actually covered this frame divided by `delta_time`
+    pub speed: f32,
+    /// Horizontal distance between the player and the enemy
+    pub enemy_distance: f32,
+    /// Whether a wall stopped most of the player's movement this frame
+    pub hit_wall_head_on: bool,
+}
+
+/// Why a combo was lost.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ComboReset {
+    /// The player stood still for longer than the grace period
+    Stopped,
+    /// The enemy came within danger range
+    EnemyClose,
+    /// The player ran into a wall head-on
+    HitWall,
+}
+
+/// Tracks the movement combo and its score multiplier.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ComboMeter {
+    /// Multiplier steps earned so far (0 = 1.0x)
+    steps: u32,
+    /// Seconds of movement accumulated toward the next step
+    progress: f32,
+    /// Seconds the player has been below the speed threshold
+    stopped_for: f32,
+}
+
+impl ComboMeter {
+    /// Creates a meter at 1.0x.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the current score multiplier, from 1.0 to [`MAX_MULTIPLIER`].
+    pub fn multiplier(&self) -> f32 {
+        1.0 + self.steps as f32 * MULTIPLIER_STEP
+    }
+
+    /// Drops the multiplier back to 1.0x and clears all progress.
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+
+    /// Advances the combo by one gameplay frame.
+    ///
+    /// # Arguments
+    /// * `frame` - What happened this frame
+    ///
+    /// # Returns
+    /// `Some(reason)` if an active combo (above 1.0x) was lost this frame, otherwise `None`
+    pub fn tick(&mut self, frame: ComboFrame) -> Option<ComboReset> {
+        let reset = if frame.hit_wall_head_on {
+            Some(ComboReset::HitWall)
+        } else if frame.enemy_distance < DANGER_RANGE {
+            Some(ComboReset::EnemyClose)
+        } else if frame.speed >= MIN_COMBO_SPEED {
+            self.stopped_for = 0.0;
+            self.progress += frame.delta_time;
+            while self.progress >= STEP_INTERVAL {
+                self.progress -= STEP_INTERVAL;
+                self.steps = (self.steps + 1).min(MAX_STEPS);
+            }
+            None
+        } else {
+            self.stopped_for += frame.delta_time;
+            (self.stopped_for > STOP_GRACE_PERIOD).then_some(ComboReset::Stopped)
+        };
+
+        let reason = reset?;
+        let was_active = self.steps > 0;
+        self.reset();
+        was_active.then_some(reason)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn moving(delta_time: f32) -> ComboFrame {
+        ComboFrame {
+            delta_time,
+            speed: MIN_COMBO_SPEED + 40.0,
+            enemy_distance: DANGER_RANGE * 4.0,
+            hit_wall_head_on: false,
+        }
+    }
+
+    fn climbed(steps: u32) -> ComboMeter {
+        let mut combo = ComboMeter::new();
+        for _ in 0..steps {
+            combo.tick(moving(STEP_INTERVAL));
+        }
+        combo
+    }
+
+    #[test]
+    fn test_multiplier_climbs_one_step_per_interval() {
+        let mut combo = ComboMeter::new();
+        assert_eq!(combo.multiplier(), 1.0);
+
+        combo.tick(moving(STEP_INTERVAL - 0.01));
+        assert_eq!(combo.multiplier(), 1.0);
+        combo.tick(moving(0.01));
+        assert!((combo.multiplier() - 1.1).abs() < 1e-5);
+
+        // Capped at the maximum
+        for _ in 0..30 {
+            combo.tick(moving(STEP_INTERVAL));
+        }
+        assert!((combo.multiplier() - MAX_MULTIPLIER).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_short_stop_keeps_combo() {
+        let mut combo = climbed(3);
+        let stopped = ComboFrame {
+            speed: 0.0,
+            ..moving(STOP_GRACE_PERIOD)
+        };
+        assert_eq!(combo.tick(stopped), None);
+        assert!((combo.multiplier() - 1.3).abs() < 1e-5);
+
+        // Moving again clears the stop timer
+        combo.tick(moving(0.1));
+        assert_eq!(combo.tick(stopped), None);
+    }
+
+    #[test]
+    fn test_long_stop_resets_combo() {
+        let mut combo = climbed(3);
+        let stopped = ComboFrame {
+            speed: 0.0,
+            ..moving(1.0)
+        };
+        assert_eq!(combo.tick(stopped), None);
+        assert_eq!(combo.tick(stopped), Some(ComboReset::Stopped));
+        assert_eq!(combo.multiplier(), 1.0);
+    }
+
+    #[test]
+    fn test_enemy_close_resets_combo() {
+        let mut combo = climbed(2);
+        let close = ComboFrame {
+            enemy_distance: DANGER_RANGE - 1.0,
+            ..moving(0.016)
+        };
+        assert_eq!(combo.tick(close), Some(ComboReset::EnemyClose));
+        assert_eq!(combo.multiplier(), 1.0);
+    }
+
+    #[test]
+    fn test_head_on_wall_hit_resets_combo() {
+        let mut combo = climbed(2);
+        let hit = ComboFrame {
+            hit_wall_head_on: true,
+            ..moving(0.016)
+        };
+        assert_eq!(combo.tick(hit), Some(ComboReset::HitWall));
+        assert_eq!(combo.multiplier(), 1.0);
+
+        // Losing an empty combo is not reported
+        assert_eq!(combo.tick(hit), None);
+    }
+
+    #[test]
+    fn test_pausing_neither_advances_nor_resets_combo() {
+        let mut combo = climbed(4);
+        combo.tick(moving(1.0));
+        let before_pause = combo.clone();
+
+        // A paused frame has no elapsed game time even though the player is standing still
+        let paused = ComboFrame {
+            delta_time: 0.0,
+            speed: 0.0,
+            ..moving(0.0)
+        };
+        for _ in 0..1000 {
+            assert_eq!(combo.tick(paused), None);
+        }
+        assert_eq!(combo, before_pause);
+
+        // Progress picks up where it left off after resuming
+        combo.tick(moving(STEP_INTERVAL - 1.0));
+        assert!((combo.multiplier() - 1.5).abs() < 1e-5);
+    }
+}
@@ -0,0 +1,349 @@
+//! Warning cues and ducking the music under them.
+//!
+//! A few sounds carry information the player must not miss: the sting when the enemy closes in,
+//! the beep when the timer turns critical, the capture sting, and the denied sound for an
+//! unreachable click-to-move target. Each is a [`DuckCue`], and while one is playing the music
+//! bus is turned down so it cuts through, the way a sidechain compressor ducks a mix.
+//!
+//! [`MusicDucker`] keeps the window of every cue still playing and moves an envelope toward the
+//! deepest of them, quickly on the way down ([`DuckSettings::attack_secs`]) and slowly on the way
+//! back up ([`DuckSettings::release_secs`]). Overlapping cues take the deepest duck rather than
+//! adding up. The envelope is applied to the music bus, on top of the music's own volume, so the
+//! volume a screen sets for the music is never changed; music that is already close to silent
+//! isn't ducked at all.
+//!
+//! [`WarningCues`] decides when the enemy sting and the timer beep play, from the facts of each
+//! frame.
+//!
+//! # Usage
+//!
+//! ```rust,ignore
+//! use mirador::game::cues::{DuckCue, DuckSettings, MusicDucker};
+//!
+//! let mut ducker = MusicDucker::new(DuckSettings::default());
+//! ducker.trigger(DuckCue::CaptureSting);
+//!
+//! // Once per frame, with the music's volume in decibels
+//! let duck_db = ducker.advance(0.1, -10.0);
+//! assert_eq!(duck_db, -6.0);
+//! ```
+
+use crate::game::combo::DANGER_RANGE;
+use crate::game::haptics::ENEMY_CLOSE_COOLDOWN;
+
+/// How far the music is turned down under the loudest cue, in decibels.
+pub const DEFAULT_DUCK_DB: f32 = -6.0;
+
+/// Seconds the music takes to duck all the way down.
+pub const DEFAULT_ATTACK_SECS: f32 = 0.05;
+
+/// Seconds the music takes to come all the way back up.
+pub const DEFAULT_RELEASE_SECS: f32 = 0.6;
+
+/// Music at or below this volume, in decibels, is quiet enough that it isn't ducked.
+pub const MIN_DUCKED_MUSIC_DB: f32 = -40.0;
+
+/// A sound important enough to duck the music while it plays.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DuckCue {
+    /// The enemy just came within danger range
+    EnemySting,
+    /// The level timer just crossed its critical threshold
+    TimerCritical,
+    /// The enemy caught the player
+    CaptureSting,
+    /// A click-to-move target can't be walked to
+    Denied,
+}
+
+impl DuckCue {
+    /// Returns how long the cue holds the music down, about the length of its sound.
+    pub fn hold_secs(self) -> f32 {
+        match self {
+            DuckCue::EnemySting => 0.8,
+            DuckCue::TimerCritical => 0.6,
+            DuckCue::CaptureSting => 1.2,
+            DuckCue::Denied => 0.3,
+        }
+    }
+
+    /// Returns how deep the cue ducks the music, as a fraction of [`DuckSettings::amount_db`].
+    pub fn depth(self) -> f32 {
+        match self {
+            DuckCue::EnemySting | DuckCue::CaptureSting => 1.0,
+            DuckCue::TimerCritical => 0.8,
+            DuckCue::Denied => 0.5,
+        }
+    }
+}
+
+/// How the music is ducked under cues.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DuckSettings {
+    /// Change in music volume under a full-depth cue, in decibels; negative turns it down
+    pub amount_db: f32,
+    /// Seconds to duck all the way down
+    pub attack_secs: f32,
+    /// Seconds to come all the way back up
+    pub release_secs: f32,
+}
+
+impl Default for DuckSettings {
+    fn default() -> Self {
+        Self {
+            amount_db: DEFAULT_DUCK_DB,
+            attack_secs: DEFAULT_ATTACK_SECS,
+            release_secs: DEFAULT_RELEASE_SECS,
+        }
+    }
+}
+
+/// Moves the duck envelope one step toward its target.
+///
+/// The envelope rises at a constant rate that covers the full range in `attack_secs`, and falls
+/// at one that covers it in `release_secs`. It never overshoots the target.
+///
+/// # Arguments
+/// * `level` - Current depth of the duck, from 0.0 (none) to 1.0 (full)
+/// * `target` - Depth the playing cues ask for
+/// * `delta_time` - Seconds since the last step
+/// * `attack_secs` - Seconds to go from no duck to full
+/// * `release_secs` - Seconds to go from full duck to none
+///
+/// # Returns
+/// The new depth of the duck
+pub fn envelope_step(
+    level: f32,
+    target: f32,
+    delta_time: f32,
+    attack_secs: f32,
+    release_secs: f32,
+) -> f32 {
+    let ramp_secs = if target > level {
+        attack_secs
+    } else {
+        release_secs
+    };
+    if ramp_secs <= 0.0 {
+        return target;
+    }
+    let step = delta_time.max(0.0) / ramp_secs;
+    if target > level {
+        (level + step).min(target)
+    } else {
+        (level - step).max(target)
+    }
+}
+
+/// Turns the music down while warning cues play.
+#[derive(Debug, Clone)]
+pub struct MusicDucker {
+    /// How far and how fast the music ducks
+    pub settings: DuckSettings,
+    /// Cues still playing, with the seconds each has left
+    active: Vec<(DuckCue, f32)>,
+    /// Current depth of the duck, from 0.0 to 1.0
+    level: f32,
+}
+
+impl Default for MusicDucker {
+    fn default() -> Self {
+        Self::new(DuckSettings::default())
+    }
+}
+
+impl MusicDucker {
+    /// Creates a ducker with no cues playing.
+    ///
+    /// # Arguments
+    /// * `settings` - How far and how fast the music ducks
+    pub fn new(settings: DuckSettings) -> Self {
+        Self {
+            settings,
+            active: Vec::new(),
+            level: 0.0,
+        }
+    }
+
+    /// Starts a cue's window, or restarts it if the cue is already playing.
+    ///
+    /// # Arguments
+    /// * `cue` - The cue that just started playing
+    pub fn trigger(&mut self, cue: DuckCue) {
+        self.active.retain(|(playing, _)| *playing != cue);
+        self.active.push((cue, cue.hold_secs()));
+    }
+
+    /// Returns the depth the playing cues ask for: the deepest of them, or 0.0 for none.
+    pub fn target(&self) -> f32 {
+        self.active
+            .iter()
+            .map(|(cue, _)| cue.depth())
+            .fold(0.0, f32::max)
+    }
+
+    /// Advances the cue windows and the envelope by one frame.
+    ///
+    /// # Arguments
+    /// * `delta_time` - Seconds since the last frame
+    /// * `music_db` - The music's own volume in decibels; at or below
+    ///   [`MIN_DUCKED_MUSIC_DB`] the duck is dropped
+    ///
+    /// # Returns
+    /// The change to apply to the music bus this frame, in decibels
+    pub fn advance(&mut self, delta_time: f32, music_db: f32) -> f32 {
+        for (_, remaining) in &mut self.active {
+            *remaining -= delta_time;
+        }
+        self.active.retain(|(_, remaining)| *remaining > 0.0);
+
+        if music_db <= MIN_DUCKED_MUSIC_DB {
+            self.level = 0.0;
+            return 0.0;
+        }
+
+        self.level = envelope_step(
+            self.level,
+            self.target(),
+            delta_time,
+            self.settings.attack_secs,
+            self.settings.release_secs,
+        );
+        self.level * self.settings.amount_db
+    }
+}
+
+/// Decides when the enemy sting and the timer beep play.
+#[derive(Debug, Clone, Default)]
+pub struct WarningCues {
+    /// Whether the enemy was within danger range last frame
+    enemy_close: bool,
+    /// Whether the timer was critical last frame
+    timer_critical: bool,
+    /// Seconds until the enemy sting can play again
+    sting_cooldown: f32,
+}
+
+impl WarningCues {
+    /// Checks one frame for cues to play.
+    ///
+    /// The enemy sting plays when the enemy comes within [`DANGER_RANGE`], at most once every
+    /// [`ENEMY_CLOSE_COOLDOWN`] seconds, and the timer beep when the timer crosses its critical
+    /// threshold.
+    ///
+    /// # Arguments
+    /// * `delta_time` - Seconds since the last frame
+    /// * `enemy_distance` - Distance to the enemy, or `None` if there isn't one
+    /// * `timer_critical` - Whether the timer is running and below its critical threshold
+    ///
+    /// # Returns
+    /// The cues to play this frame
+    pub fn update(
+        &mut self,
+        delta_time: f32,
+        enemy_distance: Option<f32>,
+        timer_critical: bool,
+    ) -> Vec<DuckCue> {
+        self.sting_cooldown -= delta_time;
+        let enemy_close = enemy_distance.is_some_and(|distance| distance < DANGER_RANGE);
+
+        let mut cues = Vec::new();
+        if enemy_close && !self.enemy_close && self.sting_cooldown <= 0.0 {
+            self.sting_cooldown = ENEMY_CLOSE_COOLDOWN;
+            cues.push(DuckCue::EnemySting);
+        }
+        if timer_critical && !self.timer_critical {
+            cues.push(DuckCue::TimerCritical);
+        }
+
+        self.enemy_close = enemy_close;
+        self.timer_critical = timer_critical;
+        cues
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FRAME: f32 = 0.01;
+
+    /// Advances the ducker through `secs` of frames, returning the last duck in decibels.
+    fn run(ducker: &mut MusicDucker, secs: f32) -> f32 {
+        let mut duck = 0.0;
+        for _ in 0..(secs / FRAME).round() as usize {
+            duck = ducker.advance(FRAME, -10.0);
+        }
+        duck
+    }
+
+    #[test]
+    fn test_envelope_attacks_fast_and_releases_slowly() {
+        assert_eq!(envelope_step(0.0, 1.0, 0.025, 0.05, 0.6), 0.5);
+        assert_eq!(envelope_step(0.0, 1.0, 0.1, 0.05, 0.6), 1.0);
+        assert!((envelope_step(1.0, 0.0, 0.3, 0.05, 0.6) - 0.5).abs() < 1e-6);
+        assert_eq!(envelope_step(0.4, 0.0, 1.0, 0.05, 0.6), 0.0);
+        // Zero-length ramps jump straight to the target
+        assert_eq!(envelope_step(0.0, 0.8, 0.001, 0.0, 0.6), 0.8);
+    }
+
+    #[test]
+    fn test_cue_ducks_then_releases() {
+        let mut ducker = MusicDucker::default();
+        ducker.trigger(DuckCue::EnemySting);
+
+        assert!((run(&mut ducker, DEFAULT_ATTACK_SECS) - DEFAULT_DUCK_DB).abs() < 1e-4);
+        // Still held while the sting plays
+        assert!((run(&mut ducker, 0.5) - DEFAULT_DUCK_DB).abs() < 1e-4);
+
+        // Halfway back up half a release after the sting ends
+        run(&mut ducker, DuckCue::EnemySting.hold_secs() - 0.55);
+        let halfway = run(&mut ducker, DEFAULT_RELEASE_SECS / 2.0);
+        assert!((halfway - DEFAULT_DUCK_DB / 2.0).abs() < 0.2, "{}", halfway);
+        assert_eq!(run(&mut ducker, DEFAULT_RELEASE_SECS), 0.0);
+    }
+
+    #[test]
+    fn test_overlapping_cues_take_the_deepest_duck() {
+        let mut ducker = MusicDucker::default();
+        ducker.trigger(DuckCue::Denied);
+        ducker.trigger(DuckCue::TimerCritical);
+        ducker.trigger(DuckCue::Denied);
+        assert_eq!(ducker.target(), DuckCue::TimerCritical.depth());
+        let duck = run(&mut ducker, 0.2);
+        assert!((duck - 0.8 * DEFAULT_DUCK_DB).abs() < 1e-4);
+
+        // Both cues have ended by now
+        run(&mut ducker, 0.5);
+        assert_eq!(ducker.target(), 0.0);
+    }
+
+    #[test]
+    fn test_quiet_music_is_not_ducked() {
+        let mut ducker = MusicDucker::default();
+        ducker.trigger(DuckCue::CaptureSting);
+        assert_eq!(ducker.advance(0.1, MIN_DUCKED_MUSIC_DB), 0.0);
+        assert_eq!(ducker.advance(0.1, -60.0), 0.0);
+        assert_eq!(ducker.advance(0.1, -10.0), DEFAULT_DUCK_DB);
+    }
+
+    #[test]
+    fn test_warning_cues_fire_on_crossing() {
+        let mut cues = WarningCues::default();
+        assert!(cues.update(FRAME, Some(500.0), false).is_empty());
+        assert_eq!(
+            cues.update(FRAME, Some(DANGER_RANGE - 1.0), true),
+            vec![DuckCue::EnemySting, DuckCue::TimerCritical]
+        );
+        assert!(cues.update(FRAME, Some(10.0), true).is_empty());
+
+        // Leaving and coming back straight away is still on cooldown
+        cues.update(FRAME, Some(500.0), true);
+        assert!(cues.update(FRAME, Some(10.0), true).is_empty());
+        cues.update(ENEMY_CLOSE_COOLDOWN, Some(500.0), true);
+        assert_eq!(
+            cues.update(FRAME, Some(10.0), true),
+            vec![DuckCue::EnemySting]
+        );
+    }
+}
@@ -0,0 +1,604 @@
+//! Seeded daily challenge for the Mirador game.
+//!
+//! Every UTC day has one [`DailyChallenge`]: a seed derived from the date with a 64-bit FNV-1a
+//! hash of `"mirador-YYYY-MM-DD"`, a fixed enemy pressure, and two [`DailyModifier`]s picked from
+//! the same hash. Each level of a daily run generates its maze from [`DailyChallenge::level_seed`],
+//! so everyone playing on the same day gets the same mazes. The date always comes from UTC, so the
+//! challenge doesn't change with the player's timezone.
+//!
+//! Only the first completed attempt of a day counts. [`DailyResults`] keeps one [`DailyResult`]
+//! per day in the data directory (see [`crate::paths`]); once a day has a completed result, later
+//! runs that day are practice and [`DailyResults::record`] refuses to overwrite it. Results dated
+//! before the newest recorded day are refused too, so turning the system clock back can't replay
+//! an old day.
+//!
+//! # Usage
+//!
+//! ```rust,ignore
+//! let today = daily::utc_today();
+//! let challenge = DailyChallenge::for_date(today);
+//! let practice = !results.can_record(today);
+//!
+//! // When the run ends
+//! results.record(DailyResult::completed(today, score, level))?;
+//! ```
+
+use crate::game::director::EnemyTuning;
+use crate::game::player::Player;
+use crate::game::score::group_digits;
+use crate::paths;
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+/// Number of days kept in the results file; the oldest are dropped first.
+pub const MAX_DAILY_RESULTS: usize = 365;
+
+/// Number of days listed in the history on the game over screen, today included.
+pub const HISTORY_DAYS: usize = 7;
+
+/// Number of modifiers active in each daily challenge.
+pub const MODIFIERS_PER_DAY: usize = 2;
+
+/// Enemy pressures a daily challenge can be played at, from the director's range.
+const DAILY_PRESSURES: [f32; 4] = [-0.25, 0.0, 0.25, 0.5];
+
+/// Offset basis of the 64-bit FNV-1a hash.
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+
+/// Prime of the 64-bit FNV-1a hash.
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// Hashes bytes with 64-bit FNV-1a.
+///
+/// # Arguments
+/// * `bytes` - The bytes to hash
+///
+/// # Returns
+/// The hash, identical on every platform
+pub fn fnv1a_64(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(FNV_OFFSET_BASIS, |hash, &byte| {
+        (hash ^ byte as u64).wrapping_mul(FNV_PRIME)
+    })
+}
+
+/// Returns the current UTC date.
+pub fn utc_today() -> NaiveDate {
+    utc_date(Utc::now())
+}
+
+/// Returns the UTC date of an instant.
+///
+/// # Arguments
+/// * `now` - The instant, in any timezone converted to UTC
+///
+/// # Returns
+/// The calendar date in UTC
+pub fn utc_date(now: DateTime<Utc>) -> NaiveDate {
+    now.date_naive()
+}
+
+/// Formats a date the way it is stored in the results file.
+///
+/// # Arguments
+/// * `date` - The date to format
+///
+/// # Returns
+/// The date as `YYYY-MM-DD`
+pub fn date_key(date: NaiveDate) -> String {
+    date.format("%Y-%m-%d").to_string()
+}
+
+/// Parses a date stored in the results file.
+///
+/// # Arguments
+/// * `key` - The date as `YYYY-MM-DD`
+///
+/// # Returns
+/// The date, or `None` if the text isn't a valid date
+pub fn parse_date_key(key: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(key, "%Y-%m-%d").ok()
+}
+
+/// A rule change applied to every level of a daily challenge.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DailyModifier {
+    /// The enemy moves 10% faster
+    SwiftHunter,
+    /// The enemy notices the player from 25% farther away
+    KeenHunter,
+    /// The enemy tries new directions 20% more often
+    RestlessHunter,
+    /// The player walks 10% slower
+    HeavyLegs,
+    /// The player has 25% less stamina
+    ShortBreath,
+}
+
+impl DailyModifier {
+    /// Every modifier, in the order the daily hash indexes them.
+    pub const ALL: [DailyModifier; 5] = [
+        DailyModifier::SwiftHunter,
+        DailyModifier::KeenHunter,
+        DailyModifier::RestlessHunter,
+        DailyModifier::HeavyLegs,
+        DailyModifier::ShortBreath,
+    ];
+
+    /// Returns the name shown on the game over screen.
+    pub fn name(self) -> &'static str {
+        match self {
+            DailyModifier::SwiftHunter => "Swift Hunter",
+            DailyModifier::KeenHunter => "Keen Hunter",
+            DailyModifier::RestlessHunter => "Restless Hunter",
+            DailyModifier::HeavyLegs => "Heavy Legs",
+            DailyModifier::ShortBreath => "Short Breath",
+        }
+    }
+
+    /// Applies the modifier to the enemy tuning.
+    ///
+    /// # Arguments
+    /// * `tuning` - The tuning to adjust; player modifiers leave it unchanged
+    pub fn apply_to_enemy(self, tuning: &mut EnemyTuning) {
+        match self {
+            DailyModifier::SwiftHunter => tuning.speed_multiplier *= 1.1,
+            DailyModifier::KeenHunter => tuning.alert_radius_multiplier *= 1.25,
+            DailyModifier::RestlessHunter => tuning.replan_multiplier *= 1.2,
+            DailyModifier::HeavyLegs | DailyModifier::ShortBreath => {}
+        }
+    }
+
+    /// Applies the modifier to a freshly created player.
+    ///
+    /// # Arguments
+    /// * `player` - The player to adjust; enemy modifiers leave it unchanged
+    pub fn apply_to_player(self, player: &mut Player) {
+        match self {
+            DailyModifier::HeavyLegs => player.base_speed *= 0.9,
+            DailyModifier::ShortBreath => {
+                player.max_stamina *= 0.75;
+                player.stamina = player.stamina.min(player.max_stamina);
+            }
+            DailyModifier::SwiftHunter
+            | DailyModifier::KeenHunter
+            | DailyModifier::RestlessHunter => {}
+        }
+    }
+}
+
+/// The challenge every player gets on one UTC day.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DailyChallenge {
+    /// The UTC date of the challenge
+    pub date: NaiveDate,
+    /// FNV-1a hash of `"mirador-YYYY-MM-DD"`
+    pub seed: u64,
+    /// Fixed director pressure the enemy is tuned for
+    pub pressure: f32,
+    /// Modifiers active on every level
+    pub modifiers: [DailyModifier; MODIFIERS_PER_DAY],
+}
+
+impl DailyChallenge {
+    /// Derives the challenge for a date.
+    ///
+    /// # Arguments
+    /// * `date` - The UTC date
+    ///
+    /// # Returns
+    /// The same challenge on every platform for the same date
+    pub fn for_date(date: NaiveDate) -> Self {
+        let seed = fnv1a_64(format!("mirador-{}", date_key(date)).as_bytes());
+        let pressure = DAILY_PRESSURES[((seed >> 8) % DAILY_PRESSURES.len() as u64) as usize];
+
+        // Two distinct modifiers: the second is offset from the first by 1..len
+        let count = DailyModifier::ALL.len() as u64;
+        let first = (seed >> 16) % count;
+        let second = (first + 1 + (seed >> 32) % (count - 1)) % count;
+
+        Self {
+            date,
+            seed,
+            pressure,
+            modifiers: [
+                DailyModifier::ALL[first as usize],
+                DailyModifier::ALL[second as usize],
+            ],
+        }
+    }
+
+    /// Returns the maze seed for a level of the challenge.
+    ///
+    /// # Arguments
+    /// * `level` - The level number, starting at 1
+    ///
+    /// # Returns
+    /// FNV-1a hash of `"mirador-YYYY-MM-DD-level-N"`
+    pub fn level_seed(&self, level: i32) -> u64 {
+        fnv1a_64(format!("mirador-{}-level-{}", date_key(self.date), level).as_bytes())
+    }
+
+    /// Returns the enemy tuning for every level of the challenge.
+    ///
+    /// # Returns
+    /// The tuning for the fixed pressure with the enemy modifiers applied
+    pub fn enemy_tuning(&self) -> EnemyTuning {
+        let mut tuning = EnemyTuning::for_pressure(self.pressure);
+        for modifier in self.modifiers {
+            modifier.apply_to_enemy(&mut tuning);
+        }
+        tuning
+    }
+
+    /// Applies the player modifiers to a freshly created player.
+    ///
+    /// # Arguments
+    /// * `player` - The player starting the run
+    pub fn apply_to_player(&self, player: &mut Player) {
+        for modifier in self.modifiers {
+            modifier.apply_to_player(player);
+        }
+    }
+
+    /// Returns the modifier names joined for display.
+    pub fn modifier_names(&self) -> String {
+        self.modifiers
+            .iter()
+            .map(|modifier| modifier.name())
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+/// A daily challenge run in progress.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DailyRun {
+    /// The challenge being played
+    pub challenge: DailyChallenge,
+    /// Whether the day already has a recorded result, so this run won't be recorded
+    pub practice: bool,
+}
+
+impl DailyRun {
+    /// Returns the label shown next to the level on the HUD.
+    pub fn hud_label(&self) -> &'static str {
+        if self.practice {
+            "Daily (practice)"
+        } else {
+            "Daily"
+        }
+    }
+}
+
+/// The recorded result of one day's challenge.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DailyResult {
+    /// The UTC date of the challenge, `YYYY-MM-DD`
+    pub date: String,
+    /// Run score when the attempt ended
+    pub score: u32,
+    /// Level the attempt ended on
+    pub level: i32,
+    /// Whether the attempt ended in a game over rather than being abandoned
+    pub completed_attempt: bool,
+}
+
+impl DailyResult {
+    /// Creates the result of an attempt that ended in a game over.
+    ///
+    /// # Arguments
+    /// * `date` - The UTC date of the challenge
+    /// * `score` - Run score when the attempt ended
+    /// * `level` - Level the attempt ended on
+    pub fn completed(date: NaiveDate, score: u32, level: i32) -> Self {
+        Self {
+            date: date_key(date),
+            score,
+            level,
+            completed_attempt: true,
+        }
+    }
+
+    /// Creates the result of an attempt the player quit before it ended.
+    ///
+    /// Abandoned results are shown in the history but don't lock the day.
+    ///
+    /// # Arguments
+    /// * `date` - The UTC date of the challenge
+    /// * `score` - Run score when the attempt was abandoned
+    /// * `level` - Level the attempt was abandoned on
+    pub fn abandoned(date: NaiveDate, score: u32, level: i32) -> Self {
+        Self {
+            completed_attempt: false,
+            ..Self::completed(date, score, level)
+        }
+    }
+}
+
+/// Daily challenge results that persist between sessions.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DailyResults {
+    /// One result per day, oldest first
+    pub results: Vec<DailyResult>,
+}
+
+impl DailyResults {
+    /// Loads results from the data directory.
+    ///
+    /// # Returns
+    /// The saved results, or no results if the file is missing or invalid
+    pub fn load() -> Self {
+        Self::load_from(&paths::get().daily_results())
+    }
+
+    /// Loads results from a specific file.
+    ///
+    /// # Arguments
+    /// * `path` - The results file to read
+    ///
+    /// # Returns
+    /// The saved results, or no results if the file is missing or invalid
+    pub fn load_from(path: &Path) -> Self {
+        let contents = match fs::read_to_string(path) {
+            Ok(contents) => contents,
+            Err(_) => return Self::default(),
+        };
+
+        match serde_json::from_str(&contents) {
+            Ok(results) => results,
+            Err(e) => {
+                eprintln!(
+                    "Failed to parse daily results file {}: {}, starting fresh",
+                    path.display(),
+                    e
+                );
+                Self::default()
+            }
+        }
+    }
+
+    /// Saves results to the data directory.
+    ///
+    /// # Returns
+    /// `Ok(())` on success, or an error message if the file could not be written
+    pub fn save(&self) -> Result<(), String> {
+        self.save_to(&paths::get().daily_results())
+    }
+
+    /// Saves results to a specific file.
+    ///
+    /// # Arguments
+    /// * `path` - The results file to write
+    ///
+    /// # Returns
+    /// `Ok(())` on success, or an error message if the file could not be written
+    pub fn save_to(&self, path: &Path) -> Result<(), String> {
+        let contents = serde_json::to_string_pretty(self)
+            .map_err(|e| format!("Failed to serialize daily results: {}", e))?;
+        fs::write(path, contents)
+            .map_err(|e| format!("Failed to write daily results to {}: {}", path.display(), e))
+    }
+
+    /// Returns the result recorded for a date, if any.
+    ///
+    /// # Arguments
+    /// * `date` - The UTC date
+    pub fn get(&self, date: NaiveDate) -> Option<&DailyResult> {
+        let key = date_key(date);
+        self.results.iter().find(|result| result.date == key)
+    }
+
+    /// Returns the newest date with a recorded result.
+    pub fn latest_date(&self) -> Option<NaiveDate> {
+        self.results
+            .iter()
+            .filter_map(|result| parse_date_key(&result.date))
+            .max()
+    }
+
+    /// Returns whether a new result may still be recorded for a date.
+    ///
+    /// # Arguments
+    /// * `date` - The UTC date
+    ///
+    /// # Returns
+    /// `false` once the date has a completed result or if a later day has a result
+    pub fn can_record(&self, date: NaiveDate) -> bool {
+        if self.latest_date().is_some_and(|latest| date < latest) {
+            return false;
+        }
+        !self
+            .get(date)
+            .is_some_and(|result| result.completed_attempt)
+    }
+
+    /// Records a day's result.
+    ///
+    /// An abandoned result for the same day is replaced; a completed one never is.
+    ///
+    /// # Arguments
+    /// * `result` - The result to record
+    ///
+    /// # Returns
+    /// `Ok(())` if the result was recorded, or why it was refused
+    pub fn record(&mut self, result: DailyResult) -> Result<(), String> {
+        let date = parse_date_key(&result.date)
+            .ok_or_else(|| format!("Invalid daily result date {}", result.date))?;
+        if let Some(latest) = self.latest_date().filter(|&latest| date < latest) {
+            return Err(format!(
+                "Refusing daily result for {}: a result for {} is already recorded; \
+                 has the system clock moved back?",
+                result.date,
+                date_key(latest)
+            ));
+        }
+
+        match self.results.iter_mut().find(|r| r.date == result.date) {
+            Some(existing) if existing.completed_attempt => {
+                return Err(format!(
+                    "The daily challenge for {} already has a recorded result",
+                    result.date
+                ));
+            }
+            Some(existing) => *existing = result,
+            None => self.results.push(result),
+        }
+
+        self.results.sort_by(|a, b| a.date.cmp(&b.date));
+        let excess = self.results.len().saturating_sub(MAX_DAILY_RESULTS);
+        self.results.drain(..excess);
+        Ok(())
+    }
+
+    /// Formats today's result and the history for the game over screen.
+    ///
+    /// # Arguments
+    /// * `today` - The UTC date of the challenge being played
+    ///
+    /// # Returns
+    /// One line for today followed by one line per day of the last [`HISTORY_DAYS`] days
+    pub fn summary_lines(&self, today: NaiveDate) -> String {
+        let describe = |result: Option<&DailyResult>| match result {
+            Some(result) if result.completed_attempt => {
+                format!(
+                    "{} pts, level {}",
+                    group_digits(result.score.into()),
+                    result.level
+                )
+            }
+            Some(result) => format!(
+                "{} pts, level {} (unfinished)",
+                group_digits(result.score.into()),
+                result.level
+            ),
+            None => "-".to_string(),
+        };
+
+        let mut lines = vec![
+            format!("Daily {}: {}", date_key(today), describe(self.get(today))),
+            "Last 7 days:".to_string(),
+        ];
+        for days_ago in 0..HISTORY_DAYS as i64 {
+            let date = today - Duration::days(days_ago);
+            lines.push(format!(
+                "{}  {}",
+                date.format("%m-%d"),
+                describe(self.get(date))
+            ));
+        }
+        lines.join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{FixedOffset, TimeZone};
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).expect("valid date")
+    }
+
+    #[test]
+    fn test_date_derivation_is_stable() {
+        // Reference vectors for 64-bit FNV-1a
+        assert_eq!(fnv1a_64(b""), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(fnv1a_64(b"a"), 0xaf63_dc4c_8601_ec8c);
+
+        let day = date(2025, 3, 14);
+        let challenge = DailyChallenge::for_date(day);
+        assert_eq!(challenge.seed, fnv1a_64(b"mirador-2025-03-14"));
+        assert_eq!(challenge, DailyChallenge::for_date(day));
+        assert_ne!(challenge.modifiers[0], challenge.modifiers[1]);
+        assert!(DAILY_PRESSURES.contains(&challenge.pressure));
+        assert_ne!(challenge.level_seed(1), challenge.level_seed(2));
+
+        let next_day = DailyChallenge::for_date(date(2025, 3, 15));
+        assert_ne!(challenge.seed, next_day.seed);
+        assert_ne!(challenge.level_seed(1), next_day.level_seed(1));
+
+        // Every day over a year picks two distinct modifiers
+        for offset in 0..365 {
+            let challenge = DailyChallenge::for_date(day + Duration::days(offset));
+            assert_ne!(challenge.modifiers[0], challenge.modifiers[1]);
+        }
+    }
+
+    #[test]
+    fn test_date_is_the_same_in_every_timezone() {
+        // 23:30 in New York on March 13 is already March 14 in UTC
+        let new_york = FixedOffset::west_opt(4 * 3600).expect("valid offset");
+        let tokyo = FixedOffset::east_opt(9 * 3600).expect("valid offset");
+        let evening = new_york
+            .with_ymd_and_hms(2025, 3, 13, 23, 30, 0)
+            .unwrap()
+            .with_timezone(&Utc);
+        let morning = tokyo
+            .with_ymd_and_hms(2025, 3, 14, 12, 30, 0)
+            .unwrap()
+            .with_timezone(&Utc);
+        assert_eq!(utc_date(evening), date(2025, 3, 14));
+        assert_eq!(utc_date(evening), utc_date(morning));
+    }
+
+    #[test]
+    fn test_only_first_completed_attempt_counts() {
+        let today = date(2025, 3, 14);
+        let mut results = DailyResults::default();
+        assert!(results.can_record(today));
+
+        // Abandoning doesn't lock the day
+        results
+            .record(DailyResult::abandoned(today, 100, 2))
+            .expect("record abandoned");
+        assert!(results.can_record(today));
+
+        results
+            .record(DailyResult::completed(today, 900, 4))
+            .expect("record completed");
+        assert!(!results.can_record(today));
+        assert!(
+            results
+                .record(DailyResult::completed(today, 5000, 9))
+                .is_err()
+        );
+        assert_eq!(results.get(today).map(|r| r.score), Some(900));
+        assert_eq!(results.results.len(), 1);
+
+        // Turning the clock back to an earlier, unplayed day is refused too
+        let yesterday = today - Duration::days(1);
+        assert!(!results.can_record(yesterday));
+        assert!(
+            results
+                .record(DailyResult::completed(yesterday, 5000, 9))
+                .is_err()
+        );
+
+        let tomorrow = today + Duration::days(1);
+        assert!(results.can_record(tomorrow));
+        results
+            .record(DailyResult::completed(tomorrow, 300, 2))
+            .expect("record next day");
+
+        let summary = results.summary_lines(tomorrow);
+        assert!(summary.starts_with("Daily 2025-03-15: 300 pts, level 2"));
+        assert!(summary.contains("03-14  900 pts, level 4"));
+        assert!(summary.contains("03-13  -"));
+        assert_eq!(summary.lines().count(), 2 + HISTORY_DAYS);
+    }
+
+    #[test]
+    fn test_results_round_trip() {
+        let path = std::env::temp_dir().join("mirador_daily_results_round_trip.json");
+        let mut results = DailyResults::default();
+        results
+            .record(DailyResult::completed(date(2025, 3, 14), 900, 4))
+            .expect("record completed");
+        results.save_to(&path).expect("save results");
+        assert_eq!(DailyResults::load_from(&path), results);
+        let _ = fs::remove_file(&path);
+    }
+}
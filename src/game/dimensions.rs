@@ -0,0 +1,105 @@
+//! The player's size and reach.
+//!
+//! [`PlayerDimensions`] gathers the distances that decide how the player fits through the maze
+//! and how close things have to be: the radius and height of the collision cylinder, how far
+//! the player can reach to use an interactable, and how close the enemy has to get to catch
+//! them. They live on the game state so upgrades such as "Slim" and test mode can change them.
+//! Change them through [`GameState::set_dimensions`](crate::game::GameState::set_dimensions),
+//! which keeps the collision system in step and moves the player out of any wall the new size
+//! would leave them inside.
+//!
+//! # Usage
+//!
+//! ```rust,ignore
+//! let mut dimensions = PlayerDimensions::for_mode(game_state.is_test_mode);
+//! dimensions.collision_radius *= 0.9;
+//! game_state.set_dimensions(dimensions);
+//! ```
+
+use crate::game::interact::INTERACT_RANGE;
+
+/// Radius of the player's collision cylinder, in world units.
+pub const DEFAULT_COLLISION_RADIUS: f32 = 5.0;
+
+/// Height of the player's collision cylinder, in world units.
+pub const DEFAULT_COLLISION_HEIGHT: f32 = 100.0;
+
+/// How close the enemy has to get to catch the player on level 1, in world units.
+///
+/// The enemy's actual catch distance shrinks on later levels, see
+/// [`crate::game::enemy::capture_distance`].
+pub const DEFAULT_CATCH_RADIUS: f32 = 15.0;
+
+/// How much further the player reaches in test mode, so interactables placed in the
+/// hand-edited test maze can be tried from anywhere in their cell.
+pub const TEST_MODE_REACH_SCALE: f32 = 2.0;
+
+/// The player's collision size and the ranges things happen at around them.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PlayerDimensions {
+    /// Radius of the collision cylinder
+    pub collision_radius: f32,
+    /// Height of the collision cylinder
+    pub collision_height: f32,
+    /// Farthest an interactable can be from the player's eye and still be used
+    pub reach: f32,
+    /// How close the enemy has to get to catch the player, before level scaling
+    pub catch_radius: f32,
+}
+
+impl Default for PlayerDimensions {
+    fn default() -> Self {
+        Self {
+            collision_radius: DEFAULT_COLLISION_RADIUS,
+            collision_height: DEFAULT_COLLISION_HEIGHT,
+            reach: INTERACT_RANGE,
+            catch_radius: DEFAULT_CATCH_RADIUS,
+        }
+    }
+}
+
+impl PlayerDimensions {
+    /// Returns the dimensions used in test mode.
+    pub fn test_mode() -> Self {
+        Self {
+            reach: INTERACT_RANGE * TEST_MODE_REACH_SCALE,
+            ..Self::default()
+        }
+    }
+
+    /// Returns the dimensions a run starts with, before any upgrades.
+    ///
+    /// # Arguments
+    /// * `is_test_mode` - Whether test mode is on
+    pub fn for_mode(is_test_mode: bool) -> Self {
+        if is_test_mode {
+            Self::test_mode()
+        } else {
+            Self::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_test_mode_only_extends_reach() {
+        let (normal, test) = (
+            PlayerDimensions::for_mode(false),
+            PlayerDimensions::for_mode(true),
+        );
+        assert_eq!(normal, PlayerDimensions::default());
+        assert_eq!(normal.collision_radius, 5.0);
+        assert_eq!(normal.collision_height, 100.0);
+        assert!(test.reach > normal.reach);
+        assert_eq!(
+            PlayerDimensions {
+                reach: normal.reach,
+                ..test
+            },
+            normal
+        );
+    }
+}
@@ -0,0 +1,294 @@
+//! Adaptive enemy difficulty for the Mirador game.
+//!
+//! [`DifficultyDirector`] rubber-bands the enemy to the player. After each level it looks at the
+//! level's [`LevelResult`] — how long the player took compared to a par time derived from the
+//! maze's solution length, how many close calls they had with the enemy, and whether they died —
+//! and nudges a single *pressure* value between [`MIN_PRESSURE`] and [`MAX_PRESSURE`]. The
+//! pressure maps to an [`EnemyTuning`] that is applied when the next level is set up: strong
+//! players get a faster enemy that notices them from farther away and replans more eagerly,
+//! struggling players get breathing room.
+//!
+//! The director is a pure state machine with no knowledge of rendering, audio, or the enemy
+//! itself. Pressure carries over into the next run, so a player who just lost starts the new run
+//! with an easier enemy.
+//!
+//! # Usage
+//!
+//! ```rust,ignore
+//! let mut director = DifficultyDirector::new();
+//!
+//! // When a level ends, cleared or not
+//! let tuning = director.record_level(&game_state.level_result(), died);
+//!
+//! // When the next level's enemy is spawned
+//! enemy.tuning = director.tuning();
+//! ```
+
+use crate::game::score::LevelResult;
+use serde::{Deserialize, Serialize};
+
+/// Lowest pressure, reached by players who keep dying or crawling through levels.
+pub const MIN_PRESSURE: f32 = -1.0;
+
+/// Highest pressure, reached by players who keep clearing levels well under par.
+pub const MAX_PRESSURE: f32 = 1.0;
+
+/// Largest change to enemy speed at full pressure in either direction (±15%).
+pub const MAX_SPEED_ADJUSTMENT: f32 = 0.15;
+
+/// Largest change to the enemy's alert (pursuit) radius at full pressure (±25%).
+pub const MAX_ALERT_ADJUSTMENT: f32 = 0.25;
+
+/// Largest change to how aggressively the enemy replans its route at full pressure (±20%).
+pub const MAX_REPLAN_ADJUSTMENT: f32 = 0.2;
+
+/// Enemy distance in world units that counts as a close call.
+pub const CLOSE_CALL_RANGE: f32 = 150.0;
+
+/// Par time per step of the maze's solution path, in seconds.
+///
+/// A grid step of the standard 25x25 maze is about 60 world units, which the player covers in
+/// about half a second at walking speed.
+pub const PAR_SECONDS_PER_STEP: f32 = 0.5;
+
+/// Largest pressure gain from a single level, earned by clearing it in no time at all.
+const MAX_CLEAR_GAIN: f32 = 0.25;
+
+/// Largest pressure loss from a single slow clear.
+const MAX_SLOW_LOSS: f32 = 0.25;
+
+/// Pressure lost for each close call, up to [`MAX_CLOSE_CALL_LOSS`].
+const CLOSE_CALL_LOSS: f32 = 0.05;
+
+/// Largest pressure loss from close calls in a single level.
+const MAX_CLOSE_CALL_LOSS: f32 = 0.2;
+
+/// Pressure lost when the player dies.
+const DEATH_LOSS: f32 = 0.4;
+
+/// Multipliers applied to the enemy's level-scaled parameters.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct EnemyTuning {
+    /// Multiplier on the enemy's movement speed
+    pub speed_multiplier: f32,
+    /// Multiplier on the distance at which the enemy notices and pursues the player
+    pub alert_radius_multiplier: f32,
+    /// Multiplier on the enemy's rotation step, i.e. how quickly it tries new directions
+    pub replan_multiplier: f32,
+}
+
+impl EnemyTuning {
+    /// Tuning that leaves the enemy exactly as the level scaling makes it.
+    pub const NEUTRAL: EnemyTuning = EnemyTuning {
+        speed_multiplier: 1.0,
+        alert_radius_multiplier: 1.0,
+        replan_multiplier: 1.0,
+    };
+
+    /// Builds the tuning for a pressure value.
+    ///
+    /// # Arguments
+    /// * `pressure` - Pressure in `[MIN_PRESSURE, MAX_PRESSURE]`; values outside are clamped
+    ///
+    /// # Returns
+    /// Multipliers within `1.0 ± MAX_*_ADJUSTMENT`
+    pub fn for_pressure(pressure: f32) -> Self {
+        let pressure = pressure.clamp(MIN_PRESSURE, MAX_PRESSURE);
+        Self {
+            speed_multiplier: 1.0 + MAX_SPEED_ADJUSTMENT * pressure,
+            alert_radius_multiplier: 1.0 + MAX_ALERT_ADJUSTMENT * pressure,
+            replan_multiplier: 1.0 + MAX_REPLAN_ADJUSTMENT * pressure,
+        }
+    }
+}
+
+impl Default for EnemyTuning {
+    fn default() -> Self {
+        Self::NEUTRAL
+    }
+}
+
+/// Rubber-banding director that adjusts the enemy to the player's recent performance.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DifficultyDirector {
+    /// Current pressure in `[MIN_PRESSURE, MAX_PRESSURE]`
+    pressure: f32,
+    /// Whether the director adapts at all
+    enabled: bool,
+}
+
+impl Default for DifficultyDirector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DifficultyDirector {
+    /// Creates an enabled director with neutral pressure.
+    pub fn new() -> Self {
+        Self {
+            pressure: 0.0,
+            enabled: true,
+        }
+    }
+
+    /// Turns adaptation on or off.
+    ///
+    /// A disabled director always reports [`EnemyTuning::NEUTRAL`] and ignores level results,
+    /// for modes and settings where the difficulty must stay fixed.
+    ///
+    /// # Arguments
+    /// * `enabled` - Whether the director should adapt
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+        if !enabled {
+            self.pressure = 0.0;
+        }
+    }
+
+    /// Returns whether the director is adapting.
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Returns the current pressure.
+    pub fn pressure(&self) -> f32 {
+        self.pressure
+    }
+
+    /// Returns the tuning to apply to the next level's enemy.
+    pub fn tuning(&self) -> EnemyTuning {
+        if self.enabled {
+            EnemyTuning::for_pressure(self.pressure)
+        } else {
+            EnemyTuning::NEUTRAL
+        }
+    }
+
+    /// Updates the pressure from a finished level.
+    ///
+    /// Clearing a level under par raises the pressure in proportion to how far under par the
+    /// player was; clearing it over par, close calls, and dying lower it.
+    ///
+    /// # Arguments
+    /// * `result` - Statistics for the level that just ended
+    /// * `died` - Whether the level ended with the player losing
+    ///
+    /// # Returns
+    /// The tuning to apply to the next level's enemy
+    pub fn record_level(&mut self, result: &LevelResult, died: bool) -> EnemyTuning {
+        if !self.enabled {
+            return EnemyTuning::NEUTRAL;
+        }
+
+        let close_call_loss =
+            (result.close_calls as f32 * CLOSE_CALL_LOSS).min(MAX_CLOSE_CALL_LOSS);
+        let change = if died {
+            -DEATH_LOSS
+        } else {
+            Self::pace_change(result) - close_call_loss
+        };
+        self.pressure = (self.pressure + change).clamp(MIN_PRESSURE, MAX_PRESSURE);
+        self.tuning()
+    }
+
+    /// Pressure change earned by the time a level took compared to its par.
+    ///
+    /// # Arguments
+    /// * `result` - Statistics for the cleared level
+    ///
+    /// # Returns
+    /// A gain of up to [`MAX_CLEAR_GAIN`] under par, a loss of up to [`MAX_SLOW_LOSS`] over par,
+    /// or no change when the level has no known solution length
+    fn pace_change(result: &LevelResult) -> f32 {
+        if result.solution_length == 0 {
+            return 0.0;
+        }
+        let par = result.solution_length as f32 * PAR_SECONDS_PER_STEP;
+        let pace = result.completion_time.max(0.0) / par;
+        if pace < 1.0 {
+            (1.0 - pace) * MAX_CLEAR_GAIN
+        } else {
+            -((pace - 1.0).min(1.0) * MAX_SLOW_LOSS)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cleared(completion_time: f32, close_calls: u32) -> LevelResult {
+        LevelResult {
+            level: 1,
+            completion_time,
+            time_remaining: 30.0 - completion_time,
+            cells_visited: 40,
+            total_cells: 100,
+            closest_enemy_distance: f32::MAX,
+            keys_collected: 0,
+            difficulty_multiplier: 1.0,
+            combo_multiplier: 1.0,
+            solution_length: 40,
+            close_calls,
+            duplicate_anomalies: 0,
+        }
+    }
+
+    #[test]
+    fn test_fast_clears_raise_pressure_up_to_the_cap() {
+        let mut director = DifficultyDirector::new();
+        let mut previous = director.tuning();
+        for _ in 0..20 {
+            // Par is 20 seconds; clearing in 8 is well under it
+            let tuning = director.record_level(&cleared(8.0, 0), false);
+            assert!(tuning.speed_multiplier >= previous.speed_multiplier);
+            assert!(tuning.alert_radius_multiplier >= previous.alert_radius_multiplier);
+            assert!(tuning.replan_multiplier >= previous.replan_multiplier);
+            previous = tuning;
+        }
+        assert_eq!(director.pressure(), MAX_PRESSURE);
+        assert_eq!(previous, EnemyTuning::for_pressure(MAX_PRESSURE));
+        assert!((previous.speed_multiplier - 1.15).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_tuning_is_clamped() {
+        let floor = EnemyTuning::for_pressure(-5.0);
+        assert!((floor.speed_multiplier - 0.85).abs() < 1e-6);
+        assert!((floor.alert_radius_multiplier - 0.75).abs() < 1e-6);
+        assert!((floor.replan_multiplier - 0.8).abs() < 1e-6);
+        assert_eq!(
+            EnemyTuning::for_pressure(5.0),
+            EnemyTuning::for_pressure(1.0)
+        );
+
+        let mut director = DifficultyDirector::new();
+        for _ in 0..10 {
+            director.record_level(&cleared(60.0, 10), true);
+        }
+        assert_eq!(director.pressure(), MIN_PRESSURE);
+        assert_eq!(director.tuning(), floor);
+
+        // Slow clears and close calls both give the player room
+        let mut director = DifficultyDirector::new();
+        director.record_level(&cleared(30.0, 0), false);
+        assert!(director.pressure() < 0.0);
+        let mut director = DifficultyDirector::new();
+        director.record_level(&cleared(20.0, 3), false);
+        assert!(director.pressure() < 0.0);
+    }
+
+    #[test]
+    fn test_disabled_director_stays_neutral() {
+        let mut director = DifficultyDirector::new();
+        director.record_level(&cleared(8.0, 0), false);
+        director.set_enabled(false);
+        assert_eq!(director.tuning(), EnemyTuning::NEUTRAL);
+        assert_eq!(
+            director.record_level(&cleared(8.0, 0), false),
+            EnemyTuning::NEUTRAL
+        );
+        assert_eq!(director.pressure(), 0.0);
+    }
+}
@@ -0,0 +1,325 @@
+//! The door that closes the entrance behind the player.
+//!
+//! Every level starts with an [`EntranceDoor`] standing open in the gap north of the cell the
+//! player spawns in. Once the player has walked [`TRIGGER_CELLS`] cells away it slides shut
+//! over [`CLOSE_SECS`], out of the outer wall it was hidden in, and lands with a thud and a
+//! [`CameraShake`]. Its collision only goes up once it is fully closed, through
+//! [`CollisionSystem::set_door`](crate::game::collision::CollisionSystem::set_door), so it can
+//! never close on the player: if they run back into the doorway while it is sliding, it backs
+//! off by [`REOPEN_FRACTION`] and tries again once they are clear. The enemy steers with the
+//! same collision geometry, so it treats the closed door as a wall too.
+//!
+//! # Usage
+//!
+//! ```rust,ignore
+//! use mirador::game::door::{CLOSE_SECS, EntranceDoor};
+//!
+//! let grid = vec![vec![true; 7]; 7];
+//! let mut door = EntranceDoor::new(&grid, false);
+//!
+//! // Walk far from the entrance, then wait for the door to close
+//! let away = [0.0, 50.0, 0.0];
+//! door.update(0.0, away, 5.0);
+//! let closed = (0..=(CLOSE_SECS * 10.0) as usize).any(|_| door.update(0.1, away, 5.0));
+//! assert!(closed && door.is_closed());
+//! ```
+
+use crate::game::map_view::ease_in_out;
+use crate::math::coordinates;
+use rand::Rng;
+
+/// Cells the player has to walk away from the entrance before the door starts closing.
+pub const TRIGGER_CELLS: f32 = 2.0;
+
+/// Seconds the door takes to slide from fully open to fully closed.
+pub const CLOSE_SECS: f32 = 1.5;
+
+/// Fraction of its travel the door backs off when the player steps into the doorway.
+pub const REOPEN_FRACTION: f32 = 0.2;
+
+/// Seconds the camera shakes after the door lands.
+pub const SHAKE_SECS: f32 = 0.4;
+
+/// Largest camera offset of the shake, in world units.
+pub const SHAKE_AMPLITUDE: f32 = 1.5;
+
+/// Where a closed door stands, as a wall segment across the Z axis.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DoorSpan {
+    /// World X of the door's western edge when closed
+    pub x: f32,
+    /// World Z of the door's plane
+    pub z: f32,
+    /// Width of the door along X
+    pub width: f32,
+    /// Height of the door
+    pub height: f32,
+}
+
+impl DoorSpan {
+    /// Finds the gap north of the cell the player spawns in.
+    ///
+    /// # Arguments
+    /// * `maze_grid` - The wall grid of the level
+    /// * `is_test_mode` - Whether the level uses the test mode floor size
+    pub fn at_entrance(maze_grid: &[Vec<bool>], is_test_mode: bool) -> Self {
+        let maze_dimensions = (maze_grid[0].len(), maze_grid.len());
+        let cell_size = coordinates::calculate_cell_size(maze_dimensions, is_test_mode);
+        let spawn = coordinates::get_bottom_left_cell(maze_dimensions);
+        Self {
+            x: -(maze_dimensions.0 as f32 * cell_size) / 2.0 + spawn.col as f32 * cell_size,
+            z: -(maze_dimensions.1 as f32 * cell_size) / 2.0 + spawn.row as f32 * cell_size,
+            width: cell_size,
+            height: cell_size,
+        }
+    }
+
+    /// Returns whether a cylinder standing at a position overlaps the doorway.
+    ///
+    /// # Arguments
+    /// * `position` - World position of the cylinder's base
+    /// * `radius` - Radius of the cylinder
+    pub fn blocks(&self, position: [f32; 3], radius: f32) -> bool {
+        (position[2] - self.z).abs() < radius
+            && position[0] > self.x - radius
+            && position[0] < self.x + self.width + radius
+    }
+}
+
+/// How far along the door is.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DoorPhase {
+    /// Standing open, waiting for the player to walk away
+    Open,
+    /// Sliding shut
+    Closing,
+    /// Backing off from a player in the doorway, down to this progress
+    Reopening(f32),
+    /// Shut, with its collision up
+    Closed,
+}
+
+/// The door that closes the entrance behind the player.
+#[derive(Debug, Clone)]
+pub struct EntranceDoor {
+    /// Where the door stands once closed
+    pub span: DoorSpan,
+    /// World `[x, z]` the player spawns at
+    spawn: [f32; 2],
+    /// Distance from the spawn point that starts the door closing
+    trigger_distance: f32,
+    /// How far the door has slid, from 0.0 (open) to 1.0 (closed)
+    progress: f32,
+    /// What the door is doing
+    phase: DoorPhase,
+}
+
+impl EntranceDoor {
+    /// Creates an open door at a level's entrance.
+    ///
+    /// # Arguments
+    /// * `maze_grid` - The wall grid of the level
+    /// * `is_test_mode` - Whether the level uses the test mode floor size
+    pub fn new(maze_grid: &[Vec<bool>], is_test_mode: bool) -> Self {
+        let maze_dimensions = (maze_grid[0].len(), maze_grid.len());
+        let spawn = coordinates::maze_to_world(
+            &coordinates::get_bottom_left_cell(maze_dimensions),
+            maze_dimensions,
+            0.0,
+            is_test_mode,
+        );
+        let span = DoorSpan::at_entrance(maze_grid, is_test_mode);
+        Self {
+            span,
+            spawn: [spawn[0], spawn[2]],
+            trigger_distance: span.width * TRIGGER_CELLS,
+            progress: 0.0,
+            phase: DoorPhase::Open,
+        }
+    }
+
+    /// Returns what the door is doing.
+    pub fn phase(&self) -> DoorPhase {
+        self.phase
+    }
+
+    /// Returns how far the door has slid, from 0.0 (open) to 1.0 (closed).
+    pub fn progress(&self) -> f32 {
+        self.progress
+    }
+
+    /// Returns whether the door is shut.
+    pub fn is_closed(&self) -> bool {
+        self.phase == DoorPhase::Closed
+    }
+
+    /// Returns how far west of its closed position the door is drawn.
+    ///
+    /// Eased so the door starts and lands gently; fully open, it is a whole door width
+    /// into the outer wall, out of sight.
+    pub fn slide_offset(&self) -> f32 {
+        -(1.0 - ease_in_out(self.progress)) * self.span.width
+    }
+
+    /// Advances the door by one frame.
+    ///
+    /// # Arguments
+    /// * `delta_time` - Seconds since the last frame
+    /// * `player_position` - The player's world position
+    /// * `player_radius` - Radius of the player's collision cylinder
+    ///
+    /// # Returns
+    /// `true` on the frame the door lands, when its thud and shake should play
+    pub fn update(
+        &mut self,
+        delta_time: f32,
+        player_position: [f32; 3],
+        player_radius: f32,
+    ) -> bool {
+        let step = delta_time / CLOSE_SECS;
+        let in_doorway = self.span.blocks(player_position, player_radius);
+        match self.phase {
+            DoorPhase::Open => {
+                let distance =
+                    (player_position[0] - self.spawn[0]).hypot(player_position[2] - self.spawn[1]);
+                if distance >= self.trigger_distance {
+                    self.phase = DoorPhase::Closing;
+                }
+            }
+            DoorPhase::Closing if in_doorway => {
+                self.phase = DoorPhase::Reopening((self.progress - REOPEN_FRACTION).max(0.0));
+            }
+            DoorPhase::Closing => {
+                self.progress = (self.progress + step).min(1.0);
+                if self.progress >= 1.0 {
+                    self.phase = DoorPhase::Closed;
+                    return true;
+                }
+            }
+            DoorPhase::Reopening(until) => {
+                self.progress = (self.progress - step).max(until);
+                if self.progress <= until && !in_doorway {
+                    self.phase = DoorPhase::Closing;
+                }
+            }
+            DoorPhase::Closed => {}
+        }
+        false
+    }
+}
+
+/// A brief shake of the camera that dies away, like the one when the entrance door lands.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CameraShake {
+    /// Seconds of shaking left
+    remaining: f32,
+    /// This frame's offset of the camera, in view space X and Y
+    offset: [f32; 2],
+}
+
+impl CameraShake {
+    /// Starts shaking at full strength for [`SHAKE_SECS`].
+    pub fn start(&mut self) {
+        self.remaining = SHAKE_SECS;
+    }
+
+    /// Picks this frame's offset, weaker the longer the shake has run.
+    ///
+    /// # Arguments
+    /// * `delta_time` - Seconds since the last frame
+    /// * `rng` - Random source for the offsets
+    pub fn update(&mut self, delta_time: f32, rng: &mut impl Rng) {
+        self.remaining = (self.remaining - delta_time).max(0.0);
+        let strength = SHAKE_AMPLITUDE * self.remaining / SHAKE_SECS;
+        self.offset = if strength > 0.0 {
+            [
+                rng.gen_range(-1.0..=1.0) * strength,
+                rng.gen_range(-1.0..=1.0) * strength,
+            ]
+        } else {
+            [0.0, 0.0]
+        };
+    }
+
+    /// Returns this frame's offset of the camera, in view space X and Y.
+    pub fn offset(&self) -> [f32; 2] {
+        self.offset
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+
+    const FRAME: f32 = 0.05;
+    const RADIUS: f32 = 5.0;
+
+    fn door() -> EntranceDoor {
+        EntranceDoor::new(&vec![vec![true; 25]; 25], false)
+    }
+
+    /// A position inside the maze, well away from the doorway.
+    fn away(door: &EntranceDoor) -> [f32; 3] {
+        [
+            door.spawn[0] + door.trigger_distance,
+            50.0,
+            door.spawn[1] - door.trigger_distance,
+        ]
+    }
+
+    #[test]
+    fn test_door_waits_for_the_player_then_closes_once() {
+        let mut door = door();
+        let spawn = [door.spawn[0], 50.0, door.spawn[1]];
+        for _ in 0..100 {
+            assert!(!door.update(FRAME, spawn, RADIUS));
+        }
+        assert_eq!(door.progress(), 0.0);
+        assert_eq!(door.slide_offset(), -door.span.width);
+
+        let away = away(&door);
+        let landed = (0..100)
+            .filter(|_| door.update(FRAME, away, RADIUS))
+            .count();
+        assert_eq!(landed, 1);
+        assert!(door.is_closed());
+        assert_eq!(door.slide_offset(), 0.0);
+    }
+
+    #[test]
+    fn test_door_backs_off_from_a_player_in_the_doorway() {
+        let mut door = door();
+        let away = away(&door);
+        for _ in 0..16 {
+            door.update(FRAME, away, RADIUS);
+        }
+        let before = door.progress();
+        assert!(before > REOPEN_FRACTION);
+
+        let doorway = [door.span.x + door.span.width / 2.0, 50.0, door.span.z];
+        for _ in 0..100 {
+            assert!(!door.update(FRAME, doorway, RADIUS));
+        }
+        assert_eq!(door.phase(), DoorPhase::Reopening(before - REOPEN_FRACTION));
+        assert!((door.progress() - (before - REOPEN_FRACTION)).abs() < 1e-5);
+
+        let landed = (0..100).any(|_| door.update(FRAME, away, RADIUS));
+        assert!(landed && door.is_closed());
+    }
+
+    #[test]
+    fn test_camera_shake_dies_away() {
+        let mut rng = StdRng::seed_from_u64(3);
+        let mut shake = CameraShake::default();
+        shake.start();
+        shake.update(0.0, &mut rng);
+        let first = shake.offset();
+        assert!(first.iter().all(|offset| offset.abs() <= SHAKE_AMPLITUDE));
+        assert!(first != [0.0, 0.0]);
+
+        shake.update(SHAKE_SECS, &mut rng);
+        assert_eq!(shake.offset(), [0.0, 0.0]);
+    }
+}
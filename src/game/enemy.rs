@@ -14,7 +14,7 @@
 //!
 //! # Usage
 //!
-//! ```rust
+//! ```rust,ignore
 //! use crate::game::enemy::{Enemy, place_enemy_standard};
 //!
 //! // Create an enemy at a specific position
@@ -27,9 +27,20 @@
 //! let enemy = place_enemy_standard(exit_pos, player_pos, level, collision_checker);
 //! ```
 
+use crate::game::dimensions::DEFAULT_CATCH_RADIUS;
+use crate::game::director::EnemyTuning;
+use crate::game::levels;
+use crate::game::maze::floors::{FloorPlan, Layer};
+use crate::game::trail::EnemyTrail;
 use crate::math::vec::Vec3;
 use std::f32::consts::PI;
 
+/// Multiplier on the enemy's pursuit distance while the player is crouching.
+pub const CROUCH_ALERT_MULTIPLIER: f32 = 0.5;
+
+/// Smallest fraction of the catch radius the catch distance shrinks to on high levels.
+const MIN_CATCH_FRACTION: f32 = 2.0 / 3.0;
+
 /// Represents an enemy entity in the game with AI-driven behavior.
 ///
 /// The enemy uses a pathfinding system to navigate toward the player while avoiding
@@ -46,7 +57,7 @@ use std::f32::consts::PI;
 ///
 /// # Example
 ///
-/// ```rust
+/// ```rust,ignore
 /// let mut enemy = Enemy::new([100.0, 30.0, 100.0], 150.0);
 ///
 /// // Update enemy behavior each frame
@@ -67,6 +78,15 @@ pub struct Enemy {
     pub base_speed: f32,
     /// Current movement speed after level-based scaling
     pub current_speed: f32,
+    /// Recent positions left behind as a fading trail
+    pub trail: EnemyTrail,
+    /// Adaptive difficulty multipliers applied on top of level scaling
+    pub tuning: EnemyTuning,
+    /// Floor the enemy is on; always the lower floor on single-floor levels
+    pub layer: Layer,
+    /// Whether the player is crouching, which shrinks the pursuit distance by
+    /// [`CROUCH_ALERT_MULTIPLIER`]; set by the game loop every frame
+    pub player_crouching: bool,
 }
 
 impl Enemy {
@@ -83,7 +103,7 @@ impl Enemy {
     ///
     /// # Example
     ///
-    /// ```rust
+    /// ```rust,ignore
     /// let enemy = Enemy::new([100.0, 30.0, 100.0], 150.0);
     /// ```
     pub fn new(position: [f32; 3], path_radius: f32) -> Self {
@@ -92,6 +112,10 @@ impl Enemy {
             pathfinder: EnemyPathfinder::new(position, path_radius),
             base_speed: 150.0, // Slightly reduced base speed for better scaling
             current_speed: 150.0,
+            trail: EnemyTrail::new(),
+            tuning: EnemyTuning::NEUTRAL,
+            layer: Layer::Lower,
+            player_crouching: false,
         }
     }
 
@@ -116,7 +140,7 @@ impl Enemy {
     ///
     /// # Example
     ///
-    /// ```rust
+    /// ```rust,ignore
     /// enemy.update(
     ///     [50.0, 30.0, 50.0],  // player position
     ///     0.016,               // delta time (60 FPS)
@@ -162,6 +186,160 @@ impl Enemy {
         }
     }
 
+    /// Updates the enemy on a level that may be split across two floors.
+    ///
+    /// While the player is on the other floor the enemy walks toward the nearest stairwell
+    /// on the way to them instead, and cannot catch them through the floor. Afterward the
+    /// enemy's height follows the ground beneath it, including up and down the ramps.
+    ///
+    /// # Arguments
+    ///
+    /// * `player_position` - Current 3D position of the player `[x, y, z]`
+    /// * `player_layer` - Floor the player is on
+    /// * `floors` - The level's floor plan, or `None` on single-floor levels
+    /// * `delta_time` - Time elapsed since last frame in seconds
+    /// * `level` - Current game level (affects enemy aggression)
+    /// * `line_intersects_geometry` - Checks a line for walls on the given floor, with heights
+    ///   measured from that floor's surface
+    pub fn update_on_floors<F>(
+        &mut self,
+        player_position: [f32; 3],
+        player_layer: Layer,
+        floors: Option<&FloorPlan>,
+        delta_time: f32,
+        level: u32,
+        line_intersects_geometry: F,
+    ) where
+        F: Fn([f32; 3], [f32; 3], Layer) -> bool,
+    {
+        let target = floors
+            .and_then(|plan| {
+                plan.waypoint(
+                    self.pathfinder.position,
+                    self.layer,
+                    player_position,
+                    player_layer,
+                )
+            })
+            .unwrap_or(player_position);
+        let can_capture = self.layer == player_layer;
+        self.step_on_floors(
+            target,
+            can_capture,
+            floors,
+            delta_time,
+            level,
+            line_intersects_geometry,
+        );
+    }
+
+    /// Updates one enemy of a pack, which may be steering for a waypoint on its route.
+    ///
+    /// Without a waypoint this is [`Enemy::update_on_floors`]. With one, the enemy walks toward
+    /// the waypoint instead of the player, and only catches the player when it actually gets
+    /// within [`capture_distance`] of them on the way.
+    ///
+    /// # Arguments
+    ///
+    /// * `waypoint` - Where the pack planned for the enemy to steer, or `None` to chase the
+    ///   player directly
+    /// * `player_position` - Current 3D position of the player `[x, y, z]`
+    /// * `player_layer` - Floor the player is on
+    /// * `floors` - The level's floor plan, or `None` on single-floor levels
+    /// * `delta_time` - Time elapsed since last frame in seconds
+    /// * `level` - Current game level (affects enemy aggression)
+    /// * `line_intersects_geometry` - Checks a line for walls on the given floor, with heights
+    ///   measured from that floor's surface
+    #[allow(clippy::too_many_arguments)]
+    pub fn update_in_pack<F>(
+        &mut self,
+        waypoint: Option<[f32; 3]>,
+        player_position: [f32; 3],
+        player_layer: Layer,
+        floors: Option<&FloorPlan>,
+        delta_time: f32,
+        level: u32,
+        line_intersects_geometry: F,
+    ) where
+        F: Fn([f32; 3], [f32; 3], Layer) -> bool,
+    {
+        let Some(waypoint) = waypoint else {
+            self.update_on_floors(
+                player_position,
+                player_layer,
+                floors,
+                delta_time,
+                level,
+                line_intersects_geometry,
+            );
+            return;
+        };
+
+        // Arriving at the waypoint is not reaching the player
+        self.step_on_floors(
+            waypoint,
+            false,
+            floors,
+            delta_time,
+            level,
+            line_intersects_geometry,
+        );
+        let distance = Vec3(self.pathfinder.position)
+            .to_2d()
+            .distance_to(&Vec3(player_position).to_2d());
+        if !self.pathfinder.locked
+            && self.layer == player_layer
+            && distance < capture_distance(self.pathfinder.catch_radius, level)
+        {
+            self.pathfinder.reached_player = true;
+            self.pathfinder.locked = true;
+        }
+    }
+
+    /// Moves the enemy toward a target on its own floor, then lets its height follow the
+    /// ground beneath it.
+    ///
+    /// # Arguments
+    ///
+    /// * `target` - Where the enemy heads this frame
+    /// * `can_capture` - Whether arriving at the target catches the player
+    /// * `floors` - The level's floor plan, or `None` on single-floor levels
+    /// * `delta_time` - Time elapsed since last frame in seconds
+    /// * `level` - Current game level (affects enemy aggression)
+    /// * `line_intersects_geometry` - Checks a line for walls on the given floor
+    fn step_on_floors<F>(
+        &mut self,
+        target: [f32; 3],
+        can_capture: bool,
+        floors: Option<&FloorPlan>,
+        delta_time: f32,
+        level: u32,
+        line_intersects_geometry: F,
+    ) where
+        F: Fn([f32; 3], [f32; 3], Layer) -> bool,
+    {
+        let layer = self.layer;
+        let ground = floors.map_or(0.0, |plan| plan.ground(self.pathfinder.position, layer).1);
+
+        // A ramp is open toward one floor at each end, so from inside a stairwell the enemy
+        // can see out of either
+        let on_ramp = floors.is_some_and(|plan| plan.on_ramp(self.pathfinder.position));
+
+        self.pathfinder.can_capture = can_capture;
+        self.update(target, delta_time, level, |from, to| {
+            let from = [from[0], from[1] - ground, from[2]];
+            let to = [to[0], to[1] - ground, to[2]];
+            line_intersects_geometry(from, to, layer)
+                && (!on_ramp || line_intersects_geometry(from, to, layer.other()))
+        });
+
+        if let Some(plan) = floors.filter(|_| !self.pathfinder.locked) {
+            let (layer, new_ground) = plan.ground(self.pathfinder.position, self.layer);
+            self.layer = layer;
+            self.pathfinder.position[1] += new_ground - ground;
+        }
+    }
+
     /// Scales enemy aggression parameters based on the current game level.
     ///
     /// This method adjusts various enemy attributes to make them more challenging
@@ -169,30 +347,42 @@ impl Enemy {
     ///
     /// # Scaling Factors
     ///
-    /// - **Speed**: Increases by 20% per level, capped at 500% of base speed
+    /// - **Speed**: Increases by 20% per level, capped at 500% of base speed, see
+    ///   [`levels::enemy_speed_multiplier`]
     /// - **Path radius**: Decreases by 15% per level, minimum 30% of original
     /// - **Arrival threshold**: Decreases by 10% per level, minimum 20% of original
     /// - **Rotation step**: Increases by 25% per level, maximum 300% of original
     /// - **Pursuit distance**: Increases by 30% per level, maximum 400% of original
     ///
+    /// Speed, pursuit distance and rotation step are then multiplied by the
+    /// enemy's adaptive [`EnemyTuning`], and the pursuit distance is shrunk while
+    /// the player is crouching.
+    ///
     /// # Arguments
     ///
     /// * `level` - Current game level (1-based)
     ///
     /// # Example
     ///
-    /// ```rust
+    /// ```rust,ignore
     /// enemy.scale_aggression_by_level(5); // Scale for level 5
     /// ```
     fn scale_aggression_by_level(&mut self, level: u32) {
-        let level_f = level as f32;
-
-        // Speed scaling: increases by 20% per level, capped at 500% of base speed
-        let speed_multiplier = (1.0 + (level_f * 0.2)).min(5.0);
-        self.current_speed = self.base_speed * speed_multiplier;
+        // Speed scaling follows the level plans' curve
+        let speed_multiplier = levels::enemy_speed_multiplier(level as i32);
+        self.current_speed = self.base_speed * speed_multiplier * self.tuning.speed_multiplier;
 
         // Update pathfinder aggression parameters
         self.pathfinder.update_aggression_for_level(level);
+
+        // Adaptive difficulty nudges the level-scaled values up or down
+        self.pathfinder.pursuit_distance *= self.tuning.alert_radius_multiplier;
+        self.pathfinder.rotation_step *= self.tuning.replan_multiplier;
+
+        // A crouching player has to be much closer before the enemy notices them
+        if self.player_crouching {
+            self.pathfinder.pursuit_distance *= CROUCH_ALERT_MULTIPLIER;
+        }
     }
 
     /// Returns current aggression statistics for debugging purposes.
@@ -207,7 +397,7 @@ impl Enemy {
     ///
     /// # Example
     ///
-    /// ```rust
+    /// ```rust,ignore
     /// let (speed, radius, threshold, rotation) = enemy.get_aggression_stats();
     /// println!("Enemy speed: {}, radius: {}, threshold: {}, rotation: {}",
     ///          speed, radius, threshold, rotation);
@@ -220,6 +410,21 @@ impl Enemy {
             self.pathfinder.rotation_step,
         )
     }
+
+    /// Checks whether the player is within the enemy's pursuit distance.
+    ///
+    /// # Arguments
+    ///
+    /// * `player_position` - Current player position in world coordinates
+    ///
+    /// # Returns
+    ///
+    /// `true` while the enemy is closing in on the player, ignoring height.
+    pub fn is_pursuing(&self, player_position: [f32; 3]) -> bool {
+        let enemy_2d = Vec3(self.pathfinder.position).to_2d();
+        let player_2d = Vec3(player_position).to_2d();
+        enemy_2d.distance_to(&player_2d) < self.pathfinder.pursuit_distance
+    }
 }
 
 /// Advanced pathfinding system for enemy movement and navigation.
@@ -241,7 +446,7 @@ impl Enemy {
 ///
 /// # Example
 ///
-/// ```rust
+/// ```rust,ignore
 /// let mut pathfinder = EnemyPathfinder::new([100.0, 30.0, 100.0], 150.0);
 ///
 /// // Update pathfinding
@@ -273,8 +478,14 @@ pub struct EnemyPathfinder {
     pub last_position: [f32; 3],
     /// Whether the enemy has reached the player
     pub reached_player: bool,
+    /// Whether getting close enough to the player counts as catching them; cleared while the
+    /// player is on the other floor of a two-floor level
+    pub can_capture: bool,
     /// Whether the enemy is locked (cannot move)
     pub locked: bool,
+    /// How close the enemy has to get to catch the player on level 1, copied from the
+    /// player's dimensions every frame
+    pub catch_radius: f32,
     /// Current aggression level (matches game level)
     pub aggression_level: u32,
     /// Distance at which enemy starts pursuing more aggressively (scales with level)
@@ -297,7 +508,7 @@ impl EnemyPathfinder {
     ///
     /// # Example
     ///
-    /// ```rust
+    /// ```rust,ignore
     /// let pathfinder = EnemyPathfinder::new([100.0, 30.0, 100.0], 150.0);
     /// ```
     pub fn new(position: [f32; 3], path_radius: f32) -> Self {
@@ -313,7 +524,9 @@ impl EnemyPathfinder {
             stuck_counter: 0,
             last_position: position,
             reached_player: false,
+            can_capture: true,
             locked: true,
+            catch_radius: DEFAULT_CATCH_RADIUS,
             aggression_level: 1,
             pursuit_distance: path_radius * 2.0,
             base_pursuit_distance: path_radius * 2.0,
@@ -338,7 +551,7 @@ impl EnemyPathfinder {
     ///
     /// # Example
     ///
-    /// ```rust
+    /// ```rust,ignore
     /// pathfinder.update_aggression_for_level(3); // Scale for level 3
     /// ```
     pub fn update_aggression_for_level(&mut self, level: u32) {
@@ -385,7 +598,7 @@ impl EnemyPathfinder {
     ///
     /// # Example
     ///
-    /// ```rust
+    /// ```rust,ignore
     /// if let Some(target) = pathfinder.update(player_pos, level, collision_checker) {
     ///     // Enemy has a valid target to move toward
     ///     println!("Moving toward: {:?}", target);
@@ -432,7 +645,7 @@ impl EnemyPathfinder {
     ///
     /// # Example
     ///
-    /// ```rust
+    /// ```rust,ignore
     /// pathfinder.update_stuck_detection(5); // Update for level 5
     /// ```
     fn update_stuck_detection(&mut self, level: u32) {
@@ -479,7 +692,7 @@ impl EnemyPathfinder {
     ///
     /// # Example
     ///
-    /// ```rust
+    /// ```rust,ignore
     /// if pathfinder.needs_new_target(3) {
     ///     // Calculate new target for level 3
     /// }
@@ -528,7 +741,7 @@ impl EnemyPathfinder {
     ///
     /// # Example
     ///
-    /// ```rust
+    /// ```rust,ignore
     /// pathfinder.calculate_new_target(
     ///     [50.0, 30.0, 50.0],  // player position
     ///     3,                   // level 3
@@ -564,11 +777,11 @@ impl EnemyPathfinder {
             if distance_to_player < close_pursuit_radius {
                 self.current_target = Some(player_position);
 
-                // Tighter capture radius at higher levels
-                let capture_distance = (15.0 / (1.0 + level as f32 * 0.2)).max(10.0);
-                if distance_to_player < capture_distance {
+                if self.can_capture
+                    && distance_to_player < capture_distance(self.catch_radius, level)
+                {
+                    // Stay put so the capture sequence can lunge from here
                     self.reached_player = true;
-                    self.position = [0.0, 30.0, 0.0];
                     self.locked = true;
                 }
                 return;
@@ -680,7 +893,7 @@ impl EnemyPathfinder {
     ///
     /// # Example
     ///
-    /// ```rust
+    /// ```rust,ignore
     /// let is_safe = pathfinder.is_safe_path(
     ///     [100.0, 30.0, 100.0],  // start
     ///     [150.0, 30.0, 150.0],  // end
@@ -737,7 +950,7 @@ impl EnemyPathfinder {
     ///
     /// # Example
     ///
-    /// ```rust
+    /// ```rust,ignore
     /// pathfinder.set_position([120.0, 30.0, 120.0]);
     /// ```
     pub fn set_position(&mut self, new_position: [f32; 3]) {
@@ -753,7 +966,7 @@ impl EnemyPathfinder {
     ///
     /// # Example
     ///
-    /// ```rust
+    /// ```rust,ignore
     /// if let Some(direction) = pathfinder.get_movement_direction() {
     ///     println!("Moving in direction: {:?}", direction);
     /// }
@@ -775,7 +988,7 @@ impl EnemyPathfinder {
     ///
     /// # Example
     ///
-    /// ```rust
+    /// ```rust,ignore
     /// if let Some(distance) = pathfinder.distance_to_target() {
     ///     println!("Distance to target: {:.2}", distance);
     /// }
@@ -797,7 +1010,7 @@ impl EnemyPathfinder {
     ///
     /// # Example
     ///
-    /// ```rust
+    /// ```rust,ignore
     /// if pathfinder.has_reached_target() {
     ///     println!("Enemy has reached its target");
     ///     // Calculate new target
@@ -815,6 +1028,22 @@ impl EnemyPathfinder {
     }
 }
 
+/// Returns how close the enemy has to get to catch the player.
+///
+/// The distance tightens on higher levels, down to two thirds of the level 1 radius.
+///
+/// # Arguments
+///
+/// * `catch_radius` - The catch distance on level 1, from the player's dimensions
+/// * `level` - Current game level
+///
+/// # Returns
+///
+/// The catch distance in world units
+pub fn capture_distance(catch_radius: f32, level: u32) -> f32 {
+    (catch_radius / (1.0 + level as f32 * 0.2)).max(catch_radius * MIN_CATCH_FRACTION)
+}
+
 /// Places an enemy strategically with level-based positioning.
 ///
 /// This function calculates an optimal position for an enemy based on the
@@ -844,7 +1073,7 @@ impl EnemyPathfinder {
 ///
 /// # Example
 ///
-/// ```rust
+/// ```rust,ignore
 /// let enemy = place_enemy(
 ///     [200.0, 30.0, 200.0],  // exit position
 ///     [50.0, 30.0, 50.0],    // player position
@@ -951,7 +1180,7 @@ where
 ///
 /// # Example
 ///
-/// ```rust
+/// ```rust,ignore
 /// let valid_position = validate_enemy_position(
 ///     [100.0, 30.0, 100.0],  // proposed position
 ///     [50.0, 30.0, 50.0],    // player position
@@ -1021,7 +1250,7 @@ where
 ///
 /// # Example
 ///
-/// ```rust
+/// ```rust,ignore
 /// let enemy = place_enemy_standard(
 ///     [200.0, 30.0, 200.0],  // exit position
 ///     [50.0, 30.0, 50.0],    // player position
@@ -0,0 +1,510 @@
+//! Ambient events that add variety to a level.
+//!
+//! When a maze loads, [`EventSchedule::roll`] picks up to [`MAX_EVENTS_PER_LEVEL`] events from
+//! the [`REGISTRY`], weighted and limited by level, and schedules when each starts and ends.
+//! Events don't add mechanics of their own. Their start and end hooks only adjust the
+//! [`EventEffects`] that other systems already read each frame: the renderer scales its fog
+//! tuning, the player scales stamina regeneration, and the audio manager plays the requested
+//! cues. Each event also announces itself with a short HUD line.
+//!
+//! The schedule runs on a [`GameClock`](crate::game::clock::GameClock) that only advances during
+//! play, so pausing holds events where they are. Selection is seeded from the maze, so a retried
+//! level gets the same events at the same times. Events that share an [`EventChannel`] never
+//! overlap; a later one waits for the earlier one to end.
+//!
+//! # Usage
+//!
+//! ```rust,ignore
+//! let mut schedule = EventSchedule::roll(level, events::level_seed(&maze_grid, level));
+//! let mut clock = GameClock::new();
+//!
+//! // Once per frame during play
+//! clock.advance_secs(delta_time);
+//! for transition in schedule.update(clock.now()) {
+//!     // Play the announcement cue for started events
+//! }
+//! let tuning = schedule.effects().apply_to_tuning(&base_tuning);
+//! ```
+
+use crate::game::daily;
+use crate::renderer::tuning::RenderTuning;
+use rand::prelude::*;
+use std::f32::consts::TAU;
+use std::time::Duration;
+
+/// Most events one level can roll.
+pub const MAX_EVENTS_PER_LEVEL: usize = 2;
+
+/// How long an event's HUD line stays up after it starts.
+pub const ANNOUNCEMENT_DURATION: Duration = Duration::from_secs(3);
+
+/// Earliest an event can start, in seconds of play into the level.
+const EARLIEST_START_SECS: f32 = 8.0;
+
+/// Latest an event can be rolled to start, in seconds of play into the level.
+const LATEST_START_SECS: f32 = 45.0;
+
+/// Pause between two events on the same channel, so their announcements don't run together.
+const CHANNEL_GAP: Duration = Duration::from_secs(2);
+
+/// How much of their usual reach the fog distances and the light keep during a blackout.
+const BLACKOUT_SCALE: f32 = 0.6;
+
+/// Stamina regeneration multiplier during an adrenaline rush.
+const ADRENALINE_REGEN_SCALE: f32 = 2.0;
+
+/// Salt mixed into the level seed so events don't correlate with the maze layout.
+const EVENT_SEED_SALT: u64 = 0x6576_656e_7473;
+
+/// Identifies an ambient event.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EventId {
+    /// The fog closes in and the lights dim
+    Blackout,
+    /// The enemy is heard from somewhere it isn't
+    Echoes,
+    /// Stamina comes back faster
+    Adrenaline,
+}
+
+/// What an event affects; events on the same channel never run at the same time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EventChannel {
+    /// What the player sees and hears around them
+    Atmosphere,
+    /// The player's own abilities
+    Player,
+}
+
+/// Called when an event starts or ends to adjust the shared effects.
+pub type EventHook = fn(&mut EventEffects, &ScheduledEvent);
+
+/// An entry in the [`REGISTRY`].
+#[derive(Debug, Clone, Copy)]
+pub struct EventDefinition {
+    /// Which event this is
+    pub id: EventId,
+    /// First level the event can roll on
+    pub min_level: i32,
+    /// Relative chance of being picked among the events allowed on a level
+    pub weight: u32,
+    /// How long the event lasts once it starts
+    pub duration: Duration,
+    /// What the event affects, to keep conflicting events apart
+    pub channel: EventChannel,
+    /// HUD line shown when the event starts
+    pub announcement: &'static str,
+    /// Applies the event to the shared effects
+    pub on_start: EventHook,
+    /// Reverts what `on_start` did
+    pub on_end: EventHook,
+}
+
+/// Every event a level can roll.
+pub const REGISTRY: &[EventDefinition] = &[
+    EventDefinition {
+        id: EventId::Blackout,
+        min_level: 3,
+        weight: 3,
+        duration: Duration::from_secs(20),
+        channel: EventChannel::Atmosphere,
+        announcement: "The lights flicker...",
+        on_start: |effects, _| {
+            effects.fog_distance_scale = BLACKOUT_SCALE;
+            effects.light_scale = BLACKOUT_SCALE;
+        },
+        on_end: |effects, _| {
+            effects.fog_distance_scale = 1.0;
+            effects.light_scale = 1.0;
+        },
+    },
+    EventDefinition {
+        id: EventId::Echoes,
+        min_level: 2,
+        weight: 4,
+        duration: Duration::from_secs(6),
+        channel: EventChannel::Atmosphere,
+        announcement: "You hear distant footsteps",
+        on_start: |effects, event| effects.echo_bearing = Some(event.bearing),
+        on_end: |_, _| {},
+    },
+    EventDefinition {
+        id: EventId::Adrenaline,
+        min_level: 1,
+        weight: 3,
+        duration: Duration::from_secs(15),
+        channel: EventChannel::Player,
+        announcement: "Adrenaline surges through you",
+        on_start: |effects, _| effects.stamina_regen_scale = ADRENALINE_REGEN_SCALE,
+        on_end: |effects, _| effects.stamina_regen_scale = 1.0,
+    },
+];
+
+impl EventId {
+    /// Returns the event's entry in the [`REGISTRY`].
+    pub fn definition(self) -> &'static EventDefinition {
+        REGISTRY
+            .iter()
+            .find(|definition| definition.id == self)
+            .expect("Every event is registered")
+    }
+}
+
+/// Adjustments the running events make to existing tunables.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EventEffects {
+    /// Multiplier on the fog start and end distances
+    pub fog_distance_scale: f32,
+    /// Multiplier on how much light reaches distant geometry; less light means more fog
+    pub light_scale: f32,
+    /// Multiplier on the player's stamina regeneration rate
+    pub stamina_regen_scale: f32,
+    /// Bearing in radians around the player to play a phantom enemy sound from, once
+    pub echo_bearing: Option<f32>,
+}
+
+impl Default for EventEffects {
+    fn default() -> Self {
+        Self {
+            fog_distance_scale: 1.0,
+            light_scale: 1.0,
+            stamina_regen_scale: 1.0,
+            echo_bearing: None,
+        }
+    }
+}
+
+impl EventEffects {
+    /// Applies the effects to the renderer's fog settings.
+    ///
+    /// # Arguments
+    /// * `tuning` - The renderer's current settings, left unchanged
+    ///
+    /// # Returns
+    /// A copy with the fog pulled in, and strengthened toward full as the light drops
+    pub fn apply_to_tuning(&self, tuning: &RenderTuning) -> RenderTuning {
+        RenderTuning {
+            fog_start: tuning.fog_start * self.fog_distance_scale,
+            fog_end: tuning.fog_end * self.fog_distance_scale,
+            fog_strength: 1.0 - (1.0 - tuning.fog_strength) * self.light_scale,
+            ..*tuning
+        }
+    }
+}
+
+/// One event placed on a level's timeline.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ScheduledEvent {
+    /// Which event this is
+    pub id: EventId,
+    /// Clock reading the event starts at
+    pub start: Duration,
+    /// Clock reading the event ends at
+    pub end: Duration,
+    /// Random bearing in radians, for events that come from a direction
+    pub bearing: f32,
+}
+
+/// An event starting or ending, in the order the hooks ran.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EventTransition {
+    /// The event's start hook ran
+    Started(EventId),
+    /// The event's end hook ran
+    Ended(EventId),
+}
+
+/// Where a scheduled event is in its lifetime.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Phase {
+    Pending,
+    Active,
+    Finished,
+}
+
+/// A level's events and the effects of the ones running.
+#[derive(Debug, Clone, Default)]
+pub struct EventSchedule {
+    /// The level's events, ordered by start time
+    events: Vec<ScheduledEvent>,
+    /// Lifetime of each event in `events`
+    phases: Vec<Phase>,
+    /// Combined effects of the running events
+    effects: EventEffects,
+}
+
+/// Derives a level's event seed from its maze.
+///
+/// # Arguments
+/// * `maze_grid` - 2D grid of the maze where `true` marks a wall
+/// * `level` - The level number
+///
+/// # Returns
+/// The same seed whenever the same maze is played on the same level
+pub fn level_seed(maze_grid: &[Vec<bool>], level: i32) -> u64 {
+    let mut bytes = level.to_le_bytes().to_vec();
+    bytes.extend(maze_grid.iter().flatten().map(|&wall| wall as u8));
+    daily::fnv1a_64(&bytes) ^ EVENT_SEED_SALT
+}
+
+impl EventSchedule {
+    /// Rolls a level's events.
+    ///
+    /// Picks between none and [`MAX_EVENTS_PER_LEVEL`] distinct events allowed on `level`,
+    /// weighted by the registry, and places each one's start on the timeline. An event that
+    /// would overlap an earlier one on its channel is pushed back until that one is over.
+    ///
+    /// # Arguments
+    /// * `level` - The level being played
+    /// * `seed` - Seed for the roll, see [`level_seed`]
+    ///
+    /// # Returns
+    /// The schedule, with nothing started yet
+    pub fn roll(level: i32, seed: u64) -> Self {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut candidates: Vec<&EventDefinition> = REGISTRY
+            .iter()
+            .filter(|definition| level >= definition.min_level)
+            .collect();
+
+        let count = rng
+            .gen_range(0..=MAX_EVENTS_PER_LEVEL)
+            .min(candidates.len());
+        let mut events: Vec<ScheduledEvent> = Vec::with_capacity(count);
+        for _ in 0..count {
+            let Ok(&definition) =
+                candidates.choose_weighted(&mut rng, |definition| definition.weight)
+            else {
+                break;
+            };
+            candidates.retain(|other| other.id != definition.id);
+            let start =
+                Duration::from_secs_f32(rng.gen_range(EARLIEST_START_SECS..=LATEST_START_SECS));
+            events.push(ScheduledEvent {
+                id: definition.id,
+                start,
+                end: start + definition.duration,
+                bearing: rng.gen_range(0.0..TAU),
+            });
+        }
+
+        events.sort_by_key(|event| event.start);
+        for index in 1..events.len() {
+            let channel = events[index].id.definition().channel;
+            let clear_at = events[..index]
+                .iter()
+                .filter(|earlier| earlier.id.definition().channel == channel)
+                .map(|earlier| earlier.end + CHANNEL_GAP)
+                .max();
+            if let Some(clear_at) = clear_at.filter(|&clear_at| clear_at > events[index].start) {
+                let duration = events[index].end - events[index].start;
+                events[index].start = clear_at;
+                events[index].end = clear_at + duration;
+            }
+        }
+        events.sort_by_key(|event| event.start);
+
+        Self {
+            phases: vec![Phase::Pending; events.len()],
+            events,
+            effects: EventEffects::default(),
+        }
+    }
+
+    /// Returns the level's events, ordered by start time.
+    pub fn events(&self) -> &[ScheduledEvent] {
+        &self.events
+    }
+
+    /// Returns the combined effects of the running events.
+    pub fn effects(&self) -> &EventEffects {
+        &self.effects
+    }
+
+    /// Takes the pending phantom enemy sound, so it is only played once.
+    ///
+    /// # Returns
+    /// The bearing to play it from, if an event asked for one since the last call
+    pub fn take_echo(&mut self) -> Option<f32> {
+        self.effects.echo_bearing.take()
+    }
+
+    /// Starts and ends events up to the current clock reading.
+    ///
+    /// Hooks run in timeline order even when a long frame crosses several transitions. An end
+    /// and a start at the same moment end first, so effects never stack.
+    ///
+    /// # Arguments
+    /// * `now` - Current reading of the level's game clock
+    ///
+    /// # Returns
+    /// The transitions that happened, in the order their hooks ran
+    pub fn update(&mut self, now: Duration) -> Vec<EventTransition> {
+        // (time, is_start, index); ends sort before starts at the same time
+        let mut due: Vec<(Duration, bool, usize)> = Vec::new();
+        for (index, (event, phase)) in self.events.iter().zip(&self.phases).enumerate() {
+            if *phase == Phase::Pending && event.start <= now {
+                due.push((event.start, true, index));
+            }
+            if *phase != Phase::Finished && event.end <= now {
+                due.push((event.end, false, index));
+            }
+        }
+        due.sort();
+
+        due.into_iter()
+            .map(|(_, is_start, index)| {
+                let event = self.events[index];
+                let definition = event.id.definition();
+                if is_start {
+                    self.phases[index] = Phase::Active;
+                    (definition.on_start)(&mut self.effects, &event);
+                    EventTransition::Started(event.id)
+                } else {
+                    self.phases[index] = Phase::Finished;
+                    (definition.on_end)(&mut self.effects, &event);
+                    EventTransition::Ended(event.id)
+                }
+            })
+            .collect()
+    }
+
+    /// Returns the HUD line of the most recently started event, while it is still fresh.
+    ///
+    /// # Arguments
+    /// * `now` - Current reading of the level's game clock
+    ///
+    /// # Returns
+    /// The announcement for [`ANNOUNCEMENT_DURATION`] after an event starts, otherwise `None`
+    pub fn announcement(&self, now: Duration) -> Option<&'static str> {
+        self.events
+            .iter()
+            .zip(&self.phases)
+            .filter(|&(event, phase)| {
+                *phase != Phase::Pending && now < event.start + ANNOUNCEMENT_DURATION
+            })
+            .max_by_key(|(event, _)| event.start)
+            .map(|(event, _)| event.id.definition().announcement)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn secs(seconds: u64) -> Duration {
+        Duration::from_secs(seconds)
+    }
+
+    #[test]
+    fn test_rolls_are_seeded_and_respect_the_registry() {
+        for seed in 0..200 {
+            let schedule = EventSchedule::roll(5, seed);
+            let again = EventSchedule::roll(5, seed);
+            assert_eq!(schedule.events(), again.events());
+            assert!(schedule.events().len() <= MAX_EVENTS_PER_LEVEL);
+
+            for (index, event) in schedule.events().iter().enumerate() {
+                assert_eq!(event.end - event.start, event.id.definition().duration);
+                assert!(event.start >= Duration::from_secs_f32(EARLIEST_START_SECS));
+                assert!(!schedule.events()[..index].iter().any(|e| e.id == event.id));
+            }
+
+            // Level 1 only allows events with no level requirement
+            for event in EventSchedule::roll(1, seed).events() {
+                assert_eq!(event.id, EventId::Adrenaline);
+            }
+        }
+        assert!((0..200).any(|seed| EventSchedule::roll(5, seed).events().is_empty()));
+        assert!((0..200).any(|seed| EventSchedule::roll(5, seed).events().len() == 2));
+    }
+
+    #[test]
+    fn test_events_on_the_same_channel_never_overlap() {
+        let mut seen_atmosphere_pair = false;
+        for seed in 0..500 {
+            let schedule = EventSchedule::roll(5, seed);
+            let events = schedule.events();
+            for (index, event) in events.iter().enumerate() {
+                for other in &events[index + 1..] {
+                    if event.id.definition().channel == other.id.definition().channel {
+                        seen_atmosphere_pair = true;
+                        assert!(other.start >= event.end + CHANNEL_GAP);
+                    }
+                }
+            }
+        }
+        assert!(seen_atmosphere_pair);
+    }
+
+    #[test]
+    fn test_hooks_run_in_order_and_restore_the_effects() {
+        let blackout = EventId::Blackout.definition().duration;
+        let mut schedule = EventSchedule {
+            events: vec![
+                ScheduledEvent {
+                    id: EventId::Blackout,
+                    start: secs(10),
+                    end: secs(10) + blackout,
+                    bearing: 0.0,
+                },
+                ScheduledEvent {
+                    id: EventId::Echoes,
+                    start: secs(10) + blackout,
+                    end: secs(36) + blackout,
+                    bearing: 1.5,
+                },
+            ],
+            phases: vec![Phase::Pending; 2],
+            effects: EventEffects::default(),
+        };
+
+        assert!(schedule.update(secs(9)).is_empty());
+        assert_eq!(
+            schedule.update(secs(10)),
+            vec![EventTransition::Started(EventId::Blackout)]
+        );
+        assert_eq!(schedule.effects().fog_distance_scale, BLACKOUT_SCALE);
+        assert_eq!(
+            schedule.announcement(secs(11)),
+            Some("The lights flicker...")
+        );
+        assert_eq!(schedule.announcement(secs(14)), None);
+
+        // One long frame crosses the blackout's end and the echo's start: end first
+        assert_eq!(
+            schedule.update(secs(40)),
+            vec![
+                EventTransition::Ended(EventId::Blackout),
+                EventTransition::Started(EventId::Echoes),
+            ]
+        );
+        assert_eq!(schedule.effects().fog_distance_scale, 1.0);
+        assert_eq!(schedule.take_echo(), Some(1.5));
+        assert_eq!(schedule.take_echo(), None);
+
+        // Time that never passes (a paused clock) changes nothing
+        assert!(schedule.update(secs(40)).is_empty());
+        assert_eq!(
+            schedule.update(secs(100)),
+            vec![EventTransition::Ended(EventId::Echoes)]
+        );
+        assert_eq!(*schedule.effects(), EventEffects::default());
+    }
+
+    #[test]
+    fn test_blackout_pulls_in_the_fog() {
+        let tuning = RenderTuning::default();
+        let effects = EventEffects {
+            fog_distance_scale: BLACKOUT_SCALE,
+            light_scale: BLACKOUT_SCALE,
+            ..EventEffects::default()
+        };
+
+        let dimmed = effects.apply_to_tuning(&tuning);
+        assert!((dimmed.fog_end - tuning.fog_end * 0.6).abs() < 1e-3);
+        assert!((dimmed.fog_strength - (1.0 - (1.0 - tuning.fog_strength) * 0.6)).abs() < 1e-6);
+        assert_eq!(dimmed.background_color, tuning.background_color);
+        assert_eq!(EventEffects::default().apply_to_tuning(&tuning), tuning);
+    }
+}
@@ -0,0 +1,255 @@
+//! Per-cell frame cost table for finding slow spots in a maze.
+//!
+//! In debug builds and test mode, every frame's CPU time is charged to the maze cell the player
+//! is standing in. Each cell keeps a rolling average, so a corner where the frame rate dips shows
+//! up as a hot cell in the floor overlay drawn by
+//! [`HeatmapRenderer`](crate::renderer::game_renderer::heatmap::HeatmapRenderer), and the whole
+//! table can be written to a CSV file to compare runs. None of the render passes write GPU
+//! timestamps, so only CPU time is recorded.
+//!
+//! The table is one flat `Vec` covering the wall grid of every floor, sized once per level, so
+//! recording a frame never allocates.
+//!
+//! # Usage
+//!
+//! ```rust,ignore
+//! let mut heatmap = FrameHeatmap::for_grid(&maze_grid, 1);
+//! heatmap.record(player.current_cell, player.layer, 16.7);
+//! for (cell, layer, cost) in heatmap.recorded() {
+//!     println!("{:?} {:?}: {:.2} ms", layer, cell, cost.average_ms);
+//! }
+//! ```
+
+use crate::game::maze::floors::Layer;
+use crate::game::maze::generator::Cell;
+use crate::paths;
+use chrono::Local;
+use std::fs;
+use std::io::{self, Write};
+use std::path::PathBuf;
+
+/// Number of recent frames a cell's rolling average mostly reflects.
+pub const ROLLING_WINDOW: u32 = 30;
+
+/// Frame cost recorded for one cell.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct CellCost {
+    /// Rolling average CPU frame time in milliseconds
+    pub average_ms: f32,
+    /// Frames recorded in this cell
+    pub samples: u32,
+}
+
+impl CellCost {
+    /// Adds one frame to the rolling average.
+    ///
+    /// The first [`ROLLING_WINDOW`] frames are averaged evenly. After that each new frame
+    /// carries a fixed weight of `1 / ROLLING_WINDOW`, so old frames fade out without keeping
+    /// a history per cell.
+    ///
+    /// # Arguments
+    /// * `frame_ms` - CPU time of the frame in milliseconds
+    pub fn add_sample(&mut self, frame_ms: f32) {
+        self.samples = self.samples.saturating_add(1);
+        let weight = 1.0 / self.samples.min(ROLLING_WINDOW) as f32;
+        self.average_ms += (frame_ms - self.average_ms) * weight;
+    }
+}
+
+/// Rolling frame cost of every cell in the current level.
+#[derive(Debug, Clone, Default)]
+pub struct FrameHeatmap {
+    width: usize,
+    height: usize,
+    /// Costs for every floor, one grid after another in [`Layer::index`] order
+    cells: Vec<CellCost>,
+}
+
+impl FrameHeatmap {
+    /// Creates an empty table sized for a level's wall grid.
+    ///
+    /// # Arguments
+    /// * `maze_grid` - 2D grid of the maze where `true` marks a wall
+    /// * `floors` - Number of floors in the level, 1 or 2
+    pub fn for_grid(maze_grid: &[Vec<bool>], floors: usize) -> Self {
+        let height = maze_grid.len();
+        let width = maze_grid.first().map_or(0, |row| row.len());
+        Self {
+            width,
+            height,
+            cells: vec![CellCost::default(); width * height * floors],
+        }
+    }
+
+    /// Finds a cell's slot in the flat table.
+    ///
+    /// # Arguments
+    /// * `cell` - The wall-grid cell
+    /// * `layer` - The floor the cell is on
+    ///
+    /// # Returns
+    /// The index into the table, or `None` if the cell or floor is outside the level
+    pub fn index(&self, cell: Cell, layer: Layer) -> Option<usize> {
+        if cell.row >= self.height || cell.col >= self.width {
+            return None;
+        }
+        let index = (layer.index() * self.height + cell.row) * self.width + cell.col;
+        (index < self.cells.len()).then_some(index)
+    }
+
+    /// Charges one frame to the cell the player is standing in.
+    ///
+    /// Cells outside the level are ignored.
+    ///
+    /// # Arguments
+    /// * `cell` - The player's wall-grid cell
+    /// * `layer` - The floor the player is on
+    /// * `frame_ms` - CPU time of the frame in milliseconds
+    pub fn record(&mut self, cell: Cell, layer: Layer, frame_ms: f32) {
+        if let Some(index) = self.index(cell, layer) {
+            self.cells[index].add_sample(frame_ms);
+        }
+    }
+
+    /// Returns every cell with at least one recorded frame, in table order.
+    pub fn recorded(&self) -> impl Iterator<Item = (Cell, Layer, CellCost)> + '_ {
+        let per_floor = self.width * self.height;
+        self.cells
+            .iter()
+            .enumerate()
+            .filter(|(_, cost)| cost.samples > 0)
+            .map(move |(index, cost)| {
+                let layer = if index < per_floor {
+                    Layer::Lower
+                } else {
+                    Layer::Upper
+                };
+                let offset = index % per_floor;
+                let cell = Cell::new(offset / self.width, offset % self.width);
+                (cell, layer, *cost)
+            })
+    }
+
+    /// Returns the cheapest and most expensive average among the recorded cells.
+    ///
+    /// # Returns
+    /// `(min_ms, max_ms)`, or `None` if nothing has been recorded yet
+    pub fn range(&self) -> Option<(f32, f32)> {
+        self.recorded().fold(None, |range, (_, _, cost)| {
+            let (min, max) = range.unwrap_or((cost.average_ms, cost.average_ms));
+            Some((min.min(cost.average_ms), max.max(cost.average_ms)))
+        })
+    }
+
+    /// Writes the recorded cells as CSV, one row per cell.
+    ///
+    /// # Arguments
+    /// * `out` - Where to write the table
+    pub fn write_csv(&self, out: &mut impl Write) -> io::Result<()> {
+        writeln!(out, "floor,row,col,samples,average_cpu_ms")?;
+        for (cell, layer, cost) in self.recorded() {
+            writeln!(
+                out,
+                "{},{},{},{},{:.3}",
+                layer.index(),
+                cell.row,
+                cell.col,
+                cost.samples,
+                cost.average_ms
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Saves the table to a timestamped CSV file in the benchmarks directory.
+    ///
+    /// # Returns
+    /// The file written, or an error message if it could not be written
+    pub fn export(&self) -> Result<PathBuf, String> {
+        let dir = paths::get().benchmarks_dir();
+        fs::create_dir_all(&dir)
+            .map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+
+        let path = dir.join(format!(
+            "frame_heatmap_{}.csv",
+            Local::now().format("%Y%m%d_%H%M%S")
+        ));
+        let mut contents = Vec::new();
+        self.write_csv(&mut contents)
+            .map_err(|e| format!("Failed to format frame heatmap: {}", e))?;
+        fs::write(&path, contents)
+            .map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+        Ok(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn open_grid(width: usize, height: usize) -> Vec<Vec<bool>> {
+        vec![vec![false; width]; height]
+    }
+
+    #[test]
+    fn test_rolling_average_settles_on_recent_frames() {
+        let mut cost = CellCost::default();
+        cost.add_sample(10.0);
+        cost.add_sample(20.0);
+        assert_eq!(cost.samples, 2);
+        assert!((cost.average_ms - 15.0).abs() < 1e-4);
+
+        // Past the window, a steady new cost takes over from the old one
+        for _ in 0..ROLLING_WINDOW * 10 {
+            cost.add_sample(40.0);
+        }
+        assert!((cost.average_ms - 40.0).abs() < 0.01);
+
+        // And a single spike only moves it by a window's share
+        cost.add_sample(40.0 + ROLLING_WINDOW as f32);
+        assert!((cost.average_ms - 41.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_cells_map_to_their_own_slots() {
+        let heatmap = FrameHeatmap::for_grid(&open_grid(5, 3), 2);
+        assert_eq!(heatmap.index(Cell::new(0, 0), Layer::Lower), Some(0));
+        assert_eq!(heatmap.index(Cell::new(1, 2), Layer::Lower), Some(7));
+        assert_eq!(heatmap.index(Cell::new(0, 0), Layer::Upper), Some(15));
+        assert_eq!(heatmap.index(Cell::new(2, 4), Layer::Upper), Some(29));
+        assert_eq!(heatmap.index(Cell::new(3, 0), Layer::Lower), None);
+        assert_eq!(heatmap.index(Cell::new(0, 5), Layer::Lower), None);
+
+        let single = FrameHeatmap::for_grid(&open_grid(5, 3), 1);
+        assert_eq!(single.index(Cell::new(0, 0), Layer::Upper), None);
+    }
+
+    #[test]
+    fn test_recorded_cells_round_trip_to_csv() {
+        let mut heatmap = FrameHeatmap::for_grid(&open_grid(4, 4), 2);
+        assert_eq!(heatmap.range(), None);
+
+        heatmap.record(Cell::new(1, 2), Layer::Lower, 8.0);
+        heatmap.record(Cell::new(3, 1), Layer::Upper, 12.0);
+        heatmap.record(Cell::new(9, 9), Layer::Lower, 100.0);
+
+        let recorded: Vec<_> = heatmap.recorded().collect();
+        assert_eq!(recorded.len(), 2);
+        assert_eq!(
+            (recorded[0].0, recorded[0].1),
+            (Cell::new(1, 2), Layer::Lower)
+        );
+        assert_eq!(
+            (recorded[1].0, recorded[1].1),
+            (Cell::new(3, 1), Layer::Upper)
+        );
+        assert_eq!(heatmap.range(), Some((8.0, 12.0)));
+
+        let mut csv = Vec::new();
+        heatmap.write_csv(&mut csv).unwrap();
+        assert_eq!(
+            String::from_utf8(csv).unwrap(),
+            "floor,row,col,samples,average_cpu_ms\n0,1,2,1,8.000\n1,3,1,1,12.000\n"
+        );
+    }
+}
@@ -0,0 +1,450 @@
+//! Controller rumble for the Mirador game.
+//!
+//! [`HapticsDirector`] decides when the controller should rumble. It is ticked once per frame with
+//! a [`HapticFrame`] of facts about the game and fires a [`Rumble`] when something worth feeling
+//! happens:
+//!
+//! - [`Rumble::EnemyClose`]: a short strong pulse when the enemy comes within [`DANGER_RANGE`]
+//! - [`Rumble::EnemyLingering`]: a soft pulse every [`LINGER_INTERVAL`] seconds while it stays there
+//! - [`Rumble::TimerCritical`]: a double tap when the timer crosses its critical threshold
+//! - [`Rumble::Caught`]: a long rumble that fades out when the enemy catches the player
+//!
+//! Nothing new fires on menu screens, while the window is unfocused, or when the haptics
+//! intensity setting is `0`. Losing focus or turning haptics off also stops whatever is playing,
+//! and so does opening a menu, except that the caught rumble is left to fade out over the game
+//! over screen.
+//!
+//! The director knows nothing about controllers: it plays rumbles through a [`HapticsOutput`].
+//! [`GamepadRumble`] drives every connected force-feedback gamepad through the gilrs context
+//! [`Gamepads`](crate::game::input::Gamepads) owns, and quietly drops commands for controllers
+//! that are missing or were unplugged mid-rumble.
+//!
+//! # Usage
+//!
+//! ```rust,ignore
+//! let mut director = HapticsDirector::new();
+//! let mut gamepads = Gamepads::new();
+//!
+//! // Once per frame
+//! gamepads.poll(settings.gamepad_deadzone);
+//! director.update(frame, settings.haptics_intensity, &mut gamepads);
+//! ```
+
+use crate::game::combo::DANGER_RANGE;
+use gilrs::Gilrs;
+use gilrs::ff::{
+    BaseEffect, BaseEffectType, Effect, EffectBuilder, Envelope, Repeat, Replay, Ticks,
+};
+use std::time::Duration;
+
+/// Seconds between soft pulses while the enemy stays within [`DANGER_RANGE`].
+pub const LINGER_INTERVAL: f32 = 1.0;
+
+/// Seconds after an [`Rumble::EnemyClose`] pulse before another one can fire.
+///
+/// Keeps an enemy hovering at the edge of danger range from pulsing every few frames.
+pub const ENEMY_CLOSE_COOLDOWN: f32 = 3.0;
+
+/// Something the player should feel through the controller.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Rumble {
+    /// The enemy just came within danger range
+    EnemyClose,
+    /// The enemy is still within danger range
+    EnemyLingering,
+    /// The level timer just crossed its critical threshold
+    TimerCritical,
+    /// The enemy caught the player
+    Caught,
+}
+
+impl Rumble {
+    /// Returns how the rumble feels.
+    pub fn pattern(self) -> RumblePattern {
+        match self {
+            Rumble::EnemyClose => RumblePattern {
+                strong: 1.0,
+                weak: 0.6,
+                pulse: Duration::from_millis(180),
+                gap: Duration::ZERO,
+                pulses: 1,
+                fade: Duration::ZERO,
+            },
+            Rumble::EnemyLingering => RumblePattern {
+                strong: 0.0,
+                weak: 0.35,
+                pulse: Duration::from_millis(120),
+                gap: Duration::ZERO,
+                pulses: 1,
+                fade: Duration::ZERO,
+            },
+            Rumble::TimerCritical => RumblePattern {
+                strong: 0.8,
+                weak: 0.4,
+                pulse: Duration::from_millis(90),
+                gap: Duration::from_millis(90),
+                pulses: 2,
+                fade: Duration::ZERO,
+            },
+            Rumble::Caught => RumblePattern {
+                strong: 1.0,
+                weak: 1.0,
+                pulse: Duration::from_millis(1500),
+                gap: Duration::ZERO,
+                pulses: 1,
+                fade: Duration::from_millis(1200),
+            },
+        }
+    }
+}
+
+/// The shape of a rumble: one or more identical pulses on both motors.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RumblePattern {
+    /// Strength of the low-frequency (strong) motor, from `0.0` to `1.0`
+    pub strong: f32,
+    /// Strength of the high-frequency (weak) motor, from `0.0` to `1.0`
+    pub weak: f32,
+    /// How long each pulse lasts
+    pub pulse: Duration,
+    /// Pause between pulses
+    pub gap: Duration,
+    /// Number of pulses
+    pub pulses: u32,
+    /// How long the end of each pulse takes to fade to nothing
+    pub fade: Duration,
+}
+
+impl RumblePattern {
+    /// Returns how long the whole rumble lasts.
+    pub fn duration(&self) -> Duration {
+        (self.pulse + self.gap) * self.pulses.max(1) - self.gap
+    }
+}
+
+/// Something that can play rumbles, usually [`Gamepads`](crate::game::input::Gamepads).
+pub trait HapticsOutput {
+    /// Starts a rumble, replacing any that is still playing.
+    ///
+    /// # Arguments
+    /// * `rumble` - The rumble to play
+    /// * `intensity` - Master intensity from `0.0` to `1.0` the pattern is scaled by
+    fn play(&mut self, rumble: Rumble, intensity: f32);
+
+    /// Stops the rumble that is playing, if any.
+    fn stop(&mut self);
+}
+
+/// Facts about a single frame that drive the controller rumble.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HapticFrame {
+    /// Seconds since the last frame
+    pub delta_time: f32,
+    /// Whether a level is being played rather than a menu or loading screen being shown
+    pub in_gameplay: bool,
+    /// Whether the game window has keyboard focus
+    pub window_focused: bool,
+    /// Horizontal distance between the player and the enemy, or `None` while the enemy is locked
+    pub enemy_distance: Option<f32>,
+    /// Whether the level timer is at or below its critical threshold
+    pub timer_critical: bool,
+    /// Whether the enemy caught the player this frame
+    pub caught: bool,
+}
+
+/// Decides when the controller rumbles.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct HapticsDirector {
+    /// Whether the enemy was within danger range last frame
+    in_danger: bool,
+    /// Whether the timer was critical last frame
+    timer_critical: bool,
+    /// Whether the player was caught last frame
+    caught: bool,
+    /// Seconds until the next soft pulse while the enemy lingers
+    linger_timer: f32,
+    /// Seconds until another [`Rumble::EnemyClose`] pulse may fire
+    enemy_close_cooldown: f32,
+    /// The rumble that is playing and how many seconds of it are left
+    playing: Option<(Rumble, f32)>,
+}
+
+impl HapticsDirector {
+    /// Creates a director with nothing playing.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Works out which rumble, if any, a frame calls for.
+    ///
+    /// Edges are tracked on every frame, even suppressed ones, so coming back from a menu
+    /// does not replay a rumble for something that happened before it opened.
+    ///
+    /// # Arguments
+    /// * `frame` - Facts about the current frame
+    /// * `intensity` - Master haptics intensity; `0.0` disables rumble entirely
+    ///
+    /// # Returns
+    /// The rumble to start this frame, or `None`
+    pub fn tick(&mut self, frame: HapticFrame, intensity: f32) -> Option<Rumble> {
+        self.enemy_close_cooldown = (self.enemy_close_cooldown - frame.delta_time).max(0.0);
+        self.linger_timer -= frame.delta_time;
+        if let Some((_, remaining)) = &mut self.playing {
+            *remaining -= frame.delta_time;
+        }
+        if self.playing.is_some_and(|(_, remaining)| remaining <= 0.0) {
+            self.playing = None;
+        }
+
+        let in_danger = frame
+            .enemy_distance
+            .is_some_and(|distance| distance < DANGER_RANGE);
+        let entered_danger = in_danger && !self.in_danger;
+        let timer_went_critical = frame.timer_critical && !self.timer_critical;
+        let just_caught = frame.caught && !self.caught;
+        self.in_danger = in_danger;
+        self.timer_critical = frame.timer_critical;
+        self.caught = frame.caught;
+
+        let enabled = intensity > 0.0 && frame.window_focused && frame.in_gameplay;
+        let rumble = if !enabled {
+            None
+        } else if just_caught {
+            Some(Rumble::Caught)
+        } else if timer_went_critical {
+            Some(Rumble::TimerCritical)
+        } else if entered_danger && self.enemy_close_cooldown <= 0.0 {
+            Some(Rumble::EnemyClose)
+        } else if in_danger && self.linger_timer <= 0.0 {
+            Some(Rumble::EnemyLingering)
+        } else {
+            None
+        };
+
+        match rumble {
+            Some(Rumble::EnemyClose) => {
+                self.enemy_close_cooldown = ENEMY_CLOSE_COOLDOWN;
+                self.linger_timer = LINGER_INTERVAL;
+            }
+            Some(Rumble::EnemyLingering) => self.linger_timer = LINGER_INTERVAL,
+            _ if entered_danger => self.linger_timer = LINGER_INTERVAL,
+            _ => {}
+        }
+        rumble
+    }
+
+    /// Ticks the director and plays or stops rumbles on an output.
+    ///
+    /// # Arguments
+    /// * `frame` - Facts about the current frame
+    /// * `intensity` - Master haptics intensity; `0.0` disables rumble entirely
+    /// * `output` - Where rumbles are played
+    pub fn update(&mut self, frame: HapticFrame, intensity: f32, output: &mut impl HapticsOutput) {
+        let rumble = self.tick(frame, intensity);
+
+        // The caught rumble fades out over the game over screen; anything else stops with gameplay
+        let keep_playing = intensity > 0.0
+            && frame.window_focused
+            && (frame.in_gameplay || matches!(self.playing, Some((Rumble::Caught, _))));
+        if !keep_playing && self.playing.take().is_some() {
+            output.stop();
+        }
+
+        if let Some(rumble) = rumble {
+            let intensity = intensity.min(1.0);
+            output.play(rumble, intensity);
+            self.playing = Some((rumble, rumble.pattern().duration().as_secs_f32()));
+        }
+    }
+}
+
+/// Plays rumbles on every connected gamepad that supports force feedback.
+#[derive(Default)]
+pub struct GamepadRumble {
+    /// The effect that is playing; dropping it stops it
+    effect: Option<Effect>,
+}
+
+impl GamepadRumble {
+    /// Creates a rumble output with nothing playing.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Starts a rumble on every connected gamepad, replacing any that is still playing.
+    ///
+    /// # Arguments
+    /// * `gilrs` - The gamepad context the gamepads are listed from
+    /// * `rumble` - The rumble to play
+    /// * `intensity` - Master intensity from `0.0` to `1.0` the pattern is scaled by
+    pub fn play(&mut self, gilrs: &mut Gilrs, rumble: Rumble, intensity: f32) {
+        let gamepads: Vec<_> = gilrs
+            .gamepads()
+            .filter(|(_, gamepad)| gamepad.is_ff_supported())
+            .map(|(id, _)| id)
+            .collect();
+        if gamepads.is_empty() {
+            return;
+        }
+
+        let pattern = rumble.pattern();
+        let to_ticks = |duration: Duration| Ticks::from_ms(duration.as_millis() as u32);
+        let scheduling = Replay {
+            after: Ticks::from_ms(0),
+            play_for: to_ticks(pattern.pulse),
+            with_delay: to_ticks(pattern.gap),
+        };
+        let envelope = Envelope {
+            fade_length: to_ticks(pattern.fade),
+            ..Default::default()
+        };
+        let magnitude = |strength: f32| (strength * intensity * u16::MAX as f32) as u16;
+
+        let mut builder = EffectBuilder::new();
+        builder
+            .add_effect(BaseEffect {
+                kind: BaseEffectType::Strong {
+                    magnitude: magnitude(pattern.strong),
+                },
+                scheduling,
+                envelope,
+            })
+            .add_effect(BaseEffect {
+                kind: BaseEffectType::Weak {
+                    magnitude: magnitude(pattern.weak),
+                },
+                scheduling,
+                envelope,
+            })
+            .repeat(Repeat::For(to_ticks(pattern.duration())))
+            .gamepads(&gamepads);
+
+        // A gamepad can be unplugged between listing it and building the effect
+        let effect = match builder.finish(gilrs) {
+            Ok(effect) => effect,
+            Err(e) => {
+                eprintln!("Failed to create {:?} rumble: {}", rumble, e);
+                return;
+            }
+        };
+        if let Err(e) = effect.play() {
+            eprintln!("Failed to play {:?} rumble: {}", rumble, e);
+        }
+        self.effect = Some(effect);
+    }
+
+    /// Stops the rumble that is playing, if any.
+    pub fn stop(&mut self) {
+        if let Some(effect) = self.effect.take() {
+            // Fails harmlessly if the gamepad was unplugged mid-rumble
+            let _ = effect.stop();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Records what the director asked for instead of driving a controller.
+    #[derive(Default)]
+    struct RecordingOutput {
+        played: Vec<Rumble>,
+        stops: usize,
+    }
+
+    impl HapticsOutput for RecordingOutput {
+        fn play(&mut self, rumble: Rumble, _intensity: f32) {
+            self.played.push(rumble);
+        }
+
+        fn stop(&mut self) {
+            self.stops += 1;
+        }
+    }
+
+    fn frame(enemy_distance: Option<f32>, timer_critical: bool, caught: bool) -> HapticFrame {
+        HapticFrame {
+            delta_time: 0.25,
+            in_gameplay: true,
+            window_focused: true,
+            enemy_distance,
+            timer_critical,
+            caught,
+        }
+    }
+
+    #[test]
+    fn test_scripted_level_fires_expected_rumbles() {
+        let mut director = HapticsDirector::new();
+        let mut output = RecordingOutput::default();
+        let far = Some(DANGER_RANGE * 2.0);
+        let close = Some(DANGER_RANGE / 2.0);
+
+        // Enemy approaches and stays close for a second and a half
+        director.update(frame(far, false, false), 1.0, &mut output);
+        for _ in 0..7 {
+            director.update(frame(close, false, false), 1.0, &mut output);
+        }
+        // Enemy backs off and returns while the strong pulse is cooling down, so it only
+        // gets the soft one
+        director.update(frame(far, false, false), 1.0, &mut output);
+        director.update(frame(close, false, false), 1.0, &mut output);
+        // Timer goes critical, stays critical, then the player is caught
+        director.update(frame(far, true, false), 1.0, &mut output);
+        director.update(frame(far, true, false), 1.0, &mut output);
+        director.update(frame(close, true, true), 1.0, &mut output);
+
+        assert_eq!(
+            output.played,
+            vec![
+                Rumble::EnemyClose,
+                Rumble::EnemyLingering,
+                Rumble::EnemyLingering,
+                Rumble::TimerCritical,
+                Rumble::Caught,
+            ]
+        );
+
+        // The caught rumble is left to fade out on the game over screen
+        let game_over = HapticFrame {
+            in_gameplay: false,
+            ..frame(None, false, false)
+        };
+        director.update(game_over, 1.0, &mut output);
+        assert_eq!(output.stops, 0);
+    }
+
+    #[test]
+    fn test_zero_intensity_disables_rumble() {
+        let mut director = HapticsDirector::new();
+        let mut output = RecordingOutput::default();
+        director.update(frame(Some(0.0), true, true), 0.0, &mut output);
+        assert!(output.played.is_empty());
+    }
+
+    #[test]
+    fn test_unfocused_and_menus_suppress_rumble() {
+        let mut director = HapticsDirector::new();
+        let mut output = RecordingOutput::default();
+        director.update(frame(None, true, false), 1.0, &mut output);
+        assert_eq!(output.played, vec![Rumble::TimerCritical]);
+
+        // Losing focus stops the rumble and nothing new starts
+        let unfocused = HapticFrame {
+            window_focused: false,
+            ..frame(Some(0.0), true, false)
+        };
+        director.update(unfocused, 1.0, &mut output);
+        assert_eq!(output.stops, 1);
+
+        // Events that happen on a menu are not replayed on return
+        let paused = HapticFrame {
+            in_gameplay: false,
+            ..frame(None, true, true)
+        };
+        director.update(paused, 1.0, &mut output);
+        director.update(frame(None, true, true), 1.0, &mut output);
+        assert_eq!(output.played, vec![Rumble::TimerCritical]);
+    }
+}
@@ -0,0 +1,277 @@
+//! Hardcore permadeath mode for the Mirador game.
+//!
+//! A hardcore run can't retry a level: when the timer runs out the run is over, and its seed is
+//! buried in the [`Graveyard`] so the same mazes can never be played again. The graveyard lives in
+//! the data directory (see [`crate::paths`]) and keeps the newest [`MAX_GRAVES`] seeds, dropping
+//! the oldest first. Typing a buried seed on the title screen, or starting a daily challenge whose
+//! seed is buried, is refused with a message saying why.
+//!
+//! In exchange, every level of a hardcore run scores [`HARDCORE_SCORE_MULTIPLIER`] times the
+//! usual points, and the graveyard remembers the best score and level any hardcore run reached.
+//!
+//! # Usage
+//!
+//! ```rust,ignore
+//! graveyard.check_seed(seed)?;
+//!
+//! // When the run dies
+//! graveyard.bury(Grave { seed, level, score });
+//! graveyard.save()?;
+//! ```
+
+use crate::paths;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+/// Score multiplier applied to every level of a hardcore run.
+pub const HARDCORE_SCORE_MULTIPLIER: f32 = 1.5;
+
+/// Most graves kept; the oldest are dropped past this.
+pub const MAX_GRAVES: usize = 10_000;
+
+/// Label shown next to the level on the HUD during a hardcore run.
+pub const HARDCORE_LABEL: &str = "Hardcore";
+
+/// Returns the difficulty multiplier a level is scored with.
+///
+/// # Arguments
+/// * `hardcore` - Whether the run is hardcore
+pub fn score_multiplier(hardcore: bool) -> f32 {
+    if hardcore {
+        HARDCORE_SCORE_MULTIPLIER
+    } else {
+        1.0
+    }
+}
+
+/// A hardcore run that died.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Grave {
+    /// Seed the run's mazes were generated from
+    pub seed: u64,
+    /// Level the run died on
+    pub level: i32,
+    /// Run score at the moment of death
+    pub score: u32,
+}
+
+/// Seeds burned by hardcore deaths, persisted between sessions.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Graveyard {
+    /// One grave per burned seed, oldest first
+    pub graves: Vec<Grave>,
+    /// Best score any hardcore run reached
+    pub best_score: u32,
+    /// Furthest level any hardcore run reached
+    pub best_level: i32,
+}
+
+impl Graveyard {
+    /// Loads the graveyard from the data directory.
+    ///
+    /// # Returns
+    /// The saved graveyard, or an empty one if the file is missing or invalid
+    pub fn load() -> Self {
+        Self::load_from(&paths::get().hardcore_graveyard())
+    }
+
+    /// Loads the graveyard from a specific file.
+    ///
+    /// # Arguments
+    /// * `path` - The graveyard file to read
+    ///
+    /// # Returns
+    /// The saved graveyard, or an empty one if the file is missing or invalid
+    pub fn load_from(path: &Path) -> Self {
+        let contents = match fs::read_to_string(path) {
+            Ok(contents) => contents,
+            Err(_) => return Self::default(),
+        };
+
+        match serde_json::from_str(&contents) {
+            Ok(graveyard) => graveyard,
+            Err(e) => {
+                eprintln!(
+                    "Failed to parse hardcore graveyard file {}: {}, starting fresh",
+                    path.display(),
+                    e
+                );
+                Self::default()
+            }
+        }
+    }
+
+    /// Saves the graveyard to the data directory.
+    ///
+    /// # Returns
+    /// `Ok(())` on success, or an error message if the file could not be written
+    pub fn save(&self) -> Result<(), String> {
+        self.save_to(&paths::get().hardcore_graveyard())
+    }
+
+    /// Saves the graveyard to a specific file.
+    ///
+    /// # Arguments
+    /// * `path` - The graveyard file to write
+    ///
+    /// # Returns
+    /// `Ok(())` on success, or an error message if the file could not be written
+    pub fn save_to(&self, path: &Path) -> Result<(), String> {
+        let contents = serde_json::to_string_pretty(self)
+            .map_err(|e| format!("Failed to serialize hardcore graveyard: {}", e))?;
+        fs::write(path, contents).map_err(|e| {
+            format!(
+                "Failed to write hardcore graveyard to {}: {}",
+                path.display(),
+                e
+            )
+        })
+    }
+
+    /// Returns whether a seed has been burned by a hardcore death.
+    ///
+    /// # Arguments
+    /// * `seed` - The run or daily challenge seed
+    pub fn is_burned(&self, seed: u64) -> bool {
+        self.graves.iter().any(|grave| grave.seed == seed)
+    }
+
+    /// Checks that a seed can still be played.
+    ///
+    /// # Arguments
+    /// * `seed` - The run or daily challenge seed
+    ///
+    /// # Returns
+    /// `Ok(())` if the seed is playable, or a message explaining why it was refused
+    pub fn check_seed(&self, seed: u64) -> Result<(), String> {
+        match self.graves.iter().rev().find(|grave| grave.seed == seed) {
+            Some(grave) => Err(format!(
+                "This seed was burned when a hardcore run died on level {}",
+                grave.level
+            )),
+            None => Ok(()),
+        }
+    }
+
+    /// Buries a dead hardcore run, burning its seed.
+    ///
+    /// Updates the hardcore bests and drops the oldest graves past [`MAX_GRAVES`].
+    ///
+    /// # Arguments
+    /// * `grave` - The run that died
+    pub fn bury(&mut self, grave: Grave) {
+        self.best_score = self.best_score.max(grave.score);
+        self.best_level = self.best_level.max(grave.level);
+        self.graves.push(grave);
+
+        let excess = self.graves.len().saturating_sub(MAX_GRAVES);
+        self.graves.drain(..excess);
+    }
+
+    /// Returns the number of seeds in the graveyard.
+    pub fn count(&self) -> usize {
+        self.graves.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::score::{LevelResult, ScoreCalculator};
+
+    fn grave(seed: u64) -> Grave {
+        Grave {
+            seed,
+            level: 3,
+            score: 1200,
+        }
+    }
+
+    #[test]
+    fn test_burned_seeds_are_refused() {
+        let mut graveyard = Graveyard::default();
+        assert_eq!(graveyard.count(), 0);
+        assert_eq!(graveyard.check_seed(42), Ok(()));
+
+        graveyard.bury(grave(42));
+        assert!(graveyard.is_burned(42));
+        let message = graveyard.check_seed(42).unwrap_err();
+        assert!(message.contains("burned"));
+        assert!(message.contains("level 3"));
+
+        // Other seeds are still playable
+        assert!(!graveyard.is_burned(43));
+        assert_eq!(graveyard.check_seed(43), Ok(()));
+    }
+
+    #[test]
+    fn test_hardcore_multiplier_scales_the_level_score() {
+        let result = LevelResult {
+            level: 2,
+            completion_time: 20.0,
+            time_remaining: 10.0,
+            cells_visited: 10,
+            total_cells: 40,
+            closest_enemy_distance: 100.0,
+            keys_collected: 0,
+            difficulty_multiplier: score_multiplier(false),
+            combo_multiplier: 1.0,
+            solution_length: 0,
+            close_calls: 0,
+            duplicate_anomalies: 0,
+        };
+        let calculator = ScoreCalculator::default();
+        let normal = calculator.calculate(&result);
+        let hardcore = calculator.calculate(&LevelResult {
+            difficulty_multiplier: score_multiplier(true),
+            ..result
+        });
+
+        assert_eq!(normal.multiplier, 1.0);
+        assert_eq!(hardcore.multiplier, HARDCORE_SCORE_MULTIPLIER);
+        assert!(hardcore.total > normal.total);
+        let expected = normal.total as f32 * HARDCORE_SCORE_MULTIPLIER;
+        assert!((hardcore.total as f32 - expected).abs() <= 1.0);
+    }
+
+    #[test]
+    fn test_graveyard_drops_the_oldest_graves() {
+        let mut graveyard = Graveyard::default();
+        for seed in 0..MAX_GRAVES as u64 + 3 {
+            graveyard.bury(grave(seed));
+        }
+        assert_eq!(graveyard.count(), MAX_GRAVES);
+        assert!(!graveyard.is_burned(2));
+        assert!(graveyard.is_burned(3));
+        assert!(graveyard.is_burned(MAX_GRAVES as u64 + 2));
+    }
+
+    #[test]
+    fn test_bests_track_the_furthest_run() {
+        let mut graveyard = Graveyard::default();
+        graveyard.bury(Grave {
+            seed: 1,
+            level: 5,
+            score: 800,
+        });
+        graveyard.bury(Grave {
+            seed: 2,
+            level: 2,
+            score: 1500,
+        });
+        assert_eq!(graveyard.best_level, 5);
+        assert_eq!(graveyard.best_score, 1500);
+    }
+
+    #[test]
+    fn test_graveyard_round_trip() {
+        let path = std::env::temp_dir().join("mirador_hardcore_graveyard_round_trip.json");
+        let mut graveyard = Graveyard::default();
+        graveyard.bury(grave(7));
+        graveyard.save_to(&path).expect("save graveyard");
+        assert_eq!(Graveyard::load_from(&path), graveyard);
+        let _ = fs::remove_file(&path);
+    }
+}
@@ -0,0 +1,440 @@
+//! Per-frame player input from the keyboard and mouse or a gamepad.
+//!
+//! Gameplay reads one [`PlayerInput`] each frame instead of raw keys, so walking and looking work
+//! the same whichever device they come from. The keyboard's comes from the held keys in
+//! [`KeyState`]; a gamepad's comes from [`Gamepads::poll`], and the two are merged, so a player
+//! can pick up a controller mid-level without touching a menu.
+//!
+//! The move axis is analog: a stick pushed halfway walks at half speed, while the keyboard's
+//! all-or-nothing keys always ask for full speed. Sticks pass through a radial deadzone first
+//! (see [`apply_deadzone`]), so a worn stick resting slightly off center doesn't creep.
+//!
+//! Gamepads also drive the menus. The D-pad and the A button are reported as [`MenuNav`] steps,
+//! which each menu hands to its [`ButtonManager`](crate::renderer::ui::button::ButtonManager) to
+//! move the focus highlight and click the focused button.
+//!
+//! [`Gamepads`] follows controllers being plugged in and out: the most recently used one
+//! drives the player, and when it is unplugged the next most recently connected one takes
+//! over. It also owns the gilrs context the controller rumble is played through.
+//!
+//! # Usage
+//!
+//! ```rust,ignore
+//! let mut gamepads = Gamepads::new();
+//!
+//! // Once per frame
+//! let frame = gamepads.poll(settings.gamepad_deadzone);
+//! for nav in &frame.menu {
+//!     title_menu.navigate(*nav, &mut audio_manager);
+//! }
+//! key_state.update(&mut game_state, &frame.input);
+//! ```
+
+use crate::game::haptics::{GamepadRumble, HapticsOutput, Rumble};
+use crate::game::keys::{GameKey, KeyState};
+use gilrs::{Axis, Button, EventType, GamepadId, Gilrs};
+
+/// How far a stick can rest off center before it counts as pushed, by default.
+pub const DEFAULT_DEADZONE: f32 = 0.15;
+
+/// How many degrees per second the view turns with the look stick pushed all the way, by default.
+pub const DEFAULT_LOOK_SENSITIVITY: f32 = 180.0;
+
+/// What the player asked for this frame, from whichever devices they are using.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct PlayerInput {
+    /// Strafe and forward deflection from `-1.0` to `1.0`, right and forward positive; its
+    /// length is at most `1.0`
+    pub move_axis: [f32; 2],
+    /// Turn and tilt deflection from `-1.0` to `1.0`, right and up positive
+    pub look_axis: [f32; 2],
+    /// Whether sprint is held
+    pub sprint: bool,
+    /// Whether pause was pressed this frame
+    pub pause: bool,
+    /// Whether interact was pressed this frame
+    pub interact: bool,
+    /// Whether the quick-settings radial is held open
+    pub quick_radial: bool,
+}
+
+impl PlayerInput {
+    /// Reads the held movement and sprint keys.
+    ///
+    /// Pause, interact, and the quick-settings radial are acted on as soon as their key
+    /// events arrive, so they are never set here.
+    ///
+    /// # Arguments
+    /// * `keys` - The keys currently held
+    pub fn from_keys(keys: &KeyState) -> Self {
+        let axis = |positive: GameKey, negative: GameKey| {
+            keys.is_pressed(positive) as i32 as f32 - keys.is_pressed(negative) as i32 as f32
+        };
+        Self {
+            move_axis: clamp_length([
+                axis(GameKey::MoveRight, GameKey::MoveLeft),
+                axis(GameKey::MoveForward, GameKey::MoveBackward),
+            ]),
+            sprint: keys.is_pressed(GameKey::Sprint),
+            ..Self::default()
+        }
+    }
+
+    /// Combines the input of two devices used at once.
+    ///
+    /// The axes add up, with the move axis kept within a length of `1.0` so two devices
+    /// can't walk faster than one; a button counts if it is held or pressed on either.
+    ///
+    /// # Arguments
+    /// * `other` - The other device's input
+    pub fn merge(self, other: Self) -> Self {
+        Self {
+            move_axis: clamp_length([
+                self.move_axis[0] + other.move_axis[0],
+                self.move_axis[1] + other.move_axis[1],
+            ]),
+            look_axis: [
+                (self.look_axis[0] + other.look_axis[0]).clamp(-1.0, 1.0),
+                (self.look_axis[1] + other.look_axis[1]).clamp(-1.0, 1.0),
+            ],
+            sprint: self.sprint || other.sprint,
+            pause: self.pause || other.pause,
+            interact: self.interact || other.interact,
+            quick_radial: self.quick_radial || other.quick_radial,
+        }
+    }
+
+    /// Whether the move axis asks for any movement.
+    pub fn is_moving(&self) -> bool {
+        self.move_axis != [0.0, 0.0]
+    }
+}
+
+/// Shortens a 2D axis to a length of at most `1.0`, keeping its direction.
+fn clamp_length(axis: [f32; 2]) -> [f32; 2] {
+    let length = axis[0].hypot(axis[1]);
+    if length > 1.0 {
+        [axis[0] / length, axis[1] / length]
+    } else {
+        axis
+    }
+}
+
+/// Drops a stick's resting wobble and rescales the rest of its travel.
+///
+/// The deadzone is radial, so diagonals aren't snapped to the axes. Past the deadzone the
+/// deflection ramps up from `0.0` again, so the slowest walk is still reachable.
+///
+/// # Arguments
+/// * `axis` - Raw stick deflection, each component from `-1.0` to `1.0`
+/// * `deadzone` - How far off center the stick can be before it counts, from `0.0` to below `1.0`
+///
+/// # Returns
+/// The deflection with the deadzone removed, at most `1.0` long
+pub fn apply_deadzone(axis: [f32; 2], deadzone: f32) -> [f32; 2] {
+    let deadzone = deadzone.clamp(0.0, 0.95);
+    let length = axis[0].hypot(axis[1]);
+    if length <= deadzone {
+        return [0.0, 0.0];
+    }
+    let scaled = ((length - deadzone) / (1.0 - deadzone)).min(1.0);
+    [axis[0] / length * scaled, axis[1] / length * scaled]
+}
+
+/// A step through a menu with a gamepad.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MenuNav {
+    /// Focus the button before the focused one (D-pad up or left)
+    Previous,
+    /// Focus the button after the focused one (D-pad down or right)
+    Next,
+    /// Click the focused button (A)
+    Activate,
+}
+
+impl MenuNav {
+    /// Returns the menu step a gamepad button makes, if any.
+    ///
+    /// # Arguments
+    /// * `button` - The button that was pressed
+    pub fn from_button(button: Button) -> Option<Self> {
+        match button {
+            Button::DPadUp | Button::DPadLeft => Some(Self::Previous),
+            Button::DPadDown | Button::DPadRight => Some(Self::Next),
+            Button::South => Some(Self::Activate),
+            _ => None,
+        }
+    }
+}
+
+/// Which of the connected gamepads drives the player.
+///
+/// Generic over the id so the hot-plug rules can be tested without a controller.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ActiveGamepad<Id> {
+    /// Connected gamepads, least recently connected or used first
+    connected: Vec<Id>,
+}
+
+impl<Id> Default for ActiveGamepad<Id> {
+    fn default() -> Self {
+        Self {
+            connected: Vec::new(),
+        }
+    }
+}
+
+impl<Id: Copy + PartialEq> ActiveGamepad<Id> {
+    /// Returns the gamepad that drives the player, or `None` if none is connected.
+    pub fn active(&self) -> Option<Id> {
+        self.connected.last().copied()
+    }
+
+    /// Notes a gamepad being plugged in; it takes over from the active one.
+    pub fn connected(&mut self, id: Id) {
+        self.used(id);
+    }
+
+    /// Notes a button being pressed on a gamepad; it takes over from the active one.
+    pub fn used(&mut self, id: Id) {
+        self.connected.retain(|&other| other != id);
+        self.connected.push(id);
+    }
+
+    /// Notes a gamepad being unplugged.
+    ///
+    /// # Returns
+    /// Whether it was the active gamepad
+    pub fn disconnected(&mut self, id: Id) -> bool {
+        let was_active = self.active() == Some(id);
+        self.connected.retain(|&other| other != id);
+        was_active
+    }
+}
+
+/// What the gamepads did since the last frame.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct GamepadFrame {
+    /// The active gamepad's input
+    pub input: PlayerInput,
+    /// Menu steps, in the order their buttons were pressed
+    pub menu: Vec<MenuNav>,
+    /// Whether the gamepad driving the player was unplugged
+    pub lost_active: bool,
+}
+
+/// The system's gamepads, read once per frame.
+pub struct Gamepads {
+    /// Gamepad context, or `None` if gamepads are unavailable on this system
+    gilrs: Option<Gilrs>,
+    /// Which gamepad drives the player
+    active: ActiveGamepad<GamepadId>,
+    /// Rumble played on the connected gamepads
+    rumble: GamepadRumble,
+}
+
+impl Gamepads {
+    /// Connects to the system's gamepads, starting with the ones already plugged in.
+    ///
+    /// # Returns
+    /// Gamepads that report no input if gamepad support could not be initialized
+    pub fn new() -> Self {
+        let gilrs = match Gilrs::new() {
+            Ok(gilrs) => Some(gilrs),
+            Err(e) => {
+                eprintln!("Gamepad support unavailable: {}", e);
+                None
+            }
+        };
+        let mut active = ActiveGamepad::default();
+        if let Some(gilrs) = &gilrs {
+            for (id, gamepad) in gilrs.gamepads() {
+                println!("Gamepad connected: {}", gamepad.name());
+                active.connected(id);
+            }
+        }
+        Self {
+            gilrs,
+            active,
+            rumble: GamepadRumble::new(),
+        }
+    }
+
+    /// Processes gamepad events and reads the active gamepad.
+    ///
+    /// Call once per frame.
+    ///
+    /// # Arguments
+    /// * `deadzone` - How far off center a stick can be before it counts
+    pub fn poll(&mut self, deadzone: f32) -> GamepadFrame {
+        let mut frame = GamepadFrame::default();
+        let Some(gilrs) = &mut self.gilrs else {
+            return frame;
+        };
+
+        while let Some(event) = gilrs.next_event() {
+            match event.event {
+                EventType::Connected => {
+                    println!("Gamepad connected: {}", gilrs.gamepad(event.id).name());
+                    self.active.connected(event.id);
+                }
+                EventType::Disconnected => {
+                    println!("Gamepad disconnected");
+                    frame.lost_active |= self.active.disconnected(event.id);
+                }
+                EventType::ButtonPressed(button, _) => {
+                    self.active.used(event.id);
+                    match button {
+                        Button::Start => frame.input.pause = true,
+                        Button::South => frame.input.interact = true,
+                        _ => {}
+                    }
+                    frame.menu.extend(MenuNav::from_button(button));
+                }
+                _ => {}
+            }
+        }
+
+        let Some(gamepad) = self
+            .active
+            .active()
+            .and_then(|id| gilrs.connected_gamepad(id))
+        else {
+            return frame;
+        };
+        let stick = |x, y| apply_deadzone([gamepad.value(x), gamepad.value(y)], deadzone);
+        frame.input.move_axis = stick(Axis::LeftStickX, Axis::LeftStickY);
+        frame.input.look_axis = stick(Axis::RightStickX, Axis::RightStickY);
+        frame.input.sprint =
+            gamepad.is_pressed(Button::LeftThumb) || gamepad.is_pressed(Button::LeftTrigger2);
+        frame.input.quick_radial = gamepad.is_pressed(Button::LeftTrigger);
+        frame
+    }
+}
+
+impl Default for Gamepads {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl HapticsOutput for Gamepads {
+    fn play(&mut self, rumble: Rumble, intensity: f32) {
+        if let Some(gilrs) = &mut self.gilrs {
+            self.rumble.play(gilrs, rumble, intensity);
+        }
+    }
+
+    fn stop(&mut self) {
+        self.rumble.stop();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_axis_eq(actual: [f32; 2], expected: [f32; 2]) {
+        assert!(
+            (actual[0] - expected[0]).abs() < 1e-5 && (actual[1] - expected[1]).abs() < 1e-5,
+            "{:?} != {:?}",
+            actual,
+            expected
+        );
+    }
+
+    #[test]
+    fn test_deadzone_drops_rest_and_rescales_travel() {
+        assert_eq!(apply_deadzone([0.1, -0.1], 0.2), [0.0, 0.0]);
+        // Just past the deadzone starts from nothing, and full deflection is still full
+        assert_axis_eq(apply_deadzone([0.2, 0.0], 0.2), [0.0, 0.0]);
+        assert_axis_eq(apply_deadzone([0.6, 0.0], 0.2), [0.5, 0.0]);
+        assert_axis_eq(apply_deadzone([0.0, -1.0], 0.2), [0.0, -1.0]);
+        // Radial, so a diagonal keeps its direction, and corners don't go past full
+        let corner = apply_deadzone([1.0, 1.0], 0.2);
+        assert_axis_eq(corner, [0.5f32.sqrt(), 0.5f32.sqrt()]);
+        // No deadzone passes the stick through
+        assert_axis_eq(apply_deadzone([0.3, 0.4], 0.0), [0.3, 0.4]);
+    }
+
+    #[test]
+    fn test_keyboard_input_walks_at_full_speed() {
+        let mut keys = KeyState::default();
+        keys.press_key(GameKey::MoveForward);
+        assert_eq!(PlayerInput::from_keys(&keys).move_axis, [0.0, 1.0]);
+
+        keys.press_key(GameKey::MoveLeft);
+        keys.press_key(GameKey::Sprint);
+        let input = PlayerInput::from_keys(&keys);
+        assert_axis_eq(input.move_axis, [-(0.5f32.sqrt()), 0.5f32.sqrt()]);
+        assert!(input.sprint);
+
+        // Opposite keys cancel out
+        keys.press_key(GameKey::MoveRight);
+        keys.press_key(GameKey::MoveBackward);
+        assert!(!PlayerInput::from_keys(&keys).is_moving());
+    }
+
+    #[test]
+    fn test_merge_never_walks_faster_than_one_device() {
+        let keyboard = PlayerInput {
+            move_axis: [0.0, 1.0],
+            ..PlayerInput::default()
+        };
+        let gamepad = PlayerInput {
+            move_axis: [0.0, 0.5],
+            look_axis: [0.25, -0.5],
+            interact: true,
+            ..PlayerInput::default()
+        };
+        let merged = keyboard.merge(gamepad);
+        assert_axis_eq(merged.move_axis, [0.0, 1.0]);
+        assert_eq!(merged.look_axis, [0.25, -0.5]);
+        assert!(merged.interact);
+        assert!(!merged.sprint && !merged.pause);
+
+        // A half-pushed stick alone walks at half speed
+        assert_eq!(PlayerInput::default().merge(gamepad).move_axis, [0.0, 0.5]);
+    }
+
+    #[test]
+    fn test_hot_plugging_hands_over_the_active_gamepad() {
+        let mut gamepads = ActiveGamepad::default();
+        assert_eq!(gamepads.active(), None);
+
+        gamepads.connected(1);
+        gamepads.connected(2);
+        assert_eq!(gamepads.active(), Some(2));
+
+        // Pressing a button on the older one takes over
+        gamepads.used(1);
+        assert_eq!(gamepads.active(), Some(1));
+
+        // Unplugging an idle one changes nothing; unplugging the active one falls back
+        gamepads.connected(3);
+        gamepads.used(1);
+        assert!(!gamepads.disconnected(2));
+        assert_eq!(gamepads.active(), Some(1));
+        assert!(gamepads.disconnected(1));
+        assert_eq!(gamepads.active(), Some(3));
+        assert!(gamepads.disconnected(3));
+        assert_eq!(gamepads.active(), None);
+
+        // Replugging the same gamepad doesn't list it twice
+        gamepads.connected(4);
+        gamepads.connected(4);
+        assert!(gamepads.disconnected(4));
+        assert_eq!(gamepads.active(), None);
+    }
+
+    #[test]
+    fn test_menu_nav_from_buttons() {
+        assert_eq!(
+            MenuNav::from_button(Button::DPadUp),
+            Some(MenuNav::Previous)
+        );
+        assert_eq!(MenuNav::from_button(Button::DPadRight), Some(MenuNav::Next));
+        assert_eq!(MenuNav::from_button(Button::South), Some(MenuNav::Activate));
+        assert_eq!(MenuNav::from_button(Button::Start), None);
+    }
+}
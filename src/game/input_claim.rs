@@ -0,0 +1,288 @@
+//! Which part of the interface an input event belongs to.
+//!
+//! Two kinds of UI share the window: egui panels (the tuning panel and the sandbox panel) and the
+//! game's own [`ButtonManager`](crate::renderer::ui::button::ButtonManager) menus, with the
+//! quick-settings radial, text fields and gameplay underneath. Left to themselves they all see
+//! every click, so a click on a tuning panel slider over the title screen would also start a run.
+//!
+//! Each of them is an [`InputConsumer`], listed highest priority first. Before an event is handed
+//! out, every consumer says whether it currently claims the pointer or the keyboard, and
+//! [`InputClaims::claimant`] picks the first one that does. The claimant gets the event to itself;
+//! the consumers below it and gameplay never see it. Events nobody claims go down the chain as
+//! before, and events that only report state, like the cursor moving or the window resizing, are
+//! never claimed, since every consumer needs them to keep its hover and layout current.
+//!
+//! Claims are asked before the event is delivered, so answering one must not change anything:
+//! egui answers from the state of its last frame and the buttons from a passive hit test.
+//!
+//! # Usage
+//!
+//! ```rust,ignore
+//! let claims = state.input_claims();
+//! let kind = InputKind::of(&event);
+//! if claims.receives(InputConsumer::Buttons, kind) {
+//!     pause_menu.handle_input(&event, audio);
+//! }
+//! if !claims.receives(InputConsumer::Gameplay, kind) {
+//!     return; // Gameplay only gets unclaimed events
+//! }
+//! ```
+
+use winit::event::WindowEvent;
+
+/// What an event is aimed at, which decides which claim it falls under.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InputKind {
+    /// A mouse button or wheel
+    Pointer,
+    /// A key or input method event
+    Keyboard,
+    /// Anything else, which every consumer receives
+    Broadcast,
+}
+
+impl InputKind {
+    /// Sorts a window event by what it is aimed at.
+    ///
+    /// # Arguments
+    /// * `event` - The window event
+    pub fn of(event: &WindowEvent) -> Self {
+        match event {
+            WindowEvent::MouseInput { .. } | WindowEvent::MouseWheel { .. } => InputKind::Pointer,
+            WindowEvent::KeyboardInput { .. } | WindowEvent::Ime(_) => InputKind::Keyboard,
+            _ => InputKind::Broadcast,
+        }
+    }
+}
+
+/// Something that takes input, highest priority first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum InputConsumer {
+    /// An open text field: the title seed and code fields or the pause menu search
+    TextEntry,
+    /// The debug tuning panel
+    TuningPanel,
+    /// The practice sandbox's settings panel
+    SandboxPanel,
+    /// The quick-settings radial, while its key is held
+    QuickRadial,
+    /// The buttons of the menu on screen
+    Buttons,
+    /// Walking the maze, or starting a run from the title screen
+    Gameplay,
+}
+
+impl InputConsumer {
+    /// Every consumer that can claim input, highest priority first.
+    ///
+    /// Gameplay isn't here: it never claims, it gets whatever nobody else did.
+    pub const CLAIMING: [InputConsumer; 5] = [
+        InputConsumer::TextEntry,
+        InputConsumer::TuningPanel,
+        InputConsumer::SandboxPanel,
+        InputConsumer::QuickRadial,
+        InputConsumer::Buttons,
+    ];
+
+    /// Whether the cursor has to stay free while this consumer holds the pointer.
+    ///
+    /// The radial is steered by the captured mouse, so it is the one that doesn't.
+    pub fn needs_cursor(self) -> bool {
+        self != InputConsumer::QuickRadial
+    }
+}
+
+/// What one consumer claims right now.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Claim {
+    /// Whether it wants mouse buttons and the wheel
+    pub pointer: bool,
+    /// Whether it wants keys
+    pub keyboard: bool,
+}
+
+impl Claim {
+    /// Returns whether this claim covers an event of the given kind.
+    ///
+    /// # Arguments
+    /// * `kind` - What the event is aimed at
+    pub fn covers(self, kind: InputKind) -> bool {
+        match kind {
+            InputKind::Pointer => self.pointer,
+            InputKind::Keyboard => self.keyboard,
+            InputKind::Broadcast => false,
+        }
+    }
+}
+
+/// The claims of every consumer, taken just before an event is handed out.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct InputClaims {
+    /// Claim of each consumer, in [`InputConsumer::CLAIMING`] order
+    claims: [Claim; InputConsumer::CLAIMING.len()],
+}
+
+impl InputClaims {
+    /// Creates a set of claims where nobody claims anything.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets what one consumer claims.
+    ///
+    /// # Arguments
+    /// * `consumer` - The consumer; setting a claim for gameplay does nothing
+    /// * `claim` - What it claims
+    pub fn with(mut self, consumer: InputConsumer, claim: Claim) -> Self {
+        if let Some(index) = Self::index(consumer) {
+            self.claims[index] = claim;
+        }
+        self
+    }
+
+    /// Returns the consumer that gets an event of the given kind to itself.
+    ///
+    /// # Arguments
+    /// * `kind` - What the event is aimed at
+    ///
+    /// # Returns
+    /// The highest priority consumer claiming it, or `None` if nobody does and it goes to
+    /// everyone
+    pub fn claimant(&self, kind: InputKind) -> Option<InputConsumer> {
+        InputConsumer::CLAIMING
+            .into_iter()
+            .zip(self.claims)
+            .find(|(_, claim)| claim.covers(kind))
+            .map(|(consumer, _)| consumer)
+    }
+
+    /// Returns whether a consumer should be handed an event of the given kind.
+    ///
+    /// # Arguments
+    /// * `consumer` - The consumer asking
+    /// * `kind` - What the event is aimed at
+    pub fn receives(&self, consumer: InputConsumer, kind: InputKind) -> bool {
+        self.claimant(kind)
+            .is_none_or(|claimant| claimant == consumer)
+    }
+
+    /// Returns whether the cursor has to stay released, because a consumer that is clicked
+    /// with it holds the pointer.
+    pub fn keeps_cursor_free(&self) -> bool {
+        self.claimant(InputKind::Pointer)
+            .is_some_and(InputConsumer::needs_cursor)
+    }
+
+    /// Finds where a consumer's claim is kept.
+    fn index(consumer: InputConsumer) -> Option<usize> {
+        InputConsumer::CLAIMING
+            .iter()
+            .position(|&claiming| claiming == consumer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const POINTER: Claim = Claim {
+        pointer: true,
+        keyboard: false,
+    };
+    const KEYBOARD: Claim = Claim {
+        pointer: false,
+        keyboard: true,
+    };
+    const BOTH: Claim = Claim {
+        pointer: true,
+        keyboard: true,
+    };
+
+    /// Every consumer an event of the given kind is handed to.
+    fn receivers(claims: &InputClaims, kind: InputKind) -> Vec<InputConsumer> {
+        InputConsumer::CLAIMING
+            .into_iter()
+            .chain([InputConsumer::Gameplay])
+            .filter(|&consumer| claims.receives(consumer, kind))
+            .collect()
+    }
+
+    #[test]
+    fn test_overlapping_consumers_get_each_event_once() {
+        // The tuning panel sits over a pause menu button, with the search field open
+        let claims = InputClaims::new()
+            .with(InputConsumer::TuningPanel, POINTER)
+            .with(InputConsumer::Buttons, POINTER)
+            .with(InputConsumer::TextEntry, KEYBOARD);
+
+        assert_eq!(
+            receivers(&claims, InputKind::Pointer),
+            vec![InputConsumer::TuningPanel]
+        );
+        assert_eq!(
+            receivers(&claims, InputKind::Keyboard),
+            vec![InputConsumer::TextEntry]
+        );
+
+        // Moving the panel off the button hands its clicks back to the button alone
+        let claims = claims.with(InputConsumer::TuningPanel, Claim::default());
+        assert_eq!(
+            receivers(&claims, InputKind::Pointer),
+            vec![InputConsumer::Buttons]
+        );
+    }
+
+    #[test]
+    fn test_gameplay_never_receives_claimed_clicks() {
+        let everyone = || {
+            InputConsumer::CLAIMING
+                .into_iter()
+                .fold(InputClaims::new(), |claims, consumer| {
+                    claims.with(consumer, BOTH)
+                })
+        };
+        // Drop claims from the top one at a time; someone always outranks gameplay
+        for skipped in 0..InputConsumer::CLAIMING.len() {
+            let claims = InputConsumer::CLAIMING[..skipped]
+                .iter()
+                .fold(everyone(), |claims, &consumer| {
+                    claims.with(consumer, Claim::default())
+                });
+            let expected = InputConsumer::CLAIMING[skipped];
+            for kind in [InputKind::Pointer, InputKind::Keyboard] {
+                assert_eq!(claims.claimant(kind), Some(expected));
+                assert_eq!(receivers(&claims, kind), vec![expected]);
+                assert!(!claims.receives(InputConsumer::Gameplay, kind));
+            }
+        }
+
+        // With no claims at all, every consumer and gameplay get the click
+        let claims = InputClaims::new();
+        assert_eq!(claims.claimant(InputKind::Pointer), None);
+        assert!(claims.receives(InputConsumer::Gameplay, InputKind::Pointer));
+        assert_eq!(receivers(&claims, InputKind::Pointer).len(), 6);
+    }
+
+    #[test]
+    fn test_state_events_and_the_cursor() {
+        // Cursor moves and resizes reach everyone however much is claimed
+        let claims = InputClaims::new().with(InputConsumer::TuningPanel, BOTH);
+        assert_eq!(receivers(&claims, InputKind::Broadcast).len(), 6);
+        assert!(claims.keeps_cursor_free());
+
+        // The radial steers with the captured mouse, so its claim leaves the cursor locked
+        let claims = InputClaims::new()
+            .with(InputConsumer::QuickRadial, POINTER)
+            .with(InputConsumer::Buttons, POINTER);
+        assert_eq!(
+            claims.claimant(InputKind::Pointer),
+            Some(InputConsumer::QuickRadial)
+        );
+        assert!(!claims.keeps_cursor_free());
+        assert!(!InputClaims::new().keeps_cursor_free());
+
+        // Gameplay can't claim anything
+        let claims = InputClaims::new().with(InputConsumer::Gameplay, BOTH);
+        assert_eq!(claims, InputClaims::new());
+    }
+}
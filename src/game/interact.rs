@@ -0,0 +1,216 @@
+//! Interactable objects for the Mirador game.
+//!
+//! Anything the player can use by looking at it and pressing the interact key — keys, levers and
+//! the like — registers an [`Interactable`] on the game state. Once per frame the player's view
+//! ray is tested against the registry with [`find_target`]: the closest interactable whose
+//! bounding sphere the ray passes through, within the player's reach and not hidden behind a
+//! wall, becomes the target. The reticle expands and shows the target's prompt while there is one.
+//!
+//! # Usage
+//!
+//! ```rust,ignore
+//! game_state.interactables.push(Interactable::new([120.0, 30.0, -40.0], "Pick up key"));
+//!
+//! // Once per frame
+//! game_state.update_interaction_target();
+//! if let Some(target) = game_state.interaction_target() {
+//!     println!("{}", target.hint());
+//! }
+//! ```
+
+/// Farthest an interactable can be from the player's eye and still be used by default, in
+/// world units. See [`crate::game::dimensions::PlayerDimensions::reach`].
+///
+/// About one maze cell, so the player has to walk up to things rather than use them from
+/// down a corridor.
+pub const INTERACT_RANGE: f32 = 70.0;
+
+/// Radius of an interactable's bounding sphere unless it sets its own.
+pub const DEFAULT_INTERACT_RADIUS: f32 = 12.0;
+
+/// Key shown in interaction hints.
+pub const INTERACT_KEY_LABEL: &str = "E";
+
+/// Something in the maze the player can interact with.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Interactable {
+    /// World position of the center of the object
+    pub position: [f32; 3],
+    /// Radius of the sphere the view ray has to pass through
+    pub radius: f32,
+    /// What interacting does, shown after the key in the hint (e.g. "Pick up key")
+    pub prompt: String,
+}
+
+impl Interactable {
+    /// Creates an interactable with the default radius.
+    ///
+    /// # Arguments
+    /// * `position` - World position of the center of the object
+    /// * `prompt` - What interacting does, e.g. "Pick up key"
+    pub fn new(position: [f32; 3], prompt: &str) -> Self {
+        Self {
+            position,
+            radius: DEFAULT_INTERACT_RADIUS,
+            prompt: prompt.to_string(),
+        }
+    }
+
+    /// Returns the hint line shown below the reticle, e.g. "E — Pick up key".
+    pub fn hint(&self) -> String {
+        format!("{} — {}", INTERACT_KEY_LABEL, self.prompt)
+    }
+}
+
+/// Finds how far along a ray it first touches a sphere.
+///
+/// # Arguments
+/// * `origin` - Start of the ray
+/// * `direction` - Unit direction of the ray
+/// * `center` - Center of the sphere
+/// * `radius` - Radius of the sphere
+///
+/// # Returns
+/// The distance along the ray to the sphere, `0.0` if the ray starts inside it, or `None` if
+/// the ray misses it or the sphere is behind the origin
+pub fn ray_sphere_distance(
+    origin: [f32; 3],
+    direction: [f32; 3],
+    center: [f32; 3],
+    radius: f32,
+) -> Option<f32> {
+    let to_center = [
+        center[0] - origin[0],
+        center[1] - origin[1],
+        center[2] - origin[2],
+    ];
+    let along =
+        to_center[0] * direction[0] + to_center[1] * direction[1] + to_center[2] * direction[2];
+    let distance_squared = to_center.iter().map(|d| d * d).sum::<f32>();
+    let radius_squared = radius * radius;
+    if distance_squared <= radius_squared {
+        return Some(0.0);
+    }
+    if along < 0.0 {
+        return None;
+    }
+
+    let miss_squared = distance_squared - along * along;
+    if miss_squared > radius_squared {
+        return None;
+    }
+    Some(along - (radius_squared - miss_squared).sqrt())
+}
+
+/// Picks the interactable the player is looking at.
+///
+/// # Arguments
+/// * `eye` - World position of the player's eye
+/// * `direction` - Unit direction the player is looking in
+/// * `interactables` - Every interactable in the level
+/// * `reach` - Farthest an interactable can be from the eye, usually [`INTERACT_RANGE`]
+/// * `line_intersects_geometry` - Checks whether a wall blocks the line between two points
+///
+/// # Returns
+/// The index of the closest interactable the view ray hits within `reach` with nothing in
+/// the way, or `None`
+pub fn find_target<F>(
+    eye: [f32; 3],
+    direction: [f32; 3],
+    interactables: &[Interactable],
+    reach: f32,
+    line_intersects_geometry: F,
+) -> Option<usize>
+where
+    F: Fn([f32; 3], [f32; 3]) -> bool,
+{
+    interactables
+        .iter()
+        .enumerate()
+        .filter_map(|(index, interactable)| {
+            ray_sphere_distance(eye, direction, interactable.position, interactable.radius)
+                .filter(|distance| *distance <= reach)
+                .map(|distance| (index, distance))
+        })
+        .filter(|(index, _)| !line_intersects_geometry(eye, interactables[*index].position))
+        .min_by(|(_, a), (_, b)| a.total_cmp(b))
+        .map(|(index, _)| index)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ray_sphere_distance() {
+        let forward = [0.0, 0.0, -1.0];
+        let distance = ray_sphere_distance([0.0; 3], forward, [0.0, 0.0, -50.0], 10.0);
+        assert_eq!(distance, Some(40.0));
+
+        // Off to the side, behind, and from inside
+        assert_eq!(
+            ray_sphere_distance([0.0; 3], forward, [30.0, 0.0, -50.0], 10.0),
+            None
+        );
+        assert_eq!(
+            ray_sphere_distance([0.0; 3], forward, [0.0, 0.0, 50.0], 10.0),
+            None
+        );
+        assert_eq!(
+            ray_sphere_distance([0.0; 3], forward, [0.0, 0.0, 5.0], 10.0),
+            Some(0.0)
+        );
+    }
+
+    #[test]
+    fn test_find_target_prefers_closest_visible_in_range() {
+        let forward = [0.0, 0.0, -1.0];
+        let interactables = vec![
+            Interactable::new([0.0, 0.0, -60.0], "Pull lever"),
+            Interactable::new([0.0, 0.0, -30.0], "Pick up key"),
+            Interactable::new([0.0, 0.0, -200.0], "Too far"),
+        ];
+
+        let target = find_target([0.0; 3], forward, &interactables, INTERACT_RANGE, |_, _| {
+            false
+        });
+        assert_eq!(target, Some(1));
+        assert_eq!(interactables[1].hint(), "E — Pick up key");
+
+        // With only the key hidden behind a wall, the lever is the closest visible one
+        let key_hidden = |_: [f32; 3], to: [f32; 3]| to[2] > -40.0;
+        assert_eq!(
+            find_target(
+                [0.0; 3],
+                forward,
+                &interactables,
+                INTERACT_RANGE,
+                key_hidden
+            ),
+            Some(0)
+        );
+
+        // The far one is only in reach of a longer arm
+        let far = &interactables[2..];
+        assert_eq!(
+            find_target([0.0; 3], forward, far, INTERACT_RANGE, |_, _| false),
+            None
+        );
+        assert_eq!(
+            find_target([0.0; 3], forward, far, 250.0, |_, _| false),
+            Some(0)
+        );
+
+        // Looking the other way finds nothing
+        assert_eq!(
+            find_target(
+                [0.0; 3],
+                [0.0, 0.0, 1.0],
+                &interactables,
+                INTERACT_RANGE,
+                |_, _| false
+            ),
+            None
+        );
+    }
+}
@@ -0,0 +1,782 @@
+//! Session journal for post-mortem debugging.
+//!
+//! The journal is an append-only, in-memory ring of timestamped [`JournalEvent`]s describing what
+//! happened during a session: screen transitions, timer starts and expiries, level loads with
+//! their plan and maze seed, upgrade picks, enemy state changes, and errors returned by the renderer.
+//! Recording an event only stores the enum and its fields; nothing is formatted or written to disk
+//! until the journal is flushed.
+//!
+//! The journal is flushed to a new file in the data directory's journal folder (see
+//! [`crate::paths`]) on game over, on panic, and when the process is interrupted. Only the newest
+//! [`MAX_JOURNAL_FILES`] files are kept. Run the game with `--journal <file>` to pretty-print a
+//! saved journal.
+//!
+//! There is one journal per process so the panic hook and the Ctrl-C handler can reach it;
+//! [`session`] returns a cheap handle to it, and [`GameState`](crate::game::GameState) keeps one
+//! so events can be recorded from anywhere in the game loop.
+//!
+//! # Usage
+//!
+//! ```rust,ignore
+//! let journal = journal::session();
+//! journal.record(JournalEvent::TimerExpired);
+//! journal.record_error("text rendering", &err);
+//!
+//! if let Ok(path) = journal.flush(FlushReason::GameOver) {
+//!     println!("Journal written to {}", path.display());
+//! }
+//! ```
+
+use crate::game::CurrentScreen;
+use crate::game::audio_debug::AudioDebugSnapshot;
+use crate::game::director::EnemyTuning;
+use crate::game::levels::LevelPlan;
+use crate::game::maze::generator::Cell;
+use crate::game::wall_clip::ClipReport;
+use crate::paths;
+use chrono::Local;
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::collections::VecDeque;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+/// Number of events kept in memory; the oldest are dropped first.
+pub const JOURNAL_CAPACITY: usize = 4096;
+
+/// Number of journal files kept in the journal folder; older files are deleted on flush.
+pub const MAX_JOURNAL_FILES: usize = 10;
+
+lazy_static::lazy_static! {
+    /// The journal shared by the whole process.
+    static ref SESSION_JOURNAL: JournalHandle = JournalHandle::new(JOURNAL_CAPACITY);
+}
+
+/// Returns a handle to the process-wide session journal.
+pub fn session() -> JournalHandle {
+    SESSION_JOURNAL.clone()
+}
+
+/// Something that happened during the session.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind")]
+pub enum JournalEvent {
+    /// The player started a new run from level 1
+    RunStarted,
+    /// The session switched to a player profile, at startup or from the title screen
+    ProfileSelected {
+        /// Name of the profile
+        name: String,
+    },
+    /// The game moved from one screen to another
+    ScreenChanged {
+        /// Screen being left
+        from: CurrentScreen,
+        /// Screen being shown
+        to: CurrentScreen,
+    },
+    /// The level timer started counting down
+    TimerStarted {
+        /// Length of the timer in seconds
+        seconds: f32,
+    },
+    /// The level timer reached zero
+    TimerExpired,
+    /// A level was planned, before its maze was generated
+    LevelPlanned {
+        /// Every setting the level is played with
+        plan: LevelPlan,
+    },
+    /// A generated maze finished loading
+    LevelLoaded {
+        /// Level number
+        level: i32,
+        /// Seed the maze layout was shuffled with
+        seed: u64,
+        /// Maze width in cells
+        width: usize,
+        /// Maze height in cells
+        height: usize,
+    },
+    /// The current level was restarted on the same maze
+    LevelRetried {
+        /// Level number
+        level: i32,
+    },
+    /// The player picked an upgrade
+    UpgradePicked {
+        /// Name of the upgrade
+        name: String,
+    },
+    /// The enemy was locked in place or released
+    EnemyLockChanged {
+        /// Whether the enemy is now locked
+        locked: bool,
+    },
+    /// The adaptive difficulty director retuned the enemy for the next level
+    EnemyTuned {
+        /// Director pressure after the level, from -1 (easiest) to 1 (hardest)
+        pressure: f32,
+        /// Multipliers applied to the next level's enemy
+        tuning: EnemyTuning,
+    },
+    /// The enemy caught the player
+    EnemyReachedPlayer,
+    /// The level's enemy was scheduled to arrive partway through
+    EnemySpawnScheduled {
+        /// Seconds of play into the level the enemy arrives
+        spawn_at: f32,
+        /// Cell of the wall grid it arrives in
+        cell: Cell,
+    },
+    /// The delayed enemy arrived in the maze
+    EnemySpawned {
+        /// Cell of the wall grid it arrived in, after the fairness check
+        cell: Cell,
+    },
+    /// The player reached the exit before the delayed enemy arrived
+    EnemySpawnCancelled,
+    /// Glyphs were rasterized ahead of time while a loading screen was shown
+    GlyphsWarmed {
+        /// Renderer and style of each set of glyphs rasterized
+        warmed: Vec<String>,
+        /// Styles left for first use because loading finished or the budget ran out
+        skipped: Vec<String>,
+        /// Milliseconds spent rasterizing
+        millis: u64,
+    },
+    /// Text was drawn in a style whose glyphs weren't warmed, rasterizing them mid-frame
+    ColdGlyphs {
+        /// The text renderer that drew it
+        renderer: String,
+        /// ID of the text buffer drawn
+        buffer: String,
+        /// Font family and drawn size
+        style: String,
+    },
+    /// The player was found inside a wall and moved out of it
+    WallClipped {
+        /// Where it happened and the history leading up to it
+        report: ClipReport,
+    },
+    /// What the audio manager was playing, dumped from the audio debug overlay
+    AudioSnapshot {
+        /// Every source and the recent audio events
+        snapshot: AudioDebugSnapshot,
+    },
+    /// A recoverable error was reported and the game carried on
+    Error {
+        /// What the game was doing when the error occurred
+        context: Cow<'static, str>,
+        /// The error message
+        message: String,
+    },
+    /// The process panicked
+    Panic {
+        /// The panic message and location
+        message: String,
+    },
+}
+
+impl fmt::Display for JournalEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            JournalEvent::RunStarted => write!(f, "Run started"),
+            JournalEvent::ProfileSelected { name } => write!(f, "Profile: {}", name),
+            JournalEvent::ScreenChanged { from, to } => write!(f, "Screen {:?} -> {:?}", from, to),
+            JournalEvent::TimerStarted { seconds } => write!(f, "Timer started ({:.1}s)", seconds),
+            JournalEvent::TimerExpired => write!(f, "Timer expired"),
+            JournalEvent::LevelPlanned { plan } => write!(f, "{}", plan),
+            JournalEvent::LevelLoaded {
+                level,
+                seed,
+                width,
+                height,
+            } => write!(
+                f,
+                "Level {} loaded ({}x{}, seed {})",
+                level, width, height, seed
+            ),
+            JournalEvent::LevelRetried { level } => write!(f, "Level {} retried", level),
+            JournalEvent::UpgradePicked { name } => write!(f, "Upgrade picked: {}", name),
+            JournalEvent::EnemyLockChanged { locked } => {
+                write!(f, "Enemy {}", if *locked { "locked" } else { "released" })
+            }
+            JournalEvent::EnemyTuned { pressure, tuning } => write!(
+                f,
+                "Enemy tuned: pressure {:+.2}, speed x{:.2}, alert x{:.2}, replan x{:.2}",
+                pressure,
+                tuning.speed_multiplier,
+                tuning.alert_radius_multiplier,
+                tuning.replan_multiplier
+            ),
+            JournalEvent::EnemyReachedPlayer => write!(f, "Enemy reached the player"),
+            JournalEvent::EnemySpawnScheduled { spawn_at, cell } => write!(
+                f,
+                "Enemy spawn scheduled at {:.1}s in cell ({}, {})",
+                spawn_at, cell.row, cell.col
+            ),
+            JournalEvent::EnemySpawned { cell } => {
+                write!(f, "Enemy spawned in cell ({}, {})", cell.row, cell.col)
+            }
+            JournalEvent::EnemySpawnCancelled => write!(f, "Enemy spawn cancelled"),
+            JournalEvent::GlyphsWarmed {
+                warmed,
+                skipped,
+                millis,
+            } => write!(
+                f,
+                "Warmed {} text styles in {} ms ({} skipped)",
+                warmed.len(),
+                millis,
+                skipped.len()
+            ),
+            JournalEvent::ColdGlyphs {
+                renderer,
+                buffer,
+                style,
+            } => write!(
+                f,
+                "Cold glyphs: {} '{}' rasterized {} on first use",
+                renderer, buffer, style
+            ),
+            JournalEvent::WallClipped { report } => write!(
+                f,
+                "Player inside wall ({}, {}) at {:?}, moved to {:?}",
+                report.wall.row, report.wall.col, report.position, report.resolved
+            ),
+            JournalEvent::AudioSnapshot { snapshot } => write!(f, "Audio snapshot: {}", snapshot),
+            JournalEvent::Error { context, message } => {
+                write!(f, "ERROR in {}: {}", context, message)
+            }
+            JournalEvent::Panic { message } => write!(f, "PANIC: {}", message),
+        }
+    }
+}
+
+/// A journal event with the time it was recorded.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JournalEntry {
+    /// Milliseconds since the journal was created
+    pub elapsed_ms: u64,
+    /// What happened
+    pub event: JournalEvent,
+}
+
+impl fmt::Display for JournalEntry {
+    /// Formats the entry as `[mm:ss.mmm] event`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let minutes = self.elapsed_ms / 60_000;
+        let seconds = (self.elapsed_ms / 1000) % 60;
+        let millis = self.elapsed_ms % 1000;
+        write!(
+            f,
+            "[{:02}:{:02}.{:03}] {}",
+            minutes, seconds, millis, self.event
+        )
+    }
+}
+
+/// Why a journal was written to disk.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FlushReason {
+    /// The player lost a level
+    GameOver,
+    /// The process panicked
+    Panic,
+    /// The process received Ctrl-C
+    Interrupted,
+}
+
+/// The contents of a journal file.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct JournalFile {
+    /// Why the journal was written
+    pub reason: FlushReason,
+    /// Local time the file was written, RFC 3339
+    pub written_at: String,
+    /// Profile the session was playing as when the file was written
+    #[serde(default)]
+    pub profile: Option<String>,
+    /// Events dropped from the front of the ring before the flush
+    pub dropped_events: usize,
+    /// Recorded events, oldest first
+    pub entries: Vec<JournalEntry>,
+}
+
+/// Append-only ring of journal events.
+#[derive(Debug)]
+pub struct Journal {
+    /// When the journal was created
+    started: Instant,
+    /// Recorded events, oldest first
+    entries: VecDeque<JournalEntry>,
+    /// Maximum number of events kept
+    capacity: usize,
+    /// Number of events dropped to stay within `capacity`
+    dropped: usize,
+    /// Errors recorded since the current run started
+    run_errors: usize,
+    /// Last screen passed to [`Journal::observe_screen`]
+    last_screen: Option<CurrentScreen>,
+    /// File written by the most recent flush
+    last_path: Option<PathBuf>,
+    /// Profile passed to [`Journal::select_profile`] last
+    profile: Option<String>,
+}
+
+impl Journal {
+    /// Creates an empty journal.
+    ///
+    /// # Arguments
+    /// * `capacity` - Maximum number of events kept in memory
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            started: Instant::now(),
+            entries: VecDeque::with_capacity(capacity),
+            capacity: capacity.max(1),
+            dropped: 0,
+            run_errors: 0,
+            last_screen: None,
+            last_path: None,
+            profile: None,
+        }
+    }
+
+    /// Appends an event, dropping the oldest one if the ring is full.
+    ///
+    /// # Arguments
+    /// * `event` - The event to record
+    pub fn record(&mut self, event: JournalEvent) {
+        if matches!(
+            event,
+            JournalEvent::Error { .. } | JournalEvent::Panic { .. }
+        ) {
+            self.run_errors += 1;
+        }
+        if self.entries.len() == self.capacity {
+            self.entries.pop_front();
+            self.dropped += 1;
+        }
+        self.entries.push_back(JournalEntry {
+            elapsed_ms: self.started.elapsed().as_millis() as u64,
+            event,
+        });
+    }
+
+    /// Records the start of a new run and forgets errors from the previous one.
+    pub fn begin_run(&mut self) {
+        self.run_errors = 0;
+        self.record(JournalEvent::RunStarted);
+    }
+
+    /// Records the profile the session is playing as, which later flushes name in their
+    /// header.
+    ///
+    /// # Arguments
+    /// * `name` - Name of the profile
+    pub fn select_profile(&mut self, name: &str) {
+        self.profile = Some(name.to_string());
+        self.record(JournalEvent::ProfileSelected {
+            name: name.to_string(),
+        });
+    }
+
+    /// Records a screen transition if `screen` differs from the last observed screen.
+    ///
+    /// # Arguments
+    /// * `screen` - The screen currently being shown
+    ///
+    /// # Returns
+    /// `true` if the screen changed since the last call
+    pub fn observe_screen(&mut self, screen: CurrentScreen) -> bool {
+        let previous = self.last_screen.replace(screen);
+        match previous {
+            Some(from) if from != screen => {
+                self.record(JournalEvent::ScreenChanged { from, to: screen });
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Returns the recorded events, oldest first.
+    #[cfg(test)]
+    pub fn entries(&self) -> impl Iterator<Item = &JournalEntry> {
+        self.entries.iter()
+    }
+
+    /// Returns the newest events, oldest first.
+    ///
+    /// # Arguments
+    /// * `count` - Most events to return
+    pub fn tail(&self, count: usize) -> impl Iterator<Item = &JournalEntry> {
+        self.entries
+            .iter()
+            .skip(self.entries.len().saturating_sub(count))
+    }
+
+    /// Returns whether an error or panic was recorded since the current run started.
+    pub fn had_run_errors(&self) -> bool {
+        self.run_errors > 0
+    }
+
+    /// Returns the file written by the most recent flush, if any.
+    #[cfg(test)]
+    pub fn last_path(&self) -> Option<&Path> {
+        self.last_path.as_deref()
+    }
+
+    /// Writes the journal to a new file in `dir` and deletes the oldest files beyond
+    /// [`MAX_JOURNAL_FILES`].
+    ///
+    /// The in-memory events are kept, so later flushes contain the whole session.
+    ///
+    /// # Arguments
+    /// * `dir` - Directory to write the journal file to
+    /// * `reason` - Why the journal is being written
+    ///
+    /// # Returns
+    /// The path of the new journal file, or an error message if it could not be written
+    pub fn flush_to(&mut self, dir: &Path, reason: FlushReason) -> Result<PathBuf, String> {
+        fs::create_dir_all(dir)
+            .map_err(|e| format!("Failed to create journal directory: {}", e))?;
+
+        let now = Local::now();
+        let file = JournalFile {
+            reason,
+            written_at: now.to_rfc3339(),
+            profile: self.profile.clone(),
+            dropped_events: self.dropped,
+            entries: self.entries.iter().cloned().collect(),
+        };
+        let contents = serde_json::to_string_pretty(&file)
+            .map_err(|e| format!("Failed to serialize journal: {}", e))?;
+
+        // Timestamped names sort oldest first, which rotation relies on
+        let mut path = dir.join(format!("journal-{}.json", now.format("%Y%m%d-%H%M%S")));
+        let mut suffix = 1;
+        while path.exists() {
+            path = dir.join(format!(
+                "journal-{}-{}.json",
+                now.format("%Y%m%d-%H%M%S"),
+                suffix
+            ));
+            suffix += 1;
+        }
+        fs::write(&path, contents)
+            .map_err(|e| format!("Failed to write journal to {}: {}", path.display(), e))?;
+
+        rotate_journals(dir, MAX_JOURNAL_FILES);
+        self.last_path = Some(path.clone());
+        Ok(path)
+    }
+}
+
+/// Deletes the oldest journal files in `dir` so at most `keep` remain.
+///
+/// # Arguments
+/// * `dir` - Directory holding the journal files
+/// * `keep` - Number of files to keep
+fn rotate_journals(dir: &Path, keep: usize) {
+    let Ok(read_dir) = fs::read_dir(dir) else {
+        return;
+    };
+    let mut journals: Vec<PathBuf> = read_dir
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| {
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| name.starts_with("journal-") && name.ends_with(".json"))
+        })
+        .collect();
+    journals.sort();
+
+    let excess = journals.len().saturating_sub(keep);
+    for old in &journals[..excess] {
+        if let Err(e) = fs::remove_file(old) {
+            eprintln!("Failed to remove old journal {}: {}", old.display(), e);
+        }
+    }
+}
+
+/// Cheap, cloneable handle to a shared [`Journal`].
+#[derive(Debug, Clone)]
+pub struct JournalHandle {
+    /// The shared journal
+    journal: Arc<Mutex<Journal>>,
+}
+
+impl JournalHandle {
+    /// Creates a handle to a new, empty journal.
+    ///
+    /// # Arguments
+    /// * `capacity` - Maximum number of events kept in memory
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            journal: Arc::new(Mutex::new(Journal::new(capacity))),
+        }
+    }
+
+    /// Appends an event to the journal.
+    ///
+    /// # Arguments
+    /// * `event` - The event to record
+    pub fn record(&self, event: JournalEvent) {
+        if let Ok(mut journal) = self.journal.lock() {
+            journal.record(event);
+        }
+    }
+
+    /// Records a recoverable error.
+    ///
+    /// # Arguments
+    /// * `context` - What the game was doing, e.g. `"text rendering"`
+    /// * `error` - The error that was reported
+    pub fn record_error(&self, context: &'static str, error: &dyn fmt::Display) {
+        self.record(JournalEvent::Error {
+            context: Cow::Borrowed(context),
+            message: error.to_string(),
+        });
+    }
+
+    /// Records the start of a new run. See [`Journal::begin_run`].
+    pub fn begin_run(&self) {
+        if let Ok(mut journal) = self.journal.lock() {
+            journal.begin_run();
+        }
+    }
+
+    /// Records the profile the session is playing as. See [`Journal::select_profile`].
+    ///
+    /// # Arguments
+    /// * `name` - Name of the profile
+    pub fn select_profile(&self, name: &str) {
+        if let Ok(mut journal) = self.journal.lock() {
+            journal.select_profile(name);
+        }
+    }
+
+    /// Records a screen transition if the screen changed. See [`Journal::observe_screen`].
+    ///
+    /// # Arguments
+    /// * `screen` - The screen currently being shown
+    ///
+    /// # Returns
+    /// `true` if the screen changed since the last call
+    pub fn observe_screen(&self, screen: CurrentScreen) -> bool {
+        self.journal
+            .lock()
+            .is_ok_and(|mut journal| journal.observe_screen(screen))
+    }
+
+    /// Returns copies of the newest events, oldest first. See [`Journal::tail`].
+    ///
+    /// # Arguments
+    /// * `count` - Most events to return
+    pub fn tail(&self, count: usize) -> Vec<JournalEntry> {
+        self.journal
+            .lock()
+            .map(|journal| journal.tail(count).cloned().collect())
+            .unwrap_or_default()
+    }
+
+    /// Returns whether an error was recorded since the current run started.
+    pub fn had_run_errors(&self) -> bool {
+        self.journal
+            .lock()
+            .is_ok_and(|journal| journal.had_run_errors())
+    }
+
+    /// Writes the journal to a new file in the data directory's journal folder.
+    ///
+    /// # Arguments
+    /// * `reason` - Why the journal is being written
+    ///
+    /// # Returns
+    /// The path of the new journal file, or an error message if it could not be written
+    pub fn flush(&self, reason: FlushReason) -> Result<PathBuf, String> {
+        let mut journal = self
+            .journal
+            .lock()
+            .map_err(|_| "Journal lock was poisoned".to_string())?;
+        journal.flush_to(&paths::get().journal_dir(), reason)
+    }
+
+    /// Records a panic and flushes the journal without waiting on its lock.
+    ///
+    /// The panicking thread may already hold the lock, so this gives up instead of deadlocking.
+    ///
+    /// # Arguments
+    /// * `message` - The panic message and location
+    ///
+    /// # Returns
+    /// The path of the new journal file, or an error message if it could not be written
+    pub fn flush_after_panic(&self, message: String) -> Result<PathBuf, String> {
+        let mut journal = self
+            .journal
+            .try_lock()
+            .map_err(|_| "Journal was busy when the panic happened".to_string())?;
+        journal.record(JournalEvent::Panic { message });
+        journal.flush_to(&paths::get().journal_dir(), FlushReason::Panic)
+    }
+}
+
+/// Installs a panic hook that flushes the session journal before the default hook runs.
+pub fn install_panic_hook() {
+    let default_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        match session().flush_after_panic(info.to_string()) {
+            Ok(path) => eprintln!("Session journal written to {}", path.display()),
+            Err(e) => eprintln!("Failed to write session journal: {}", e),
+        }
+        default_hook(info);
+    }));
+}
+
+/// Reads a journal file and formats it for reading.
+///
+/// # Arguments
+/// * `path` - The journal file to read
+///
+/// # Returns
+/// One line per event with its timestamp, or an error message if the file could not be read
+pub fn pretty_print(path: &Path) -> Result<String, String> {
+    let contents = fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read journal {}: {}", path.display(), e))?;
+    let file: JournalFile = serde_json::from_str(&contents)
+        .map_err(|e| format!("Failed to parse journal {}: {}", path.display(), e))?;
+    Ok(format_journal(&file))
+}
+
+/// Formats a journal for reading.
+///
+/// # Arguments
+/// * `file` - The parsed journal
+///
+/// # Returns
+/// A header line followed by one `[mm:ss.mmm] event` line per event
+pub fn format_journal(file: &JournalFile) -> String {
+    let mut output = format!(
+        "Journal written {} ({:?}), {} events",
+        file.written_at,
+        file.reason,
+        file.entries.len()
+    );
+    if let Some(profile) = &file.profile {
+        output.push_str(&format!(", profile {}", profile));
+    }
+    if file.dropped_events > 0 {
+        output.push_str(&format!(", {} older events dropped", file.dropped_events));
+    }
+    for entry in &file.entries {
+        output.push_str(&format!("\n{}", entry));
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::levels::{self, Difficulty, DifficultyCurve};
+
+    #[test]
+    fn test_ring_drops_oldest_and_tracks_run_errors() {
+        let mut journal = Journal::new(3);
+        journal.record(JournalEvent::TimerStarted { seconds: 30.0 });
+        journal.record(JournalEvent::Error {
+            context: Cow::Borrowed("text rendering"),
+            message: "atlas full".to_string(),
+        });
+        assert!(journal.had_run_errors());
+
+        assert!(!journal.observe_screen(CurrentScreen::Game));
+        assert!(journal.observe_screen(CurrentScreen::GameOver));
+        assert!(!journal.observe_screen(CurrentScreen::GameOver));
+        journal.begin_run();
+        assert!(!journal.had_run_errors());
+
+        let events: Vec<_> = journal.entries().map(|entry| &entry.event).collect();
+        assert_eq!(
+            events,
+            [
+                &JournalEvent::Error {
+                    context: Cow::Borrowed("text rendering"),
+                    message: "atlas full".to_string(),
+                },
+                &JournalEvent::ScreenChanged {
+                    from: CurrentScreen::Game,
+                    to: CurrentScreen::GameOver,
+                },
+                &JournalEvent::RunStarted,
+            ]
+        );
+        assert_eq!(journal.dropped, 1);
+
+        let tail: Vec<_> = journal.tail(2).map(|entry| &entry.event).collect();
+        assert_eq!(tail, events[1..]);
+        assert_eq!(journal.tail(10).count(), 3);
+        assert_eq!(journal.tail(0).count(), 0);
+    }
+
+    #[test]
+    fn test_flush_round_trips_and_rotates() {
+        let dir = std::env::temp_dir().join(format!("mirador-journal-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).expect("Failed to create test directory");
+        for i in 0..MAX_JOURNAL_FILES {
+            fs::write(
+                dir.join(format!("journal-00000000-0000{:02}.json", i)),
+                "{}",
+            )
+            .expect("Failed to write old journal");
+        }
+
+        let mut journal = Journal::new(16);
+        journal.select_profile("Robin");
+        journal.record(JournalEvent::LevelPlanned {
+            plan: levels::plan_level(
+                2,
+                Difficulty::Normal,
+                42,
+                EnemyTuning::NEUTRAL,
+                &DifficultyCurve::STANDARD,
+            ),
+        });
+        journal.record(JournalEvent::LevelLoaded {
+            level: 2,
+            seed: 42,
+            width: 25,
+            height: 25,
+        });
+        journal.record(JournalEvent::EnemySpawnScheduled {
+            spawn_at: 12.5,
+            cell: Cell::new(3, 7),
+        });
+        journal.record(JournalEvent::TimerExpired);
+        let path = journal
+            .flush_to(&dir, FlushReason::GameOver)
+            .expect("Failed to flush journal");
+        assert_eq!(journal.last_path(), Some(path.as_path()));
+
+        let remaining = fs::read_dir(&dir)
+            .expect("Failed to read directory")
+            .count();
+        assert_eq!(remaining, MAX_JOURNAL_FILES);
+        assert!(!dir.join("journal-00000000-000000.json").exists());
+
+        let printed = pretty_print(&path).expect("Failed to read journal back");
+        assert!(printed.contains("GameOver"));
+        assert!(printed.contains("profile Robin"));
+        assert!(printed.contains("Profile: Robin"));
+        assert!(printed.contains("Level 2 (Normal, seed 42): 27x27, 30s timer"));
+        assert!(printed.contains("Level 2 loaded (25x25, seed 42)"));
+        assert!(printed.contains("Enemy spawn scheduled at 12.5s in cell (3, 7)"));
+        assert!(printed.ends_with("Timer expired"));
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -1,17 +1,21 @@
 //! Keyboard and mouse input handling for the game.
 //!
 //! This module defines the [`GameKey`] enum for abstracting game actions from physical keys,
-//! and provides [`KeyState`] for tracking pressed keys and updating the [`GameState`] accordingly.
+//! and provides [`KeyState`] for tracking pressed keys and updating the [`GameState`] accordingly,
+//! together with the gamepad's [`PlayerInput`].
 //! It also includes utilities for mapping from winit key events to game actions.
 
+use crate::game::input::PlayerInput;
+use crate::game::player::Gait;
 use crate::game::{CurrentScreen, GameState};
+use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
-use winit::keyboard;
+use winit::keyboard::KeyCode;
 /// Enum representing all possible in-game actions that can be triggered by keyboard or mouse input.
 ///
 /// This abstraction allows the game logic to be decoupled from specific physical keys or buttons.
 /// Variants include movement, mouse buttons, toggles, and quitting.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum GameKey {
     /// Left mouse button.
     MouseButtonLeft,
@@ -27,20 +31,80 @@ pub enum GameKey {
     MoveRight,
     /// Sprint (Shift).
     Sprint,
+    /// Crouch-walk while held (Ctrl).
+    Crouch,
     /// Jump (Space).
     Jump,
-    /// Toggle UI sliders (C).
-    ToggleSliders,
+    /// Toggle the renderer tuning panel in debug builds (C).
+    ToggleTuningPanel,
     /// Quit the game (`).
     Quit,
     /// Escape key (toggle mouse capture).
     Escape,
-    /// Toggle Bounding Boxes (B).
+    /// Cycle the bounding box overlay: off, still, moving with breathing walls (B).
     ToggleBoundingBoxes,
+    /// Toggle the frame heatmap overlay in debug builds and test mode (H).
+    ToggleFrameHeatmap,
+    /// Save the frame heatmap as CSV in debug builds and test mode (F6).
+    ExportFrameHeatmap,
     /// Toggle Upgrade Menu (U).
     ToggleUpgradeMenu,
     /// Save Benchmark Results (F5).
     SaveBenchmark,
+    /// Toggle input latency measurement mode in debug builds (F7).
+    ToggleLatencyProbe,
+    /// Send a latency probe while measurement mode is on, in debug builds; otherwise open or
+    /// close the audio debug overlay in debug builds and test mode (F8).
+    LatencyProbe,
+    /// Write what the audio manager is playing to the run journal, in debug builds and test
+    /// mode (F9).
+    DumpAudioSnapshot,
+    /// Panic inside the next frame to exercise the error screen, in debug builds (F12).
+    DebugPanic,
+    /// Open or close the debug inspector window in debug builds and test mode (F11).
+    ToggleDebugWindow,
+    /// Hold for the overhead map view (Tab).
+    MapView,
+    /// Show or hide the minimap (M).
+    ToggleMinimap,
+    /// Interact with what the reticle is on (E).
+    Interact,
+    /// Hold for the quick-settings radial (V).
+    QuickRadial,
+}
+
+impl GameKey {
+    /// Returns the name shown for the action on the controls page.
+    pub fn label(self) -> &'static str {
+        match self {
+            GameKey::MouseButtonLeft => "Left Click",
+            GameKey::MouseButtonRight => "Right Click",
+            GameKey::MoveForward => "Move Forward",
+            GameKey::MoveBackward => "Move Back",
+            GameKey::MoveLeft => "Strafe Left",
+            GameKey::MoveRight => "Strafe Right",
+            GameKey::Sprint => "Sprint",
+            GameKey::Crouch => "Crouch",
+            GameKey::Jump => "Jump",
+            GameKey::ToggleTuningPanel => "Tuning Panel",
+            GameKey::Quit => "Quit",
+            GameKey::Escape => "Pause",
+            GameKey::ToggleBoundingBoxes => "Bounding Boxes",
+            GameKey::ToggleFrameHeatmap => "Frame Heatmap",
+            GameKey::ExportFrameHeatmap => "Export Heatmap",
+            GameKey::ToggleUpgradeMenu => "Upgrade Menu",
+            GameKey::SaveBenchmark => "Save Benchmark",
+            GameKey::ToggleLatencyProbe => "Latency Mode",
+            GameKey::LatencyProbe => "Latency Probe",
+            GameKey::DumpAudioSnapshot => "Audio Snapshot",
+            GameKey::DebugPanic => "Debug Panic",
+            GameKey::ToggleDebugWindow => "Debug Window",
+            GameKey::MapView => "Map View",
+            GameKey::ToggleMinimap => "Toggle Minimap",
+            GameKey::Interact => "Interact",
+            GameKey::QuickRadial => "Quick Settings",
+        }
+    }
 }
 
 /// Tracks the set of currently pressed game keys.
@@ -54,13 +118,6 @@ pub struct KeyState {
 }
 
 impl KeyState {
-    /// Creates a new, empty [`KeyState`]
-    pub fn new() -> Self {
-        Self {
-            pressed_keys: HashSet::new(),
-        }
-    }
-
     /// Marks a key as pressed.
     pub fn press_key(&mut self, key: GameKey) {
         self.pressed_keys.insert(key);
@@ -80,33 +137,58 @@ impl KeyState {
     ///
     /// This method removes all keys from the `pressed_keys` set, effectively
     /// resetting the input state to no keys being pressed.
-    pub fn _clear(&mut self) {
+    pub fn clear(&mut self) {
         self.pressed_keys.clear();
     }
 
-    /// Updates the [`GameState`] based on the current pressed keys.
+    /// Updates the [`GameState`] based on the current pressed keys and the gamepad.
     ///
-    /// - Handles movement, sprinting, jumping, toggling UI, and mouse capture.
-    /// - Adjusts player speed for sprinting.
-    /// - Moves the player according to pressed movement keys.
+    /// - Handles movement, sprinting, crouching, jumping, toggling UI, and mouse capture.
+    /// - Blends player speed toward the walking, sprinting, or crouching speed.
+    /// - Crouching only works during play, so it ends by itself when the exit cutscene
+    ///   or the capture sequence starts. The quick-settings crouch lock holds it without Ctrl.
+    /// - Moves the player according to the movement keys and the gamepad's stick together, or
+    ///   along the click-to-move walk, which any movement input cancels.
     /// - Handles mouse and escape key actions.
-    pub fn update(&mut self, game_state: &mut GameState) {
+    ///
+    /// # Arguments
+    /// * `game_state` - The game state to update
+    /// * `gamepad` - This frame's input from the active gamepad
+    pub fn update(&mut self, game_state: &mut GameState, gamepad: &PlayerInput) {
+        let input = PlayerInput::from_keys(self).merge(*gamepad);
         // Handle sprint speed changes
-        let is_sprinting = self.is_pressed(GameKey::Sprint) && game_state.player.stamina > 0.0;
-        let forward = self.is_pressed(GameKey::MoveForward);
-        let backward = self.is_pressed(GameKey::MoveBackward);
-        let left = self.is_pressed(GameKey::MoveLeft);
-        let right = self.is_pressed(GameKey::MoveRight);
-        let is_moving = forward || backward || left || right;
+        // The player is frozen while the overhead map view is up or the enemy has them
+        let can_move = !game_state.map_view.is_engaged() && game_state.capture.is_none();
+        let is_crouching = can_move
+            && game_state.current_screen == CurrentScreen::Game
+            && (self.is_pressed(GameKey::Crouch) || game_state.crouch_locked);
+        let is_sprinting =
+            can_move && !is_crouching && input.sprint && game_state.player.stamina > 0.0;
+        let manual = can_move && input.is_moving();
+        // Manual movement always takes over from click-to-move
+        if manual {
+            game_state.auto_walk.cancel();
+        }
+        let move_axis = if manual {
+            input.move_axis
+        } else if can_move && game_state.update_auto_walk() {
+            [0.0, 1.0]
+        } else {
+            [0.0, 0.0]
+        };
+        let is_moving = move_axis != [0.0, 0.0];
+        let gait = if is_crouching {
+            Gait::Crouch
+        } else if is_sprinting {
+            Gait::Sprint
+        } else {
+            Gait::Walk
+        };
+        game_state.player.update_gait(gait, game_state.delta_time);
         // Update stamina
         game_state
             .player
             .update_stamina(is_sprinting, is_moving, game_state.delta_time);
-        if is_sprinting {
-            game_state.player.speed = game_state.player.base_speed * 1.75;
-        } else {
-            game_state.player.speed = game_state.player.base_speed;
-        }
 
         if game_state.current_screen != CurrentScreen::Game {
             game_state
@@ -118,8 +200,8 @@ impl KeyState {
         if game_state.current_screen == CurrentScreen::Game
             || game_state.current_screen == CurrentScreen::ExitReached
         {
-            // Handle movement audio based on current state
-            if is_moving {
+            // Handle movement audio based on current state; crouch-walking is silent
+            if is_moving && !is_crouching {
                 if is_sprinting {
                     // Switch to sprint audio if not already sprinting
                     if !game_state.audio_manager.is_sprinting() {
@@ -138,7 +220,7 @@ impl KeyState {
                     }
                 }
             } else {
-                // Stop movement sounds when player is not moving
+                // Stop movement sounds when player is not moving or is crouching
                 if game_state.audio_manager.is_moving() {
                     game_state
                         .audio_manager
@@ -148,26 +230,18 @@ impl KeyState {
             }
 
             // Handle player movement with collision
-            game_state.player.move_with_collision(
+            game_state.last_movement = game_state.player.move_with_collision(
                 &mut game_state.audio_manager,
                 &game_state.collision_system,
                 game_state.delta_time,
-                forward,
-                backward,
-                left,
-                right,
+                move_axis,
             );
         }
 
         // Handle non-movement keys
         if self.is_pressed(GameKey::MouseButtonLeft) && game_state.capture_mouse {
             if game_state.current_screen == CurrentScreen::Loading {
-                if game_state.maze_path.is_some() {
-                    game_state.current_screen = CurrentScreen::Game;
-                    if let Some(timer) = &mut game_state.game_ui.timer {
-                        timer.start();
-                    }
-                }
+                game_state.begin_loaded_level();
             } else if game_state.current_screen == CurrentScreen::GameOver {
                 game_state.current_screen = CurrentScreen::NewGame;
             }
@@ -175,62 +249,77 @@ impl KeyState {
     }
 }
 
-macro_rules! match_char_key {
-    ($c:expr, {
-        $($key:literal => $variant:expr),* $(,)?
-    }) => {{
-        match $c.to_ascii_lowercase().as_str() {
-            $($key => Some($variant),)*
-            _ => None,
-        }
-    }};
+/// Returns the keys of the actions that can't be rebound.
+///
+/// The rebindable actions (see [`crate::game::bindings::REBINDABLE`]) are missing here, except
+/// that the arrow keys always walk.
+pub fn fixed_game_bindings() -> Vec<(KeyCode, GameKey)> {
+    vec![
+        (KeyCode::ArrowUp, GameKey::MoveForward),
+        (KeyCode::ArrowDown, GameKey::MoveBackward),
+        (KeyCode::ArrowLeft, GameKey::MoveLeft),
+        (KeyCode::ArrowRight, GameKey::MoveRight),
+        (KeyCode::ControlLeft, GameKey::Crouch),
+        (KeyCode::ControlRight, GameKey::Crouch),
+        (KeyCode::Space, GameKey::Jump),
+        (KeyCode::F5, GameKey::SaveBenchmark),
+        (KeyCode::F6, GameKey::ExportFrameHeatmap),
+        (KeyCode::F7, GameKey::ToggleLatencyProbe),
+        (KeyCode::F8, GameKey::LatencyProbe),
+        (KeyCode::F9, GameKey::DumpAudioSnapshot),
+        (KeyCode::F11, GameKey::ToggleDebugWindow),
+        (KeyCode::F12, GameKey::DebugPanic),
+        (KeyCode::Tab, GameKey::MapView),
+        (KeyCode::KeyC, GameKey::ToggleTuningPanel),
+        (KeyCode::Backquote, GameKey::Quit),
+        (KeyCode::KeyB, GameKey::ToggleBoundingBoxes),
+        (KeyCode::KeyH, GameKey::ToggleFrameHeatmap),
+        (KeyCode::KeyU, GameKey::ToggleUpgradeMenu),
+        (KeyCode::KeyV, GameKey::QuickRadial),
+    ]
 }
 
-macro_rules! match_named_key {
-    ($k:expr, {
-        $($key:ident => $variant:expr),* $(,)?
-    }) => {{
-        match $k {
-            $(winit::keyboard::NamedKey::$key => Some($variant),)*
-            _ => None,
-        }
-    }};
+/// Returns the fixed keys that keep working on menu screens.
+///
+/// Quitting, the upgrade menu, and the debug keys work wherever the player is; walking, jumping,
+/// crouching, the map view, and the quick-settings radial only mean something in the maze.
+pub fn menu_game_bindings() -> Vec<(KeyCode, GameKey)> {
+    fixed_game_bindings()
+        .into_iter()
+        .filter(|(_, action)| {
+            !matches!(
+                action,
+                GameKey::MoveForward
+                    | GameKey::MoveBackward
+                    | GameKey::MoveLeft
+                    | GameKey::MoveRight
+                    | GameKey::Crouch
+                    | GameKey::Jump
+                    | GameKey::MapView
+                    | GameKey::QuickRadial
+            )
+        })
+        .collect()
 }
 
-/// Converts a winit [`keyboard::Key`] to a [`GameKey`] if it matches a mapped action.
-///
-/// Supports both named keys (arrows, shift, space, escape) and character keys (WASD, C, Q).
-///
-/// # Arguments
-/// * `key` - The winit key event to convert.
+/// Returns the keys each gameplay action is bound to by default.
 ///
-/// # Returns
-/// * `Some(GameKey)` if the key maps to a game action.
-/// * `None` otherwise.
-pub fn winit_key_to_game_key(key: &keyboard::Key) -> Option<GameKey> {
-    match key {
-        keyboard::Key::Named(named) => match_named_key!(named, {
-            ArrowUp => GameKey::MoveForward,
-            ArrowDown => GameKey::MoveBackward,
-            ArrowLeft => GameKey::MoveLeft,
-            ArrowRight => GameKey::MoveRight,
-            Shift => GameKey::Sprint,
-            Space => GameKey::Jump,
-            Escape => GameKey::Escape,
-            F5 => GameKey::SaveBenchmark,
-        }),
-
-        keyboard::Key::Character(c) => match_char_key!(c, {
-            "w" => GameKey::MoveForward,
-            "s" => GameKey::MoveBackward,
-            "a" => GameKey::MoveLeft,
-            "d" => GameKey::MoveRight,
-            "c" => GameKey::ToggleSliders,
-            "`" => GameKey::Quit,
-            "b" => GameKey::ToggleBoundingBoxes,
-            "u" => GameKey::ToggleUpgradeMenu,
-        }),
-
-        _ => None,
-    }
+/// These are the [`fixed_game_bindings`] plus the default keys of the rebindable actions. Keys
+/// are physical, so WASD stays where it is on keyboards with another layout.
+pub fn default_game_bindings() -> Vec<(KeyCode, GameKey)> {
+    let rebindable = [
+        (KeyCode::KeyW, GameKey::MoveForward),
+        (KeyCode::KeyS, GameKey::MoveBackward),
+        (KeyCode::KeyA, GameKey::MoveLeft),
+        (KeyCode::KeyD, GameKey::MoveRight),
+        (KeyCode::ShiftLeft, GameKey::Sprint),
+        (KeyCode::ShiftRight, GameKey::Sprint),
+        (KeyCode::Escape, GameKey::Escape),
+        (KeyCode::KeyE, GameKey::Interact),
+        (KeyCode::KeyM, GameKey::ToggleMinimap),
+    ];
+    fixed_game_bindings()
+        .into_iter()
+        .chain(rebindable)
+        .collect()
 }
@@ -0,0 +1,616 @@
+//! Per-level content plans for the Mirador game.
+//!
+//! Everything that changes from one level to the next, such as the maze size, the timer, how
+//! fast the enemy runs, and which of the deep-level features are switched on, is decided in one
+//! place: [`plan_level`] turns a level number, the run's [`Difficulty`], the level's seed, the
+//! director's enemy tuning and the [`DifficultyCurve`] into a [`LevelPlan`]. The loading and
+//! level-start code only reads the plan, so the difficulty curve can be reviewed, dumped, and
+//! tested without starting a level.
+//!
+//! The maze grows along the [`DifficultyCurve`], by two cells a side per level up to
+//! [`MAX_MAZE_SIZE`] by default. The plan's [`MazeConfig`] is what the maze is carved from.
+//!
+//! The plan is decided before the maze is carved. Features that are rolled from the finished
+//! maze, such as the level's events, theme, anomaly spot and spawn cell, are only switched on
+//! or off here and still rolled from the maze's own seed, so a retried level rolls them the
+//! same way.
+//!
+//! Each plan is recorded in the session journal, and the current one is shown in the debug
+//! window.
+//!
+//! # Usage
+//!
+//! ```rust,ignore
+//! let difficulty = Difficulty::of(hardcore, is_test_mode);
+//! let plan = plan_level(level, difficulty, seed, director.tuning(), &DifficultyCurve::STANDARD);
+//! let (generator, maze) = MazeGenerator::from_config(&plan.maze_config());
+//! game_state.game_ui.timer = Some(GameTimer::new(plan.timer.config()));
+//! enemy.tuning = plan.enemy.tuning;
+//! ```
+
+use crate::game::TimerConfig;
+use crate::game::breathing;
+use crate::game::director::EnemyTuning;
+use crate::game::maze::floors;
+use crate::game::signage::HINT_LEVELS;
+use crate::game::spawn::{DELAYED_SPAWN_LEVEL, HARDCORE_DELAYED_SPAWN_LEVEL};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::time::Duration;
+
+/// Width of the first level's maze, in cells.
+pub const MAZE_WIDTH: usize = 25;
+
+/// Height of the first level's maze, in cells.
+pub const MAZE_HEIGHT: usize = 25;
+
+/// Cells added to each side of the maze per level on the standard curve.
+pub const MAZE_GROWTH_PER_LEVEL: usize = 2;
+
+/// Largest maze a level may be planned with, in cells along either side.
+pub const MAX_MAZE_SIZE: usize = 40;
+
+/// Seconds on the level timer, before upgrades.
+pub const TIMER_SECS: f32 = 30.0;
+
+/// Shortest a level's timer may ever be planned, in seconds.
+pub const MIN_TIMER_SECS: f32 = 20.0;
+
+/// Seconds left when the timer turns to its warning color.
+pub const TIMER_WARNING_SECS: f32 = 20.0;
+
+/// Seconds left when the timer turns to its critical color.
+pub const TIMER_CRITICAL_SECS: f32 = 10.0;
+
+/// First level that may hide an anomaly.
+pub const ANOMALY_MIN_LEVEL: i32 = 2;
+
+/// Most the enemy's speed is multiplied by on deep levels.
+pub const MAX_ENEMY_SPEED_MULTIPLIER: f32 = 5.0;
+
+/// First level a second enemy joins the hunt on the standard curve.
+pub const PACK_FROM_LEVEL: i32 = 8;
+
+/// Levels between one more enemy joining the pack and the next on the standard curve.
+pub const LEVELS_PER_EXTRA_ENEMY: i32 = 6;
+
+/// Most enemies that may ever hunt the player at once.
+pub const MAX_ENEMIES: u32 = 3;
+
+/// How the run is being played.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Difficulty {
+    /// A regular or daily run
+    #[default]
+    Normal,
+    /// A hardcore run, whose enemy is delayed from an earlier level
+    Hardcore,
+    /// The test maze, which has none of the deep-level features
+    Test,
+}
+
+impl Difficulty {
+    /// Returns the difficulty of a run.
+    ///
+    /// # Arguments
+    /// * `hardcore` - Whether the run is hardcore
+    /// * `is_test_mode` - Whether the test maze is being played
+    pub fn of(hardcore: bool, is_test_mode: bool) -> Self {
+        if is_test_mode {
+            Difficulty::Test
+        } else if hardcore {
+            Difficulty::Hardcore
+        } else {
+            Difficulty::Normal
+        }
+    }
+}
+
+/// The size and seed a maze is carved from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MazeConfig {
+    /// Maze width in cells
+    pub width: usize,
+    /// Maze height in cells
+    pub height: usize,
+    /// Seed for the maze layout and exit, or `None` for a random maze
+    pub seed: Option<u64>,
+}
+
+impl MazeConfig {
+    /// Returns the config of a random maze of the first level's size.
+    pub fn standard() -> Self {
+        Self {
+            width: MAZE_WIDTH,
+            height: MAZE_HEIGHT,
+            seed: None,
+        }
+    }
+
+    /// Returns the config with its seed fixed.
+    ///
+    /// # Arguments
+    /// * `seed` - Seed for the maze layout and exit, or `None` for a random maze
+    pub fn with_seed(self, seed: Option<u64>) -> Self {
+        Self { seed, ..self }
+    }
+}
+
+/// How the maze grows and the pack of enemies ramps up from one level to the next.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DifficultyCurve {
+    /// Size of the first level's maze, in cells along each side
+    pub base_size: usize,
+    /// Cells added to each side per level
+    pub growth_per_level: usize,
+    /// Largest the maze grows, in cells along each side
+    pub max_size: usize,
+    /// First level a second enemy hunts the player on
+    pub pack_from_level: i32,
+    /// Levels between one more enemy joining the pack and the next
+    pub levels_per_extra_enemy: i32,
+    /// Most enemies hunting the player at once, never more than [`MAX_ENEMIES`]
+    pub max_enemies: u32,
+}
+
+impl DifficultyCurve {
+    /// The curve regular runs are played on.
+    pub const STANDARD: Self = Self {
+        base_size: MAZE_WIDTH,
+        growth_per_level: MAZE_GROWTH_PER_LEVEL,
+        max_size: MAX_MAZE_SIZE,
+        pack_from_level: PACK_FROM_LEVEL,
+        levels_per_extra_enemy: LEVELS_PER_EXTRA_ENEMY,
+        max_enemies: MAX_ENEMIES,
+    };
+
+    /// Returns the maze a level is played in, with a random seed.
+    ///
+    /// # Arguments
+    /// * `level` - The level being played; levels below 1 get the first level's maze
+    ///
+    /// # Returns
+    /// A square maze, [`DifficultyCurve::growth_per_level`] cells a side larger per level
+    /// up to [`DifficultyCurve::max_size`] and never beyond [`MAX_MAZE_SIZE`]
+    pub fn maze_config(&self, level: i32) -> MazeConfig {
+        let levels_in = (level.max(1) - 1) as usize;
+        let size = self
+            .base_size
+            .saturating_add(self.growth_per_level.saturating_mul(levels_in))
+            .min(self.max_size)
+            .clamp(1, MAX_MAZE_SIZE);
+        MazeConfig {
+            width: size,
+            height: size,
+            seed: None,
+        }
+    }
+
+    /// Returns how many enemies hunt the player on a level.
+    ///
+    /// # Arguments
+    /// * `level` - The level being played
+    ///
+    /// # Returns
+    /// One enemy before [`DifficultyCurve::pack_from_level`], then one more every
+    /// [`DifficultyCurve::levels_per_extra_enemy`] levels up to
+    /// [`DifficultyCurve::max_enemies`], and never more than [`MAX_ENEMIES`]
+    pub fn enemy_count(&self, level: i32) -> u32 {
+        if level < self.pack_from_level {
+            return 1;
+        }
+        let extra = (level - self.pack_from_level) / self.levels_per_extra_enemy.max(1) + 1;
+        (1 + extra as u32)
+            .min(self.max_enemies)
+            .clamp(1, MAX_ENEMIES)
+    }
+}
+
+impl Default for DifficultyCurve {
+    fn default() -> Self {
+        Self::STANDARD
+    }
+}
+
+/// The level timer's settings.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct TimerPlan {
+    /// Seconds on the timer, before upgrades
+    pub duration_secs: f32,
+    /// Seconds left when the timer turns to its warning color
+    pub warning_secs: f32,
+    /// Seconds left when the timer turns to its critical color
+    pub critical_secs: f32,
+}
+
+impl TimerPlan {
+    /// Builds the timer configuration, in the default colors.
+    pub fn config(&self) -> TimerConfig {
+        TimerConfig {
+            duration: Duration::from_secs_f32(self.duration_secs),
+            warning_threshold: Duration::from_secs_f32(self.warning_secs),
+            critical_threshold: Duration::from_secs_f32(self.critical_secs),
+            ..TimerConfig::default()
+        }
+    }
+}
+
+/// The level's enemies.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct EnemyPlan {
+    /// Number of enemies hunting the player
+    pub count: u32,
+    /// Multiplier on the enemy's base speed from the level alone
+    pub speed_multiplier: f32,
+    /// Adaptive difficulty or daily challenge multipliers on top of the level scaling
+    pub tuning: EnemyTuning,
+    /// Whether the enemy arrives partway through the level instead of waiting from the start
+    pub delayed_spawn: bool,
+}
+
+/// Every setting of one level, decided before its maze is carved.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LevelPlan {
+    /// Level number
+    pub level: i32,
+    /// How the run is being played
+    pub difficulty: Difficulty,
+    /// Seed the maze is carved from
+    pub seed: u64,
+    /// Maze width in cells
+    pub maze_width: usize,
+    /// Maze height in cells
+    pub maze_height: usize,
+    /// Whether a second maze is stacked on top of the first
+    pub multi_floor: bool,
+    /// The level timer
+    pub timer: TimerPlan,
+    /// The level's enemies
+    pub enemy: EnemyPlan,
+    /// Whether an anomaly may be rolled
+    pub anomaly: bool,
+    /// How far the walls breathe, in world units
+    pub wall_breath: f32,
+    /// Whether arrows are painted along the start of the solution, whatever the hints setting
+    pub signposts: bool,
+    /// Whether ambient events and a theme are rolled
+    pub modifiers: bool,
+}
+
+impl LevelPlan {
+    /// Returns the size and seed the level's maze is carved from.
+    pub fn maze_config(&self) -> MazeConfig {
+        MazeConfig {
+            width: self.maze_width,
+            height: self.maze_height,
+            seed: Some(self.seed),
+        }
+    }
+}
+
+impl Default for LevelPlan {
+    /// The plan of a normal level 1 with a neutral enemy and seed 0.
+    fn default() -> Self {
+        plan_level(
+            1,
+            Difficulty::Normal,
+            0,
+            EnemyTuning::NEUTRAL,
+            &DifficultyCurve::STANDARD,
+        )
+    }
+}
+
+impl fmt::Display for LevelPlan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Level {} ({:?}, seed {}): {}x{}{}, {:.0}s timer, {} enem{} x{:.2} speed{}",
+            self.level,
+            self.difficulty,
+            self.seed,
+            self.maze_width,
+            self.maze_height,
+            if self.multi_floor { " two floors" } else { "" },
+            self.timer.duration_secs,
+            self.enemy.count,
+            if self.enemy.count == 1 { "y" } else { "ies" },
+            self.enemy.speed_multiplier * self.enemy.tuning.speed_multiplier,
+            if self.enemy.delayed_spawn {
+                " delayed"
+            } else {
+                ""
+            },
+        )
+    }
+}
+
+/// Returns how much faster than its base speed the enemy runs on a level.
+///
+/// # Arguments
+/// * `level` - The level being played
+///
+/// # Returns
+/// 20% faster per level, capped at [`MAX_ENEMY_SPEED_MULTIPLIER`]
+pub fn enemy_speed_multiplier(level: i32) -> f32 {
+    (1.0 + level as f32 * 0.2).min(MAX_ENEMY_SPEED_MULTIPLIER)
+}
+
+/// Plans a level.
+///
+/// # Arguments
+/// * `level` - The level being planned
+/// * `difficulty` - How the run is being played
+/// * `seed` - Seed the level's maze is carved from
+/// * `director_tuning` - The adaptive difficulty or daily challenge enemy tuning
+/// * `curve` - How the maze grows and the pack ramps up; the test maze stays the first
+///   level's size, with one enemy
+///
+/// # Returns
+/// The same plan for the same arguments
+pub fn plan_level(
+    level: i32,
+    difficulty: Difficulty,
+    seed: u64,
+    director_tuning: EnemyTuning,
+    curve: &DifficultyCurve,
+) -> LevelPlan {
+    let features = difficulty != Difficulty::Test;
+    let maze = if features {
+        curve.maze_config(level)
+    } else {
+        curve.maze_config(1)
+    };
+    let multi_floor = floors::is_multi_floor_level(level, !features);
+    let first_delayed_spawn = if difficulty == Difficulty::Hardcore {
+        HARDCORE_DELAYED_SPAWN_LEVEL
+    } else {
+        DELAYED_SPAWN_LEVEL
+    };
+    LevelPlan {
+        level,
+        difficulty,
+        seed,
+        maze_width: maze.width,
+        maze_height: maze.height,
+        multi_floor,
+        timer: TimerPlan {
+            duration_secs: TIMER_SECS.max(MIN_TIMER_SECS),
+            warning_secs: TIMER_WARNING_SECS,
+            critical_secs: TIMER_CRITICAL_SECS,
+        },
+        enemy: EnemyPlan {
+            count: if features {
+                curve.enemy_count(level)
+            } else {
+                1
+            },
+            speed_multiplier: enemy_speed_multiplier(level),
+            tuning: director_tuning,
+            // Two-floor levels keep their enemy upstairs from the start
+            delayed_spawn: features && !multi_floor && level >= first_delayed_spawn,
+        },
+        // Nor do they hide anomalies
+        anomaly: features && !multi_floor && level >= ANOMALY_MIN_LEVEL,
+        wall_breath: if features {
+            breathing::wall_breath_for_level(level)
+        } else {
+            0.0
+        },
+        signposts: features && level <= HINT_LEVELS,
+        modifiers: features,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DIFFICULTIES: [Difficulty; 3] =
+        [Difficulty::Normal, Difficulty::Hardcore, Difficulty::Test];
+
+    #[test]
+    fn test_curve_stays_within_bounds() {
+        for difficulty in DIFFICULTIES {
+            let mut last_speed = 0.0;
+            for level in 1..=200 {
+                let plan = plan_level(
+                    level,
+                    difficulty,
+                    7,
+                    EnemyTuning::NEUTRAL,
+                    &DifficultyCurve::STANDARD,
+                );
+                assert!(
+                    plan.timer.duration_secs >= MIN_TIMER_SECS,
+                    "level {}",
+                    level
+                );
+                assert!(plan.timer.critical_secs < plan.timer.warning_secs);
+                assert!(plan.timer.warning_secs <= plan.timer.duration_secs);
+                assert!(plan.enemy.speed_multiplier >= last_speed, "level {}", level);
+                assert!(plan.enemy.speed_multiplier <= MAX_ENEMY_SPEED_MULTIPLIER);
+                assert!(plan.maze_width <= MAX_MAZE_SIZE && plan.maze_height <= MAX_MAZE_SIZE);
+                assert!((1..=MAX_ENEMIES).contains(&plan.enemy.count));
+                last_speed = plan.enemy.speed_multiplier;
+            }
+        }
+    }
+
+    #[test]
+    fn test_test_mode_has_no_deep_level_features() {
+        let plan = plan_level(
+            12,
+            Difficulty::Test,
+            3,
+            EnemyTuning::NEUTRAL,
+            &DifficultyCurve::STANDARD,
+        );
+        assert!(!plan.multi_floor);
+        assert!(!plan.anomaly);
+        assert!(!plan.enemy.delayed_spawn);
+        assert!(!plan.signposts);
+        assert!(!plan.modifiers);
+        assert_eq!(plan.wall_breath, 0.0);
+    }
+
+    #[test]
+    fn test_plans_carry_the_seed_and_tuning() {
+        let tuning = EnemyTuning::for_pressure(0.5);
+        let plan = plan_level(
+            4,
+            Difficulty::Normal,
+            99,
+            tuning,
+            &DifficultyCurve::STANDARD,
+        );
+        assert_eq!(plan.seed, 99);
+        assert_eq!(plan.enemy.tuning, tuning);
+        assert_eq!(
+            plan,
+            plan_level(
+                4,
+                Difficulty::Normal,
+                99,
+                tuning,
+                &DifficultyCurve::STANDARD
+            )
+        );
+    }
+
+    #[test]
+    fn test_maze_grows_along_the_curve_up_to_its_cap() {
+        let curve = DifficultyCurve::STANDARD;
+        assert_eq!(curve.maze_config(1), MazeConfig::standard());
+        assert_eq!(curve.maze_config(0), MazeConfig::standard());
+        assert_eq!(curve.maze_config(5).width, 33);
+        assert_eq!(curve.maze_config(8).width, 39);
+        assert_eq!(curve.maze_config(9).width, MAX_MAZE_SIZE);
+        assert_eq!(curve.maze_config(i32::MAX).height, MAX_MAZE_SIZE);
+
+        let capped = DifficultyCurve {
+            base_size: 10,
+            growth_per_level: 5,
+            max_size: 20,
+            ..curve
+        };
+        assert_eq!(capped.maze_config(2).width, 15);
+        assert_eq!(capped.maze_config(4).width, 20);
+
+        // The test maze doesn't grow
+        let plan = plan_level(5, Difficulty::Test, 1, EnemyTuning::NEUTRAL, &curve);
+        assert_eq!(
+            (plan.maze_width, plan.maze_height),
+            (MAZE_WIDTH, MAZE_HEIGHT)
+        );
+    }
+
+    #[test]
+    fn test_enemy_count_ramps_up_to_its_cap() {
+        let curve = DifficultyCurve::STANDARD;
+        let counts: Vec<u32> = (1..=25).map(|level| curve.enemy_count(level)).collect();
+        assert!(counts[..7].iter().all(|&count| count == 1));
+        assert!(counts[7..13].iter().all(|&count| count == 2));
+        assert!(counts[13..].iter().all(|&count| count == 3));
+        assert_eq!(curve.enemy_count(i32::MAX), MAX_ENEMIES);
+
+        let crowded = DifficultyCurve {
+            pack_from_level: 2,
+            levels_per_extra_enemy: 1,
+            max_enemies: 10,
+            ..curve
+        };
+        assert_eq!(crowded.enemy_count(2), 2);
+        assert_eq!(crowded.enemy_count(50), MAX_ENEMIES);
+
+        // The test maze keeps its one enemy
+        let plan = plan_level(20, Difficulty::Test, 1, EnemyTuning::NEUTRAL, &curve);
+        assert_eq!(plan.enemy.count, 1);
+    }
+
+    #[test]
+    fn test_plan_maze_config_carries_the_seed() {
+        let plan = plan_level(
+            3,
+            Difficulty::Normal,
+            42,
+            EnemyTuning::NEUTRAL,
+            &DifficultyCurve::STANDARD,
+        );
+        let config = plan.maze_config();
+        assert_eq!(config.seed, Some(42));
+        assert_eq!((config.width, config.height), (29, 29));
+    }
+
+    /// Pins the values levels 1 to 20 were played with before the plans existed, apart from
+    /// the enemy count, which follows the pack ramp added since.
+    #[test]
+    fn test_levels_one_to_twenty_match_the_golden_curve() {
+        // (speed, two floors, wall breath, anomaly, delayed spawn, hardcore delayed spawn, signs)
+        #[rustfmt::skip]
+        let golden: [(f32, bool, f32, bool, bool, bool, bool); 20] = [
+            (1.2, false, 0.0, false, false, false, true),
+            (1.4, false, 0.0, true, false, false, true),
+            (1.6, false, 0.0, true, false, true, false),
+            (1.8, false, 0.0, true, false, true, false),
+            (2.0, false, 0.0, true, true, true, false),
+            (2.2, true, 0.3, false, false, false, false),
+            (2.4, true, 0.6, false, false, false, false),
+            (2.6, true, 0.9, false, false, false, false),
+            (2.8, true, 1.2, false, false, false, false),
+            (3.0, true, 1.5, false, false, false, false),
+            (3.2, true, 1.8, false, false, false, false),
+            (3.4, true, 2.1, false, false, false, false),
+            (3.6, true, 2.4, false, false, false, false),
+            (3.8, true, 2.7, false, false, false, false),
+            (4.0, true, 3.0, false, false, false, false),
+            (4.2, true, 3.0, false, false, false, false),
+            (4.4, true, 3.0, false, false, false, false),
+            (4.6, true, 3.0, false, false, false, false),
+            (4.8, true, 3.0, false, false, false, false),
+            (5.0, true, 3.0, false, false, false, false),
+        ];
+        for (level, row) in (1..=20).zip(golden) {
+            let (speed, multi_floor, breath, anomaly, delayed, hardcore_delayed, signposts) = row;
+            let plan = plan_level(
+                level,
+                Difficulty::Normal,
+                1,
+                EnemyTuning::NEUTRAL,
+                &DifficultyCurve::STANDARD,
+            );
+            let hardcore = plan_level(
+                level,
+                Difficulty::Hardcore,
+                1,
+                EnemyTuning::NEUTRAL,
+                &DifficultyCurve::STANDARD,
+            );
+            assert!(
+                (plan.enemy.speed_multiplier - speed).abs() < 1e-5,
+                "level {}",
+                level
+            );
+            assert_eq!(plan.multi_floor, multi_floor, "level {}", level);
+            assert!((plan.wall_breath - breath).abs() < 1e-5, "level {}", level);
+            assert_eq!(plan.anomaly, anomaly, "level {}", level);
+            assert_eq!(plan.enemy.delayed_spawn, delayed, "level {}", level);
+            assert_eq!(
+                hardcore.enemy.delayed_spawn, hardcore_delayed,
+                "level {}",
+                level
+            );
+            assert_eq!(plan.signposts, signposts, "level {}", level);
+            let size = (25 + 2 * (level as usize - 1)).min(MAX_MAZE_SIZE);
+            assert_eq!((plan.maze_width, plan.maze_height), (size, size));
+            assert_eq!(plan.timer.duration_secs, 30.0);
+            assert_eq!(plan.timer.warning_secs, 20.0);
+            assert_eq!(plan.timer.critical_secs, 10.0);
+            assert_eq!(
+                plan.enemy.count,
+                DifficultyCurve::STANDARD.enemy_count(level)
+            );
+            assert!(plan.modifiers);
+        }
+    }
+}
@@ -0,0 +1,178 @@
+//! Overhead map view for the Mirador game.
+//!
+//! This module provides [`MapView`], which lets the player hold a key to pitch the camera up to
+//! a bird's-eye view [`MAP_VIEW_HEIGHT`] units above their head, looking straight down. The
+//! camera eases between first person and the overhead view over [`TRANSITION_SECONDS`]; while
+//! the view is up the player cannot move and the level timer drains at [`TIMER_RATE`] times the
+//! normal speed. The fog still limits how much of the maze the view reveals.
+//!
+//! The view is a pure state machine ticked once per frame. The game decides whether the view is
+//! allowed this frame (only during play, never during the exit cutscene or with the enemy within
+//! [`ENEMY_BLOCK_RANGE`]); when it is not, the camera eases back down even if the key is held.
+//!
+//! # Usage
+//!
+//! ```rust,ignore
+//! let mut map_view = MapView::new();
+//!
+//! // Once per frame
+//! map_view.update(key_held, allowed, delta_time);
+//! game_ui.timer_rate = map_view.timer_rate();
+//!
+//! // When building the camera
+//! let view_matrix = map_view.view_matrix(&player);
+//! ```
+
+use crate::game::player::Player;
+use crate::math::mat::Mat4;
+
+/// Height in world units the overhead camera rises above the player.
+pub const MAP_VIEW_HEIGHT: f32 = 400.0;
+
+/// Camera pitch in degrees of the overhead view (straight down).
+pub const MAP_VIEW_PITCH: f32 = -90.0;
+
+/// Seconds the camera takes to move between first person and the overhead view.
+pub const TRANSITION_SECONDS: f32 = 0.4;
+
+/// How many times faster than normal the level timer drains while the view is held.
+pub const TIMER_RATE: f32 = 2.0;
+
+/// Enemy distance in world units within which the view cannot be used.
+pub const ENEMY_BLOCK_RANGE: f32 = 200.0;
+
+/// Hold-to-peek overhead camera that blends with the player's first-person view.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct MapView {
+    /// Linear transition progress from 0 (first person) to 1 (overhead)
+    progress: f32,
+    /// Whether the view was held and allowed on the last update
+    held: bool,
+}
+
+impl MapView {
+    /// Creates a map view in first person.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Advances the camera transition by one frame.
+    ///
+    /// # Arguments
+    /// * `requested` - Whether the map view key is held
+    /// * `allowed` - Whether the game currently permits the map view
+    /// * `delta_time` - Seconds since the last frame
+    pub fn update(&mut self, requested: bool, allowed: bool, delta_time: f32) {
+        self.held = requested && allowed;
+        let step = delta_time.max(0.0) / TRANSITION_SECONDS;
+        self.progress = if self.held {
+            (self.progress + step).min(1.0)
+        } else {
+            (self.progress - step).max(0.0)
+        };
+    }
+
+    /// Snaps the camera back to first person, e.g. when a level starts.
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+
+    /// Returns whether the view is held and allowed right now.
+    pub fn is_held(&self) -> bool {
+        self.held
+    }
+
+    /// Returns whether the camera has left first person at all.
+    ///
+    /// The player is frozen and the compass is hidden for as long as this is true.
+    pub fn is_engaged(&self) -> bool {
+        self.progress > 0.0
+    }
+
+    /// Returns the eased blend between first person (0) and overhead (1).
+    pub fn blend(&self) -> f32 {
+        ease_in_out(self.progress)
+    }
+
+    /// Returns the rate the level timer should drain at this frame.
+    pub fn timer_rate(&self) -> f32 {
+        if self.held { TIMER_RATE } else { 1.0 }
+    }
+
+    /// Builds the camera's view matrix for this frame.
+    ///
+    /// Blends the player's first-person camera with the overhead camera by raising the eye
+    /// and pitching it down together, so the transition sweeps smoothly up and over.
+    ///
+    /// # Arguments
+    /// * `player` - The player whose camera is being overridden
+    ///
+    /// # Returns
+    /// The player's own view matrix while in first person, otherwise the blended view
+    pub fn view_matrix(&self, player: &Player) -> Mat4 {
+        if !self.is_engaged() {
+            return player.get_view_matrix();
+        }
+
+        let t = self.blend();
+        let pitch = player.pitch + (MAP_VIEW_PITCH - player.pitch) * t;
+        let height = player.position[1] + MAP_VIEW_HEIGHT * t;
+
+        // Same construction as `Player::get_view_matrix`, from the blended eye
+        let rotation_matrix = Mat4::rotation_y(player.yaw).multiply(&Mat4::rotation_x(pitch));
+        let translation_matrix =
+            Mat4::translation(-player.position[0], -height, -player.position[2]);
+        translation_matrix.multiply(&rotation_matrix)
+    }
+}
+
+/// Smoothstep easing that starts and ends slowly.
+///
+/// # Arguments
+/// * `t` - Linear progress, clamped to `[0, 1]`
+///
+/// # Returns
+/// The eased progress in `[0, 1]`
+pub fn ease_in_out(t: f32) -> f32 {
+    let t = t.clamp(0.0, 1.0);
+    t * t * (3.0 - 2.0 * t)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_view_rises_while_held_and_returns_when_blocked() {
+        let mut map_view = MapView::new();
+        assert!(!map_view.is_engaged());
+
+        map_view.update(true, true, TRANSITION_SECONDS / 2.0);
+        assert!(map_view.is_held());
+        assert_eq!(map_view.timer_rate(), TIMER_RATE);
+        assert!((map_view.blend() - 0.5).abs() < 1e-6);
+
+        map_view.update(true, true, TRANSITION_SECONDS);
+        assert_eq!(map_view.blend(), 1.0);
+
+        // The enemy closing in forces the camera back down even though the key is held
+        map_view.update(true, false, TRANSITION_SECONDS / 4.0);
+        assert!(!map_view.is_held());
+        assert_eq!(map_view.timer_rate(), 1.0);
+        assert!(map_view.is_engaged());
+
+        map_view.update(true, false, TRANSITION_SECONDS);
+        assert!(!map_view.is_engaged());
+        assert_eq!(map_view.blend(), 0.0);
+    }
+
+    #[test]
+    fn test_easing_is_symmetric() {
+        assert_eq!(ease_in_out(-1.0), 0.0);
+        assert_eq!(ease_in_out(2.0), 1.0);
+        for i in 0..=10 {
+            let t = i as f32 / 10.0;
+            assert!((ease_in_out(t) + ease_in_out(1.0 - t) - 1.0).abs() < 1e-6);
+        }
+    }
+}
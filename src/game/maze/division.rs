@@ -0,0 +1,424 @@
+//! Parallel maze generation by recursive division into regions.
+//!
+//! Kruskal's algorithm over the whole grid is one long sequential pass, which gets slow on
+//! very large mazes. [`plan_divided`] splits the grid instead: any region wider or taller than
+//! [`MAX_REGION_SIDE`] cells is divided into quadrants (or halves, when only one side is too
+//! long), recursively, until every leaf region is small. Each leaf is carved on its own with
+//! Kruskal's algorithm on a rayon worker, then the regions are joined back together: the
+//! children of every division are connected through openings in the walls between them, one
+//! fewer opening than there are children, so the joined maze is still a perfect maze.
+//!
+//! The result never depends on how the work was scheduled. Every leaf is shuffled with its own
+//! seed, mixed from the maze seed and the region's position and size, the leaves are collected
+//! in a fixed order, and the openings are picked sequentially from a seed mixed the same way
+//! for each division. The same seed gives the same maze on one core or sixty-four.
+//!
+//! A final pass checks that every cell is reachable and no passage closes a loop, carving
+//! whatever is needed to join stray pieces. It never has anything to do unless the division
+//! itself is broken, and the number of repairs is reported so tests can hold it to zero.
+//!
+//! The plan lists passages region by region, so replaying it through
+//! [`MazeGenerator`](crate::game::maze::generator::MazeGenerator) shows the loading screen
+//! filling in one region after another, then the openings joining them.
+//!
+//! # Usage
+//!
+//! ```rust,ignore
+//! let plan = plan_divided(101, 101, seed);
+//! for passage in &plan.passages {
+//!     maze.carve_passage(passage.cell1, passage.cell2);
+//! }
+//! ```
+
+use crate::game::maze::generator::{Cell, Edge, UnionFind};
+use rand::prelude::*;
+use rayon::prelude::*;
+
+/// Longest side, in cells, of a region that is carved without being divided further.
+pub const MAX_REGION_SIDE: usize = 16;
+
+/// Mixed into the maze seed for each leaf region's edge order.
+const REGION_SEED_SALT: u64 = 0x5245_4749_4F4E_5345;
+
+/// Mixed into the maze seed for the openings between regions.
+const OPENING_SEED_SALT: u64 = 0x4F50_454E_494E_4753;
+
+/// A rectangle of maze cells.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Region {
+    /// Top row
+    pub row: usize,
+    /// Left column
+    pub col: usize,
+    /// Height in cells
+    pub height: usize,
+    /// Width in cells
+    pub width: usize,
+}
+
+impl Region {
+    /// Creates a region.
+    ///
+    /// # Arguments
+    /// * `row`, `col` - Top-left cell
+    /// * `height`, `width` - Size in cells
+    pub fn new(row: usize, col: usize, height: usize, width: usize) -> Self {
+        Self {
+            row,
+            col,
+            height,
+            width,
+        }
+    }
+
+    /// Returns whether a cell lies inside the region.
+    pub fn contains(&self, cell: Cell) -> bool {
+        (self.row..self.row + self.height).contains(&cell.row)
+            && (self.col..self.col + self.width).contains(&cell.col)
+    }
+
+    /// Splits the region into the regions it is carved as.
+    ///
+    /// # Returns
+    /// Four quadrants when both sides are longer than [`MAX_REGION_SIDE`], two halves when
+    /// one is, and nothing when the region is small enough to carve whole. Quadrants come in
+    /// the order top-left, top-right, bottom-left, bottom-right.
+    fn split(&self) -> Vec<Region> {
+        let (top, bottom) = (self.height / 2, self.height - self.height / 2);
+        let (left, right) = (self.width / 2, self.width - self.width / 2);
+        let (row, col) = (self.row, self.col);
+        match (self.height > MAX_REGION_SIDE, self.width > MAX_REGION_SIDE) {
+            (true, true) => vec![
+                Region::new(row, col, top, left),
+                Region::new(row, col + left, top, right),
+                Region::new(row + top, col, bottom, left),
+                Region::new(row + top, col + left, bottom, right),
+            ],
+            (true, false) => vec![
+                Region::new(row, col, top, self.width),
+                Region::new(row + top, col, bottom, self.width),
+            ],
+            (false, true) => vec![
+                Region::new(row, col, self.height, left),
+                Region::new(row, col + left, self.height, right),
+            ],
+            (false, false) => Vec::new(),
+        }
+    }
+}
+
+/// The passages of a maze generated by recursive division, in the order to carve them.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DividedPlan {
+    /// Every passage, leaf region by leaf region, then the openings between regions
+    pub passages: Vec<Edge>,
+    /// Leaf regions, in the order their passages are listed
+    pub regions: Vec<Region>,
+    /// For each leaf region, the index in `passages` just past its last passage
+    pub region_ends: Vec<usize>,
+    /// Passages the connectivity pass had to add; zero for a sound division
+    pub repaired: usize,
+}
+
+impl DividedPlan {
+    /// Returns how many leaf regions are fully carved after a number of passages.
+    ///
+    /// # Arguments
+    /// * `carved` - Passages carved so far, from the start of the plan
+    pub fn regions_complete(&self, carved: usize) -> usize {
+        self.region_ends.partition_point(|&end| end <= carved)
+    }
+}
+
+/// Plans a maze by dividing it into regions, carving them in parallel, and joining them.
+///
+/// # Arguments
+/// * `width` - Maze width in cells
+/// * `height` - Maze height in cells
+/// * `seed` - Seed for every region and opening; the same seed always plans the same maze
+///
+/// # Returns
+/// The passages of a perfect maze covering every cell
+pub fn plan_divided(width: usize, height: usize, seed: u64) -> DividedPlan {
+    let mut regions = Vec::new();
+    let mut divisions = Vec::new();
+    divide(
+        Region::new(0, 0, height, width),
+        &mut regions,
+        &mut divisions,
+    );
+
+    // The heavy part; collect keeps the regions' order whatever order they finish in
+    let carved: Vec<Vec<Edge>> = regions
+        .par_iter()
+        .map(|region| carve_region(region, mix_seed(seed ^ REGION_SEED_SALT, region)))
+        .collect();
+
+    let mut passages = Vec::new();
+    let mut region_ends = Vec::with_capacity(regions.len());
+    for region_passages in carved {
+        passages.extend(region_passages);
+        region_ends.push(passages.len());
+    }
+    for (parent, children) in &divisions {
+        let mut rng = StdRng::seed_from_u64(mix_seed(seed ^ OPENING_SEED_SALT, parent));
+        passages.extend(openings(children, &mut rng));
+    }
+
+    let repaired = repair_connectivity(width, height, &mut passages);
+    DividedPlan {
+        passages,
+        regions,
+        region_ends,
+        repaired,
+    }
+}
+
+/// Splits a region recursively, collecting the leaves and every division.
+///
+/// # Arguments
+/// * `region` - The region to split
+/// * `leaves` - Receives the regions small enough to carve whole, in depth-first order
+/// * `divisions` - Receives each divided region with its children, after theirs
+fn divide(region: Region, leaves: &mut Vec<Region>, divisions: &mut Vec<(Region, Vec<Region>)>) {
+    let children = region.split();
+    if children.is_empty() {
+        leaves.push(region);
+        return;
+    }
+    for &child in &children {
+        divide(child, leaves, divisions);
+    }
+    divisions.push((region, children));
+}
+
+/// Mixes a seed with a region's position and size.
+///
+/// Uses the SplitMix64 finalizer, so neighboring regions get unrelated seeds.
+fn mix_seed(seed: u64, region: &Region) -> u64 {
+    [region.row, region.col, region.height, region.width]
+        .into_iter()
+        .fold(seed, |state, value| {
+            let mut z = (state ^ value as u64).wrapping_add(0x9E37_79B9_7F4A_7C15);
+            z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+            z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+            z ^ (z >> 31)
+        })
+}
+
+/// Carves one region with Kruskal's algorithm.
+///
+/// # Arguments
+/// * `region` - The region to carve
+/// * `seed` - Seed for the region's edge order
+///
+/// # Returns
+/// The passages of a spanning tree of the region, in the order they were carved
+fn carve_region(region: &Region, seed: u64) -> Vec<Edge> {
+    let mut edges = Vec::new();
+    let mut union_find = UnionFind::new();
+    for row in region.row..region.row + region.height {
+        for col in region.col..region.col + region.width {
+            let cell = Cell::new(row, col);
+            union_find.make_set(cell);
+            if col + 1 < region.col + region.width {
+                edges.push(Edge::new(cell, Cell::new(row, col + 1)));
+            }
+            if row + 1 < region.row + region.height {
+                edges.push(Edge::new(cell, Cell::new(row + 1, col)));
+            }
+        }
+    }
+    edges.shuffle(&mut StdRng::seed_from_u64(seed));
+    edges.retain(|edge| union_find.union(edge.cell1, edge.cell2));
+    edges
+}
+
+/// Picks the openings that join the children of one division.
+///
+/// Halves are joined by one opening. Quadrants touch in a ring of four walls, and all but
+/// one of them, picked at random, get an opening.
+///
+/// # Arguments
+/// * `children` - The regions the division made, in [`Region::split`] order
+/// * `rng` - Random source for the division
+///
+/// # Returns
+/// One passage per opening, at a random place along its wall
+fn openings(children: &[Region], rng: &mut StdRng) -> Vec<Edge> {
+    let pairs: Vec<(usize, usize)> = if children.len() == 4 {
+        let mut ring = vec![(0, 1), (2, 3), (0, 2), (1, 3)];
+        ring.remove(rng.gen_range(0..ring.len()));
+        ring
+    } else {
+        vec![(0, 1)]
+    };
+    pairs
+        .into_iter()
+        .map(|(first, second)| opening_between(&children[first], &children[second], rng))
+        .collect()
+}
+
+/// Picks a place in the wall between two regions that touch.
+///
+/// # Arguments
+/// * `first` - The region above or to the left
+/// * `second` - The region below or to the right
+/// * `rng` - Random source for the position along the wall
+fn opening_between(first: &Region, second: &Region, rng: &mut StdRng) -> Edge {
+    if first.col + first.width == second.col {
+        let rows =
+            first.row.max(second.row)..(first.row + first.height).min(second.row + second.height);
+        let row = rng.gen_range(rows);
+        Edge::new(Cell::new(row, second.col - 1), Cell::new(row, second.col))
+    } else {
+        let cols =
+            first.col.max(second.col)..(first.col + first.width).min(second.col + second.width);
+        let col = rng.gen_range(cols);
+        Edge::new(Cell::new(second.row - 1, col), Cell::new(second.row, col))
+    }
+}
+
+/// Checks that the passages form a perfect maze and fixes them if not.
+///
+/// Drops any passage that would close a loop, then joins any piece still cut off from the
+/// rest through the first wall, in row-major order, that separates it from another piece.
+///
+/// # Arguments
+/// * `width` - Maze width in cells
+/// * `height` - Maze height in cells
+/// * `passages` - The passages to check, fixed in place
+///
+/// # Returns
+/// How many passages were dropped or added
+fn repair_connectivity(width: usize, height: usize, passages: &mut Vec<Edge>) -> usize {
+    let mut union_find = UnionFind::new();
+    for row in 0..height {
+        for col in 0..width {
+            union_find.make_set(Cell::new(row, col));
+        }
+    }
+    let before = passages.len();
+    passages.retain(|edge| union_find.union(edge.cell1, edge.cell2));
+    let mut repaired = before - passages.len();
+
+    for row in 0..height {
+        for col in 0..width {
+            let cell = Cell::new(row, col);
+            for neighbor in [Cell::new(row, col + 1), Cell::new(row + 1, col)] {
+                if neighbor.row < height && neighbor.col < width && union_find.union(cell, neighbor)
+                {
+                    passages.push(Edge::new(cell, neighbor));
+                    repaired += 1;
+                }
+            }
+        }
+    }
+    repaired
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::maze::generator::MazeGenerator;
+
+    fn plan_on_threads(threads: usize, width: usize, height: usize, seed: u64) -> DividedPlan {
+        rayon::ThreadPoolBuilder::new()
+            .num_threads(threads)
+            .build()
+            .expect("Failed to build thread pool")
+            .install(|| plan_divided(width, height, seed))
+    }
+
+    #[test]
+    fn test_same_seed_same_maze_on_any_number_of_threads() {
+        let single = plan_on_threads(1, 101, 101, 7);
+        for threads in [2, 3, 8] {
+            assert_eq!(plan_on_threads(threads, 101, 101, 7), single);
+        }
+        assert_ne!(plan_on_threads(4, 101, 101, 8).passages, single.passages);
+
+        // The generator replays the plan into the same walls and exit every time
+        let generate = || {
+            let (mut generator, maze) = MazeGenerator::divided(64, 40, 99);
+            while !generator.is_complete() {
+                generator.step();
+            }
+            let maze = maze.lock().expect("Failed to lock maze");
+            (maze.walls.clone(), maze.exit_cell)
+        };
+        let pool = |threads| {
+            rayon::ThreadPoolBuilder::new()
+                .num_threads(threads)
+                .build()
+                .expect("Failed to build thread pool")
+        };
+        assert_eq!(pool(1).install(generate), pool(6).install(generate));
+    }
+
+    #[test]
+    fn test_plans_are_perfect_mazes() {
+        for (width, height) in [(1, 1), (1, 40), (40, 1), (17, 17), (33, 20), (101, 101)] {
+            let plan = plan_divided(width, height, 3);
+            assert_eq!(plan.repaired, 0, "{}x{}", width, height);
+            // A spanning tree: one fewer passage than cells, and nothing left apart
+            assert_eq!(plan.passages.len(), width * height - 1);
+            let mut union_find = UnionFind::new();
+            for row in 0..height {
+                for col in 0..width {
+                    union_find.make_set(Cell::new(row, col));
+                }
+            }
+            for edge in &plan.passages {
+                assert!(union_find.union(edge.cell1, edge.cell2));
+                let distance = edge.cell1.row.abs_diff(edge.cell2.row)
+                    + edge.cell1.col.abs_diff(edge.cell2.col);
+                assert_eq!(distance, 1);
+            }
+
+            // Leaves tile the grid without overlapping and stay small
+            let area: usize = plan.regions.iter().map(|r| r.width * r.height).sum();
+            assert_eq!(area, width * height);
+            assert!(
+                plan.regions
+                    .iter()
+                    .all(|r| r.width <= MAX_REGION_SIDE && r.height <= MAX_REGION_SIDE)
+            );
+        }
+
+        // A broken plan is put right
+        let mut passages = plan_divided(20, 20, 5).passages;
+        passages.remove(10);
+        passages.push(passages[0]);
+        assert_eq!(repair_connectivity(20, 20, &mut passages), 2);
+        assert_eq!(passages.len(), 399);
+    }
+
+    #[test]
+    fn test_regions_fill_in_one_after_another() {
+        let (mut generator, _maze) = MazeGenerator::divided(50, 50, 11);
+        let plan = generator
+            .divided_plan()
+            .expect("A divided generator has a plan")
+            .clone();
+        assert_eq!(plan.regions.len(), 16);
+
+        let mut last = (0, plan.regions.len());
+        while !generator.is_complete() {
+            let before = generator.get_progress().0;
+            if generator.step() {
+                // Every carve inside a region belongs to the first unfinished one
+                let edge = plan.passages[before];
+                let region = plan.regions_complete(before);
+                if region < plan.regions.len() {
+                    assert!(plan.regions[region].contains(edge.cell1));
+                    assert!(plan.regions[region].contains(edge.cell2));
+                }
+            }
+            let progress = generator.regions_progress();
+            assert!(progress.0 >= last.0);
+            last = progress;
+        }
+        assert_eq!(last, (16, 16));
+    }
+}
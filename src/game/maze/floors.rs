@@ -0,0 +1,533 @@
+//! Two-floor mazes joined by stairwells.
+//!
+//! From [`MULTI_FLOOR_MIN_LEVEL`] on, a level is two mazes of the same size stacked one
+//! [`FloorPlan::floor_height`] apart. A few [`Stairwell`] cells are shared by both floors: on the
+//! lower floor a stairwell only opens toward its low side, on the upper floor only toward its
+//! high side, and a ramp inside the cell climbs from one floor to the other. The player starts
+//! downstairs and the exit is always upstairs.
+//!
+//! Everything here works in wall-grid cells, the same coordinates the collision system and the
+//! level geometry use, except [`Stairwell::cell`], which is in maze cells like the generator.
+//!
+//! # Usage
+//!
+//! ```rust,ignore
+//! let (stairwells, upper_seed) = plan_floors(25, 25, seed);
+//! let (lower, _) = MazeGenerator::for_floor(25, 25, seed, &stairwells, Layer::Lower);
+//! let (upper, _) = MazeGenerator::for_floor(25, 25, upper_seed, &stairwells, Layer::Upper);
+//!
+//! // Once both have finished generating
+//! let plan = FloorPlan::new(lower_walls, upper_walls, stairwells, false);
+//! let (layer, ground) = plan.ground(player.position, player.layer);
+//! ```
+
+use crate::game::maze::generator::Cell;
+use crate::math::coordinates::{self, Direction};
+use rand::prelude::*;
+use std::collections::VecDeque;
+
+/// First level that is built as two floors.
+pub const MULTI_FLOOR_MIN_LEVEL: i32 = 6;
+
+/// Fewest stairwells a two-floor level gets.
+pub const MIN_STAIRWELLS: usize = 2;
+
+/// Most stairwells a two-floor level gets.
+pub const MAX_STAIRWELLS: usize = 3;
+
+/// Minimum Manhattan distance in maze cells between two stairwells.
+const MIN_STAIRWELL_SPACING: usize = 3;
+
+/// Mixed into the level seed so stairwell placement doesn't mirror the lower floor's edge order.
+const FLOOR_SEED_SALT: u64 = 0x5354_4149_5257_454C;
+
+/// Returns whether a level is built as two floors.
+///
+/// # Arguments
+/// * `level` - The level being generated (1-based)
+/// * `is_test_mode` - Test mode always uses a single floor
+pub fn is_multi_floor_level(level: i32, is_test_mode: bool) -> bool {
+    !is_test_mode && level >= MULTI_FLOOR_MIN_LEVEL
+}
+
+/// One floor of a two-floor level.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Layer {
+    /// The ground floor, where the player spawns; single-floor levels only have this one
+    #[default]
+    Lower,
+    /// The floor above, which holds the exit
+    Upper,
+}
+
+impl Layer {
+    /// Returns `0` for the lower floor and `1` for the upper floor.
+    pub fn index(self) -> usize {
+        self as usize
+    }
+
+    /// Returns the floor a stairwell leads to from this one.
+    pub fn other(self) -> Self {
+        match self {
+            Layer::Lower => Layer::Upper,
+            Layer::Upper => Layer::Lower,
+        }
+    }
+}
+
+/// A maze cell with a ramp joining the two floors.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stairwell {
+    /// The maze cell (not wall-grid cell) holding the ramp; never on the maze's border
+    pub cell: Cell,
+    /// Direction the ramp climbs toward
+    pub rise: Direction,
+}
+
+impl Stairwell {
+    /// Returns the stairwell's cell in wall-grid coordinates.
+    pub fn grid_cell(&self) -> Cell {
+        Cell::new(self.cell.row * 2 + 1, self.cell.col * 2 + 1)
+    }
+
+    /// Returns the maze cell the stairwell opens into on a floor.
+    ///
+    /// That is the cell at the foot of the ramp on the lower floor and the cell at its top
+    /// on the upper floor.
+    pub fn opening(&self, layer: Layer) -> Cell {
+        let direction = match layer {
+            Layer::Lower => coordinates::opposite_direction(self.rise),
+            Layer::Upper => self.rise,
+        };
+        let Cell { row, col } = self.cell;
+        // Stairwells are never on the border, so every neighbor exists
+        match direction {
+            Direction::North => Cell::new(row - 1, col),
+            Direction::South => Cell::new(row + 1, col),
+            Direction::East => Cell::new(row, col + 1),
+            Direction::West => Cell::new(row, col - 1),
+        }
+    }
+}
+
+/// Picks where the stairwells of a two-floor level go.
+///
+/// Stairwells are kept off the maze's border, so both ends of every ramp lead somewhere, and
+/// at least [`MIN_STAIRWELL_SPACING`] cells apart, so they never share a neighbor.
+///
+/// # Arguments
+/// * `width` - Maze width in cells
+/// * `height` - Maze height in cells
+/// * `rng` - Random source, so seeded levels always get the same stairwells
+///
+/// # Returns
+/// Between [`MIN_STAIRWELLS`] and [`MAX_STAIRWELLS`] stairwells, or fewer if the maze is too
+/// small to fit them
+pub fn choose_stairwells(width: usize, height: usize, rng: &mut impl Rng) -> Vec<Stairwell> {
+    if width < 3 || height < 3 {
+        return Vec::new();
+    }
+
+    let count = rng.gen_range(MIN_STAIRWELLS..=MAX_STAIRWELLS);
+    let directions = [
+        Direction::North,
+        Direction::East,
+        Direction::South,
+        Direction::West,
+    ];
+    let mut stairwells: Vec<Stairwell> = Vec::with_capacity(count);
+
+    // Bounded so a maze too small for every stairwell still finishes
+    for _ in 0..count * 50 {
+        if stairwells.len() == count {
+            break;
+        }
+        let cell = Cell::new(rng.gen_range(1..height - 1), rng.gen_range(1..width - 1));
+        let spaced = stairwells.iter().all(|other| {
+            other.cell.row.abs_diff(cell.row) + other.cell.col.abs_diff(cell.col)
+                >= MIN_STAIRWELL_SPACING
+        });
+        if spaced {
+            let rise = directions[rng.gen_range(0..directions.len())];
+            stairwells.push(Stairwell { cell, rise });
+        }
+    }
+
+    stairwells
+}
+
+/// Chooses the stairwells and the upper floor's seed for a two-floor level.
+///
+/// # Arguments
+/// * `width` - Maze width in cells
+/// * `height` - Maze height in cells
+/// * `seed` - The level's seed, which the lower floor is generated from
+///
+/// # Returns
+/// The stairwells and the seed to generate the upper floor with
+pub fn plan_floors(width: usize, height: usize, seed: u64) -> (Vec<Stairwell>, u64) {
+    let mut rng = StdRng::seed_from_u64(seed ^ FLOOR_SEED_SALT);
+    let stairwells = choose_stairwells(width, height, &mut rng);
+    (stairwells, rng.next_u64())
+}
+
+/// The finished layout of a two-floor level.
+#[derive(Debug, Clone)]
+pub struct FloorPlan {
+    /// Wall grids of the lower and upper floor, indexed by [`Layer::index`]
+    pub grids: [Vec<Vec<bool>>; 2],
+    /// Ramps joining the floors
+    pub stairwells: Vec<Stairwell>,
+    /// How far the upper floor sits above the lower one; the internal wall height
+    pub floor_height: f32,
+    /// Whether the level uses test mode's smaller floor
+    pub is_test_mode: bool,
+}
+
+impl FloorPlan {
+    /// Creates the floor plan of a level from its two generated floors.
+    ///
+    /// # Arguments
+    /// * `lower` - Wall grid of the lower floor
+    /// * `upper` - Wall grid of the upper floor, the same size as `lower`
+    /// * `stairwells` - Stairwells both floors were generated around
+    /// * `is_test_mode` - Whether the level uses test mode's smaller floor
+    pub fn new(
+        lower: Vec<Vec<bool>>,
+        upper: Vec<Vec<bool>>,
+        stairwells: Vec<Stairwell>,
+        is_test_mode: bool,
+    ) -> Self {
+        let maze_dimensions = (lower[0].len(), lower.len());
+        Self {
+            floor_height: coordinates::calculate_cell_size(maze_dimensions, is_test_mode),
+            grids: [lower, upper],
+            stairwells,
+            is_test_mode,
+        }
+    }
+
+    /// Returns the wall grid of one floor.
+    pub fn grid(&self, layer: Layer) -> &[Vec<bool>] {
+        &self.grids[layer.index()]
+    }
+
+    /// Returns the wall grid dimensions (width, height) shared by both floors.
+    pub fn maze_dimensions(&self) -> (usize, usize) {
+        (self.grids[0][0].len(), self.grids[0].len())
+    }
+
+    /// Returns the world height of a floor's surface.
+    pub fn base_height(&self, layer: Layer) -> f32 {
+        layer.index() as f32 * self.floor_height
+    }
+
+    /// Finds the stairwell occupying a wall-grid cell, if any.
+    pub fn stairwell_at(&self, grid_cell: Cell) -> Option<&Stairwell> {
+        self.stairwells
+            .iter()
+            .find(|stairwell| stairwell.grid_cell() == grid_cell)
+    }
+
+    /// Returns how far up a stairwell's ramp a position is.
+    ///
+    /// # Returns
+    /// `0.0` at the foot of the ramp and `1.0` at its top, clamped to the cell
+    pub fn ramp_progress(&self, stairwell: &Stairwell, position: [f32; 3]) -> f32 {
+        let center = coordinates::maze_to_world(
+            &stairwell.grid_cell(),
+            self.maze_dimensions(),
+            0.0,
+            self.is_test_mode,
+        );
+        let along = match stairwell.rise {
+            Direction::East => position[0] - center[0],
+            Direction::West => center[0] - position[0],
+            Direction::South => position[2] - center[2],
+            Direction::North => center[2] - position[2],
+        };
+        // The ramp spans one cell and climbs one floor, so the cell size is the floor height
+        (along / self.floor_height + 0.5).clamp(0.0, 1.0)
+    }
+
+    /// Works out the floor and ground height under a position.
+    ///
+    /// Off the stairwells this is just the surface of the current floor. On a ramp the ground
+    /// follows the slope, and whoever is standing on it belongs to the upper floor from
+    /// halfway up, which is where the collision walls of the two floors hand over.
+    ///
+    /// # Arguments
+    /// * `position` - World position to test
+    /// * `layer` - Floor the walker was on last
+    ///
+    /// # Returns
+    /// The walker's floor and the world height of the ground beneath them
+    pub fn ground(&self, position: [f32; 3], layer: Layer) -> (Layer, f32) {
+        let cell = coordinates::world_to_maze(position, self.maze_dimensions(), self.is_test_mode);
+        match self.stairwell_at(cell) {
+            Some(stairwell) => {
+                let progress = self.ramp_progress(stairwell, position);
+                let layer = if progress >= 0.5 {
+                    Layer::Upper
+                } else {
+                    Layer::Lower
+                };
+                (layer, progress * self.floor_height)
+            }
+            None => (layer, self.base_height(layer)),
+        }
+    }
+
+    /// Finds the shortest walk between two cells, climbing stairwells as needed.
+    ///
+    /// Moving between floors is one step: from a stairwell's cell on one floor to the same
+    /// cell on the other.
+    ///
+    /// # Arguments
+    /// * `from` - Wall-grid cell and floor the walk starts on
+    /// * `to` - Wall-grid cell and floor the walk ends on
+    ///
+    /// # Returns
+    /// Every cell on the walk including both ends, or `None` if either end is a wall,
+    /// outside the grid, or unreachable
+    pub fn route(&self, from: (Cell, Layer), to: (Cell, Layer)) -> Option<Vec<(Cell, Layer)>> {
+        let (width, height) = self.maze_dimensions();
+        let is_open = |(cell, layer): (Cell, Layer)| {
+            cell.row < height && cell.col < width && !self.grid(layer)[cell.row][cell.col]
+        };
+        if !is_open(from) || !is_open(to) {
+            return None;
+        }
+
+        let index = |(cell, layer): (Cell, Layer)| {
+            layer.index() * width * height + cell.row * width + cell.col
+        };
+        let mut previous: Vec<Option<(Cell, Layer)>> = vec![None; 2 * width * height];
+        let mut queue = VecDeque::from([from]);
+        previous[index(from)] = Some(from);
+
+        while let Some(node) = queue.pop_front() {
+            if node == to {
+                let mut path = vec![node];
+                let mut current = node;
+                while current != from {
+                    current = previous[index(current)]?;
+                    path.push(current);
+                }
+                path.reverse();
+                return Some(path);
+            }
+
+            let (cell, layer) = node;
+            let mut neighbors = vec![
+                (Cell::new(cell.row.wrapping_sub(1), cell.col), layer),
+                (Cell::new(cell.row + 1, cell.col), layer),
+                (Cell::new(cell.row, cell.col.wrapping_sub(1)), layer),
+                (Cell::new(cell.row, cell.col + 1), layer),
+            ];
+            if self.stairwell_at(cell).is_some() {
+                neighbors.push((cell, layer.other()));
+            }
+
+            for next in neighbors {
+                if is_open(next) && previous[index(next)].is_none() {
+                    previous[index(next)] = Some(node);
+                    queue.push_back(next);
+                }
+            }
+        }
+        None
+    }
+
+    /// Finds the length of the shortest walk from the entrance to the exit.
+    ///
+    /// # Arguments
+    /// * `entrance` - Wall-grid cell on the lower floor the player enters at
+    /// * `exit` - Wall-grid cell of the exit on the upper floor
+    ///
+    /// # Returns
+    /// The number of steps, or `None` if the exit cannot be reached
+    pub fn solution_length(&self, entrance: Cell, exit: Cell) -> Option<usize> {
+        self.route((entrance, Layer::Lower), (exit, Layer::Upper))
+            .map(|path| path.len() - 1)
+    }
+
+    /// Returns whether a position is inside one of the stairwells.
+    pub fn on_ramp(&self, position: [f32; 3]) -> bool {
+        let cell = coordinates::world_to_maze(position, self.maze_dimensions(), self.is_test_mode);
+        self.stairwell_at(cell).is_some()
+    }
+
+    /// Picks the next point to head for when chasing something on the other floor.
+    ///
+    /// # Arguments
+    /// * `from` - World position of the chaser
+    /// * `from_layer` - Floor the chaser is on
+    /// * `to` - World position of the target
+    /// * `to_layer` - Floor the target is on
+    ///
+    /// # Returns
+    /// The center of the next cell toward the target at the chaser's height, or `None` if both
+    /// are on the same floor or there is no walkable route
+    pub fn waypoint(
+        &self,
+        from: [f32; 3],
+        from_layer: Layer,
+        to: [f32; 3],
+        to_layer: Layer,
+    ) -> Option<[f32; 3]> {
+        if from_layer == to_layer {
+            return None;
+        }
+        let maze_dimensions = self.maze_dimensions();
+        let start = coordinates::world_to_maze(from, maze_dimensions, self.is_test_mode);
+        let goal = coordinates::world_to_maze(to, maze_dimensions, self.is_test_mode);
+        let path = self.route((start, from_layer), (goal, to_layer))?;
+
+        // Changing floors happens on the ramp itself, so skip past the stairwell's own cell
+        let (cell, _) = path.iter().find(|(cell, _)| *cell != start)?;
+        Some(coordinates::maze_to_world(
+            cell,
+            maze_dimensions,
+            from[1],
+            self.is_test_mode,
+        ))
+    }
+
+    /// Finds the stairwell closest to a position as the crow flies.
+    ///
+    /// # Returns
+    /// The (x, z) world position of the stairwell's center, or `None` if there are none
+    pub fn nearest_stairwell(&self, position: [f32; 3]) -> Option<(f32, f32)> {
+        self.stairwells
+            .iter()
+            .map(|stairwell| {
+                let center = coordinates::maze_to_world(
+                    &stairwell.grid_cell(),
+                    self.maze_dimensions(),
+                    0.0,
+                    self.is_test_mode,
+                );
+                (center[0], center[2])
+            })
+            .min_by(|a, b| {
+                let distance = |(x, z): (f32, f32)| (x - position[0]).hypot(z - position[2]);
+                distance(*a).total_cmp(&distance(*b))
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::maze::generator::MazeGenerator;
+
+    fn generate_plan(seed: u64) -> (FloorPlan, Cell) {
+        let (stairwells, upper_seed) = plan_floors(9, 7, seed);
+        let mut floors = [Layer::Lower, Layer::Upper].map(|layer| {
+            let seed = if layer == Layer::Lower {
+                seed
+            } else {
+                upper_seed
+            };
+            let (mut generator, maze) = MazeGenerator::for_floor(9, 7, seed, &stairwells, layer);
+            while !generator.is_complete() {
+                generator.step();
+            }
+            let maze = maze.lock().expect("Failed to lock maze");
+            (maze.walls.clone(), maze.exit_cell)
+        });
+        assert_eq!(floors[0].1, None, "The lower floor has no exit");
+        let exit = floors[1].1.expect("The upper floor has an exit");
+        let upper = std::mem::take(&mut floors[1].0);
+        let lower = std::mem::take(&mut floors[0].0);
+        (
+            FloorPlan::new(lower, upper, stairwells, false),
+            Cell::new(exit.row * 2 + 1, exit.col * 2 + 1),
+        )
+    }
+
+    #[test]
+    fn test_stairwells_are_interior_and_spaced() {
+        for seed in 0..20 {
+            let (stairwells, _) = plan_floors(25, 25, seed);
+            assert!((MIN_STAIRWELLS..=MAX_STAIRWELLS).contains(&stairwells.len()));
+            for (i, a) in stairwells.iter().enumerate() {
+                assert!((1..24).contains(&a.cell.row) && (1..24).contains(&a.cell.col));
+                for b in &stairwells[i + 1..] {
+                    let distance =
+                        a.cell.row.abs_diff(b.cell.row) + a.cell.col.abs_diff(b.cell.col);
+                    assert!(distance >= MIN_STAIRWELL_SPACING);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_exit_is_reached_by_climbing_a_stairwell() {
+        for seed in 0..10 {
+            let (plan, exit) = generate_plan(seed);
+            let entrance = Cell::new(plan.grids[0].len() - 2, 1);
+            let path = plan
+                .route((entrance, Layer::Lower), (exit, Layer::Upper))
+                .expect("Exit is reachable from the entrance");
+            assert_eq!(plan.solution_length(entrance, exit), Some(path.len() - 1));
+
+            // Floors only change on a stairwell, each opening to its own side
+            for pair in path.windows(2) {
+                let ((from, from_layer), (to, to_layer)) = (pair[0], pair[1]);
+                if from_layer != to_layer {
+                    assert_eq!(from, to);
+                    assert!(plan.stairwell_at(from).is_some());
+                }
+            }
+            for stairwell in &plan.stairwells {
+                let grid_cell = stairwell.grid_cell();
+                for layer in [Layer::Lower, Layer::Upper] {
+                    let open_sides = [(0, 1), (2, 1), (1, 0), (1, 2)]
+                        .iter()
+                        .filter(|(dr, dc)| {
+                            !plan.grid(layer)[grid_cell.row + dr - 1][grid_cell.col + dc - 1]
+                        })
+                        .count();
+                    assert_eq!(open_sides, 1, "A stairwell opens one way per floor");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_ramp_ground_height() {
+        let (plan, _) = generate_plan(3);
+        let stairwell = plan.stairwells[0];
+        let center =
+            coordinates::maze_to_world(&stairwell.grid_cell(), plan.maze_dimensions(), 0.0, false);
+        let (dx, dz) = match stairwell.rise {
+            Direction::East => (1.0, 0.0),
+            Direction::West => (-1.0, 0.0),
+            Direction::South => (0.0, 1.0),
+            Direction::North => (0.0, -1.0),
+        };
+        let along = |t: f32| {
+            let offset = (t - 0.5) * plan.floor_height;
+            [center[0] + dx * offset, 0.0, center[2] + dz * offset]
+        };
+
+        let (layer, height) = plan.ground(along(0.1), Layer::Lower);
+        assert_eq!(layer, Layer::Lower);
+        assert!((height - 0.1 * plan.floor_height).abs() < 1e-3);
+
+        let (layer, height) = plan.ground(along(0.9), Layer::Lower);
+        assert_eq!(layer, Layer::Upper);
+        assert!((height - 0.9 * plan.floor_height).abs() < 1e-3);
+
+        // Off the ramp the ground is the current floor
+        let elsewhere =
+            coordinates::maze_to_world(&Cell::new(1, 1), plan.maze_dimensions(), 0.0, false);
+        assert_eq!(
+            plan.ground(elsewhere, Layer::Upper),
+            (Layer::Upper, plan.floor_height)
+        );
+        assert_eq!(plan.ground(elsewhere, Layer::Lower), (Layer::Lower, 0.0));
+    }
+}
@@ -5,7 +5,7 @@
 //!
 //! # Examples
 //!
-//! ```rust
+//! ```rust,ignore
 //! use maze_generator::{MazeGenerator, Cell};
 //!
 //! // Create a 10x10 maze generator
@@ -16,22 +16,30 @@
 //!     generator.step();
 //! }
 //!
-//! // Save the maze to a file
-//! maze.lock().unwrap().save_to_file().expect("Failed to save maze");
+//! // Build the level from the finished maze
+//! let (maze_grid, exit_cell) = maze.lock().unwrap().level_grid();
+//!
+//! // Replay how it was carved, and where it leads
+//! let order = maze.lock().unwrap().carve_order();
+//! let path = generator.solution_path();
 //! ```
+use crate::game::levels::MazeConfig;
+use crate::game::maze::division::{DividedPlan, plan_divided};
+use crate::game::maze::find_path;
+use crate::game::maze::floors::{Layer, Stairwell};
 use chrono::Local;
 use rand::prelude::*;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::collections::HashSet;
 use std::collections::hash_map::Entry;
 use std::fs;
-use std::io::Write;
 use std::path::Path;
 use std::path::PathBuf;
 use std::sync::{Arc, Mutex};
 
 /// Represents a cell in the maze grid
-#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct Cell {
     /// Row index of the cell
     pub row: usize,
@@ -47,7 +55,7 @@ impl Cell {
 }
 
 /// Represents an edge between two cells in the maze
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct Edge {
     /// First cell connected by the edge
     pub cell1: Cell,
@@ -126,6 +134,25 @@ impl UnionFind {
     }
 }
 
+/// How one entry of the maze's wall grid looks on the loading screen preview.
+///
+/// The discriminants are the bytes uploaded to the GPU preview grid, so they must
+/// stay in sync with the tile constants in `2D-maze-shader.wgsl`.
+#[repr(u8)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PreviewTile {
+    /// A wall that has not been carved
+    Wall = 0,
+    /// A carved passage between two cells, or a wall-grid corner that is open
+    Passage = 1,
+    /// A cell that has not been joined to any other cell yet
+    UnconnectedCell = 2,
+    /// A cell that has been joined to at least one neighbor
+    ConnectedCell = 3,
+    /// The maze exit
+    Exit = 4,
+}
+
 /// Represents a maze with walls and passages
 #[derive(Clone)]
 pub struct Maze {
@@ -141,12 +168,20 @@ pub struct Maze {
     pub processed_edges: usize,
     /// Exit cell of the maze (if set)
     pub exit_cell: Option<Cell>,
+    /// Step each wall grid entry was carved at, shaped like `walls`
+    ///
+    /// `None` for walls and for cells not yet joined to a neighbor. A passage shares its
+    /// step with any cell it joins for the first time.
+    pub carve_steps: Vec<Vec<Option<u32>>>,
+    /// Number of passages carved so far, which is the step the next one is stamped with
+    pub carved_passages: u32,
 }
 
 impl Maze {
     /// Creates a new maze with all walls present
     pub fn new(width: usize, height: usize) -> Self {
         let walls = vec![vec![true; width * 2 + 1]; height * 2 + 1];
+        let carve_steps = vec![vec![None; width * 2 + 1]; height * 2 + 1];
         Self {
             width,
             height,
@@ -154,19 +189,156 @@ impl Maze {
             total_edges: 0,
             processed_edges: 0,
             exit_cell: None,
+            carve_steps,
+            carved_passages: 0,
         }
     }
 
+    /// Removes the wall between two neighboring cells and stamps the carve step
+    ///
+    /// # Arguments
+    /// * `cell1` - One cell of the pair
+    /// * `cell2` - The cell next to it
+    pub fn carve_passage(&mut self, cell1: Cell, cell2: Cell) {
+        let step = self.carved_passages;
+        let wall_row = cell1.row + cell2.row + 1;
+        let wall_col = cell1.col + cell2.col + 1;
+        self.walls[wall_row][wall_col] = false;
+        self.carve_steps[wall_row][wall_col] = Some(step);
+        for cell in [cell1, cell2] {
+            self.carve_steps[cell.row * 2 + 1][cell.col * 2 + 1].get_or_insert(step);
+        }
+        self.carved_passages += 1;
+    }
+
+    /// Returns every carved entry of the wall grid in the order it was carved
+    ///
+    /// Entries carved in the same step, a passage and the cells it joined, come in
+    /// row-major order.
+    ///
+    /// # Returns
+    /// Wall grid coordinates (`row * 2 + 1`, `col * 2 + 1` for cells), earliest first
+    pub fn carve_order(&self) -> Vec<Cell> {
+        let mut carved: Vec<(u32, Cell)> = self
+            .carve_steps
+            .iter()
+            .enumerate()
+            .flat_map(|(row, steps)| {
+                steps
+                    .iter()
+                    .enumerate()
+                    .filter_map(move |(col, step)| step.map(|step| (step, Cell::new(row, col))))
+            })
+            .collect();
+        carved.sort_by_key(|&(step, _)| step);
+        carved.into_iter().map(|(_, cell)| cell).collect()
+    }
+
+    /// Returns the cell the player enters the maze at, the bottom-left corner
+    pub fn entrance(&self) -> Cell {
+        Cell::new(self.height.saturating_sub(1), 0)
+    }
+
+    /// Finds the shortest path from a cell to the exit
+    ///
+    /// # Arguments
+    /// * `from` - The maze cell (not wall grid cell) to start from
+    ///
+    /// # Returns
+    /// Every wall grid entry on the path, cells and the passages between them, starting at
+    /// `from`, or `None` if there is no exit or it can't be reached
+    pub fn solution_path(&self, from: Cell) -> Option<Vec<Cell>> {
+        let exit = self.exit_cell?;
+        find_path(
+            &self.walls,
+            Cell::new(from.row * 2 + 1, from.col * 2 + 1),
+            Cell::new(exit.row * 2 + 1, exit.col * 2 + 1),
+        )
+    }
+
     /// Sets a random cell as the exit
-    pub fn set_random_exit(&mut self) {
-        let mut rng = thread_rng();
-        let row = rng.gen_range(0..self.height);
-        let col = rng.gen_range(0..self.width);
-        self.exit_cell = Some(Cell::new(row, col));
+    ///
+    /// # Arguments
+    /// * `rng` - Random source, so seeded generators always pick the same exit
+    pub fn set_random_exit(&mut self, rng: &mut impl Rng) {
+        self.set_random_exit_avoiding(rng, &[]);
+    }
+
+    /// Sets a random cell as the exit, rerolling any cell in `avoid`
+    ///
+    /// # Arguments
+    /// * `rng` - Random source, so seeded generators always pick the same exit
+    /// * `avoid` - Cells the exit must not land on; if that is every cell, no exit is set
+    pub fn set_random_exit_avoiding(&mut self, rng: &mut impl Rng, avoid: &[Cell]) {
+        if avoid.len() >= self.width * self.height {
+            return;
+        }
+        loop {
+            let row = rng.gen_range(0..self.height);
+            let col = rng.gen_range(0..self.width);
+            let cell = Cell::new(row, col);
+            if !avoid.contains(&cell) {
+                self.exit_cell = Some(cell);
+                return;
+            }
+        }
+    }
+
+    /// Classifies one entry of the wall grid for the loading screen preview.
+    ///
+    /// # Arguments
+    /// * `row` - Row in the wall grid
+    /// * `col` - Column in the wall grid
+    /// * `connected` - Cells that have been joined to a neighbor so far
+    pub fn preview_tile(&self, row: usize, col: usize, connected: &HashSet<Cell>) -> PreviewTile {
+        if self.walls[row][col] {
+            return PreviewTile::Wall;
+        }
+        // Cells sit at odd coordinates; everything else open is a carved passage
+        if row.is_multiple_of(2) || col.is_multiple_of(2) {
+            return PreviewTile::Passage;
+        }
+        let cell = Cell::new(row / 2, col / 2);
+        if Some(cell) == self.exit_cell {
+            PreviewTile::Exit
+        } else if connected.contains(&cell) {
+            PreviewTile::ConnectedCell
+        } else {
+            PreviewTile::UnconnectedCell
+        }
+    }
+
+    /// Classifies the whole wall grid for the loading screen preview.
+    ///
+    /// # Arguments
+    /// * `connected` - Cells that have been joined to a neighbor so far
+    ///
+    /// # Returns
+    /// One [`PreviewTile`] byte per wall grid entry, row-major, `(2 * width + 1)` bytes per row
+    pub fn preview_tiles(&self, connected: &HashSet<Cell>) -> Vec<u8> {
+        let mut tiles = Vec::with_capacity(self.walls.len() * self.walls[0].len());
+        for row in 0..self.walls.len() {
+            for col in 0..self.walls[0].len() {
+                tiles.push(self.preview_tile(row, col, connected) as u8);
+            }
+        }
+        tiles
     }
 
     /// Generates pixel data for rendering the maze
+    ///
+    /// Used by the CPU fallback of the loading screen preview.
     pub fn get_render_data(&self, connected: &HashSet<Cell>) -> Vec<u8> {
+        self.get_render_data_with_path(connected, &[])
+    }
+
+    /// Generates pixel data for rendering the maze with a path drawn over it
+    ///
+    /// # Arguments
+    /// * `connected` - Cells that have been joined to a neighbor so far
+    /// * `path` - Wall grid entries to highlight, as from [`Maze::solution_path`]; the exit
+    ///   keeps its own color
+    pub fn get_render_data_with_path(&self, connected: &HashSet<Cell>, path: &[Cell]) -> Vec<u8> {
         let cell_px = 4;
         let wall_px = 1;
         let render_width = self.width * cell_px + (self.width + 1) * wall_px;
@@ -175,7 +347,6 @@ impl Maze {
 
         for row in 0..self.walls.len() {
             for col in 0..self.walls[0].len() {
-                let is_wall = self.walls[row][col];
                 let px_row = row / 2;
                 let px_col = col / 2;
                 let x = px_col * (cell_px + wall_px) + if col % 2 == 0 { 0 } else { wall_px };
@@ -183,22 +354,15 @@ impl Maze {
                 let w = if col % 2 == 0 { wall_px } else { cell_px };
                 let h = if row % 2 == 0 { wall_px } else { cell_px };
 
-                let color = if is_wall {
-                    [0, 0, 0, 255] // wall
-                } else {
-                    // Identify if this is a cell (not wall row/col)
-                    if row % 2 == 1 && col % 2 == 1 {
-                        let cell = Cell::new(row / 2, col / 2);
-                        if Some(cell) == self.exit_cell {
-                            [255, 0, 0, 255] // exit cell = red
-                        } else if connected.contains(&cell) {
-                            [255, 255, 255, 255] // connected cell = white
-                        } else {
-                            [0, 0, 0, 255] // unconnected cell = black
-                        }
-                    } else {
-                        [255, 255, 255, 255] // passage or non-cell = white
+                let color = match self.preview_tile(row, col, connected) {
+                    PreviewTile::Wall | PreviewTile::UnconnectedCell => [0, 0, 0, 255],
+                    PreviewTile::Passage | PreviewTile::ConnectedCell
+                        if path.contains(&Cell::new(row, col)) =>
+                    {
+                        [255, 200, 60, 255]
                     }
+                    PreviewTile::Passage | PreviewTile::ConnectedCell => [255, 255, 255, 255],
+                    PreviewTile::Exit => [255, 0, 0, 255],
                 };
 
                 for dy in 0..h {
@@ -258,35 +422,37 @@ impl Maze {
         (self.width, self.height)
     }
 
-    /// Saves the current maze to a timestamped file in the `src/maze/saved-mazes/generated` directory.
+    /// Returns the maze as a wall grid with its exit, in the form levels are built from.
     ///
-    /// # File Naming
-    /// The output file is named using the current local time in the format:
-    /// `Maze_MM-DD-YY_HH-MMPM.mz` (e.g., `Maze_06-24-25_11-24PM.mz`).
+    /// Gives the same result as writing the maze with [`Maze::to_mz_string`] and parsing it
+    /// back with [`parse_maze_str`](crate::game::maze::parse_maze_str), without the round trip.
+    ///
+    /// # Returns
+    /// A tuple:
+    /// - A copy of the wall grid, where `true` indicates a wall
+    /// - The exit cell in wall grid coordinates (`row * 2 + 1`, `col * 2 + 1`), if one is set
+    ///   on an open cell
+    pub fn level_grid(&self) -> (Vec<Vec<bool>>, Option<Cell>) {
+        let exit_cell = self
+            .exit_cell
+            .map(|exit| Cell::new(exit.row * 2 + 1, exit.col * 2 + 1))
+            .filter(|exit| {
+                self.walls
+                    .get(exit.row)
+                    .and_then(|row| row.get(exit.col))
+                    .is_some_and(|&wall| !wall)
+            });
+        (self.walls.clone(), exit_cell)
+    }
+
+    /// Formats the maze as the text of a `.mz` file.
     ///
     /// # File Format
-    /// The maze is saved as a plain text file, where each cell is represented by either:
+    /// Each row of the wall grid is written on its own line, where each entry is either:
     /// - `#` for a wall cell (`true` in `self.walls`)
     /// - ` `(space) for an open cell (`false` in `self.walls`)
     /// - `*` for the exit cell (if one is set)
     ///
-    /// Each row of the maze is written on a new line, preserving the maze's 2D structure.
-    ///
-    /// # Side Effects
-    /// - Ensures the output directory exists (creates it if necessary).
-    /// - Prints the output file path to stdout upon success.
-    /// - Prints error messages to stderr if directory creation, file creation, or writing fails.
-    ///
-    /// # Returns
-    /// - `Ok(PathBuf)` with the path to the saved file on success.
-    /// - `Err(std::io::Error)` if any I/O operation fails.
-    ///
-    /// # Errors
-    /// Returns an error if:
-    /// - The output directory cannot be created.
-    /// - The file cannot be created.
-    /// - Any write operation fails.
-    ///
     /// # Example Output
     /// ```text
     /// ###################################################
@@ -299,55 +465,50 @@ impl Maze {
     /// # #     # #         # #       # # #     #   #     #
     /// ###################################################
     /// ```
-    ///
-    /// # See Also
-    /// - The generated file can be found in `src/maze/saved-mazes/generated/`.
-    pub fn save_to_file(&self) -> Result<PathBuf, std::io::Error> {
-        let timestamp = Local::now().format("Maze_%m-%d-%y_%I-%M%p.mz").to_string();
-        let output_path = Path::new("src/game/maze/saved-mazes/generated").join(timestamp);
-
-        if let Err(e) = fs::create_dir_all("src/game/maze/saved-mazes/generated") {
-            eprintln!("Failed to create output directory: {}", e);
-            return Err(e);
-        }
-
-        let mut file = match fs::File::create(&output_path) {
-            Ok(f) => f,
-            Err(e) => {
-                eprintln!("Failed to create maze file: {}", e);
-                return Err(e);
-            }
-        };
-
+    pub fn to_mz_string(&self) -> String {
+        let mut contents = String::with_capacity(self.walls.len() * (self.width * 2 + 2));
         for (row_idx, row) in self.walls.iter().enumerate() {
             for (col_idx, &cell) in row.iter().enumerate() {
                 let symbol = if cell {
-                    b'#' // Wall
+                    '#' // Wall
                 } else if row_idx % 2 == 1 && col_idx % 2 == 1 {
                     // This is a maze cell position
-                    let maze_row = row_idx / 2;
-                    let maze_col = col_idx / 2;
-                    let current_cell = Cell::new(maze_row, maze_col);
-
+                    let current_cell = Cell::new(row_idx / 2, col_idx / 2);
                     if Some(current_cell) == self.exit_cell {
-                        b'*' // Exit cell
+                        '*' // Exit cell
                     } else {
-                        b' ' // Regular open cell
+                        ' ' // Regular open cell
                     }
                 } else {
-                    b' ' // Passage
+                    ' ' // Passage
                 };
-
-                if let Err(e) = file.write_all(&[symbol]) {
-                    eprintln!("Failed to write to file: {}", e);
-                    return Err(e);
-                }
-            }
-            if let Err(e) = file.write_all(b"\n") {
-                eprintln!("Failed to write newline: {}", e);
-                return Err(e);
+                contents.push(symbol);
             }
+            contents.push('\n');
         }
+        contents
+    }
+
+    /// Saves the current maze to a timestamped file, for reproducing a level later.
+    ///
+    /// # File Naming
+    /// The output file is named using the current local time in the format:
+    /// `Maze_MM-DD-YY_HH-MMPM.mz` (e.g., `Maze_06-24-25_11-24PM.mz`). The contents are
+    /// [`Maze::to_mz_string`].
+    ///
+    /// # Arguments
+    /// * `dir` - Directory to save into, created if necessary; see
+    ///   [`Paths::saved_mazes_dir`](crate::paths::Paths::saved_mazes_dir)
+    ///
+    /// # Returns
+    /// - `Ok(PathBuf)` with the path to the saved file on success.
+    /// - `Err(std::io::Error)` if the directory or file cannot be created or written.
+    pub fn save_to_file(&self, dir: &Path) -> Result<PathBuf, std::io::Error> {
+        let timestamp = Local::now().format("Maze_%m-%d-%y_%I-%M%p.mz").to_string();
+        let output_path = dir.join(timestamp);
+
+        fs::create_dir_all(dir)?;
+        fs::write(&output_path, self.to_mz_string())?;
 
         println!("Maze saved to: {}", output_path.display());
         if let Some(exit) = self.exit_cell {
@@ -372,15 +533,113 @@ pub struct MazeGenerator {
     pub fast_threshold: usize,
     /// Whether we're in fast mode
     pub fast_mode: bool,
+    /// Seed the edge order was shuffled with; the same seed produces the same layout
+    pub seed: u64,
+    /// Random source seeded with `seed`, used to pick the exit once generation completes
+    rng: StdRng,
+    /// The cell most recently joined to the maze, highlighted on the loading screen
+    ///
+    /// `None` before the first wall is removed and once generation completes.
+    pub carving_front: Option<Cell>,
+    /// Which floor of a two-floor level this generates, or `None` for a single-floor level
+    pub layer: Option<Layer>,
+    /// Stairwells carved around, which the exit never lands on
+    stairwells: Vec<Stairwell>,
+    /// The region plan the edges replay, for generators made by [`MazeGenerator::divided`]
+    divided: Option<DividedPlan>,
 }
 
 impl MazeGenerator {
     /// Creates a new maze generator with the given dimensions
     /// Returns both the generator and a shared reference to the maze
     pub fn new(width: usize, height: usize) -> (Self, Arc<Mutex<Maze>>) {
+        Self::with_seed(width, height, thread_rng().next_u64())
+    }
+
+    /// Creates a maze generator whose layout and exit are fixed by a seed
+    ///
+    /// # Arguments
+    /// * `width` - Maze width in cells
+    /// * `height` - Maze height in cells
+    /// * `seed` - Seed for the edge order and exit; the same seed produces the same maze
+    ///
+    /// # Returns
+    /// Both the generator and a shared reference to the maze
+    pub fn with_seed(width: usize, height: usize, seed: u64) -> (Self, Arc<Mutex<Maze>>) {
+        Self::build(width, height, seed, &[], None, None)
+    }
+
+    /// Creates a maze generator for a maze config
+    ///
+    /// # Arguments
+    /// * `config` - The maze's size, and its seed or `None` for a random maze
+    ///
+    /// # Returns
+    /// Both the generator and a shared reference to the maze
+    pub fn from_config(config: &MazeConfig) -> (Self, Arc<Mutex<Maze>>) {
+        match config.seed {
+            Some(seed) => Self::with_seed(config.width, config.height, seed),
+            None => Self::new(config.width, config.height),
+        }
+    }
+
+    /// Creates a generator that carves a large maze region by region
+    ///
+    /// The maze is planned up front by [`plan_divided`], which carves its regions in
+    /// parallel, and each step replays one passage of the plan, so the loading screen shows
+    /// the regions filling in one after another. The same seed produces the same maze on any
+    /// number of cores.
+    ///
+    /// # Arguments
+    /// * `width` - Maze width in cells
+    /// * `height` - Maze height in cells
+    /// * `seed` - Seed for the regions, the openings between them, and the exit
+    ///
+    /// # Returns
+    /// Both the generator and a shared reference to the maze
+    pub fn divided(width: usize, height: usize, seed: u64) -> (Self, Arc<Mutex<Maze>>) {
+        let plan = plan_divided(width, height, seed);
+        Self::build(width, height, seed, &[], None, Some(plan))
+    }
+
+    /// Creates a generator for one floor of a two-floor level
+    ///
+    /// Each stairwell is walled in except for a single opening, toward the foot of its ramp
+    /// on the lower floor and toward the top on the upper floor, and the rest of the floor is
+    /// carved around it. Only the upper floor gets an exit.
+    ///
+    /// # Arguments
+    /// * `width` - Maze width in cells
+    /// * `height` - Maze height in cells
+    /// * `seed` - Seed for the edge order and exit
+    /// * `stairwells` - Stairwells shared by both floors
+    /// * `layer` - Which floor to generate
+    ///
+    /// # Returns
+    /// Both the generator and a shared reference to the maze
+    pub fn for_floor(
+        width: usize,
+        height: usize,
+        seed: u64,
+        stairwells: &[Stairwell],
+        layer: Layer,
+    ) -> (Self, Arc<Mutex<Maze>>) {
+        Self::build(width, height, seed, stairwells, Some(layer), None)
+    }
+
+    /// Shared setup for [`MazeGenerator::with_seed`], [`MazeGenerator::divided`] and
+    /// [`MazeGenerator::for_floor`]
+    fn build(
+        width: usize,
+        height: usize,
+        seed: u64,
+        stairwells: &[Stairwell],
+        layer: Option<Layer>,
+        divided: Option<DividedPlan>,
+    ) -> (Self, Arc<Mutex<Maze>>) {
         let maze = Arc::new(Mutex::new(Maze::new(width, height)));
         let maze_clone = Arc::clone(&maze);
-        let mut rng = thread_rng();
+        let mut rng = StdRng::seed_from_u64(seed);
         let mut union_find = UnionFind::new();
         let mut edges = Vec::new();
 
@@ -396,27 +655,48 @@ impl MazeGenerator {
             }
         }
 
-        // Generate edges
-        for row in 0..height {
-            for col in 0..width {
-                let current = Cell::new(row, col);
-                if col + 1 < width {
-                    let right = Cell::new(row, col + 1);
-                    edges.push(Edge::new(current, right));
-                }
-                if row + 1 < height {
-                    let bottom = Cell::new(row + 1, col);
-                    edges.push(Edge::new(current, bottom));
+        if let Some(plan) = &divided {
+            // Already in carving order; every edge of the plan is a passage
+            edges = plan.passages.clone();
+        } else {
+            // Generate edges
+            for row in 0..height {
+                for col in 0..width {
+                    let current = Cell::new(row, col);
+                    if col + 1 < width {
+                        let right = Cell::new(row, col + 1);
+                        edges.push(Edge::new(current, right));
+                    }
+                    if row + 1 < height {
+                        let bottom = Cell::new(row + 1, col);
+                        edges.push(Edge::new(current, bottom));
+                    }
                 }
             }
-        }
 
-        edges.shuffle(&mut rng);
+            // Stairwells only connect through the opening carved below
+            let is_stairwell =
+                |cell: &Cell| stairwells.iter().any(|stairwell| stairwell.cell == *cell);
+            edges.retain(|edge| !is_stairwell(&edge.cell1) && !is_stairwell(&edge.cell2));
 
+            edges.shuffle(&mut rng);
+        }
+
+        let mut connected_cells = HashSet::new();
         {
             let mut maze_lock = maze.lock().expect("Failed to lock maze");
             maze_lock.total_edges = edges.len();
             maze_lock.processed_edges = 0;
+
+            if let Some(layer) = layer {
+                for stairwell in stairwells {
+                    let opening = stairwell.opening(layer);
+                    union_find.union(stairwell.cell, opening);
+                    maze_lock.carve_passage(stairwell.cell, opening);
+                    connected_cells.insert(stairwell.cell);
+                    connected_cells.insert(opening);
+                }
+            }
         }
 
         let generator = Self {
@@ -425,9 +705,15 @@ impl MazeGenerator {
             edges,
             current_edge: 0,
             generation_complete: false,
-            connected_cells: HashSet::new(),
+            connected_cells,
             fast_threshold: 800, // Switch to fast mode when 600 edges remain
             fast_mode: false,
+            seed,
+            rng,
+            carving_front: None,
+            layer,
+            stairwells: stairwells.to_vec(),
+            divided,
         };
 
         (generator, maze_clone)
@@ -440,8 +726,13 @@ impl MazeGenerator {
             if !self.generation_complete {
                 // Mark generation as complete and set random exit
                 self.generation_complete = true;
+                self.carving_front = None;
                 let mut maze = self.maze.lock().expect("Failed to lock maze");
-                maze.set_random_exit();
+                // The exit of a two-floor level is always upstairs, off the stairwells
+                if self.layer != Some(Layer::Lower) {
+                    let avoid: Vec<Cell> = self.stairwells.iter().map(|s| s.cell).collect();
+                    maze.set_random_exit_avoiding(&mut self.rng, &avoid);
+                }
             }
             return false;
         }
@@ -458,12 +749,11 @@ impl MazeGenerator {
         maze.processed_edges += 1;
 
         if self.union_find.union(edge.cell1, edge.cell2) {
-            let wall_row = edge.cell1.row + edge.cell2.row + 1;
-            let wall_col = edge.cell1.col + edge.cell2.col + 1;
-            maze.walls[wall_row][wall_col] = false;
+            maze.carve_passage(edge.cell1, edge.cell2);
 
             self.connected_cells.insert(edge.cell1);
             self.connected_cells.insert(edge.cell2);
+            self.carving_front = Some(edge.cell2);
             return true;
         }
 
@@ -488,4 +778,220 @@ impl MazeGenerator {
             self.current_edge as f32 / self.edges.len() as f32
         }
     }
+
+    /// Returns how many regions are fully carved, for generators made by
+    /// [`MazeGenerator::divided`]
+    ///
+    /// # Returns
+    /// A tuple of (regions complete, total regions); a generator that isn't divided counts
+    /// as a single region
+    pub fn regions_progress(&self) -> (usize, usize) {
+        match &self.divided {
+            Some(plan) => (plan.regions_complete(self.current_edge), plan.regions.len()),
+            None => (usize::from(self.generation_complete), 1),
+        }
+    }
+
+    /// Returns the region plan being replayed, if the generator was made by
+    /// [`MazeGenerator::divided`]
+    pub fn divided_plan(&self) -> Option<&DividedPlan> {
+        self.divided.as_ref()
+    }
+
+    /// Returns the shortest way through the finished floor to its exit
+    ///
+    /// Single-floor levels are entered at [`Maze::entrance`] and the upper floor of a
+    /// two-floor level from whichever stairwell is closest to the exit. The lower floor has
+    /// no exit, so it has no path.
+    ///
+    /// # Returns
+    /// Every wall grid entry on the path, entrance first, or `None` until generation is
+    /// complete
+    pub fn solution_path(&self) -> Option<Vec<Cell>> {
+        if !self.generation_complete {
+            return None;
+        }
+        let maze = self.maze.lock().expect("Failed to lock maze");
+        let starts: Vec<Cell> = match self.layer {
+            Some(Layer::Upper) => self.stairwells.iter().map(|s| s.cell).collect(),
+            _ => vec![maze.entrance()],
+        };
+        starts
+            .into_iter()
+            .filter_map(|start| maze.solution_path(start))
+            .min_by_key(Vec::len)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_preview_tiles_track_generation() {
+        let (mut generator, maze) = MazeGenerator::new(3, 2);
+        let tiles = maze
+            .lock()
+            .expect("Failed to lock maze")
+            .preview_tiles(&generator.connected_cells);
+        assert_eq!(tiles.len(), 7 * 5);
+        assert_eq!(tiles[0], PreviewTile::Wall as u8);
+        assert_eq!(tiles[7 + 1], PreviewTile::UnconnectedCell as u8);
+
+        while !generator.is_complete() {
+            generator.step();
+        }
+        assert_eq!(generator.carving_front, None);
+
+        let maze = maze.lock().expect("Failed to lock maze");
+        let tiles = maze.preview_tiles(&generator.connected_cells);
+        let exit = maze.exit_cell.expect("Completed maze has an exit");
+        let exit_index = (exit.row * 2 + 1) * 7 + exit.col * 2 + 1;
+        assert_eq!(tiles[exit_index], PreviewTile::Exit as u8);
+        assert!(!tiles.contains(&(PreviewTile::UnconnectedCell as u8)));
+
+        // A spanning tree of 6 cells removes exactly 5 walls
+        let passages = tiles
+            .iter()
+            .filter(|&&tile| tile == PreviewTile::Passage as u8)
+            .count();
+        assert_eq!(passages, 5);
+    }
+
+    #[test]
+    fn test_same_seed_generates_same_maze() {
+        let generate = |seed| {
+            let (mut generator, maze) = MazeGenerator::with_seed(8, 6, seed);
+            while !generator.is_complete() {
+                generator.step();
+            }
+            let maze = maze.lock().expect("Failed to lock maze");
+            (maze.walls.clone(), maze.exit_cell)
+        };
+
+        assert_eq!(generate(42), generate(42));
+        assert_ne!(generate(42).0, generate(43).0);
+    }
+
+    #[test]
+    fn test_fixed_seed_config_reproduces_its_maze() {
+        let generate = |config: MazeConfig| {
+            let (mut generator, maze) = MazeGenerator::from_config(&config);
+            while !generator.is_complete() {
+                generator.step();
+            }
+            let maze = maze.lock().expect("Failed to lock maze");
+            (maze.walls.clone(), maze.exit_cell)
+        };
+        let config = MazeConfig {
+            width: 9,
+            height: 7,
+            seed: Some(5),
+        };
+
+        let (walls, exit) = generate(config);
+        assert_eq!((walls[0].len(), walls.len()), (19, 15));
+        assert_eq!((walls, exit), generate(config));
+    }
+
+    #[test]
+    fn test_level_grid_matches_the_saved_file() {
+        let (mut generator, maze) = MazeGenerator::with_seed(7, 5, 9);
+        while !generator.is_complete() {
+            generator.step();
+        }
+        let maze = maze.lock().expect("Failed to lock maze");
+
+        let parsed = crate::game::maze::parse_maze_str(&maze.to_mz_string())
+            .expect("Generated mazes always parse");
+        assert_eq!(maze.level_grid(), parsed);
+        assert!(maze.level_grid().1.is_some());
+    }
+
+    #[test]
+    fn test_carve_steps_follow_generation_order() {
+        let (mut generator, maze) = MazeGenerator::with_seed(6, 5, 21);
+        let mut carved = 0;
+        while !generator.is_complete() {
+            if generator.step() {
+                carved += 1;
+                assert_eq!(
+                    maze.lock().expect("Failed to lock maze").carved_passages,
+                    carved
+                );
+            }
+        }
+        let maze = maze.lock().expect("Failed to lock maze");
+        // A spanning tree of 30 cells carves 29 passages, one per step
+        assert_eq!(carved, 29);
+
+        let order = maze.carve_order();
+        assert_eq!(order.len(), 30 + 29);
+        let steps: Vec<u32> = order
+            .iter()
+            .map(|cell| maze.carve_steps[cell.row][cell.col].expect("Carved entries have a step"))
+            .collect();
+        assert!(steps.is_sorted());
+        assert_eq!(steps[0], 0);
+        assert_eq!(*steps.last().unwrap(), 28);
+
+        // A passage is never carved before the cells it joins, and walls have no step
+        for (row, line) in maze.walls.iter().enumerate() {
+            for (col, &wall) in line.iter().enumerate() {
+                let step = maze.carve_steps[row][col];
+                assert_eq!(step.is_none(), wall, "({}, {})", row, col);
+                if !wall && row % 2 != col % 2 {
+                    let (a, b) = if row % 2 == 0 {
+                        ((row - 1, col), (row + 1, col))
+                    } else {
+                        ((row, col - 1), (row, col + 1))
+                    };
+                    assert!(maze.carve_steps[a.0][a.1] <= step);
+                    assert!(maze.carve_steps[b.0][b.1] <= step);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_solution_path_runs_from_entrance_to_exit() {
+        let (mut generator, maze) = MazeGenerator::with_seed(9, 7, 4);
+        assert_eq!(generator.solution_path(), None);
+        while !generator.is_complete() {
+            generator.step();
+        }
+        let path = generator
+            .solution_path()
+            .expect("A finished maze has a path");
+        let maze = maze.lock().expect("Failed to lock maze");
+        let (grid, exit) = maze.level_grid();
+        assert_eq!(path[0], Cell::new(grid.len() - 2, 1));
+        assert_eq!(path.last().copied(), exit);
+        assert!(path.iter().all(|cell| !grid[cell.row][cell.col]));
+        for pair in path.windows(2) {
+            let distance = pair[0].row.abs_diff(pair[1].row) + pair[0].col.abs_diff(pair[1].col);
+            assert_eq!(distance, 1);
+        }
+
+        // Two-floor levels run from the nearest stairwell, and only upstairs
+        let (stairwells, upper_seed) = crate::game::maze::floors::plan_floors(9, 7, 4);
+        let (mut lower, _) = MazeGenerator::for_floor(9, 7, 4, &stairwells, Layer::Lower);
+        let (mut upper, upper_maze) =
+            MazeGenerator::for_floor(9, 7, upper_seed, &stairwells, Layer::Upper);
+        while !lower.is_complete() || !upper.is_complete() {
+            lower.step();
+            upper.step();
+        }
+        assert_eq!(lower.solution_path(), None);
+        let path = upper.solution_path().expect("The upper floor has a path");
+        let upper_maze = upper_maze.lock().expect("Failed to lock maze");
+        assert!(stairwells.iter().any(|stairwell| path[0]
+            == Cell::new(stairwell.cell.row * 2 + 1, stairwell.cell.col * 2 + 1)));
+        for stairwell in &stairwells {
+            let other = upper_maze
+                .solution_path(stairwell.cell)
+                .expect("Every stairwell reaches the exit");
+            assert!(path.len() <= other.len());
+        }
+    }
 }
@@ -1,80 +1,430 @@
 //! Maze Generation, Encoding, and Animation module.
 //!
 //! This module provides maze generation, parsing, and title screen logic.
-//! It includes utilities for reading maze files into a 2D wall representation.
+//! It includes utilities for reading maze files into a 2D wall representation, and a
+//! file watcher used to reload hand-edited mazes in test mode.
 
+pub mod division;
+pub mod floors;
 pub mod generator;
+pub mod watcher;
 
 use self::generator::Cell;
-use std::fs::File;
-use std::io::{BufRead, BufReader};
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, VecDeque};
+use std::fs;
+use std::path::Path;
+
 /// Parses a maze file into a 2D vector of wall booleans and detects the exit cell if marked.
 ///
-/// Each line of the file is read as a row of the maze. Each character is mapped as follows:
-/// - `#` becomes `true` (wall)
-/// - Any other character becomes `false` (open space)
+/// Each line of the file is read as a row of the wall grid, which includes both walls and
+/// spaces: `#` becomes `true` (wall), and ` ` and `*` become `false` (open). The optional exit
+/// cell is marked with `*` and returned in wall grid coordinates.
 ///
-/// The function also detects an optional exit cell, marked with `*`.
-/// The coordinates of the `*` character are interpreted in the wall grid, which includes both walls
-/// and spaces. To convert this to the maze cell coordinates (excluding walls), the function checks:
-/// - The row and column indices of `*` must both be **odd** (i.e., part of a valid cell, not a wall)
-/// - The maze cell coordinate is then computed by halving both indices:
-///   `maze_row = (maze_height - 1) - (row_idx / 2)`, `maze_col = col_idx / 2`
+/// Problems are reported instead of panicking, so a typo in a hand-edited maze is shown to
+/// the designer rather than crashing the game. In addition to I/O errors, the file is
+/// rejected if:
+/// - It is empty or smaller than 3x3
+/// - Its rows are not all the same width (trailing `\r` is ignored)
+/// - It contains characters other than `#`, ` `, and `*`
+/// - It has more than one `*`, or a `*` that is not on a cell (odd row and column)
 ///
 /// # Arguments
 /// * `path` - Path to the maze file to parse.
 ///
 /// # Returns
-/// A tuple:
-/// - A 2D vector (`Vec<Vec<bool>>`) where `true` indicates a wall and `false` an open cell
-/// - An optional `Cell` representing the location of the exit cell, if found (with bottom-left origin)
-///
-/// # Panics
-/// - If the file cannot be opened.
-/// - If any line cannot be read.
-///
-/// # Example
-/// ```text
-/// # # #
-/// # * #
-/// # # #
-/// ```
-/// becomes:
-/// ```ignore
-/// vec![
-///     vec![true, false, true],
-///     vec![true, false, true],
-///     vec![true, false, true],
-/// ]
-/// exit_cell: Some(Cell { row: 0, col: 0 }) // bottom-left origin
-/// ```
-pub fn parse_maze_file(path: &str) -> (Vec<Vec<bool>>, Option<Cell>) {
-    let file = File::open(path).expect("Failed to open maze file");
-    let reader = BufReader::new(file);
-
-    let lines: Vec<String> = reader
-        .lines()
-        .map(|line| line.expect("Failed to read line"))
-        .collect();
-
-    let mut maze_grid = Vec::with_capacity(lines.len());
+/// The wall grid and optional exit cell on success, or a message describing the first
+/// problem found (with a 1-based line and column where applicable)
+pub fn try_parse_maze_file(path: &Path) -> Result<(Vec<Vec<bool>>, Option<Cell>), String> {
+    let contents = fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    parse_maze_str(&contents)
+}
+
+/// Parses maze text in the `.mz` format. See [`try_parse_maze_file`] for the rules.
+///
+/// # Arguments
+/// * `contents` - The full text of a maze file
+///
+/// # Returns
+/// The wall grid and optional exit cell, or a description of the first problem found
+pub fn parse_maze_str(contents: &str) -> Result<(Vec<Vec<bool>>, Option<Cell>), String> {
+    let mut maze_grid: Vec<Vec<bool>> = Vec::new();
     let mut exit_cell = None;
 
-    for (wall_row_idx, line) in lines.into_iter().enumerate() {
-        let mut row = Vec::new();
+    for (wall_row_idx, line) in contents.lines().enumerate() {
+        let line = line.trim_end_matches('\r');
+        let mut row = Vec::with_capacity(line.len());
+
         for (wall_col_idx, c) in line.chars().enumerate() {
-            let is_wall = c == '#';
-            row.push(is_wall);
-
-            // Check for exit marker
-            if c == '*' && wall_row_idx % 2 == 1 && wall_col_idx % 2 == 1 {
-                let maze_row = wall_row_idx;
-                let maze_col = wall_col_idx;
-                exit_cell = Some(Cell::new(maze_row, maze_col));
+            match c {
+                '#' => row.push(true),
+                ' ' => row.push(false),
+                '*' => {
+                    if wall_row_idx % 2 == 0 || wall_col_idx % 2 == 0 {
+                        return Err(format!(
+                            "Line {}, column {}: exit marker '*' must be on a cell (odd row and column)",
+                            wall_row_idx + 1,
+                            wall_col_idx + 1
+                        ));
+                    }
+                    if exit_cell.is_some() {
+                        return Err(format!(
+                            "Line {}, column {}: maze has more than one exit marker '*'",
+                            wall_row_idx + 1,
+                            wall_col_idx + 1
+                        ));
+                    }
+                    exit_cell = Some(Cell::new(wall_row_idx, wall_col_idx));
+                    row.push(false);
+                }
+                other => {
+                    return Err(format!(
+                        "Line {}, column {}: unexpected character {:?} (use '#', ' ', or '*')",
+                        wall_row_idx + 1,
+                        wall_col_idx + 1,
+                        other
+                    ));
+                }
             }
         }
+
+        if let Some(expected) = maze_grid.first().map(Vec::len).filter(|&w| w != row.len()) {
+            return Err(format!(
+                "Line {}: row is {} characters wide, expected {}",
+                wall_row_idx + 1,
+                row.len(),
+                expected
+            ));
+        }
         maze_grid.push(row);
     }
 
-    (maze_grid, exit_cell)
+    if maze_grid.len() < 3 || maze_grid[0].len() < 3 {
+        return Err("Maze must be at least 3x3 characters".to_string());
+    }
+
+    Ok((maze_grid, exit_cell))
+}
+
+/// Finds the length of the shortest walkable path between two cells of the wall grid.
+///
+/// Moves are one grid step up, down, left, or right through open entries.
+///
+/// # Arguments
+/// * `maze_grid` - 2D grid of the maze where `true` marks a wall
+/// * `start` - Grid cell the path starts from
+/// * `goal` - Grid cell the path ends at
+///
+/// # Returns
+/// The number of steps on the shortest path, or `None` if either cell is a wall,
+/// outside the grid, or unreachable
+pub fn solution_length(maze_grid: &[Vec<bool>], start: Cell, goal: Cell) -> Option<usize> {
+    find_path(maze_grid, start, goal).map(|path| path.len() - 1)
+}
+
+/// Finds the shortest walkable path between two cells of the wall grid.
+///
+/// Moves are one grid step up, down, left, or right through open entries.
+///
+/// # Arguments
+/// * `maze_grid` - 2D grid of the maze where `true` marks a wall
+/// * `start` - Grid cell the path starts from
+/// * `goal` - Grid cell the path ends at
+///
+/// # Returns
+/// Every cell on the path including both ends, or `None` if either cell is a wall,
+/// outside the grid, or unreachable
+pub fn find_path(maze_grid: &[Vec<bool>], start: Cell, goal: Cell) -> Option<Vec<Cell>> {
+    let height = maze_grid.len();
+    let width = maze_grid.first().map_or(0, Vec::len);
+    let is_open =
+        |cell: Cell| cell.row < height && cell.col < width && !maze_grid[cell.row][cell.col];
+    if !is_open(start) || !is_open(goal) {
+        return None;
+    }
+
+    let mut previous: Vec<Option<Cell>> = vec![None; width * height];
+    let mut queue = VecDeque::from([start]);
+    previous[start.row * width + start.col] = Some(start);
+
+    while let Some(cell) = queue.pop_front() {
+        if cell == goal {
+            let mut path = vec![cell];
+            let mut current = cell;
+            while current != start {
+                current = previous[current.row * width + current.col]?;
+                path.push(current);
+            }
+            path.reverse();
+            return Some(path);
+        }
+        let neighbors = [
+            (cell.row.wrapping_sub(1), cell.col),
+            (cell.row + 1, cell.col),
+            (cell.row, cell.col.wrapping_sub(1)),
+            (cell.row, cell.col + 1),
+        ];
+        for (row, col) in neighbors {
+            let next = Cell::new(row, col);
+            if is_open(next) && previous[row * width + col].is_none() {
+                previous[row * width + col] = Some(cell);
+                queue.push_back(next);
+            }
+        }
+    }
+    None
+}
+
+/// Finds the cheapest walkable path between two cells of the wall grid, with A*.
+///
+/// Moves are one grid step up, down, left, or right through open entries, as in
+/// [`find_path`]. Each step costs 1 plus whatever `extra_cost` charges for the entry it
+/// steps onto, which lets callers steer paths away from entries without walling them off.
+///
+/// # Arguments
+/// * `maze_grid` - 2D grid of the maze where `true` marks a wall
+/// * `start` - Grid cell the path starts from
+/// * `goal` - Grid cell the path ends at
+/// * `extra_cost` - Extra cost of stepping onto an entry; `|_| 0` gives the shortest path
+///
+/// # Returns
+/// Every cell on the cheapest path including both ends, or `None` if either cell is a wall,
+/// outside the grid, or unreachable
+pub fn find_path_with_cost(
+    maze_grid: &[Vec<bool>],
+    start: Cell,
+    goal: Cell,
+    extra_cost: impl Fn(Cell) -> u32,
+) -> Option<Vec<Cell>> {
+    let height = maze_grid.len();
+    let width = maze_grid.first().map_or(0, Vec::len);
+    let is_open =
+        |cell: Cell| cell.row < height && cell.col < width && !maze_grid[cell.row][cell.col];
+    if !is_open(start) || !is_open(goal) {
+        return None;
+    }
+    // Every step costs at least 1, so the grid distance never overestimates
+    let heuristic = |cell: Cell| (cell.row.abs_diff(goal.row) + cell.col.abs_diff(goal.col)) as u32;
+
+    let index = |cell: Cell| cell.row * width + cell.col;
+    let mut cost = vec![u32::MAX; width * height];
+    let mut previous: Vec<Option<Cell>> = vec![None; width * height];
+    let mut open = BinaryHeap::new();
+    cost[index(start)] = 0;
+    open.push(Reverse((heuristic(start), 0, start.row, start.col)));
+
+    while let Some(Reverse((_, cell_cost, row, col))) = open.pop() {
+        let cell = Cell::new(row, col);
+        if cell_cost > cost[index(cell)] {
+            continue;
+        }
+        if cell == goal {
+            let mut path = vec![cell];
+            let mut current = cell;
+            while current != start {
+                current = previous[index(current)]?;
+                path.push(current);
+            }
+            path.reverse();
+            return Some(path);
+        }
+        let neighbors = [
+            (row.wrapping_sub(1), col),
+            (row + 1, col),
+            (row, col.wrapping_sub(1)),
+            (row, col + 1),
+        ];
+        for (row, col) in neighbors {
+            let next = Cell::new(row, col);
+            if !is_open(next) {
+                continue;
+            }
+            let next_cost = cell_cost.saturating_add(1).saturating_add(extra_cost(next));
+            if next_cost < cost[index(next)] {
+                cost[index(next)] = next_cost;
+                previous[index(next)] = Some(cell);
+                open.push(Reverse((
+                    next_cost.saturating_add(heuristic(next)),
+                    next_cost,
+                    row,
+                    col,
+                )));
+            }
+        }
+    }
+    None
+}
+
+/// Measures how many grid steps every open entry of the wall grid is from one cell.
+///
+/// Moves are one grid step up, down, left, or right through open entries, as in [`find_path`].
+///
+/// # Arguments
+/// * `maze_grid` - 2D grid of the maze where `true` marks a wall
+/// * `start` - Grid cell the distances are measured from
+///
+/// # Returns
+/// A grid the size of `maze_grid` holding each entry's distance from `start`, or `None` for
+/// walls and unreachable entries. Every entry is `None` if `start` is a wall or outside the grid.
+pub fn path_distances(maze_grid: &[Vec<bool>], start: Cell) -> Vec<Vec<Option<usize>>> {
+    let height = maze_grid.len();
+    let width = maze_grid.first().map_or(0, Vec::len);
+    let is_open =
+        |cell: Cell| cell.row < height && cell.col < width && !maze_grid[cell.row][cell.col];
+
+    let mut distances = vec![vec![None; width]; height];
+    if !is_open(start) {
+        return distances;
+    }
+    distances[start.row][start.col] = Some(0);
+    let mut queue = VecDeque::from([(start, 0)]);
+
+    while let Some((cell, distance)) = queue.pop_front() {
+        let neighbors = [
+            (cell.row.wrapping_sub(1), cell.col),
+            (cell.row + 1, cell.col),
+            (cell.row, cell.col.wrapping_sub(1)),
+            (cell.row, cell.col + 1),
+        ];
+        for (row, col) in neighbors {
+            let next = Cell::new(row, col);
+            if is_open(next) && distances[row][col].is_none() {
+                distances[row][col] = Some(distance + 1);
+                queue.push_back((next, distance + 1));
+            }
+        }
+    }
+    distances
+}
+
+/// Finds the dead ends of a maze: open cells with exactly one open neighbor.
+///
+/// Only the cells of the maze can be dead ends, since the passages between them always join
+/// two cells.
+///
+/// # Arguments
+/// * `maze_grid` - 2D grid of the maze where `true` marks a wall
+///
+/// # Returns
+/// Every dead end in wall grid coordinates, in row-major order
+pub fn dead_ends(maze_grid: &[Vec<bool>]) -> Vec<Cell> {
+    let height = maze_grid.len();
+    let width = maze_grid.first().map_or(0, Vec::len);
+    let is_open = |row: usize, col: usize| row < height && col < width && !maze_grid[row][col];
+
+    let mut dead_ends = Vec::new();
+    for row in 0..height {
+        for col in 0..width {
+            if !is_open(row, col) {
+                continue;
+            }
+            let open_neighbors = [
+                (row.wrapping_sub(1), col),
+                (row + 1, col),
+                (row, col.wrapping_sub(1)),
+                (row, col + 1),
+            ]
+            .iter()
+            .filter(|&&(r, c)| is_open(r, c))
+            .count();
+            if open_neighbors == 1 {
+                dead_ends.push(Cell::new(row, col));
+            }
+        }
+    }
+    dead_ends
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_maze_str_finds_exit() {
+        let (grid, exit) = parse_maze_str("#####\n#*  #\n#####\n").expect("valid maze");
+        assert_eq!(grid.len(), 3);
+        assert!(grid[0].iter().all(|&wall| wall));
+        assert!(!grid[1][1]);
+        assert_eq!(exit, Some(Cell::new(1, 1)));
+    }
+
+    #[test]
+    fn test_parse_maze_str_reports_errors() {
+        let ragged = parse_maze_str("#####\n#  #\n#####").unwrap_err();
+        assert!(ragged.starts_with("Line 2:"), "{}", ragged);
+
+        let typo = parse_maze_str("#####\n# x #\n#####").unwrap_err();
+        assert!(typo.starts_with("Line 2, column 3:"), "{}", typo);
+
+        let misplaced_exit = parse_maze_str("##*##\n#   #\n#####").unwrap_err();
+        assert!(misplaced_exit.contains("exit marker"), "{}", misplaced_exit);
+    }
+
+    #[test]
+    fn test_solution_length_follows_passages() {
+        let (grid, exit) =
+            parse_maze_str("#####\n#*# #\n# # #\n#   #\n#####\n").expect("valid maze");
+        let exit = exit.expect("maze has an exit");
+        assert_eq!(solution_length(&grid, Cell::new(3, 3), exit), Some(4));
+        assert_eq!(solution_length(&grid, exit, exit), Some(0));
+        assert_eq!(solution_length(&grid, Cell::new(0, 0), exit), None);
+    }
+
+    #[test]
+    fn test_extra_cost_steers_paths_around_entries() {
+        // Two ways from the bottom-left to the top-right: along the bottom and up the right
+        // side, or the long way round through the middle and along the top
+        let (grid, _) =
+            parse_maze_str("#######\n#     #\n# ### #\n#   # #\n### # #\n#     #\n#######\n")
+                .expect("valid maze");
+        let (start, goal) = (Cell::new(5, 1), Cell::new(1, 5));
+        let shortest = find_path_with_cost(&grid, start, goal, |_| 0).expect("reachable");
+        assert_eq!(
+            shortest.len() - 1,
+            solution_length(&grid, start, goal).unwrap()
+        );
+
+        // Charging for the entries of the shortest path sends it the other way
+        let avoided = shortest[3..shortest.len() - 1].to_vec();
+        let detour = find_path_with_cost(&grid, start, goal, |cell| {
+            if avoided.contains(&cell) { 20 } else { 0 }
+        })
+        .expect("reachable");
+        assert!(detour.len() > shortest.len());
+        assert!(detour.iter().all(|cell| !avoided.contains(cell)));
+        assert!(detour.windows(2).all(|pair| {
+            pair[0].row.abs_diff(pair[1].row) + pair[0].col.abs_diff(pair[1].col) == 1
+        }));
+
+        assert_eq!(
+            find_path_with_cost(&grid, start, Cell::new(0, 0), |_| 0),
+            None
+        );
+    }
+
+    #[test]
+    fn test_path_distances_match_solution_lengths() {
+        let (grid, exit) =
+            parse_maze_str("#####\n#*# #\n# # #\n#   #\n#####\n").expect("valid maze");
+        let exit = exit.expect("maze has an exit");
+        let distances = path_distances(&grid, Cell::new(3, 3));
+        assert_eq!(distances[exit.row][exit.col], Some(4));
+        assert_eq!(distances[1][3], Some(2));
+        assert_eq!(distances[0][0], None);
+        assert!(
+            path_distances(&grid, Cell::new(0, 0))
+                .iter()
+                .flatten()
+                .all(Option::is_none)
+        );
+    }
+
+    #[test]
+    fn test_dead_ends_have_one_way_out() {
+        let (grid, exit) =
+            parse_maze_str("#####\n#*# #\n# # #\n#   #\n#####\n").expect("valid maze");
+        assert_eq!(dead_ends(&grid), vec![exit.unwrap(), Cell::new(1, 3)]);
+        assert!(dead_ends(&vec![vec![true; 3]; 3]).is_empty());
+    }
 }
@@ -0,0 +1,333 @@
+//! Maze file watching for test mode level design.
+//!
+//! This module provides [`MazeFileWatcher`], which watches a single `.mz` file on a background
+//! thread and reports when it has changed so the level can be reloaded without restarting the
+//! game. The file's directory is watched with the `notify` crate, since editors often replace a
+//! file rather than write to it, and events for the file make the thread read it again. A change
+//! is only reported when the file's contents hash differently from the last version reported, so
+//! saving without edits or touching the file reloads nothing, while an edit that keeps the size
+//! and lands within the filesystem's timestamp granularity is still seen.
+//!
+//! Editors commonly save in several steps (truncate then write, or write to a temporary file and
+//! rename), so a change is only reported once the file has stopped changing for
+//! [`DEBOUNCE_INTERVAL`].
+//!
+//! # Usage
+//!
+//! ```rust,ignore
+//! let watcher = MazeFileWatcher::start(Path::new("test_maze.mz"));
+//!
+//! // Once per frame
+//! if watcher.take_change() {
+//!     // Re-parse and rebuild the level
+//! }
+//!
+//! // Dropping the watcher stops and joins the background thread
+//! drop(watcher);
+//! ```
+
+use notify::{Event, RecommendedWatcher, RecursiveMode, Watcher};
+use std::collections::hash_map::DefaultHasher;
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::path::Path;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+/// How long the background thread waits for a file event before checking whether it should
+/// stop and whether a pending change has settled.
+const WAKE_INTERVAL: Duration = Duration::from_millis(100);
+
+/// How long the file must stay unchanged before a change is reported.
+pub const DEBOUNCE_INTERVAL: Duration = Duration::from_millis(250);
+
+/// A hash of the file's contents, used to detect changes.
+///
+/// `None` means the file could not be read, for example while an editor is replacing it.
+type FileStamp = Option<u64>;
+
+/// Watches a maze file on a background thread and reports debounced changes.
+pub struct MazeFileWatcher {
+    /// Receives one message per debounced change
+    changes: Receiver<()>,
+    /// Tells the background thread to exit
+    stop: Arc<AtomicBool>,
+    /// The background thread, taken when the watcher is stopped
+    thread: Option<JoinHandle<()>>,
+}
+
+impl MazeFileWatcher {
+    /// Starts watching `path` for changes.
+    ///
+    /// The file does not need to exist yet; creating it counts as a change.
+    ///
+    /// # Arguments
+    /// * `path` - The maze file to watch
+    ///
+    /// # Returns
+    /// A watcher whose background thread is already running
+    pub fn start(path: &Path) -> Self {
+        let (sender, changes) = mpsc::channel();
+        let stop = Arc::new(AtomicBool::new(false));
+
+        // Watching starts before this returns, so no edit made after it is missed
+        let initial = read_stamp(path);
+        let thread = watch_directory(path).and_then(|(watcher, events)| {
+            let path = path.to_path_buf();
+            let stop = Arc::clone(&stop);
+            thread::Builder::new()
+                .name("maze-file-watcher".to_string())
+                .spawn(move || {
+                    // Dropping the watcher at the end of the thread stops the events
+                    let _watcher = watcher;
+                    watch_loop(&path, initial, &events, &stop, &sender);
+                })
+                .map_err(|e| eprintln!("Failed to start maze file watcher: {}", e))
+                .ok()
+        });
+
+        println!("Watching {} for changes", path.display());
+
+        Self {
+            changes,
+            stop,
+            thread,
+        }
+    }
+
+    /// Checks whether the file has changed since the last call.
+    ///
+    /// Multiple pending changes are collapsed into one.
+    ///
+    /// # Returns
+    /// `true` if at least one debounced change was reported
+    pub fn take_change(&self) -> bool {
+        self.changes.try_iter().count() > 0
+    }
+
+    /// Stops the background thread and waits for it to exit.
+    ///
+    /// Calling this more than once is harmless. It is also called on drop.
+    pub fn stop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+        let panicked = self
+            .thread
+            .take()
+            .is_some_and(|thread| thread.join().is_err());
+        if panicked {
+            eprintln!("Maze file watcher thread panicked");
+        }
+    }
+}
+
+impl Drop for MazeFileWatcher {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+/// Starts watching the directory `path` is in.
+///
+/// Editors that save by renaming a temporary file replace the watched file, so the directory is
+/// watched rather than the file.
+///
+/// # Returns
+/// The watcher, which must be kept alive, and the events it sends, or `None` if watching failed
+fn watch_directory(path: &Path) -> Option<(RecommendedWatcher, Receiver<notify::Result<Event>>)> {
+    let (event_sender, events) = mpsc::channel();
+    let mut watcher = notify::recommended_watcher(move |event: notify::Result<Event>| {
+        let _ = event_sender.send(event);
+    })
+    .map_err(|e| eprintln!("Failed to start maze file watcher: {}", e))
+    .ok()?;
+    let directory = path
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+        .unwrap_or(Path::new("."));
+    watcher
+        .watch(directory, RecursiveMode::NonRecursive)
+        .map_err(|e| eprintln!("Failed to watch {}: {}", directory.display(), e))
+        .ok()?;
+    Some((watcher, events))
+}
+
+/// Body of the background thread: reads the file again after each event for it until told to
+/// stop.
+fn watch_loop(
+    path: &Path,
+    initial: FileStamp,
+    events: &Receiver<notify::Result<Event>>,
+    stop: &AtomicBool,
+    sender: &Sender<()>,
+) {
+    let mut debouncer = Debouncer::new(initial);
+    let mut pending = false;
+    while !stop.load(Ordering::Relaxed) {
+        match events.recv_timeout(WAKE_INTERVAL) {
+            Ok(Ok(event)) => pending |= concerns(&event, path),
+            Ok(Err(e)) => eprintln!("Maze file watcher error: {}", e),
+            Err(RecvTimeoutError::Timeout) => {}
+            Err(RecvTimeoutError::Disconnected) => break,
+        }
+        if !pending {
+            continue;
+        }
+
+        let stamp = read_stamp(path);
+        if debouncer.observe(stamp, Instant::now()) {
+            pending = false;
+            if sender.send(()).is_err() {
+                // The watcher was dropped without stopping the thread
+                break;
+            }
+        } else if debouncer.is_settled(Instant::now()) {
+            // The file settled back on the version already reported
+            pending = false;
+        }
+    }
+}
+
+/// Returns whether a file event is about the watched file.
+fn concerns(event: &Event, path: &Path) -> bool {
+    event
+        .paths
+        .iter()
+        .any(|changed| changed.file_name() == path.file_name())
+}
+
+/// Hashes the contents of `path`.
+fn read_stamp(path: &Path) -> FileStamp {
+    let contents = fs::read(path).ok()?;
+    let mut hasher = DefaultHasher::new();
+    contents.hash(&mut hasher);
+    Some(hasher.finish())
+}
+
+/// Turns a stream of file stamps into debounced change notifications.
+#[derive(Debug)]
+struct Debouncer {
+    /// The stamp of the last reported (or initial) version of the file
+    reported: FileStamp,
+    /// The most recently observed stamp
+    latest: FileStamp,
+    /// When `latest` was first observed
+    latest_since: Instant,
+}
+
+impl Debouncer {
+    /// Creates a debouncer that treats `initial` as already reported.
+    fn new(initial: FileStamp) -> Self {
+        Self {
+            reported: initial,
+            latest: initial,
+            latest_since: Instant::now(),
+        }
+    }
+
+    /// Records a new observation of the file.
+    ///
+    /// # Arguments
+    /// * `stamp` - The file's current stamp
+    /// * `now` - When the stamp was read
+    ///
+    /// # Returns
+    /// `true` if the file has settled on a new, readable version that should be reported
+    fn observe(&mut self, stamp: FileStamp, now: Instant) -> bool {
+        if stamp != self.latest {
+            self.latest = stamp;
+            self.latest_since = now;
+            return false;
+        }
+
+        if self.is_settled(now) && stamp.is_some() && stamp != self.reported {
+            self.reported = stamp;
+            return true;
+        }
+        false
+    }
+
+    /// Returns whether the file has stayed unchanged for [`DEBOUNCE_INTERVAL`].
+    fn is_settled(&self, now: Instant) -> bool {
+        now.duration_since(self.latest_since) >= DEBOUNCE_INTERVAL
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rapid_writes_are_reported_once() {
+        let start = Instant::now();
+        let first = Some(10);
+        let second = Some(12);
+        let third = Some(14);
+        let mut debouncer = Debouncer::new(first);
+
+        // Two quick saves in a row
+        assert!(!debouncer.observe(second, start));
+        assert!(!debouncer.observe(third, start + Duration::from_millis(50)));
+        assert!(!debouncer.observe(third, start + Duration::from_millis(150)));
+
+        // Reported once the file settles, and only once
+        assert!(debouncer.observe(third, start + Duration::from_millis(350)));
+        assert!(!debouncer.observe(third, start + Duration::from_millis(450)));
+    }
+
+    #[test]
+    fn test_missing_file_is_not_reported() {
+        let start = Instant::now();
+        let mut debouncer = Debouncer::new(Some(10));
+
+        // Editor deletes the file while replacing it
+        assert!(!debouncer.observe(None, start));
+        assert!(!debouncer.observe(None, start + Duration::from_secs(1)));
+    }
+
+    #[test]
+    fn test_same_size_edits_change_the_stamp() {
+        let path = std::env::temp_dir().join("mirador_watcher_same_size.mz");
+        fs::write(&path, "# #\n###\n").unwrap();
+        let before = read_stamp(&path);
+
+        // Flipping a wall to a corridor keeps the size
+        fs::write(&path, "# #\n# #\n").unwrap();
+        let after = read_stamp(&path);
+        assert!(before.is_some());
+        assert_ne!(before, after);
+
+        // Writing the same contents again is no change
+        fs::write(&path, "# #\n# #\n").unwrap();
+        assert_eq!(read_stamp(&path), after);
+
+        let _ = fs::remove_file(&path);
+        assert_eq!(read_stamp(&path), None);
+    }
+
+    #[test]
+    fn test_watcher_reports_an_edit() {
+        let dir = std::env::temp_dir().join("mirador_watcher_edit");
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("level.mz");
+        fs::write(&path, "# #\n###\n").unwrap();
+
+        let watcher = MazeFileWatcher::start(&path);
+        assert!(!watcher.take_change());
+        fs::write(&path, "# #\n# #\n").unwrap();
+
+        let deadline = Instant::now() + Duration::from_secs(5);
+        let mut changed = false;
+        while !changed && Instant::now() < deadline {
+            thread::sleep(Duration::from_millis(50));
+            changed = watcher.take_change();
+        }
+        assert!(changed);
+
+        drop(watcher);
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -21,11 +21,13 @@
 //! The timer's decimal point is always positioned at the exact horizontal center of the screen
 //! by measuring the width of the timer string up to and including the decimal point, then
 //! offsetting the x position accordingly. This creates a visually stable timer display
-//! where the decimal point never moves as the numbers change.
+//! where the decimal point never moves as the numbers change. The width is measured with
+//! tabular digits by [`TextRenderer::measure_figures`], which caches it, so it is only
+//! measured again when the number of digits changes.
 //!
 //! # Usage Example
 //!
-//! ```rust
+//! ```rust,ignore
 //! use game_state::GameState;
 //!
 //! // Create a new game state with default values
@@ -45,26 +47,110 @@
 //! ```
 
 // Timer decimal alignment: The timer's decimal point is always aligned with the vertical center of the screen by measuring the width of the timer string up to and including the decimal and offsetting the x position accordingly. See initialize_game_ui and update_game_ui for details.
+pub mod announcer;
+pub mod anomaly;
 pub mod audio;
+pub mod audio_debug;
+pub mod auto_walk;
+pub mod bindings;
+pub mod breathing;
+pub mod capture;
+pub mod challenge;
+pub mod clock;
+pub mod codex;
 pub mod collision;
+pub mod combo;
+pub mod cues;
+pub mod daily;
+pub mod dimensions;
+pub mod director;
+pub mod door;
 pub mod enemy;
+pub mod events;
+pub mod frame_heatmap;
+pub mod haptics;
+pub mod hardcore;
+pub mod input;
+pub mod input_claim;
+pub mod interact;
+pub mod journal;
 pub mod keys;
+pub mod levels;
+pub mod map_view;
 pub mod maze;
+pub mod navmesh;
+pub mod pack;
 pub mod player;
+pub mod quick_radial;
+pub mod replay;
+pub mod retry;
+pub mod sandbox;
+pub mod save;
+pub mod score;
+pub mod seed;
+pub mod signage;
+pub mod sound_propagation;
+pub mod soundscape;
+pub mod spatial_audio;
+pub mod spawn;
+pub mod theme;
+pub mod time_of_day;
+pub mod toasts;
+pub mod trail;
 pub mod upgrades;
+pub mod wall_clip;
 
+use self::announcer::AnnouncerFrame;
+use self::anomaly::{Anomaly, Shimmer};
 use self::audio::GameAudioManager;
+use self::auto_walk::{ARRIVAL_FRACTION, AutoWalk, ENEMY_STOP_RANGE};
+use self::capture::{CapturePhase, CaptureSequence};
+use self::challenge::Challenge;
+use self::clock::GameClock;
 use self::collision::CollisionSystem;
-use self::player::Player;
+use self::combo::{ComboFrame, ComboMeter, ComboReset};
+use self::cues::{DuckCue, WarningCues};
+use self::daily::DailyRun;
+use self::dimensions::PlayerDimensions;
+use self::director::{CLOSE_CALL_RANGE, DifficultyDirector, EnemyTuning};
+use self::door::{CameraShake, EntranceDoor};
+use self::events::{EventSchedule, EventTransition};
+use self::frame_heatmap::FrameHeatmap;
+use self::haptics::HapticFrame;
+use self::interact::Interactable;
+use self::journal::{JournalEvent, JournalHandle};
+use self::levels::{Difficulty, DifficultyCurve, LevelPlan};
+use self::map_view::{ENEMY_BLOCK_RANGE, MapView};
+use self::pack::Pack;
+use self::player::{MovementOutcome, Player};
+use self::quick_radial::QuickRadial;
+use self::replay::RunRecording;
+use self::retry::LevelSnapshot;
+use self::sandbox::SandboxSession;
+use self::score::{LevelResult, ScoreBreakdown, ScoreCalculator, VisitedCells};
+use self::signage::SignDecal;
+use self::soundscape::Soundscape;
+use self::spawn::SpawnDirector;
+use self::theme::LevelTheme;
+use self::time_of_day::TimeOfDay;
+use self::toasts::ToastSender;
+use self::upgrades::AvailableUpgrade;
+use self::wall_clip::{ClipContext, WallClipMonitor};
 use crate::game::enemy::Enemy;
+use crate::game::maze::floors::Layer;
 use crate::game::maze::generator::Cell;
+use crate::math::coordinates;
+use crate::math::mat::Mat4;
+use crate::renderer::particles::ParticleSystem;
+use crate::renderer::safe_area::SafeArea;
 use crate::renderer::text::TextPosition;
 use crate::renderer::text::TextRenderer;
 use crate::renderer::text::TextStyle;
 use glyphon::Color;
+use std::collections::HashMap;
+use std::error::Error;
 use std::path::PathBuf;
 use std::time::{Duration, Instant};
-use winit::window::Window;
 
 /// Represents the entire mutable state of the game.
 ///
@@ -85,7 +171,8 @@ use winit::window::Window;
 ///
 /// The GameState is typically created once at game startup using [`GameState::new()`],
 /// then continuously updated throughout the game loop. Different screens may modify
-/// different subsets of the state.
+/// different subsets of the state. [`GameState::builder`] makes one without an audio
+/// device, for tools and front-ends that only need the maze and the player.
 pub struct GameState {
     /// The player character containing position, orientation, movement state, and camera data.
     ///
@@ -123,12 +210,18 @@ pub struct GameState {
     /// at regular intervals (typically every second).
     pub last_fps_time: Instant,
 
-    /// File system path to the currently loaded maze definition.
+    /// File system path the current maze was saved to or loaded from.
     ///
-    /// `None` if no maze is currently loaded. When `Some`, contains the path
-    /// to the maze file that was used to generate the current game level.
+    /// `None` if the maze has no file, either because it was never saved
+    /// or because saving failed. Generated levels are built from the maze in
+    /// memory, so this is only a record for reproducing the level.
     pub maze_path: Option<PathBuf>,
 
+    /// Whether the current level's maze has been built and can be played.
+    ///
+    /// Cleared whenever a new level starts loading.
+    pub level_ready: bool,
+
     /// Whether mouse input is captured for camera movement.
     ///
     /// When `true`, mouse movement controls the camera/player view direction.
@@ -142,6 +235,11 @@ pub struct GameState {
     /// pickups, and other interactive elements in the game world.
     pub collision_system: CollisionSystem,
 
+    /// The player's collision size, reach, and the distance the enemy catches them from.
+    ///
+    /// Change with [`GameState::set_dimensions`] so the collision system follows.
+    pub dimensions: PlayerDimensions,
+
     /// Whether the player has reached the maze exit.
     ///
     /// Set to `true` when the player successfully navigates to the exit cell.
@@ -176,10 +274,21 @@ pub struct GameState {
     /// The enemy entity in the game world.
     ///
     /// Contains enemy position, AI state, movement patterns, and any
-    /// enemy-specific behavior flags. Currently supports a single enemy
-    /// but could be extended to support multiple enemies.
+    /// enemy-specific behavior flags. On levels with more than one enemy
+    /// this is the lead, and the rest hunt alongside it in [`GameState::pack`].
     pub enemy: Enemy,
 
+    /// The enemies hunting alongside the lead enemy on deeper levels.
+    ///
+    /// Empty while a single enemy hunts the player; see [`GameState::spawn_pack`].
+    pub pack: Pack,
+
+    /// Whether the game state has an enemy at all.
+    ///
+    /// Only false for game states built with [`GameStateBuilder::without_enemy`];
+    /// see [`GameState::enemy_present`].
+    pub enemy_enabled: bool,
+
     /// Centralized audio management system.
     ///
     /// Handles background music, sound effects, spatial audio positioning,
@@ -204,18 +313,246 @@ pub struct GameState {
     /// Prevents the audio from being triggered multiple times during
     /// the exit sequence animation.
     pub beeper_rise_played: bool,
-}
 
-/// Represents the current state of the pause menu.
-///
-/// Used to track whether the pause menu is currently displayed to the player
-/// or hidden. This affects input handling and rendering behavior.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub enum PauseMenuState {
-    /// Pause menu is currently visible and active
-    Open,
-    /// Pause menu is hidden, game continues normally
-    Closed,
+    /// Bitset of maze grid cells the player has walked through this level.
+    ///
+    /// Rebuilt for every new maze and used for the exploration score bonus.
+    pub visited_cells: VisitedCells,
+
+    /// The player's path through this level, replayed when the level is escaped.
+    ///
+    /// Cleared for every new maze and recorded alongside the visited cells.
+    pub run_recording: RunRecording,
+
+    /// Watches the player for ending up inside a wall, cleared for every new maze.
+    pub wall_clip: WallClipMonitor,
+
+    /// Closest distance the enemy has come to the player during this level.
+    ///
+    /// Starts at `f32::MAX` and is used for the untouchable score bonus.
+    pub closest_enemy_distance: f32,
+
+    /// Score breakdown for the most recently completed level.
+    ///
+    /// Captured when the player reaches the exit so the level summary can
+    /// display it, then consumed when the points are added to the run score.
+    pub last_score_breakdown: Option<ScoreBreakdown>,
+
+    /// Movement combo that multiplies the level score.
+    ///
+    /// Ticked once per frame on the game screen and reset for every new maze.
+    pub combo: ComboMeter,
+
+    /// Edge detection for the enemy sting and the timer beep.
+    ///
+    /// Ticked by [`GameState::update_warning_cues`] on the game screen.
+    pub warning_cues: WarningCues,
+
+    /// Result of the player's most recent movement step.
+    ///
+    /// Written by the movement input handler and read by [`GameState::update_combo`].
+    pub last_movement: MovementOutcome,
+
+    /// Dust and wall impact particles around the player.
+    ///
+    /// Only updated on the game screen, so particles freeze while paused, and
+    /// cleared for every new maze.
+    pub particles: ParticleSystem,
+
+    /// Snapshot of the current level used by the "Retry Level" buttons.
+    ///
+    /// Captured when a level finishes loading and cleared whenever a new level
+    /// starts loading. `None` while no retryable level is loaded (e.g. in test mode).
+    pub level_snapshot: Option<LevelSnapshot>,
+
+    /// Handle to the session journal used to record events for post-mortem debugging.
+    pub journal: JournalHandle,
+
+    /// Posts toast notifications; detached until the app hands it the manager's sender.
+    pub toasts: ToastSender,
+
+    /// Adaptive difficulty director that tunes the enemy between levels.
+    ///
+    /// Fed a [`LevelResult`] whenever a level ends; its tuning is applied to
+    /// the enemy when the next level is set up.
+    pub director: DifficultyDirector,
+
+    /// Grid steps on the shortest path from the entrance to the exit of the current maze.
+    ///
+    /// Used as the par for the adaptive difficulty director; 0 when unknown.
+    pub solution_length: usize,
+
+    /// Number of close calls with the enemy during this level.
+    pub close_calls: u32,
+
+    /// Number of anomalies taken this level whose cosmetic was already unlocked.
+    ///
+    /// Scored as a bonus by the [`ScoreCalculator`] when the level ends.
+    pub duplicate_anomalies: u32,
+
+    /// Whether the enemy was within close-call range on the previous frame.
+    pub in_close_call: bool,
+
+    /// Hold-to-peek overhead camera.
+    ///
+    /// Updated once per frame by [`GameState::update_map_view`] and read by the
+    /// renderer when building the camera.
+    pub map_view: MapView,
+
+    /// Hold-to-open quick-settings radial.
+    ///
+    /// Opened and activated by the app's input handling, and closed by
+    /// [`GameState::update_quick_radial`] as soon as play stops.
+    pub quick_radial: QuickRadial,
+
+    /// Whether the player keeps crouch-walking without holding the crouch key.
+    ///
+    /// Switched from the quick-settings radial and released at the start of every level.
+    pub crouch_locked: bool,
+
+    /// What the HUD compass points at.
+    ///
+    /// Switched from the quick-settings radial; unlike crouch lock it lasts between levels.
+    pub compass_mode: CompassMode,
+
+    /// Things in the current level the player can interact with.
+    ///
+    /// Cleared when a new level starts loading.
+    pub interactables: Vec<Interactable>,
+
+    /// Index into [`GameState::interactables`] of the one the player is looking at.
+    ///
+    /// Updated once per frame by [`GameState::update_interaction_target`].
+    pub interaction_target: Option<usize>,
+
+    /// Click-to-move walk the player is following, if any.
+    ///
+    /// Started by [`GameState::request_auto_walk`], steered once per frame by
+    /// [`GameState::update_auto_walk`], and cancelled for every new maze.
+    pub auto_walk: AutoWalk,
+
+    /// The capture sequence playing after the enemy caught the player, if any.
+    ///
+    /// Started by [`GameState::start_capture`] and cleared once the game over screen
+    /// takes over or a new maze starts.
+    pub capture: Option<CaptureSequence>,
+
+    /// Whether arrows are painted on the walls near the entrance on every level.
+    ///
+    /// Mirrors the navigation hints setting. The first [`HINT_LEVELS`] levels get
+    /// arrows either way.
+    pub navigation_hints: bool,
+
+    /// Arrows painted on the walls along the start of the solution path.
+    ///
+    /// Placed for every new maze by [`GameState::reset_level_tracking`].
+    pub signposts: Vec<SignDecal>,
+
+    /// The daily challenge being played, or `None` for a regular run.
+    ///
+    /// While set, every level's maze comes from the challenge's seed and the enemy
+    /// uses the challenge's fixed tuning instead of the adaptive director.
+    pub daily: Option<DailyRun>,
+
+    /// Seed typed on the title screen for the next regular run, or `None` for a random one.
+    ///
+    /// Kept when returning to the title screen, so friends can race the same run again.
+    pub chosen_seed: Option<u64>,
+
+    /// Whether runs are played in hardcore mode, toggled on the title screen.
+    ///
+    /// A hardcore run can't retry a level, scores more, and burns its seed when it dies.
+    /// Kept when returning to the title screen, like the chosen seed.
+    pub hardcore: bool,
+
+    /// Challenge code entered on the title screen, or `None` for a regular run.
+    ///
+    /// Its seed and mode are copied into [`GameState::chosen_seed`] and
+    /// [`GameState::hardcore`] when entered; while set, its difficulty and modifiers
+    /// apply to every level and the run ends after its last level. Kept when returning
+    /// to the title screen, like the chosen seed.
+    pub challenge: Option<Challenge>,
+
+    /// Seed every level of the current regular run is generated from.
+    ///
+    /// Set at the start of each run by [`GameState::begin_run_seed`].
+    pub run_seed: u64,
+
+    /// Plan of the current level: its maze size, timer, enemy, and which features it has.
+    ///
+    /// Set by [`GameState::plan_level`] before each maze is generated, and read by
+    /// everything that sets the level up.
+    pub level_plan: LevelPlan,
+
+    /// How the maze grows from level to level, passed to every [`GameState::plan_level`].
+    pub difficulty_curve: DifficultyCurve,
+
+    /// Ambient events rolled for the current level.
+    ///
+    /// Rolled for every new maze by [`GameState::reset_level_tracking`] and advanced
+    /// by [`GameState::update_events`]. Always empty in test mode.
+    pub events: EventSchedule,
+
+    /// Ambient dressing of the current level, rolled with its events.
+    pub theme: LevelTheme,
+
+    /// Hour of the night the current level is lit as, set for every new maze.
+    ///
+    /// Kept after the run ends, so the frozen frame behind the game over screen keeps the
+    /// tint of the level where the run ended. Neutral in test mode.
+    pub time_of_day: TimeOfDay,
+
+    /// Ambient beds and accents of the current level, for its theme.
+    ///
+    /// Seeded for every new maze by [`GameState::reset_level_tracking`] and advanced by
+    /// [`GameState::update_soundscape`].
+    pub soundscape: Soundscape,
+
+    /// How far the current level's walls breathe, in world units.
+    ///
+    /// Set for every new maze by [`GameState::reset_level_tracking`] from the level's
+    /// [plan](LevelPlan::wall_breath). Always 0.0 in test mode.
+    pub wall_breath: f32,
+
+    /// The anomaly hidden in the current level, until the player takes it.
+    ///
+    /// Rolled for every new maze by [`GameState::reset_level_tracking`] and taken with
+    /// [`GameState::collect_anomaly`].
+    pub anomaly: Option<Anomaly>,
+
+    /// Paces the shimmer that plays near the anomaly.
+    pub anomaly_shimmer: Shimmer,
+
+    /// Schedules the enemy's arrival on levels where it spawns partway through.
+    ///
+    /// Rolled for every new maze by [`GameState::reset_level_tracking`] and advanced by
+    /// [`GameState::update_spawn`]. While its spawn is pending the level has no enemy.
+    pub spawn_director: SpawnDirector,
+
+    /// The door that closes the entrance once the player walks away from it.
+    ///
+    /// Set up for every new maze by [`GameState::reset_level_tracking`] and advanced by
+    /// [`GameState::update_entrance_door`]. `None` in test mode.
+    pub entrance_door: Option<EntranceDoor>,
+
+    /// Shake added to the camera, started when the entrance door lands.
+    pub camera_shake: CameraShake,
+
+    /// Clock the level's events are scheduled on.
+    ///
+    /// Only advanced on the game screen, so events hold still while paused.
+    pub event_clock: GameClock,
+
+    /// Rolling CPU frame time of every cell the player has stood in this level.
+    ///
+    /// Only recorded in debug builds and test mode; see [`GameState::record_frame_cost`].
+    pub frame_heatmap: FrameHeatmap,
+
+    /// The practice sandbox being played, or `None` outside the sandbox.
+    ///
+    /// While set, mazes come from the sandbox's settings, there is no timer or score,
+    /// and nothing is recorded in the codex or saved to disk.
+    pub sandbox: Option<SandboxSession>,
 }
 
 /// Represents the different screens or states the game can be in.
@@ -223,7 +560,7 @@ pub enum PauseMenuState {
 /// Each screen has its own input handling, rendering logic, and state management.
 /// The current screen determines which game systems are active and how user
 /// input is interpreted.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 pub enum CurrentScreen {
     /// Initial screen shown when the game starts, typically with menu options
     Title,
@@ -241,49 +578,180 @@ pub enum CurrentScreen {
     UpgradeMenu,
     /// Victory screen shown when the player successfully reaches the maze exit
     ExitReached,
+    /// Lifetime stats for every kind of maze played, reached from the title screen
+    Codex,
+    /// The profile picker, reached from the title screen and shown on a first launch
+    Profiles,
+    /// Shown after the frame update panicked, with the panic message and a way back to the title
+    Error,
 }
 
-impl Default for GameState {
-    /// Returns a new [`GameState`] with default values.
-    ///
-    /// This is equivalent to calling [`GameState::new()`] and is provided
-    /// for convenience when using derive macros or generic code that expects
-    /// a Default implementation.
+/// What the HUD compass points at.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CompassMode {
+    /// The way to the exit, or to the stairs while the exit is on the other floor
+    #[default]
+    Exit,
+    /// North, the way a maze's first row lies, for finding the way without help
+    North,
+}
+
+impl CompassMode {
+    /// Returns the other mode.
+    pub fn next(self) -> Self {
+        match self {
+            Self::Exit => Self::North,
+            Self::North => Self::Exit,
+        }
+    }
+
+    /// Returns the name shown when switching to the mode.
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Exit => "exit",
+            Self::North => "north",
+        }
+    }
+}
+
+/// Where a [`GameStateBuilder`] gets its audio manager from.
+enum BuilderAudio {
+    /// Open the default audio device
+    Device,
+    /// Use a manager the caller already made
+    Manager(Box<GameAudioManager>),
+    /// Use a [`GameAudioManager::silent`] manager
+    Silent,
+}
+
+/// Builds a [`GameState`], so tests and tools can make one without the parts they don't need.
+///
+/// Made with [`GameState::builder`]. Every option defaults to what the game itself uses:
+/// audio on the default device, an enemy at its usual spawn point, the title screen, normal
+/// (non-test) mode, and a random run seed. The game uses [`GameState::new`], which panics
+/// if the audio device can't be opened.
+pub struct GameStateBuilder {
+    /// Where the audio manager comes from
+    audio: BuilderAudio,
+    /// The enemy to start with, or `None` for one at the usual spawn point
+    enemy: Option<Enemy>,
+    /// Whether the game state has an enemy at all
+    enemy_enabled: bool,
+    /// The screen the game state starts on
+    screen: CurrentScreen,
+    /// Whether the game state starts in test mode
+    test_mode: bool,
+    /// The run seed, or `None` for a random one
+    seed: Option<u64>,
+}
+
+impl Default for GameStateBuilder {
     fn default() -> Self {
-        Self::new()
+        Self {
+            audio: BuilderAudio::Device,
+            enemy: None,
+            enemy_enabled: true,
+            screen: CurrentScreen::Title,
+            test_mode: false,
+            seed: None,
+        }
     }
 }
 
-impl GameState {
-    /// Creates a new [`GameState`] with initialized default values.
+impl std::fmt::Debug for GameStateBuilder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let audio = match self.audio {
+            BuilderAudio::Device => "device",
+            BuilderAudio::Manager(_) => "manager",
+            BuilderAudio::Silent => "silent",
+        };
+        f.debug_struct("GameStateBuilder")
+            .field("audio", &audio)
+            .field("enemy", &self.enemy)
+            .field("enemy_enabled", &self.enemy_enabled)
+            .field("screen", &self.screen)
+            .field("test_mode", &self.test_mode)
+            .field("seed", &self.seed)
+            .finish()
+    }
+}
+
+impl GameStateBuilder {
+    /// Gives the game state an audio manager the caller already made.
     ///
-    /// This constructor performs several important initialization steps:
+    /// # Arguments
     ///
-    /// 1. **Audio System**: Initializes the audio manager and spawns the enemy audio source
-    /// 2. **Player Setup**: Creates a new player at the default starting position
-    /// 3. **Timing**: Sets up frame timing and FPS tracking systems
-    /// 4. **Collision System**: Configures collision detection with appropriate player dimensions
-    /// 5. **UI Management**: Initializes the game UI manager for timers, scores, and levels
-    /// 6. **Screen State**: Sets the initial screen to the title screen
-    /// 7. **Enemy Setup**: Creates and positions the enemy entity
-    /// 8. **Audio Configuration**: Sets appropriate volume levels for the title screen
+    /// * `audio_manager` - The manager to play the game's audio through
+    pub fn with_audio(mut self, audio_manager: GameAudioManager) -> Self {
+        self.audio = BuilderAudio::Manager(Box::new(audio_manager));
+        self
+    }
+
+    /// Gives the game state a [`GameAudioManager::silent`] manager, which never opens an
+    /// audio device.
+    pub fn without_audio(mut self) -> Self {
+        self.audio = BuilderAudio::Silent;
+        self
+    }
+
+    /// Starts the game state with a given enemy instead of one at the usual spawn point.
     ///
-    /// # Panics
+    /// # Arguments
     ///
-    /// This function will panic if:
-    /// - The audio manager fails to initialize (missing audio drivers, etc.)
-    /// - The enemy audio source cannot be spawned
-    /// - Title screen audio volumes cannot be set
+    /// * `enemy` - The enemy hunting the player
+    pub fn with_enemy(mut self, enemy: Enemy) -> Self {
+        self.enemy = Some(enemy);
+        self.enemy_enabled = true;
+        self
+    }
+
+    /// Leaves the enemy out, so [`GameState::enemy_present`] is always false.
     ///
-    /// # Examples
+    /// The game state still holds an [`Enemy`], locked in place and never drawn, and no
+    /// enemy audio source is spawned.
+    pub fn without_enemy(mut self) -> Self {
+        self.enemy = None;
+        self.enemy_enabled = false;
+        self
+    }
+
+    /// Sets the screen the game state starts on.
     ///
-    /// ```rust
-    /// let game_state = GameState::new();
-    /// assert_eq!(game_state.current_screen, CurrentScreen::Title);
-    /// assert!(!game_state.exit_reached);
-    /// assert_eq!(game_state.game_ui.get_level(), 1);
-    /// ```
-    pub fn new() -> Self {
+    /// # Arguments
+    ///
+    /// * `screen` - The starting screen, [`CurrentScreen::Title`] by default
+    pub fn with_screen(mut self, screen: CurrentScreen) -> Self {
+        self.screen = screen;
+        self
+    }
+
+    /// Sets whether the game state starts in test mode, with test mode's player dimensions.
+    ///
+    /// # Arguments
+    ///
+    /// * `test_mode` - Whether to start in test mode
+    pub fn with_test_mode(mut self, test_mode: bool) -> Self {
+        self.test_mode = test_mode;
+        self
+    }
+
+    /// Fixes the run seed, as if it had been typed on the title screen.
+    ///
+    /// # Arguments
+    ///
+    /// * `seed` - The seed every level of the run is generated from
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// Creates the game state with no maze loaded.
+    ///
+    /// # Returns
+    ///
+    /// The game state, or an error if the audio device can't be opened or the embedded audio
+    /// assets can't be decoded
+    pub fn build(self) -> Result<GameState, Box<dyn Error>> {
         use crate::benchmarks::{BenchmarkConfig, Profiler};
 
         // Initialize profiler for GameState initialization benchmarking
@@ -293,20 +761,33 @@ impl GameState {
             write_to_file: false,
             min_duration_threshold: std::time::Duration::from_micros(1),
             max_samples: 1000,
+            autosave_interval: std::time::Duration::from_secs(60),
+            max_sections: 256,
         });
 
         // Benchmark audio manager initialization (most taxing part)
         init_profiler.start_section("audio_manager_initialization");
-        let mut audio_manager =
-            GameAudioManager::new().expect("Failed to initialize audio manager");
+        let mut audio_manager = match self.audio {
+            BuilderAudio::Device => GameAudioManager::new()?,
+            BuilderAudio::Manager(audio_manager) => *audio_manager,
+            BuilderAudio::Silent => GameAudioManager::silent()?,
+        };
         init_profiler.end_section("audio_manager_initialization");
 
+        // Benchmark enemy creation
+        init_profiler.start_section("enemy_creation");
+        let mut enemy = self
+            .enemy
+            .unwrap_or_else(|| Enemy::new([-0.5, 30.0, 0.0], 150.0));
+        enemy.pathfinder.locked |= !self.enemy_enabled;
+        init_profiler.end_section("enemy_creation");
+
         // Benchmark enemy audio source spawning
-        init_profiler.start_section("enemy_audio_source_spawning");
-        audio_manager
-            .spawn_enemy("enemy".to_string(), [-0.5, 30.0, 0.0])
-            .expect("Failed to spawn enemy");
-        init_profiler.end_section("enemy_audio_source_spawning");
+        if self.enemy_enabled {
+            init_profiler.start_section("enemy_audio_source_spawning");
+            audio_manager.spawn_enemy("enemy".to_string(), enemy.pathfinder.position)?;
+            init_profiler.end_section("enemy_audio_source_spawning");
+        }
 
         // Benchmark player creation
         init_profiler.start_section("player_creation");
@@ -315,18 +796,12 @@ impl GameState {
 
         // Benchmark collision system initialization
         init_profiler.start_section("collision_system_init");
-        let collision_system = CollisionSystem::new(
-            5.0,   // player_radius - horizontal collision boundary
-            100.0, // player_height - vertical collision boundary
-        );
+        let dimensions = PlayerDimensions::for_mode(self.test_mode);
+        let collision_system =
+            CollisionSystem::new(dimensions.collision_radius, dimensions.collision_height);
         init_profiler.end_section("collision_system_init");
 
-        // Benchmark enemy creation
-        init_profiler.start_section("enemy_creation");
-        let enemy = Enemy::new([-0.5, 30.0, 0.0], 150.0);
-        init_profiler.end_section("enemy_creation");
-
-        let mut game_state = Self {
+        let mut game_state = GameState {
             // Initialize player at default starting position with default orientation
             player,
 
@@ -339,13 +814,14 @@ impl GameState {
 
             // No maze loaded initially
             maze_path: None,
+            level_ready: false,
 
             // Start with mouse captured for immediate gameplay readiness
             capture_mouse: true,
 
-            // Initialize collision system with player dimensions
-            // These values should match the actual player model dimensions
+            // Initialize collision system with the player's dimensions
             collision_system,
+            dimensions,
 
             // Game starts with exit not reached
             exit_reached: false,
@@ -354,33 +830,152 @@ impl GameState {
             // Initialize UI management system
             game_ui: GameUIManager::new(),
 
-            // Start on the title screen
-            current_screen: CurrentScreen::Title,
+            // Start on the chosen screen, the title screen by default
+            current_screen: self.screen,
             previous_screen: None,
 
-            // Create enemy at specified starting position with movement speed
+            // Enemy created above, present unless it was left out
             enemy,
+            pack: Pack::default(),
+            enemy_enabled: self.enemy_enabled,
 
             // Audio manager was initialized above
             audio_manager,
 
-            // Start in normal (non-test) mode
-            is_test_mode: false,
+            // Start in normal (non-test) mode unless asked otherwise
+            is_test_mode: self.test_mode,
 
             // Exit animation not active initially
             exit_reached_timer: 0.0,
             beeper_rise_played: false,
+
+            // Level tracking is reset whenever a new maze is built
+            visited_cells: VisitedCells::default(),
+            run_recording: RunRecording::default(),
+            wall_clip: WallClipMonitor::new(),
+            closest_enemy_distance: f32::MAX,
+            last_score_breakdown: None,
+            combo: ComboMeter::new(),
+            warning_cues: WarningCues::default(),
+            last_movement: MovementOutcome::default(),
+            particles: ParticleSystem::new(),
+            level_snapshot: None,
+            journal: journal::session(),
+            toasts: ToastSender::detached(),
+            director: DifficultyDirector::new(),
+            solution_length: 0,
+            close_calls: 0,
+            duplicate_anomalies: 0,
+            in_close_call: false,
+            map_view: MapView::new(),
+            quick_radial: QuickRadial::default(),
+            crouch_locked: false,
+            compass_mode: CompassMode::Exit,
+            interactables: Vec::new(),
+            interaction_target: None,
+            auto_walk: AutoWalk::new(),
+            capture: None,
+            navigation_hints: false,
+            signposts: Vec::new(),
+            daily: None,
+            chosen_seed: self.seed,
+            challenge: None,
+            hardcore: false,
+            run_seed: self.seed.unwrap_or_else(rand::random),
+            level_plan: LevelPlan::default(),
+            difficulty_curve: DifficultyCurve::STANDARD,
+            events: EventSchedule::default(),
+            theme: LevelTheme::default(),
+            time_of_day: TimeOfDay::NEUTRAL,
+            soundscape: Soundscape::new(LevelTheme::default(), 0),
+            wall_breath: 0.0,
+            anomaly: None,
+            anomaly_shimmer: Shimmer::default(),
+            spawn_director: SpawnDirector::default(),
+            entrance_door: None,
+            camera_shake: CameraShake::default(),
+            event_clock: GameClock::new(),
+            frame_heatmap: FrameHeatmap::default(),
+            sandbox: None,
         };
 
         // Benchmark title screen audio configuration
         init_profiler.start_section("title_audio_config");
-        game_state
-            .audio_manager
-            .set_title_screen_volumes()
-            .expect("Failed to set title screen volumes");
+        game_state.audio_manager.set_title_screen_volumes()?;
         init_profiler.end_section("title_audio_config");
 
-        game_state
+        Ok(game_state)
+    }
+}
+
+impl Default for GameState {
+    /// Returns a new [`GameState`] with default values.
+    ///
+    /// This is equivalent to calling [`GameState::new()`] and is provided
+    /// for convenience when using derive macros or generic code that expects
+    /// a Default implementation.
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl GameState {
+    /// Creates a new [`GameState`] with initialized default values.
+    ///
+    /// This constructor performs several important initialization steps:
+    ///
+    /// 1. **Audio System**: Initializes the audio manager and spawns the enemy audio source
+    /// 2. **Player Setup**: Creates a new player at the default starting position
+    /// 3. **Timing**: Sets up frame timing and FPS tracking systems
+    /// 4. **Collision System**: Configures collision detection with appropriate player dimensions
+    /// 5. **UI Management**: Initializes the game UI manager for timers, scores, and levels
+    /// 6. **Screen State**: Sets the initial screen to the title screen
+    /// 7. **Enemy Setup**: Creates and positions the enemy entity
+    /// 8. **Audio Configuration**: Sets appropriate volume levels for the title screen
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if:
+    /// - The audio manager fails to initialize (missing audio drivers, etc.)
+    /// - The enemy audio source cannot be spawned
+    /// - Title screen audio volumes cannot be set
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// let game_state = GameState::new();
+    /// assert_eq!(game_state.current_screen, CurrentScreen::Title);
+    /// assert!(!game_state.exit_reached);
+    /// assert_eq!(game_state.game_ui.get_level(), 1);
+    /// ```
+    pub fn new() -> Self {
+        Self::builder()
+            .build()
+            .expect("Failed to initialize game state")
+    }
+
+    /// Returns a builder for a [`GameState`], to leave out audio or the enemy.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use mirador::{CurrentScreen, GameState};
+    ///
+    /// // No audio device is opened, so this works on a headless machine
+    /// let game_state = GameState::builder()
+    ///     .without_audio()
+    ///     .without_enemy()
+    ///     .with_screen(CurrentScreen::Game)
+    ///     .with_seed(42)
+    ///     .build()
+    ///     .unwrap();
+    /// assert_eq!(game_state.current_screen, CurrentScreen::Game);
+    /// assert_eq!(game_state.run_seed, 42);
+    /// assert!(!game_state.enemy_present());
+    /// assert!(!game_state.audio_manager.is_moving());
+    /// ```
+    pub fn builder() -> GameStateBuilder {
+        GameStateBuilder::default()
     }
 
     /// Starts the game timer with optional custom configuration.
@@ -395,7 +990,7 @@ impl GameState {
     ///
     /// # Examples
     ///
-    /// ```rust
+    /// ```rust,ignore
     /// // Start timer with default 30-second configuration
     /// game_state.start_game_timer(None);
     ///
@@ -410,6 +1005,11 @@ impl GameState {
     /// ```
     pub fn start_game_timer(&mut self, config: Option<TimerConfig>) {
         self.game_ui.start_timer(config);
+        if let Some(timer) = &self.game_ui.timer {
+            self.journal.record(JournalEvent::TimerStarted {
+                seconds: timer.config.duration.as_secs_f32(),
+            });
+        }
     }
 
     /// Stops the currently running game timer.
@@ -448,7 +1048,7 @@ impl GameState {
     ///
     /// # Examples
     ///
-    /// ```rust
+    /// ```rust,ignore
     /// if game_state.is_game_timer_expired() {
     ///     // Handle timeout condition (e.g., game over, move to next level)
     ///     game_state.current_screen = CurrentScreen::GameOver;
@@ -470,7 +1070,7 @@ impl GameState {
     ///
     /// # Examples
     ///
-    /// ```rust
+    /// ```rust,ignore
     /// // Advance to level 2
     /// game_state.set_level(2);
     ///
@@ -492,7 +1092,7 @@ impl GameState {
     ///
     /// # Examples
     ///
-    /// ```rust
+    /// ```rust,ignore
     /// // Set initial score
     /// game_state.set_score(0);
     ///
@@ -503,24 +1103,1488 @@ impl GameState {
     pub fn set_score(&mut self, score: u32) {
         self.game_ui.set_score(score);
     }
-}
 
-/// Configuration settings for game timers.
-///
-/// This struct defines all the parameters needed to create a countdown timer,
-/// including the total duration, visual warning thresholds, and color schemes
-/// for different timer states.
-///
-/// # Color Transitions
-///
-/// The timer changes color based on remaining time:
-/// - **Normal**: More time than warning threshold (typically green)
-/// - **Warning**: Between warning and critical thresholds (typically yellow)
-/// - **Critical**: Less than critical threshold remaining (typically red)
-#[derive(Debug, Clone)]
-pub struct TimerConfig {
-    /// Total duration of the timer countdown.
-    pub duration: Duration,
+    /// Resets per-level scoring statistics for a freshly built maze.
+    ///
+    /// Clears the visited cell set and frame heatmap, resizing them to match the
+    /// new maze grid, drops the recorded path, forgets the closest enemy approach and close calls from the previous
+    /// level, measures the new maze's solution length, drops the movement
+    /// combo back to 1.0x, clears any leftover particles and click-to-move
+    /// walk, rolls the level's ambient events, hidden anomaly and delayed enemy
+    /// spawn, sets the level's time of day, and opens the entrance door.
+    ///
+    /// # Arguments
+    ///
+    /// * `maze_grid` - 2D grid of the new maze where `true` marks a wall
+    /// * `exit_cell` - The new maze's exit cell, if it has one
+    pub fn reset_level_tracking(&mut self, maze_grid: &[Vec<bool>], exit_cell: Option<Cell>) {
+        self.visited_cells = VisitedCells::for_grid(maze_grid);
+        let floors = if self.collision_system.floors.is_some() {
+            2
+        } else {
+            1
+        };
+        self.frame_heatmap = FrameHeatmap::for_grid(maze_grid, floors);
+        self.run_recording.clear();
+        self.wall_clip.reset();
+        self.closest_enemy_distance = f32::MAX;
+        self.close_calls = 0;
+        self.duplicate_anomalies = 0;
+        self.in_close_call = false;
+        self.map_view.reset();
+        self.auto_walk.cancel();
+        self.capture = None;
+        self.quick_radial.cancel();
+        self.crouch_locked = false;
+        // The player always enters at the bottom-left open cell
+        let entrance = Cell::new(maze_grid.len().saturating_sub(2), 1);
+        self.solution_length = exit_cell
+            .and_then(|exit| match &self.collision_system.floors {
+                Some(plan) => plan.solution_length(entrance, exit),
+                None => maze::solution_length(maze_grid, entrance, exit),
+            })
+            .unwrap_or(0);
+        self.signposts = self.place_signposts(maze_grid, entrance, exit_cell);
+        let level = self.game_ui.level;
+        let level_seed = events::level_seed(maze_grid, level);
+        let plan = &self.level_plan;
+        (self.events, self.theme, self.wall_breath) =
+            if self.collision_system.is_test_mode || !plan.modifiers {
+                (EventSchedule::default(), LevelTheme::default(), 0.0)
+            } else {
+                (
+                    EventSchedule::roll(level, level_seed),
+                    LevelTheme::roll(level, level_seed),
+                    plan.wall_breath,
+                )
+            };
+        self.time_of_day = if self.collision_system.is_test_mode {
+            TimeOfDay::NEUTRAL
+        } else {
+            time_of_day::for_level(level)
+        };
+        self.soundscape = Soundscape::new(self.theme, level_seed);
+        self.audio_manager
+            .start_ambience(self.soundscape.profile())
+            .expect("Failed to start ambience");
+        self.place_anomaly(maze_grid, entrance, exit_cell, level_seed);
+        self.schedule_spawn(maze_grid, entrance, exit_cell, level_seed);
+        self.event_clock = GameClock::new();
+        self.player.stamina_regen_multiplier = 1.0;
+        self.game_ui.event_announcement = None;
+        self.last_score_breakdown = None;
+        self.combo.reset();
+        self.game_ui.combo_multiplier = self.combo.multiplier();
+        self.particles.clear();
+        self.entrance_door =
+            (!self.collision_system.is_test_mode).then(|| EntranceDoor::new(maze_grid, false));
+        self.collision_system.set_door(None);
+        self.camera_shake = CameraShake::default();
+    }
+
+    /// Paints arrows on the walls along the first third of the solution path.
+    ///
+    /// Only done on levels [planned](LevelPlan::signposts) with them or with navigation hints on,
+    /// and never in test mode, where the inner walls aren't drawn. On two-floor levels only
+    /// the part of the path up to the stairs is signed.
+    ///
+    /// # Arguments
+    ///
+    /// * `maze_grid` - 2D grid of the lower floor where `true` marks a wall
+    /// * `entrance` - The cell the player starts in
+    /// * `exit_cell` - The maze's exit cell, if known
+    ///
+    /// # Returns
+    ///
+    /// The arrows to draw, or none when hints are off
+    fn place_signposts(
+        &self,
+        maze_grid: &[Vec<bool>],
+        entrance: Cell,
+        exit_cell: Option<Cell>,
+    ) -> Vec<SignDecal> {
+        let hints_on = self.navigation_hints || self.level_plan.signposts;
+        let Some(exit) = exit_cell.filter(|_| hints_on && !self.collision_system.is_test_mode)
+        else {
+            return Vec::new();
+        };
+
+        let path = match &self.collision_system.floors {
+            Some(plan) => plan
+                .route((entrance, Layer::Lower), (exit, Layer::Upper))
+                .map(|route| {
+                    route
+                        .into_iter()
+                        .take_while(|&(_, layer)| layer == Layer::Lower)
+                        .map(|(cell, _)| cell)
+                        .collect()
+                }),
+            None => maze::find_path(maze_grid, entrance, exit),
+        }
+        .unwrap_or_default();
+
+        let maze_dimensions = (maze_grid.first().map_or(0, Vec::len), maze_grid.len());
+        signage::place_signposts(maze_grid, &path, exit)
+            .iter()
+            .map(|signpost| signpost.decal(maze_dimensions, false))
+            .collect()
+    }
+
+    /// Rolls the level's anomaly and registers it as an interactable.
+    ///
+    /// Only levels [planned](LevelPlan::anomaly) with one may hide it, and test mode and
+    /// two-floor levels never do. An anomaly left over from an earlier attempt at the
+    /// level is replaced, so retrying brings back one the player already took.
+    ///
+    /// # Arguments
+    ///
+    /// * `maze_grid` - 2D grid of the maze where `true` marks a wall
+    /// * `entrance` - The cell the player starts in
+    /// * `exit_cell` - The maze's exit cell, if known
+    /// * `level_seed` - The level's event seed
+    fn place_anomaly(
+        &mut self,
+        maze_grid: &[Vec<bool>],
+        entrance: Cell,
+        exit_cell: Option<Cell>,
+        level_seed: u64,
+    ) {
+        if let Some(old) = self.anomaly.take() {
+            let old = old.interactable();
+            self.interactables
+                .retain(|interactable| *interactable != old);
+        }
+        self.interaction_target = None;
+        self.anomaly_shimmer = Shimmer::default();
+        if !self.level_plan.anomaly
+            || self.collision_system.is_test_mode
+            || self.collision_system.floors.is_some()
+        {
+            return;
+        }
+        self.anomaly =
+            exit_cell.and_then(|exit| Anomaly::roll(level_seed, maze_grid, entrance, exit, false));
+        if let Some(anomaly) = &self.anomaly {
+            self.interactables.push(anomaly.interactable());
+        }
+    }
+
+    /// Takes the anomaly if the player is looking at it.
+    ///
+    /// Should be called when the interact key is pressed.
+    ///
+    /// # Returns
+    ///
+    /// The id of the collected anomaly, or `None` if the player isn't looking at it
+    pub fn collect_anomaly(&mut self) -> Option<&'static str> {
+        let interactable = self.anomaly.as_ref()?.interactable();
+        if self.interaction_target() != Some(&interactable) {
+            return None;
+        }
+        self.interactables.retain(|other| *other != interactable);
+        self.interaction_target = None;
+        self.game_ui.interaction_hint = None;
+        self.anomaly.take().map(|anomaly| anomaly.collectible)
+    }
+
+    /// Plays the shimmer while the player is near the anomaly.
+    ///
+    /// Should be called once per frame while the game screen is active.
+    pub fn update_anomaly_shimmer(&mut self) {
+        let [x, _, z] = self.player.position;
+        let distance = self.anomaly.as_ref().map(|anomaly| {
+            let [ax, _, az] = anomaly.position;
+            ((ax - x).powi(2) + (az - z).powi(2)).sqrt()
+        });
+        if let Some(volume) = self.anomaly_shimmer.update(distance, self.delta_time) {
+            self.audio_manager
+                .play_anomaly_shimmer(volume)
+                .expect("Failed to play anomaly shimmer");
+        }
+    }
+
+    /// Rolls whether the level's enemy spawns partway through.
+    ///
+    /// Only levels [planned](levels::EnemyPlan::delayed_spawn) with a delayed spawn roll one;
+    /// test mode, the sandbox, two-floor levels, and game states built without an enemy
+    /// always start with the enemy in the maze. While a spawn is pending the enemy's audio
+    /// source is removed, to be spawned again when the enemy arrives; otherwise it is
+    /// spawned now if an earlier level's delayed spawn left it out.
+    ///
+    /// # Arguments
+    ///
+    /// * `maze_grid` - 2D grid of the maze where `true` marks a wall
+    /// * `entrance` - The cell the player starts in
+    /// * `exit_cell` - The maze's exit cell, if known
+    /// * `level_seed` - The level's event seed
+    fn schedule_spawn(
+        &mut self,
+        maze_grid: &[Vec<bool>],
+        entrance: Cell,
+        exit_cell: Option<Cell>,
+        level_seed: u64,
+    ) {
+        let delayable = self.level_plan.enemy.delayed_spawn
+            && self.enemy_enabled
+            && self.sandbox.is_none()
+            && !self.collision_system.is_test_mode
+            && self.collision_system.floors.is_none();
+        self.spawn_director = exit_cell
+            .filter(|_| delayable)
+            .map(|exit| SpawnDirector::roll(level_seed, maze_grid, entrance, exit))
+            .unwrap_or_default();
+        self.audio_manager.stop_spawn_drone();
+
+        if let Some(cell) = self.spawn_director.cell() {
+            self.journal.record(JournalEvent::EnemySpawnScheduled {
+                spawn_at: self.spawn_director.spawn_at(),
+                cell,
+            });
+            self.audio_manager
+                .remove_enemy("enemy")
+                .expect("Failed to remove enemy audio");
+        } else if self.enemy_enabled && !self.audio_manager.has_enemy("enemy") {
+            self.audio_manager
+                .spawn_enemy("enemy".to_string(), self.enemy.pathfinder.position)
+                .expect("Failed to spawn enemy audio");
+        }
+        self.spawn_pack();
+    }
+
+    /// Advances the level's delayed enemy spawn by one frame.
+    ///
+    /// Only play time counts: the spawn holds still off the game screen and while the
+    /// level timer is paused or stopped. While the telegraph builds, the spawn warning
+    /// replaces any event announcement and the drone rises; when the spawn fires the enemy
+    /// materializes. Should be called once per frame on every screen, after
+    /// [`GameState::update_events`], so the drone falls silent when play stops.
+    pub fn update_spawn(&mut self) {
+        let playing = self.current_screen == CurrentScreen::Game
+            && self
+                .game_ui
+                .timer
+                .as_ref()
+                .is_some_and(|timer| timer.is_running && !timer.is_paused());
+        if playing && self.spawn_director.update(self.delta_time) {
+            self.materialize_enemy();
+        }
+
+        if let Some(warning) = self.spawn_director.warning().filter(|_| playing) {
+            self.game_ui.event_announcement = Some(warning);
+        }
+        let drone = if playing && self.spawn_director.is_pending() {
+            self.spawn_director.telegraph()
+        } else {
+            0.0
+        };
+        self.audio_manager
+            .set_spawn_drone(self.spawn_director.position(), drone)
+            .expect("Failed to play spawn drone");
+    }
+
+    /// Brings the delayed enemy into the maze at the spawn cell.
+    ///
+    /// The cell is checked against where the player stands first, see
+    /// [`SpawnDirector::settle_cell`]. The new enemy keeps the path radius the level
+    /// placed its predecessor with, starts locked like every enemy, and is released by
+    /// the game loop on the next frame.
+    fn materialize_enemy(&mut self) {
+        let cell = self
+            .spawn_director
+            .settle_cell(&self.collision_system.maze_grid, self.player.current_cell);
+        let position = self.spawn_director.position();
+        let mut enemy = Enemy::new(position, self.enemy.pathfinder.base_path_radius);
+        enemy.tuning = self.level_plan.enemy.tuning;
+        self.enemy = enemy;
+        self.journal.record(JournalEvent::EnemySpawned { cell });
+
+        self.audio_manager.stop_spawn_drone();
+        self.audio_manager
+            .spawn_enemy("enemy".to_string(), position)
+            .expect("Failed to spawn enemy audio");
+        self.audio_manager
+            .play_enemy_sting()
+            .expect("Failed to play enemy sting");
+        self.spawn_pack();
+    }
+
+    /// Brings the rest of the level's enemies into the maze around the lead enemy.
+    ///
+    /// The level's [plan](LevelPlan::enemy) decides how many enemies hunt the player. The
+    /// pack starts on the open entries closest to the lead on its floor, at least two grid
+    /// steps from each other and from the lead, with the lead's tuning. Whatever pack the
+    /// last level or attempt left behind is cleared first, and no pack spawns while the
+    /// lead itself is missing.
+    fn spawn_pack(&mut self) {
+        self.clear_pack();
+        let extra = self.level_plan.enemy.count.saturating_sub(1) as usize;
+        if extra == 0 || !self.enemy_present() || self.collision_system.maze_grid.is_empty() {
+            return;
+        }
+
+        let lead = &self.enemy;
+        let layer = lead.layer;
+        let maze_dimensions = self.collision_system.maze_dimensions;
+        let is_test_mode = self.collision_system.is_test_mode;
+        let lead_position = lead.pathfinder.position;
+        let start = coordinates::world_to_maze(lead_position, maze_dimensions, is_test_mode);
+        let hover = lead_position[1] - self.collision_system.ground(lead_position, layer).1;
+        let mut open: Vec<(usize, Cell)> =
+            maze::path_distances(self.collision_system.grid(layer), start)
+                .into_iter()
+                .enumerate()
+                .flat_map(|(row, distances)| {
+                    distances
+                        .into_iter()
+                        .enumerate()
+                        .filter_map(move |(col, distance)| {
+                            distance.map(|distance| (distance, Cell::new(row, col)))
+                        })
+                })
+                .collect();
+        open.sort_by_key(|&(distance, cell)| (distance, cell.row, cell.col));
+
+        let mut taken = vec![start];
+        let mut enemies = Vec::with_capacity(extra);
+        for (_, cell) in open {
+            if enemies.len() == extra {
+                break;
+            }
+            if taken
+                .iter()
+                .any(|other| other.row.abs_diff(cell.row) + other.col.abs_diff(cell.col) < 2)
+            {
+                continue;
+            }
+            taken.push(cell);
+            let center = coordinates::maze_to_world(&cell, maze_dimensions, 0.0, is_test_mode);
+            let ground = self.collision_system.ground(center, layer).1;
+            let mut enemy = Enemy::new(
+                [center[0], ground + hover, center[2]],
+                lead.pathfinder.base_path_radius,
+            );
+            enemy.tuning = lead.tuning;
+            enemy.layer = layer;
+            enemies.push(enemy);
+        }
+
+        self.pack.reset(enemies);
+        for (id, enemy) in self.pack.audio_ids().zip(&self.pack.enemies) {
+            self.audio_manager
+                .spawn_enemy(id, enemy.pathfinder.position)
+                .expect("Failed to spawn enemy audio");
+        }
+    }
+
+    /// Takes every pack member out of the maze, leaving the lead enemy alone.
+    pub fn clear_pack(&mut self) {
+        for id in self.pack.audio_ids() {
+            self.audio_manager
+                .remove_enemy(&id)
+                .expect("Failed to remove enemy audio");
+        }
+        self.pack.reset(Vec::new());
+    }
+
+    /// Returns every enemy hunting the player, the lead first and then the pack.
+    pub fn enemies(&self) -> impl Iterator<Item = &Enemy> {
+        std::iter::once(&self.enemy).chain(&self.pack.enemies)
+    }
+
+    /// Moves every enemy one frame closer to the player.
+    ///
+    /// The pack follows the lead's lock and the routes [`Pack::waypoints`] plans for it. A
+    /// pack member that catches the player is swapped in as the lead, so the capture sequence
+    /// plays out on the enemy that caught them.
+    pub fn update_enemies(&mut self) {
+        let player_position = self.player.position;
+        let player_layer = self.player.layer;
+        let level = self.game_ui.level as u32;
+        let delta_time = self.delta_time;
+        let collision_system = &self.collision_system;
+        let waypoints = self.pack.waypoints(
+            &self.enemy,
+            player_position,
+            player_layer,
+            collision_system,
+            delta_time,
+        );
+
+        let locked = self.enemy.pathfinder.locked;
+        let enemies = std::iter::once(&mut self.enemy).chain(&mut self.pack.enemies);
+        for (enemy, waypoint) in enemies.zip(waypoints) {
+            enemy.pathfinder.locked = locked;
+            enemy.player_crouching = self.player.crouching;
+            enemy.pathfinder.catch_radius = self.dimensions.catch_radius;
+            enemy.update_in_pack(
+                waypoint,
+                player_position,
+                player_layer,
+                collision_system.floors.as_ref(),
+                delta_time,
+                level,
+                |from, to, layer| {
+                    collision_system.cylinder_intersects_geometry(from, to, 5.0, layer)
+                },
+            );
+        }
+
+        if let Some(catcher) = self
+            .pack
+            .enemies
+            .iter()
+            .position(|enemy| enemy.pathfinder.reached_player)
+        {
+            std::mem::swap(&mut self.enemy, &mut self.pack.enemies[catcher]);
+        }
+    }
+
+    /// Pauses the audio of every enemy, the lead and the pack.
+    ///
+    /// # Returns
+    ///
+    /// `Ok(())` on success, or the first error from [`GameAudioManager::pause_enemy_audio`]
+    pub fn pause_enemy_audio(&mut self) -> Result<(), Box<dyn Error>> {
+        self.audio_manager.pause_enemy_audio("enemy")?;
+        for id in self.pack.audio_ids() {
+            self.audio_manager.pause_enemy_audio(&id)?;
+        }
+        Ok(())
+    }
+
+    /// Resumes the audio of every enemy, the lead and the pack.
+    ///
+    /// # Returns
+    ///
+    /// `Ok(())` on success, or the first error from [`GameAudioManager::resume_enemy_audio`]
+    pub fn resume_enemy_audio(&mut self) -> Result<(), Box<dyn Error>> {
+        self.audio_manager.resume_enemy_audio("enemy")?;
+        for id in self.pack.audio_ids() {
+            self.audio_manager.resume_enemy_audio(&id)?;
+        }
+        Ok(())
+    }
+
+    /// Moves every enemy's audio source to where the enemy stands.
+    ///
+    /// # Returns
+    ///
+    /// `Ok(())` on success, or the first error from
+    /// [`GameAudioManager::update_enemy_position`]
+    pub fn update_enemy_audio(&mut self) -> Result<(), Box<dyn Error>> {
+        self.audio_manager
+            .update_enemy_position("enemy", self.enemy.pathfinder.position)?;
+        for (id, enemy) in self.pack.audio_ids().zip(&self.pack.enemies) {
+            self.audio_manager
+                .update_enemy_position(&id, enemy.pathfinder.position)?;
+        }
+        Ok(())
+    }
+
+    /// Cancels the delayed enemy spawn, for the player reaching the exit first.
+    ///
+    /// Does nothing if the enemy has already arrived or the level never delayed it.
+    pub fn cancel_spawn(&mut self) {
+        if self.spawn_director.cancel() {
+            self.journal.record(JournalEvent::EnemySpawnCancelled);
+            self.audio_manager.stop_spawn_drone();
+        }
+    }
+
+    /// Returns whether the player is on the floor the exit is on.
+    ///
+    /// Always true on single-floor levels; on two-floor levels the exit is upstairs.
+    pub fn on_exit_floor(&self) -> bool {
+        self.collision_system.floors.is_none() || self.player.layer == Layer::Upper
+    }
+
+    /// Picks where the compass should point.
+    ///
+    /// On the lower floor of a two-floor level the exit is out of reach until the player
+    /// climbs, so the compass points at the nearest stairwell instead. In
+    /// [`CompassMode::North`] it points just north of the player instead.
+    ///
+    /// # Arguments
+    ///
+    /// * `exit_position` - The (x, z) world position of the exit
+    ///
+    /// # Returns
+    ///
+    /// The (x, z) world position the compass should point at
+    pub fn compass_target(&self, exit_position: (f32, f32)) -> (f32, f32) {
+        if self.compass_mode == CompassMode::North {
+            // Rows count up along +z, so north is -z; only the direction matters
+            let [x, _, z] = self.player.position;
+            return (x, z - 1.0);
+        }
+        if self.on_exit_floor() {
+            return exit_position;
+        }
+        self.collision_system
+            .floors
+            .as_ref()
+            .and_then(|plan| plan.nearest_stairwell(self.player.position))
+            .unwrap_or(exit_position)
+    }
+
+    /// Remembers the freshly loaded level so it can be retried without regenerating it.
+    ///
+    /// Call this once the player has been placed at the entrance and the enemy at
+    /// its spawn point. The current run score is recorded as the level's starting score.
+    ///
+    /// # Arguments
+    ///
+    /// * `maze_grid` - 2D grid of the level's maze where `true` marks a wall
+    /// * `exit_cell` - The maze's exit cell
+    pub fn cache_level(&mut self, maze_grid: &[Vec<bool>], exit_cell: Cell) {
+        self.level_snapshot = Some(LevelSnapshot::capture(
+            maze_grid,
+            exit_cell,
+            &self.player,
+            &self.enemy,
+            self.game_ui.score,
+        ));
+    }
+
+    /// Forgets the cached level so a later retry cannot bring back an old maze.
+    pub fn clear_level_snapshot(&mut self) {
+        self.level_snapshot = None;
+    }
+
+    /// Restarts the current level from its cached snapshot, skipping the loading screen.
+    ///
+    /// Puts the player back at the entrance and the enemy at its spawn point,
+    /// restarts the timer with the level's configured duration, clears per-level
+    /// tracking and the combo, and reverts the run score to what it was when the
+    /// level began. Anything earned during the abandoned attempt is forfeited.
+    ///
+    /// The maze geometry and collision data are left as they are, since they
+    /// still belong to the cached level.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the level was restarted, `false` if there is no cached level.
+    pub fn retry_level(&mut self) -> bool {
+        let Some(snapshot) = self.level_snapshot.take() else {
+            return false;
+        };
+
+        self.player = snapshot.spawn_player();
+        self.enemy = snapshot.spawn_enemy();
+        // The director may have eased off after the death
+        self.level_plan.enemy.tuning = self.enemy_tuning();
+        self.enemy.tuning = self.level_plan.enemy.tuning;
+        self.exit_cell = Some(snapshot.exit_cell());
+        self.exit_reached = false;
+        self.exit_reached_timer = 0.0;
+        self.beeper_rise_played = false;
+        self.last_movement = MovementOutcome::default();
+        self.reset_level_tracking(snapshot.maze_grid(), Some(snapshot.exit_cell()));
+        self.set_score(snapshot.score());
+        self.reset_game_timer();
+        self.journal.record(JournalEvent::LevelRetried {
+            level: self.game_ui.level,
+        });
+
+        // There is no countdown, so go straight back into the maze
+        self.current_screen = CurrentScreen::Game;
+        self.previous_screen = None;
+        self.level_snapshot = Some(snapshot);
+        true
+    }
+
+    /// Charges a frame's CPU time to the cell the player is standing in.
+    ///
+    /// Only done in debug builds and test mode, where the frame heatmap overlay is
+    /// available.
+    ///
+    /// # Arguments
+    ///
+    /// * `frame_time` - CPU time spent on the frame
+    pub fn record_frame_cost(&mut self, frame_time: Duration) {
+        if !cfg!(debug_assertions) && !self.is_test_mode {
+            return;
+        }
+        self.frame_heatmap.record(
+            self.player.current_cell,
+            self.player.layer,
+            frame_time.as_secs_f32() * 1000.0,
+        );
+    }
+
+    /// Records per-frame statistics used for level scoring.
+    ///
+    /// Marks the player's current cell as visited, records the player's path
+    /// for the level-end replay, tracks the closest
+    /// distance between the player and an unlocked enemy, and counts each
+    /// time the enemy comes within close-call range. Should be called once
+    /// per frame while the game screen is active.
+    pub fn track_level_progress(&mut self) {
+        self.visited_cells.mark(self.player.current_cell);
+        self.run_recording
+            .record(self.player.position, self.delta_time);
+
+        let distance = self.enemy_distance();
+        if let Some(distance) = distance {
+            self.closest_enemy_distance = self.closest_enemy_distance.min(distance);
+        }
+
+        let in_close_call = distance.is_some_and(|distance| distance < CLOSE_CALL_RANGE);
+        if in_close_call && !self.in_close_call {
+            self.close_calls += 1;
+        }
+        self.in_close_call = in_close_call;
+    }
+
+    /// Advances the overhead map view by one frame.
+    ///
+    /// The view is only allowed while playing, so it is unavailable during the
+    /// exit animation, the loading screen, and menus, and it drops back to
+    /// first person whenever the enemy comes within [`ENEMY_BLOCK_RANGE`].
+    /// The level timer drains faster while the view is held.
+    ///
+    /// # Arguments
+    ///
+    /// * `held` - Whether the map view key is held
+    pub fn update_map_view(&mut self, held: bool) {
+        let enemy_clear = self
+            .enemy_distance()
+            .is_none_or(|distance| distance > ENEMY_BLOCK_RANGE);
+        let allowed =
+            self.current_screen == CurrentScreen::Game && !self.exit_reached && enemy_clear;
+        self.map_view.update(held, allowed, self.delta_time);
+        self.game_ui.timer_rate = self.map_view.timer_rate();
+    }
+
+    /// Returns whether the quick-settings radial may be open.
+    ///
+    /// Only during play: never on menus or the loading screen, during the exit
+    /// animation, while the overhead map view is up, or while the enemy has the player.
+    pub fn quick_radial_allowed(&self) -> bool {
+        self.current_screen == CurrentScreen::Game
+            && !self.exit_reached
+            && !self.map_view.is_engaged()
+            && self.capture.is_none()
+    }
+
+    /// Advances the quick-settings radial by one frame.
+    ///
+    /// Closes the radial without activating anything once it is no longer allowed or its
+    /// key went up unseen, and counts down its confirmation line in real time, so the line
+    /// doesn't linger while the radial slows the game down again.
+    ///
+    /// # Arguments
+    ///
+    /// * `held` - Whether the quick-settings key is held
+    pub fn update_quick_radial(&mut self, held: bool) {
+        let real_delta = self.delta_time / self.quick_radial.time_scale();
+        if self.quick_radial.is_open() && !(held && self.quick_radial_allowed()) {
+            self.quick_radial.cancel();
+        }
+        self.quick_radial.update(real_delta);
+        self.game_ui.quick_confirmation = self.quick_radial.confirmation().map(str::to_owned);
+    }
+
+    /// Returns whether the center-screen reticle should be drawn.
+    ///
+    /// Only while playing in first person: never on menus, during the exit
+    /// animation, while the overhead map view is up, or while the enemy has
+    /// the player.
+    pub fn reticle_visible(&self) -> bool {
+        self.current_screen == CurrentScreen::Game
+            && !self.map_view.is_engaged()
+            && self.capture.is_none()
+    }
+
+    /// Starts the capture sequence after the enemy catches the player.
+    ///
+    /// The enemy lunges from where it caught the player toward the camera, so
+    /// this should be called before the enemy is moved or replaced. Stops any
+    /// click-to-move walk and footsteps, and plays the capture sting.
+    pub fn start_capture(&mut self) {
+        self.capture = Some(CaptureSequence::new(
+            self.enemy.pathfinder.position,
+            self.player.position,
+            self.player.view_direction(),
+        ));
+        self.auto_walk.cancel();
+        self.audio_manager
+            .stop_movement()
+            .expect("Failed to stop movement audio");
+        self.audio_manager
+            .play_capture_sting()
+            .expect("Failed to play capture sting");
+    }
+
+    /// Advances the capture sequence by one frame and moves the enemy along its lunge.
+    ///
+    /// # Returns
+    ///
+    /// `true` once the sequence has finished and the game over screen should be shown.
+    /// Always `false` while no capture is playing.
+    pub fn update_capture(&mut self) -> bool {
+        let Some(capture) = self.capture.as_mut() else {
+            return false;
+        };
+        let phase = capture.advance(self.delta_time);
+        self.enemy.pathfinder.position = capture.enemy_position();
+        phase == CapturePhase::Finished
+    }
+
+    /// Changes the player's size and ranges.
+    ///
+    /// The collision system is updated to the new cylinder, and the player is pushed out of
+    /// any wall a wider or taller cylinder now overlaps, so a mid-run upgrade never leaves
+    /// them stuck inside one.
+    ///
+    /// # Arguments
+    ///
+    /// * `dimensions` - The player's new dimensions
+    pub fn set_dimensions(&mut self, dimensions: PlayerDimensions) {
+        if dimensions == self.dimensions {
+            return;
+        }
+        self.dimensions = dimensions;
+        self.collision_system.player_radius = dimensions.collision_radius;
+        self.collision_system.player_height = dimensions.collision_height;
+
+        let (position, layer) = (self.player.position, self.player.layer);
+        let ground = self.collision_system.ground(position, layer).1;
+        let resolved = self
+            .collision_system
+            .push_out_of_walls([position[0], position[1] - ground, position[2]], layer);
+        self.player.position = [resolved[0], resolved[1] + ground, resolved[2]];
+    }
+
+    /// Checks, once a second, whether the player has ended up inside a wall.
+    ///
+    /// Records the frame in [`GameState::wall_clip`]. When a check finds the player inside a
+    /// wall, they are moved to the nearest place they can stand, and the clip is recorded in
+    /// the journal and, with `--clip-reports`, written to its own file. Should be called once
+    /// per gameplay frame, after the player has moved.
+    ///
+    /// # Arguments
+    ///
+    /// * `upgrades` - Upgrades the player holds, for the report
+    pub fn check_wall_clip(&mut self, upgrades: &HashMap<AvailableUpgrade, u32>) {
+        if !self.wall_clip.record(self.player.position, self.delta_time) {
+            return;
+        }
+        let (position, layer) = (self.player.position, self.player.layer);
+        let Some(wall) = self.collision_system.wall_containing(position, layer) else {
+            return;
+        };
+        let ground = self.collision_system.ground(position, layer).1;
+        let resolved = self
+            .collision_system
+            .nearest_open_position([position[0], position[1] - ground, position[2]], layer);
+        self.player.position = [resolved[0], resolved[1] + ground, resolved[2]];
+        eprintln!(
+            "WARNING: Player was inside wall ({}, {}) at {:?}, moved to {:?}",
+            wall.row, wall.col, position, self.player.position
+        );
+        self.toasts.gameplay("Moved you out of a wall");
+
+        let mut held: Vec<(String, u32)> = upgrades
+            .iter()
+            .map(|(upgrade, &count)| (format!("{:?}", upgrade), count))
+            .collect();
+        held.sort();
+        let context = ClipContext {
+            level: self.game_ui.level,
+            run_seed: self.run_seed,
+            upper_floor: layer == Layer::Upper,
+            upgrades: &held,
+        };
+        let Some(report) = self.wall_clip.capture(context, wall, self.player.position) else {
+            return;
+        };
+        if wall_clip::report_files_enabled() {
+            wall_clip::write_report_in_background(
+                report.clone(),
+                crate::paths::get().clip_reports_dir(),
+                self.toasts.clone(),
+            );
+        }
+        self.journal.record(JournalEvent::WallClipped { report });
+    }
+
+    /// Works out which interactable, if any, the player is looking at.
+    ///
+    /// Casts the player's view ray against [`GameState::interactables`] and
+    /// mirrors the target's hint into the game UI. There is never a target
+    /// while the reticle is hidden. Should be called once per frame after the
+    /// map view has been updated.
+    pub fn update_interaction_target(&mut self) {
+        self.interaction_target = None;
+        if self.reticle_visible() && !self.interactables.is_empty() {
+            let eye = self.player.position;
+            let layer = self.player.layer;
+            let ground = self.collision_system.ground(eye, layer).1;
+            let collision_system = &self.collision_system;
+            self.interaction_target = interact::find_target(
+                eye,
+                self.player.view_direction(),
+                &self.interactables,
+                self.dimensions.reach,
+                |from, to| {
+                    collision_system.cylinder_intersects_geometry(
+                        [from[0], from[1] - ground, from[2]],
+                        [to[0], to[1] - ground, to[2]],
+                        1.0,
+                        layer,
+                    )
+                },
+            );
+        }
+        self.game_ui.interaction_hint = self.interaction_target().map(Interactable::hint);
+    }
+
+    /// Returns the interactable the player is looking at, if any.
+    pub fn interaction_target(&self) -> Option<&Interactable> {
+        self.interaction_target
+            .and_then(|index| self.interactables.get(index))
+    }
+
+    /// Sends the player walking to the floor under a point of the 3D view.
+    ///
+    /// The point is unprojected onto the player's floor and snapped to the open
+    /// cell under it, or in front of it when the point is on a wall, then a
+    /// route there is found with the grid pathfinder. Replaces any walk already
+    /// in progress. Plays the denied cue instead when there is nowhere reachable
+    /// to walk to, the enemy is within [`ENEMY_STOP_RANGE`], or the player
+    /// can't move right now.
+    ///
+    /// # Arguments
+    ///
+    /// * `ndc` - The clicked point in normalized device coordinates of the 3D view
+    /// * `view_proj` - The camera's view-projection matrix for this frame
+    ///
+    /// # Returns
+    ///
+    /// `true` if a walk was started.
+    pub fn request_auto_walk(&mut self, ndc: [f32; 2], view_proj: &Mat4) -> bool {
+        self.auto_walk.cancel();
+        let started = match self.plan_auto_walk(ndc, view_proj) {
+            Some((waypoints, arrival_radius)) => {
+                self.auto_walk.start(waypoints, arrival_radius);
+                true
+            }
+            None => false,
+        };
+        if !started {
+            self.audio_manager
+                .play_denied()
+                .expect("Failed to play denied sound");
+        }
+        started
+    }
+
+    /// Works out the route for [`GameState::request_auto_walk`].
+    ///
+    /// # Returns
+    ///
+    /// The world positions of the cells to walk through and the distance within
+    /// which each counts as reached, or `None` if the click should be denied.
+    fn plan_auto_walk(&self, ndc: [f32; 2], view_proj: &Mat4) -> Option<(Vec<[f32; 3]>, f32)> {
+        let enemy_close = self
+            .enemy_distance()
+            .is_some_and(|distance| distance < ENEMY_STOP_RANGE);
+        if self.current_screen != CurrentScreen::Game
+            || self.exit_reached
+            || self.map_view.is_engaged()
+            || self.capture.is_some()
+            || enemy_close
+        {
+            return None;
+        }
+
+        let layer = self.player.layer;
+        let eye = self.player.position;
+        let ground = self.collision_system.ground(eye, layer).1;
+        let mut hit = auto_walk::unproject_to_floor(ndc, &view_proj.try_inverse()?, ground)?;
+
+        // A wall in the way means the click landed on the wall; find where the view ray
+        // meets it so the click snaps to the cell in front of it
+        let blocked = |t: f32| {
+            let point = [
+                eye[0] + (hit[0] - eye[0]) * t,
+                (eye[1] - ground) * (1.0 - t) + t,
+                eye[2] + (hit[2] - eye[2]) * t,
+            ];
+            let from = [eye[0], eye[1] - ground, eye[2]];
+            self.collision_system
+                .cylinder_intersects_geometry(from, point, 1.0, layer)
+        };
+        if blocked(1.0) {
+            let (mut clear, mut wall) = (0.0, 1.0);
+            for _ in 0..16 {
+                let middle = (clear + wall) / 2.0;
+                if blocked(middle) {
+                    wall = middle;
+                } else {
+                    clear = middle;
+                }
+            }
+            hit = [
+                eye[0] + (hit[0] - eye[0]) * wall,
+                ground,
+                eye[2] + (hit[2] - eye[2]) * wall,
+            ];
+        }
+
+        let maze_dimensions = self.collision_system.maze_dimensions;
+        let is_test_mode = self.collision_system.is_test_mode;
+        let goal = auto_walk::snap_to_open_cell(
+            self.collision_system.grid(layer),
+            auto_walk::world_to_grid_point(hit, maze_dimensions, is_test_mode),
+        )?;
+        self.plan_walk_to(goal, layer)
+    }
+
+    /// Sends the player walking along the shortest route to the exit.
+    ///
+    /// Used by the soak test's bot in place of a click, so the walk is steered
+    /// and stopped exactly like click-to-move. Replaces any walk already in
+    /// progress.
+    ///
+    /// # Returns
+    ///
+    /// `true` if a walk was started, `false` if the level has no exit or the
+    /// player can't reach it.
+    pub fn auto_walk_to_exit(&mut self) -> bool {
+        self.auto_walk.cancel();
+        // On two-floor levels the exit is upstairs
+        let layer = match self.collision_system.floors {
+            Some(_) => Layer::Upper,
+            None => Layer::Lower,
+        };
+        let Some((waypoints, arrival_radius)) = self
+            .exit_cell
+            .and_then(|exit| self.plan_walk_to(exit, layer))
+        else {
+            return false;
+        };
+        self.auto_walk.start(waypoints, arrival_radius);
+        true
+    }
+
+    /// Works out the route from the player to a cell for click-to-move.
+    ///
+    /// # Arguments
+    ///
+    /// * `goal` - Wall-grid cell to walk to
+    /// * `goal_layer` - Floor the cell is on
+    ///
+    /// # Returns
+    ///
+    /// The world positions of the cells to walk through and the distance within
+    /// which each counts as reached, or `None` if the cell can't be reached.
+    fn plan_walk_to(&self, goal: Cell, goal_layer: Layer) -> Option<(Vec<[f32; 3]>, f32)> {
+        let layer = self.player.layer;
+        let eye = self.player.position;
+        let ground = self.collision_system.ground(eye, layer).1;
+        let maze_dimensions = self.collision_system.maze_dimensions;
+        let is_test_mode = self.collision_system.is_test_mode;
+        let start = coordinates::world_to_maze(eye, maze_dimensions, is_test_mode);
+        let route = self
+            .collision_system
+            .route((start, layer), (goal, goal_layer))?;
+
+        // The player is already in the first cell
+        let mut waypoints: Vec<[f32; 3]> = Vec::with_capacity(route.len());
+        let mut last_cell = start;
+        for (cell, cell_layer) in route.into_iter().skip(1) {
+            // Changing floors happens by walking across the stairwell's one cell
+            if cell == last_cell {
+                continue;
+            }
+            let center = coordinates::maze_to_world(&cell, maze_dimensions, 0.0, is_test_mode);
+            let floor = self.collision_system.ground(center, cell_layer).1;
+            waypoints.push([center[0], floor, center[2]]);
+            last_cell = cell;
+        }
+        if waypoints.is_empty() {
+            let center = coordinates::maze_to_world(&goal, maze_dimensions, ground, is_test_mode);
+            waypoints.push(center);
+        }
+
+        let cell_size = coordinates::calculate_cell_size(maze_dimensions, is_test_mode);
+        Some((waypoints, cell_size * ARRIVAL_FRACTION))
+    }
+
+    /// Leaves the loading screen for the level and starts its timer, once the level is ready.
+    ///
+    /// Does nothing while the maze is still being generated.
+    pub fn begin_loaded_level(&mut self) {
+        if !self.level_ready {
+            return;
+        }
+        self.current_screen = CurrentScreen::Game;
+        if let Some(timer) = &mut self.game_ui.timer {
+            timer.start();
+        }
+    }
+
+    /// Steers the click-to-move walk by one frame.
+    ///
+    /// Stops the walk when play stops, including the exit cutscene, or when
+    /// the enemy comes within [`ENEMY_STOP_RANGE`]. Should be called once per
+    /// frame while the player is free to move.
+    ///
+    /// # Returns
+    ///
+    /// Whether the walk moves the player forward this frame.
+    pub fn update_auto_walk(&mut self) -> bool {
+        if !self.auto_walk.is_active() {
+            return false;
+        }
+        let enemy_close = self
+            .enemy_distance()
+            .is_some_and(|distance| distance < ENEMY_STOP_RANGE);
+        if self.current_screen != CurrentScreen::Game || self.exit_reached || enemy_close {
+            self.auto_walk.cancel();
+            return false;
+        }
+        self.auto_walk
+            .steer(self.player.position, &mut self.player.yaw, self.delta_time)
+    }
+
+    /// Advances the movement combo by one frame.
+    ///
+    /// Feeds the combo the player's speed from [`GameState::last_movement`], the
+    /// current enemy distance, and whether the player ran into a wall. Plays the
+    /// combo-lost cue when an active combo is reset and mirrors the multiplier
+    /// into the game UI. Should be called once per frame after movement while
+    /// the game screen is active; not calling it (e.g. while paused) leaves the
+    /// combo untouched.
+    ///
+    /// # Returns
+    ///
+    /// The reason the combo was lost this frame, if it was.
+    pub fn update_combo(&mut self) -> Option<ComboReset> {
+        let speed = if self.delta_time > 0.0 {
+            self.last_movement.distance / self.delta_time
+        } else {
+            0.0
+        };
+
+        let reset = self.combo.tick(ComboFrame {
+            delta_time: self.delta_time,
+            speed,
+            enemy_distance: self.enemy_distance().unwrap_or(f32::MAX),
+            hit_wall_head_on: self.last_movement.hit_wall_head_on,
+        });
+
+        if reset.is_some() {
+            self.audio_manager
+                .play_combo_lost()
+                .expect("Failed to play combo lost sound");
+        }
+        self.game_ui.combo_multiplier = self.combo.multiplier();
+        reset
+    }
+
+    /// Advances the level's ambient events by one frame.
+    ///
+    /// Starts and ends events on the level's event clock, plays the event cue for
+    /// each one that starts and any phantom enemy sound an event asked for, and
+    /// mirrors the running events into the player's stamina regeneration and the
+    /// HUD announcement. Should be called once per frame while the game screen is
+    /// active; not calling it (e.g. while paused) holds every event where it is.
+    pub fn update_events(&mut self) {
+        self.event_clock.advance_secs(self.delta_time);
+        let now = self.event_clock.now();
+
+        for transition in self.events.update(now) {
+            if let EventTransition::Started(_) = transition {
+                self.audio_manager
+                    .play_event_cue()
+                    .expect("Failed to play event cue");
+            }
+        }
+        if let Some(bearing) = self.events.take_echo() {
+            self.audio_manager
+                .play_echo(bearing)
+                .expect("Failed to play echo");
+        }
+
+        self.player.stamina_regen_multiplier = self.events.effects().stamina_regen_scale;
+        self.game_ui.event_announcement = self.events.announcement(now);
+    }
+
+    /// Advances the level's ambience by one frame.
+    ///
+    /// Plays the accents that fire this frame and fades the ambience bus in, or out once
+    /// the exit cutscene has started. Should be called once per frame on the game screen
+    /// and during the exit cutscene; not calling it (e.g. while paused) holds the
+    /// soundscape where it is.
+    pub fn update_soundscape(&mut self) {
+        if self.current_screen == CurrentScreen::ExitReached {
+            self.soundscape.begin_fade_out();
+        }
+        for shot in self.soundscape.update(self.delta_time) {
+            self.audio_manager
+                .play_ambient_accent(&shot)
+                .expect("Failed to play ambient accent");
+        }
+        self.audio_manager
+            .set_ambience_level(self.soundscape.gain());
+    }
+
+    /// Advances the entrance door and the camera shake by one frame.
+    ///
+    /// When the door lands, its collision goes up, the thud plays, and the camera
+    /// shakes. Should be called once per frame while the game screen is active.
+    pub fn update_entrance_door(&mut self) {
+        let (delta_time, position) = (self.delta_time, self.player.position);
+        let radius = self.collision_system.player_radius;
+        let landed = self
+            .entrance_door
+            .as_mut()
+            .is_some_and(|door| door.update(delta_time, position, radius));
+        if landed {
+            self.collision_system
+                .set_door(self.entrance_door.as_ref().map(|door| door.span));
+            self.camera_shake.start();
+            self.audio_manager
+                .play_door_thud()
+                .expect("Failed to play door thud");
+        }
+        self.camera_shake
+            .update(self.delta_time, &mut rand::thread_rng());
+    }
+
+    /// Plays the enemy sting and the timer beep when their moment comes.
+    ///
+    /// Both duck the music while they play. Should be called once per frame while
+    /// the game screen is active.
+    pub fn update_warning_cues(&mut self) {
+        let cues = self.warning_cues.update(
+            self.delta_time,
+            self.enemy_distance(),
+            self.timer_critical(),
+        );
+        for cue in cues {
+            let result = match cue {
+                DuckCue::EnemySting => self.audio_manager.play_enemy_sting(),
+                DuckCue::TimerCritical => self.audio_manager.play_timer_critical(),
+                DuckCue::CaptureSting | DuckCue::Denied => Ok(()),
+            };
+            result.expect("Failed to play warning cue");
+        }
+    }
+
+    /// Gathers the facts the controller rumble reacts to this frame.
+    ///
+    /// # Arguments
+    ///
+    /// * `window_focused` - Whether the game window has keyboard focus
+    ///
+    /// # Returns
+    ///
+    /// The frame to tick the haptics director with
+    pub fn haptic_frame(&self, window_focused: bool) -> HapticFrame {
+        HapticFrame {
+            delta_time: self.delta_time,
+            in_gameplay: self.current_screen == CurrentScreen::Game,
+            window_focused,
+            enemy_distance: self.enemy_distance(),
+            timer_critical: self.timer_critical(),
+            caught: self.enemy.pathfinder.reached_player,
+        }
+    }
+
+    /// Gathers the facts the accessibility announcer reacts to this frame.
+    ///
+    /// # Arguments
+    ///
+    /// * `exit_bearing` - Where the HUD compass points, in degrees clockwise from where the
+    ///   player faces, or `None` if it isn't pointing anywhere
+    ///
+    /// # Returns
+    ///
+    /// The frame to update the announcer with
+    pub fn announcer_frame(&self, exit_bearing: Option<f32>) -> AnnouncerFrame {
+        let timer = self.game_ui.timer.as_ref();
+        AnnouncerFrame {
+            delta_time: self.delta_time,
+            screen: self.current_screen,
+            level: self.game_ui.level,
+            score: self.game_ui.score,
+            seconds_left: timer
+                .filter(|timer| timer.is_running)
+                .map(|timer| timer.get_remaining_time().as_secs_f32()),
+            enemy_distance: self.enemy_distance(),
+            exit_bearing,
+        }
+    }
+
+    /// Whether the level timer is running and below its critical threshold.
+    fn timer_critical(&self) -> bool {
+        self.game_ui.timer.as_ref().is_some_and(|timer| {
+            timer.is_running && timer.get_remaining_time() <= timer.config.critical_threshold
+        })
+    }
+
+    /// Horizontal distance between the player and the closest enemy.
+    ///
+    /// # Returns
+    ///
+    /// The distance in world units, or `None` while the enemies are locked in place.
+    fn enemy_distance(&self) -> Option<f32> {
+        if self.enemy.pathfinder.locked {
+            return None;
+        }
+        let player = self.player.position;
+        self.enemies()
+            .map(|enemy| {
+                let enemy = enemy.pathfinder.position;
+                let dx = player[0] - enemy[0];
+                let dz = player[2] - enemy[2];
+                (dx * dx + dz * dz).sqrt()
+            })
+            .reduce(f32::min)
+    }
+
+    /// Builds a [`LevelResult`] snapshot from the current level's statistics.
+    ///
+    /// # Returns
+    ///
+    /// A `LevelResult` describing the level as it stands right now.
+    pub fn level_result(&self) -> LevelResult {
+        let (completion_time, time_remaining) = if let Some(timer) = &self.game_ui.timer {
+            let remaining = timer.get_remaining_time().as_secs_f32();
+            (timer.config.duration.as_secs_f32() - remaining, remaining)
+        } else {
+            (30.0, 0.0) // Fallback values
+        };
+
+        LevelResult {
+            level: self.game_ui.level,
+            completion_time,
+            time_remaining,
+            cells_visited: self.visited_cells.visited_count(),
+            total_cells: self.visited_cells.walkable_count(),
+            closest_enemy_distance: self.closest_enemy_distance,
+            keys_collected: 0,
+            difficulty_multiplier: hardcore::score_multiplier(self.hardcore),
+            combo_multiplier: self.combo.multiplier(),
+            solution_length: self.solution_length,
+            close_calls: self.close_calls,
+            duplicate_anomalies: self.duplicate_anomalies,
+        }
+    }
+
+    /// Scores the current level and stores the breakdown for the level summary.
+    ///
+    /// Call this at the moment the player reaches the exit so the completion
+    /// time reflects the actual run rather than the exit animation.
+    ///
+    /// # Returns
+    ///
+    /// A reference to the stored [`ScoreBreakdown`].
+    pub fn finish_level(&mut self) -> &ScoreBreakdown {
+        let breakdown = ScoreCalculator::default().calculate(&self.level_result());
+        self.last_score_breakdown.insert(breakdown)
+    }
+
+    /// Feeds the level that just ended to the adaptive difficulty director.
+    ///
+    /// The new tuning takes effect when the next level's enemy is spawned and
+    /// is recorded in the session journal. Does nothing in test mode or during
+    /// a daily challenge or a challenge code with a fixed difficulty.
+    ///
+    /// # Arguments
+    ///
+    /// * `died` - Whether the level ended with the player losing
+    pub fn adapt_difficulty(&mut self, died: bool) {
+        let fixed_challenge = self
+            .challenge
+            .as_ref()
+            .is_some_and(|challenge| challenge.difficulty.pressure().is_some());
+        if self.is_test_mode
+            || self.daily.is_some()
+            || fixed_challenge
+            || !self.director.is_enabled()
+        {
+            return;
+        }
+        let tuning = self.director.record_level(&self.level_result(), died);
+        self.journal.record(JournalEvent::EnemyTuned {
+            pressure: self.director.pressure(),
+            tuning,
+        });
+    }
+
+    /// Returns the tuning for the next level's enemy.
+    ///
+    /// Daily challenges use their fixed tuning, challenge codes their difficulty and
+    /// modifiers, and regular runs the director's.
+    pub fn enemy_tuning(&self) -> EnemyTuning {
+        match (&self.daily, &self.challenge) {
+            (Some(run), _) => run.challenge.enemy_tuning(),
+            (None, Some(challenge)) => challenge.enemy_tuning(self.director.tuning()),
+            (None, None) => self.director.tuning(),
+        }
+    }
+
+    /// Returns the code that sets up the current run on another machine.
+    ///
+    /// # Returns
+    ///
+    /// The entered challenge's code, or one for the run's seed and mode; `None` for
+    /// daily challenges, test mode, and the sandbox, which a code can't describe.
+    pub fn challenge_code(&self) -> Option<String> {
+        if self.daily.is_some() || self.is_test_mode || self.sandbox.is_some() {
+            return None;
+        }
+        let challenge = match &self.challenge {
+            Some(challenge) => Challenge {
+                seed: self.run_seed,
+                hardcore: self.hardcore,
+                ..challenge.clone()
+            },
+            None => Challenge::for_run(self.run_seed, self.hardcore),
+        };
+        Some(challenge.encode())
+    }
+
+    /// Returns whether the level just finished was the entered challenge's last.
+    pub fn challenge_complete(&self) -> bool {
+        self.daily.is_none()
+            && self
+                .challenge
+                .as_ref()
+                .is_some_and(|challenge| challenge.is_last_level(self.game_ui.level))
+    }
+
+    /// Returns whether the level has an enemy hunting the player.
+    ///
+    /// Always true outside the sandbox, where the enemy can be switched off, unless the
+    /// game state was built [without one](GameStateBuilder::without_enemy) or the enemy
+    /// hasn't [arrived](SpawnDirector) yet.
+    pub fn enemy_present(&self) -> bool {
+        self.enemy_enabled
+            && !self.spawn_director.is_pending()
+            && self
+                .sandbox
+                .as_ref()
+                .is_none_or(|session| session.generated.enemy)
+    }
+
+    /// Picks the seed for a new run: the one chosen on the title screen, or a random one.
+    pub fn begin_run_seed(&mut self) {
+        self.run_seed = self.chosen_seed.unwrap_or_else(rand::random);
+    }
+
+    /// Returns the seed a hardcore death burns: the daily challenge's seed, or the run seed.
+    pub fn burnable_seed(&self) -> u64 {
+        match &self.daily {
+            Some(run) => run.challenge.seed,
+            None => self.run_seed,
+        }
+    }
+
+    /// Returns the maze seed for a level.
+    ///
+    /// # Arguments
+    ///
+    /// * `level` - The level about to be generated
+    ///
+    /// # Returns
+    ///
+    /// The daily challenge's seed for the level, or one derived from the run seed.
+    pub fn level_seed(&self, level: i32) -> u64 {
+        match &self.daily {
+            Some(run) => run.challenge.level_seed(level),
+            None => seed::level_seed(self.run_seed, level),
+        }
+    }
+
+    /// Plans a level of the current run and makes it the current plan.
+    ///
+    /// The plan is recorded in the journal.
+    ///
+    /// # Arguments
+    ///
+    /// * `level` - The level about to be generated
+    ///
+    /// # Returns
+    ///
+    /// The new plan, seeded from [`GameState::level_seed`] with the current enemy tuning.
+    pub fn plan_level(&mut self, level: i32) -> &LevelPlan {
+        let plan = levels::plan_level(
+            level,
+            Difficulty::of(self.hardcore, self.is_test_mode),
+            self.level_seed(level),
+            self.enemy_tuning(),
+            &self.difficulty_curve,
+        );
+        self.set_level_plan(plan);
+        &self.level_plan
+    }
+
+    /// Makes a plan the current one and records it in the journal.
+    ///
+    /// # Arguments
+    ///
+    /// * `plan` - The plan of the level about to be generated
+    pub fn set_level_plan(&mut self, plan: LevelPlan) {
+        self.journal
+            .record(JournalEvent::LevelPlanned { plan: plan.clone() });
+        self.level_plan = plan;
+    }
+
+    /// Returns the line naming the run's seed, shown while a level loads.
+    pub fn seed_label(&self) -> String {
+        if let Some(session) = &self.sandbox {
+            return format!(
+                "Seed: {} (sandbox)",
+                seed::format_seed(session.generated.seed)
+            );
+        }
+        match &self.daily {
+            Some(run) => format!("Seed: daily {}", daily::date_key(run.challenge.date)),
+            None => format!("Seed: {}", seed::format_seed(self.run_seed)),
+        }
+    }
+
+    /// Adds the completed level's points to the run score.
+    ///
+    /// This is the single place level points are awarded. Uses the breakdown
+    /// captured by [`GameState::finish_level`], scoring the level now if none
+    /// was captured.
+    ///
+    /// # Returns
+    ///
+    /// The [`ScoreBreakdown`] that was applied.
+    pub fn apply_level_score(&mut self) -> ScoreBreakdown {
+        let breakdown = self
+            .last_score_breakdown
+            .take()
+            .unwrap_or_else(|| ScoreCalculator::default().calculate(&self.level_result()));
+        self.set_score(self.game_ui.score + breakdown.total);
+        breakdown
+    }
+}
+
+/// Configuration settings for game timers.
+///
+/// This struct defines all the parameters needed to create a countdown timer,
+/// including the total duration, visual warning thresholds, and color schemes
+/// for different timer states.
+///
+/// # Color Transitions
+///
+/// The timer changes color based on remaining time:
+/// - **Normal**: More time than warning threshold (typically green)
+/// - **Warning**: Between warning and critical thresholds (typically yellow)
+/// - **Critical**: Less than critical threshold remaining (typically red)
+#[derive(Debug, Clone)]
+pub struct TimerConfig {
+    /// Total duration of the timer countdown.
+    pub duration: Duration,
 
     /// Time remaining when the timer should switch to warning color.
     ///
@@ -565,12 +2629,33 @@ impl Default for TimerConfig {
     }
 }
 
+/// Something that can pause the level timer.
+///
+/// Each source pauses and resumes the timer independently, so the timer only counts down
+/// again once every source that paused it has resumed it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PauseSource {
+    /// The pause menu is open
+    Menu,
+    /// The window lost focus
+    FocusLoss,
+}
+
+impl PauseSource {
+    /// Number of pause sources.
+    pub const COUNT: usize = 2;
+}
+
 /// Internal timer implementation that handles countdown logic and state tracking.
 ///
 /// This struct manages the actual countdown mechanics, pause/resume functionality,
 /// color transitions, and time formatting. It's used internally by [`GameUIManager`]
 /// and is not typically accessed directly by game code.
 ///
+/// Time is measured with a [`GameClock`] that is advanced by [`update()`](Self::update)
+/// each frame. Pausing simply stops advancing the clock, so paused time never counts
+/// against the timer and tests can drive the timer with exact durations.
+///
 /// # Timer States
 ///
 /// - **Stopped**: Timer is not running and will not count down
@@ -579,8 +2664,11 @@ impl Default for TimerConfig {
 /// - **Expired**: Timer has reached zero and stopped automatically
 #[derive(Debug)]
 pub struct GameTimer {
-    /// The moment when the timer was started (or last restarted).
-    pub start_time: Instant,
+    /// Clock advanced only while the timer is running and not paused.
+    pub clock: GameClock,
+
+    /// Clock reading when the timer was started (or last restarted).
+    pub start_time: Duration,
 
     /// Configuration defining duration, thresholds, and colors.
     pub config: TimerConfig,
@@ -591,16 +2679,10 @@ pub struct GameTimer {
     /// Whether the timer has reached zero.
     pub is_expired: bool,
 
-    /// If paused, the moment when pause was activated.
-    ///
-    /// `None` if the timer is not currently paused.
-    pub paused_at: Option<Instant>,
-
-    /// Total time that has been spent in paused state.
+    /// Which [`PauseSource`]s currently have the timer paused, indexed by source.
     ///
-    /// This is subtracted from elapsed time calculations to ensure
-    /// that paused time doesn't count against the timer duration.
-    pub elapsed_paused: Duration,
+    /// The clock is not advanced while any of them does.
+    pub paused_by: [bool; PauseSource::COUNT],
 }
 
 impl GameTimer {
@@ -614,12 +2696,12 @@ impl GameTimer {
     /// * `config` - Timer configuration including duration and color settings
     pub fn new(config: TimerConfig) -> Self {
         Self {
-            start_time: Instant::now(),
+            clock: GameClock::new(),
+            start_time: Duration::ZERO,
             config,
             is_running: false,
             is_expired: false,
-            paused_at: None,
-            elapsed_paused: Duration::ZERO,
+            paused_by: [false; PauseSource::COUNT],
         }
     }
 
@@ -629,44 +2711,54 @@ impl GameTimer {
     /// the configured duration. If the timer was previously paused or expired,
     /// it will be reset to a fresh state.
     pub fn start(&mut self) {
-        self.start_time = Instant::now();
+        self.start_time = self.clock.now();
         self.is_running = true;
         self.is_expired = false;
-        self.paused_at = None;
-        self.elapsed_paused = Duration::ZERO;
+        self.paused_by = [false; PauseSource::COUNT];
     }
 
-    /// Pauses the timer if it's currently running.
+    /// Pauses the timer on behalf of `source` if it's currently running.
     ///
     /// While paused, the timer will not count down and its display
     /// will remain frozen. The timer can be resumed with [`resume()`].
     ///
-    /// If the timer is already paused or not running, this method has no effect.
-    pub fn pause(&mut self) {
-        if self.is_running && self.paused_at.is_none() {
-            self.paused_at = Some(Instant::now());
+    /// If the timer is already paused by `source` or not running, this method has no effect.
+    ///
+    /// # Parameters
+    ///
+    /// * `source` - What is pausing the timer
+    pub fn pause(&mut self, source: PauseSource) {
+        if self.is_running {
+            self.paused_by[source as usize] = true;
         }
     }
 
-    /// Resumes the timer from a paused state.
+    /// Releases the pause held by `source`.
     ///
-    /// This method calculates how long the timer was paused and adds that
-    /// duration to the total paused time, ensuring that paused time doesn't
-    /// count against the timer duration.
+    /// Counting continues from where it was paused on the next [`update()`](Self::update),
+    /// unless another source still has the timer paused.
     ///
-    /// If the timer is not currently paused, this method has no effect.
-    pub fn resume(&mut self) {
-        if let Some(paused_at) = self.paused_at.take() {
-            self.elapsed_paused += paused_at.elapsed();
-        }
+    /// If the timer is not currently paused by `source`, this method has no effect.
+    ///
+    /// # Parameters
+    ///
+    /// * `source` - What paused the timer
+    pub fn resume(&mut self, source: PauseSource) {
+        self.paused_by[source as usize] = false;
+    }
+
+    /// Returns whether any source has the timer paused.
+    pub fn is_paused(&self) -> bool {
+        self.paused_by.contains(&true)
     }
 
     /// Stops the timer immediately.
     ///
-    /// Unlike pausing, a stopped timer cannot be resumed. To restart timing,
-    /// use [`start()`] which will reset the timer to its full duration.
+    /// Unlike pausing, a stopped timer cannot be resumed, and any pauses are dropped.
+    /// To restart timing, use [`start()`] which will reset the timer to its full duration.
     pub fn stop(&mut self) {
         self.is_running = false;
+        self.paused_by = [false; PauseSource::COUNT];
     }
 
     /// Resets the timer to its initial state without starting it.
@@ -674,16 +2766,21 @@ impl GameTimer {
     /// This clears the expired flag and resets pause state, but does not
     /// automatically start the timer. Call [`start()`] to begin countdown.
     pub fn reset(&mut self) {
-        self.start_time = Instant::now();
+        self.start_time = self.clock.now();
         self.is_expired = false;
-        self.paused_at = None;
-        self.elapsed_paused = Duration::ZERO;
+        self.paused_by = [false; PauseSource::COUNT];
+    }
+
+    /// Returns how much time has counted against the timer since it was started.
+    ///
+    /// Paused time is not included.
+    pub fn elapsed(&self) -> Duration {
+        self.clock.now() - self.start_time
     }
 
     /// Calculates and returns the time remaining on the timer.
     ///
-    /// This method accounts for paused time to ensure accurate remaining time
-    /// calculation. If the timer is expired, stopped, or has no time remaining,
+    /// If the timer is expired, stopped, or has no time remaining,
     /// returns [`Duration::ZERO`].
     ///
     /// # Returns
@@ -696,20 +2793,8 @@ impl GameTimer {
             return Duration::ZERO;
         }
 
-        // Calculate elapsed time, accounting for current pause state
-        let elapsed = if let Some(paused_at) = self.paused_at {
-            // Timer is currently paused, so use pause time as the end point
-            paused_at.duration_since(self.start_time) - self.elapsed_paused
-        } else {
-            // Timer is running, use current time as the end point
-            Instant::now().duration_since(self.start_time) - self.elapsed_paused
-        };
-
         // Subtract elapsed time from total duration, ensuring we don't go negative
-        self.config
-            .duration
-            .checked_sub(elapsed)
-            .unwrap_or(Duration::ZERO)
+        self.config.duration.saturating_sub(self.elapsed())
     }
 
     /// Checks if the timer has expired (reached zero).
@@ -722,10 +2807,14 @@ impl GameTimer {
         self.is_expired || (!self.is_running && self.get_remaining_time().is_zero())
     }
 
-    /// Updates the timer state and checks for expiration.
+    /// Advances the timer and checks for expiration.
+    ///
+    /// This method should be called every frame with the frame's delta time.
+    /// The timer's clock only moves while the timer is running and not paused.
     ///
-    /// This method should be called every frame to update the timer's internal
-    /// state. It checks if the timer has reached zero and updates the expired flag.
+    /// # Parameters
+    ///
+    /// * `delta` - Time elapsed since the last update
     ///
     /// # Returns
     ///
@@ -733,12 +2822,14 @@ impl GameTimer {
     /// `false` if the timer was already expired or is still running.
     ///
     /// This return value is useful for triggering one-time events when the timer expires.
-    pub fn update(&mut self) -> bool {
+    pub fn update(&mut self, delta: Duration) -> bool {
         // Don't update if not running or currently paused
-        if !self.is_running || self.paused_at.is_some() {
+        if !self.is_running || self.is_paused() {
             return false;
         }
 
+        self.clock.advance(delta);
+
         let remaining = self.get_remaining_time();
         let was_expired = self.is_expired;
         self.is_expired = remaining.is_zero();
@@ -814,6 +2905,24 @@ pub struct GameUIManager {
 
     /// The current game score.
     pub score: u32,
+
+    /// The current movement combo multiplier (1.0 = no combo).
+    pub combo_multiplier: f32,
+
+    /// How many seconds of timer each real second drains (1.0 = normal).
+    pub timer_rate: f32,
+
+    /// Label shown next to the level for special runs, such as the daily challenge.
+    pub mode_label: Option<&'static str>,
+
+    /// Hint shown below the reticle while the player looks at an interactable.
+    pub interaction_hint: Option<String>,
+
+    /// Line announcing a level event that just started.
+    pub event_announcement: Option<&'static str>,
+
+    /// Line confirming what the quick-settings radial just switched.
+    pub quick_confirmation: Option<String>,
 }
 
 impl Default for GameUIManager {
@@ -826,12 +2935,18 @@ impl Default for GameUIManager {
 impl GameUIManager {
     /// Creates a new GameUIManager with initial values.
     ///
-    /// Initializes with no active timer, level 1, and score 0.
+    /// Initializes with no active timer, level 1, score 0, and no combo.
     pub fn new() -> Self {
         Self {
             timer: None,
             level: 1,
             score: 0,
+            combo_multiplier: 1.0,
+            timer_rate: 1.0,
+            mode_label: None,
+            interaction_hint: None,
+            event_announcement: None,
+            quick_confirmation: None,
         }
     }
 
@@ -871,15 +2986,22 @@ impl GameUIManager {
         }
     }
 
-    /// Updates the timer countdown and returns whether the timer is still running.
+    /// Advances the timer countdown by one frame.
+    ///
+    /// The frame's time is scaled by [`timer_rate`](Self::timer_rate), so the
+    /// timer can be made to drain faster than real time.
+    ///
+    /// # Parameters
+    ///
+    /// * `delta_time` - Seconds since the last frame
     ///
     /// # Returns
     ///
-    /// `true` if the timer is running after the update, `false` otherwise.
+    /// `true` if the timer expired during this update, `false` otherwise.
     /// If no timer exists, returns `false`.
-    pub fn update_timer(&mut self) -> bool {
+    pub fn update_timer(&mut self, delta_time: f32) -> bool {
         if let Some(timer) = &mut self.timer {
-            timer.update()
+            timer.update(GameClock::frame_delta(delta_time * self.timer_rate))
         } else {
             false
         }
@@ -966,55 +3088,240 @@ impl GameUIManager {
     ///
     /// # Returns
     ///
-    /// A string in the format "Score: X" where X is the current score.
+    /// A string in the format "Score: X" where X is the current score, its digits grouped
+    /// in thousands.
     pub fn get_score_text(&self) -> String {
-        format!("Score: {}", self.score)
+        format!("Score: {}", score::group_digits(self.score.into()))
+    }
+
+    /// Gets the formatted combo multiplier text for display.
+    ///
+    /// # Returns
+    ///
+    /// A string in the format "xM.M" where M.M is the current multiplier.
+    pub fn get_combo_text(&self) -> String {
+        format!("x{:.1}", self.combo_multiplier)
+    }
+
+    /// Gets the color of the combo multiplier text.
+    ///
+    /// The color ramps from white at 1.0x through yellow to orange-red at the
+    /// maximum multiplier, so a growing combo is noticeable at a glance.
+    ///
+    /// # Returns
+    ///
+    /// The color for the current multiplier.
+    pub fn get_combo_color(&self) -> Color {
+        let t = ((self.combo_multiplier - 1.0) / (combo::MAX_MULTIPLIER - 1.0)).clamp(0.0, 1.0);
+        let lerp = |from: u8, to: u8, t: f32| (from as f32 + (to as f32 - from as f32) * t) as u8;
+        if t <= 0.5 {
+            let t = t * 2.0;
+            Color::rgb(255, lerp(255, 220, t), lerp(255, 80, t))
+        } else {
+            let t = (t - 0.5) * 2.0;
+            Color::rgb(255, lerp(220, 90, t), lerp(80, 40, t))
+        }
     }
 
-    /// Pauses the timer if it is currently running.
+    /// Pauses the timer on behalf of `source` if it is currently running.
     ///
     /// If no timer exists, this method does nothing.
-    pub fn pause_timer(&mut self) {
+    pub fn pause_timer(&mut self, source: PauseSource) {
         if let Some(timer) = &mut self.timer {
-            timer.pause();
+            timer.pause(source);
         }
     }
 
-    /// Resumes the timer if it is currently paused.
+    /// Releases the pause `source` holds on the timer.
     ///
+    /// The timer only counts down again once no other source has it paused.
     /// If no timer exists, this method does nothing.
-    pub fn resume_timer(&mut self) {
+    pub fn resume_timer(&mut self, source: PauseSource) {
         if let Some(timer) = &mut self.timer {
-            timer.resume();
+            timer.resume(source);
+        }
+    }
+}
+
+/// Text sizes of the HUD for one window size and display scale factor.
+///
+/// Everything is in physical pixels, the unit the text renderer draws in.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HudMetrics {
+    /// Font size of the timer
+    pub timer_font_size: f32,
+    /// Line height of the timer
+    pub timer_line_height: f32,
+    /// Widest the timer text may be
+    pub timer_max_width: f32,
+    /// Tallest the timer text may be
+    pub timer_max_height: f32,
+    /// Font size of the smaller labels
+    pub label_font_size: f32,
+    /// Line height of the smaller labels
+    pub label_line_height: f32,
+    /// Widest a label may be
+    pub label_max_width: f32,
+    /// Tallest a label may be
+    pub label_max_height: f32,
+}
+
+impl HudMetrics {
+    /// Picks the HUD text sizes for a window.
+    ///
+    /// The size tier is chosen from the window's logical size, and the tier's sizes are
+    /// multiplied by the scale factor, so the HUD looks the same size on a 100% and a 150%
+    /// display and is drawn at the display's full resolution on both.
+    ///
+    /// # Arguments
+    /// * `width` - Window width in physical pixels
+    /// * `height` - Window height in physical pixels
+    /// * `scale_factor` - The window's display scale factor
+    pub fn for_window(width: u32, height: u32, scale_factor: f64) -> Self {
+        let scale = if scale_factor.is_finite() && scale_factor > 0.0 {
+            scale_factor as f32
+        } else {
+            1.0
+        };
+        let logical_width = width as f32 / scale;
+        let logical_height = height as f32 / scale;
+
+        // If the window is large, scale up the text; otherwise, use default sizes
+        let (timer_font_size, timer_line_height, timer_max_width, timer_max_height) =
+            if logical_width >= 1920.0 {
+                (80.0, 100.0, 300.0, 120.0)
+            } else if logical_width >= 1600.0 || logical_height >= 900.0 {
+                (60.0, 76.0, 200.0, 80.0)
+            } else {
+                (48.0, 60.0, 150.0, 60.0)
+            };
+        let (label_font_size, label_line_height, label_max_width, label_max_height) =
+            if logical_width >= 1600.0 || logical_height >= 900.0 {
+                (24.0, 28.0, 160.0, 32.0)
+            } else {
+                (18.0, 22.0, 120.0, 25.0)
+            };
+
+        Self {
+            timer_font_size: timer_font_size * scale,
+            timer_line_height: timer_line_height * scale,
+            timer_max_width: timer_max_width * scale,
+            timer_max_height: timer_max_height * scale,
+            label_font_size: label_font_size * scale,
+            label_line_height: label_line_height * scale,
+            label_max_width: label_max_width * scale,
+            label_max_height: label_max_height * scale,
         }
     }
 }
 
+/// Resizes the HUD text created by [`initialize_game_ui`] for a new window size or scale factor.
+///
+/// The level and score sizes are left alone, as they follow the window every frame.
+///
+/// # Arguments
+/// * `text_renderer` - The renderer holding the HUD text buffers
+/// * `width` - Window width in physical pixels
+/// * `height` - Window height in physical pixels
+/// * `scale_factor` - The window's display scale factor
+pub fn resize_game_ui(
+    text_renderer: &mut TextRenderer,
+    width: u32,
+    height: u32,
+    scale_factor: f64,
+) {
+    let metrics = HudMetrics::for_window(width, height, scale_factor);
+    let label = (metrics.label_font_size, metrics.label_line_height);
+    let small_label = (label.0 * 0.75, label.1 * 0.75);
+    for (id, (font_size, line_height), max_size) in [
+        (
+            "main_timer",
+            (metrics.timer_font_size, metrics.timer_line_height),
+            (metrics.timer_max_width, metrics.timer_max_height),
+        ),
+        (
+            "combo",
+            small_label,
+            (metrics.label_max_width / 2.0, metrics.label_max_height),
+        ),
+        (
+            "run_mode",
+            small_label,
+            (metrics.label_max_width, metrics.label_max_height),
+        ),
+        (
+            "level_summary",
+            label,
+            (
+                metrics.label_max_width * 2.0,
+                metrics.label_line_height * 10.0,
+            ),
+        ),
+        (
+            "interaction_hint",
+            label,
+            (metrics.label_max_width * 3.0, metrics.label_max_height),
+        ),
+        (
+            "event_announcement",
+            label,
+            (metrics.label_max_width * 3.0, metrics.label_max_height),
+        ),
+        (
+            "quick_confirmation",
+            label,
+            (metrics.label_max_width * 3.0, metrics.label_max_height),
+        ),
+    ] {
+        let (Ok(style), Ok(position)) =
+            (text_renderer.get_style(id), text_renderer.get_position(id))
+        else {
+            continue;
+        };
+        let _ = text_renderer.update_style(
+            id,
+            TextStyle {
+                font_size,
+                line_height,
+                ..style
+            },
+        );
+        let _ = text_renderer.update_position(
+            id,
+            TextPosition {
+                max_width: Some(max_size.0),
+                max_height: Some(max_size.1),
+                ..position
+            },
+        );
+    }
+}
+
 /// Sets up the timer, score, and level display using the TextRenderer
+///
+/// # Arguments
+/// * `text_renderer` - The renderer to create the HUD text buffers in
+/// * `game_ui` - The UI manager whose timer, level, and score are shown
+/// * `width` - Window width in physical pixels
+/// * `height` - Window height in physical pixels
+/// * `scale_factor` - The window's display scale factor
 pub fn initialize_game_ui(
     text_renderer: &mut TextRenderer,
     game_ui: &GameUIManager,
-    window: &Window,
+    width: u32,
+    height: u32,
+    scale_factor: f64,
 ) {
-    let size = window.inner_size();
-    let width = size.width;
-    let height = size.height;
-
-    // --- Responsive scaling logic ---
-    // If the window is large, scale up the text; otherwise, use default sizes
-    let (timer_font_size, timer_line_height, timer_max_width, timer_max_height) = if width >= 1920 {
-        (80.0, 100.0, 300.0, 120.0)
-    } else if width >= 1600 || height >= 900 {
-        (60.0, 76.0, 200.0, 80.0)
-    } else {
-        (48.0, 60.0, 150.0, 60.0)
-    };
-    let (label_font_size, label_line_height, label_max_width, label_max_height) =
-        if width >= 1600 || height >= 900 {
-            (24.0, 28.0, 160.0, 32.0)
-        } else {
-            (18.0, 22.0, 120.0, 25.0)
-        };
+    let HudMetrics {
+        timer_font_size,
+        timer_line_height,
+        timer_max_width,
+        timer_max_height,
+        label_font_size,
+        label_line_height,
+        label_max_width,
+        label_max_height,
+    } = HudMetrics::for_window(width, height, scale_factor);
 
     // Timer display (decimal-aligned at top)
     let timer_text = game_ui.get_timer_text();
@@ -1029,7 +3336,7 @@ pub fn initialize_game_ui(
     // Find decimal position in timer_text
     let decimal_index = timer_text.find('.').unwrap_or(timer_text.len() - 1) + 1;
     let decimal_substr = &timer_text[..decimal_index];
-    let (_min_x, decimal_offset, _h) = text_renderer.measure_text(decimal_substr, &timer_style);
+    let decimal_offset = text_renderer.measure_figures(decimal_substr, &timer_style);
     let timer_position = TextPosition {
         x: (width as f32 / 2.0) - decimal_offset,
         y: 10.0,
@@ -1086,6 +3393,142 @@ pub fn initialize_game_ui(
         Some(score_style),
         Some(score_position),
     );
+
+    // Combo multiplier (small text right of the score; positioned every frame)
+    let combo_style = TextStyle {
+        font_family: "Hanken Grotesk".to_string(),
+        font_size: label_font_size * 0.75,
+        line_height: label_line_height * 0.75,
+        color: game_ui.get_combo_color(),
+        weight: glyphon::Weight::BOLD,
+        style: glyphon::Style::Normal,
+    };
+    let combo_position = TextPosition {
+        x: 20.0 + label_max_width,
+        y: 50.0,
+        max_width: Some(label_max_width / 2.0),
+        max_height: Some(label_max_height),
+    };
+    text_renderer.create_text_buffer(
+        "combo",
+        &game_ui.get_combo_text(),
+        Some(combo_style),
+        Some(combo_position),
+    );
+
+    // Run mode label (small text right of the level; positioned every frame)
+    let mode_style = TextStyle {
+        font_family: "Hanken Grotesk".to_string(),
+        font_size: label_font_size * 0.75,
+        line_height: label_line_height * 0.75,
+        color: Color::rgb(255, 200, 120),
+        weight: glyphon::Weight::BOLD,
+        style: glyphon::Style::Normal,
+    };
+    let mode_position = TextPosition {
+        x: 20.0 + label_max_width,
+        y: 20.0,
+        max_width: Some(label_max_width),
+        max_height: Some(label_max_height),
+    };
+    text_renderer.create_text_buffer(
+        "run_mode",
+        game_ui.mode_label.unwrap_or_default(),
+        Some(mode_style),
+        Some(mode_position),
+    );
+
+    // Level summary (score breakdown shown while the exit animation plays)
+    let summary_style = TextStyle {
+        font_family: "Hanken Grotesk".to_string(),
+        font_size: label_font_size,
+        line_height: label_line_height,
+        color: Color::rgb(255, 255, 255),
+        weight: glyphon::Weight::NORMAL,
+        style: glyphon::Style::Normal,
+    };
+    let summary_position = TextPosition {
+        x: 20.0,
+        y: 90.0,
+        max_width: Some(label_max_width * 2.0),
+        max_height: Some(label_line_height * 10.0),
+    };
+    text_renderer.create_text_buffer(
+        "level_summary",
+        "",
+        Some(summary_style),
+        Some(summary_position),
+    );
+    let _ = text_renderer.set_buffer_visibility("level_summary", false);
+
+    // Interaction hint (below the reticle while looking at an interactable; positioned every frame)
+    let hint_style = TextStyle {
+        font_family: "Hanken Grotesk".to_string(),
+        font_size: label_font_size,
+        line_height: label_line_height,
+        color: Color::rgb(255, 255, 255),
+        weight: glyphon::Weight::BOLD,
+        style: glyphon::Style::Normal,
+    };
+    let hint_position = TextPosition {
+        x: width as f32 / 2.0,
+        y: height as f32 / 2.0 + label_line_height,
+        max_width: Some(label_max_width * 3.0),
+        max_height: Some(label_max_height),
+    };
+    text_renderer.create_text_buffer(
+        "interaction_hint",
+        "",
+        Some(hint_style),
+        Some(hint_position),
+    );
+    let _ = text_renderer.set_buffer_visibility("interaction_hint", false);
+
+    // Event announcement (centered below the timer; positioned every frame)
+    let event_style = TextStyle {
+        font_family: "Hanken Grotesk".to_string(),
+        font_size: label_font_size,
+        line_height: label_line_height,
+        color: Color::rgb(200, 200, 255),
+        weight: glyphon::Weight::NORMAL,
+        style: glyphon::Style::Italic,
+    };
+    let event_position = TextPosition {
+        x: width as f32 / 2.0,
+        y: timer_max_height + 20.0,
+        max_width: Some(label_max_width * 3.0),
+        max_height: Some(label_max_height),
+    };
+    text_renderer.create_text_buffer(
+        "event_announcement",
+        "",
+        Some(event_style),
+        Some(event_position),
+    );
+    let _ = text_renderer.set_buffer_visibility("event_announcement", false);
+
+    // Quick-settings confirmation (below the interaction hint; positioned every frame)
+    let confirmation_style = TextStyle {
+        font_family: "Hanken Grotesk".to_string(),
+        font_size: label_font_size,
+        line_height: label_line_height,
+        color: Color::rgb(255, 255, 255),
+        weight: glyphon::Weight::MEDIUM,
+        style: glyphon::Style::Normal,
+    };
+    let confirmation_position = TextPosition {
+        x: width as f32 / 2.0,
+        y: height as f32 / 2.0 + label_line_height * 3.0,
+        max_width: Some(label_max_width * 3.0),
+        max_height: Some(label_max_height),
+    };
+    text_renderer.create_text_buffer(
+        "quick_confirmation",
+        "",
+        Some(confirmation_style),
+        Some(confirmation_position),
+    );
+    let _ = text_renderer.set_buffer_visibility("quick_confirmation", false);
 }
 
 /// Helper to update the text content of a buffer and re-apply style
@@ -1099,15 +3542,18 @@ fn update_text_content(
 }
 
 /// Call this every frame to update the timer, score, and level displays
+///
+/// The timer is centered in `hud`, the HUD safe area, rather than the whole window.
 pub fn update_game_ui(
     text_renderer: &mut TextRenderer,
     game_ui: &mut GameUIManager,
     current_screen: &CurrentScreen,
-    window: &Window,
+    delta_time: f32,
+    hud: SafeArea,
 ) -> bool {
     // Only update the timer if in Game
     let timer_expired = if let CurrentScreen::Game = current_screen {
-        game_ui.update_timer()
+        game_ui.update_timer(delta_time)
     } else {
         false
     };
@@ -1126,32 +3572,589 @@ pub fn update_game_ui(
     // Update level and score displays
     let _ = update_text_content(text_renderer, "level", &game_ui.get_level_text());
     let _ = update_text_content(text_renderer, "score", &game_ui.get_score_text());
+    update_combo_text(text_renderer, game_ui);
+    update_mode_text(text_renderer, game_ui);
+    update_interaction_hint(text_renderer, game_ui, current_screen, hud);
+    update_event_announcement(text_renderer, game_ui, current_screen, hud);
+    update_quick_confirmation(text_renderer, game_ui, current_screen, hud);
 
-    // Adjust timer position if window size changes
-    let size = window.inner_size();
-    let width = size.width;
-    let height = size.height;
-    let (timer_max_width, timer_max_height) = if width >= 1920 {
-        (300.0, 120.0)
-    } else if width >= 1600 || height >= 900 {
-        (200.0, 80.0)
-    } else {
-        (150.0, 60.0)
-    };
-
-    // Get current timer style for positioning calculations
-    if let Ok(timer_style) = text_renderer.get_style("main_timer") {
+    // Adjust timer position if the safe area changes; its size is set by resize_game_ui.
+    // The digits are tabular, so the offset is only measured when the digit count changes.
+    if let (Ok(timer_style), Ok(position)) = (
+        text_renderer.get_style("main_timer"),
+        text_renderer.get_position("main_timer"),
+    ) {
         let decimal_index = timer_text.find('.').unwrap_or(timer_text.len() - 1) + 1;
         let decimal_substr = &timer_text[..decimal_index];
-        let (_min_x, decimal_offset, _h) = text_renderer.measure_text(decimal_substr, &timer_style);
+        let decimal_offset = text_renderer.measure_figures(decimal_substr, &timer_style);
         let timer_position = TextPosition {
-            x: (width as f32 / 2.0) - decimal_offset,
-            y: 10.0,
-            max_width: Some(timer_max_width),
-            max_height: Some(timer_max_height),
+            x: hud.center_x() - decimal_offset,
+            y: hud.y + 10.0,
+            ..position
         };
         let _ = text_renderer.update_position("main_timer", timer_position);
     }
 
     timer_expired
 }
+
+/// Updates the combo multiplier text and keeps it just right of the score.
+///
+/// The combo follows the score's current size and position, so it stays in
+/// place when the score grows or the score text is rescaled on resize.
+fn update_combo_text(text_renderer: &mut TextRenderer, game_ui: &GameUIManager) {
+    let combo_text = game_ui.get_combo_text();
+    let _ = update_text_content(text_renderer, "combo", &combo_text);
+
+    let (Ok(score_style), Ok(score_position), Ok(mut combo_style)) = (
+        text_renderer.get_style("score"),
+        text_renderer.get_position("score"),
+        text_renderer.get_style("combo"),
+    ) else {
+        return;
+    };
+
+    combo_style.font_size = score_style.font_size * 0.75;
+    combo_style.line_height = score_style.line_height * 0.75;
+    combo_style.color = game_ui.get_combo_color();
+    let score_width = text_renderer.measure_figures(&game_ui.get_score_text(), &score_style);
+    let (_min_x, combo_width, combo_height) = text_renderer.measure_text(&combo_text, &combo_style);
+
+    // Drop the smaller text so both share roughly the same baseline
+    let combo_position = TextPosition {
+        x: score_position.x + score_width + score_style.font_size * 0.5,
+        y: score_position.y + (score_style.line_height - combo_style.line_height) * 0.8,
+        max_width: Some(combo_width + 10.0),
+        max_height: Some(combo_height + 10.0),
+    };
+    let _ = text_renderer.update_style("combo", combo_style);
+    let _ = text_renderer.update_position("combo", combo_position);
+}
+
+/// Updates the run mode label and keeps it just right of the level.
+///
+/// Works like [`update_combo_text`], following the level's size and position.
+/// The label is empty during regular runs.
+fn update_mode_text(text_renderer: &mut TextRenderer, game_ui: &GameUIManager) {
+    let mode_text = game_ui.mode_label.unwrap_or_default();
+    let _ = update_text_content(text_renderer, "run_mode", mode_text);
+
+    let (Ok(level_style), Ok(level_position), Ok(mut mode_style)) = (
+        text_renderer.get_style("level"),
+        text_renderer.get_position("level"),
+        text_renderer.get_style("run_mode"),
+    ) else {
+        return;
+    };
+
+    mode_style.font_size = level_style.font_size * 0.75;
+    mode_style.line_height = level_style.line_height * 0.75;
+    let (_min_x, level_width, _h) =
+        text_renderer.measure_text(&game_ui.get_level_text(), &level_style);
+    let (_min_x, mode_width, mode_height) = text_renderer.measure_text(mode_text, &mode_style);
+
+    let mode_position = TextPosition {
+        x: level_position.x + level_width + level_style.font_size * 0.5,
+        y: level_position.y + (level_style.line_height - mode_style.line_height) * 0.8,
+        max_width: Some(mode_width + 10.0),
+        max_height: Some(mode_height + 10.0),
+    };
+    let _ = text_renderer.update_style("run_mode", mode_style);
+    let _ = text_renderer.update_position("run_mode", mode_position);
+}
+
+/// Updates the interaction hint and centers it just below the reticle.
+///
+/// The hint is only visible during play while the player is looking at an
+/// interactable.
+fn update_interaction_hint(
+    text_renderer: &mut TextRenderer,
+    game_ui: &GameUIManager,
+    current_screen: &CurrentScreen,
+    hud: SafeArea,
+) {
+    let hint = game_ui
+        .interaction_hint
+        .as_deref()
+        .filter(|_| *current_screen == CurrentScreen::Game);
+    let Some(hint) = hint else {
+        let _ = text_renderer.set_buffer_visibility("interaction_hint", false);
+        return;
+    };
+
+    let _ = update_text_content(text_renderer, "interaction_hint", hint);
+    if let Ok(hint_style) = text_renderer.get_style("interaction_hint") {
+        let (_min_x, hint_width, hint_height) = text_renderer.measure_text(hint, &hint_style);
+        let hint_position = TextPosition {
+            x: hud.center_x() - hint_width / 2.0,
+            y: hud.y + hud.height / 2.0 + hint_style.line_height,
+            max_width: Some(hint_width + 10.0),
+            max_height: Some(hint_height + 10.0),
+        };
+        let _ = text_renderer.update_position("interaction_hint", hint_position);
+    }
+    let _ = text_renderer.set_buffer_visibility("interaction_hint", true);
+}
+
+/// Updates the quick-settings confirmation and centers it below the interaction hint.
+///
+/// The confirmation is only visible during play, for a moment after a radial slot is
+/// activated.
+fn update_quick_confirmation(
+    text_renderer: &mut TextRenderer,
+    game_ui: &GameUIManager,
+    current_screen: &CurrentScreen,
+    hud: SafeArea,
+) {
+    let confirmation = game_ui
+        .quick_confirmation
+        .as_deref()
+        .filter(|_| *current_screen == CurrentScreen::Game);
+    let Some(confirmation) = confirmation else {
+        let _ = text_renderer.set_buffer_visibility("quick_confirmation", false);
+        return;
+    };
+
+    let _ = update_text_content(text_renderer, "quick_confirmation", confirmation);
+    if let Ok(style) = text_renderer.get_style("quick_confirmation") {
+        let (_min_x, text_width, text_height) = text_renderer.measure_text(confirmation, &style);
+        let position = TextPosition {
+            x: hud.center_x() - text_width / 2.0,
+            y: hud.y + hud.height / 2.0 + style.line_height * 3.0,
+            max_width: Some(text_width + 10.0),
+            max_height: Some(text_height + 10.0),
+        };
+        let _ = text_renderer.update_position("quick_confirmation", position);
+    }
+    let _ = text_renderer.set_buffer_visibility("quick_confirmation", true);
+}
+
+/// Updates the event announcement and centers it below the timer.
+///
+/// The announcement is only visible during play, for a few seconds after a
+/// level event starts.
+fn update_event_announcement(
+    text_renderer: &mut TextRenderer,
+    game_ui: &GameUIManager,
+    current_screen: &CurrentScreen,
+    hud: SafeArea,
+) {
+    let announcement = game_ui
+        .event_announcement
+        .filter(|_| *current_screen == CurrentScreen::Game);
+    let Some(announcement) = announcement else {
+        let _ = text_renderer.set_buffer_visibility("event_announcement", false);
+        return;
+    };
+
+    let _ = update_text_content(text_renderer, "event_announcement", announcement);
+    if let (Ok(event_style), Ok(timer_position)) = (
+        text_renderer.get_style("event_announcement"),
+        text_renderer.get_position("main_timer"),
+    ) {
+        let (_min_x, event_width, event_height) =
+            text_renderer.measure_text(announcement, &event_style);
+        let event_position = TextPosition {
+            x: hud.center_x() - event_width / 2.0,
+            y: timer_position.y + timer_position.max_height.unwrap_or(0.0) + 10.0,
+            max_width: Some(event_width + 10.0),
+            max_height: Some(event_height + 10.0),
+        };
+        let _ = text_renderer.update_position("event_announcement", event_position);
+    }
+    let _ = text_renderer.set_buffer_visibility("event_announcement", true);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A 30 s timer with warning at 20 s and critical at 10 s remaining.
+    fn started_timer() -> GameTimer {
+        let mut timer = GameTimer::new(TimerConfig::default());
+        timer.start();
+        timer
+    }
+
+    fn secs(seconds: u64) -> Duration {
+        Duration::from_secs(seconds)
+    }
+
+    /// A game state with no audio device or enemy, already on the gameplay screen.
+    fn playing_state() -> GameState {
+        GameState::builder()
+            .without_audio()
+            .without_enemy()
+            .with_screen(CurrentScreen::Game)
+            .build()
+            .expect("Failed to build game state")
+    }
+
+    #[test]
+    fn test_timer_does_not_count_before_start() {
+        let mut timer = GameTimer::new(TimerConfig::default());
+        assert!(!timer.update(secs(100)));
+        timer.start();
+        assert_eq!(timer.get_remaining_time(), secs(30));
+    }
+
+    #[test]
+    fn test_timer_expires_exactly_at_duration() {
+        let mut timer = started_timer();
+
+        assert!(!timer.update(secs(30) - Duration::from_millis(1)));
+        assert!(!timer.is_expired());
+        assert_eq!(timer.get_remaining_time(), Duration::from_millis(1));
+
+        // Just-expired edge fires once, on the frame that reaches zero
+        assert!(timer.update(Duration::from_millis(1)));
+        assert!(timer.is_expired());
+        assert!(!timer.update(secs(1)));
+        assert_eq!(timer.get_remaining_time(), Duration::ZERO);
+    }
+
+    #[test]
+    fn test_large_frame_overshoot_still_expires_once() {
+        let mut timer = started_timer();
+        assert!(timer.update(secs(45)));
+        assert_eq!(timer.get_remaining_time(), Duration::ZERO);
+        assert_eq!(timer.format_time(), "00.00");
+    }
+
+    #[test]
+    fn test_paused_time_does_not_count() {
+        let mut timer = started_timer();
+        timer.update(secs(5));
+        timer.pause(PauseSource::Menu);
+        assert!(!timer.update(secs(60)));
+        assert_eq!(timer.get_remaining_time(), secs(25));
+        timer.resume(PauseSource::Menu);
+        timer.update(secs(5));
+        assert_eq!(timer.get_remaining_time(), secs(20));
+    }
+
+    #[test]
+    fn test_pause_resume_across_expiration_boundary() {
+        let mut timer = started_timer();
+        timer.update(secs(29));
+
+        // Time spent paused past the deadline must not expire the timer
+        timer.pause(PauseSource::Menu);
+        assert!(!timer.update(secs(10)));
+        assert!(!timer.is_expired());
+        assert_eq!(timer.get_remaining_time(), secs(1));
+
+        timer.resume(PauseSource::Menu);
+        assert!(!timer.update(Duration::from_millis(500)));
+        assert!(timer.update(Duration::from_millis(500)));
+        assert!(timer.is_expired());
+    }
+
+    #[test]
+    fn test_nested_pauses_resume_only_when_every_source_has() {
+        let mut timer = started_timer();
+        timer.pause(PauseSource::Menu);
+        timer.pause(PauseSource::FocusLoss);
+        // Pausing twice from one source needs only one resume
+        timer.pause(PauseSource::FocusLoss);
+
+        timer.resume(PauseSource::FocusLoss);
+        assert!(timer.is_paused());
+        assert!(!timer.update(secs(10)));
+        assert_eq!(timer.get_remaining_time(), secs(30));
+
+        timer.resume(PauseSource::Menu);
+        assert!(!timer.is_paused());
+        timer.update(secs(10));
+        assert_eq!(timer.get_remaining_time(), secs(20));
+
+        // A resume from a source that never paused changes nothing
+        timer.pause(PauseSource::Menu);
+        timer.resume(PauseSource::FocusLoss);
+        assert!(timer.is_paused());
+    }
+
+    #[test]
+    fn test_stopping_a_paused_timer_drops_its_pauses() {
+        let mut timer = started_timer();
+        timer.update(secs(5));
+        timer.pause(PauseSource::Menu);
+        timer.stop();
+        assert!(!timer.is_paused());
+
+        // Resuming afterwards doesn't bring it back to life
+        timer.resume(PauseSource::Menu);
+        assert!(!timer.update(secs(60)));
+        assert_eq!(timer.get_remaining_time(), Duration::ZERO);
+
+        timer.start();
+        assert!(!timer.is_paused());
+        timer.update(secs(5));
+        assert_eq!(timer.get_remaining_time(), secs(25));
+    }
+
+    #[test]
+    fn test_overlapping_pauses_across_expiration_boundary() {
+        let mut timer = started_timer();
+        timer.update(secs(29));
+
+        timer.pause(PauseSource::FocusLoss);
+        assert!(!timer.update(secs(5)));
+        timer.pause(PauseSource::Menu);
+        timer.resume(PauseSource::FocusLoss);
+        assert!(!timer.update(secs(5)));
+        assert!(!timer.is_expired());
+        assert_eq!(timer.get_remaining_time(), secs(1));
+
+        timer.resume(PauseSource::Menu);
+        assert!(timer.update(secs(1)));
+        assert!(timer.is_expired());
+        assert!(!timer.is_paused());
+    }
+
+    #[test]
+    fn test_pause_on_stopped_timer_is_ignored() {
+        let mut timer = GameTimer::new(TimerConfig::default());
+        timer.pause(PauseSource::Menu);
+        assert!(!timer.is_paused());
+    }
+
+    #[test]
+    fn test_restart_after_expiry_resets_countdown() {
+        let mut timer = started_timer();
+        timer.update(secs(30));
+        assert!(timer.is_expired());
+
+        timer.reset();
+        timer.start();
+        assert!(!timer.is_expired());
+        assert_eq!(timer.get_remaining_time(), secs(30));
+        assert_eq!(timer.elapsed(), Duration::ZERO);
+    }
+
+    #[test]
+    fn test_color_thresholds_at_exact_boundaries() {
+        let config = TimerConfig::default();
+        let mut timer = started_timer();
+
+        // 20.001 s remaining: still normal
+        timer.update(secs(10) - Duration::from_millis(1));
+        assert_eq!(timer.get_current_color(), config.normal_color);
+
+        // Exactly 20 s remaining: warning
+        timer.update(Duration::from_millis(1));
+        assert_eq!(timer.get_current_color(), config.warning_color);
+
+        // 10.001 s remaining: still warning
+        timer.update(secs(10) - Duration::from_millis(1));
+        assert_eq!(timer.get_current_color(), config.warning_color);
+
+        // Exactly 10 s remaining: critical
+        timer.update(Duration::from_millis(1));
+        assert_eq!(timer.get_current_color(), config.critical_color);
+    }
+
+    #[test]
+    fn test_format_time() {
+        let mut timer = started_timer();
+        assert_eq!(timer.format_time(), "30.00");
+        timer.update(Duration::from_millis(6_550));
+        assert_eq!(timer.format_time(), "23.45");
+    }
+
+    #[test]
+    fn test_ui_manager_ignores_invalid_delta_time() {
+        let mut game_ui = GameUIManager::new();
+        game_ui.start_timer(None);
+        assert!(!game_ui.update_timer(-5.0));
+        assert!(!game_ui.update_timer(f32::NAN));
+        assert_eq!(game_ui.get_timer_text(), "30.00");
+
+        game_ui.pause_timer(PauseSource::Menu);
+        assert!(!game_ui.update_timer(100.0));
+        game_ui.resume_timer(PauseSource::Menu);
+        assert!(game_ui.update_timer(30.0));
+        assert!(game_ui.is_timer_expired());
+    }
+
+    #[test]
+    fn test_builder_defaults_match_the_game() {
+        let game_state = GameState::builder().without_audio().build().unwrap();
+        assert_eq!(game_state.current_screen, CurrentScreen::Title);
+        assert!(!game_state.is_test_mode);
+        assert!(game_state.enemy_present());
+        assert_eq!(game_state.chosen_seed, None);
+        assert_eq!(game_state.dimensions, PlayerDimensions::default());
+
+        let game_state = GameState::builder()
+            .without_audio()
+            .with_enemy(Enemy::new([100.0, 30.0, -40.0], 90.0))
+            .with_test_mode(true)
+            .with_seed(1234)
+            .build()
+            .unwrap();
+        assert!(game_state.enemy_present());
+        assert_eq!(game_state.enemy.pathfinder.position, [100.0, 30.0, -40.0]);
+        assert!(game_state.is_test_mode);
+        assert_eq!(game_state.dimensions, PlayerDimensions::test_mode());
+        assert_eq!(game_state.run_seed, 1234);
+
+        // The chosen seed survives into every new run
+        let mut game_state = game_state;
+        game_state.begin_run_seed();
+        assert_eq!(game_state.run_seed, 1234);
+        assert!(!playing_state().enemy_present());
+    }
+
+    #[test]
+    fn test_game_timer_counts_down_and_expires() {
+        let mut game_state = playing_state();
+        assert!(!game_state.is_game_timer_expired());
+
+        game_state.start_game_timer(None);
+        assert!(!game_state.game_ui.update_timer(10.0));
+        assert_eq!(game_state.game_ui.get_timer_text(), "20.00");
+
+        game_state.reset_game_timer();
+        assert_eq!(game_state.game_ui.get_timer_text(), "30.00");
+        assert!(game_state.game_ui.update_timer(30.0));
+        assert!(game_state.is_game_timer_expired());
+    }
+
+    #[test]
+    fn test_level_score_is_added_to_the_run_score() {
+        let mut game_state = playing_state();
+        game_state.set_score(500);
+        assert_eq!(game_state.game_ui.get_score(), 500);
+
+        let total = game_state.finish_level().total;
+        assert!(game_state.last_score_breakdown.is_some());
+        let applied = game_state.apply_level_score();
+        assert_eq!(applied.total, total);
+        assert_eq!(game_state.game_ui.get_score(), 500 + total);
+        assert!(game_state.last_score_breakdown.is_none());
+    }
+
+    #[test]
+    fn test_pausing_stops_the_timer_and_hides_the_reticle() {
+        let mut game_state = playing_state();
+        game_state.start_game_timer(None);
+        game_state.game_ui.update_timer(5.0);
+
+        game_state.previous_screen = Some(game_state.current_screen);
+        game_state.current_screen = CurrentScreen::Pause;
+        game_state.game_ui.pause_timer(PauseSource::Menu);
+        assert!(!game_state.reticle_visible());
+        assert!(!game_state.game_ui.update_timer(60.0));
+        assert_eq!(game_state.game_ui.get_timer_text(), "25.00");
+
+        game_state.current_screen = game_state.previous_screen.take().unwrap();
+        game_state.game_ui.resume_timer(PauseSource::Menu);
+        assert!(game_state.reticle_visible());
+        game_state.game_ui.update_timer(5.0);
+        assert_eq!(game_state.game_ui.get_timer_text(), "20.00");
+    }
+
+    #[test]
+    fn test_retry_clears_the_exit_and_returns_to_the_maze() {
+        let maze_grid = vec![
+            vec![true, true, true, true],
+            vec![true, false, false, true],
+            vec![true, true, true, true],
+        ];
+        let exit_cell = Cell::new(1, 2);
+        let mut game_state = playing_state();
+        assert!(!game_state.retry_level());
+
+        game_state.set_score(300);
+        game_state.cache_level(&maze_grid, exit_cell);
+        game_state.set_score(900);
+        game_state.exit_reached = true;
+        game_state.exit_reached_timer = 0.5;
+        assert!(!game_state.quick_radial_allowed());
+
+        game_state.previous_screen = Some(CurrentScreen::Game);
+        game_state.current_screen = CurrentScreen::Pause;
+        assert!(game_state.retry_level());
+        assert!(!game_state.exit_reached);
+        assert_eq!(game_state.exit_reached_timer, 0.0);
+        assert_eq!(game_state.exit_cell, Some(exit_cell));
+        assert_eq!(game_state.current_screen, CurrentScreen::Game);
+        assert_eq!(game_state.previous_screen, None);
+        assert_eq!(game_state.game_ui.get_score(), 300);
+        assert!(game_state.quick_radial_allowed());
+    }
+
+    #[test]
+    fn test_pack_spawns_apart_around_the_lead() {
+        let (maze_grid, _) = maze::parse_maze_str(
+            "\
+###########
+#         #
+###########
+",
+        )
+        .expect("valid maze");
+        let mut game_state = GameState::builder()
+            .without_audio()
+            .with_screen(CurrentScreen::Game)
+            .build()
+            .expect("Failed to build game state");
+        game_state
+            .collision_system
+            .build_from_maze(&maze_grid, false);
+        let maze_dimensions = game_state.collision_system.maze_dimensions;
+        let lead = coordinates::maze_to_world(&Cell::new(1, 5), maze_dimensions, 30.0, false);
+        game_state.enemy = Enemy::new(lead, 150.0);
+        game_state.level_plan.enemy.count = 3;
+
+        game_state.reset_level_tracking(&maze_grid, None);
+        let cells: Vec<Cell> = game_state
+            .enemies()
+            .map(|enemy| {
+                coordinates::world_to_maze(enemy.pathfinder.position, maze_dimensions, false)
+            })
+            .collect();
+        assert_eq!(cells, [Cell::new(1, 5), Cell::new(1, 3), Cell::new(1, 7)]);
+
+        game_state.clear_pack();
+        assert_eq!(game_state.enemies().count(), 1);
+    }
+
+    #[test]
+    fn test_compass_points_north_in_north_mode() {
+        let mut game_state = playing_state();
+        game_state.player.position = [10.0, 0.0, 20.0];
+        assert_eq!(game_state.compass_target((50.0, 60.0)), (50.0, 60.0));
+
+        game_state.compass_mode = game_state.compass_mode.next();
+        let (x, z) = game_state.compass_target((50.0, 60.0));
+        assert_eq!(x, 10.0);
+        assert!(z < 20.0);
+
+        game_state.compass_mode = game_state.compass_mode.next();
+        assert_eq!(game_state.compass_mode, CompassMode::Exit);
+    }
+
+    #[test]
+    fn test_hud_metrics_follow_the_display_scale_factor() {
+        let hd = HudMetrics::for_window(1920, 1080, 1.0);
+        assert_eq!(hd.timer_font_size, 80.0);
+        assert_eq!(hd.label_font_size, 24.0);
+        assert_eq!(HudMetrics::for_window(1280, 720, 1.0).timer_font_size, 48.0);
+
+        // The same logical window on a 150% display draws everything half as large again
+        let scaled = HudMetrics::for_window(2880, 1620, 1.5);
+        assert_eq!(scaled.timer_font_size, 120.0);
+        assert_eq!(scaled.timer_max_width, 450.0);
+        assert_eq!(scaled.label_line_height, 42.0);
+
+        // A small logical window keeps the small tier however many pixels it has
+        let small = HudMetrics::for_window(2560, 1440, 2.0);
+        assert_eq!(small.timer_font_size, 96.0);
+        assert_eq!(small.label_font_size, 36.0);
+
+        // A broken scale factor is treated as 100%
+        assert_eq!(HudMetrics::for_window(1920, 1080, 0.0), hd);
+    }
+}
@@ -0,0 +1,593 @@
+//! Navigation mesh export for experimenting with AI outside the game.
+//!
+//! A [`Navmesh`] describes the walkable space of one level: a floor square for every open entry
+//! of the wall grid, a box for every wall the player collides with, where the player spawns and
+//! the exit is, which floor squares join up, and the [`WorldScale`] that maps grid coordinates to
+//! world units. It is read straight out of a built [`CollisionSystem`], using the same grid
+//! origin, cell size, and solid-cell test the game collides with, so the export can't drift from
+//! what the player actually bumps into. In test mode only the perimeter walls collide, and the
+//! export follows suit.
+//!
+//! The export runs headless from the command line, without a window or a GPU:
+//!
+//! - `--export-navmesh --seed <seed>` exports the first level of the run with that seed, in the
+//!   base36 or decimal form the title screen accepts.
+//! - `--export-navmesh --maze <file>` exports a hand-edited or saved `.mz` maze.
+//! - Adding `--obj` also writes the floor and walls as a Wavefront OBJ model.
+//!
+//! Files are written to [`Paths::navmesh_dir`](crate::paths::Paths::navmesh_dir).
+//!
+//! # Coordinates
+//!
+//! Positions are world units with Y up, the floor at `y = 0`, and the maze centered on the
+//! origin. Grid rows run along Z and columns along X, so the wall-grid entry at `row`, `col`
+//! spans `origin[0] + col * cell_size` to one cell further on X, and likewise on Z from
+//! `origin[1]`.
+//!
+//! # Usage
+//!
+//! ```rust,ignore
+//! if let Some(request) = navmesh::export_request(&args)? {
+//!     for path in navmesh::export(&request, &paths::get().navmesh_dir())? {
+//!         println!("Wrote {}", path.display());
+//!     }
+//! }
+//! ```
+
+use crate::game::collision::CollisionSystem;
+use crate::game::dimensions::PlayerDimensions;
+use crate::game::levels::{MAZE_HEIGHT, MAZE_WIDTH};
+use crate::game::maze::floors::Layer;
+use crate::game::maze::generator::{Cell, MazeGenerator};
+use crate::game::maze::try_parse_maze_file;
+use crate::game::seed;
+use crate::math::coordinates::{self, constants::get_floor_size};
+use serde::{Deserialize, Serialize};
+use std::fmt::Write as _;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Command-line flag that exports a level's navigation mesh instead of starting the game.
+pub const EXPORT_NAVMESH_FLAG: &str = "--export-navmesh";
+
+/// Command-line flag naming the run seed whose first level is exported.
+pub const SEED_FLAG: &str = "--seed";
+
+/// Command-line flag naming the `.mz` maze file to export.
+pub const MAZE_FLAG: &str = "--maze";
+
+/// Command-line flag that also writes a Wavefront OBJ model of the export.
+pub const OBJ_FLAG: &str = "--obj";
+
+/// Version of the JSON layout, raised whenever a field changes meaning.
+pub const FORMAT_VERSION: u32 = 1;
+
+/// The numbers needed to map between the wall grid and world units.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct WorldScale {
+    /// Side of the square floor the maze is fitted to
+    pub floor_size: f32,
+    /// Side of one wall-grid entry
+    pub cell_size: f32,
+    /// Height of the walls the player collides with
+    pub wall_height: f32,
+    /// Entries across the wall grid, along X
+    pub grid_width: usize,
+    /// Entries down the wall grid, along Z
+    pub grid_height: usize,
+    /// World `[x, z]` of the corner where grid row and column 0 meet
+    pub origin: [f32; 2],
+    /// Radius of the player's collision cylinder
+    pub player_radius: f32,
+    /// Height of the player's collision cylinder
+    pub player_height: f32,
+    /// Whether the level uses the smaller test mode floor, where only the perimeter collides
+    pub test_mode: bool,
+}
+
+/// One walkable square of floor.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NavCell {
+    /// Wall-grid row
+    pub row: usize,
+    /// Wall-grid column
+    pub col: usize,
+    /// World position of the middle of the square, on the floor
+    pub center: [f32; 3],
+    /// Corners of the square on the floor, counter-clockwise seen from above
+    pub polygon: [[f32; 3]; 4],
+    /// Indices of the squares that share an edge with this one
+    pub neighbors: Vec<usize>,
+}
+
+/// The box around one wall the player collides with.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WallBox {
+    /// Wall-grid row
+    pub row: usize,
+    /// Wall-grid column
+    pub col: usize,
+    /// Corner with the smallest coordinates
+    pub min: [f32; 3],
+    /// Corner with the largest coordinates
+    pub max: [f32; 3],
+}
+
+/// World positions of the places a level is built around.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Landmarks {
+    /// Where the player is put at the start of the level, on the floor
+    pub spawn: [f32; 3],
+    /// Middle of the first open cell of the maze, which route lengths are measured from
+    pub entrance: [f32; 3],
+    /// Middle of the exit cell, if the level has one
+    pub exit: Option<[f32; 3]>,
+}
+
+/// The walkable space of one level, in the form it is exported.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Navmesh {
+    /// Layout version, see [`FORMAT_VERSION`]
+    pub format_version: u32,
+    /// Where the level came from, such as `seed k7` or the maze file's name
+    pub source: String,
+    /// Grid to world mapping
+    pub scale: WorldScale,
+    /// Spawn, entrance, and exit
+    pub landmarks: Landmarks,
+    /// Every walkable square, in row-major order
+    pub cells: Vec<NavCell>,
+    /// Every wall the player collides with, in row-major order
+    pub walls: Vec<WallBox>,
+}
+
+impl Navmesh {
+    /// Reads the walkable space out of a level's collision geometry.
+    ///
+    /// Only the lower floor is exported, which is the whole level on single-floor levels.
+    ///
+    /// # Arguments
+    /// * `collision` - Collision system built for the level with `build_from_maze`
+    /// * `exit` - The exit cell in wall grid coordinates, if the level has one
+    /// * `source` - Where the level came from, recorded in the export
+    pub fn from_collision(collision: &CollisionSystem, exit: Option<Cell>, source: &str) -> Self {
+        let grid = collision.grid(Layer::Lower);
+        let is_test_mode = collision.is_test_mode;
+        let dimensions = (grid[0].len(), grid.len());
+        let cell_size = coordinates::calculate_cell_size(dimensions, is_test_mode);
+        let (origin_x, origin_z) = CollisionSystem::grid_origin(grid, cell_size);
+        let corner = |row: usize, col: usize| {
+            (
+                origin_x + col as f32 * cell_size,
+                origin_z + row as f32 * cell_size,
+            )
+        };
+        let center = |cell: Cell| coordinates::maze_to_world(&cell, dimensions, 0.0, is_test_mode);
+
+        // Index of the floor square at each open grid entry
+        let mut index = vec![vec![None; dimensions.0]; dimensions.1];
+        let mut cells = Vec::new();
+        let mut walls = Vec::new();
+        for (row, entries) in index.iter_mut().enumerate() {
+            for (col, entry) in entries.iter_mut().enumerate() {
+                let (x, z) = corner(row, col);
+                if collision.is_solid(Layer::Lower, row as isize, col as isize) {
+                    walls.push(WallBox {
+                        row,
+                        col,
+                        min: [x, 0.0, z],
+                        max: [x + cell_size, cell_size, z + cell_size],
+                    });
+                    continue;
+                }
+                *entry = Some(cells.len());
+                cells.push(NavCell {
+                    row,
+                    col,
+                    center: center(Cell::new(row, col)),
+                    polygon: [
+                        [x, 0.0, z],
+                        [x, 0.0, z + cell_size],
+                        [x + cell_size, 0.0, z + cell_size],
+                        [x + cell_size, 0.0, z],
+                    ],
+                    neighbors: Vec::new(),
+                });
+            }
+        }
+        for cell in &mut cells {
+            let (row, col) = (cell.row, cell.col);
+            let steps = [
+                (row.checked_sub(1), Some(col)),
+                (Some(row), col.checked_sub(1)),
+                (Some(row), Some(col + 1)),
+                (Some(row + 1), Some(col)),
+            ];
+            cell.neighbors = steps
+                .into_iter()
+                .filter_map(|(row, col)| *index.get(row?)?.get(col?)?)
+                .collect();
+        }
+
+        let spawn = coordinates::get_bottom_left_cell(dimensions);
+        Self {
+            format_version: FORMAT_VERSION,
+            source: source.to_string(),
+            scale: WorldScale {
+                floor_size: get_floor_size(dimensions, is_test_mode),
+                cell_size,
+                wall_height: cell_size,
+                grid_width: dimensions.0,
+                grid_height: dimensions.1,
+                origin: [origin_x, origin_z],
+                player_radius: collision.player_radius,
+                player_height: collision.player_height,
+                test_mode: is_test_mode,
+            },
+            landmarks: Landmarks {
+                spawn: center(spawn),
+                entrance: center(Cell::new(dimensions.1.saturating_sub(2), 1)),
+                exit: exit.map(center),
+            },
+            cells,
+            walls,
+        }
+    }
+
+    /// Builds the collision geometry for a wall grid and exports it.
+    ///
+    /// # Arguments
+    /// * `grid` - The level's wall grid, where `true` marks a wall
+    /// * `exit` - The exit cell in wall grid coordinates, if the level has one
+    /// * `is_test_mode` - Whether the level uses the test mode floor
+    /// * `source` - Where the level came from, recorded in the export
+    pub fn from_grid(
+        grid: &[Vec<bool>],
+        exit: Option<Cell>,
+        is_test_mode: bool,
+        source: &str,
+    ) -> Self {
+        let dimensions = PlayerDimensions::for_mode(is_test_mode);
+        let mut collision =
+            CollisionSystem::new(dimensions.collision_radius, dimensions.collision_height);
+        collision.build_from_maze(grid, is_test_mode);
+        Self::from_collision(&collision, exit, source)
+    }
+
+    /// Checks whether a world position is on one of the exported floor squares.
+    ///
+    /// Squares include their edges, so a point on a wall's face is walkable.
+    ///
+    /// # Arguments
+    /// * `x` - World X
+    /// * `z` - World Z
+    #[cfg(test)]
+    pub fn is_walkable(&self, x: f32, z: f32) -> bool {
+        self.cells.iter().any(|cell| {
+            let [min, _, max, _] = cell.polygon;
+            (min[0]..=max[0]).contains(&x) && (min[2]..=max[2]).contains(&z)
+        })
+    }
+
+    /// Finds the exported wall a world position is strictly inside.
+    ///
+    /// # Arguments
+    /// * `x` - World X
+    /// * `z` - World Z
+    #[cfg(test)]
+    pub fn wall_at(&self, x: f32, z: f32) -> Option<&WallBox> {
+        self.walls
+            .iter()
+            .find(|wall| wall.min[0] < x && x < wall.max[0] && wall.min[2] < z && z < wall.max[2])
+    }
+
+    /// Formats the export as pretty-printed JSON.
+    pub fn to_json(&self) -> Result<String, String> {
+        serde_json::to_string_pretty(self)
+            .map_err(|e| format!("Failed to serialize navigation mesh: {}", e))
+    }
+
+    /// Formats the floor squares and wall boxes as a Wavefront OBJ model.
+    ///
+    /// The floor and the walls are separate objects. Faces wind counter-clockwise seen from
+    /// outside, so their normals point up out of the floor and out of the walls.
+    pub fn to_obj(&self) -> String {
+        let mut obj = format!("# Mirador navigation mesh: {}\no floor\n", self.source);
+        for cell in &self.cells {
+            for [x, y, z] in cell.polygon {
+                let _ = writeln!(obj, "v {} {} {}", x, y, z);
+            }
+        }
+        for index in 0..self.cells.len() {
+            let first = index * 4 + 1;
+            let _ = writeln!(obj, "f {} {} {} {}", first, first + 1, first + 2, first + 3);
+        }
+
+        obj.push_str("o walls\n");
+        let floor_vertices = self.cells.len() * 4;
+        for wall in &self.walls {
+            let (min, max) = (wall.min, wall.max);
+            for y in [min[1], max[1]] {
+                for [x, z] in [
+                    [min[0], min[2]],
+                    [max[0], min[2]],
+                    [max[0], max[2]],
+                    [min[0], max[2]],
+                ] {
+                    let _ = writeln!(obj, "v {} {} {}", x, y, z);
+                }
+            }
+        }
+        // Corners 0-3 go around the bottom, 4-7 around the top in the same order
+        const BOX_FACES: [[usize; 4]; 6] = [
+            [0, 1, 2, 3],
+            [4, 7, 6, 5],
+            [0, 4, 5, 1],
+            [1, 5, 6, 2],
+            [2, 6, 7, 3],
+            [3, 7, 4, 0],
+        ];
+        for index in 0..self.walls.len() {
+            let first = floor_vertices + index * 8 + 1;
+            for face in BOX_FACES {
+                let [a, b, c, d] = face.map(|corner| first + corner);
+                let _ = writeln!(obj, "f {} {} {} {}", a, b, c, d);
+            }
+        }
+        obj
+    }
+}
+
+/// Where the level to export comes from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NavmeshSource {
+    /// The first level of a run with this seed
+    Seed(u64),
+    /// A maze file in the `.mz` format
+    MazeFile(PathBuf),
+}
+
+/// An export asked for on the command line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExportRequest {
+    /// The level to export
+    pub source: NavmeshSource,
+    /// Whether to write a Wavefront OBJ model next to the JSON
+    pub obj: bool,
+}
+
+/// Reads an export request from the process arguments.
+///
+/// # Arguments
+/// * `args` - The process arguments, including the program name
+///
+/// # Returns
+/// The request if [`EXPORT_NAVMESH_FLAG`] was given, or an error message if it doesn't have
+/// exactly one of `--seed` and `--maze` with a valid value
+pub fn export_request(args: &[String]) -> Result<Option<ExportRequest>, String> {
+    if !args.iter().any(|arg| arg == EXPORT_NAVMESH_FLAG) {
+        return Ok(None);
+    }
+    let usage = || {
+        format!(
+            "Usage: mirador {} ({} <seed> | {} <file>) [{}]",
+            EXPORT_NAVMESH_FLAG, SEED_FLAG, MAZE_FLAG, OBJ_FLAG
+        )
+    };
+    let value = |flag: &str| {
+        args.iter()
+            .position(|arg| arg == flag)
+            .map(|index| args.get(index + 1).filter(|value| !value.starts_with("--")))
+    };
+    let source = match (value(SEED_FLAG), value(MAZE_FLAG)) {
+        (Some(Some(text)), None) => NavmeshSource::Seed(
+            seed::parse_seed(text).map_err(|e| format!("Invalid seed {:?}: {}", text, e))?,
+        ),
+        (None, Some(Some(path))) => NavmeshSource::MazeFile(PathBuf::from(path)),
+        _ => return Err(usage()),
+    };
+    Ok(Some(ExportRequest {
+        source,
+        obj: args.iter().any(|arg| arg == OBJ_FLAG),
+    }))
+}
+
+/// Carves a seeded run's first level the way the game does, without a window.
+///
+/// # Arguments
+/// * `run_seed` - The run's seed
+///
+/// # Returns
+/// The wall grid and its exit in wall grid coordinates
+pub fn first_level_grid(run_seed: u64) -> (Vec<Vec<bool>>, Option<Cell>) {
+    let (mut generator, maze) =
+        MazeGenerator::with_seed(MAZE_WIDTH, MAZE_HEIGHT, seed::level_seed(run_seed, 1));
+    while !generator.is_complete() {
+        generator.step();
+    }
+    let maze = maze.lock().expect("Failed to lock maze");
+    maze.level_grid()
+}
+
+/// Builds the navigation mesh a request asks for and writes it.
+///
+/// # Arguments
+/// * `request` - The level to export and the formats to write
+/// * `dir` - Directory to write into, created if necessary; see
+///   [`Paths::navmesh_dir`](crate::paths::Paths::navmesh_dir)
+///
+/// # Returns
+/// The files written, JSON first, or an error message if the maze can't be read or a file
+/// can't be written
+pub fn export(request: &ExportRequest, dir: &Path) -> Result<Vec<PathBuf>, String> {
+    let (grid, exit, source, stem) = match &request.source {
+        NavmeshSource::Seed(run_seed) => {
+            let (grid, exit) = first_level_grid(*run_seed);
+            let seed = seed::format_seed(*run_seed);
+            (
+                grid,
+                exit,
+                format!("seed {}", seed),
+                format!("seed-{}", seed),
+            )
+        }
+        NavmeshSource::MazeFile(path) => {
+            let (grid, exit) = try_parse_maze_file(path)?;
+            let name = path.file_stem().map_or_else(
+                || "maze".to_string(),
+                |stem| stem.to_string_lossy().into_owned(),
+            );
+            (grid, exit, path.display().to_string(), name)
+        }
+    };
+    let navmesh = Navmesh::from_grid(&grid, exit, false, &source);
+
+    fs::create_dir_all(dir).map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+    let mut files = vec![(dir.join(format!("{}.json", stem)), navmesh.to_json()?)];
+    if request.obj {
+        files.push((dir.join(format!("{}.obj", stem)), navmesh.to_obj()));
+    }
+    files
+        .into_iter()
+        .map(|(path, contents)| {
+            fs::write(&path, contents)
+                .map(|_| path.clone())
+                .map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    fn args(list: &[&str]) -> Vec<String> {
+        list.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn test_export_agrees_with_collision() {
+        let (grid, exit) = first_level_grid(42);
+        for is_test_mode in [false, true] {
+            let dimensions = PlayerDimensions::for_mode(is_test_mode);
+            let mut collision =
+                CollisionSystem::new(dimensions.collision_radius, dimensions.collision_height);
+            collision.build_from_maze(&grid, is_test_mode);
+            let navmesh = Navmesh::from_grid(&grid, exit, is_test_mode, "test");
+
+            let half = navmesh.scale.floor_size / 2.0;
+            let mut rng = StdRng::seed_from_u64(7);
+            let (mut walkable, mut buried) = (0, 0);
+            for _ in 0..4000 {
+                let position = [rng.gen_range(-half..half), 0.0, rng.gen_range(-half..half)];
+                let found = collision.wall_containing(position, Layer::Lower);
+                if navmesh.is_walkable(position[0], position[2]) {
+                    assert_eq!(found, None, "walkable {:?} is inside a wall", position);
+                    walkable += 1;
+                } else if let Some(wall) = navmesh.wall_at(position[0], position[2]) {
+                    assert_eq!(
+                        found,
+                        Some(Cell::new(wall.row, wall.col)),
+                        "{:?} is not in the exported wall",
+                        position
+                    );
+                    buried += 1;
+                }
+            }
+            assert!(walkable > 0 && buried > 0);
+            assert!(walkable + buried > 3990, "too few points classified");
+        }
+    }
+
+    #[test]
+    fn test_graph_and_landmarks() {
+        let (grid, exit) = first_level_grid(42);
+        let navmesh = Navmesh::from_grid(&grid, exit, false, "test");
+        assert_eq!(
+            navmesh.cells.len() + navmesh.walls.len(),
+            grid.len() * grid[0].len()
+        );
+
+        // Neighbors go both ways and are one cell apart
+        for (index, cell) in navmesh.cells.iter().enumerate() {
+            for &neighbor in &cell.neighbors {
+                let other = &navmesh.cells[neighbor];
+                assert!(other.neighbors.contains(&index));
+                assert_eq!(
+                    cell.row.abs_diff(other.row) + cell.col.abs_diff(other.col),
+                    1
+                );
+            }
+        }
+
+        // A perfect maze joins every open square to the entrance
+        let entrance = navmesh.landmarks.entrance;
+        let start = navmesh
+            .cells
+            .iter()
+            .position(|cell| cell.center == entrance)
+            .expect("entrance is a walkable square");
+        let mut reached = vec![false; navmesh.cells.len()];
+        let mut pending = vec![start];
+        while let Some(index) = pending.pop() {
+            if !std::mem::replace(&mut reached[index], true) {
+                pending.extend(&navmesh.cells[index].neighbors);
+            }
+        }
+        assert!(reached.iter().all(|&reached| reached));
+
+        let exit = navmesh.landmarks.exit.expect("seeded levels have an exit");
+        assert!(navmesh.is_walkable(exit[0], exit[2]));
+
+        let json = navmesh.to_json().unwrap();
+        assert_eq!(serde_json::from_str::<Navmesh>(&json).unwrap(), navmesh);
+        let obj = navmesh.to_obj();
+        let vertices = obj.lines().filter(|line| line.starts_with("v ")).count();
+        assert_eq!(vertices, navmesh.cells.len() * 4 + navmesh.walls.len() * 8);
+    }
+
+    #[test]
+    fn test_export_request_parsing() {
+        assert_eq!(
+            export_request(&args(&["mirador", "--seed", "k7"])),
+            Ok(None)
+        );
+        assert_eq!(
+            export_request(&args(&[
+                "mirador",
+                EXPORT_NAVMESH_FLAG,
+                SEED_FLAG,
+                "k7",
+                OBJ_FLAG
+            ])),
+            Ok(Some(ExportRequest {
+                source: NavmeshSource::Seed(seed::parse_seed("k7").unwrap()),
+                obj: true,
+            }))
+        );
+        assert_eq!(
+            export_request(&args(&["mirador", MAZE_FLAG, "a.mz", EXPORT_NAVMESH_FLAG])),
+            Ok(Some(ExportRequest {
+                source: NavmeshSource::MazeFile(PathBuf::from("a.mz")),
+                obj: false,
+            }))
+        );
+        for bad in [
+            &["mirador", EXPORT_NAVMESH_FLAG][..],
+            &["mirador", EXPORT_NAVMESH_FLAG, SEED_FLAG][..],
+            &["mirador", EXPORT_NAVMESH_FLAG, SEED_FLAG, "k!"][..],
+            &[
+                "mirador",
+                EXPORT_NAVMESH_FLAG,
+                SEED_FLAG,
+                "1",
+                MAZE_FLAG,
+                "a.mz",
+            ][..],
+        ] {
+            assert!(export_request(&args(bad)).is_err(), "{:?}", bad);
+        }
+    }
+}
@@ -0,0 +1,581 @@
+//! Pack behavior for levels with more than one enemy.
+//!
+//! Enemies that each chase the player on their own pile into the same corridor and arrive as
+//! one blob. [`plan_pack`] plans every enemy's route together instead, on the maze's wall grid,
+//! so the pack spreads out:
+//!
+//! - **Separation**: an enemy within [`PackConfig::separation_steps`] of another is charged
+//!   extra for entries next to that enemy's planned route, so it prefers a different corridor.
+//!   Entries another enemy stands on or is about to step onto cost extra for everyone, so
+//!   enemies that do end up together split up again.
+//! - **Intercepts**: at most [`PackConfig::max_direct_chasers`] enemies, the closest ones, head
+//!   for the player's own cell. The rest head for cells ahead of the player along the corridor
+//!   they are moving down, cutting them off instead of trailing behind.
+//!
+//! Routes come from [`find_path_with_cost`], so the penalties only bend routes; an enemy
+//! still takes a crowded corridor when it is the only way through. Debug builds log any two
+//! enemies planned from the same entry, and stop the game once two enemies have shared an
+//! entry for longer than [`MAX_STACKED_SECS`].
+//!
+//! How many enemies hunt the player ramps up with the level along the
+//! [`DifficultyCurve`](crate::game::levels::DifficultyCurve). The first enemy stays the lead in
+//! [`GameState::enemy`](crate::game::GameState::enemy); the rest are kept in a [`Pack`], which
+//! replans whenever an enemy or the player steps onto another entry and hands every enemy the
+//! next waypoint along its route.
+//!
+//! # Usage
+//!
+//! ```rust,ignore
+//! let waypoints = pack.waypoints(&lead, player.position, player.layer, &collision_system, dt);
+//! for (enemy, waypoint) in iter::once(&mut lead).chain(&mut pack.enemies).zip(waypoints) {
+//!     enemy.update_in_pack(waypoint, player.position, player.layer, floors, dt, level, check);
+//! }
+//! ```
+
+use crate::game::collision::CollisionSystem;
+use crate::game::enemy::Enemy;
+use crate::game::maze::floors::Layer;
+use crate::game::maze::generator::Cell;
+use crate::game::maze::{find_path_with_cost, path_distances};
+use crate::math::coordinates;
+use std::iter;
+
+/// Longest two enemies may share an entry, in seconds, before debug builds stop the game.
+pub const MAX_STACKED_SECS: f32 = 3.0;
+
+/// How the pack spreads out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PackConfig {
+    /// Grid steps apart, counted through walls, within which enemies steer clear of each
+    /// other's routes
+    pub separation_steps: usize,
+    /// Extra cost of an entry next to the route of an enemy within `separation_steps`
+    pub near_route_penalty: u32,
+    /// Extra cost of an entry another enemy stands on or is about to step onto
+    pub occupied_penalty: u32,
+    /// Most enemies that head for the player's own cell at once
+    pub max_direct_chasers: usize,
+    /// Grid steps ahead of the player between one intercept cell and the next
+    pub intercept_lead_steps: usize,
+    /// Grid steps from the player, ignoring walls, within which an enemy stops following its
+    /// route and closes in with its own pathfinding
+    pub close_in_steps: usize,
+}
+
+impl Default for PackConfig {
+    /// Enemies within two maze cells keep apart, one enemy chases directly, and the rest aim
+    /// about three cells ahead of the player, then six, and so on. An enemy within three cells
+    /// of the player closes in on them directly.
+    fn default() -> Self {
+        Self {
+            separation_steps: 4,
+            near_route_penalty: 12,
+            occupied_penalty: 40,
+            max_direct_chasers: 1,
+            intercept_lead_steps: 6,
+            close_in_steps: 6,
+        }
+    }
+}
+
+/// The enemies hunting alongside the level's lead enemy, and the routes planned for them.
+///
+/// The lead itself stays in [`GameState::enemy`](crate::game::GameState::enemy), which the
+/// capture sequence, the retry snapshot and the enemy trail follow. A pack member that catches
+/// the player is swapped into its place.
+#[derive(Debug, Clone, Default)]
+pub struct Pack {
+    /// Every enemy besides the lead
+    pub enemies: Vec<Enemy>,
+    /// How the pack spreads out
+    pub config: PackConfig,
+    /// The entries the current plans were made from, the enemies' and then the player's
+    planned_from: Option<(Vec<Cell>, Cell)>,
+    /// The current plans, one per enemy on the player's floor
+    plans: Vec<Option<PackPlan>>,
+    /// The entry the player stands on and the one they stood on before it
+    player_cells: Option<(Cell, Cell)>,
+    /// Seconds two enemies have shared an entry without a break
+    stacked_secs: f32,
+}
+
+impl Pack {
+    /// Replaces the enemies of the pack and forgets the old plans.
+    ///
+    /// # Arguments
+    /// * `enemies` - Every enemy besides the lead; empty for a lone enemy
+    pub fn reset(&mut self, enemies: Vec<Enemy>) {
+        *self = Self {
+            enemies,
+            config: self.config,
+            ..Self::default()
+        };
+    }
+
+    /// Returns the id of every pack member's audio source, in the order of the enemies.
+    pub fn audio_ids(&self) -> impl Iterator<Item = String> {
+        (0..self.enemies.len()).map(|i| format!("enemy_pack_{}", i))
+    }
+
+    /// Picks where every enemy steers this frame.
+    ///
+    /// Enemies on the player's floor are planned together with [`plan_pack`], again whenever
+    /// one of them or the player steps onto another entry, and steer for the next entry or
+    /// two along their route. Enemies on the other floor, enemies within
+    /// [`PackConfig::close_in_steps`] of the player, and a lead without a pack get no
+    /// waypoint and chase the player with their own pathfinding.
+    ///
+    /// # Arguments
+    /// * `lead` - The lead enemy
+    /// * `player_position` - Current 3D position of the player `[x, y, z]`
+    /// * `player_layer` - Floor the player is on
+    /// * `collision_system` - The level's collision data, for the wall grids
+    /// * `delta_time` - Time elapsed since last frame in seconds
+    ///
+    /// # Returns
+    /// One waypoint per enemy, the lead first and then the pack in order, or `None` for an
+    /// enemy chasing the player directly
+    ///
+    /// # Panics
+    /// In debug builds, once two unlocked enemies on the player's floor have shared an entry
+    /// for longer than [`MAX_STACKED_SECS`]
+    pub fn waypoints(
+        &mut self,
+        lead: &Enemy,
+        player_position: [f32; 3],
+        player_layer: Layer,
+        collision_system: &CollisionSystem,
+        delta_time: f32,
+    ) -> Vec<Option<[f32; 3]>> {
+        if self.enemies.is_empty() {
+            return vec![None];
+        }
+        let maze_dimensions = collision_system.maze_dimensions;
+        let is_test_mode = collision_system.is_test_mode;
+        let to_cell =
+            |position| coordinates::world_to_maze(position, maze_dimensions, is_test_mode);
+
+        let player = to_cell(player_position);
+        let player_previous = match self.player_cells {
+            Some((current, previous)) if current == player => previous,
+            Some((current, _)) => current,
+            None => player,
+        };
+        self.player_cells = Some((player, player_previous));
+
+        let enemies: Vec<&Enemy> = iter::once(lead).chain(&self.enemies).collect();
+        let hunting: Vec<usize> = (0..enemies.len())
+            .filter(|&i| enemies[i].layer == player_layer)
+            .collect();
+        let cells: Vec<Cell> = hunting
+            .iter()
+            .map(|&i| to_cell(enemies[i].pathfinder.position))
+            .collect();
+
+        if lead.pathfinder.locked || overlapping_enemies(&cells).is_empty() {
+            self.stacked_secs = 0.0;
+        } else {
+            self.stacked_secs += delta_time;
+        }
+        debug_assert!(
+            self.stacked_secs <= MAX_STACKED_SECS,
+            "enemies {:?} shared an entry for {:.1}s",
+            overlapping_enemies(&cells),
+            self.stacked_secs
+        );
+
+        let planned_from = (cells, player);
+        if self.planned_from.as_ref() != Some(&planned_from) {
+            self.plans = plan_pack(
+                collision_system.grid(player_layer),
+                &planned_from.0,
+                player,
+                player_previous,
+                &self.config,
+            );
+            self.planned_from = Some(planned_from);
+        }
+
+        let mut waypoints = vec![None; enemies.len()];
+        for (&i, plan) in hunting.iter().zip(&self.plans) {
+            let Some(plan) = plan else {
+                continue;
+            };
+            let from = plan.path[0];
+            if grid_distance(from, player) <= self.config.close_in_steps {
+                continue;
+            }
+            // Two entries ahead on a straight, only the next one around a corner
+            let next = plan
+                .path
+                .get(2)
+                .filter(|ahead| ahead.row == from.row || ahead.col == from.col)
+                .or(plan.path.get(1))
+                .unwrap_or(&from);
+            waypoints[i] = Some(coordinates::maze_to_world(
+                next,
+                maze_dimensions,
+                enemies[i].pathfinder.position[1],
+                is_test_mode,
+            ));
+        }
+        waypoints
+    }
+}
+
+/// Where one enemy of the pack is headed, and how it gets there.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PackPlan {
+    /// The entry the enemy is headed for: the player's cell or an intercept ahead of them
+    pub target: Cell,
+    /// Every entry from the enemy's own to the target, both included
+    pub path: Vec<Cell>,
+}
+
+/// Plans the routes of every enemy in the pack at once.
+///
+/// Enemies are planned closest to the player first, so the closest ones chase directly and
+/// the rest fit around their routes.
+///
+/// # Arguments
+/// * `maze_grid` - 2D grid of the maze where `true` marks a wall
+/// * `enemies` - The entry each enemy stands on
+/// * `player` - The entry the player stands on
+/// * `player_previous` - The entry the player stood on before, giving their heading; the
+///   same as `player` while they stand still
+/// * `config` - How the pack spreads out
+///
+/// # Returns
+/// One plan per enemy, in the order of `enemies`, or `None` for an enemy that can't reach
+/// the player
+pub fn plan_pack(
+    maze_grid: &[Vec<bool>],
+    enemies: &[Cell],
+    player: Cell,
+    player_previous: Cell,
+    config: &PackConfig,
+) -> Vec<Option<PackPlan>> {
+    #[cfg(debug_assertions)]
+    for (first, second) in overlapping_enemies(enemies) {
+        eprintln!(
+            "[pack] enemies {} and {} share cell ({}, {})",
+            first, second, enemies[first].row, enemies[first].col
+        );
+    }
+
+    let height = maze_grid.len();
+    let width = maze_grid.first().map_or(0, Vec::len);
+    let distances = path_distances(maze_grid, player);
+    let mut order: Vec<usize> = (0..enemies.len()).collect();
+    order.sort_by_key(|&i| {
+        distances
+            .get(enemies[i].row)
+            .and_then(|row| row.get(enemies[i].col))
+            .copied()
+            .flatten()
+            .unwrap_or(usize::MAX)
+    });
+
+    let route = predict_route(
+        maze_grid,
+        player,
+        player_previous,
+        enemies.len() * config.intercept_lead_steps,
+    );
+    // Where the rest close in from when there is nothing ahead to cut off
+    let beside: Vec<Cell> = neighborhood(player, width, height)
+        .filter(|&cell| cell != player && !maze_grid[cell.row][cell.col])
+        .collect();
+    let mut plans: Vec<Option<PackPlan>> = vec![None; enemies.len()];
+    let mut targets = Vec::new();
+    for (rank, &i) in order.iter().enumerate() {
+        let target = if rank < config.max_direct_chasers {
+            player
+        } else {
+            let nth = rank + 1 - config.max_direct_chasers;
+            intercept_cell(&route, &beside, nth, config, &targets)
+        };
+        targets.push(target);
+
+        let mut penalty = vec![0u32; width * height];
+        for (j, &other) in enemies.iter().enumerate() {
+            if j == i {
+                continue;
+            }
+            penalty[other.row * width + other.col] += config.occupied_penalty;
+            let Some(plan) = &plans[j] else {
+                continue;
+            };
+            if let Some(next) = plan.path.get(1) {
+                penalty[next.row * width + next.col] += config.occupied_penalty;
+            }
+            if grid_distance(enemies[i], other) > config.separation_steps {
+                continue;
+            }
+            for &cell in &plan.path {
+                for near in neighborhood(cell, width, height) {
+                    let entry = &mut penalty[near.row * width + near.col];
+                    *entry = (*entry).max(config.near_route_penalty);
+                }
+            }
+        }
+        // An enemy is never charged for the entry it already stands on
+        penalty[enemies[i].row * width + enemies[i].col] = 0;
+
+        plans[i] = find_path_with_cost(maze_grid, enemies[i], target, |cell| {
+            penalty[cell.row * width + cell.col]
+        })
+        .map(|path| PackPlan { target, path });
+    }
+    plans
+}
+
+/// Finds every pair of enemies standing on the same entry.
+///
+/// # Arguments
+/// * `enemies` - The entry each enemy stands on
+///
+/// # Returns
+/// The indices of each overlapping pair, lower first
+pub fn overlapping_enemies(enemies: &[Cell]) -> Vec<(usize, usize)> {
+    let mut pairs = Vec::new();
+    for (first, a) in enemies.iter().enumerate() {
+        for (second, b) in enemies.iter().enumerate().skip(first + 1) {
+            if a == b {
+                pairs.push((first, second));
+            }
+        }
+    }
+    pairs
+}
+
+/// Follows the player's heading down the maze to guess where they are going.
+///
+/// The route follows the corridor the player is heading down, around its turns, and stops at
+/// a dead end or a junction, where there is no telling which way the player will go.
+///
+/// # Arguments
+/// * `maze_grid` - 2D grid of the maze where `true` marks a wall
+/// * `player` - The entry the player stands on
+/// * `player_previous` - The entry the player stood on before
+/// * `steps` - Most grid steps to look ahead
+///
+/// # Returns
+/// The entries ahead of the player, starting with their own
+pub fn predict_route(
+    maze_grid: &[Vec<bool>],
+    player: Cell,
+    player_previous: Cell,
+    steps: usize,
+) -> Vec<Cell> {
+    let height = maze_grid.len();
+    let width = maze_grid.first().map_or(0, Vec::len);
+    let mut route = vec![player];
+    if player == player_previous {
+        return route;
+    }
+    let (mut previous, mut current) = (player_previous, player);
+    while route.len() <= steps {
+        let ahead: Vec<Cell> = neighborhood(current, width, height)
+            .filter(|&next| next != current && next != previous)
+            .filter(|next| !maze_grid[next.row][next.col])
+            .collect();
+        let [next] = ahead[..] else {
+            break;
+        };
+        (previous, current) = (current, next);
+        route.push(current);
+    }
+    route
+}
+
+/// Picks the intercept cell of the `nth` enemy that isn't chasing directly.
+///
+/// # Arguments
+/// * `route` - The player's predicted route, as from [`predict_route`]
+/// * `beside` - Open entries next to the player, used once the route has no free cell
+/// * `nth` - Which intercepting enemy this is, counting from 1
+/// * `config` - How far apart intercepts are
+/// * `taken` - Targets already handed out, which are skipped
+///
+/// # Returns
+/// The free route cell closest to `nth` leads ahead, else a free entry beside the player,
+/// else the player's own entry
+fn intercept_cell(
+    route: &[Cell],
+    beside: &[Cell],
+    nth: usize,
+    config: &PackConfig,
+    taken: &[Cell],
+) -> Cell {
+    let ideal = (nth * config.intercept_lead_steps).min(route.len() - 1);
+    route[..=ideal]
+        .iter()
+        .rev()
+        .chain(beside)
+        .find(|cell| !taken.contains(cell))
+        .copied()
+        .unwrap_or(route[0])
+}
+
+/// Counts the grid steps between two entries, ignoring walls.
+fn grid_distance(a: Cell, b: Cell) -> usize {
+    a.row.abs_diff(b.row) + a.col.abs_diff(b.col)
+}
+
+/// Returns an entry and the entries beside it that are inside the grid.
+fn neighborhood(cell: Cell, width: usize, height: usize) -> impl Iterator<Item = Cell> {
+    [
+        (cell.row, cell.col),
+        (cell.row.wrapping_sub(1), cell.col),
+        (cell.row + 1, cell.col),
+        (cell.row, cell.col.wrapping_sub(1)),
+        (cell.row, cell.col + 1),
+    ]
+    .into_iter()
+    .filter(move |&(row, col)| row < height && col < width)
+    .map(|(row, col)| Cell::new(row, col))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::maze::parse_maze_str;
+
+    /// Three long corridors joined at both ends.
+    const LADDER: &str = "\
+#################
+#               #
+# ############# #
+#               #
+# ############# #
+#               #
+#################
+";
+
+    #[test]
+    fn test_pack_spreads_over_corridors_without_stacking() {
+        let (grid, _) = parse_maze_str(LADDER).expect("valid maze");
+        let config = PackConfig::default();
+        // The pack starts bunched up in the middle corridor, the player at the far end
+        let mut enemies = vec![Cell::new(3, 1), Cell::new(3, 3), Cell::new(3, 5)];
+        // The player paces up and down the right-hand side
+        let player_route: Vec<Cell> = [3, 2, 1, 2, 3, 4, 5, 4]
+            .iter()
+            .cycle()
+            .take(40)
+            .map(|&row| Cell::new(row, 15))
+            .collect();
+
+        let mut corridors_used = std::collections::HashSet::new();
+        let mut stacked_ticks = 0;
+        let mut previous_player = player_route[0];
+        for (tick, &player) in player_route.iter().enumerate() {
+            let plans = plan_pack(&grid, &enemies, player, previous_player, &config);
+            assert_eq!(
+                plans
+                    .iter()
+                    .flatten()
+                    .filter(|plan| plan.target == player)
+                    .count(),
+                config.max_direct_chasers,
+                "tick {}",
+                tick
+            );
+            for (enemy, plan) in enemies.iter_mut().zip(plans) {
+                let plan = plan.expect("Every enemy reaches the player");
+                if let Some(&next) = plan.path.get(1) {
+                    *enemy = next;
+                }
+                // The corridor an enemy is in while crossing the middle of the maze
+                if (3..=13).contains(&enemy.col) {
+                    corridors_used.insert(enemy.row);
+                }
+            }
+            if overlapping_enemies(&enemies).is_empty() {
+                stacked_ticks = 0;
+            } else {
+                stacked_ticks += 1;
+                assert!(stacked_ticks <= 2, "enemies stacked up at tick {}", tick);
+            }
+            previous_player = player;
+        }
+        assert!(corridors_used.len() >= 2, "{:?}", corridors_used);
+    }
+
+    #[test]
+    fn test_waypoints_follow_the_routes_until_the_pack_closes_in() {
+        let (grid, _) = parse_maze_str(LADDER).expect("valid maze");
+        let mut collision_system = CollisionSystem::new(5.0, 30.0);
+        collision_system.build_from_maze(&grid, false);
+        let dimensions = collision_system.maze_dimensions;
+        let at =
+            |row, col| coordinates::maze_to_world(&Cell::new(row, col), dimensions, 30.0, false);
+        let mut lead = Enemy::new(at(3, 3), 150.0);
+        lead.pathfinder.locked = false;
+        let player = at(1, 15);
+
+        // A lone enemy keeps its own pathfinding
+        let mut pack = Pack::default();
+        assert_eq!(
+            pack.waypoints(&lead, player, Layer::Lower, &collision_system, 0.1),
+            vec![None]
+        );
+
+        // Far from the player, the whole pack steers along its routes, one or two entries on
+        pack.reset(vec![
+            Enemy::new(at(5, 1), 150.0),
+            Enemy::new(at(1, 11), 150.0),
+        ]);
+        let waypoints = pack.waypoints(&lead, player, Layer::Lower, &collision_system, 0.1);
+        assert_eq!(waypoints.len(), 3);
+        let lead_waypoint = waypoints[0].expect("The lead is far from the player");
+        let next = coordinates::world_to_maze(lead_waypoint, dimensions, false);
+        assert!(grid_distance(next, Cell::new(3, 3)) <= 2, "{:?}", next);
+        assert!(waypoints[1].is_some());
+        // Close to the player, an enemy closes in with its own pathfinding
+        assert_eq!(waypoints[2], None);
+
+        // Enemies on another floor head for the stairs on their own
+        pack.enemies[0].layer = Layer::Upper;
+        let waypoints = pack.waypoints(&lead, player, Layer::Lower, &collision_system, 0.1);
+        assert_eq!(waypoints[1], None);
+    }
+
+    #[test]
+    fn test_intercepts_lie_ahead_of_the_player() {
+        let (grid, _) = parse_maze_str(LADDER).expect("valid maze");
+        // Walking left along the top corridor
+        let route = predict_route(&grid, Cell::new(1, 11), Cell::new(1, 12), 20);
+        assert_eq!(route[0], Cell::new(1, 11));
+        assert_eq!(route[10], Cell::new(1, 1));
+        // The corridor turns down at the corner and stops at the junction below it
+        assert_eq!(route.last(), Some(&Cell::new(3, 1)));
+
+        let config = PackConfig::default();
+        let enemies = [Cell::new(5, 1), Cell::new(5, 15), Cell::new(3, 9)];
+        let plans = plan_pack(&grid, &enemies, Cell::new(1, 11), Cell::new(1, 12), &config);
+        let targets: Vec<Cell> = plans.iter().flatten().map(|plan| plan.target).collect();
+        assert_eq!(targets.len(), 3);
+        assert!(targets.contains(&Cell::new(1, 11)));
+        assert!(targets.contains(&route[6]));
+        assert!(targets.contains(&route[12]));
+
+        // Standing still gives nothing to lead, so the rest close in from beside the player
+        assert_eq!(
+            predict_route(&grid, Cell::new(1, 11), Cell::new(1, 11), 20).len(),
+            1
+        );
+        let plans = plan_pack(&grid, &enemies, Cell::new(1, 11), Cell::new(1, 11), &config);
+        let mut targets: Vec<Cell> = plans.iter().flatten().map(|plan| plan.target).collect();
+        targets.sort_by_key(|cell| (cell.row, cell.col));
+        assert_eq!(
+            targets,
+            vec![Cell::new(1, 10), Cell::new(1, 11), Cell::new(1, 12)]
+        );
+        assert_eq!(
+            overlapping_enemies(&[Cell::new(1, 1), Cell::new(3, 3), Cell::new(1, 1)]),
+            vec![(0, 2)]
+        );
+    }
+}
@@ -7,8 +7,10 @@
 //!
 //! The player system handles:
 //! - **Position and Orientation**: 3D world position, pitch/yaw angles for camera control
-//! - **Movement**: Forward/backward/left/right movement with speed-based physics
+//! - **Movement**: Forward/backward/left/right movement, accelerated toward top speed and
+//!   slowed by friction (see [`MovementConfig`])
 //! - **Stamina System**: Sprint mechanics with depletion and regeneration
+//! - **Gaits**: Walking, sprinting, and crouch-walking, with speed blended between them
 //! - **Maze Integration**: Cell-based positioning and spawn logic
 //! - **View Matrix**: Camera transformation calculations for rendering
 //!
@@ -25,7 +27,7 @@
 //!
 //! # Usage Example
 //!
-//! ```rust
+//! ```rust,ignore
 //! use crate::game::player::Player;
 //!
 //! let mut player = Player::new();
@@ -43,10 +45,140 @@
 //! let view_matrix = player.get_view_matrix();
 //! ```
 
+use crate::game::collision::WallImpact;
+use crate::game::maze::floors::Layer;
 use crate::game::maze::generator::Cell;
 use crate::math::coordinates::{self, constants::PLAYER_HEIGHT};
 use crate::math::mat::Mat4;
 
+/// Largest frame delta, in seconds, that movement is integrated over.
+///
+/// A frame hitch (window drag, breakpoint, shader compile) can produce a huge
+/// `delta_time`. Capping it keeps a single movement step small enough that the
+/// collision system can still catch it, at the cost of the player briefly
+/// moving slower than real time after a hitch.
+pub const MAX_MOVEMENT_DELTA: f32 = 0.1;
+
+/// Fraction of an intended step the player must actually cover for the step
+/// not to count as a head-on wall hit.
+///
+/// Sliding along a wall keeps most of the step, while walking straight into one
+/// keeps almost none of it.
+const HEAD_ON_PROGRESS_RATIO: f32 = 0.3;
+
+/// Shortest intended step, in world units, that can count as a head-on wall hit.
+///
+/// Ignores the tiny steps produced by very small frame deltas.
+const HEAD_ON_MIN_STEP: f32 = 0.5;
+
+/// Speed multiplier applied to `base_speed` while sprinting.
+pub const SPRINT_SPEED_MULTIPLIER: f32 = 1.75;
+
+/// Speed multiplier applied to `base_speed` while crouch-walking.
+pub const CROUCH_SPEED_MULTIPLIER: f32 = 0.4;
+
+/// Time constant, in seconds, of the blend between gait speeds.
+///
+/// After this long the speed has covered about two thirds of the way to the new gait's.
+pub const SPEED_BLEND_TIME: f32 = 0.1;
+
+/// How quickly the player speeds up, slows down, and turns.
+///
+/// Rates are in world units per second squared. The defaults reach walking and sprinting
+/// speed in about 0.15 s, which is barely noticeable but takes the twitch out of small
+/// adjustments against walls.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MovementConfig {
+    /// Acceleration toward the input direction while walking or crouching
+    pub acceleration: f32,
+    /// Acceleration toward the input direction while sprinting
+    pub sprint_acceleration: f32,
+    /// Ground friction slowing the player while no movement key is held
+    pub deceleration: f32,
+    /// Fraction of the acceleration lost when the input points straight against the motion
+    ///
+    /// Scales with how far the input turns from the motion: a 180° turn accelerates at
+    /// `1 - turn_penalty` of the usual rate and a 90° turn loses half that.
+    pub turn_penalty: f32,
+}
+
+impl Default for MovementConfig {
+    fn default() -> Self {
+        Self {
+            acceleration: 800.0,
+            sprint_acceleration: 1400.0,
+            deceleration: 1000.0,
+            turn_penalty: 0.5,
+        }
+    }
+}
+
+/// How far the camera drops while crouching, in world units.
+pub const CROUCH_CAMERA_DROP: f32 = 10.0;
+
+/// Seconds the camera takes to drop into, or rise out of, a crouch.
+pub const CROUCH_TRANSITION_TIME: f32 = 0.2;
+
+/// How the player is moving, which sets the speed they blend toward.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Gait {
+    /// Normal walking speed
+    #[default]
+    Walk,
+    /// Faster, but drains stamina
+    Sprint,
+    /// Slower and silent, and the enemy only notices the player from closer
+    Crouch,
+}
+
+impl Gait {
+    /// Returns the multiplier applied to the player's base speed in this gait.
+    pub fn speed_multiplier(self) -> f32 {
+        match self {
+            Gait::Walk => 1.0,
+            Gait::Sprint => SPRINT_SPEED_MULTIPLIER,
+            Gait::Crouch => CROUCH_SPEED_MULTIPLIER,
+        }
+    }
+}
+
+/// What happened when the player tried to move during a frame.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct MovementOutcome {
+    /// Horizontal distance the player actually covered after collision resolution
+    pub distance: f32,
+    /// Whether a wall stopped most of the intended movement
+    pub hit_wall_head_on: bool,
+    /// The hardest wall the player ran into during the step, if any
+    pub impact: Option<WallImpact>,
+}
+
+impl MovementOutcome {
+    /// Compares an intended step with the step the player actually took.
+    ///
+    /// # Arguments
+    ///
+    /// * `start` - Position before moving
+    /// * `desired` - Position the player was trying to reach
+    /// * `resolved` - Position after collision resolution
+    ///
+    /// # Returns
+    ///
+    /// The distance covered and whether the step was a head-on wall hit
+    pub fn from_step(start: [f32; 3], desired: [f32; 3], resolved: [f32; 3]) -> Self {
+        let horizontal =
+            |to: [f32; 3]| ((to[0] - start[0]).powi(2) + (to[2] - start[2]).powi(2)).sqrt();
+        let intended = horizontal(desired);
+        let distance = horizontal(resolved);
+        Self {
+            distance,
+            hit_wall_head_on: intended > HEAD_ON_MIN_STEP
+                && distance < intended * HEAD_ON_PROGRESS_RATIO,
+            impact: None,
+        }
+    }
+}
+
 /// Represents the player character's state in the world.
 ///
 /// The `Player` struct encapsulates all player-related state including position,
@@ -64,6 +196,9 @@ use crate::math::mat::Mat4;
 /// ## Movement Parameters
 /// - `base_speed`: Base movement speed in units per second
 /// - `speed`: Current movement speed (can be modified by upgrades/effects)
+/// - `velocity`: Horizontal velocity, which accelerates toward `speed` in the input direction
+/// - `movement`: Acceleration, friction, and turning rates
+/// - `acceleration_multiplier`: Upgrade multiplier on the acceleration rates
 /// - `mouse_sensitivity`: Multiplier for mouse movement sensitivity
 ///
 /// ## Maze Integration
@@ -76,9 +211,13 @@ use crate::math::mat::Mat4;
 /// - `stamina_regen_rate`: Stamina points regenerated per second
 /// - `last_sprint_time`: Time accumulator for regeneration cooldown
 ///
+/// ## Crouching
+/// - `crouching`: Whether the player is crouch-walking this frame
+/// - `crouch_amount`: How far the camera has dropped into the crouch
+///
 /// # Examples
 ///
-/// ```rust
+/// ```rust,ignore
 /// use crate::game::player::Player;
 ///
 /// let player = Player::new();
@@ -129,10 +268,24 @@ pub struct Player {
 
     /// Current movement speed in units per second.
     ///
-    /// This is the actual speed used for movement calculations.
+    /// This is the top speed the player accelerates toward; `velocity` never exceeds it.
     /// Can be modified by sprinting, upgrades, or other game effects.
     pub speed: f32,
 
+    /// Horizontal velocity `[x, z]` in units per second.
+    ///
+    /// Integrated from the movement keys each frame by [`Player::accelerate`], and
+    /// turned into the frame's step by [`Player::desired_position`].
+    pub velocity: [f32; 2],
+
+    /// Acceleration, friction, and turning rates.
+    pub movement: MovementConfig,
+
+    /// Multiplier on the acceleration rates (1.0 = normal).
+    ///
+    /// Raised by the "Lightfoot" upgrade. Friction is left alone.
+    pub acceleration_multiplier: f32,
+
     /// Mouse sensitivity multiplier for camera control.
     ///
     /// Higher values make mouse movement more responsive.
@@ -144,6 +297,12 @@ pub struct Player {
     /// Used for collision detection, game logic, and maze navigation.
     pub current_cell: Cell,
 
+    /// The floor the player is on.
+    ///
+    /// Always [`Layer::Lower`] except on two-floor levels, where walking up a
+    /// stairwell's ramp moves the player to [`Layer::Upper`].
+    pub layer: Layer,
+
     /// Current stamina value (0.0 to max_stamina).
     ///
     /// Stamina is consumed when sprinting and regenerates over time.
@@ -167,11 +326,33 @@ pub struct Player {
     /// The rate at which stamina recovers when regeneration is active.
     pub stamina_regen_rate: f32,
 
+    /// Temporary multiplier on `stamina_regen_rate` (1.0 = normal).
+    ///
+    /// Set each frame from the running level events, so it never outlasts them.
+    pub stamina_regen_multiplier: f32,
+
     /// Time accumulator for stamina regeneration cooldown.
     ///
     /// Tracks how much time has passed since the last sprint.
     /// When this exceeds `stamina_regen_cooldown`, regeneration begins.
     pub last_sprint_time: f32,
+
+    /// How the player is moving this frame.
+    ///
+    /// Set by [`Player::update_gait`]; sprinting accelerates at the sprint rate.
+    pub gait: Gait,
+
+    /// Whether the player is crouching this frame.
+    ///
+    /// Set by [`Player::update_gait`]. Crouching slows the player, silences their
+    /// footsteps, and halves how far away the enemy notices them.
+    pub crouching: bool,
+
+    /// How far the camera has dropped into the crouch, from 0.0 (standing) to 1.0.
+    ///
+    /// Moves toward the current stance over [`CROUCH_TRANSITION_TIME`] seconds and
+    /// lowers the camera by up to [`CROUCH_CAMERA_DROP`].
+    pub crouch_amount: f32,
 }
 
 impl Player {
@@ -191,7 +372,7 @@ impl Player {
     ///
     /// # Examples
     ///
-    /// ```rust
+    /// ```rust,ignore
     /// use crate::game::player::Player;
     ///
     /// let player = Player::new();
@@ -207,13 +388,21 @@ impl Player {
             fov: 100.0,
             base_speed: 120.0,
             speed: 120.0,
+            velocity: [0.0, 0.0],
+            movement: MovementConfig::default(),
+            acceleration_multiplier: 1.0,
             mouse_sensitivity: 1.0,
             current_cell: Cell::default(),
+            layer: Layer::Lower,
             stamina: 1.0,
             max_stamina: 2.0,
             stamina_regen_cooldown: 0.7,
             stamina_regen_rate: 1.5,
+            stamina_regen_multiplier: 1.0,
             last_sprint_time: 0.0,
+            gait: Gait::Walk,
+            crouching: false,
+            crouch_amount: 0.0,
         }
     }
 
@@ -238,7 +427,7 @@ impl Player {
     ///
     /// # Examples
     ///
-    /// ```rust
+    /// ```rust,ignore
     /// use crate::game::player::Player;
     /// use crate::math::mat::Mat4;
     ///
@@ -257,13 +446,24 @@ impl Player {
         let rotation_matrix = yaw_matrix.multiply(&pitch_matrix);
 
         // Create translation matrix (negative because we move the world opposite to camera)
+        let eye_height = self.position[1] - self.crouch_amount * CROUCH_CAMERA_DROP;
         let translation_matrix =
-            Mat4::translation(-self.position[0], -self.position[1], -self.position[2]);
+            Mat4::translation(-self.position[0], -eye_height, -self.position[2]);
 
         // View matrix = rotation * translation
         translation_matrix.multiply(&rotation_matrix)
     }
 
+    /// Returns the unit vector the player is looking along.
+    ///
+    /// Matches the camera of [`Player::get_view_matrix`]: yaw turns about the Y axis and
+    /// positive pitch looks up.
+    pub fn view_direction(&self) -> [f32; 3] {
+        let (sin_yaw, cos_yaw) = self.yaw.to_radians().sin_cos();
+        let (sin_pitch, cos_pitch) = self.pitch.to_radians().sin_cos();
+        [-sin_yaw * cos_pitch, sin_pitch, -cos_yaw * cos_pitch]
+    }
+
     /// Computes the combined view-projection matrix for rendering.
     ///
     /// This method combines the view matrix (camera transformation) with
@@ -283,7 +483,7 @@ impl Player {
     ///
     /// # Examples
     ///
-    /// ```rust
+    /// ```rust,ignore
     /// use crate::game::player::Player;
     ///
     /// let player = Player::new();
@@ -323,7 +523,7 @@ impl Player {
     ///
     /// # Examples
     ///
-    /// ```rust
+    /// ```rust,ignore
     /// use crate::game::player::Player;
     ///
     /// let mut player = Player::new();
@@ -346,6 +546,22 @@ impl Player {
         self.pitch = self.pitch.clamp(-89.0, 89.0);
     }
 
+    /// Turns the camera with a look stick.
+    ///
+    /// Unlike the mouse, a stick sets a turning rate: held all the way over, the view turns
+    /// `degrees_per_second` each second. Pushing the stick up looks up.
+    ///
+    /// # Arguments
+    ///
+    /// * `look_axis` - Turn and tilt deflection, right and up positive
+    /// * `degrees_per_second` - How fast the view turns with the stick pushed all the way
+    /// * `delta_time` - Time elapsed since last frame in seconds
+    pub fn stick_look(&mut self, look_axis: [f32; 2], degrees_per_second: f32, delta_time: f32) {
+        let turn = degrees_per_second * delta_time;
+        self.yaw -= look_axis[0] * turn;
+        self.pitch = (self.pitch + look_axis[1] * turn).clamp(-89.0, 89.0);
+    }
+
     /// Moves the player forward based on current yaw and speed.
     ///
     /// Moves the player in the direction they are currently facing.
@@ -367,7 +583,7 @@ impl Player {
     ///
     /// # Examples
     ///
-    /// ```rust
+    /// ```rust,ignore
     /// use crate::game::player::Player;
     ///
     /// let mut player = Player::new();
@@ -404,7 +620,7 @@ impl Player {
     ///
     /// # Examples
     ///
-    /// ```rust
+    /// ```rust,ignore
     /// use crate::game::player::Player;
     ///
     /// let mut player = Player::new();
@@ -444,7 +660,7 @@ impl Player {
     ///
     /// # Examples
     ///
-    /// ```rust
+    /// ```rust,ignore
     /// use crate::game::player::Player;
     ///
     /// let mut player = Player::new();
@@ -481,7 +697,7 @@ impl Player {
     ///
     /// # Examples
     ///
-    /// ```rust
+    /// ```rust,ignore
     /// use crate::game::player::Player;
     ///
     /// let mut player = Player::new();
@@ -500,6 +716,103 @@ impl Player {
         self.position[2] -= right_z * self.speed * delta_time;
     }
 
+    /// Accelerates the player toward the direction of the move axis.
+    ///
+    /// The velocity moves toward `speed`, scaled by how far the move axis is pushed, in the
+    /// input direction at the movement config's
+    /// acceleration (or sprint acceleration), scaled by `acceleration_multiplier` and reduced
+    /// by the turn penalty when the input points away from the current motion, so reversing
+    /// blends through a stop instead of flipping. With no input, friction slows the player to
+    /// a complete stop at the deceleration rate. The velocity is never faster than `speed`.
+    ///
+    /// Time is integrated over at most [`MAX_MOVEMENT_DELTA`] seconds, like the step itself.
+    ///
+    /// # Arguments
+    ///
+    /// * `delta_time` - Time elapsed since last frame in seconds
+    /// * `move_axis` - Strafe and forward deflection, right and forward positive (see
+    ///   [`PlayerInput::move_axis`](crate::game::input::PlayerInput::move_axis)); anything
+    ///   longer than `1.0` walks at full speed
+    pub fn accelerate(&mut self, delta_time: f32, move_axis: [f32; 2]) {
+        let step_time = delta_time.clamp(0.0, MAX_MOVEMENT_DELTA);
+        let (sin_yaw, cos_yaw) = self.yaw.to_radians().sin_cos();
+
+        let [strafe, forward] = move_axis;
+        let mut direction = [
+            strafe * cos_yaw - forward * sin_yaw,
+            -strafe * sin_yaw - forward * cos_yaw,
+        ];
+        let length = (direction[0] * direction[0] + direction[1] * direction[1]).sqrt();
+        let current_speed = (self.velocity[0].powi(2) + self.velocity[1].powi(2)).sqrt();
+
+        let (target, rate) = if length > f32::EPSILON {
+            direction = [direction[0] / length, direction[1] / length];
+            let target_speed = self.speed * length.min(1.0);
+            let acceleration = if self.gait == Gait::Sprint {
+                self.movement.sprint_acceleration
+            } else {
+                self.movement.acceleration
+            };
+            // 1 moving along the input, -1 pushing straight against the motion
+            let alignment = if current_speed > f32::EPSILON {
+                (self.velocity[0] * direction[0] + self.velocity[1] * direction[1]) / current_speed
+            } else {
+                1.0
+            };
+            let turn = 1.0 - self.movement.turn_penalty * (1.0 - alignment) / 2.0;
+            (
+                [direction[0] * target_speed, direction[1] * target_speed],
+                acceleration * self.acceleration_multiplier * turn,
+            )
+        } else {
+            ([0.0, 0.0], self.movement.deceleration)
+        };
+
+        // Move toward the target velocity by at most this frame's share of the rate, landing
+        // on it exactly, so friction comes to a complete stop
+        let change = [target[0] - self.velocity[0], target[1] - self.velocity[1]];
+        let distance = (change[0] * change[0] + change[1] * change[1]).sqrt();
+        let max_change = rate * step_time;
+        if distance <= max_change {
+            self.velocity = target;
+        } else {
+            let scale = max_change / distance;
+            self.velocity[0] += change[0] * scale;
+            self.velocity[1] += change[1] * scale;
+        }
+
+        // Dropping out of a sprint lowers the ceiling as the speed blends down
+        let new_speed = (self.velocity[0].powi(2) + self.velocity[1].powi(2)).sqrt();
+        if new_speed > self.speed {
+            let scale = self.speed / new_speed;
+            self.velocity[0] *= scale;
+            self.velocity[1] *= scale;
+        }
+    }
+
+    /// Calculates where the player wants to move this frame, before collisions.
+    ///
+    /// The step is the velocity (see [`Player::accelerate`]) integrated over at most
+    /// [`MAX_MOVEMENT_DELTA`] seconds. The velocity is never faster than `speed`, so the
+    /// step is never longer than `speed * MAX_MOVEMENT_DELTA`, and neither a frame hitch
+    /// nor diagonal input can produce a step long enough to tunnel through a wall.
+    ///
+    /// # Arguments
+    ///
+    /// * `delta_time` - Time elapsed since last frame in seconds
+    ///
+    /// # Returns
+    ///
+    /// The desired position `[x, y, z]` for this frame
+    pub fn desired_position(&self, delta_time: f32) -> [f32; 3] {
+        let step_time = delta_time.clamp(0.0, MAX_MOVEMENT_DELTA);
+        [
+            self.position[0] + self.velocity[0] * step_time,
+            self.position[1],
+            self.position[2] + self.velocity[1] * step_time,
+        ]
+    }
+
     /// Moves the player upward in the Y direction.
     ///
     /// Increases the player's height (Y coordinate) at half the normal movement speed.
@@ -515,7 +828,7 @@ impl Player {
     ///
     /// # Examples
     ///
-    /// ```rust
+    /// ```rust,ignore
     /// use crate::game::player::Player;
     ///
     /// let mut player = Player::new();
@@ -545,7 +858,7 @@ impl Player {
     ///
     /// # Examples
     ///
-    /// ```rust
+    /// ```rust,ignore
     /// use crate::game::player::Player;
     ///
     /// let mut player = Player::new();
@@ -573,7 +886,7 @@ impl Player {
     ///
     /// # Examples
     ///
-    /// ```rust
+    /// ```rust,ignore
     /// use crate::game::player::Player;
     ///
     /// let mut player = Player::new();
@@ -615,7 +928,7 @@ impl Player {
     ///
     /// # Examples
     ///
-    /// ```rust
+    /// ```rust,ignore
     /// use crate::game::player::Player;
     ///
     /// let mut player = Player::new();
@@ -644,6 +957,12 @@ impl Player {
 
         // Set the initial orientation to face north (into the maze)
         self.yaw = coordinates::direction_to_yaw(coordinates::Direction::North);
+
+        // Every level starts standing still, ready to walk
+        self.crouching = false;
+        self.crouch_amount = 0.0;
+        self.speed = self.base_speed;
+        self.velocity = [0.0, 0.0];
     }
 
     /// Updates the player's stamina based on sprinting state and time.
@@ -667,13 +986,18 @@ impl Player {
     ///
     /// ## Stamina Regeneration
     /// - Starts after `stamina_regen_cooldown` seconds of not sprinting
-    /// - Regenerates at rate of `stamina_regen_rate` points per second
+    /// - Regenerates at rate of `stamina_regen_rate` points per second, scaled by
+    ///   `stamina_regen_multiplier`
     /// - Clamps to maximum of `max_stamina`
     /// - Cooldown timer accumulates when not sprinting
     ///
+    /// ## Crouch-Walking
+    /// - Neither drains nor regenerates stamina, and the cooldown timer holds still
+    /// - Crouching in place regenerates as usual
+    ///
     /// # Examples
     ///
-    /// ```rust
+    /// ```rust,ignore
     /// use crate::game::player::Player;
     ///
     /// let mut player = Player::new();
@@ -688,6 +1012,9 @@ impl Player {
     /// assert!(player.stamina > initial_stamina - 0.7);
     /// ```
     pub fn update_stamina(&mut self, is_sprinting: bool, is_moving: bool, delta_time: f32) {
+        if self.crouching && is_moving {
+            return;
+        }
         if is_sprinting && is_moving && self.stamina > 0.0 {
             self.stamina -= 0.7 * delta_time; // Deplete stamina
             if self.stamina < 0.0 {
@@ -697,7 +1024,8 @@ impl Player {
         } else {
             self.last_sprint_time += delta_time;
             if self.last_sprint_time > self.stamina_regen_cooldown {
-                self.stamina += self.stamina_regen_rate * delta_time;
+                self.stamina +=
+                    self.stamina_regen_rate * self.stamina_regen_multiplier * delta_time;
                 if self.stamina > self.max_stamina {
                     self.stamina = self.max_stamina;
                 }
@@ -719,7 +1047,7 @@ impl Player {
     ///
     /// # Examples
     ///
-    /// ```rust
+    /// ```rust,ignore
     /// use crate::game::player::Player;
     ///
     /// let mut player = Player::new();
@@ -738,4 +1066,255 @@ impl Player {
     pub fn stamina_ratio(&self) -> f32 {
         (self.stamina / self.max_stamina).clamp(0.0, 1.0)
     }
+
+    /// Moves the player's speed and stance toward a gait.
+    ///
+    /// The speed blends exponentially toward `base_speed` times the gait's multiplier
+    /// over [`SPEED_BLEND_TIME`], so switching between walking, sprinting, and crouching
+    /// never snaps. The camera drops into or rises out of the crouch at a steady rate,
+    /// taking [`CROUCH_TRANSITION_TIME`] seconds for the whole way.
+    ///
+    /// # Arguments
+    ///
+    /// * `gait` - How the player is moving this frame
+    /// * `delta_time` - Time elapsed since last frame in seconds
+    pub fn update_gait(&mut self, gait: Gait, delta_time: f32) {
+        self.gait = gait;
+        self.crouching = gait == Gait::Crouch;
+
+        let target_speed = self.base_speed * gait.speed_multiplier();
+        let blend = 1.0 - (-delta_time / SPEED_BLEND_TIME).exp();
+        self.speed += (target_speed - self.speed) * blend;
+
+        let target_crouch = if self.crouching { 1.0 } else { 0.0 };
+        let step = delta_time / CROUCH_TRANSITION_TIME;
+        self.crouch_amount += (target_crouch - self.crouch_amount).clamp(-step, step);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gait_speeds_blend_instead_of_snapping() {
+        let mut player = Player::new();
+        let walk = player.base_speed;
+
+        player.update_gait(Gait::Sprint, 1.0 / 60.0);
+        assert!(player.speed > walk);
+        assert!(player.speed < walk * SPRINT_SPEED_MULTIPLIER);
+
+        for _ in 0..120 {
+            player.update_gait(Gait::Sprint, 1.0 / 60.0);
+        }
+        assert!((player.speed - walk * SPRINT_SPEED_MULTIPLIER).abs() < 0.01);
+
+        // Going straight from a sprint to a crouch slows down steadily
+        let mut speeds = Vec::new();
+        for _ in 0..120 {
+            player.update_gait(Gait::Crouch, 1.0 / 60.0);
+            speeds.push(player.speed);
+        }
+        assert!(speeds.windows(2).all(|pair| pair[1] <= pair[0]));
+        assert!(speeds[0] > walk);
+        assert!((player.speed - walk * CROUCH_SPEED_MULTIPLIER).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_crouch_lowers_the_camera_over_the_transition() {
+        let mut player = Player::new();
+        // Looking straight north, the view matrix's translation row is the eye position
+        player.pitch = 0.0;
+        player.yaw = 0.0;
+        let standing = player.get_view_matrix();
+
+        player.update_gait(Gait::Crouch, CROUCH_TRANSITION_TIME / 2.0);
+        assert!(player.crouching);
+        assert!((player.crouch_amount - 0.5).abs() < 1e-5);
+
+        player.update_gait(Gait::Crouch, CROUCH_TRANSITION_TIME);
+        assert_eq!(player.crouch_amount, 1.0);
+        let crouched = player.get_view_matrix();
+        let standing_y = standing.0[3][1];
+        assert!((crouched.0[3][1] - (standing_y + CROUCH_CAMERA_DROP)).abs() < 1e-3);
+
+        player.update_gait(Gait::Walk, CROUCH_TRANSITION_TIME);
+        assert!(!player.crouching);
+        assert_eq!(player.crouch_amount, 0.0);
+    }
+
+    #[test]
+    fn test_crouch_walking_freezes_stamina() {
+        let mut player = Player::new();
+        player.update_gait(Gait::Crouch, 0.0);
+
+        // Moving while crouched neither drains nor regenerates, even well past the cooldown
+        let stamina = player.stamina;
+        player.update_stamina(false, true, 5.0);
+        assert_eq!(player.stamina, stamina);
+        assert_eq!(player.last_sprint_time, 0.0);
+
+        // Standing still in a crouch regenerates as usual
+        player.update_stamina(false, false, 1.0);
+        assert!(player.stamina > stamina);
+    }
+
+    const TICK: f32 = 1.0 / 60.0;
+    /// Move axis of the forward key held alone
+    const FORWARD: [f32; 2] = [0.0, 1.0];
+    /// Move axis of the forward and left keys held together
+    const FORWARD_LEFT: [f32; 2] = [-1.0, 1.0];
+
+    fn speed_of(player: &Player) -> f32 {
+        player.velocity[0].hypot(player.velocity[1])
+    }
+
+    /// Facing north, so forward is -Z and the velocity's second component tells direction.
+    fn north_facing_player() -> Player {
+        let mut player = Player::new();
+        player.yaw = 0.0;
+        player
+    }
+
+    #[test]
+    fn test_reaching_full_speed_takes_about_a_sixth_of_a_second() {
+        let mut player = north_facing_player();
+        let mut ticks_to_max = None;
+        for tick in 1..=60 {
+            player.accelerate(TICK, FORWARD);
+            let speed = speed_of(&player);
+            assert!(
+                speed <= player.speed + 1e-4,
+                "{} over {}",
+                speed,
+                player.speed
+            );
+            if ticks_to_max.is_none() && (player.speed - speed) < 1e-3 {
+                ticks_to_max = Some(tick);
+            }
+        }
+        // Not on the first frame, but within roughly 0.15 s
+        let seconds = ticks_to_max.expect("reached full speed") as f32 * TICK;
+        assert!((0.1..=0.2).contains(&seconds), "{}", seconds);
+        assert!(player.velocity[1] < 0.0);
+
+        // Lightfoot gets there sooner
+        let mut lightfoot = north_facing_player();
+        lightfoot.acceleration_multiplier = 1.5;
+        player.velocity = [0.0, 0.0];
+        for _ in 0..3 {
+            player.accelerate(TICK, FORWARD);
+            lightfoot.accelerate(TICK, FORWARD);
+        }
+        assert!(speed_of(&lightfoot) > speed_of(&player));
+    }
+
+    #[test]
+    fn test_releasing_input_stops_the_player_completely() {
+        let mut player = north_facing_player();
+        player.update_gait(Gait::Sprint, 1.0);
+        for _ in 0..60 {
+            player.accelerate(TICK, FORWARD_LEFT);
+        }
+        let top = speed_of(&player);
+        assert!(top > player.base_speed);
+
+        // Friction stops a sprint within top speed over the deceleration, plus a frame
+        let bound = (top / player.movement.deceleration / TICK).ceil() as usize + 1;
+        let mut speeds = Vec::new();
+        for _ in 0..bound {
+            player.accelerate(TICK, [0.0, 0.0]);
+            speeds.push(speed_of(&player));
+        }
+        assert_eq!(player.velocity, [0.0, 0.0]);
+        assert!(speeds.windows(2).all(|pair| pair[1] <= pair[0]));
+
+        // And nothing moves once stopped
+        let position = player.desired_position(TICK);
+        assert_eq!(position, player.position);
+    }
+
+    #[test]
+    fn test_reversing_blends_through_a_stop() {
+        let mut player = north_facing_player();
+        for _ in 0..60 {
+            player.accelerate(TICK, FORWARD);
+        }
+
+        // Holding backward takes several frames to turn the motion around
+        let mut ticks_to_turn = 0;
+        while player.velocity[1] < 0.0 {
+            player.accelerate(TICK, [0.0, -1.0]);
+            ticks_to_turn += 1;
+            assert!(ticks_to_turn < 60);
+        }
+        assert!(ticks_to_turn > 2, "{}", ticks_to_turn);
+
+        // The turn penalty makes it slower than accelerating from rest
+        let from_rest = (player.speed / player.movement.acceleration / TICK).ceil() as usize;
+        assert!(
+            ticks_to_turn > from_rest,
+            "{} vs {}",
+            ticks_to_turn,
+            from_rest
+        );
+    }
+
+    #[test]
+    fn test_max_speed_is_never_exceeded() {
+        let mut player = north_facing_player();
+        // Mixed input, gaits changing underneath, and the odd long frame
+        for tick in 0..600 {
+            let gait = match tick / 50 % 3 {
+                0 => Gait::Sprint,
+                1 => Gait::Walk,
+                _ => Gait::Crouch,
+            };
+            let delta_time = if tick % 97 == 0 { 0.5 } else { TICK };
+            player.update_gait(gait, delta_time);
+            player.yaw = (tick as f32 * 7.0) % 360.0;
+            // Analog input, including corners pushed past full deflection
+            let strafe = (tick % 13) as f32 / 6.0 - 1.0;
+            let forward = if tick % 5 != 0 { 1.0 } else { -0.5 };
+            player.accelerate(delta_time, [strafe, forward]);
+            assert!(speed_of(&player) <= player.speed + 1e-3);
+
+            let position = player.desired_position(delta_time);
+            let step = (position[0] - player.position[0]).hypot(position[2] - player.position[2]);
+            assert!(step <= player.speed * MAX_MOVEMENT_DELTA + 1e-3);
+        }
+    }
+
+    #[test]
+    fn test_stick_deflection_scales_walking_speed() {
+        let mut half = north_facing_player();
+        let mut full = north_facing_player();
+        for _ in 0..60 {
+            half.accelerate(TICK, [0.0, 0.5]);
+            full.accelerate(TICK, FORWARD);
+        }
+        assert!((speed_of(&full) - full.speed).abs() < 1e-3);
+        assert!((speed_of(&half) - half.speed / 2.0).abs() < 1e-3);
+        // Facing north, forward is toward -z
+        assert!(half.velocity[1] < 0.0 && half.velocity[0].abs() < 1e-4);
+
+        // Pushing right strafes toward +x
+        let mut strafing = north_facing_player();
+        strafing.accelerate(TICK, [1.0, 0.0]);
+        assert!(strafing.velocity[0] > 0.0 && strafing.velocity[1].abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_stick_look_turns_at_a_rate() {
+        let mut player = north_facing_player();
+        player.pitch = 0.0;
+        player.stick_look([1.0, 0.0], 180.0, 0.5);
+        assert!((player.yaw + 90.0).abs() < 1e-4);
+        player.stick_look([0.0, 0.5], 180.0, 0.5);
+        assert!((player.pitch - 45.0).abs() < 1e-4);
+        // Pitch stops short of straight up
+        player.stick_look([0.0, 1.0], 180.0, 10.0);
+        assert_eq!(player.pitch, 89.0);
+    }
 }
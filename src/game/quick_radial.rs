@@ -0,0 +1,434 @@
+//! Quick-settings radial menu for the Mirador game.
+//!
+//! Holding the quick-settings key (V) during play opens [`QuickRadial`]: a ring of slots around
+//! the center of the screen, each bound to a [`QuickAction`] toggle. Moving the mouse pushes a
+//! virtual pointer out from the center, and the slot it points at is highlighted; releasing
+//! the key activates that slot, or cancels when the pointer is still inside the
+//! [`DEAD_ZONE`]. On a gamepad the radial is held open with the left bumper and the right
+//! stick points at a slot directly (see [`QuickRadial::point_stick`]). While the radial is open the game runs at [`QUICK_RADIAL_TIME_SCALE`] times
+//! normal speed, and after a slot is activated a short confirmation line is shown for
+//! [`CONFIRMATION_SECS`].
+//!
+//! The slots come from a [`QuickRadialConfig`], so a new toggle only needs a [`QuickAction`]
+//! variant, its label, and the code that applies it; the selection math and the menu drawing
+//! work for any slot count from [`MIN_SLOTS`] to [`MAX_SLOTS`].
+//!
+//! # Usage
+//!
+//! ```rust,ignore
+//! // Key pressed during play
+//! game_state.quick_radial.open();
+//!
+//! // Mouse motion or the right stick while open
+//! game_state.quick_radial.move_pointer(dx, dy);
+//! game_state.quick_radial.point_stick(gamepad_input.look_axis);
+//!
+//! // Key released
+//! if let Some(action) = game_state.quick_radial.release() {
+//!     let confirmation = apply(action);
+//!     game_state.quick_radial.confirm(confirmation);
+//! }
+//! ```
+
+use std::f32::consts::TAU;
+
+/// How fast the game runs while the radial is open, as a fraction of normal speed.
+pub const QUICK_RADIAL_TIME_SCALE: f32 = 0.2;
+
+/// Distance in mouse units the pointer must leave the center before a slot is selected.
+///
+/// Releasing the key with the pointer inside the dead zone cancels.
+pub const DEAD_ZONE: f32 = 30.0;
+
+/// Farthest the pointer can be pushed from the center, in mouse units.
+///
+/// Keeping it close means turning back toward another slot never takes a long sweep.
+pub const POINTER_REACH: f32 = 100.0;
+
+/// Seconds the confirmation line stays up after a slot is activated.
+pub const CONFIRMATION_SECS: f32 = 1.5;
+
+/// Fewest slots the radial is laid out for.
+pub const MIN_SLOTS: usize = 4;
+
+/// Most slots the radial is laid out for; more would make each slot too narrow to aim at.
+pub const MAX_SLOTS: usize = 8;
+
+/// A toggle that can be bound to a radial slot.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QuickAction {
+    /// Hide or show the center-screen reticle
+    Reticle,
+    /// Point the compass at the exit or at north
+    CompassMode,
+    /// Hide or show the minimap
+    Minimap,
+    /// Keep crouch-walking without holding the crouch key
+    CrouchLock,
+    /// Mute or unmute the background music
+    MusicMute,
+    /// Save the next frame as a screenshot
+    Screenshot,
+    /// Mute or unmute the ambient soundscape
+    Ambience,
+    /// Switch between the starfield and the sky panorama
+    Background,
+    /// Turn click-to-move on or off
+    ClickToMove,
+    /// Turn reduced visual effects on or off
+    ReduceEffects,
+    /// Turn accessibility announcements on or off
+    Announcements,
+}
+
+impl QuickAction {
+    /// Returns the label shown in the action's slot.
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Reticle => "Reticle",
+            Self::CompassMode => "Compass",
+            Self::Minimap => "Minimap",
+            Self::CrouchLock => "Crouch lock",
+            Self::MusicMute => "Music",
+            Self::Screenshot => "Screenshot",
+            Self::Ambience => "Ambience",
+            Self::Background => "Background",
+            Self::ClickToMove => "Click to move",
+            Self::ReduceEffects => "Reduce effects",
+            Self::Announcements => "Announcements",
+        }
+    }
+}
+
+/// The actions laid out around the radial, clockwise from the top.
+#[derive(Debug, Clone, PartialEq)]
+pub struct QuickRadialConfig {
+    /// One action per slot, clockwise from straight up
+    pub slots: Vec<QuickAction>,
+}
+
+impl Default for QuickRadialConfig {
+    /// The toggles most often reached for mid-level, one in every slot the radial has room for.
+    fn default() -> Self {
+        Self {
+            slots: vec![
+                QuickAction::Reticle,
+                QuickAction::CompassMode,
+                QuickAction::Minimap,
+                QuickAction::CrouchLock,
+                QuickAction::MusicMute,
+                QuickAction::Screenshot,
+                QuickAction::Ambience,
+                QuickAction::ReduceEffects,
+            ],
+        }
+    }
+}
+
+/// Returns the slot a pointer selects.
+///
+/// Slot 0 is straight up and the rest follow clockwise, each owning an equal wedge centered
+/// on its direction.
+///
+/// # Arguments
+/// * `pointer` - Pointer offset from the center, with y growing downward as on screen
+/// * `slot_count` - Number of slots around the radial
+///
+/// # Returns
+/// The selected slot, or `None` inside the [`DEAD_ZONE`] or when there are no slots
+pub fn slot_at(pointer: [f32; 2], slot_count: usize) -> Option<usize> {
+    let [x, y] = pointer;
+    if slot_count == 0 || x.hypot(y) < DEAD_ZONE {
+        return None;
+    }
+    // Clockwise angle from straight up
+    let angle = x.atan2(-y).rem_euclid(TAU);
+    let wedge = TAU / slot_count as f32;
+    Some(((angle + wedge / 2.0) / wedge) as usize % slot_count)
+}
+
+/// Returns the direction of a slot from the center.
+///
+/// # Arguments
+/// * `index` - The slot, counted clockwise from straight up
+/// * `slot_count` - Number of slots around the radial
+///
+/// # Returns
+/// A unit vector with y growing downward as on screen
+pub fn slot_direction(index: usize, slot_count: usize) -> [f32; 2] {
+    let angle = index as f32 * TAU / slot_count.max(1) as f32;
+    [angle.sin(), -angle.cos()]
+}
+
+/// State of the quick-settings radial.
+#[derive(Debug, Clone)]
+pub struct QuickRadial {
+    /// The actions laid out around the radial
+    config: QuickRadialConfig,
+    /// Pointer offset from the center while the radial is open
+    pointer: Option<[f32; 2]>,
+    /// Confirmation line of the last activated slot, and the seconds it has left
+    confirmation: Option<(String, f32)>,
+}
+
+impl Default for QuickRadial {
+    fn default() -> Self {
+        Self::new(QuickRadialConfig::default())
+    }
+}
+
+impl QuickRadial {
+    /// Creates a closed radial.
+    ///
+    /// # Arguments
+    /// * `config` - The actions to lay out, between [`MIN_SLOTS`] and [`MAX_SLOTS`] of them
+    pub fn new(config: QuickRadialConfig) -> Self {
+        debug_assert!(
+            (MIN_SLOTS..=MAX_SLOTS).contains(&config.slots.len()),
+            "the radial needs {} to {} slots",
+            MIN_SLOTS,
+            MAX_SLOTS
+        );
+        Self {
+            config,
+            pointer: None,
+            confirmation: None,
+        }
+    }
+
+    /// Returns the actions laid out around the radial.
+    pub fn config(&self) -> &QuickRadialConfig {
+        &self.config
+    }
+
+    /// Returns whether the radial is open.
+    pub fn is_open(&self) -> bool {
+        self.pointer.is_some()
+    }
+
+    /// Opens the radial with the pointer at the center.
+    pub fn open(&mut self) {
+        self.pointer = Some([0.0, 0.0]);
+    }
+
+    /// Closes the radial without activating anything.
+    pub fn cancel(&mut self) {
+        self.pointer = None;
+    }
+
+    /// Moves the pointer, keeping it within [`POINTER_REACH`] of the center.
+    ///
+    /// Does nothing while the radial is closed.
+    ///
+    /// # Arguments
+    /// * `dx` - Horizontal mouse motion
+    /// * `dy` - Vertical mouse motion, positive downward
+    pub fn move_pointer(&mut self, dx: f32, dy: f32) {
+        let Some([x, y]) = self.pointer else {
+            return;
+        };
+        let (x, y) = (x + dx, y + dy);
+        let length = x.hypot(y);
+        let scale = if length > POINTER_REACH {
+            POINTER_REACH / length
+        } else {
+            1.0
+        };
+        self.pointer = Some([x * scale, y * scale]);
+    }
+
+    /// Points the pointer the way a stick is pushed, as far as the stick is pushed.
+    ///
+    /// Does nothing while the radial is closed or the stick is centered, so the slot stays
+    /// selected when the stick springs back before the button is let go.
+    ///
+    /// # Arguments
+    /// * `axis` - Stick deflection from `-1.0` to `1.0`, right and up positive
+    pub fn point_stick(&mut self, axis: [f32; 2]) {
+        if self.pointer.is_none() || axis == [0.0, 0.0] {
+            return;
+        }
+        let [x, y] = axis;
+        let scale = POINTER_REACH / x.hypot(y).max(1.0);
+        self.pointer = Some([x * scale, -y * scale]);
+    }
+
+    /// Returns the slot the pointer selects, if the radial is open and it selects one.
+    pub fn selected(&self) -> Option<usize> {
+        self.pointer
+            .and_then(|pointer| slot_at(pointer, self.config.slots.len()))
+    }
+
+    /// Closes the radial and returns the action of the selected slot.
+    ///
+    /// # Returns
+    /// The action to apply, or `None` when the pointer was in the dead zone
+    pub fn release(&mut self) -> Option<QuickAction> {
+        let action = self.selected().map(|index| self.config.slots[index]);
+        self.pointer = None;
+        action
+    }
+
+    /// Returns how fast the game should run, as a fraction of normal speed.
+    pub fn time_scale(&self) -> f32 {
+        if self.is_open() {
+            QUICK_RADIAL_TIME_SCALE
+        } else {
+            1.0
+        }
+    }
+
+    /// Shows a confirmation line for [`CONFIRMATION_SECS`].
+    ///
+    /// # Arguments
+    /// * `text` - What the activated slot did, such as "Crouch lock on"
+    pub fn confirm(&mut self, text: String) {
+        self.confirmation = Some((text, CONFIRMATION_SECS));
+    }
+
+    /// Counts down the confirmation line.
+    ///
+    /// # Arguments
+    /// * `delta_time` - Real seconds since the last frame, not slowed by the radial
+    pub fn update(&mut self, delta_time: f32) {
+        if let Some((_, remaining)) = &mut self.confirmation {
+            *remaining -= delta_time;
+        }
+        self.confirmation = self
+            .confirmation
+            .take()
+            .filter(|(_, remaining)| *remaining > 0.0);
+    }
+
+    /// Returns the confirmation line, while it is still showing.
+    pub fn confirmation(&self) -> Option<&str> {
+        self.confirmation.as_ref().map(|(text, _)| text.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pointer_angle_selects_slots_clockwise_from_the_top() {
+        assert_eq!(slot_at([0.0, -50.0], 4), Some(0));
+        assert_eq!(slot_at([50.0, 0.0], 4), Some(1));
+        assert_eq!(slot_at([0.0, 50.0], 4), Some(2));
+        assert_eq!(slot_at([-50.0, 0.0], 4), Some(3));
+
+        // Just either side of the top wedge's left edge
+        let edge = -TAU / 8.0;
+        let near = |angle: f32| [angle.sin() * 50.0, -angle.cos() * 50.0];
+        assert_eq!(slot_at(near(edge + 0.01), 4), Some(0));
+        assert_eq!(slot_at(near(edge - 0.01), 4), Some(3));
+    }
+
+    #[test]
+    fn test_dead_zone_cancels() {
+        assert_eq!(slot_at([0.0, 0.0], 6), None);
+        assert_eq!(slot_at([DEAD_ZONE * 0.7, -DEAD_ZONE * 0.7], 6), None);
+        assert_eq!(slot_at([0.0, -DEAD_ZONE], 6), Some(0));
+        assert_eq!(slot_at([0.0, -50.0], 0), None);
+    }
+
+    #[test]
+    fn test_pointing_at_a_slot_selects_it_for_every_slot_count() {
+        for count in MIN_SLOTS..=MAX_SLOTS {
+            for index in 0..count {
+                let [x, y] = slot_direction(index, count);
+                let pointer = [x * POINTER_REACH, y * POINTER_REACH];
+                assert_eq!(
+                    slot_at(pointer, count),
+                    Some(index),
+                    "{} of {}",
+                    index,
+                    count
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_pointer_stays_within_reach() {
+        let mut radial = QuickRadial::default();
+        radial.move_pointer(500.0, 0.0);
+        assert_eq!(radial.selected(), None);
+
+        radial.open();
+        radial.move_pointer(500.0, 0.0);
+        radial.move_pointer(500.0, 0.0);
+        let [x, y] = radial.pointer.unwrap();
+        assert!((x.hypot(y) - POINTER_REACH).abs() < 1e-3);
+
+        // Sweeping back across the center doesn't take the full distance travelled
+        radial.move_pointer(-2.0 * POINTER_REACH, 0.0);
+        assert_eq!(radial.pointer, Some([-POINTER_REACH, 0.0]));
+    }
+
+    #[test]
+    fn test_stick_points_at_slots() {
+        let mut radial = QuickRadial::default();
+        radial.point_stick([0.0, 1.0]);
+        assert!(!radial.is_open());
+
+        radial.open();
+        let count = radial.config().slots.len();
+        for index in 0..count {
+            let [x, y] = slot_direction(index, count);
+            radial.point_stick([x, -y]);
+            assert_eq!(radial.selected(), Some(index), "{} of {}", index, count);
+        }
+
+        // A nudge stays in the dead zone, and a stick springing back keeps the selection
+        radial.point_stick([0.1, 0.0]);
+        assert_eq!(radial.selected(), None);
+        radial.point_stick([0.0, 1.0]);
+        radial.point_stick([0.0, 0.0]);
+        assert_eq!(radial.release(), Some(radial.config().slots[0]));
+    }
+
+    #[test]
+    fn test_release_activates_the_selected_slot_and_closes() {
+        let mut radial = QuickRadial::default();
+        radial.open();
+        assert_eq!(radial.time_scale(), QUICK_RADIAL_TIME_SCALE);
+        radial.move_pointer(0.0, -80.0);
+        assert_eq!(radial.release(), Some(radial.config().slots[0]));
+        assert!(!radial.is_open());
+        assert_eq!(radial.time_scale(), 1.0);
+
+        radial.open();
+        radial.move_pointer(5.0, 5.0);
+        assert_eq!(radial.release(), None);
+    }
+
+    #[test]
+    fn test_confirmation_expires() {
+        let mut radial = QuickRadial::default();
+        radial.confirm("Crouch lock on".to_string());
+        radial.update(CONFIRMATION_SECS / 2.0);
+        assert_eq!(radial.confirmation(), Some("Crouch lock on"));
+        radial.update(CONFIRMATION_SECS);
+        assert_eq!(radial.confirmation(), None);
+    }
+
+    #[test]
+    fn test_default_config_fits_the_radial() {
+        let slots = QuickRadialConfig::default().slots;
+        assert!((MIN_SLOTS..=MAX_SLOTS).contains(&slots.len()));
+        for (i, action) in slots.iter().enumerate() {
+            assert!(!slots[i + 1..].contains(action), "{:?} repeats", action);
+        }
+        for action in [
+            QuickAction::CompassMode,
+            QuickAction::Minimap,
+            QuickAction::Reticle,
+            QuickAction::CrouchLock,
+            QuickAction::MusicMute,
+            QuickAction::Screenshot,
+        ] {
+            assert!(slots.contains(&action), "{:?} is missing", action);
+        }
+    }
+}
@@ -0,0 +1,255 @@
+//! Recording of the player's path through a level, replayed when the level ends.
+//!
+//! [`RunRecording`] samples the player's position every [`SAMPLE_INTERVAL`] seconds of play
+//! into a ring buffer of [`RECORDING_CAPACITY`] samples, so a very long level keeps its most
+//! recent stretch. When the level is escaped, [`ReplayPlayback`] walks the recording at
+//! [`PLAYBACK_SPEED`] times real time for the picture-in-picture replay, holds the last frame
+//! for [`LOOP_HOLD`] seconds and starts over.
+//!
+//! Positions are interpolated between samples, so the replay camera glides instead of stepping
+//! from sample to sample.
+//!
+//! # Usage
+//!
+//! ```rust,ignore
+//! // Once per gameplay frame
+//! recording.record(player.position, delta_time);
+//!
+//! // Once per frame on the summary screen
+//! playback.advance(delta_time, recording.duration());
+//! if let Some(position) = recording.position_at(playback.time()) {
+//!     let view_matrix = replay_view_matrix(position);
+//! }
+//! ```
+
+use crate::math::mat::Mat4;
+use std::collections::VecDeque;
+
+/// Seconds of play between recorded positions.
+pub const SAMPLE_INTERVAL: f32 = 0.1;
+
+/// Most positions kept; ten minutes of play at [`SAMPLE_INTERVAL`].
+pub const RECORDING_CAPACITY: usize = 6000;
+
+/// How many times faster than real time the replay plays.
+pub const PLAYBACK_SPEED: f32 = 4.0;
+
+/// Seconds the replay holds on the exit before looping.
+pub const LOOP_HOLD: f32 = 1.0;
+
+/// Height in world units the replay camera follows above the recorded eye.
+pub const REPLAY_CAMERA_HEIGHT: f32 = 300.0;
+
+/// The player's recent positions in the current level.
+#[derive(Debug, Clone, Default)]
+pub struct RunRecording {
+    /// Recorded positions, oldest first, [`SAMPLE_INTERVAL`] seconds apart
+    samples: VecDeque<[f32; 3]>,
+    /// Seconds of play since the last sample was taken
+    since_sample: f32,
+}
+
+impl RunRecording {
+    /// Drops every recorded position, ready for a new level.
+    pub fn clear(&mut self) {
+        self.samples.clear();
+        self.since_sample = 0.0;
+    }
+
+    /// Advances the recording by one gameplay frame.
+    ///
+    /// The first frame after [`RunRecording::clear`] is always recorded, then one position
+    /// every [`SAMPLE_INTERVAL`]; once the buffer is full the oldest position is dropped.
+    ///
+    /// # Arguments
+    /// * `position` - The player's position this frame
+    /// * `delta_time` - Seconds of play since the previous frame
+    pub fn record(&mut self, position: [f32; 3], delta_time: f32) {
+        self.since_sample += delta_time;
+        if !self.samples.is_empty() && self.since_sample < SAMPLE_INTERVAL {
+            return;
+        }
+        self.since_sample = (self.since_sample - SAMPLE_INTERVAL).max(0.0);
+        if self.samples.len() == RECORDING_CAPACITY {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(position);
+    }
+
+    /// Returns the number of recorded positions.
+    pub fn len(&self) -> usize {
+        self.samples.len()
+    }
+
+    /// Returns whether nothing has been recorded yet.
+    pub fn is_empty(&self) -> bool {
+        self.samples.is_empty()
+    }
+
+    /// Returns the seconds of play the recording covers.
+    pub fn duration(&self) -> f32 {
+        self.samples.len().saturating_sub(1) as f32 * SAMPLE_INTERVAL
+    }
+
+    /// Returns where the player was a given time into the recording.
+    ///
+    /// # Arguments
+    /// * `time` - Seconds since the first recorded position, clamped to the recording
+    ///
+    /// # Returns
+    /// The position interpolated between the two nearest samples, or `None` if nothing was
+    /// recorded
+    pub fn position_at(&self, time: f32) -> Option<[f32; 3]> {
+        let last = self.samples.len().checked_sub(1)?;
+        let step = (time / SAMPLE_INTERVAL).clamp(0.0, last as f32);
+        let index = (step.floor() as usize).min(last);
+        let from = self.samples[index];
+        let to = self.samples[(index + 1).min(last)];
+        let t = step - index as f32;
+        Some(std::array::from_fn(|axis| {
+            from[axis] + (to[axis] - from[axis]) * t
+        }))
+    }
+}
+
+/// Looping playback position through a [`RunRecording`].
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct ReplayPlayback {
+    /// Seconds into the recording
+    time: f32,
+    /// Seconds spent holding on the last frame before looping
+    held: f32,
+    /// Whether playback is stopped where it is
+    paused: bool,
+}
+
+impl ReplayPlayback {
+    /// Starts playback over from the beginning, unpaused.
+    pub fn restart(&mut self) {
+        *self = Self::default();
+    }
+
+    /// Pauses or resumes playback.
+    ///
+    /// # Arguments
+    /// * `paused` - Whether playback should stop where it is
+    pub fn set_paused(&mut self, paused: bool) {
+        self.paused = paused;
+    }
+
+    /// Returns whether playback is paused.
+    #[cfg(test)]
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    /// Returns how far into the recording playback is, in seconds of play.
+    pub fn time(&self) -> f32 {
+        self.time
+    }
+
+    /// Advances playback by one frame, looping after a hold on the last frame.
+    ///
+    /// # Arguments
+    /// * `delta_time` - Real seconds since the previous frame
+    /// * `duration` - Seconds of play the recording covers
+    pub fn advance(&mut self, delta_time: f32, duration: f32) {
+        if self.paused {
+            return;
+        }
+        if self.time < duration {
+            self.time = (self.time + delta_time * PLAYBACK_SPEED).min(duration);
+            return;
+        }
+        self.held += delta_time;
+        if self.held >= LOOP_HOLD {
+            self.time = 0.0;
+            self.held = 0.0;
+        }
+    }
+}
+
+/// Builds the replay camera, looking straight down from above a recorded position.
+///
+/// North stays up, so fast turns in the recording don't spin the picture.
+///
+/// # Arguments
+/// * `position` - The recorded eye position the camera follows
+///
+/// # Returns
+/// A view matrix in the same construction as `Player::get_view_matrix`
+pub fn replay_view_matrix(position: [f32; 3]) -> Mat4 {
+    let rotation_matrix = Mat4::rotation_x(-90.0);
+    let translation_matrix = Mat4::translation(
+        -position[0],
+        -(position[1] + REPLAY_CAMERA_HEIGHT),
+        -position[2],
+    );
+    translation_matrix.multiply(&rotation_matrix)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_recording_samples_at_the_interval_and_drops_the_oldest() {
+        let mut recording = RunRecording::default();
+        recording.record([0.0; 3], 0.0);
+        recording.record([1.0, 0.0, 0.0], SAMPLE_INTERVAL / 2.0);
+        assert_eq!(recording.len(), 1);
+        recording.record([2.0, 0.0, 0.0], SAMPLE_INTERVAL / 2.0);
+        assert_eq!(recording.len(), 2);
+        assert_eq!(
+            recording.position_at(SAMPLE_INTERVAL),
+            Some([2.0, 0.0, 0.0])
+        );
+
+        for step in 0..RECORDING_CAPACITY {
+            recording.record([step as f32 + 10.0, 0.0, 0.0], SAMPLE_INTERVAL);
+        }
+        assert_eq!(recording.len(), RECORDING_CAPACITY);
+        assert_eq!(recording.position_at(0.0), Some([10.0, 0.0, 0.0]));
+
+        recording.clear();
+        assert!(recording.is_empty());
+        assert_eq!(recording.position_at(0.0), None);
+    }
+
+    #[test]
+    fn test_positions_are_interpolated_and_clamped() {
+        let mut recording = RunRecording::default();
+        recording.record([0.0, 5.0, 0.0], 0.0);
+        recording.record([10.0, 5.0, -20.0], SAMPLE_INTERVAL);
+        assert!((recording.duration() - SAMPLE_INTERVAL).abs() < 1e-6);
+
+        let middle = recording.position_at(SAMPLE_INTERVAL / 2.0).unwrap();
+        assert!((middle[0] - 5.0).abs() < 1e-4);
+        assert!((middle[2] + 10.0).abs() < 1e-4);
+        assert_eq!(recording.position_at(-1.0), Some([0.0, 5.0, 0.0]));
+        assert_eq!(recording.position_at(99.0), Some([10.0, 5.0, -20.0]));
+    }
+
+    #[test]
+    fn test_playback_runs_fast_holds_and_loops() {
+        let mut playback = ReplayPlayback::default();
+        playback.advance(1.0, 10.0);
+        assert_eq!(playback.time(), PLAYBACK_SPEED);
+
+        playback.advance(10.0, 10.0);
+        assert_eq!(playback.time(), 10.0);
+        playback.advance(LOOP_HOLD / 2.0, 10.0);
+        assert_eq!(playback.time(), 10.0);
+        playback.advance(LOOP_HOLD / 2.0, 10.0);
+        assert_eq!(playback.time(), 0.0);
+
+        playback.advance(1.0, 10.0);
+        playback.set_paused(true);
+        playback.advance(1.0, 10.0);
+        assert!(playback.is_paused());
+        assert_eq!(playback.time(), PLAYBACK_SPEED);
+
+        playback.restart();
+        assert_eq!(playback, ReplayPlayback::default());
+    }
+}
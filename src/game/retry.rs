@@ -0,0 +1,134 @@
+//! Quick level retry for the Mirador game.
+//!
+//! When a level finishes loading, a [`LevelSnapshot`] records everything needed to replay it from
+//! the start: the generated maze grid and exit cell, the player and enemy exactly as they were
+//! spawned, and the run score before the level began. Retrying restores these instead of
+//! generating a new maze, so the player skips the loading animation entirely.
+//!
+//! Retrying forfeits anything earned during the attempt: the score goes back to the snapshot and
+//! per-level tracking (visited cells, closest enemy approach, combo) starts over. The snapshot is
+//! dropped as soon as a new level starts loading, so a retry can never bring back an old maze.
+//!
+//! # Usage
+//!
+//! ```rust,ignore
+//! // Once the level's maze, player, and enemy are in place
+//! game_state.cache_level(&maze_grid, exit_cell);
+//!
+//! // From the pause or game over screen
+//! if game_state.retry_level() {
+//!     // Back in the game at the entrance
+//! }
+//! ```
+
+use crate::game::enemy::Enemy;
+use crate::game::maze::generator::Cell;
+use crate::game::player::Player;
+
+/// Everything needed to restart the current level without regenerating it.
+#[derive(Debug, Clone)]
+pub struct LevelSnapshot {
+    /// 2D grid of the level's maze where `true` marks a wall
+    maze_grid: Vec<Vec<bool>>,
+    /// The maze cell the player must reach
+    exit_cell: Cell,
+    /// The player as spawned at the maze entrance
+    player: Player,
+    /// The enemy as spawned for this level
+    enemy: Enemy,
+    /// Run score before the level began
+    score: u32,
+}
+
+impl LevelSnapshot {
+    /// Records the freshly loaded level.
+    ///
+    /// # Arguments
+    /// * `maze_grid` - 2D grid of the maze where `true` marks a wall
+    /// * `exit_cell` - The maze's exit cell
+    /// * `player` - The player, already placed at the maze entrance
+    /// * `enemy` - The enemy, already placed at its spawn point
+    /// * `score` - Run score before the level began
+    pub fn capture(
+        maze_grid: &[Vec<bool>],
+        exit_cell: Cell,
+        player: &Player,
+        enemy: &Enemy,
+        score: u32,
+    ) -> Self {
+        Self {
+            maze_grid: maze_grid.to_vec(),
+            exit_cell,
+            player: player.clone(),
+            enemy: enemy.clone(),
+            score,
+        }
+    }
+
+    /// Returns the level's maze grid.
+    pub fn maze_grid(&self) -> &[Vec<bool>] {
+        &self.maze_grid
+    }
+
+    /// Returns the level's exit cell.
+    pub fn exit_cell(&self) -> Cell {
+        self.exit_cell
+    }
+
+    /// Returns the run score the level started with.
+    pub fn score(&self) -> u32 {
+        self.score
+    }
+
+    /// Returns a fresh copy of the player at the maze entrance.
+    pub fn spawn_player(&self) -> Player {
+        self.player.clone()
+    }
+
+    /// Returns a fresh copy of the enemy at its spawn point.
+    ///
+    /// The enemy starts locked; the game loop unlocks it once the timer runs.
+    pub fn spawn_enemy(&self) -> Enemy {
+        let mut enemy = self.enemy.clone();
+        enemy.pathfinder.locked = true;
+        enemy.pathfinder.reached_player = false;
+        enemy
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_spawns_are_unaffected_by_the_attempt() {
+        let maze_grid = vec![vec![true, true, true], vec![true, false, true]];
+        let player = Player {
+            position: [10.0, 30.0, 20.0],
+            stamina: 2.0,
+            ..Player::new()
+        };
+        let mut enemy = Enemy::new([200.0, 30.0, 200.0], 150.0);
+        enemy.pathfinder.locked = false;
+        let snapshot = LevelSnapshot::capture(&maze_grid, Cell::new(1, 1), &player, &enemy, 1200);
+
+        // Play out a bad attempt on a spawned copy
+        let mut attempt = snapshot.spawn_player();
+        attempt.position = [500.0, 30.0, 500.0];
+        attempt.stamina = 0.0;
+        assert_eq!(attempt.stamina, 0.0);
+
+        let retried = snapshot.spawn_player();
+        assert_eq!(retried.position, [10.0, 30.0, 20.0]);
+        assert_eq!(retried.stamina, 2.0);
+
+        let enemy = snapshot.spawn_enemy();
+        assert_eq!(enemy.pathfinder.position, [200.0, 30.0, 200.0]);
+        assert!(enemy.pathfinder.locked);
+        assert!(!enemy.pathfinder.reached_player);
+
+        assert_eq!(snapshot.maze_grid(), maze_grid.as_slice());
+        assert_eq!(snapshot.exit_cell(), Cell::new(1, 1));
+        assert_eq!(snapshot.score(), 1200);
+    }
+}
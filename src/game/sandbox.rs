@@ -0,0 +1,384 @@
+//! Practice sandbox for the Mirador game.
+//!
+//! The sandbox is reached from the title screen and generates mazes from [`SandboxSettings`]
+//! instead of the run's level progression: any size, either algorithm, any seed, with the enemy
+//! and fog switched on or off. There is no timer and no score, and nothing is written to disk.
+//! The one thing that leaves the sandbox is its seed, which can be handed to the title screen's
+//! seed field for the next regular run.
+//!
+//! The mouse looks around while exploring, so the settings panel is driven by the keyboard.
+//! Tab switches between [`SandboxFocus::Look`] and [`SandboxFocus::Ui`]; in UI mode the arrow
+//! keys move between the [`SandboxControl`]s and change the selected one.
+//!
+//! Entering the sandbox keeps a [`SavedRun`] of everything a regular run carries between
+//! levels, which is put back when the sandbox is left, so playing in the sandbox never changes
+//! the next run.
+//!
+//! # Usage
+//!
+//! ```rust,ignore
+//! let mut session = SandboxSession::new(SandboxSettings::default(), &game_state);
+//! session.select(1);
+//! session.adjust(1); // One cell taller
+//! session.saved_run.restore(&mut game_state);
+//! ```
+
+use crate::game::GameState;
+use crate::game::codex::MazeAlgorithm;
+use crate::game::director::DifficultyDirector;
+use crate::game::player::Player;
+use crate::renderer::tuning::RenderTuning;
+
+/// Smallest maze width or height the sandbox generates, in cells.
+pub const MIN_SANDBOX_SIZE: usize = 5;
+
+/// Largest maze width or height the sandbox generates, in cells.
+///
+/// The floor is the same size for every maze, so larger mazes have cells too narrow to walk.
+pub const MAX_SANDBOX_SIZE: usize = 30;
+
+/// Label shown in the HUD while playing in the sandbox.
+pub const SANDBOX_LABEL: &str = "Sandbox";
+
+/// Fog strength used while the sandbox's fog is on and the renderer's own fog is weaker.
+pub const SANDBOX_FOG_STRENGTH: f32 = 0.85;
+
+/// Parameters of the next sandbox maze.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SandboxSettings {
+    /// Maze width in cells
+    pub width: usize,
+    /// Maze height in cells
+    pub height: usize,
+    /// How the maze is carved
+    pub algorithm: MazeAlgorithm,
+    /// Seed for the maze layout and exit
+    pub seed: u64,
+    /// Whether the enemy hunts the player
+    pub enemy: bool,
+    /// Whether the renderer's fog is drawn
+    pub fog: bool,
+    /// Whether the loading screen plays the carving animation, or skips straight to the maze
+    pub animate: bool,
+}
+
+impl Default for SandboxSettings {
+    /// A regular-sized single-floor maze with a random seed, no enemy and no animation.
+    fn default() -> Self {
+        Self {
+            width: 25,
+            height: 25,
+            algorithm: MazeAlgorithm::Kruskal,
+            seed: rand::random(),
+            enemy: false,
+            fog: true,
+            animate: false,
+        }
+    }
+}
+
+impl SandboxSettings {
+    /// Applies the fog switch to the renderer's fog settings.
+    ///
+    /// # Arguments
+    /// * `tuning` - The renderer's current settings, left unchanged
+    ///
+    /// # Returns
+    /// A copy with the fog at least [`SANDBOX_FOG_STRENGTH`] while it is on, and none while
+    /// it is off
+    pub fn apply_to_tuning(&self, tuning: &RenderTuning) -> RenderTuning {
+        let fog_strength = if self.fog {
+            tuning.fog_strength.max(SANDBOX_FOG_STRENGTH)
+        } else {
+            0.0
+        };
+        RenderTuning {
+            fog_strength,
+            ..*tuning
+        }
+    }
+}
+
+/// One row of the sandbox panel, in the order the rows are shown.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SandboxControl {
+    /// Maze width stepper
+    Width,
+    /// Maze height stepper
+    Height,
+    /// Algorithm selector
+    Algorithm,
+    /// Seed stepper; activating it rolls a random seed
+    Seed,
+    /// Enemy switch
+    Enemy,
+    /// Fog switch
+    Fog,
+    /// Carving animation switch
+    Animate,
+    /// Regenerates the maze from the settings
+    Generate,
+    /// Hands the seed to the title screen for the next run
+    UseSeed,
+    /// Returns to the title screen
+    Leave,
+}
+
+impl SandboxControl {
+    /// Every control, in panel order.
+    pub const ALL: [SandboxControl; 10] = [
+        SandboxControl::Width,
+        SandboxControl::Height,
+        SandboxControl::Algorithm,
+        SandboxControl::Seed,
+        SandboxControl::Enemy,
+        SandboxControl::Fog,
+        SandboxControl::Animate,
+        SandboxControl::Generate,
+        SandboxControl::UseSeed,
+        SandboxControl::Leave,
+    ];
+}
+
+/// What the keyboard and mouse currently drive in the sandbox.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SandboxFocus {
+    /// The mouse looks around and the movement keys walk
+    Look,
+    /// The cursor is free and the keys navigate the settings panel
+    Ui,
+}
+
+/// Everything a regular run carries between levels, kept aside while the sandbox is open.
+#[derive(Debug, Clone)]
+pub struct SavedRun {
+    player: Player,
+    level: i32,
+    score: u32,
+    mode_label: Option<&'static str>,
+    director: DifficultyDirector,
+    run_seed: u64,
+}
+
+impl SavedRun {
+    /// Copies the run state out of the game state.
+    ///
+    /// # Arguments
+    /// * `game_state` - The game state the sandbox is about to take over
+    pub fn capture(game_state: &GameState) -> Self {
+        Self {
+            player: game_state.player.clone(),
+            level: game_state.game_ui.level,
+            score: game_state.game_ui.score,
+            mode_label: game_state.game_ui.mode_label,
+            director: game_state.director.clone(),
+            run_seed: game_state.run_seed,
+        }
+    }
+
+    /// Puts the run state back, undoing everything the sandbox changed.
+    ///
+    /// # Arguments
+    /// * `game_state` - The game state the sandbox is handing back
+    pub fn restore(&self, game_state: &mut GameState) {
+        game_state.player = self.player.clone();
+        game_state.game_ui.level = self.level;
+        game_state.game_ui.score = self.score;
+        game_state.game_ui.mode_label = self.mode_label;
+        game_state.director = self.director.clone();
+        game_state.run_seed = self.run_seed;
+    }
+}
+
+/// An open sandbox: its settings, the panel's keyboard state, and the run it set aside.
+#[derive(Debug, Clone)]
+pub struct SandboxSession {
+    /// Parameters the next Generate uses
+    pub settings: SandboxSettings,
+    /// Parameters the maze being explored was generated with
+    pub generated: SandboxSettings,
+    /// Whether the mouse looks around or drives the panel
+    pub focus: SandboxFocus,
+    /// Index into [`SandboxControl::ALL`] of the selected panel row
+    pub selected: usize,
+    /// The run state to restore when the sandbox is left
+    pub saved_run: SavedRun,
+}
+
+impl SandboxSession {
+    /// Opens a sandbox in look mode, setting aside the current run.
+    ///
+    /// # Arguments
+    /// * `settings` - Parameters of the first maze
+    /// * `game_state` - The game state whose run is set aside
+    pub fn new(settings: SandboxSettings, game_state: &GameState) -> Self {
+        Self {
+            settings,
+            generated: settings,
+            focus: SandboxFocus::Look,
+            selected: 0,
+            saved_run: SavedRun::capture(game_state),
+        }
+    }
+
+    /// Returns the selected panel row.
+    pub fn selected_control(&self) -> SandboxControl {
+        SandboxControl::ALL[self.selected % SandboxControl::ALL.len()]
+    }
+
+    /// Switches between look mode and UI mode.
+    ///
+    /// # Returns
+    /// The new focus
+    pub fn toggle_focus(&mut self) -> SandboxFocus {
+        self.focus = match self.focus {
+            SandboxFocus::Look => SandboxFocus::Ui,
+            SandboxFocus::Ui => SandboxFocus::Look,
+        };
+        self.focus
+    }
+
+    /// Moves the selection up or down the panel, wrapping at either end.
+    ///
+    /// # Arguments
+    /// * `step` - Rows to move; negative moves up
+    pub fn select(&mut self, step: isize) {
+        let len = SandboxControl::ALL.len() as isize;
+        self.selected = (self.selected as isize + step).rem_euclid(len) as usize;
+    }
+
+    /// Changes the selected control by one step.
+    ///
+    /// Sizes stay within [`MIN_SANDBOX_SIZE`] and [`MAX_SANDBOX_SIZE`], the seed wraps, the
+    /// algorithm cycles through [`MazeAlgorithm::ALL`], and switches flip whichever way they
+    /// are stepped. Buttons ignore it.
+    ///
+    /// # Arguments
+    /// * `step` - Direction to step in; negative steps down
+    pub fn adjust(&mut self, step: isize) {
+        let control = self.selected_control();
+        let settings = &mut self.settings;
+        match control {
+            SandboxControl::Width => settings.width = step_size(settings.width, step),
+            SandboxControl::Height => settings.height = step_size(settings.height, step),
+            SandboxControl::Algorithm => {
+                let all = MazeAlgorithm::ALL;
+                let index = all
+                    .iter()
+                    .position(|&a| a == settings.algorithm)
+                    .unwrap_or(0);
+                let next = (index as isize + step.signum()).rem_euclid(all.len() as isize);
+                settings.algorithm = all[next as usize];
+            }
+            SandboxControl::Seed => {
+                settings.seed = settings.seed.wrapping_add_signed(step as i64);
+            }
+            SandboxControl::Enemy => settings.enemy = !settings.enemy,
+            SandboxControl::Fog => settings.fog = !settings.fog,
+            SandboxControl::Animate => settings.animate = !settings.animate,
+            SandboxControl::Generate | SandboxControl::UseSeed | SandboxControl::Leave => {}
+        }
+    }
+}
+
+/// Steps a maze size and keeps it in the sandbox's range.
+fn step_size(size: usize, step: isize) -> usize {
+    size.saturating_add_signed(step)
+        .clamp(MIN_SANDBOX_SIZE, MAX_SANDBOX_SIZE)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn session() -> SandboxSession {
+        SandboxSession {
+            settings: SandboxSettings {
+                seed: 7,
+                ..SandboxSettings::default()
+            },
+            generated: SandboxSettings::default(),
+            focus: SandboxFocus::Look,
+            selected: 0,
+            saved_run: SavedRun {
+                player: Player::new(),
+                level: 4,
+                score: 1200,
+                mode_label: None,
+                director: DifficultyDirector::default(),
+                run_seed: 99,
+            },
+        }
+    }
+
+    #[test]
+    fn test_selection_wraps_around_the_panel() {
+        let mut session = session();
+        session.select(-1);
+        assert_eq!(session.selected_control(), SandboxControl::Leave);
+        session.select(1);
+        assert_eq!(session.selected_control(), SandboxControl::Width);
+        session.select(3);
+        assert_eq!(session.selected_control(), SandboxControl::Seed);
+    }
+
+    #[test]
+    fn test_adjust_clamps_sizes_and_wraps_the_seed() {
+        let mut session = session();
+        for _ in 0..100 {
+            session.adjust(1);
+        }
+        assert_eq!(session.settings.width, MAX_SANDBOX_SIZE);
+
+        session.select(1);
+        for _ in 0..100 {
+            session.adjust(-1);
+        }
+        assert_eq!(session.settings.height, MIN_SANDBOX_SIZE);
+
+        session.select(2);
+        session.adjust(-8);
+        assert_eq!(session.settings.seed, u64::MAX);
+
+        session.select(-1);
+        session.adjust(1);
+        assert_eq!(session.settings.algorithm, MazeAlgorithm::StackedKruskal);
+        session.adjust(-1);
+        assert_eq!(session.settings.algorithm, MazeAlgorithm::Kruskal);
+        session.adjust(-1);
+        assert_eq!(session.settings.algorithm, MazeAlgorithm::Divided);
+    }
+
+    #[test]
+    fn test_focus_toggles_and_buttons_ignore_adjust() {
+        let mut session = session();
+        assert_eq!(session.toggle_focus(), SandboxFocus::Ui);
+        assert_eq!(session.toggle_focus(), SandboxFocus::Look);
+
+        let before = session.settings;
+        session.select(7);
+        session.adjust(1);
+        session.select(1);
+        session.adjust(-1);
+        assert_eq!(session.settings, before);
+    }
+
+    #[test]
+    fn test_fog_switch_overrides_the_tuned_strength() {
+        let tuning = RenderTuning::default();
+        let mut settings = SandboxSettings::default();
+        assert_eq!(
+            settings.apply_to_tuning(&tuning).fog_strength,
+            SANDBOX_FOG_STRENGTH
+        );
+
+        settings.fog = false;
+        let foggy = RenderTuning {
+            fog_strength: 1.0,
+            ..tuning
+        };
+        let cleared = settings.apply_to_tuning(&foggy);
+        assert_eq!(cleared.fog_strength, 0.0);
+        assert_eq!(cleared.fog_end, foggy.fog_end);
+    }
+}
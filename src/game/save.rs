@@ -0,0 +1,526 @@
+//! Saved runs for the Mirador game.
+//!
+//! A regular run is saved whenever one of its levels finishes loading, so quitting at any point
+//! keeps the run at the start of the level being played. The [`SavedGame`] holds the level,
+//! the score the level started with, the upgrades owned, the timer the level runs, the run's
+//! seed and mode, the file the level's maze was written to, and the player as spawned at the
+//! entrance. Every level's maze comes from the run seed, so resuming generates the saved level
+//! again rather than reading the maze file, which is only kept as a record.
+//!
+//! Saves are written to a temporary file that then replaces the old one, so a crash partway
+//! through a save leaves the previous save intact. A file that can't be read, was made by a
+//! different save version, or holds values no run could reach is refused with a [`SaveError`]
+//! saying why.
+//!
+//! # Usage
+//!
+//! ```rust,ignore
+//! // Once a level has loaded
+//! save::save_state(&game_state, &upgrades, &paths::get().saved_game())?;
+//!
+//! // From the title screen's Continue button
+//! let saved = save::load_state(&paths::get().saved_game())?;
+//! saved.restore(&mut game_state, &mut upgrades)?;
+//! ```
+
+use crate::game::challenge::Challenge;
+use crate::game::levels::TimerPlan;
+use crate::game::player::Player;
+use crate::game::upgrades::AvailableUpgrade;
+use crate::game::{GameState, GameTimer, TimerConfig};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+/// Version written into every save; files with any other version are refused.
+pub const SAVE_VERSION: u32 = 1;
+
+/// Why a saved run couldn't be loaded or written.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SaveError {
+    /// There is no save file
+    Missing,
+    /// The file couldn't be read or written
+    Io(String),
+    /// The file isn't a save, or is damaged
+    Corrupt(String),
+    /// The file was written by a different save version
+    VersionMismatch {
+        /// The file's version, or `None` if it doesn't say
+        found: Option<u32>,
+    },
+    /// The file reads, but holds a run the game can't resume
+    Invalid(String),
+}
+
+impl fmt::Display for SaveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Missing => write!(f, "No saved run"),
+            Self::Io(message) => write!(f, "Saved run couldn't be accessed: {}", message),
+            Self::Corrupt(message) => write!(f, "Saved run is damaged: {}", message),
+            Self::VersionMismatch { found: Some(found) } => write!(
+                f,
+                "Saved run is from a different version of Mirador (save version {}, this game reads {})",
+                found, SAVE_VERSION
+            ),
+            Self::VersionMismatch { found: None } => write!(
+                f,
+                "Saved run has no version, so it can't be from this version of Mirador"
+            ),
+            Self::Invalid(message) => write!(f, "Saved run can't be resumed: {}", message),
+        }
+    }
+}
+
+impl std::error::Error for SaveError {}
+
+/// The player as spawned at the entrance of the saved level.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SpawnInfo {
+    /// Position at the entrance; the height includes Tall Boots
+    pub position: [f32; 3],
+    /// Horizontal facing in degrees
+    pub yaw: f32,
+    /// Vertical facing in degrees
+    pub pitch: f32,
+    /// Walking speed with upgrades and modifiers applied
+    pub base_speed: f32,
+    /// Stamina when full
+    pub max_stamina: f32,
+    /// Acceleration multiplier from Lightfoot
+    pub acceleration_multiplier: f32,
+}
+
+impl SpawnInfo {
+    /// Records how a player was spawned.
+    ///
+    /// # Arguments
+    /// * `player` - The player, standing at the entrance
+    pub fn of(player: &Player) -> Self {
+        Self {
+            position: player.position,
+            yaw: player.yaw,
+            pitch: player.pitch,
+            base_speed: player.base_speed,
+            max_stamina: player.max_stamina,
+            acceleration_multiplier: player.acceleration_multiplier,
+        }
+    }
+
+    /// Returns a fresh player spawned this way, with full stamina.
+    pub fn spawn(&self) -> Player {
+        let mut player = Player::new();
+        player.position = self.position;
+        player.yaw = self.yaw;
+        player.pitch = self.pitch;
+        player.base_speed = self.base_speed;
+        player.speed = self.base_speed;
+        player.max_stamina = self.max_stamina;
+        player.stamina = self.max_stamina;
+        player.acceleration_multiplier = self.acceleration_multiplier;
+        player
+    }
+}
+
+/// A regular run saved at the start of one of its levels.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SavedGame {
+    /// Save format version, [`SAVE_VERSION`] when written
+    pub version: u32,
+    /// Level the run resumes at
+    pub level: i32,
+    /// Run score before the level began
+    pub score: u32,
+    /// How many of each upgrade the player owns
+    pub upgrades: HashMap<AvailableUpgrade, u32>,
+    /// The level's timer, with Slow Time included
+    pub timer: TimerPlan,
+    /// Seed every level of the run is generated from
+    pub run_seed: u64,
+    /// File the level's maze was written to, if it was
+    pub maze_path: Option<PathBuf>,
+    /// Whether the run is hardcore
+    pub hardcore: bool,
+    /// Code of the challenge the run was started from, if any
+    pub challenge: Option<String>,
+    /// The player as spawned at the entrance
+    pub spawn: SpawnInfo,
+}
+
+impl SavedGame {
+    /// Records the run at the start of the level that just loaded.
+    ///
+    /// # Arguments
+    /// * `game_state` - The game, with the level loaded and the player at its entrance
+    /// * `upgrades` - How many of each upgrade the player owns
+    pub fn capture(game_state: &GameState, upgrades: &HashMap<AvailableUpgrade, u32>) -> Self {
+        let timer = match &game_state.game_ui.timer {
+            Some(timer) => timer_plan(&timer.config),
+            None => game_state.level_plan.timer,
+        };
+        Self {
+            version: SAVE_VERSION,
+            level: game_state.game_ui.level,
+            score: game_state
+                .level_snapshot
+                .as_ref()
+                .map_or(game_state.game_ui.score, |snapshot| snapshot.score()),
+            upgrades: upgrades.clone(),
+            timer,
+            run_seed: game_state.run_seed,
+            maze_path: game_state.maze_path.clone(),
+            hardcore: game_state.hardcore,
+            challenge: game_state.challenge.as_ref().map(Challenge::encode),
+            spawn: SpawnInfo::of(&game_state.player),
+        }
+    }
+
+    /// Checks that the run could have been played.
+    ///
+    /// # Returns
+    /// `Ok(())`, or [`SaveError::Invalid`] naming the first value that is out of range
+    pub fn validate(&self) -> Result<(), SaveError> {
+        if self.level < 1 {
+            return Err(SaveError::Invalid(format!(
+                "level {} is before the first",
+                self.level
+            )));
+        }
+        let timer = [
+            self.timer.duration_secs,
+            self.timer.warning_secs,
+            self.timer.critical_secs,
+        ];
+        if timer.iter().any(|secs| !secs.is_finite() || *secs < 0.0) || timer[0] <= 0.0 {
+            return Err(SaveError::Invalid(
+                "the timer has no time on it".to_string(),
+            ));
+        }
+        let spawn = &self.spawn;
+        let stats = [
+            spawn.yaw,
+            spawn.pitch,
+            spawn.base_speed,
+            spawn.max_stamina,
+            spawn.acceleration_multiplier,
+        ];
+        if spawn
+            .position
+            .iter()
+            .chain(&stats)
+            .any(|value| !value.is_finite())
+            || spawn.base_speed <= 0.0
+            || spawn.max_stamina <= 0.0
+            || spawn.acceleration_multiplier <= 0.0
+        {
+            return Err(SaveError::Invalid(
+                "the player's stats are out of range".to_string(),
+            ));
+        }
+        self.challenge()?;
+        Ok(())
+    }
+
+    /// Returns the challenge the run was started from.
+    ///
+    /// # Returns
+    /// The decoded challenge, `None` for a run without one, or [`SaveError::Invalid`] if
+    /// its code can't be read
+    pub fn challenge(&self) -> Result<Option<Challenge>, SaveError> {
+        self.challenge
+            .as_deref()
+            .map(Challenge::decode)
+            .transpose()
+            .map_err(|e| SaveError::Invalid(e.to_string()))
+    }
+
+    /// Puts the game back at the start of the saved level.
+    ///
+    /// Sets the level, score, timer, run seed and mode, and spawns the player with the saved
+    /// stats. Generating the level's maze is left to the caller.
+    ///
+    /// # Arguments
+    /// * `game_state` - The game to resume, fresh from the title screen
+    /// * `upgrades` - The upgrade counts to replace with the saved ones
+    ///
+    /// # Returns
+    /// `Ok(())`, or [`SaveError::Invalid`] if the run can't be resumed, leaving both untouched
+    pub fn restore(
+        &self,
+        game_state: &mut GameState,
+        upgrades: &mut HashMap<AvailableUpgrade, u32>,
+    ) -> Result<(), SaveError> {
+        self.validate()?;
+        game_state.challenge = self.challenge()?;
+        game_state.hardcore = self.hardcore;
+        game_state.run_seed = self.run_seed;
+        game_state.maze_path = self.maze_path.clone();
+        game_state.player = self.spawn.spawn();
+        game_state.set_level(self.level);
+        game_state.set_score(self.score);
+        game_state.game_ui.timer = Some(GameTimer::new(self.timer.config()));
+        *upgrades = self.upgrades.clone();
+        Ok(())
+    }
+
+    /// Writes the save, replacing an old one only once the new one is complete.
+    ///
+    /// # Arguments
+    /// * `path` - The save file to write
+    pub fn write_to(&self, path: &Path) -> Result<(), SaveError> {
+        let contents = serde_json::to_string_pretty(self)
+            .map_err(|e| SaveError::Io(format!("Failed to serialize saved run: {}", e)))?;
+        write_atomically(path, contents.as_bytes())
+            .map_err(|e| SaveError::Io(format!("{}: {}", path.display(), e)))
+    }
+}
+
+/// Converts a running timer's configuration back into a plan.
+///
+/// # Arguments
+/// * `config` - The timer's configuration
+fn timer_plan(config: &TimerConfig) -> TimerPlan {
+    TimerPlan {
+        duration_secs: config.duration.as_secs_f32(),
+        warning_secs: config.warning_threshold.as_secs_f32(),
+        critical_secs: config.critical_threshold.as_secs_f32(),
+    }
+}
+
+/// Writes a file through a temporary file next to it, so readers only ever see a whole file.
+///
+/// # Arguments
+/// * `path` - The file to write
+/// * `contents` - Everything the file should hold
+pub fn write_atomically(path: &Path, contents: &[u8]) -> io::Result<()> {
+    let mut temporary = path.as_os_str().to_owned();
+    temporary.push(".tmp");
+    let temporary = PathBuf::from(temporary);
+
+    let result = File::create(&temporary)
+        .and_then(|mut file| {
+            file.write_all(contents)?;
+            file.sync_all()
+        })
+        .and_then(|()| fs::rename(&temporary, path));
+    if result.is_err() {
+        let _ = fs::remove_file(&temporary);
+    }
+    result
+}
+
+/// Saves the run at the start of the level that just loaded.
+///
+/// The upgrades are kept by the upgrade menu rather than the game state, so they are passed
+/// in alongside it.
+///
+/// # Arguments
+/// * `game_state` - The game, with the level loaded and the player at its entrance
+/// * `upgrades` - How many of each upgrade the player owns
+/// * `path` - The save file to write
+///
+/// # Returns
+/// The run that was saved, or an error if the file couldn't be written
+pub fn save_state(
+    game_state: &GameState,
+    upgrades: &HashMap<AvailableUpgrade, u32>,
+    path: &Path,
+) -> Result<SavedGame, SaveError> {
+    let saved = SavedGame::capture(game_state, upgrades);
+    saved.write_to(path)?;
+    Ok(saved)
+}
+
+/// Loads a saved run.
+///
+/// The version is checked before the rest of the file, so a save from another version is
+/// reported as such rather than as damaged.
+///
+/// # Arguments
+/// * `path` - The save file to read
+///
+/// # Returns
+/// The saved run, or why it can't be resumed
+pub fn load_state(path: &Path) -> Result<SavedGame, SaveError> {
+    let contents = fs::read_to_string(path).map_err(|e| match e.kind() {
+        io::ErrorKind::NotFound => SaveError::Missing,
+        _ => SaveError::Io(format!("{}: {}", path.display(), e)),
+    })?;
+
+    let value: serde_json::Value =
+        serde_json::from_str(&contents).map_err(|e| SaveError::Corrupt(e.to_string()))?;
+    let found = value
+        .get("version")
+        .and_then(serde_json::Value::as_u64)
+        .map(|version| version.min(u32::MAX as u64) as u32);
+    if found != Some(SAVE_VERSION) {
+        return Err(SaveError::VersionMismatch { found });
+    }
+
+    let saved: SavedGame =
+        serde_json::from_value(value).map_err(|e| SaveError::Corrupt(e.to_string()))?;
+    saved.validate()?;
+    Ok(saved)
+}
+
+/// Deletes a saved run, once the run it holds can no longer be continued.
+///
+/// # Arguments
+/// * `path` - The save file to delete
+///
+/// # Returns
+/// `Ok(())` if the file is gone, including when there was none
+pub fn delete_state(path: &Path) -> Result<(), SaveError> {
+    match fs::remove_file(path) {
+        Err(e) if e.kind() != io::ErrorKind::NotFound => {
+            Err(SaveError::Io(format!("{}: {}", path.display(), e)))
+        }
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::challenge::ChallengeDifficulty;
+
+    fn scratch_file(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join("mirador_save_tests");
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(name);
+        let _ = fs::remove_file(&path);
+        path
+    }
+
+    fn run_in_progress() -> (GameState, HashMap<AvailableUpgrade, u32>) {
+        let mut game_state = GameState::builder()
+            .without_audio()
+            .with_seed(0xfeed)
+            .build()
+            .unwrap();
+        game_state.begin_run_seed();
+        game_state.hardcore = true;
+        game_state.challenge = Some(Challenge {
+            difficulty: ChallengeDifficulty::Hard,
+            level_cap: Some(8),
+            ..Challenge::for_run(game_state.run_seed, true)
+        });
+        game_state.set_level(4);
+        game_state.set_score(2750);
+        game_state.game_ui.timer = Some(GameTimer::new(
+            TimerPlan {
+                duration_secs: 95.0,
+                warning_secs: 20.0,
+                critical_secs: 10.0,
+            }
+            .config(),
+        ));
+        game_state.maze_path = Some(PathBuf::from("mazes/level4.txt"));
+        game_state.player.position = [12.0, 56.0, -40.0];
+        game_state.player.base_speed = 121.0;
+        game_state.player.max_stamina = 2.2;
+
+        let upgrades = HashMap::from([(AvailableUpgrade::SpeedUp, 2), (AvailableUpgrade::Slim, 1)]);
+        (game_state, upgrades)
+    }
+
+    #[test]
+    fn test_saved_run_resumes_where_it_was_saved() {
+        let path = scratch_file("round_trip.json");
+        let (game_state, upgrades) = run_in_progress();
+        let saved = save_state(&game_state, &upgrades, &path).unwrap();
+        let loaded = load_state(&path).unwrap();
+        assert_eq!(loaded, saved);
+
+        let mut resumed = GameState::builder().without_audio().build().unwrap();
+        let mut resumed_upgrades = HashMap::new();
+        loaded.restore(&mut resumed, &mut resumed_upgrades).unwrap();
+
+        assert_eq!(resumed.game_ui.level, 4);
+        assert_eq!(resumed.game_ui.score, 2750);
+        assert_eq!(resumed.run_seed, game_state.run_seed);
+        assert_eq!(resumed.level_seed(4), game_state.level_seed(4));
+        assert!(resumed.hardcore);
+        assert_eq!(resumed.challenge, game_state.challenge);
+        assert_eq!(resumed.maze_path, game_state.maze_path);
+        assert_eq!(resumed_upgrades, upgrades);
+        let timer = resumed.game_ui.timer.as_ref().unwrap();
+        assert_eq!(timer.config.duration.as_secs_f32(), 95.0);
+        assert_eq!(resumed.player.position, [12.0, 56.0, -40.0]);
+        assert_eq!(resumed.player.base_speed, 121.0);
+        assert_eq!(resumed.player.stamina, 2.2);
+
+        let _ = fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_bad_files_are_refused_with_a_reason() {
+        let path = scratch_file("refused.json");
+        assert_eq!(load_state(&path), Err(SaveError::Missing));
+
+        fs::write(&path, "{ \"version\": 1, \"level\": ").unwrap();
+        assert!(matches!(load_state(&path), Err(SaveError::Corrupt(_))));
+
+        fs::write(&path, "{ \"version\": 1, \"level\": 3 }").unwrap();
+        assert!(matches!(load_state(&path), Err(SaveError::Corrupt(_))));
+
+        let (game_state, upgrades) = run_in_progress();
+        let mut saved = SavedGame::capture(&game_state, &upgrades);
+        saved.version = SAVE_VERSION + 1;
+        fs::write(&path, serde_json::to_string(&saved).unwrap()).unwrap();
+        let error = load_state(&path).unwrap_err();
+        assert_eq!(
+            error,
+            SaveError::VersionMismatch {
+                found: Some(SAVE_VERSION + 1)
+            }
+        );
+        assert!(error.to_string().contains("different version"));
+
+        fs::write(&path, "{ \"level\": 3 }").unwrap();
+        assert_eq!(
+            load_state(&path),
+            Err(SaveError::VersionMismatch { found: None })
+        );
+
+        saved.version = SAVE_VERSION;
+        saved.level = 0;
+        fs::write(&path, serde_json::to_string(&saved).unwrap()).unwrap();
+        assert!(matches!(load_state(&path), Err(SaveError::Invalid(_))));
+
+        saved.level = 4;
+        saved.challenge = Some("not a code".to_string());
+        fs::write(&path, serde_json::to_string(&saved).unwrap()).unwrap();
+        assert!(matches!(load_state(&path), Err(SaveError::Invalid(_))));
+
+        let _ = fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_failed_save_keeps_the_previous_one() {
+        let path = scratch_file("atomic.json");
+        let (mut game_state, upgrades) = run_in_progress();
+        let first = save_state(&game_state, &upgrades, &path).unwrap();
+
+        // A directory where the temporary file goes makes the next save fail partway
+        let mut temporary = path.as_os_str().to_owned();
+        temporary.push(".tmp");
+        fs::create_dir_all(&temporary).unwrap();
+        game_state.set_level(5);
+        assert!(save_state(&game_state, &upgrades, &path).is_err());
+        assert_eq!(load_state(&path), Ok(first));
+
+        fs::remove_dir(&temporary).unwrap();
+        let second = save_state(&game_state, &upgrades, &path).unwrap();
+        assert_eq!(load_state(&path).map(|saved| saved.level), Ok(5));
+        assert_eq!(second.level, 5);
+
+        assert_eq!(delete_state(&path), Ok(()));
+        assert_eq!(delete_state(&path), Ok(()));
+        assert_eq!(load_state(&path), Err(SaveError::Missing));
+    }
+}
@@ -0,0 +1,513 @@
+//! Level scoring pipeline for the Mirador game.
+//!
+//! This module is the single source of truth for how a completed level is scored.
+//! Every bonus the player can earn is computed in one place by [`ScoreCalculator`],
+//! which turns a [`LevelResult`] snapshot into an itemized [`ScoreBreakdown`].
+//!
+//! # Key Features
+//!
+//! - **Itemized results**: Each bonus is reported separately for the level summary UI
+//! - **Exploration tracking**: [`VisitedCells`] records which maze cells the player walked through
+//! - **Serializable output**: Breakdowns derive `serde` traits for the stats/persistence layer
+//! - **Pinned formula**: Unit tests lock the formula so balance changes are deliberate diffs
+//! - **Readable points**: [`group_digits`] splits large scores into thousands for display
+//!
+//! # Usage
+//!
+//! ```rust,ignore
+//! use crate::game::score::{LevelResult, ScoreCalculator};
+//!
+//! let result = LevelResult {
+//!     level: 2,
+//!     completion_time: 18.0,
+//!     time_remaining: 12.0,
+//!     cells_visited: 40,
+//!     total_cells: 120,
+//!     closest_enemy_distance: 250.0,
+//!     keys_collected: 0,
+//!     difficulty_multiplier: 1.0,
+//!     combo_multiplier: game_state.combo.multiplier(),
+//!     solution_length: 36,
+//!     close_calls: 1,
+//!     duplicate_anomalies: 0,
+//! };
+//!
+//! let breakdown = ScoreCalculator::default().calculate(&result);
+//! game_state.set_score(game_state.game_ui.score + breakdown.total);
+//! ```
+
+use crate::game::anomaly::DUPLICATE_BONUS;
+use crate::game::maze::generator::Cell;
+use serde::{Deserialize, Serialize};
+
+/// Separator placed between groups of three digits in displayed points.
+///
+/// The game has no localization layer, so every locale gets thousands commas.
+pub const DIGIT_GROUP_SEPARATOR: char = ',';
+
+/// Formats a number with its digits grouped in thousands, e.g. `1234567` as `"1,234,567"`.
+///
+/// # Arguments
+///
+/// * `value` - The number to format
+///
+/// # Returns
+///
+/// The number's digits with [`DIGIT_GROUP_SEPARATOR`] between each group of three.
+pub fn group_digits(value: u64) -> String {
+    let digits = value.to_string();
+    let mut grouped = String::with_capacity(digits.len() + digits.len() / 3);
+    for (i, digit) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            grouped.push(DIGIT_GROUP_SEPARATOR);
+        }
+        grouped.push(digit);
+    }
+    grouped
+}
+
+/// Snapshot of everything that happened during a single level that affects scoring.
+///
+/// A `LevelResult` is captured at the moment the player reaches the exit and is
+/// handed to [`ScoreCalculator::calculate`] to produce the level's score.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LevelResult {
+    /// The level that was completed (1-based)
+    pub level: i32,
+    /// Seconds it took the player to reach the exit
+    pub completion_time: f32,
+    /// Seconds left on the level timer when the exit was reached
+    pub time_remaining: f32,
+    /// Number of distinct walkable cells the player passed through
+    pub cells_visited: usize,
+    /// Total number of walkable cells in the maze
+    pub total_cells: usize,
+    /// Closest distance (in world units) the enemy got to the player during the level
+    pub closest_enemy_distance: f32,
+    /// Number of keys picked up during the level
+    pub keys_collected: u32,
+    /// Combined multiplier from active modifiers and difficulty settings (1.0 = none)
+    pub difficulty_multiplier: f32,
+    /// Movement combo multiplier held when the exit was reached (1.0 = no combo)
+    pub combo_multiplier: f32,
+    /// Number of grid steps on the shortest path from the entrance to the exit (0 = unknown)
+    #[serde(default)]
+    pub solution_length: usize,
+    /// Number of times the enemy came within close-call range of the player
+    #[serde(default)]
+    pub close_calls: u32,
+    /// Number of anomalies taken whose cosmetic was already unlocked
+    #[serde(default)]
+    pub duplicate_anomalies: u32,
+}
+
+/// Itemized score for a completed level.
+///
+/// Produced by [`ScoreCalculator::calculate`] and consumed both by the level
+/// summary display and by the code that adds the level's points to the run score.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct ScoreBreakdown {
+    /// Points awarded for completing the level at all (scales with level)
+    pub base: u32,
+    /// Bonus for reaching the exit quickly
+    pub time_bonus: u32,
+    /// Flat bonus for reaching higher levels
+    pub level_bonus: u32,
+    /// Bonus for sustaining a fast pace
+    pub streak_bonus: u32,
+    /// Bonus proportional to the fraction of the maze explored
+    pub exploration_bonus: u32,
+    /// Bonus for never letting the enemy get within the danger radius
+    pub untouchable_bonus: u32,
+    /// Bonus for keys collected during the level
+    pub key_bonus: u32,
+    /// Bonus for anomalies whose cosmetic was already unlocked
+    #[serde(default)]
+    pub anomaly_bonus: u32,
+    /// Difficulty multiplier applied to the sum of all components above
+    pub multiplier: f32,
+    /// Movement combo multiplier applied on top of `multiplier`
+    pub combo_multiplier: f32,
+    /// Final number of points awarded for the level
+    pub total: u32,
+}
+
+impl ScoreBreakdown {
+    /// Sum of all itemized components before the multiplier is applied.
+    ///
+    /// # Returns
+    ///
+    /// The unmultiplied subtotal of the breakdown.
+    pub fn subtotal(&self) -> u32 {
+        self.base
+            + self.time_bonus
+            + self.level_bonus
+            + self.streak_bonus
+            + self.exploration_bonus
+            + self.untouchable_bonus
+            + self.key_bonus
+            + self.anomaly_bonus
+    }
+
+    /// Formats the breakdown as multi-line text for the level summary display.
+    ///
+    /// Components that awarded no points are omitted to keep the summary short.
+    ///
+    /// # Returns
+    ///
+    /// A newline-separated list of labelled score components ending in the total.
+    pub fn summary_lines(&self) -> String {
+        let mut lines = vec![format!("Level Clear: +{}", group_digits(self.base.into()))];
+        let items = [
+            ("Time Bonus", self.time_bonus),
+            ("Level Bonus", self.level_bonus),
+            ("Streak Bonus", self.streak_bonus),
+            ("Exploration", self.exploration_bonus),
+            ("Untouchable", self.untouchable_bonus),
+            ("Keys", self.key_bonus),
+            ("Anomaly", self.anomaly_bonus),
+        ];
+        for (label, points) in items {
+            if points > 0 {
+                lines.push(format!("{}: +{}", label, group_digits(points.into())));
+            }
+        }
+        if (self.multiplier - 1.0).abs() > f32::EPSILON {
+            lines.push(format!("Multiplier: x{:.2}", self.multiplier));
+        }
+        if self.combo_multiplier > 1.0 {
+            lines.push(format!("Combo: x{:.1}", self.combo_multiplier));
+        }
+        lines.push(format!("Total: +{}", group_digits(self.total.into())));
+        lines.join("\n")
+    }
+}
+
+/// Computes itemized level scores from [`LevelResult`] snapshots.
+///
+/// All tunable scoring constants live on this struct so the formula stays in
+/// one place. Use [`ScoreCalculator::default`] for the game's standard balance.
+#[derive(Debug, Clone)]
+pub struct ScoreCalculator {
+    /// Base points awarded per level number
+    pub base_per_level: u32,
+    /// Distance in world units the enemy must stay beyond for the untouchable bonus
+    pub untouchable_radius: f32,
+    /// Untouchable bonus awarded per level number
+    pub untouchable_per_level: u32,
+    /// Fraction of the base score awarded for exploring the entire maze
+    pub exploration_weight: f32,
+    /// Points awarded per collected key
+    pub points_per_key: u32,
+    /// Points awarded per anomaly whose cosmetic was already unlocked
+    pub points_per_duplicate_anomaly: u32,
+}
+
+impl Default for ScoreCalculator {
+    fn default() -> Self {
+        Self {
+            base_per_level: 150,
+            untouchable_radius: 100.0,
+            untouchable_per_level: 50,
+            exploration_weight: 0.5,
+            points_per_key: 100,
+            points_per_duplicate_anomaly: DUPLICATE_BONUS,
+        }
+    }
+}
+
+impl ScoreCalculator {
+    /// Scores a completed level.
+    ///
+    /// # Scoring Components
+    ///
+    /// - **Base**: `base_per_level` points per level
+    /// - **Time bonus**: Multiplier on the base score based on completion time
+    ///   - Exceptional (≤15s): 3x-5x
+    ///   - Good (≤25s): 1.5x-3x
+    ///   - Average (≤35s): 0.5x-1.5x
+    ///   - Slow (>35s): 0.1x-0.5x
+    /// - **Level bonus**: 50 points per level above 5
+    /// - **Streak bonus**: 25 points per level when completed in 20s or less
+    /// - **Exploration bonus**: Fraction of the maze visited times `exploration_weight` of the base
+    /// - **Untouchable bonus**: `untouchable_per_level` per level if the enemy never came within `untouchable_radius`
+    /// - **Key bonus**: `points_per_key` per key collected
+    /// - **Anomaly bonus**: `points_per_duplicate_anomaly` per anomaly whose cosmetic was
+    ///   already unlocked
+    ///
+    /// The subtotal is multiplied by the result's `difficulty_multiplier` and
+    /// `combo_multiplier` and rounded.
+    ///
+    /// # Arguments
+    ///
+    /// * `result` - The level snapshot to score
+    ///
+    /// # Returns
+    ///
+    /// The itemized [`ScoreBreakdown`] for the level.
+    pub fn calculate(&self, result: &LevelResult) -> ScoreBreakdown {
+        let level = result.level.max(1) as u32;
+        let completion_time = result.completion_time.max(0.0);
+
+        let base = self.base_per_level * level;
+
+        let time_multiplier = if completion_time <= 15.0 {
+            3.0 + ((15.0 - completion_time) / 15.0) * 2.0
+        } else if completion_time <= 25.0 {
+            1.5 + ((25.0 - completion_time) / 10.0) * 1.5
+        } else if completion_time <= 35.0 {
+            0.5 + ((35.0 - completion_time) / 10.0) * 1.0
+        } else {
+            0.1 + ((45.0 - completion_time).max(0.0) / 10.0) * 0.4
+        };
+        let time_bonus = (base as f32 * time_multiplier) as u32;
+
+        let level_bonus = level.saturating_sub(5) * 50;
+
+        let streak_bonus = if completion_time <= 20.0 {
+            level * 25
+        } else {
+            0
+        };
+
+        let explored_ratio = if result.total_cells > 0 {
+            (result.cells_visited as f32 / result.total_cells as f32).clamp(0.0, 1.0)
+        } else {
+            0.0
+        };
+        let exploration_bonus = (base as f32 * self.exploration_weight * explored_ratio) as u32;
+
+        let untouchable_bonus = if result.closest_enemy_distance >= self.untouchable_radius {
+            self.untouchable_per_level * level
+        } else {
+            0
+        };
+
+        let key_bonus = result.keys_collected * self.points_per_key;
+
+        let anomaly_bonus = result.duplicate_anomalies * self.points_per_duplicate_anomaly;
+
+        let multiplier = result.difficulty_multiplier.max(0.0);
+        let combo_multiplier = result.combo_multiplier.max(1.0);
+
+        let mut breakdown = ScoreBreakdown {
+            base,
+            time_bonus,
+            level_bonus,
+            streak_bonus,
+            exploration_bonus,
+            untouchable_bonus,
+            key_bonus,
+            anomaly_bonus,
+            multiplier,
+            combo_multiplier,
+            total: 0,
+        };
+        breakdown.total =
+            (breakdown.subtotal() as f32 * multiplier * combo_multiplier).round() as u32;
+        breakdown
+    }
+}
+
+/// Lightweight bitset recording which maze grid cells the player has visited.
+///
+/// The grid matches the wall grid used for rendering and collision, where
+/// `true` cells are walls. Only walkable cells count toward exploration.
+#[derive(Debug, Clone, Default)]
+pub struct VisitedCells {
+    width: usize,
+    height: usize,
+    bits: Vec<u64>,
+    visited: usize,
+    walkable: usize,
+}
+
+impl VisitedCells {
+    /// Creates an empty visited set sized for the given maze grid.
+    ///
+    /// # Arguments
+    ///
+    /// * `maze_grid` - 2D grid of the maze where `true` marks a wall
+    ///
+    /// # Returns
+    ///
+    /// A `VisitedCells` with no cells marked.
+    pub fn for_grid(maze_grid: &[Vec<bool>]) -> Self {
+        let height = maze_grid.len();
+        let width = maze_grid.first().map_or(0, |row| row.len());
+        let walkable = maze_grid.iter().flatten().filter(|&&wall| !wall).count();
+        Self {
+            width,
+            height,
+            bits: vec![0; (width * height).div_ceil(64)],
+            visited: 0,
+            walkable,
+        }
+    }
+
+    /// Marks a cell as visited.
+    ///
+    /// Cells outside the grid are ignored.
+    ///
+    /// # Arguments
+    ///
+    /// * `cell` - The grid cell the player is standing in
+    ///
+    /// # Returns
+    ///
+    /// `true` if the cell had not been visited before, `false` otherwise.
+    pub fn mark(&mut self, cell: Cell) -> bool {
+        if cell.row >= self.height || cell.col >= self.width {
+            return false;
+        }
+        let index = cell.row * self.width + cell.col;
+        let (word, bit) = (index / 64, 1u64 << (index % 64));
+        if self.bits[word] & bit != 0 {
+            return false;
+        }
+        self.bits[word] |= bit;
+        self.visited += 1;
+        true
+    }
+
+    /// Checks whether a cell has been visited.
+    ///
+    /// # Arguments
+    ///
+    /// * `cell` - The grid cell to check
+    ///
+    /// # Returns
+    ///
+    /// `true` if the cell is inside the grid and has been marked.
+    pub fn is_visited(&self, cell: Cell) -> bool {
+        if cell.row >= self.height || cell.col >= self.width {
+            return false;
+        }
+        let index = cell.row * self.width + cell.col;
+        self.bits[index / 64] & (1u64 << (index % 64)) != 0
+    }
+
+    /// Number of distinct cells marked as visited.
+    pub fn visited_count(&self) -> usize {
+        self.visited
+    }
+
+    /// Number of walkable cells in the grid this set was built for.
+    pub fn walkable_count(&self) -> usize {
+        self.walkable
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(level: i32, completion_time: f32) -> LevelResult {
+        LevelResult {
+            level,
+            completion_time,
+            time_remaining: (30.0 - completion_time).max(0.0),
+            cells_visited: 0,
+            total_cells: 100,
+            closest_enemy_distance: 0.0,
+            keys_collected: 0,
+            difficulty_multiplier: 1.0,
+            combo_multiplier: 1.0,
+            solution_length: 0,
+            close_calls: 0,
+            duplicate_anomalies: 0,
+        }
+    }
+
+    #[test]
+    fn test_fast_clear_level_one() {
+        let breakdown = ScoreCalculator::default().calculate(&result(1, 15.0));
+        assert_eq!(breakdown.base, 150);
+        assert_eq!(breakdown.time_bonus, 450);
+        assert_eq!(breakdown.level_bonus, 0);
+        assert_eq!(breakdown.streak_bonus, 25);
+        assert_eq!(breakdown.exploration_bonus, 0);
+        assert_eq!(breakdown.untouchable_bonus, 0);
+        assert_eq!(breakdown.total, 625);
+    }
+
+    #[test]
+    fn test_slow_clear_high_level_with_all_bonuses() {
+        let mut level_result = result(7, 35.0);
+        level_result.cells_visited = 50;
+        level_result.closest_enemy_distance = 300.0;
+        level_result.keys_collected = 2;
+        let breakdown = ScoreCalculator::default().calculate(&level_result);
+        assert_eq!(breakdown.base, 1050);
+        assert_eq!(breakdown.time_bonus, 525);
+        assert_eq!(breakdown.level_bonus, 100);
+        assert_eq!(breakdown.streak_bonus, 0);
+        assert_eq!(breakdown.exploration_bonus, 262);
+        assert_eq!(breakdown.untouchable_bonus, 350);
+        assert_eq!(breakdown.key_bonus, 200);
+        assert_eq!(breakdown.total, 2487);
+    }
+
+    #[test]
+    fn test_multiplier_applies_to_subtotal() {
+        let mut level_result = result(3, 30.0);
+        level_result.difficulty_multiplier = 1.5;
+        let breakdown = ScoreCalculator::default().calculate(&level_result);
+        assert_eq!(breakdown.subtotal(), 450 + 450);
+        assert_eq!(breakdown.total, 1350);
+    }
+
+    #[test]
+    fn test_combo_multiplier_stacks_with_difficulty() {
+        let mut level_result = result(3, 30.0);
+        level_result.difficulty_multiplier = 1.5;
+        level_result.combo_multiplier = 1.4;
+        let breakdown = ScoreCalculator::default().calculate(&level_result);
+        assert_eq!(breakdown.total, 1890);
+        assert!(breakdown.summary_lines().contains("Combo: x1.4"));
+    }
+
+    #[test]
+    fn test_duplicate_anomalies_pay_the_duplicate_bonus() {
+        let mut level_result = result(1, 15.0);
+        level_result.duplicate_anomalies = 2;
+        let breakdown = ScoreCalculator::default().calculate(&level_result);
+        assert_eq!(breakdown.anomaly_bonus, 2 * DUPLICATE_BONUS);
+        assert_eq!(breakdown.total, 625 + 2 * DUPLICATE_BONUS);
+        assert!(breakdown.summary_lines().contains("Anomaly: +500"));
+    }
+
+    #[test]
+    fn test_visited_cells_counts_each_cell_once() {
+        let grid = vec![
+            vec![true, true, true],
+            vec![true, false, false],
+            vec![true, true, true],
+        ];
+        let mut visited = VisitedCells::for_grid(&grid);
+        assert_eq!(visited.walkable_count(), 2);
+        assert!(visited.mark(Cell::new(1, 1)));
+        assert!(!visited.mark(Cell::new(1, 1)));
+        assert!(!visited.mark(Cell::new(5, 5)));
+        assert!(visited.is_visited(Cell::new(1, 1)));
+        assert_eq!(visited.visited_count(), 1);
+    }
+
+    #[test]
+    fn test_group_digits() {
+        assert_eq!(group_digits(0), "0");
+        assert_eq!(group_digits(999), "999");
+        assert_eq!(group_digits(1000), "1,000");
+        assert_eq!(group_digits(1234567), "1,234,567");
+        assert_eq!(group_digits(u32::MAX.into()), "4,294,967,295");
+    }
+
+    #[test]
+    fn test_summary_groups_large_points() {
+        let mut breakdown = ScoreCalculator::default().calculate(&result(20, 10.0));
+        breakdown.total = 12500;
+        let summary = breakdown.summary_lines();
+        assert!(summary.contains("Level Clear: +3,000"));
+        assert!(summary.ends_with("Total: +12,500"));
+    }
+}
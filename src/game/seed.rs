@@ -0,0 +1,555 @@
+//! Run seeds that players can share to race on the same mazes.
+//!
+//! Every regular run generates its levels from one `u64` seed. The player can type a seed on the
+//! title screen to replay a friend's run, or leave it empty for a random one. Seeds are written
+//! in lowercase base36 so they are short enough to read out loud, and raw decimal numbers are
+//! accepted too. A string made only of digits is always read as decimal, so [`format_seed`] falls
+//! back to decimal for the rare seed whose base36 form has no letters.
+//!
+//! [`SeedField`] is the text the player is typing, with a caret, kept separate from the title
+//! screen that draws it. It also follows input method composition, so layouts that build
+//! characters in several keystrokes (CJK input methods, dead keys) type into it correctly.
+//!
+//! # Usage
+//!
+//! ```rust,ignore
+//! let mut field = SeedField::new(game_state.chosen_seed);
+//! field.insert('k');
+//! field.apply_ime(&Ime::Commit("7".to_string()));
+//! if let Ok(seed) = field.parse() {
+//!     game_state.chosen_seed = Some(seed);
+//! }
+//! assert_eq!(parse_seed(&format_seed(seed)), Ok(seed));
+//! ```
+
+use crate::game::bindings::Action;
+use crate::game::daily;
+use std::fmt;
+use std::ops::Range;
+use winit::event::{Ime, KeyEvent};
+use winit::keyboard::{Key, NamedKey};
+
+/// Longest seed the field accepts, in characters; `u64::MAX` is 20 decimal digits.
+pub const MAX_SEED_LENGTH: usize = 20;
+
+/// Base seeds are written in.
+const RADIX: u32 = 36;
+
+/// Why a seed could not be read.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SeedError {
+    /// Nothing was typed
+    Empty,
+    /// A character that is neither a digit nor a letter, with its position in characters
+    InvalidCharacter {
+        /// Position of the character, counting from 0
+        index: usize,
+        /// The character itself
+        ch: char,
+    },
+    /// The number is too large to fit in a seed
+    Overflow,
+}
+
+impl fmt::Display for SeedError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => write!(f, "Seed is empty"),
+            Self::InvalidCharacter { index, ch } => {
+                write!(f, "Seed can't contain '{}' (character {})", ch, index + 1)
+            }
+            Self::Overflow => write!(f, "Seed is too large"),
+        }
+    }
+}
+
+/// Writes a seed the way players share it.
+///
+/// # Arguments
+/// * `seed` - The seed to write
+///
+/// # Returns
+/// The seed in lowercase base36, or in decimal if its base36 form has no letters
+pub fn format_seed(seed: u64) -> String {
+    let mut digits = Vec::new();
+    let mut rest = seed;
+    loop {
+        let digit = (rest % RADIX as u64) as u32;
+        digits.push(char::from_digit(digit, RADIX).expect("Digit is below the radix"));
+        rest /= RADIX as u64;
+        if rest == 0 {
+            break;
+        }
+    }
+
+    if digits.iter().all(char::is_ascii_digit) {
+        seed.to_string()
+    } else {
+        digits.iter().rev().collect()
+    }
+}
+
+/// Reads a seed typed by the player.
+///
+/// # Arguments
+/// * `text` - Decimal digits, or base36 digits and letters in any case
+///
+/// # Returns
+/// The seed, or why it can't be used
+pub fn parse_seed(text: &str) -> Result<u64, SeedError> {
+    if text.is_empty() {
+        return Err(SeedError::Empty);
+    }
+    if let Some(index) = invalid_character(text) {
+        let ch = text.chars().nth(index).expect("Index is within the text");
+        return Err(SeedError::InvalidCharacter { index, ch });
+    }
+
+    let radix = if text.chars().all(|ch| ch.is_ascii_digit()) {
+        10
+    } else {
+        RADIX
+    };
+    text.chars().try_fold(0u64, |seed, ch| {
+        let digit = ch.to_digit(radix).expect("Characters were checked");
+        seed.checked_mul(radix as u64)
+            .and_then(|seed| seed.checked_add(digit as u64))
+            .ok_or(SeedError::Overflow)
+    })
+}
+
+/// Returns the position of the first character that can't appear in a seed.
+///
+/// # Arguments
+/// * `text` - The text to check
+///
+/// # Returns
+/// The position in characters, or `None` if every character is a digit or an ASCII letter
+pub fn invalid_character(text: &str) -> Option<usize> {
+    text.chars().position(|ch| !ch.is_ascii_alphanumeric())
+}
+
+/// Derives the maze seed for one level of a seeded run.
+///
+/// # Arguments
+/// * `run_seed` - The run's seed
+/// * `level` - The level number, starting at 1
+///
+/// # Returns
+/// FNV-1a hash of `"mirador-run-SEED-level-N"`
+pub fn level_seed(run_seed: u64, level: i32) -> u64 {
+    daily::fnv1a_64(format!("mirador-run-{}-level-{}", run_seed, level).as_bytes())
+}
+
+/// A seed being typed, with a caret.
+///
+/// While an input method is composing, its uncommitted text (the preedit) is shown at the
+/// caret but kept out of the typed text, so it doesn't count toward [`MAX_SEED_LENGTH`] and
+/// isn't parsed until the input method commits it.
+///
+/// The title screen reuses the field for challenge codes, which are longer than seeds; see
+/// [`with_max_length`](Self::with_max_length).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SeedField {
+    /// The typed characters; may include invalid ones so they can be shown
+    text: String,
+    /// Caret position in characters, from 0 to the text's length
+    caret: usize,
+    /// Uncommitted input method text, shown at the caret
+    preedit: String,
+    /// Caret inside the preedit in characters, or `None` when the input method hides it
+    preedit_caret: Option<usize>,
+    /// Most characters the field holds, or `None` for [`MAX_SEED_LENGTH`]
+    max_length: Option<usize>,
+}
+
+impl SeedField {
+    /// Starts editing, prefilled with the current seed.
+    ///
+    /// # Arguments
+    /// * `seed` - The seed currently chosen, or `None` for an empty field
+    ///
+    /// # Returns
+    /// A field with the caret at the end
+    pub fn new(seed: Option<u64>) -> Self {
+        let text = seed.map(format_seed).unwrap_or_default();
+        Self {
+            caret: text.chars().count(),
+            text,
+            ..Self::default()
+        }
+    }
+
+    /// Lets the field hold more or fewer characters than a seed.
+    ///
+    /// # Arguments
+    /// * `max_length` - Most characters the field holds
+    pub fn with_max_length(mut self, max_length: usize) -> Self {
+        self.max_length = Some(max_length);
+        self
+    }
+
+    /// Returns the typed text.
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Returns the caret position in characters.
+    #[cfg(test)]
+    pub fn caret(&self) -> usize {
+        self.caret
+    }
+
+    /// Types a character at the caret.
+    ///
+    /// Control characters are ignored, and so is anything past [`MAX_SEED_LENGTH`] or the
+    /// field's own limit. Other characters are kept even when they can't appear in a seed, so
+    /// the field can show them.
+    ///
+    /// # Arguments
+    /// * `ch` - The character typed
+    pub fn insert(&mut self, ch: char) {
+        let max_length = self.max_length.unwrap_or(MAX_SEED_LENGTH);
+        if ch.is_control() || self.text.chars().count() >= max_length {
+            return;
+        }
+        let at = self.byte_offset(self.caret);
+        self.text.insert(at, ch);
+        self.caret += 1;
+    }
+
+    /// Types text at the caret as if each character were typed in turn.
+    ///
+    /// Pasted and input method text goes through the same checks as [`insert`](Self::insert),
+    /// so control characters are dropped and the text stops at [`MAX_SEED_LENGTH`].
+    ///
+    /// # Arguments
+    /// * `text` - The text to type
+    pub fn paste(&mut self, text: &str) {
+        text.chars().for_each(|ch| self.insert(ch));
+    }
+
+    /// Edits the text or moves the caret for a key press.
+    ///
+    /// The key bound to [`Action::Erase`], Backspace by default, deletes before the caret.
+    /// Delete, the left and right arrows, Home, and End edit and move as usual; any other key
+    /// types its text, if it has any.
+    ///
+    /// # Arguments
+    /// * `event` - The keyboard event, already known to be a press
+    /// * `action` - What the key is bound to in the text entry context, if anything
+    pub fn edit_key(&mut self, event: &KeyEvent, action: Option<Action>) {
+        if action == Some(Action::Erase) {
+            self.backspace();
+            return;
+        }
+        match &event.logical_key {
+            Key::Named(NamedKey::Delete) => self.delete(),
+            Key::Named(NamedKey::ArrowLeft) => self.move_left(),
+            Key::Named(NamedKey::ArrowRight) => self.move_right(),
+            Key::Named(NamedKey::Home) => self.move_home(),
+            Key::Named(NamedKey::End) => self.move_end(),
+            _ => {
+                if let Some(text) = &event.text {
+                    self.paste(text);
+                }
+            }
+        }
+    }
+
+    /// Follows an input method event.
+    ///
+    /// A preedit replaces the composition shown at the caret, and an empty one cancels it.
+    /// A commit ends the composition and types its text like [`paste`](Self::paste). The
+    /// input method turning on or off drops whatever composition was left.
+    ///
+    /// # Arguments
+    /// * `event` - The input method event from the window
+    pub fn apply_ime(&mut self, event: &Ime) {
+        match event {
+            Ime::Preedit(preedit, cursor) => {
+                self.preedit = preedit.clone();
+                // winit gives the cursor as byte offsets into the preedit
+                self.preedit_caret = cursor
+                    .and_then(|(start, _)| preedit.get(..start))
+                    .map(|before| before.chars().count());
+            }
+            Ime::Commit(text) => {
+                self.cancel_preedit();
+                self.paste(text);
+            }
+            Ime::Enabled | Ime::Disabled => self.cancel_preedit(),
+        }
+    }
+
+    /// Drops the input method composition without typing it.
+    pub fn cancel_preedit(&mut self) {
+        self.preedit.clear();
+        self.preedit_caret = None;
+    }
+
+    /// Returns whether an input method is composing text at the caret.
+    pub fn is_composing(&self) -> bool {
+        !self.preedit.is_empty()
+    }
+
+    /// Deletes the character before the caret.
+    pub fn backspace(&mut self) {
+        if self.caret == 0 {
+            return;
+        }
+        self.caret -= 1;
+        let at = self.byte_offset(self.caret);
+        self.text.remove(at);
+    }
+
+    /// Deletes the character after the caret.
+    pub fn delete(&mut self) {
+        if self.caret < self.text.chars().count() {
+            let at = self.byte_offset(self.caret);
+            self.text.remove(at);
+        }
+    }
+
+    /// Moves the caret one character left, stopping at the start.
+    pub fn move_left(&mut self) {
+        self.caret = self.caret.saturating_sub(1);
+    }
+
+    /// Moves the caret one character right, stopping at the end.
+    pub fn move_right(&mut self) {
+        self.caret = (self.caret + 1).min(self.text.chars().count());
+    }
+
+    /// Moves the caret to the start of the text.
+    pub fn move_home(&mut self) {
+        self.caret = 0;
+    }
+
+    /// Moves the caret to the end of the text.
+    pub fn move_end(&mut self) {
+        self.caret = self.text.chars().count();
+    }
+
+    /// Returns whether the text has a character that can't appear in a seed.
+    #[cfg(test)]
+    pub fn has_invalid_character(&self) -> bool {
+        invalid_character(&self.text).is_some()
+    }
+
+    /// Reads the typed seed.
+    ///
+    /// # Returns
+    /// The seed, or why it can't be used
+    pub fn parse(&self) -> Result<u64, SeedError> {
+        parse_seed(&self.text)
+    }
+
+    /// Returns the text with a `|` drawn at the caret.
+    ///
+    /// While an input method is composing, its preedit is shown at the caret and the `|`
+    /// moves to the input method's caret inside it, or after it when that is hidden.
+    pub fn display(&self) -> String {
+        let at = self.byte_offset(self.caret);
+        let preedit_at = self.preedit_byte_offset();
+        format!(
+            "{}{}|{}{}",
+            &self.text[..at],
+            &self.preedit[..preedit_at],
+            &self.preedit[preedit_at..],
+            &self.text[at..]
+        )
+    }
+
+    /// Returns where the `|` is in [`display`](Self::display), as a byte offset.
+    pub fn display_caret(&self) -> usize {
+        self.byte_offset(self.caret) + self.preedit_byte_offset()
+    }
+
+    /// Returns where the preedit is in [`display`](Self::display), in bytes.
+    ///
+    /// The range covers the `|` too when the caret is inside the preedit.
+    ///
+    /// # Returns
+    /// The preedit's bytes, or `None` while nothing is being composed
+    pub fn preedit_range(&self) -> Option<Range<usize>> {
+        if !self.is_composing() {
+            return None;
+        }
+        let start = self.byte_offset(self.caret);
+        let caret_inside = self.preedit_byte_offset() < self.preedit.len();
+        Some(start..start + self.preedit.len() + usize::from(caret_inside))
+    }
+
+    /// Converts a caret position in characters to a byte offset into the text.
+    fn byte_offset(&self, caret: usize) -> usize {
+        byte_offset_in(&self.text, caret)
+    }
+
+    /// Returns the byte offset of the input method's caret inside the preedit.
+    fn preedit_byte_offset(&self) -> usize {
+        self.preedit_caret.map_or(self.preedit.len(), |caret| {
+            byte_offset_in(&self.preedit, caret)
+        })
+    }
+}
+
+/// Converts a position in characters to a byte offset into `text`.
+fn byte_offset_in(text: &str, chars: usize) -> usize {
+    text.char_indices()
+        .nth(chars)
+        .map_or(text.len(), |(offset, _)| offset)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_seeds_round_trip_through_their_text() {
+        for seed in [
+            0,
+            9,
+            10,
+            35,
+            36,
+            1_295,
+            46_655,
+            0xdead_beef,
+            u64::MAX - 1,
+            u64::MAX,
+        ] {
+            assert_eq!(parse_seed(&format_seed(seed)), Ok(seed), "seed {}", seed);
+        }
+        assert_eq!(format_seed(35), "z");
+        assert_eq!(format_seed(u64::MAX), "3w5e11264sgsf");
+
+        // 36 is "10" in base36, which would read back as ten, so it is written in decimal
+        assert_eq!(format_seed(36), "36");
+        assert_eq!(parse_seed("Z"), Ok(35));
+        assert_eq!(parse_seed("007"), Ok(7));
+    }
+
+    #[test]
+    fn test_seeds_that_overflow_or_contain_symbols_are_rejected() {
+        assert_eq!(parse_seed(""), Err(SeedError::Empty));
+        assert_eq!(parse_seed("18446744073709551615"), Ok(u64::MAX));
+        assert_eq!(parse_seed("18446744073709551616"), Err(SeedError::Overflow));
+        assert_eq!(parse_seed("3w5e11264sgsg"), Err(SeedError::Overflow));
+        assert_eq!(parse_seed("zzzzzzzzzzzzzz"), Err(SeedError::Overflow));
+        assert_eq!(
+            parse_seed("ab-cd"),
+            Err(SeedError::InvalidCharacter { index: 2, ch: '-' })
+        );
+        assert_eq!(
+            parse_seed("seé"),
+            Err(SeedError::InvalidCharacter { index: 2, ch: 'é' })
+        );
+    }
+
+    #[test]
+    fn test_field_edits_at_the_caret() {
+        let mut field = SeedField::new(Some(35));
+        assert_eq!((field.text(), field.caret()), ("z", 1));
+
+        field.move_home();
+        field.insert('a');
+        field.insert('é');
+        assert_eq!(field.display(), "aé|z");
+        assert!(field.has_invalid_character());
+
+        field.backspace();
+        field.move_right();
+        field.move_right();
+        field.insert('1');
+        assert_eq!(field.display(), "az1|");
+        assert!(!field.has_invalid_character());
+        assert_eq!(field.parse(), parse_seed("az1"));
+
+        field.move_left();
+        field.delete();
+        field.insert('\u{8}');
+        assert_eq!(field.display(), "az|");
+
+        let mut full = SeedField::new(None);
+        for _ in 0..MAX_SEED_LENGTH + 5 {
+            full.insert('9');
+        }
+        assert_eq!(full.text().len(), MAX_SEED_LENGTH);
+
+        let mut code = SeedField::new(None).with_max_length(MAX_SEED_LENGTH + 4);
+        code.paste(&"A".repeat(MAX_SEED_LENGTH + 10));
+        assert_eq!(code.text().len(), MAX_SEED_LENGTH + 4);
+    }
+
+    #[test]
+    fn test_ime_preedit_shows_at_the_caret_until_committed() {
+        let mut field = SeedField::new(None);
+        field.insert('a');
+        field.insert('z');
+        field.move_left();
+        field.apply_ime(&Ime::Enabled);
+
+        field.apply_ime(&Ime::Preedit("k".to_string(), Some((1, 1))));
+        assert_eq!((field.display().as_str(), field.text()), ("ak|z", "az"));
+        assert_eq!(field.preedit_range(), Some(1..2));
+
+        // The input method's caret can sit inside the composition
+        field.apply_ime(&Ime::Preedit("kの".to_string(), Some((1, 1))));
+        assert_eq!((field.display().as_str(), field.text()), ("ak|のz", "az"));
+        assert_eq!(field.preedit_range(), Some(1..6));
+        assert_eq!(field.display_caret(), 2);
+
+        // winit clears the preedit right before committing
+        field.apply_ime(&Ime::Preedit(String::new(), None));
+        field.apply_ime(&Ime::Commit("k7".to_string()));
+        assert_eq!((field.display().as_str(), field.text()), ("ak7|z", "ak7z"));
+        assert_eq!(field.preedit_range(), None);
+        assert_eq!(field.parse(), parse_seed("ak7z"));
+    }
+
+    #[test]
+    fn test_ime_cancel_drops_the_composition() {
+        let mut field = SeedField::new(Some(35));
+        field.apply_ime(&Ime::Preedit("にほ".to_string(), None));
+        assert_eq!((field.display().as_str(), field.text()), ("zにほ|", "z"));
+        assert!(field.is_composing());
+
+        field.apply_ime(&Ime::Preedit(String::new(), None));
+        assert_eq!((field.display().as_str(), field.text()), ("z|", "z"));
+        assert!(!field.is_composing());
+
+        field.apply_ime(&Ime::Preedit("ん".to_string(), Some((3, 3))));
+        field.apply_ime(&Ime::Disabled);
+        assert_eq!((field.display().as_str(), field.text()), ("z|", "z"));
+    }
+
+    #[test]
+    fn test_only_committed_text_counts_toward_the_limit() {
+        let mut field = SeedField::new(None);
+        field.paste(&"9".repeat(MAX_SEED_LENGTH - 1));
+
+        // The composition may run past the limit while it is being typed
+        field.apply_ime(&Ime::Preedit("abc".to_string(), Some((3, 3))));
+        assert_eq!(field.display().chars().count(), MAX_SEED_LENGTH + 3);
+        assert_eq!(field.text().len(), MAX_SEED_LENGTH - 1);
+
+        field.apply_ime(&Ime::Commit("abc".to_string()));
+        assert_eq!(
+            field.text(),
+            format!("{}a", "9".repeat(MAX_SEED_LENGTH - 1))
+        );
+
+        // Pasting goes through the same checks as typing
+        let mut pasted = SeedField::new(None);
+        pasted.paste("ab\ncd\t");
+        assert_eq!(pasted.display(), "abcd|");
+    }
+
+    #[test]
+    fn test_level_seeds_depend_on_the_run_and_the_level() {
+        assert_eq!(level_seed(42, 1), level_seed(42, 1));
+        assert_ne!(level_seed(42, 1), level_seed(42, 2));
+        assert_ne!(level_seed(42, 1), level_seed(43, 1));
+    }
+}
@@ -0,0 +1,323 @@
+//! Arrows painted on the maze walls near the entrance.
+//!
+//! A gentler aid than the compass for new players: when a maze loads, a few wall faces along
+//! the first third of the solution path get an arrow pointing the way the path goes on. Turns
+//! are signed on the wall the player walks toward, and straight stretches on a wall beside them.
+//! The exit cell's walls are never painted, so the signs only hint at the way out.
+//!
+//! Placement works on grid cells and is kept separate from the world-space [`SignDecal`]s the
+//! renderer draws, so it can be tested without a maze on screen.
+//!
+//! # Usage
+//!
+//! ```rust,ignore
+//! let path = maze::find_path(&maze_grid, entrance, exit).unwrap_or_default();
+//! let decals: Vec<SignDecal> = place_signposts(&maze_grid, &path, exit)
+//!     .iter()
+//!     .map(|signpost| signpost.decal(maze_dimensions, is_test_mode))
+//!     .collect();
+//! ```
+
+use crate::game::maze::generator::Cell;
+use crate::math::coordinates::{self, Direction};
+
+/// Levels up to this one always get signs, whether or not the navigation hints setting is on.
+pub const HINT_LEVELS: i32 = 2;
+
+/// Most signs painted in one maze.
+pub const MAX_SIGNPOSTS: usize = 5;
+
+/// Fewest steps along the path between two signs, so they don't bunch up around one corner.
+const MIN_SPACING: usize = 2;
+
+/// Height of a sign's center above the floor, as a fraction of the cell size.
+const SIGN_HEIGHT: f32 = 0.55;
+
+/// How far a sign sits in front of its wall, in world units, so it doesn't z-fight with it.
+const WALL_OFFSET: f32 = 0.5;
+
+/// Which way the path goes at a cell.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Turn {
+    /// The path turns to the left of the way the player was walking
+    Left,
+    /// The path turns to the right of the way the player was walking
+    Right,
+    /// The path carries straight on
+    Straight,
+}
+
+/// A sign painted on one wall face of a cell on the path.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Signpost {
+    /// The open cell the sign is seen from
+    pub cell: Cell,
+    /// Side of the cell the painted wall is on
+    pub wall: Direction,
+    /// Way the arrow points for someone facing the wall; only ever left or right
+    pub arrow: Turn,
+}
+
+/// A sign placed in the world, ready to be drawn.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SignDecal {
+    /// Center of the sign, just in front of the wall
+    pub center: [f32; 3],
+    /// Unit normal of the wall, pointing into the open cell
+    pub normal: [f32; 3],
+    /// Side of the sign the arrow points to, for someone facing the wall
+    pub arrow: Turn,
+    /// Width and height of the sign in world units
+    pub size: f32,
+}
+
+/// Classifies the turn the path makes between two steps.
+///
+/// # Arguments
+/// * `incoming` - Direction of the step into the cell
+/// * `outgoing` - Direction of the step out of the cell
+///
+/// # Returns
+/// The turn, or [`Turn::Straight`] when the path doubles back, which a solution path never does
+pub fn classify_turn(incoming: Direction, outgoing: Direction) -> Turn {
+    if outgoing == right_of(incoming) {
+        Turn::Right
+    } else if outgoing == coordinates::opposite_direction(right_of(incoming)) {
+        Turn::Left
+    } else {
+        Turn::Straight
+    }
+}
+
+/// Picks the wall faces to paint along the first third of a solution path.
+///
+/// Turns are preferred, signed on the wall straight ahead of the player. Straight stretches
+/// fill any remaining slots, signed on a wall beside the player with the arrow pointing onward.
+///
+/// # Arguments
+/// * `maze_grid` - 2D grid of the maze where `true` marks a wall
+/// * `path` - Solution path from the entrance, one adjacent cell per step
+/// * `exit` - The maze's exit cell, whose walls are never painted
+///
+/// # Returns
+/// Up to [`MAX_SIGNPOSTS`] signs in path order; fewer on short paths
+pub fn place_signposts(maze_grid: &[Vec<bool>], path: &[Cell], exit: Cell) -> Vec<Signpost> {
+    let last = (path.len().saturating_sub(1) / 3).min(path.len().saturating_sub(2));
+
+    let mut turns = Vec::new();
+    let mut straights = Vec::new();
+    for index in 1..=last {
+        let cell = path[index];
+        if cell == exit {
+            continue;
+        }
+        let (Some(incoming), Some(outgoing)) = (
+            step_direction(path[index - 1], cell),
+            step_direction(cell, path[index + 1]),
+        ) else {
+            continue;
+        };
+
+        match classify_turn(incoming, outgoing) {
+            Turn::Straight => {
+                // Facing a wall on the left, the path carries on to the right, and vice versa
+                let left = coordinates::opposite_direction(right_of(incoming));
+                let side = [(left, Turn::Right), (right_of(incoming), Turn::Left)]
+                    .into_iter()
+                    .find(|&(wall, _)| is_wall(maze_grid, cell, wall));
+                if let Some((wall, arrow)) = side {
+                    straights.push((index, Signpost { cell, wall, arrow }));
+                }
+            }
+            turn => {
+                if is_wall(maze_grid, cell, incoming) {
+                    let signpost = Signpost {
+                        cell,
+                        wall: incoming,
+                        arrow: turn,
+                    };
+                    turns.push((index, signpost));
+                }
+            }
+        }
+    }
+
+    let mut chosen: Vec<(usize, Signpost)> = Vec::new();
+    for (index, signpost) in turns.into_iter().chain(straights) {
+        if chosen.len() == MAX_SIGNPOSTS {
+            break;
+        }
+        if chosen
+            .iter()
+            .all(|&(other, _)| index.abs_diff(other) >= MIN_SPACING)
+        {
+            chosen.push((index, signpost));
+        }
+    }
+    chosen.sort_by_key(|&(index, _)| index);
+    chosen.into_iter().map(|(_, signpost)| signpost).collect()
+}
+
+impl Signpost {
+    /// Works out where the sign sits in the world.
+    ///
+    /// # Arguments
+    /// * `maze_dimensions` - The dimensions of the maze (width, height) in cells
+    /// * `is_test_mode` - Whether test mode is enabled (affects the cell size)
+    ///
+    /// # Returns
+    /// The sign centered on the wall face, just below eye level on the ground floor
+    pub fn decal(&self, maze_dimensions: (usize, usize), is_test_mode: bool) -> SignDecal {
+        let cell_size = coordinates::calculate_cell_size(maze_dimensions, is_test_mode);
+        let center = coordinates::maze_to_world(&self.cell, maze_dimensions, 0.0, is_test_mode);
+        let (dx, dz) = direction_offset(self.wall);
+        let reach = cell_size * 0.5 - WALL_OFFSET;
+
+        SignDecal {
+            center: [
+                center[0] + dx * reach,
+                cell_size * SIGN_HEIGHT,
+                center[2] + dz * reach,
+            ],
+            normal: [-dx, 0.0, -dz],
+            arrow: self.arrow,
+            size: cell_size * 0.4,
+        }
+    }
+}
+
+/// Returns the direction 90° clockwise, which is to the right when facing `direction`.
+fn right_of(direction: Direction) -> Direction {
+    match direction {
+        Direction::North => Direction::East,
+        Direction::East => Direction::South,
+        Direction::South => Direction::West,
+        Direction::West => Direction::North,
+    }
+}
+
+/// Returns the unit (x, z) world offset of a direction; north is toward lower rows.
+fn direction_offset(direction: Direction) -> (f32, f32) {
+    match direction {
+        Direction::North => (0.0, -1.0),
+        Direction::East => (1.0, 0.0),
+        Direction::South => (0.0, 1.0),
+        Direction::West => (-1.0, 0.0),
+    }
+}
+
+/// Returns the direction of a single step between adjacent cells.
+fn step_direction(from: Cell, to: Cell) -> Option<Direction> {
+    match (
+        to.row as isize - from.row as isize,
+        to.col as isize - from.col as isize,
+    ) {
+        (-1, 0) => Some(Direction::North),
+        (1, 0) => Some(Direction::South),
+        (0, 1) => Some(Direction::East),
+        (0, -1) => Some(Direction::West),
+        _ => None,
+    }
+}
+
+/// Returns whether the neighbor of `cell` in `direction` is a wall; off the grid counts as one.
+fn is_wall(maze_grid: &[Vec<bool>], cell: Cell, direction: Direction) -> bool {
+    let (row, col) = match direction {
+        Direction::North => (cell.row.checked_sub(1), Some(cell.col)),
+        Direction::South => (Some(cell.row + 1), Some(cell.col)),
+        Direction::East => (Some(cell.row), Some(cell.col + 1)),
+        Direction::West => (Some(cell.row), cell.col.checked_sub(1)),
+    };
+    match (row, col) {
+        (Some(row), Some(col)) => maze_grid
+            .get(row)
+            .and_then(|cells| cells.get(col))
+            .copied()
+            .unwrap_or(true),
+        _ => true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid(rows: &[&str]) -> Vec<Vec<bool>> {
+        rows.iter()
+            .map(|row| row.chars().map(|c| c == '#').collect())
+            .collect()
+    }
+
+    #[test]
+    fn test_turns_are_classified_from_the_walkers_point_of_view() {
+        use Direction::*;
+        assert_eq!(classify_turn(North, North), Turn::Straight);
+        assert_eq!(classify_turn(North, East), Turn::Right);
+        assert_eq!(classify_turn(North, West), Turn::Left);
+        assert_eq!(classify_turn(East, South), Turn::Right);
+        assert_eq!(classify_turn(South, East), Turn::Left);
+        assert_eq!(classify_turn(West, South), Turn::Left);
+        assert_eq!(classify_turn(West, West), Turn::Straight);
+    }
+
+    #[test]
+    fn test_signs_point_along_the_path_and_avoid_the_exit() {
+        // North up the left corridor, then east along the top to the exit
+        let maze = grid(&[
+            "#########",
+            "#.......#",
+            "#.#######",
+            "#.#######",
+            "#.#######",
+            "#.#######",
+            "#.#######",
+            "#.#######",
+            "#########",
+        ]);
+        let mut path: Vec<Cell> = (1..=7).rev().map(|row| Cell::new(row, 1)).collect();
+        path.extend((2..=7).map(|col| Cell::new(1, col)));
+        let exit = Cell::new(1, 7);
+
+        let signposts = place_signposts(&maze, &path, exit);
+        assert!(!signposts.is_empty() && signposts.len() <= MAX_SIGNPOSTS);
+        for signpost in &signposts {
+            assert_ne!(signpost.cell, exit);
+            assert!(is_wall(&maze, signpost.cell, signpost.wall));
+            assert!(path[..=(path.len() - 1) / 3].contains(&signpost.cell));
+        }
+
+        // Walking north with walls on both sides, the left (west) wall is signed first and
+        // its arrow points to the right of someone facing it, which is north
+        assert_eq!(signposts[0].wall, Direction::West);
+        assert_eq!(signposts[0].arrow, Turn::Right);
+
+        // The right turn at the top corner is signed on the wall straight ahead
+        let long_path: Vec<Cell> = path[3..].to_vec();
+        let corner = place_signposts(&maze, &long_path, Cell::new(1, 7));
+        assert!(corner.contains(&Signpost {
+            cell: Cell::new(1, 1),
+            wall: Direction::North,
+            arrow: Turn::Right,
+        }));
+        assert!(place_signposts(&maze, &path[..2], exit).is_empty());
+    }
+
+    #[test]
+    fn test_decal_sits_just_in_front_of_its_wall() {
+        let signpost = Signpost {
+            cell: Cell::new(1, 1),
+            wall: Direction::North,
+            arrow: Turn::Left,
+        };
+        let dimensions = (9, 9);
+        let cell_size = coordinates::calculate_cell_size(dimensions, false);
+        let cell_center = coordinates::maze_to_world(&Cell::new(1, 1), dimensions, 0.0, false);
+
+        let decal = signpost.decal(dimensions, false);
+        assert_eq!(decal.normal, [0.0, 0.0, 1.0]);
+        assert_eq!(decal.center[0], cell_center[0]);
+        let wall_z = cell_center[2] - cell_size * 0.5;
+        assert!(decal.center[2] > wall_z && decal.center[2] - wall_z <= WALL_OFFSET + 0.01);
+        assert!(decal.center[1] > 0.0 && decal.center[1] < cell_size);
+    }
+}
@@ -0,0 +1,357 @@
+//! Propagation delay and doppler shift for enemy audio.
+//!
+//! Sound takes time to cover distance, so a far-away enemy should be heard where it was a
+//! moment ago, and one rushing toward the player should sound a little higher. Both effects are
+//! computed here from plain positions and timestamps so they can be tested without an audio
+//! device:
+//!
+//! - [`PositionHistory`] remembers where a source has been over the last
+//!   [`MAX_PROPAGATION_DELAY`] and interpolates between samples.
+//! - [`propagation_delay`] turns a distance into how far behind the heard position lags.
+//! - [`doppler_pitch`] turns the radial speed between source and listener into a pitch
+//!   multiplier, and [`smooth_pitch`] eases toward it so frame jitter doesn't warble.
+//!
+//! [`EnemyPropagation`] ties them together for one enemy. The world is much smaller than real
+//! life, so [`SPEED_OF_SOUND`] is far slower than the real thing to keep the effects audible.
+//!
+//! # Usage
+//!
+//! ```rust,ignore
+//! let mut propagation = EnemyPropagation::default();
+//!
+//! // Once per frame
+//! let (heard_at, pitch) = propagation.update(enemy_position, listener_position, Instant::now());
+//! track.set_position(heard_at, tween);
+//! sound.set_playback_rate(pitch, tween);
+//! ```
+
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+/// Speed sound travels at, in world units per second.
+pub const SPEED_OF_SOUND: f32 = 6000.0;
+
+/// Longest delay a source's heard position can lag behind, and how much history is kept.
+pub const MAX_PROPAGATION_DELAY: Duration = Duration::from_millis(600);
+
+/// Largest pitch shift doppler can apply, as a fraction of the normal pitch.
+pub const MAX_PITCH_SHIFT: f64 = 0.1;
+
+/// Time constant of the pitch smoothing, in seconds.
+const PITCH_SMOOTHING_SECS: f64 = 0.25;
+
+/// Most samples a [`PositionHistory`] keeps, whatever the frame rate.
+const MAX_HISTORY_SAMPLES: usize = 64;
+
+/// Distance a source can move between two updates before it counts as a teleport.
+///
+/// Well above the enemy's top speed at low frame rates, so only spawns and level changes reset
+/// the history instead of gliding the heard position across the maze.
+const TELEPORT_DISTANCE: f32 = 250.0;
+
+/// Recent positions of a sound source, oldest first.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PositionHistory {
+    /// Timestamped positions, in the order they were recorded
+    samples: VecDeque<(Instant, [f32; 3])>,
+}
+
+impl PositionHistory {
+    /// Records where the source is now and forgets samples older than the longest delay.
+    ///
+    /// One sample older than [`MAX_PROPAGATION_DELAY`] is kept so the oldest moment in the
+    /// window can still be interpolated.
+    ///
+    /// # Arguments
+    /// * `now` - When the position was recorded; must not be earlier than the last sample
+    /// * `position` - Where the source is
+    pub fn push(&mut self, now: Instant, position: [f32; 3]) {
+        self.samples.push_back((now, position));
+
+        let oldest_needed = now.checked_sub(MAX_PROPAGATION_DELAY);
+        while self.samples.len() > MAX_HISTORY_SAMPLES
+            || self
+                .samples
+                .get(1)
+                .zip(oldest_needed)
+                .is_some_and(|(&(time, _), oldest)| time <= oldest)
+        {
+            self.samples.pop_front();
+        }
+    }
+
+    /// Returns where the source was at a moment in the past.
+    ///
+    /// # Arguments
+    /// * `at` - The moment to look up
+    ///
+    /// # Returns
+    /// The position interpolated between the samples around `at`, clamped to the oldest and
+    /// newest samples, or `None` if nothing has been recorded
+    pub fn sample(&self, at: Instant) -> Option<[f32; 3]> {
+        let after = self.samples.partition_point(|&(time, _)| time <= at);
+        if after == 0 {
+            return self.samples.front().map(|&(_, position)| position);
+        }
+        let (before_time, before) = self.samples[after - 1];
+        let Some(&(after_time, after)) = self.samples.get(after) else {
+            return Some(before);
+        };
+
+        let span = after_time.duration_since(before_time).as_secs_f32();
+        let t = at.duration_since(before_time).as_secs_f32() / span;
+        Some(std::array::from_fn(|i| {
+            before[i] + (after[i] - before[i]) * t
+        }))
+    }
+
+    /// Returns the most recent position, if any.
+    pub fn latest(&self) -> Option<[f32; 3]> {
+        self.samples.back().map(|&(_, position)| position)
+    }
+}
+
+/// Returns how long sound takes to reach the listener from a distance.
+///
+/// # Arguments
+/// * `distance` - Distance between source and listener in world units
+///
+/// # Returns
+/// `distance / SPEED_OF_SOUND`, capped at [`MAX_PROPAGATION_DELAY`]
+pub fn propagation_delay(distance: f32) -> Duration {
+    Duration::from_secs_f32(distance.max(0.0) / SPEED_OF_SOUND).min(MAX_PROPAGATION_DELAY)
+}
+
+/// Returns the pitch multiplier for a source moving relative to the listener.
+///
+/// # Arguments
+/// * `radial_speed` - How fast the distance between them is shrinking, in world units per
+///   second; negative when the source is moving away
+///
+/// # Returns
+/// `SPEED_OF_SOUND / (SPEED_OF_SOUND - radial_speed)`, clamped to within
+/// [`MAX_PITCH_SHIFT`] of 1
+pub fn doppler_pitch(radial_speed: f32) -> f64 {
+    let speed = SPEED_OF_SOUND as f64;
+    // Past half the speed of sound the result is clamped anyway, and this keeps clear of zero
+    let radial_speed = (radial_speed as f64).clamp(-speed, speed * 0.5);
+    (speed / (speed - radial_speed)).clamp(1.0 - MAX_PITCH_SHIFT, 1.0 + MAX_PITCH_SHIFT)
+}
+
+/// Eases a pitch toward its target.
+///
+/// # Arguments
+/// * `current` - The pitch last frame
+/// * `target` - The pitch doppler asks for now
+/// * `delta_time` - Seconds since last frame
+///
+/// # Returns
+/// The new pitch, which covers the same fraction of the gap per second at any frame rate
+pub fn smooth_pitch(current: f64, target: f64, delta_time: f64) -> f64 {
+    let blend = 1.0 - (-delta_time.max(0.0) / PITCH_SMOOTHING_SECS).exp();
+    current + (target - current) * blend
+}
+
+/// Distance between two points.
+fn distance(a: [f32; 3], b: [f32; 3]) -> f32 {
+    (0..3).map(|i| (a[i] - b[i]).powi(2)).sum::<f32>().sqrt()
+}
+
+/// Propagation delay and doppler state for one enemy.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EnemyPropagation {
+    /// Where the enemy has really been
+    history: PositionHistory,
+    /// When the last update happened and how far away the enemy really was then
+    last_distance: Option<(Instant, f32)>,
+    /// Smoothed pitch multiplier
+    pitch: f64,
+}
+
+impl Default for EnemyPropagation {
+    /// No history yet, at normal pitch.
+    fn default() -> Self {
+        Self {
+            history: PositionHistory::default(),
+            last_distance: None,
+            pitch: 1.0,
+        }
+    }
+}
+
+impl EnemyPropagation {
+    /// Records where the enemy is and works out how it should sound this frame.
+    ///
+    /// # Arguments
+    /// * `position` - Where the enemy really is
+    /// * `listener` - Where the listener is
+    /// * `now` - The current time
+    ///
+    /// # Returns
+    /// Where the enemy should be heard, lagging by the propagation delay, and the smoothed
+    /// doppler pitch multiplier
+    pub fn update(
+        &mut self,
+        position: [f32; 3],
+        listener: [f32; 3],
+        now: Instant,
+    ) -> ([f32; 3], f64) {
+        let teleported = self
+            .history
+            .latest()
+            .is_some_and(|latest| distance(latest, position) > TELEPORT_DISTANCE);
+        if teleported {
+            *self = Self::default();
+        }
+        self.history.push(now, position);
+
+        let current_distance = distance(position, listener);
+        let heard_at = now
+            .checked_sub(propagation_delay(current_distance))
+            .and_then(|then| self.history.sample(then))
+            .unwrap_or(position);
+
+        if let Some((then, previous)) = self.last_distance {
+            let delta_time = now.saturating_duration_since(then).as_secs_f32();
+            if delta_time > 0.0 {
+                let target = doppler_pitch((previous - current_distance) / delta_time);
+                self.pitch = smooth_pitch(self.pitch, target, delta_time as f64);
+            }
+        }
+        self.last_distance = Some((now, current_distance));
+
+        (heard_at, self.pitch)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FRAME: Duration = Duration::from_millis(16);
+
+    #[test]
+    fn test_history_interpolates_and_forgets_old_samples() {
+        let start = Instant::now();
+        let mut history = PositionHistory::default();
+        assert_eq!(history.sample(start), None);
+
+        history.push(start, [0.0, 0.0, 0.0]);
+        history.push(start + Duration::from_millis(100), [10.0, 0.0, -20.0]);
+        let halfway = history.sample(start + Duration::from_millis(50)).unwrap();
+        assert!((halfway[0] - 5.0).abs() < 1e-4 && (halfway[2] + 10.0).abs() < 1e-4);
+
+        // Outside the recorded span, the nearest end is used
+        assert_eq!(
+            history.sample(start + Duration::from_secs(1)),
+            Some([10.0, 0.0, -20.0])
+        );
+
+        // A long run at a high frame rate stays within the sample cap and the delay window
+        for i in 0..1000 {
+            history.push(
+                start + Duration::from_millis(200) + FRAME / 4 * i,
+                [i as f32; 3],
+            );
+        }
+        assert!(history.samples.len() <= MAX_HISTORY_SAMPLES);
+        let latest = history.samples.back().unwrap().0;
+        let oldest = history.samples.front().unwrap().0;
+        assert!(latest.duration_since(oldest) <= MAX_PROPAGATION_DELAY + FRAME);
+    }
+
+    #[test]
+    fn test_delay_grows_with_distance_up_to_the_cap() {
+        assert_eq!(propagation_delay(0.0), Duration::ZERO);
+        let near = propagation_delay(SPEED_OF_SOUND * 0.05);
+        assert!((near.as_secs_f32() - 0.05).abs() < 1e-4);
+        assert_eq!(
+            propagation_delay(SPEED_OF_SOUND * 10.0),
+            MAX_PROPAGATION_DELAY
+        );
+    }
+
+    #[test]
+    fn test_stationary_source_has_no_shift() {
+        assert_eq!(doppler_pitch(0.0), 1.0);
+
+        let start = Instant::now();
+        let mut propagation = EnemyPropagation::default();
+        for i in 0..60 {
+            let (heard_at, pitch) =
+                propagation.update([300.0, 0.0, 40.0], [0.0; 3], start + FRAME * i);
+            assert_eq!(heard_at, [300.0, 0.0, 40.0]);
+            assert_eq!(pitch, 1.0);
+        }
+    }
+
+    #[test]
+    fn test_approaching_at_constant_speed_settles_on_a_constant_rise() {
+        let expected = doppler_pitch(300.0);
+        assert!(expected > 1.0 && expected < 1.0 + MAX_PITCH_SHIFT);
+        assert_eq!(doppler_pitch(SPEED_OF_SOUND * 10.0), 1.0 + MAX_PITCH_SHIFT);
+        assert_eq!(doppler_pitch(-SPEED_OF_SOUND * 10.0), 1.0 - MAX_PITCH_SHIFT);
+
+        let start = Instant::now();
+        let mut propagation = EnemyPropagation::default();
+        let mut pitches = Vec::new();
+        for i in 0..180 {
+            let x = 2000.0 - 300.0 * (FRAME * i).as_secs_f32();
+            let (heard_at, pitch) = propagation.update([x, 0.0, 0.0], [0.0; 3], start + FRAME * i);
+            // The heard position trails the real one, farther back when farther away
+            assert!(heard_at[0] >= x);
+            pitches.push(pitch);
+        }
+
+        let settled = &pitches[120..];
+        assert!(
+            settled.iter().all(|pitch| (pitch - expected).abs() < 0.002),
+            "{:?}",
+            settled
+        );
+    }
+
+    #[test]
+    fn test_passing_by_flips_the_shift_smoothly() {
+        let start = Instant::now();
+        let mut propagation = EnemyPropagation::default();
+        let mut pitches = Vec::new();
+        for i in 0..240 {
+            let x = -600.0 + 500.0 * (FRAME * i).as_secs_f32();
+            let (_, pitch) = propagation.update([x, 0.0, 60.0], [0.0; 3], start + FRAME * i);
+            pitches.push(pitch);
+        }
+
+        assert!(pitches[60] > 1.0, "approaching should raise the pitch");
+        assert!(pitches[239] < 1.0, "leaving should lower the pitch");
+
+        // The flip is spread over many frames instead of happening in one step
+        let swing = pitches[60] - pitches[239];
+        let largest_step = pitches
+            .windows(2)
+            .map(|pair| (pair[1] - pair[0]).abs())
+            .fold(0.0, f64::max);
+        assert!(
+            largest_step < swing / 10.0,
+            "pitch jumped by {}",
+            largest_step
+        );
+    }
+
+    #[test]
+    fn test_teleporting_starts_over() {
+        let start = Instant::now();
+        let mut propagation = EnemyPropagation::default();
+        let mut pitch = 1.0;
+        for i in 0..30 {
+            let position = [1000.0 - 10.0 * i as f32, 0.0, 0.0];
+            (_, pitch) = propagation.update(position, [0.0; 3], start + FRAME * i);
+        }
+        assert!(pitch > 1.0);
+
+        let (heard_at, pitch) =
+            propagation.update([-2000.0, 0.0, 0.0], [0.0; 3], start + FRAME * 30);
+        assert_eq!(heard_at, [-2000.0, 0.0, 0.0]);
+        assert_eq!(pitch, 1.0);
+    }
+}
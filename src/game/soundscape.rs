@@ -0,0 +1,713 @@
+//! Ambient soundscapes that fill the quiet between enemy encounters.
+//!
+//! Every [`LevelTheme`] has a [`SoundscapeProfile`]: a few looping bed layers (a low drone,
+//! wind, a distant rumble) that play for the whole level, and a set of sparse accents (drips,
+//! creaks, far-off knocks) fired at random intervals with random volume, pan, and pitch within
+//! the profile's ranges. Nothing is recorded: the beds and accents are synthesized once when
+//! the audio manager starts, see [`bed_samples`] and [`accent_samples`].
+//!
+//! A [`Soundscape`] schedules the accents of one level. It is seeded from the level's seed, so a
+//! retried level or a shared run seed hears the same accents at the same moments, and it runs
+//! on its own [`GameClock`], advanced only while the level is being played, so it holds still
+//! while paused. No more than [`MAX_PLAYING_ACCENTS`] accents ever overlap. The soundscape also
+//! fades the whole ambience in over the first [`FADE_IN_SECS`] of a level and out over
+//! [`FADE_OUT_SECS`] once the exit cutscene starts; the audio manager applies that
+//! [`Soundscape::gain`] to its ambience bus, on top of the player's ambience volume.
+//!
+//! # Usage
+//!
+//! ```rust,ignore
+//! use mirador::game::soundscape::Soundscape;
+//! use mirador::game::theme::LevelTheme;
+//!
+//! let mut soundscape = Soundscape::new(LevelTheme::Rain, 42);
+//!
+//! // Once per frame while the level is being played
+//! for shot in soundscape.update(1.0 / 60.0) {
+//!     println!("{:?} at {:.1} dB", shot.accent, shot.volume_db);
+//! }
+//! let gain = soundscape.gain();
+//! assert!(gain > 0.0 && gain < 0.1);
+//! ```
+
+use crate::game::clock::GameClock;
+use crate::game::theme::LevelTheme;
+use rand::prelude::*;
+use std::f32::consts::TAU;
+use std::time::Duration;
+
+/// Sample rate the beds and accents are synthesized at.
+pub const SAMPLE_RATE: u32 = 22_050;
+
+/// Length of each bed loop, in seconds.
+pub const BED_LOOP_SECS: f32 = 8.0;
+
+/// Most accents that may be playing at once.
+pub const MAX_PLAYING_ACCENTS: usize = 3;
+
+/// Seconds the ambience takes to fade in at the start of a level.
+pub const FADE_IN_SECS: f32 = 4.0;
+
+/// Seconds the ambience takes to fade out once the exit cutscene starts.
+pub const FADE_OUT_SECS: f32 = 0.8;
+
+/// Step the pause menu's ambience button turns the volume up by.
+pub const AMBIENCE_VOLUME_STEP: f32 = 0.25;
+
+/// Salt mixed into the level seed so the accents don't correlate with the level's events.
+const SOUNDSCAPE_SEED_SALT: u64 = 0x0073_6f75_6e64;
+
+/// Seed of the noise the beds and accents are synthesized from, so they sound the same on
+/// every launch.
+const SYNTH_SEED: u64 = 7;
+
+/// Seconds at the end of a noise bed crossfaded into its start, so the loop has no seam.
+const LOOP_CROSSFADE_SECS: f32 = 0.5;
+
+/// Loudest sample of a synthesized bed.
+const BED_PEAK: f32 = 0.5;
+
+/// Loudest sample of a synthesized accent.
+const ACCENT_PEAK: f32 = 0.8;
+
+/// A looping layer of the ambience.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BedLayer {
+    /// A low, slowly breathing chord
+    Drone,
+    /// Filtered noise gusting slowly
+    Wind,
+    /// Deep noise, like something heavy far off
+    Rumble,
+}
+
+impl BedLayer {
+    /// Every bed layer, in the order the audio manager keeps their sounds.
+    pub const ALL: [BedLayer; 3] = [BedLayer::Drone, BedLayer::Wind, BedLayer::Rumble];
+}
+
+/// A one-shot sound fired now and then over the beds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Accent {
+    /// A drop of water landing
+    Drip,
+    /// Old wood straining
+    Creak,
+    /// Two dull knocks somewhere in the walls
+    Knock,
+}
+
+impl Accent {
+    /// Every accent, in the order the audio manager keeps their sounds.
+    pub const ALL: [Accent; 3] = [Accent::Drip, Accent::Creak, Accent::Knock];
+
+    /// Returns the length of the accent's sound at its natural pitch, in seconds.
+    pub fn length_secs(self) -> f32 {
+        match self {
+            Accent::Drip => 0.35,
+            Accent::Creak => 0.9,
+            Accent::Knock => 0.6,
+        }
+    }
+}
+
+/// How loud a bed layer plays in a profile.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BedSpec {
+    /// The layer
+    pub layer: BedLayer,
+    /// Volume of the layer, in decibels
+    pub volume_db: f32,
+}
+
+/// How an accent is randomized in a profile.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AccentSpec {
+    /// The accent
+    pub accent: Accent,
+    /// How often the accent is picked, relative to the profile's other accents
+    pub weight: u32,
+    /// Quietest and loudest the accent plays, in decibels
+    pub volume_db: (f32, f32),
+    /// Slowest and fastest playback rate; 1.0 is the natural pitch
+    pub playback_rate: (f64, f64),
+}
+
+/// The beds and accents of a theme's soundscape.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SoundscapeProfile {
+    /// Bed layers that loop for the whole level
+    pub beds: &'static [BedSpec],
+    /// Accents fired over the beds
+    pub accents: &'static [AccentSpec],
+    /// Shortest and longest wait between accents, in seconds
+    pub interval_secs: (f32, f32),
+    /// Farthest an accent is panned to either side, from 0.0 (centered) to 1.0
+    pub max_pan: f32,
+}
+
+/// Dust levels: a dry, still maze where the wood creaks and something knocks now and then.
+const DUST_PROFILE: SoundscapeProfile = SoundscapeProfile {
+    beds: &[
+        BedSpec {
+            layer: BedLayer::Drone,
+            volume_db: -22.0,
+        },
+        BedSpec {
+            layer: BedLayer::Wind,
+            volume_db: -30.0,
+        },
+        BedSpec {
+            layer: BedLayer::Rumble,
+            volume_db: -28.0,
+        },
+    ],
+    accents: &[
+        AccentSpec {
+            accent: Accent::Creak,
+            weight: 3,
+            volume_db: (-30.0, -20.0),
+            playback_rate: (0.8, 1.15),
+        },
+        AccentSpec {
+            accent: Accent::Knock,
+            weight: 2,
+            volume_db: (-32.0, -22.0),
+            playback_rate: (0.85, 1.1),
+        },
+        AccentSpec {
+            accent: Accent::Drip,
+            weight: 1,
+            volume_db: (-34.0, -26.0),
+            playback_rate: (0.9, 1.2),
+        },
+    ],
+    interval_secs: (4.0, 11.0),
+    max_pan: 0.8,
+};
+
+/// Rain levels: gusting wind and water dripping everywhere, the odd creak underneath.
+const RAIN_PROFILE: SoundscapeProfile = SoundscapeProfile {
+    beds: &[
+        BedSpec {
+            layer: BedLayer::Drone,
+            volume_db: -28.0,
+        },
+        BedSpec {
+            layer: BedLayer::Wind,
+            volume_db: -22.0,
+        },
+        BedSpec {
+            layer: BedLayer::Rumble,
+            volume_db: -26.0,
+        },
+    ],
+    accents: &[
+        AccentSpec {
+            accent: Accent::Drip,
+            weight: 5,
+            volume_db: (-30.0, -18.0),
+            playback_rate: (0.75, 1.35),
+        },
+        AccentSpec {
+            accent: Accent::Creak,
+            weight: 1,
+            volume_db: (-32.0, -24.0),
+            playback_rate: (0.8, 1.0),
+        },
+    ],
+    interval_secs: (1.5, 5.0),
+    max_pan: 1.0,
+};
+
+impl SoundscapeProfile {
+    /// Returns the soundscape of a theme.
+    pub fn for_theme(theme: LevelTheme) -> Self {
+        match theme {
+            LevelTheme::Dust => DUST_PROFILE,
+            LevelTheme::Rain => RAIN_PROFILE,
+        }
+    }
+}
+
+/// One accent to play now.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AccentShot {
+    /// Which accent to play
+    pub accent: Accent,
+    /// Volume in decibels
+    pub volume_db: f32,
+    /// Stereo position, from -1.0 (left) to 1.0 (right)
+    pub panning: f32,
+    /// Playback rate; 1.0 is the natural pitch
+    pub playback_rate: f64,
+}
+
+impl AccentShot {
+    /// Returns how long the shot plays for at its playback rate.
+    pub fn duration(&self) -> Duration {
+        Duration::from_secs_f64(self.accent.length_secs() as f64 / self.playback_rate)
+    }
+}
+
+/// Schedules a level's accents and fades its ambience in and out.
+#[derive(Debug, Clone)]
+pub struct Soundscape {
+    /// The theme's beds and accents
+    profile: SoundscapeProfile,
+    /// Picks when accents fire and how they sound, seeded from the level
+    rng: StdRng,
+    /// Time the level has been played for
+    clock: GameClock,
+    /// When the next accent fires
+    next_accent: Duration,
+    /// When each accent still counted as playing ends
+    playing: Vec<Duration>,
+    /// When the fade out started, once the exit cutscene has
+    fade_out_from: Option<Duration>,
+}
+
+impl Soundscape {
+    /// Creates the soundscape for a level, silent until it fades in.
+    ///
+    /// # Arguments
+    /// * `theme` - The level's theme
+    /// * `seed` - The level's seed, see [`crate::game::events::level_seed`]
+    pub fn new(theme: LevelTheme, seed: u64) -> Self {
+        let profile = SoundscapeProfile::for_theme(theme);
+        let mut rng = StdRng::seed_from_u64(seed ^ SOUNDSCAPE_SEED_SALT);
+        let next_accent = roll_interval(&profile, &mut rng);
+        Self {
+            profile,
+            rng,
+            clock: GameClock::new(),
+            next_accent,
+            playing: Vec::new(),
+            fade_out_from: None,
+        }
+    }
+
+    /// Returns the profile the soundscape was made from.
+    pub fn profile(&self) -> &SoundscapeProfile {
+        &self.profile
+    }
+
+    /// Advances the soundscape by one frame.
+    ///
+    /// Should be called once per frame while the level is being played, including the exit
+    /// cutscene; not calling it (e.g. while paused) holds everything where it is.
+    ///
+    /// # Arguments
+    /// * `delta_time` - Seconds since the last frame
+    ///
+    /// # Returns
+    /// The accents to play this frame, usually none
+    pub fn update(&mut self, delta_time: f32) -> Vec<AccentShot> {
+        self.clock.advance_secs(delta_time);
+        let now = self.clock.now();
+
+        let mut shots = Vec::new();
+        while self.next_accent <= now {
+            let fire_at = self.next_accent;
+            // Rolled even when it can't play, so the sequence only depends on the seed
+            let shot = roll_shot(&self.profile, &mut self.rng);
+            self.next_accent += roll_interval(&self.profile, &mut self.rng);
+
+            self.playing.retain(|&end| end > fire_at);
+            let end = fire_at + shot.duration();
+            if self.fade_out_from.is_some() || self.playing.len() >= MAX_PLAYING_ACCENTS {
+                continue;
+            }
+            self.playing.push(end);
+            // A long hitch can skip past an accent entirely
+            if end > now {
+                shots.push(shot);
+            }
+        }
+        shots
+    }
+
+    /// Starts fading the ambience out, for the exit cutscene.
+    ///
+    /// No accents fire once the fade has started. Calling it again keeps the first start.
+    pub fn begin_fade_out(&mut self) {
+        self.fade_out_from.get_or_insert(self.clock.now());
+    }
+
+    /// Returns how loud the ambience is right now, from 0.0 (silent) to 1.0.
+    pub fn gain(&self) -> f32 {
+        let now = self.clock.now().as_secs_f32();
+        let fade_in = (now / FADE_IN_SECS).clamp(0.0, 1.0);
+        let fade_out = self.fade_out_from.map_or(1.0, |from| {
+            1.0 - ((now - from.as_secs_f32()) / FADE_OUT_SECS).clamp(0.0, 1.0)
+        });
+        fade_in * fade_out
+    }
+
+    /// Returns how many accents are counted as still playing.
+    pub fn playing_accents(&self) -> usize {
+        let now = self.clock.now();
+        self.playing.iter().filter(|&&end| end > now).count()
+    }
+}
+
+/// Returns the ambience volume the pause menu's button moves to next.
+///
+/// The volume goes up by [`AMBIENCE_VOLUME_STEP`] and wraps from full back to off.
+///
+/// # Arguments
+/// * `volume` - The current volume, from 0.0 to 1.0
+pub fn next_ambience_volume(volume: f32) -> f32 {
+    if volume >= 1.0 - f32::EPSILON {
+        return 0.0;
+    }
+    let steps = (volume / AMBIENCE_VOLUME_STEP + 1e-3).floor() + 1.0;
+    (steps * AMBIENCE_VOLUME_STEP).min(1.0)
+}
+
+/// Rolls the wait until the next accent.
+fn roll_interval(profile: &SoundscapeProfile, rng: &mut StdRng) -> Duration {
+    let (min, max) = profile.interval_secs;
+    Duration::from_secs_f32(rng.gen_range(min..=max))
+}
+
+/// Picks an accent by weight and randomizes how it sounds.
+fn roll_shot(profile: &SoundscapeProfile, rng: &mut StdRng) -> AccentShot {
+    let total: u32 = profile.accents.iter().map(|spec| spec.weight).sum();
+    let mut pick = rng.gen_range(0..total.max(1));
+    let spec = profile
+        .accents
+        .iter()
+        .find(|spec| {
+            let found = pick < spec.weight;
+            pick = pick.saturating_sub(spec.weight);
+            found
+        })
+        .unwrap_or(&profile.accents[0]);
+
+    let (quiet, loud) = spec.volume_db;
+    let (slow, fast) = spec.playback_rate;
+    AccentShot {
+        accent: spec.accent,
+        volume_db: rng.gen_range(quiet..=loud),
+        panning: rng.gen_range(-profile.max_pan..=profile.max_pan),
+        playback_rate: rng.gen_range(slow..=fast),
+    }
+}
+
+/// Synthesizes one loop of a bed layer.
+///
+/// The loop is [`BED_LOOP_SECS`] long and its end runs straight into its start.
+///
+/// # Arguments
+/// * `layer` - The layer to synthesize
+///
+/// # Returns
+/// Mono samples at [`SAMPLE_RATE`]
+pub fn bed_samples(layer: BedLayer) -> Vec<f32> {
+    let rate = SAMPLE_RATE as f32;
+    let len = (BED_LOOP_SECS * rate) as usize;
+    // Tones and swells complete whole cycles over the loop, so they wrap cleanly
+    let looped = |hz: f32| (hz * BED_LOOP_SECS).round() / BED_LOOP_SECS;
+    let mut rng = StdRng::seed_from_u64(SYNTH_SEED);
+
+    let samples = match layer {
+        BedLayer::Drone => {
+            let partials = [(55.0, 1.0), (82.5, 0.6), (110.0, 0.35), (164.8, 0.15)];
+            (0..len)
+                .map(|i| {
+                    let t = i as f32 / rate;
+                    let breath = 0.75 + 0.25 * (TAU * looped(0.25) * t).sin();
+                    let tone: f32 = partials
+                        .iter()
+                        .map(|&(hz, level)| level * (TAU * looped(hz) * t).sin())
+                        .sum();
+                    tone * breath
+                })
+                .collect()
+        }
+        BedLayer::Wind => {
+            let noise = filtered_noise(&mut rng, len, |t| {
+                // The cutoff sweeps slowly, so the wind gusts
+                let gust = 0.5 + 0.5 * (TAU * looped(0.125) * t).sin();
+                250.0 + 900.0 * gust
+            });
+            let swell = |t: f32| 0.55 + 0.45 * (TAU * looped(0.125) * t - 0.6).sin();
+            seamless(
+                noise
+                    .iter()
+                    .enumerate()
+                    .map(|(i, sample)| sample * swell(i as f32 / rate))
+                    .collect(),
+                len,
+            )
+        }
+        BedLayer::Rumble => {
+            let noise = filtered_noise(&mut rng, len, |_| 45.0);
+            let swell = |t: f32| 0.7 + 0.3 * (TAU * looped(0.375) * t).sin();
+            seamless(
+                noise
+                    .iter()
+                    .enumerate()
+                    .map(|(i, sample)| sample * swell(i as f32 / rate))
+                    .collect(),
+                len,
+            )
+        }
+    };
+    normalized(samples, BED_PEAK)
+}
+
+/// Synthesizes an accent at its natural pitch.
+///
+/// # Arguments
+/// * `accent` - The accent to synthesize
+///
+/// # Returns
+/// Mono samples at [`SAMPLE_RATE`], [`Accent::length_secs`] long and silent at both ends
+pub fn accent_samples(accent: Accent) -> Vec<f32> {
+    let rate = SAMPLE_RATE as f32;
+    let len = (accent.length_secs() * rate) as usize;
+    let mut rng = StdRng::seed_from_u64(SYNTH_SEED);
+    let mut phase = 0.0_f32;
+
+    let samples: Vec<f32> = match accent {
+        Accent::Drip => (0..len)
+            .map(|i| {
+                let t = i as f32 / rate;
+                // A quick downward chirp that rings out
+                let hz = 700.0 + 900.0 * (-t / 0.015).exp();
+                phase += TAU * hz / rate;
+                phase.sin() * (-t / 0.06).exp()
+            })
+            .collect(),
+        Accent::Creak => {
+            let mut smoothed = 0.0;
+            (0..len)
+                .map(|i| {
+                    let t = i as f32 / rate;
+                    let hz = 85.0 + 25.0 * (TAU * 1.3 * t).sin();
+                    phase = (phase + hz / rate).fract();
+                    // A sawtooth softened by a low-pass, chattering as the wood catches
+                    let saw = 2.0 * phase - 1.0;
+                    smoothed += (saw - smoothed) * 0.25;
+                    let chatter = 0.5 + 0.5 * (TAU * 22.0 * t).sin().abs();
+                    let envelope = (t / 0.12).min(1.0) * (1.0 - t / accent.length_secs());
+                    smoothed * chatter * envelope
+                })
+                .collect()
+        }
+        Accent::Knock => (0..len)
+            .map(|i| {
+                let t = i as f32 / rate;
+                [0.0, 0.22]
+                    .iter()
+                    .filter(|&&start| t >= start)
+                    .map(|&start| {
+                        let t = t - start;
+                        let thump = (TAU * (60.0 + 30.0 * (-t / 0.03).exp()) * t).sin();
+                        let click = rng.gen_range(-1.0..1.0) * (-t / 0.006).exp();
+                        (thump + 0.4 * click) * (-t / 0.05).exp()
+                    })
+                    .sum()
+            })
+            .collect(),
+    };
+
+    // Ramp both ends so a cut-off tail or a noisy attack can't click
+    let ramp = (0.004 * rate) as usize;
+    let samples = samples
+        .iter()
+        .enumerate()
+        .map(|(i, &sample)| {
+            let edge = i.min(len - 1 - i).min(ramp) as f32 / ramp as f32;
+            sample * edge
+        })
+        .collect();
+    normalized(samples, ACCENT_PEAK)
+}
+
+/// Low-pass filters white noise with a cutoff that can change over time.
+///
+/// Makes [`LOOP_CROSSFADE_SECS`] more than `len` samples, for [`seamless`] to fold back in.
+fn filtered_noise(rng: &mut StdRng, len: usize, cutoff_hz: impl Fn(f32) -> f32) -> Vec<f32> {
+    let rate = SAMPLE_RATE as f32;
+    let extra = (LOOP_CROSSFADE_SECS * rate) as usize;
+    let mut state = 0.0;
+    (0..len + extra)
+        .map(|i| {
+            let t = (i % len) as f32 / rate;
+            let alpha = 1.0 - (-TAU * cutoff_hz(t) / rate).exp();
+            state += (rng.gen_range(-1.0..1.0) - state) * alpha;
+            state
+        })
+        .collect()
+}
+
+/// Folds the samples past `len` back over the start with an equal-power crossfade.
+///
+/// The last kept sample then runs straight into the first, so the loop has no seam.
+fn seamless(mut samples: Vec<f32>, len: usize) -> Vec<f32> {
+    let extra = samples.len() - len;
+    for i in 0..extra {
+        let fade = i as f32 / extra as f32 * std::f32::consts::FRAC_PI_2;
+        samples[i] = samples[i] * fade.sin() + samples[len + i] * fade.cos();
+    }
+    samples.truncate(len);
+    samples
+}
+
+/// Scales the samples so the loudest one is `peak`.
+fn normalized(samples: Vec<f32>, peak: f32) -> Vec<f32> {
+    let loudest = samples
+        .iter()
+        .fold(0.0_f32, |max, sample| max.max(sample.abs()));
+    if loudest == 0.0 {
+        return samples;
+    }
+    samples
+        .iter()
+        .map(|sample| sample * peak / loudest)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Plays a soundscape for `secs` at 60 FPS, collecting every accent with its frame.
+    fn play(soundscape: &mut Soundscape, secs: f32) -> Vec<(usize, AccentShot)> {
+        let frames = (secs * 60.0) as usize;
+        (0..frames)
+            .flat_map(|frame| {
+                soundscape
+                    .update(1.0 / 60.0)
+                    .into_iter()
+                    .map(move |shot| (frame, shot))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_accents_replay_identically_from_the_same_seed() {
+        let first = play(&mut Soundscape::new(LevelTheme::Dust, 9), 120.0);
+        let second = play(&mut Soundscape::new(LevelTheme::Dust, 9), 120.0);
+        let other = play(&mut Soundscape::new(LevelTheme::Dust, 10), 120.0);
+        assert!(!first.is_empty());
+        assert_eq!(first, second);
+        assert_ne!(first, other);
+    }
+
+    #[test]
+    fn test_accents_stay_within_the_profile() {
+        for theme in [LevelTheme::Dust, LevelTheme::Rain] {
+            let profile = SoundscapeProfile::for_theme(theme);
+            let (min, max) = profile.interval_secs;
+            let shots = play(&mut Soundscape::new(theme, 3), 600.0);
+            assert!(shots.len() as f32 >= 600.0 / max);
+            assert!(shots.len() as f32 <= 600.0 / min + 1.0);
+
+            for (_, shot) in shots {
+                let spec = profile
+                    .accents
+                    .iter()
+                    .find(|spec| spec.accent == shot.accent)
+                    .expect("accent not in the profile");
+                assert!((spec.volume_db.0..=spec.volume_db.1).contains(&shot.volume_db));
+                assert!(
+                    (spec.playback_rate.0..=spec.playback_rate.1).contains(&shot.playback_rate)
+                );
+                assert!(shot.panning.abs() <= profile.max_pan);
+            }
+        }
+    }
+
+    #[test]
+    fn test_overlapping_accents_are_bounded() {
+        let mut soundscape = Soundscape::new(LevelTheme::Rain, 5);
+        // A ten minute hitch fires every accent it skipped past at once
+        let shots = soundscape.update(600.0);
+        assert!(shots.len() <= MAX_PLAYING_ACCENTS);
+        assert!(soundscape.playing_accents() <= MAX_PLAYING_ACCENTS);
+
+        for _ in 0..36_000 {
+            soundscape.update(1.0 / 60.0);
+            assert!(soundscape.playing_accents() <= MAX_PLAYING_ACCENTS);
+        }
+    }
+
+    #[test]
+    fn test_ambience_fades_in_and_out_on_the_level_clock() {
+        let mut soundscape = Soundscape::new(LevelTheme::Dust, 1);
+        assert_eq!(soundscape.gain(), 0.0);
+        soundscape.update(FADE_IN_SECS / 2.0);
+        assert!((soundscape.gain() - 0.5).abs() < 1e-4);
+        soundscape.update(FADE_IN_SECS);
+        assert_eq!(soundscape.gain(), 1.0);
+
+        soundscape.begin_fade_out();
+        soundscape.update(FADE_OUT_SECS / 2.0);
+        soundscape.begin_fade_out();
+        assert!((soundscape.gain() - 0.5).abs() < 1e-4);
+        soundscape.update(FADE_OUT_SECS);
+        assert_eq!(soundscape.gain(), 0.0);
+        assert!(play(&mut soundscape, 60.0).is_empty());
+    }
+
+    #[test]
+    fn test_ambience_volume_steps_up_and_wraps() {
+        let mut volume = 1.0;
+        let steps: Vec<f32> = (0..6)
+            .map(|_| {
+                volume = next_ambience_volume(volume);
+                volume
+            })
+            .collect();
+        assert_eq!(steps, [0.0, 0.25, 0.5, 0.75, 1.0, 0.0]);
+        // A volume between steps, e.g. from a hand-edited settings file, rounds up
+        assert_eq!(next_ambience_volume(0.6), 0.75);
+    }
+
+    #[test]
+    fn test_beds_loop_without_a_seam() {
+        for layer in BedLayer::ALL {
+            let samples = bed_samples(layer);
+            assert_eq!(samples.len(), (BED_LOOP_SECS * SAMPLE_RATE as f32) as usize);
+            let peak = samples.iter().fold(0.0_f32, |max, s| max.max(s.abs()));
+            assert!(
+                (peak - BED_PEAK).abs() < 1e-4,
+                "{:?} peaks at {}",
+                layer,
+                peak
+            );
+
+            // The jump from the last sample back to the first is no bigger than any other step
+            let largest_step = samples
+                .windows(2)
+                .map(|pair| (pair[1] - pair[0]).abs())
+                .fold(0.0, f32::max);
+            let seam = (samples[0] - samples[samples.len() - 1]).abs();
+            assert!(
+                seam <= largest_step,
+                "{:?} seam {} > {}",
+                layer,
+                seam,
+                largest_step
+            );
+        }
+    }
+
+    #[test]
+    fn test_accents_are_silent_at_both_ends() {
+        for accent in Accent::ALL {
+            let samples = accent_samples(accent);
+            assert_eq!(
+                samples.len(),
+                (accent.length_secs() * SAMPLE_RATE as f32) as usize
+            );
+            assert_eq!(samples[0], 0.0);
+            assert_eq!(samples[samples.len() - 1], 0.0);
+            assert!(samples.iter().all(|s| s.abs() <= ACCENT_PEAK + 1e-4));
+        }
+    }
+}
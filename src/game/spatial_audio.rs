@@ -0,0 +1,335 @@
+//! How positioned sounds are mixed for the player's output device.
+//!
+//! Kira pans a spatial track by comparing the direction of the sound with the direction each
+//! ear faces, which reads well on headphones but smears on speakers, where both ears hear both
+//! speakers. The player picks an [`OutputProfile`], and [`SpatialMix::for_profile`] turns it into
+//! how strongly sounds are panned and how much quieter sounds behind the listener get:
+//!
+//! - **Headphones** pan a little softer than full, since nothing bleeds between the ears, and
+//!   turn sounds behind the listener down so front and back are easy to tell apart. With HRTF
+//!   on and a backend that supports it, the enemy is rendered binaurally instead.
+//! - **Speakers** pan as wide as they go and only dip sounds behind the listener slightly, since
+//!   speakers in front of the player can't place anything behind them anyway.
+//! - **Mono** folds the whole mix down to one channel and keeps only distance attenuation, for
+//!   players who hear with one ear or play on a single speaker.
+//!
+//! No audio backend the game runs on renders HRTF yet ([`BACKEND_HAS_HRTF`]), so the HRTF
+//! setting shows as unavailable instead of doing nothing.
+//!
+//! # Usage
+//!
+//! ```rust,ignore
+//! let mix = SpatialMix::for_profile(settings.audio_output, settings.hrtf && supports_hrtf);
+//! track.set_spatialization_strength(mix.spatialization_strength, tween);
+//! sound.set_volume(rear_gain_db(&mix, listener, yaw, enemy_position), tween);
+//! downmix.set_enabled(mix.downmix_to_mono);
+//! ```
+
+use kira::Frame;
+use kira::effect::{Effect, EffectBuilder};
+use kira::info::Info;
+use serde::{Deserialize, Serialize};
+use std::f32::consts::TAU;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+/// Whether the audio backend can render sounds through head-related transfer functions.
+///
+/// Kira, the only backend, pans by the direction each ear faces and has no HRTF processing.
+pub const BACKEND_HAS_HRTF: bool = false;
+
+/// How long the audio test plays.
+pub const AUDIO_TEST_DURATION: Duration = Duration::from_secs(6);
+
+/// Times the audio test sound circles the listener.
+const AUDIO_TEST_LAPS: f32 = 2.0;
+
+/// Distance the audio test sound circles at, in world units; close enough to be loud.
+const AUDIO_TEST_RADIUS: f32 = 100.0;
+
+/// Distance below which a sound is treated as coming from the listener's position.
+const MIN_DIRECTION_DISTANCE: f32 = 1.0;
+
+/// The kind of device the player listens through.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum OutputProfile {
+    /// Headphones or earbuds, one channel per ear
+    Headphones,
+    /// Stereo speakers in front of the player
+    #[default]
+    Speakers,
+    /// A single speaker, or one ear
+    Mono,
+}
+
+impl OutputProfile {
+    /// Returns the profile after this one, wrapping around.
+    pub fn next(self) -> Self {
+        match self {
+            OutputProfile::Headphones => OutputProfile::Speakers,
+            OutputProfile::Speakers => OutputProfile::Mono,
+            OutputProfile::Mono => OutputProfile::Headphones,
+        }
+    }
+
+    /// Returns the short name shown on the settings button.
+    pub fn label(self) -> &'static str {
+        match self {
+            OutputProfile::Headphones => "Headphones",
+            OutputProfile::Speakers => "Speakers",
+            OutputProfile::Mono => "Mono",
+        }
+    }
+}
+
+/// How positioned sounds are mixed for one output profile.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SpatialMix {
+    /// How far sounds are panned toward the ear facing them, from 0.0 (not at all) to 1.0
+    pub spatialization_strength: f32,
+    /// Volume change of a sound directly behind the listener, in decibels
+    pub rear_attenuation_db: f32,
+    /// Whether the enemy is rendered binaurally, which needs [`BACKEND_HAS_HRTF`]
+    pub binaural: bool,
+    /// Whether the whole mix is folded down to one channel
+    pub downmix_to_mono: bool,
+}
+
+impl SpatialMix {
+    /// Picks the mix for a profile.
+    ///
+    /// # Arguments
+    /// * `profile` - The device the player listens through
+    /// * `hrtf` - Whether HRTF is both turned on and supported by the backend
+    pub fn for_profile(profile: OutputProfile, hrtf: bool) -> Self {
+        match profile {
+            OutputProfile::Headphones => Self {
+                spatialization_strength: 0.8,
+                rear_attenuation_db: -6.0,
+                binaural: hrtf,
+                downmix_to_mono: false,
+            },
+            OutputProfile::Speakers => Self {
+                spatialization_strength: 1.0,
+                rear_attenuation_db: -2.0,
+                binaural: false,
+                downmix_to_mono: false,
+            },
+            OutputProfile::Mono => Self {
+                spatialization_strength: 0.0,
+                rear_attenuation_db: 0.0,
+                binaural: false,
+                downmix_to_mono: true,
+            },
+        }
+    }
+}
+
+impl Default for SpatialMix {
+    fn default() -> Self {
+        Self::for_profile(OutputProfile::default(), false)
+    }
+}
+
+/// Returns the listener's forward and right directions on the ground, as (x, z) pairs.
+///
+/// # Arguments
+/// * `yaw` - The player's yaw in degrees; 0 faces -z
+fn ground_axes(yaw: f32) -> ([f32; 2], [f32; 2]) {
+    let (sin_yaw, cos_yaw) = yaw.to_radians().sin_cos();
+    ([-sin_yaw, -cos_yaw], [cos_yaw, -sin_yaw])
+}
+
+/// Returns the listener's orientation for a yaw, as an `[x, y, z, w]` quaternion.
+///
+/// Kira's listener faces -z with +x to its right, like the player at a yaw of 0, so the
+/// orientation is a turn of `yaw` about the y axis.
+///
+/// # Arguments
+/// * `yaw` - The player's yaw in degrees
+pub fn listener_orientation(yaw: f32) -> [f32; 4] {
+    let (sin_half, cos_half) = (yaw.to_radians() / 2.0).sin_cos();
+    [0.0, sin_half, 0.0, cos_half]
+}
+
+/// Returns how much a sound is turned down for coming from behind the listener.
+///
+/// Nothing for sounds in front or level with the listener, rising to the mix's full rear
+/// attenuation for a sound straight behind.
+///
+/// # Arguments
+/// * `mix` - The mix of the current output profile
+/// * `listener` - Where the listener is
+/// * `yaw` - The listener's yaw in degrees
+/// * `source` - Where the sound is
+///
+/// # Returns
+/// The volume change in decibels, never above 0.0
+pub fn rear_gain_db(mix: &SpatialMix, listener: [f32; 3], yaw: f32, source: [f32; 3]) -> f32 {
+    let offset = [source[0] - listener[0], source[2] - listener[2]];
+    let distance = offset[0].hypot(offset[1]);
+    if distance < MIN_DIRECTION_DISTANCE {
+        return 0.0;
+    }
+    let (forward, _right) = ground_axes(yaw);
+    let facing = (offset[0] * forward[0] + offset[1] * forward[1]) / distance;
+    mix.rear_attenuation_db * (-facing).max(0.0)
+}
+
+/// Returns where the audio test sound is a while after it started.
+///
+/// It starts straight ahead of the listener and circles clockwise, passing the right ear first,
+/// so the player can hear it sweep through every direction.
+///
+/// # Arguments
+/// * `listener` - Where the listener is
+/// * `yaw` - The listener's yaw in degrees
+/// * `elapsed` - Time since the test started
+pub fn audio_test_position(listener: [f32; 3], yaw: f32, elapsed: Duration) -> [f32; 3] {
+    let progress = elapsed.as_secs_f32() / AUDIO_TEST_DURATION.as_secs_f32();
+    let (sin_angle, cos_angle) = (progress * AUDIO_TEST_LAPS * TAU).sin_cos();
+    let (forward, right) = ground_axes(yaw);
+    [
+        listener[0] + AUDIO_TEST_RADIUS * (forward[0] * cos_angle + right[0] * sin_angle),
+        listener[1],
+        listener[2] + AUDIO_TEST_RADIUS * (forward[1] * cos_angle + right[1] * sin_angle),
+    ]
+}
+
+/// Builds the effect that folds the main track down to mono while it is enabled.
+#[derive(Debug, Default)]
+pub struct MonoDownmixBuilder;
+
+/// Turns the mono downmix on the main track on and off.
+#[derive(Debug, Clone)]
+pub struct MonoDownmixHandle {
+    /// Read by the effect on the audio thread
+    enabled: Arc<AtomicBool>,
+}
+
+impl MonoDownmixHandle {
+    /// Turns the downmix on or off, from the next audio chunk.
+    pub fn set_enabled(&self, enabled: bool) {
+        self.enabled.store(enabled, Ordering::Relaxed);
+    }
+}
+
+/// Averages the two channels of every frame while enabled.
+struct MonoDownmix {
+    /// Shared with [`MonoDownmixHandle`]
+    enabled: Arc<AtomicBool>,
+}
+
+impl EffectBuilder for MonoDownmixBuilder {
+    type Handle = MonoDownmixHandle;
+
+    fn build(self) -> (Box<dyn Effect>, Self::Handle) {
+        let enabled = Arc::new(AtomicBool::new(false));
+        (
+            Box::new(MonoDownmix {
+                enabled: enabled.clone(),
+            }),
+            MonoDownmixHandle { enabled },
+        )
+    }
+}
+
+impl Effect for MonoDownmix {
+    fn process(&mut self, input: &mut [Frame], _dt: f64, _info: &Info) {
+        if self.enabled.load(Ordering::Relaxed) {
+            for frame in input {
+                *frame = frame.as_mono();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(a: [f32; 3], b: [f32; 3]) {
+        for i in 0..3 {
+            assert!((a[i] - b[i]).abs() < 1e-3, "{:?} != {:?}", a, b);
+        }
+    }
+
+    #[test]
+    fn test_profiles_trade_panning_for_rear_attenuation() {
+        let headphones = SpatialMix::for_profile(OutputProfile::Headphones, true);
+        let speakers = SpatialMix::for_profile(OutputProfile::Speakers, true);
+        let mono = SpatialMix::for_profile(OutputProfile::Mono, true);
+
+        assert!(speakers.spatialization_strength > headphones.spatialization_strength);
+        assert!(speakers.rear_attenuation_db > headphones.rear_attenuation_db);
+        assert!(headphones.binaural);
+        assert!(!speakers.binaural);
+        assert!(!SpatialMix::for_profile(OutputProfile::Headphones, false).binaural);
+
+        // Mono keeps only the distance falloff
+        assert_eq!(mono.spatialization_strength, 0.0);
+        assert_eq!(mono.rear_attenuation_db, 0.0);
+        assert!(mono.downmix_to_mono);
+        assert_eq!(rear_gain_db(&mono, [0.0; 3], 0.0, [0.0, 0.0, 50.0]), 0.0);
+
+        let mut profile = OutputProfile::default();
+        for _ in 0..3 {
+            profile = profile.next();
+        }
+        assert_eq!(profile, OutputProfile::default());
+    }
+
+    #[test]
+    fn test_rear_attenuation_follows_the_listeners_facing() {
+        let mix = SpatialMix::for_profile(OutputProfile::Headphones, false);
+        let listener = [10.0, 0.0, 10.0];
+
+        // Facing -z at a yaw of 0: ahead is free, behind gets the full cut, the side none
+        assert_eq!(rear_gain_db(&mix, listener, 0.0, [10.0, 0.0, -40.0]), 0.0);
+        assert_eq!(
+            rear_gain_db(&mix, listener, 0.0, [10.0, 0.0, 60.0]),
+            mix.rear_attenuation_db
+        );
+        assert!(rear_gain_db(&mix, listener, 0.0, [60.0, 0.0, 10.0]).abs() < 1e-6);
+
+        // Turned around, the same sound is in front
+        assert!(rear_gain_db(&mix, listener, 180.0, [10.0, 0.0, 60.0]).abs() < 1e-6);
+        // On top of the listener there is no direction to judge
+        assert_eq!(rear_gain_db(&mix, listener, 0.0, listener), 0.0);
+    }
+
+    #[test]
+    fn test_listener_orientation_turns_about_y() {
+        let [x, y, z, w] = listener_orientation(0.0);
+        assert_eq!([x, y, z], [0.0, 0.0, 0.0]);
+        assert!((w - 1.0).abs() < 1e-6);
+
+        let [_, y, _, w] = listener_orientation(180.0);
+        assert!((y - 1.0).abs() < 1e-6 && w.abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_audio_test_orbits_from_ahead_past_the_right_ear() {
+        let listener = [0.0, 5.0, 0.0];
+        let lap = AUDIO_TEST_DURATION.div_f32(AUDIO_TEST_LAPS);
+
+        assert_close(
+            audio_test_position(listener, 0.0, Duration::ZERO),
+            [0.0, 5.0, -AUDIO_TEST_RADIUS],
+        );
+        assert_close(
+            audio_test_position(listener, 0.0, lap / 4),
+            [AUDIO_TEST_RADIUS, 5.0, 0.0],
+        );
+        assert_close(
+            audio_test_position(listener, 0.0, lap / 2),
+            [0.0, 5.0, AUDIO_TEST_RADIUS],
+        );
+        // Facing +x at a yaw of 270, ahead is +x
+        assert_close(
+            audio_test_position(listener, 270.0, Duration::ZERO),
+            [AUDIO_TEST_RADIUS, 5.0, 0.0],
+        );
+    }
+}
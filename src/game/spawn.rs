@@ -0,0 +1,385 @@
+//! Delayed enemy spawns for the Mirador game.
+//!
+//! On deeper levels the enemy doesn't wait in the maze from the start. From level
+//! [`DELAYED_SPAWN_LEVEL`] on (or [`HARDCORE_DELAYED_SPAWN_LEVEL`] in a hardcore run), levels
+//! are [planned](crate::game::levels::EnemyPlan::delayed_spawn) with a delayed spawn and the
+//! [`SpawnDirector`] schedules it to arrive between [`EARLIEST_SPAWN_SECS`] and
+//! [`LATEST_SPAWN_SECS`] of play into the level, in a dead end well away from the player.
+//!
+//! The last [`TELEGRAPH_SECS`] before it arrives are telegraphed: a dark column of light grows
+//! over the spawn cell, a low drone rises from it, and the HUD shows [`SPAWN_WARNING`]. When the
+//! spawn fires the enemy materializes in the cell and the column collapses over
+//! [`COLLAPSE_SECS`]. Reaching the exit first cancels the spawn.
+//!
+//! Like the level's events, the spawn time and cell are rolled from the level seed, so a retried
+//! level or a shared seed brings the enemy in at the same moment. Only play time counts, so the
+//! spawn holds still while the game is paused or the timer hasn't started.
+//!
+//! # Fairness
+//!
+//! The enemy never appears within [`MIN_SPAWN_PATH_STEPS`] grid steps of the player, measured
+//! along the maze rather than through walls. The rolled cell is checked against the entrance,
+//! and again against wherever the player stands when the spawn fires; if the player has come too
+//! close, the enemy arrives in the dead end farthest from them instead.
+//!
+//! Test mode, the sandbox, and two-floor levels never delay the spawn.
+//!
+//! # Usage
+//!
+//! ```rust,ignore
+//! let mut spawn = SpawnDirector::roll(level_seed, &maze_grid, entrance, exit);
+//!
+//! // Once per frame of play
+//! if spawn.update(delta_time) {
+//!     let cell = spawn.settle_cell(&maze_grid, player.current_cell);
+//!     enemy = Enemy::new(spawn.position(), path_radius);
+//! }
+//! ```
+
+use crate::game::maze::{self, generator::Cell};
+use crate::math::coordinates;
+use rand::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// First level of a regular run whose enemy spawns partway through.
+pub const DELAYED_SPAWN_LEVEL: i32 = 5;
+
+/// First level of a hardcore run whose enemy spawns partway through.
+pub const HARDCORE_DELAYED_SPAWN_LEVEL: i32 = 3;
+
+/// Earliest the enemy can arrive, in seconds of play.
+pub const EARLIEST_SPAWN_SECS: f32 = 10.0;
+
+/// Latest the enemy can arrive, in seconds of play.
+pub const LATEST_SPAWN_SECS: f32 = 20.0;
+
+/// How long the telegraph builds before the enemy arrives, in seconds.
+pub const TELEGRAPH_SECS: f32 = 5.0;
+
+/// How long the telegraph takes to collapse once the enemy has arrived, in seconds.
+pub const COLLAPSE_SECS: f32 = 0.6;
+
+/// Fewest grid steps along the maze between the player and the spawn cell.
+///
+/// A grid step is one entry of the wall grid, so this is about eight maze cells.
+pub const MIN_SPAWN_PATH_STEPS: usize = 16;
+
+/// HUD warning shown while the telegraph builds.
+pub const SPAWN_WARNING: &str = "Something is coming";
+
+/// Height the spawned enemy stands at, matching the enemy's usual spawn height.
+const SPAWN_HEIGHT: f32 = 30.0;
+
+/// Salt mixed into the level seed so the spawn doesn't correlate with the level's events.
+const SPAWN_SEED_SALT: u64 = 0x0073_7061_776e_5f64;
+
+/// Where a delayed spawn is up to.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SpawnPhase {
+    /// The level's enemy is in the maze from the start
+    #[default]
+    Off,
+    /// Counting down to the enemy's arrival
+    Scheduled,
+    /// The enemy has arrived; the telegraph collapses
+    Spawned,
+    /// The player reached the exit before the enemy arrived
+    Cancelled,
+}
+
+/// Schedules and telegraphs the enemy's arrival on levels where it spawns partway through.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SpawnDirector {
+    /// Where the spawn is up to
+    phase: SpawnPhase,
+    /// Seconds of play into the level the enemy arrives
+    spawn_at: f32,
+    /// Seconds of play so far
+    elapsed: f32,
+    /// Seconds since the enemy arrived
+    since_spawn: f32,
+    /// Cell of the wall grid the enemy arrives in
+    cell: Cell,
+    /// World position of the center of the spawn cell
+    position: [f32; 3],
+}
+
+impl SpawnDirector {
+    /// Rolls where and when a level's enemy spawns partway through.
+    ///
+    /// Only rolled for levels whose [plan](crate::game::levels::EnemyPlan::delayed_spawn)
+    /// delays the spawn.
+    ///
+    /// # Arguments
+    /// * `seed` - The level's event seed, see [`crate::game::events::level_seed`]
+    /// * `maze_grid` - 2D grid of the maze where `true` marks a wall
+    /// * `entrance` - The cell the player starts in
+    /// * `exit` - The maze's exit cell
+    ///
+    /// # Returns
+    /// A scheduled spawn, or one that is [`SpawnPhase::Off`] in mazes with no dead end far
+    /// enough from the entrance
+    pub fn roll(seed: u64, maze_grid: &[Vec<bool>], entrance: Cell, exit: Cell) -> Self {
+        let candidates = spawn_candidates(maze_grid, entrance, exit);
+        let mut rng = StdRng::seed_from_u64(seed ^ SPAWN_SEED_SALT);
+        let Some(&cell) = candidates.choose(&mut rng) else {
+            return Self::default();
+        };
+        Self {
+            phase: SpawnPhase::Scheduled,
+            spawn_at: rng.gen_range(EARLIEST_SPAWN_SECS..=LATEST_SPAWN_SECS),
+            elapsed: 0.0,
+            since_spawn: 0.0,
+            cell,
+            position: cell_position(maze_grid, cell),
+        }
+    }
+
+    /// Returns where the spawn is up to.
+    pub fn phase(&self) -> SpawnPhase {
+        self.phase
+    }
+
+    /// Returns whether the enemy is still on its way.
+    pub fn is_pending(&self) -> bool {
+        self.phase == SpawnPhase::Scheduled
+    }
+
+    /// Returns the seconds of play into the level the enemy arrives.
+    pub fn spawn_at(&self) -> f32 {
+        self.spawn_at
+    }
+
+    /// Returns the cell the enemy arrives in, or `None` when the spawn isn't delayed.
+    pub fn cell(&self) -> Option<Cell> {
+        (self.phase != SpawnPhase::Off).then_some(self.cell)
+    }
+
+    /// Returns the world position of the center of the spawn cell.
+    pub fn position(&self) -> [f32; 3] {
+        self.position
+    }
+
+    /// Advances the spawn by one frame of play.
+    ///
+    /// # Arguments
+    /// * `delta_time` - Seconds since the last frame
+    ///
+    /// # Returns
+    /// `true` on the frame the enemy should arrive
+    pub fn update(&mut self, delta_time: f32) -> bool {
+        let delta_time = delta_time.max(0.0);
+        match self.phase {
+            SpawnPhase::Scheduled => {
+                self.elapsed += delta_time;
+                if self.elapsed >= self.spawn_at {
+                    self.phase = SpawnPhase::Spawned;
+                    self.since_spawn = 0.0;
+                    return true;
+                }
+                false
+            }
+            SpawnPhase::Spawned => {
+                self.since_spawn += delta_time;
+                false
+            }
+            SpawnPhase::Off | SpawnPhase::Cancelled => false,
+        }
+    }
+
+    /// Cancels a spawn that hasn't fired yet, for the player reaching the exit.
+    ///
+    /// # Returns
+    /// `true` if a pending spawn was cancelled
+    pub fn cancel(&mut self) -> bool {
+        if !self.is_pending() {
+            return false;
+        }
+        self.phase = SpawnPhase::Cancelled;
+        true
+    }
+
+    /// Returns how far the telegraph has built, from 0.0 to 1.0.
+    ///
+    /// Rises over the [`TELEGRAPH_SECS`] before the enemy arrives and falls back to 0.0 over
+    /// [`COLLAPSE_SECS`] afterward; see [`SpawnDirector::collapsing`].
+    pub fn telegraph(&self) -> f32 {
+        match self.phase {
+            SpawnPhase::Scheduled => {
+                let telegraph_start = self.spawn_at - TELEGRAPH_SECS;
+                ((self.elapsed - telegraph_start) / TELEGRAPH_SECS).clamp(0.0, 1.0)
+            }
+            SpawnPhase::Spawned => (1.0 - self.since_spawn / COLLAPSE_SECS).max(0.0),
+            SpawnPhase::Off | SpawnPhase::Cancelled => 0.0,
+        }
+    }
+
+    /// Returns whether the telegraph is collapsing after the enemy's arrival.
+    pub fn collapsing(&self) -> bool {
+        self.phase == SpawnPhase::Spawned && self.telegraph() > 0.0
+    }
+
+    /// Returns the HUD warning to show this frame, while the telegraph builds.
+    pub fn warning(&self) -> Option<&'static str> {
+        (self.is_pending() && self.telegraph() > 0.0).then_some(SPAWN_WARNING)
+    }
+
+    /// Moves the spawn if the player has come too close to it, and returns the final cell.
+    ///
+    /// Should be called when [`SpawnDirector::update`] fires. The rolled cell is kept while it
+    /// is at least [`MIN_SPAWN_PATH_STEPS`] from the player along the maze; otherwise the
+    /// enemy arrives in the dead end farthest from the player.
+    ///
+    /// # Arguments
+    /// * `maze_grid` - 2D grid of the maze where `true` marks a wall
+    /// * `player_cell` - The cell the player is standing in
+    ///
+    /// # Returns
+    /// The cell the enemy arrives in
+    pub fn settle_cell(&mut self, maze_grid: &[Vec<bool>], player_cell: Cell) -> Cell {
+        let distances = maze::path_distances(maze_grid, player_cell);
+        let distance = |cell: Cell| {
+            distances
+                .get(cell.row)
+                .and_then(|row| row.get(cell.col).copied().flatten())
+        };
+        // A player somewhere the maze can't measure from can't be too close
+        if distance(self.cell).is_none_or(|steps| steps >= MIN_SPAWN_PATH_STEPS) {
+            return self.cell;
+        }
+
+        let farthest = maze::dead_ends(maze_grid)
+            .into_iter()
+            .filter_map(|cell| distance(cell).map(|steps| (steps, cell)))
+            .max_by_key(|&(steps, _)| steps);
+        if let Some((_, cell)) = farthest {
+            self.cell = cell;
+            self.position = cell_position(maze_grid, cell);
+        }
+        self.cell
+    }
+}
+
+/// Finds the cells a delayed enemy may arrive in.
+///
+/// # Arguments
+/// * `maze_grid` - 2D grid of the maze where `true` marks a wall
+/// * `entrance` - The cell the player starts in
+/// * `exit` - The maze's exit cell
+///
+/// # Returns
+/// Every dead end other than the exit at least [`MIN_SPAWN_PATH_STEPS`] from the entrance
+pub fn spawn_candidates(maze_grid: &[Vec<bool>], entrance: Cell, exit: Cell) -> Vec<Cell> {
+    let distances = maze::path_distances(maze_grid, entrance);
+    maze::dead_ends(maze_grid)
+        .into_iter()
+        .filter(|&cell| {
+            cell != exit
+                && distances[cell.row][cell.col].is_some_and(|steps| steps >= MIN_SPAWN_PATH_STEPS)
+        })
+        .collect()
+}
+
+/// Returns the world position of the center of a cell, at the enemy's height.
+fn cell_position(maze_grid: &[Vec<bool>], cell: Cell) -> [f32; 3] {
+    let maze_dimensions = (maze_grid.first().map_or(0, Vec::len), maze_grid.len());
+    coordinates::maze_to_world(&cell, maze_dimensions, SPAWN_HEIGHT, false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::maze::generator::MazeGenerator;
+
+    /// Generates a seeded maze as the game does, returning its grid, entrance and exit.
+    fn generated_maze(seed: u64) -> (Vec<Vec<bool>>, Cell, Cell) {
+        let (mut generator, maze) = MazeGenerator::with_seed(12, 12, seed);
+        while !generator.is_complete() {
+            generator.step();
+        }
+        let (grid, exit) = maze.lock().unwrap().level_grid();
+        let entrance = Cell::new(grid.len() - 2, 1);
+        (grid, entrance, exit.expect("Completed maze has an exit"))
+    }
+
+    #[test]
+    fn test_spawns_are_rolled_far_from_the_entrance() {
+        let (grid, entrance, exit) = generated_maze(7);
+        for seed in 0..50 {
+            let spawn = SpawnDirector::roll(seed, &grid, entrance, exit);
+            assert!(spawn.is_pending());
+            assert!((EARLIEST_SPAWN_SECS..=LATEST_SPAWN_SECS).contains(&spawn.spawn_at()));
+            let cell = spawn.cell().unwrap();
+            assert_ne!(cell, exit);
+            let steps = maze::solution_length(&grid, entrance, cell).unwrap();
+            assert!(
+                steps >= MIN_SPAWN_PATH_STEPS,
+                "{:?} is {} steps away",
+                cell,
+                steps
+            );
+            assert_eq!(spawn, SpawnDirector::roll(seed, &grid, entrance, exit));
+        }
+    }
+
+    #[test]
+    fn test_telegraph_builds_then_collapses() {
+        let (grid, entrance, exit) = generated_maze(3);
+        let mut spawn = SpawnDirector::roll(9, &grid, entrance, exit);
+        let spawn_at = spawn.spawn_at();
+
+        assert!(!spawn.update(spawn_at - TELEGRAPH_SECS));
+        assert_eq!(spawn.telegraph(), 0.0);
+        assert_eq!(spawn.warning(), None);
+
+        assert!(!spawn.update(TELEGRAPH_SECS / 2.0));
+        assert!((spawn.telegraph() - 0.5).abs() < 1e-4);
+        assert_eq!(spawn.warning(), Some(SPAWN_WARNING));
+
+        assert!(spawn.update(TELEGRAPH_SECS));
+        assert_eq!(spawn.phase(), SpawnPhase::Spawned);
+        assert!(spawn.collapsing());
+        assert_eq!(spawn.warning(), None);
+        assert!(!spawn.update(COLLAPSE_SECS));
+        assert_eq!(spawn.telegraph(), 0.0);
+        assert!(!spawn.collapsing());
+        assert!(!spawn.cancel());
+    }
+
+    #[test]
+    fn test_reaching_the_exit_cancels_a_pending_spawn() {
+        let (grid, entrance, exit) = generated_maze(5);
+        let mut spawn = SpawnDirector::roll(2, &grid, entrance, exit);
+        spawn.update(spawn.spawn_at() - 1.0);
+        assert!(spawn.cancel());
+        assert_eq!(spawn.phase(), SpawnPhase::Cancelled);
+        assert!(!spawn.update(10.0));
+        assert_eq!(spawn.telegraph(), 0.0);
+        assert!(!spawn.cancel());
+    }
+
+    #[test]
+    fn test_spawn_moves_away_from_a_player_who_came_too_close() {
+        let (grid, entrance, exit) = generated_maze(11);
+        let mut spawn = SpawnDirector::roll(4, &grid, entrance, exit);
+        let rolled = spawn.cell().unwrap();
+
+        // Far enough from the entrance, so a player who stayed put keeps the rolled cell
+        assert_eq!(spawn.settle_cell(&grid, entrance), rolled);
+
+        let moved = spawn.settle_cell(&grid, rolled);
+        assert_ne!(moved, rolled);
+        assert!(maze::solution_length(&grid, rolled, moved).unwrap() >= MIN_SPAWN_PATH_STEPS);
+        assert_eq!(spawn.position(), cell_position(&grid, moved));
+    }
+
+    #[test]
+    fn test_spawn_round_trips_through_json() {
+        let (grid, entrance, exit) = generated_maze(1);
+        let mut spawn = SpawnDirector::roll(6, &grid, entrance, exit);
+        spawn.update(3.0);
+        let json = serde_json::to_string(&spawn).expect("Failed to serialize spawn");
+        let restored: SpawnDirector = serde_json::from_str(&json).expect("Failed to parse spawn");
+        assert_eq!(restored, spawn);
+    }
+}
@@ -0,0 +1,119 @@
+//! Ambient themes that set the mood of a level.
+//!
+//! Every level is dressed in a [`LevelTheme`], rolled from the same maze seed as its events so a
+//! retried level or a shared seed looks the same. The theme only changes the [`Ambience`]: the
+//! sparse layer of dust motes or rain streaks the renderer draws between the maze and the HUD.
+//! The first level is always dusty, and later levels turn rainy about one time in
+//! [`RAIN_ODDS`]. Test mode always uses dust.
+//!
+//! # Usage
+//!
+//! ```rust,ignore
+//! let theme = LevelTheme::roll(level, events::level_seed(&maze_grid, level));
+//! let ambience = theme.ambience();
+//! ```
+
+use rand::prelude::*;
+
+/// One level in this many is rainy, from level 2 on.
+pub const RAIN_ODDS: u32 = 4;
+
+/// Salt mixed into the level seed so the theme doesn't correlate with the level's events.
+const THEME_SEED_SALT: u64 = 0x0074_6865_6d65;
+
+/// The ambient dressing of a level.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LevelTheme {
+    /// Dust motes drifting slowly through the air
+    #[default]
+    Dust,
+    /// Rain streaks falling through the maze
+    Rain,
+}
+
+/// How the ambient particle layer looks for a theme.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Ambience {
+    /// Chance that a cell of the procedural grid holds a particle, from 0.0 to 1.0
+    pub density: f32,
+    /// Drift in view heights per second; positive y is up
+    pub velocity: [f32; 2],
+    /// Linear color of the particles, with their strongest opacity in alpha
+    pub color: [f32; 4],
+    /// Length of each particle along its drift, in view heights; 0.0 draws round motes
+    pub streak: f32,
+}
+
+impl LevelTheme {
+    /// Rolls a level's theme.
+    ///
+    /// # Arguments
+    /// * `level` - The level being played
+    /// * `seed` - The level's event seed, see [`crate::game::events::level_seed`]
+    ///
+    /// # Returns
+    /// The same theme whenever the same maze is played on the same level
+    pub fn roll(level: i32, seed: u64) -> Self {
+        let mut rng = StdRng::seed_from_u64(seed ^ THEME_SEED_SALT);
+        if level > 1 && rng.gen_ratio(1, RAIN_ODDS) {
+            Self::Rain
+        } else {
+            Self::Dust
+        }
+    }
+
+    /// Returns how the ambient particle layer looks for this theme.
+    pub fn ambience(self) -> Ambience {
+        match self {
+            Self::Dust => Ambience {
+                density: 0.35,
+                velocity: [0.012, -0.006],
+                color: [0.85, 0.8, 0.7, 0.35],
+                streak: 0.0,
+            },
+            Self::Rain => Ambience {
+                density: 0.5,
+                velocity: [-0.08, -1.6],
+                color: [0.6, 0.68, 0.8, 0.22],
+                streak: 0.06,
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_theme_roll_is_deterministic_and_starts_dusty() {
+        for seed in 0..200 {
+            assert_eq!(LevelTheme::roll(1, seed), LevelTheme::Dust);
+            assert_eq!(LevelTheme::roll(4, seed), LevelTheme::roll(4, seed));
+        }
+
+        let rainy = (0..1000)
+            .filter(|&seed| LevelTheme::roll(5, seed) == LevelTheme::Rain)
+            .count();
+        let expected = 1000 / RAIN_ODDS as usize;
+        assert!(
+            rainy.abs_diff(expected) < expected / 2,
+            "{} rainy levels out of 1000",
+            rainy
+        );
+    }
+
+    #[test]
+    fn test_rain_falls_faster_than_dust_drifts() {
+        let (dust, rain) = (LevelTheme::Dust.ambience(), LevelTheme::Rain.ambience());
+        let speed = |ambience: Ambience| ambience.velocity[0].hypot(ambience.velocity[1]);
+        assert!(rain.velocity[1] < 0.0);
+        assert!(speed(rain) > 10.0 * speed(dust));
+        assert_eq!(dust.streak, 0.0);
+        assert!(rain.streak > 0.0);
+        for ambience in [dust, rain] {
+            assert!((0.0..=1.0).contains(&ambience.density));
+            assert!(ambience.color[3] > 0.0 && ambience.color[3] < 0.5);
+        }
+    }
+}
@@ -0,0 +1,280 @@
+//! The time of day a run moves through as it goes deeper.
+//!
+//! Each level of a run is lit as a later hour of one long night: warm dusk on the first level,
+//! deep midnight by level [`MIDNIGHT_LEVEL`], and a cold gray-blue before dawn from level
+//! [`PRE_DAWN_LEVEL`] on. [`for_level`] reads the [`TimeOfDay`] for a level off a few
+//! [`KEYFRAMES`], easing smoothly between them, so the change from one level to the next is
+//! gentle but the start and end of a long run look nothing alike.
+//!
+//! A [`TimeOfDay`] only adjusts values the renderer already reads each frame: it tints the
+//! background and fog colors, thins out the starfield, and scales the maze's shading through
+//! [`RenderTuning::ambient`]. The game state keeps the time of day of the level being played,
+//! so the frozen frame behind the game over screen keeps the tint of the level where the run
+//! ended. Players who would rather not have it change can pin one with [`TimeOfDayPin`].
+//!
+//! # Usage
+//!
+//! ```rust,ignore
+//! game_state.time_of_day = time_of_day::for_level(level);
+//!
+//! // Once per frame, before rendering
+//! let time_of_day = settings.time_of_day.apply(game_state.time_of_day);
+//! let tuning = time_of_day.apply_to_tuning(&base_tuning);
+//! ```
+
+use crate::renderer::tuning::RenderTuning;
+use serde::{Deserialize, Serialize};
+
+/// Level by which the run has reached midnight.
+pub const MIDNIGHT_LEVEL: i32 = 10;
+
+/// Level from which the run sits in the cold light before dawn.
+pub const PRE_DAWN_LEVEL: i32 = 20;
+
+/// How one hour of the night tints the scene.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TimeOfDay {
+    /// Linear color the background is tinted toward, with how strongly in alpha
+    pub sky_tint: [f32; 4],
+    /// Linear color the fog is tinted toward, with how strongly in alpha
+    pub fog_tint: [f32; 4],
+    /// Share of the tuned stars that are shown, from 0.0 to 1.0
+    pub star_density: f32,
+    /// Multiplier on the maze's shading; 1.0 leaves it unchanged
+    pub ambient: f32,
+}
+
+/// Warm dusk oranges, with only the brightest stars out.
+pub const DUSK: TimeOfDay = TimeOfDay {
+    sky_tint: [0.07, 0.026, 0.011, 0.9],
+    fog_tint: [0.09, 0.034, 0.014, 0.9],
+    star_density: 0.35,
+    ambient: 1.0,
+};
+
+/// Deep blue-black, with every star out and the maze at its darkest.
+pub const MIDNIGHT: TimeOfDay = TimeOfDay {
+    sky_tint: [0.001, 0.0015, 0.008, 0.9],
+    fog_tint: [0.001, 0.002, 0.01, 0.9],
+    star_density: 1.0,
+    ambient: 0.8,
+};
+
+/// Cold gray-blue before dawn, with the stars fading again.
+pub const PRE_DAWN: TimeOfDay = TimeOfDay {
+    sky_tint: [0.025, 0.033, 0.045, 0.9],
+    fog_tint: [0.035, 0.045, 0.06, 0.9],
+    star_density: 0.55,
+    ambient: 0.9,
+};
+
+/// The level each hour of the night is reached on, in order.
+pub const KEYFRAMES: [(i32, TimeOfDay); 3] = [
+    (1, DUSK),
+    (MIDNIGHT_LEVEL, MIDNIGHT),
+    (PRE_DAWN_LEVEL, PRE_DAWN),
+];
+
+impl TimeOfDay {
+    /// No tint at all, for test mode and the sandbox.
+    pub const NEUTRAL: Self = Self {
+        sky_tint: [0.0; 4],
+        fog_tint: [0.0; 4],
+        star_density: 1.0,
+        ambient: 1.0,
+    };
+
+    /// Applies the time of day to the renderer's settings.
+    ///
+    /// # Arguments
+    /// * `tuning` - The renderer's current settings, left unchanged
+    ///
+    /// # Returns
+    /// A copy with the background and fog tinted, the starfield thinned out, and the
+    /// ambient multiplier scaled
+    pub fn apply_to_tuning(&self, tuning: &RenderTuning) -> RenderTuning {
+        RenderTuning {
+            background_color: tint(tuning.background_color, self.sky_tint),
+            fog_color: tint(tuning.fog_color, self.fog_tint),
+            star_count: (tuning.star_count as f32 * self.star_density).round() as usize,
+            ambient: tuning.ambient * self.ambient,
+            ..*tuning
+        }
+    }
+
+    /// Blends between two times of day.
+    ///
+    /// # Arguments
+    /// * `other` - The time of day at `t` = 1.0
+    /// * `t` - How far toward `other`, from 0.0 to 1.0
+    fn lerp(&self, other: &Self, t: f32) -> Self {
+        let mix = |a: f32, b: f32| a + (b - a) * t;
+        let mix4 = |a: [f32; 4], b: [f32; 4]| std::array::from_fn(|i| mix(a[i], b[i]));
+        Self {
+            sky_tint: mix4(self.sky_tint, other.sky_tint),
+            fog_tint: mix4(self.fog_tint, other.fog_tint),
+            star_density: mix(self.star_density, other.star_density),
+            ambient: mix(self.ambient, other.ambient),
+        }
+    }
+}
+
+/// Works out the time of day of a level.
+///
+/// Between two keyframes the time of day eases in and out, so it changes least just after
+/// and just before each keyframe. Levels before the first keyframe are dusk and levels past
+/// the last are pre-dawn.
+///
+/// # Arguments
+/// * `level` - The level being played, from 1
+pub fn for_level(level: i32) -> TimeOfDay {
+    let after = KEYFRAMES
+        .iter()
+        .position(|&(keyframe, _)| level < keyframe)
+        .unwrap_or(KEYFRAMES.len());
+    if after == 0 {
+        return KEYFRAMES[0].1;
+    }
+    if after == KEYFRAMES.len() {
+        return KEYFRAMES[KEYFRAMES.len() - 1].1;
+    }
+    let (from_level, from) = KEYFRAMES[after - 1];
+    let (to_level, to) = KEYFRAMES[after];
+    let t = (level - from_level) as f32 / (to_level - from_level) as f32;
+    from.lerp(&to, t * t * (3.0 - 2.0 * t))
+}
+
+/// Blends a color toward a tint.
+///
+/// # Arguments
+/// * `color` - Linear RGBA color, whose alpha is kept
+/// * `tint` - Linear color to blend toward, with how strongly in alpha
+fn tint(color: [f32; 4], tint: [f32; 4]) -> [f32; 4] {
+    let strength = tint[3].clamp(0.0, 1.0);
+    let mix = |i: usize| color[i] + (tint[i] - color[i]) * strength;
+    [mix(0), mix(1), mix(2), color[3]]
+}
+
+/// Whether the time of day follows the run or stays fixed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TimeOfDayPin {
+    /// Moves from dusk to pre-dawn as the run goes deeper
+    #[default]
+    Run,
+    /// Always dusk
+    Dusk,
+    /// Always midnight
+    Midnight,
+    /// Always pre-dawn
+    PreDawn,
+}
+
+impl TimeOfDayPin {
+    /// Returns the pin after this one, wrapping around.
+    pub fn next(self) -> Self {
+        match self {
+            Self::Run => Self::Dusk,
+            Self::Dusk => Self::Midnight,
+            Self::Midnight => Self::PreDawn,
+            Self::PreDawn => Self::Run,
+        }
+    }
+
+    /// Returns a short label for menus.
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Run => "Run",
+            Self::Dusk => "Dusk",
+            Self::Midnight => "Night",
+            Self::PreDawn => "Dawn",
+        }
+    }
+
+    /// Picks the time of day to draw.
+    ///
+    /// # Arguments
+    /// * `level` - The time of day of the level being played
+    ///
+    /// # Returns
+    /// The pinned time of day, or `level` when following the run; a neutral level, as in test
+    /// mode, stays neutral either way
+    pub fn apply(self, level: TimeOfDay) -> TimeOfDay {
+        if level == TimeOfDay::NEUTRAL {
+            return level;
+        }
+        match self {
+            Self::Run => level,
+            Self::Dusk => DUSK,
+            Self::Midnight => MIDNIGHT,
+            Self::PreDawn => PRE_DAWN,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Checks two times of day match to within rounding.
+    fn assert_close(a: TimeOfDay, b: TimeOfDay) {
+        let values = |time: TimeOfDay| {
+            let mut values = time.sky_tint.to_vec();
+            values.extend(time.fog_tint);
+            values.extend([time.star_density, time.ambient]);
+            values
+        };
+        for (x, y) in values(a).into_iter().zip(values(b)) {
+            assert!((x - y).abs() < 1e-5, "{:?} != {:?}", a, b);
+        }
+    }
+
+    #[test]
+    fn test_keyframes_are_hit_exactly() {
+        for (level, time) in KEYFRAMES {
+            assert_eq!(for_level(level), time);
+        }
+        assert_eq!(for_level(0), DUSK);
+        assert_eq!(for_level(PRE_DAWN_LEVEL + 15), PRE_DAWN);
+    }
+
+    #[test]
+    fn test_midpoints_are_halfway_between_keyframes() {
+        // Level 15 is halfway from midnight to pre-dawn, where the easing is at one half
+        assert_close(for_level(15), MIDNIGHT.lerp(&PRE_DAWN, 0.5));
+        // Level 5.5 would be halfway from dusk to midnight; the levels either side of it
+        // are eased by the same amount from each end
+        let early = for_level(5);
+        let late = for_level(6);
+        assert_close(early.lerp(&late, 0.5), DUSK.lerp(&MIDNIGHT, 0.5));
+        // Easing changes the least right next to a keyframe
+        let step = |level: i32| (for_level(level + 1).ambient - for_level(level).ambient).abs();
+        assert!(step(1) < step(5));
+        assert!(step(MIDNIGHT_LEVEL - 1) < step(5));
+    }
+
+    #[test]
+    fn test_tint_follows_the_night_and_pins_hold_it() {
+        let tuning = RenderTuning::default();
+        let dusk = DUSK.apply_to_tuning(&tuning);
+        let midnight = MIDNIGHT.apply_to_tuning(&tuning);
+        // Dusk is warm, midnight is blue
+        assert!(dusk.background_color[0] > dusk.background_color[2]);
+        assert!(midnight.background_color[2] > midnight.background_color[0]);
+        assert!(dusk.star_count < midnight.star_count);
+        assert!(midnight.ambient < dusk.ambient);
+        assert_eq!(dusk.fog_start, tuning.fog_start);
+        assert_eq!(TimeOfDay::NEUTRAL.apply_to_tuning(&tuning), tuning);
+
+        assert_eq!(TimeOfDayPin::Run.apply(for_level(7)), for_level(7));
+        assert_eq!(TimeOfDayPin::Midnight.apply(for_level(2)), MIDNIGHT);
+        assert_eq!(
+            TimeOfDayPin::Dusk.apply(TimeOfDay::NEUTRAL),
+            TimeOfDay::NEUTRAL
+        );
+        let mut pin = TimeOfDayPin::default();
+        for _ in 0..4 {
+            pin = pin.next();
+        }
+        assert_eq!(pin, TimeOfDayPin::Run);
+    }
+}
@@ -0,0 +1,429 @@
+//! Toast notifications for things that happen away from the player's attention.
+//!
+//! A toast is one short line with an icon, shown in a corner of the HUD for a few seconds:
+//! the test maze reloading, a clip report being written, the player being moved out of a
+//! wall. [`ToastManager`] keeps them in a queue; at most [`MAX_VISIBLE`] are on screen at
+//! once and the rest wait their turn in the order they were posted. Each one slides in over
+//! [`SLIDE_IN_SECS`], holds for [`HOLD_SECS`], and slides out over [`SLIDE_OUT_SECS`].
+//!
+//! Toasts are posted through a [`ToastSender`], a cheap handle that can be cloned and moved
+//! to any thread. Posting never blocks; the manager picks new toasts up on the main thread
+//! the next time it is ticked, so a background writer can report back without touching the
+//! renderer.
+//!
+//! Time is kept on two [`GameClock`]s. [`ToastKind::System`] toasts, about the program itself,
+//! run on real time and keep ticking through menus and pauses. [`ToastKind::Gameplay`] toasts,
+//! about the run, run on a clock that only moves during play, so one posted just before the
+//! pause menu opens is still there when play resumes.
+//!
+//! # Usage
+//!
+//! ```rust,ignore
+//! let mut toasts = ToastManager::new();
+//! let sender = toasts.sender();
+//! std::thread::spawn(move || sender.system("Report saved"));
+//!
+//! // Once per frame
+//! toasts.tick(real_delta, if playing { game_delta } else { 0.0 });
+//! for toast in toasts.visible() {
+//!     draw(toast.text, toast.kind, toast.slide);
+//! }
+//! ```
+
+use crate::game::clock::GameClock;
+use crate::game::map_view::ease_in_out;
+use std::collections::VecDeque;
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::time::Duration;
+
+/// Most toasts on screen at once; later ones wait until one leaves.
+pub const MAX_VISIBLE: usize = 3;
+
+/// Most toasts waiting to be shown; past this the oldest waiting one is dropped.
+///
+/// Keeps something posting every frame from building an endless backlog.
+pub const MAX_PENDING: usize = 16;
+
+/// Seconds a toast takes to slide in.
+pub const SLIDE_IN_SECS: f32 = 0.25;
+
+/// Seconds a toast stays fully on screen.
+pub const HOLD_SECS: f32 = 3.0;
+
+/// Seconds a toast takes to slide out.
+pub const SLIDE_OUT_SECS: f32 = 0.3;
+
+/// Which clock a toast's timer runs on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ToastKind {
+    /// About the program, like a file being written; keeps ticking while the game is paused
+    System,
+    /// About the run; only ticks during play
+    Gameplay,
+}
+
+/// One notification.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Toast {
+    /// Which clock the toast's timer runs on
+    pub kind: ToastKind,
+    /// The single line shown
+    pub text: String,
+}
+
+impl Toast {
+    /// Creates a toast that keeps ticking while the game is paused.
+    ///
+    /// # Arguments
+    /// * `text` - The line to show
+    pub fn system(text: impl Into<String>) -> Self {
+        Self {
+            kind: ToastKind::System,
+            text: text.into(),
+        }
+    }
+
+    /// Creates a toast that only ticks during play.
+    ///
+    /// # Arguments
+    /// * `text` - The line to show
+    pub fn gameplay(text: impl Into<String>) -> Self {
+        Self {
+            kind: ToastKind::Gameplay,
+            text: text.into(),
+        }
+    }
+}
+
+/// Posts toasts to a [`ToastManager`] from anywhere, including other threads.
+#[derive(Debug, Clone)]
+pub struct ToastSender {
+    /// Sending half of the manager's channel
+    sender: Sender<Toast>,
+}
+
+impl ToastSender {
+    /// Creates a sender nobody listens to, for game states made without a window.
+    ///
+    /// Toasts posted through it are dropped.
+    pub fn detached() -> Self {
+        Self {
+            sender: mpsc::channel().0,
+        }
+    }
+
+    /// Posts a toast.
+    ///
+    /// Never blocks. If the manager is gone, as when the game is shutting down, the toast is
+    /// dropped.
+    ///
+    /// # Arguments
+    /// * `toast` - The toast to show
+    pub fn send(&self, toast: Toast) {
+        let _ = self.sender.send(toast);
+    }
+
+    /// Posts a toast that keeps ticking while the game is paused.
+    ///
+    /// # Arguments
+    /// * `text` - The line to show
+    pub fn system(&self, text: impl Into<String>) {
+        self.send(Toast::system(text));
+    }
+
+    /// Posts a toast that only ticks during play.
+    ///
+    /// # Arguments
+    /// * `text` - The line to show
+    pub fn gameplay(&self, text: impl Into<String>) {
+        self.send(Toast::gameplay(text));
+    }
+}
+
+/// A toast on screen.
+#[derive(Debug, Clone)]
+struct ShownToast {
+    /// The toast
+    toast: Toast,
+    /// Reading of the toast's clock when it was shown
+    shown_at: Duration,
+}
+
+/// A toast on screen this frame, as it should be drawn.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct VisibleToast<'a> {
+    /// Which clock the toast runs on, which also picks its icon
+    pub kind: ToastKind,
+    /// The line to show
+    pub text: &'a str,
+    /// How far the toast has slid in, from 0 (off screen) to 1 (fully in)
+    pub slide: f32,
+}
+
+/// Queues toasts and times the ones on screen.
+#[derive(Debug)]
+pub struct ToastManager {
+    /// Receives toasts posted through any [`ToastSender`]
+    receiver: Receiver<Toast>,
+    /// Kept so new senders can be handed out
+    sender: ToastSender,
+    /// Toasts waiting for room on screen, oldest first
+    pending: VecDeque<Toast>,
+    /// Toasts on screen, oldest first
+    shown: Vec<ShownToast>,
+    /// Real time, for system toasts
+    system_clock: GameClock,
+    /// Play time, for gameplay toasts
+    gameplay_clock: GameClock,
+}
+
+impl Default for ToastManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ToastManager {
+    /// Creates a manager with nothing queued.
+    pub fn new() -> Self {
+        let (sender, receiver) = mpsc::channel();
+        Self {
+            receiver,
+            sender: ToastSender { sender },
+            pending: VecDeque::new(),
+            shown: Vec::new(),
+            system_clock: GameClock::new(),
+            gameplay_clock: GameClock::new(),
+        }
+    }
+
+    /// Returns a handle that posts toasts to this manager.
+    pub fn sender(&self) -> ToastSender {
+        self.sender.clone()
+    }
+
+    /// Queues a toast from the main thread.
+    ///
+    /// Goes through the same channel as the senders, so toasts keep the order they were
+    /// posted in wherever they came from.
+    ///
+    /// # Arguments
+    /// * `toast` - The toast to show
+    #[cfg(test)]
+    pub fn push(&self, toast: Toast) {
+        self.sender.send(toast);
+    }
+
+    /// Advances the timers, retires finished toasts, and shows waiting ones.
+    ///
+    /// Also picks up everything posted through senders since the last tick.
+    ///
+    /// # Arguments
+    /// * `real_delta` - Seconds since the last frame, for system toasts
+    /// * `gameplay_delta` - Seconds of play since the last frame, for gameplay toasts; zero
+    ///   while the game is paused or off the game screen
+    pub fn tick(&mut self, real_delta: f32, gameplay_delta: f32) {
+        self.system_clock.advance_secs(real_delta);
+        self.gameplay_clock.advance_secs(gameplay_delta);
+        while let Ok(toast) = self.receiver.try_recv() {
+            if self.pending.len() >= MAX_PENDING {
+                self.pending.pop_front();
+            }
+            self.pending.push_back(toast);
+        }
+
+        let total = SLIDE_IN_SECS + HOLD_SECS + SLIDE_OUT_SECS;
+        let (system, gameplay) = (self.system_clock, self.gameplay_clock);
+        self.shown.retain(|shown| {
+            let clock = match shown.toast.kind {
+                ToastKind::System => system,
+                ToastKind::Gameplay => gameplay,
+            };
+            (clock.now() - shown.shown_at).as_secs_f32() < total
+        });
+
+        while self.shown.len() < MAX_VISIBLE {
+            let Some(toast) = self.pending.pop_front() else {
+                break;
+            };
+            let shown_at = self.clock(toast.kind).now();
+            self.shown.push(ShownToast { toast, shown_at });
+        }
+    }
+
+    /// Returns the toasts on screen, oldest first.
+    pub fn visible(&self) -> impl Iterator<Item = VisibleToast<'_>> + '_ {
+        self.shown.iter().map(|shown| {
+            let age = (self.clock(shown.toast.kind).now() - shown.shown_at).as_secs_f32();
+            VisibleToast {
+                kind: shown.toast.kind,
+                text: &shown.toast.text,
+                slide: slide_progress(age),
+            }
+        })
+    }
+
+    /// Returns the number of toasts waiting for room on screen, as of the last tick.
+    #[cfg(test)]
+    pub fn pending_len(&self) -> usize {
+        self.pending.len()
+    }
+
+    /// Returns whether nothing is on screen or waiting, as of the last tick.
+    #[cfg(test)]
+    pub fn is_empty(&self) -> bool {
+        self.shown.is_empty() && self.pending.is_empty()
+    }
+
+    /// Returns the clock a kind of toast runs on.
+    fn clock(&self, kind: ToastKind) -> GameClock {
+        match kind {
+            ToastKind::System => self.system_clock,
+            ToastKind::Gameplay => self.gameplay_clock,
+        }
+    }
+}
+
+/// Returns how far a toast of the given age has slid in.
+///
+/// # Arguments
+/// * `age` - Seconds since the toast was shown
+///
+/// # Returns
+/// 0 when off screen, 1 when fully in, eased in between
+pub fn slide_progress(age: f32) -> f32 {
+    if age < SLIDE_IN_SECS {
+        ease_in_out(age / SLIDE_IN_SECS)
+    } else if age < SLIDE_IN_SECS + HOLD_SECS {
+        1.0
+    } else {
+        1.0 - ease_in_out((age - SLIDE_IN_SECS - HOLD_SECS) / SLIDE_OUT_SECS)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread;
+
+    /// Just over a toast's whole time on screen
+    const LIFETIME: f32 = SLIDE_IN_SECS + HOLD_SECS + SLIDE_OUT_SECS + 0.01;
+
+    fn texts(manager: &ToastManager) -> Vec<String> {
+        manager
+            .visible()
+            .map(|toast| toast.text.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_toasts_show_in_the_order_posted() {
+        let mut manager = ToastManager::new();
+        let sender = manager.sender();
+        sender.system("first");
+        manager.push(Toast::system("second"));
+        sender.gameplay("third");
+        manager.tick(0.0, 0.0);
+        assert_eq!(texts(&manager), ["first", "second", "third"]);
+
+        // Each slides in, holds, and slides out
+        assert_eq!(manager.visible().next().unwrap().slide, 0.0);
+        manager.tick(SLIDE_IN_SECS + 0.5, SLIDE_IN_SECS + 0.5);
+        assert!(manager.visible().all(|toast| toast.slide == 1.0));
+        let halfway_out = HOLD_SECS - 0.5 + SLIDE_OUT_SECS / 2.0;
+        manager.tick(halfway_out, halfway_out);
+        assert_eq!(manager.visible().count(), 3);
+        assert!(
+            manager
+                .visible()
+                .all(|toast| toast.slide > 0.0 && toast.slide < 1.0)
+        );
+        manager.tick(SLIDE_OUT_SECS, SLIDE_OUT_SECS);
+        assert!(manager.is_empty());
+    }
+
+    #[test]
+    fn test_overflow_waits_its_turn() {
+        let mut manager = ToastManager::new();
+        for i in 0..MAX_VISIBLE + 2 {
+            manager.push(Toast::system(format!("toast {}", i)));
+        }
+        manager.tick(0.0, 0.0);
+        assert_eq!(manager.visible().count(), MAX_VISIBLE);
+        assert_eq!(manager.pending_len(), 2);
+
+        // Half a lifetime later the first ones are still up, so nothing new comes in
+        manager.tick(LIFETIME / 2.0, 0.0);
+        assert_eq!(texts(&manager)[0], "toast 0");
+        assert_eq!(manager.pending_len(), 2);
+
+        // Once they leave, the waiting ones take their place in order
+        manager.tick(LIFETIME / 2.0 + 0.01, 0.0);
+        assert_eq!(texts(&manager), ["toast 3", "toast 4"]);
+
+        // A runaway poster can't queue forever; the oldest waiting toasts give way
+        for i in 0..MAX_PENDING + MAX_VISIBLE + 5 {
+            manager.push(Toast::system(format!("flood {}", i)));
+        }
+        manager.tick(LIFETIME, 0.0);
+        assert_eq!(manager.pending_len(), MAX_PENDING - MAX_VISIBLE);
+        assert_eq!(texts(&manager)[0], format!("flood {}", MAX_VISIBLE + 5));
+    }
+
+    #[test]
+    fn test_gameplay_toasts_wait_out_the_pause() {
+        let mut manager = ToastManager::new();
+        manager.push(Toast::gameplay("moved out of a wall"));
+        manager.push(Toast::system("report saved"));
+        manager.tick(0.0, 0.0);
+
+        // Paused: real time passes but play time doesn't
+        manager.tick(LIFETIME, 0.0);
+        assert_eq!(texts(&manager), ["moved out of a wall"]);
+        assert_eq!(manager.visible().next().unwrap().slide, 0.0);
+
+        manager.tick(LIFETIME, LIFETIME);
+        assert!(manager.is_empty());
+    }
+
+    #[test]
+    fn test_posting_from_other_threads() {
+        let mut manager = ToastManager::new();
+        let handles: Vec<_> = (0..4)
+            .map(|worker| {
+                let sender = manager.sender();
+                thread::spawn(move || {
+                    for i in 0..3 {
+                        sender.system(format!("{}-{}", worker, i));
+                    }
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        // Nothing is lost, and each thread's toasts keep their order
+        let mut seen = Vec::new();
+        while !manager.is_empty() || seen.is_empty() {
+            manager.tick(LIFETIME, 0.0);
+            seen.extend(texts(&manager));
+        }
+        assert_eq!(seen.len(), 12);
+        for worker in 0..4 {
+            let own: Vec<String> = seen
+                .iter()
+                .filter(|text| text.starts_with(&format!("{}-", worker)))
+                .cloned()
+                .collect();
+            let posted: Vec<String> = (0..3).map(|i| format!("{}-{}", worker, i)).collect();
+            assert_eq!(own, posted);
+        }
+
+        // A sender outliving its manager just drops what it posts
+        let sender = manager.sender();
+        drop(manager);
+        sender.system("nobody is listening");
+        ToastSender::detached().gameplay("nor here");
+    }
+}
@@ -0,0 +1,215 @@
+//! Enemy trail tracking for the Mirador game.
+//!
+//! This module provides [`EnemyTrail`], a fixed-size ring buffer of breadcrumbs marking where the
+//! enemy has recently walked. A breadcrumb is dropped every [`DEPOSIT_INTERVAL`] seconds and fades
+//! out over the trail lifetime, so the player can tell that the enemy passed through a corridor
+//! even when the enemy itself is out of sight.
+//!
+//! The trail lives on [`Enemy`](crate::game::enemy::Enemy), which is rebuilt for every level, so
+//! the trail is cleared on level transitions without any extra bookkeeping.
+//!
+//! # Usage
+//!
+//! ```rust,ignore
+//! let mut trail = EnemyTrail::new();
+//!
+//! // Once per gameplay frame
+//! trail.update(enemy_position, delta_time, EnemyTrail::lifetime_for(reduce_effects));
+//!
+//! for crumb in trail.iter() {
+//!     let fade = trail.fade(crumb); // 1.0 when fresh, 0.0 when expired
+//! }
+//! ```
+
+/// Maximum number of breadcrumbs kept in the trail.
+pub const TRAIL_CAPACITY: usize = 40;
+
+/// Seconds between breadcrumb deposits.
+pub const DEPOSIT_INTERVAL: f32 = 0.3;
+
+/// Seconds a breadcrumb stays visible.
+pub const TRAIL_LIFETIME: f32 = 10.0;
+
+/// Seconds a breadcrumb stays visible when reduced effects are enabled.
+pub const REDUCED_TRAIL_LIFETIME: f32 = 3.0;
+
+/// Minimum distance the enemy must move before another breadcrumb is dropped.
+///
+/// Keeps a stationary enemy from stacking every breadcrumb on the same spot.
+const MIN_DEPOSIT_DISTANCE: f32 = 5.0;
+
+/// A single point on the enemy trail.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Breadcrumb {
+    /// World position where the breadcrumb was dropped
+    pub position: [f32; 3],
+    /// Seconds since the breadcrumb was dropped
+    pub age: f32,
+}
+
+/// A ring buffer of the enemy's most recent positions.
+#[derive(Debug, Clone)]
+pub struct EnemyTrail {
+    /// Breadcrumb storage; only the first `len` slots (in ring order) are live
+    crumbs: [Breadcrumb; TRAIL_CAPACITY],
+    /// Slot the next breadcrumb will be written to
+    next: usize,
+    /// Number of breadcrumbs currently stored
+    len: usize,
+    /// Seconds since the last breadcrumb was dropped
+    since_deposit: f32,
+    /// Lifetime used by the most recent update
+    lifetime: f32,
+}
+
+impl Default for EnemyTrail {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl EnemyTrail {
+    /// Creates an empty trail.
+    pub fn new() -> Self {
+        Self {
+            crumbs: [Breadcrumb {
+                position: [0.0; 3],
+                age: 0.0,
+            }; TRAIL_CAPACITY],
+            next: 0,
+            len: 0,
+            since_deposit: DEPOSIT_INTERVAL,
+            lifetime: TRAIL_LIFETIME,
+        }
+    }
+
+    /// Returns the breadcrumb lifetime for the current effects setting.
+    ///
+    /// # Arguments
+    /// * `reduce_effects` - Whether the player has asked for reduced visual effects
+    pub fn lifetime_for(reduce_effects: bool) -> f32 {
+        if reduce_effects {
+            REDUCED_TRAIL_LIFETIME
+        } else {
+            TRAIL_LIFETIME
+        }
+    }
+
+    /// Returns the number of live breadcrumbs.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the trail has no breadcrumbs.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Ages the trail and drops a new breadcrumb when one is due.
+    ///
+    /// Breadcrumbs older than `lifetime` are discarded. When the buffer is full the oldest
+    /// breadcrumb is overwritten.
+    ///
+    /// # Arguments
+    /// * `enemy_position` - The enemy's current world position
+    /// * `delta_time` - Seconds since the last update
+    /// * `lifetime` - Seconds a breadcrumb stays visible
+    pub fn update(&mut self, enemy_position: [f32; 3], delta_time: f32, lifetime: f32) {
+        self.lifetime = lifetime;
+
+        for i in 0..self.len {
+            let slot = self.slot(i);
+            self.crumbs[slot].age += delta_time;
+        }
+
+        // Breadcrumbs are stored oldest first, so expired ones are always at the front
+        while self.len > 0 && self.crumbs[self.slot(0)].age >= lifetime {
+            self.len -= 1;
+        }
+
+        self.since_deposit += delta_time;
+        if self.since_deposit < DEPOSIT_INTERVAL {
+            return;
+        }
+
+        let moved_enough = self.newest().is_none_or(|newest| {
+            let dx = enemy_position[0] - newest.position[0];
+            let dz = enemy_position[2] - newest.position[2];
+            (dx * dx + dz * dz).sqrt() >= MIN_DEPOSIT_DISTANCE
+        });
+        if moved_enough {
+            self.push(enemy_position);
+        }
+    }
+
+    /// Returns the live breadcrumbs, oldest first.
+    pub fn iter(&self) -> impl Iterator<Item = &Breadcrumb> {
+        (0..self.len).map(|i| &self.crumbs[self.slot(i)])
+    }
+
+    /// Returns how visible a breadcrumb should be.
+    ///
+    /// # Arguments
+    /// * `crumb` - A breadcrumb from this trail
+    ///
+    /// # Returns
+    /// `1.0` for a fresh breadcrumb, falling linearly to `0.0` at the end of its lifetime
+    pub fn fade(&self, crumb: &Breadcrumb) -> f32 {
+        (1.0 - crumb.age / self.lifetime).clamp(0.0, 1.0)
+    }
+
+    /// Returns the most recently dropped breadcrumb, if any.
+    fn newest(&self) -> Option<&Breadcrumb> {
+        self.len.checked_sub(1).map(|i| &self.crumbs[self.slot(i)])
+    }
+
+    /// Adds a breadcrumb, overwriting the oldest one when full.
+    fn push(&mut self, position: [f32; 3]) {
+        self.crumbs[self.next] = Breadcrumb { position, age: 0.0 };
+        self.next = (self.next + 1) % TRAIL_CAPACITY;
+        self.len = (self.len + 1).min(TRAIL_CAPACITY);
+        self.since_deposit = 0.0;
+    }
+
+    /// Maps a position in oldest-first order to a storage slot.
+    fn slot(&self, index: usize) -> usize {
+        (self.next + TRAIL_CAPACITY - self.len + index) % TRAIL_CAPACITY
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_full_trail_overwrites_oldest() {
+        let mut trail = EnemyTrail::new();
+        for i in 0..TRAIL_CAPACITY + 5 {
+            trail.update([i as f32 * 10.0, 30.0, 0.0], DEPOSIT_INTERVAL, 1000.0);
+        }
+
+        assert_eq!(trail.len(), TRAIL_CAPACITY);
+        let first = trail.iter().next().unwrap();
+        let last = trail.iter().last().unwrap();
+        assert_eq!(first.position[0], 50.0);
+        assert_eq!(last.position[0], (TRAIL_CAPACITY + 4) as f32 * 10.0);
+    }
+
+    #[test]
+    fn test_breadcrumbs_fade_and_expire() {
+        let lifetime = REDUCED_TRAIL_LIFETIME;
+        let mut trail = EnemyTrail::new();
+        trail.update([0.0, 30.0, 0.0], 0.0, lifetime);
+        assert_eq!(trail.len(), 1);
+
+        // Stand still so no new breadcrumbs are dropped
+        trail.update([0.0, 30.0, 0.0], lifetime / 2.0, lifetime);
+        let crumb = *trail.iter().next().unwrap();
+        assert!((trail.fade(&crumb) - 0.5).abs() < 1e-5);
+
+        // The expired breadcrumb is dropped and a fresh one takes its place
+        trail.update([0.0, 30.0, 0.0], lifetime, lifetime);
+        assert_eq!(trail.len(), 1);
+        assert_eq!(trail.iter().next().unwrap().age, 0.0);
+    }
+}
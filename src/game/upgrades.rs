@@ -7,14 +7,15 @@
 //! # Overview
 //!
 //! The upgrade system consists of:
-//! - `Upgrade`: Represents a single upgrade with name, tooltip, and rarity
+//! - `Upgrade`: Represents a single upgrade with name, tooltip, rarity, and icon
+//! - `UpgradeIcon`: An icon embedded with an upgrade's definition, registered by the upgrade menu
 //! - `UpgradeRarity`: Defines the rarity levels and their associated weights
 //! - `AvailableUpgrade`: Enum of all possible upgrades in the game
 //! - `UpgradeManager`: Manages player upgrades and provides selection logic
 //!
 //! # Usage
 //!
-//! ```rust
+//! ```rust,ignore
 //! use mirador::game::upgrades::{UpgradeManager, AvailableUpgrade};
 //!
 //! let mut manager = UpgradeManager::new();
@@ -29,7 +30,9 @@
 //! let speed_count = manager.get_upgrade_count(&AvailableUpgrade::SpeedUp);
 //! ```
 
+use crate::assets;
 use rand::Rng;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
 /// Represents a single upgrade that can be applied to the player.
@@ -44,6 +47,21 @@ pub struct Upgrade {
     pub tooltip: String,
     /// The rarity level of the upgrade, affecting selection probability
     pub rarity: UpgradeRarity,
+    /// The icon shown on the upgrade's card, or `None` for the blank icon
+    pub icon: Option<UpgradeIcon>,
+}
+
+/// An icon that ships with an upgrade's definition.
+///
+/// Upgrade icons aren't part of the icon bundle every button manager loads at startup; the
+/// upgrade menu registers them itself with
+/// [`ButtonManager::register_icon_from_data`](crate::renderer::ui::button::ButtonManager::register_icon_from_data).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UpgradeIcon {
+    /// Icon texture ID the upgrade's card refers to
+    pub id: &'static str,
+    /// Encoded image bytes, embedded with `include_bytes!()`
+    pub bytes: &'static [u8],
 }
 
 /// Defines the rarity levels for upgrades and their associated weights.
@@ -76,7 +94,7 @@ impl UpgradeRarity {
     ///
     /// # Example
     ///
-    /// ```rust
+    /// ```rust,ignore
     /// use mirador::game::upgrades::UpgradeRarity;
     ///
     /// assert_eq!(UpgradeRarity::Common.weight(), 40.0);
@@ -98,7 +116,7 @@ impl UpgradeRarity {
 /// Each variant corresponds to a specific upgrade that can be applied
 /// to the player. This enum is used as a key in the upgrade manager
 /// to track how many of each upgrade the player has collected.
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum AvailableUpgrade {
     /// Increases player movement speed
     SpeedUp,
@@ -112,14 +130,31 @@ pub enum AvailableUpgrade {
     HeadStart,
     /// Increases maximum stamina for longer sprinting
     Dash,
+    /// Shrinks the player's collision radius to squeeze past corners
+    Slim,
+    /// Raises the player's acceleration so they reach full speed sooner
+    Lightfoot,
     /// A mysterious upgrade with unknown effects
     Unknown,
 }
 
 impl AvailableUpgrade {
+    /// Every upgrade in the game, including ones never offered in the upgrade menu.
+    pub const ALL: [AvailableUpgrade; 9] = [
+        AvailableUpgrade::SpeedUp,
+        AvailableUpgrade::SlowTime,
+        AvailableUpgrade::SilentStep,
+        AvailableUpgrade::TallBoots,
+        AvailableUpgrade::HeadStart,
+        AvailableUpgrade::Dash,
+        AvailableUpgrade::Slim,
+        AvailableUpgrade::Lightfoot,
+        AvailableUpgrade::Unknown,
+    ];
+
     /// Converts this upgrade variant into a full `Upgrade` struct.
     ///
-    /// This method provides the name, tooltip, and rarity for each upgrade type.
+    /// This method provides the name, tooltip, rarity, and icon for each upgrade type.
     ///
     /// # Returns
     ///
@@ -127,7 +162,7 @@ impl AvailableUpgrade {
     ///
     /// # Example
     ///
-    /// ```rust
+    /// ```rust,ignore
     /// use mirador::game::upgrades::{AvailableUpgrade, UpgradeRarity};
     ///
     /// let upgrade = AvailableUpgrade::SpeedUp.to_upgrade();
@@ -141,24 +176,40 @@ impl AvailableUpgrade {
                 tooltip: "Increases your movement speed, making you faster and more agile."
                     .to_string(),
                 rarity: UpgradeRarity::Common,
+                icon: Some(UpgradeIcon {
+                    id: "speed_up_icon",
+                    bytes: assets::SPEED_UP_ICON,
+                }),
             },
             AvailableUpgrade::SlowTime => Upgrade {
                 name: "Slow Time".to_string(),
                 tooltip: "Each second lasts longer, giving you more time to navigate the maze."
                     .to_string(),
                 rarity: UpgradeRarity::Uncommon,
+                icon: Some(UpgradeIcon {
+                    id: "slower_seconds_icon",
+                    bytes: assets::SLOWER_SECONDS_ICON,
+                }),
             },
             AvailableUpgrade::SilentStep => Upgrade {
                 name: "Silent Step".to_string(),
                 tooltip: "Reduces the noise you make while moving, making you harder to detect."
                     .to_string(),
                 rarity: UpgradeRarity::Rare,
+                icon: Some(UpgradeIcon {
+                    id: "silent_step_icon",
+                    bytes: assets::SILENT_STEP_ICON,
+                }),
             },
             AvailableUpgrade::TallBoots => Upgrade {
                 name: "Tall Boots".to_string(),
                 tooltip: "Makes you taller, allowing you to better see over the walls of the maze."
                     .to_string(),
                 rarity: UpgradeRarity::Uncommon,
+                icon: Some(UpgradeIcon {
+                    id: "tall_boots_icon",
+                    bytes: assets::TALL_BOOTS_ICON,
+                }),
             },
             AvailableUpgrade::HeadStart => Upgrade {
                 name: "Head Start".to_string(),
@@ -166,18 +217,45 @@ impl AvailableUpgrade {
                     "Prevents the enemy from moving for a short time at the start of each level."
                         .to_string(),
                 rarity: UpgradeRarity::Rare,
+                icon: Some(UpgradeIcon {
+                    id: "head_start_icon",
+                    bytes: assets::HEAD_START_ICON,
+                }),
             },
             AvailableUpgrade::Dash => Upgrade {
                 name: "Dash".to_string(),
                 tooltip: "Increase your maximum stamina, allowing you to sprint for longer."
                     .to_string(),
                 rarity: UpgradeRarity::Epic,
+                icon: Some(UpgradeIcon {
+                    id: "dash_icon",
+                    bytes: assets::DASH_ICON,
+                }),
+            },
+            AvailableUpgrade::Slim => Upgrade {
+                name: "Slim".to_string(),
+                tooltip:
+                    "Makes you narrower, so you brush past corners instead of catching on them."
+                        .to_string(),
+                rarity: UpgradeRarity::Uncommon,
+                icon: None,
+            },
+            AvailableUpgrade::Lightfoot => Upgrade {
+                name: "Lightfoot".to_string(),
+                tooltip: "You get up to speed faster, making quick corrections snappier."
+                    .to_string(),
+                rarity: UpgradeRarity::Common,
+                icon: None,
             },
             AvailableUpgrade::Unknown => Upgrade {
                 name: "Unknown".to_string(),
                 tooltip: "A mysterious upgrade with unpredictable effects. What could it do?"
                     .to_string(),
                 rarity: UpgradeRarity::Legendary,
+                icon: Some(UpgradeIcon {
+                    id: "unknown_icon",
+                    bytes: assets::UNKNOWN_ICON,
+                }),
             },
         }
     }
@@ -190,7 +268,7 @@ impl AvailableUpgrade {
 ///
 /// # Examples
 ///
-/// ```rust
+/// ```rust,ignore
 /// use mirador::game::upgrades::{UpgradeManager, AvailableUpgrade};
 ///
 /// let mut manager = UpgradeManager::new();
@@ -238,7 +316,7 @@ impl UpgradeManager {
     ///
     /// # Examples
     ///
-    /// ```rust
+    /// ```rust,ignore
     /// use mirador::game::upgrades::UpgradeManager;
     ///
     /// let manager = UpgradeManager::new();
@@ -264,6 +342,8 @@ impl UpgradeManager {
             AvailableUpgrade::TallBoots,
             AvailableUpgrade::HeadStart,
             AvailableUpgrade::Dash,
+            AvailableUpgrade::Slim,
+            AvailableUpgrade::Lightfoot,
         ];
 
         // Weighted random selection based on rarity, ensuring no duplicates
@@ -319,7 +399,7 @@ impl UpgradeManager {
     ///
     /// # Examples
     ///
-    /// ```rust
+    /// ```rust,ignore
     /// use mirador::game::upgrades::{UpgradeManager, AvailableUpgrade};
     ///
     /// let mut manager = UpgradeManager::new();
@@ -357,7 +437,7 @@ impl UpgradeManager {
     ///
     /// # Examples
     ///
-    /// ```rust
+    /// ```rust,ignore
     /// use mirador::game::upgrades::{UpgradeManager, AvailableUpgrade};
     ///
     /// let mut manager = UpgradeManager::new();
@@ -390,7 +470,7 @@ impl UpgradeManager {
     ///
     /// # Examples
     ///
-    /// ```rust
+    /// ```rust,ignore
     /// use mirador::game::upgrades::{UpgradeManager, AvailableUpgrade};
     ///
     /// let mut manager = UpgradeManager::new();
@@ -443,6 +523,8 @@ impl UpgradeManager {
             AvailableUpgrade::TallBoots,
             AvailableUpgrade::HeadStart,
             AvailableUpgrade::Dash,
+            AvailableUpgrade::Slim,
+            AvailableUpgrade::Lightfoot,
         ];
 
         for available in all_upgrades {
@@ -495,4 +577,20 @@ mod tests {
         names.dedup();
         assert_eq!(names.len(), 6);
     }
+
+    /// Tests that upgrades with their own icon don't share an ID with each other or the bundle.
+    #[test]
+    fn test_upgrade_icons_have_their_own_ids() {
+        let mut ids: Vec<&str> = AvailableUpgrade::ALL
+            .iter()
+            .filter_map(|upgrade| upgrade.to_upgrade().icon)
+            .map(|icon| icon.id)
+            .collect();
+        assert_eq!(ids.len(), 7);
+        ids.extend(assets::icon_textures().iter().map(|(id, _)| *id));
+        let count = ids.len();
+        ids.sort();
+        ids.dedup();
+        assert_eq!(ids.len(), count);
+    }
 }
@@ -0,0 +1,336 @@
+//! Detection of the player ending up inside a wall.
+//!
+//! The collision resolver should never let the player into a wall, but reports of it still come
+//! in and it has never been reproduced. [`WallClipMonitor`] keeps the player's last
+//! [`CLIP_HISTORY`] positions and frame times in a fixed ring, and once every
+//! [`CHECK_INTERVAL`] seconds of play asks whether the player is inside a wall (see
+//! [`CollisionSystem::wall_containing`](crate::game::collision::CollisionSystem::wall_containing)).
+//! When they are, a [`ClipReport`] with the history leading up to it is recorded in the session
+//! journal, the player is moved to the nearest place they can stand, and play carries on.
+//!
+//! Recording a frame copies two values into the ring and the check is a single grid lookup, so
+//! the monitor is always on. A report allocates at most [`CLIP_HISTORY`] entries per list, and
+//! only [`MAX_REPORTS_PER_LEVEL`] are captured per level, so a player stuck in a loop of clips
+//! can't turn the check into a frame spike.
+//!
+//! Run the game with `--clip-reports` to also write each report to its own file in the data
+//! directory (see [`crate::paths`]). The file is written on a background thread, which posts a
+//! toast naming it once it is saved.
+//!
+//! # Usage
+//!
+//! ```rust,ignore
+//! // Once per gameplay frame
+//! if monitor.record(player.position, delta_time) {
+//!     if let Some(wall) = collision_system.wall_containing(player.position, player.layer) {
+//!         if let Some(report) = monitor.capture(context, wall, resolved) {
+//!             journal.record(JournalEvent::WallClipped { report });
+//!         }
+//!     }
+//! }
+//! ```
+
+use crate::game::maze::generator::Cell;
+use crate::game::toasts::ToastSender;
+use chrono::Local;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+
+/// Command-line flag that writes each wall-clip report to its own file.
+pub const CLIP_REPORTS_FLAG: &str = "--clip-reports";
+
+/// Seconds of play between checks for the player being inside a wall.
+pub const CHECK_INTERVAL: f32 = 1.0;
+
+/// Frames of position and timing history kept for a report.
+pub const CLIP_HISTORY: usize = 120;
+
+/// Most reports captured in one level; later clips are still resolved but not reported.
+pub const MAX_REPORTS_PER_LEVEL: usize = 3;
+
+/// Whether reports are written to files, set once from the command line.
+static WRITE_REPORT_FILES: AtomicBool = AtomicBool::new(false);
+
+/// Turns on writing each report to its own file, for the `--clip-reports` flag.
+pub fn enable_report_files() {
+    WRITE_REPORT_FILES.store(true, Ordering::Relaxed);
+}
+
+/// Returns whether reports are written to their own files.
+pub fn report_files_enabled() -> bool {
+    WRITE_REPORT_FILES.load(Ordering::Relaxed)
+}
+
+/// What was going on in the game when the player was found inside a wall.
+#[derive(Debug, Clone, Copy)]
+pub struct ClipContext<'a> {
+    /// Level number
+    pub level: i32,
+    /// Seed of the run, which every level's maze is generated from
+    pub run_seed: u64,
+    /// Whether the player was on the upper floor of a two-floor level
+    pub upper_floor: bool,
+    /// Upgrades the player holds, with how many of each
+    pub upgrades: &'a [(String, u32)],
+}
+
+/// Everything known about one time the player was found inside a wall.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ClipReport {
+    /// Level number
+    pub level: i32,
+    /// Seed of the run, which every level's maze is generated from
+    pub run_seed: u64,
+    /// Whether the player was on the upper floor of a two-floor level
+    pub upper_floor: bool,
+    /// Wall-grid cell of the wall the player was inside
+    pub wall: Cell,
+    /// Where the player was found
+    pub position: [f32; 3],
+    /// Where the player was moved to
+    pub resolved: [f32; 3],
+    /// Upgrades the player held, as `"name xcount"`
+    pub upgrades: Vec<String>,
+    /// The player's position each frame before the check, oldest first
+    pub positions: Vec<[f32; 3]>,
+    /// Seconds each of those frames took, oldest first
+    pub delta_times: Vec<f32>,
+}
+
+/// Watches the player for ending up inside a wall.
+#[derive(Debug, Clone)]
+pub struct WallClipMonitor {
+    /// Position of each recent frame; only the first `len` slots (in ring order) are live
+    positions: [[f32; 3]; CLIP_HISTORY],
+    /// Frame time of each recent frame, in the same slots as `positions`
+    delta_times: [f32; CLIP_HISTORY],
+    /// Slot the next frame will be written to
+    next: usize,
+    /// Number of frames currently stored
+    len: usize,
+    /// Seconds of play since the last check
+    since_check: f32,
+    /// Reports captured in the current level
+    reports: usize,
+}
+
+impl Default for WallClipMonitor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl WallClipMonitor {
+    /// Creates a monitor with no history.
+    pub fn new() -> Self {
+        Self {
+            positions: [[0.0; 3]; CLIP_HISTORY],
+            delta_times: [0.0; CLIP_HISTORY],
+            next: 0,
+            len: 0,
+            since_check: 0.0,
+            reports: 0,
+        }
+    }
+
+    /// Forgets the history and the reports captured, ready for a new level.
+    pub fn reset(&mut self) {
+        *self = Self::new();
+    }
+
+    /// Records one gameplay frame.
+    ///
+    /// # Arguments
+    /// * `position` - The player's position after this frame's movement
+    /// * `delta_time` - Seconds the frame took
+    ///
+    /// # Returns
+    /// `true` once every [`CHECK_INTERVAL`], when the player should be checked
+    pub fn record(&mut self, position: [f32; 3], delta_time: f32) -> bool {
+        self.positions[self.next] = position;
+        self.delta_times[self.next] = delta_time;
+        self.next = (self.next + 1) % CLIP_HISTORY;
+        self.len = (self.len + 1).min(CLIP_HISTORY);
+
+        self.since_check += delta_time;
+        if self.since_check < CHECK_INTERVAL {
+            return false;
+        }
+        self.since_check = 0.0;
+        true
+    }
+
+    /// Returns the recorded frames as `(position, delta_time)`, oldest first.
+    pub fn history(&self) -> impl Iterator<Item = ([f32; 3], f32)> + '_ {
+        let start = (self.next + CLIP_HISTORY - self.len) % CLIP_HISTORY;
+        (0..self.len).map(move |i| {
+            let slot = (start + i) % CLIP_HISTORY;
+            (self.positions[slot], self.delta_times[slot])
+        })
+    }
+
+    /// Builds the report for a clip, unless this level has had its share.
+    ///
+    /// # Arguments
+    /// * `context` - What was going on in the game
+    /// * `wall` - Wall-grid cell of the wall the player was inside
+    /// * `resolved` - Where the player was moved to
+    ///
+    /// # Returns
+    /// The report, or `None` after [`MAX_REPORTS_PER_LEVEL`] reports this level
+    pub fn capture(
+        &mut self,
+        context: ClipContext,
+        wall: Cell,
+        resolved: [f32; 3],
+    ) -> Option<ClipReport> {
+        if self.reports >= MAX_REPORTS_PER_LEVEL {
+            return None;
+        }
+        self.reports += 1;
+
+        let mut positions = Vec::with_capacity(self.len);
+        let mut delta_times = Vec::with_capacity(self.len);
+        for (position, delta_time) in self.history() {
+            positions.push(position);
+            delta_times.push(delta_time);
+        }
+        Some(ClipReport {
+            level: context.level,
+            run_seed: context.run_seed,
+            upper_floor: context.upper_floor,
+            wall,
+            position: positions.last().copied().unwrap_or(resolved),
+            resolved,
+            upgrades: context
+                .upgrades
+                .iter()
+                .map(|(name, count)| format!("{} x{}", name, count))
+                .collect(),
+            positions,
+            delta_times,
+        })
+    }
+}
+
+/// Writes a report to its own file.
+///
+/// # Arguments
+/// * `report` - The report to write
+/// * `dir` - Directory to write it to, created if missing
+///
+/// # Returns
+/// The path of the new file, or an error message if it could not be written
+pub fn write_report(report: &ClipReport, dir: &Path) -> Result<PathBuf, String> {
+    fs::create_dir_all(dir)
+        .map_err(|e| format!("Failed to create clip report directory: {}", e))?;
+    let contents = serde_json::to_string_pretty(report)
+        .map_err(|e| format!("Failed to serialize clip report: {}", e))?;
+
+    let stamp = Local::now().format("%Y%m%d-%H%M%S");
+    let mut path = dir.join(format!("clip-{}.json", stamp));
+    let mut suffix = 1;
+    while path.exists() {
+        path = dir.join(format!("clip-{}-{}.json", stamp, suffix));
+        suffix += 1;
+    }
+    fs::write(&path, contents)
+        .map_err(|e| format!("Failed to write clip report to {}: {}", path.display(), e))?;
+    Ok(path)
+}
+
+/// Writes a report to its own file on a background thread, so the frame never waits on disk.
+///
+/// Posts a toast naming the file once it is written, or saying it couldn't be.
+///
+/// # Arguments
+/// * `report` - The report to write
+/// * `dir` - Directory to write it to, created if missing
+/// * `toasts` - Where to post the outcome
+pub fn write_report_in_background(report: ClipReport, dir: PathBuf, toasts: ToastSender) {
+    let spawned = thread::Builder::new()
+        .name("clip-report-writer".to_string())
+        .spawn(move || match write_report(&report, &dir) {
+            Ok(path) => {
+                println!("Clip report written to {}", path.display());
+                let name = path.file_name().unwrap_or(path.as_os_str());
+                toasts.system(format!("Clip report saved as {}", name.to_string_lossy()));
+            }
+            Err(e) => {
+                eprintln!("{}", e);
+                toasts.system("Couldn't save clip report");
+            }
+        });
+    if let Err(e) = spawned {
+        eprintln!("Failed to start clip report writer: {}", e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn context(upgrades: &[(String, u32)]) -> ClipContext<'_> {
+        ClipContext {
+            level: 4,
+            run_seed: 42,
+            upper_floor: false,
+            upgrades,
+        }
+    }
+
+    #[test]
+    fn test_checks_once_per_interval_not_per_frame() {
+        let mut monitor = WallClipMonitor::new();
+        let frame = 1.0 / 60.0;
+        let checks = (0..600).filter(|_| monitor.record([0.0; 3], frame)).count();
+        assert!((9..=10).contains(&checks), "{}", checks);
+
+        // A long hitch is still only one check
+        assert!(monitor.record([0.0; 3], 5.0));
+        assert!(!monitor.record([0.0; 3], frame));
+    }
+
+    #[test]
+    fn test_report_holds_the_latest_history_in_order() {
+        let mut monitor = WallClipMonitor::new();
+        let frames = CLIP_HISTORY + 30;
+        for i in 0..frames {
+            monitor.record([i as f32, 0.0, 0.0], i as f32 / 1000.0);
+        }
+        let upgrades = vec![("Slim".to_string(), 2)];
+        let report = monitor
+            .capture(context(&upgrades), Cell::new(2, 3), [1.0, 2.0, 3.0])
+            .expect("first report");
+
+        assert_eq!(report.positions.len(), CLIP_HISTORY);
+        assert_eq!(report.delta_times.len(), CLIP_HISTORY);
+        assert_eq!(report.positions[0], [30.0, 0.0, 0.0]);
+        assert_eq!(report.position, [(frames - 1) as f32, 0.0, 0.0]);
+        assert_eq!(report.upgrades, vec!["Slim x2"]);
+        assert_eq!((report.level, report.run_seed), (4, 42));
+        // Capturing never grows past the ring
+        assert!(report.positions.capacity() <= CLIP_HISTORY);
+    }
+
+    #[test]
+    fn test_reports_are_capped_per_level() {
+        let mut monitor = WallClipMonitor::new();
+        monitor.record([0.0; 3], 0.1);
+        let reports = (0..MAX_REPORTS_PER_LEVEL + 2)
+            .filter_map(|_| monitor.capture(context(&[]), Cell::new(0, 0), [0.0; 3]))
+            .count();
+        assert_eq!(reports, MAX_REPORTS_PER_LEVEL);
+
+        monitor.reset();
+        assert_eq!(monitor.history().count(), 0);
+        assert!(
+            monitor
+                .capture(context(&[]), Cell::new(0, 0), [0.0; 3])
+                .is_some()
+        );
+    }
+}
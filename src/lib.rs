@@ -0,0 +1,75 @@
+//! Mirador - A 3D Maze Runner Game
+//!
+//! Mirador is a first-person maze runner game built with Rust and WGPU, featuring
+//! procedurally generated mazes, enemy AI, audio systems, and a progression system with
+//! upgrades. The `mirador` binary is a thin command line over this library, which other
+//! front-ends and tools can depend on to generate mazes and draw them.
+//!
+//! # Features
+//! - **3D Graphics**: Real-time 3D rendering using WGPU
+//! - **Procedural Generation**: Dynamically generated mazes with increasing complexity
+//! - **Enemy AI**: Rotation-based pathfinding enemy that pursues the player
+//! - **Audio System**: 3D spatial audio with multiple sound sources
+//! - **Progression System**: Player upgrades and level-based difficulty scaling
+//! - **Test Mode**: Development mode with simplified gameplay for testing
+//!
+//! # Architecture
+//! The crate follows a modular architecture:
+//! - `app`: Application state management and event handling, and [`run`] to play the game
+//!   in its own window
+//! - `game`: Core game logic, player, enemy, and maze systems
+//! - `renderer`: Graphics rendering pipeline and UI components
+//! - `math`: Mathematical utilities for 3D graphics
+//! - `paths`: Where settings, results, and journals are saved
+//! - `benchmarks`: Performance profiling
+//! - `test_mode`: The test level for development, the soak test, and the self-test
+//!
+//! These modules are internal; the types other crates need are re-exported at the root.
+//!
+//! # Embedding
+//! The entry points that don't need the game's window or an audio device are:
+//! - [`MazeGenerator`], which carves a maze from a seed, with [`Maze::to_mz_string`] and
+//!   [`parse_maze_str`] to save and load it
+//! - [`GameState::builder`], for a game state without audio
+//! - [`MazeView`], which draws the maze with a wgpu device, queue, and target texture
+//!   created by the caller
+//!
+//! ```rust
+//! use mirador::{parse_maze_str, MazeGenerator};
+//!
+//! let (mut generator, maze) = MazeGenerator::with_seed(8, 6, 42);
+//! while !generator.is_complete() {
+//!     generator.step();
+//! }
+//!
+//! let maze = maze.lock().unwrap();
+//! let (grid, exit_cell) = maze.level_grid();
+//! assert_eq!((grid.len(), grid[0].len()), (13, 17));
+//! assert!(exit_cell.is_some());
+//!
+//! // The .mz text reads back as the same level
+//! assert_eq!(parse_maze_str(&maze.to_mz_string()).unwrap(), (grid, exit_cell));
+//! ```
+
+#![warn(missing_docs)]
+pub(crate) mod app;
+pub(crate) mod assets;
+pub(crate) mod benchmarks;
+pub(crate) mod game;
+pub(crate) mod math;
+pub(crate) mod paths;
+pub(crate) mod renderer;
+pub(crate) mod test_mode;
+
+pub use app::cli::run_from_args;
+pub use app::run;
+pub use game::audio::GameAudioManager;
+pub use game::collision::CollisionSystem;
+pub use game::enemy::Enemy;
+pub use game::maze::generator::{Maze, MazeGenerator};
+pub use game::maze::parse_maze_str;
+pub use game::player::Player;
+pub use game::{CurrentScreen, GameState, GameStateBuilder};
+pub use paths::Paths;
+pub use renderer::backend::GraphicsBackend;
+pub use renderer::maze_view::MazeView;
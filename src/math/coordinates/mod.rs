@@ -18,13 +18,30 @@ pub mod constants {
     /// Standard height of the player in the world
     pub const PLAYER_HEIGHT: f32 = 50.0;
 
-    /// Get the floor size based on test mode
-    /// In test mode, the floor is 1/4 the size of normal mode
-    pub fn get_floor_size(is_test_mode: bool) -> f32 {
+    /// Floor size of the test maze, which is always the same size.
+    pub const TEST_FLOOR_SIZE: f32 = 1500.0;
+
+    /// Floor size of a standard 25x25 maze.
+    pub const STANDARD_FLOOR_SIZE: f32 = 3000.0;
+
+    /// Larger side of a standard 25x25 maze's wall grid, which fills [`STANDARD_FLOOR_SIZE`].
+    pub const STANDARD_GRID_SIZE: usize = 51;
+
+    /// Get the floor size of a maze.
+    ///
+    /// Outside test mode the floor grows with the maze, so every maze's cells are as wide as
+    /// a standard maze's whatever its size. In test mode the floor is always
+    /// [`TEST_FLOOR_SIZE`].
+    ///
+    /// # Arguments
+    /// * `maze_dimensions` - The dimensions of the wall grid (width, height) in cells
+    /// * `is_test_mode` - Whether the test maze is being played
+    pub fn get_floor_size(maze_dimensions: (usize, usize), is_test_mode: bool) -> f32 {
         if is_test_mode {
-            1500.0 // 1/4 of 3000.0 <-- meth-a-matics ??
+            TEST_FLOOR_SIZE
         } else {
-            3000.0
+            let (maze_width, maze_height) = maze_dimensions;
+            STANDARD_FLOOR_SIZE * maze_width.max(maze_height) as f32 / STANDARD_GRID_SIZE as f32
         }
     }
 }
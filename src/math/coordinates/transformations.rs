@@ -29,7 +29,7 @@ pub fn maze_to_world(
 ) -> [f32; 3] {
     let (maze_width, maze_height) = maze_dimensions;
     let max_dimension = maze_width.max(maze_height) as f32;
-    let cell_size = get_floor_size(is_test_mode) / max_dimension;
+    let cell_size = get_floor_size(maze_dimensions, is_test_mode) / max_dimension;
 
     // Calculate the world origin offset (bottom-left corner of the maze)
     let origin_x = -(maze_width as f32 * cell_size) / 2.0;
@@ -60,7 +60,7 @@ pub fn world_to_maze(
 ) -> Cell {
     let (maze_width, maze_height) = maze_dimensions;
     let max_dimension = maze_width.max(maze_height) as f32;
-    let cell_size = get_floor_size(is_test_mode) / max_dimension;
+    let cell_size = get_floor_size(maze_dimensions, is_test_mode) / max_dimension;
 
     let origin_x = -(maze_width as f32 * cell_size) / 2.0;
     let origin_z = -(maze_height as f32 * cell_size) / 2.0;
@@ -89,44 +89,5 @@ pub fn world_to_maze(
 pub fn calculate_cell_size(maze_dimensions: (usize, usize), is_test_mode: bool) -> f32 {
     let (maze_width, maze_height) = maze_dimensions;
     let max_dimension = maze_width.max(maze_height) as f32;
-    get_floor_size(is_test_mode) / max_dimension
-}
-
-/// Calculates the world coordinates of the bottom-left cell in the maze.
-///
-/// # Arguments
-/// * `maze_dimensions` - The dimensions of the maze (width, height) in cells
-/// * `y_position` - The desired y-coordinate (height) in the world
-///
-/// # Returns
-/// The world coordinates for the bottom-left cell
-pub fn get_bottom_left_cell_position(
-    maze_dimensions: (usize, usize),
-    y_position: f32,
-    is_test_mode: bool,
-) -> [f32; 3] {
-    // Bottom-left cell is at (height-1, 0) in our grid system
-    let (_, maze_height) = maze_dimensions;
-    let bottom_left = Cell::new(maze_height - 1, 0);
-    maze_to_world(&bottom_left, maze_dimensions, y_position, is_test_mode)
-}
-
-/// Converts a position in the maze wall grid to a position in the maze cell grid.
-///
-/// # Arguments
-/// * `wall_row` - Row in the wall grid
-/// * `wall_col` - Column in the wall grid
-///
-/// # Returns
-/// A tuple (is_cell, Cell) where is_cell indicates if this position represents a cell
-/// (not a wall or passage), and Cell is the corresponding maze cell if applicable
-pub fn wall_grid_to_maze_cell(wall_row: usize, wall_col: usize) -> (bool, Cell) {
-    let is_cell = wall_row % 2 == 1 && wall_col % 2 == 1;
-    let cell = if is_cell {
-        Cell::new(wall_row / 2, wall_col / 2)
-    } else {
-        Cell::new(0, 0) // Default, not valid if is_cell is false
-    };
-
-    (is_cell, cell)
+    get_floor_size(maze_dimensions, is_test_mode) / max_dimension
 }
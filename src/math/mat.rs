@@ -49,7 +49,7 @@ use crate::math::deg_to_rad;
 /// # Examples
 ///
 /// Creating a translation matrix:
-/// ```
+/// ```rust,ignore
 /// # use your_crate::math::Mat4;
 /// let translation = Mat4::translation(2.0, 3.0, 4.0);
 /// ```
@@ -268,6 +268,78 @@ impl Mat4 {
         ])
     }
 
+    /// Computes the inverse of any invertible matrix.
+    ///
+    /// Unlike [`Mat4::inverse`], this handles projective matrices such as a combined
+    /// view-projection, which is what unprojecting a screen point needs.
+    ///
+    /// # Returns
+    /// - `Some(inverse)` if the matrix is invertible
+    /// - `None` if it is singular
+    pub fn try_inverse(&self) -> Option<Mat4> {
+        let m = self.0;
+
+        // Determinants of the 2x2 minors built from the first two and last two columns
+        let s0 = m[0][0] * m[1][1] - m[1][0] * m[0][1];
+        let s1 = m[0][0] * m[1][2] - m[1][0] * m[0][2];
+        let s2 = m[0][0] * m[1][3] - m[1][0] * m[0][3];
+        let s3 = m[0][1] * m[1][2] - m[1][1] * m[0][2];
+        let s4 = m[0][1] * m[1][3] - m[1][1] * m[0][3];
+        let s5 = m[0][2] * m[1][3] - m[1][2] * m[0][3];
+
+        let c5 = m[2][2] * m[3][3] - m[3][2] * m[2][3];
+        let c4 = m[2][1] * m[3][3] - m[3][1] * m[2][3];
+        let c3 = m[2][1] * m[3][2] - m[3][1] * m[2][2];
+        let c2 = m[2][0] * m[3][3] - m[3][0] * m[2][3];
+        let c1 = m[2][0] * m[3][2] - m[3][0] * m[2][2];
+        let c0 = m[2][0] * m[3][1] - m[3][0] * m[2][1];
+
+        let det = s0 * c5 - s1 * c4 + s2 * c3 + s3 * c2 - s4 * c1 + s5 * c0;
+        if det.abs() < f32::MIN_POSITIVE {
+            return None;
+        }
+        let inv_det = 1.0 / det;
+
+        Some(Mat4([
+            [
+                (m[1][1] * c5 - m[1][2] * c4 + m[1][3] * c3) * inv_det,
+                (-m[0][1] * c5 + m[0][2] * c4 - m[0][3] * c3) * inv_det,
+                (m[3][1] * s5 - m[3][2] * s4 + m[3][3] * s3) * inv_det,
+                (-m[2][1] * s5 + m[2][2] * s4 - m[2][3] * s3) * inv_det,
+            ],
+            [
+                (-m[1][0] * c5 + m[1][2] * c2 - m[1][3] * c1) * inv_det,
+                (m[0][0] * c5 - m[0][2] * c2 + m[0][3] * c1) * inv_det,
+                (-m[3][0] * s5 + m[3][2] * s2 - m[3][3] * s1) * inv_det,
+                (m[2][0] * s5 - m[2][2] * s2 + m[2][3] * s1) * inv_det,
+            ],
+            [
+                (m[1][0] * c4 - m[1][1] * c2 + m[1][3] * c0) * inv_det,
+                (-m[0][0] * c4 + m[0][1] * c2 - m[0][3] * c0) * inv_det,
+                (m[3][0] * s4 - m[3][1] * s2 + m[3][3] * s0) * inv_det,
+                (-m[2][0] * s4 + m[2][1] * s2 - m[2][3] * s0) * inv_det,
+            ],
+            [
+                (-m[1][0] * c3 + m[1][1] * c1 - m[1][2] * c0) * inv_det,
+                (m[0][0] * c3 - m[0][1] * c1 + m[0][2] * c0) * inv_det,
+                (-m[3][0] * s3 + m[3][1] * s1 - m[3][2] * s0) * inv_det,
+                (m[2][0] * s3 - m[2][1] * s1 + m[2][2] * s0) * inv_det,
+            ],
+        ]))
+    }
+
+    /// Returns this matrix with its translation removed.
+    ///
+    /// For a view matrix this keeps only the camera's rotation, which is what a background
+    /// that should stay infinitely far away, like a sky, is drawn with.
+    pub fn without_translation(&self) -> Mat4 {
+        let mut m = self.0;
+        m[3][0] = 0.0;
+        m[3][1] = 0.0;
+        m[3][2] = 0.0;
+        Mat4(m)
+    }
+
     /// Multiplies two matrices (self * b).
     ///
     /// # Note
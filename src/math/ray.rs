@@ -0,0 +1,158 @@
+//! View rays for backgrounds drawn infinitely far away.
+//!
+//! A sky is drawn with a single full-screen triangle: every pixel turns its normalized device
+//! coordinates back into a world-space direction and looks that direction up in the sky
+//! texture. Only the camera's rotation takes part, so walking around never moves the sky.
+//!
+//! [`sky_ray_matrix`] builds the inverse view-projection the shader receives. The tests check
+//! it against `view_ray`, the per-pixel reconstruction the shader mirrors, and `equirect_uv`,
+//! which maps the resulting direction onto an equirectangular panorama. The axis conventions are the game's own: +X is
+//! right, +Y is up, and a camera with no yaw or pitch looks down -Z.
+//!
+//! # Usage
+//!
+//! ```rust,ignore
+//! use mirador::math::mat::Mat4;
+//! use mirador::math::ray::{equirect_uv, sky_ray_matrix, view_ray};
+//!
+//! // A camera standing anywhere, looking straight ahead
+//! let view = Mat4::translation(-40.0, -50.0, 120.0);
+//! let projection = Mat4::perspective(1.2, 16.0 / 9.0, 0.1, 1000.0);
+//! let inverse = sky_ray_matrix(&view, &projection).unwrap();
+//!
+//! // The center of the screen looks at the middle of the panorama
+//! let uv = equirect_uv(view_ray([0.0, 0.0], &inverse));
+//! assert!((uv[0] - 0.5).abs() < 1e-5 && (uv[1] - 0.5).abs() < 1e-5);
+//! ```
+
+use crate::math::mat::Mat4;
+#[cfg(test)]
+use std::f32::consts::{PI, TAU};
+
+/// Builds the matrix that turns clip-space positions into world-space view directions.
+///
+/// # Arguments
+/// * `view` - The camera's view matrix; its translation is ignored
+/// * `projection` - The camera's projection matrix
+///
+/// # Returns
+/// The inverse of the rotation-only view-projection, or `None` if it is singular
+pub fn sky_ray_matrix(view: &Mat4, projection: &Mat4) -> Option<Mat4> {
+    view.without_translation()
+        .multiply(projection)
+        .try_inverse()
+}
+
+/// Reconstructs the direction a point of the screen looks in.
+///
+/// # Arguments
+/// * `ndc` - Normalized device coordinates, from -1.0 to 1.0 with +y up
+/// * `inverse` - Matrix from [`sky_ray_matrix`]
+///
+/// # Returns
+/// A unit-length world-space direction
+#[cfg(test)]
+pub fn view_ray(ndc: [f32; 2], inverse: &Mat4) -> [f32; 3] {
+    // Any point along the ray will do; the far plane is never degenerate
+    let m = inverse.0;
+    let clip = [ndc[0], ndc[1], 1.0, 1.0];
+    let mut world = [0.0; 4];
+    for (j, value) in world.iter_mut().enumerate() {
+        *value = clip[0] * m[0][j] + clip[1] * m[1][j] + clip[2] * m[2][j] + clip[3] * m[3][j];
+    }
+    let direction = [
+        world[0] / world[3],
+        world[1] / world[3],
+        world[2] / world[3],
+    ];
+    let length = direction.iter().map(|c| c * c).sum::<f32>().sqrt();
+    direction.map(|c| c / length)
+}
+
+/// Finds where a direction lands on an equirectangular panorama.
+///
+/// The middle of the panorama is straight ahead (-Z), its left and right edges meet straight
+/// behind (+Z), and its top and bottom rows are straight up and down.
+///
+/// # Arguments
+/// * `direction` - A unit-length world-space direction
+///
+/// # Returns
+/// `[u, v]` texture coordinates from 0.0 to 1.0, with v = 0.0 at the top
+#[cfg(test)]
+pub fn equirect_uv(direction: [f32; 3]) -> [f32; 2] {
+    [
+        0.5 + direction[0].atan2(-direction[2]) / TAU,
+        0.5 - direction[1].clamp(-1.0, 1.0).asin() / PI,
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Builds a view matrix the way the player's camera does.
+    fn camera(position: [f32; 3], yaw: f32, pitch: f32) -> Mat4 {
+        let rotation = Mat4::rotation_y(yaw).multiply(&Mat4::rotation_x(pitch));
+        Mat4::translation(-position[0], -position[1], -position[2]).multiply(&rotation)
+    }
+
+    /// The direction the player looks in, as in `Player::view_direction`.
+    fn forward(yaw: f32, pitch: f32) -> [f32; 3] {
+        let (sin_yaw, cos_yaw) = yaw.to_radians().sin_cos();
+        let (sin_pitch, cos_pitch) = pitch.to_radians().sin_cos();
+        [-sin_yaw * cos_pitch, sin_pitch, -cos_yaw * cos_pitch]
+    }
+
+    fn assert_close(actual: [f32; 3], expected: [f32; 3]) {
+        for (a, e) in actual.iter().zip(expected) {
+            assert!((a - e).abs() < 1e-4, "{:?} != {:?}", actual, expected);
+        }
+    }
+
+    fn projection() -> Mat4 {
+        Mat4::perspective(90_f32.to_radians(), 1.0, 0.1, 1000.0)
+    }
+
+    #[test]
+    fn test_center_of_screen_looks_where_the_camera_does() {
+        for (yaw, pitch) in [(0.0, 0.0), (90.0, 0.0), (200.0, 30.0), (-45.0, -60.0)] {
+            let inverse = sky_ray_matrix(&camera([0.0; 3], yaw, pitch), &projection()).unwrap();
+            assert_close(view_ray([0.0, 0.0], &inverse), forward(yaw, pitch));
+        }
+    }
+
+    #[test]
+    fn test_translation_is_ignored() {
+        let still = sky_ray_matrix(&camera([0.0; 3], 30.0, 10.0), &projection()).unwrap();
+        let moved =
+            sky_ray_matrix(&camera([500.0, 50.0, -900.0], 30.0, 10.0), &projection()).unwrap();
+        for ndc in [[0.0, 0.0], [-1.0, 1.0], [0.7, -0.3]] {
+            assert_close(view_ray(ndc, &moved), view_ray(ndc, &still));
+        }
+    }
+
+    #[test]
+    fn test_screen_edges_follow_the_axes() {
+        // With a 90 degree field of view the edges are 45 degrees off center
+        let inverse = sky_ray_matrix(&camera([0.0; 3], 0.0, 0.0), &projection()).unwrap();
+        let diagonal = std::f32::consts::FRAC_1_SQRT_2;
+        assert_close(view_ray([1.0, 0.0], &inverse), [diagonal, 0.0, -diagonal]);
+        assert_close(view_ray([-1.0, 0.0], &inverse), [-diagonal, 0.0, -diagonal]);
+        assert_close(view_ray([0.0, 1.0], &inverse), [0.0, diagonal, -diagonal]);
+    }
+
+    #[test]
+    fn test_equirect_uv_wraps_around_behind_the_camera() {
+        let uv = equirect_uv;
+        assert_eq!(uv([0.0, 0.0, -1.0]), [0.5, 0.5]);
+        assert_eq!(uv([1.0, 0.0, 0.0]), [0.75, 0.5]);
+        assert_eq!(uv([-1.0, 0.0, 0.0]), [0.25, 0.5]);
+        assert_eq!(uv([0.0, 1.0, 0.0])[1], 0.0);
+        assert_eq!(uv([0.0, -1.0, 0.0])[1], 1.0);
+
+        // Either side of straight behind lands on opposite edges
+        assert!(uv([0.01, 0.0, 1.0])[0] > 0.99);
+        assert!(uv([-0.01, 0.0, 1.0])[0] < 0.01);
+    }
+}
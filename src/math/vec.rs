@@ -49,7 +49,7 @@ Requirements for Memory Compatibility with WGPU:
 /// # Examples
 ///
 /// Basic usage:
-/// ```
+/// ```rust,ignore
 /// # use your_crate::math::Vec3;
 /// let v = Vec3::new(1.0, 2.0, 3.0);
 /// let w = Vec3::new(4.0, 5.0, 6.0);
@@ -64,7 +64,7 @@ impl Vec3 {
     /// Creates a new `Vec3` from components.
     ///
     /// # Example
-    /// ```
+    /// ```rust,ignore
     /// # use your_crate::math::Vec3;
     /// let v = Vec3::new(1.0, 2.0, 3.0);
     /// ```
@@ -78,7 +78,7 @@ impl Vec3 {
     /// `self.x() * other.x() + self.y() * other.y() + self.z() * other.z()`
     ///
     /// # Example
-    /// ```
+    /// ```rust,ignore
     /// # use your_crate::math::Vec3;
     /// let v = Vec3::new(1.0, 2.0, 3.0);
     /// let w = Vec3::new(4.0, 5.0, 6.0);
@@ -96,7 +96,7 @@ impl Vec3 {
     /// - Direction follows right-hand rule
     ///
     /// # Example
-    /// ```
+    /// ```rust,ignore
     /// # use your_crate::math::Vec3;
     /// let x = Vec3::new(1.0, 0.0, 0.0);
     /// let y = Vec3::new(0.0, 1.0, 0.0);
@@ -128,7 +128,7 @@ impl Vec3 {
     /// The distance between the two vectors
     ///
     /// # Example
-    /// ```
+    /// ```rust,ignore
     /// # use your_crate::math::Vec3;
     /// let v1 = Vec3::new(0.0, 0.0, 0.0);
     /// let v2 = Vec3::new(3.0, 4.0, 0.0);
@@ -155,7 +155,7 @@ impl Vec3 {
     /// - Otherwise returns vector with same direction but length 1
     ///
     /// # Example
-    /// ```
+    /// ```rust,ignore
     /// # use your_crate::math::Vec3;
     /// let v = Vec3::new(1.0, 2.0, 3.0).normalize();
     /// assert!((v.length() - 1.0).abs() < 1e-6);
@@ -196,7 +196,7 @@ impl Vec3 {
 /// Adds two vectors component-wise.
 ///
 /// # Example
-/// ```
+/// ```rust,ignore
 /// # use your_crate::math::Vec3;
 /// let v = Vec3::new(1.0, 2.0, 3.0);
 /// let w = Vec3::new(4.0, 5.0, 6.0);
@@ -230,7 +230,7 @@ impl Sub for Vec3 {
 /// Multiplies vector by scalar (component-wise).
 ///
 /// # Example
-/// ```
+/// ```rust,ignore
 /// # use your_crate::math::Vec3;
 /// let v = Vec3::new(1.0, 2.0, 3.0);
 /// let scaled = v * 2.0;  // Vec3::new(2.0, 4.0, 6.0)
@@ -251,18 +251,6 @@ impl Mul<f32> for Vec3 {
 pub struct Vec2(pub [f32; 2]);
 
 impl Vec2 {
-    /// Creates a new 2D vector with the given x and z components.
-    ///
-    /// # Arguments
-    /// * `x` - The x component
-    /// * `z` - The z component
-    ///
-    /// # Returns
-    /// A new Vec2 instance
-    pub fn new(x: f32, z: f32) -> Self {
-        Vec2([x, z])
-    }
-
     /// Returns the x component of the vector.
     pub fn x(&self) -> f32 {
         self.0[0]
@@ -316,9 +304,9 @@ impl Vec2 {
     /// A new vector rotated by the specified angle
     ///
     /// # Example
-    /// ```
+    /// ```rust,ignore
     /// # use your_crate::math::Vec2;
-    /// let v = Vec2::new(1.0, 0.0);
+    /// let v = Vec2([1.0, 0.0]);
     /// let rotated = v.rotate(std::f32::consts::PI / 2.0); // Rotate 90 degrees
     /// ```
     pub fn rotate(&self, angle: f32) -> Self {
@@ -0,0 +1,523 @@
+//! Where Mirador keeps the files it writes.
+//!
+//! Every persisted file (settings, daily challenge results, the hardcore graveyard, the maze
+//! codex, session journals, benchmark results, wall-clip reports, copies of generated mazes,
+//! navigation mesh exports, soak test reports, and screenshots), along with files the player supplies such as a
+//! custom marker or sky panorama, lives under one data directory described by [`Paths`]. By default that is the working
+//! directory, as it always has been. Two overrides make the game portable, so its data travels
+//! with it, for example on a USB stick:
+//!
+//! - `--data-dir <path>` on the command line. Relative paths resolve against the executable's
+//!   directory, not the working directory.
+//! - A [`PORTABLE_SENTINEL`] file next to the executable, which roots everything in the
+//!   executable's directory. `--data-dir` wins when both are present.
+//!
+//! Each player's own files (settings, daily challenge results, the hardcore graveyard, the saved
+//! run, key bindings, and the maze codex with its unlocks and thumbnails) live in a profile
+//! directory under [`PROFILES_DIR`] once a profile is active. Journals, benchmarks, debug dumps,
+//! screenshots and custom images stay shared at the top of the data directory. Without a profile, as before profiles
+//! existed, everything sits at the top.
+//!
+//! The paths are resolved once at startup and installed with [`init`]; [`set_profile`] switches
+//! the active profile later. Modules that read or write files get their locations from [`get`]
+//! and never build them on their own; a unit test below enforces that.
+//!
+//! # Usage
+//!
+//! ```rust,ignore
+//! let paths = Paths::from_args(&args)?;
+//! paths.prepare()?;
+//! paths::init(paths);
+//! paths::set_profile(Some("Robin"));
+//!
+//! let settings = Settings::load_from(&paths::get().settings());
+//! ```
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{OnceLock, RwLock};
+
+/// Command-line flag that overrides the data directory.
+pub const DATA_DIR_FLAG: &str = "--data-dir";
+
+/// File next to the executable that turns on portable mode.
+pub const PORTABLE_SENTINEL: &str = "portable.txt";
+
+/// Settings file, relative to the data directory.
+const SETTINGS_FILE: &str = "settings.json";
+
+/// Daily challenge results file, relative to the data directory.
+const DAILY_RESULTS_FILE: &str = "daily_results.json";
+
+/// Seeds burned by hardcore deaths, relative to the data directory.
+const HARDCORE_GRAVEYARD_FILE: &str = "hardcore_graveyard.json";
+
+/// Run saved at the start of its current level, relative to the data directory.
+const SAVED_GAME_FILE: &str = "saved_game.json";
+
+/// Keys bound to the rebindable actions, relative to the data directory.
+const KEY_BINDINGS_FILE: &str = "key_bindings.json";
+
+/// Session journal directory, relative to the data directory.
+const JOURNAL_DIR: &str = "debug-analytics/journals";
+
+/// Benchmark results directory, relative to the data directory.
+const BENCHMARKS_DIR: &str = "debug-analytics/benchmarks";
+
+/// Wall-clip reports directory, relative to the data directory.
+const CLIP_REPORTS_DIR: &str = "debug-analytics/clip-reports";
+
+/// Directory generated mazes are saved to, relative to the data directory.
+const SAVED_MAZES_DIR: &str = "debug-analytics/mazes";
+
+/// Directory navigation mesh exports are written to, relative to the data directory.
+const NAVMESH_DIR: &str = "debug-analytics/navmeshes";
+
+/// Directory soak test reports are written to, relative to the data directory.
+const SOAK_REPORTS_DIR: &str = "debug-analytics/soak-reports";
+
+/// Directory screenshots are saved to, relative to the data directory.
+const SCREENSHOTS_DIR: &str = "screenshots";
+
+/// Custom player marker image, relative to the data directory.
+const CUSTOM_MARKER_FILE: &str = "custom/marker.png";
+
+/// Custom sky panorama image, relative to the data directory.
+const CUSTOM_PANORAMA_FILE: &str = "custom/panorama.jpg";
+
+/// Maze codex stats, relative to the data directory.
+const CODEX_FILE: &str = "codex.json";
+
+/// Directory maze codex thumbnails are saved to, relative to the data directory.
+const CODEX_THUMBNAILS_DIR: &str = "codex";
+
+/// Directory holding one subdirectory per profile, relative to the data directory.
+pub const PROFILES_DIR: &str = "profiles";
+
+/// File naming the profile last played, relative to the data directory.
+const ACTIVE_PROFILE_FILE: &str = "profiles/active_profile.txt";
+
+/// The paths installed by [`init`], switched between profiles by [`set_profile`].
+static PATHS: OnceLock<RwLock<Paths>> = OnceLock::new();
+
+/// Locations of every file the game persists.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Paths {
+    /// Directory everything is stored under; empty for the working directory
+    root: PathBuf,
+    /// Profile whose directory holds the per-player files; `None` keeps them at the root
+    profile: Option<String>,
+}
+
+impl Paths {
+    /// Roots every file under a specific directory.
+    ///
+    /// # Arguments
+    /// * `root` - The data directory
+    pub fn with_root(root: impl Into<PathBuf>) -> Self {
+        Self {
+            root: root.into(),
+            profile: None,
+        }
+    }
+
+    /// Routes the per-player files into a profile's directory.
+    ///
+    /// The name must already be checked with
+    /// [`profiles::validate_name`](crate::app::profiles::validate_name); it becomes a
+    /// directory name as is.
+    ///
+    /// # Arguments
+    /// * `profile` - The profile's name, or `None` to keep the files at the root
+    pub fn with_profile(mut self, profile: Option<&str>) -> Self {
+        self.profile = profile.map(str::to_string);
+        self
+    }
+
+    /// Works out the data directory from the overrides.
+    ///
+    /// # Arguments
+    /// * `data_dir` - Value of the `--data-dir` flag, if given
+    /// * `exe_dir` - Directory the executable is in
+    /// * `portable` - Whether [`PORTABLE_SENTINEL`] exists next to the executable
+    ///
+    /// # Returns
+    /// Paths rooted at `data_dir` (resolved against `exe_dir` when relative), else at `exe_dir`
+    /// in portable mode, else at the working directory
+    pub fn resolve(data_dir: Option<&Path>, exe_dir: &Path, portable: bool) -> Self {
+        match data_dir {
+            Some(dir) => Self::with_root(exe_dir.join(dir)),
+            None if portable => Self::with_root(exe_dir),
+            None => Self::default(),
+        }
+    }
+
+    /// Works out the data directory from the command line and the executable's location.
+    ///
+    /// # Arguments
+    /// * `args` - The process arguments, including the program name
+    ///
+    /// # Returns
+    /// The resolved paths, or an error message if `--data-dir` is missing its value
+    pub fn from_args(args: &[String]) -> Result<Self, String> {
+        let data_dir = data_dir_arg(args)?;
+        let exe_dir = std::env::current_exe()
+            .ok()
+            .and_then(|exe| exe.parent().map(Path::to_path_buf))
+            .unwrap_or_default();
+        let portable = exe_dir.join(PORTABLE_SENTINEL).is_file();
+        Ok(Self::resolve(data_dir.as_deref(), &exe_dir, portable))
+    }
+
+    /// Creates the data directory if needed and checks that it can be written to.
+    ///
+    /// # Returns
+    /// `Ok(())` when files can be saved there, or an error message naming the directory
+    pub fn prepare(&self) -> Result<(), String> {
+        if self.root.as_os_str().is_empty() {
+            return Ok(());
+        }
+        fs::create_dir_all(&self.root).map_err(|e| {
+            format!(
+                "Failed to create data directory {}: {}",
+                self.root.display(),
+                e
+            )
+        })?;
+        let probe = self.root.join(".mirador-write-test");
+        fs::write(&probe, b"").map_err(|e| {
+            format!(
+                "Data directory {} is not writable: {}",
+                self.root.display(),
+                e
+            )
+        })?;
+        let _ = fs::remove_file(probe);
+        Ok(())
+    }
+
+    /// Returns the data directory; empty for the working directory.
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Returns the active profile's name, if one is active.
+    pub fn profile(&self) -> Option<&str> {
+        self.profile.as_deref()
+    }
+
+    /// Returns the directory holding every profile's directory.
+    pub fn profiles_dir(&self) -> PathBuf {
+        self.root.join(PROFILES_DIR)
+    }
+
+    /// Returns the file naming the profile last played.
+    pub fn active_profile_file(&self) -> PathBuf {
+        self.root.join(ACTIVE_PROFILE_FILE)
+    }
+
+    /// Returns the directory the per-player files are in: the active profile's, or the root.
+    pub fn profile_dir(&self) -> PathBuf {
+        match &self.profile {
+            Some(profile) => self.profiles_dir().join(profile),
+            None => self.root.clone(),
+        }
+    }
+
+    /// Returns every per-player file and directory, in the order they are listed above.
+    ///
+    /// These are what a profile owns, so they are what moves when old data is migrated into
+    /// a profile.
+    pub fn profile_files(&self) -> Vec<PathBuf> {
+        vec![
+            self.settings(),
+            self.daily_results(),
+            self.hardcore_graveyard(),
+            self.saved_game(),
+            self.key_bindings(),
+            self.codex(),
+            self.codex_thumbnails_dir(),
+        ]
+    }
+
+    /// Returns the settings file.
+    pub fn settings(&self) -> PathBuf {
+        self.profile_dir().join(SETTINGS_FILE)
+    }
+
+    /// Returns the daily challenge results file.
+    pub fn daily_results(&self) -> PathBuf {
+        self.profile_dir().join(DAILY_RESULTS_FILE)
+    }
+
+    /// Returns the hardcore graveyard file.
+    pub fn hardcore_graveyard(&self) -> PathBuf {
+        self.profile_dir().join(HARDCORE_GRAVEYARD_FILE)
+    }
+
+    /// Returns the file the run in progress is saved to.
+    pub fn saved_game(&self) -> PathBuf {
+        self.profile_dir().join(SAVED_GAME_FILE)
+    }
+
+    /// Returns the file the player's key bindings are saved to.
+    pub fn key_bindings(&self) -> PathBuf {
+        self.profile_dir().join(KEY_BINDINGS_FILE)
+    }
+
+    /// Returns the directory session journals are written to.
+    pub fn journal_dir(&self) -> PathBuf {
+        self.root.join(JOURNAL_DIR)
+    }
+
+    /// Returns the directory benchmark results are written to.
+    pub fn benchmarks_dir(&self) -> PathBuf {
+        self.root.join(BENCHMARKS_DIR)
+    }
+
+    /// Returns the directory wall-clip reports are written to.
+    pub fn clip_reports_dir(&self) -> PathBuf {
+        self.root.join(CLIP_REPORTS_DIR)
+    }
+
+    /// Returns the directory each generated maze is saved to.
+    pub fn saved_mazes_dir(&self) -> PathBuf {
+        self.root.join(SAVED_MAZES_DIR)
+    }
+
+    /// Returns the directory navigation mesh exports are written to.
+    pub fn navmesh_dir(&self) -> PathBuf {
+        self.root.join(NAVMESH_DIR)
+    }
+
+    /// Returns the directory soak test reports are written to.
+    pub fn soak_reports_dir(&self) -> PathBuf {
+        self.root.join(SOAK_REPORTS_DIR)
+    }
+
+    /// Returns the directory screenshots are saved to.
+    pub fn screenshots_dir(&self) -> PathBuf {
+        self.root.join(SCREENSHOTS_DIR)
+    }
+
+    /// Returns the custom player marker image the player may supply.
+    pub fn custom_marker(&self) -> PathBuf {
+        self.root.join(CUSTOM_MARKER_FILE)
+    }
+
+    /// Returns the equirectangular sky panorama the player may supply.
+    pub fn custom_panorama(&self) -> PathBuf {
+        self.root.join(CUSTOM_PANORAMA_FILE)
+    }
+
+    /// Returns the maze codex stats file, which also holds the unlocks.
+    pub fn codex(&self) -> PathBuf {
+        self.profile_dir().join(CODEX_FILE)
+    }
+
+    /// Returns the directory maze codex thumbnails are saved to.
+    pub fn codex_thumbnails_dir(&self) -> PathBuf {
+        self.profile_dir().join(CODEX_THUMBNAILS_DIR)
+    }
+
+    /// Returns the thumbnail of the best run on one kind of maze.
+    ///
+    /// # Arguments
+    /// * `slug` - The maze kind's file name, without an extension
+    pub fn codex_thumbnail(&self, slug: &str) -> PathBuf {
+        self.codex_thumbnails_dir().join(format!("{}.png", slug))
+    }
+}
+
+/// Reads the value of `--data-dir` from the process arguments.
+///
+/// # Arguments
+/// * `args` - The process arguments, including the program name
+///
+/// # Returns
+/// The directory if the flag was given, or an error message if it has no value
+fn data_dir_arg(args: &[String]) -> Result<Option<PathBuf>, String> {
+    let Some(index) = args.iter().position(|arg| arg == DATA_DIR_FLAG) else {
+        return Ok(None);
+    };
+    match args.get(index + 1) {
+        Some(dir) if !dir.starts_with("--") => Ok(Some(PathBuf::from(dir))),
+        _ => Err(format!("Usage: mirador {} <path>", DATA_DIR_FLAG)),
+    }
+}
+
+/// Installs the paths every module reads from for the rest of the process.
+///
+/// Should be called once at startup, before anything is loaded or saved. Later calls are ignored.
+///
+/// # Arguments
+/// * `paths` - The resolved paths
+pub fn init(paths: Paths) {
+    if PATHS.set(RwLock::new(paths)).is_err() {
+        eprintln!("Data paths were already set, ignoring the new ones");
+    }
+}
+
+/// Returns the installed paths, or the working directory if [`init`] was never called.
+pub fn get() -> Paths {
+    installed()
+        .read()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .clone()
+}
+
+/// Switches which profile's directory the per-player files come from.
+///
+/// Only changes where files are found; whatever was loaded from the old profile has to be
+/// loaded again by the caller.
+///
+/// # Arguments
+/// * `profile` - The profile's name, already validated, or `None` for the root
+pub fn set_profile(profile: Option<&str>) {
+    let mut paths = installed()
+        .write()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    paths.profile = profile.map(str::to_string);
+}
+
+/// Returns the lock around the installed paths, installing the defaults if needed.
+fn installed() -> &'static RwLock<Paths> {
+    PATHS.get_or_init(|| RwLock::new(Paths::default()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(list: &[&str]) -> Vec<String> {
+        list.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn test_overrides_resolve_against_the_executable() {
+        let exe_dir = Path::new("/media/usb/mirador");
+
+        assert_eq!(Paths::resolve(None, exe_dir, false), Paths::default());
+        assert_eq!(Paths::default().settings(), PathBuf::from(SETTINGS_FILE));
+
+        let portable = Paths::resolve(None, exe_dir, true);
+        assert_eq!(portable.root(), exe_dir);
+
+        // The flag wins over the sentinel, and relative paths ignore the working directory
+        let relative = Paths::resolve(Some(Path::new("saves")), exe_dir, true);
+        assert_eq!(relative.root(), exe_dir.join("saves"));
+        assert_eq!(
+            relative.journal_dir(),
+            exe_dir.join("saves").join(JOURNAL_DIR)
+        );
+
+        let absolute = Paths::resolve(Some(Path::new("/data/mirador")), exe_dir, false);
+        assert_eq!(absolute.root(), Path::new("/data/mirador"));
+    }
+
+    #[test]
+    fn test_profile_files_route_into_the_profile_directory() {
+        let root = Path::new("/data/mirador");
+        let shared = Paths::with_root(root);
+        let robin = Paths::with_root(root).with_profile(Some("Robin"));
+        let sam = Paths::with_root(root).with_profile(Some("Sam"));
+
+        let robin_dir = root.join(PROFILES_DIR).join("Robin");
+        assert_eq!(robin.profile(), Some("Robin"));
+        assert_eq!(robin.settings(), robin_dir.join(SETTINGS_FILE));
+        assert_eq!(robin.daily_results(), robin_dir.join(DAILY_RESULTS_FILE));
+        assert_eq!(
+            robin.hardcore_graveyard(),
+            robin_dir.join(HARDCORE_GRAVEYARD_FILE)
+        );
+        assert_eq!(robin.saved_game(), robin_dir.join(SAVED_GAME_FILE));
+        assert_eq!(robin.key_bindings(), robin_dir.join(KEY_BINDINGS_FILE));
+        assert_eq!(robin.codex(), robin_dir.join(CODEX_FILE));
+        assert_eq!(
+            robin.codex_thumbnail("prim"),
+            robin_dir.join(CODEX_THUMBNAILS_DIR).join("prim.png")
+        );
+
+        // No two profiles share a file, and none of them uses the root's
+        for (mine, theirs) in robin.profile_files().iter().zip(sam.profile_files()) {
+            assert_ne!(*mine, theirs);
+        }
+        for (mine, legacy) in robin.profile_files().iter().zip(shared.profile_files()) {
+            assert_ne!(*mine, legacy);
+            assert_eq!(legacy.parent(), Some(root));
+        }
+
+        // Files that belong to the machine rather than the player stay shared
+        assert_eq!(robin.journal_dir(), shared.journal_dir());
+        assert_eq!(robin.benchmarks_dir(), shared.benchmarks_dir());
+        assert_eq!(robin.custom_marker(), shared.custom_marker());
+        assert_eq!(robin.active_profile_file(), shared.active_profile_file());
+    }
+
+    #[test]
+    fn test_data_dir_flag_parsing() {
+        assert_eq!(data_dir_arg(&args(&["mirador"])), Ok(None));
+        assert_eq!(
+            data_dir_arg(&args(&["mirador", "--data-dir", "saves"])),
+            Ok(Some(PathBuf::from("saves")))
+        );
+        assert!(data_dir_arg(&args(&["mirador", "--data-dir"])).is_err());
+        assert!(data_dir_arg(&args(&["mirador", "--data-dir", "--journal"])).is_err());
+    }
+
+    #[test]
+    fn test_prepare_creates_the_data_directory() {
+        let root = std::env::temp_dir().join("mirador_paths_prepare/nested");
+        let _ = fs::remove_dir_all(root.parent().unwrap());
+
+        let paths = Paths::with_root(&root);
+        assert_eq!(paths.prepare(), Ok(()));
+        assert!(root.is_dir());
+        assert_eq!(fs::read_dir(&root).unwrap().count(), 0);
+
+        let _ = fs::remove_dir_all(root.parent().unwrap());
+    }
+
+    /// Every persisted location must come from [`Paths`], so no other module may name a
+    /// platform directory or one of the data files itself.
+    #[test]
+    fn test_paths_is_the_only_source_of_data_locations() {
+        let forbidden = [
+            "dirs::",
+            "config_dir(",
+            "data_dir(",
+            "home_dir(",
+            SETTINGS_FILE,
+            DAILY_RESULTS_FILE,
+            HARDCORE_GRAVEYARD_FILE,
+            SAVED_GAME_FILE,
+            KEY_BINDINGS_FILE,
+            CUSTOM_MARKER_FILE,
+            CUSTOM_PANORAMA_FILE,
+            CODEX_FILE,
+            ACTIVE_PROFILE_FILE,
+            "debug-analytics/",
+        ];
+
+        let mut pending = vec![PathBuf::from(concat!(env!("CARGO_MANIFEST_DIR"), "/src"))];
+        let mut offenders = Vec::new();
+        while let Some(dir) = pending.pop() {
+            for entry in fs::read_dir(&dir).unwrap().flatten() {
+                let path = entry.path();
+                if path.is_dir() {
+                    pending.push(path);
+                } else if path.extension().is_some_and(|ext| ext == "rs")
+                    && path.file_name().is_some_and(|name| name != "paths.rs")
+                {
+                    let source = fs::read_to_string(&path).unwrap();
+                    for needle in forbidden {
+                        if source.contains(needle) {
+                            offenders.push(format!("{} uses {}", path.display(), needle));
+                        }
+                    }
+                }
+            }
+        }
+        assert!(offenders.is_empty(), "{:#?}", offenders);
+    }
+}
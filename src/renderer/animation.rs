@@ -0,0 +1,259 @@
+//! Per-frame animation state for the renderer.
+//!
+//! Everything on screen that moves on its own, such as twinkling stars, the HUD bars, the
+//! pulsing game over prompt, and the heartbeat distortion of the timer's last seconds, is
+//! advanced once per frame by
+//! [`WgpuRenderer::update_animations`](crate::renderer::wgpu_lib::WgpuRenderer::update_animations)
+//! before any rendering commands are recorded. The render path only reads the values stored
+//! here and uploads them, so rendering the same frame twice draws the same thing.
+//!
+//! # Usage
+//!
+//! ```rust,ignore
+//! let mut animations = FrameAnimations::default();
+//!
+//! // Once per frame, before update_canvas
+//! animations.advance(delta_time, tuning.star_twinkle_speed);
+//! animations.set_timer(game_state.game_ui.timer.as_ref());
+//! animations.stamina_progress = game_state.player.stamina_ratio();
+//!
+//! // In the render path
+//! timer_bar.update_uniforms(&queue, &animations.timer_bar_uniforms(&hud, timer_bar.linear_output));
+//! ```
+
+use crate::game::GameTimer;
+use crate::renderer::game_renderer::stamina_bar::StaminaBarUniforms;
+use crate::renderer::game_renderer::timer_bar::TimerBarUniforms;
+use crate::renderer::safe_area::SafeArea;
+
+/// Darkest gray the game over restart prompt pulses down to.
+const RESTART_TEXT_BASE_GRAY: f32 = 100.0;
+
+/// How far above [`RESTART_TEXT_BASE_GRAY`] the restart prompt pulses, reaching white.
+const RESTART_TEXT_GRAY_RANGE: f32 = 155.0;
+
+/// Seconds left on the level timer at which the heartbeat distortion starts.
+pub const HEARTBEAT_THRESHOLD_SECS: f32 = 5.0;
+
+/// Beats per minute of the heartbeat distortion.
+pub const HEARTBEAT_BPM: f32 = 100.0;
+
+/// Animation values for the current frame.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FrameAnimations {
+    /// Seconds of animation time, accumulated from frame deltas
+    pub clock: f32,
+    /// Starfield animation time, accumulated from frame deltas scaled by the twinkle speed
+    ///
+    /// Accumulating keeps the stars from jumping when the twinkle speed is tuned live.
+    pub star_time: f32,
+    /// Fraction of the level timer remaining, from 0.0 to 1.0
+    pub timer_progress: f32,
+    /// Seconds the level timer has been running
+    pub timer_time: f32,
+    /// Player stamina, from 0.0 (empty) to 1.0 (full)
+    pub stamina_progress: f32,
+    /// Gray level of the pulsing game over restart prompt
+    pub restart_text_gray: u8,
+    /// Strength of the heartbeat distortion, from 0.0 (off) to 1.0 as the timer runs out
+    pub heartbeat: f32,
+    /// How far through the current heartbeat, from 0.0 to 1.0
+    ///
+    /// Read from the timer's clock, so the pulse freezes while the game is paused.
+    pub heartbeat_phase: f32,
+}
+
+impl Default for FrameAnimations {
+    /// All clocks at zero with full timer and stamina bars.
+    fn default() -> Self {
+        Self {
+            clock: 0.0,
+            star_time: 0.0,
+            timer_progress: 1.0,
+            timer_time: 0.0,
+            stamina_progress: 1.0,
+            restart_text_gray: restart_text_gray(0.0),
+            heartbeat: 0.0,
+            heartbeat_phase: 0.0,
+        }
+    }
+}
+
+impl FrameAnimations {
+    /// Advances the animation clocks by one frame.
+    ///
+    /// # Arguments
+    /// * `delta_time` - Seconds since the last frame; negative values are ignored
+    /// * `star_twinkle_speed` - How fast the starfield animates relative to real time
+    pub fn advance(&mut self, delta_time: f32, star_twinkle_speed: f32) {
+        let delta_time = delta_time.max(0.0);
+        self.clock += delta_time;
+        self.star_time += delta_time * star_twinkle_speed;
+        self.restart_text_gray = restart_text_gray(self.clock);
+    }
+
+    /// Reads the timer bar and heartbeat values from the level timer.
+    ///
+    /// The heartbeat only beats while the timer is counting down; a stopped or expired timer
+    /// turns it off.
+    ///
+    /// # Arguments
+    /// * `timer` - The level timer, or `None` before one has started
+    pub fn set_timer(&mut self, timer: Option<&GameTimer>) {
+        (self.timer_progress, self.timer_time) = match timer {
+            Some(timer) => {
+                let remaining = timer.get_remaining_time().as_secs_f32();
+                let total = timer.config.duration.as_secs_f32();
+                let progress = if total > 0.0 {
+                    (remaining / total).clamp(0.0, 1.0)
+                } else {
+                    0.0
+                };
+                (progress, timer.elapsed().as_secs_f32())
+            }
+            None => (1.0, 0.0),
+        };
+        (self.heartbeat, self.heartbeat_phase) = match timer {
+            Some(timer) if timer.is_running && !timer.is_expired => {
+                heartbeat(timer.get_remaining_time().as_secs_f32(), self.timer_time)
+            }
+            _ => (0.0, 0.0),
+        };
+    }
+
+    /// Builds this frame's timer bar uniforms.
+    ///
+    /// # Arguments
+    /// * `hud` - The HUD safe area the bar spans
+    /// * `linear_output` - 1.0 when rendering to an sRGB target
+    pub fn timer_bar_uniforms(&self, hud: &SafeArea, linear_output: f32) -> TimerBarUniforms {
+        TimerBarUniforms {
+            progress: self.timer_progress.clamp(0.0, 1.0),
+            time: self.timer_time,
+            resolution: [hud.width, hud.height],
+            linear_output,
+            origin_x: hud.x,
+        }
+    }
+
+    /// Builds this frame's stamina bar uniforms.
+    ///
+    /// # Arguments
+    /// * `hud` - The HUD safe area the bar spans
+    pub fn stamina_bar_uniforms(&self, hud: &SafeArea) -> StaminaBarUniforms {
+        StaminaBarUniforms {
+            progress: self.stamina_progress.clamp(0.0, 1.0),
+            time: self.clock,
+            resolution: [hud.width, hud.height],
+            origin: [hud.x, hud.y],
+        }
+    }
+}
+
+/// Works out the gray level of the game over restart prompt.
+///
+/// # Arguments
+/// * `clock` - Animation time in seconds
+///
+/// # Returns
+/// A gray level that pulses smoothly between dark gray and white about every three seconds
+pub fn restart_text_gray(clock: f32) -> u8 {
+    let color_shift = (clock * 2.0).sin() * 0.5 + 0.5;
+    (RESTART_TEXT_BASE_GRAY + RESTART_TEXT_GRAY_RANGE * color_shift) as u8
+}
+
+/// Works out the heartbeat distortion for the time left on the level timer.
+///
+/// # Arguments
+/// * `remaining_secs` - Seconds left on the timer
+/// * `elapsed_secs` - Seconds the timer has counted, which sets the beat's phase
+///
+/// # Returns
+/// The strength, ramping from 0.0 at [`HEARTBEAT_THRESHOLD_SECS`] to 1.0 at zero, and how far
+/// through the current beat the pulse is, at [`HEARTBEAT_BPM`]
+pub fn heartbeat(remaining_secs: f32, elapsed_secs: f32) -> (f32, f32) {
+    let strength = (1.0 - remaining_secs / HEARTBEAT_THRESHOLD_SECS).clamp(0.0, 1.0);
+    if strength <= 0.0 {
+        return (0.0, 0.0);
+    }
+    (strength, (elapsed_secs * HEARTBEAT_BPM / 60.0).fract())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::{PauseSource, TimerConfig};
+
+    /// The bytes the render path would upload for one frame.
+    fn uploaded_bytes(animations: &FrameAnimations, hud: &SafeArea) -> Vec<u8> {
+        let timer_bar = animations.timer_bar_uniforms(hud, 1.0);
+        let stamina_bar = animations.stamina_bar_uniforms(hud);
+        let mut bytes = bytemuck::bytes_of(&timer_bar).to_vec();
+        bytes.extend_from_slice(bytemuck::bytes_of(&stamina_bar));
+        bytes.extend_from_slice(bytemuck::bytes_of(&animations.star_time));
+        bytes
+    }
+
+    #[test]
+    fn test_renders_without_an_update_upload_identical_uniforms() {
+        let hud = SafeArea::full(1920.0, 1080.0);
+        let mut timer = GameTimer::new(TimerConfig::default());
+        timer.start();
+        let mut animations = FrameAnimations::default();
+        animations.advance(1.0 / 60.0, 1.0);
+        animations.set_timer(Some(&timer));
+        animations.stamina_progress = 0.4;
+
+        let first = uploaded_bytes(&animations, &hud);
+        let second = uploaded_bytes(&animations, &hud);
+        assert_eq!(first, second);
+
+        // Only an update moves anything
+        animations.advance(1.0 / 60.0, 1.0);
+        assert_ne!(uploaded_bytes(&animations, &hud), first);
+    }
+
+    #[test]
+    fn test_star_time_accumulates_at_twinkle_speed() {
+        let mut animations = FrameAnimations::default();
+        for _ in 0..60 {
+            animations.advance(1.0 / 60.0, 0.5);
+        }
+        assert!((animations.clock - 1.0).abs() < 1e-4);
+        assert!((animations.star_time - 0.5).abs() < 1e-4);
+
+        // A stalled clock never runs the stars backwards
+        animations.advance(-1.0, 0.5);
+        assert!((animations.star_time - 0.5).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_heartbeat_ramps_up_and_freezes_while_paused() {
+        assert_eq!(heartbeat(HEARTBEAT_THRESHOLD_SECS + 1.0, 3.0), (0.0, 0.0));
+        assert_eq!(heartbeat(HEARTBEAT_THRESHOLD_SECS, 3.0).0, 0.0);
+        assert!((heartbeat(HEARTBEAT_THRESHOLD_SECS / 2.0, 3.0).0 - 0.5).abs() < 1e-6);
+        assert_eq!(heartbeat(0.0, 3.0).0, 1.0);
+
+        let config = TimerConfig {
+            duration: std::time::Duration::from_secs(6),
+            ..TimerConfig::default()
+        };
+        let mut timer = GameTimer::new(config);
+        timer.start();
+        timer.update(std::time::Duration::from_millis(2300));
+        let mut animations = FrameAnimations::default();
+        animations.set_timer(Some(&timer));
+        assert!(animations.heartbeat > 0.0);
+        let phase = animations.heartbeat_phase;
+
+        // Paused time doesn't reach the timer's clock, so the pulse holds still
+        timer.pause(PauseSource::Menu);
+        timer.update(std::time::Duration::from_millis(300));
+        animations.set_timer(Some(&timer));
+        assert_eq!(animations.heartbeat_phase, phase);
+
+        timer.stop();
+        animations.set_timer(Some(&timer));
+        assert_eq!(animations.heartbeat, 0.0);
+    }
+}
@@ -0,0 +1,195 @@
+//! Choice of graphics API.
+//!
+//! By default wgpu picks the backend itself. Some drivers render parts of the game wrong on one
+//! API and fine on another, so the player can ask for a specific one with `--backend <name>` on
+//! the command line or the `graphics_backend` setting. The flag is saved to the setting, so it
+//! sticks for later launches. If the requested backend can't drive the window, the game warns
+//! and falls back to [`GraphicsBackend::Auto`] instead of exiting.
+//!
+//! # Usage
+//!
+//! ```rust,ignore
+//! let instance = wgpu::Instance::new(&GraphicsBackend::Gl.instance_descriptor());
+//! // Once the adapter is chosen
+//! println!("{}", adapter_summary(&adapter.get_info()));
+//! ```
+
+use serde::{Deserialize, Serialize};
+
+/// Command-line flag that picks the graphics backend.
+pub const BACKEND_FLAG: &str = "--backend";
+
+/// Graphics API the renderer is asked to use.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum GraphicsBackend {
+    /// Let wgpu pick from every backend it supports on this platform
+    #[default]
+    Auto,
+    /// Vulkan
+    Vulkan,
+    /// OpenGL, or OpenGL ES
+    Gl,
+    /// Direct3D 12, on Windows
+    Dx12,
+    /// Metal, on macOS
+    Metal,
+}
+
+impl GraphicsBackend {
+    /// Every backend, in the order they are listed in usage messages.
+    pub const ALL: [GraphicsBackend; 5] = [
+        GraphicsBackend::Auto,
+        GraphicsBackend::Vulkan,
+        GraphicsBackend::Gl,
+        GraphicsBackend::Dx12,
+        GraphicsBackend::Metal,
+    ];
+
+    /// Returns the name used on the command line and in the settings file.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Auto => "auto",
+            Self::Vulkan => "vulkan",
+            Self::Gl => "gl",
+            Self::Dx12 => "dx12",
+            Self::Metal => "metal",
+        }
+    }
+
+    /// Reads a backend name, ignoring case.
+    ///
+    /// # Arguments
+    /// * `name` - One of the names returned by [`GraphicsBackend::name`]
+    ///
+    /// # Returns
+    /// The backend, or `None` if the name isn't known
+    pub fn parse(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|backend| backend.name().eq_ignore_ascii_case(name))
+    }
+
+    /// Returns the wgpu backends the instance may use.
+    pub fn backends(self) -> wgpu::Backends {
+        match self {
+            Self::Auto => wgpu::Backends::all(),
+            Self::Vulkan => wgpu::Backends::VULKAN,
+            Self::Gl => wgpu::Backends::GL,
+            Self::Dx12 => wgpu::Backends::DX12,
+            Self::Metal => wgpu::Backends::METAL,
+        }
+    }
+
+    /// Returns the descriptor for a wgpu instance limited to this backend.
+    pub fn instance_descriptor(self) -> wgpu::InstanceDescriptor {
+        wgpu::InstanceDescriptor {
+            backends: self.backends(),
+            ..Default::default()
+        }
+    }
+}
+
+/// Reads the value of `--backend` from the process arguments.
+///
+/// # Arguments
+/// * `args` - The process arguments, including the program name
+///
+/// # Returns
+/// The backend if the flag was given, or an error message if its value is missing or unknown
+pub fn backend_arg(args: &[String]) -> Result<Option<GraphicsBackend>, String> {
+    let Some(index) = args.iter().position(|arg| arg == BACKEND_FLAG) else {
+        return Ok(None);
+    };
+    match args
+        .get(index + 1)
+        .and_then(|name| GraphicsBackend::parse(name))
+    {
+        Some(backend) => Ok(Some(backend)),
+        None => {
+            let names: Vec<&str> = GraphicsBackend::ALL.iter().map(|b| b.name()).collect();
+            Err(format!(
+                "Usage: mirador {} <{}>",
+                BACKEND_FLAG,
+                names.join("|")
+            ))
+        }
+    }
+}
+
+/// Describes the adapter the renderer runs on, for the startup log and the debug panel.
+///
+/// # Arguments
+/// * `info` - The adapter's information
+///
+/// # Returns
+/// The backend, adapter name, and driver version on one line
+pub fn adapter_summary(info: &wgpu::AdapterInfo) -> String {
+    let driver = match (info.driver.is_empty(), info.driver_info.is_empty()) {
+        (true, true) => "unknown".to_string(),
+        (false, true) => info.driver.clone(),
+        (true, false) => info.driver_info.clone(),
+        (false, false) => format!("{} {}", info.driver, info.driver_info),
+    };
+    format!(
+        "Backend: {}, adapter: {}, driver: {}",
+        info.backend, info.name, driver
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(list: &[&str]) -> Vec<String> {
+        list.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn test_backend_flag_parsing() {
+        assert_eq!(backend_arg(&args(&["mirador"])), Ok(None));
+        assert_eq!(
+            backend_arg(&args(&["mirador", "--backend", "gl"])),
+            Ok(Some(GraphicsBackend::Gl))
+        );
+        assert_eq!(
+            backend_arg(&args(&[
+                "mirador",
+                "--data-dir",
+                "saves",
+                "--backend",
+                "Vulkan"
+            ])),
+            Ok(Some(GraphicsBackend::Vulkan))
+        );
+        assert!(backend_arg(&args(&["mirador", "--backend"])).is_err());
+        assert!(backend_arg(&args(&["mirador", "--backend", "opengl"])).is_err());
+
+        for backend in GraphicsBackend::ALL {
+            assert_eq!(GraphicsBackend::parse(backend.name()), Some(backend));
+        }
+        assert_eq!(GraphicsBackend::Gl.backends(), wgpu::Backends::GL);
+        assert_eq!(GraphicsBackend::Auto.backends(), wgpu::Backends::all());
+    }
+
+    #[test]
+    fn test_adapter_summary_names_the_driver() {
+        let mut info = wgpu::AdapterInfo {
+            name: "Test GPU".to_string(),
+            vendor: 0,
+            device: 0,
+            device_type: wgpu::DeviceType::DiscreteGpu,
+            driver: "radv".to_string(),
+            driver_info: "Mesa 24.1".to_string(),
+            backend: wgpu::Backend::Vulkan,
+        };
+        assert_eq!(
+            adapter_summary(&info),
+            "Backend: vulkan, adapter: Test GPU, driver: radv Mesa 24.1"
+        );
+
+        info.driver.clear();
+        info.driver_info.clear();
+        assert!(adapter_summary(&info).ends_with("driver: unknown"));
+    }
+}
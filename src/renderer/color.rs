@@ -0,0 +1,161 @@
+//! Color space conversions for the Mirador renderer.
+//!
+//! # Color Conventions
+//!
+//! - **CPU-side color constants are sRGB.** Design colors are written the way they appear in a
+//!   color picker or hex code: either as `u8` channels (`glyphon::Color::rgb(59, 130, 246)`) or
+//!   as sRGB-encoded floats (`[0.08, 0.09, 0.11, 0.88]`). Alpha is always linear.
+//! - **Colors are converted exactly once, right before they reach wgpu.** Clear colors, vertex
+//!   colors, and uniforms go through [`srgb_for_target`] (or [`linear_for_target`] for values
+//!   that are already linear), which returns what the render target expects: linear values for
+//!   an `*Srgb` surface, whose hardware encodes them on write, or sRGB-encoded values otherwise.
+//! - **Values used in shader lighting or fog math are linear.** [`RenderTuning`] colors are
+//!   edited with egui's linear color picker and feed fog blending directly, so they are stored
+//!   linear and only pass through [`linear_for_target`].
+//! - **egui draws to a non-sRGB view of the surface** (see [`ui_format`]), because egui outputs
+//!   sRGB-encoded colors and would otherwise be encoded a second time.
+//!
+//! [`RenderTuning`]: crate::renderer::tuning::RenderTuning
+
+/// Converts one sRGB-encoded channel to linear light.
+///
+/// # Arguments
+/// * `c` - Channel value in `[0, 1]`
+///
+/// # Returns
+/// The linear channel value in `[0, 1]`
+pub fn srgb_to_linear(c: f32) -> f32 {
+    if c <= 0.04045 {
+        c / 12.92
+    } else {
+        ((c + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+/// Converts one linear channel to sRGB encoding.
+///
+/// # Arguments
+/// * `c` - Channel value in `[0, 1]`
+///
+/// # Returns
+/// The sRGB-encoded channel value in `[0, 1]`
+pub fn linear_to_srgb(c: f32) -> f32 {
+    if c <= 0.003_130_8 {
+        c * 12.92
+    } else {
+        1.055 * c.powf(1.0 / 2.4) - 0.055
+    }
+}
+
+/// Converts 8-bit sRGBA channels to sRGB-encoded floats.
+///
+/// # Arguments
+/// * `color` - Color as `[r, g, b, a]` bytes
+///
+/// # Returns
+/// The same color as floats in `[0, 1]`, still sRGB-encoded
+pub fn srgba_from_u8(color: [u8; 4]) -> [f32; 4] {
+    color.map(|c| c as f32 / 255.0)
+}
+
+/// Converts an sRGB-encoded RGBA color to linear RGBA, leaving alpha untouched.
+pub fn srgba_to_linear(color: [f32; 4]) -> [f32; 4] {
+    let [r, g, b, a] = color;
+    [srgb_to_linear(r), srgb_to_linear(g), srgb_to_linear(b), a]
+}
+
+/// Converts a linear RGBA color to sRGB encoding, leaving alpha untouched.
+pub fn linear_to_srgba(color: [f32; 4]) -> [f32; 4] {
+    let [r, g, b, a] = color;
+    [linear_to_srgb(r), linear_to_srgb(g), linear_to_srgb(b), a]
+}
+
+/// Prepares an sRGB-encoded color for writing to a render target.
+///
+/// # Arguments
+/// * `color` - sRGB-encoded RGBA color
+/// * `format` - Format of the render target
+///
+/// # Returns
+/// Linear RGBA for `*Srgb` formats, or the color unchanged for other formats
+pub fn srgb_for_target(color: [f32; 4], format: wgpu::TextureFormat) -> [f32; 4] {
+    if format.is_srgb() {
+        srgba_to_linear(color)
+    } else {
+        color
+    }
+}
+
+/// Prepares a linear color for writing to a render target.
+///
+/// # Arguments
+/// * `color` - Linear RGBA color
+/// * `format` - Format of the render target
+///
+/// # Returns
+/// The color unchanged for `*Srgb` formats, or sRGB-encoded RGBA for other formats
+pub fn linear_for_target(color: [f32; 4], format: wgpu::TextureFormat) -> [f32; 4] {
+    if format.is_srgb() {
+        color
+    } else {
+        linear_to_srgba(color)
+    }
+}
+
+/// Builds a render pass clear color from a value already prepared for the target.
+///
+/// # Arguments
+/// * `color` - Output of [`srgb_for_target`] or [`linear_for_target`]
+pub fn clear_color(color: [f32; 4]) -> wgpu::Color {
+    let [r, g, b, a] = color.map(f64::from);
+    wgpu::Color { r, g, b, a }
+}
+
+/// Returns the texture format egui should render with for a given surface format.
+///
+/// egui blends and outputs sRGB-encoded colors, so it has to draw through a non-sRGB view of the
+/// surface to avoid being gamma-encoded twice.
+///
+/// # Arguments
+/// * `surface_format` - Format the surface is configured with
+pub fn ui_format(surface_format: wgpu::TextureFormat) -> wgpu::TextureFormat {
+    surface_format.remove_srgb_suffix()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_every_byte_round_trips_through_linear() {
+        for byte in 0..=255u8 {
+            let srgb = byte as f32 / 255.0;
+            let back = linear_to_srgb(srgb_to_linear(srgb));
+            assert_eq!((back * 255.0).round() as u8, byte);
+        }
+        assert!((srgb_to_linear(0.5) - 0.214_041).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_conversion_depends_on_target_format() {
+        let design = srgba_from_u8([128, 64, 255, 128]);
+        let srgb_target = srgb_for_target(design, wgpu::TextureFormat::Bgra8UnormSrgb);
+        assert!((srgb_target[0] - srgb_to_linear(design[0])).abs() < 1e-6);
+        assert_eq!(srgb_target[2], 1.0);
+        assert_eq!(srgb_target[3], design[3]);
+
+        assert_eq!(
+            srgb_for_target(design, wgpu::TextureFormat::Bgra8Unorm),
+            design
+        );
+        let linear = [0.25, 0.5, 0.75, 1.0];
+        assert_eq!(
+            linear_for_target(linear, wgpu::TextureFormat::Rgba8UnormSrgb),
+            linear
+        );
+        assert_eq!(
+            ui_format(wgpu::TextureFormat::Bgra8UnormSrgb),
+            wgpu::TextureFormat::Bgra8Unorm
+        );
+    }
+}
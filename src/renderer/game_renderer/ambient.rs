@@ -0,0 +1,183 @@
+//! Ambient Renderer Module
+//!
+//! This module draws the level theme's ambient particles, dust motes or rain streaks, over
+//! the 3D view and under the HUD. The particles are generated procedurally in a full-screen
+//! pass, so they cost a few hash lookups per pixel and no geometry, and they never write
+//! depth. Each layer shifts with the camera's yaw and pitch so the particles stay put in
+//! the world as the player looks around.
+
+use crate::game::player::Player;
+use crate::game::theme::Ambience;
+use crate::math::deg_to_rad;
+use crate::renderer::pipeline_builder::{
+    BindGroupLayoutBuilder, PipelineBuilder, create_uniform_buffer,
+};
+use crate::renderer::safe_area::SafeArea;
+use wgpu;
+
+/// Uniform data passed to the ambient shader.
+///
+/// # Memory Layout
+/// 64 bytes: the color, three 2D vectors, and six scalars.
+#[repr(C)]
+#[derive(Debug, Copy, Clone, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct AmbientUniforms {
+    /// Particle color prepared for the render target, with the strongest opacity in alpha
+    pub color: [f32; 4],
+    /// Size of the 3D view in pixels
+    pub resolution: [f32; 2],
+    /// Top-left corner of the 3D view on the surface, in pixels
+    pub origin: [f32; 2],
+    /// Drift in view heights per second; positive y is up
+    pub velocity: [f32; 2],
+    /// Animation time in seconds
+    pub time: f32,
+    /// Camera yaw in radians, from 0 to a full turn
+    pub yaw: f32,
+    /// Camera pitch in radians
+    pub pitch: f32,
+    /// Chance that a grid cell holds a particle, from 0.0 to 1.0
+    pub density: f32,
+    /// Particle length along the drift in view heights; 0.0 draws round motes
+    pub streak: f32,
+    /// View heights the scene moves across the screen per radian the camera turns
+    pub view_per_radian: f32,
+}
+
+/// Renders the ambient particle layer of the level theme.
+///
+/// Like the vignette, the layer is drawn with a procedural full-screen triangle and needs
+/// no vertex buffer.
+pub struct AmbientRenderer {
+    /// The WebGPU render pipeline for the particles
+    pub pipeline: wgpu::RenderPipeline,
+    /// GPU buffer containing the ambient uniforms
+    pub uniform_buffer: wgpu::Buffer,
+    /// Bind group that associates the uniform buffer with shader bindings
+    pub bind_group: wgpu::BindGroup,
+}
+
+impl AmbientRenderer {
+    /// Creates a new ambient renderer.
+    ///
+    /// # Arguments
+    /// * `device` - WebGPU device for creating GPU resources
+    /// * `surface_config` - Surface configuration containing the target pixel format
+    ///
+    /// # Returns
+    /// An `AmbientRenderer` that draws nothing until its uniforms are updated
+    pub fn new(device: &wgpu::Device, surface_config: &wgpu::SurfaceConfiguration) -> Self {
+        let uniforms = AmbientUniforms {
+            color: [0.0; 4],
+            resolution: [1.0, 1.0],
+            origin: [0.0, 0.0],
+            velocity: [0.0, 0.0],
+            time: 0.0,
+            yaw: 0.0,
+            pitch: 0.0,
+            density: 0.0,
+            streak: 0.0,
+            view_per_radian: 1.0,
+        };
+        let uniform_buffer = create_uniform_buffer(device, &uniforms, "Ambient Uniform Buffer");
+
+        let bind_group_layout = BindGroupLayoutBuilder::new(device)
+            .with_label("Ambient Bind Group Layout")
+            .with_uniform_buffer(0, wgpu::ShaderStages::FRAGMENT)
+            .build();
+
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            layout: &bind_group_layout,
+            entries: &[wgpu::BindGroupEntry {
+                binding: 0,
+                resource: uniform_buffer.as_entire_binding(),
+            }],
+            label: Some("Ambient Bind Group"),
+        });
+
+        let pipeline = PipelineBuilder::new(device, surface_config.format)
+            .with_label("Ambient Pipeline")
+            .with_shader(include_str!("../shaders/ambient.wgsl"))
+            .with_bind_group_layout(&bind_group_layout)
+            .with_alpha_blending()
+            .build();
+
+        Self {
+            pipeline,
+            uniform_buffer,
+            bind_group,
+        }
+    }
+
+    /// Uploads this frame's uniforms.
+    ///
+    /// # Arguments
+    /// * `queue` - WebGPU command queue for GPU operations
+    /// * `ambience` - How the level theme's particles look
+    /// * `color` - The ambience's color, already prepared for the render target
+    /// * `view` - Region of the surface the 3D view is drawn in
+    /// * `player` - The player whose camera the particles follow
+    /// * `time` - Animation time in seconds
+    pub fn update(
+        &self,
+        queue: &wgpu::Queue,
+        ambience: &Ambience,
+        color: [f32; 4],
+        view: &SafeArea,
+        player: &Player,
+        time: f32,
+    ) {
+        let uniforms = AmbientUniforms {
+            color,
+            resolution: [view.width.max(1.0), view.height.max(1.0)],
+            origin: [view.x, view.y],
+            velocity: ambience.velocity,
+            time,
+            yaw: deg_to_rad(player.yaw.rem_euclid(360.0)),
+            pitch: deg_to_rad(player.pitch),
+            density: ambience.density.clamp(0.0, 1.0),
+            streak: ambience.streak.max(0.0),
+            view_per_radian: view_heights_per_radian(player.fov),
+        };
+        queue.write_buffer(&self.uniform_buffer, 0, bytemuck::cast_slice(&[uniforms]));
+    }
+
+    /// Renders the particles to the current render pass.
+    ///
+    /// # Arguments
+    /// * `render_pass` - Active render pass whose viewport covers the 3D view
+    pub fn render(&self, render_pass: &mut wgpu::RenderPass) {
+        render_pass.set_pipeline(&self.pipeline);
+        render_pass.set_bind_group(0, &self.bind_group, &[]);
+        render_pass.draw(0..3, 0..1);
+    }
+}
+
+/// Returns how far the scene near the middle of the view moves when the camera turns.
+///
+/// # Arguments
+/// * `fov` - Vertical field of view in degrees
+///
+/// # Returns
+/// The distance in view heights per radian of camera rotation
+fn view_heights_per_radian(fov: f32) -> f32 {
+    0.5 / (deg_to_rad(fov.clamp(1.0, 179.0)) * 0.5).tan()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::renderer::uniform_layout::assert_matches_shader;
+
+    #[test]
+    fn test_uniforms_match_the_shader_layout() {
+        assert_matches_shader::<AmbientUniforms>(include_str!("../shaders/ambient.wgsl"));
+    }
+
+    #[test]
+    fn test_wider_views_turn_more_slowly() {
+        // Half the view height is tan(45°) = 1 unit away at a 90 degree field of view
+        assert!((view_heights_per_radian(90.0) - 0.5).abs() < 1e-6);
+        assert!(view_heights_per_radian(60.0) > view_heights_per_radian(100.0));
+    }
+}
@@ -0,0 +1,186 @@
+//! Anomaly rendering module for the Mirador game.
+//!
+//! This module draws the anomaly some levels hide in a dead end (see [`crate::game::anomaly`]).
+//! The anomaly is a small tetrahedron floating above the floor, slowly turning and bobbing,
+//! lit from within in the color of the cosmetic it unlocks. Its 12 vertices are generated in
+//! the shader from the vertex index, so it needs no vertex buffer; it is depth tested and
+//! writes depth like the maze, so walls in front hide it.
+
+use crate::game::GameState;
+use crate::game::anomaly::{ANOMALY_SIZE, Cosmetic, collectible};
+use crate::renderer::pipeline_builder::{
+    BindGroupLayoutBuilder, PipelineBuilder, create_uniform_buffer,
+};
+use wgpu;
+
+/// Number of vertices drawn: four triangular faces.
+const ANOMALY_VERTEX_COUNT: u32 = 12;
+
+/// Uniform data passed to the anomaly shader.
+///
+/// # Memory Layout
+/// 112 bytes: the matrix, two vectors, the time, then padding up to a 16-byte multiple.
+#[repr(C)]
+#[derive(Copy, Clone, Debug, bytemuck::Pod, bytemuck::Zeroable)]
+struct AnomalyUniforms {
+    /// View-projection matrix for transforming vertices to screen space
+    view_proj_matrix: [[f32; 4]; 4],
+    /// Center of the anomaly (x, y, z) followed by its size in world units
+    center_size: [f32; 4],
+    /// Linear RGB glow color, then 1.0
+    color: [f32; 4],
+    /// Animation time in seconds
+    time: f32,
+    /// Padding to keep the struct 16-byte aligned
+    _padding: [f32; 3],
+}
+
+/// Returns the linear color an anomaly glows in, after the cosmetic it unlocks.
+///
+/// # Arguments
+/// * `cosmetic` - The cosmetic collecting the anomaly unlocks
+fn glow_color(cosmetic: Cosmetic) -> [f32; 3] {
+    match cosmetic {
+        Cosmetic::DuskPalette => [0.55, 0.2, 1.0],
+        Cosmetic::EmberStars => [1.0, 0.55, 0.1],
+        Cosmetic::RingReticle => [0.85, 0.9, 1.0],
+    }
+}
+
+/// Renders the level's anomaly.
+pub struct AnomalyRenderer {
+    /// The render pipeline for the anomaly
+    pipeline: wgpu::RenderPipeline,
+    /// Uniform buffer containing shader uniforms
+    uniform_buffer: wgpu::Buffer,
+    /// Bind group containing the uniforms
+    bind_group: wgpu::BindGroup,
+    /// Whether the last update found an anomaly to draw
+    visible: bool,
+}
+
+impl AnomalyRenderer {
+    /// Creates a new anomaly renderer.
+    ///
+    /// # Arguments
+    ///
+    /// * `device` - WGPU device for creating GPU resources
+    /// * `surface_config` - Surface configuration for pipeline creation
+    /// * `sample_count` - Samples per pixel of the render target the 3D view is drawn into
+    ///
+    /// # Returns
+    ///
+    /// A new `AnomalyRenderer` with nothing to draw until its first update.
+    pub fn new(
+        device: &wgpu::Device,
+        surface_config: &wgpu::SurfaceConfiguration,
+        sample_count: u32,
+    ) -> Self {
+        let uniforms = AnomalyUniforms {
+            view_proj_matrix: [[0.0; 4]; 4],
+            center_size: [0.0, 0.0, 0.0, ANOMALY_SIZE],
+            color: [1.0; 4],
+            time: 0.0,
+            _padding: [0.0; 3],
+        };
+        let uniform_buffer = create_uniform_buffer(device, &uniforms, "Anomaly Uniform Buffer");
+
+        let bind_group_layout = BindGroupLayoutBuilder::new(device)
+            .with_label("Anomaly Bind Group Layout")
+            .with_uniform_buffer(0, wgpu::ShaderStages::VERTEX | wgpu::ShaderStages::FRAGMENT)
+            .build();
+
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            layout: &bind_group_layout,
+            entries: &[wgpu::BindGroupEntry {
+                binding: 0,
+                resource: uniform_buffer.as_entire_binding(),
+            }],
+            label: Some("Anomaly Bind Group"),
+        });
+
+        let pipeline = PipelineBuilder::new(device, surface_config.format)
+            .with_label("Anomaly Pipeline")
+            .with_shader(include_str!("../shaders/anomaly.wgsl"))
+            .with_bind_group_layout(&bind_group_layout)
+            .with_no_culling()
+            .with_depth_stencil(wgpu::DepthStencilState {
+                depth_write_enabled: true,
+                depth_compare: wgpu::CompareFunction::LessEqual,
+                format: wgpu::TextureFormat::Depth24Plus,
+                stencil: wgpu::StencilState::default(),
+                bias: wgpu::DepthBiasState::default(),
+            })
+            .with_sample_count(sample_count)
+            .build();
+
+        Self {
+            pipeline,
+            uniform_buffer,
+            bind_group,
+            visible: false,
+        }
+    }
+
+    /// Uploads the level's anomaly and the view-projection matrix.
+    ///
+    /// # Arguments
+    ///
+    /// * `queue` - WGPU queue for uploading data
+    /// * `game_state` - Current game state holding the anomaly, if the level has one
+    /// * `view_proj_matrix` - Current view-projection matrix for rendering
+    /// * `time` - Animation time in seconds
+    pub fn update(
+        &mut self,
+        queue: &wgpu::Queue,
+        game_state: &GameState,
+        view_proj_matrix: [[f32; 4]; 4],
+        time: f32,
+    ) {
+        let Some(anomaly) = &game_state.anomaly else {
+            self.visible = false;
+            return;
+        };
+        self.visible = true;
+
+        let [x, y, z] = anomaly.position;
+        let [r, g, b] = collectible(anomaly.collectible)
+            .map_or([1.0; 3], |collectible| glow_color(collectible.cosmetic));
+        let uniforms = AnomalyUniforms {
+            view_proj_matrix,
+            center_size: [x, y, z, ANOMALY_SIZE],
+            color: [r, g, b, 1.0],
+            time,
+            _padding: [0.0; 3],
+        };
+        queue.write_buffer(&self.uniform_buffer, 0, bytemuck::cast_slice(&[uniforms]));
+    }
+
+    /// Renders the anomaly to the specified render pass.
+    ///
+    /// Does nothing when the level has no anomaly, or it has been taken.
+    ///
+    /// # Arguments
+    ///
+    /// * `render_pass` - The render pass to draw to
+    pub fn render(&self, render_pass: &mut wgpu::RenderPass) {
+        if !self.visible {
+            return;
+        }
+
+        render_pass.set_pipeline(&self.pipeline);
+        render_pass.set_bind_group(0, &self.bind_group, &[]);
+        render_pass.draw(0..ANOMALY_VERTEX_COUNT, 0..1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::renderer::uniform_layout::assert_matches_shader;
+
+    #[test]
+    fn test_uniforms_match_the_shader_layout() {
+        assert_matches_shader::<AnomalyUniforms>(include_str!("../shaders/anomaly.wgsl"));
+    }
+}
@@ -15,15 +15,15 @@
 //!
 //! # Usage
 //!
-//! ```rust
+//! ```rust,ignore
 //! // Create compass renderer
 //! let compass = CompassRenderer::new(device, queue, surface_config);
 //!
 //! // Update direction based on player and exit positions
-//! compass.update_compass_with_yaw(player_pos, exit_pos, player_yaw);
+//! compass.update_compass_with_yaw(player_pos, exit_pos, player_yaw, delta_time);
 //!
 //! // Render compass overlay
-//! compass.render(&mut render_pass, window);
+//! compass.render(&mut render_pass);
 //! ```
 //!
 //! # Texture Requirements
@@ -38,13 +38,61 @@
 //! and world coordinates for direction calculations. The needle rotation is
 //! calculated relative to the player's forward direction.
 
+use crate::assets;
 use crate::renderer::pipeline_builder::BindGroupLayoutBuilder;
 use crate::renderer::pipeline_builder::PipelineBuilder;
 use crate::renderer::pipeline_builder::create_uniform_buffer;
+use crate::renderer::safe_area::SafeArea;
+use image;
+use std::f32::consts::{PI, TAU};
 use wgpu;
 use wgpu::util::DeviceExt;
-use crate::assets;
-use image;
+
+/// Seconds the needle takes to cover about two thirds of the way to a new bearing.
+pub const DEFAULT_COMPASS_SMOOTHING: f32 = 0.08;
+
+/// Returns the signed angle to turn from `current` to `target` the short way round.
+///
+/// # Arguments
+/// * `target` - Angle to turn toward, in radians
+/// * `current` - Angle to turn from, in radians
+///
+/// # Returns
+/// The difference in radians, in `[-π, π]`
+pub fn shortest_angle_diff(target: f32, current: f32) -> f32 {
+    (target - current + PI).rem_euclid(TAU) - PI
+}
+
+/// Returns how much of the remaining turn to cover in one frame.
+///
+/// Exponential smoothing by time rather than by frame, so the needle settles just as fast
+/// at 30 fps as at 240.
+///
+/// # Arguments
+/// * `delta_time` - Seconds since the last frame
+/// * `time_constant` - Seconds to cover about two thirds of the turn; `0.0` or less snaps
+pub fn smoothing_blend(delta_time: f32, time_constant: f32) -> f32 {
+    if time_constant <= 0.0 {
+        return 1.0;
+    }
+    1.0 - (-delta_time.max(0.0) / time_constant).exp()
+}
+
+/// Eases an angle toward a target for one frame, never turning the long way round.
+///
+/// # Arguments
+/// * `current` - The angle shown last frame, in radians
+/// * `target` - The angle to ease toward, in radians
+/// * `delta_time` - Seconds since the last frame
+/// * `time_constant` - Smoothing time constant in seconds
+///
+/// # Returns
+/// The new angle in radians, in `[-π, π]`
+pub fn ease_angle(current: f32, target: f32, delta_time: f32, time_constant: f32) -> f32 {
+    let eased =
+        current + shortest_angle_diff(target, current) * smoothing_blend(delta_time, time_constant);
+    shortest_angle_diff(eased, 0.0)
+}
 
 /// Uniform data for compass positioning and sizing.
 ///
@@ -54,14 +102,14 @@ use image;
 ///
 /// # Memory Layout
 ///
-/// - `screen_position`: Normalized screen coordinates [x, y] (0.0 to 1.0)
+/// - `screen_position`: Normalized screen coordinates [x, y] of the compass center (0.0 to 1.0)
 /// - `compass_size`: Size as fraction of screen [width, height] (0.0 to 1.0)
 /// - `_padding`: Ensures proper GPU memory alignment
 ///
 /// # Default Values
 ///
-/// - Position: Bottom-right corner (0.85, 0.85)
-/// - Size: 12% of screen (0.12, 0.12)
+/// - Position: Bottom-right corner (0.85, 0.15)
+/// - Size: 25% of a 16:9 screen (0.25, 0.25)
 #[repr(C)]
 #[derive(Debug, Copy, Clone, bytemuck::Pod, bytemuck::Zeroable)]
 struct CompassUniforms {
@@ -98,9 +146,9 @@ struct CompassUniforms {
 ///
 /// # Smoothing System
 ///
-/// The compass uses exponential smoothing to prevent jarring needle movements:
-/// - `smoothing_factor`: Controls responsiveness (0.01 = very smooth, 1.0 = instant)
-/// - `smoothed_compass_angle`: Current interpolated angle for needle selection
+/// The compass eases the needle toward the exit's bearing by time, not by frame:
+/// - `smoothing_time`: Time constant in seconds (smaller = more responsive, 0 = instant)
+/// - `displayed_angle`: The angle being shown, kept between frames and cleared on level load
 ///
 /// # Performance Characteristics
 ///
@@ -115,7 +163,7 @@ struct CompassUniforms {
 ///
 /// # Example
 ///
-/// ```rust
+/// ```rust,ignore
 /// # use crate::renderer::game_renderer::compass::CompassRenderer;
 /// # let device: wgpu::Device = unimplemented!();
 /// # let queue: wgpu::Queue = unimplemented!();
@@ -124,10 +172,10 @@ struct CompassUniforms {
 /// let compass = CompassRenderer::new(&device, &queue, &surface_config);
 ///
 /// // Update direction
-/// compass.update_compass_with_yaw(player_pos, exit_pos, player_yaw);
+/// compass.update_compass_with_yaw(player_pos, exit_pos, player_yaw, delta_time);
 ///
 /// // Render
-/// compass.render(&mut render_pass, window);
+/// compass.render(&mut render_pass);
 /// ```
 pub struct CompassRenderer {
     /// WGPU render pipeline for compass rendering.
@@ -166,18 +214,17 @@ pub struct CompassRenderer {
     /// Updated based on calculated direction to exit.
     current_needle_index: usize,
 
-    /// Current smoothed compass angle in radians.
+    /// Angle the needle is showing, in radians relative to the player's facing.
     ///
-    /// This is the interpolated angle used for needle selection.
-    /// Ranges from 0 to 2π and is updated with smoothing applied.
-    smoothed_compass_angle: f32,
+    /// Ranges from -π to π and eases toward the exit's bearing each frame.
+    /// `None` until the first update of a level, which snaps straight to the bearing.
+    displayed_angle: Option<f32>,
 
-    /// Smoothing factor for compass movement (0.01 to 1.0).
+    /// Smoothing time constant in seconds.
     ///
-    /// Controls how quickly the needle responds to direction changes.
-    /// Lower values = smoother but slower response.
-    /// Higher values = faster but potentially jittery response.
-    smoothing_factor: f32,
+    /// Seconds the needle takes to cover about two thirds of a turn.
+    /// Lower values = faster but potentially jittery response, 0 = instant.
+    smoothing_time: f32,
 }
 
 impl CompassRenderer {
@@ -197,8 +244,8 @@ impl CompassRenderer {
         let needle_textures = Self::load_needle_textures(device, queue);
 
         let uniforms = CompassUniforms {
-            screen_position: [0.85, 0.85], // Bottom-right corner (normalized coordinates)
-            compass_size: [0.12, 0.12],    // 12% of screen size
+            screen_position: [0.85, 0.15], // Bottom-right corner (normalized coordinates)
+            compass_size: [0.25, 0.25],    // 25% of screen size
             _padding: [0.0; 4],
         };
 
@@ -307,9 +354,8 @@ impl CompassRenderer {
             base_bind_group,
             needle_bind_groups,
             current_needle_index: 0,
-
-            smoothed_compass_angle: 0.0,
-            smoothing_factor: 0.8, // Higher = more responsive, lower = smoother
+            displayed_angle: None,
+            smoothing_time: DEFAULT_COMPASS_SMOOTHING,
         }
     }
 
@@ -318,7 +364,10 @@ impl CompassRenderer {
         let img = match image::load_from_memory(assets::GOLD_COMPASS) {
             Ok(img) => img.to_rgba8(),
             Err(e) => {
-                eprintln!("Failed to load compass base texture from embedded assets: {}", e);
+                eprintln!(
+                    "Failed to load compass base texture from embedded assets: {}",
+                    e
+                );
                 // Create a fallback texture (solid color or default compass)
                 image::RgbaImage::new(64, 64)
             }
@@ -370,7 +419,10 @@ impl CompassRenderer {
             let img = match image::load_from_memory(needle_data) {
                 Ok(img) => img.to_rgba8(),
                 Err(e) => {
-                    eprintln!("Failed to load needle texture {} from embedded assets: {}", i, e);
+                    eprintln!(
+                        "Failed to load needle texture {} from embedded assets: {}",
+                        i, e
+                    );
                     // Create a fallback texture (transparent or simple needle)
                     image::RgbaImage::new(64, 64)
                 }
@@ -462,7 +514,7 @@ impl CompassRenderer {
     ///
     /// # Example
     ///
-    /// ```rust
+    /// ```rust,ignore
     /// # use crate::renderer::game_renderer::compass::CompassRenderer;
     /// # let compass: CompassRenderer = unimplemented!();
     /// # let queue: wgpu::Queue = unimplemented!();
@@ -488,6 +540,45 @@ impl CompassRenderer {
         queue.write_buffer(&self.uniform_buffer, 0, bytemuck::cast_slice(&[uniforms]));
     }
 
+    /// Computes where the compass sits inside the HUD safe area.
+    ///
+    /// The compass keeps the size and place it has on a 16:9 window, measured
+    /// from the safe area's height, and is pulled left if it would otherwise
+    /// cross the safe area's right edge on narrow windows.
+    ///
+    /// # Parameters
+    ///
+    /// - `hud` - HUD safe area in pixels
+    /// - `width`, `height` - Window size in pixels
+    ///
+    /// # Returns
+    ///
+    /// The `(screen_position, compass_size)` pair for [`update_uniforms`](Self::update_uniforms)
+    pub fn layout_in(hud: &SafeArea, width: f32, height: f32) -> ([f32; 2], [f32; 2]) {
+        // A zero-sized window would divide by zero below
+        let (width, height) = (width.max(1.0), height.max(1.0));
+        let half_height = hud.height * 0.125;
+        let half_width = half_height * 16.0 / 9.0;
+        let center_x = (hud.x + hud.width * 0.85).min(hud.right() - half_width);
+        let center_y = hud.bottom() - hud.height * 0.15;
+        (
+            [center_x / width, 1.0 - center_y / height],
+            [2.0 * half_width / width, 2.0 * half_height / height],
+        )
+    }
+
+    /// Places the compass inside the HUD safe area.
+    ///
+    /// # Parameters
+    ///
+    /// - `queue` - WGPU queue for buffer uploads
+    /// - `hud` - HUD safe area in pixels
+    /// - `width`, `height` - Window size in pixels
+    pub fn update_layout(&self, queue: &wgpu::Queue, hud: &SafeArea, width: f32, height: f32) {
+        let (screen_position, compass_size) = Self::layout_in(hud, width, height);
+        self.update_uniforms(queue, screen_position, compass_size);
+    }
+
     /// Renders the compass overlay to the current render pass.
     ///
     /// This method performs a two-pass rendering approach:
@@ -514,23 +605,21 @@ impl CompassRenderer {
     /// # Parameters
     ///
     /// - `render_pass` - Active render pass to render into
-    /// - `window` - Window reference (currently unused, reserved for future use)
     ///
     /// # Example
     ///
-    /// ```rust
+    /// ```rust,ignore
     /// # use crate::renderer::game_renderer::compass::CompassRenderer;
     /// # let compass: CompassRenderer = unimplemented!();
     /// # let mut render_pass: wgpu::RenderPass = unimplemented!();
-    /// # let window: &winit::window::Window = unimplemented!();
     ///
     /// // Render game scene first
     /// // ... render background, maze, player, etc ...
     ///
     /// // Render compass overlay on top
-    /// compass.render(&mut render_pass, window);
+    /// compass.render(&mut render_pass);
     /// ```
-    pub fn render(&self, render_pass: &mut wgpu::RenderPass, _window: &winit::window::Window) {
+    pub fn render(&self, render_pass: &mut wgpu::RenderPass) {
         render_pass.set_pipeline(&self.pipeline);
         render_pass.set_vertex_buffer(0, self.vertex_buffer.slice(..));
 
@@ -543,55 +632,6 @@ impl CompassRenderer {
         render_pass.draw(0..6, 0..1);
     }
 
-    /// Calculate which needle image to show based on player and exit positions
-    pub fn update_compass_direction(&mut self, player_pos: (f32, f32), exit_pos: (f32, f32)) {
-        // Vector from player to exit
-        let direction_vector = (player_pos.0 - exit_pos.0, player_pos.1 - exit_pos.1);
-
-        // Skip if too close (avoid jitter when on top of exit)
-        let distance_sq =
-            direction_vector.0 * direction_vector.0 + direction_vector.1 * direction_vector.1;
-        if distance_sq < 0.0001 {
-            return;
-        }
-
-        // Calculate angle to exit in world space
-        let mut target_angle = direction_vector.1.atan2(direction_vector.0); // [-π, π]
-
-        // Normalize to [0, 2π]
-        if target_angle < 0.0 {
-            target_angle += 2.0 * std::f32::consts::PI;
-        }
-
-        // Smooth angle update (exponential smoothing)
-        let alpha = self.smoothing_factor; // Lower = slower/smoother
-        let mut delta = target_angle - self.smoothed_compass_angle;
-
-        // Wrap to [-π, π] for shortest rotation
-        if delta > std::f32::consts::PI {
-            delta -= 2.0 * std::f32::consts::PI;
-        } else if delta < -std::f32::consts::PI {
-            delta += 2.0 * std::f32::consts::PI;
-        }
-
-        self.smoothed_compass_angle += alpha * delta;
-
-        // Re-wrap smoothed angle to [0, 2π]
-        while self.smoothed_compass_angle < 0.0 {
-            self.smoothed_compass_angle += 2.0 * std::f32::consts::PI;
-        }
-        while self.smoothed_compass_angle >= 2.0 * std::f32::consts::PI {
-            self.smoothed_compass_angle -= 2.0 * std::f32::consts::PI;
-        }
-
-        // Map to needle frame (0–11, since we have 12 needles indexed 1-12)
-        let new_index = ((self.smoothed_compass_angle / (2.0 * std::f32::consts::PI)) * 12.0)
-            .floor() as usize
-            % 12;
-
-        self.current_needle_index = new_index;
-    }
-
     /// Updates the compass to point toward the exit from the player's current position.
     ///
     /// This function calculates the direction from the player to the exit cell and
@@ -604,7 +644,7 @@ impl CompassRenderer {
     /// 1. **Vector Calculation**: Computes direction vector from player to exit
     /// 2. **Player Orientation**: Accounts for player's current facing direction
     /// 3. **Coordinate Transformation**: Converts world direction to player-relative direction
-    /// 4. **Smoothing**: Applies exponential smoothing to prevent jarring movements
+    /// 4. **Smoothing**: Eases toward the bearing by time to prevent jarring movements
     /// 5. **Needle Selection**: Maps smoothed angle to appropriate needle sprite (0-11)
     ///
     /// # Coordinate Systems
@@ -616,9 +656,10 @@ impl CompassRenderer {
     /// # Smoothing Behavior
     ///
     /// The compass uses exponential smoothing to create fluid needle movement:
-    /// - **Shortest Path**: Always takes the shortest angular distance
-    /// - **Configurable Response**: Smoothing factor controls responsiveness
-    /// - **Wrapping**: Properly handles angle wrapping around 0°/360°
+    /// - **Shortest Path**: Always takes the shortest angular distance, so crossing behind
+    ///   the player moves the needle a few degrees rather than a full turn
+    /// - **Framerate Independent**: The smoothing time constant is in seconds, not frames
+    /// - **Level Start**: The first update after [`reset`](Self::reset) snaps to the bearing
     ///
     /// # Performance Notes
     ///
@@ -630,10 +671,11 @@ impl CompassRenderer {
     /// * `player_pos` - The player's position as (x, z) coordinates in world space
     /// * `exit_pos` - The exit's position as (x, z) coordinates in world space
     /// * `player_yaw_degrees` - The player's current yaw angle in degrees (0-360)
+    /// * `delta_time` - Seconds since the last frame
     ///
     /// # Example
     ///
-    /// ```rust
+    /// ```rust,ignore
     /// # use crate::renderer::game_renderer::compass::CompassRenderer;
     /// # let mut compass: CompassRenderer = unimplemented!();
     ///
@@ -641,7 +683,8 @@ impl CompassRenderer {
     /// compass.update_compass_with_yaw(
     ///     (player.x, player.z),      // Player position
     ///     (exit.x, exit.z),          // Exit position
-    ///     player.yaw_degrees         // Player facing direction
+    ///     player.yaw_degrees,        // Player facing direction
+    ///     delta_time,                // Seconds since the last frame
     /// );
     /// ```
     pub fn update_compass_with_yaw(
@@ -649,6 +692,7 @@ impl CompassRenderer {
         player_pos: (f32, f32), // (x, z) coordinates
         exit_pos: (f32, f32),   // (x, z) coordinates
         player_yaw_degrees: f32,
+        delta_time: f32,
     ) {
         // Calculate vector from player to exit
         let dx = exit_pos.0 - player_pos.0; // Change in X
@@ -679,93 +723,140 @@ impl CompassRenderer {
         let forward_dot = -forward_x * dir_x - forward_z * dir_z; // Dot product with forward vector
         let right_dot = right_x * dir_x - right_z * dir_z; // Dot product with right vector
 
-        // Calculate angle using atan2
-        let mut target_compass_angle = right_dot.atan2(forward_dot);
-
-        // Normalize to [-π, π]
-        target_compass_angle = self.normalize_angle(target_compass_angle);
-
-        // Initialize smoothed angle on first update
-        if self.smoothed_compass_angle.is_nan() {
-            self.smoothed_compass_angle = target_compass_angle;
-        }
-
-        // Calculate the shortest angular distance for smooth interpolation
-        let angle_diff =
-            self.shortest_angle_diff(target_compass_angle, self.smoothed_compass_angle);
-
-        // Apply smoothing
-        self.smoothed_compass_angle += angle_diff * self.smoothing_factor;
-
-        // Normalize the smoothed angle
-        self.smoothed_compass_angle = self.normalize_angle(self.smoothed_compass_angle);
+        // Calculate angle using atan2, in [-π, π]
+        let target_compass_angle = right_dot.atan2(forward_dot);
+
+        // Snap on the first update of a level, then ease the short way round
+        let displayed = match self.displayed_angle {
+            Some(current) => ease_angle(
+                current,
+                target_compass_angle,
+                delta_time,
+                self.smoothing_time,
+            ),
+            None => target_compass_angle,
+        };
+        self.displayed_angle = Some(displayed);
 
         // Convert to needle index (0-11 for 12 needle sprites)
         // Convert from [-π, π] to [0, 2π] for easier indexing
-        let angle_for_index = if self.smoothed_compass_angle < 0.0 {
-            self.smoothed_compass_angle + 2.0 * std::f32::consts::PI
-        } else {
-            self.smoothed_compass_angle
-        };
+        let angle_for_index = displayed.rem_euclid(TAU);
 
         // Convert to 12-segment index (each segment is 30° = π/6 radians)
         // Add half a segment (π/12) for proper rounding to nearest segment
-        let needle_index = ((angle_for_index + std::f32::consts::PI / 12.0)
-            / (std::f32::consts::PI / 6.0))
-            .floor() as usize
-            % 12;
+        let needle_index = ((angle_for_index + PI / 12.0) / (PI / 6.0)).floor() as usize % 12;
 
         self.current_needle_index = needle_index;
     }
 
-    /// Normalize angle to [-π, π]
-    fn normalize_angle(&self, mut angle: f32) -> f32 {
-        while angle > std::f32::consts::PI {
-            angle -= 2.0 * std::f32::consts::PI;
-        }
-        while angle < -std::f32::consts::PI {
-            angle += 2.0 * std::f32::consts::PI;
-        }
-        angle
+    /// Forgets the displayed angle so the next update points straight at the exit.
+    ///
+    /// Called when a level loads, so the needle doesn't swing over from where the last
+    /// level's exit was.
+    pub fn reset(&mut self) {
+        self.displayed_angle = None;
+    }
+
+    /// Returns the angle the needle is showing in degrees, or `None` before the first
+    /// update of a level.
+    pub fn get_compass_angle_degrees(&self) -> Option<f32> {
+        self.displayed_angle.map(f32::to_degrees)
     }
 
-    /// Calculate shortest angular difference between two angles
-    fn shortest_angle_diff(&self, target: f32, current: f32) -> f32 {
-        let mut diff = target - current;
+    /// Sets the smoothing time constant in seconds (0.0 = instant).
+    pub fn set_smoothing_time(&mut self, seconds: f32) {
+        self.smoothing_time = seconds.max(0.0);
+    }
+}
 
-        // Wrap to shortest path
-        if diff > std::f32::consts::PI {
-            diff -= 2.0 * std::f32::consts::PI;
-        } else if diff < -std::f32::consts::PI {
-            diff += 2.0 * std::f32::consts::PI;
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::renderer::safe_area::DEFAULT_HUD_MAX_ASPECT;
+
+    #[test]
+    fn test_compass_stays_inside_the_hud() {
+        for (width, height) in [(3840.0, 1080.0), (2520.0, 1080.0), (1440.0, 1080.0)] {
+            let hud = SafeArea::centered(width, height, DEFAULT_HUD_MAX_ASPECT);
+            let ([x, y], [w, h]) = CompassRenderer::layout_in(&hud, width, height);
+            let left = (x - w / 2.0) * width;
+            let top = (1.0 - y - h / 2.0) * height;
+            assert!(hud.contains(left, top, w * width, h * height));
         }
 
-        diff
+        // On 16:9 the compass keeps its original place and size
+        let hud = SafeArea::full(1920.0, 1080.0);
+        let ([x, y], [w, h]) = CompassRenderer::layout_in(&hud, 1920.0, 1080.0);
+        assert!((x - 0.85).abs() < 1e-5 && (y - 0.15).abs() < 1e-5);
+        assert!((w - 0.25).abs() < 1e-5 && (h - 0.25).abs() < 1e-5);
     }
 
-    /// Alternative update with configurable smoothing
-    pub fn update_compass_with_smoothing(
-        &mut self,
-        player_pos: (f32, f32),
-        exit_pos: (f32, f32),
-        player_yaw_degrees: f32,
-        smoothing: f32, // 0.0 = very smooth, 1.0 = instant response
-    ) {
-        let old_smoothing = self.smoothing_factor;
-        self.smoothing_factor = smoothing.clamp(0.01, 1.0);
-
-        self.update_compass_with_yaw(player_pos, exit_pos, player_yaw_degrees);
+    #[test]
+    fn test_compass_layout_on_tiny_windows() {
+        for (width, height) in [(1.0, 1.0), (30.0, 2000.0), (2000.0, 30.0), (0.0, 0.0)] {
+            let hud = SafeArea::centered(width, height, DEFAULT_HUD_MAX_ASPECT);
+            let ([x, y], [w, h]) = CompassRenderer::layout_in(&hud, width, height);
+            for value in [x, y, w, h] {
+                assert!(value.is_finite(), "{}x{} gave {}", width, height, value);
+            }
+            assert!(w >= 0.0 && h >= 0.0);
+        }
+    }
 
-        self.smoothing_factor = old_smoothing;
+    #[test]
+    fn test_shortest_angle_diff_crosses_the_boundary() {
+        let diff = |target: f32, current: f32| {
+            shortest_angle_diff(target.to_radians(), current.to_radians()).to_degrees()
+        };
+        assert!((diff(-179.0, 179.0) - 2.0).abs() < 1e-3);
+        assert!((diff(179.0, -179.0) + 2.0).abs() < 1e-3);
+        assert!((diff(10.0, 350.0) - 20.0).abs() < 1e-3);
+        assert!((diff(90.0, -90.0).abs() - 180.0).abs() < 1e-3);
+        assert!((diff(45.0, 45.0 + 720.0)).abs() < 1e-3);
+        for (target, current) in [(3.0, -3.0), (-3.1, 3.1), (100.0, -100.0)] {
+            let d = shortest_angle_diff(target, current);
+            assert!(
+                (-PI..=PI).contains(&d),
+                "{} from {} gave {}",
+                target,
+                current,
+                d
+            );
+        }
     }
 
-    /// For debugging - get current compass angle in degrees
-    pub fn get_compass_angle_degrees(&self) -> f32 {
-        self.smoothed_compass_angle.to_degrees()
+    #[test]
+    fn test_smoothing_converges_at_any_framerate() {
+        let target = 90f32.to_radians();
+        for fps in [30.0, 60.0, 144.0] {
+            let dt = 1.0 / fps;
+            let mut angle = 0.0;
+            let mut t = 0.0;
+            while t < 5.0 * DEFAULT_COMPASS_SMOOTHING {
+                angle = ease_angle(angle, target, dt, DEFAULT_COMPASS_SMOOTHING);
+                t += dt;
+            }
+            // Five time constants leave under 1% of the turn, whatever the framerate
+            assert!(
+                (angle - target).abs() < target * 0.01,
+                "{} fps left {}",
+                fps,
+                angle
+            );
+        }
+        assert!((ease_angle(0.0, target, 1.0 / 60.0, 0.0) - target).abs() < 1e-5);
     }
 
-    /// Set smoothing factor (0.0 = very smooth, 1.0 = instant)
-    pub fn set_smoothing_factor(&mut self, factor: f32) {
-        self.smoothing_factor = factor.clamp(0.01, 1.0);
+    #[test]
+    fn test_smoothing_never_takes_the_long_way() {
+        // Walking past the exit flips its bearing across the ±180° line
+        let target = (-170f32).to_radians();
+        let mut angle = 170f32.to_radians();
+        for _ in 0..120 {
+            angle = ease_angle(angle, target, 1.0 / 60.0, DEFAULT_COMPASS_SMOOTHING);
+            // The needle stays in the 20° behind the player instead of sweeping through 0°
+            assert!(angle.abs() >= 170f32.to_radians() - 1e-4, "{}", angle);
+        }
+        assert!(shortest_angle_diff(target, angle).abs() < 1e-3);
     }
 }
@@ -0,0 +1,359 @@
+//! Death Fade Renderer Module
+//!
+//! This module eases the cut from the live 3D view to the game over screen. On the first
+//! game over frame the scene is drawn into a [`SceneTarget`] and copied once into a second
+//! texture, which holds the moment of death. For the next [`FADE_SECONDS`] the live scene keeps
+//! being drawn while that frozen copy fades in over it; once it is opaque the scene is no
+//! longer drawn at all, and the game over screen is only the frozen copy under its overlay
+//! and text.
+//!
+//! The frozen copy keeps the size the window had when it was taken. If the window is resized
+//! on the game over screen it is scaled to fit and centered between black bars rather than
+//! stretched. When either texture can't be allocated the scene is drawn every frame instead,
+//! as it was before the fade.
+
+use crate::renderer::gpu_memory::GpuMemoryTracker;
+use crate::renderer::pipeline_builder::{
+    BindGroupLayoutBuilder, PipelineBuilder, create_uniform_buffer,
+};
+use crate::renderer::scene_target::SceneTarget;
+use wgpu;
+
+/// Seconds the frozen copy takes to fade in over the live scene.
+pub const FADE_SECONDS: f32 = 0.5;
+
+/// Uniform data passed to the death fade shader.
+///
+/// # Memory Layout
+/// 16 bytes: the quad's scale, its opacity, and padding.
+#[repr(C)]
+#[derive(Debug, Copy, Clone, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct DeathFadeUniforms {
+    /// Half-size of the quad in clip space along x and y, at most 1.0
+    pub scale: [f32; 2],
+    /// Opacity of the frozen copy, from 0.0 to 1.0
+    pub alpha: f32,
+    /// Padding to keep the struct 16-byte aligned
+    pub _padding: f32,
+}
+
+/// The clock of the fade, advanced every frame.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct DeathFade {
+    /// Seconds since the game over screen was entered, or `None` away from it
+    elapsed: Option<f32>,
+}
+
+impl DeathFade {
+    /// Advances the fade by one frame.
+    ///
+    /// # Arguments
+    /// * `on_game_over` - Whether the game over screen is showing this frame
+    /// * `delta_time` - Seconds since the last frame
+    pub fn advance(&mut self, on_game_over: bool, delta_time: f32) {
+        self.elapsed = match (on_game_over, self.elapsed) {
+            (false, _) => None,
+            (true, None) => Some(0.0),
+            (true, Some(elapsed)) => Some(elapsed + delta_time.max(0.0)),
+        };
+    }
+
+    /// Returns whether the game over screen is showing.
+    pub fn is_active(&self) -> bool {
+        self.elapsed.is_some()
+    }
+
+    /// Returns the seconds since the game over screen was entered, 0.0 away from it.
+    pub fn elapsed(&self) -> f32 {
+        self.elapsed.unwrap_or(0.0)
+    }
+
+    /// Returns the opacity of the frozen copy, easing from 0.0 to 1.0 over [`FADE_SECONDS`].
+    pub fn alpha(&self) -> f32 {
+        let t = (self.elapsed.unwrap_or(0.0) / FADE_SECONDS).clamp(0.0, 1.0);
+        t * t * (3.0 - 2.0 * t)
+    }
+
+    /// Returns whether the frozen copy has fully covered the live scene.
+    pub fn is_complete(&self) -> bool {
+        self.elapsed.is_some_and(|elapsed| elapsed >= FADE_SECONDS)
+    }
+}
+
+/// Returns how much of the surface a copy of another size covers when scaled to fit it.
+///
+/// # Arguments
+/// * `copy` - Size of the copy in pixels
+/// * `surface` - Size of the surface in pixels
+///
+/// # Returns
+/// The half-size of the quad in clip space along x and y; one of them is 1.0 and the other
+/// at most 1.0, so the copy keeps its aspect ratio between bars
+pub fn letterbox_scale(copy: (u32, u32), surface: (u32, u32)) -> [f32; 2] {
+    let copy_aspect = copy.0.max(1) as f32 / copy.1.max(1) as f32;
+    let surface_aspect = surface.0.max(1) as f32 / surface.1.max(1) as f32;
+    if copy_aspect > surface_aspect {
+        [1.0, surface_aspect / copy_aspect]
+    } else {
+        [copy_aspect / surface_aspect, 1.0]
+    }
+}
+
+/// Renders the crossfade into the frozen game over screen.
+pub struct DeathFadeRenderer {
+    /// Pipeline drawing the frozen copy onto the surface
+    pipeline: wgpu::RenderPipeline,
+    /// Buffer of the death fade uniforms
+    uniform_buffer: wgpu::Buffer,
+    /// Bind group of the death fade uniforms
+    uniform_bind_group: wgpu::BindGroup,
+    /// The offscreen texture the scene is drawn into on the frame it is captured
+    scene: SceneTarget,
+    /// The copy of the scene at the moment of death
+    frozen: SceneTarget,
+    /// The clock of the fade
+    fade: DeathFade,
+    /// Whether the frozen copy holds this game over's scene
+    captured: bool,
+    /// Whether the copy was taken this frame, when the live scene is only in the copy
+    captured_this_frame: bool,
+}
+
+impl DeathFadeRenderer {
+    /// Creates the death fade renderer; its textures are created at the first game over.
+    ///
+    /// # Arguments
+    /// * `device` - WebGPU device for creating GPU resources
+    /// * `surface_config` - Surface configuration containing the format and size
+    /// * `gpu_memory` - Tracker the textures are counted in
+    pub fn new(
+        device: &wgpu::Device,
+        surface_config: &wgpu::SurfaceConfiguration,
+        gpu_memory: &GpuMemoryTracker,
+    ) -> Self {
+        let uniforms = DeathFadeUniforms {
+            scale: [1.0, 1.0],
+            alpha: 0.0,
+            _padding: 0.0,
+        };
+        let uniform_buffer = create_uniform_buffer(device, &uniforms, "Death Fade Uniform Buffer");
+
+        let uniform_bind_group_layout = BindGroupLayoutBuilder::new(device)
+            .with_label("Death Fade Uniform Bind Group Layout")
+            .with_uniform_buffer(0, wgpu::ShaderStages::VERTEX_FRAGMENT)
+            .build();
+        let scene = SceneTarget::new(device, "death capture", surface_config, gpu_memory)
+            .with_usage(wgpu::TextureUsages::COPY_SRC);
+        let frozen = SceneTarget::new(device, "death fade", surface_config, gpu_memory)
+            .with_usage(wgpu::TextureUsages::COPY_DST);
+
+        let uniform_bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            layout: &uniform_bind_group_layout,
+            entries: &[wgpu::BindGroupEntry {
+                binding: 0,
+                resource: uniform_buffer.as_entire_binding(),
+            }],
+            label: Some("Death Fade Uniform Bind Group"),
+        });
+
+        let pipeline = PipelineBuilder::new(device, surface_config.format)
+            .with_label("Death Fade Pipeline")
+            .with_shader(include_str!("../shaders/death_fade.wgsl"))
+            .with_bind_group_layout(&uniform_bind_group_layout)
+            .with_bind_group_layout(frozen.bind_group_layout())
+            .with_alpha_blending()
+            .with_no_culling()
+            .build();
+
+        Self {
+            pipeline,
+            uniform_buffer,
+            uniform_bind_group,
+            scene,
+            frozen,
+            fade: DeathFade::default(),
+            captured: false,
+            captured_this_frame: false,
+        }
+    }
+
+    /// Matches a new surface size.
+    ///
+    /// Only the texture the scene is captured through follows the surface; the frozen copy
+    /// keeps its size and is letterboxed instead.
+    ///
+    /// # Arguments
+    /// * `width` - New surface width in pixels
+    /// * `height` - New surface height in pixels
+    pub fn resize(&mut self, width: u32, height: u32) {
+        self.scene.resize(width, height);
+    }
+
+    /// Advances the fade by one frame, freeing the textures once they are no longer needed.
+    ///
+    /// # Arguments
+    /// * `on_game_over` - Whether the game over screen is showing this frame
+    /// * `delta_time` - Seconds since the last frame
+    pub fn update(&mut self, on_game_over: bool, delta_time: f32) {
+        self.fade.advance(on_game_over, delta_time);
+        self.captured_this_frame = false;
+        if self.captured {
+            // The copy has been submitted, so the texture it was taken from can go
+            self.scene.release();
+        }
+        if !on_game_over && self.captured {
+            self.frozen.release();
+            self.captured = false;
+        }
+    }
+
+    /// Returns the seconds since the game over screen was entered, 0.0 away from it.
+    pub fn elapsed(&self) -> f32 {
+        self.fade.elapsed()
+    }
+
+    /// Returns whether the live scene still has to be drawn this frame.
+    pub fn draws_live_scene(&self) -> bool {
+        !(self.captured && self.fade.is_complete())
+    }
+
+    /// Returns the texture to draw this frame's scene into when it is to be captured.
+    ///
+    /// # Arguments
+    /// * `device` - WebGPU device for creating the textures
+    ///
+    /// # Returns
+    /// A view of the texture, or `None` if the scene isn't captured this frame or the
+    /// textures can't be allocated, in which case the scene should be drawn to the surface
+    pub fn capture_view(&mut self, device: &wgpu::Device) -> Option<wgpu::TextureView> {
+        if !self.fade.is_active() || self.captured {
+            return None;
+        }
+        let (width, height) = self.scene.size();
+        self.frozen.resize(width, height);
+        self.frozen.view(device)?;
+        self.scene.view(device)
+    }
+
+    /// Copies the scene drawn into [`DeathFadeRenderer::capture_view`] into the frozen copy.
+    ///
+    /// # Arguments
+    /// * `encoder` - Command encoder to record the copy into
+    pub fn capture(&mut self, encoder: &mut wgpu::CommandEncoder) {
+        let (Some(scene), Some(frozen)) = (self.scene.texture(), self.frozen.texture()) else {
+            return;
+        };
+        encoder.copy_texture_to_texture(
+            scene.as_image_copy(),
+            frozen.as_image_copy(),
+            scene.size(),
+        );
+        self.captured = true;
+        self.captured_this_frame = true;
+    }
+
+    /// Draws the frozen copy onto the surface at the fade's current opacity.
+    ///
+    /// Once the fade is complete the pass clears the surface first, leaving black bars
+    /// around a copy that doesn't fill it. Does nothing before the copy has been taken.
+    ///
+    /// # Arguments
+    /// * `queue` - WebGPU queue for uploading uniforms
+    /// * `encoder` - Command encoder to record the pass into
+    /// * `surface_view` - The surface to draw the copy onto
+    /// * `surface_size` - Size of the surface in pixels
+    pub fn render(
+        &self,
+        queue: &wgpu::Queue,
+        encoder: &mut wgpu::CommandEncoder,
+        surface_view: &wgpu::TextureView,
+        surface_size: (u32, u32),
+    ) {
+        if !self.captured {
+            return;
+        }
+        let Some(frozen) = self.frozen.bind_group() else {
+            return;
+        };
+        // On the frame it was taken the copy stands in for the live scene
+        let covers_surface = self.captured_this_frame || !self.draws_live_scene();
+        let uniforms = DeathFadeUniforms {
+            scale: letterbox_scale(self.frozen.size(), surface_size),
+            alpha: if covers_surface {
+                1.0
+            } else {
+                self.fade.alpha()
+            },
+            _padding: 0.0,
+        };
+        queue.write_buffer(&self.uniform_buffer, 0, bytemuck::bytes_of(&uniforms));
+
+        let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some("Death Fade Pass"),
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view: surface_view,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: if covers_surface {
+                        wgpu::LoadOp::Clear(wgpu::Color::BLACK)
+                    } else {
+                        wgpu::LoadOp::Load
+                    },
+                    store: wgpu::StoreOp::Store,
+                },
+            })],
+            depth_stencil_attachment: None,
+            occlusion_query_set: None,
+            timestamp_writes: None,
+        });
+        pass.set_pipeline(&self.pipeline);
+        pass.set_bind_group(0, &self.uniform_bind_group, &[]);
+        pass.set_bind_group(1, frozen, &[]);
+        pass.draw(0..6, 0..1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::renderer::uniform_layout::assert_matches_shader;
+
+    #[test]
+    fn test_uniforms_match_the_shader_layout() {
+        assert_matches_shader::<DeathFadeUniforms>(include_str!("../shaders/death_fade.wgsl"));
+    }
+
+    #[test]
+    fn test_fade_eases_in_and_resets_off_the_game_over_screen() {
+        let mut fade = DeathFade::default();
+        fade.advance(false, 0.1);
+        assert!(!fade.is_active());
+
+        fade.advance(true, 0.1);
+        assert!(fade.is_active());
+        assert_eq!(fade.alpha(), 0.0);
+
+        fade.advance(true, FADE_SECONDS / 2.0);
+        assert!((fade.alpha() - 0.5).abs() < 1e-6);
+        assert!(!fade.is_complete());
+
+        fade.advance(true, FADE_SECONDS);
+        assert_eq!(fade.alpha(), 1.0);
+        assert!(fade.is_complete());
+
+        fade.advance(false, 0.1);
+        assert!(!fade.is_active());
+        assert!(!fade.is_complete());
+    }
+
+    #[test]
+    fn test_letterbox_keeps_the_copy_aspect_ratio() {
+        assert_eq!(letterbox_scale((1600, 900), (1600, 900)), [1.0, 1.0]);
+        // A wider window puts bars at the sides
+        assert_eq!(letterbox_scale((1600, 900), (3200, 900)), [0.5, 1.0]);
+        // A taller one puts them above and below
+        assert_eq!(letterbox_scale((1600, 900), (1600, 1800)), [1.0, 0.5]);
+        // Scaling the window evenly changes nothing
+        assert_eq!(letterbox_scale((1600, 900), (800, 450)), [1.0, 1.0]);
+    }
+}
@@ -22,9 +22,10 @@
 //!
 //! # Usage
 //!
-//! ```rust
-//! // Enable debug rendering
-//! debug_renderer.debug_render_bounding_boxes = true;
+//! ```rust,ignore
+//! // Enable debug rendering, then move the boxes with the breathing walls
+//! debug_renderer.cycle_bounding_boxes();
+//! debug_renderer.cycle_bounding_boxes();
 //!
 //! // Update debug vertices when collision system changes
 //! debug_renderer.update_debug_vertices(device, collision_system);
@@ -72,13 +73,14 @@ pub const BOUNDING_BOX_MATERIAL: u32 = 2;
 ///
 /// # Example
 ///
-/// ```rust
+/// ```rust,ignore
 /// # use crate::renderer::game_renderer::debug::DebugRenderer;
 /// # let device: wgpu::Device = unimplemented!();
 /// # let collision_system: CollisionSystem = unimplemented!();
 ///
 /// let mut debug_renderer = DebugRenderer {
 ///     debug_render_bounding_boxes: true,
+///     breathe_bounding_boxes: false,
 ///     debug_vertex_buffer: None,
 ///     debug_vertex_count: 0,
 /// };
@@ -98,6 +100,13 @@ pub struct DebugRenderer {
     /// This field defaults to `false` to avoid performance impact in release builds.
     pub debug_render_bounding_boxes: bool,
 
+    /// Whether the bounding boxes move with the breathing walls.
+    ///
+    /// Boxes always show the static collision faces the player bumps into. When `true`,
+    /// the shader pushes them along with the drawn walls instead, so switching this on
+    /// and off shows how far the visible walls have drifted from their collision faces.
+    pub breathe_bounding_boxes: bool,
+
     /// Vertex buffer for the debug renderer.
     ///
     /// Contains the vertex data for all debug elements (bounding boxes, etc.).
@@ -118,6 +127,23 @@ pub struct DebugRenderer {
 }
 
 impl DebugRenderer {
+    /// Steps the bounding box overlay to its next mode.
+    ///
+    /// Cycles from hidden, to boxes at the collision faces, to boxes moving with the
+    /// breathing walls, and back to hidden.
+    pub fn cycle_bounding_boxes(&mut self) {
+        let (shown, breathing) = match (
+            self.debug_render_bounding_boxes,
+            self.breathe_bounding_boxes,
+        ) {
+            (false, _) => (true, false),
+            (true, false) => (true, true),
+            (true, true) => (false, false),
+        };
+        self.debug_render_bounding_boxes = shown;
+        self.breathe_bounding_boxes = breathing;
+    }
+
     /// Updates the debug vertex buffer with current collision system data.
     ///
     /// This method regenerates the vertex buffer containing all debug elements
@@ -144,7 +170,7 @@ impl DebugRenderer {
     ///
     /// # Example
     ///
-    /// ```rust
+    /// ```rust,ignore
     /// # use crate::renderer::game_renderer::debug::DebugRenderer;
     /// # let mut debug_renderer: DebugRenderer = unimplemented!();
     /// # let device: wgpu::Device = unimplemented!();
@@ -200,6 +226,7 @@ impl DebugRenderer {
 /// - `color`: RGBA color values (8-bit per channel)
 /// - `material`: Material ID for shader routing
 /// - `tex_coords`: Texture coordinates (unused for debug, set to `[0.0, 0.0]`)
+/// - `normal`: Breathing axis, left at zero; callers that know the face fill it in
 ///
 /// # Face Order
 ///
@@ -221,7 +248,7 @@ impl DebugRenderer {
 ///
 /// # Example
 ///
-/// ```rust
+/// ```rust,ignore
 /// # use crate::game::collision::AABB;
 /// # use crate::renderer::game_renderer::debug::create_aabb_box_vertices;
 /// # use crate::math::vec::Vec3;
@@ -248,36 +275,42 @@ pub fn create_aabb_box_vertices(aabb: &AABB) -> Vec<Vertex> {
             color,
             material: BOUNDING_BOX_MATERIAL,
             tex_coords: [0.0, 0.0], // Debug doesn't use texture coordinates
+            normal: [0.0, 0.0, 0.0],
         },
         Vertex {
             position: [max[0], min[1], max[2]],
             color,
             material: BOUNDING_BOX_MATERIAL,
             tex_coords: [0.0, 0.0],
+            normal: [0.0, 0.0, 0.0],
         },
         Vertex {
             position: [max[0], max[1], max[2]],
             color,
             material: BOUNDING_BOX_MATERIAL,
             tex_coords: [0.0, 0.0],
+            normal: [0.0, 0.0, 0.0],
         },
         Vertex {
             position: [min[0], min[1], max[2]],
             color,
             material: BOUNDING_BOX_MATERIAL,
             tex_coords: [0.0, 0.0],
+            normal: [0.0, 0.0, 0.0],
         },
         Vertex {
             position: [max[0], max[1], max[2]],
             color,
             material: BOUNDING_BOX_MATERIAL,
             tex_coords: [0.0, 0.0],
+            normal: [0.0, 0.0, 0.0],
         },
         Vertex {
             position: [min[0], max[1], max[2]],
             color,
             material: BOUNDING_BOX_MATERIAL,
             tex_coords: [0.0, 0.0],
+            normal: [0.0, 0.0, 0.0],
         },
     ]);
 
@@ -288,36 +321,42 @@ pub fn create_aabb_box_vertices(aabb: &AABB) -> Vec<Vertex> {
             color,
             material: BOUNDING_BOX_MATERIAL,
             tex_coords: [0.0, 0.0],
+            normal: [0.0, 0.0, 0.0],
         },
         Vertex {
             position: [min[0], min[1], min[2]],
             color,
             material: BOUNDING_BOX_MATERIAL,
             tex_coords: [0.0, 0.0],
+            normal: [0.0, 0.0, 0.0],
         },
         Vertex {
             position: [min[0], max[1], min[2]],
             color,
             material: BOUNDING_BOX_MATERIAL,
             tex_coords: [0.0, 0.0],
+            normal: [0.0, 0.0, 0.0],
         },
         Vertex {
             position: [max[0], min[1], min[2]],
             color,
             material: BOUNDING_BOX_MATERIAL,
             tex_coords: [0.0, 0.0],
+            normal: [0.0, 0.0, 0.0],
         },
         Vertex {
             position: [min[0], max[1], min[2]],
             color,
             material: BOUNDING_BOX_MATERIAL,
             tex_coords: [0.0, 0.0],
+            normal: [0.0, 0.0, 0.0],
         },
         Vertex {
             position: [max[0], max[1], min[2]],
             color,
             material: BOUNDING_BOX_MATERIAL,
             tex_coords: [0.0, 0.0],
+            normal: [0.0, 0.0, 0.0],
         },
     ]);
 
@@ -328,36 +367,42 @@ pub fn create_aabb_box_vertices(aabb: &AABB) -> Vec<Vertex> {
             color,
             material: BOUNDING_BOX_MATERIAL,
             tex_coords: [0.0, 0.0],
+            normal: [0.0, 0.0, 0.0],
         },
         Vertex {
             position: [max[0], max[1], min[2]],
             color,
             material: BOUNDING_BOX_MATERIAL,
             tex_coords: [0.0, 0.0],
+            normal: [0.0, 0.0, 0.0],
         },
         Vertex {
             position: [max[0], max[1], max[2]],
             color,
             material: BOUNDING_BOX_MATERIAL,
             tex_coords: [0.0, 0.0],
+            normal: [0.0, 0.0, 0.0],
         },
         Vertex {
             position: [max[0], min[1], min[2]],
             color,
             material: BOUNDING_BOX_MATERIAL,
             tex_coords: [0.0, 0.0],
+            normal: [0.0, 0.0, 0.0],
         },
         Vertex {
             position: [max[0], max[1], max[2]],
             color,
             material: BOUNDING_BOX_MATERIAL,
             tex_coords: [0.0, 0.0],
+            normal: [0.0, 0.0, 0.0],
         },
         Vertex {
             position: [max[0], min[1], max[2]],
             color,
             material: BOUNDING_BOX_MATERIAL,
             tex_coords: [0.0, 0.0],
+            normal: [0.0, 0.0, 0.0],
         },
     ]);
 
@@ -368,36 +413,42 @@ pub fn create_aabb_box_vertices(aabb: &AABB) -> Vec<Vertex> {
             color,
             material: BOUNDING_BOX_MATERIAL,
             tex_coords: [0.0, 0.0],
+            normal: [0.0, 0.0, 0.0],
         },
         Vertex {
             position: [min[0], max[1], max[2]],
             color,
             material: BOUNDING_BOX_MATERIAL,
             tex_coords: [0.0, 0.0],
+            normal: [0.0, 0.0, 0.0],
         },
         Vertex {
             position: [min[0], max[1], min[2]],
             color,
             material: BOUNDING_BOX_MATERIAL,
             tex_coords: [0.0, 0.0],
+            normal: [0.0, 0.0, 0.0],
         },
         Vertex {
             position: [min[0], min[1], max[2]],
             color,
             material: BOUNDING_BOX_MATERIAL,
             tex_coords: [0.0, 0.0],
+            normal: [0.0, 0.0, 0.0],
         },
         Vertex {
             position: [min[0], max[1], min[2]],
             color,
             material: BOUNDING_BOX_MATERIAL,
             tex_coords: [0.0, 0.0],
+            normal: [0.0, 0.0, 0.0],
         },
         Vertex {
             position: [min[0], min[1], min[2]],
             color,
             material: BOUNDING_BOX_MATERIAL,
             tex_coords: [0.0, 0.0],
+            normal: [0.0, 0.0, 0.0],
         },
     ]);
 
@@ -408,36 +459,42 @@ pub fn create_aabb_box_vertices(aabb: &AABB) -> Vec<Vertex> {
             color,
             material: BOUNDING_BOX_MATERIAL,
             tex_coords: [0.0, 0.0],
+            normal: [0.0, 0.0, 0.0],
         },
         Vertex {
             position: [max[0], max[1], max[2]],
             color,
             material: BOUNDING_BOX_MATERIAL,
             tex_coords: [0.0, 0.0],
+            normal: [0.0, 0.0, 0.0],
         },
         Vertex {
             position: [max[0], max[1], min[2]],
             color,
             material: BOUNDING_BOX_MATERIAL,
             tex_coords: [0.0, 0.0],
+            normal: [0.0, 0.0, 0.0],
         },
         Vertex {
             position: [min[0], max[1], max[2]],
             color,
             material: BOUNDING_BOX_MATERIAL,
             tex_coords: [0.0, 0.0],
+            normal: [0.0, 0.0, 0.0],
         },
         Vertex {
             position: [max[0], max[1], min[2]],
             color,
             material: BOUNDING_BOX_MATERIAL,
             tex_coords: [0.0, 0.0],
+            normal: [0.0, 0.0, 0.0],
         },
         Vertex {
             position: [min[0], max[1], min[2]],
             color,
             material: BOUNDING_BOX_MATERIAL,
             tex_coords: [0.0, 0.0],
+            normal: [0.0, 0.0, 0.0],
         },
     ]);
 
@@ -448,36 +505,42 @@ pub fn create_aabb_box_vertices(aabb: &AABB) -> Vec<Vertex> {
             color,
             material: BOUNDING_BOX_MATERIAL,
             tex_coords: [0.0, 0.0],
+            normal: [0.0, 0.0, 0.0],
         },
         Vertex {
             position: [max[0], min[1], min[2]],
             color,
             material: BOUNDING_BOX_MATERIAL,
             tex_coords: [0.0, 0.0],
+            normal: [0.0, 0.0, 0.0],
         },
         Vertex {
             position: [max[0], min[1], max[2]],
             color,
             material: BOUNDING_BOX_MATERIAL,
             tex_coords: [0.0, 0.0],
+            normal: [0.0, 0.0, 0.0],
         },
         Vertex {
             position: [min[0], min[1], min[2]],
             color,
             material: BOUNDING_BOX_MATERIAL,
             tex_coords: [0.0, 0.0],
+            normal: [0.0, 0.0, 0.0],
         },
         Vertex {
             position: [max[0], min[1], max[2]],
             color,
             material: BOUNDING_BOX_MATERIAL,
             tex_coords: [0.0, 0.0],
+            normal: [0.0, 0.0, 0.0],
         },
         Vertex {
             position: [min[0], min[1], max[2]],
             color,
             material: BOUNDING_BOX_MATERIAL,
             tex_coords: [0.0, 0.0],
+            normal: [0.0, 0.0, 0.0],
         },
     ]);
 
@@ -521,7 +584,7 @@ pub fn create_aabb_box_vertices(aabb: &AABB) -> Vec<Vertex> {
 ///
 /// # Example
 ///
-/// ```rust
+/// ```rust,ignore
 /// # use crate::game::collision::BVH;
 /// # use crate::renderer::game_renderer::debug::collect_wall_face_debug_vertices;
 ///
@@ -573,7 +636,7 @@ pub fn collect_wall_face_debug_vertices(bvh: &BVH) -> Vec<Vertex> {
 ///
 /// # Example
 ///
-/// ```rust
+/// ```rust,ignore
 /// # use crate::game::collision::BVHNode;
 /// # use crate::renderer::game_renderer::debug::collect_wall_faces_recursive;
 ///
@@ -589,9 +652,14 @@ fn collect_wall_faces_recursive(node: &BVHNode, vertices: &mut Vec<Vertex>) {
             collect_wall_faces_recursive(right, vertices);
         }
         BVHNode::Leaf { faces, .. } => {
-            // Visualize only the actual wall faces
+            // Visualize only the actual wall faces, breathing along the same axis as the wall
             for face in faces {
-                vertices.extend(create_aabb_box_vertices(&face.aabb));
+                let normal = face.normal.map(f32::abs);
+                vertices.extend(
+                    create_aabb_box_vertices(&face.aabb)
+                        .into_iter()
+                        .map(|vertex| Vertex { normal, ..vertex }),
+                );
             }
         }
     }
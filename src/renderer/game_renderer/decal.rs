@@ -0,0 +1,220 @@
+//! Wall sign rendering module for the Mirador game.
+//!
+//! This module draws the arrows [`crate::game::signage`] paints on the walls near the entrance.
+//! Each sign is a small quad standing just in front of its wall face, with the arrow drawn
+//! procedurally in the shader. Like the click-to-move path, every sign is drawn with a single
+//! instanced draw call, alpha blended and depth tested without writing depth, so walls in front
+//! still hide them.
+
+use crate::game::GameState;
+use crate::game::signage::{MAX_SIGNPOSTS, Turn};
+use crate::renderer::pipeline_builder::{
+    BindGroupLayoutBuilder, PipelineBuilder, create_uniform_buffer,
+};
+use wgpu::{self, util::DeviceExt};
+
+/// Paint color as linear RGBA; a weathered off-white that stands out against the walls.
+const PAINT_COLOR: [f32; 4] = [0.85, 0.82, 0.7, 0.85];
+
+/// Uniform data structure for the decal shader.
+#[repr(C)]
+#[derive(Copy, Clone, Debug, bytemuck::Pod, bytemuck::Zeroable)]
+struct DecalUniforms {
+    /// View-projection matrix for transforming vertices to screen space
+    view_proj_matrix: [[f32; 4]; 4],
+    /// Paint color
+    color: [f32; 4],
+}
+
+/// Per-sign instance data.
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Default, bytemuck::Pod, bytemuck::Zeroable)]
+struct DecalInstance {
+    /// Sign center (x, y, z) followed by its size in world units
+    center_size: [f32; 4],
+    /// Wall normal (x, z), then 1.0 for a right arrow or -1.0 for a left one, then padding
+    normal_arrow: [f32; 4],
+}
+
+/// Renders the wall signs as instanced quads.
+pub struct DecalRenderer {
+    /// The render pipeline for sign rendering
+    pipeline: wgpu::RenderPipeline,
+    /// Vertex buffer containing the unit quad corners
+    vertex_buffer: wgpu::Buffer,
+    /// Instance buffer holding one entry per sign slot
+    instance_buffer: wgpu::Buffer,
+    /// Uniform buffer containing shader uniforms
+    uniform_buffer: wgpu::Buffer,
+    /// Bind group containing the uniforms
+    bind_group: wgpu::BindGroup,
+    /// Number of instances written by the last update
+    instance_count: u32,
+}
+
+impl DecalRenderer {
+    /// Creates a new decal renderer.
+    ///
+    /// # Arguments
+    ///
+    /// * `device` - WGPU device for creating GPU resources
+    /// * `surface_config` - Surface configuration for pipeline creation
+    /// * `sample_count` - Samples per pixel of the render target the 3D view is drawn into
+    ///
+    /// # Returns
+    ///
+    /// A new `DecalRenderer` with an empty instance buffer sized for [`MAX_SIGNPOSTS`].
+    pub fn new(
+        device: &wgpu::Device,
+        surface_config: &wgpu::SurfaceConfiguration,
+        sample_count: u32,
+    ) -> Self {
+        let uniforms = DecalUniforms {
+            view_proj_matrix: [[0.0; 4]; 4],
+            color: PAINT_COLOR,
+        };
+        let uniform_buffer = create_uniform_buffer(device, &uniforms, "Decal Uniform Buffer");
+
+        let bind_group_layout = BindGroupLayoutBuilder::new(device)
+            .with_label("Decal Bind Group Layout")
+            .with_uniform_buffer(0, wgpu::ShaderStages::VERTEX | wgpu::ShaderStages::FRAGMENT)
+            .build();
+
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            layout: &bind_group_layout,
+            entries: &[wgpu::BindGroupEntry {
+                binding: 0,
+                resource: uniform_buffer.as_entire_binding(),
+            }],
+            label: Some("Decal Bind Group"),
+        });
+
+        // Unit quad corners across the wall face (right, up)
+        let corners: &[f32] = &[
+            -0.5, -0.5, 0.5, -0.5, -0.5, 0.5, // Triangle 1
+            0.5, -0.5, 0.5, 0.5, -0.5, 0.5, // Triangle 2
+        ];
+        let vertex_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Decal Vertex Buffer"),
+            contents: bytemuck::cast_slice(corners),
+            usage: wgpu::BufferUsages::VERTEX,
+        });
+
+        let instance_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Decal Instance Buffer"),
+            contents: bytemuck::cast_slice(&[DecalInstance::default(); MAX_SIGNPOSTS]),
+            usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
+        });
+
+        let vertex_buffer_layout = wgpu::VertexBufferLayout {
+            array_stride: 2 * 4, // 2 floats * 4 bytes each
+            step_mode: wgpu::VertexStepMode::Vertex,
+            attributes: &[wgpu::VertexAttribute {
+                offset: 0,
+                shader_location: 0,
+                format: wgpu::VertexFormat::Float32x2, // corner (right, up)
+            }],
+        };
+        let instance_buffer_layout = wgpu::VertexBufferLayout {
+            array_stride: std::mem::size_of::<DecalInstance>() as wgpu::BufferAddress,
+            step_mode: wgpu::VertexStepMode::Instance,
+            attributes: &[
+                wgpu::VertexAttribute {
+                    offset: 0,
+                    shader_location: 1,
+                    format: wgpu::VertexFormat::Float32x4, // center + size
+                },
+                wgpu::VertexAttribute {
+                    offset: 4 * 4,
+                    shader_location: 2,
+                    format: wgpu::VertexFormat::Float32x4, // normal + arrow side
+                },
+            ],
+        };
+
+        let pipeline = PipelineBuilder::new(device, surface_config.format)
+            .with_label("Decal Pipeline")
+            .with_shader(include_str!("../shaders/decal.wgsl"))
+            .with_vertex_buffer(vertex_buffer_layout)
+            .with_vertex_buffer(instance_buffer_layout)
+            .with_bind_group_layout(&bind_group_layout)
+            .with_alpha_blending()
+            .with_no_culling()
+            .with_depth_stencil(wgpu::DepthStencilState {
+                depth_write_enabled: false,
+                depth_compare: wgpu::CompareFunction::LessEqual,
+                format: wgpu::TextureFormat::Depth24Plus,
+                stencil: wgpu::StencilState::default(),
+                bias: wgpu::DepthBiasState::default(),
+            })
+            .with_sample_count(sample_count)
+            .build();
+
+        Self {
+            pipeline,
+            vertex_buffer,
+            instance_buffer,
+            uniform_buffer,
+            bind_group,
+            instance_count: 0,
+        }
+    }
+
+    /// Uploads the level's signs and the view-projection matrix.
+    ///
+    /// # Arguments
+    ///
+    /// * `queue` - WGPU queue for uploading data
+    /// * `game_state` - Current game state containing the placed signs
+    /// * `view_proj_matrix` - Current view-projection matrix for rendering
+    pub fn update(
+        &mut self,
+        queue: &wgpu::Queue,
+        game_state: &GameState,
+        view_proj_matrix: [[f32; 4]; 4],
+    ) {
+        let count = game_state.signposts.len().min(MAX_SIGNPOSTS);
+
+        let mut instances = [DecalInstance::default(); MAX_SIGNPOSTS];
+        for (instance, decal) in instances.iter_mut().zip(&game_state.signposts[..count]) {
+            let [x, y, z] = decal.center;
+            let side = if decal.arrow == Turn::Left { -1.0 } else { 1.0 };
+            instance.center_size = [x, y, z, decal.size];
+            instance.normal_arrow = [decal.normal[0], decal.normal[2], side, 0.0];
+        }
+        self.instance_count = count as u32;
+
+        if self.instance_count > 0 {
+            queue.write_buffer(
+                &self.instance_buffer,
+                0,
+                bytemuck::cast_slice(&instances[..count]),
+            );
+        }
+
+        let uniforms = DecalUniforms {
+            view_proj_matrix,
+            color: PAINT_COLOR,
+        };
+        queue.write_buffer(&self.uniform_buffer, 0, bytemuck::cast_slice(&[uniforms]));
+    }
+
+    /// Renders the signs to the specified render pass.
+    ///
+    /// Does nothing when the level has no signs.
+    ///
+    /// # Arguments
+    ///
+    /// * `render_pass` - The render pass to draw to
+    pub fn render(&self, render_pass: &mut wgpu::RenderPass) {
+        if self.instance_count == 0 {
+            return;
+        }
+
+        render_pass.set_pipeline(&self.pipeline);
+        render_pass.set_vertex_buffer(0, self.vertex_buffer.slice(..));
+        render_pass.set_vertex_buffer(1, self.instance_buffer.slice(..));
+        render_pass.set_bind_group(0, &self.bind_group, &[]);
+        render_pass.draw(0..6, 0..self.instance_count);
+    }
+}
@@ -3,20 +3,32 @@
 //! This module handles the rendering of enemy entities in the game world.
 //! It provides billboard-based rendering with smooth rotation towards the player,
 //! texture support, and depth-aware rendering.
+//!
+//! The enemy's shape and animation come from the skins in [`super::enemy_skin`]. Every skin
+//! is uploaded once at startup into its own vertex and index buffers; switching skins only
+//! changes which buffers are bound, so the pipeline is never rebuilt.
+//!
+//! Each enemy that can hunt the player at once, up to [`MAX_ENEMIES`], gets its own uniform
+//! buffer, bind group and skin choice, so the lead and its pack are drawn with one pipeline.
 
+use crate::assets;
 use crate::game::GameState;
 use crate::game::enemy::Enemy;
+use crate::game::levels::MAX_ENEMIES;
+use crate::renderer::game_renderer::enemy_skin::{
+    EnemyAnimation, EnemyMesh, EnemySkin, EnemySkinId,
+};
 use crate::renderer::pipeline_builder::{
     BindGroupLayoutBuilder, PipelineBuilder, create_uniform_buffer,
 };
-use wgpu::{self, util::DeviceExt};
-use crate::assets;
 use image;
+use std::collections::HashMap;
+use wgpu::{self, util::DeviceExt};
 
 /// Uniform data structure for enemy rendering shader.
 ///
 /// Contains view-projection matrix, enemy position, size, player position,
-/// and padding for proper memory alignment.
+/// the skin's animation state, and padding for proper memory alignment.
 #[repr(C)]
 #[derive(Copy, Clone, Debug, bytemuck::Pod, bytemuck::Zeroable)]
 struct EnemyUniforms {
@@ -28,8 +40,38 @@ struct EnemyUniforms {
     enemy_size: f32,
     /// Current position of the player in world space (x, y, z)
     player_position: [f32; 3],
+    /// Vertical bob offset in units of the enemy's size
+    bob_offset: f32,
+    /// Linear RGBA tint multiplied with the sprite
+    tint: [f32; 4],
+    /// Roll around the axis facing the player, in degrees
+    roll: f32,
     /// Padding for proper memory alignment
-    _padding: f32,
+    _padding: [f32; 3],
+}
+
+/// GPU buffers and animation for one enemy skin.
+struct SkinBuffers {
+    /// Vertex buffer in the enemy pipeline's position + tex_coords layout
+    vertex_buffer: wgpu::Buffer,
+    /// Triangle list index buffer
+    index_buffer: wgpu::Buffer,
+    /// Number of indices to draw
+    index_count: u32,
+    /// Animation applied while the skin is shown
+    animation: EnemyAnimation,
+}
+
+/// The uniforms and animation state of one drawn enemy.
+struct EnemySprite {
+    /// Uniform buffer containing shader uniforms
+    uniform_buffer: wgpu::Buffer,
+    /// Bind group containing uniforms, texture, and sampler
+    bind_group: wgpu::BindGroup,
+    /// The skin drawn this frame
+    current_skin: EnemySkinId,
+    /// Current smoothed rotation angle in radians
+    smoothed_rotation: f32,
 }
 
 /// Renders enemy entities as billboard sprites that face the player.
@@ -40,15 +82,15 @@ struct EnemyUniforms {
 pub struct EnemyRenderer {
     /// The render pipeline for enemy rendering
     pipeline: wgpu::RenderPipeline,
-    /// Vertex buffer containing billboard quad vertices
-    vertex_buffer: wgpu::Buffer,
-    /// Uniform buffer containing shader uniforms
-    uniform_buffer: wgpu::Buffer,
-    /// Bind group containing uniforms, texture, and sampler
-    bind_group: wgpu::BindGroup,
+    /// Uploaded mesh and animation for every skin
+    skins: HashMap<EnemySkinId, SkinBuffers>,
+    /// Animation time in seconds, used to drive skin animations
+    time: f32,
+    /// One sprite per enemy that can hunt at once, the lead first
+    sprites: Vec<EnemySprite>,
+    /// Number of enemies drawn this frame
+    drawn: usize,
 
-    /// Current smoothed rotation angle in radians
-    smoothed_rotation: f32,
     /// Smoothing factor for rotation interpolation (0.0 = very smooth, 1.0 = instant)
     smoothing_factor: f32,
 }
@@ -62,6 +104,7 @@ impl EnemyRenderer {
     /// * `device` - WGPU device for creating GPU resources
     /// * `queue` - WGPU queue for uploading data to GPU
     /// * `surface_config` - Surface configuration for pipeline creation
+    /// * `sample_count` - Samples per pixel of the render target the 3D view is drawn into
     ///
     /// # Returns
     ///
@@ -71,6 +114,7 @@ impl EnemyRenderer {
         device: &wgpu::Device,
         queue: &wgpu::Queue,
         surface_config: &wgpu::SurfaceConfiguration,
+        sample_count: u32,
     ) -> Self {
         // Load jeffree texture
         let jeffree_texture = Self::load_slime_texture(device, queue);
@@ -80,11 +124,12 @@ impl EnemyRenderer {
             enemy_position: enemy.pathfinder.position,
             enemy_size: enemy.size,
             player_position: [0.0; 3],
-            _padding: 0.0,
+            bob_offset: 0.0,
+            tint: [1.0; 4],
+            roll: 0.0,
+            _padding: [0.0; 3],
         };
 
-        let uniform_buffer = create_uniform_buffer(device, &uniforms, "Enemy Uniform Buffer");
-
         // Create bind group layout for texture + sampler + uniforms
         let bind_group_layout = BindGroupLayoutBuilder::new(device)
             .with_label("Enemy Bind Group Layout")
@@ -104,27 +149,39 @@ impl EnemyRenderer {
             ..Default::default()
         });
 
-        // Create bind group for jeffree texture
+        // Create one bind group per enemy, all sharing the jeffree texture
         let jeffree_texture_view =
             jeffree_texture.create_view(&wgpu::TextureViewDescriptor::default());
-        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
-            layout: &bind_group_layout,
-            entries: &[
-                wgpu::BindGroupEntry {
-                    binding: 0,
-                    resource: uniform_buffer.as_entire_binding(),
-                },
-                wgpu::BindGroupEntry {
-                    binding: 1,
-                    resource: wgpu::BindingResource::TextureView(&jeffree_texture_view),
-                },
-                wgpu::BindGroupEntry {
-                    binding: 2,
-                    resource: wgpu::BindingResource::Sampler(&sampler),
-                },
-            ],
-            label: Some("Enemy Bind Group"),
-        });
+        let sprites = (0..MAX_ENEMIES)
+            .map(|_| {
+                let uniform_buffer =
+                    create_uniform_buffer(device, &uniforms, "Enemy Uniform Buffer");
+                let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+                    layout: &bind_group_layout,
+                    entries: &[
+                        wgpu::BindGroupEntry {
+                            binding: 0,
+                            resource: uniform_buffer.as_entire_binding(),
+                        },
+                        wgpu::BindGroupEntry {
+                            binding: 1,
+                            resource: wgpu::BindingResource::TextureView(&jeffree_texture_view),
+                        },
+                        wgpu::BindGroupEntry {
+                            binding: 2,
+                            resource: wgpu::BindingResource::Sampler(&sampler),
+                        },
+                    ],
+                    label: Some("Enemy Bind Group"),
+                });
+                EnemySprite {
+                    uniform_buffer,
+                    bind_group,
+                    current_skin: EnemySkinId::Slime,
+                    smoothed_rotation: 0.0,
+                }
+            })
+            .collect();
 
         // Create vertex buffer layout for position + tex_coords
         let vertex_buffer_layout = wgpu::VertexBufferLayout {
@@ -157,16 +214,26 @@ impl EnemyRenderer {
                 stencil: wgpu::StencilState::default(),
                 bias: wgpu::DepthBiasState::default(),
             })
+            .with_sample_count(sample_count)
             .build();
 
-        let vertex_buffer = Self::create_billboard_vertices(device);
+        let skins = EnemySkinId::ALL
+            .into_iter()
+            .map(|id| {
+                let skin = EnemySkin::load(id);
+                (
+                    id,
+                    Self::upload_skin(device, id, &skin.mesh, skin.animation),
+                )
+            })
+            .collect();
 
         Self {
             pipeline,
-            vertex_buffer,
-            uniform_buffer,
-            bind_group,
-            smoothed_rotation: 0.0,
+            skins,
+            time: 0.0,
+            sprites,
+            drawn: 1,
             smoothing_factor: 0.85, // Smooth rotation
         }
     }
@@ -236,45 +303,107 @@ impl EnemyRenderer {
         texture
     }
 
-    /// Creates vertex buffer containing billboard quad vertices.
-    ///
-    /// Creates a quad centered at the origin that will be transformed by the shader
-    /// to create a billboard effect. The quad includes both position and texture coordinates.
+    /// Uploads a skin's mesh into its own vertex and index buffers.
     ///
     /// # Arguments
     ///
-    /// * `device` - WGPU device for creating the vertex buffer
+    /// * `device` - WGPU device for creating the buffers
+    /// * `id` - The skin being uploaded, used for buffer labels
+    /// * `mesh` - The skin's mesh
+    /// * `animation` - The skin's animation
     ///
     /// # Returns
     ///
-    /// A WGPU buffer containing the billboard vertices.
-    fn create_billboard_vertices(device: &wgpu::Device) -> wgpu::Buffer {
-        // Create a quad centered at origin that will be positioned and rotated by the shader
-        // The quad is in local space and will be transformed to world space
-        let vertices: &[f32] = &[
-            // Position (x, y, z)    // Texture coords (u, v)
-            // Triangle 1
-            -0.5, -0.5, 0.0, 0.0, 1.0, // Bottom-left
-            0.5, -0.5, 0.0, 1.0, 1.0, // Bottom-right
-            -0.5, 0.5, 0.0, 0.0, 0.0, // Top-left
-            // Triangle 2
-            0.5, -0.5, 0.0, 1.0, 1.0, // Bottom-right
-            0.5, 0.5, 0.0, 1.0, 0.0, // Top-right
-            -0.5, 0.5, 0.0, 0.0, 0.0, // Top-left
-        ];
-
-        device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
-            label: Some("Enemy Billboard Vertex Buffer"),
-            contents: bytemuck::cast_slice(vertices),
+    /// The skin's GPU buffers.
+    fn upload_skin(
+        device: &wgpu::Device,
+        id: EnemySkinId,
+        mesh: &EnemyMesh,
+        animation: EnemyAnimation,
+    ) -> SkinBuffers {
+        let vertex_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some(&format!("Enemy {} Vertex Buffer", id.name())),
+            contents: bytemuck::cast_slice(&mesh.vertices),
             usage: wgpu::BufferUsages::VERTEX,
-        })
+        });
+        let index_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some(&format!("Enemy {} Index Buffer", id.name())),
+            contents: bytemuck::cast_slice(&mesh.indices),
+            usage: wgpu::BufferUsages::INDEX,
+        });
+
+        SkinBuffers {
+            vertex_buffer,
+            index_buffer,
+            index_count: mesh.indices.len() as u32,
+            animation,
+        }
     }
 
-    /// Updates enemy position and rotation to face the player.
+    /// Replaces a skin's mesh at runtime without rebuilding the pipeline.
+    ///
+    /// # Arguments
+    ///
+    /// * `device` - WGPU device for creating the new buffers
+    /// * `id` - The skin whose mesh is replaced
+    /// * `mesh` - The new mesh, in the same vertex layout
+    pub fn set_skin_mesh(&mut self, device: &wgpu::Device, id: EnemySkinId, mesh: &EnemyMesh) {
+        let animation = self
+            .skins
+            .remove(&id)
+            .map(|skin| skin.animation)
+            .unwrap_or_default();
+        self.skins
+            .insert(id, Self::upload_skin(device, id, mesh, animation));
+    }
+
+    /// Gets the skin the lead enemy is drawn with this frame.
+    pub fn current_skin(&self) -> EnemySkinId {
+        self.sprites[0].current_skin
+    }
+
+    /// Advances every enemy's rotation and skin animation by one frame.
+    ///
+    /// Calculates the direction from each enemy to the player and smoothly interpolates the
+    /// enemy's rotation to face that direction, and picks the skin to draw. Called once per
+    /// frame before rendering.
+    ///
+    /// # Arguments
     ///
-    /// Calculates the direction to the player and smoothly interpolates the enemy's
-    /// rotation to face that direction. Updates the uniform buffer with current
-    /// game state data.
+    /// * `game_state` - Current game state containing player and enemy positions
+    /// * `time` - Animation time in seconds
+    pub fn animate(&mut self, game_state: &GameState, time: f32) {
+        let player_position = game_state.player.position;
+        self.drawn = 0;
+        for (sprite, enemy) in self.sprites.iter_mut().zip(game_state.enemies()) {
+            // Calculate rotation to face player
+            let dx = player_position[0] - enemy.pathfinder.position[0];
+            let dz = player_position[2] - enemy.pathfinder.position[2];
+
+            // Calculate target rotation using the same coordinate system as your compass
+            // Your compass uses dx.atan2(dz) pattern, so use that here
+            let target_rotation = dx.atan2(dz);
+
+            // Smooth rotation interpolation
+            let mut rotation_diff = target_rotation - sprite.smoothed_rotation;
+
+            // Wrap to shortest path
+            if rotation_diff > std::f32::consts::PI {
+                rotation_diff -= 2.0 * std::f32::consts::PI;
+            } else if rotation_diff < -std::f32::consts::PI {
+                rotation_diff += 2.0 * std::f32::consts::PI;
+            }
+
+            sprite.smoothed_rotation += rotation_diff * self.smoothing_factor;
+
+            // Show the aggressive skin while the enemy closes in
+            sprite.current_skin = EnemySkinId::select(enemy.is_pursuing(player_position));
+            self.drawn += 1;
+        }
+        self.time = time;
+    }
+
+    /// Uploads every enemy's uniforms for the current frame.
     ///
     /// # Arguments
     ///
@@ -282,65 +411,57 @@ impl EnemyRenderer {
     /// * `game_state` - Current game state containing player and enemy positions
     /// * `view_proj_matrix` - Current view-projection matrix for rendering
     pub fn update(
-        &mut self,
+        &self,
         queue: &wgpu::Queue,
         game_state: &GameState,
         view_proj_matrix: [[f32; 4]; 4],
     ) {
-        // Calculate rotation to face player
-        let dx = game_state.player.position[0] - game_state.enemy.pathfinder.position[0];
-        let dz = game_state.player.position[2] - game_state.enemy.pathfinder.position[2];
-
-        // Calculate target rotation using the same coordinate system as your compass
-        // Your compass uses dx.atan2(dz) pattern, so use that here
-        let target_rotation = dx.atan2(dz);
-
-        // Smooth rotation interpolation
-        let mut rotation_diff = target_rotation - self.smoothed_rotation;
-
-        // Wrap to shortest path
-        if rotation_diff > std::f32::consts::PI {
-            rotation_diff -= 2.0 * std::f32::consts::PI;
-        } else if rotation_diff < -std::f32::consts::PI {
-            rotation_diff += 2.0 * std::f32::consts::PI;
-        }
-
-        self.smoothed_rotation += rotation_diff * self.smoothing_factor;
+        let time = self.time;
+        for (sprite, enemy) in self.sprites.iter().zip(game_state.enemies()) {
+            let animation = &self.skins[&sprite.current_skin].animation;
 
-        // Update uniform buffer
-        let uniforms = EnemyUniforms {
-            view_proj_matrix,
-            enemy_position: game_state.enemy.pathfinder.position,
-            enemy_size: game_state.enemy.size,
-            player_position: game_state.player.position,
-            _padding: 0.0,
-        };
+            // Update uniform buffer
+            let uniforms = EnemyUniforms {
+                view_proj_matrix,
+                enemy_position: enemy.pathfinder.position,
+                enemy_size: enemy.size,
+                player_position: game_state.player.position,
+                bob_offset: animation.bob_offset(time),
+                tint: animation.pulse_color(time),
+                roll: animation.roll(time),
+                _padding: [0.0; 3],
+            };
 
-        queue.write_buffer(&self.uniform_buffer, 0, bytemuck::cast_slice(&[uniforms]));
+            queue.write_buffer(&sprite.uniform_buffer, 0, bytemuck::cast_slice(&[uniforms]));
+        }
     }
 
-    /// Renders the enemy to the specified render pass.
+    /// Renders the enemies to the specified render pass.
     ///
-    /// Sets up the render pipeline, vertex buffer, and bind group, then draws
-    /// the enemy billboard using 6 vertices (2 triangles).
+    /// Sets up the render pipeline, then binds each enemy's current skin buffers and bind
+    /// group and draws the skin's mesh.
     ///
     /// # Arguments
     ///
     /// * `render_pass` - The render pass to draw to
     pub fn render(&self, render_pass: &mut wgpu::RenderPass) {
         render_pass.set_pipeline(&self.pipeline);
-        render_pass.set_vertex_buffer(0, self.vertex_buffer.slice(..));
-        render_pass.set_bind_group(0, &self.bind_group, &[]);
-        render_pass.draw(0..6, 0..1);
+        for sprite in self.sprites.iter().take(self.drawn) {
+            let skin = &self.skins[&sprite.current_skin];
+            render_pass.set_vertex_buffer(0, skin.vertex_buffer.slice(..));
+            render_pass.set_index_buffer(skin.index_buffer.slice(..), wgpu::IndexFormat::Uint32);
+            render_pass.set_bind_group(0, &sprite.bind_group, &[]);
+            render_pass.draw_indexed(0..skin.index_count, 0, 0..1);
+        }
     }
 
-    /// Gets the current rotation angle of the enemy.
+    /// Gets the current rotation angle of the lead enemy.
     ///
     /// # Returns
     ///
     /// The current smoothed rotation angle in radians.
     pub fn get_rotation(&self) -> f32 {
-        self.smoothed_rotation
+        self.sprites[0].smoothed_rotation
     }
 
     /// Sets the smoothing factor for rotation interpolation.
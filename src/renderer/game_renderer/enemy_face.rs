@@ -0,0 +1,192 @@
+//! Enemy Face Renderer Module
+//!
+//! This module flashes the enemy's face over the whole screen when it catches the player.
+//! The slime texture is stretched to cover the surface and torn apart by a time-driven
+//! noise and distortion effect in the shader, on a flickering dark red backdrop.
+
+use crate::assets;
+use crate::assets::registry::decode_image_or_placeholder;
+use crate::renderer::gpu_memory::{
+    GpuMemoryTracker, TextureCategory, TrackedTexture, create_tracked_texture,
+};
+use crate::renderer::pipeline_builder::{
+    BindGroupLayoutBuilder, PipelineBuilder, create_uniform_buffer,
+};
+use wgpu;
+
+/// Uniform data passed to the enemy face shader.
+///
+/// # Memory Layout
+/// 16 bytes: four scalars.
+#[repr(C)]
+#[derive(Debug, Copy, Clone, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct EnemyFaceUniforms {
+    /// Seconds since the capture started, drives the distortion
+    pub time: f32,
+    /// Opacity of the whole overlay, from 0.0 to 1.0
+    pub intensity: f32,
+    /// Surface width divided by height
+    pub screen_aspect: f32,
+    /// Face texture width divided by height
+    pub image_aspect: f32,
+}
+
+/// Renders the full-screen enemy face.
+///
+/// Drawn with a procedural full-screen triangle, so it needs no vertex buffer.
+pub struct EnemyFaceRenderer {
+    /// The WebGPU render pipeline for the face
+    pub pipeline: wgpu::RenderPipeline,
+    /// GPU buffer containing the face uniforms
+    pub uniform_buffer: wgpu::Buffer,
+    /// Bind group for the uniforms, face texture, and sampler
+    pub bind_group: wgpu::BindGroup,
+    /// Face texture width divided by height
+    pub image_aspect: f32,
+    /// The face texture, kept alive for the bind group
+    _texture: TrackedTexture,
+}
+
+impl EnemyFaceRenderer {
+    /// Creates a new enemy face renderer and uploads the face texture.
+    ///
+    /// # Arguments
+    /// * `device` - WebGPU device for creating GPU resources
+    /// * `queue` - WebGPU queue for uploading the face texture
+    /// * `surface_config` - Surface configuration containing the target pixel format
+    /// * `gpu_memory` - Tracker the face texture is counted in
+    ///
+    /// # Returns
+    /// An `EnemyFaceRenderer` that draws nothing until its intensity is raised
+    pub fn new(
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        surface_config: &wgpu::SurfaceConfiguration,
+        gpu_memory: &GpuMemoryTracker,
+    ) -> Self {
+        let rgba = decode_image_or_placeholder("slime enemy", assets::SLIME_IMAGE);
+        let (width, height) = rgba.dimensions();
+        let texture_size = wgpu::Extent3d {
+            width,
+            height,
+            depth_or_array_layers: 1,
+        };
+
+        let texture = create_tracked_texture(
+            device,
+            gpu_memory,
+            TextureCategory::Materials,
+            &wgpu::TextureDescriptor {
+                label: Some("Enemy Face Texture"),
+                size: texture_size,
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: wgpu::TextureDimension::D2,
+                format: wgpu::TextureFormat::Rgba8UnormSrgb,
+                usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
+                view_formats: &[],
+            },
+        );
+
+        queue.write_texture(
+            wgpu::TexelCopyTextureInfo {
+                aspect: wgpu::TextureAspect::All,
+                texture: &texture,
+                mip_level: 0,
+                origin: wgpu::Origin3d::ZERO,
+            },
+            &rgba,
+            wgpu::TexelCopyBufferLayout {
+                offset: 0,
+                bytes_per_row: Some(4 * width),
+                rows_per_image: Some(height),
+            },
+            texture_size,
+        );
+
+        let texture_view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+        let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+            label: Some("Enemy Face Sampler"),
+            mag_filter: wgpu::FilterMode::Linear,
+            min_filter: wgpu::FilterMode::Linear,
+            ..Default::default()
+        });
+
+        let image_aspect = width as f32 / height.max(1) as f32;
+        let uniforms = EnemyFaceUniforms {
+            time: 0.0,
+            intensity: 0.0,
+            screen_aspect: 1.0,
+            image_aspect,
+        };
+        let uniform_buffer = create_uniform_buffer(device, &uniforms, "Enemy Face Uniform Buffer");
+
+        let bind_group_layout = BindGroupLayoutBuilder::new(device)
+            .with_label("Enemy Face Bind Group Layout")
+            .with_uniform_buffer(0, wgpu::ShaderStages::FRAGMENT)
+            .with_texture(1, wgpu::ShaderStages::FRAGMENT)
+            .with_sampler(2, wgpu::ShaderStages::FRAGMENT)
+            .build();
+
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            layout: &bind_group_layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: uniform_buffer.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: wgpu::BindingResource::TextureView(&texture_view),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 2,
+                    resource: wgpu::BindingResource::Sampler(&sampler),
+                },
+            ],
+            label: Some("Enemy Face Bind Group"),
+        });
+
+        let pipeline = PipelineBuilder::new(device, surface_config.format)
+            .with_label("Enemy Face Pipeline")
+            .with_shader(include_str!("../shaders/enemy_face.wgsl"))
+            .with_bind_group_layout(&bind_group_layout)
+            .with_alpha_blending()
+            .build();
+
+        Self {
+            pipeline,
+            uniform_buffer,
+            bind_group,
+            image_aspect,
+            _texture: texture,
+        }
+    }
+
+    /// Uploads this frame's uniforms.
+    ///
+    /// # Arguments
+    /// * `queue` - WebGPU command queue for GPU operations
+    /// * `time` - Seconds since the capture started
+    /// * `intensity` - Opacity of the whole overlay, from 0.0 to 1.0
+    /// * `screen_aspect` - Surface width divided by height
+    pub fn update(&self, queue: &wgpu::Queue, time: f32, intensity: f32, screen_aspect: f32) {
+        let uniforms = EnemyFaceUniforms {
+            time,
+            intensity: intensity.clamp(0.0, 1.0),
+            screen_aspect,
+            image_aspect: self.image_aspect,
+        };
+        queue.write_buffer(&self.uniform_buffer, 0, bytemuck::cast_slice(&[uniforms]));
+    }
+
+    /// Renders the face to the current render pass.
+    ///
+    /// # Arguments
+    /// * `render_pass` - Active render pass drawing over the finished frame
+    pub fn render(&self, render_pass: &mut wgpu::RenderPass) {
+        render_pass.set_pipeline(&self.pipeline);
+        render_pass.set_bind_group(0, &self.bind_group, &[]);
+        render_pass.draw(0..3, 0..1);
+    }
+}
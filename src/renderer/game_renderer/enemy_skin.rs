@@ -0,0 +1,400 @@
+//! Data-driven enemy appearance for the Mirador game.
+//!
+//! An enemy "skin" pairs a mesh with a small animation description, both embedded from
+//! `assets/enemies/`. Meshes are Wavefront OBJ files parsed by [`EnemyMesh::parse_obj`] into the
+//! enemy pipeline's vertex layout (position + texture coordinates), so swapping skins never
+//! rebuilds the pipeline. Animations are JSON files describing a bob, an in-plane roll, and a
+//! looping tint curve.
+//!
+//! A skin whose mesh or animation fails to load falls back to the built-in billboard quad or a
+//! still animation with a warning, so a broken asset never stops the enemy from being drawn.
+//!
+//! # Usage
+//!
+//! ```rust,ignore
+//! let skin = EnemySkin::load(EnemySkinId::select(enemy.is_pursuing(player.position)));
+//! let tint = skin.animation.pulse_color(elapsed_seconds);
+//! ```
+
+use crate::assets;
+use serde::Deserialize;
+use std::collections::HashMap;
+
+/// Identifies one of the embedded enemy skins.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EnemySkinId {
+    /// The calm slime billboard shown while the enemy wanders
+    Slime,
+    /// A spinning, red-pulsing star shown while the enemy closes in on the player
+    Hunter,
+}
+
+impl EnemySkinId {
+    /// All skins, in the order they are loaded.
+    pub const ALL: [EnemySkinId; 2] = [EnemySkinId::Slime, EnemySkinId::Hunter];
+
+    /// Returns the skin's id as used in asset names and warnings.
+    pub fn name(self) -> &'static str {
+        match self {
+            EnemySkinId::Slime => "slime",
+            EnemySkinId::Hunter => "hunter",
+        }
+    }
+
+    /// Picks the skin for the enemy's current alert state.
+    ///
+    /// # Arguments
+    /// * `pursuing` - Whether the player is within the enemy's pursuit distance
+    pub fn select(pursuing: bool) -> Self {
+        if pursuing {
+            EnemySkinId::Hunter
+        } else {
+            EnemySkinId::Slime
+        }
+    }
+
+    /// Returns the skin's embedded OBJ mesh and JSON animation sources.
+    fn sources(self) -> (&'static str, &'static str) {
+        match self {
+            EnemySkinId::Slime => (assets::SLIME_ENEMY_MESH, assets::SLIME_ENEMY_ANIMATION),
+            EnemySkinId::Hunter => (assets::HUNTER_ENEMY_MESH, assets::HUNTER_ENEMY_ANIMATION),
+        }
+    }
+}
+
+/// Indexed triangle mesh in the enemy pipeline's vertex layout.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EnemyMesh {
+    /// Vertices as `[x, y, z, u, v]`, in units of the enemy's size
+    pub vertices: Vec<[f32; 5]>,
+    /// Triangle list indices into `vertices`
+    pub indices: Vec<u32>,
+}
+
+impl EnemyMesh {
+    /// Returns the built-in billboard: a unit quad showing the whole sprite.
+    pub fn builtin() -> Self {
+        Self {
+            vertices: vec![
+                [-0.5, -0.5, 0.0, 0.0, 1.0], // Bottom-left
+                [0.5, -0.5, 0.0, 1.0, 1.0],  // Bottom-right
+                [0.5, 0.5, 0.0, 1.0, 0.0],   // Top-right
+                [-0.5, 0.5, 0.0, 0.0, 0.0],  // Top-left
+            ],
+            indices: vec![0, 1, 3, 1, 2, 3],
+        }
+    }
+
+    /// Parses a Wavefront OBJ mesh.
+    ///
+    /// Supports `v`, `vt`, and `f` statements (with `v`, `v/vt`, `v/vt/vn`, and `v//vn`
+    /// corners and negative indices); other statements are ignored. Polygons are
+    /// fan-triangulated. OBJ texture coordinates start at the bottom-left and are flipped to
+    /// match the sprite; corners without one get the sprite projected straight onto the XY
+    /// plane.
+    ///
+    /// # Arguments
+    /// * `source` - Contents of the OBJ file
+    ///
+    /// # Returns
+    /// The mesh, or an error naming the offending line
+    pub fn parse_obj(source: &str) -> Result<Self, String> {
+        let mut positions: Vec<[f32; 3]> = Vec::new();
+        let mut tex_coords: Vec<[f32; 2]> = Vec::new();
+        let mut corners: HashMap<(usize, Option<usize>), u32> = HashMap::new();
+        let mut mesh = Self {
+            vertices: Vec::new(),
+            indices: Vec::new(),
+        };
+
+        for (line_index, line) in source.lines().enumerate() {
+            let line_number = line_index + 1;
+            let mut parts = line.split_whitespace();
+            match parts.next() {
+                Some("v") => {
+                    let values = parse_floats(parts, 3, line_number)?;
+                    positions.push([values[0], values[1], values[2]]);
+                }
+                Some("vt") => {
+                    let values = parse_floats(parts, 2, line_number)?;
+                    tex_coords.push([values[0], 1.0 - values[1]]);
+                }
+                Some("f") => {
+                    let mut face = Vec::new();
+                    for corner in parts {
+                        let mut fields = corner.split('/');
+                        let position = resolve_index(fields.next(), positions.len(), line_number)?
+                            .ok_or_else(|| {
+                                format!("line {}: face corner has no position", line_number)
+                            })?;
+                        let tex_coord =
+                            resolve_index(fields.next(), tex_coords.len(), line_number)?;
+
+                        let index = *corners.entry((position, tex_coord)).or_insert_with(|| {
+                            let [x, y, z] = positions[position];
+                            let [u, v] = match tex_coord {
+                                Some(tex_coord) => tex_coords[tex_coord],
+                                None => [x + 0.5, 0.5 - y],
+                            };
+                            mesh.vertices.push([x, y, z, u, v]);
+                            (mesh.vertices.len() - 1) as u32
+                        });
+                        face.push(index);
+                    }
+                    if face.len() < 3 {
+                        return Err(format!(
+                            "line {}: face needs at least 3 corners",
+                            line_number
+                        ));
+                    }
+                    for i in 1..face.len() - 1 {
+                        mesh.indices
+                            .extend_from_slice(&[face[0], face[i], face[i + 1]]);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        if mesh.indices.is_empty() {
+            return Err("mesh has no faces".to_string());
+        }
+        Ok(mesh)
+    }
+}
+
+/// Parses the first `count` whitespace-separated floats of an OBJ statement.
+fn parse_floats<'a>(
+    parts: impl Iterator<Item = &'a str>,
+    count: usize,
+    line_number: usize,
+) -> Result<Vec<f32>, String> {
+    let values = parts
+        .take(count)
+        .map(|part| {
+            part.parse::<f32>()
+                .map_err(|e| format!("line {}: invalid number '{}': {}", line_number, part, e))
+        })
+        .collect::<Result<Vec<f32>, String>>()?;
+    if values.len() < count {
+        return Err(format!(
+            "line {}: expected {} numbers, found {}",
+            line_number,
+            count,
+            values.len()
+        ));
+    }
+    Ok(values)
+}
+
+/// Resolves a 1-based (or negative, relative) OBJ index to a 0-based one.
+///
+/// # Returns
+/// `None` for a missing or empty field, otherwise the index if it is in range
+fn resolve_index(
+    field: Option<&str>,
+    len: usize,
+    line_number: usize,
+) -> Result<Option<usize>, String> {
+    let Some(field) = field.filter(|field| !field.is_empty()) else {
+        return Ok(None);
+    };
+    let index: i64 = field
+        .parse()
+        .map_err(|e| format!("line {}: invalid index '{}': {}", line_number, field, e))?;
+    let resolved = if index < 0 {
+        len as i64 + index
+    } else {
+        index - 1
+    };
+    if resolved < 0 || resolved >= len as i64 {
+        return Err(format!(
+            "line {}: index {} is out of range",
+            line_number, index
+        ));
+    }
+    Ok(Some(resolved as usize))
+}
+
+/// Looping animation applied to an enemy skin.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct EnemyAnimation {
+    /// Height of the vertical bob, as a fraction of the enemy's size
+    pub bob_amplitude: f32,
+    /// Bobs per second
+    pub bob_speed: f32,
+    /// Degrees per second the mesh rolls around the axis facing the player
+    pub rotation_rate: f32,
+    /// Seconds for one pass through `pulse_colors`
+    pub pulse_period: f32,
+    /// Linear RGBA tints the sprite cycles through, multiplied with its texture
+    pub pulse_colors: Vec<[f32; 4]>,
+}
+
+impl Default for EnemyAnimation {
+    /// A still, untinted enemy.
+    fn default() -> Self {
+        Self {
+            bob_amplitude: 0.0,
+            bob_speed: 0.0,
+            rotation_rate: 0.0,
+            pulse_period: 1.0,
+            pulse_colors: vec![[1.0, 1.0, 1.0, 1.0]],
+        }
+    }
+}
+
+impl EnemyAnimation {
+    /// Returns the vertical bob offset in units of the enemy's size.
+    ///
+    /// # Arguments
+    /// * `time` - Seconds since the animation started
+    pub fn bob_offset(&self, time: f32) -> f32 {
+        self.bob_amplitude * (time * self.bob_speed * std::f32::consts::TAU).sin()
+    }
+
+    /// Returns the roll angle in degrees.
+    ///
+    /// # Arguments
+    /// * `time` - Seconds since the animation started
+    pub fn roll(&self, time: f32) -> f32 {
+        (time * self.rotation_rate) % 360.0
+    }
+
+    /// Samples the looping tint curve, blending linearly between neighbouring colors.
+    ///
+    /// # Arguments
+    /// * `time` - Seconds since the animation started
+    pub fn pulse_color(&self, time: f32) -> [f32; 4] {
+        let count = self.pulse_colors.len();
+        if count == 0 {
+            return [1.0; 4];
+        }
+        if count == 1 || self.pulse_period <= 0.0 {
+            return self.pulse_colors[0];
+        }
+
+        let position = (time / self.pulse_period).rem_euclid(1.0) * count as f32;
+        let from = (position.floor() as usize).min(count - 1);
+        let to = (from + 1) % count;
+        let t = position - from as f32;
+        let (a, b) = (self.pulse_colors[from], self.pulse_colors[to]);
+        std::array::from_fn(|i| a[i] + (b[i] - a[i]) * t)
+    }
+}
+
+/// A loaded enemy skin.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EnemySkin {
+    /// The skin's mesh
+    pub mesh: EnemyMesh,
+    /// The skin's animation
+    pub animation: EnemyAnimation,
+}
+
+impl EnemySkin {
+    /// Loads an embedded skin, falling back to the built-in shape for broken assets.
+    ///
+    /// # Arguments
+    /// * `id` - The skin to load
+    pub fn load(id: EnemySkinId) -> Self {
+        let (mesh, animation) = id.sources();
+        Self::from_sources(id.name(), mesh, animation)
+    }
+
+    /// Builds a skin from OBJ and JSON sources.
+    ///
+    /// A mesh that fails to parse is replaced by [`EnemyMesh::builtin`] and an animation that
+    /// fails to parse by [`EnemyAnimation::default`], each with a warning.
+    ///
+    /// # Arguments
+    /// * `name` - Skin name used in warnings
+    /// * `mesh_source` - Contents of the OBJ mesh
+    /// * `animation_source` - Contents of the JSON animation
+    pub fn from_sources(name: &str, mesh_source: &str, animation_source: &str) -> Self {
+        let mesh = EnemyMesh::parse_obj(mesh_source).unwrap_or_else(|e| {
+            eprintln!(
+                "Warning: failed to load enemy skin '{}' mesh: {}, using the built-in billboard",
+                name, e
+            );
+            EnemyMesh::builtin()
+        });
+        let animation = serde_json::from_str(animation_source).unwrap_or_else(|e| {
+            eprintln!(
+                "Warning: failed to load enemy skin '{}' animation: {}, using no animation",
+                name, e
+            );
+            EnemyAnimation::default()
+        });
+        Self { mesh, animation }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A quad and a triangle sharing an edge, with one corner reused with a different UV.
+    const TEST_OBJ: &str = "\
+# test mesh
+o test
+v -0.5 -0.5 0.0
+v 0.5 -0.5 0.0
+v 0.5 0.5 0.0
+v -0.5 0.5 0.0
+v 1.0 0.0 0.0
+vt 0.0 0.0
+vt 1.0 0.0
+vt 1.0 1.0
+vt 0.0 1.0
+vn 0.0 0.0 1.0
+f 1/1/1 2/2/1 3/3/1 4/4/1
+f 2 5 -3/3
+";
+
+    #[test]
+    fn test_obj_loader_counts() {
+        let mesh = EnemyMesh::parse_obj(TEST_OBJ).expect("test mesh parses");
+        // Four quad corners, plus vertex 2 without a UV and vertex 5
+        assert_eq!(mesh.vertices.len(), 6);
+        // Two triangles for the quad and one for the triangle
+        assert_eq!(mesh.indices.len(), 9);
+        assert!(
+            mesh.indices
+                .iter()
+                .all(|&i| (i as usize) < mesh.vertices.len())
+        );
+        // OBJ's bottom-left texture origin is flipped to the sprite's top-left
+        assert_eq!(mesh.vertices[0], [-0.5, -0.5, 0.0, 0.0, 1.0]);
+
+        for id in EnemySkinId::ALL {
+            let (mesh, _) = id.sources();
+            assert!(EnemyMesh::parse_obj(mesh).is_ok(), "{} mesh", id.name());
+        }
+    }
+
+    #[test]
+    fn test_broken_skin_falls_back_to_builtin() {
+        assert!(EnemyMesh::parse_obj("v 0 0 0\nf 1 2 3\n").is_err());
+        assert!(EnemyMesh::parse_obj("v 0 zero 0\n").is_err());
+        assert!(EnemyMesh::parse_obj("").is_err());
+
+        let skin = EnemySkin::from_sources("broken", "not a mesh", "{ not json");
+        assert_eq!(skin.mesh, EnemyMesh::builtin());
+        assert_eq!(skin.animation, EnemyAnimation::default());
+    }
+
+    #[test]
+    fn test_pulse_color_loops_through_keys() {
+        let animation = EnemyAnimation {
+            pulse_period: 2.0,
+            pulse_colors: vec![[1.0, 1.0, 1.0, 1.0], [1.0, 0.0, 0.0, 1.0]],
+            ..EnemyAnimation::default()
+        };
+        assert_eq!(animation.pulse_color(0.0), [1.0, 1.0, 1.0, 1.0]);
+        assert_eq!(animation.pulse_color(1.0), [1.0, 0.0, 0.0, 1.0]);
+        assert_eq!(animation.pulse_color(0.5), [1.0, 0.5, 0.5, 1.0]);
+        assert_eq!(animation.pulse_color(2.0), animation.pulse_color(0.0));
+    }
+}
@@ -13,7 +13,7 @@
 //!
 //! # Usage
 //!
-//! ```rust
+//! ```rust,ignore
 //! // Create game over renderer
 //! let game_over = GameOverRenderer::new(device, surface_config);
 //!
@@ -21,7 +21,7 @@
 //! game_over.update_time(queue, elapsed_time);
 //!
 //! // Render overlay (after game scene)
-//! game_over.render(&mut render_pass, window);
+//! game_over.render(&mut render_pass);
 //! ```
 //!
 //! # Shader Requirements
@@ -104,7 +104,7 @@ struct GameOverUniforms {
 ///
 /// # Example
 ///
-/// ```rust
+/// ```rust,ignore
 /// # use crate::renderer::game_renderer::game_over::GameOverRenderer;
 /// # let device: wgpu::Device = unimplemented!();
 /// # let surface_config: wgpu::SurfaceConfiguration = unimplemented!();
@@ -115,7 +115,7 @@ struct GameOverUniforms {
 /// // During game over state
 /// let elapsed = start_time.elapsed().as_secs_f32();
 /// game_over.update_time(&queue, elapsed);
-/// game_over.render(&mut render_pass, window);
+/// game_over.render(&mut render_pass);
 /// ```
 pub struct GameOverRenderer {
     /// WGPU render pipeline for game over overlay.
@@ -172,7 +172,7 @@ impl GameOverRenderer {
     ///
     /// # Example
     ///
-    /// ```rust,no_run
+    /// ```rust,ignore
     /// # use crate::renderer::maze_renderer::GameOverRenderer;
     /// # let device: wgpu::Device = unimplemented!();
     /// # let surface_config: wgpu::SurfaceConfiguration = unimplemented!();
@@ -237,7 +237,7 @@ impl GameOverRenderer {
     ///
     /// # Example
     ///
-    /// ```rust,no_run
+    /// ```rust,ignore
     /// # use crate::renderer::maze_renderer::GameOverRenderer;
     /// # let renderer: GameOverRenderer = unimplemented!();
     /// # let queue: wgpu::Queue = unimplemented!();
@@ -271,23 +271,21 @@ impl GameOverRenderer {
     /// # Parameters
     ///
     /// - `render_pass` - Active render pass to render into
-    /// - `window` - Window reference for potential window-specific adjustments
     ///
     /// # Example
     ///
-    /// ```rust,no_run
+    /// ```rust,ignore
     /// # use crate::renderer::maze_renderer::GameOverRenderer;
     /// # let renderer: GameOverRenderer = unimplemented!();
     /// # let mut render_pass: wgpu::RenderPass = unimplemented!();
-    /// # let window: &winit::window::Window = unimplemented!();
     ///
     /// // Render game scene first
     /// // ... render background, stars, game objects ...
     ///
     /// // Render game over overlay on top
-    /// renderer.render(&mut render_pass, window);
+    /// renderer.render(&mut render_pass);
     /// ```
-    pub fn render(&self, render_pass: &mut wgpu::RenderPass, _window: &winit::window::Window) {
+    pub fn render(&self, render_pass: &mut wgpu::RenderPass) {
         render_pass.set_pipeline(&self.pipeline);
         render_pass.set_bind_group(0, &self.bind_group, &[]);
         render_pass.set_vertex_buffer(0, self.vertex_buffer.slice(..));
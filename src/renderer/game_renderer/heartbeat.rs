@@ -0,0 +1,183 @@
+//! Heartbeat Renderer Module
+//!
+//! This module makes the screen panic in the level timer's last seconds: the finished 3D view
+//! bulges outward on every heartbeat and its colors split apart toward the edges. The effect
+//! needs to read the frame it distorts, so while it is active the scene is drawn into a
+//! [`SceneTarget`] the size of the surface instead of the surface itself, then drawn onto the
+//! surface through the distortion before the HUD goes on top.
+//!
+//! The strength and phase of the beat come from
+//! [`FrameAnimations`](crate::renderer::animation::FrameAnimations), which reads them off the
+//! level timer's clock so pausing freezes the pulse. When the texture can't be allocated the
+//! scene is drawn straight to the surface as usual, undistorted.
+
+use crate::renderer::gpu_memory::GpuMemoryTracker;
+use crate::renderer::pipeline_builder::{
+    BindGroupLayoutBuilder, PipelineBuilder, create_uniform_buffer,
+};
+use crate::renderer::scene_target::SceneTarget;
+use wgpu;
+
+/// Uniform data passed to the heartbeat shader.
+///
+/// # Memory Layout
+/// 16 bytes: three scalars and padding.
+#[repr(C)]
+#[derive(Debug, Copy, Clone, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct HeartbeatUniforms {
+    /// Strength of the effect, from 0.0 to 1.0
+    pub intensity: f32,
+    /// How far through the current heartbeat, from 0.0 to 1.0
+    pub phase: f32,
+    /// Surface width divided by height
+    pub aspect: f32,
+    /// Padding to keep the struct 16-byte aligned
+    pub _padding: f32,
+}
+
+/// Renders the heartbeat distortion of the timer's last seconds.
+pub struct HeartbeatRenderer {
+    /// Pipeline drawing the distorted scene onto the surface
+    pipeline: wgpu::RenderPipeline,
+    /// Buffer of the heartbeat uniforms
+    uniform_buffer: wgpu::Buffer,
+    /// Bind group of the heartbeat uniforms
+    uniform_bind_group: wgpu::BindGroup,
+    /// The offscreen texture the scene is drawn into while the effect is active
+    target: SceneTarget,
+}
+
+impl HeartbeatRenderer {
+    /// Creates the heartbeat renderer; its texture is created the first time it is needed.
+    ///
+    /// # Arguments
+    /// * `device` - WebGPU device for creating GPU resources
+    /// * `surface_config` - Surface configuration containing the format and size
+    /// * `gpu_memory` - Tracker the scene texture is counted in
+    pub fn new(
+        device: &wgpu::Device,
+        surface_config: &wgpu::SurfaceConfiguration,
+        gpu_memory: &GpuMemoryTracker,
+    ) -> Self {
+        let uniforms = HeartbeatUniforms {
+            intensity: 0.0,
+            phase: 0.0,
+            aspect: 1.0,
+            _padding: 0.0,
+        };
+        let uniform_buffer = create_uniform_buffer(device, &uniforms, "Heartbeat Uniform Buffer");
+
+        let uniform_bind_group_layout = BindGroupLayoutBuilder::new(device)
+            .with_label("Heartbeat Uniform Bind Group Layout")
+            .with_uniform_buffer(0, wgpu::ShaderStages::FRAGMENT)
+            .build();
+        let target = SceneTarget::new(device, "heartbeat", surface_config, gpu_memory);
+
+        let uniform_bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            layout: &uniform_bind_group_layout,
+            entries: &[wgpu::BindGroupEntry {
+                binding: 0,
+                resource: uniform_buffer.as_entire_binding(),
+            }],
+            label: Some("Heartbeat Uniform Bind Group"),
+        });
+
+        let pipeline = PipelineBuilder::new(device, surface_config.format)
+            .with_label("Heartbeat Pipeline")
+            .with_shader(include_str!("../shaders/heartbeat.wgsl"))
+            .with_bind_group_layout(&uniform_bind_group_layout)
+            .with_bind_group_layout(target.bind_group_layout())
+            .with_no_culling()
+            .build();
+
+        Self {
+            pipeline,
+            uniform_buffer,
+            uniform_bind_group,
+            target,
+        }
+    }
+
+    /// Matches a new surface size; the texture is recreated at the new size the next time it
+    /// is needed.
+    ///
+    /// # Arguments
+    /// * `width` - New surface width in pixels
+    /// * `height` - New surface height in pixels
+    pub fn resize(&mut self, width: u32, height: u32) {
+        self.target.resize(width, height);
+    }
+
+    /// Returns the texture to draw this frame's scene into, creating it if needed.
+    ///
+    /// # Arguments
+    /// * `device` - WebGPU device for creating the texture
+    ///
+    /// # Returns
+    /// A view of the scene texture, or `None` if it can't be allocated, in which case the
+    /// scene should be drawn straight to the surface
+    pub fn scene_view(&mut self, device: &wgpu::Device) -> Option<wgpu::TextureView> {
+        self.target.view(device)
+    }
+
+    /// Draws the scene texture onto the surface through the distortion.
+    ///
+    /// Does nothing if the texture was never allocated.
+    ///
+    /// # Arguments
+    /// * `queue` - WebGPU queue for uploading uniforms
+    /// * `encoder` - Command encoder to record the pass into
+    /// * `surface_view` - The surface to draw the distorted scene onto
+    /// * `intensity` - Strength of the effect, from 0.0 to 1.0
+    /// * `phase` - How far through the current heartbeat, from 0.0 to 1.0
+    pub fn render(
+        &self,
+        queue: &wgpu::Queue,
+        encoder: &mut wgpu::CommandEncoder,
+        surface_view: &wgpu::TextureView,
+        intensity: f32,
+        phase: f32,
+    ) {
+        let Some(scene) = self.target.bind_group() else {
+            return;
+        };
+        let (width, height) = self.target.size();
+        let uniforms = HeartbeatUniforms {
+            intensity: intensity.clamp(0.0, 1.0),
+            phase: phase.clamp(0.0, 1.0),
+            aspect: width as f32 / height as f32,
+            _padding: 0.0,
+        };
+        queue.write_buffer(&self.uniform_buffer, 0, bytemuck::bytes_of(&uniforms));
+
+        let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some("Heartbeat Pass"),
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view: surface_view,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Load,
+                    store: wgpu::StoreOp::Store,
+                },
+            })],
+            depth_stencil_attachment: None,
+            occlusion_query_set: None,
+            timestamp_writes: None,
+        });
+        pass.set_pipeline(&self.pipeline);
+        pass.set_bind_group(0, &self.uniform_bind_group, &[]);
+        pass.set_bind_group(1, scene, &[]);
+        pass.draw(0..3, 0..1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::renderer::uniform_layout::assert_matches_shader;
+
+    #[test]
+    fn test_uniforms_match_the_shader_layout() {
+        assert_matches_shader::<HeartbeatUniforms>(include_str!("../shaders/heartbeat.wgsl"));
+    }
+}
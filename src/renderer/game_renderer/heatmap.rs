@@ -0,0 +1,270 @@
+//! Frame heatmap overlay for the Mirador game.
+//!
+//! This debug view tints every cell the player has stood in by its rolling CPU frame time from
+//! the [`FrameHeatmap`], blue for the cheapest cell recorded this level and red for the most
+//! expensive. Like the click-to-move path, the tiles are instanced floor quads drawn with one
+//! draw call, alpha blended without writing depth. Each instance carries its own color, and the
+//! instances are built in a buffer allocated once, so updating the overlay doesn't allocate.
+
+use crate::game::GameState;
+use crate::game::frame_heatmap::FrameHeatmap;
+use crate::math::coordinates;
+use crate::renderer::pipeline_builder::{
+    BindGroupLayoutBuilder, PipelineBuilder, create_uniform_buffer,
+};
+use wgpu::{self, util::DeviceExt};
+
+/// Most tiles drawn at once; cells past this in table order are left out.
+pub const MAX_HEATMAP_TILES: usize = 4096;
+
+/// Fraction of the cell size a tile covers, leaving a gap between neighbouring cells.
+const TILE_FILL: f32 = 0.9;
+
+/// Height the tiles are lifted above the floor beneath them.
+const TILE_LIFT: f32 = 0.5;
+
+/// Color of the cheapest recorded cell as linear RGBA.
+const COOL_COLOR: [f32; 4] = [0.1, 0.35, 1.0, 0.45];
+
+/// Color of the most expensive recorded cell as linear RGBA.
+const HOT_COLOR: [f32; 4] = [1.0, 0.15, 0.05, 0.55];
+
+/// Uniform data structure for the heatmap shader.
+#[repr(C)]
+#[derive(Copy, Clone, Debug, bytemuck::Pod, bytemuck::Zeroable)]
+struct HeatmapUniforms {
+    /// View-projection matrix for transforming vertices to screen space
+    view_proj_matrix: [[f32; 4]; 4],
+    /// Width of a tile in world units
+    tile_size: f32,
+    /// Height the tiles are lifted above the floor
+    lift: f32,
+    /// Padding for proper memory alignment
+    _padding: [f32; 2],
+}
+
+/// Per-tile instance data.
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Default, bytemuck::Pod, bytemuck::Zeroable)]
+struct HeatmapInstance {
+    /// Cell center on the floor (x, y, z) followed by an unused 0.0
+    position: [f32; 4],
+    /// Tile color
+    color: [f32; 4],
+}
+
+/// Renders the frame heatmap as instanced floor quads.
+pub struct HeatmapRenderer {
+    /// Whether the overlay is drawn, toggled from the debug keys
+    pub visible: bool,
+    /// The render pipeline for heatmap rendering
+    pipeline: wgpu::RenderPipeline,
+    /// Vertex buffer containing the unit quad corners
+    vertex_buffer: wgpu::Buffer,
+    /// Instance buffer holding one entry per tile slot
+    instance_buffer: wgpu::Buffer,
+    /// Uniform buffer containing shader uniforms
+    uniform_buffer: wgpu::Buffer,
+    /// Bind group containing the uniforms
+    bind_group: wgpu::BindGroup,
+    /// Tiles built on the CPU each update, reused so updates don't allocate
+    instances: Vec<HeatmapInstance>,
+}
+
+impl HeatmapRenderer {
+    /// Creates a new heatmap renderer, hidden until toggled on.
+    ///
+    /// # Arguments
+    ///
+    /// * `device` - WGPU device for creating GPU resources
+    /// * `surface_config` - Surface configuration for pipeline creation
+    /// * `sample_count` - Samples per pixel of the render target the 3D view is drawn into
+    ///
+    /// # Returns
+    ///
+    /// A new `HeatmapRenderer` with an empty instance buffer sized for [`MAX_HEATMAP_TILES`].
+    pub fn new(
+        device: &wgpu::Device,
+        surface_config: &wgpu::SurfaceConfiguration,
+        sample_count: u32,
+    ) -> Self {
+        let uniforms = HeatmapUniforms {
+            view_proj_matrix: [[0.0; 4]; 4],
+            tile_size: 0.0,
+            lift: TILE_LIFT,
+            _padding: [0.0; 2],
+        };
+        let uniform_buffer = create_uniform_buffer(device, &uniforms, "Heatmap Uniform Buffer");
+
+        let bind_group_layout = BindGroupLayoutBuilder::new(device)
+            .with_label("Heatmap Bind Group Layout")
+            .with_uniform_buffer(0, wgpu::ShaderStages::VERTEX)
+            .build();
+
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            layout: &bind_group_layout,
+            entries: &[wgpu::BindGroupEntry {
+                binding: 0,
+                resource: uniform_buffer.as_entire_binding(),
+            }],
+            label: Some("Heatmap Bind Group"),
+        });
+
+        // Unit quad corners in the XZ plane
+        let corners: &[f32] = &[
+            -0.5, -0.5, 0.5, -0.5, -0.5, 0.5, // Triangle 1
+            0.5, -0.5, 0.5, 0.5, -0.5, 0.5, // Triangle 2
+        ];
+        let vertex_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Heatmap Vertex Buffer"),
+            contents: bytemuck::cast_slice(corners),
+            usage: wgpu::BufferUsages::VERTEX,
+        });
+
+        let instance_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Heatmap Instance Buffer"),
+            contents: bytemuck::cast_slice(&vec![HeatmapInstance::default(); MAX_HEATMAP_TILES]),
+            usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
+        });
+
+        let vertex_buffer_layout = wgpu::VertexBufferLayout {
+            array_stride: 2 * 4, // 2 floats * 4 bytes each
+            step_mode: wgpu::VertexStepMode::Vertex,
+            attributes: &[wgpu::VertexAttribute {
+                offset: 0,
+                shader_location: 0,
+                format: wgpu::VertexFormat::Float32x2, // corner (x, z)
+            }],
+        };
+        let instance_buffer_layout = wgpu::VertexBufferLayout {
+            array_stride: std::mem::size_of::<HeatmapInstance>() as wgpu::BufferAddress,
+            step_mode: wgpu::VertexStepMode::Instance,
+            attributes: &[
+                wgpu::VertexAttribute {
+                    offset: 0,
+                    shader_location: 1,
+                    format: wgpu::VertexFormat::Float32x4, // position
+                },
+                wgpu::VertexAttribute {
+                    offset: 16,
+                    shader_location: 2,
+                    format: wgpu::VertexFormat::Float32x4, // color
+                },
+            ],
+        };
+
+        let pipeline = PipelineBuilder::new(device, surface_config.format)
+            .with_label("Heatmap Pipeline")
+            .with_shader(include_str!("../shaders/heatmap.wgsl"))
+            .with_vertex_buffer(vertex_buffer_layout)
+            .with_vertex_buffer(instance_buffer_layout)
+            .with_bind_group_layout(&bind_group_layout)
+            .with_alpha_blending()
+            .with_no_culling()
+            .with_depth_stencil(wgpu::DepthStencilState {
+                depth_write_enabled: false,
+                depth_compare: wgpu::CompareFunction::LessEqual,
+                format: wgpu::TextureFormat::Depth24Plus,
+                stencil: wgpu::StencilState::default(),
+                bias: wgpu::DepthBiasState::default(),
+            })
+            .with_sample_count(sample_count)
+            .build();
+
+        Self {
+            visible: false,
+            pipeline,
+            vertex_buffer,
+            instance_buffer,
+            uniform_buffer,
+            bind_group,
+            instances: Vec::with_capacity(MAX_HEATMAP_TILES),
+        }
+    }
+
+    /// Uploads the recorded cells and the view-projection matrix.
+    ///
+    /// Does nothing while the overlay is hidden.
+    ///
+    /// # Arguments
+    ///
+    /// * `queue` - WGPU queue for uploading data
+    /// * `game_state` - Current game state containing the frame heatmap
+    /// * `view_proj_matrix` - Current view-projection matrix for rendering
+    pub fn update(
+        &mut self,
+        queue: &wgpu::Queue,
+        game_state: &GameState,
+        view_proj_matrix: [[f32; 4]; 4],
+    ) {
+        self.instances.clear();
+        if !self.visible {
+            return;
+        }
+
+        let heatmap: &FrameHeatmap = &game_state.frame_heatmap;
+        let Some((min_ms, max_ms)) = heatmap.range() else {
+            return;
+        };
+        let collision = &game_state.collision_system;
+        let maze_dimensions = collision.maze_dimensions;
+        let spread = (max_ms - min_ms).max(f32::EPSILON);
+
+        for (cell, layer, cost) in heatmap.recorded().take(MAX_HEATMAP_TILES) {
+            let floor = collision
+                .floors
+                .as_ref()
+                .map_or(0.0, |plan| plan.base_height(layer));
+            let [x, y, z] =
+                coordinates::maze_to_world(&cell, maze_dimensions, floor, collision.is_test_mode);
+            self.instances.push(HeatmapInstance {
+                position: [x, y, z, 0.0],
+                color: heat_color((cost.average_ms - min_ms) / spread),
+            });
+        }
+
+        queue.write_buffer(
+            &self.instance_buffer,
+            0,
+            bytemuck::cast_slice(&self.instances),
+        );
+
+        let cell_size = coordinates::calculate_cell_size(maze_dimensions, collision.is_test_mode);
+        let uniforms = HeatmapUniforms {
+            view_proj_matrix,
+            tile_size: cell_size * TILE_FILL,
+            lift: TILE_LIFT,
+            _padding: [0.0; 2],
+        };
+        queue.write_buffer(&self.uniform_buffer, 0, bytemuck::cast_slice(&[uniforms]));
+    }
+
+    /// Renders the heatmap tiles to the specified render pass.
+    ///
+    /// Does nothing while the overlay is hidden or no cell has been recorded.
+    ///
+    /// # Arguments
+    ///
+    /// * `render_pass` - The render pass to draw to
+    pub fn render(&self, render_pass: &mut wgpu::RenderPass) {
+        if self.instances.is_empty() {
+            return;
+        }
+
+        render_pass.set_pipeline(&self.pipeline);
+        render_pass.set_vertex_buffer(0, self.vertex_buffer.slice(..));
+        render_pass.set_vertex_buffer(1, self.instance_buffer.slice(..));
+        render_pass.set_bind_group(0, &self.bind_group, &[]);
+        render_pass.draw(0..6, 0..self.instances.len() as u32);
+    }
+}
+
+/// Blends from the cool color to the hot color.
+///
+/// # Arguments
+///
+/// * `t` - Where the cell's cost falls between the cheapest and most expensive cell, 0 to 1
+fn heat_color(t: f32) -> [f32; 4] {
+    let t = t.clamp(0.0, 1.0);
+    std::array::from_fn(|i| COOL_COLOR[i] + (HOT_COLOR[i] - COOL_COLOR[i]) * t)
+}
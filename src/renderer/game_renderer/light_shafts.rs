@@ -0,0 +1,921 @@
+//! Light Shafts Renderer Module
+//!
+//! This module casts shafts of light from a beacon floating over the exit. Each frame the
+//! beacon is projected onto the screen; the light grows as the player closes in and fades out
+//! as the beacon leaves the 3D view. Walls, pillars and the enemy in front of the beacon cut
+//! the shafts off where they stand, and block the glow as a whole.
+//!
+//! The effect takes three steps after the scene is drawn:
+//! 1. An occlusion pass reads the depth buffer around the beacon and blends how much of it is
+//!    in view into a one-pixel running average, so the light dims and returns smoothly rather
+//!    than flickering as wall edges cross it.
+//! 2. A shafts pass reads the scene from a [`SceneTarget`] and marches each pixel toward the
+//!    beacon, adding the light that isn't blocked on the way.
+//! 3. With reduced effects on, the shafts pass is replaced by a glow added around the beacon,
+//!    which needs no offscreen copy of the scene.
+//!
+//! The shafts pass is timed on the GPU where the device supports it. A [`ShaftGovernor`]
+//! watches frame times and that measurement, and turns the shafts off for a while when the
+//! game can't keep up with them.
+
+use crate::game::GameState;
+use crate::game::maze::floors::Layer;
+use crate::math::coordinates;
+use crate::math::mat::Mat4;
+use crate::renderer::game_renderer::{Z_NEAR, player_camera};
+use crate::renderer::gpu_memory::{
+    GpuMemoryTracker, TextureCategory, TrackedTexture, create_tracked_texture,
+};
+use crate::renderer::gpu_timer::PassTimer;
+use crate::renderer::pipeline_builder::{
+    BindGroupLayoutBuilder, PipelineBuilder, create_uniform_buffer,
+};
+use crate::renderer::safe_area::SafeArea;
+use crate::renderer::scene_target::SceneTarget;
+use std::borrow::Cow;
+use wgpu;
+
+/// Height of the beacon over the exit's floor, in cells.
+const BEACON_HEIGHT: f32 = 0.6;
+
+/// Linear color of the beacon's light, the green of the exit.
+const BEACON_COLOR: [f32; 4] = [0.35, 1.0, 0.55, 1.0];
+
+/// Distance in cells within which the light is at full strength.
+const NEAR_CELLS: f32 = 2.0;
+
+/// Distance in cells past which the light is at its weakest.
+const FAR_CELLS: f32 = 14.0;
+
+/// Strength of the light from the far end of the maze.
+const MIN_INTENSITY: f32 = 0.25;
+
+/// How far past the 3D view's edges the beacon can go, as a fraction of the surface, before
+/// its light has faded out.
+const EDGE_MARGIN: f32 = 0.15;
+
+/// Seconds the occlusion average takes to cover about two thirds of a change.
+const OCCLUSION_TIME_CONSTANT: f32 = 0.25;
+
+/// Longest average frame time the shafts are kept on for, in seconds.
+const FRAME_TIME_LIMIT: f32 = 1.0 / 25.0;
+
+/// GPU time the shafts pass is meant to take, in milliseconds.
+const GPU_BUDGET_MS: f32 = 0.4;
+
+/// How many times over its budget the shafts pass may run before it is turned off.
+const GPU_OVERRUN_FACTOR: f32 = 4.0;
+
+/// Weight of a new frame in the average frame time.
+const FRAME_TIME_WEIGHT: f32 = 0.05;
+
+/// Longest frame counted in the average, so one hitch doesn't turn the shafts off.
+const MAX_COUNTED_FRAME: f32 = 0.25;
+
+/// Seconds the shafts stay off before they are tried again.
+const RETRY_SECS: f32 = 15.0;
+
+/// Format of the one-pixel occlusion average.
+const VISIBILITY_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::R16Float;
+
+/// Uniform data shared by the light shaft shaders.
+///
+/// # Memory Layout
+/// 64 bytes: the beacon, the view rectangle, the glow's shape and the light's color.
+#[repr(C)]
+#[derive(Debug, Copy, Clone, PartialEq, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct LightShaftUniforms {
+    /// Beacon position on the surface, from 0.0 to 1.0 with y down
+    pub beacon_uv: [f32; 2],
+    /// Beacon depth as the depth buffer stores it
+    pub beacon_depth: f32,
+    /// Brightness of the shafts, from 0.0 to 1.0
+    pub intensity: f32,
+    /// Top-left corner of the 3D view on the surface, from 0.0 to 1.0
+    pub view_min: [f32; 2],
+    /// Bottom-right corner of the 3D view on the surface, from 0.0 to 1.0
+    pub view_max: [f32; 2],
+    /// Surface width divided by height
+    pub aspect: f32,
+    /// Radius of the beacon's glow as a fraction of the surface height
+    pub glow_radius: f32,
+    /// Padding to keep the color 16-byte aligned
+    pub _padding: [f32; 2],
+    /// Linear color of the light
+    pub color: [f32; 4],
+}
+
+/// Where a point in the world lands on the surface.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BeaconProjection {
+    /// Position on the surface, from 0.0 to 1.0 with y down
+    pub uv: [f32; 2],
+    /// Depth as the depth buffer stores it
+    pub depth: f32,
+    /// Distance in front of the camera
+    pub w: f32,
+}
+
+/// Projects a world point onto the surface through the 3D view.
+///
+/// # Arguments
+/// * `view_proj` - The camera's combined view and projection
+/// * `point` - World position to project
+/// * `view` - Where the 3D view sits on the surface, in pixels
+/// * `surface` - Surface width and height in pixels
+///
+/// # Returns
+/// Where the point lands, or `None` if it is behind the camera
+pub fn project(
+    view_proj: &Mat4,
+    point: [f32; 3],
+    view: &SafeArea,
+    surface: (f32, f32),
+) -> Option<BeaconProjection> {
+    let m = &view_proj.0;
+    let clip: [f32; 4] = std::array::from_fn(|j| {
+        point[0] * m[0][j] + point[1] * m[1][j] + point[2] * m[2][j] + m[3][j]
+    });
+    let w = clip[3];
+    if w < Z_NEAR || surface.0 <= 0.0 || surface.1 <= 0.0 {
+        return None;
+    }
+    let (ndc_x, ndc_y) = (clip[0] / w, clip[1] / w);
+    let x = view.x + (ndc_x * 0.5 + 0.5) * view.width;
+    let y = view.y + (0.5 - ndc_y * 0.5) * view.height;
+    Some(BeaconProjection {
+        uv: [x / surface.0, y / surface.1],
+        depth: clip[2] / w,
+        w,
+    })
+}
+
+/// Returns how much of the light is left as the beacon leaves the 3D view.
+///
+/// # Arguments
+/// * `uv` - Beacon position on the surface
+/// * `view_min` - Top-left corner of the 3D view on the surface
+/// * `view_max` - Bottom-right corner of the 3D view on the surface
+///
+/// # Returns
+/// 1.0 inside the view, falling to 0.0 [`EDGE_MARGIN`] past its edges
+pub fn edge_fade(uv: [f32; 2], view_min: [f32; 2], view_max: [f32; 2]) -> f32 {
+    let outside_x = (view_min[0] - uv[0]).max(uv[0] - view_max[0]).max(0.0);
+    let outside_y = (view_min[1] - uv[1]).max(uv[1] - view_max[1]).max(0.0);
+    (1.0 - outside_x.max(outside_y) / EDGE_MARGIN).clamp(0.0, 1.0)
+}
+
+/// Returns how strong the light is from a distance.
+///
+/// # Arguments
+/// * `distance` - Distance from the eye to the beacon in world units
+/// * `cell_size` - Size of a maze cell in world units
+///
+/// # Returns
+/// 1.0 within [`NEAR_CELLS`], easing down to [`MIN_INTENSITY`] at [`FAR_CELLS`] and beyond
+pub fn proximity(distance: f32, cell_size: f32) -> f32 {
+    let cells = distance / cell_size.max(f32::EPSILON);
+    let t = ((cells - NEAR_CELLS) / (FAR_CELLS - NEAR_CELLS)).clamp(0.0, 1.0);
+    let eased = (1.0 - t) * (1.0 - t);
+    MIN_INTENSITY + (1.0 - MIN_INTENSITY) * eased
+}
+
+/// Returns how far this frame moves the occlusion average toward the new measurement.
+///
+/// # Arguments
+/// * `delta_time` - Seconds since the last frame
+pub fn occlusion_blend(delta_time: f32) -> f32 {
+    1.0 - (-delta_time.max(0.0) / OCCLUSION_TIME_CONSTANT).exp()
+}
+
+/// Which of the effect's passes run this frame.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShaftMode {
+    /// Nothing is drawn; there is no beacon or it is out of view
+    Off,
+    /// The scene is drawn offscreen and the shafts are cast over it
+    Shafts,
+    /// Only a glow is added around the beacon, for reduced effects
+    Glow,
+}
+
+/// What the camera sees of the beacon this frame.
+#[derive(Debug, Clone, Copy)]
+pub struct ShaftFrame {
+    /// The camera's combined view and projection
+    pub view_proj: Mat4,
+    /// World position of the eye
+    pub eye: [f32; 3],
+    /// World position of the beacon, if the level has an exit
+    pub beacon: Option<[f32; 3]>,
+    /// Size of a maze cell in world units
+    pub cell_size: f32,
+    /// Where the 3D view sits on the surface, in pixels
+    pub view: SafeArea,
+    /// Surface width and height in pixels
+    pub surface: (f32, f32),
+}
+
+/// Works out this frame's passes and their uniforms.
+///
+/// # Arguments
+/// * `frame` - What the camera sees of the beacon
+/// * `reduce_effects` - Whether the player has asked for reduced visual effects
+/// * `allowed` - Whether the [`ShaftGovernor`] lets the shafts run
+///
+/// # Returns
+/// The mode and the uniforms for the shaders, which only matter when the mode isn't
+/// [`ShaftMode::Off`]
+pub fn plan(
+    frame: &ShaftFrame,
+    reduce_effects: bool,
+    allowed: bool,
+) -> (ShaftMode, LightShaftUniforms) {
+    let (width, height) = frame.surface;
+    let view_min = [frame.view.x / width, frame.view.y / height];
+    let view_max = [frame.view.right() / width, frame.view.bottom() / height];
+    let mut uniforms = LightShaftUniforms {
+        beacon_uv: [0.5, 0.5],
+        beacon_depth: 1.0,
+        intensity: 0.0,
+        view_min,
+        view_max,
+        aspect: if height > 0.0 { width / height } else { 1.0 },
+        glow_radius: 0.0,
+        _padding: [0.0; 2],
+        color: BEACON_COLOR,
+    };
+    let off = (ShaftMode::Off, uniforms);
+    if !reduce_effects && !allowed {
+        return off;
+    }
+    let Some(beacon) = frame.beacon else {
+        return off;
+    };
+    let Some(projection) = project(&frame.view_proj, beacon, &frame.view, frame.surface) else {
+        return off;
+    };
+    let fade = edge_fade(projection.uv, view_min, view_max);
+    if fade <= 0.0 || projection.depth >= 1.0 {
+        return off;
+    }
+
+    // The glow is half a cell across wherever the beacon is
+    let half_cell = frame.cell_size * 0.5;
+    let rim = project(
+        &frame.view_proj,
+        [beacon[0], beacon[1] + half_cell, beacon[2]],
+        &frame.view,
+        frame.surface,
+    );
+    let glow_radius = rim.map_or(0.0, |rim| (rim.uv[1] - projection.uv[1]).abs());
+
+    let distance = beacon
+        .iter()
+        .zip(frame.eye)
+        .map(|(b, e)| (b - e) * (b - e))
+        .sum::<f32>()
+        .sqrt();
+    uniforms.beacon_uv = projection.uv;
+    uniforms.beacon_depth = projection.depth;
+    uniforms.intensity = proximity(distance, frame.cell_size) * fade;
+    uniforms.glow_radius = glow_radius.clamp(0.01, 0.5);
+    let mode = if reduce_effects {
+        ShaftMode::Glow
+    } else {
+        ShaftMode::Shafts
+    };
+    (mode, uniforms)
+}
+
+/// Returns where the exit's beacon floats, over the exit's floor.
+///
+/// # Arguments
+/// * `game_state` - Current game state holding the collision system
+/// * `exit_position` - The (x, z) world position of the exit
+pub fn beacon_position(game_state: &GameState, exit_position: (f32, f32)) -> [f32; 3] {
+    let collision = &game_state.collision_system;
+    let cell_size =
+        coordinates::calculate_cell_size(collision.maze_dimensions, collision.is_test_mode);
+    // On two-floor levels the exit is upstairs
+    let (_, floor) = collision.ground([exit_position.0, 0.0, exit_position.1], Layer::Upper);
+    [
+        exit_position.0,
+        floor + cell_size * BEACON_HEIGHT,
+        exit_position.1,
+    ]
+}
+
+/// Turns the shafts off while the game can't keep up with them.
+///
+/// Tracks an average of recent frame times, and the GPU time of the shafts pass where it is
+/// measured. When either goes over its limit the shafts are turned off for [`RETRY_SECS`],
+/// then tried again.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ShaftGovernor {
+    /// Average frame time in seconds while the shafts were on
+    frame_time: Option<f32>,
+    /// Seconds left before the shafts are tried again
+    off_for: f32,
+}
+
+impl ShaftGovernor {
+    /// Counts one frame drawn with the shafts and decides whether the next may use them.
+    ///
+    /// # Arguments
+    /// * `delta_time` - Seconds the frame took
+    /// * `gpu_ms` - Average GPU time of the shafts pass in milliseconds, if measured
+    ///
+    /// # Returns
+    /// Whether the shafts may be drawn
+    pub fn update(&mut self, delta_time: f32, gpu_ms: Option<f32>) -> bool {
+        if self.off_for > 0.0 {
+            self.off_for -= delta_time.max(0.0);
+            return false;
+        }
+        if !delta_time.is_finite() || delta_time <= 0.0 {
+            return true;
+        }
+        let counted = delta_time.min(MAX_COUNTED_FRAME);
+        let average = match self.frame_time {
+            Some(average) => average + (counted - average) * FRAME_TIME_WEIGHT,
+            None => counted,
+        };
+        self.frame_time = Some(average);
+
+        let gpu_over = gpu_ms.is_some_and(|ms| ms > GPU_BUDGET_MS * GPU_OVERRUN_FACTOR);
+        if average > FRAME_TIME_LIMIT || gpu_over {
+            eprintln!(
+                "Turning the exit's light shafts off for {}s: frames average {:.1} ms{}",
+                RETRY_SECS,
+                average * 1000.0,
+                gpu_ms.map_or(String::new(), |ms| format!(
+                    ", the shafts take {:.2} ms on the GPU",
+                    ms
+                )),
+            );
+            self.frame_time = None;
+            self.off_for = RETRY_SECS;
+            return false;
+        }
+        true
+    }
+
+    /// Waits out the cooldown on a frame drawn without the shafts.
+    ///
+    /// # Arguments
+    /// * `delta_time` - Seconds the frame took
+    pub fn idle(&mut self, delta_time: f32) {
+        if self.off_for > 0.0 {
+            self.off_for -= delta_time.max(0.0);
+        }
+    }
+
+    /// Returns whether the shafts are turned off for now.
+    pub fn is_off(&self) -> bool {
+        self.off_for > 0.0
+    }
+}
+
+/// Renders the light shafts from the exit's beacon.
+pub struct LightShaftRenderer {
+    /// Pipeline blending the beacon's visibility into the running average
+    occlusion_pipeline: wgpu::RenderPipeline,
+    /// Pipeline drawing the scene with the shafts added
+    shafts_pipeline: wgpu::RenderPipeline,
+    /// Pipeline adding the reduced-effects glow over the scene
+    glow_pipeline: wgpu::RenderPipeline,
+    /// Buffer of the light shaft uniforms
+    uniform_buffer: wgpu::Buffer,
+    /// Bind group of the light shaft uniforms
+    uniform_bind_group: wgpu::BindGroup,
+    /// Layout of the depth bind group, made each frame from that frame's depth view
+    depth_bind_group_layout: wgpu::BindGroupLayout,
+    /// The one-pixel running average of how much of the beacon is in view
+    _visibility_texture: TrackedTexture,
+    /// View the occlusion pass draws into
+    visibility_view: wgpu::TextureView,
+    /// Bind group the shaft passes read the average through
+    visibility_bind_group: wgpu::BindGroup,
+    /// Whether the average holds earlier frames, or must start over from darkness
+    visibility_valid: bool,
+    /// The offscreen texture the scene is drawn into while the shafts are on
+    target: SceneTarget,
+    /// Times the shafts pass, when the device supports it
+    timer: Option<PassTimer>,
+    /// Turns the shafts off when the game can't keep up with them
+    governor: ShaftGovernor,
+    /// This frame's passes
+    mode: ShaftMode,
+    /// This frame's uniforms
+    uniforms: LightShaftUniforms,
+    /// How far this frame moves the occlusion average
+    blend: f32,
+}
+
+/// Declares a light shaft shader's depth texture for a depth buffer with `depth_samples`
+/// samples per pixel.
+///
+/// The shaders read the depth buffer with `textureLoad` at sample 0, which reads the first
+/// sample of a multisampled texture the same way, so only the declaration changes.
+///
+/// # Arguments
+/// * `source` - WGSL source declaring the depth texture as `texture_depth_2d`
+/// * `depth_samples` - Samples per pixel of the depth buffer
+fn shader_for_depth_samples(source: &str, depth_samples: u32) -> Cow<'_, str> {
+    if depth_samples > 1 {
+        Cow::Owned(source.replace("texture_depth_2d", "texture_depth_multisampled_2d"))
+    } else {
+        Cow::Borrowed(source)
+    }
+}
+
+impl LightShaftRenderer {
+    /// Creates the light shaft renderer; its scene texture is created the first time it is
+    /// needed.
+    ///
+    /// # Arguments
+    /// * `device` - WebGPU device for creating GPU resources
+    /// * `queue` - WebGPU queue the shafts pass is timed on
+    /// * `surface_config` - Surface configuration containing the format and size
+    /// * `gpu_memory` - Tracker the textures are counted in
+    /// * `depth_samples` - Samples per pixel of the depth buffer the scene is drawn with
+    pub fn new(
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        surface_config: &wgpu::SurfaceConfiguration,
+        gpu_memory: &GpuMemoryTracker,
+        depth_samples: u32,
+    ) -> Self {
+        let uniforms = LightShaftUniforms {
+            beacon_uv: [0.5, 0.5],
+            beacon_depth: 1.0,
+            intensity: 0.0,
+            view_min: [0.0, 0.0],
+            view_max: [1.0, 1.0],
+            aspect: 1.0,
+            glow_radius: 0.0,
+            _padding: [0.0; 2],
+            color: BEACON_COLOR,
+        };
+        let uniform_buffer = create_uniform_buffer(device, &uniforms, "Light Shaft Uniform Buffer");
+        let uniform_bind_group_layout = BindGroupLayoutBuilder::new(device)
+            .with_label("Light Shaft Uniform Bind Group Layout")
+            .with_uniform_buffer(0, wgpu::ShaderStages::FRAGMENT)
+            .build();
+        let uniform_bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            layout: &uniform_bind_group_layout,
+            entries: &[wgpu::BindGroupEntry {
+                binding: 0,
+                resource: uniform_buffer.as_entire_binding(),
+            }],
+            label: Some("Light Shaft Uniform Bind Group"),
+        });
+        let depth_bind_group_layout =
+            BindGroupLayoutBuilder::new(device).with_label("Light Shaft Depth Bind Group Layout");
+        let depth_bind_group_layout = if depth_samples > 1 {
+            depth_bind_group_layout.with_multisampled_depth_texture(0, wgpu::ShaderStages::FRAGMENT)
+        } else {
+            depth_bind_group_layout.with_depth_texture(0, wgpu::ShaderStages::FRAGMENT)
+        }
+        .build();
+        let occlusion_shader = shader_for_depth_samples(
+            include_str!("../shaders/light_shaft_occlusion.wgsl"),
+            depth_samples,
+        );
+        let shafts_shader =
+            shader_for_depth_samples(include_str!("../shaders/light_shafts.wgsl"), depth_samples);
+
+        let visibility_texture = create_tracked_texture(
+            device,
+            gpu_memory,
+            TextureCategory::SceneTargets,
+            &wgpu::TextureDescriptor {
+                label: Some("Light Shaft Visibility Texture"),
+                size: wgpu::Extent3d {
+                    width: 1,
+                    height: 1,
+                    depth_or_array_layers: 1,
+                },
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: wgpu::TextureDimension::D2,
+                format: VISIBILITY_FORMAT,
+                usage: wgpu::TextureUsages::RENDER_ATTACHMENT
+                    | wgpu::TextureUsages::TEXTURE_BINDING,
+                view_formats: &[],
+            },
+        );
+        let visibility_view =
+            visibility_texture.create_view(&wgpu::TextureViewDescriptor::default());
+        let visibility_bind_group_layout = BindGroupLayoutBuilder::new(device)
+            .with_label("Light Shaft Visibility Bind Group Layout")
+            .with_texture(0, wgpu::ShaderStages::FRAGMENT)
+            .build();
+        let visibility_bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            layout: &visibility_bind_group_layout,
+            entries: &[wgpu::BindGroupEntry {
+                binding: 0,
+                resource: wgpu::BindingResource::TextureView(&visibility_view),
+            }],
+            label: Some("Light Shaft Visibility Bind Group"),
+        });
+
+        let target = SceneTarget::new(device, "light shafts", surface_config, gpu_memory);
+
+        // The new measurement is weighted by the blend constant, set each frame
+        let occlusion_pipeline = PipelineBuilder::new(device, VISIBILITY_FORMAT)
+            .with_label("Light Shaft Occlusion Pipeline")
+            .with_shader(&occlusion_shader)
+            .with_bind_group_layout(&uniform_bind_group_layout)
+            .with_bind_group_layout(&depth_bind_group_layout)
+            .with_blend_state(wgpu::BlendState {
+                color: wgpu::BlendComponent {
+                    src_factor: wgpu::BlendFactor::Constant,
+                    dst_factor: wgpu::BlendFactor::OneMinusConstant,
+                    operation: wgpu::BlendOperation::Add,
+                },
+                alpha: wgpu::BlendComponent::REPLACE,
+            })
+            .with_no_culling()
+            .build();
+        let shafts_pipeline = PipelineBuilder::new(device, surface_config.format)
+            .with_label("Light Shafts Pipeline")
+            .with_shader(&shafts_shader)
+            .with_bind_group_layout(&uniform_bind_group_layout)
+            .with_bind_group_layout(&visibility_bind_group_layout)
+            .with_bind_group_layout(target.bind_group_layout())
+            .with_bind_group_layout(&depth_bind_group_layout)
+            .with_no_culling()
+            .build();
+        let glow_pipeline = PipelineBuilder::new(device, surface_config.format)
+            .with_label("Light Shaft Glow Pipeline")
+            .with_shader(&shafts_shader)
+            .with_fragment_entry("fs_glow")
+            .with_bind_group_layout(&uniform_bind_group_layout)
+            .with_bind_group_layout(&visibility_bind_group_layout)
+            .with_blend_state(wgpu::BlendState {
+                color: wgpu::BlendComponent {
+                    src_factor: wgpu::BlendFactor::One,
+                    dst_factor: wgpu::BlendFactor::One,
+                    operation: wgpu::BlendOperation::Add,
+                },
+                alpha: wgpu::BlendComponent {
+                    src_factor: wgpu::BlendFactor::Zero,
+                    dst_factor: wgpu::BlendFactor::One,
+                    operation: wgpu::BlendOperation::Add,
+                },
+            })
+            .with_no_culling()
+            .build();
+
+        Self {
+            occlusion_pipeline,
+            shafts_pipeline,
+            glow_pipeline,
+            uniform_buffer,
+            uniform_bind_group,
+            depth_bind_group_layout,
+            _visibility_texture: visibility_texture,
+            visibility_view,
+            visibility_bind_group,
+            visibility_valid: false,
+            target,
+            timer: PassTimer::new(device, queue, "Light Shafts"),
+            governor: ShaftGovernor::default(),
+            mode: ShaftMode::Off,
+            uniforms,
+            blend: 1.0,
+        }
+    }
+
+    /// Matches a new surface size; the scene texture is recreated at the new size the next
+    /// time it is needed.
+    ///
+    /// # Arguments
+    /// * `width` - New surface width in pixels
+    /// * `height` - New surface height in pixels
+    pub fn resize(&mut self, width: u32, height: u32) {
+        self.target.resize(width, height);
+    }
+
+    /// Works out this frame's passes from where the beacon is.
+    ///
+    /// # Arguments
+    /// * `device` - WebGPU device, polled for the last shafts pass's timing
+    /// * `game_state` - Current game state holding the player and the camera
+    /// * `exit_position` - The (x, z) world position of the exit, if the level has one
+    /// * `view` - Where the 3D view sits on the surface, in pixels
+    /// * `reduce_effects` - Whether the player has asked for reduced visual effects
+    /// * `delta_time` - Seconds since the last frame
+    pub fn update(
+        &mut self,
+        device: &wgpu::Device,
+        game_state: &GameState,
+        exit_position: Option<(f32, f32)>,
+        view: SafeArea,
+        reduce_effects: bool,
+        delta_time: f32,
+    ) {
+        if let Some(timer) = &mut self.timer {
+            timer.poll(device);
+        }
+        // Only frames drawn with the shafts count toward turning them off
+        let allowed = if self.mode == ShaftMode::Shafts {
+            self.governor.update(
+                delta_time,
+                self.timer.as_ref().and_then(PassTimer::average_ms),
+            )
+        } else {
+            self.governor.idle(delta_time);
+            !self.governor.is_off()
+        };
+
+        let (width, height) = self.target.size();
+        let camera = player_camera(game_state, view.aspect());
+        let collision = &game_state.collision_system;
+        let frame = ShaftFrame {
+            view_proj: camera.view.multiply(&camera.projection),
+            eye: game_state.player.position,
+            // The overhead map view looks down on the maze, not toward the exit
+            beacon: exit_position
+                .filter(|_| !game_state.map_view.is_engaged())
+                .map(|exit| beacon_position(game_state, exit)),
+            cell_size: coordinates::calculate_cell_size(
+                collision.maze_dimensions,
+                collision.is_test_mode,
+            ),
+            view,
+            surface: (width as f32, height as f32),
+        };
+        let (mode, uniforms) = plan(&frame, reduce_effects, allowed);
+        if mode == ShaftMode::Off {
+            self.visibility_valid = false;
+        }
+        self.mode = mode;
+        self.uniforms = uniforms;
+        self.blend = occlusion_blend(delta_time);
+    }
+
+    /// Returns the texture to draw this frame's scene into when the shafts are on, creating
+    /// it if needed.
+    ///
+    /// # Arguments
+    /// * `device` - WebGPU device for creating the texture
+    ///
+    /// # Returns
+    /// A view of the scene texture, or `None` if the shafts are off this frame or the texture
+    /// can't be allocated, in which case the scene should be drawn where it would have gone
+    pub fn scene_view(&mut self, device: &wgpu::Device) -> Option<wgpu::TextureView> {
+        if self.mode != ShaftMode::Shafts {
+            return None;
+        }
+        self.target.view(device)
+    }
+
+    /// Measures the beacon's visibility and draws the shafts or the glow.
+    ///
+    /// With the shafts on, the scene texture is drawn onto `destination` with the shafts
+    /// added; otherwise the glow is added over the scene already in `destination`. Does
+    /// nothing while the beacon is out of view.
+    ///
+    /// # Arguments
+    /// * `device` - WebGPU device for creating the depth bind group
+    /// * `queue` - WebGPU queue for uploading uniforms
+    /// * `encoder` - Command encoder to record the passes into
+    /// * `depth_view` - Depth buffer the scene was drawn with
+    /// * `destination` - Where the scene goes next: the surface, or another effect's texture
+    pub fn render(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        encoder: &mut wgpu::CommandEncoder,
+        depth_view: &wgpu::TextureView,
+        destination: &wgpu::TextureView,
+    ) {
+        if self.mode == ShaftMode::Off {
+            return;
+        }
+        queue.write_buffer(&self.uniform_buffer, 0, bytemuck::bytes_of(&self.uniforms));
+        let depth_bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            layout: &self.depth_bind_group_layout,
+            entries: &[wgpu::BindGroupEntry {
+                binding: 0,
+                resource: wgpu::BindingResource::TextureView(depth_view),
+            }],
+            label: Some("Light Shaft Depth Bind Group"),
+        });
+
+        {
+            // The average starts from darkness, so the light fades in when it first appears
+            let load = if self.visibility_valid {
+                wgpu::LoadOp::Load
+            } else {
+                wgpu::LoadOp::Clear(wgpu::Color::BLACK)
+            };
+            let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some("Light Shaft Occlusion Pass"),
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view: &self.visibility_view,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load,
+                        store: wgpu::StoreOp::Store,
+                    },
+                })],
+                depth_stencil_attachment: None,
+                occlusion_query_set: None,
+                timestamp_writes: None,
+            });
+            pass.set_pipeline(&self.occlusion_pipeline);
+            pass.set_blend_constant(wgpu::Color {
+                r: self.blend as f64,
+                g: self.blend as f64,
+                b: self.blend as f64,
+                a: self.blend as f64,
+            });
+            pass.set_bind_group(0, &self.uniform_bind_group, &[]);
+            pass.set_bind_group(1, &depth_bind_group, &[]);
+            pass.draw(0..3, 0..1);
+        }
+        self.visibility_valid = true;
+
+        // Without the scene texture the scene went straight to the destination, so fall back
+        // to the glow
+        let scene = self
+            .target
+            .bind_group()
+            .filter(|_| self.mode == ShaftMode::Shafts);
+        if let Some(scene) = scene {
+            let timestamp_writes = self.timer.as_mut().and_then(PassTimer::timestamp_writes);
+            let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some("Light Shafts Pass"),
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view: destination,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Load,
+                        store: wgpu::StoreOp::Store,
+                    },
+                })],
+                depth_stencil_attachment: None,
+                occlusion_query_set: None,
+                timestamp_writes,
+            });
+            pass.set_pipeline(&self.shafts_pipeline);
+            pass.set_bind_group(0, &self.uniform_bind_group, &[]);
+            pass.set_bind_group(1, &self.visibility_bind_group, &[]);
+            pass.set_bind_group(2, scene, &[]);
+            pass.set_bind_group(3, &depth_bind_group, &[]);
+            pass.draw(0..3, 0..1);
+            drop(pass);
+            if let Some(timer) = &mut self.timer {
+                timer.resolve(encoder);
+            }
+        } else {
+            let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some("Light Shaft Glow Pass"),
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view: destination,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Load,
+                        store: wgpu::StoreOp::Store,
+                    },
+                })],
+                depth_stencil_attachment: None,
+                occlusion_query_set: None,
+                timestamp_writes: None,
+            });
+            pass.set_pipeline(&self.glow_pipeline);
+            pass.set_bind_group(0, &self.uniform_bind_group, &[]);
+            pass.set_bind_group(1, &self.visibility_bind_group, &[]);
+            pass.draw(0..3, 0..1);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::renderer::game_renderer::camera_projection;
+    use crate::renderer::uniform_layout::assert_matches_shader;
+
+    fn frame(view_proj: Mat4, beacon: Option<[f32; 3]>) -> ShaftFrame {
+        ShaftFrame {
+            view_proj,
+            eye: [0.0, 0.0, 0.0],
+            beacon,
+            cell_size: 1.0,
+            view: SafeArea::full(800.0, 600.0),
+            surface: (800.0, 600.0),
+        }
+    }
+
+    fn camera() -> Mat4 {
+        // At the origin, looking down negative z
+        camera_projection(90.0, 800.0 / 600.0)
+    }
+
+    #[test]
+    fn test_uniforms_match_the_shader_layout() {
+        for source in [
+            include_str!("../shaders/light_shaft_occlusion.wgsl"),
+            include_str!("../shaders/light_shafts.wgsl"),
+        ] {
+            assert_matches_shader::<LightShaftUniforms>(source);
+        }
+    }
+
+    #[test]
+    fn test_shaders_declare_depth_for_the_sample_count() {
+        for source in [
+            include_str!("../shaders/light_shaft_occlusion.wgsl"),
+            include_str!("../shaders/light_shafts.wgsl"),
+        ] {
+            assert_eq!(shader_for_depth_samples(source, 1), source);
+            let multisampled = shader_for_depth_samples(source, 4);
+            assert!(multisampled.contains("texture_depth_multisampled_2d"));
+            assert!(!multisampled.contains("texture_depth_2d"));
+        }
+    }
+
+    #[test]
+    fn test_projection_finds_the_beacon_on_screen() {
+        let view = SafeArea::full(800.0, 600.0);
+        let ahead = project(&camera(), [0.0, 0.0, -10.0], &view, (800.0, 600.0))
+            .expect("in front of the camera");
+        assert!((ahead.uv[0] - 0.5).abs() < 1e-4 && (ahead.uv[1] - 0.5).abs() < 1e-4);
+        assert!(ahead.depth > 0.0 && ahead.depth < 1.0);
+
+        let right_and_up = project(&camera(), [2.0, 2.0, -10.0], &view, (800.0, 600.0))
+            .expect("in front of the camera");
+        assert!(right_and_up.uv[0] > 0.5 && right_and_up.uv[1] < 0.5);
+
+        assert_eq!(
+            project(&camera(), [0.0, 0.0, 10.0], &view, (800.0, 600.0)),
+            None
+        );
+    }
+
+    #[test]
+    fn test_plan_turns_off_out_of_view() {
+        let ahead = plan(&frame(camera(), Some([0.0, 0.0, -5.0])), false, true);
+        assert_eq!(ahead.0, ShaftMode::Shafts);
+        assert!(ahead.1.intensity > 0.0);
+        assert_eq!(
+            plan(&frame(camera(), Some([0.0, 0.0, -5.0])), true, true).0,
+            ShaftMode::Glow
+        );
+        assert_eq!(
+            plan(&frame(camera(), Some([0.0, 0.0, -5.0])), false, false).0,
+            ShaftMode::Off
+        );
+        assert_eq!(
+            plan(&frame(camera(), Some([0.0, 0.0, 5.0])), false, true).0,
+            ShaftMode::Off
+        );
+        assert_eq!(
+            plan(&frame(camera(), Some([50.0, 0.0, -5.0])), false, true).0,
+            ShaftMode::Off
+        );
+        assert_eq!(plan(&frame(camera(), None), false, true).0, ShaftMode::Off);
+    }
+
+    #[test]
+    fn test_light_grows_as_the_player_closes_in() {
+        let mut last = 0.0;
+        for cells in (0..=20).rev() {
+            let intensity = proximity(cells as f32 * 3.0, 3.0);
+            assert!(intensity >= last);
+            assert!((MIN_INTENSITY..=1.0).contains(&intensity));
+            last = intensity;
+        }
+        assert_eq!(proximity(0.0, 3.0), 1.0);
+        assert_eq!(proximity(100.0, 3.0), MIN_INTENSITY);
+        assert!(occlusion_blend(1.0 / 60.0) > 0.0 && occlusion_blend(1.0 / 60.0) < 0.1);
+    }
+
+    #[test]
+    fn test_governor_turns_slow_shafts_off_then_retries() {
+        let mut governor = ShaftGovernor::default();
+        for _ in 0..200 {
+            assert!(governor.update(1.0 / 60.0, Some(0.2)));
+        }
+        // One hitch isn't enough
+        assert!(governor.update(2.0, None));
+
+        let mut slow = 0;
+        while governor.update(1.0 / 15.0, None) {
+            slow += 1;
+            assert!(slow < 200, "never turned off");
+        }
+        assert!(governor.is_off());
+        governor.idle(RETRY_SECS + 1.0);
+        assert!(!governor.is_off());
+        assert!(governor.update(1.0 / 60.0, None));
+
+        // A pass far over its GPU budget turns off straight away
+        assert!(!governor.update(1.0 / 60.0, Some(GPU_BUDGET_MS * 10.0)));
+    }
+}
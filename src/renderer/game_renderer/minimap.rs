@@ -0,0 +1,465 @@
+//! Minimap Renderer Module
+//!
+//! This module draws a small top-down map of the maze in the bottom-left corner of the HUD
+//! while a level is being played. Only what the player has explored is filled in: the cells
+//! they have walked through, and the walls beside them. The exit cell is always shown, and an
+//! arrow marks where the player stands and which way they are facing.
+//!
+//! The maze's wall grid goes to the GPU as an `R8Unorm` texture with one texel per grid cell,
+//! holding its [`MinimapCell`]. The texels are rebuilt on the CPU every frame, which is cheap
+//! for a maze grid, but only uploaded when a cell changed, so walking down a corridor already
+//! explored writes nothing. Like the timer bar, the map is drawn with a full-screen triangle
+//! whose fragment shader places the map and the arrow from a uniform buffer.
+//!
+//! # Usage
+//!
+//! ```rust,ignore
+//! let mut minimap = MinimapRenderer::new(&device, &surface_config, &gpu_memory);
+//!
+//! // Each frame during play
+//! minimap.update(&device, &queue, &game_state, &hud);
+//! minimap.render(&mut render_pass);
+//! ```
+
+use crate::game::GameState;
+use crate::game::maze::generator::Cell;
+use crate::game::score::VisitedCells;
+use crate::math::coordinates;
+use crate::renderer::gpu_memory::{
+    GpuMemoryTracker, TextureCategory, TrackedTexture, create_tracked_texture,
+};
+use crate::renderer::pipeline_builder::{
+    BindGroupLayoutBuilder, PipelineBuilder, create_uniform_buffer,
+};
+use crate::renderer::safe_area::SafeArea;
+use wgpu;
+
+/// Longest side of the map as a fraction of the HUD height.
+const MAP_SIZE: f32 = 0.24;
+
+/// Gap between the map and the HUD's edges as a fraction of the HUD height.
+const MAP_MARGIN: f32 = 0.03;
+
+/// Length of the player arrow as a fraction of the HUD height.
+const MARKER_SIZE: f32 = 0.012;
+
+/// What a texel of the minimap texture shows.
+#[repr(u8)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MinimapCell {
+    /// Not explored yet, left transparent
+    Unknown = 0,
+    /// A wall beside an explored cell
+    Wall = 1,
+    /// A cell the player has walked through
+    Visited = 2,
+    /// The exit, shown whether or not it has been found
+    Exit = 3,
+}
+
+/// Works out what every cell of the map shows.
+///
+/// # Arguments
+/// * `maze_grid` - 2D grid of the maze where `true` marks a wall
+/// * `visited` - The cells the player has walked through this level
+/// * `exit_cell` - The maze's exit cell, if it has one
+/// * `cells` - Filled with one [`MinimapCell`] per grid cell, row by row from the top
+pub fn minimap_cells(
+    maze_grid: &[Vec<bool>],
+    visited: &VisitedCells,
+    exit_cell: Option<Cell>,
+    cells: &mut Vec<u8>,
+) {
+    cells.clear();
+    for (row, walls) in maze_grid.iter().enumerate() {
+        for (col, &wall) in walls.iter().enumerate() {
+            let state = if exit_cell == Some(Cell::new(row, col)) {
+                MinimapCell::Exit
+            } else if !wall && visited.is_visited(Cell::new(row, col)) {
+                MinimapCell::Visited
+            } else if wall && beside_visited(visited, row, col) {
+                MinimapCell::Wall
+            } else {
+                MinimapCell::Unknown
+            };
+            cells.push(state as u8);
+        }
+    }
+}
+
+/// Checks whether any of the eight cells around a grid cell has been visited.
+fn beside_visited(visited: &VisitedCells, row: usize, col: usize) -> bool {
+    (row.saturating_sub(1)..=row + 1).any(|r| {
+        (col.saturating_sub(1)..=col + 1)
+            .any(|c| (r, c) != (row, col) && visited.is_visited(Cell::new(r, c)))
+    })
+}
+
+/// Places the map in the bottom-left corner of the HUD.
+///
+/// # Arguments
+/// * `hud` - HUD safe area in pixels
+/// * `grid_size` - Columns and rows of the maze grid
+///
+/// # Returns
+/// The map's left edge, top edge, width, and height in pixels; its cells are square
+pub fn minimap_rect(hud: &SafeArea, grid_size: (usize, usize)) -> [f32; 4] {
+    let (cols, rows) = (grid_size.0.max(1) as f32, grid_size.1.max(1) as f32);
+    let cell = hud.height * MAP_SIZE / cols.max(rows);
+    let (width, height) = (cols * cell, rows * cell);
+    let margin = hud.height * MAP_MARGIN;
+    [
+        hud.x + margin,
+        hud.bottom() - margin - height,
+        width,
+        height,
+    ]
+}
+
+/// Finds where the player stands on the map, in cells.
+///
+/// # Arguments
+/// * `position` - The player's world position
+/// * `grid_size` - Columns and rows of the maze grid
+/// * `is_test_mode` - Whether the maze was built at the test mode floor size
+///
+/// # Returns
+/// The column and row, with fractions for where in the cell the player is
+pub fn player_map_position(
+    position: [f32; 3],
+    grid_size: (usize, usize),
+    is_test_mode: bool,
+) -> [f32; 2] {
+    let cell = coordinates::world_to_maze(position, grid_size, is_test_mode);
+    let center = coordinates::maze_to_world(&cell, grid_size, position[1], is_test_mode);
+    let cell_size = coordinates::calculate_cell_size(grid_size, is_test_mode);
+    let offset = |along: f32| (along / cell_size).clamp(-0.5, 0.5);
+    [
+        cell.col as f32 + 0.5 + offset(position[0] - center[0]),
+        cell.row as f32 + 0.5 + offset(position[2] - center[2]),
+    ]
+}
+
+/// Uniform data passed to the minimap shader.
+///
+/// # Memory Layout
+/// 48 bytes: a `vec4`, three `vec2`s, and two floats.
+#[repr(C)]
+#[derive(Debug, Copy, Clone, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct MinimapUniforms {
+    /// The map's left edge, top edge, width, and height in pixels
+    pub rect: [f32; 4],
+    /// Columns and rows of the maze grid
+    pub grid_size: [f32; 2],
+    /// Where the player stands, in cells
+    pub player: [f32; 2],
+    /// Unit vector the player faces, in cells; rows grow down the map
+    pub heading: [f32; 2],
+    /// 1.0 when the shader's sRGB colors must be converted to linear for an sRGB target
+    pub linear_output: f32,
+    /// Length of the player arrow in pixels
+    pub marker_size: f32,
+}
+
+/// The grid texture and the bind group that samples it.
+struct MinimapTexture {
+    /// One texel per grid cell
+    texture: TrackedTexture,
+    /// Binds the uniforms and the texture
+    bind_group: wgpu::BindGroup,
+    /// Columns and rows of the grid the texture was made for
+    grid_size: (usize, usize),
+}
+
+/// Draws the explored part of the maze as a small map in the corner of the HUD.
+pub struct MinimapRenderer {
+    /// Whether the map is drawn, toggled with M
+    pub visible: bool,
+    /// The render pipeline for the map
+    pipeline: wgpu::RenderPipeline,
+    /// GPU buffer containing the uniform data (MinimapUniforms)
+    uniform_buffer: wgpu::Buffer,
+    /// Layout of the bind group, kept to bind a new texture for each maze size
+    bind_group_layout: wgpu::BindGroupLayout,
+    /// The grid texture, once a maze has been loaded
+    texture: Option<MinimapTexture>,
+    /// This frame's cells, reused between frames
+    cells: Vec<u8>,
+    /// The cells last uploaded to the texture
+    uploaded: Vec<u8>,
+    /// Tracker the grid texture is counted in
+    gpu_memory: GpuMemoryTracker,
+    /// Value of [`MinimapUniforms::linear_output`] for the render target
+    linear_output: f32,
+}
+
+impl MinimapRenderer {
+    /// Creates a new minimap renderer, shown, with no maze yet.
+    ///
+    /// # Arguments
+    /// * `device` - The wgpu device for creating GPU resources
+    /// * `surface_config` - Surface configuration containing the target format
+    /// * `gpu_memory` - Tracker the grid texture is counted in as UI
+    pub fn new(
+        device: &wgpu::Device,
+        surface_config: &wgpu::SurfaceConfiguration,
+        gpu_memory: &GpuMemoryTracker,
+    ) -> Self {
+        // The map's colors are sRGB design values, so sRGB targets need them linearized
+        let linear_output = if surface_config.format.is_srgb() {
+            1.0
+        } else {
+            0.0
+        };
+
+        let uniform_buffer = create_uniform_buffer(
+            device,
+            &MinimapUniforms {
+                rect: [0.0; 4],
+                grid_size: [1.0, 1.0],
+                player: [0.0; 2],
+                heading: [0.0, -1.0],
+                linear_output,
+                marker_size: 0.0,
+            },
+            "Minimap Uniform Buffer",
+        );
+
+        let bind_group_layout = BindGroupLayoutBuilder::new(device)
+            .with_label("Minimap Bind Group Layout")
+            .with_uniform_buffer(0, wgpu::ShaderStages::FRAGMENT)
+            .with_texture(1, wgpu::ShaderStages::FRAGMENT)
+            .build();
+
+        let pipeline = PipelineBuilder::new(device, surface_config.format)
+            .with_label("Minimap Pipeline")
+            .with_shader(include_str!("../shaders/minimap.wgsl"))
+            .with_bind_group_layout(&bind_group_layout)
+            .with_alpha_blending()
+            .build();
+
+        Self {
+            visible: true,
+            pipeline,
+            uniform_buffer,
+            bind_group_layout,
+            texture: None,
+            cells: Vec::new(),
+            uploaded: Vec::new(),
+            gpu_memory: gpu_memory.clone(),
+            linear_output,
+        }
+    }
+
+    /// Brings the map up to date with the maze, the explored cells, and the player.
+    ///
+    /// # Arguments
+    /// * `device` - WebGPU device, for a new texture when the maze size changes
+    /// * `queue` - The wgpu command queue for texture and buffer uploads
+    /// * `game_state` - The maze, explored cells, exit, and player
+    /// * `hud` - HUD safe area in pixels
+    pub fn update(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        game_state: &GameState,
+        hud: &SafeArea,
+    ) {
+        let maze_grid = &game_state.collision_system.maze_grid;
+        let grid_size = (maze_grid.first().map_or(0, Vec::len), maze_grid.len());
+        if grid_size.0 == 0 || grid_size.1 == 0 {
+            self.texture = None;
+            return;
+        }
+
+        minimap_cells(
+            maze_grid,
+            &game_state.visited_cells,
+            game_state.exit_cell,
+            &mut self.cells,
+        );
+        if self
+            .texture
+            .as_ref()
+            .is_none_or(|texture| texture.grid_size != grid_size)
+        {
+            self.texture = Some(self.create_texture(device, grid_size));
+            self.uploaded.clear();
+        }
+        if let Some(texture) = &self.texture
+            && self.cells != self.uploaded
+        {
+            queue.write_texture(
+                wgpu::TexelCopyTextureInfo {
+                    texture: &texture.texture,
+                    mip_level: 0,
+                    origin: wgpu::Origin3d::ZERO,
+                    aspect: wgpu::TextureAspect::All,
+                },
+                &self.cells,
+                wgpu::TexelCopyBufferLayout {
+                    offset: 0,
+                    bytes_per_row: Some(grid_size.0 as u32),
+                    rows_per_image: Some(grid_size.1 as u32),
+                },
+                wgpu::Extent3d {
+                    width: grid_size.0 as u32,
+                    height: grid_size.1 as u32,
+                    depth_or_array_layers: 1,
+                },
+            );
+            self.uploaded.clone_from(&self.cells);
+        }
+
+        let player = &game_state.player;
+        let (sin_yaw, cos_yaw) = player.yaw.to_radians().sin_cos();
+        let uniforms = MinimapUniforms {
+            rect: minimap_rect(hud, grid_size),
+            grid_size: [grid_size.0 as f32, grid_size.1 as f32],
+            player: player_map_position(player.position, grid_size, game_state.is_test_mode),
+            // Forward is -z at yaw 0, and z grows with the row, down the map
+            heading: [-sin_yaw, -cos_yaw],
+            linear_output: self.linear_output,
+            marker_size: (hud.height * MARKER_SIZE).max(6.0),
+        };
+        queue.write_buffer(&self.uniform_buffer, 0, bytemuck::bytes_of(&uniforms));
+    }
+
+    /// Creates the grid texture and its bind group for a maze size.
+    fn create_texture(&self, device: &wgpu::Device, grid_size: (usize, usize)) -> MinimapTexture {
+        let texture = create_tracked_texture(
+            device,
+            &self.gpu_memory,
+            TextureCategory::Ui,
+            &wgpu::TextureDescriptor {
+                label: Some("Minimap Grid Texture"),
+                size: wgpu::Extent3d {
+                    width: grid_size.0 as u32,
+                    height: grid_size.1 as u32,
+                    depth_or_array_layers: 1,
+                },
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: wgpu::TextureDimension::D2,
+                format: wgpu::TextureFormat::R8Unorm,
+                usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
+                view_formats: &[],
+            },
+        );
+        let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("Minimap Bind Group"),
+            layout: &self.bind_group_layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: self.uniform_buffer.as_entire_binding(),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: wgpu::BindingResource::TextureView(&view),
+                },
+            ],
+        });
+        MinimapTexture {
+            texture,
+            bind_group,
+            grid_size,
+        }
+    }
+
+    /// Draws the map prepared by [`update`](Self::update); nothing before a maze is loaded.
+    ///
+    /// # Arguments
+    /// * `render_pass` - Active render pass to draw into
+    pub fn render(&self, render_pass: &mut wgpu::RenderPass) {
+        let Some(texture) = &self.texture else {
+            return;
+        };
+        render_pass.set_pipeline(&self.pipeline);
+        render_pass.set_bind_group(0, &texture.bind_group, &[]);
+        render_pass.draw(0..3, 0..1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::renderer::safe_area::DEFAULT_HUD_MAX_ASPECT;
+
+    /// A 5x5 grid with a ring of walls around a plus-shaped corridor.
+    fn plus_maze() -> Vec<Vec<bool>> {
+        let inner = 1..4;
+        (0..5)
+            .map(|row| {
+                (0..5)
+                    .map(|col| {
+                        let across = row == 2 && inner.contains(&col);
+                        let down = col == 2 && inner.contains(&row);
+                        !(across || down)
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_only_explored_cells_and_their_walls_are_shown() {
+        let grid = plus_maze();
+        let mut visited = VisitedCells::for_grid(&grid);
+        let mut cells = Vec::new();
+
+        minimap_cells(&grid, &visited, Some(Cell::new(1, 2)), &mut cells);
+        assert_eq!(cells.len(), 25);
+        // Before anything is explored only the exit shows
+        assert_eq!(cells[5 + 2], MinimapCell::Exit as u8);
+        assert_eq!(
+            cells
+                .iter()
+                .filter(|&&c| c != MinimapCell::Unknown as u8)
+                .count(),
+            1
+        );
+
+        visited.mark(Cell::new(2, 1));
+        minimap_cells(&grid, &visited, Some(Cell::new(1, 2)), &mut cells);
+        let at = |row: usize, col: usize| cells[row * 5 + col];
+        assert_eq!(at(2, 1), MinimapCell::Visited as u8);
+        // Walls around the explored cell, diagonals included
+        for (row, col) in [(1, 0), (1, 1), (2, 0), (3, 0), (3, 1)] {
+            assert_eq!(at(row, col), MinimapCell::Wall as u8, "({}, {})", row, col);
+        }
+        // Open cells next to it stay unexplored, and so do walls further away
+        assert_eq!(at(2, 2), MinimapCell::Unknown as u8);
+        assert_eq!(at(0, 4), MinimapCell::Unknown as u8);
+    }
+
+    #[test]
+    fn test_map_stays_in_the_hud_with_square_cells() {
+        for (width, height) in [(1920.0, 1080.0), (1280.0, 720.0), (320.0, 200.0)] {
+            let hud = SafeArea::centered(width, height, DEFAULT_HUD_MAX_ASPECT);
+            for grid_size in [(51, 51), (61, 31), (7, 41)] {
+                let [x, y, map_width, map_height] = minimap_rect(&hud, grid_size);
+                assert!(hud.contains(x, y, map_width, map_height));
+                let cell_width = map_width / grid_size.0 as f32;
+                let cell_height = map_height / grid_size.1 as f32;
+                assert!((cell_width - cell_height).abs() < 1e-3);
+            }
+        }
+    }
+
+    #[test]
+    fn test_player_position_matches_the_cell_centers() {
+        let grid_size = (9, 7);
+        let cell = Cell::new(3, 5);
+        let center = coordinates::maze_to_world(&cell, grid_size, 50.0, false);
+        let [col, row] = player_map_position(center, grid_size, false);
+        assert!((col - 5.5).abs() < 1e-3 && (row - 3.5).abs() < 1e-3);
+
+        // A quarter of a cell toward the next column
+        let cell_size = coordinates::calculate_cell_size(grid_size, false);
+        let nudged = [center[0] + cell_size * 0.25, center[1], center[2]];
+        let [col, _] = player_map_position(nudged, grid_size, false);
+        assert!((col - 5.75).abs() < 1e-3);
+    }
+}
@@ -11,17 +11,33 @@
 //! - `GameRenderer`: Main renderer coordinating all visual elements
 //! - `CompassRenderer`: Renders the directional compass overlay
 //! - `EnemyRenderer`: Handles enemy visualization and animation
+//! - `TrailRenderer`: Draws the fading trail the enemy leaves on the floor
+//! - `PathRenderer`: Marks the route of a click-to-move walk on the floor
+//! - `DecalRenderer`: Paints the arrows on the walls near the entrance
+//! - `AnomalyRenderer`: Draws the glowing anomaly some levels hide in a dead end
+//! - `SpawnTelegraphRenderer`: Raises a dark column over the cell a delayed enemy arrives in
+//! - `ParticleRenderer`: Draws dust and wall impact particles
 //! - `StarRenderer`: Creates animated starfield background effects
+//! - `PanoramaRenderer`: Draws a sky panorama in place of the starfield
 //! - `TimerBarRenderer`: Renders the time remaining indicator
 //! - `StaminaBarRenderer`: Displays player stamina levels
 //! - `DebugRenderer`: Development tools for debugging and visualization
+//! - `AmbientRenderer`: Drifts the level theme's dust or rain between the maze and the HUD
+//! - `VignetteRenderer`: Darkens the screen edges and fades to black during the capture sequence
+//! - `EnemyFaceRenderer`: Flashes the enemy's face over the screen when it catches the player
+//! - `HeartbeatRenderer`: Pulses and color-splits the 3D view in the timer's last seconds
+//! - `LightShaftRenderer`: Casts shafts of light from the exit's beacon past whatever hides it
+//! - `DeathFadeRenderer`: Fades the frozen moment of death in over the live view at game over
+//! - `MinimapRenderer`: Maps the explored part of the maze in the corner of the HUD
 //!
 //! # Rendering Pipeline
 //!
 //! The renderer uses a structured multi-pass approach:
-//! 1. **Background Pass**: Animated starfield using `StarRenderer`
+//! 1. **Background Pass**: Animated starfield using `StarRenderer`, or the sky panorama
+//!    using `PanoramaRenderer`, depending on the chosen background
 //! 2. **Geometry Pass**: Maze floors and walls with depth testing
-//! 3. **Entity Pass**: Enemies and interactive elements
+//! 3. **Entity Pass**: Enemies and interactive elements, then particles
+//!    and the ambient dust or rain
 //! 4. **UI Pass**: Compass, timer, stamina bars, and overlays
 //! 5. **Debug Pass**: Optional development overlays
 //!
@@ -35,7 +51,7 @@
 //!
 //! # Usage
 //!
-//! ```rust
+//! ```rust,ignore
 //! use mirador::renderer::game_renderer::GameRenderer;
 //! use wgpu::{Device, Queue, SurfaceConfiguration};
 //!
@@ -43,41 +59,114 @@
 //! let mut renderer = GameRenderer::new(&device, &queue, &surface_config);
 //!
 //! // Load textures
-//! renderer.load_ceiling_texture(&device, &queue)?;
+//! renderer.load_ceiling_texture(&device, &queue, &gpu_memory)?;
 //!
 //! // Update depth buffer on resize
-//! let depth_view = renderer.update_depth_texture(&device, width, height);
+//! let depth_view = renderer.update_depth_texture(&device, &gpu_memory, width, height);
 //!
 //! // Render frame
-//! renderer.render_game(&queue, &game_state, &mut pass, aspect_ratio);
+//! renderer.render_game(&queue, &game_state, &mut pass, aspect_ratio, &tuning);
 //! ```
 
+pub mod ambient;
+pub mod anomaly;
 pub mod compass;
+pub mod death_fade;
 pub mod debug;
+pub mod decal;
 pub mod enemy;
+pub mod enemy_face;
+pub mod enemy_skin;
 pub mod game_over;
+pub mod heartbeat;
+pub mod heatmap;
+pub mod light_shafts;
+pub mod minimap;
+pub mod panorama;
+pub mod path;
+pub mod reticle;
+pub mod spawn_telegraph;
 pub mod stamina_bar;
 pub mod stars;
 pub mod timer_bar;
+pub mod trail;
+pub mod vignette;
 
+use crate::assets;
+use crate::assets::registry::decode_image_or_placeholder;
 use crate::game::GameState;
 use crate::game::enemy::Enemy;
+use crate::game::maze::floors::Layer;
 use crate::math::deg_to_rad;
 use crate::math::mat::Mat4;
+use crate::renderer::game_renderer::anomaly::AnomalyRenderer;
 use crate::renderer::game_renderer::compass::CompassRenderer;
 use crate::renderer::game_renderer::debug::DebugRenderer;
+use crate::renderer::game_renderer::decal::DecalRenderer;
 use crate::renderer::game_renderer::enemy::EnemyRenderer;
+use crate::renderer::game_renderer::heatmap::HeatmapRenderer;
+use crate::renderer::game_renderer::path::PathRenderer;
+use crate::renderer::game_renderer::spawn_telegraph::SpawnTelegraphRenderer;
 use crate::renderer::game_renderer::stars::StarRenderer;
+use crate::renderer::game_renderer::trail::TrailRenderer;
+use crate::renderer::gpu_memory::{
+    GpuMemoryTracker, TextureCategory, TrackedTexture, create_tracked_texture,
+};
+use crate::renderer::particles::ParticleRenderer;
 use crate::renderer::pipeline_builder::PipelineBuilder;
-use crate::renderer::primitives::{Uniforms, Vertex};
-use crate::assets;
-use image;
+use crate::renderer::primitives::{Uniforms, Vertex, create_z_facing_wall};
+use crate::renderer::rectangle::RectangleRenderer;
+use crate::renderer::tuning::RenderTuning;
+use reticle::ReticleRenderer;
 use stamina_bar::StaminaBarRenderer;
-use std::time::Instant;
 use timer_bar::TimerBarRenderer;
 use wgpu;
 use wgpu::util::DeviceExt;
 
+/// Vertices in the entrance door's quad.
+const DOOR_VERTEX_COUNT: u32 = 6;
+
+/// Distance from the camera to the near clipping plane.
+pub const Z_NEAR: f32 = 0.1;
+
+/// Distance from the camera to the far clipping plane.
+pub const Z_FAR: f32 = 2000.0;
+
+/// Builds the 3D view's projection matrix.
+///
+/// # Arguments
+/// * `fov` - Vertical field of view in degrees
+/// * `aspect` - Width of the 3D view divided by its height
+pub fn camera_projection(fov: f32, aspect: f32) -> Mat4 {
+    Mat4::perspective(deg_to_rad(fov), aspect, Z_NEAR, Z_FAR)
+}
+
+/// Returns the camera the game is drawn from: the player's eye, blended into the overhead
+/// map view while it is engaged and nudged by any camera shake.
+///
+/// # Arguments
+/// * `game_state` - Current game state containing the player, map view and camera shake
+/// * `aspect` - Width of the 3D view divided by its height
+pub fn player_camera(game_state: &GameState, aspect: f32) -> SceneCamera {
+    let [shake_x, shake_y] = game_state.camera_shake.offset();
+    SceneCamera {
+        view: game_state
+            .map_view
+            .view_matrix(&game_state.player)
+            .multiply(&Mat4::translation(shake_x, shake_y, 0.0)),
+        projection: camera_projection(game_state.player.fov, aspect),
+    }
+}
+
+/// Camera a game scene is drawn from.
+#[derive(Debug, Clone, Copy)]
+pub struct SceneCamera {
+    /// World to camera transform
+    pub view: Mat4,
+    /// Camera to clip space transform
+    pub projection: Mat4,
+}
+
 /// Main renderer for the 3D maze game.
 ///
 /// The `GameRenderer` is responsible for rendering the complete 3D maze environment,
@@ -121,17 +210,29 @@ use wgpu::util::DeviceExt;
 /// - `pipeline` - Main render pipeline for maze geometry with depth testing and alpha blending
 /// - `vertex_buffer` - Combined vertex buffer containing both floor and wall geometry data
 /// - `vertex_count` - Total number of vertices to render from the combined buffer
+/// - `upper_floor_start` - First vertex of the upper floor on two-floor levels
+/// - `door_vertex_buffer` - The entrance door's quad, rewritten every frame as it slides
 /// - `uniform_buffer` - GPU buffer storing model-view-projection matrix for vertex transformations
 /// - `uniform_bind_group` - WebGPU bind group linking uniform buffer to shader binding point 0
 /// - `depth_texture` - Optional depth buffer for proper 3D occlusion (recreated on resize)
+/// - `sample_count` - Samples per pixel of the 3D view's pipelines and depth buffer
 /// - `star_renderer` - Background renderer for animated starfield effects
 /// - `debug_renderer` - Development tools for rendering bounding boxes and debug overlays
 /// - `compass_renderer` - Renders the directional compass overlay
 /// - `exit_position` - Optional coordinates of the maze exit for special rendering
 /// - `enemy_renderer` - Handles enemy visualization and animation
-/// - `start_time` - Tracks animation start time for time-based effects
+/// - `trail_renderer` - Draws the fading trail the enemy leaves on the floor
+/// - `path_renderer` - Marks the route of a click-to-move walk on the floor
+/// - `heatmap_renderer` - Debug overlay tinting each cell by its frame cost
+/// - `decal_renderer` - Paints the arrows on the walls near the entrance
+/// - `anomaly_renderer` - Draws the glowing anomaly some levels hide in a dead end
+/// - `spawn_telegraph_renderer` - Raises a dark column over the cell a delayed enemy arrives in
+/// - `particle_renderer` - Draws dust and wall impact particles
+/// - `time` - Animation time in seconds for time-based effects
 /// - `timer_bar_renderer` - Renders the time remaining indicator
 /// - `stamina_bar_renderer` - Displays player stamina levels
+/// - `reticle_renderer` - Draws the center-screen reticle
+/// - `letterbox_renderer` - Draws the black bars around a boxed 3D view
 /// - `ceiling_texture` - Optional texture for ceiling rendering
 /// - `ceiling_texture_view` - Texture view for ceiling rendering
 /// - `ceiling_sampler` - Sampler for ceiling texture filtering
@@ -143,12 +244,19 @@ pub struct GameRenderer {
     pub vertex_buffer: wgpu::Buffer,
     /// Total number of vertices to render from the combined buffer
     pub vertex_count: u32,
+    /// First vertex of the upper floor's geometry, which sits at the end of the buffer on
+    /// two-floor levels; the map view leaves it out while the player is downstairs
+    pub upper_floor_start: Option<u32>,
+    /// The entrance door's quad, rewritten every frame as it slides
+    pub door_vertex_buffer: wgpu::Buffer,
     /// GPU buffer storing model-view-projection matrix for vertex transformations
     pub uniform_buffer: wgpu::Buffer,
     /// WebGPU bind group linking uniform buffer to shader binding point 0
     pub uniform_bind_group: wgpu::BindGroup,
     /// Optional depth buffer for proper 3D occlusion (recreated on resize)
-    pub depth_texture: Option<wgpu::Texture>,
+    pub depth_texture: Option<TrackedTexture>,
+    /// Samples per pixel of the 3D view's pipelines and depth buffer
+    pub sample_count: u32,
     /// Background renderer for animated starfield effects
     pub star_renderer: StarRenderer,
     /// Development tools for rendering bounding boxes and debug overlays
@@ -159,14 +267,32 @@ pub struct GameRenderer {
     pub exit_position: Option<(f32, f32)>,
     /// Handles enemy visualization and animation
     pub enemy_renderer: EnemyRenderer,
-    /// Tracks animation start time for time-based effects
-    pub start_time: Instant,
+    /// Draws the fading trail the enemy leaves on the floor
+    pub trail_renderer: TrailRenderer,
+    /// Marks the route of a click-to-move walk on the floor
+    pub path_renderer: PathRenderer,
+    /// Debug overlay tinting each cell by its frame cost
+    pub heatmap_renderer: HeatmapRenderer,
+    /// Paints the arrows on the walls near the entrance
+    pub decal_renderer: DecalRenderer,
+    /// Draws the glowing anomaly some levels hide in a dead end
+    pub anomaly_renderer: AnomalyRenderer,
+    /// Raises a dark column over the cell a delayed enemy arrives in
+    pub spawn_telegraph_renderer: SpawnTelegraphRenderer,
+    /// Draws dust and wall impact particles
+    pub particle_renderer: ParticleRenderer,
+    /// Animation time in seconds for time-based effects, set by [`GameRenderer::animate`]
+    pub time: f32,
     /// Renders the time remaining indicator
     pub timer_bar_renderer: TimerBarRenderer,
     /// Displays player stamina levels
     pub stamina_bar_renderer: StaminaBarRenderer,
+    /// Draws the center-screen reticle
+    pub reticle_renderer: ReticleRenderer,
+    /// Draws the black bars around a boxed 3D view
+    pub letterbox_renderer: RectangleRenderer,
     /// Optional texture for ceiling rendering
-    pub ceiling_texture: Option<wgpu::Texture>,
+    pub ceiling_texture: Option<TrackedTexture>,
     /// Texture view for ceiling rendering
     pub ceiling_texture_view: Option<wgpu::TextureView>,
     /// Sampler for ceiling texture filtering
@@ -194,7 +320,7 @@ impl GameRenderer {
     ///
     /// # Examples
     ///
-    /// ```rust
+    /// ```rust,ignore
     /// use mirador::renderer::game_renderer::GameRenderer;
     /// use wgpu::{Device, Queue, SurfaceConfiguration};
     ///
@@ -212,6 +338,36 @@ impl GameRenderer {
         device: &wgpu::Device,
         queue: &wgpu::Queue,
         surface_config: &wgpu::SurfaceConfiguration,
+    ) -> Self {
+        Self::with_sample_count(device, queue, surface_config, 1)
+    }
+
+    /// Creates a `GameRenderer` whose 3D view is drawn with MSAA.
+    ///
+    /// The pipelines of everything drawn in the 3D view, and the depth buffer, use
+    /// `sample_count` samples per pixel, so the view must be drawn into a color target with
+    /// the same count and resolved afterwards. The HUD renderers draw on the resolved image
+    /// with one sample, since their quads are axis-aligned and don't alias.
+    ///
+    /// # Arguments
+    ///
+    /// * `device` - WebGPU device for creating GPU resources
+    /// * `queue` - WebGPU queue for command submission
+    /// * `surface_config` - Surface configuration for format and size information
+    /// * `sample_count` - Samples per pixel of the 3D view, `1` for no MSAA
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// use mirador::renderer::game_renderer::GameRenderer;
+    ///
+    /// let renderer = GameRenderer::with_sample_count(&device, &queue, &surface_config, 4);
+    /// ```
+    pub fn with_sample_count(
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        surface_config: &wgpu::SurfaceConfiguration,
+        sample_count: u32,
     ) -> Self {
         use crate::benchmarks::{BenchmarkConfig, Profiler};
 
@@ -222,6 +378,8 @@ impl GameRenderer {
             write_to_file: false,
             min_duration_threshold: std::time::Duration::from_micros(1),
             max_samples: 1000,
+            autosave_interval: std::time::Duration::from_secs(60),
+            max_sections: 256,
         });
 
         // Benchmark uniform buffer creation
@@ -293,6 +451,7 @@ impl GameRenderer {
                 stencil: wgpu::StencilState::default(),
                 bias: wgpu::DepthBiasState::default(),
             })
+            .with_sample_count(sample_count)
             .build();
         init_profiler.end_section("main_pipeline_creation");
 
@@ -304,17 +463,29 @@ impl GameRenderer {
             contents: bytemuck::cast_slice(&empty_vertices),
             usage: wgpu::BufferUsages::VERTEX,
         });
+        let door_vertex_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Entrance Door Vertex Buffer"),
+            size: (DOOR_VERTEX_COUNT as usize * std::mem::size_of::<Vertex>()) as u64,
+            usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
         init_profiler.end_section("vertex_buffer_creation");
 
         // Benchmark star renderer creation
         init_profiler.start_section("star_renderer_creation");
-        let star_renderer = stars::create_star_renderer(device, surface_config, 100);
+        let star_renderer = stars::create_star_renderer(
+            device,
+            surface_config,
+            RenderTuning::default().star_count,
+            sample_count,
+        );
         init_profiler.end_section("star_renderer_creation");
 
         // Benchmark debug renderer creation
         init_profiler.start_section("debug_renderer_creation");
         let debug_renderer = DebugRenderer {
             debug_render_bounding_boxes: false,
+            breathe_bounding_boxes: false,
             debug_vertex_buffer: None,
             debug_vertex_count: 0,
         };
@@ -328,9 +499,26 @@ impl GameRenderer {
         // Benchmark enemy renderer creation
         init_profiler.start_section("enemy_renderer_creation");
         let enemy = Enemy::new([-1370.0, 50.0, 1370.0], 100.0);
-        let enemy_renderer = EnemyRenderer::new(enemy, device, queue, surface_config);
+        let enemy_renderer = EnemyRenderer::new(enemy, device, queue, surface_config, sample_count);
         init_profiler.end_section("enemy_renderer_creation");
 
+        // Benchmark trail renderer creation
+        init_profiler.start_section("trail_renderer_creation");
+        let trail_renderer = TrailRenderer::new(device, surface_config, sample_count);
+        init_profiler.end_section("trail_renderer_creation");
+
+        let path_renderer = PathRenderer::new(device, surface_config, sample_count);
+        let heatmap_renderer = HeatmapRenderer::new(device, surface_config, sample_count);
+        let decal_renderer = DecalRenderer::new(device, surface_config, sample_count);
+        let anomaly_renderer = AnomalyRenderer::new(device, surface_config, sample_count);
+        let spawn_telegraph_renderer =
+            SpawnTelegraphRenderer::new(device, surface_config, sample_count);
+
+        // Benchmark particle renderer creation
+        init_profiler.start_section("particle_renderer_creation");
+        let particle_renderer = ParticleRenderer::new(device, surface_config, sample_count);
+        init_profiler.end_section("particle_renderer_creation");
+
         // Benchmark timer bar renderer creation
         init_profiler.start_section("timer_bar_renderer_creation");
         let timer_bar_renderer = TimerBarRenderer::new(device, surface_config);
@@ -341,21 +529,37 @@ impl GameRenderer {
         let stamina_bar_renderer = StaminaBarRenderer::new(device, surface_config);
         init_profiler.end_section("stamina_bar_renderer_creation");
 
+        let reticle_renderer = ReticleRenderer::new(device, surface_config);
+
+        let letterbox_renderer = RectangleRenderer::new(device, surface_config.format);
+
         Self {
             pipeline,
             vertex_buffer,
             vertex_count: 0, // Will be set when maze is loaded
+            upper_floor_start: None,
+            door_vertex_buffer,
             uniform_buffer,
             uniform_bind_group,
             depth_texture: None,
+            sample_count,
             star_renderer,
             debug_renderer,
             compass_renderer,
             exit_position: None,
             enemy_renderer,
-            start_time: Instant::now(), // Initialize start time
+            trail_renderer,
+            path_renderer,
+            heatmap_renderer,
+            decal_renderer,
+            anomaly_renderer,
+            spawn_telegraph_renderer,
+            particle_renderer,
+            time: 0.0,
             timer_bar_renderer,
             stamina_bar_renderer,
+            reticle_renderer,
+            letterbox_renderer,
             ceiling_texture: None,
             ceiling_texture_view: None,
             ceiling_sampler: None,
@@ -373,6 +577,7 @@ impl GameRenderer {
     ///
     /// * `device` - WebGPU device for creating GPU resources
     /// * `queue` - WebGPU queue for uploading texture data
+    /// * `gpu_memory` - Tracker the texture is counted in as a material
     ///
     /// # Returns
     ///
@@ -380,17 +585,17 @@ impl GameRenderer {
     ///
     /// # Examples
     ///
-    /// ```rust
+    /// ```rust,ignore
     /// use mirador::renderer::game_renderer::GameRenderer;
     /// use wgpu::{Device, Queue};
     ///
     /// let mut renderer = GameRenderer::new(&device, &queue, &surface_config);
-    /// renderer.load_ceiling_texture(&device, &queue)?;
+    /// renderer.load_ceiling_texture(&device, &queue, &gpu_memory)?;
     /// ```
     ///
     /// # Texture Details
     ///
-    /// - Loads texture from `assets/tiles.jpg`
+    /// - Loads texture from `assets/tiles.jpg`, or a magenta placeholder if it is broken
     /// - Creates RGBA8 texture with sRGB format
     /// - Uses repeat addressing for seamless tiling
     /// - Linear filtering for smooth texture interpolation
@@ -399,10 +604,11 @@ impl GameRenderer {
         &mut self,
         device: &wgpu::Device,
         queue: &wgpu::Queue,
+        gpu_memory: &GpuMemoryTracker,
     ) -> Result<(), Box<dyn std::error::Error>> {
-        // Load the tiles texture from embedded assets
-        let img = image::load_from_memory(assets::TILES_IMAGE)?;
-        let rgba = img.to_rgba8();
+        // Load the tiles texture from embedded assets, substituting a magenta
+        // placeholder if it is broken so the ceiling is never silently untextured
+        let rgba = decode_image_or_placeholder("ceiling tiles", assets::TILES_IMAGE);
         let dimensions = rgba.dimensions();
 
         let texture_size = wgpu::Extent3d {
@@ -411,16 +617,21 @@ impl GameRenderer {
             depth_or_array_layers: 1,
         };
 
-        let texture = device.create_texture(&wgpu::TextureDescriptor {
-            label: Some("Ceiling Texture"),
-            size: texture_size,
-            mip_level_count: 1,
-            sample_count: 1,
-            dimension: wgpu::TextureDimension::D2,
-            format: wgpu::TextureFormat::Rgba8UnormSrgb,
-            usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
-            view_formats: &[],
-        });
+        let texture = create_tracked_texture(
+            device,
+            gpu_memory,
+            TextureCategory::Materials,
+            &wgpu::TextureDescriptor {
+                label: Some("Ceiling Texture"),
+                size: texture_size,
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: wgpu::TextureDimension::D2,
+                format: wgpu::TextureFormat::Rgba8UnormSrgb,
+                usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
+                view_formats: &[],
+            },
+        );
 
         queue.write_texture(
             wgpu::TexelCopyTextureInfo {
@@ -525,6 +736,7 @@ impl GameRenderer {
     /// # Arguments
     ///
     /// * `device` - WebGPU device for creating GPU resources
+    /// * `gpu_memory` - Tracker the depth buffer is counted in as a scene target
     /// * `width` - New width of the surface
     /// * `height` - New height of the surface
     ///
@@ -534,12 +746,12 @@ impl GameRenderer {
     ///
     /// # Examples
     ///
-    /// ```rust
+    /// ```rust,ignore
     /// use mirador::renderer::game_renderer::GameRenderer;
     /// use wgpu::Device;
     ///
     /// let mut renderer = GameRenderer::new(&device, &queue, &surface_config);
-    /// let depth_view = renderer.update_depth_texture(&device, 1920, 1080);
+    /// let depth_view = renderer.update_depth_texture(&device, &gpu_memory, 1920, 1080);
     /// ```
     ///
     /// # Memory Management
@@ -547,9 +759,11 @@ impl GameRenderer {
     /// - Automatically drops old depth texture when recreating
     /// - Only recreates when dimensions actually change
     /// - Uses Depth24Plus format for optimal precision and performance
+    /// - Has [`GameRenderer::sample_count`] samples per pixel, matching the pipelines
     pub fn update_depth_texture(
         &mut self,
         device: &wgpu::Device,
+        gpu_memory: &GpuMemoryTracker,
         width: u32,
         height: u32,
     ) -> wgpu::TextureView {
@@ -572,20 +786,27 @@ impl GameRenderer {
                 drop(depth_texture);
             }
 
-            self.depth_texture = Some(device.create_texture(&wgpu::TextureDescriptor {
-                label: Some("Depth Texture"),
-                size: wgpu::Extent3d {
-                    width,
-                    height,
-                    depth_or_array_layers: 1,
+            self.depth_texture = Some(create_tracked_texture(
+                device,
+                gpu_memory,
+                TextureCategory::SceneTargets,
+                &wgpu::TextureDescriptor {
+                    label: Some("Depth Texture"),
+                    size: wgpu::Extent3d {
+                        width,
+                        height,
+                        depth_or_array_layers: 1,
+                    },
+                    mip_level_count: 1,
+                    sample_count: self.sample_count,
+                    dimension: wgpu::TextureDimension::D2,
+                    format: wgpu::TextureFormat::Depth24Plus,
+                    // Post effects such as the light shafts read it back
+                    usage: wgpu::TextureUsages::RENDER_ATTACHMENT
+                        | wgpu::TextureUsages::TEXTURE_BINDING,
+                    view_formats: &[],
                 },
-                mip_level_count: 1,
-                sample_count: 1,
-                dimension: wgpu::TextureDimension::D2,
-                format: wgpu::TextureFormat::Depth24Plus,
-                usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
-                view_formats: &[],
-            }));
+            ));
         }
         self.depth_texture
             .as_ref()
@@ -593,6 +814,21 @@ impl GameRenderer {
             .create_view(&wgpu::TextureViewDescriptor::default())
     }
 
+    /// Advances the scene's animations by one frame.
+    ///
+    /// Stores the animation time for the maze shader and turns the enemy toward the
+    /// player. Called once per frame before [`GameRenderer::render_game`], which only
+    /// uploads the results.
+    ///
+    /// # Arguments
+    ///
+    /// * `game_state` - Current game state containing player and enemy information
+    /// * `time` - Animation time in seconds
+    pub fn animate(&mut self, game_state: &GameState, time: f32) {
+        self.time = time;
+        self.enemy_renderer.animate(game_state, time);
+    }
+
     /// Renders the complete game scene including maze, enemies, and UI elements.
     ///
     /// This is the main rendering method that coordinates all visual elements
@@ -604,30 +840,40 @@ impl GameRenderer {
     /// * `queue` - WebGPU queue for command submission
     /// * `game_state` - Current game state containing player and enemy information
     /// * `pass` - Render pass to record drawing commands
-    /// * `aspect` - Aspect ratio of the surface for projection calculations
+    /// * `aspect` - Aspect ratio of the 3D view's viewport for projection calculations
+    /// * `tuning` - Live renderer parameters, used here for distance fog and the ambient
+    ///   multiplier
+    /// * `reduce_effects` - Whether the player has asked for reduced visual effects, which
+    ///   keeps breathing walls still
     ///
     /// # Examples
     ///
-    /// ```rust
+    /// ```rust,ignore
     /// use mirador::renderer::game_renderer::GameRenderer;
     /// use mirador::game::GameState;
+    /// use mirador::renderer::tuning::RenderTuning;
     /// use wgpu::{Queue, RenderPass};
     ///
     /// let mut renderer = GameRenderer::new(&device, &queue, &surface_config);
-    /// renderer.render_game(&queue, &game_state, &mut pass, 16.0 / 9.0);
+    /// renderer.render_game(&queue, &game_state, &mut pass, 16.0 / 9.0, &RenderTuning::default(), false);
     /// ```
     ///
     /// # Rendering Order
     ///
     /// The method renders elements in this order:
     /// 1. **Maze/Floor**: Main geometry with depth testing
-    /// 2. **Enemies**: Animated enemy entities
-    /// 3. **UI Elements**: Compass, timer, stamina bars (handled separately)
+    /// 2. **Enemy Trail**: Fading breadcrumbs on the floor, drawn regardless of
+    ///    whether the enemy itself is visible
+    /// 3. **Enemies**: Animated enemy entities
+    /// 4. **UI Elements**: Compass, timer, stamina bars (handled separately)
     ///
     /// # Matrix Calculations
     ///
-    /// - View matrix from player camera position and orientation
-    /// - Projection matrix with configurable FOV and aspect ratio
+    /// - View matrix from player camera position and orientation, blended with the
+    ///   overhead map view while it is engaged
+    /// - Projection matrix with configurable FOV and aspect ratio. The player's FOV is
+    ///   vertical and held constant, so wider viewports see more to the sides rather than
+    ///   less above and below; a boxed view caps how far that goes
     /// - Combined view-projection matrix for efficient rendering
     /// - Model matrix for floor (identity) and individual enemy transforms
     pub fn render_game(
@@ -636,16 +882,40 @@ impl GameRenderer {
         game_state: &GameState,
         pass: &mut wgpu::RenderPass,
         aspect: f32,
+        tuning: &RenderTuning,
+        reduce_effects: bool,
     ) {
-        // Calculate view and projection matrices once
-        let view_matrix = game_state.player.get_view_matrix();
-        let projection_matrix = Mat4::perspective(
-            deg_to_rad(game_state.player.fov),
-            aspect,
-            0.1,    // zNear
-            2000.0, // zFar
-        );
-        let view_proj_matrix = view_matrix.multiply(&projection_matrix);
+        let camera = player_camera(game_state, aspect);
+        self.render_scene(queue, game_state, pass, &camera, tuning, reduce_effects);
+    }
+
+    /// Renders the game scene from a given camera instead of the player's.
+    ///
+    /// Draws everything [`GameRenderer::render_game`] draws, in the same order. Used for
+    /// views that follow something other than the player, like the level-end replay.
+    ///
+    /// # Arguments
+    ///
+    /// * `queue` - WebGPU queue for command submission
+    /// * `game_state` - Current game state containing the enemy and everything on the floor
+    /// * `pass` - Render pass to record drawing commands
+    /// * `camera` - View and projection to draw the scene with
+    /// * `tuning` - Live renderer parameters, used here for distance fog and the ambient
+    ///   multiplier
+    /// * `reduce_effects` - Whether the player has asked for reduced visual effects, which
+    ///   keeps breathing walls still
+    pub fn render_scene(
+        &mut self,
+        queue: &wgpu::Queue,
+        game_state: &GameState,
+        pass: &mut wgpu::RenderPass,
+        camera: &SceneCamera,
+        tuning: &RenderTuning,
+        reduce_effects: bool,
+    ) {
+        // Calculate the view-projection matrix once for everything drawn
+        let view_matrix = camera.view;
+        let view_proj_matrix = view_matrix.multiply(&camera.projection);
 
         // ==============================================
         // 1. RENDER MAZE/FLOOR FIRST
@@ -657,13 +927,25 @@ impl GameRenderer {
             // Combine matrices: Projection * View * Model
             let final_mvp_matrix = model_matrix.multiply(&view_proj_matrix);
 
-            // Calculate elapsed time for animation
-            let elapsed = self.start_time.elapsed().as_secs_f32();
-
             let uniforms = Uniforms {
                 matrix: final_mvp_matrix.into(),
-                time: elapsed,
-                _padding: [0.0; 7],
+                time: self.time,
+                fog_start: tuning.fog_start,
+                fog_end: tuning.fog_end,
+                fog_strength: tuning.fog_strength,
+                fog_color: tuning.fog_color,
+                wall_breath: if reduce_effects {
+                    0.0
+                } else {
+                    game_state.wall_breath
+                },
+                bounding_box_breath: if self.debug_renderer.breathe_bounding_boxes {
+                    1.0
+                } else {
+                    0.0
+                },
+                ambient: tuning.ambient,
+                _padding: 0.0,
             };
 
             // Upload uniform values for the maze/floor
@@ -681,7 +963,42 @@ impl GameRenderer {
                     pass.set_bind_group(0, &self.uniform_bind_group, &[]);
                 }
 
-                pass.draw(0..self.vertex_count, 0..1);
+                // Looking down from above the lower floor, the upper floor would hide it
+                let vertex_count = self
+                    .upper_floor_start
+                    .filter(|_| {
+                        game_state.map_view.is_engaged() && game_state.player.layer == Layer::Lower
+                    })
+                    .unwrap_or(self.vertex_count);
+                pass.draw(0..vertex_count, 0..1);
+
+                // The entrance door slides out of the outer wall, so it stays hidden while open
+                if let Some(door) = game_state
+                    .entrance_door
+                    .as_ref()
+                    .filter(|door| door.progress() > 0.0)
+                {
+                    let span = door.span;
+                    // The door holds still while it slides, even when the walls breathe
+                    let vertices = create_z_facing_wall(
+                        span.x + door.slide_offset(),
+                        0.0,
+                        span.z,
+                        span.width,
+                        span.height,
+                    )
+                    .map(|vertex| Vertex {
+                        normal: [0.0; 3],
+                        ..vertex
+                    });
+                    queue.write_buffer(
+                        &self.door_vertex_buffer,
+                        0,
+                        bytemuck::cast_slice(&vertices),
+                    );
+                    pass.set_vertex_buffer(0, self.door_vertex_buffer.slice(..));
+                    pass.draw(0..DOOR_VERTEX_COUNT, 0..1);
+                }
             }
 
             // Debug rendering for maze/floor
@@ -696,9 +1013,27 @@ impl GameRenderer {
         }
 
         // ==============================================
-        // 2. RENDER ENEMIES
+        // 2. RENDER WALL SIGNS, ENEMY TRAIL, CLICK-TO-MOVE PATH AND FRAME HEATMAP
         // ==============================================
         {
+            self.decal_renderer
+                .update(queue, game_state, view_proj_matrix.0);
+            self.decal_renderer.render(pass);
+            self.trail_renderer
+                .update(queue, game_state, view_proj_matrix.0);
+            self.trail_renderer.render(pass);
+            self.path_renderer
+                .update(queue, game_state, view_proj_matrix.0);
+            self.path_renderer.render(pass);
+            self.heatmap_renderer
+                .update(queue, game_state, view_proj_matrix.0);
+            self.heatmap_renderer.render(pass);
+        }
+
+        // ==============================================
+        // 3. RENDER ENEMIES
+        // ==============================================
+        if game_state.enemy_present() {
             // Update enemy transform with the combined view-projection matrix
             self.enemy_renderer.update(
                 queue,
@@ -709,5 +1044,37 @@ impl GameRenderer {
             // Actually render the enemy
             self.enemy_renderer.render(pass);
         }
+
+        // ==============================================
+        // 4. RENDER THE ANOMALY
+        // ==============================================
+        {
+            self.anomaly_renderer
+                .update(queue, game_state, view_proj_matrix.0, self.time);
+            self.anomaly_renderer.render(pass);
+        }
+
+        // ==============================================
+        // 5. RENDER THE SPAWN TELEGRAPH
+        // ==============================================
+        {
+            self.spawn_telegraph_renderer
+                .update(queue, game_state, view_proj_matrix.0, self.time);
+            self.spawn_telegraph_renderer.render(pass);
+        }
+
+        // ==============================================
+        // 6. RENDER PARTICLES
+        // ==============================================
+        {
+            // Drawn last since particles are blended over everything without writing depth
+            self.particle_renderer.update(
+                queue,
+                &game_state.particles,
+                &view_matrix,
+                view_proj_matrix.0,
+            );
+            self.particle_renderer.render(pass);
+        }
     }
 }
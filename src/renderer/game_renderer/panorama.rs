@@ -0,0 +1,336 @@
+//! Panorama Renderer Module
+//!
+//! This module draws an equirectangular panorama as the sky behind the maze, in place of the
+//! starfield when the [`BackgroundMode::Panorama`] background is chosen. The panorama is drawn
+//! with a full-screen triangle whose fragments each rebuild their view ray from the inverse of
+//! the camera's rotation-only view-projection (see [`crate::math::ray`]), so the sky turns
+//! with the player and never moves as they walk.
+//!
+//! The pipeline is built once at startup. The texture is loaded on a background thread by a
+//! [`PanoramaLoader`] the first time the panorama is asked for, and kept afterwards, so
+//! switching between the two backgrounds costs nothing. Until it is ready the starfield is
+//! drawn instead.
+
+use crate::assets::panorama::{PanoramaImage, PanoramaLoader};
+use crate::math::mat::Mat4;
+use crate::math::ray::sky_ray_matrix;
+use crate::paths;
+use crate::renderer::gpu_memory::{
+    GpuMemoryTracker, TextureCategory, TrackedTexture, create_tracked_texture,
+};
+use crate::renderer::pipeline_builder::{
+    BindGroupLayoutBuilder, PipelineBuilder, create_uniform_buffer,
+};
+use serde::{Deserialize, Serialize};
+use wgpu;
+
+/// What is drawn behind the maze.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BackgroundMode {
+    /// The procedural, twinkling starfield
+    #[default]
+    Stars,
+    /// An equirectangular sky panorama, the player's own if they supplied one
+    Panorama,
+}
+
+impl BackgroundMode {
+    /// Returns the mode after this one, wrapping around.
+    pub fn next(self) -> Self {
+        match self {
+            Self::Stars => Self::Panorama,
+            Self::Panorama => Self::Stars,
+        }
+    }
+
+    /// Returns a short label for menus.
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Stars => "Stars",
+            Self::Panorama => "Pano",
+        }
+    }
+}
+
+/// Uniform data passed to the panorama shader.
+///
+/// # Memory Layout
+/// 64 bytes: a single matrix.
+#[repr(C)]
+#[derive(Debug, Copy, Clone, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct PanoramaUniforms {
+    /// Inverse of the rotation-only view-projection, from [`sky_ray_matrix`]
+    pub inverse_view_proj: [[f32; 4]; 4],
+}
+
+/// The uploaded panorama texture and what the renderer needs from it.
+struct PanoramaTexture {
+    /// The texture with its whole mip chain; kept alive for the bind group
+    _texture: TrackedTexture,
+    /// Bind group of the texture view and its sampler
+    bind_group: wgpu::BindGroup,
+    /// Linear RGBA average color of the panorama
+    average_color: [f32; 4],
+}
+
+/// Renders the sky panorama background.
+pub struct PanoramaRenderer {
+    /// The WebGPU render pipeline for the panorama
+    pub pipeline: wgpu::RenderPipeline,
+    /// GPU buffer containing the panorama uniforms
+    pub uniform_buffer: wgpu::Buffer,
+    /// Bind group that associates the uniform buffer with shader bindings
+    pub uniform_bind_group: wgpu::BindGroup,
+    /// Layout of the texture bind group, for when the panorama arrives
+    texture_bind_group_layout: wgpu::BindGroupLayout,
+    /// Format the texture is created in, matched to the render target's encoding
+    texture_format: wgpu::TextureFormat,
+    /// The panorama, once it has loaded
+    texture: Option<PanoramaTexture>,
+    /// The load in progress, if any
+    loader: Option<PanoramaLoader>,
+    /// Whether loading failed, so it isn't retried every frame
+    failed: bool,
+}
+
+impl PanoramaRenderer {
+    /// Creates a new panorama renderer with no panorama loaded.
+    ///
+    /// # Arguments
+    /// * `device` - WebGPU device for creating GPU resources
+    /// * `surface_config` - Surface configuration containing the target pixel format
+    /// * `sample_count` - Samples per pixel of the render target the 3D view is drawn into
+    pub fn new(
+        device: &wgpu::Device,
+        surface_config: &wgpu::SurfaceConfiguration,
+        sample_count: u32,
+    ) -> Self {
+        let uniforms = PanoramaUniforms {
+            inverse_view_proj: Mat4::identity().into(),
+        };
+        let uniform_buffer = create_uniform_buffer(device, &uniforms, "Panorama Uniform Buffer");
+
+        let uniform_bind_group_layout = BindGroupLayoutBuilder::new(device)
+            .with_label("Panorama Uniform Bind Group Layout")
+            .with_uniform_buffer(0, wgpu::ShaderStages::FRAGMENT)
+            .build();
+        let texture_bind_group_layout = BindGroupLayoutBuilder::new(device)
+            .with_label("Panorama Texture Bind Group Layout")
+            .with_texture(0, wgpu::ShaderStages::FRAGMENT)
+            .with_sampler(1, wgpu::ShaderStages::FRAGMENT)
+            .build();
+
+        let uniform_bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            layout: &uniform_bind_group_layout,
+            entries: &[wgpu::BindGroupEntry {
+                binding: 0,
+                resource: uniform_buffer.as_entire_binding(),
+            }],
+            label: Some("Panorama Uniform Bind Group"),
+        });
+
+        let pipeline = PipelineBuilder::new(device, surface_config.format)
+            .with_label("Panorama Pipeline")
+            .with_shader(include_str!("../shaders/panorama.wgsl"))
+            .with_bind_group_layout(&uniform_bind_group_layout)
+            .with_bind_group_layout(&texture_bind_group_layout)
+            .with_sample_count(sample_count)
+            .build();
+
+        // Sampling an sRGB texture decodes to linear, which an sRGB target encodes again;
+        // other targets take the panorama's sRGB values as they are
+        let texture_format = if surface_config.format.is_srgb() {
+            wgpu::TextureFormat::Rgba8UnormSrgb
+        } else {
+            wgpu::TextureFormat::Rgba8Unorm
+        };
+
+        Self {
+            pipeline,
+            uniform_buffer,
+            uniform_bind_group,
+            texture_bind_group_layout,
+            texture_format,
+            texture: None,
+            loader: None,
+            failed: false,
+        }
+    }
+
+    /// Returns whether the panorama has loaded and can be drawn.
+    pub fn is_ready(&self) -> bool {
+        self.texture.is_some()
+    }
+
+    /// Returns the panorama's linear RGBA average color, once it has loaded.
+    pub fn average_color(&self) -> Option<[f32; 4]> {
+        self.texture.as_ref().map(|texture| texture.average_color)
+    }
+
+    /// Starts loading the panorama when it is first wanted, and uploads it once it arrives.
+    ///
+    /// Should be called once per frame.
+    ///
+    /// # Arguments
+    /// * `device` - WebGPU device for creating the texture
+    /// * `queue` - WebGPU queue for uploading the texture
+    /// * `gpu_memory` - Tracker the texture is counted in
+    /// * `wanted` - Whether the panorama background is chosen
+    pub fn update(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        gpu_memory: &GpuMemoryTracker,
+        wanted: bool,
+    ) {
+        if wanted && self.texture.is_none() && self.loader.is_none() && !self.failed {
+            self.loader = Some(PanoramaLoader::start(&paths::get().custom_panorama()));
+        }
+
+        let Some(result) = self.loader.as_ref().and_then(PanoramaLoader::poll) else {
+            return;
+        };
+        self.loader = None;
+        match result {
+            Ok(panorama) => self.upload(device, queue, gpu_memory, &panorama),
+            Err(e) => {
+                eprintln!("{}", e);
+                self.failed = true;
+            }
+        }
+    }
+
+    /// Creates the texture for a decoded panorama and uploads its mip chain.
+    fn upload(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        gpu_memory: &GpuMemoryTracker,
+        panorama: &PanoramaImage,
+    ) {
+        let (width, height) = panorama.mips[0].1;
+        let texture = create_tracked_texture(
+            device,
+            gpu_memory,
+            TextureCategory::Materials,
+            &wgpu::TextureDescriptor {
+                label: Some("Panorama Texture"),
+                size: wgpu::Extent3d {
+                    width,
+                    height,
+                    depth_or_array_layers: 1,
+                },
+                mip_level_count: panorama.mips.len() as u32,
+                sample_count: 1,
+                dimension: wgpu::TextureDimension::D2,
+                format: self.texture_format,
+                usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
+                view_formats: &[],
+            },
+        );
+
+        for (mip_level, (pixels, (width, height))) in panorama.mips.iter().enumerate() {
+            queue.write_texture(
+                wgpu::TexelCopyTextureInfo {
+                    aspect: wgpu::TextureAspect::All,
+                    texture: &texture,
+                    mip_level: mip_level as u32,
+                    origin: wgpu::Origin3d::ZERO,
+                },
+                pixels,
+                wgpu::TexelCopyBufferLayout {
+                    offset: 0,
+                    bytes_per_row: Some(4 * width),
+                    rows_per_image: Some(*height),
+                },
+                wgpu::Extent3d {
+                    width: *width,
+                    height: *height,
+                    depth_or_array_layers: 1,
+                },
+            );
+        }
+
+        // Wraps around the horizon, but not over the poles
+        let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+        let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+            address_mode_u: wgpu::AddressMode::Repeat,
+            address_mode_v: wgpu::AddressMode::ClampToEdge,
+            address_mode_w: wgpu::AddressMode::ClampToEdge,
+            mag_filter: wgpu::FilterMode::Linear,
+            min_filter: wgpu::FilterMode::Linear,
+            mipmap_filter: wgpu::FilterMode::Linear,
+            ..Default::default()
+        });
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            layout: &self.texture_bind_group_layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: wgpu::BindingResource::TextureView(&view),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: wgpu::BindingResource::Sampler(&sampler),
+                },
+            ],
+            label: Some("Panorama Texture Bind Group"),
+        });
+
+        self.texture = Some(PanoramaTexture {
+            _texture: texture,
+            bind_group,
+            average_color: panorama.average_color,
+        });
+    }
+
+    /// Uploads this frame's camera.
+    ///
+    /// # Arguments
+    /// * `queue` - WebGPU command queue for GPU operations
+    /// * `view` - The camera's view matrix; its translation is ignored
+    /// * `projection` - The camera's projection matrix
+    pub fn update_uniforms(&self, queue: &wgpu::Queue, view: &Mat4, projection: &Mat4) {
+        let Some(inverse_view_proj) = sky_ray_matrix(view, projection) else {
+            return;
+        };
+        let uniforms = PanoramaUniforms {
+            inverse_view_proj: inverse_view_proj.into(),
+        };
+        queue.write_buffer(&self.uniform_buffer, 0, bytemuck::cast_slice(&[uniforms]));
+    }
+
+    /// Renders the panorama to the current render pass.
+    ///
+    /// Draws nothing until the panorama has loaded.
+    ///
+    /// # Arguments
+    /// * `render_pass` - Active render pass whose viewport covers the 3D view
+    pub fn render(&self, render_pass: &mut wgpu::RenderPass) {
+        let Some(texture) = &self.texture else {
+            return;
+        };
+        render_pass.set_pipeline(&self.pipeline);
+        render_pass.set_bind_group(0, &self.uniform_bind_group, &[]);
+        render_pass.set_bind_group(1, &texture.bind_group, &[]);
+        render_pass.draw(0..3, 0..1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::renderer::uniform_layout::assert_matches_shader;
+
+    #[test]
+    fn test_uniforms_match_the_shader_layout() {
+        assert_matches_shader::<PanoramaUniforms>(include_str!("../shaders/panorama.wgsl"));
+    }
+
+    #[test]
+    fn test_stars_stay_the_default_and_modes_cycle() {
+        assert_eq!(BackgroundMode::default(), BackgroundMode::Stars);
+        assert_eq!(BackgroundMode::Stars.next(), BackgroundMode::Panorama);
+        assert_eq!(BackgroundMode::Panorama.next(), BackgroundMode::Stars);
+    }
+}
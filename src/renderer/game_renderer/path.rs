@@ -0,0 +1,238 @@
+//! Click-to-move path rendering module for the Mirador game.
+//!
+//! This module draws the rest of the player's [`AutoWalk`] route as small, soft markers on the
+//! floor, one per cell, with a ring marking the destination. Like the enemy trail, all markers
+//! are drawn with a single instanced draw call from one instance buffer that is overwritten in
+//! place every frame, and they are alpha blended without writing depth so walls still hide them.
+
+use crate::game::GameState;
+use crate::game::auto_walk::AutoWalk;
+use crate::renderer::pipeline_builder::{
+    BindGroupLayoutBuilder, PipelineBuilder, create_uniform_buffer,
+};
+use wgpu::{self, util::DeviceExt};
+
+/// Most markers drawn at once; routes longer than this only show their next cells.
+pub const MAX_PATH_MARKERS: usize = 256;
+
+/// Width of a marker along the way in world units.
+const MARKER_SIZE: f32 = 10.0;
+
+/// Height the markers are lifted above the floor beneath them.
+const MARKER_LIFT: f32 = 1.0;
+
+/// Marker color as linear RGBA; kept faint so the route guides without cluttering the floor.
+const MARKER_COLOR: [f32; 4] = [0.55, 0.8, 1.0, 0.35];
+
+/// Uniform data structure for the path shader.
+#[repr(C)]
+#[derive(Copy, Clone, Debug, bytemuck::Pod, bytemuck::Zeroable)]
+struct PathUniforms {
+    /// View-projection matrix for transforming vertices to screen space
+    view_proj_matrix: [[f32; 4]; 4],
+    /// Marker color
+    color: [f32; 4],
+    /// Width of a marker along the way in world units
+    base_size: f32,
+    /// Height the markers are lifted above the floor
+    lift: f32,
+    /// Padding for proper memory alignment
+    _padding: [f32; 2],
+}
+
+/// Per-marker instance data.
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Default, bytemuck::Pod, bytemuck::Zeroable)]
+struct PathInstance {
+    /// Marker world position (x, y, z) followed by 1.0 for the destination, otherwise 0.0
+    position_destination: [f32; 4],
+}
+
+/// Renders the click-to-move route as instanced floor quads.
+pub struct PathRenderer {
+    /// The render pipeline for path rendering
+    pipeline: wgpu::RenderPipeline,
+    /// Vertex buffer containing the unit quad corners
+    vertex_buffer: wgpu::Buffer,
+    /// Instance buffer holding one entry per marker slot
+    instance_buffer: wgpu::Buffer,
+    /// Uniform buffer containing shader uniforms
+    uniform_buffer: wgpu::Buffer,
+    /// Bind group containing the uniforms
+    bind_group: wgpu::BindGroup,
+    /// Number of instances written by the last update
+    instance_count: u32,
+}
+
+impl PathRenderer {
+    /// Creates a new path renderer.
+    ///
+    /// # Arguments
+    ///
+    /// * `device` - WGPU device for creating GPU resources
+    /// * `surface_config` - Surface configuration for pipeline creation
+    /// * `sample_count` - Samples per pixel of the render target the 3D view is drawn into
+    ///
+    /// # Returns
+    ///
+    /// A new `PathRenderer` with an empty instance buffer sized for [`MAX_PATH_MARKERS`].
+    pub fn new(
+        device: &wgpu::Device,
+        surface_config: &wgpu::SurfaceConfiguration,
+        sample_count: u32,
+    ) -> Self {
+        let uniforms = PathUniforms {
+            view_proj_matrix: [[0.0; 4]; 4],
+            color: MARKER_COLOR,
+            base_size: MARKER_SIZE,
+            lift: MARKER_LIFT,
+            _padding: [0.0; 2],
+        };
+        let uniform_buffer = create_uniform_buffer(device, &uniforms, "Path Uniform Buffer");
+
+        let bind_group_layout = BindGroupLayoutBuilder::new(device)
+            .with_label("Path Bind Group Layout")
+            .with_uniform_buffer(0, wgpu::ShaderStages::VERTEX | wgpu::ShaderStages::FRAGMENT)
+            .build();
+
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            layout: &bind_group_layout,
+            entries: &[wgpu::BindGroupEntry {
+                binding: 0,
+                resource: uniform_buffer.as_entire_binding(),
+            }],
+            label: Some("Path Bind Group"),
+        });
+
+        // Unit quad corners in the XZ plane
+        let corners: &[f32] = &[
+            -0.5, -0.5, 0.5, -0.5, -0.5, 0.5, // Triangle 1
+            0.5, -0.5, 0.5, 0.5, -0.5, 0.5, // Triangle 2
+        ];
+        let vertex_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Path Vertex Buffer"),
+            contents: bytemuck::cast_slice(corners),
+            usage: wgpu::BufferUsages::VERTEX,
+        });
+
+        let instance_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Path Instance Buffer"),
+            contents: bytemuck::cast_slice(&[PathInstance::default(); MAX_PATH_MARKERS]),
+            usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
+        });
+
+        let vertex_buffer_layout = wgpu::VertexBufferLayout {
+            array_stride: 2 * 4, // 2 floats * 4 bytes each
+            step_mode: wgpu::VertexStepMode::Vertex,
+            attributes: &[wgpu::VertexAttribute {
+                offset: 0,
+                shader_location: 0,
+                format: wgpu::VertexFormat::Float32x2, // corner (x, z)
+            }],
+        };
+        let instance_buffer_layout = wgpu::VertexBufferLayout {
+            array_stride: std::mem::size_of::<PathInstance>() as wgpu::BufferAddress,
+            step_mode: wgpu::VertexStepMode::Instance,
+            attributes: &[wgpu::VertexAttribute {
+                offset: 0,
+                shader_location: 1,
+                format: wgpu::VertexFormat::Float32x4, // position + destination flag
+            }],
+        };
+
+        let pipeline = PipelineBuilder::new(device, surface_config.format)
+            .with_label("Path Pipeline")
+            .with_shader(include_str!("../shaders/path.wgsl"))
+            .with_vertex_buffer(vertex_buffer_layout)
+            .with_vertex_buffer(instance_buffer_layout)
+            .with_bind_group_layout(&bind_group_layout)
+            .with_alpha_blending()
+            .with_no_culling()
+            .with_depth_stencil(wgpu::DepthStencilState {
+                depth_write_enabled: false,
+                depth_compare: wgpu::CompareFunction::LessEqual,
+                format: wgpu::TextureFormat::Depth24Plus,
+                stencil: wgpu::StencilState::default(),
+                bias: wgpu::DepthBiasState::default(),
+            })
+            .with_sample_count(sample_count)
+            .build();
+
+        Self {
+            pipeline,
+            vertex_buffer,
+            instance_buffer,
+            uniform_buffer,
+            bind_group,
+            instance_count: 0,
+        }
+    }
+
+    /// Uploads the rest of the route and the view-projection matrix.
+    ///
+    /// The instance data is built on the stack and written over the existing instance buffer,
+    /// so no GPU or heap allocations happen per frame.
+    ///
+    /// # Arguments
+    ///
+    /// * `queue` - WGPU queue for uploading data
+    /// * `game_state` - Current game state containing the auto-walk route
+    /// * `view_proj_matrix` - Current view-projection matrix for rendering
+    pub fn update(
+        &mut self,
+        queue: &wgpu::Queue,
+        game_state: &GameState,
+        view_proj_matrix: [[f32; 4]; 4],
+    ) {
+        let auto_walk: &AutoWalk = &game_state.auto_walk;
+        let remaining = auto_walk.remaining();
+        let count = remaining.len().min(MAX_PATH_MARKERS);
+
+        let mut instances = [PathInstance::default(); MAX_PATH_MARKERS];
+        for (instance, waypoint) in instances.iter_mut().zip(&remaining[..count]) {
+            let [x, y, z] = *waypoint;
+            instance.position_destination = [x, y, z, 0.0];
+        }
+        // Only flag the destination when it made it into the buffer
+        if count > 0 && count == remaining.len() {
+            instances[count - 1].position_destination[3] = 1.0;
+        }
+        self.instance_count = count as u32;
+
+        if self.instance_count > 0 {
+            queue.write_buffer(
+                &self.instance_buffer,
+                0,
+                bytemuck::cast_slice(&instances[..count]),
+            );
+        }
+
+        let uniforms = PathUniforms {
+            view_proj_matrix,
+            color: MARKER_COLOR,
+            base_size: MARKER_SIZE,
+            lift: MARKER_LIFT,
+            _padding: [0.0; 2],
+        };
+        queue.write_buffer(&self.uniform_buffer, 0, bytemuck::cast_slice(&[uniforms]));
+    }
+
+    /// Renders the route markers to the specified render pass.
+    ///
+    /// Does nothing while the player is not auto-walking.
+    ///
+    /// # Arguments
+    ///
+    /// * `render_pass` - The render pass to draw to
+    pub fn render(&self, render_pass: &mut wgpu::RenderPass) {
+        if self.instance_count == 0 {
+            return;
+        }
+
+        render_pass.set_pipeline(&self.pipeline);
+        render_pass.set_vertex_buffer(0, self.vertex_buffer.slice(..));
+        render_pass.set_vertex_buffer(1, self.instance_buffer.slice(..));
+        render_pass.set_bind_group(0, &self.bind_group, &[]);
+        render_pass.draw(0..6, 0..self.instance_count);
+    }
+}
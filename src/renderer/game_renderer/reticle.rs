@@ -0,0 +1,241 @@
+//! Reticle Renderer Module
+//!
+//! This module draws the small center-screen reticle that shows where the player is
+//! looking. The reticle is a dot, a cross or a ring drawn with signed distance functions in a
+//! full-screen triangle pass, scissored to a small square around the view's center.
+//! While the player looks at an interactable, the reticle grows slightly and a ring
+//! glyph fades in around it; the matching "E — ..." hint is drawn by the text renderer.
+
+use crate::renderer::color::srgb_for_target;
+use crate::renderer::pipeline_builder::{
+    BindGroupLayoutBuilder, PipelineBuilder, create_uniform_buffer,
+};
+use serde::{Deserialize, Serialize};
+use wgpu;
+
+/// How quickly the reticle grows toward or shrinks from its expanded size, per second.
+///
+/// Fast enough to feel responsive, slow enough that sweeping across an interactable
+/// doesn't make the reticle flicker.
+pub const EXPANSION_RATE: f32 = 14.0;
+
+/// Shape of the center-screen reticle.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ReticleStyle {
+    /// No reticle is drawn
+    Off,
+    /// A small filled circle
+    #[default]
+    Dot,
+    /// A plus sign
+    Cross,
+    /// A hollow circle
+    Ring,
+}
+
+/// User settings for the reticle's look.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ReticleSettings {
+    /// Shape of the reticle, or [`ReticleStyle::Off`] to hide it
+    pub style: ReticleStyle,
+    /// Size in pixels: the dot's or ring's diameter, or the length of the cross's arms end to end
+    pub size: f32,
+    /// sRGB color of the reticle
+    pub color: [f32; 3],
+    /// Opacity from `0.0` (invisible) to `1.0` (solid)
+    pub opacity: f32,
+}
+
+impl Default for ReticleSettings {
+    /// A 6 pixel white dot at 80% opacity.
+    fn default() -> Self {
+        Self {
+            style: ReticleStyle::Dot,
+            size: 6.0,
+            color: [1.0, 1.0, 1.0],
+            opacity: 0.8,
+        }
+    }
+}
+
+impl ReticleSettings {
+    /// Returns whether these settings draw anything at all.
+    pub fn is_enabled(&self) -> bool {
+        self.style != ReticleStyle::Off && self.size > 0.0 && self.opacity > 0.0
+    }
+
+    /// Returns half the width of the square the reticle can draw into, in pixels.
+    ///
+    /// Covers the fully expanded reticle and its interaction ring, so the scissor rect
+    /// never clips them.
+    pub fn half_extent(&self) -> f32 {
+        self.size.max(0.0) * 2.0 + 4.0
+    }
+}
+
+/// Uniform data passed to the reticle shader.
+///
+/// # Memory Layout
+/// 48 bytes: the color, the center, four scalars, then padding up to a 16-byte multiple.
+#[repr(C)]
+#[derive(Debug, Copy, Clone, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct ReticleUniforms {
+    /// RGBA color, already converted for the render target
+    pub color: [f32; 4],
+    /// Center of the reticle in pixels from the top-left corner of the surface
+    pub center: [f32; 2],
+    /// Size of the reticle in pixels
+    pub size: f32,
+    /// How far the reticle has grown toward its interaction look, from 0.0 to 1.0
+    pub expansion: f32,
+    /// 1.0 for a dot, 2.0 for a cross, 3.0 for a ring
+    pub style: f32,
+    /// Padding to keep the struct 16-byte aligned
+    pub _padding: [f32; 3],
+}
+
+/// Moves the reticle's expansion toward its target.
+///
+/// # Arguments
+/// * `current` - Expansion last frame, from 0.0 to 1.0
+/// * `target` - 1.0 while an interactable is targeted, otherwise 0.0
+/// * `delta_time` - Seconds since the last frame
+///
+/// # Returns
+/// The new expansion, which approaches `target` exponentially and never overshoots it
+pub fn ease_expansion(current: f32, target: f32, delta_time: f32) -> f32 {
+    let blend = 1.0 - (-EXPANSION_RATE * delta_time.max(0.0)).exp();
+    current + (target - current) * blend
+}
+
+/// Renders the center-screen reticle.
+///
+/// Like the stamina bar, the reticle is drawn with a procedural full-screen triangle and
+/// needs no vertex buffer. The caller limits the pass to a scissor rect around the center.
+pub struct ReticleRenderer {
+    /// The WebGPU render pipeline for the reticle
+    pub pipeline: wgpu::RenderPipeline,
+    /// GPU buffer containing the reticle uniforms
+    pub uniform_buffer: wgpu::Buffer,
+    /// Bind group that associates the uniform buffer with shader bindings
+    pub bind_group: wgpu::BindGroup,
+    /// Format of the surface, used to prepare the reticle color
+    pub format: wgpu::TextureFormat,
+    /// Current expansion toward the interaction look, from 0.0 to 1.0
+    pub expansion: f32,
+}
+
+impl ReticleRenderer {
+    /// Creates a new reticle renderer.
+    ///
+    /// # Arguments
+    /// * `device` - WebGPU device for creating GPU resources
+    /// * `surface_config` - Surface configuration containing the target pixel format
+    ///
+    /// # Returns
+    /// A `ReticleRenderer` with the reticle collapsed
+    pub fn new(device: &wgpu::Device, surface_config: &wgpu::SurfaceConfiguration) -> Self {
+        let uniforms = ReticleUniforms {
+            color: [1.0; 4],
+            center: [0.0; 2],
+            size: ReticleSettings::default().size,
+            expansion: 0.0,
+            style: 1.0,
+            _padding: [0.0; 3],
+        };
+        let uniform_buffer = create_uniform_buffer(device, &uniforms, "Reticle Uniform Buffer");
+
+        let bind_group_layout = BindGroupLayoutBuilder::new(device)
+            .with_label("Reticle Bind Group Layout")
+            .with_uniform_buffer(0, wgpu::ShaderStages::FRAGMENT)
+            .build();
+
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            layout: &bind_group_layout,
+            entries: &[wgpu::BindGroupEntry {
+                binding: 0,
+                resource: uniform_buffer.as_entire_binding(),
+            }],
+            label: Some("Reticle Bind Group"),
+        });
+
+        let pipeline = PipelineBuilder::new(device, surface_config.format)
+            .with_label("Reticle Pipeline")
+            .with_shader(include_str!("../shaders/reticle.wgsl"))
+            .with_bind_group_layout(&bind_group_layout)
+            .with_alpha_blending()
+            .build();
+
+        Self {
+            pipeline,
+            uniform_buffer,
+            bind_group,
+            format: surface_config.format,
+            expansion: 0.0,
+        }
+    }
+
+    /// Eases the expansion toward the interaction look by one frame.
+    ///
+    /// # Arguments
+    /// * `targeting` - Whether the player is looking at an interactable
+    /// * `delta_time` - Seconds since the last frame
+    pub fn animate(&mut self, targeting: bool, delta_time: f32) {
+        let target = if targeting { 1.0 } else { 0.0 };
+        self.expansion = ease_expansion(self.expansion, target, delta_time);
+    }
+
+    /// Uploads this frame's uniforms.
+    ///
+    /// # Arguments
+    /// * `queue` - WebGPU command queue for GPU operations
+    /// * `settings` - The player's reticle settings
+    /// * `center` - Center of the 3D view in pixels
+    pub fn update(&self, queue: &wgpu::Queue, settings: &ReticleSettings, center: [f32; 2]) {
+        let [r, g, b] = settings.color;
+        let uniforms = ReticleUniforms {
+            color: srgb_for_target([r, g, b, settings.opacity.clamp(0.0, 1.0)], self.format),
+            center,
+            size: settings.size.max(0.0),
+            expansion: self.expansion,
+            style: match settings.style {
+                ReticleStyle::Ring => 3.0,
+                ReticleStyle::Cross => 2.0,
+                ReticleStyle::Dot | ReticleStyle::Off => 1.0,
+            },
+            _padding: [0.0; 3],
+        };
+        queue.write_buffer(&self.uniform_buffer, 0, bytemuck::cast_slice(&[uniforms]));
+    }
+
+    /// Renders the reticle to the current render pass.
+    ///
+    /// # Arguments
+    /// * `render_pass` - Active render pass, scissored to the reticle's square
+    pub fn render(&self, render_pass: &mut wgpu::RenderPass) {
+        render_pass.set_pipeline(&self.pipeline);
+        render_pass.set_bind_group(0, &self.bind_group, &[]);
+        render_pass.draw(0..3, 0..1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_expansion_eases_without_overshooting() {
+        let mut expansion = 0.0;
+        for _ in 0..10 {
+            let next = ease_expansion(expansion, 1.0, 1.0 / 60.0);
+            assert!(next > expansion && next < 1.0);
+            expansion = next;
+        }
+        assert!(expansion > 0.85);
+
+        // A long stall snaps to the target, and a stalled clock doesn't move it
+        assert!((ease_expansion(expansion, 0.0, 10.0)).abs() < 1e-4);
+        assert_eq!(ease_expansion(0.5, 1.0, 0.0), 0.5);
+    }
+}
@@ -0,0 +1,196 @@
+//! Spawn telegraph rendering module for the Mirador game.
+//!
+//! This module draws the warning before a delayed enemy arrives (see [`crate::game::spawn`]):
+//! a dark red column of light rising over the spawn cell, tall enough to be seen over the
+//! walls from across the maze, and a stain spreading on the floor beneath it. Both grow with
+//! the telegraph's progress, and the column flares and pinches shut once the enemy has
+//! arrived. The geometry is generated in the shader from the vertex index, so it needs no
+//! vertex buffer; it is alpha blended and does not write depth, so walls in front still hide
+//! its base.
+
+use crate::game::GameState;
+use crate::renderer::pipeline_builder::{
+    BindGroupLayoutBuilder, PipelineBuilder, create_uniform_buffer,
+};
+use wgpu;
+
+/// Number of sides of the column; must match `SIDES` in the shader.
+const COLUMN_SIDES: u32 = 12;
+
+/// Number of vertices drawn: a quad per side of the column, then the floor stain.
+const TELEGRAPH_VERTEX_COUNT: u32 = (COLUMN_SIDES + 1) * 6;
+
+/// Radius of the column at full strength, in world units.
+const COLUMN_RADIUS: f32 = 14.0;
+
+/// Height of the column at full strength, in world units.
+const COLUMN_HEIGHT: f32 = 420.0;
+
+/// Column color as linear RGBA; alpha is its opacity at full strength.
+const TELEGRAPH_COLOR: [f32; 4] = [0.55, 0.04, 0.1, 0.7];
+
+/// Uniform data passed to the telegraph shader.
+///
+/// # Memory Layout
+/// 112 bytes: the matrix, two vectors, then four scalars.
+#[repr(C)]
+#[derive(Copy, Clone, Debug, bytemuck::Pod, bytemuck::Zeroable)]
+struct TelegraphUniforms {
+    /// View-projection matrix for transforming vertices to screen space
+    view_proj_matrix: [[f32; 4]; 4],
+    /// Center of the spawn cell on the floor (x, y, z) followed by the column's radius
+    center_radius: [f32; 4],
+    /// Column color, with alpha as its opacity at full strength
+    color: [f32; 4],
+    /// How far the telegraph has built, from 0.0 to 1.0
+    progress: f32,
+    /// 1.0 while the column collapses after the enemy arrived, else 0.0
+    collapsing: f32,
+    /// Animation time in seconds
+    time: f32,
+    /// Height of the column at full strength
+    height: f32,
+}
+
+/// Renders the telegraph of a delayed enemy spawn.
+pub struct SpawnTelegraphRenderer {
+    /// The render pipeline for the telegraph
+    pipeline: wgpu::RenderPipeline,
+    /// Uniform buffer containing shader uniforms
+    uniform_buffer: wgpu::Buffer,
+    /// Bind group containing the uniforms
+    bind_group: wgpu::BindGroup,
+    /// Whether the last update found a telegraph to draw
+    visible: bool,
+}
+
+impl SpawnTelegraphRenderer {
+    /// Creates a new spawn telegraph renderer.
+    ///
+    /// # Arguments
+    ///
+    /// * `device` - WGPU device for creating GPU resources
+    /// * `surface_config` - Surface configuration for pipeline creation
+    /// * `sample_count` - Samples per pixel of the render target the 3D view is drawn into
+    ///
+    /// # Returns
+    ///
+    /// A new `SpawnTelegraphRenderer` with nothing to draw until its first update.
+    pub fn new(
+        device: &wgpu::Device,
+        surface_config: &wgpu::SurfaceConfiguration,
+        sample_count: u32,
+    ) -> Self {
+        let uniforms = TelegraphUniforms {
+            view_proj_matrix: [[0.0; 4]; 4],
+            center_radius: [0.0, 0.0, 0.0, COLUMN_RADIUS],
+            color: TELEGRAPH_COLOR,
+            progress: 0.0,
+            collapsing: 0.0,
+            time: 0.0,
+            height: COLUMN_HEIGHT,
+        };
+        let uniform_buffer =
+            create_uniform_buffer(device, &uniforms, "Spawn Telegraph Uniform Buffer");
+
+        let bind_group_layout = BindGroupLayoutBuilder::new(device)
+            .with_label("Spawn Telegraph Bind Group Layout")
+            .with_uniform_buffer(0, wgpu::ShaderStages::VERTEX | wgpu::ShaderStages::FRAGMENT)
+            .build();
+
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            layout: &bind_group_layout,
+            entries: &[wgpu::BindGroupEntry {
+                binding: 0,
+                resource: uniform_buffer.as_entire_binding(),
+            }],
+            label: Some("Spawn Telegraph Bind Group"),
+        });
+
+        let pipeline = PipelineBuilder::new(device, surface_config.format)
+            .with_label("Spawn Telegraph Pipeline")
+            .with_shader(include_str!("../shaders/spawn_telegraph.wgsl"))
+            .with_bind_group_layout(&bind_group_layout)
+            .with_alpha_blending()
+            .with_no_culling()
+            .with_depth_stencil(wgpu::DepthStencilState {
+                depth_write_enabled: false,
+                depth_compare: wgpu::CompareFunction::LessEqual,
+                format: wgpu::TextureFormat::Depth24Plus,
+                stencil: wgpu::StencilState::default(),
+                bias: wgpu::DepthBiasState::default(),
+            })
+            .with_sample_count(sample_count)
+            .build();
+
+        Self {
+            pipeline,
+            uniform_buffer,
+            bind_group,
+            visible: false,
+        }
+    }
+
+    /// Uploads the telegraph's progress and the view-projection matrix.
+    ///
+    /// # Arguments
+    ///
+    /// * `queue` - WGPU queue for uploading data
+    /// * `game_state` - Current game state holding the level's spawn director
+    /// * `view_proj_matrix` - Current view-projection matrix for rendering
+    /// * `time` - Animation time in seconds
+    pub fn update(
+        &mut self,
+        queue: &wgpu::Queue,
+        game_state: &GameState,
+        view_proj_matrix: [[f32; 4]; 4],
+        time: f32,
+    ) {
+        let spawn = &game_state.spawn_director;
+        let progress = spawn.telegraph();
+        self.visible = progress > 0.0;
+        if !self.visible {
+            return;
+        }
+
+        let [x, _, z] = spawn.position();
+        let uniforms = TelegraphUniforms {
+            view_proj_matrix,
+            center_radius: [x, 0.0, z, COLUMN_RADIUS],
+            color: TELEGRAPH_COLOR,
+            progress,
+            collapsing: if spawn.collapsing() { 1.0 } else { 0.0 },
+            time,
+            height: COLUMN_HEIGHT,
+        };
+        queue.write_buffer(&self.uniform_buffer, 0, bytemuck::cast_slice(&[uniforms]));
+    }
+
+    /// Renders the telegraph to the specified render pass.
+    ///
+    /// Does nothing unless a delayed spawn is about to fire or has just fired.
+    ///
+    /// # Arguments
+    ///
+    /// * `render_pass` - The render pass to draw to
+    pub fn render(&self, render_pass: &mut wgpu::RenderPass) {
+        if !self.visible {
+            return;
+        }
+
+        render_pass.set_pipeline(&self.pipeline);
+        render_pass.set_bind_group(0, &self.bind_group, &[]);
+        render_pass.draw(0..TELEGRAPH_VERTEX_COUNT, 0..1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::renderer::uniform_layout::assert_matches_shader;
+
+    #[test]
+    fn test_uniforms_match_the_shader_layout() {
+        assert_matches_shader::<TelegraphUniforms>(include_str!("../shaders/spawn_telegraph.wgsl"));
+    }
+}
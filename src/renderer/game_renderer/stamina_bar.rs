@@ -7,19 +7,18 @@
 use crate::renderer::pipeline_builder::{
     BindGroupLayoutBuilder, PipelineBuilder, create_uniform_buffer,
 };
-use std::time::Instant;
 use wgpu;
 
 /// Uniform data structure passed to the stamina bar shader.
 ///
 /// This struct is laid out in memory according to WebGPU's uniform buffer
-/// alignment requirements. The `_padding` field ensures proper 16-byte alignment.
+/// alignment requirements; `origin` also pads the struct to 16-byte alignment.
 ///
 /// # Memory Layout
 /// - `progress`: Current stamina level (0.0 = empty, 1.0 = full)
 /// - `time`: Elapsed time in seconds for animations
-/// - `resolution`: Screen resolution [width, height] for aspect ratio correction
-/// - `_padding`: Padding bytes to maintain 16-byte alignment
+/// - `resolution`: Size of the HUD safe area [width, height] the bar spans
+/// - `origin`: Top-left corner of the HUD safe area in pixels
 #[repr(C)]
 #[derive(Debug, Copy, Clone, bytemuck::Pod, bytemuck::Zeroable)]
 pub struct StaminaBarUniforms {
@@ -27,10 +26,10 @@ pub struct StaminaBarUniforms {
     pub progress: f32,
     /// Elapsed time in seconds since renderer creation, used for animations
     pub time: f32,
-    /// Screen resolution as [width, height] for proper aspect ratio
+    /// Size of the HUD safe area as [width, height]; the bar spans its width
     pub resolution: [f32; 2],
-    /// Padding to ensure proper GPU memory alignment (unused)
-    pub _padding: [f32; 2],
+    /// Top-left corner of the HUD safe area in pixels
+    pub origin: [f32; 2],
 }
 
 /// GPU-accelerated stamina bar renderer.
@@ -55,8 +54,6 @@ pub struct StaminaBarRenderer {
     pub uniform_buffer: wgpu::Buffer,
     /// Bind group that associates the uniform buffer with shader bindings
     pub bind_group: wgpu::BindGroup,
-    /// Creation timestamp for calculating elapsed time
-    pub start_time: Instant,
 }
 
 impl StaminaBarRenderer {
@@ -73,7 +70,7 @@ impl StaminaBarRenderer {
     /// A fully initialized `StaminaBarRenderer` ready for rendering
     ///
     /// # Example
-    /// ```rust
+    /// ```rust,ignore
     /// let renderer = StaminaBarRenderer::new(&device, &surface_config);
     /// ```
     pub fn new(device: &wgpu::Device, surface_config: &wgpu::SurfaceConfiguration) -> Self {
@@ -82,7 +79,7 @@ impl StaminaBarRenderer {
             progress: 1.0,              // Start with full stamina
             time: 0.0,                  // Start time at zero
             resolution: [800.0, 600.0], // Default resolution
-            _padding: [0.0; 2],         // Padding for alignment
+            origin: [0.0; 2],           // Safe area starts at the top-left corner
         };
 
         // Create GPU uniform buffer
@@ -116,46 +113,30 @@ impl StaminaBarRenderer {
             pipeline,
             uniform_buffer,
             bind_group,
-            start_time: Instant::now(),
         }
     }
 
-    /// Updates the stamina bar's uniform data on the GPU.
+    /// Uploads the stamina bar's uniform data to the GPU.
     ///
-    /// This method should be called each frame to update the stamina bar's
-    /// visual state. The progress value is automatically clamped to ensure
-    /// it stays within valid bounds.
+    /// This method should be called each frame with uniforms built from the frame's
+    /// animation state, see [`FrameAnimations::stamina_bar_uniforms`].
     ///
     /// # Arguments
     /// * `queue` - WebGPU command queue for GPU operations
-    /// * `progress` - Stamina level from 0.0 (empty) to 1.0 (full)
-    /// * `resolution` - Current screen resolution as [width, height]
-    /// * `time` - Current time in seconds for shader animations
+    /// * `uniforms` - This frame's stamina bar uniforms
     ///
     /// # Note
-    /// The progress value is clamped to [0.0, 1.0] range and is NOT inverted,
-    /// meaning the bar shrinks as stamina decreases (0.0 = empty bar).
+    /// The progress is NOT inverted, meaning the bar shrinks as stamina
+    /// decreases (0.0 = empty bar).
     ///
     /// # Example
-    /// ```rust
-    /// renderer.update_uniforms(&queue, 0.75, [1920.0, 1080.0], elapsed_time);
+    /// ```rust,ignore
+    /// renderer.update_uniforms(&queue, &animations.stamina_bar_uniforms(&hud));
     /// ```
-    pub fn update_uniforms(
-        &self,
-        queue: &wgpu::Queue,
-        progress: f32,
-        resolution: [f32; 2],
-        time: f32,
-    ) {
-        let uniforms = StaminaBarUniforms {
-            progress: progress.clamp(0.0, 1.0), // Ensure valid range
-            time,
-            resolution,
-            _padding: [0.0; 2],
-        };
-
-        // Upload uniform data to GPU buffer
-        queue.write_buffer(&self.uniform_buffer, 0, bytemuck::cast_slice(&[uniforms]));
+    ///
+    /// [`FrameAnimations::stamina_bar_uniforms`]: crate::renderer::animation::FrameAnimations::stamina_bar_uniforms
+    pub fn update_uniforms(&self, queue: &wgpu::Queue, uniforms: &StaminaBarUniforms) {
+        queue.write_buffer(&self.uniform_buffer, 0, bytemuck::bytes_of(uniforms));
     }
 
     /// Renders the stamina bar to the current render pass.
@@ -173,7 +154,7 @@ impl StaminaBarRenderer {
     /// - Relies on vertex shader to generate screen-covering geometry
     ///
     /// # Example
-    /// ```rust
+    /// ```rust,ignore
     /// let mut render_pass = encoder.begin_render_pass(&render_pass_desc);
     /// renderer.render(&mut render_pass);
     /// ```
@@ -3,9 +3,11 @@
 //! This module provides [`StarRenderer`], which renders a field of animated stars using `wgpu`.
 //! Stars are randomly generated in screen space and rendered as glowing points/quads.
 //!
-//! The renderer supports updating the background color and animating stars over time via uniform buffers.
+//! The renderer supports updating the background color, tinting the stars, and animating stars over
+//! time via uniform buffers.
 
-use rand::Rng;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
 use wgpu::util::DeviceExt;
 use wgpu::{self, Buffer};
 
@@ -24,7 +26,7 @@ struct Star {
 
 /// Handles GPU resources and rendering pipeline for the animated starfield.
 ///
-/// Contains vertex/index buffers, uniform buffers for time and background color,
+/// Contains vertex/index buffers, uniform buffers for time, background color and star tint,
 /// the render pipeline, and the bind group for uniforms.
 pub struct StarRenderer {
     /// Vertex buffer containing star quad vertices.
@@ -33,12 +35,16 @@ pub struct StarRenderer {
     pub index_buffer: Buffer,
     /// Number of indices to draw.
     pub num_indices: u32,
+    /// Number of stars in the field.
+    pub star_count: usize,
     /// Render pipeline for the starfield.
     pub pipeline: wgpu::RenderPipeline,
     /// Uniform buffer for animation time.
     pub time_buffer: Buffer,
     /// Uniform buffer for background color (RGBA).
     pub background_color_buffer: Buffer,
+    /// Uniform buffer for the star tint: RGB color, and in alpha how far stars are blended toward it.
+    pub star_tint_buffer: Buffer,
     /// Bind group for uniforms.
     pub uniform_bind_group: wgpu::BindGroup,
 }
@@ -49,6 +55,7 @@ pub struct StarRenderer {
 /// - `device`: The wgpu device to create buffers and pipeline.
 /// - `surface_config`: The surface configuration (for color format).
 /// - `num_stars`: Number of stars to generate.
+/// - `sample_count`: Samples per pixel of the render target the stars are drawn into.
 ///
 /// # Returns
 /// A fully initialized [`StarRenderer`] ready for rendering.
@@ -56,16 +63,38 @@ pub struct StarRenderer {
 /// # Implementation Notes
 /// - Stars are randomly placed in NDC space ([-1, 1]).
 /// - Each star is rendered as a quad (two triangles).
-/// - Uniform buffers are created for animation time and background color.
+/// - Uniform buffers are created for animation time, background color and star tint.
 /// - The render pipeline and bind group are created using [`create_star_pipeline`].
 pub fn create_star_renderer(
     device: &wgpu::Device,
     surface_config: &wgpu::SurfaceConfiguration,
     num_stars: usize,
+    sample_count: u32,
+) -> StarRenderer {
+    create_seeded_star_renderer(device, surface_config, num_stars, sample_count, None)
+}
+
+/// Creates a [`StarRenderer`] whose stars are placed from a fixed seed.
+///
+/// # Arguments
+/// - `device`, `surface_config`, `num_stars`, `sample_count`: As for [`create_star_renderer`].
+/// - `seed`: Seed for the star positions, sizes and brightness; `None` picks a fresh field.
+///
+/// # Returns
+/// A fully initialized [`StarRenderer`], the same field every time for the same seed.
+pub fn create_seeded_star_renderer(
+    device: &wgpu::Device,
+    surface_config: &wgpu::SurfaceConfiguration,
+    num_stars: usize,
+    sample_count: u32,
+    seed: Option<u64>,
 ) -> StarRenderer {
     // Generate random stars in screen space (-1 to 1)
     let mut stars = Vec::new();
-    let mut rng = rand::thread_rng();
+    let mut rng = match seed {
+        Some(seed) => StdRng::seed_from_u64(seed),
+        None => StdRng::from_entropy(),
+    };
 
     for _ in 0..num_stars {
         stars.push(Star {
@@ -162,20 +191,31 @@ pub fn create_star_renderer(
         usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
     });
 
+    // Create star tint uniform buffer (default to no tint)
+    let star_tint_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+        label: Some("Star Tint Buffer"),
+        contents: bytemuck::cast_slice(&[0.0f32; 4]),
+        usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+    });
+
     let (pipeline, uniform_bind_group) = create_star_pipeline(
         device,
         surface_config,
+        sample_count,
         &time_buffer,
         &background_color_buffer,
+        &star_tint_buffer,
     );
 
     StarRenderer {
         vertex_buffer,
         index_buffer,
         num_indices: indices.len() as u32,
+        star_count: num_stars,
         pipeline,
         time_buffer,
         background_color_buffer,
+        star_tint_buffer,
         uniform_bind_group,
     }
 }
@@ -185,8 +225,10 @@ pub fn create_star_renderer(
 /// # Arguments
 /// - `device`: The wgpu device.
 /// - `surface_config`: The surface configuration (for color format).
+/// - `sample_count`: Samples per pixel of the render target the stars are drawn into.
 /// - `time_buffer`: Uniform buffer for animation time.
 /// - `background_color_buffer`: Uniform buffer for background color.
+/// - `star_tint_buffer`: Uniform buffer for the star tint.
 ///
 /// # Returns
 /// A tuple of (`wgpu::RenderPipeline`, `wgpu::BindGroup`).
@@ -198,8 +240,10 @@ pub fn create_star_renderer(
 pub fn create_star_pipeline(
     device: &wgpu::Device,
     surface_config: &wgpu::SurfaceConfiguration,
+    sample_count: u32,
     time_buffer: &Buffer,
     background_color_buffer: &Buffer,
+    star_tint_buffer: &Buffer,
 ) -> (wgpu::RenderPipeline, wgpu::BindGroup) {
     let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
         label: Some("Star Shader"),
@@ -230,6 +274,16 @@ pub fn create_star_pipeline(
                     },
                     count: None,
                 },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 2,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
             ],
             label: Some("uniform_bind_group_layout"),
         });
@@ -246,6 +300,10 @@ pub fn create_star_pipeline(
                 binding: 1,
                 resource: background_color_buffer.as_entire_binding(),
             },
+            wgpu::BindGroupEntry {
+                binding: 2,
+                resource: star_tint_buffer.as_entire_binding(),
+            },
         ],
         label: Some("uniform_bind_group"),
     });
@@ -314,7 +372,7 @@ pub fn create_star_pipeline(
         },
         depth_stencil: None,
         multisample: wgpu::MultisampleState {
-            count: 1,
+            count: sample_count,
             mask: !0,
             alpha_to_coverage_enabled: false,
         },
@@ -340,6 +398,15 @@ impl StarRenderer {
         );
     }
 
+    /// Updates the tint the starfield shader blends the stars toward.
+    ///
+    /// # Arguments
+    /// - `queue`: The wgpu queue to write to the buffer.
+    /// - `tint`: The tint color as `[r, g, b, amount]`; an amount of 0.0 leaves the stars untinted.
+    pub fn update_star_tint(&self, queue: &wgpu::Queue, tint: [f32; 4]) {
+        queue.write_buffer(&self.star_tint_buffer, 0, bytemuck::cast_slice(&tint));
+    }
+
     /// Updates the animation time uniform for the starfield shader.
     ///
     /// # Arguments
@@ -348,4 +415,19 @@ impl StarRenderer {
     pub fn update_star_time(&self, queue: &wgpu::Queue, time: f32) {
         queue.write_buffer(&self.time_buffer, 0, bytemuck::cast_slice(&[time]));
     }
+
+    /// Draws the starfield into a render pass.
+    ///
+    /// # Arguments
+    /// - `render_pass`: The pass to draw into, on a target with the pipeline's sample count.
+    pub fn render(&self, render_pass: &mut wgpu::RenderPass) {
+        if self.num_indices == 0 {
+            return;
+        }
+        render_pass.set_pipeline(&self.pipeline);
+        render_pass.set_bind_group(0, &self.uniform_bind_group, &[]);
+        render_pass.set_vertex_buffer(0, self.vertex_buffer.slice(..));
+        render_pass.set_index_buffer(self.index_buffer.slice(..), wgpu::IndexFormat::Uint16);
+        render_pass.draw_indexed(0..self.num_indices, 0, 0..1);
+    }
 }
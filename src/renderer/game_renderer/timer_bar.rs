@@ -28,11 +28,14 @@ pub struct TimerBarUniforms {
     /// Current time in seconds since the timer started, used for animations
     pub time: f32,
 
-    /// Screen resolution as [width, height] in pixels for proper scaling
+    /// Size of the HUD safe area as [width, height] in pixels; the bar is sized and centered in it
     pub resolution: [f32; 2],
 
-    /// Padding to ensure proper GPU memory alignment (16-byte alignment)
-    pub _padding: [f32; 2],
+    /// 1.0 when the shader's sRGB colors must be converted to linear for an sRGB target
+    pub linear_output: f32,
+
+    /// Left edge of the HUD safe area in pixels
+    pub origin_x: f32,
 }
 
 /// GPU-accelerated timer bar renderer.
@@ -42,7 +45,7 @@ pub struct TimerBarUniforms {
 /// custom shaders for efficient GPU rendering.
 ///
 /// # Usage
-/// ```rust,no_run
+/// ```rust,ignore
 /// # use wgpu;
 /// # let device: wgpu::Device = todo!();
 /// # let surface_config: wgpu::SurfaceConfiguration = todo!();
@@ -51,7 +54,9 @@ pub struct TimerBarUniforms {
 /// // In your render loop:
 /// # let queue: wgpu::Queue = todo!();
 /// # let mut render_pass: wgpu::RenderPass = todo!();
-/// timer_bar.update_uniforms(&queue, 0.5, [800.0, 600.0], 1.0);
+/// # let animations: FrameAnimations = todo!();
+/// # let hud: SafeArea = todo!();
+/// timer_bar.update_uniforms(&queue, &animations.timer_bar_uniforms(&hud, timer_bar.linear_output()));
 /// timer_bar.render(&mut render_pass);
 /// ```
 pub struct TimerBarRenderer {
@@ -66,6 +71,9 @@ pub struct TimerBarRenderer {
 
     /// Start time for calculating elapsed time in animations
     pub start_time: Instant,
+
+    /// Value of [`TimerBarUniforms::linear_output`] for the render target
+    linear_output: f32,
 }
 
 impl TimerBarRenderer {
@@ -82,19 +90,27 @@ impl TimerBarRenderer {
     /// A new `TimerBarRenderer` instance ready for rendering
     ///
     /// # Example
-    /// ```rust,no_run
+    /// ```rust,ignore
     /// # use wgpu;
     /// # let device: wgpu::Device = todo!();
     /// # let surface_config: wgpu::SurfaceConfiguration = todo!();
     /// let timer_bar = TimerBarRenderer::new(&device, &surface_config);
     /// ```
     pub fn new(device: &wgpu::Device, surface_config: &wgpu::SurfaceConfiguration) -> Self {
+        // The bar's colors are sRGB design values, so sRGB targets need them linearized
+        let linear_output = if surface_config.format.is_srgb() {
+            1.0
+        } else {
+            0.0
+        };
+
         // Initialize uniform data with default values
         let uniforms = TimerBarUniforms {
             progress: 1.0,              // Start at full progress
             time: 0.0,                  // Start time at zero
             resolution: [800.0, 600.0], // Default resolution
-            _padding: [0.0; 2],         // Zero padding
+            linear_output,
+            origin_x: 0.0, // Safe area starts at the left edge
         };
 
         // Create the uniform buffer on the GPU
@@ -129,45 +145,27 @@ impl TimerBarRenderer {
             uniform_buffer,
             bind_group,
             start_time: Instant::now(),
+            linear_output,
         }
     }
 
-    /// Updates the uniform buffer with new timer bar parameters.
+    /// Returns the value of [`TimerBarUniforms::linear_output`] for the render target.
+    pub fn linear_output(&self) -> f32 {
+        self.linear_output
+    }
+
+    /// Uploads the timer bar uniforms to the GPU.
     ///
-    /// This method should be called each frame to update the timer bar's
-    /// appearance and animation state. The progress value is automatically
-    /// clamped to the valid range [0.0, 1.0].
+    /// This method should be called each frame with uniforms built from the frame's
+    /// animation state, see [`FrameAnimations::timer_bar_uniforms`].
     ///
     /// # Arguments
     /// * `queue` - The wgpu command queue for buffer uploads
-    /// * `progress` - Progress value (0.0 = empty, 1.0 = full), will be clamped
-    /// * `resolution` - Current screen resolution as [width, height]
-    /// * `time` - Current time in seconds for animations
+    /// * `uniforms` - This frame's timer bar uniforms
     ///
-    /// # Example
-    /// ```rust,no_run
-    /// # use wgpu;
-    /// # let timer_bar: TimerBarRenderer = todo!();
-    /// # let queue: wgpu::Queue = todo!();
-    /// // Update with 75% progress at 1920x1080 resolution
-    /// timer_bar.update_uniforms(&queue, 0.75, [1920.0, 1080.0], 2.5);
-    /// ```
-    pub fn update_uniforms(
-        &self,
-        queue: &wgpu::Queue,
-        progress: f32,
-        resolution: [f32; 2],
-        time: f32,
-    ) {
-        let uniforms = TimerBarUniforms {
-            progress: progress.clamp(0.0, 1.0), // Ensure progress stays in valid range
-            time,
-            resolution,
-            _padding: [0.0; 2],
-        };
-
-        // Upload the new uniform data to the GPU
-        queue.write_buffer(&self.uniform_buffer, 0, bytemuck::cast_slice(&[uniforms]));
+    /// [`FrameAnimations::timer_bar_uniforms`]: crate::renderer::animation::FrameAnimations::timer_bar_uniforms
+    pub fn update_uniforms(&self, queue: &wgpu::Queue, uniforms: &TimerBarUniforms) {
+        queue.write_buffer(&self.uniform_buffer, 0, bytemuck::bytes_of(uniforms));
     }
 
     /// Renders the timer bar to the current render pass.
@@ -184,7 +182,7 @@ impl TimerBarRenderer {
     /// the timer bar displays the current state.
     ///
     /// # Example
-    /// ```rust,no_run
+    /// ```rust,ignore
     /// # use wgpu;
     /// # let timer_bar: TimerBarRenderer = todo!();
     /// # let mut render_pass: wgpu::RenderPass = todo!();
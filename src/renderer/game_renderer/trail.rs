@@ -0,0 +1,231 @@
+//! Enemy trail rendering module for the Mirador game.
+//!
+//! This module draws the enemy's [`EnemyTrail`] as soft, flat splotches on the floor that shrink
+//! and fade with age. All breadcrumbs are drawn with a single instanced draw call from one
+//! instance buffer that is overwritten in place every frame.
+//!
+//! The trail is alpha blended and does not write depth, so overlapping breadcrumbs layer
+//! cleanly on the floor while walls still hide the ones behind them.
+
+use crate::game::GameState;
+use crate::game::trail::{EnemyTrail, TRAIL_CAPACITY};
+use crate::renderer::pipeline_builder::{
+    BindGroupLayoutBuilder, PipelineBuilder, create_uniform_buffer,
+};
+use wgpu::{self, util::DeviceExt};
+
+/// Width of a fresh breadcrumb splotch in world units.
+const BREADCRUMB_SIZE: f32 = 40.0;
+
+/// Height the trail is drawn at, just above the floor and the exit patch.
+const TRAIL_FLOOR_HEIGHT: f32 = 1.5;
+
+/// Trail color as linear RGBA; alpha is the opacity of a fresh breadcrumb.
+const TRAIL_COLOR: [f32; 4] = [0.35, 0.9, 0.3, 0.45];
+
+/// Uniform data structure for the trail shader.
+#[repr(C)]
+#[derive(Copy, Clone, Debug, bytemuck::Pod, bytemuck::Zeroable)]
+struct TrailUniforms {
+    /// View-projection matrix for transforming vertices to screen space
+    view_proj_matrix: [[f32; 4]; 4],
+    /// Trail color, with alpha as the opacity of a fresh breadcrumb
+    color: [f32; 4],
+    /// Width of a fresh breadcrumb in world units
+    base_size: f32,
+    /// Height the trail is drawn at
+    floor_height: f32,
+    /// Padding for proper memory alignment
+    _padding: [f32; 2],
+}
+
+/// Per-breadcrumb instance data.
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Default, bytemuck::Pod, bytemuck::Zeroable)]
+struct TrailInstance {
+    /// Breadcrumb world position (x, y, z) followed by its fade factor
+    position_fade: [f32; 4],
+}
+
+/// Renders the enemy trail as instanced floor quads.
+pub struct TrailRenderer {
+    /// The render pipeline for trail rendering
+    pipeline: wgpu::RenderPipeline,
+    /// Vertex buffer containing the unit quad corners
+    vertex_buffer: wgpu::Buffer,
+    /// Instance buffer holding one entry per breadcrumb slot
+    instance_buffer: wgpu::Buffer,
+    /// Uniform buffer containing shader uniforms
+    uniform_buffer: wgpu::Buffer,
+    /// Bind group containing the uniforms
+    bind_group: wgpu::BindGroup,
+    /// Number of instances written by the last update
+    instance_count: u32,
+}
+
+impl TrailRenderer {
+    /// Creates a new trail renderer.
+    ///
+    /// # Arguments
+    ///
+    /// * `device` - WGPU device for creating GPU resources
+    /// * `surface_config` - Surface configuration for pipeline creation
+    /// * `sample_count` - Samples per pixel of the render target the 3D view is drawn into
+    ///
+    /// # Returns
+    ///
+    /// A new `TrailRenderer` with an empty instance buffer sized for a full trail.
+    pub fn new(
+        device: &wgpu::Device,
+        surface_config: &wgpu::SurfaceConfiguration,
+        sample_count: u32,
+    ) -> Self {
+        let uniforms = TrailUniforms {
+            view_proj_matrix: [[0.0; 4]; 4],
+            color: TRAIL_COLOR,
+            base_size: BREADCRUMB_SIZE,
+            floor_height: TRAIL_FLOOR_HEIGHT,
+            _padding: [0.0; 2],
+        };
+        let uniform_buffer = create_uniform_buffer(device, &uniforms, "Trail Uniform Buffer");
+
+        let bind_group_layout = BindGroupLayoutBuilder::new(device)
+            .with_label("Trail Bind Group Layout")
+            .with_uniform_buffer(0, wgpu::ShaderStages::VERTEX | wgpu::ShaderStages::FRAGMENT)
+            .build();
+
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            layout: &bind_group_layout,
+            entries: &[wgpu::BindGroupEntry {
+                binding: 0,
+                resource: uniform_buffer.as_entire_binding(),
+            }],
+            label: Some("Trail Bind Group"),
+        });
+
+        // Unit quad corners in the XZ plane
+        let corners: &[f32] = &[
+            -0.5, -0.5, 0.5, -0.5, -0.5, 0.5, // Triangle 1
+            0.5, -0.5, 0.5, 0.5, -0.5, 0.5, // Triangle 2
+        ];
+        let vertex_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Trail Vertex Buffer"),
+            contents: bytemuck::cast_slice(corners),
+            usage: wgpu::BufferUsages::VERTEX,
+        });
+
+        let instance_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Trail Instance Buffer"),
+            contents: bytemuck::cast_slice(&[TrailInstance::default(); TRAIL_CAPACITY]),
+            usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
+        });
+
+        let vertex_buffer_layout = wgpu::VertexBufferLayout {
+            array_stride: 2 * 4, // 2 floats * 4 bytes each
+            step_mode: wgpu::VertexStepMode::Vertex,
+            attributes: &[wgpu::VertexAttribute {
+                offset: 0,
+                shader_location: 0,
+                format: wgpu::VertexFormat::Float32x2, // corner (x, z)
+            }],
+        };
+        let instance_buffer_layout = wgpu::VertexBufferLayout {
+            array_stride: std::mem::size_of::<TrailInstance>() as wgpu::BufferAddress,
+            step_mode: wgpu::VertexStepMode::Instance,
+            attributes: &[wgpu::VertexAttribute {
+                offset: 0,
+                shader_location: 1,
+                format: wgpu::VertexFormat::Float32x4, // position + fade
+            }],
+        };
+
+        let pipeline = PipelineBuilder::new(device, surface_config.format)
+            .with_label("Trail Pipeline")
+            .with_shader(include_str!("../shaders/trail.wgsl"))
+            .with_vertex_buffer(vertex_buffer_layout)
+            .with_vertex_buffer(instance_buffer_layout)
+            .with_bind_group_layout(&bind_group_layout)
+            .with_alpha_blending()
+            .with_no_culling()
+            .with_depth_stencil(wgpu::DepthStencilState {
+                depth_write_enabled: false,
+                depth_compare: wgpu::CompareFunction::LessEqual,
+                format: wgpu::TextureFormat::Depth24Plus,
+                stencil: wgpu::StencilState::default(),
+                bias: wgpu::DepthBiasState::default(),
+            })
+            .with_sample_count(sample_count)
+            .build();
+
+        Self {
+            pipeline,
+            vertex_buffer,
+            instance_buffer,
+            uniform_buffer,
+            bind_group,
+            instance_count: 0,
+        }
+    }
+
+    /// Uploads the current trail and view-projection matrix.
+    ///
+    /// The instance data is built on the stack and written over the existing instance buffer,
+    /// so no GPU or heap allocations happen per frame.
+    ///
+    /// # Arguments
+    ///
+    /// * `queue` - WGPU queue for uploading data
+    /// * `game_state` - Current game state containing the enemy trail
+    /// * `view_proj_matrix` - Current view-projection matrix for rendering
+    pub fn update(
+        &mut self,
+        queue: &wgpu::Queue,
+        game_state: &GameState,
+        view_proj_matrix: [[f32; 4]; 4],
+    ) {
+        let trail: &EnemyTrail = &game_state.enemy.trail;
+
+        let mut instances = [TrailInstance::default(); TRAIL_CAPACITY];
+        for (instance, crumb) in instances.iter_mut().zip(trail.iter()) {
+            let [x, y, z] = crumb.position;
+            instance.position_fade = [x, y, z, trail.fade(crumb)];
+        }
+        self.instance_count = trail.len() as u32;
+
+        if self.instance_count > 0 {
+            queue.write_buffer(
+                &self.instance_buffer,
+                0,
+                bytemuck::cast_slice(&instances[..trail.len()]),
+            );
+        }
+
+        let uniforms = TrailUniforms {
+            view_proj_matrix,
+            color: TRAIL_COLOR,
+            base_size: BREADCRUMB_SIZE,
+            floor_height: TRAIL_FLOOR_HEIGHT,
+            _padding: [0.0; 2],
+        };
+        queue.write_buffer(&self.uniform_buffer, 0, bytemuck::cast_slice(&[uniforms]));
+    }
+
+    /// Renders the trail to the specified render pass.
+    ///
+    /// Does nothing while the trail is empty.
+    ///
+    /// # Arguments
+    ///
+    /// * `render_pass` - The render pass to draw to
+    pub fn render(&self, render_pass: &mut wgpu::RenderPass) {
+        if self.instance_count == 0 {
+            return;
+        }
+
+        render_pass.set_pipeline(&self.pipeline);
+        render_pass.set_vertex_buffer(0, self.vertex_buffer.slice(..));
+        render_pass.set_vertex_buffer(1, self.instance_buffer.slice(..));
+        render_pass.set_bind_group(0, &self.bind_group, &[]);
+        render_pass.draw(0..6, 0..self.instance_count);
+    }
+}
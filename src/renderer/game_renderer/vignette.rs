@@ -0,0 +1,114 @@
+//! Vignette Renderer Module
+//!
+//! This module darkens the edges of the screen, closing in toward the center as its
+//! strength rises, and can fade the whole screen to black. It is drawn over the finished
+//! frame as a procedural full-screen triangle and is used by the capture sequence.
+
+use crate::renderer::pipeline_builder::{
+    BindGroupLayoutBuilder, PipelineBuilder, create_uniform_buffer,
+};
+use wgpu;
+
+/// Uniform data passed to the vignette shader.
+///
+/// # Memory Layout
+/// 16 bytes: three scalars and padding.
+#[repr(C)]
+#[derive(Debug, Copy, Clone, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct VignetteUniforms {
+    /// How dark the screen edges are, from 0.0 to 1.0
+    pub strength: f32,
+    /// How far the whole screen has faded to black, from 0.0 to 1.0
+    pub fade: f32,
+    /// Surface width divided by height
+    pub aspect: f32,
+    /// Padding to keep the struct 16-byte aligned
+    pub _padding: f32,
+}
+
+/// Renders the darkened screen edges and fade to black.
+///
+/// Like the reticle, the vignette is drawn with a procedural full-screen triangle and
+/// needs no vertex buffer.
+pub struct VignetteRenderer {
+    /// The WebGPU render pipeline for the vignette
+    pub pipeline: wgpu::RenderPipeline,
+    /// GPU buffer containing the vignette uniforms
+    pub uniform_buffer: wgpu::Buffer,
+    /// Bind group that associates the uniform buffer with shader bindings
+    pub bind_group: wgpu::BindGroup,
+}
+
+impl VignetteRenderer {
+    /// Creates a new vignette renderer.
+    ///
+    /// # Arguments
+    /// * `device` - WebGPU device for creating GPU resources
+    /// * `surface_config` - Surface configuration containing the target pixel format
+    ///
+    /// # Returns
+    /// A `VignetteRenderer` that draws nothing until its strength or fade is raised
+    pub fn new(device: &wgpu::Device, surface_config: &wgpu::SurfaceConfiguration) -> Self {
+        let uniforms = VignetteUniforms {
+            strength: 0.0,
+            fade: 0.0,
+            aspect: 1.0,
+            _padding: 0.0,
+        };
+        let uniform_buffer = create_uniform_buffer(device, &uniforms, "Vignette Uniform Buffer");
+
+        let bind_group_layout = BindGroupLayoutBuilder::new(device)
+            .with_label("Vignette Bind Group Layout")
+            .with_uniform_buffer(0, wgpu::ShaderStages::FRAGMENT)
+            .build();
+
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            layout: &bind_group_layout,
+            entries: &[wgpu::BindGroupEntry {
+                binding: 0,
+                resource: uniform_buffer.as_entire_binding(),
+            }],
+            label: Some("Vignette Bind Group"),
+        });
+
+        let pipeline = PipelineBuilder::new(device, surface_config.format)
+            .with_label("Vignette Pipeline")
+            .with_shader(include_str!("../shaders/vignette.wgsl"))
+            .with_bind_group_layout(&bind_group_layout)
+            .with_alpha_blending()
+            .build();
+
+        Self {
+            pipeline,
+            uniform_buffer,
+            bind_group,
+        }
+    }
+
+    /// Uploads this frame's uniforms.
+    ///
+    /// # Arguments
+    /// * `queue` - WebGPU command queue for GPU operations
+    /// * `strength` - How dark the screen edges are, from 0.0 to 1.0
+    /// * `fade` - How far the whole screen has faded to black, from 0.0 to 1.0
+    /// * `aspect` - Surface width divided by height
+    pub fn update(&self, queue: &wgpu::Queue, strength: f32, fade: f32, aspect: f32) {
+        let uniforms = VignetteUniforms {
+            strength: strength.clamp(0.0, 1.0),
+            fade: fade.clamp(0.0, 1.0),
+            aspect,
+            _padding: 0.0,
+        };
+        queue.write_buffer(&self.uniform_buffer, 0, bytemuck::cast_slice(&[uniforms]));
+    }
+
+    /// Renders the vignette to the current render pass.
+    ///
+    /// # Arguments
+    /// * `render_pass` - Active render pass drawing over the finished frame
+    pub fn render(&self, render_pass: &mut wgpu::RenderPass) {
+        render_pass.set_pipeline(&self.pipeline);
+        render_pass.set_bind_group(0, &self.bind_group, &[]);
+        render_pass.draw(0..3, 0..1);
+    }
+}
@@ -0,0 +1,686 @@
+//! Golden image tests for the screens that can be drawn without a window.
+//!
+//! Each test draws one frame of a screen into an offscreen [`WIDTH`] x [`HEIGHT`] target, reads
+//! the pixels back and compares them with a PNG checked in under `tests/goldens/`. Every source
+//! of change between runs is pinned: mazes and starfields come from fixed seeds, shader time is
+//! a constant ([`MazeView::render`]'s `time` and [`LoadingRenderer::pinned_time`]), the upgrade
+//! cards are picked rather than drawn at random, and nothing that reads the wall clock is part of
+//! a captured frame.
+//!
+//! Drivers rasterize edges slightly differently, so a pixel only counts as changed when a channel
+//! is off by more than [`CHANNEL_TOLERANCE`], and a frame passes while at most
+//! [`MAX_DIFFERING_PIXELS`] pixels changed. On failure the actual frame and a diff (changed pixels
+//! in red over a dimmed copy of the frame) are written to `target/golden/` for inspection.
+//!
+//! Every frame must also be fully opaque. On surfaces whose compositor reads alpha, a pixel
+//! with alpha below 1.0 lets the desktop show through the window, so a frame with one fails
+//! before it is compared, and can't be written as a golden either.
+//!
+//! Set `UPDATE_GOLDENS=1` to write the current frames as the new goldens after an intentional
+//! change. Without a GPU adapter (CI machines without one) the tests print why and pass.
+//!
+//! The game over and upgrade screens are drawn over a seeded starfield, standing in for the
+//! frozen game frame behind them. The level-end replay is covered on its own, composited over a
+//! black frame, since the summary screen it is drawn over is not.
+
+use crate::game::GameState;
+use crate::game::maze::generator::Cell;
+use crate::game::maze::generator::MazeGenerator;
+use crate::game::replay::{PLAYBACK_SPEED, SAMPLE_INTERVAL};
+use crate::game::upgrades::AvailableUpgrade;
+use crate::renderer::game_renderer::GameRenderer;
+use crate::renderer::game_renderer::game_over::GameOverRenderer;
+use crate::renderer::game_renderer::stars::{StarRenderer, create_seeded_star_renderer};
+use crate::renderer::gpu_memory::GpuMemoryTracker;
+use crate::renderer::loading_renderer::{LoadingRenderer, PATH_PULSE_SECS};
+use crate::renderer::maze_view::MazeView;
+use crate::renderer::primitives::Vertex;
+use crate::renderer::rectangle::Rectangle;
+use crate::renderer::replay_pip::ReplayPip;
+use crate::renderer::text::TextRenderer;
+use crate::renderer::title::{TitleRenderer, create_title_text, layout_title_text};
+use crate::renderer::tuning::RenderTuning;
+use crate::renderer::ui::game_over_menu::GameOverMenu;
+use crate::renderer::ui::title_menu::TitleMenu;
+use crate::renderer::ui::upgrade_menu::{self, UpgradeMenu};
+use crate::renderer::{color, wgpu_lib};
+use glyphon::Resolution;
+use image::{Rgba, RgbaImage};
+use std::path::PathBuf;
+use wgpu::util::DeviceExt;
+use winit::dpi::PhysicalSize;
+
+/// Width of every golden frame in pixels.
+const WIDTH: u32 = 800;
+
+/// Height of every golden frame in pixels.
+const HEIGHT: u32 = 450;
+
+/// Format of the offscreen target, the sRGB format most surfaces use.
+const FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Rgba8UnormSrgb;
+
+/// Largest difference in any channel that still counts as the same pixel.
+const CHANNEL_TOLERANCE: u8 = 8;
+
+/// Most pixels that may differ before a frame fails, about 0.5% of the frame.
+const MAX_DIFFERING_PIXELS: usize = 1_800;
+
+/// Shader time every golden frame is drawn at, in seconds.
+const PINNED_TIME: f32 = 2.5;
+
+/// Creates a device on any available adapter, or `None` when running without a GPU.
+pub(crate) fn headless_device() -> Option<(wgpu::Device, wgpu::Queue)> {
+    let instance = wgpu::Instance::default();
+    let adapter = pollster::block_on(instance.request_adapter(&wgpu::RequestAdapterOptions {
+        power_preference: wgpu::PowerPreference::default(),
+        force_fallback_adapter: false,
+        compatible_surface: None,
+    }))?;
+    pollster::block_on(adapter.request_device(&wgpu::DeviceDescriptor::default(), None)).ok()
+}
+
+/// Surface configuration matching the offscreen target, for renderers that are built from one.
+fn target_config() -> wgpu::SurfaceConfiguration {
+    wgpu::SurfaceConfiguration {
+        usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+        format: FORMAT,
+        width: WIDTH,
+        height: HEIGHT,
+        present_mode: wgpu::PresentMode::Fifo,
+        desired_maximum_frame_latency: 2,
+        alpha_mode: wgpu::CompositeAlphaMode::Auto,
+        view_formats: vec![],
+    }
+}
+
+/// Draws one frame into a fresh offscreen target and reads it back.
+///
+/// # Arguments
+/// * `device` - Device to draw with
+/// * `queue` - Queue the frame is submitted on
+/// * `draw` - Records the frame into the encoder, given the target's view
+///
+/// # Returns
+/// The frame's pixels, top row first
+fn capture(
+    device: &wgpu::Device,
+    queue: &wgpu::Queue,
+    draw: impl FnOnce(&mut wgpu::CommandEncoder, &wgpu::TextureView),
+) -> RgbaImage {
+    let texture = device.create_texture(&wgpu::TextureDescriptor {
+        label: Some("Golden Target"),
+        size: wgpu::Extent3d {
+            width: WIDTH,
+            height: HEIGHT,
+            depth_or_array_layers: 1,
+        },
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension: wgpu::TextureDimension::D2,
+        format: FORMAT,
+        usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::COPY_SRC,
+        view_formats: &[],
+    });
+    let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+
+    // Copied rows have to start on 256-byte boundaries
+    let row_bytes = WIDTH * 4;
+    let padded_row_bytes =
+        row_bytes.div_ceil(wgpu::COPY_BYTES_PER_ROW_ALIGNMENT) * wgpu::COPY_BYTES_PER_ROW_ALIGNMENT;
+    let readback = device.create_buffer(&wgpu::BufferDescriptor {
+        label: Some("Golden Readback"),
+        size: (padded_row_bytes * HEIGHT) as u64,
+        usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+        mapped_at_creation: false,
+    });
+
+    let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+    draw(&mut encoder, &view);
+    encoder.copy_texture_to_buffer(
+        texture.as_image_copy(),
+        wgpu::TexelCopyBufferInfo {
+            buffer: &readback,
+            layout: wgpu::TexelCopyBufferLayout {
+                offset: 0,
+                bytes_per_row: Some(padded_row_bytes),
+                rows_per_image: Some(HEIGHT),
+            },
+        },
+        texture.size(),
+    );
+    queue.submit(Some(encoder.finish()));
+
+    let slice = readback.slice(..);
+    slice.map_async(wgpu::MapMode::Read, |_| {});
+    device.poll(wgpu::Maintain::Wait);
+    let data = slice.get_mapped_range();
+    let pixels = data
+        .chunks(padded_row_bytes as usize)
+        .flat_map(|row| &row[..row_bytes as usize])
+        .copied()
+        .collect();
+    RgbaImage::from_raw(WIDTH, HEIGHT, pixels).expect("readback holds a full frame")
+}
+
+/// How an actual frame differs from its golden.
+struct Comparison {
+    /// Number of pixels with a channel off by more than [`CHANNEL_TOLERANCE`]
+    differing: usize,
+    /// Changed pixels in red over a dimmed grayscale copy of the actual frame
+    diff: RgbaImage,
+}
+
+/// Compares a frame with its golden, pixel by pixel.
+///
+/// # Arguments
+/// * `expected` - The golden frame
+/// * `actual` - The frame just drawn, the same size as the golden
+fn compare(expected: &RgbaImage, actual: &RgbaImage) -> Comparison {
+    let mut differing = 0;
+    let diff = RgbaImage::from_fn(actual.width(), actual.height(), |x, y| {
+        let (Rgba(want), Rgba(got)) = (expected.get_pixel(x, y), actual.get_pixel(x, y));
+        let changed = want
+            .iter()
+            .zip(got)
+            .any(|(want, got)| want.abs_diff(*got) > CHANNEL_TOLERANCE);
+        if changed {
+            differing += 1;
+            Rgba([255, 0, 0, 255])
+        } else {
+            let gray = ((got[0] as u32 + got[1] as u32 + got[2] as u32) / 9) as u8;
+            Rgba([gray, gray, gray, 255])
+        }
+    });
+    Comparison { differing, diff }
+}
+
+/// Counts the pixels of a frame that aren't fully opaque.
+///
+/// # Arguments
+/// * `frame` - The frame just drawn
+fn translucent_pixels(frame: &RgbaImage) -> usize {
+    frame
+        .pixels()
+        .filter(|Rgba([.., alpha])| *alpha < u8::MAX)
+        .count()
+}
+
+/// Checks a frame against its golden, or writes it as the golden with `UPDATE_GOLDENS` set.
+///
+/// # Arguments
+/// * `name` - Name of the golden, without the `.png`
+/// * `actual` - The frame just drawn
+///
+/// # Panics
+/// If any pixel isn't fully opaque, or if the golden is missing, a different size, or more
+/// than [`MAX_DIFFERING_PIXELS`] pixels changed; the actual frame and the diff are written to
+/// `target/golden/` first
+fn assert_matches_golden(name: &str, actual: &RgbaImage) {
+    let translucent = translucent_pixels(actual);
+    assert_eq!(
+        translucent, 0,
+        "Frame {} has {} pixels with alpha below 1.0, which show the desktop through the window",
+        name, translucent
+    );
+
+    let root = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let golden_path = root.join("tests/goldens").join(format!("{}.png", name));
+    if std::env::var_os("UPDATE_GOLDENS").is_some() {
+        std::fs::create_dir_all(golden_path.parent().expect("goldens have a directory"))
+            .expect("Failed to create the goldens directory");
+        actual
+            .save(&golden_path)
+            .expect("Failed to write the golden");
+        println!("Updated golden {}", golden_path.display());
+        return;
+    }
+
+    let out_dir = root.join("target/golden");
+    std::fs::create_dir_all(&out_dir).expect("Failed to create target/golden");
+    let actual_path = out_dir.join(format!("{}-actual.png", name));
+    let fail = |reason: String| -> ! {
+        actual
+            .save(&actual_path)
+            .expect("Failed to write the actual frame");
+        panic!(
+            "{} ({}); actual frame written to {}. Run with UPDATE_GOLDENS=1 if the change is intended.",
+            reason,
+            golden_path.display(),
+            actual_path.display()
+        );
+    };
+
+    let expected = match image::open(&golden_path) {
+        Ok(expected) => expected.to_rgba8(),
+        Err(e) => fail(format!("Golden {} can't be read: {}", name, e)),
+    };
+    if expected.dimensions() != actual.dimensions() {
+        fail(format!(
+            "Golden {} is {:?} but the frame is {:?}",
+            name,
+            expected.dimensions(),
+            actual.dimensions()
+        ));
+    }
+    let comparison = compare(&expected, actual);
+    if comparison.differing > MAX_DIFFERING_PIXELS {
+        let diff_path = out_dir.join(format!("{}-diff.png", name));
+        comparison
+            .diff
+            .save(&diff_path)
+            .expect("Failed to write the diff");
+        fail(format!(
+            "Golden {} differs in {} pixels, more than {}; diff written to {}",
+            name,
+            comparison.differing,
+            MAX_DIFFERING_PIXELS,
+            diff_path.display()
+        ));
+    }
+}
+
+/// Carves a small fixed maze and puts the player at its entrance.
+///
+/// # Returns
+/// The wall grid, the exit, and a game state without audio
+fn fixed_level() -> (Vec<Vec<bool>>, Option<Cell>, GameState) {
+    let (mut generator, maze) = MazeGenerator::with_seed(6, 6, 7);
+    while !generator.is_complete() {
+        generator.step();
+    }
+    let (grid, exit_cell) = maze.lock().expect("Failed to lock maze").level_grid();
+
+    let mut game_state = GameState::builder()
+        .without_audio()
+        .build()
+        .expect("Failed to build game state");
+    game_state.collision_system.build_from_maze(&grid, false);
+    game_state.player.spawn_at_maze_entrance(&grid, false);
+    (grid, exit_cell, game_state)
+}
+
+/// Draws the maze from the entrance of a small fixed maze.
+///
+/// # Arguments
+/// * `device` - Device to draw with
+/// * `queue` - Queue the frame is submitted on
+/// * `wall_breath` - How far the walls breathe, as on a deep level
+fn capture_game(device: &wgpu::Device, queue: &wgpu::Queue, wall_breath: f32) -> RgbaImage {
+    let (grid, exit_cell, mut game_state) = fixed_level();
+    game_state.wall_breath = wall_breath;
+
+    let mut view = MazeView::new(device, queue, FORMAT, WIDTH, HEIGHT);
+    view.load_maze(device, &grid, exit_cell);
+    capture(device, queue, |encoder, target| {
+        view.render(device, queue, encoder, target, &game_state, PINNED_TIME);
+    })
+}
+
+#[test]
+fn test_compare_counts_only_pixels_past_the_tolerance() {
+    let expected = RgbaImage::from_pixel(4, 2, Rgba([100, 100, 100, 255]));
+    let mut actual = expected.clone();
+    actual.put_pixel(0, 0, Rgba([100 + CHANNEL_TOLERANCE, 100, 100, 255]));
+    actual.put_pixel(1, 0, Rgba([100, 100 - CHANNEL_TOLERANCE - 1, 100, 255]));
+    actual.put_pixel(3, 1, Rgba([100, 100, 100, 0]));
+
+    let comparison = compare(&expected, &actual);
+    assert_eq!(comparison.differing, 2);
+    assert_eq!(comparison.diff.get_pixel(1, 0), &Rgba([255, 0, 0, 255]));
+    assert_eq!(comparison.diff.get_pixel(2, 0), &Rgba([33, 33, 33, 255]));
+}
+
+#[test]
+fn test_translucent_pixels_are_counted() {
+    let mut frame = RgbaImage::from_pixel(4, 4, Rgba([10, 20, 30, 255]));
+    assert_eq!(translucent_pixels(&frame), 0);
+    frame.put_pixel(1, 2, Rgba([10, 20, 30, 254]));
+    frame.put_pixel(3, 0, Rgba([0, 0, 0, 0]));
+    assert_eq!(translucent_pixels(&frame), 2);
+}
+
+#[test]
+fn test_game_screen_matches_golden() {
+    let Some((device, queue)) = headless_device() else {
+        eprintln!("No GPU adapter available; skipping game screen golden");
+        return;
+    };
+    assert_matches_golden("game_entrance", &capture_game(&device, &queue, 0.0));
+}
+
+#[test]
+fn test_breathing_walls_match_golden() {
+    let Some((device, queue)) = headless_device() else {
+        eprintln!("No GPU adapter available; skipping breathing walls golden");
+        return;
+    };
+    let breath = crate::game::breathing::MAX_WALL_BREATH;
+    assert_matches_golden("game_breathing", &capture_game(&device, &queue, breath));
+}
+
+/// Draws one loading screen frame of a loading renderer.
+fn capture_loading(
+    device: &wgpu::Device,
+    queue: &wgpu::Queue,
+    loading: &mut LoadingRenderer,
+) -> RgbaImage {
+    let size = PhysicalSize::new(WIDTH, HEIGHT);
+    loading.update_maze(queue, false);
+    loading.update_loading_bar(queue, loading.get_generation_progress(), size);
+    loading.update_exit_shader(queue, size);
+
+    let clear = color::clear_color(color::srgb_for_target(
+        color::srgba_from_u8(wgpu_lib::LOADING_CLEAR_COLOR),
+        FORMAT,
+    ));
+    capture(device, queue, |encoder, target| {
+        let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some("Golden Loading Pass"),
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view: target,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Clear(clear),
+                    store: wgpu::StoreOp::Store,
+                },
+            })],
+            depth_stencil_attachment: None,
+            occlusion_query_set: None,
+            timestamp_writes: None,
+        });
+        loading.render(&mut pass, size);
+    })
+}
+
+#[test]
+fn test_loading_screen_at_half_progress_matches_golden() {
+    let Some((device, queue)) = headless_device() else {
+        eprintln!("No GPU adapter available; skipping loading screen golden");
+        return;
+    };
+    let mut loading = LoadingRenderer::new_seeded(
+        &device,
+        &target_config(),
+        &GpuMemoryTracker::default(),
+        Some(7),
+    );
+    loading.pinned_time = Some(PINNED_TIME);
+    while loading.get_generation_progress() < 0.5 {
+        loading.generator.step();
+    }
+    let frame = capture_loading(&device, &queue, &mut loading);
+    assert_matches_golden("loading_half", &frame);
+}
+
+#[test]
+fn test_solution_path_pulse_matches_golden() {
+    let Some((device, queue)) = headless_device() else {
+        eprintln!("No GPU adapter available; skipping solution path golden");
+        return;
+    };
+    let mut loading = LoadingRenderer::new_seeded(
+        &device,
+        &target_config(),
+        &GpuMemoryTracker::default(),
+        Some(7),
+    );
+    loading.pinned_time = Some(PINNED_TIME);
+    while !loading.generator.is_complete() {
+        loading.generator.step();
+    }
+    loading.start_path_reveal();
+    // Halfway along, with the earlier cells' carve flash long over
+    loading.update_maze(&queue, false);
+    loading.pinned_time = Some(PINNED_TIME + PATH_PULSE_SECS / 2.0);
+    let frame = capture_loading(&device, &queue, &mut loading);
+    assert_matches_golden("loading_path_pulse", &frame);
+}
+
+#[test]
+fn test_level_replay_matches_golden() {
+    let Some((device, queue)) = headless_device() else {
+        eprintln!("No GPU adapter available; skipping level replay golden");
+        return;
+    };
+    let (grid, exit_cell, mut game_state) = fixed_level();
+    let start = game_state.player.position;
+    game_state.run_recording.record(start, 0.0);
+    game_state
+        .run_recording
+        .record([start[0] + 120.0, start[1], start[2]], SAMPLE_INTERVAL);
+
+    // The main renderer's maze, which the replay shares
+    let config = target_config();
+    let mut renderer = GameRenderer::new(&device, &queue, &config);
+    let (mut vertices, _) = Vertex::create_floor_vertices(&grid, exit_cell, false);
+    vertices.append(&mut Vertex::create_wall_vertices(&grid, false));
+    renderer.vertex_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+        label: Some("Golden Maze Vertex Buffer"),
+        contents: bytemuck::cast_slice(&vertices),
+        usage: wgpu::BufferUsages::VERTEX,
+    });
+    renderer.vertex_count = vertices.len() as u32;
+    renderer.time = PINNED_TIME;
+
+    let mut pip = ReplayPip::new(&device, &queue, &config, &GpuMemoryTracker::default());
+    pip.update(
+        SAMPLE_INTERVAL / PLAYBACK_SPEED / 2.0,
+        None,
+        game_state.run_recording.duration(),
+    );
+    pip.sync_scene(&renderer, RenderTuning::default(), false);
+
+    let frame = capture(&device, &queue, |encoder, target| {
+        encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some("Golden Clear Pass"),
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view: target,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Clear(wgpu::Color::BLACK),
+                    store: wgpu::StoreOp::Store,
+                },
+            })],
+            depth_stencil_attachment: None,
+            occlusion_query_set: None,
+            timestamp_writes: None,
+        });
+        pip.render(&device, &queue, encoder, target, &game_state);
+    });
+    assert_matches_golden("level_replay", &frame);
+}
+
+/// Begins a pass drawing straight into the offscreen target.
+///
+/// # Arguments
+/// * `encoder` - Encoder the pass is recorded into
+/// * `target` - View of the offscreen target
+/// * `load` - Whether the pass clears the target or draws over what's there
+fn target_pass<'a>(
+    encoder: &'a mut wgpu::CommandEncoder,
+    target: &'a wgpu::TextureView,
+    load: wgpu::LoadOp<wgpu::Color>,
+) -> wgpu::RenderPass<'a> {
+    encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+        label: Some("Golden Pass"),
+        color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+            view: target,
+            resolve_target: None,
+            ops: wgpu::Operations {
+                load,
+                store: wgpu::StoreOp::Store,
+            },
+        })],
+        depth_stencil_attachment: None,
+        occlusion_query_set: None,
+        timestamp_writes: None,
+    })
+}
+
+/// Creates the seeded starfield the game over and upgrade goldens are drawn over.
+///
+/// It stands in for the frozen game frame behind those screens, which needs the whole scene.
+fn seeded_stars(device: &wgpu::Device, queue: &wgpu::Queue) -> StarRenderer {
+    let tuning = RenderTuning::default();
+    let stars =
+        create_seeded_star_renderer(device, &target_config(), tuning.star_count, 1, Some(7));
+    stars.update_background_color(queue, tuning.background_color);
+    stars.update_star_time(queue, PINNED_TIME);
+    stars.update_star_tint(queue, [0.0; 4]);
+    stars
+}
+
+/// Records the seeded starfield over the default background color.
+fn draw_stars(
+    encoder: &mut wgpu::CommandEncoder,
+    target: &wgpu::TextureView,
+    stars: &StarRenderer,
+) {
+    let background = color::clear_color(color::linear_for_target(
+        RenderTuning::default().background_color,
+        FORMAT,
+    ));
+    let mut pass = target_pass(encoder, target, wgpu::LoadOp::Clear(background));
+    stars.render(&mut pass);
+}
+
+#[test]
+fn test_title_screen_matches_golden() {
+    let Some((device, queue)) = headless_device() else {
+        eprintln!("No GPU adapter available; skipping title screen golden");
+        return;
+    };
+    let config = target_config();
+    let size = PhysicalSize::new(WIDTH, HEIGHT);
+    let resolution = Resolution {
+        width: WIDTH,
+        height: HEIGHT,
+    };
+    let gpu_memory = GpuMemoryTracker::default();
+    let title = TitleRenderer::new(&device, &queue, &config);
+
+    let mut text_renderer = TextRenderer::new(&device, &queue, FORMAT, size, &gpu_memory);
+    create_title_text(&mut text_renderer, WIDTH as f32, HEIGHT as f32);
+    layout_title_text(&mut text_renderer, WIDTH as f32, HEIGHT as f32, PINNED_TIME);
+    text_renderer.resize(&queue, resolution);
+    text_renderer
+        .prepare(&device, &queue, &config)
+        .expect("Failed to prepare the title text");
+
+    // A fresh profile: no seed chosen, no saved run, hardcore off
+    let mut menu = TitleMenu::new(&device, &queue, FORMAT, size, &gpu_memory);
+    menu.refresh_seed_field(None, None);
+    menu.refresh_hardcore_toggle(false);
+    menu.refresh_profile_label(None);
+    menu.refresh_continue_button(None);
+    menu.resize(&queue, resolution);
+    menu.prepare(&device, &queue, &config)
+        .expect("Failed to prepare the title menu");
+
+    let clear = color::clear_color(color::srgb_for_target(
+        color::srgba_from_u8(wgpu_lib::TITLE_CLEAR_COLOR),
+        FORMAT,
+    ));
+    let frame = capture(&device, &queue, |encoder, target| {
+        let mut pass = target_pass(encoder, target, wgpu::LoadOp::Clear(clear));
+        title.render(&mut pass);
+        text_renderer
+            .render(&mut pass)
+            .expect("Failed to draw the title text");
+        menu.render(&device, &mut pass)
+            .expect("Failed to draw the title menu");
+    });
+    assert_matches_golden("title", &frame);
+}
+
+#[test]
+fn test_game_over_screen_matches_golden() {
+    let Some((device, queue)) = headless_device() else {
+        eprintln!("No GPU adapter available; skipping game over screen golden");
+        return;
+    };
+    let config = target_config();
+    let size = PhysicalSize::new(WIDTH, HEIGHT);
+    let resolution = Resolution {
+        width: WIDTH,
+        height: HEIGHT,
+    };
+    let gpu_memory = GpuMemoryTracker::default();
+    let stars = seeded_stars(&device, &queue);
+    let overlay = GameOverRenderer::new(&device, &config);
+    overlay.update_time(&queue, PINNED_TIME);
+
+    let mut text_renderer = TextRenderer::new(&device, &queue, FORMAT, size, &gpu_memory);
+    text_renderer.create_game_over_display(WIDTH, HEIGHT);
+    text_renderer.show_game_over_display();
+    text_renderer.handle_game_over_text(WIDTH, HEIGHT);
+    text_renderer.resize(&queue, resolution);
+    text_renderer
+        .prepare(&device, &queue, &config)
+        .expect("Failed to prepare the game over text");
+
+    let mut menu = GameOverMenu::new(&device, &queue, FORMAT, size, &gpu_memory);
+    menu.show();
+    menu.resize(&queue, resolution);
+    menu.prepare(&device, &queue, &config)
+        .expect("Failed to prepare the game over menu");
+
+    let frame = capture(&device, &queue, |encoder, target| {
+        draw_stars(encoder, target, &stars);
+        let mut pass = target_pass(encoder, target, wgpu::LoadOp::Load);
+        overlay.render(&mut pass);
+        text_renderer
+            .render(&mut pass)
+            .expect("Failed to draw the game over text");
+        menu.render(&device, &mut pass)
+            .expect("Failed to draw the game over menu");
+    });
+    assert_matches_golden("game_over", &frame);
+}
+
+#[test]
+fn test_upgrade_menu_matches_golden() {
+    let Some((device, queue)) = headless_device() else {
+        eprintln!("No GPU adapter available; skipping upgrade menu golden");
+        return;
+    };
+    let config = target_config();
+    let size = PhysicalSize::new(WIDTH, HEIGHT);
+    let stars = seeded_stars(&device, &queue);
+
+    let mut menu = UpgradeMenu::new(&device, &queue, FORMAT, size, &GpuMemoryTracker::default());
+    menu.show_upgrades(vec![
+        AvailableUpgrade::SpeedUp.to_upgrade(),
+        AvailableUpgrade::Dash.to_upgrade(),
+        AvailableUpgrade::Lightfoot.to_upgrade(),
+    ]);
+    menu.resize(
+        &queue,
+        Resolution {
+            width: WIDTH,
+            height: HEIGHT,
+        },
+    );
+    menu.prepare(&device, &queue, &config)
+        .expect("Failed to prepare the upgrade menu");
+    menu.button_manager
+        .rectangle_renderer
+        .add_rectangle(Rectangle::new(
+            0.0,
+            0.0,
+            WIDTH as f32,
+            HEIGHT as f32,
+            upgrade_menu::OVERLAY_COLOR,
+        ));
+
+    let frame = capture(&device, &queue, |encoder, target| {
+        draw_stars(encoder, target, &stars);
+        let mut pass = target_pass(encoder, target, wgpu::LoadOp::Load);
+        menu.button_manager
+            .rectangle_renderer
+            .render(&device, &mut pass);
+        menu.render(&device, &mut pass)
+            .expect("Failed to draw the upgrade menu");
+    });
+    assert_matches_golden("upgrade_menu", &frame);
+}
@@ -0,0 +1,481 @@
+//! GPU texture memory accounting for the Mirador renderer.
+//!
+//! Every texture the renderer creates is registered with a [`GpuMemoryTracker`], owned by
+//! [`WgpuRenderer`](crate::renderer::wgpu_lib::WgpuRenderer), so the debug overlay can show how
+//! much texture memory is in use per [`TextureCategory`] and the log gets a warning once the
+//! total goes over budget. Sizes are estimated from the texture's extent and its format's block
+//! size; drivers add their own padding, so the real footprint is usually a little larger.
+//!
+//! Creating textures through [`create_tracked_texture`] registers them automatically and returns
+//! a [`TrackedTexture`] that releases its registration when dropped, so a resized or replaced
+//! texture never lingers in the totals.
+//!
+//! # Usage
+//!
+//! ```rust,ignore
+//! let tracker = GpuMemoryTracker::new(DEFAULT_BUDGET_BYTES);
+//! let depth = create_tracked_texture(&device, &tracker, TextureCategory::SceneTargets, &desc);
+//! println!("{}", tracker.summary());
+//!
+//! // Systems with a resolution knob can ask whether they should shrink
+//! if tracker.is_over_budget() {
+//!     tracker.reduce();
+//! }
+//! let scale = tracker.resolution_scale();
+//! ```
+
+use std::collections::HashMap;
+use std::ops::Deref;
+use std::sync::{Arc, Mutex};
+
+/// Number of bytes in a megabyte, as shown in the overlay and log.
+pub const BYTES_PER_MB: u64 = 1024 * 1024;
+
+/// Texture memory budget unless the settings choose another, in bytes.
+///
+/// Leaves plenty of room for everything else on a 2 GB card.
+pub const DEFAULT_BUDGET_BYTES: u64 = 512 * BYTES_PER_MB;
+
+/// Highest pressure level [`GpuMemoryTracker::reduce`] steps up to.
+pub const MAX_PRESSURE: u32 = 2;
+
+/// What a texture is used for, so the totals show where the memory goes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TextureCategory {
+    /// Depth buffers and other per-frame render targets sized to the window
+    SceneTargets,
+    /// Icons, menu art, and the loading screen maze preview
+    Ui,
+    /// Textures sampled by the 3D scene, such as the ceiling tiles
+    Materials,
+    /// Glyph atlases used by the text renderers
+    Text,
+}
+
+impl TextureCategory {
+    /// Every category, in the order the overlay lists them.
+    pub const ALL: [TextureCategory; 4] = [
+        TextureCategory::SceneTargets,
+        TextureCategory::Ui,
+        TextureCategory::Materials,
+        TextureCategory::Text,
+    ];
+
+    /// Returns the short name shown in the overlay.
+    pub fn label(self) -> &'static str {
+        match self {
+            TextureCategory::SceneTargets => "Scene",
+            TextureCategory::Ui => "UI",
+            TextureCategory::Materials => "Materials",
+            TextureCategory::Text => "Text",
+        }
+    }
+}
+
+/// Works out how many bytes one texel block of a format takes.
+///
+/// Depth formats without a copyable depth aspect don't report a size, so `Depth24Plus` is
+/// counted as the 32-bit texel most drivers back it with, and combined depth-stencil formats
+/// add the stencil byte on top.
+///
+/// # Arguments
+/// * `format` - The texture format
+///
+/// # Returns
+/// Bytes per block, where a block is one texel for uncompressed formats
+pub fn block_size_bytes(format: wgpu::TextureFormat) -> u64 {
+    match format {
+        wgpu::TextureFormat::Depth24Plus => 4,
+        wgpu::TextureFormat::Depth24PlusStencil8 => 5,
+        wgpu::TextureFormat::Depth32FloatStencil8 => 5,
+        wgpu::TextureFormat::NV12 => 3,
+        _ => format.block_copy_size(None).unwrap_or(4) as u64,
+    }
+}
+
+/// Estimates the memory a texture takes from its descriptor.
+///
+/// Counts every mip level, array layer, and sample. Mip sizes are rounded up to whole blocks,
+/// so compressed formats are counted the way the GPU stores them.
+///
+/// # Arguments
+/// * `desc` - The descriptor the texture is created from
+///
+/// # Returns
+/// The estimated size in bytes
+pub fn texture_size_bytes(desc: &wgpu::TextureDescriptor) -> u64 {
+    let (block_width, block_height) = desc.format.block_dimensions();
+    let block_bytes = block_size_bytes(desc.format);
+    let layers = match desc.dimension {
+        wgpu::TextureDimension::D3 => 1,
+        _ => desc.size.depth_or_array_layers.max(1) as u64,
+    };
+
+    let mut total = 0;
+    for level in 0..desc.mip_level_count.max(1) {
+        let width = (desc.size.width >> level).max(1);
+        let height = (desc.size.height >> level).max(1);
+        let depth = match desc.dimension {
+            wgpu::TextureDimension::D3 => (desc.size.depth_or_array_layers >> level).max(1),
+            _ => 1,
+        };
+        let blocks_wide = width.div_ceil(block_width) as u64;
+        let blocks_high = height.div_ceil(block_height) as u64;
+        total += blocks_wide * blocks_high * depth as u64 * block_bytes;
+    }
+    total * layers * desc.sample_count.max(1) as u64
+}
+
+/// Formats a byte count in megabytes for the overlay and log.
+fn format_mb(bytes: u64) -> String {
+    format!("{:.1} MB", bytes as f64 / BYTES_PER_MB as f64)
+}
+
+/// Bookkeeping shared by every clone of a [`GpuMemoryTracker`].
+#[derive(Debug)]
+struct TrackerState {
+    /// Size and category of every live registration, keyed by registration ID
+    entries: HashMap<u64, (TextureCategory, u64)>,
+    /// ID handed to the next registration
+    next_id: u64,
+    /// Budget in bytes the total is compared against
+    budget: u64,
+    /// Whether the current stretch over budget has already been logged
+    warned: bool,
+    /// How far systems have been asked to cut back, from 0 to [`MAX_PRESSURE`]
+    pressure: u32,
+}
+
+impl TrackerState {
+    fn total(&self) -> u64 {
+        self.entries.values().map(|(_, bytes)| bytes).sum()
+    }
+
+    /// Logs once each time the total crosses over the budget.
+    fn check_budget(&mut self) {
+        let total = self.total();
+        if total <= self.budget {
+            self.warned = false;
+        } else if !self.warned {
+            self.warned = true;
+            eprintln!(
+                "GPU texture memory {} is over the {} budget",
+                format_mb(total),
+                format_mb(self.budget)
+            );
+        }
+    }
+}
+
+/// Shared handle to the renderer's texture memory totals.
+///
+/// Cloning the handle shares the same totals, so renderers that create textures can keep a
+/// clone while [`WgpuRenderer`](crate::renderer::wgpu_lib::WgpuRenderer) owns the original.
+#[derive(Debug, Clone)]
+pub struct GpuMemoryTracker {
+    /// The totals, shared by every clone
+    state: Arc<Mutex<TrackerState>>,
+}
+
+impl Default for GpuMemoryTracker {
+    /// A tracker with the [`DEFAULT_BUDGET_BYTES`] budget.
+    fn default() -> Self {
+        Self::new(DEFAULT_BUDGET_BYTES)
+    }
+}
+
+impl GpuMemoryTracker {
+    /// Creates a tracker with nothing registered.
+    ///
+    /// # Arguments
+    /// * `budget` - Bytes of texture memory allowed before a warning is logged
+    pub fn new(budget: u64) -> Self {
+        Self {
+            state: Arc::new(Mutex::new(TrackerState {
+                entries: HashMap::new(),
+                next_id: 0,
+                budget,
+                warned: false,
+                pressure: 0,
+            })),
+        }
+    }
+
+    /// Locks the shared totals, recovering them if another thread panicked while holding them.
+    fn lock(&self) -> std::sync::MutexGuard<'_, TrackerState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Records memory the renderer doesn't create itself, such as a library's glyph atlas.
+    ///
+    /// # Arguments
+    /// * `category` - What the memory is used for
+    /// * `bytes` - Estimated size in bytes
+    ///
+    /// # Returns
+    /// A registration that removes the memory from the totals when dropped
+    pub fn register(&self, category: TextureCategory, bytes: u64) -> MemoryRegistration {
+        let mut state = self.lock();
+        let id = state.next_id;
+        state.next_id += 1;
+        state.entries.insert(id, (category, bytes));
+        state.check_budget();
+        MemoryRegistration {
+            id,
+            tracker: self.clone(),
+        }
+    }
+
+    /// Removes a registration from the totals.
+    fn unregister(&self, id: u64) {
+        let mut state = self.lock();
+        state.entries.remove(&id);
+        state.check_budget();
+    }
+
+    /// Returns the estimated texture memory in use, in bytes.
+    pub fn total(&self) -> u64 {
+        self.lock().total()
+    }
+
+    /// Returns the estimated memory in use by one category, in bytes.
+    ///
+    /// # Arguments
+    /// * `category` - The category to total
+    pub fn category_total(&self, category: TextureCategory) -> u64 {
+        self.lock()
+            .entries
+            .values()
+            .filter(|(entry_category, _)| *entry_category == category)
+            .map(|(_, bytes)| bytes)
+            .sum()
+    }
+
+    /// Returns the budget in bytes.
+    pub fn budget(&self) -> u64 {
+        self.lock().budget
+    }
+
+    /// Changes the budget and warns right away if the total is already over it.
+    ///
+    /// # Arguments
+    /// * `budget` - Bytes of texture memory allowed before a warning is logged
+    pub fn set_budget(&self, budget: u64) {
+        let mut state = self.lock();
+        state.budget = budget;
+        state.warned = false;
+        state.check_budget();
+    }
+
+    /// Returns whether the total is over the budget.
+    pub fn is_over_budget(&self) -> bool {
+        let state = self.lock();
+        state.total() > state.budget
+    }
+
+    /// Asks the systems with a resolution knob to cut back one step.
+    ///
+    /// Pressure only goes up until [`GpuMemoryTracker::relax`] is called; systems read it
+    /// through [`GpuMemoryTracker::pressure`] or [`GpuMemoryTracker::resolution_scale`] the
+    /// next time they create their textures.
+    ///
+    /// # Returns
+    /// The new pressure level, at most [`MAX_PRESSURE`]
+    pub fn reduce(&self) -> u32 {
+        let mut state = self.lock();
+        state.pressure = (state.pressure + 1).min(MAX_PRESSURE);
+        state.pressure
+    }
+
+    /// Lets systems go back to their full resolution.
+    pub fn relax(&self) {
+        self.lock().pressure = 0;
+    }
+
+    /// Returns how far systems have been asked to cut back, from 0 to [`MAX_PRESSURE`].
+    pub fn pressure(&self) -> u32 {
+        self.lock().pressure
+    }
+
+    /// Returns the fraction of their full resolution systems should render at.
+    ///
+    /// Each pressure level halves the resolution along each axis.
+    pub fn resolution_scale(&self) -> f32 {
+        0.5f32.powi(self.pressure() as i32)
+    }
+
+    /// Builds the usage summary shown in the debug overlay.
+    ///
+    /// # Returns
+    /// The total against the budget, then each category's share in megabytes on a second
+    /// line, e.g. `"GPU Tex: 12.4 / 512.0 MB\nScene 7.9, UI 1.2, Materials 3.0, Text 0.3"`
+    pub fn summary(&self) -> String {
+        let categories = TextureCategory::ALL
+            .iter()
+            .map(|category| {
+                format!(
+                    "{} {:.1}",
+                    category.label(),
+                    self.category_total(*category) as f64 / BYTES_PER_MB as f64
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+        format!(
+            "GPU Tex: {:.1} / {}\n{}",
+            self.total() as f64 / BYTES_PER_MB as f64,
+            format_mb(self.budget()),
+            categories
+        )
+    }
+}
+
+/// Memory counted in a [`GpuMemoryTracker`] until this is dropped.
+#[derive(Debug)]
+pub struct MemoryRegistration {
+    /// ID of the entry in the tracker
+    id: u64,
+    /// Tracker the entry belongs to
+    tracker: GpuMemoryTracker,
+}
+
+impl Drop for MemoryRegistration {
+    fn drop(&mut self) {
+        self.tracker.unregister(self.id);
+    }
+}
+
+/// A texture whose memory is counted until it is dropped.
+///
+/// Dereferences to the underlying [`wgpu::Texture`], so views and copies work as usual.
+#[derive(Debug)]
+pub struct TrackedTexture {
+    /// The texture itself
+    texture: wgpu::Texture,
+    /// Keeps the texture's size in the totals while it is alive
+    _registration: MemoryRegistration,
+}
+
+impl Deref for TrackedTexture {
+    type Target = wgpu::Texture;
+
+    fn deref(&self) -> &wgpu::Texture {
+        &self.texture
+    }
+}
+
+/// Creates a texture and counts its memory in the tracker.
+///
+/// Use this in place of `device.create_texture` everywhere the renderer creates textures.
+///
+/// # Arguments
+/// * `device` - WGPU device for creating the texture
+/// * `tracker` - Tracker the texture is counted in
+/// * `category` - What the texture is used for
+/// * `desc` - Descriptor the texture is created from
+///
+/// # Returns
+/// The texture, which stays counted until it is dropped
+pub fn create_tracked_texture(
+    device: &wgpu::Device,
+    tracker: &GpuMemoryTracker,
+    category: TextureCategory,
+    desc: &wgpu::TextureDescriptor,
+) -> TrackedTexture {
+    TrackedTexture {
+        texture: device.create_texture(desc),
+        _registration: tracker.register(category, texture_size_bytes(desc)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn descriptor(
+        width: u32,
+        height: u32,
+        mip_level_count: u32,
+        format: wgpu::TextureFormat,
+    ) -> wgpu::TextureDescriptor<'static> {
+        wgpu::TextureDescriptor {
+            label: None,
+            size: wgpu::Extent3d {
+                width,
+                height,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format,
+            usage: wgpu::TextureUsages::TEXTURE_BINDING,
+            view_formats: &[],
+        }
+    }
+
+    #[test]
+    fn test_texture_size_across_formats() {
+        use wgpu::TextureFormat;
+
+        let rgba = descriptor(256, 128, 1, TextureFormat::Rgba8UnormSrgb);
+        assert_eq!(texture_size_bytes(&rgba), 256 * 128 * 4);
+
+        let depth = descriptor(1920, 1080, 1, TextureFormat::Depth24Plus);
+        assert_eq!(texture_size_bytes(&depth), 1920 * 1080 * 4);
+
+        let grid = descriptor(51, 51, 1, TextureFormat::R8Unorm);
+        assert_eq!(texture_size_bytes(&grid), 51 * 51);
+
+        let hdr = descriptor(64, 64, 1, TextureFormat::Rgba16Float);
+        assert_eq!(texture_size_bytes(&hdr), 64 * 64 * 8);
+
+        // BC1 stores each 4x4 block in 8 bytes, rounding partial blocks up
+        let compressed = descriptor(10, 10, 1, TextureFormat::Bc1RgbaUnorm);
+        assert_eq!(texture_size_bytes(&compressed), 3 * 3 * 8);
+
+        // Multisampling multiplies the size by the sample count
+        let mut msaa = descriptor(100, 100, 1, TextureFormat::Depth24PlusStencil8);
+        msaa.sample_count = 4;
+        assert_eq!(texture_size_bytes(&msaa), 100 * 100 * 5 * 4);
+    }
+
+    #[test]
+    fn test_texture_size_counts_mips_and_layers() {
+        // 4x4 + 2x2 + 1x1 texels
+        let mipped = descriptor(4, 4, 3, wgpu::TextureFormat::Rgba8UnormSrgb);
+        assert_eq!(texture_size_bytes(&mipped), (16 + 4 + 1) * 4);
+
+        let mut layered = descriptor(8, 8, 1, wgpu::TextureFormat::R8Unorm);
+        layered.size.depth_or_array_layers = 6;
+        assert_eq!(texture_size_bytes(&layered), 8 * 8 * 6);
+    }
+
+    #[test]
+    fn test_tracker_totals_budget_and_pressure() {
+        let tracker = GpuMemoryTracker::new(1000);
+        let scene = tracker.register(TextureCategory::SceneTargets, 600);
+        let text = tracker.register(TextureCategory::Text, 300);
+        assert_eq!(tracker.total(), 900);
+        assert_eq!(tracker.category_total(TextureCategory::Text), 300);
+        assert!(!tracker.is_over_budget());
+
+        // Clones share the totals, and dropping a registration releases it
+        let clone = tracker.clone();
+        let ui = clone.register(TextureCategory::Ui, 200);
+        assert!(tracker.is_over_budget());
+        drop(scene);
+        assert_eq!(tracker.total(), 500);
+        assert!(!tracker.is_over_budget());
+        drop((text, ui));
+        assert_eq!(tracker.total(), 0);
+
+        assert_eq!(tracker.resolution_scale(), 1.0);
+        assert_eq!(tracker.reduce(), 1);
+        assert_eq!(tracker.reduce(), 2);
+        assert_eq!(tracker.reduce(), MAX_PRESSURE);
+        assert_eq!(tracker.resolution_scale(), 0.25);
+        tracker.relax();
+        assert_eq!(tracker.pressure(), 0);
+    }
+}
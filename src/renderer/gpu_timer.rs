@@ -0,0 +1,192 @@
+//! GPU timestamp queries for timing single passes.
+//!
+//! A [`PassTimer`] writes a timestamp at the start and end of one render pass, copies the pair
+//! into a buffer the CPU can read, and maps it once the frame has been submitted. Reading the
+//! result never waits on the GPU: while one measurement is still on its way back, later passes
+//! go untimed, so the timer samples every few frames rather than every frame.
+//!
+//! Timestamps need [`wgpu::Features::TIMESTAMP_QUERY`], which the device only asks for when the
+//! adapter supports it. Without it [`PassTimer::new`] returns `None` and callers carry on
+//! untimed.
+//!
+//! # Usage
+//!
+//! ```rust,ignore
+//! let mut timer = PassTimer::new(&device, &queue, "Light Shafts");
+//!
+//! // Each frame, before recording the pass
+//! if let Some(timer) = &mut timer {
+//!     timer.poll(&device);
+//! }
+//! let timestamp_writes = timer.as_ref().and_then(PassTimer::timestamp_writes);
+//! // ... begin the pass with timestamp_writes, draw, end the pass ...
+//! if let Some(timer) = &mut timer {
+//!     timer.resolve(&mut encoder);
+//! }
+//! let milliseconds = timer.and_then(|timer| timer.average_ms());
+//! ```
+
+use std::sync::{Arc, Mutex};
+
+/// Bytes taken by the two timestamps.
+const RESULT_BYTES: u64 = 2 * std::mem::size_of::<u64>() as u64;
+
+/// Weight of a new measurement in the running average.
+const AVERAGE_WEIGHT: f32 = 0.1;
+
+/// Where the current measurement is on its way back from the GPU.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Stage {
+    /// Free to time the next pass
+    Idle,
+    /// Timestamps were written and copied in a frame that hasn't been mapped yet
+    Recorded,
+    /// Waiting for the copy to be mapped
+    Mapping,
+}
+
+/// Times one render pass a frame on the GPU, when the device supports timestamps.
+pub struct PassTimer {
+    /// The two timestamps of the pass
+    query_set: wgpu::QuerySet,
+    /// Buffer the timestamps are resolved into
+    resolve_buffer: wgpu::Buffer,
+    /// Copy of the resolved timestamps the CPU maps
+    read_buffer: wgpu::Buffer,
+    /// Nanoseconds per timestamp tick
+    period: f32,
+    /// Where the current measurement is
+    stage: Stage,
+    /// Set by the map callback, with whether the mapping succeeded
+    mapped: Arc<Mutex<Option<bool>>>,
+    /// Whether this frame's pass asked for timestamps
+    timing: bool,
+    /// Running average of the pass's duration in milliseconds
+    average_ms: Option<f32>,
+}
+
+impl PassTimer {
+    /// Creates a timer if the device can write timestamps.
+    ///
+    /// # Arguments
+    /// * `device` - WebGPU device for creating the query set and buffers
+    /// * `queue` - Queue the pass is submitted on, for the timestamp period
+    /// * `label` - Name of the timed pass, for debug labels
+    ///
+    /// # Returns
+    /// The timer, or `None` without [`wgpu::Features::TIMESTAMP_QUERY`]
+    pub fn new(device: &wgpu::Device, queue: &wgpu::Queue, label: &str) -> Option<Self> {
+        if !device.features().contains(wgpu::Features::TIMESTAMP_QUERY) {
+            return None;
+        }
+        let query_set = device.create_query_set(&wgpu::QuerySetDescriptor {
+            label: Some(&format!("{} Timestamps", label)),
+            ty: wgpu::QueryType::Timestamp,
+            count: 2,
+        });
+        let resolve_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some(&format!("{} Timestamp Resolve Buffer", label)),
+            size: RESULT_BYTES,
+            usage: wgpu::BufferUsages::QUERY_RESOLVE | wgpu::BufferUsages::COPY_SRC,
+            mapped_at_creation: false,
+        });
+        let read_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some(&format!("{} Timestamp Read Buffer", label)),
+            size: RESULT_BYTES,
+            usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+            mapped_at_creation: false,
+        });
+        Some(Self {
+            query_set,
+            resolve_buffer,
+            read_buffer,
+            period: queue.get_timestamp_period(),
+            stage: Stage::Idle,
+            mapped: Arc::new(Mutex::new(None)),
+            timing: false,
+            average_ms: None,
+        })
+    }
+
+    /// Picks up a finished measurement and starts mapping the last recorded one.
+    ///
+    /// Call once a frame before recording the pass; the frame that recorded the timestamps
+    /// must have been submitted already.
+    ///
+    /// # Arguments
+    /// * `device` - The device, polled so map callbacks run
+    pub fn poll(&mut self, device: &wgpu::Device) {
+        self.timing = false;
+        match self.stage {
+            Stage::Idle => {}
+            Stage::Recorded => {
+                let mapped = Arc::clone(&self.mapped);
+                self.read_buffer
+                    .slice(..)
+                    .map_async(wgpu::MapMode::Read, move |result| {
+                        if let Ok(mut mapped) = mapped.lock() {
+                            *mapped = Some(result.is_ok());
+                        }
+                    });
+                self.stage = Stage::Mapping;
+            }
+            Stage::Mapping => {
+                device.poll(wgpu::Maintain::Poll);
+                let Some(success) = self.mapped.lock().ok().and_then(|mut m| m.take()) else {
+                    return;
+                };
+                if success {
+                    let ticks = {
+                        let data = self.read_buffer.slice(..).get_mapped_range();
+                        let start = u64::from_le_bytes(data[0..8].try_into().unwrap_or_default());
+                        let end = u64::from_le_bytes(data[8..16].try_into().unwrap_or_default());
+                        end.saturating_sub(start)
+                    };
+                    self.read_buffer.unmap();
+                    let milliseconds = ticks as f32 * self.period / 1_000_000.0;
+                    self.average_ms = Some(match self.average_ms {
+                        Some(average) => average + (milliseconds - average) * AVERAGE_WEIGHT,
+                        None => milliseconds,
+                    });
+                }
+                self.stage = Stage::Idle;
+            }
+        }
+    }
+
+    /// Returns the timestamp writes for this frame's pass.
+    ///
+    /// # Returns
+    /// The writes, or `None` while an earlier measurement is still on its way back
+    pub fn timestamp_writes(&mut self) -> Option<wgpu::RenderPassTimestampWrites<'_>> {
+        if self.stage != Stage::Idle {
+            return None;
+        }
+        self.timing = true;
+        Some(wgpu::RenderPassTimestampWrites {
+            query_set: &self.query_set,
+            beginning_of_pass_write_index: Some(0),
+            end_of_pass_write_index: Some(1),
+        })
+    }
+
+    /// Copies this frame's timestamps where they can be read, after the pass has ended.
+    ///
+    /// Does nothing if the pass wasn't timed.
+    ///
+    /// # Arguments
+    /// * `encoder` - The encoder the pass was recorded into
+    pub fn resolve(&mut self, encoder: &mut wgpu::CommandEncoder) {
+        if !std::mem::take(&mut self.timing) {
+            return;
+        }
+        encoder.resolve_query_set(&self.query_set, 0..2, &self.resolve_buffer, 0);
+        encoder.copy_buffer_to_buffer(&self.resolve_buffer, 0, &self.read_buffer, 0, RESULT_BYTES);
+        self.stage = Stage::Recorded;
+    }
+
+    /// Returns the running average of the pass's duration in milliseconds, once measured.
+    pub fn average_ms(&self) -> Option<f32> {
+        self.average_ms
+    }
+}
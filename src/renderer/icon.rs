@@ -1,12 +1,20 @@
-use wgpu::{
-    BindGroup, BindGroupLayout, BufferUsages, ColorTargetState, ColorWrites, Device,
-    FragmentState, MultisampleState, PrimitiveState, RenderPass, RenderPipeline,
-    SamplerBindingType, ShaderStages, Texture, TextureFormat, VertexAttribute,
-    VertexBufferLayout, VertexFormat, VertexState, util::DeviceExt,
+use crate::renderer::gpu_memory::{
+    GpuMemoryTracker, TextureCategory, TrackedTexture, create_tracked_texture,
 };
 use image;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::mem;
+use wgpu::{
+    BindGroup, BindGroupLayout, BufferUsages, ColorTargetState, ColorWrites, Device, FragmentState,
+    MultisampleState, PrimitiveState, RenderPass, RenderPipeline, SamplerBindingType, ShaderStages,
+    TextureFormat, VertexAttribute, VertexBufferLayout, VertexFormat, VertexState, util::DeviceExt,
+};
+
+/// Texture ID of the built-in placeholder drawn for icons with an unknown texture.
+pub const MISSING_ICON_ID: &str = "missing_icon";
+
+/// Side length in pixels of the generated missing icon texture.
+const MISSING_ICON_SIZE: u32 = 8;
 
 /// Vertex data structure for rendering icon quads.
 ///
@@ -64,6 +72,11 @@ pub struct Icon {
     pub height: f32,
     /// Identifier for the texture to use when rendering this icon
     pub texture_id: String,
+    /// Whether to round the icon's edges to whole pixels when drawing.
+    ///
+    /// Icons at rest are snapped so small sizes stay crisp; icons that are animating
+    /// turn this off so their movement stays smooth instead of stepping a pixel at a time.
+    pub snap_to_pixels: bool,
 }
 
 impl Icon {
@@ -71,7 +84,7 @@ impl Icon {
     ///
     /// # Arguments
     /// * `x` - X position in screen coordinates
-    /// * `y` - Y position in screen coordinates  
+    /// * `y` - Y position of the bottom edge, in pixels up from the bottom of the window
     /// * `width` - Width in pixels
     /// * `height` - Height in pixels
     /// * `texture_id` - String identifier for the texture to use
@@ -85,8 +98,39 @@ impl Icon {
             width,
             height,
             texture_id,
+            snap_to_pixels: true,
         }
     }
+
+    /// Sets whether the icon is snapped to whole pixels when drawn.
+    ///
+    /// # Arguments
+    /// * `snap` - `false` while the icon is animating, `true` when it is at rest
+    ///
+    /// # Returns
+    /// The icon with the updated snapping flag
+    pub fn with_pixel_snap(mut self, snap: bool) -> Self {
+        self.snap_to_pixels = snap;
+        self
+    }
+
+    /// Returns the rectangle the icon is drawn into, in screen pixels.
+    ///
+    /// When snapping is enabled the edges are rounded independently, so neighbouring
+    /// icons never overlap or leave gaps and the size stays consistent across frames.
+    ///
+    /// # Returns
+    /// `(x, y, width, height)` in screen coordinates
+    pub fn draw_rect(&self) -> (f32, f32, f32, f32) {
+        if !self.snap_to_pixels {
+            return (self.x, self.y, self.width, self.height);
+        }
+        let left = self.x.round();
+        let top = self.y.round();
+        let right = (self.x + self.width).round();
+        let bottom = (self.y + self.height).round();
+        (left, top, right - left, bottom - top)
+    }
 }
 
 /// High-performance batch renderer for 2D icons using WGPU.
@@ -106,7 +150,9 @@ pub struct IconRenderer {
     /// Collection of icons to be rendered
     icons: Vec<Icon>,
     /// Cache of loaded textures, views, and bind groups keyed by texture ID
-    textures: HashMap<String, (Texture, BindGroup)>,
+    textures: HashMap<String, (TrackedTexture, BindGroup)>,
+    /// Tracker every icon texture is counted in as UI
+    gpu_memory: GpuMemoryTracker,
     /// Current window width in pixels (used for coordinate conversion)
     window_width: f32,
     /// Current window height in pixels (used for coordinate conversion)
@@ -117,6 +163,8 @@ pub struct IconRenderer {
     cached_index_buffers: HashMap<String, wgpu::Buffer>,
     /// Number of icons for each texture in the cached buffers
     cached_icon_counts: HashMap<String, usize>,
+    /// Unknown texture IDs that have already been reported, to avoid log spam
+    reported_missing: HashSet<String>,
 }
 
 impl IconRenderer {
@@ -128,13 +176,22 @@ impl IconRenderer {
     /// - Render pipeline with alpha blending enabled
     /// - Default window dimensions
     ///
+    /// - The built-in missing icon texture used for unknown texture IDs
+    ///
     /// # Arguments
     /// * `device` - The WGPU device for creating graphics resources
+    /// * `queue` - The WGPU queue for uploading the missing icon texture
     /// * `surface_format` - The texture format of the render target
+    /// * `gpu_memory` - Tracker every icon texture is counted in
     ///
     /// # Returns
     /// A new `IconRenderer` instance ready for use
-    pub fn new(device: &Device, surface_format: TextureFormat) -> Self {
+    pub fn new(
+        device: &Device,
+        queue: &wgpu::Queue,
+        surface_format: TextureFormat,
+        gpu_memory: &GpuMemoryTracker,
+    ) -> Self {
         // Load the icon shader from an embedded WGSL file
         let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
             label: Some("Icon Shader"),
@@ -225,17 +282,31 @@ impl IconRenderer {
             cache: None,
         });
 
-        Self {
+        let mut renderer = Self {
             render_pipeline,
             bind_group_layout,
             icons: Vec::new(),
             textures: HashMap::new(),
+            gpu_memory: gpu_memory.clone(),
             window_width: 1360.0,
             window_height: 768.0,
             cached_vertex_buffers: HashMap::new(),
             cached_index_buffers: HashMap::new(),
             cached_icon_counts: HashMap::new(),
-        }
+            reported_missing: HashSet::new(),
+        };
+
+        // Magenta/black checkerboard so missing icons are obvious but never invisible
+        let missing_pixels = missing_icon_pixels();
+        renderer.upload_texture(
+            device,
+            queue,
+            MISSING_ICON_ID,
+            &missing_pixels,
+            (MISSING_ICON_SIZE, MISSING_ICON_SIZE),
+        );
+
+        renderer
     }
 
     /// Loads a texture from embedded assets and creates associated GPU resources.
@@ -273,146 +344,152 @@ impl IconRenderer {
         let rgba = img.to_rgba8();
         let dimensions = rgba.dimensions();
 
-        let size = wgpu::Extent3d {
-            width: dimensions.0,
-            height: dimensions.1,
-            depth_or_array_layers: 1,
-        };
-
-        // Create GPU texture
-        let texture = device.create_texture(&wgpu::TextureDescriptor {
-            label: Some(&format!("Icon texture: {}", texture_id)),
-            size,
-            mip_level_count: 1,
-            sample_count: 1,
-            dimension: wgpu::TextureDimension::D2,
-            format: wgpu::TextureFormat::Rgba8Unorm,
-            usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
-            view_formats: &[],
-        });
-
-        // Upload image data to GPU
-        queue.write_texture(
-            wgpu::TexelCopyTextureInfo {
-                aspect: wgpu::TextureAspect::All,
-                texture: &texture,
-                mip_level: 0,
-                origin: wgpu::Origin3d::ZERO,
-            },
-            &rgba,
-            wgpu::TexelCopyBufferLayout {
-                offset: 0,
-                bytes_per_row: Some(4 * dimensions.0),
-                rows_per_image: Some(dimensions.1),
-            },
-            size,
-        );
-
-        // Create texture view and sampler
-        let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
-        let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
-            address_mode_u: wgpu::AddressMode::ClampToEdge,
-            address_mode_v: wgpu::AddressMode::ClampToEdge,
-            address_mode_w: wgpu::AddressMode::ClampToEdge,
-            mag_filter: wgpu::FilterMode::Linear,
-            min_filter: wgpu::FilterMode::Linear,
-            mipmap_filter: wgpu::FilterMode::Nearest,
-            ..Default::default()
-        });
-
-        // Create bind group
-        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
-            layout: &self.bind_group_layout,
-            entries: &[
-                wgpu::BindGroupEntry {
-                    binding: 0,
-                    resource: wgpu::BindingResource::TextureView(&view),
-                },
-                wgpu::BindGroupEntry {
-                    binding: 1,
-                    resource: wgpu::BindingResource::Sampler(&sampler),
-                },
-            ],
-            label: Some(&format!("Icon bind group: {}", texture_id)),
-        });
-
-        // Cache the texture and bind group
-        self.textures.insert(texture_id.to_string(), (texture, bind_group));
+        self.upload_texture(device, queue, texture_id, &rgba, dimensions);
 
         Ok(())
     }
 
-    /// Loads a texture from a file path and creates associated GPU resources.
+    /// Registers a texture from raw RGBA8 pixels after the renderer has been created.
     ///
-    /// This method:
-    /// 1. Loads an image from the specified file path
-    /// 2. Converts it to RGBA8 format
-    /// 3. Creates a WGPU texture and uploads the image data
-    /// 4. Creates a texture view and sampler
-    /// 5. Creates a bind group for use in rendering
-    /// 6. Caches all resources for later use
+    /// This is the runtime extension point for icons that are not part of the
+    /// embedded asset bundle (e.g. new upgrade icons or modded content). Each texture
+    /// owns its own bind group, so registering new textures never disturbs the IDs
+    /// or GPU resources of textures that are already in use. Registering an ID that
+    /// already exists replaces that texture in place and refreshes its cached buffers.
     ///
     /// # Arguments
     /// * `device` - The WGPU device for creating resources
     /// * `queue` - The WGPU queue for uploading texture data
-    /// * `path` - File system path to the image file
     /// * `texture_id` - Unique identifier for this texture
+    /// * `rgba_bytes` - Tightly packed RGBA8 pixel data, row-major from the top-left
+    /// * `dimensions` - Width and height of the image in pixels
     ///
     /// # Returns
-    /// `Ok(())` on success, or an error if the image cannot be loaded
+    /// `Ok(())` on success, or an error message if the input is invalid
     ///
     /// # Errors
     /// Returns an error if:
-    /// - The image file cannot be found or read
-    /// - The image format is not supported
-    /// - GPU resource creation fails
-    pub fn load_texture(
+    /// - Either dimension is zero
+    /// - `rgba_bytes` does not contain exactly `width * height * 4` bytes
+    /// - `texture_id` is the reserved missing icon ID
+    pub fn register_texture(
         &mut self,
         device: &Device,
         queue: &wgpu::Queue,
-        path: &str,
         texture_id: &str,
-    ) -> Result<(), Box<dyn std::error::Error>> {
-        // Load and convert image to RGBA8
-        let img = image::open(std::path::Path::new(path))?;
-        let rgba = img.to_rgba8();
-        let dimensions = rgba.dimensions();
+        rgba_bytes: &[u8],
+        dimensions: (u32, u32),
+    ) -> Result<(), String> {
+        if texture_id == MISSING_ICON_ID {
+            return Err(format!("Texture ID '{}' is reserved", MISSING_ICON_ID));
+        }
+        if dimensions.0 == 0 || dimensions.1 == 0 {
+            return Err(format!(
+                "Icon texture '{}' has zero-sized dimensions {}x{}",
+                texture_id, dimensions.0, dimensions.1
+            ));
+        }
+        let expected_len = dimensions.0 as usize * dimensions.1 as usize * 4;
+        if rgba_bytes.len() != expected_len {
+            return Err(format!(
+                "Icon texture '{}' expected {} bytes for {}x{} RGBA8, got {}",
+                texture_id,
+                expected_len,
+                dimensions.0,
+                dimensions.1,
+                rgba_bytes.len()
+            ));
+        }
 
+        self.upload_texture(device, queue, texture_id, rgba_bytes, dimensions);
+        Ok(())
+    }
+
+    /// Drops a loaded or registered texture, freeing its GPU memory.
+    ///
+    /// Icons still queued with the ID draw the missing icon until they are cleared.
+    ///
+    /// # Arguments
+    /// * `texture_id` - The texture identifier to drop
+    ///
+    /// # Returns
+    /// `true` if a texture was loaded under this ID
+    pub fn remove_texture(&mut self, texture_id: &str) -> bool {
+        if self.textures.remove(texture_id).is_none() {
+            return false;
+        }
+        for id in [texture_id, MISSING_ICON_ID] {
+            self.cached_vertex_buffers.remove(id);
+            self.cached_index_buffers.remove(id);
+            self.cached_icon_counts.remove(id);
+        }
+        true
+    }
+
+    /// Creates the GPU texture and bind group for RGBA8 pixels and caches them.
+    ///
+    /// The full mip chain is generated on the CPU and uploaded with the texture, and the
+    /// sampler filters trilinearly, so icons drawn far below their source resolution
+    /// stay smooth instead of shimmering.
+    ///
+    /// Any cached vertex/index buffers for the ID and for the missing icon batch are
+    /// dropped so icons that previously fell back to the placeholder pick up the
+    /// new texture on the next render.
+    fn upload_texture(
+        &mut self,
+        device: &Device,
+        queue: &wgpu::Queue,
+        texture_id: &str,
+        rgba: &[u8],
+        dimensions: (u32, u32),
+    ) {
         let size = wgpu::Extent3d {
             width: dimensions.0,
             height: dimensions.1,
             depth_or_array_layers: 1,
         };
+        let mip_chain = generate_mip_chain(rgba, dimensions);
 
         // Create GPU texture
-        let texture = device.create_texture(&wgpu::TextureDescriptor {
-            label: Some(&format!("Icon texture: {}", texture_id)),
-            size,
-            mip_level_count: 1,
-            sample_count: 1,
-            dimension: wgpu::TextureDimension::D2,
-            format: wgpu::TextureFormat::Rgba8Unorm,
-            usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
-            view_formats: &[],
-        });
-
-        // Upload image data to GPU
-        queue.write_texture(
-            wgpu::TexelCopyTextureInfo {
-                aspect: wgpu::TextureAspect::All,
-                texture: &texture,
-                mip_level: 0,
-                origin: wgpu::Origin3d::ZERO,
+        let texture = create_tracked_texture(
+            device,
+            &self.gpu_memory,
+            TextureCategory::Ui,
+            &wgpu::TextureDescriptor {
+                label: Some(&format!("Icon texture: {}", texture_id)),
+                size,
+                mip_level_count: mip_chain.len() as u32,
+                sample_count: 1,
+                dimension: wgpu::TextureDimension::D2,
+                format: wgpu::TextureFormat::Rgba8Unorm,
+                usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
+                view_formats: &[],
             },
-            &rgba,
-            wgpu::TexelCopyBufferLayout {
-                offset: 0,
-                bytes_per_row: Some(4 * dimensions.0),
-                rows_per_image: Some(dimensions.1),
-            },
-            size,
         );
 
+        // Upload every mip level to the GPU
+        for (mip_level, (pixels, (width, height))) in mip_chain.iter().enumerate() {
+            queue.write_texture(
+                wgpu::TexelCopyTextureInfo {
+                    aspect: wgpu::TextureAspect::All,
+                    texture: &texture,
+                    mip_level: mip_level as u32,
+                    origin: wgpu::Origin3d::ZERO,
+                },
+                pixels,
+                wgpu::TexelCopyBufferLayout {
+                    offset: 0,
+                    bytes_per_row: Some(4 * width),
+                    rows_per_image: Some(*height),
+                },
+                wgpu::Extent3d {
+                    width: *width,
+                    height: *height,
+                    depth_or_array_layers: 1,
+                },
+            );
+        }
+
         // Create texture view and sampler
         let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
         let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
@@ -421,7 +498,7 @@ impl IconRenderer {
             address_mode_w: wgpu::AddressMode::ClampToEdge,
             mag_filter: wgpu::FilterMode::Linear,
             min_filter: wgpu::FilterMode::Linear,
-            mipmap_filter: wgpu::FilterMode::Nearest,
+            mipmap_filter: wgpu::FilterMode::Linear,
             ..Default::default()
         });
 
@@ -442,15 +519,23 @@ impl IconRenderer {
         });
 
         // Cache the texture and bind group
-        self.textures.insert(texture_id.to_string(), (texture, bind_group));
-
-        Ok(())
+        self.textures
+            .insert(texture_id.to_string(), (texture, bind_group));
+
+        // Icons for this ID (or ones that fell back to the placeholder) need new batches
+        for id in [texture_id, MISSING_ICON_ID] {
+            self.cached_vertex_buffers.remove(id);
+            self.cached_index_buffers.remove(id);
+            self.cached_icon_counts.remove(id);
+        }
+        self.reported_missing.remove(texture_id);
     }
 
     /// Adds an icon to the render queue.
     ///
     /// Icons added via this method will be rendered on the next call to `render()`.
-    /// The icon's texture must have been previously loaded via `load_texture()`.
+    /// If the icon's texture has not been loaded or registered, the missing icon
+    /// texture is drawn in its place.
     ///
     /// # Arguments
     /// * `icon` - The icon to add to the render queue
@@ -494,7 +579,8 @@ impl IconRenderer {
     ///
     /// ## Texture Batching
     /// Icons are grouped by texture ID to minimize the number of bind group changes,
-    /// which is an expensive GPU operation.
+    /// which is an expensive GPU operation. Icons whose texture ID is unknown are
+    /// drawn with the missing icon texture instead of being skipped.
     ///
     /// ## Buffer Caching
     /// Vertex and index buffers are cached and reused when the number of icons
@@ -519,15 +605,22 @@ impl IconRenderer {
 
         render_pass.set_pipeline(&self.render_pipeline);
 
-        // Group icons by texture to minimize bind group changes
-        let mut icons_by_texture: HashMap<String, Vec<&Icon>> = HashMap::new();
+        // Report unknown texture IDs once; they render with the missing icon
         for icon in &self.icons {
-            icons_by_texture
-                .entry(icon.texture_id.clone())
-                .or_insert_with(Vec::new)
-                .push(icon);
+            if !self.textures.contains_key(&icon.texture_id)
+                && self.reported_missing.insert(icon.texture_id.clone())
+            {
+                println!(
+                    "Icon texture '{}' not found, using missing icon",
+                    icon.texture_id
+                );
+            }
         }
 
+        // Group icons by texture to minimize bind group changes
+        let icons_by_texture =
+            batch_icons_by_texture(&self.icons, |id| self.textures.contains_key(id));
+
         // Render each texture batch
         for (texture_id, icons) in icons_by_texture {
             if let Some((_texture, bind_group)) = self.textures.get(&texture_id) {
@@ -544,10 +637,11 @@ impl IconRenderer {
 
                     for (icon_index, icon) in icons.iter().enumerate() {
                         // Convert screen coordinates to normalized device coordinates
-                        let x = (icon.x / self.window_width) * 2.0 - 1.0;
-                        let y = (icon.y / self.window_height) * 2.0 - 1.0;
-                        let width = (icon.width / self.window_width) * 2.0;
-                        let height = (icon.height / self.window_height) * 2.0;
+                        let (icon_x, icon_y, icon_width, icon_height) = icon.draw_rect();
+                        let x = (icon_x / self.window_width) * 2.0 - 1.0;
+                        let y = (icon_y / self.window_height) * 2.0 - 1.0;
+                        let width = (icon_width / self.window_width) * 2.0;
+                        let height = (icon_height / self.window_height) * 2.0;
 
                         // Create quad vertices for this icon
                         let vertices = [
@@ -630,3 +724,229 @@ impl IconRenderer {
         }
     }
 }
+
+/// Groups icons into render batches keyed by the texture they will be drawn with.
+///
+/// Icons whose texture ID is not known are collected under [`MISSING_ICON_ID`].
+///
+/// # Arguments
+/// * `icons` - The queued icons to batch
+/// * `has_texture` - Returns whether a texture ID is currently loaded
+///
+/// # Returns
+/// A map of texture ID to the icons that use it, in queue order
+fn batch_icons_by_texture(
+    icons: &[Icon],
+    has_texture: impl Fn(&str) -> bool,
+) -> HashMap<String, Vec<&Icon>> {
+    let mut icons_by_texture: HashMap<String, Vec<&Icon>> = HashMap::new();
+    for icon in icons {
+        let texture_id = if has_texture(&icon.texture_id) {
+            icon.texture_id.clone()
+        } else {
+            MISSING_ICON_ID.to_string()
+        };
+        icons_by_texture.entry(texture_id).or_default().push(icon);
+    }
+    icons_by_texture
+}
+
+/// Returns how many mip levels a full chain needs for a texture of the given size.
+///
+/// # Arguments
+/// * `width` - Width of the base level in pixels
+/// * `height` - Height of the base level in pixels
+///
+/// # Returns
+/// The number of levels from the base size down to 1x1, at least 1
+pub fn mip_level_count(width: u32, height: u32) -> u32 {
+    32 - width.max(height).max(1).leading_zeros()
+}
+
+/// Builds the full mip chain for an RGBA8 image.
+///
+/// Each level halves the previous one (rounding down, never below 1 pixel) using a
+/// 2x2 box filter. Colors are weighted by alpha so transparent pixels around an icon's
+/// edge don't darken its outline as it shrinks. Odd sizes fold the last row or column
+/// into the final texel.
+///
+/// # Arguments
+/// * `rgba` - Pixels of the base level, row-major, 4 bytes per pixel
+/// * `dimensions` - Width and height of the base level
+///
+/// # Returns
+/// The pixels and dimensions of every level, starting with a copy of the base level
+pub fn generate_mip_chain(rgba: &[u8], dimensions: (u32, u32)) -> Vec<(Vec<u8>, (u32, u32))> {
+    let level_count = mip_level_count(dimensions.0, dimensions.1);
+    let mut levels = Vec::with_capacity(level_count as usize);
+    levels.push((rgba.to_vec(), dimensions));
+
+    for _ in 1..level_count {
+        let (source, (src_width, src_height)) = levels.last().expect("base level exists");
+        let (src_width, src_height) = (*src_width as usize, *src_height as usize);
+        let width = (src_width / 2).max(1);
+        let height = (src_height / 2).max(1);
+        let mut pixels = Vec::with_capacity(width * height * 4);
+
+        for y in 0..height {
+            // The last row of an odd-height level also takes the leftover source row
+            let y_end = if y == height - 1 {
+                src_height
+            } else {
+                y * 2 + 2
+            };
+            for x in 0..width {
+                let x_end = if x == width - 1 { src_width } else { x * 2 + 2 };
+                let mut color_sum = [0.0_f32; 3];
+                let mut alpha_sum = 0.0_f32;
+                let mut count = 0.0_f32;
+                for sy in y * 2..y_end {
+                    for sx in x * 2..x_end {
+                        let index = (sy * src_width + sx) * 4;
+                        let alpha = source[index + 3] as f32;
+                        for (channel, sum) in color_sum.iter_mut().enumerate() {
+                            *sum += source[index + channel] as f32 * alpha;
+                        }
+                        alpha_sum += alpha;
+                        count += 1.0;
+                    }
+                }
+                for sum in color_sum {
+                    let value = if alpha_sum > 0.0 {
+                        sum / alpha_sum
+                    } else {
+                        0.0
+                    };
+                    pixels.push(value.round() as u8);
+                }
+                pixels.push((alpha_sum / count).round() as u8);
+            }
+        }
+
+        levels.push((pixels, (width as u32, height as u32)));
+    }
+
+    levels
+}
+
+/// Generates the RGBA8 pixels for the missing icon texture.
+///
+/// # Returns
+/// A magenta and black checkerboard of `MISSING_ICON_SIZE` squared pixels
+fn missing_icon_pixels() -> Vec<u8> {
+    let mut pixels = Vec::with_capacity((MISSING_ICON_SIZE * MISSING_ICON_SIZE * 4) as usize);
+    for y in 0..MISSING_ICON_SIZE {
+        for x in 0..MISSING_ICON_SIZE {
+            if (x / 2 + y / 2) % 2 == 0 {
+                pixels.extend_from_slice(&[255, 0, 255, 255]);
+            } else {
+                pixels.extend_from_slice(&[0, 0, 0, 255]);
+            }
+        }
+    }
+    pixels
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn batch_ids(icons: &[Icon], loaded: &HashSet<String>) -> HashMap<String, Vec<String>> {
+        batch_icons_by_texture(icons, |id| loaded.contains(id))
+            .into_iter()
+            .map(|(id, batch)| {
+                (
+                    id,
+                    batch.iter().map(|icon| icon.texture_id.clone()).collect(),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_registering_icon_keeps_existing_batches_stable() {
+        let mut loaded: HashSet<String> = ["dash_icon", "speed_up_icon"]
+            .iter()
+            .map(|id| id.to_string())
+            .collect();
+        let icons = vec![
+            Icon::new(0.0, 0.0, 32.0, 32.0, "dash_icon".to_string()),
+            Icon::new(40.0, 0.0, 32.0, 32.0, "speed_up_icon".to_string()),
+            Icon::new(80.0, 0.0, 32.0, 32.0, "blink_icon".to_string()),
+        ];
+
+        // Several frames before the new icon exists
+        let before = batch_ids(&icons, &loaded);
+        for _ in 0..3 {
+            assert_eq!(batch_ids(&icons, &loaded), before);
+        }
+        assert_eq!(before[MISSING_ICON_ID], vec!["blink_icon".to_string()]);
+
+        // Register the new icon mid-session
+        loaded.insert("blink_icon".to_string());
+        let after = batch_ids(&icons, &loaded);
+
+        assert_eq!(after["dash_icon"], before["dash_icon"]);
+        assert_eq!(after["speed_up_icon"], before["speed_up_icon"]);
+        assert_eq!(after["blink_icon"], vec!["blink_icon".to_string()]);
+        assert!(!after.contains_key(MISSING_ICON_ID));
+    }
+
+    #[test]
+    fn test_missing_icon_pixels_are_opaque_rgba() {
+        let pixels = missing_icon_pixels();
+        assert_eq!(
+            pixels.len(),
+            (MISSING_ICON_SIZE * MISSING_ICON_SIZE * 4) as usize
+        );
+        assert!(pixels.chunks(4).all(|pixel| pixel[3] == 255));
+    }
+
+    #[test]
+    fn test_mip_chain_levels_halve_down_to_one_pixel() {
+        assert_eq!(mip_level_count(1, 1), 1);
+        assert_eq!(mip_level_count(256, 256), 9);
+        assert_eq!(mip_level_count(100, 30), 7);
+
+        let pixels = vec![255; 100 * 30 * 4];
+        let chain = generate_mip_chain(&pixels, (100, 30));
+        let sizes: Vec<(u32, u32)> = chain.iter().map(|(_, size)| *size).collect();
+        assert_eq!(
+            sizes,
+            vec![
+                (100, 30),
+                (50, 15),
+                (25, 7),
+                (12, 3),
+                (6, 1),
+                (3, 1),
+                (1, 1)
+            ]
+        );
+        for (level, (width, height)) in &chain {
+            assert_eq!(level.len(), (width * height * 4) as usize);
+            assert!(level.iter().all(|&byte| byte == 255));
+        }
+    }
+
+    #[test]
+    fn test_mip_chain_ignores_color_of_transparent_pixels() {
+        // One opaque red pixel next to three transparent black ones
+        let pixels = [
+            255, 0, 0, 255, 0, 0, 0, 0, //
+            0, 0, 0, 0, 0, 0, 0, 0,
+        ];
+        let chain = generate_mip_chain(&pixels, (2, 2));
+        assert_eq!(chain.len(), 2);
+        assert_eq!(chain[1].0, vec![255, 0, 0, 64]);
+    }
+
+    #[test]
+    fn test_snapped_icons_draw_on_whole_pixels() {
+        let icon = Icon::new(10.4, 20.6, 31.3, 31.3, "dash_icon".to_string());
+        assert_eq!(icon.draw_rect(), (10.0, 21.0, 32.0, 31.0));
+
+        let animating = icon.with_pixel_snap(false);
+        assert_eq!(animating.draw_rect(), (10.4, 20.6, 31.3, 31.3));
+    }
+}
@@ -1,15 +1,68 @@
+use std::collections::HashSet;
 use std::sync::{Arc, Mutex};
 use std::time::Instant;
 
 use crate::{
-    game::maze::generator::{Maze, MazeGenerator},
-    renderer::pipeline_builder::{
-        BindGroupLayoutBuilder, PipelineBuilder, create_fullscreen_vertices, create_uniform_buffer,
-        create_vertex_2d_layout,
+    game::{
+        levels::{LevelPlan, MazeConfig},
+        maze::{
+            floors::{self, Layer, Stairwell},
+            generator::{Cell, Maze, MazeGenerator, PreviewTile},
+        },
+    },
+    renderer::{
+        color,
+        gpu_memory::{GpuMemoryTracker, TextureCategory, TrackedTexture, create_tracked_texture},
+        pipeline_builder::{
+            BindGroupLayoutBuilder, PipelineBuilder, create_fullscreen_vertices,
+            create_uniform_buffer, create_vertex_2d_layout,
+        },
+        safe_area::{clamp_scissor, top_bar_height},
     },
 };
 use wgpu;
-use winit::window::Window;
+use wgpu::util::DeviceExt;
+use winit::dpi::PhysicalSize;
+
+/// Strategies for drawing the maze preview on the loading screen.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MazePreview {
+    /// Upload one byte per wall grid entry and color the maze in the fragment shader
+    Gpu,
+    /// Rasterize the maze to an RGBA image on the CPU and upload the whole image
+    CpuFallback,
+}
+
+impl MazePreview {
+    /// Picks the preview a device can draw.
+    ///
+    /// The GPU preview reads two storage buffers in its fragment shader, which downlevel
+    /// adapters such as WebGL2 don't offer; those rasterize the maze on the CPU instead.
+    ///
+    /// # Arguments
+    /// * `device` - The device the preview is drawn with
+    pub fn for_device(device: &wgpu::Device) -> Self {
+        if device.limits().max_storage_buffers_per_shader_stage >= 2 {
+            MazePreview::Gpu
+        } else {
+            MazePreview::CpuFallback
+        }
+    }
+}
+
+/// Seconds the solution path pulse takes to sweep from the entrance to the exit.
+pub const PATH_PULSE_SECS: f32 = 1.0;
+
+/// How the solution path is highlighted on the maze preview.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PathHighlight {
+    /// Not highlighted
+    Hidden,
+    /// A band sweeping from the entrance to the exit, with how far it has gone from 0.0 to 1.0
+    Pulse(f32),
+    /// The whole path lit at once, shown instead of the pulse when effects are reduced
+    Static,
+}
 
 /// Main loading screen renderer that orchestrates maze generation visualization.
 ///
@@ -17,11 +70,21 @@ use winit::window::Window;
 /// - The maze itself being generated in real-time
 /// - An animated loading bar showing generation progress
 /// - A special effect on the maze exit cell when generation completes
+///
+/// Two-floor levels generate the lower floor first and then swap in the upper floor,
+/// so the preview always shows the floor being carved. Once the maze is complete the
+/// solution path pulses once from the entrance to the exit.
 pub struct LoadingRenderer {
     /// The maze generator that runs in a separate thread
     pub generator: MazeGenerator,
     /// Thread-safe reference to the maze being generated
     pub maze: Arc<Mutex<Maze>>,
+    /// Generator and maze of the upper floor, waiting for the lower floor to finish
+    pub upper_floor: Option<(MazeGenerator, Arc<Mutex<Maze>>)>,
+    /// The finished lower floor once the upper floor has started generating
+    pub lower_floor: Option<Arc<Mutex<Maze>>>,
+    /// Stairwells joining the floors; empty for single-floor levels
+    pub stairwells: Vec<Stairwell>,
 
     // Rendering components
     /// Renders the maze texture to the screen
@@ -31,10 +94,20 @@ pub struct LoadingRenderer {
     /// Renders special effects on the exit cell
     pub exit_shader_renderer: ExitShaderRenderer,
 
-    /// GPU texture containing the maze visualization data
-    pub texture: wgpu::Texture,
     /// Timestamp of the last frame update for timing calculations
     pub last_update: Instant,
+    /// Animation time every loading screen shader is given instead of the time since its
+    /// renderer was created; golden image tests pin it so frames come out the same
+    pub pinned_time: Option<f32>,
+
+    /// Solution path of the finished maze in wall grid coordinates, empty until it is complete
+    pub solution_path: Vec<Cell>,
+    /// Maze animation time the path pulse started at, `None` until the maze is complete
+    path_pulse_start: Option<f32>,
+    /// Whether the player skipped the path pulse
+    path_pulse_skipped: bool,
+    /// Whether `solution_path` has been sent to the maze preview
+    path_uploaded: bool,
 }
 
 impl LoadingRenderer {
@@ -43,10 +116,82 @@ impl LoadingRenderer {
     /// # Arguments
     /// * `device` - The WGPU device for creating GPU resources
     /// * `surface_config` - Surface configuration for render target format
+    /// * `gpu_memory` - Tracker the maze preview texture is counted in
     ///
     /// # Returns
     /// A fully initialized LoadingRenderer ready to render maze generation
-    pub fn new(device: &wgpu::Device, surface_config: &wgpu::SurfaceConfiguration) -> Self {
+    pub fn new(
+        device: &wgpu::Device,
+        surface_config: &wgpu::SurfaceConfiguration,
+        gpu_memory: &GpuMemoryTracker,
+    ) -> Self {
+        Self::new_seeded(device, surface_config, gpu_memory, None)
+    }
+
+    /// Creates a new loading renderer whose maze can be fixed by a seed.
+    ///
+    /// # Arguments
+    /// * `device` - The WGPU device for creating GPU resources
+    /// * `surface_config` - Surface configuration for render target format
+    /// * `gpu_memory` - Tracker the maze preview texture is counted in
+    /// * `seed` - Seed for the maze layout and exit, or `None` for a random maze
+    ///
+    /// # Returns
+    /// A fully initialized LoadingRenderer ready to render maze generation
+    pub fn new_seeded(
+        device: &wgpu::Device,
+        surface_config: &wgpu::SurfaceConfiguration,
+        gpu_memory: &GpuMemoryTracker,
+        seed: Option<u64>,
+    ) -> Self {
+        Self::new_sized(
+            device,
+            surface_config,
+            gpu_memory,
+            &MazeConfig::standard().with_seed(seed),
+        )
+    }
+
+    /// Creates a new loading renderer for a planned level.
+    ///
+    /// # Arguments
+    /// * `device` - The WGPU device for creating GPU resources
+    /// * `surface_config` - Surface configuration for render target format
+    /// * `gpu_memory` - Tracker the maze preview texture is counted in
+    /// * `plan` - The level's plan, giving the maze's seed, size, and floors
+    ///
+    /// # Returns
+    /// A fully initialized LoadingRenderer ready to render the level's maze generation
+    pub fn for_plan(
+        device: &wgpu::Device,
+        surface_config: &wgpu::SurfaceConfiguration,
+        gpu_memory: &GpuMemoryTracker,
+        plan: &LevelPlan,
+    ) -> Self {
+        let renderer = Self::new_sized(device, surface_config, gpu_memory, &plan.maze_config());
+        if plan.multi_floor {
+            renderer.with_upper_floor()
+        } else {
+            renderer
+        }
+    }
+
+    /// Creates a new loading renderer for a maze of any size.
+    ///
+    /// # Arguments
+    /// * `device` - The WGPU device for creating GPU resources
+    /// * `surface_config` - Surface configuration for render target format
+    /// * `gpu_memory` - Tracker the maze preview texture is counted in
+    /// * `maze_config` - The maze's size, and its seed or `None` for a random maze
+    ///
+    /// # Returns
+    /// A fully initialized LoadingRenderer ready to render maze generation
+    pub fn new_sized(
+        device: &wgpu::Device,
+        surface_config: &wgpu::SurfaceConfiguration,
+        gpu_memory: &GpuMemoryTracker,
+        maze_config: &MazeConfig,
+    ) -> Self {
         use crate::benchmarks::{BenchmarkConfig, Profiler};
 
         // Initialize profiler for LoadingRenderer initialization benchmarking
@@ -56,24 +201,20 @@ impl LoadingRenderer {
             write_to_file: false,
             min_duration_threshold: std::time::Duration::from_micros(1),
             max_samples: 1000,
+            autosave_interval: std::time::Duration::from_secs(60),
+            max_sections: 256,
         });
 
         // Benchmark maze generation initialization
         init_profiler.start_section("maze_generation_initialization");
-        let maze_width = 25;
-        let maze_height = 25;
-        let (generator, maze) = MazeGenerator::new(maze_width, maze_height);
+        let (generator, maze) = MazeGenerator::from_config(maze_config);
         init_profiler.end_section("maze_generation_initialization");
 
-        // Benchmark maze texture creation
-        init_profiler.start_section("maze_texture_creation");
-        let config = MazeRenderConfig::new(maze_width as u32, maze_height as u32);
-        let (texture, texture_view, sampler) = config.create_maze_texture(device);
-        init_profiler.end_section("maze_texture_creation");
-
         // Benchmark maze renderer initialization
         init_profiler.start_section("maze_renderer_initialization");
-        let maze_renderer = MazeRenderer::new(device, surface_config, &texture_view, &sampler);
+        let config = MazeRenderConfig::new(maze_config.width as u32, maze_config.height as u32);
+        let mode = MazePreview::for_device(device);
+        let maze_renderer = MazeRenderer::new(device, surface_config, gpu_memory, &config, mode);
         init_profiler.end_section("maze_renderer_initialization");
 
         // Benchmark loading bar renderer initialization
@@ -89,63 +230,204 @@ impl LoadingRenderer {
         Self {
             generator,
             maze,
+            upper_floor: None,
+            lower_floor: None,
+            stairwells: Vec::new(),
             maze_renderer,
             loading_bar_renderer,
             exit_shader_renderer,
-            texture,
             last_update: Instant::now(),
+            pinned_time: None,
+            solution_path: Vec::new(),
+            path_pulse_start: None,
+            path_pulse_skipped: false,
+            path_uploaded: false,
+        }
+    }
+
+    /// Turns the level into a two-floor level joined by stairwells.
+    ///
+    /// The lower floor is regenerated from the same seed around the stairwells, and the
+    /// upper floor is queued to start once it finishes.
+    ///
+    /// # Returns
+    /// The loading renderer, generating the lower floor
+    pub fn with_upper_floor(mut self) -> Self {
+        let size = self
+            .maze
+            .lock()
+            .map(|maze| (maze.width, maze.height))
+            .map_err(|err| err.to_string());
+        let (width, height) = match size {
+            Ok(size) => size,
+            Err(err) => {
+                eprintln!("Failed to acquire maze lock: {}", err);
+                return self;
+            }
+        };
+        let seed = self.generator.seed;
+        let (stairwells, upper_seed) = floors::plan_floors(width, height, seed);
+        (self.generator, self.maze) =
+            MazeGenerator::for_floor(width, height, seed, &stairwells, Layer::Lower);
+        self.upper_floor = Some(MazeGenerator::for_floor(
+            width,
+            height,
+            upper_seed,
+            &stairwells,
+            Layer::Upper,
+        ));
+        self.stairwells = stairwells;
+        self
+    }
+
+    /// Switches to carving the maze region by region.
+    ///
+    /// The maze is regenerated from the same seed with [`MazeGenerator::divided`], so the
+    /// preview fills in one region at a time instead of growing from one front.
+    ///
+    /// # Returns
+    /// The loading renderer, generating the divided maze
+    pub fn with_divided_regions(mut self) -> Self {
+        let size = self
+            .maze
+            .lock()
+            .map(|maze| (maze.width, maze.height))
+            .map_err(|err| err.to_string());
+        let (width, height) = match size {
+            Ok(size) => size,
+            Err(err) => {
+                eprintln!("Failed to acquire maze lock: {}", err);
+                return self;
+            }
+        };
+        (self.generator, self.maze) = MazeGenerator::divided(width, height, self.generator.seed);
+        self
+    }
+
+    /// Starts generating the upper floor if the lower floor just finished.
+    ///
+    /// # Returns
+    /// `true` if the upper floor was swapped in, `false` if there was nothing to do
+    pub fn advance_floor(&mut self) -> bool {
+        if !self.generator.is_complete() {
+            return false;
+        }
+        let Some((generator, maze)) = self.upper_floor.take() else {
+            return false;
+        };
+        self.generator = generator;
+        self.lower_floor = Some(std::mem::replace(&mut self.maze, maze));
+        true
+    }
+
+    /// Starts pulsing the solution path of the finished maze.
+    ///
+    /// Does nothing until generation is complete. The path is sent to the preview by the
+    /// next [`LoadingRenderer::update_maze`].
+    pub fn start_path_reveal(&mut self) {
+        let Some(path) = self.generator.solution_path() else {
+            return;
+        };
+        self.solution_path = path;
+        self.path_pulse_start = Some(self.animation_time(self.maze_renderer.start_time));
+        self.path_pulse_skipped = false;
+        self.path_uploaded = false;
+    }
+
+    /// Ends the path pulse early, as when the player presses a key during it.
+    pub fn skip_path_reveal(&mut self) {
+        self.path_pulse_skipped = true;
+    }
+
+    /// Returns how the solution path is highlighted this frame.
+    ///
+    /// # Arguments
+    /// * `reduce_effects` - Whether the player has asked for reduced visual effects, which
+    ///   lights the whole path at once instead of pulsing it
+    pub fn path_highlight(&self, reduce_effects: bool) -> PathHighlight {
+        let Some(start) = self.path_pulse_start else {
+            return PathHighlight::Hidden;
+        };
+        if reduce_effects {
+            return PathHighlight::Static;
+        }
+        let progress =
+            (self.animation_time(self.maze_renderer.start_time) - start) / PATH_PULSE_SECS;
+        if self.path_pulse_skipped || progress >= 1.0 {
+            PathHighlight::Hidden
+        } else {
+            PathHighlight::Pulse(progress.max(0.0))
         }
     }
 
-    /// Updates the maze texture on the GPU with new generation data.
+    /// Checks whether the path pulse is still playing, holding back the start of the level.
     ///
-    /// This is called whenever the maze generator produces new visual data
-    /// to keep the rendered maze in sync with the generation progress.
+    /// # Arguments
+    /// * `reduce_effects` - Whether the player has asked for reduced visual effects, in
+    ///   which case the path never pulses
+    pub fn is_revealing_path(&self, reduce_effects: bool) -> bool {
+        matches!(self.path_highlight(reduce_effects), PathHighlight::Pulse(_))
+    }
+
+    /// Uploads the current generation state to the maze preview.
+    ///
+    /// This is called every loading screen frame; only the parts of the maze that
+    /// changed since the last call are sent to the GPU.
     ///
     /// # Arguments
     /// * `queue` - WGPU command queue for GPU operations
-    /// * `maze_data` - Raw RGBA pixel data representing the current maze state
-    /// * `width` - Width of the maze data in pixels
-    /// * `height` - Height of the maze data in pixels
-    pub fn update_texture(
-        &self,
-        queue: &wgpu::Queue,
-        maze_data: &[u8],
-        width: usize,
-        height: usize,
-    ) {
-        queue.write_texture(
-            wgpu::TexelCopyTextureInfo {
-                texture: &self.texture,
-                mip_level: 0,
-                origin: wgpu::Origin3d::ZERO,
-                aspect: wgpu::TextureAspect::All,
-            },
-            maze_data,
-            wgpu::TexelCopyBufferLayout {
-                offset: 0,
-                bytes_per_row: Some(4 * width as u32), // 4 bytes per pixel (RGBA)
-                rows_per_image: Some(height as u32),
-            },
-            wgpu::Extent3d {
-                width: width as u32,
-                height: height as u32,
-                depth_or_array_layers: 1,
-            },
+    /// * `reduce_effects` - Whether the player has asked for reduced visual effects
+    pub fn update_maze(&mut self, queue: &wgpu::Queue, reduce_effects: bool) {
+        let time = self.animation_time(self.maze_renderer.start_time);
+        let highlight = self.path_highlight(reduce_effects);
+        let maze = match self.maze.lock() {
+            Ok(maze) => maze,
+            Err(err) => {
+                eprintln!("Failed to acquire maze lock: {}", err);
+                return;
+            }
+        };
+        if !self.path_uploaded {
+            self.maze_renderer
+                .set_path(queue, &maze, &self.solution_path);
+            self.path_uploaded = true;
+        }
+        self.maze_renderer.update(
+            queue,
+            &maze,
+            &self.generator.connected_cells,
+            self.generator.carving_front,
+            time,
+            highlight,
         );
     }
 
+    /// Returns the animation time of a loading screen shader.
+    ///
+    /// # Arguments
+    /// * `start_time` - When the shader's renderer was created
+    ///
+    /// # Returns
+    /// The pinned time if there is one, otherwise the seconds since `start_time`
+    fn animation_time(&self, start_time: Instant) -> f32 {
+        self.pinned_time
+            .unwrap_or_else(|| start_time.elapsed().as_secs_f32())
+    }
+
     /// Updates the loading bar with current progress and animation state.
     ///
     /// # Arguments
     /// * `queue` - WGPU command queue for updating uniform buffers
     /// * `progress` - Generation progress from 0.0 to 1.0
-    /// * `window` - Window reference for getting current screen dimensions
-    pub fn update_loading_bar(&self, queue: &wgpu::Queue, progress: f32, window: &Window) {
-        // Get current window dimensions for proper scaling
-        let window_size = window.inner_size();
+    /// * `window_size` - Size of the target in pixels, usually the window's inner size
+    pub fn update_loading_bar(
+        &self,
+        queue: &wgpu::Queue,
+        progress: f32,
+        window_size: PhysicalSize<u32>,
+    ) {
         let resolution = [window_size.width as f32, window_size.height as f32];
-        let time = self.loading_bar_renderer.start_time.elapsed().as_secs_f32();
+        let time = self.animation_time(self.loading_bar_renderer.start_time);
 
         self.loading_bar_renderer
             .update_uniforms(queue, progress, resolution, time);
@@ -155,11 +437,10 @@ impl LoadingRenderer {
     ///
     /// # Arguments
     /// * `queue` - WGPU command queue for updating uniform buffers
-    /// * `window` - Window reference for getting current screen dimensions
-    pub fn update_exit_shader(&self, queue: &wgpu::Queue, window: &Window) {
-        let window_size = window.inner_size();
+    /// * `window_size` - Size of the target in pixels, usually the window's inner size
+    pub fn update_exit_shader(&self, queue: &wgpu::Queue, window_size: PhysicalSize<u32>) {
         let resolution = [window_size.width as f32, window_size.height as f32];
-        let time = self.exit_shader_renderer.start_time.elapsed().as_secs_f32();
+        let time = self.animation_time(self.exit_shader_renderer.start_time);
         self.exit_shader_renderer
             .update_uniforms(queue, resolution, time);
     }
@@ -173,31 +454,39 @@ impl LoadingRenderer {
     ///
     /// # Arguments
     /// * `render_pass` - Active WGPU render pass to draw into
-    /// * `window` - Window reference for screen dimensions and positioning
-    pub fn render(&self, render_pass: &mut wgpu::RenderPass, window: &Window) {
+    /// * `window_size` - Size of the target in pixels, usually the window's inner size
+    pub fn render(&self, render_pass: &mut wgpu::RenderPass, window_size: PhysicalSize<u32>) {
         // Render maze background - shows the current generation state
         self.maze_renderer.render(render_pass);
 
         // Render loading bar overlay with animated effect at the top of screen
-        let window_size = window.inner_size();
         let bar_width = window_size.width;
-        let bar_height = (window_size.height as f32 * 0.0125).ceil() as u32; // 1.25% of screen height
+        let bar_height = top_bar_height(window_size.height as f32); // 1.25% of screen height
         let bar_x = 0u32;
         let bar_y = 0u32; // Top of screen
-        self.loading_bar_renderer.render_with_scissor(
-            render_pass,
+        if let Some((bar_x, bar_y, bar_width, bar_height)) = clamp_scissor(
             bar_x,
             bar_y,
             bar_width,
             bar_height,
-        );
+            window_size.width,
+            window_size.height,
+        ) {
+            self.loading_bar_renderer.render_with_scissor(
+                render_pass,
+                bar_x,
+                bar_y,
+                bar_width,
+                bar_height,
+            );
+        }
 
         // Render exit cell effect if maze generation is complete and has an exit
         if let Ok(maze_guard) = self.maze.lock() {
             if let Some(exit_cell) = maze_guard.exit_cell {
                 self.exit_shader_renderer.render_to_cell(
                     render_pass,
-                    window,
+                    window_size,
                     (exit_cell.col, exit_cell.row),
                     maze_guard.width,
                     maze_guard.height,
@@ -208,30 +497,18 @@ impl LoadingRenderer {
 
     /// Gets the current maze generation progress as a ratio.
     ///
+    /// Two-floor levels spend the first half of the bar on the lower floor.
+    ///
     /// # Returns
     /// Progress value from 0.0 (just started) to 1.0 (complete)
     pub fn get_generation_progress(&self) -> f32 {
-        self.generator.get_progress_ratio()
-    }
-
-    /// Checks if maze generation has completed.
-    ///
-    /// # Returns
-    /// `true` if generation is finished, `false` if still in progress
-    pub fn is_generation_complete(&self) -> bool {
-        self.generator.is_complete()
-    }
-
-    /// Gets the dimensions of the maze being generated.
-    ///
-    /// # Returns
-    /// Tuple of (width, height) in maze cells, or (126, 126) as fallback
-    pub fn get_maze_dimensions(&self) -> (u32, u32) {
-        if let Ok(maze_guard) = self.maze.lock() {
-            let (width, height) = maze_guard.get_dimensions();
-            (width as u32, height as u32)
+        let progress = self.generator.get_progress_ratio();
+        if self.upper_floor.is_some() {
+            progress * 0.5
+        } else if self.lower_floor.is_some() {
+            0.5 + progress * 0.5
         } else {
-            (126, 126) // Default fallback if lock fails
+            progress
         }
     }
 }
@@ -246,10 +523,10 @@ pub struct LoadingBarUniforms {
     pub progress: f32,
     /// Animation time in seconds since creation
     pub time: f32,
-    /// Screen resolution [width, height] for proper scaling
+    /// Size of the region the bar spans [width, height]
     pub resolution: [f32; 2],
-    /// Padding to ensure proper GPU alignment
-    pub _padding: [f32; 2],
+    /// Top-left corner of that region in pixels
+    pub origin: [f32; 2],
 }
 
 /// Uniform buffer data for the exit cell shader effect.
@@ -266,66 +543,256 @@ pub struct ExitShaderUniforms {
     pub _padding: [f32; 3],
 }
 
-/// Renderer responsible for displaying the maze texture as a background.
+/// Uniform buffer data for the GPU maze preview shader.
+///
+/// Colors are converted for the render target before upload.
+#[repr(C)]
+#[derive(Debug, Copy, Clone, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct MazePreviewUniforms {
+    /// Color of walls and cells that have not been carved yet
+    pub wall_color: [f32; 4],
+    /// Color of carved cells and passages
+    pub open_color: [f32; 4],
+    /// Color of the exit cell
+    pub exit_color: [f32; 4],
+    /// Color of the carving front glow and newly carved cells
+    pub glow_color: [f32; 4],
+    /// Color the solution path is highlighted in
+    pub path_color: [f32; 4],
+    /// Maze size in cells [width, height]
+    pub maze_size: [f32; 2],
+    /// Cell currently being carved [col, row], or negative when there is none
+    pub front_cell: [f32; 2],
+    /// Animation time in seconds since creation
+    pub time: f32,
+    /// Seconds a newly carved cell keeps pulsing
+    pub pulse_duration: f32,
+    /// How far the path pulse has swept from 0.0 to 1.0, or negative when the path is hidden
+    pub path_progress: f32,
+    /// 1.0 to light the whole path at once instead of pulsing it
+    pub path_static: f32,
+}
+
+/// GPU resources backing the maze preview, depending on the [`MazePreview`] mode.
+pub enum MazePreviewResources {
+    /// Wall grid texture, reveal times, path, and uniforms read by `fs_main`
+    Gpu {
+        /// One [`PreviewTile`] byte per wall grid entry
+        grid_texture: TrackedTexture,
+        /// Time each wall grid entry is fully revealed in seconds, `-1.0` until it is carved
+        carve_time_buffer: wgpu::Buffer,
+        /// How far along the solution path each wall grid entry is, from 0.0 at the entrance
+        /// to 1.0 at the exit, or `-1.0` off the path
+        path_order_buffer: wgpu::Buffer,
+        /// Colors, carving front, and animation time
+        uniform_buffer: wgpu::Buffer,
+    },
+    /// Full RGBA image sampled by `fs_texture`
+    CpuFallback {
+        /// GPU texture containing the rasterized maze
+        texture: TrackedTexture,
+        /// Pixel dimensions of the rasterized maze
+        config: MazeRenderConfig,
+    },
+}
+
+/// Renderer responsible for displaying the maze being generated as a background.
 ///
-/// This renderer takes the maze texture data and displays it full-screen,
-/// providing the visual backdrop for the loading screen.
+/// By default the maze's wall grid is uploaded as a small R8 texture and the
+/// fragment shader works out each pixel's color, highlights the cell currently
+/// being carved, and fades entries in one after another in the order they were
+/// carved. The CPU fallback uploads a fully rasterized RGBA image instead, and
+/// draws the solution path without animating it.
 pub struct MazeRenderer {
     /// GPU render pipeline for maze rendering
     pub pipeline: wgpu::RenderPipeline,
     /// Vertex buffer containing fullscreen quad vertices
     pub vertex_buffer: wgpu::Buffer,
-    /// Bind group containing texture and sampler resources
+    /// Bind group containing the preview resources
     pub bind_group: wgpu::BindGroup,
+    /// Textures and buffers the bind group points at
+    pub resources: MazePreviewResources,
+    /// Tiles as last uploaded, used to send only what changed
+    pub uploaded_tiles: Vec<u8>,
+    /// Animation time of the last upload, which sets how far a batch of carved entries is
+    /// spread out
+    last_upload_time: Option<f32>,
+    /// Solution path drawn by the CPU fallback, in wall grid coordinates
+    path: Vec<Cell>,
+    /// Start time for calculating animation progress
+    pub start_time: Instant,
+    /// Surface format the preview colors are converted for
+    format: wgpu::TextureFormat,
 }
 
 impl MazeRenderer {
-    /// Creates a new maze renderer with the specified texture resources.
+    /// Seconds a newly carved cell pulses before settling on the open color
+    const PULSE_DURATION: f32 = 0.6;
+
+    /// Longest a batch of carved entries is spread over, so a slow frame doesn't hold the
+    /// preview back
+    const MAX_REVEAL_SPREAD: f32 = 0.1;
+
+    /// Creates a new maze renderer for a maze of the given size.
     ///
     /// # Arguments
     /// * `device` - WGPU device for creating GPU resources
     /// * `surface_config` - Surface configuration for render target format
-    /// * `texture_view` - View of the maze texture to render
-    /// * `sampler` - Texture sampler for filtering
+    /// * `gpu_memory` - Tracker the preview texture is counted in as UI
+    /// * `config` - Dimensions of the maze being previewed
+    /// * `mode` - Whether to color the maze on the GPU or use the CPU fallback
     ///
     /// # Returns
-    /// A fully initialized MazeRenderer ready to display maze textures
+    /// A fully initialized MazeRenderer showing an uncarved maze
     pub fn new(
         device: &wgpu::Device,
         surface_config: &wgpu::SurfaceConfiguration,
-        texture_view: &wgpu::TextureView,
-        sampler: &wgpu::Sampler,
+        gpu_memory: &GpuMemoryTracker,
+        config: &MazeRenderConfig,
+        mode: MazePreview,
     ) -> Self {
-        // Create bind group layout for texture and sampler
-        let bind_group_layout = BindGroupLayoutBuilder::new(device)
-            .with_label("Maze Texture Bind Group Layout")
-            .with_texture(0, wgpu::ShaderStages::FRAGMENT)
-            .with_sampler(1, wgpu::ShaderStages::FRAGMENT)
-            .build();
-
-        // Create bind group with actual texture and sampler
-        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
-            layout: &bind_group_layout,
-            entries: &[
-                wgpu::BindGroupEntry {
-                    binding: 0,
-                    resource: wgpu::BindingResource::TextureView(texture_view),
-                },
-                wgpu::BindGroupEntry {
-                    binding: 1,
-                    resource: wgpu::BindingResource::Sampler(sampler),
-                },
-            ],
-            label: Some("Maze Texture Bind Group"),
-        });
-
-        // Create render pipeline with maze shader
-        let pipeline = PipelineBuilder::new(device, surface_config.format)
+        let pipeline_builder = PipelineBuilder::new(device, surface_config.format)
             .with_label("Maze Render Pipeline")
             .with_shader(include_str!("./shaders/2D-maze-shader.wgsl"))
-            .with_vertex_buffer(create_vertex_2d_layout())
-            .with_bind_group_layout(&bind_group_layout)
-            .build();
+            .with_vertex_buffer(create_vertex_2d_layout());
+
+        let (pipeline, bind_group, resources) = match mode {
+            MazePreview::Gpu => {
+                let grid_texture = create_tracked_texture(
+                    device,
+                    gpu_memory,
+                    TextureCategory::Ui,
+                    &wgpu::TextureDescriptor {
+                        label: Some("Maze Grid Texture"),
+                        size: wgpu::Extent3d {
+                            width: config.maze_width * 2 + 1,
+                            height: config.maze_height * 2 + 1,
+                            depth_or_array_layers: 1,
+                        },
+                        mip_level_count: 1,
+                        sample_count: 1,
+                        dimension: wgpu::TextureDimension::D2,
+                        format: wgpu::TextureFormat::R8Unorm,
+                        usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
+                        view_formats: &[],
+                    },
+                );
+                let grid_view = grid_texture.create_view(&wgpu::TextureViewDescriptor::default());
+
+                let entry_count =
+                    ((config.maze_width * 2 + 1) * (config.maze_height * 2 + 1)) as usize;
+                let unset = vec![-1.0f32; entry_count];
+                let carve_time_buffer =
+                    device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                        label: Some("Maze Carve Time Buffer"),
+                        contents: bytemuck::cast_slice(&unset),
+                        usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_DST,
+                    });
+                let path_order_buffer =
+                    device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                        label: Some("Maze Path Order Buffer"),
+                        contents: bytemuck::cast_slice(&unset),
+                        usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_DST,
+                    });
+
+                let uniforms = Self::uniforms(
+                    surface_config.format,
+                    config,
+                    None,
+                    0.0,
+                    PathHighlight::Hidden,
+                );
+                let uniform_buffer =
+                    create_uniform_buffer(device, &uniforms, "Maze Preview Uniform Buffer");
+
+                let bind_group_layout = BindGroupLayoutBuilder::new(device)
+                    .with_label("Maze Preview Bind Group Layout")
+                    .with_texture(0, wgpu::ShaderStages::FRAGMENT)
+                    .with_read_only_storage_buffer(1, wgpu::ShaderStages::FRAGMENT)
+                    .with_uniform_buffer(2, wgpu::ShaderStages::FRAGMENT)
+                    .with_read_only_storage_buffer(5, wgpu::ShaderStages::FRAGMENT)
+                    .build();
+
+                let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+                    layout: &bind_group_layout,
+                    entries: &[
+                        wgpu::BindGroupEntry {
+                            binding: 0,
+                            resource: wgpu::BindingResource::TextureView(&grid_view),
+                        },
+                        wgpu::BindGroupEntry {
+                            binding: 1,
+                            resource: carve_time_buffer.as_entire_binding(),
+                        },
+                        wgpu::BindGroupEntry {
+                            binding: 2,
+                            resource: uniform_buffer.as_entire_binding(),
+                        },
+                        wgpu::BindGroupEntry {
+                            binding: 5,
+                            resource: path_order_buffer.as_entire_binding(),
+                        },
+                    ],
+                    label: Some("Maze Preview Bind Group"),
+                });
+
+                let pipeline = pipeline_builder
+                    .with_bind_group_layout(&bind_group_layout)
+                    .build();
+
+                (
+                    pipeline,
+                    bind_group,
+                    MazePreviewResources::Gpu {
+                        grid_texture,
+                        carve_time_buffer,
+                        path_order_buffer,
+                        uniform_buffer,
+                    },
+                )
+            }
+            MazePreview::CpuFallback => {
+                let (texture, texture_view, sampler) =
+                    config.create_maze_texture(device, gpu_memory);
+
+                // Create bind group layout for texture and sampler
+                let bind_group_layout = BindGroupLayoutBuilder::new(device)
+                    .with_label("Maze Texture Bind Group Layout")
+                    .with_texture(3, wgpu::ShaderStages::FRAGMENT)
+                    .with_sampler(4, wgpu::ShaderStages::FRAGMENT)
+                    .build();
+
+                // Create bind group with actual texture and sampler
+                let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+                    layout: &bind_group_layout,
+                    entries: &[
+                        wgpu::BindGroupEntry {
+                            binding: 3,
+                            resource: wgpu::BindingResource::TextureView(&texture_view),
+                        },
+                        wgpu::BindGroupEntry {
+                            binding: 4,
+                            resource: wgpu::BindingResource::Sampler(&sampler),
+                        },
+                    ],
+                    label: Some("Maze Texture Bind Group"),
+                });
+
+                let pipeline = pipeline_builder
+                    .with_fragment_entry("fs_texture")
+                    .with_bind_group_layout(&bind_group_layout)
+                    .build();
+
+                (
+                    pipeline,
+                    bind_group,
+                    MazePreviewResources::CpuFallback {
+                        texture,
+                        config: MazeRenderConfig::new(config.maze_width, config.maze_height),
+                    },
+                )
+            }
+        };
 
         // Create fullscreen quad vertices
         let vertex_buffer = create_fullscreen_vertices(device);
@@ -334,10 +801,206 @@ impl MazeRenderer {
             pipeline,
             vertex_buffer,
             bind_group,
+            resources,
+            uploaded_tiles: Vec::new(),
+            last_upload_time: None,
+            path: Vec::new(),
+            start_time: Instant::now(),
+            format: surface_config.format,
+        }
+    }
+
+    /// Builds the preview uniforms with colors converted for the render target.
+    ///
+    /// # Arguments
+    /// * `format` - Surface format the preview is drawn to
+    /// * `config` - Dimensions of the maze being previewed
+    /// * `front` - Cell currently being carved, if any
+    /// * `time` - Animation time in seconds
+    /// * `highlight` - How the solution path is highlighted
+    fn uniforms(
+        format: wgpu::TextureFormat,
+        config: &MazeRenderConfig,
+        front: Option<Cell>,
+        time: f32,
+        highlight: PathHighlight,
+    ) -> MazePreviewUniforms {
+        let (path_progress, path_static) = match highlight {
+            PathHighlight::Hidden => (-1.0, 0.0),
+            PathHighlight::Pulse(progress) => (progress.clamp(0.0, 1.0), 0.0),
+            PathHighlight::Static => (1.0, 1.0),
+        };
+        MazePreviewUniforms {
+            wall_color: color::srgb_for_target([0.0, 0.0, 0.0, 1.0], format),
+            open_color: color::srgb_for_target([1.0, 1.0, 1.0, 1.0], format),
+            exit_color: color::srgb_for_target([1.0, 0.0, 0.0, 1.0], format),
+            glow_color: color::srgb_for_target([0.35, 0.75, 1.0, 1.0], format),
+            path_color: color::srgb_for_target([1.0, 0.78, 0.24, 1.0], format),
+            maze_size: [config.maze_width as f32, config.maze_height as f32],
+            front_cell: front.map_or([-1.0, -1.0], |cell| [cell.col as f32, cell.row as f32]),
+            time,
+            pulse_duration: Self::PULSE_DURATION,
+            path_progress,
+            path_static,
+        }
+    }
+
+    /// Sends the solution path to the preview.
+    ///
+    /// # Arguments
+    /// * `queue` - WGPU command queue for GPU operations
+    /// * `maze` - The maze the path runs through
+    /// * `path` - Wall grid entries from the entrance to the exit, or empty for no path
+    pub fn set_path(&mut self, queue: &wgpu::Queue, maze: &Maze, path: &[Cell]) {
+        if let MazePreviewResources::Gpu {
+            path_order_buffer, ..
+        } = &self.resources
+        {
+            let grid_width = maze.width * 2 + 1;
+            let mut orders = vec![-1.0f32; grid_width * (maze.height * 2 + 1)];
+            let last = path.len().saturating_sub(1).max(1) as f32;
+            for (i, cell) in path.iter().enumerate() {
+                orders[cell.row * grid_width + cell.col] = i as f32 / last;
+            }
+            queue.write_buffer(path_order_buffer, 0, bytemuck::cast_slice(&orders));
+        }
+        self.path = path.to_vec();
+    }
+
+    /// Uploads the maze's current state to the GPU.
+    ///
+    /// In GPU mode only the rows of the wall grid that changed are uploaded, along
+    /// with the reveal time of each newly carved entry. Entries carved since the last
+    /// upload are revealed one after another in carve order over the time the last
+    /// frame took. The CPU fallback rasterizes and uploads the whole maze every call.
+    ///
+    /// # Arguments
+    /// * `queue` - WGPU command queue for GPU operations
+    /// * `maze` - The maze being generated
+    /// * `connected` - Cells that have been joined to a neighbor so far
+    /// * `front` - Cell currently being carved, if any
+    /// * `time` - Animation time in seconds, the earliest newly carved entry's reveal time
+    /// * `highlight` - How the solution path is highlighted
+    pub fn update(
+        &mut self,
+        queue: &wgpu::Queue,
+        maze: &Maze,
+        connected: &HashSet<Cell>,
+        front: Option<Cell>,
+        time: f32,
+        highlight: PathHighlight,
+    ) {
+        let spread = self.last_upload_time.map_or(0.0, |last| {
+            (time - last).clamp(0.0, Self::MAX_REVEAL_SPREAD)
+        });
+        self.last_upload_time = Some(time);
+        match &self.resources {
+            MazePreviewResources::Gpu {
+                grid_texture,
+                carve_time_buffer,
+                uniform_buffer,
+                ..
+            } => {
+                let grid_width = maze.width * 2 + 1;
+                let tiles = maze.preview_tiles(connected);
+
+                // Force a full upload the first time through
+                if self.uploaded_tiles.len() != tiles.len() {
+                    self.uploaded_tiles = vec![u8::MAX; tiles.len()];
+                }
+
+                let mut changed_rows: Option<(usize, usize)> = None;
+                let mut carved = Vec::new();
+                for (index, (&new, &old)) in tiles.iter().zip(&self.uploaded_tiles).enumerate() {
+                    if new == old {
+                        continue;
+                    }
+                    let row = index / grid_width;
+                    changed_rows = Some(changed_rows.map_or((row, row), |(first, _)| (first, row)));
+
+                    // Entries that just opened up are revealed so the shader can fade them in
+                    let opened = new == PreviewTile::Passage as u8
+                        || new == PreviewTile::ConnectedCell as u8;
+                    let was_closed = old == PreviewTile::Wall as u8
+                        || old == PreviewTile::UnconnectedCell as u8
+                        || old == u8::MAX;
+                    if opened && was_closed {
+                        let step = maze.carve_steps[row][index % grid_width].unwrap_or(0);
+                        carved.push((index, step));
+                    }
+                }
+
+                let steps: Vec<u32> = carved.iter().map(|&(_, step)| step).collect();
+                for (&(index, _), reveal) in carved.iter().zip(reveal_times(&steps, time, spread)) {
+                    queue.write_buffer(
+                        carve_time_buffer,
+                        (index * std::mem::size_of::<f32>()) as u64,
+                        bytemuck::cast_slice(&[reveal]),
+                    );
+                }
+
+                if let Some((first, last)) = changed_rows {
+                    let rows = last - first + 1;
+                    queue.write_texture(
+                        wgpu::TexelCopyTextureInfo {
+                            texture: grid_texture,
+                            mip_level: 0,
+                            origin: wgpu::Origin3d {
+                                x: 0,
+                                y: first as u32,
+                                z: 0,
+                            },
+                            aspect: wgpu::TextureAspect::All,
+                        },
+                        &tiles[first * grid_width..(last + 1) * grid_width],
+                        wgpu::TexelCopyBufferLayout {
+                            offset: 0,
+                            bytes_per_row: Some(grid_width as u32), // 1 byte per tile (R8)
+                            rows_per_image: Some(rows as u32),
+                        },
+                        wgpu::Extent3d {
+                            width: grid_width as u32,
+                            height: rows as u32,
+                            depth_or_array_layers: 1,
+                        },
+                    );
+                    self.uploaded_tiles = tiles;
+                }
+
+                let config = MazeRenderConfig::new(maze.width as u32, maze.height as u32);
+                let uniforms = Self::uniforms(self.format, &config, front, time, highlight);
+                queue.write_buffer(uniform_buffer, 0, bytemuck::cast_slice(&[uniforms]));
+            }
+            MazePreviewResources::CpuFallback { texture, config } => {
+                let path = match highlight {
+                    PathHighlight::Hidden => &[][..],
+                    PathHighlight::Pulse(_) | PathHighlight::Static => &self.path[..],
+                };
+                let maze_data = maze.get_render_data_with_path(connected, path);
+                queue.write_texture(
+                    wgpu::TexelCopyTextureInfo {
+                        texture,
+                        mip_level: 0,
+                        origin: wgpu::Origin3d::ZERO,
+                        aspect: wgpu::TextureAspect::All,
+                    },
+                    &maze_data,
+                    wgpu::TexelCopyBufferLayout {
+                        offset: 0,
+                        bytes_per_row: Some(4 * config.render_width), // 4 bytes per pixel (RGBA)
+                        rows_per_image: Some(config.render_height),
+                    },
+                    wgpu::Extent3d {
+                        width: config.render_width,
+                        height: config.render_height,
+                        depth_or_array_layers: 1,
+                    },
+                );
+            }
         }
     }
 
-    /// Renders the maze texture to the current render pass.
+    /// Renders the maze preview to the current render pass.
     ///
     /// # Arguments
     /// * `render_pass` - Active render pass to draw into
@@ -380,7 +1043,7 @@ impl LoadingBarRenderer {
             progress: 0.0,
             time: 0.0,
             resolution: [800.0, 600.0], // Default resolution, updated per frame
-            _padding: [0.0; 2],
+            origin: [0.0; 2],
         };
 
         let uniform_buffer = create_uniform_buffer(device, &uniforms, "Loading Bar Uniform Buffer");
@@ -445,32 +1108,11 @@ impl LoadingBarRenderer {
             progress: visual_progress,
             time,
             resolution,
-            _padding: [0.0; 2],
+            origin: [0.0; 2],
         };
         queue.write_buffer(&self.uniform_buffer, 0, bytemuck::cast_slice(&[uniforms]));
     }
 
-    /// Renders the loading bar with default positioning (top of screen).
-    ///
-    /// # Arguments
-    /// * `render_pass` - Active render pass to draw into
-    /// * `window` - Window reference for getting screen dimensions
-    pub fn render(&self, render_pass: &mut wgpu::RenderPass, window: &Window) {
-        // Calculate default loading bar position (thin bar across top of screen)
-        let window_size = window.inner_size();
-        let bar_width = window_size.width; // Full width of screen
-        let bar_height = 8u32; // Thin bar height
-
-        let bar_x = 0u32;
-        let bar_y = 0u32; // Top of screen
-
-        // Use scissor test to constrain the animated effect to the loading bar area
-        render_pass.set_scissor_rect(bar_x, bar_y, bar_width, bar_height);
-        render_pass.set_pipeline(&self.pipeline);
-        render_pass.set_bind_group(0, &self.bind_group, &[]);
-        render_pass.draw(0..3, 0..1); // Full-screen triangle, clipped to loading bar
-    }
-
     /// Renders the loading bar with custom positioning using scissor test.
     ///
     /// # Arguments
@@ -581,14 +1223,14 @@ impl ExitShaderRenderer {
     ///
     /// # Arguments
     /// * `render_pass` - Active render pass to draw into
-    /// * `window` - Window reference for screen coordinate conversion
+    /// * `window_size` - Size of the target in pixels, which the maze is stretched to fill
     /// * `exit_cell` - (column, row) coordinates of the exit cell in the maze
     /// * `maze_width` - Width of the maze in cells
     /// * `maze_height` - Height of the maze in cells
     pub fn render_to_cell(
         &self,
         render_pass: &mut wgpu::RenderPass,
-        window: &winit::window::Window,
+        window_size: PhysicalSize<u32>,
         exit_cell: (usize, usize),
         maze_width: usize,
         maze_height: usize,
@@ -599,7 +1241,6 @@ impl ExitShaderRenderer {
         let render_width = maze_width as f32 * cell_px + (maze_width as f32 + 1.0) * wall_px;
         let render_height = maze_height as f32 * cell_px + (maze_height as f32 + 1.0) * wall_px;
 
-        let window_size = window.inner_size();
         let win_w = window_size.width as f32;
         let win_h = window_size.height as f32;
 
@@ -618,6 +1259,16 @@ impl ExitShaderRenderer {
         let scissor_height = ((h / render_height) * win_h).round().max(1.0) as u32;
 
         // Render effect only within the calculated scissor rectangle
+        let Some((scissor_x, scissor_y, scissor_width, scissor_height)) = clamp_scissor(
+            scissor_x,
+            scissor_y,
+            scissor_width,
+            scissor_height,
+            window_size.width,
+            window_size.height,
+        ) else {
+            return;
+        };
         render_pass.set_scissor_rect(scissor_x, scissor_y, scissor_width, scissor_height);
         render_pass.set_pipeline(&self.pipeline);
         render_pass.set_bind_group(0, &self.bind_group, &[]);
@@ -629,6 +1280,7 @@ impl ExitShaderRenderer {
 ///
 /// Handles the math for converting maze logical dimensions (in cells)
 /// to render dimensions (in pixels) with proper scaling and borders.
+/// Only the CPU fallback allocates a texture at the full render size.
 pub struct MazeRenderConfig {
     /// Width of the maze in logical cells
     pub maze_width: u32,
@@ -669,13 +1321,15 @@ impl MazeRenderConfig {
     ///
     /// # Arguments
     /// * `device` - WGPU device for creating GPU resources
+    /// * `gpu_memory` - Tracker the texture is counted in as UI
     ///
     /// # Returns
     /// Tuple of (texture, texture_view, sampler) ready for use in rendering
     pub fn create_maze_texture(
         &self,
         device: &wgpu::Device,
-    ) -> (wgpu::Texture, wgpu::TextureView, wgpu::Sampler) {
+        gpu_memory: &GpuMemoryTracker,
+    ) -> (TrackedTexture, wgpu::TextureView, wgpu::Sampler) {
         let texture_size = wgpu::Extent3d {
             width: self.render_width,
             height: self.render_height,
@@ -683,16 +1337,21 @@ impl MazeRenderConfig {
         };
 
         // Create texture with RGBA format for color maze data
-        let texture = device.create_texture(&wgpu::TextureDescriptor {
-            label: Some("Maze Texture"),
-            size: texture_size,
-            mip_level_count: 1,
-            sample_count: 1,
-            dimension: wgpu::TextureDimension::D2,
-            format: wgpu::TextureFormat::Rgba8UnormSrgb,
-            usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
-            view_formats: &[],
-        });
+        let texture = create_tracked_texture(
+            device,
+            gpu_memory,
+            TextureCategory::Ui,
+            &wgpu::TextureDescriptor {
+                label: Some("Maze Texture"),
+                size: texture_size,
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: wgpu::TextureDimension::D2,
+                format: wgpu::TextureFormat::Rgba8UnormSrgb,
+                usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
+                view_formats: &[],
+            },
+        );
 
         let texture_view = texture.create_view(&wgpu::TextureViewDescriptor::default());
 
@@ -710,3 +1369,50 @@ impl MazeRenderConfig {
         (texture, texture_view, sampler)
     }
 }
+
+/// Works out when each entry carved since the last upload is fully revealed.
+///
+/// The batch is spread over the next `spread` seconds in carve order, so entries carved
+/// together still appear one after another instead of all at once.
+///
+/// # Arguments
+/// * `steps` - Carve step of each newly carved entry, as in [`Maze::carve_steps`]
+/// * `time` - Animation time of this upload, when the earliest step is revealed
+/// * `spread` - Seconds to spread the batch over, usually how long the last frame took
+///
+/// # Returns
+/// One reveal time per entry, in the same order as `steps`
+pub fn reveal_times(steps: &[u32], time: f32, spread: f32) -> Vec<f32> {
+    let (Some(&first), Some(&last)) = (steps.iter().min(), steps.iter().max()) else {
+        return Vec::new();
+    };
+    let span = (last - first + 1) as f32;
+    steps
+        .iter()
+        .map(|&step| time + spread * (step - first) as f32 / span)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::renderer::uniform_layout::assert_matches_shader;
+
+    #[test]
+    fn test_uniforms_match_the_shader_layout() {
+        assert_matches_shader::<MazePreviewUniforms>(include_str!("./shaders/2D-maze-shader.wgsl"));
+    }
+
+    #[test]
+    fn test_batches_reveal_in_carve_order() {
+        let times = reveal_times(&[7, 5, 5, 6, 8], 2.0, 0.1);
+        assert_eq!(times[1], 2.0);
+        assert_eq!(times[1], times[2]);
+        assert!(times[2] < times[3] && times[3] < times[0] && times[0] < times[4]);
+        assert!(times[4] < 2.1);
+
+        // Without a previous frame to go by, the whole batch shows at once
+        assert!(reveal_times(&[3, 1, 2], 4.0, 0.0).iter().all(|&t| t == 4.0));
+        assert!(reveal_times(&[], 4.0, 0.1).is_empty());
+    }
+}
@@ -0,0 +1,250 @@
+//! Maze View Module
+//!
+//! This module draws the 3D maze into a wgpu target owned by someone else, for launchers and
+//! tools that embed Mirador instead of running the game's own window. [`MazeView`] wraps the
+//! [`GameRenderer`] and the depth buffer it needs, and takes the device, queue, and texture
+//! format the caller already created, so it works with any surface or offscreen texture.
+//!
+//! The view draws what the game draws in its 3D view: the maze, the enemy, trails, and
+//! particles, seen from the player's camera. The starfield, ambient particles, and the HUD
+//! belong to the game's own window and are left out.
+//!
+//! # Usage
+//!
+//! ```rust,no_run
+//! use mirador::{GameState, MazeGenerator, MazeView};
+//!
+//! # fn draw(device: &wgpu::Device, queue: &wgpu::Queue, target: &wgpu::TextureView) {
+//! // Carve a maze; the same seed always gives the same layout
+//! let (mut generator, maze) = MazeGenerator::with_seed(12, 12, 7);
+//! while !generator.is_complete() {
+//!     generator.step();
+//! }
+//! let (grid, exit_cell) = maze.lock().unwrap().level_grid();
+//!
+//! // Put the player at the entrance, without opening an audio device
+//! let mut game_state = GameState::builder().without_audio().build().unwrap();
+//! game_state.collision_system.build_from_maze(&grid, false);
+//! game_state.player.spawn_at_maze_entrance(&grid, false);
+//!
+//! // Draw it into the caller's texture
+//! let format = wgpu::TextureFormat::Bgra8UnormSrgb;
+//! let mut view = MazeView::new(device, queue, format, 1280, 720);
+//! view.load_maze(device, &grid, exit_cell);
+//! let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+//! view.render(device, queue, &mut encoder, target, &game_state, 0.0);
+//! queue.submit(Some(encoder.finish()));
+//! # }
+//! ```
+
+use crate::game::GameState;
+use crate::game::maze::generator::Cell;
+use crate::math::mat::Mat4;
+use crate::renderer::color;
+use crate::renderer::game_renderer::{GameRenderer, SceneCamera, camera_projection};
+use crate::renderer::gpu_memory::GpuMemoryTracker;
+use crate::renderer::primitives::Vertex;
+use crate::renderer::tuning::RenderTuning;
+use wgpu;
+use wgpu::util::DeviceExt;
+
+/// Draws a maze from the player's camera into an externally created wgpu target.
+pub struct MazeView {
+    /// The renderer for the maze and everything in it
+    renderer: GameRenderer,
+    /// Tracker the view's textures are counted in
+    gpu_memory: GpuMemoryTracker,
+    /// Format of the textures the view draws into
+    format: wgpu::TextureFormat,
+    /// Width of the target in pixels
+    width: u32,
+    /// Height of the target in pixels
+    height: u32,
+    /// Background color and fog; change it to restyle the view
+    pub tuning: RenderTuning,
+    /// Keeps breathing walls still, like the game's reduce-effects setting
+    pub reduce_effects: bool,
+    /// View matrix to draw from instead of the player's camera; the player's field of view
+    /// is kept
+    pub camera: Option<Mat4>,
+}
+
+impl MazeView {
+    /// Creates a maze view with no maze loaded.
+    ///
+    /// # Arguments
+    /// * `device` - WebGPU device for creating GPU resources
+    /// * `queue` - WebGPU queue for uploading textures
+    /// * `format` - Format of the textures the view will draw into
+    /// * `width` - Width of the target in pixels
+    /// * `height` - Height of the target in pixels
+    ///
+    /// # Returns
+    /// A `MazeView` that clears the target to the background color until
+    /// [`MazeView::load_maze`] is called
+    pub fn new(
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        format: wgpu::TextureFormat,
+        width: u32,
+        height: u32,
+    ) -> Self {
+        let gpu_memory = GpuMemoryTracker::default();
+        let surface_config = wgpu::SurfaceConfiguration {
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+            format,
+            width: width.max(1),
+            height: height.max(1),
+            present_mode: wgpu::PresentMode::Fifo,
+            desired_maximum_frame_latency: 2,
+            alpha_mode: wgpu::CompositeAlphaMode::Auto,
+            view_formats: vec![],
+        };
+        let mut renderer = GameRenderer::new(device, queue, &surface_config);
+        if let Err(e) = renderer.load_ceiling_texture(device, queue, &gpu_memory) {
+            eprintln!("Failed to load ceiling texture: {}", e);
+        }
+
+        Self {
+            renderer,
+            gpu_memory,
+            format,
+            width: surface_config.width,
+            height: surface_config.height,
+            tuning: RenderTuning::default(),
+            reduce_effects: false,
+            camera: None,
+        }
+    }
+
+    /// Builds the geometry of a single-floor maze, replacing any maze loaded before.
+    ///
+    /// # Arguments
+    /// * `device` - WebGPU device for creating the vertex buffer
+    /// * `maze_grid` - The wall grid, as returned by
+    ///   [`Maze::level_grid`](crate::game::maze::generator::Maze::level_grid) or
+    ///   [`parse_maze_str`](crate::game::maze::parse_maze_str)
+    /// * `exit_cell` - The exit in wall grid coordinates, painted on the floor if given
+    pub fn load_maze(
+        &mut self,
+        device: &wgpu::Device,
+        maze_grid: &[Vec<bool>],
+        exit_cell: Option<Cell>,
+    ) {
+        let (mut vertices, exit_position) =
+            Vertex::create_floor_vertices(maze_grid, exit_cell, false);
+        vertices.append(&mut Vertex::create_wall_vertices(maze_grid, false));
+        vertices.append(&mut Vertex::create_ceiling_vertices(maze_grid, false));
+
+        self.renderer.vertex_buffer =
+            device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                label: Some("Maze View Vertex Buffer"),
+                contents: bytemuck::cast_slice(&vertices),
+                usage: wgpu::BufferUsages::VERTEX,
+            });
+        self.renderer.vertex_count = vertices.len() as u32;
+        self.renderer.upper_floor_start = None;
+        self.renderer.exit_position = exit_cell.map(|_| exit_position);
+    }
+
+    /// Draws the maze another renderer has loaded, replacing any maze loaded before.
+    ///
+    /// The vertex buffer is shared rather than copied, so this is cheap enough to call
+    /// whenever the other renderer may have loaded a new maze.
+    ///
+    /// # Arguments
+    /// * `renderer` - The renderer whose maze geometry to draw
+    pub fn share_maze(&mut self, renderer: &GameRenderer) {
+        self.renderer.vertex_buffer = renderer.vertex_buffer.clone();
+        self.renderer.vertex_count = renderer.vertex_count;
+        self.renderer.upper_floor_start = renderer.upper_floor_start;
+        self.renderer.exit_position = renderer.exit_position;
+    }
+
+    /// Changes the size of the target; the depth buffer follows on the next render.
+    ///
+    /// # Arguments
+    /// * `width` - New width of the target in pixels
+    /// * `height` - New height of the target in pixels
+    pub fn resize(&mut self, width: u32, height: u32) {
+        self.width = width.max(1);
+        self.height = height.max(1);
+    }
+
+    /// Records the commands that draw the maze from the player's camera, or from
+    /// [`MazeView::camera`] when it is set.
+    ///
+    /// The target is cleared to the background color first, so it doesn't need clearing
+    /// beforehand.
+    ///
+    /// # Arguments
+    /// * `device` - WebGPU device, for recreating the depth buffer after a resize
+    /// * `queue` - WebGPU queue for uploading this frame's uniforms
+    /// * `encoder` - Command encoder to record the render passes into
+    /// * `target` - View of a texture in the view's format and size
+    /// * `game_state` - The game state holding the player's camera and the enemy
+    /// * `time` - Animation time in seconds
+    pub fn render(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        encoder: &mut wgpu::CommandEncoder,
+        target: &wgpu::TextureView,
+        game_state: &GameState,
+        time: f32,
+    ) {
+        let depth_view =
+            self.renderer
+                .update_depth_texture(device, &self.gpu_memory, self.width, self.height);
+        self.renderer.animate(game_state, time);
+
+        let background = color::linear_for_target(self.tuning.background_color, self.format);
+        let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some("Maze View Pass"),
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view: target,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Clear(color::clear_color(background)),
+                    store: wgpu::StoreOp::Store,
+                },
+            })],
+            depth_stencil_attachment: Some(wgpu::RenderPassDepthStencilAttachment {
+                view: &depth_view,
+                depth_ops: Some(wgpu::Operations {
+                    load: wgpu::LoadOp::Clear(1.0),
+                    store: wgpu::StoreOp::Store,
+                }),
+                stencil_ops: None,
+            }),
+            occlusion_query_set: None,
+            timestamp_writes: None,
+        });
+
+        let aspect = self.width as f32 / self.height as f32;
+        match self.camera {
+            Some(view) => {
+                let camera = SceneCamera {
+                    view,
+                    projection: camera_projection(game_state.player.fov, aspect),
+                };
+                self.renderer.render_scene(
+                    queue,
+                    game_state,
+                    &mut pass,
+                    &camera,
+                    &self.tuning,
+                    self.reduce_effects,
+                );
+            }
+            None => self.renderer.render_game(
+                queue,
+                game_state,
+                &mut pass,
+                aspect,
+                &self.tuning,
+                self.reduce_effects,
+            ),
+        }
+    }
+}
@@ -3,23 +3,61 @@
 //! This module contains submodules for uniform management, vertex definitions, and the wgpu renderer
 //! implementation. It provides the core rendering infrastructure for the application.
 
+/// Per-frame animation state advanced before rendering.
+pub mod animation;
+/// Graphics API selection and adapter diagnostics.
+pub mod backend;
+/// Color space conversions and color conventions.
+pub mod color;
 /// Game-specific rendering components and systems.
 pub mod game_renderer;
+/// Golden image tests for the screens that can be drawn offscreen.
+#[cfg(test)]
+mod golden;
+/// GPU texture memory accounting and budget.
+pub mod gpu_memory;
+/// GPU timestamp queries for timing single passes.
+pub mod gpu_timer;
 /// Icon rendering and management.
 pub mod icon;
 /// Loading screen rendering components.
 pub mod loading_renderer;
+/// Drawing the maze into a wgpu target owned by another front-end.
+pub mod maze_view;
+/// Multisample anti-aliasing of the 3D view.
+pub mod msaa;
+/// Dust and wall impact particles.
+pub mod particles;
 /// Pipeline building utilities for WGPU.
 pub mod pipeline_builder;
+/// Present mode selection, for turning vsync on or off.
+pub mod present_mode;
 /// Basic geometric primitives for rendering.
 pub mod primitives;
 /// Rectangle rendering utilities.
 pub mod rectangle;
+/// Picture-in-picture replay of the player's path at the end of a level.
+pub mod replay_pip;
+/// HUD safe area and 3D view boxing for unusual aspect ratios.
+pub mod safe_area;
+/// Offscreen copies of the 3D view for post effects.
+pub mod scene_target;
+/// Screenshots of the game window.
+pub mod screenshot;
+/// Surface alpha mode selection and the opaque alpha stamp.
+pub mod surface_alpha;
 /// Text rendering system.
 pub mod text;
 /// Title screen rendering components.
 pub mod title;
+/// Live-tunable renderer parameters.
+pub mod tuning;
 /// User interface rendering components.
 pub mod ui;
+/// Checks of uniform structs against the layout their shaders declare.
+#[cfg(test)]
+mod uniform_layout;
+/// Glyph warm-up scheduled into loading screen frames.
+pub mod warmup;
 /// Core WGPU library and utilities.
 pub mod wgpu_lib;
@@ -0,0 +1,237 @@
+//! Multisample anti-aliasing for the 3D view.
+//!
+//! With MSAA on, the clear, background, and main passes draw into a multisampled
+//! [`MsaaTarget`] instead of the scene view, testing against a depth buffer with the same
+//! sample count, and the main pass resolves the samples into the scene view as it ends.
+//! Everything drawn after that, the ambient particles, light shafts, and HUD, draws on the
+//! resolved image with one sample per pixel.
+//!
+//! The sample count is picked once when the renderer is built, since every scene pipeline is
+//! built for it. A count the adapter can't draw or resolve falls back to the highest one below
+//! it that it can, down to 1 sample, which turns MSAA off.
+//!
+//! # Usage
+//!
+//! ```rust,ignore
+//! let sample_count = msaa::supported_sample_count(&adapter, surface_config.format, 4);
+//! let mut msaa = MsaaTarget::new(&surface_config, sample_count, &gpu_memory);
+//!
+//! // Each frame, before the scene passes
+//! msaa.prepare(&device);
+//! let attachment = msaa.color_attachment(&scene_view, wgpu::LoadOp::Load, true);
+//! ```
+
+use crate::renderer::gpu_memory::{
+    GpuMemoryTracker, TextureCategory, TrackedTexture, create_tracked_texture,
+};
+use wgpu;
+
+/// Sample counts MSAA can be drawn with, from none to the most.
+pub const SAMPLE_COUNTS: [u32; 3] = [1, 2, 4];
+
+/// Format of the 3D view's depth buffer, which has to support the sample count too.
+pub const DEPTH_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Depth24Plus;
+
+/// Picks the sample count to draw the 3D view with.
+///
+/// # Arguments
+/// * `requested` - Samples per pixel asked for, such as `4` for 4x MSAA
+/// * `supported` - Whether the adapter can draw and resolve a count above 1
+///
+/// # Returns
+/// The highest count in [`SAMPLE_COUNTS`] no higher than `requested` that is supported,
+/// or 1 if none is
+pub fn choose_sample_count(requested: u32, supported: impl Fn(u32) -> bool) -> u32 {
+    SAMPLE_COUNTS
+        .iter()
+        .rev()
+        .copied()
+        .filter(|&count| count <= requested)
+        .find(|&count| count == 1 || supported(count))
+        .unwrap_or(1)
+}
+
+/// Picks the sample count the adapter supports for the surface format and the depth buffer.
+///
+/// MSAA stays off on the GL backend, which can't resolve a multisampled target drawn with a
+/// multisampled depth buffer that shaders can also read, as the light shafts' is. Logs a
+/// warning if the requested count had to be lowered.
+///
+/// # Arguments
+/// * `adapter` - The adapter the device was created on
+/// * `format` - Format of the surface the samples are resolved into
+/// * `requested` - Samples per pixel asked for
+pub fn supported_sample_count(
+    adapter: &wgpu::Adapter,
+    format: wgpu::TextureFormat,
+    requested: u32,
+) -> u32 {
+    let gl = adapter.get_info().backend == wgpu::Backend::Gl;
+    let color = adapter.get_texture_format_features(format).flags;
+    let depth = adapter.get_texture_format_features(DEPTH_FORMAT).flags;
+    let sample_count = choose_sample_count(requested, |count| {
+        !gl && color.sample_count_supported(count)
+            && color.contains(wgpu::TextureFormatFeatureFlags::MULTISAMPLE_RESOLVE)
+            && depth.sample_count_supported(count)
+    });
+    if sample_count != requested.max(1) {
+        eprintln!(
+            "Warning: {}x MSAA isn't supported by the adapter, using {}x",
+            requested, sample_count
+        );
+    }
+    sample_count
+}
+
+/// The multisampled color texture the 3D view is drawn into before it is resolved.
+pub struct MsaaTarget {
+    /// Samples per pixel; 1 when MSAA is off and no texture is made
+    sample_count: u32,
+    /// Format of the surface, which the texture matches
+    format: wgpu::TextureFormat,
+    /// Tracker the texture is counted in
+    gpu_memory: GpuMemoryTracker,
+    /// Size of the surface, and of the texture, in pixels
+    size: (u32, u32),
+    /// The texture and its view, once they have been needed
+    allocated: Option<(TrackedTexture, wgpu::TextureView)>,
+}
+
+impl MsaaTarget {
+    /// Creates a target; its texture is created the first time it is needed.
+    ///
+    /// # Arguments
+    /// * `surface_config` - Surface configuration containing the format and size
+    /// * `sample_count` - Samples per pixel, from [`supported_sample_count`]
+    /// * `gpu_memory` - Tracker the texture is counted in
+    pub fn new(
+        surface_config: &wgpu::SurfaceConfiguration,
+        sample_count: u32,
+        gpu_memory: &GpuMemoryTracker,
+    ) -> Self {
+        Self {
+            sample_count: sample_count.max(1),
+            format: surface_config.format,
+            gpu_memory: gpu_memory.clone(),
+            size: (surface_config.width.max(1), surface_config.height.max(1)),
+            allocated: None,
+        }
+    }
+
+    /// Matches a new surface size; the texture is recreated at the new size the next time it
+    /// is needed.
+    ///
+    /// # Arguments
+    /// * `width` - New surface width in pixels
+    /// * `height` - New surface height in pixels
+    pub fn resize(&mut self, width: u32, height: u32) {
+        self.size = (width.max(1), height.max(1));
+        let stale = self
+            .allocated
+            .as_ref()
+            .is_some_and(|(texture, _)| (texture.width(), texture.height()) != self.size);
+        if stale {
+            self.allocated = None;
+        }
+    }
+
+    /// Creates the texture if MSAA is on and it doesn't exist yet.
+    ///
+    /// Unlike the post effects' targets this is never skipped for the memory budget, since
+    /// the multisampled pipelines can't draw without it.
+    ///
+    /// # Arguments
+    /// * `device` - WebGPU device for creating the texture
+    pub fn prepare(&mut self, device: &wgpu::Device) {
+        if self.sample_count == 1 || self.allocated.is_some() {
+            return;
+        }
+        let (width, height) = self.size;
+        let texture = create_tracked_texture(
+            device,
+            &self.gpu_memory,
+            TextureCategory::SceneTargets,
+            &wgpu::TextureDescriptor {
+                label: Some("MSAA Color Texture"),
+                size: wgpu::Extent3d {
+                    width,
+                    height,
+                    depth_or_array_layers: 1,
+                },
+                mip_level_count: 1,
+                sample_count: self.sample_count,
+                dimension: wgpu::TextureDimension::D2,
+                format: self.format,
+                usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+                view_formats: &[],
+            },
+        );
+        let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+        self.allocated = Some((texture, view));
+    }
+
+    /// Returns the color attachment of a pass drawing the 3D view into `target`.
+    ///
+    /// With MSAA on the pass draws into the multisampled texture, and when `resolve` is set
+    /// resolves it into `target` at the end; the samples aren't needed after that, so they
+    /// are discarded. With MSAA off, or before [`MsaaTarget::prepare`], it draws straight
+    /// into `target`.
+    ///
+    /// # Arguments
+    /// * `target` - The single-sampled view the scene ends up in
+    /// * `load` - What the pass starts from
+    /// * `resolve` - Whether this is the last pass drawing into the multisampled texture
+    pub fn color_attachment<'a>(
+        &'a self,
+        target: &'a wgpu::TextureView,
+        load: wgpu::LoadOp<wgpu::Color>,
+        resolve: bool,
+    ) -> wgpu::RenderPassColorAttachment<'a> {
+        match &self.allocated {
+            Some((_, view)) => wgpu::RenderPassColorAttachment {
+                view,
+                resolve_target: resolve.then_some(target),
+                ops: wgpu::Operations {
+                    load,
+                    store: if resolve {
+                        wgpu::StoreOp::Discard
+                    } else {
+                        wgpu::StoreOp::Store
+                    },
+                },
+            },
+            None => wgpu::RenderPassColorAttachment {
+                view: target,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load,
+                    store: wgpu::StoreOp::Store,
+                },
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sample_count_falls_back_to_what_is_supported() {
+        let everything = |_| true;
+        let nothing = |_| false;
+        let only_four = |count| count == 4;
+
+        assert_eq!(choose_sample_count(4, everything), 4);
+        assert_eq!(choose_sample_count(2, everything), 2);
+        assert_eq!(choose_sample_count(1, everything), 1);
+        assert_eq!(choose_sample_count(0, everything), 1);
+        // Counts between the supported ones round down
+        assert_eq!(choose_sample_count(3, everything), 2);
+        assert_eq!(choose_sample_count(8, everything), 4);
+
+        assert_eq!(choose_sample_count(4, nothing), 1);
+        assert_eq!(choose_sample_count(4, only_four), 4);
+        assert_eq!(choose_sample_count(2, only_four), 1);
+    }
+}
@@ -0,0 +1,637 @@
+//! Dust and wall impact particles for the Mirador game.
+//!
+//! This module provides [`ParticleSystem`], a small CPU-simulated pool of particles, and
+//! [`ParticleRenderer`], which draws them as camera-facing billboards. Two emitters feed the
+//! pool:
+//!
+//! - Dust puffs kicked up behind the player's feet every other footstep while sprinting
+//! - A short burst sprayed off the wall surface when the player runs into a wall at speed
+//!
+//! The pool never grows: once [`PARTICLE_CAPACITY`] particles are alive, new ones are dropped.
+//! Particles are only aged by [`ParticleSystem::update`], which the game calls during gameplay
+//! frames, so they freeze in place while the game is paused. The pool is cleared whenever a
+//! level is (re)built.
+//!
+//! When reduced effects are enabled no dust is emitted and impact bursts are much smaller.
+//!
+//! # Usage
+//!
+//! ```rust,ignore
+//! let mut particles = ParticleSystem::new();
+//!
+//! // Once per gameplay frame
+//! particles.update(delta_time, reduce_effects);
+//! particles.track_footsteps(player_feet, is_sprinting, delta_time);
+//! if let Some(impact) = last_movement.impact {
+//!     particles.emit_impact(&impact);
+//! }
+//! ```
+
+use crate::game::collision::WallImpact;
+use crate::math::mat::Mat4;
+use crate::renderer::pipeline_builder::{
+    BindGroupLayoutBuilder, PipelineBuilder, create_uniform_buffer,
+};
+use rand::Rng;
+use wgpu::{self, util::DeviceExt};
+
+/// Maximum number of particles alive at once.
+pub const PARTICLE_CAPACITY: usize = 256;
+
+/// Seconds between dust puffs while sprinting; every other sprint footstep.
+pub const DUST_INTERVAL: f32 = 0.5;
+
+/// Slowest speed into a wall, in units per second, that sprays an impact burst.
+///
+/// Walking speed is 120, so only sprinting (or a well-upgraded walk) straight into a wall
+/// counts as hitting it at speed.
+pub const IMPACT_MIN_SPEED: f32 = 150.0;
+
+/// Seconds after an impact burst before another one can be sprayed.
+///
+/// Pressing into a wall reports an impact every frame; this keeps it to a single burst.
+pub const IMPACT_COOLDOWN: f32 = 0.4;
+
+/// Particles in a single dust puff.
+const DUST_PUFF_COUNT: usize = 5;
+
+/// Particles in a full impact burst.
+const IMPACT_BURST_COUNT: usize = 16;
+
+/// Particles in an impact burst when reduced effects are enabled.
+const REDUCED_IMPACT_BURST_COUNT: usize = 4;
+
+/// Height above the floor dust puffs start at.
+const DUST_HEIGHT: f32 = 3.0;
+
+/// Downward acceleration applied to every particle, in units per second squared.
+const GRAVITY: f32 = 60.0;
+
+/// Fraction of a particle's velocity kept after one second.
+const DRAG: f32 = 0.15;
+
+/// Dust color as linear RGBA.
+const DUST_COLOR: [f32; 4] = [0.45, 0.4, 0.34, 0.5];
+
+/// Impact spark color as linear RGBA.
+const IMPACT_COLOR: [f32; 4] = [0.85, 0.82, 0.75, 0.8];
+
+/// A single simulated particle.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Particle {
+    /// World position
+    pub position: [f32; 3],
+    /// Velocity in units per second
+    pub velocity: [f32; 3],
+    /// Seconds since the particle was emitted
+    pub age: f32,
+    /// Seconds the particle lives for
+    pub lifetime: f32,
+    /// Billboard width when freshly emitted, in world units
+    pub size: f32,
+    /// Linear RGBA color, with alpha as the opacity of a fresh particle
+    pub color: [f32; 4],
+}
+
+impl Particle {
+    /// An empty slot in the pool.
+    const EMPTY: Particle = Particle {
+        position: [0.0; 3],
+        velocity: [0.0; 3],
+        age: 0.0,
+        lifetime: 0.0,
+        size: 0.0,
+        color: [0.0; 4],
+    };
+
+    /// Returns how much of the particle is left, from 1.0 when fresh to 0.0 when expired.
+    pub fn fade(&self) -> f32 {
+        if self.lifetime <= 0.0 {
+            return 0.0;
+        }
+        (1.0 - self.age / self.lifetime).clamp(0.0, 1.0)
+    }
+}
+
+/// A fixed-size pool of CPU-simulated particles.
+#[derive(Debug, Clone)]
+pub struct ParticleSystem {
+    /// Particle storage; only the first `len` slots are live
+    particles: [Particle; PARTICLE_CAPACITY],
+    /// Number of particles currently alive
+    len: usize,
+    /// Seconds since the last dust puff
+    since_dust: f32,
+    /// Where the player's feet were on the previous footstep update
+    last_feet: Option<[f32; 3]>,
+    /// Seconds left before another impact burst can be sprayed
+    impact_cooldown: f32,
+    /// Whether the most recent update asked for reduced effects
+    reduce_effects: bool,
+    /// Number of particles dropped because the pool was full
+    dropped: usize,
+}
+
+impl Default for ParticleSystem {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ParticleSystem {
+    /// Creates an empty particle pool.
+    pub fn new() -> Self {
+        Self {
+            particles: [Particle::EMPTY; PARTICLE_CAPACITY],
+            len: 0,
+            since_dust: 0.0,
+            last_feet: None,
+            impact_cooldown: 0.0,
+            reduce_effects: false,
+            dropped: 0,
+        }
+    }
+
+    /// Returns the number of live particles.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if no particles are alive.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the number of particles dropped because the pool was full.
+    pub fn dropped_count(&self) -> usize {
+        self.dropped
+    }
+
+    /// Iterates over the live particles.
+    pub fn iter(&self) -> impl Iterator<Item = &Particle> {
+        self.particles[..self.len].iter()
+    }
+
+    /// Removes every particle and forgets the emitters' state.
+    ///
+    /// Called on level transitions so no dust or sparks carry over into the next maze.
+    pub fn clear(&mut self) {
+        self.len = 0;
+        self.since_dust = 0.0;
+        self.last_feet = None;
+        self.impact_cooldown = 0.0;
+    }
+
+    /// Ages and moves every particle, discarding the expired ones.
+    ///
+    /// Only call this during gameplay frames; skipping it while paused freezes the particles.
+    ///
+    /// # Arguments
+    /// * `delta_time` - Seconds since the last update
+    /// * `reduce_effects` - Whether the player has asked for reduced visual effects
+    pub fn update(&mut self, delta_time: f32, reduce_effects: bool) {
+        self.reduce_effects = reduce_effects;
+        self.impact_cooldown = (self.impact_cooldown - delta_time).max(0.0);
+
+        let drag = DRAG.powf(delta_time);
+        let mut i = 0;
+        while i < self.len {
+            let particle = &mut self.particles[i];
+            particle.age += delta_time;
+            if particle.age >= particle.lifetime {
+                // Keep live particles packed at the front of the pool
+                self.len -= 1;
+                self.particles.swap(i, self.len);
+                continue;
+            }
+
+            particle.velocity[1] -= GRAVITY * delta_time;
+            for axis in 0..3 {
+                particle.velocity[axis] *= drag;
+                particle.position[axis] += particle.velocity[axis] * delta_time;
+            }
+            i += 1;
+        }
+    }
+
+    /// Kicks up a dust puff behind the player every [`DUST_INTERVAL`] seconds of sprinting.
+    ///
+    /// The puff is thrown opposite to the direction the feet moved since the last call, so
+    /// it trails the player. No dust is emitted when reduced effects are enabled.
+    ///
+    /// # Arguments
+    /// * `feet_position` - Position of the player's feet on the floor
+    /// * `is_sprinting` - Whether the player is currently sprinting
+    /// * `delta_time` - Seconds since the last call
+    pub fn track_footsteps(
+        &mut self,
+        feet_position: [f32; 3],
+        is_sprinting: bool,
+        delta_time: f32,
+    ) {
+        let previous = self.last_feet.replace(feet_position);
+        let moved = previous.map(|previous| {
+            [
+                feet_position[0] - previous[0],
+                feet_position[2] - previous[2],
+            ]
+        });
+        let Some([dx, dz]) = moved.filter(|[dx, dz]| dx * dx + dz * dz > f32::EPSILON) else {
+            self.since_dust = 0.0;
+            return;
+        };
+        if !is_sprinting || self.reduce_effects {
+            self.since_dust = 0.0;
+            return;
+        }
+
+        self.since_dust += delta_time;
+        if self.since_dust < DUST_INTERVAL {
+            return;
+        }
+        self.since_dust -= DUST_INTERVAL;
+
+        let length = (dx * dx + dz * dz).sqrt();
+        let back = [-dx / length, -dz / length];
+        let mut rng = rand::thread_rng();
+        for _ in 0..DUST_PUFF_COUNT {
+            let spread = rng.gen_range(-12.0..12.0);
+            let kick = rng.gen_range(15.0..35.0);
+            self.spawn(Particle {
+                position: [
+                    feet_position[0] + rng.gen_range(-3.0..3.0),
+                    DUST_HEIGHT,
+                    feet_position[2] + rng.gen_range(-3.0..3.0),
+                ],
+                velocity: [
+                    back[0] * kick - back[1] * spread,
+                    rng.gen_range(20.0..40.0),
+                    back[1] * kick + back[0] * spread,
+                ],
+                age: 0.0,
+                lifetime: rng.gen_range(0.5..0.8),
+                size: rng.gen_range(6.0..10.0),
+                color: DUST_COLOR,
+            });
+        }
+    }
+
+    /// Sprays a burst of particles off the wall where the player ran into it.
+    ///
+    /// Impacts slower than [`IMPACT_MIN_SPEED`] are ignored, as are impacts within
+    /// [`IMPACT_COOLDOWN`] of the previous burst.
+    ///
+    /// # Arguments
+    /// * `impact` - The wall impact reported by collision resolution
+    ///
+    /// # Returns
+    /// `true` if a burst was emitted
+    pub fn emit_impact(&mut self, impact: &WallImpact) -> bool {
+        if impact.speed < IMPACT_MIN_SPEED || self.impact_cooldown > 0.0 {
+            return false;
+        }
+        self.impact_cooldown = IMPACT_COOLDOWN;
+
+        let count = if self.reduce_effects {
+            REDUCED_IMPACT_BURST_COUNT
+        } else {
+            IMPACT_BURST_COUNT
+        };
+        let [nx, _, nz] = impact.normal;
+        // Horizontal direction along the wall surface
+        let tangent = [-nz, nx];
+        let mut rng = rand::thread_rng();
+        for _ in 0..count {
+            let out = rng.gen_range(40.0..110.0);
+            let along = rng.gen_range(-60.0..60.0);
+            self.spawn(Particle {
+                position: impact.position,
+                velocity: [
+                    nx * out + tangent[0] * along,
+                    rng.gen_range(-30.0..50.0),
+                    nz * out + tangent[1] * along,
+                ],
+                age: 0.0,
+                lifetime: rng.gen_range(0.25..0.45),
+                size: rng.gen_range(2.5..4.5),
+                color: IMPACT_COLOR,
+            });
+        }
+        true
+    }
+
+    /// Adds a particle to the pool.
+    ///
+    /// # Arguments
+    /// * `particle` - The particle to add
+    ///
+    /// # Returns
+    /// `false` if the pool was full and the particle was dropped
+    pub fn spawn(&mut self, particle: Particle) -> bool {
+        if self.len == PARTICLE_CAPACITY {
+            self.dropped += 1;
+            return false;
+        }
+        self.particles[self.len] = particle;
+        self.len += 1;
+        true
+    }
+}
+
+/// Uniform data structure for the particle shader.
+#[repr(C)]
+#[derive(Copy, Clone, Debug, bytemuck::Pod, bytemuck::Zeroable)]
+struct ParticleUniforms {
+    /// View-projection matrix for transforming vertices to screen space
+    view_proj_matrix: [[f32; 4]; 4],
+    /// Camera right vector in world space (w unused)
+    camera_right: [f32; 4],
+    /// Camera up vector in world space (w unused)
+    camera_up: [f32; 4],
+}
+
+/// Per-particle instance data.
+#[repr(C)]
+#[derive(Copy, Clone, Debug, Default, bytemuck::Pod, bytemuck::Zeroable)]
+struct ParticleInstance {
+    /// Particle world position (x, y, z) followed by its billboard width
+    position_size: [f32; 4],
+    /// Particle color with alpha already faded by age
+    color: [f32; 4],
+}
+
+/// Renders the particle pool as instanced camera-facing quads.
+///
+/// Particles are alpha blended and do not write depth, so they are drawn after the main
+/// geometry and still get hidden by walls in front of them.
+pub struct ParticleRenderer {
+    /// The render pipeline for particle rendering
+    pipeline: wgpu::RenderPipeline,
+    /// Vertex buffer containing the unit quad corners
+    vertex_buffer: wgpu::Buffer,
+    /// Instance buffer holding one entry per pool slot
+    instance_buffer: wgpu::Buffer,
+    /// Uniform buffer containing shader uniforms
+    uniform_buffer: wgpu::Buffer,
+    /// Bind group containing the uniforms
+    bind_group: wgpu::BindGroup,
+    /// Number of instances written by the last update
+    instance_count: u32,
+}
+
+impl ParticleRenderer {
+    /// Creates a new particle renderer.
+    ///
+    /// # Arguments
+    ///
+    /// * `device` - WGPU device for creating GPU resources
+    /// * `surface_config` - Surface configuration for pipeline creation
+    /// * `sample_count` - Samples per pixel of the render target the 3D view is drawn into
+    ///
+    /// # Returns
+    ///
+    /// A new `ParticleRenderer` with an instance buffer sized for a full pool.
+    pub fn new(
+        device: &wgpu::Device,
+        surface_config: &wgpu::SurfaceConfiguration,
+        sample_count: u32,
+    ) -> Self {
+        let uniforms = ParticleUniforms {
+            view_proj_matrix: [[0.0; 4]; 4],
+            camera_right: [1.0, 0.0, 0.0, 0.0],
+            camera_up: [0.0, 1.0, 0.0, 0.0],
+        };
+        let uniform_buffer = create_uniform_buffer(device, &uniforms, "Particle Uniform Buffer");
+
+        let bind_group_layout = BindGroupLayoutBuilder::new(device)
+            .with_label("Particle Bind Group Layout")
+            .with_uniform_buffer(0, wgpu::ShaderStages::VERTEX)
+            .build();
+
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            layout: &bind_group_layout,
+            entries: &[wgpu::BindGroupEntry {
+                binding: 0,
+                resource: uniform_buffer.as_entire_binding(),
+            }],
+            label: Some("Particle Bind Group"),
+        });
+
+        // Unit quad corners in the billboard plane
+        let corners: &[f32] = &[
+            -0.5, -0.5, 0.5, -0.5, -0.5, 0.5, // Triangle 1
+            0.5, -0.5, 0.5, 0.5, -0.5, 0.5, // Triangle 2
+        ];
+        let vertex_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Particle Vertex Buffer"),
+            contents: bytemuck::cast_slice(corners),
+            usage: wgpu::BufferUsages::VERTEX,
+        });
+
+        let instance_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("Particle Instance Buffer"),
+            contents: bytemuck::cast_slice(&[ParticleInstance::default(); PARTICLE_CAPACITY]),
+            usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
+        });
+
+        let vertex_buffer_layout = wgpu::VertexBufferLayout {
+            array_stride: 2 * 4, // 2 floats * 4 bytes each
+            step_mode: wgpu::VertexStepMode::Vertex,
+            attributes: &[wgpu::VertexAttribute {
+                offset: 0,
+                shader_location: 0,
+                format: wgpu::VertexFormat::Float32x2, // corner (right, up)
+            }],
+        };
+        let instance_buffer_layout = wgpu::VertexBufferLayout {
+            array_stride: std::mem::size_of::<ParticleInstance>() as wgpu::BufferAddress,
+            step_mode: wgpu::VertexStepMode::Instance,
+            attributes: &[
+                wgpu::VertexAttribute {
+                    offset: 0,
+                    shader_location: 1,
+                    format: wgpu::VertexFormat::Float32x4, // position + size
+                },
+                wgpu::VertexAttribute {
+                    offset: 4 * 4,
+                    shader_location: 2,
+                    format: wgpu::VertexFormat::Float32x4, // color
+                },
+            ],
+        };
+
+        let pipeline = PipelineBuilder::new(device, surface_config.format)
+            .with_label("Particle Pipeline")
+            .with_shader(include_str!("shaders/particles.wgsl"))
+            .with_vertex_buffer(vertex_buffer_layout)
+            .with_vertex_buffer(instance_buffer_layout)
+            .with_bind_group_layout(&bind_group_layout)
+            .with_alpha_blending()
+            .with_no_culling()
+            .with_depth_stencil(wgpu::DepthStencilState {
+                depth_write_enabled: false,
+                depth_compare: wgpu::CompareFunction::LessEqual,
+                format: wgpu::TextureFormat::Depth24Plus,
+                stencil: wgpu::StencilState::default(),
+                bias: wgpu::DepthBiasState::default(),
+            })
+            .with_sample_count(sample_count)
+            .build();
+
+        Self {
+            pipeline,
+            vertex_buffer,
+            instance_buffer,
+            uniform_buffer,
+            bind_group,
+            instance_count: 0,
+        }
+    }
+
+    /// Uploads the live particles and camera matrices.
+    ///
+    /// The instance data is built on the stack and written over the existing instance buffer,
+    /// so no GPU or heap allocations happen per frame.
+    ///
+    /// # Arguments
+    ///
+    /// * `queue` - WGPU queue for uploading data
+    /// * `particles` - The particle pool to draw
+    /// * `view_matrix` - Current view matrix, used to face the billboards at the camera
+    /// * `view_proj_matrix` - Current view-projection matrix for rendering
+    pub fn update(
+        &mut self,
+        queue: &wgpu::Queue,
+        particles: &ParticleSystem,
+        view_matrix: &Mat4,
+        view_proj_matrix: [[f32; 4]; 4],
+    ) {
+        let mut instances = [ParticleInstance::default(); PARTICLE_CAPACITY];
+        for (instance, particle) in instances.iter_mut().zip(particles.iter()) {
+            let fade = particle.fade();
+            let [x, y, z] = particle.position;
+            let [r, g, b, a] = particle.color;
+            // Particles spread out a little as they fade
+            instance.position_size = [x, y, z, particle.size * (1.5 - 0.5 * fade)];
+            instance.color = [r, g, b, a * fade];
+        }
+        self.instance_count = particles.len() as u32;
+
+        if self.instance_count > 0 {
+            queue.write_buffer(
+                &self.instance_buffer,
+                0,
+                bytemuck::cast_slice(&instances[..particles.len()]),
+            );
+        }
+
+        // The view matrix's rotation rows are the camera axes in world space
+        let view = view_matrix.0;
+        let uniforms = ParticleUniforms {
+            view_proj_matrix,
+            camera_right: [view[0][0], view[1][0], view[2][0], 0.0],
+            camera_up: [view[0][1], view[1][1], view[2][1], 0.0],
+        };
+        queue.write_buffer(&self.uniform_buffer, 0, bytemuck::cast_slice(&[uniforms]));
+    }
+
+    /// Renders the particles to the specified render pass.
+    ///
+    /// Does nothing while the pool is empty.
+    ///
+    /// # Arguments
+    ///
+    /// * `render_pass` - The render pass to draw to
+    pub fn render(&self, render_pass: &mut wgpu::RenderPass) {
+        if self.instance_count == 0 {
+            return;
+        }
+
+        render_pass.set_pipeline(&self.pipeline);
+        render_pass.set_vertex_buffer(0, self.vertex_buffer.slice(..));
+        render_pass.set_vertex_buffer(1, self.instance_buffer.slice(..));
+        render_pass.set_bind_group(0, &self.bind_group, &[]);
+        render_pass.draw(0..6, 0..self.instance_count);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dust(lifetime: f32) -> Particle {
+        Particle {
+            lifetime,
+            ..Particle::EMPTY
+        }
+    }
+
+    #[test]
+    fn test_full_pool_drops_new_particles() {
+        let mut particles = ParticleSystem::new();
+        for _ in 0..PARTICLE_CAPACITY {
+            assert!(particles.spawn(dust(1.0)));
+        }
+        assert!(!particles.spawn(dust(1.0)));
+        assert_eq!(particles.len(), PARTICLE_CAPACITY);
+        assert_eq!(particles.dropped_count(), 1);
+
+        // A burst into a full pool is dropped too, and expired particles free their slots
+        let impact = WallImpact {
+            position: [0.0, 50.0, 0.0],
+            normal: [1.0, 0.0, 0.0],
+            speed: 210.0,
+        };
+        assert!(particles.emit_impact(&impact));
+        assert_eq!(particles.dropped_count(), 1 + IMPACT_BURST_COUNT);
+        particles.update(1.5, false);
+        assert!(particles.is_empty());
+    }
+
+    #[test]
+    fn test_emitters_respect_speed_cooldown_and_reduced_effects() {
+        let mut particles = ParticleSystem::new();
+        let slow = WallImpact {
+            position: [0.0, 50.0, 0.0],
+            normal: [0.0, 0.0, -1.0],
+            speed: 120.0,
+        };
+        let fast = WallImpact {
+            speed: 210.0,
+            ..slow
+        };
+        assert!(!particles.emit_impact(&slow));
+        assert!(particles.emit_impact(&fast));
+        assert!(
+            !particles.emit_impact(&fast),
+            "Bursts should be rate limited"
+        );
+        assert_eq!(particles.len(), IMPACT_BURST_COUNT);
+
+        // Nothing ages without an update, as when the game is paused
+        let ages: Vec<f32> = particles.iter().map(|particle| particle.age).collect();
+        assert!(ages.iter().all(|&age| age == 0.0));
+
+        particles.clear();
+        particles.update(IMPACT_COOLDOWN, true);
+        assert!(particles.emit_impact(&fast));
+        assert_eq!(particles.len(), REDUCED_IMPACT_BURST_COUNT);
+
+        // Reduced effects kick up no dust, full effects do once a puff is due
+        particles.clear();
+        let mut feet = [0.0, 0.0, 0.0];
+        for _ in 0..10 {
+            feet[2] -= 20.0;
+            particles.track_footsteps(feet, true, 0.125);
+        }
+        assert!(particles.is_empty());
+        particles.update(0.0, false);
+        for _ in 0..10 {
+            feet[2] -= 20.0;
+            particles.track_footsteps(feet, true, 0.125);
+        }
+        assert_eq!(particles.len(), 2 * DUST_PUFF_COUNT);
+        // Dust is thrown back against the direction of travel
+        assert!(particles.iter().all(|particle| particle.velocity[2] > 0.0));
+    }
+}
@@ -12,7 +12,7 @@
 //!
 //! ## Usage Example
 //!
-//! ```rust,no_run
+//! ```rust,ignore
 //! use crate::renderer::pipeline_builder::{PipelineBuilder, BindGroupLayoutBuilder, create_vertex_2d_layout};
 //!
 //! // Create a bind group layout for texture + sampler
@@ -64,7 +64,7 @@ use wgpu::util::DeviceExt;
 ///
 /// ## Example
 ///
-/// ```rust,no_run
+/// ```rust,ignore
 /// # use wgpu;
 /// # let device: wgpu::Device = unimplemented!();
 /// # let surface_format = wgpu::TextureFormat::Bgra8UnormSrgb;
@@ -91,8 +91,10 @@ pub struct PipelineBuilder<'a> {
     vertex_buffers: Vec<wgpu::VertexBufferLayout<'a>>,
     bind_group_layouts: Vec<&'a wgpu::BindGroupLayout>,
     blend_state: Option<wgpu::BlendState>,
+    write_mask: wgpu::ColorWrites,
     cull_mode: Option<wgpu::Face>,
     depth_stencil: Option<wgpu::DepthStencilState>,
+    sample_count: u32,
 }
 
 impl<'a> PipelineBuilder<'a> {
@@ -108,12 +110,14 @@ impl<'a> PipelineBuilder<'a> {
     /// - Vertex entry: `"vs_main"`
     /// - Fragment entry: `"fs_main"`
     /// - Blend state: `REPLACE` (opaque rendering)
+    /// - Write mask: every channel
     /// - Cull mode: Back face culling enabled
     /// - No depth testing
+    /// - One sample per pixel
     ///
     /// # Example
     ///
-    /// ```rust,no_run
+    /// ```rust,ignore
     /// # use wgpu;
     /// # let device: wgpu::Device = unimplemented!();
     /// # let surface_format = wgpu::TextureFormat::Bgra8UnormSrgb;
@@ -132,8 +136,10 @@ impl<'a> PipelineBuilder<'a> {
             vertex_buffers: Vec::new(),
             bind_group_layouts: Vec::new(),
             blend_state: Some(wgpu::BlendState::REPLACE),
+            write_mask: wgpu::ColorWrites::ALL,
             cull_mode: Some(wgpu::Face::Back),
             depth_stencil: None,
+            sample_count: 1,
         }
     }
 
@@ -148,7 +154,7 @@ impl<'a> PipelineBuilder<'a> {
     ///
     /// # Example
     ///
-    /// ```rust,no_run
+    /// ```rust,ignore
     /// # use crate::renderer::pipeline_builder::PipelineBuilder;
     /// # let builder: PipelineBuilder = unimplemented!();
     /// let builder = builder.with_label("Maze Renderer Pipeline");
@@ -169,7 +175,7 @@ impl<'a> PipelineBuilder<'a> {
     ///
     /// # Example
     ///
-    /// ```rust,no_run
+    /// ```rust,ignore
     /// # use crate::renderer::pipeline_builder::PipelineBuilder;
     /// # let builder: PipelineBuilder = unimplemented!();
     /// let shader_source = r#"
@@ -190,26 +196,6 @@ impl<'a> PipelineBuilder<'a> {
         self
     }
 
-    /// Set the vertex shader entry point function name.
-    ///
-    /// Default is `"vs_main"`. Change this if your vertex shader function has a different name.
-    ///
-    /// # Parameters
-    ///
-    /// - `entry` - Name of the vertex shader function in the WGSL source
-    ///
-    /// # Example
-    ///
-    /// ```rust,no_run
-    /// # use crate::renderer::pipeline_builder::PipelineBuilder;
-    /// # let builder: PipelineBuilder = unimplemented!();
-    /// let builder = builder.with_vertex_entry("vertex_main");
-    /// ```
-    pub fn with_vertex_entry(mut self, entry: &'a str) -> Self {
-        self.vertex_entry = Some(entry);
-        self
-    }
-
     /// Set the fragment shader entry point function name.
     ///
     /// Default is `"fs_main"`. Change this if your fragment shader function has a different name.
@@ -220,7 +206,7 @@ impl<'a> PipelineBuilder<'a> {
     ///
     /// # Example
     ///
-    /// ```rust,no_run
+    /// ```rust,ignore
     /// # use crate::renderer::pipeline_builder::PipelineBuilder;
     /// # let builder: PipelineBuilder = unimplemented!();
     /// let builder = builder.with_fragment_entry("fragment_main");
@@ -241,7 +227,7 @@ impl<'a> PipelineBuilder<'a> {
     ///
     /// # Example
     ///
-    /// ```rust,no_run
+    /// ```rust,ignore
     /// # use crate::renderer::pipeline_builder::{PipelineBuilder, create_vertex_2d_layout};
     /// # let builder: PipelineBuilder = unimplemented!();
     /// let builder = builder.with_vertex_buffer(create_vertex_2d_layout());
@@ -263,7 +249,7 @@ impl<'a> PipelineBuilder<'a> {
     ///
     /// # Example
     ///
-    /// ```rust,no_run
+    /// ```rust,ignore
     /// # use crate::renderer::pipeline_builder::PipelineBuilder;
     /// # let builder: PipelineBuilder = unimplemented!();
     /// # let bind_group_layout: &wgpu::BindGroupLayout = unimplemented!();
@@ -285,7 +271,7 @@ impl<'a> PipelineBuilder<'a> {
     ///
     /// # Example
     ///
-    /// ```rust,no_run
+    /// ```rust,ignore
     /// # use wgpu;
     /// # use crate::renderer::pipeline_builder::PipelineBuilder;
     /// # let builder: PipelineBuilder = unimplemented!();
@@ -314,7 +300,7 @@ impl<'a> PipelineBuilder<'a> {
     ///
     /// # Example
     ///
-    /// ```rust,no_run
+    /// ```rust,ignore
     /// # use crate::renderer::pipeline_builder::PipelineBuilder;
     /// # let builder: PipelineBuilder = unimplemented!();
     /// let builder = builder.with_alpha_blending();
@@ -324,6 +310,23 @@ impl<'a> PipelineBuilder<'a> {
         self
     }
 
+    /// Set which channels of the render target the pipeline writes.
+    ///
+    /// By default every channel is written. Channels left out of the mask keep
+    /// whatever was drawn before.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// # use crate::renderer::pipeline_builder::PipelineBuilder;
+    /// # let builder: PipelineBuilder = unimplemented!();
+    /// let builder = builder.with_write_mask(wgpu::ColorWrites::ALPHA);
+    /// ```
+    pub fn with_write_mask(mut self, write_mask: wgpu::ColorWrites) -> Self {
+        self.write_mask = write_mask;
+        self
+    }
+
     /// Disable face culling.
     ///
     /// By default, back faces are culled. Use this method to render both
@@ -331,7 +334,7 @@ impl<'a> PipelineBuilder<'a> {
     ///
     /// # Example
     ///
-    /// ```rust,no_run
+    /// ```rust,ignore
     /// # use crate::renderer::pipeline_builder::PipelineBuilder;
     /// # let builder: PipelineBuilder = unimplemented!();
     /// let builder = builder.with_no_culling();
@@ -352,7 +355,7 @@ impl<'a> PipelineBuilder<'a> {
     ///
     /// # Example
     ///
-    /// ```rust,no_run
+    /// ```rust,ignore
     /// # use wgpu;
     /// # use crate::renderer::pipeline_builder::PipelineBuilder;
     /// # let builder: PipelineBuilder = unimplemented!();
@@ -370,6 +373,27 @@ impl<'a> PipelineBuilder<'a> {
         self
     }
 
+    /// Set the number of samples per pixel of the render target.
+    ///
+    /// Pipelines drawing into a multisampled target, and testing against a multisampled
+    /// depth buffer, must be built with the same count as those textures.
+    ///
+    /// # Parameters
+    ///
+    /// - `sample_count` - Samples per pixel, such as `4` for 4x MSAA
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// # use crate::renderer::pipeline_builder::PipelineBuilder;
+    /// # let builder: PipelineBuilder = unimplemented!();
+    /// let builder = builder.with_sample_count(4);
+    /// ```
+    pub fn with_sample_count(mut self, sample_count: u32) -> Self {
+        self.sample_count = sample_count.max(1);
+        self
+    }
+
     /// Build the render pipeline with the configured parameters.
     ///
     /// This consumes the builder and creates the actual WGPU render pipeline.
@@ -386,7 +410,7 @@ impl<'a> PipelineBuilder<'a> {
     ///
     /// # Example
     ///
-    /// ```rust,no_run
+    /// ```rust,ignore
     /// # use crate::renderer::pipeline_builder::PipelineBuilder;
     /// # let builder: PipelineBuilder = unimplemented!();
     /// let pipeline = builder.build();
@@ -425,7 +449,7 @@ impl<'a> PipelineBuilder<'a> {
                     targets: &[Some(wgpu::ColorTargetState {
                         format: self.surface_format,
                         blend: self.blend_state,
-                        write_mask: wgpu::ColorWrites::ALL,
+                        write_mask: self.write_mask,
                     })],
                     compilation_options: wgpu::PipelineCompilationOptions::default(),
                 }),
@@ -440,7 +464,7 @@ impl<'a> PipelineBuilder<'a> {
                 },
                 depth_stencil: self.depth_stencil,
                 multisample: wgpu::MultisampleState {
-                    count: 1,
+                    count: self.sample_count,
                     mask: !0,
                     alpha_to_coverage_enabled: false,
                 },
@@ -467,7 +491,7 @@ impl<'a> PipelineBuilder<'a> {
 /// Use the fluent API to add bindings, then call [`build()`](BindGroupLayoutBuilder::build)
 /// to create the layout:
 ///
-/// ```rust,no_run
+/// ```rust,ignore
 /// # use wgpu;
 /// # let device: wgpu::Device = unimplemented!();
 /// use crate::renderer::pipeline_builder::BindGroupLayoutBuilder;
@@ -494,7 +518,7 @@ impl<'a> BindGroupLayoutBuilder<'a> {
     ///
     /// # Example
     ///
-    /// ```rust,no_run
+    /// ```rust,ignore
     /// # use wgpu;
     /// # let device: wgpu::Device = unimplemented!();
     /// use crate::renderer::pipeline_builder::BindGroupLayoutBuilder;
@@ -517,7 +541,7 @@ impl<'a> BindGroupLayoutBuilder<'a> {
     ///
     /// # Example
     ///
-    /// ```rust,no_run
+    /// ```rust,ignore
     /// # use crate::renderer::pipeline_builder::BindGroupLayoutBuilder;
     /// # let builder: BindGroupLayoutBuilder = unimplemented!();
     /// let builder = builder.with_label("Texture Resources");
@@ -546,7 +570,7 @@ impl<'a> BindGroupLayoutBuilder<'a> {
     ///
     /// # Example
     ///
-    /// ```rust,no_run
+    /// ```rust,ignore
     /// # use wgpu;
     /// # use crate::renderer::pipeline_builder::BindGroupLayoutBuilder;
     /// # let builder: BindGroupLayoutBuilder = unimplemented!();
@@ -566,6 +590,88 @@ impl<'a> BindGroupLayoutBuilder<'a> {
         self
     }
 
+    /// Add a depth texture binding.
+    ///
+    /// Creates a binding for a 2D depth texture read with `textureLoad`, such as the
+    /// scene's depth buffer after the 3D view has been drawn.
+    ///
+    /// # Parameters
+    ///
+    /// - `binding` - Binding index in the shader (e.g., `@binding(0)`)
+    /// - `visibility` - Which shader stages can access this texture
+    ///
+    /// # Shader Usage
+    ///
+    /// In WGSL, access this texture with:
+    /// ```wgsl
+    /// @group(0) @binding(0) var depth_texture: texture_depth_2d;
+    /// ```
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// # use wgpu;
+    /// # use crate::renderer::pipeline_builder::BindGroupLayoutBuilder;
+    /// # let builder: BindGroupLayoutBuilder = unimplemented!();
+    /// let builder = builder.with_depth_texture(0, wgpu::ShaderStages::FRAGMENT);
+    /// ```
+    pub fn with_depth_texture(mut self, binding: u32, visibility: wgpu::ShaderStages) -> Self {
+        self.entries.push(wgpu::BindGroupLayoutEntry {
+            binding,
+            visibility,
+            ty: wgpu::BindingType::Texture {
+                multisampled: false,
+                view_dimension: wgpu::TextureViewDimension::D2,
+                sample_type: wgpu::TextureSampleType::Depth,
+            },
+            count: None,
+        });
+        self
+    }
+
+    /// Add a multisampled depth texture binding.
+    ///
+    /// Like [`with_depth_texture()`](BindGroupLayoutBuilder::with_depth_texture), for a depth
+    /// buffer drawn with MSAA. Each sample is read with `textureLoad`.
+    ///
+    /// # Parameters
+    ///
+    /// - `binding` - Binding index in the shader (e.g., `@binding(0)`)
+    /// - `visibility` - Which shader stages can access this texture
+    ///
+    /// # Shader Usage
+    ///
+    /// In WGSL, access this texture with:
+    /// ```wgsl
+    /// @group(0) @binding(0) var depth_texture: texture_depth_multisampled_2d;
+    /// ```
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// # use wgpu;
+    /// # use crate::renderer::pipeline_builder::BindGroupLayoutBuilder;
+    /// # let builder: BindGroupLayoutBuilder = unimplemented!();
+    /// let builder = builder.with_multisampled_depth_texture(0, wgpu::ShaderStages::FRAGMENT);
+    /// ```
+    pub fn with_multisampled_depth_texture(
+        mut self,
+        binding: u32,
+        visibility: wgpu::ShaderStages,
+    ) -> Self {
+        self.entries.push(wgpu::BindGroupLayoutEntry {
+            binding,
+            visibility,
+            ty: wgpu::BindingType::Texture {
+                multisampled: true,
+                view_dimension: wgpu::TextureViewDimension::D2,
+                sample_type: wgpu::TextureSampleType::Depth,
+            },
+            count: None,
+        });
+        self
+    }
+
     /// Add a filtering sampler binding.
     ///
     /// Creates a binding for a sampler that supports filtering (linear interpolation).
@@ -585,7 +691,7 @@ impl<'a> BindGroupLayoutBuilder<'a> {
     ///
     /// # Example
     ///
-    /// ```rust,no_run
+    /// ```rust,ignore
     /// # use wgpu;
     /// # use crate::renderer::pipeline_builder::BindGroupLayoutBuilder;
     /// # let builder: BindGroupLayoutBuilder = unimplemented!();
@@ -624,7 +730,7 @@ impl<'a> BindGroupLayoutBuilder<'a> {
     ///
     /// # Example
     ///
-    /// ```rust,no_run
+    /// ```rust,ignore
     /// # use wgpu;
     /// # use crate::renderer::pipeline_builder::BindGroupLayoutBuilder;
     /// # let builder: BindGroupLayoutBuilder = unimplemented!();
@@ -644,6 +750,50 @@ impl<'a> BindGroupLayoutBuilder<'a> {
         self
     }
 
+    /// Add a read-only storage buffer binding.
+    ///
+    /// Creates a binding for a storage buffer that shaders can read but not write.
+    /// Storage buffers suit larger or runtime-sized arrays that would not fit the
+    /// fixed layout of a uniform buffer.
+    ///
+    /// # Parameters
+    ///
+    /// - `binding` - Binding index in the shader (e.g., `@binding(1)`)
+    /// - `visibility` - Which shader stages can access this storage buffer
+    ///
+    /// # Shader Usage
+    ///
+    /// In WGSL, access this storage buffer with:
+    /// ```wgsl
+    /// @group(0) @binding(1) var<storage, read> values: array<f32>;
+    /// ```
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// # use wgpu;
+    /// # use crate::renderer::pipeline_builder::BindGroupLayoutBuilder;
+    /// # let builder: BindGroupLayoutBuilder = unimplemented!();
+    /// let builder = builder.with_read_only_storage_buffer(1, wgpu::ShaderStages::FRAGMENT);
+    /// ```
+    pub fn with_read_only_storage_buffer(
+        mut self,
+        binding: u32,
+        visibility: wgpu::ShaderStages,
+    ) -> Self {
+        self.entries.push(wgpu::BindGroupLayoutEntry {
+            binding,
+            visibility,
+            ty: wgpu::BindingType::Buffer {
+                ty: wgpu::BufferBindingType::Storage { read_only: true },
+                has_dynamic_offset: false,
+                min_binding_size: None,
+            },
+            count: None,
+        });
+        self
+    }
+
     /// Build the bind group layout.
     ///
     /// This consumes the builder and creates the actual WGPU bind group layout
@@ -656,7 +806,7 @@ impl<'a> BindGroupLayoutBuilder<'a> {
     ///
     /// # Example
     ///
-    /// ```rust,no_run
+    /// ```rust,ignore
     /// # use crate::renderer::pipeline_builder::BindGroupLayoutBuilder;
     /// # let builder: BindGroupLayoutBuilder = unimplemented!();
     /// let layout = builder.build();
@@ -692,7 +842,7 @@ impl<'a> BindGroupLayoutBuilder<'a> {
 ///
 /// ## Usage Example
 ///
-/// ```rust,no_run
+/// ```rust,ignore
 /// use crate::renderer::pipeline_builder::{PipelineBuilder, create_vertex_2d_layout};
 /// # let device: wgpu::Device = unimplemented!();
 /// # let surface_format = wgpu::TextureFormat::Bgra8UnormSrgb;
@@ -770,7 +920,7 @@ pub fn create_vertex_2d_layout() -> wgpu::VertexBufferLayout<'static> {
 ///
 /// # Example
 ///
-/// ```rust,no_run
+/// ```rust,ignore
 /// use crate::renderer::pipeline_builder::create_fullscreen_vertices;
 /// # let device: wgpu::Device = unimplemented!();
 ///
@@ -0,0 +1,151 @@
+//! How finished frames are handed to the display.
+//!
+//! The surface's [`wgpu::PresentMode`] decides whether presenting a frame waits for the
+//! display's refresh. With vsync the frame rate stays at the refresh rate; without it frames
+//! are presented as fast as they are drawn, which is what benchmarking and checking the
+//! frame rate caps need. [`PresentModePreference`] is the player's choice, and
+//! [`select_present_mode`] turns it into a mode the surface supports.
+//!
+//! Changing the mode only reconfigures the surface, so it takes effect on the next frame
+//! without recreating the device or any of the renderers.
+//!
+//! # Usage
+//!
+//! ```rust,ignore
+//! let capabilities = surface.get_capabilities(&adapter);
+//! surface_config.present_mode =
+//!     select_present_mode(PresentModePreference::Immediate, &capabilities.present_modes);
+//! surface.configure(&device, &surface_config);
+//! ```
+
+use serde::{Deserialize, Serialize};
+use wgpu::PresentMode;
+
+/// How the player wants frames presented.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PresentModePreference {
+    /// Wait for the display's refresh, which every surface supports
+    #[default]
+    Vsync,
+    /// Don't wait, letting the platform pick between immediate and mailbox presentation
+    NoVsync,
+    /// Present at once, even partway through the display's refresh, which can tear
+    Immediate,
+    /// Replace the waiting frame with each newer one and show the latest at the refresh
+    Mailbox,
+}
+
+impl PresentModePreference {
+    /// Returns the preference after this one, wrapping around.
+    pub fn next(self) -> Self {
+        match self {
+            Self::Vsync => Self::NoVsync,
+            Self::NoVsync => Self::Immediate,
+            Self::Immediate => Self::Mailbox,
+            Self::Mailbox => Self::Vsync,
+        }
+    }
+
+    /// Returns a short label for menus.
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Vsync => "On",
+            Self::NoVsync => "Off",
+            Self::Immediate => "Immed.",
+            Self::Mailbox => "Mailbox",
+        }
+    }
+
+    /// Returns the present mode the preference asks for, whether or not it is supported.
+    pub fn present_mode(self) -> PresentMode {
+        match self {
+            Self::Vsync => PresentMode::AutoVsync,
+            Self::NoVsync => PresentMode::AutoNoVsync,
+            Self::Immediate => PresentMode::Immediate,
+            Self::Mailbox => PresentMode::Mailbox,
+        }
+    }
+}
+
+/// Picks how the surface presents frames.
+///
+/// # Arguments
+/// * `preference` - How the player wants frames presented
+/// * `supported` - The present modes the surface supports
+///
+/// # Returns
+/// The mode the preference asks for; `AutoNoVsync` in place of an immediate or mailbox mode
+/// the surface doesn't support, since the two `Auto` modes are supported everywhere
+pub fn select_present_mode(
+    preference: PresentModePreference,
+    supported: &[PresentMode],
+) -> PresentMode {
+    match preference.present_mode() {
+        mode @ (PresentMode::AutoVsync | PresentMode::AutoNoVsync) => mode,
+        mode if supported.contains(&mode) => mode,
+        _ => PresentMode::AutoNoVsync,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unsupported_modes_fall_back_to_no_vsync() {
+        let fifo_only = [PresentMode::Fifo];
+        let everything = [
+            PresentMode::Fifo,
+            PresentMode::FifoRelaxed,
+            PresentMode::Immediate,
+            PresentMode::Mailbox,
+        ];
+
+        for supported in [&fifo_only[..], &everything[..], &[]] {
+            assert_eq!(
+                select_present_mode(PresentModePreference::Vsync, supported),
+                PresentMode::AutoVsync
+            );
+            assert_eq!(
+                select_present_mode(PresentModePreference::NoVsync, supported),
+                PresentMode::AutoNoVsync
+            );
+        }
+
+        assert_eq!(
+            select_present_mode(PresentModePreference::Immediate, &everything),
+            PresentMode::Immediate
+        );
+        assert_eq!(
+            select_present_mode(PresentModePreference::Mailbox, &everything),
+            PresentMode::Mailbox
+        );
+        assert_eq!(
+            select_present_mode(PresentModePreference::Immediate, &fifo_only),
+            PresentMode::AutoNoVsync
+        );
+        assert_eq!(
+            select_present_mode(PresentModePreference::Mailbox, &fifo_only),
+            PresentMode::AutoNoVsync
+        );
+    }
+
+    #[test]
+    fn test_cycle_visits_every_preference() {
+        let mut preference = PresentModePreference::default();
+        let mut seen = Vec::new();
+        for _ in 0..4 {
+            seen.push(preference);
+            preference = preference.next();
+        }
+        assert_eq!(preference, PresentModePreference::default());
+        for expected in [
+            PresentModePreference::Vsync,
+            PresentModePreference::NoVsync,
+            PresentModePreference::Immediate,
+            PresentModePreference::Mailbox,
+        ] {
+            assert!(seen.contains(&expected));
+        }
+    }
+}
@@ -3,8 +3,9 @@
 //! This module provides the [`Uniforms`] struct for storing and uploading uniform data
 //! (such as transformation matrices) to the GPU, as well as helper methods for buffer and bind group creation.
 
+use crate::game::maze::floors::{FloorPlan, Layer};
 use crate::game::maze::generator::Cell;
-use crate::math::coordinates::constants::get_floor_size;
+use crate::math::coordinates::{calculate_cell_size, constants::get_floor_size};
 use bytemuck::{Pod, Zeroable};
 use wgpu;
 use wgpu::util::DeviceExt;
@@ -12,6 +13,9 @@ use wgpu::util::DeviceExt;
 /// Scale factor for ceiling texture tiling
 pub const CEILING_TEXTURE_SCALE: f32 = 0.005;
 
+/// Gap between the top of a two-floor level's upper floor and its underside
+pub const UPPER_FLOOR_THICKNESS: f32 = 1.0;
+
 /// Uniforms for the main render pipeline.
 ///
 /// This struct stores a 4x4 matrix (typically Model-View-Projection) to be sent to the GPU as a uniform buffer.
@@ -28,8 +32,26 @@ pub struct Uniforms {
     pub matrix: [[f32; 4]; 4],
     /// Current time value for shader animations.
     pub time: f32,
-    /// Padding bytes for 16-byte alignment requirements.
-    pub _padding: [f32; 7], // Padding for 16-byte alignment
+    /// View distance at which fog begins.
+    pub fog_start: f32,
+    /// View distance at which fog reaches full strength.
+    pub fog_end: f32,
+    /// Maximum fog blend factor (0.0 disables fog).
+    pub fog_strength: f32,
+    /// Color that distant geometry fades toward (RGBA).
+    pub fog_color: [f32; 4],
+    /// How far walls breathe along their normal, in world units (0.0 keeps them still).
+    ///
+    /// Never more than [`MAX_WALL_BREATH`](crate::game::breathing::MAX_WALL_BREATH), the
+    /// accepted mismatch between the drawn walls and their collision faces.
+    pub wall_breath: f32,
+    /// 1.0 to move debug bounding boxes with the breathing walls, 0.0 to draw them still.
+    pub bounding_box_breath: f32,
+    /// Multiplier on the shaded color of everything but debug bounding boxes (1.0 leaves it
+    /// unchanged).
+    pub ambient: f32,
+    /// Padding to keep the struct 16-byte aligned
+    pub _padding: f32,
 }
 
 impl Default for Uniforms {
@@ -39,7 +61,8 @@ impl Default for Uniforms {
 }
 
 impl Uniforms {
-    /// Creates a new Uniforms instance with identity matrix and zero time.
+    /// Creates a new Uniforms instance with identity matrix, zero time, fog disabled, and
+    /// unchanged shading.
     ///
     /// # Returns
     /// A new Uniforms instance with default values
@@ -52,7 +75,14 @@ impl Uniforms {
                 [0.0, 0.0, 0.0, 1.0],
             ],
             time: 0.0,
-            _padding: [0.0; 7],
+            fog_start: 0.0,
+            fog_end: 1.0,
+            fog_strength: 0.0,
+            fog_color: [0.0, 0.0, 0.0, 1.0],
+            wall_breath: 0.0,
+            bounding_box_breath: 0.0,
+            ambient: 1.0,
+            _padding: 0.0,
         }
     }
 
@@ -126,6 +156,7 @@ impl Uniforms {
 /// - `color`: RGBA color (as 4 normalized u8 values).
 /// - `material`: Material type (0 = floor, 1 = wall, 3 = ceiling, 4 = exit).
 /// - `tex_coords`: Texture coordinates for texturing (used for ceiling).
+/// - `normal`: Axis the wall breathes along, zero for surfaces that stay still.
 ///
 #[repr(C)]
 #[derive(Copy, Clone, Debug, bytemuck::Pod, bytemuck::Zeroable)]
@@ -138,6 +169,8 @@ pub struct Vertex {
     pub material: u32, // 0 = floor, 1 = wall, 3 = ceiling, 4 = exit
     /// Texture coordinates for texturing (used for ceiling).
     pub tex_coords: [f32; 2],
+    /// Axis walls are pushed along when they breathe (zero for surfaces that stay still).
+    pub normal: [f32; 3],
 }
 
 impl Vertex {
@@ -176,6 +209,15 @@ impl Vertex {
                     shader_location: 3,
                     format: wgpu::VertexFormat::Float32x2,
                 },
+                // Normal (3 floats)
+                wgpu::VertexAttribute {
+                    offset: std::mem::size_of::<[f32; 3]>() as wgpu::BufferAddress
+                        + std::mem::size_of::<[u8; 4]>() as wgpu::BufferAddress
+                        + std::mem::size_of::<u32>() as wgpu::BufferAddress
+                        + std::mem::size_of::<[f32; 2]>() as wgpu::BufferAddress,
+                    shader_location: 4,
+                    format: wgpu::VertexFormat::Float32x3,
+                },
             ],
         }
     }
@@ -189,7 +231,7 @@ impl Vertex {
         exit_cell: Option<Cell>,
         is_test_mode: bool,
     ) -> (Vec<Vertex>, (f32, f32)) {
-        let floor_size = get_floor_size(is_test_mode); // Use test mode to determine floor size
+        let floor_size = get_floor_size((maze_grid[0].len(), maze_grid.len()), is_test_mode);
         let half_size = floor_size / 2.0;
 
         // Create base floor vertices
@@ -236,6 +278,7 @@ impl Vertex {
                 color,
                 material: 0,
                 tex_coords: [0.0, 0.0], // Floor doesn't use texture coordinates
+                normal: [0.0, 0.0, 0.0],
             });
         }
 
@@ -263,14 +306,13 @@ impl Vertex {
     pub fn create_wall_vertices(maze_grid: &[Vec<bool>], is_test_mode: bool) -> Vec<Vertex> {
         let mut vertices = Vec::new();
 
-        let floor_size = get_floor_size(is_test_mode); // Use test mode to determine floor size
         let maze_width = maze_grid[0].len();
         let maze_height = maze_grid.len();
+        let floor_size = get_floor_size((maze_width, maze_height), is_test_mode);
 
         // Calculate cell size to scale the maze to fit the floor
         let max_dimension = maze_width.max(maze_height) as f32;
         let cell_size = floor_size / max_dimension;
-        let internal_wall_height = cell_size;
         let outer_wall_height = cell_size * 2.0; // Make outer walls twice as tall
 
         // Calculate origin to center the maze
@@ -340,74 +382,14 @@ impl Vertex {
             }
         } else {
             // Normal mode: create all walls from the maze grid
-            for (z, row) in maze_grid.iter().enumerate() {
-                for (x, &is_wall) in row.iter().enumerate() {
-                    if is_wall {
-                        let wx = origin_x + x as f32 * cell_size;
-                        let wz = origin_z + z as f32 * cell_size;
-
-                        // Create both X-facing and Z-facing walls for each wall cell
-
-                        // Check if we need an X-facing wall (along Z axis)
-                        if z == 0 || !maze_grid[z - 1][x] {
-                            // This is an outer-facing wall if z == 0 (top edge)
-                            let is_outer_facing = z == 0;
-                            let wall_height = if is_outer_facing {
-                                outer_wall_height
-                            } else {
-                                internal_wall_height
-                            };
-                            vertices.extend(create_z_facing_wall(
-                                wx,
-                                0.0,
-                                wz,
-                                cell_size,
-                                wall_height,
-                            ));
-                        }
-
-                        // Check if we need a Z-facing wall (along X axis)
-                        if x == 0 || !maze_grid[z][x - 1] {
-                            // This is an outer-facing wall if x == 0 (left edge)
-                            let is_outer_facing = x == 0;
-                            let wall_height = if is_outer_facing {
-                                outer_wall_height
-                            } else {
-                                internal_wall_height
-                            };
-                            vertices.extend(create_x_facing_wall(
-                                wx,
-                                0.0,
-                                wz,
-                                cell_size,
-                                wall_height,
-                            ));
-                        }
-
-                        // Always create the right and bottom walls if we're at the edge
-                        if z == maze_height - 1 {
-                            // This is an outer-facing wall (bottom edge)
-                            vertices.extend(create_z_facing_wall(
-                                wx,
-                                0.0,
-                                wz + cell_size,
-                                cell_size,
-                                outer_wall_height,
-                            ));
-                        }
-                        if x == maze_width - 1 {
-                            // This is an outer-facing wall (right edge)
-                            vertices.extend(create_x_facing_wall(
-                                wx + cell_size,
-                                0.0,
-                                wz,
-                                cell_size,
-                                outer_wall_height,
-                            ));
-                        }
-                    }
-                }
-            }
+            push_maze_walls(
+                &mut vertices,
+                maze_grid,
+                cell_size,
+                (origin_x, origin_z),
+                0.0,
+                Some(outer_wall_height),
+            );
         }
 
         vertices
@@ -418,9 +400,8 @@ impl Vertex {
         center: (f32, f32),
         is_test_mode: bool,
     ) -> Vec<Vertex> {
-        let floor_size = get_floor_size(is_test_mode); // Use test mode to determine floor size
         // Use a patch size similar to a cell size
-        let patch_size = floor_size / 13.0; // 13 is the wall grid size for 6x6 maze
+        let patch_size = calculate_cell_size((13, 13), is_test_mode); // 13 is the wall grid size for 6x6 maze
         let (center_x, center_z) = center;
         let y = 1.0;
         let half = patch_size / 2.0;
@@ -438,18 +419,21 @@ impl Vertex {
                 color: green_color,
                 material: 4,
                 tex_coords: [0.0, 0.0], // Exit doesn't use texture coordinates
+                normal: [0.0, 0.0, 0.0],
             },
             Vertex {
                 position: corners[1],
                 color: green_color,
                 material: 4,
                 tex_coords: [0.0, 0.0],
+                normal: [0.0, 0.0, 0.0],
             },
             Vertex {
                 position: corners[2],
                 color: green_color,
                 material: 4,
                 tex_coords: [0.0, 0.0],
+                normal: [0.0, 0.0, 0.0],
             },
             // Second triangle: 0, 2, 3
             Vertex {
@@ -457,27 +441,165 @@ impl Vertex {
                 color: green_color,
                 material: 4,
                 tex_coords: [0.0, 0.0],
+                normal: [0.0, 0.0, 0.0],
             },
             Vertex {
                 position: corners[2],
                 color: green_color,
                 material: 4,
                 tex_coords: [0.0, 0.0],
+                normal: [0.0, 0.0, 0.0],
             },
             Vertex {
                 position: corners[3],
                 color: green_color,
                 material: 4,
                 tex_coords: [0.0, 0.0],
+                normal: [0.0, 0.0, 0.0],
             },
         ]
     }
 
+    /// Generates the ramps inside the stairwells of a two-floor level.
+    ///
+    /// Each ramp spans its stairwell's cell, climbing from the lower floor at its foot to the
+    /// upper floor at its top. The walls around the stairwell hide the space underneath.
+    ///
+    /// # Arguments
+    /// * `plan` - The level's floor plan
+    ///
+    /// # Returns
+    /// A vector of [`Vertex`] with one sloped quad per stairwell.
+    pub fn create_ramp_vertices(plan: &FloorPlan) -> Vec<Vertex> {
+        let (maze_width, maze_height) = plan.maze_dimensions();
+        let cell_size = plan.floor_height;
+        let origin_x = -(maze_width as f32 * cell_size) / 2.0;
+        let origin_z = -(maze_height as f32 * cell_size) / 2.0;
+
+        let mut vertices = Vec::new();
+        for stairwell in &plan.stairwells {
+            let cell = stairwell.grid_cell();
+            let x0 = origin_x + cell.col as f32 * cell_size;
+            let z0 = origin_z + cell.row as f32 * cell_size;
+            let corners = [
+                [x0, z0],
+                [x0 + cell_size, z0],
+                [x0 + cell_size, z0 + cell_size],
+                [x0, z0 + cell_size],
+            ]
+            .map(|[x, z]| {
+                let height = plan.ramp_progress(stairwell, [x, 0.0, z]) * plan.floor_height;
+                [x, height, z]
+            });
+            vertices.extend(create_quad(
+                corners,
+                [120, 80, 160, 255],
+                0,
+                [[0.0, 0.0]; 4],
+            ));
+        }
+        vertices
+    }
+
+    /// Generates the upper floor of a two-floor level.
+    ///
+    /// The floor is a slab one floor height up with a hole over each stairwell. Its underside
+    /// uses the ceiling texture, and the upper maze's internal walls stand on top; the lower
+    /// maze's outer walls already reach past it.
+    ///
+    /// # Arguments
+    /// * `plan` - The level's floor plan
+    /// * `exit_cell` - The exit's wall-grid cell on the upper floor, if there is one
+    ///
+    /// # Returns
+    /// A tuple containing a vector of [`Vertex`] and the (x, z) center of the exit.
+    pub fn create_upper_floor_vertices(
+        plan: &FloorPlan,
+        exit_cell: Option<Cell>,
+    ) -> (Vec<Vertex>, (f32, f32)) {
+        let maze_grid = plan.grid(Layer::Upper);
+        let (maze_width, maze_height) = plan.maze_dimensions();
+        let cell_size = plan.floor_height;
+        let origin_x = -(maze_width as f32 * cell_size) / 2.0;
+        let origin_z = -(maze_height as f32 * cell_size) / 2.0;
+        let floor_y = plan.base_height(Layer::Upper);
+        // Kept apart from the top so the two never z-fight
+        let underside_y = floor_y - UPPER_FLOOR_THICKNESS;
+        let tex = |x: f32, z: f32| {
+            [
+                (x - origin_x) * CEILING_TEXTURE_SCALE,
+                (z - origin_z) * CEILING_TEXTURE_SCALE,
+            ]
+        };
+
+        let mut vertices = Vec::new();
+        for row in 0..maze_height {
+            let z0 = origin_z + row as f32 * cell_size;
+            let z1 = z0 + cell_size;
+            let mut col = 0;
+            while col < maze_width {
+                // One strip per run of cells between stairwell holes
+                let start = col;
+                while col < maze_width && plan.stairwell_at(Cell::new(row, col)).is_none() {
+                    col += 1;
+                }
+                if col > start {
+                    let x0 = origin_x + start as f32 * cell_size;
+                    let x1 = origin_x + col as f32 * cell_size;
+                    vertices.extend(create_quad(
+                        [
+                            [x0, floor_y, z0],
+                            [x1, floor_y, z0],
+                            [x1, floor_y, z1],
+                            [x0, floor_y, z1],
+                        ],
+                        [120, 80, 160, 255],
+                        0,
+                        [[0.0, 0.0]; 4],
+                    ));
+                    vertices.extend(create_quad(
+                        [
+                            [x0, underside_y, z0],
+                            [x1, underside_y, z0],
+                            [x1, underside_y, z1],
+                            [x0, underside_y, z1],
+                        ],
+                        [233, 224, 217, 255],
+                        3,
+                        [tex(x0, z0), tex(x1, z0), tex(x1, z1), tex(x0, z1)],
+                    ));
+                }
+                col += 1;
+            }
+        }
+
+        push_maze_walls(
+            &mut vertices,
+            maze_grid,
+            cell_size,
+            (origin_x, origin_z),
+            floor_y,
+            None,
+        );
+
+        let mut exit_position = (0.0, 0.0);
+        if let Some(exit) = exit_cell {
+            let (mut patch, position) = create_exit_cell_floor_patch(maze_grid, exit);
+            for vertex in &mut patch {
+                vertex.position[1] += floor_y;
+            }
+            vertices.extend(patch);
+            exit_position = position;
+        }
+
+        (vertices, exit_position)
+    }
+
     /// Creates ceiling vertices for the entire maze area
     pub fn create_ceiling_vertices(maze_grid: &[Vec<bool>], is_test_mode: bool) -> Vec<Vertex> {
-        let floor_size = get_floor_size(is_test_mode);
         let maze_width = maze_grid[0].len();
         let maze_height = maze_grid.len();
+        let floor_size = get_floor_size((maze_width, maze_height), is_test_mode);
 
         // Calculate cell size and ceiling height
         let max_dimension = maze_width.max(maze_height) as f32;
@@ -507,12 +629,14 @@ impl Vertex {
                 color: ceiling_color,
                 material: 3, // Ceiling material type
                 tex_coords: [0.0, 0.0],
+                normal: [0.0, 0.0, 0.0],
             },
             Vertex {
                 position: [origin_x + world_width, ceiling_height, origin_z],
                 color: ceiling_color,
                 material: 3,
                 tex_coords: [tex_u_max, 0.0],
+                normal: [0.0, 0.0, 0.0],
             },
             Vertex {
                 position: [
@@ -523,6 +647,7 @@ impl Vertex {
                 color: ceiling_color,
                 material: 3,
                 tex_coords: [tex_u_max, tex_v_max],
+                normal: [0.0, 0.0, 0.0],
             },
             // Second triangle: bottom-left, top-right, top-left
             Vertex {
@@ -530,6 +655,7 @@ impl Vertex {
                 color: ceiling_color,
                 material: 3,
                 tex_coords: [0.0, 0.0],
+                normal: [0.0, 0.0, 0.0],
             },
             Vertex {
                 position: [
@@ -540,12 +666,14 @@ impl Vertex {
                 color: ceiling_color,
                 material: 3,
                 tex_coords: [tex_u_max, tex_v_max],
+                normal: [0.0, 0.0, 0.0],
             },
             Vertex {
                 position: [origin_x, ceiling_height, origin_z + world_height],
                 color: ceiling_color,
                 material: 3,
                 tex_coords: [0.0, tex_v_max],
+                normal: [0.0, 0.0, 0.0],
             },
         ];
 
@@ -564,42 +692,49 @@ impl Vertex {
 /// An array of 6 [`Vertex`] forming two triangles (a quad).
 pub fn create_z_facing_wall(x: f32, y: f32, z: f32, width: f32, height: f32) -> [Vertex; 6] {
     let color: [u8; 4] = [107, 55, 55, 255];
+    let normal = [0.0, 0.0, 1.0];
     [
         Vertex {
             position: [x, y, z],
             color,
             material: 1,
             tex_coords: [0.0, 0.0], // Walls don't use texture coordinates
+            normal,
         },
         Vertex {
             position: [x + width, y, z],
             color,
             material: 1,
             tex_coords: [0.0, 0.0],
+            normal,
         },
         Vertex {
             position: [x + width, y + height, z],
             color,
             material: 1,
             tex_coords: [0.0, 0.0],
+            normal,
         },
         Vertex {
             position: [x, y, z],
             color,
             material: 1,
             tex_coords: [0.0, 0.0],
+            normal,
         },
         Vertex {
             position: [x + width, y + height, z],
             color,
             material: 1,
             tex_coords: [0.0, 0.0],
+            normal,
         },
         Vertex {
             position: [x, y + height, z],
             color,
             material: 1,
             tex_coords: [0.0, 0.0],
+            normal,
         },
     ]
 }
@@ -615,53 +750,174 @@ pub fn create_z_facing_wall(x: f32, y: f32, z: f32, width: f32, height: f32) ->
 /// An array of 6 [`Vertex`] forming two triangles (a quad).
 pub fn create_x_facing_wall(x: f32, y: f32, z: f32, depth: f32, height: f32) -> [Vertex; 6] {
     let color: [u8; 4] = [107, 55, 55, 255];
+    let normal = [1.0, 0.0, 0.0];
     [
         Vertex {
             position: [x, y, z],
             color,
             material: 1,
             tex_coords: [0.0, 0.0], // Walls don't use texture coordinates
+            normal,
         },
         Vertex {
             position: [x, y, z + depth],
             color,
             material: 1,
             tex_coords: [0.0, 0.0],
+            normal,
         },
         Vertex {
             position: [x, y + height, z + depth],
             color,
             material: 1,
             tex_coords: [0.0, 0.0],
+            normal,
         },
         Vertex {
             position: [x, y, z],
             color,
             material: 1,
             tex_coords: [0.0, 0.0],
+            normal,
         },
         Vertex {
             position: [x, y + height, z + depth],
             color,
             material: 1,
             tex_coords: [0.0, 0.0],
+            normal,
         },
         Vertex {
             position: [x, y + height, z],
             color,
             material: 1,
             tex_coords: [0.0, 0.0],
+            normal,
         },
     ]
 }
 
+/// Adds the wall faces of a maze grid, one cell size tall, standing on `base_y`.
+///
+/// For each wall cell, creates the faces bordering open cells, plus the maze's outer faces.
+///
+/// # Arguments
+/// * `vertices` - Vertex list to append the faces to
+/// * `maze_grid` - 2D grid of booleans, where `true` indicates a wall
+/// * `cell_size` - Size of one grid cell in world units
+/// * `origin` - World (x, z) of the grid's top-left corner
+/// * `base_y` - Height the walls stand on
+/// * `outer_wall_height` - Height of the outer faces, or `None` to leave them out
+fn push_maze_walls(
+    vertices: &mut Vec<Vertex>,
+    maze_grid: &[Vec<bool>],
+    cell_size: f32,
+    origin: (f32, f32),
+    base_y: f32,
+    outer_wall_height: Option<f32>,
+) {
+    let (origin_x, origin_z) = origin;
+    let maze_width = maze_grid[0].len();
+    let maze_height = maze_grid.len();
+    let internal_wall_height = cell_size;
+
+    for (z, row) in maze_grid.iter().enumerate() {
+        for (x, &is_wall) in row.iter().enumerate() {
+            if !is_wall {
+                continue;
+            }
+            let wx = origin_x + x as f32 * cell_size;
+            let wz = origin_z + z as f32 * cell_size;
+
+            // Create both X-facing and Z-facing walls for each wall cell
+
+            // Check if we need an X-facing wall (along Z axis)
+            if z == 0 || !maze_grid[z - 1][x] {
+                // This is an outer-facing wall if z == 0 (top edge)
+                let wall_height = if z == 0 {
+                    outer_wall_height
+                } else {
+                    Some(internal_wall_height)
+                };
+                if let Some(wall_height) = wall_height {
+                    vertices.extend(create_z_facing_wall(wx, base_y, wz, cell_size, wall_height));
+                }
+            }
+
+            // Check if we need a Z-facing wall (along X axis)
+            if x == 0 || !maze_grid[z][x - 1] {
+                // This is an outer-facing wall if x == 0 (left edge)
+                let wall_height = if x == 0 {
+                    outer_wall_height
+                } else {
+                    Some(internal_wall_height)
+                };
+                if let Some(wall_height) = wall_height {
+                    vertices.extend(create_x_facing_wall(wx, base_y, wz, cell_size, wall_height));
+                }
+            }
+
+            // Always create the right and bottom walls if we're at the edge
+            if let Some(outer_wall_height) = outer_wall_height {
+                if z == maze_height - 1 {
+                    // This is an outer-facing wall (bottom edge)
+                    vertices.extend(create_z_facing_wall(
+                        wx,
+                        base_y,
+                        wz + cell_size,
+                        cell_size,
+                        outer_wall_height,
+                    ));
+                }
+                if x == maze_width - 1 {
+                    // This is an outer-facing wall (right edge)
+                    vertices.extend(create_x_facing_wall(
+                        wx + cell_size,
+                        base_y,
+                        wz,
+                        cell_size,
+                        outer_wall_height,
+                    ));
+                }
+            }
+        }
+    }
+}
+
+/// Creates a quad from four corners given in winding order.
+///
+/// # Returns
+/// An array of 6 [`Vertex`] forming two triangles (a quad).
+fn create_quad(
+    corners: [[f32; 3]; 4],
+    color: [u8; 4],
+    material: u32,
+    tex_coords: [[f32; 2]; 4],
+) -> [Vertex; 6] {
+    let vertex = |i: usize| Vertex {
+        position: corners[i],
+        color,
+        material,
+        tex_coords: tex_coords[i],
+        normal: [0.0, 0.0, 0.0],
+    };
+    [
+        vertex(0),
+        vertex(1),
+        vertex(2),
+        vertex(0),
+        vertex(2),
+        vertex(3),
+    ]
+}
+
 fn create_exit_cell_floor_patch(
     maze_grid: &[Vec<bool>],
     exit_cell: Cell,
 ) -> (Vec<Vertex>, (f32, f32)) {
-    let floor_size = get_floor_size(false); // Normal mode for exit cell patch
     let maze_width = maze_grid[0].len();
     let maze_height = maze_grid.len();
+    let floor_size = get_floor_size((maze_width, maze_height), false); // Normal mode for exit cell patch
 
     let max_dimension = maze_width.max(maze_height) as f32;
     let cell_size = floor_size / max_dimension;
@@ -688,18 +944,21 @@ fn create_exit_cell_floor_patch(
             color: green_color,
             material: 4,
             tex_coords: [0.0, 0.0], // Exit doesn't use texture coordinates
+            normal: [0.0, 0.0, 0.0],
         },
         Vertex {
             position: corners[1],
             color: green_color,
             material: 4,
             tex_coords: [0.0, 0.0],
+            normal: [0.0, 0.0, 0.0],
         },
         Vertex {
             position: corners[2],
             color: green_color,
             material: 4,
             tex_coords: [0.0, 0.0],
+            normal: [0.0, 0.0, 0.0],
         },
         // Second triangle: 0, 2, 3
         Vertex {
@@ -707,18 +966,21 @@ fn create_exit_cell_floor_patch(
             color: green_color,
             material: 4,
             tex_coords: [0.0, 0.0],
+            normal: [0.0, 0.0, 0.0],
         },
         Vertex {
             position: corners[2],
             color: green_color,
             material: 4,
             tex_coords: [0.0, 0.0],
+            normal: [0.0, 0.0, 0.0],
         },
         Vertex {
             position: corners[3],
             color: green_color,
             material: 4,
             tex_coords: [0.0, 0.0],
+            normal: [0.0, 0.0, 0.0],
         },
     ];
 
@@ -18,6 +18,7 @@
 //! - Rounded corners provide modern UI aesthetics
 //! - Alpha blending enables overlays and transparency effects
 
+use crate::renderer::color;
 use std::mem;
 use wgpu::{
     self, BlendState, BufferUsages, ColorTargetState, ColorWrites, Device, FragmentState,
@@ -151,7 +152,7 @@ impl Vertex {
 ///
 /// ## Examples
 ///
-/// ```rust
+/// ```rust,ignore
 /// // Create a menu button
 /// let button = Rectangle::new(100.0, 50.0, 200.0, 40.0, [0.2, 0.4, 0.8, 1.0])
 ///     .with_corner_radius(8.0);
@@ -174,7 +175,8 @@ pub struct Rectangle {
     /// Height of the rectangle in screen pixels  
     pub height: f32,
 
-    /// RGBA color values (0.0 to 1.0) for the rectangle
+    /// sRGB-encoded RGBA color values (0.0 to 1.0) for the rectangle
+    /// Converted for the render target by the renderer, so design hex values can be used as-is
     /// Alpha channel enables transparency for overlays and hover effects
     pub color: [f32; 4],
 
@@ -193,7 +195,7 @@ impl Rectangle {
     ///
     /// - `x`, `y`: Top-left corner position in screen pixels
     /// - `width`, `height`: Dimensions in screen pixels
-    /// - `color`: RGBA color array with values from 0.0 to 1.0
+    /// - `color`: sRGB-encoded RGBA color array with values from 0.0 to 1.0
     ///
     /// ## Returns
     ///
@@ -225,7 +227,7 @@ impl Rectangle {
     ///
     /// ## Usage
     ///
-    /// ```rust
+    /// ```rust,ignore
     /// let rounded_button = Rectangle::new(x, y, w, h, color)
     ///     .with_corner_radius(8.0);  // 8-pixel corner radius
     /// ```
@@ -277,6 +279,10 @@ pub struct RectangleRenderer {
     /// Current window height in pixels  
     /// Used for coordinate transformation from screen space to NDC
     window_height: f32,
+
+    /// Format of the render target
+    /// Decides how rectangle colors are converted before upload
+    surface_format: wgpu::TextureFormat,
 }
 
 impl RectangleRenderer {
@@ -367,6 +373,7 @@ impl RectangleRenderer {
             // Default window size - should be updated via resize()
             window_width: 1360.0,
             window_height: 768.0,
+            surface_format,
         }
     }
 
@@ -381,7 +388,7 @@ impl RectangleRenderer {
     ///
     /// ## Usage Examples
     ///
-    /// ```rust
+    /// ```rust,ignore
     /// // Add a menu button
     /// renderer.add_rectangle(
     ///     Rectangle::new(100.0, 50.0, 200.0, 40.0, [0.2, 0.4, 0.8, 1.0])
@@ -406,7 +413,7 @@ impl RectangleRenderer {
     ///
     /// ## Usage
     ///
-    /// ```rust
+    /// ```rust,ignore
     /// // Start of frame
     /// renderer.clear_rectangles();
     ///
@@ -448,7 +455,8 @@ impl RectangleRenderer {
     /// This method performs batch rendering of all rectangles that have
     /// been added since the last clear. It:
     ///
-    /// 1. Converts screen coordinates to normalized device coordinates
+    /// 1. Converts screen coordinates to normalized device coordinates and
+    ///    colors to the render target's color space
     /// 2. Generates vertices for each rectangle (4 vertices per rectangle)
     /// 3. Creates indices for triangle rendering (2 triangles per rectangle)
     /// 4. Uploads vertex and index data to the GPU
@@ -494,13 +502,16 @@ impl RectangleRenderer {
             let width = (rectangle.width / self.window_width) * 2.0;
             let height = -(rectangle.height / self.window_height) * 2.0; // Negative due to Y-flip
 
+            // Convert the sRGB design color for the render target once per rectangle
+            let target_color = color::srgb_for_target(rectangle.color, self.surface_format);
+
             // Create the four vertices for this rectangle
             // Each vertex contains position, color, UV coords, size, and corner radius
             let vertices = [
                 // Top-left vertex
                 Vertex {
                     position: [x, y],
-                    color: rectangle.color,
+                    color: target_color,
                     uv: [0.0, 0.0], // UV coordinates for fragment shader distance calculations
                     rect_size: [rectangle.width, rectangle.height],
                     corner_radius: rectangle.corner_radius,
@@ -509,7 +520,7 @@ impl RectangleRenderer {
                 // Top-right vertex
                 Vertex {
                     position: [x + width, y],
-                    color: rectangle.color,
+                    color: target_color,
                     uv: [rectangle.width, 0.0],
                     rect_size: [rectangle.width, rectangle.height],
                     corner_radius: rectangle.corner_radius,
@@ -518,7 +529,7 @@ impl RectangleRenderer {
                 // Bottom-right vertex
                 Vertex {
                     position: [x + width, y + height],
-                    color: rectangle.color,
+                    color: target_color,
                     uv: [rectangle.width, rectangle.height],
                     rect_size: [rectangle.width, rectangle.height],
                     corner_radius: rectangle.corner_radius,
@@ -527,7 +538,7 @@ impl RectangleRenderer {
                 // Bottom-left vertex
                 Vertex {
                     position: [x, y + height],
-                    color: rectangle.color,
+                    color: target_color,
                     uv: [0.0, rectangle.height],
                     rect_size: [rectangle.width, rectangle.height],
                     corner_radius: rectangle.corner_radius,
@@ -575,3 +586,124 @@ impl RectangleRenderer {
         render_pass.draw_indexed(0..all_indices.len() as u32, 0, 0..1);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::renderer::golden::headless_device;
+
+    /// Side length of the offscreen target; 64 RGBA8 pixels make a 256-byte row.
+    const SIZE: u32 = 64;
+
+    /// Runs one render pass over an offscreen sRGB target and returns its center pixel.
+    fn render_center_pixel(
+        device: &Device,
+        queue: &wgpu::Queue,
+        clear: wgpu::Color,
+        rectangles: Option<&mut RectangleRenderer>,
+    ) -> [u8; 4] {
+        let texture = device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("Color Test Target"),
+            size: wgpu::Extent3d {
+                width: SIZE,
+                height: SIZE,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: wgpu::TextureFormat::Rgba8UnormSrgb,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::COPY_SRC,
+            view_formats: &[],
+        });
+        let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+        let readback = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Color Test Readback"),
+            size: (SIZE * SIZE * 4) as u64,
+            usage: BufferUsages::COPY_DST | BufferUsages::MAP_READ,
+            mapped_at_creation: false,
+        });
+
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor::default());
+        {
+            let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some("Color Test Pass"),
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view: &view,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(clear),
+                        store: wgpu::StoreOp::Store,
+                    },
+                })],
+                depth_stencil_attachment: None,
+                occlusion_query_set: None,
+                timestamp_writes: None,
+            });
+            if let Some(renderer) = rectangles {
+                renderer.render(device, &mut pass);
+            }
+        }
+        encoder.copy_texture_to_buffer(
+            texture.as_image_copy(),
+            wgpu::TexelCopyBufferInfo {
+                buffer: &readback,
+                layout: wgpu::TexelCopyBufferLayout {
+                    offset: 0,
+                    bytes_per_row: Some(SIZE * 4),
+                    rows_per_image: Some(SIZE),
+                },
+            },
+            texture.size(),
+        );
+        queue.submit(Some(encoder.finish()));
+
+        let slice = readback.slice(..);
+        slice.map_async(wgpu::MapMode::Read, |_| {});
+        device.poll(wgpu::Maintain::Wait);
+        let data = slice.get_mapped_range();
+        let center = ((SIZE / 2 * SIZE + SIZE / 2) * 4) as usize;
+        [
+            data[center],
+            data[center + 1],
+            data[center + 2],
+            data[center + 3],
+        ]
+    }
+
+    #[test]
+    fn test_rectangle_matches_clear_of_same_design_color() {
+        let Some((device, queue)) = headless_device() else {
+            eprintln!("No GPU adapter available; skipping color readback test");
+            return;
+        };
+        let format = wgpu::TextureFormat::Rgba8UnormSrgb;
+        let design = [59, 130, 246, 255]; // blue-500
+
+        let cleared = render_center_pixel(
+            &device,
+            &queue,
+            color::clear_color(color::srgb_for_target(color::srgba_from_u8(design), format)),
+            None,
+        );
+
+        let mut renderer = RectangleRenderer::new(&device, format);
+        renderer.resize(SIZE as f32, SIZE as f32);
+        renderer.add_rectangle(Rectangle::new(
+            0.0,
+            0.0,
+            SIZE as f32,
+            SIZE as f32,
+            color::srgba_from_u8(design),
+        ));
+        let drawn = render_center_pixel(&device, &queue, wgpu::Color::BLACK, Some(&mut renderer));
+
+        for channel in 0..4 {
+            assert!(
+                cleared[channel].abs_diff(design[channel]) <= 1,
+                "{cleared:?}"
+            );
+            assert!(drawn[channel].abs_diff(cleared[channel]) <= 1, "{drawn:?}");
+        }
+    }
+}
@@ -0,0 +1,427 @@
+//! Level-end Replay Module
+//!
+//! This module draws a small picture-in-picture replay of the player's path in the top-right
+//! corner while the level summary and the upgrade menu are up. The path comes from the level's
+//! [`RunRecording`](crate::game::replay::RunRecording) and plays at
+//! [`PLAYBACK_SPEED`](crate::game::replay::PLAYBACK_SPEED) times real time, looping, from a
+//! camera looking straight down from above the recorded eye; a dot in the middle marks where
+//! the player was. Hovering the picture with the cursor pauses it.
+//!
+//! The maze is drawn by a [`MazeView`], which owns a second [`GameRenderer`] with its own
+//! uniform buffers, so drawing the replay never overwrites the uniforms of the main scene
+//! drawn earlier in the frame. The view shares the main renderer's maze vertex buffer instead
+//! of building the geometry again. The result is drawn into an offscreen texture of about
+//! [`PIP_BASE_WIDTH`] x [`PIP_BASE_HEIGHT`] pixels, scaled with the window and recreated when
+//! it is resized, then composited over the UI as a rounded rectangle.
+//!
+//! The replay is a luxury: it is skipped while the smoothed frame time is above
+//! [`MAX_FRAME_TIME`], and for good if its texture can't be allocated or would push texture
+//! memory over budget.
+
+use crate::game::GameState;
+use crate::game::replay::{ReplayPlayback, replay_view_matrix};
+use crate::renderer::color;
+use crate::renderer::game_renderer::GameRenderer;
+use crate::renderer::gpu_memory::{
+    GpuMemoryTracker, TextureCategory, TrackedTexture, create_tracked_texture, texture_size_bytes,
+};
+use crate::renderer::maze_view::MazeView;
+use crate::renderer::pipeline_builder::{
+    BindGroupLayoutBuilder, PipelineBuilder, create_uniform_buffer,
+};
+use crate::renderer::tuning::RenderTuning;
+use wgpu;
+
+/// Width of the replay at 1080p, in pixels.
+pub const PIP_BASE_WIDTH: f32 = 480.0;
+
+/// Height of the replay at 1080p, in pixels.
+pub const PIP_BASE_HEIGHT: f32 = 270.0;
+
+/// Gap between the replay and the window edges at 1080p, in pixels.
+const PIP_MARGIN: f32 = 24.0;
+
+/// Corner radius of the replay at 1080p, in pixels.
+const PIP_CORNER_RADIUS: f32 = 14.0;
+
+/// Radius of the dot marking the player at 1080p, in pixels.
+const MARKER_RADIUS: f32 = 5.0;
+
+/// Linear RGBA color of the dot marking the player.
+const MARKER_COLOR: [f32; 4] = [1.0, 0.85, 0.2, 1.0];
+
+/// Smoothed frame time in seconds above which the replay isn't drawn.
+pub const MAX_FRAME_TIME: f32 = 1.0 / 30.0;
+
+/// Weight of the newest frame in the smoothed frame time.
+const FRAME_TIME_SMOOTHING: f32 = 0.1;
+
+/// Uniform data passed to the compositing shader.
+///
+/// # Memory Layout
+/// 48 bytes: the rectangle, the screen size, two radii, and the marker color.
+#[repr(C)]
+#[derive(Debug, Copy, Clone, bytemuck::Pod, bytemuck::Zeroable)]
+struct PipUniforms {
+    /// Left, top, width, and height of the replay in pixels
+    rect: [f32; 4],
+    /// Surface width and height in pixels
+    screen_size: [f32; 2],
+    /// Corner radius in pixels
+    corner_radius: f32,
+    /// Radius of the player marker in pixels
+    marker_radius: f32,
+    /// Color of the player marker, in the target's encoding
+    marker_color: [f32; 4],
+}
+
+/// The offscreen texture the replay is drawn into.
+struct PipTarget {
+    /// The texture; kept alive for the view and the bind group
+    texture: TrackedTexture,
+    /// View the maze is drawn into
+    view: wgpu::TextureView,
+    /// Bind group of the view and its sampler, for compositing
+    bind_group: wgpu::BindGroup,
+}
+
+/// Draws the level-end replay of the player's path.
+pub struct ReplayPip {
+    /// Draws the maze from the replay camera, with its own uniforms
+    maze_view: MazeView,
+    /// Pipeline compositing the replay over the UI
+    pipeline: wgpu::RenderPipeline,
+    /// Buffer of the compositing uniforms
+    uniform_buffer: wgpu::Buffer,
+    /// Bind group of the compositing uniforms
+    uniform_bind_group: wgpu::BindGroup,
+    /// Layout of the texture bind group, for when the target is recreated
+    texture_bind_group_layout: wgpu::BindGroupLayout,
+    /// Sampler the replay texture is read with
+    sampler: wgpu::Sampler,
+    /// Format of the surface, which the replay texture matches
+    format: wgpu::TextureFormat,
+    /// Tracker the replay texture is counted in
+    gpu_memory: GpuMemoryTracker,
+    /// The offscreen texture, created when first drawn
+    target: Option<PipTarget>,
+    /// Whether the texture couldn't be allocated, so the replay is skipped
+    allocation_failed: bool,
+    /// Left, top, width, and height of the replay in pixels
+    rect: [f32; 4],
+    /// Surface width and height in pixels
+    screen_size: [f32; 2],
+    /// Scale of the replay relative to 1080p
+    scale: f32,
+    /// Where playback is in the recording
+    playback: ReplayPlayback,
+    /// Whether the replay was shown last frame, so a new showing starts from the beginning
+    showing: bool,
+    /// Smoothed seconds per frame
+    frame_time: f32,
+    /// Animation time of the main scene, so the replay's walls breathe in step
+    time: f32,
+}
+
+impl ReplayPip {
+    /// Creates the replay renderer; its texture is created when it is first drawn.
+    ///
+    /// # Arguments
+    /// * `device` - WebGPU device for creating GPU resources
+    /// * `queue` - WebGPU queue for uploading the replay view's textures
+    /// * `surface_config` - Surface configuration containing the format and size
+    /// * `gpu_memory` - Tracker the replay texture is counted in
+    pub fn new(
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        surface_config: &wgpu::SurfaceConfiguration,
+        gpu_memory: &GpuMemoryTracker,
+    ) -> Self {
+        let format = surface_config.format;
+        let uniforms = PipUniforms {
+            rect: [0.0; 4],
+            screen_size: [1.0; 2],
+            corner_radius: 0.0,
+            marker_radius: 0.0,
+            marker_color: MARKER_COLOR,
+        };
+        let uniform_buffer = create_uniform_buffer(device, &uniforms, "Replay Uniform Buffer");
+
+        let uniform_bind_group_layout = BindGroupLayoutBuilder::new(device)
+            .with_label("Replay Uniform Bind Group Layout")
+            .with_uniform_buffer(0, wgpu::ShaderStages::VERTEX | wgpu::ShaderStages::FRAGMENT)
+            .build();
+        let texture_bind_group_layout = BindGroupLayoutBuilder::new(device)
+            .with_label("Replay Texture Bind Group Layout")
+            .with_texture(0, wgpu::ShaderStages::FRAGMENT)
+            .with_sampler(1, wgpu::ShaderStages::FRAGMENT)
+            .build();
+
+        let uniform_bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            layout: &uniform_bind_group_layout,
+            entries: &[wgpu::BindGroupEntry {
+                binding: 0,
+                resource: uniform_buffer.as_entire_binding(),
+            }],
+            label: Some("Replay Uniform Bind Group"),
+        });
+
+        let pipeline = PipelineBuilder::new(device, format)
+            .with_label("Replay Pipeline")
+            .with_shader(include_str!("shaders/replay_pip.wgsl"))
+            .with_bind_group_layout(&uniform_bind_group_layout)
+            .with_bind_group_layout(&texture_bind_group_layout)
+            .with_alpha_blending()
+            .with_no_culling()
+            .build();
+
+        let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+            label: Some("Replay Sampler"),
+            mag_filter: wgpu::FilterMode::Linear,
+            min_filter: wgpu::FilterMode::Linear,
+            ..Default::default()
+        });
+
+        let mut pip = Self {
+            maze_view: MazeView::new(device, queue, format, 1, 1),
+            pipeline,
+            uniform_buffer,
+            uniform_bind_group,
+            texture_bind_group_layout,
+            sampler,
+            format,
+            gpu_memory: gpu_memory.clone(),
+            target: None,
+            allocation_failed: false,
+            rect: [0.0; 4],
+            screen_size: [1.0; 2],
+            scale: 1.0,
+            playback: ReplayPlayback::default(),
+            showing: false,
+            frame_time: 0.0,
+            time: 0.0,
+        };
+        pip.resize(surface_config.width, surface_config.height);
+        pip
+    }
+
+    /// Lays the replay out for a new surface size; the texture is recreated at the new size
+    /// the next time it is drawn.
+    ///
+    /// # Arguments
+    /// * `width` - New surface width in pixels
+    /// * `height` - New surface height in pixels
+    pub fn resize(&mut self, width: u32, height: u32) {
+        self.screen_size = [width.max(1) as f32, height.max(1) as f32];
+        self.scale = (self.screen_size[1] / 1080.0).clamp(0.5, 2.0);
+        let size = [PIP_BASE_WIDTH * self.scale, PIP_BASE_HEIGHT * self.scale];
+        let margin = PIP_MARGIN * self.scale;
+        self.rect = [
+            self.screen_size[0] - margin - size[0],
+            margin,
+            size[0],
+            size[1],
+        ];
+
+        let texture_size = self.texture_size();
+        let stale = self.target.as_ref().is_some_and(|target| {
+            (target.texture.width(), target.texture.height()) != texture_size
+        });
+        if stale {
+            self.target = None;
+        }
+        // A smaller window may leave room for it
+        self.allocation_failed = false;
+    }
+
+    /// Returns the size of the replay texture in pixels.
+    fn texture_size(&self) -> (u32, u32) {
+        (
+            (self.rect[2].round() as u32).max(1),
+            (self.rect[3].round() as u32).max(1),
+        )
+    }
+
+    /// Returns whether a point on the surface is over the replay.
+    ///
+    /// # Arguments
+    /// * `(x, y)` - The point in pixels
+    pub fn contains(&self, (x, y): (f32, f32)) -> bool {
+        let [left, top, width, height] = self.rect;
+        x >= left && x <= left + width && y >= top && y <= top + height
+    }
+
+    /// Stops showing the replay, so it starts from the beginning when shown again.
+    pub fn hide(&mut self) {
+        self.showing = false;
+    }
+
+    /// Advances playback by one frame, restarting it if the replay wasn't shown last frame.
+    ///
+    /// # Arguments
+    /// * `delta_time` - Real seconds since the previous frame
+    /// * `pointer` - The cursor position, if the cursor is free; hovering the replay pauses it
+    /// * `duration` - Seconds of play the recording covers
+    pub fn update(&mut self, delta_time: f32, pointer: Option<(f32, f32)>, duration: f32) {
+        if !self.showing {
+            self.playback.restart();
+            self.frame_time = delta_time;
+            self.showing = true;
+        }
+        self.frame_time += (delta_time - self.frame_time) * FRAME_TIME_SMOOTHING;
+        self.playback
+            .set_paused(pointer.is_some_and(|point| self.contains(point)));
+        self.playback.advance(delta_time, duration);
+    }
+
+    /// Creates the offscreen texture if there is none, unless allocation already failed.
+    fn ensure_target(&mut self, device: &wgpu::Device) {
+        if self.target.is_none() && !self.allocation_failed {
+            self.target = self.create_target(device);
+            self.allocation_failed = self.target.is_none();
+        }
+    }
+
+    /// Allocates the offscreen texture at the current size.
+    ///
+    /// # Returns
+    /// The texture, or `None` if it would go over the texture memory budget or the device is
+    /// out of memory
+    fn create_target(&self, device: &wgpu::Device) -> Option<PipTarget> {
+        let (width, height) = self.texture_size();
+        let desc = wgpu::TextureDescriptor {
+            label: Some("Replay Texture"),
+            size: wgpu::Extent3d {
+                width,
+                height,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: self.format,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::TEXTURE_BINDING,
+            view_formats: &[],
+        };
+        if self.gpu_memory.total() + texture_size_bytes(&desc) > self.gpu_memory.budget() {
+            eprintln!("Skipping the level replay: its texture would go over the memory budget");
+            return None;
+        }
+
+        device.push_error_scope(wgpu::ErrorFilter::OutOfMemory);
+        let texture = create_tracked_texture(
+            device,
+            &self.gpu_memory,
+            TextureCategory::SceneTargets,
+            &desc,
+        );
+        if let Some(e) = pollster::block_on(device.pop_error_scope()) {
+            eprintln!("Skipping the level replay: {}", e);
+            return None;
+        }
+
+        let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            layout: &self.texture_bind_group_layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: wgpu::BindingResource::TextureView(&view),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: wgpu::BindingResource::Sampler(&self.sampler),
+                },
+            ],
+            label: Some("Replay Texture Bind Group"),
+        });
+        Some(PipTarget {
+            texture,
+            view,
+            bind_group,
+        })
+    }
+
+    /// Matches the replay to the main scene: its maze, fog, and animation time.
+    ///
+    /// # Arguments
+    /// * `renderer` - The main renderer, whose maze the replay shares
+    /// * `tuning` - Background color and fog of the main scene
+    /// * `reduce_effects` - Whether the player has asked for reduced visual effects
+    pub fn sync_scene(
+        &mut self,
+        renderer: &GameRenderer,
+        tuning: RenderTuning,
+        reduce_effects: bool,
+    ) {
+        self.maze_view.share_maze(renderer);
+        self.maze_view.tuning = tuning;
+        self.maze_view.reduce_effects = reduce_effects;
+        self.time = renderer.time;
+    }
+
+    /// Draws the replay's current frame and composites it over the surface.
+    ///
+    /// Does nothing while the frame time is over budget, when nothing was recorded, or when
+    /// the texture can't be allocated.
+    ///
+    /// # Arguments
+    /// * `device` - WebGPU device for creating the texture
+    /// * `queue` - WebGPU queue for uploading uniforms
+    /// * `encoder` - Command encoder to record the passes into
+    /// * `surface_view` - The surface to composite the replay over
+    /// * `game_state` - The game state holding the recording and the finished level
+    pub fn render(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        encoder: &mut wgpu::CommandEncoder,
+        surface_view: &wgpu::TextureView,
+        game_state: &GameState,
+    ) {
+        if self.frame_time > MAX_FRAME_TIME {
+            return;
+        }
+        let Some(position) = game_state.run_recording.position_at(self.playback.time()) else {
+            return;
+        };
+        self.ensure_target(device);
+        let Some(target) = self.target.as_ref() else {
+            return;
+        };
+
+        let (width, height) = self.texture_size();
+        self.maze_view.resize(width, height);
+        self.maze_view.camera = Some(replay_view_matrix(position));
+        self.maze_view
+            .render(device, queue, encoder, &target.view, game_state, self.time);
+
+        let uniforms = PipUniforms {
+            rect: self.rect,
+            screen_size: self.screen_size,
+            corner_radius: PIP_CORNER_RADIUS * self.scale,
+            marker_radius: MARKER_RADIUS * self.scale,
+            marker_color: color::linear_for_target(MARKER_COLOR, self.format),
+        };
+        queue.write_buffer(&self.uniform_buffer, 0, bytemuck::bytes_of(&uniforms));
+
+        let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some("Replay Composite Pass"),
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view: surface_view,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Load,
+                    store: wgpu::StoreOp::Store,
+                },
+            })],
+            depth_stencil_attachment: None,
+            occlusion_query_set: None,
+            timestamp_writes: None,
+        });
+        pass.set_pipeline(&self.pipeline);
+        pass.set_bind_group(0, &self.uniform_bind_group, &[]);
+        pass.set_bind_group(1, &target.bind_group, &[]);
+        pass.draw(0..6, 0..1);
+    }
+}
@@ -0,0 +1,323 @@
+//! Screen regions for the HUD and the 3D view on unusual aspect ratios.
+//!
+//! On very wide screens HUD elements anchored to the window edges end up far apart, and the
+//! 3D view's horizontal field of view grows with the aspect ratio because the projection holds
+//! the vertical field of view constant. [`SafeArea`] describes a centered region of the window
+//! whose aspect ratio is capped:
+//!
+//! - The HUD is always laid out inside [`SafeArea::centered`] with the user's maximum HUD
+//!   aspect ratio (21:9 by default), so on a 32:9 monitor the timer, score, stamina bar, and
+//!   compass stay within a 21:9 region in the middle of the screen.
+//! - The 3D view either fills the window ([`ViewFit::FullWidth`]) or is pillarboxed to the same
+//!   region and letterboxed below [`MIN_VIEW_ASPECT`] ([`ViewFit::Boxed`]), with black bars
+//!   filling the rest.
+//!
+//! # Usage
+//!
+//! ```rust,ignore
+//! let hud = SafeArea::centered(width as f32, height as f32, settings.hud_max_aspect);
+//! let score_x = hud.x + padding;
+//!
+//! let view = SafeArea::for_view(width as f32, height as f32, settings.hud_max_aspect, fit);
+//! pass.set_viewport(view.x, view.y, view.width, view.height, 0.0, 1.0);
+//! ```
+//!
+//! Regions are never smaller than one pixel in either direction, so a window dragged down to
+//! a sliver still produces a valid viewport. Scissor rectangles derived from window sizes go
+//! through [`clamp_scissor`] before they reach wgpu.
+
+use serde::{Deserialize, Serialize};
+
+/// Default widest aspect ratio the HUD is laid out in (21:9).
+pub const DEFAULT_HUD_MAX_ASPECT: f32 = 21.0 / 9.0;
+
+/// Narrowest aspect ratio a boxed 3D view keeps before it is letterboxed (4:3).
+pub const MIN_VIEW_ASPECT: f32 = 4.0 / 3.0;
+
+/// Narrowest maximum HUD aspect ratio a setting may ask for (4:3).
+///
+/// Keeps a bad settings file from squeezing the HUD into a sliver.
+pub const MIN_HUD_MAX_ASPECT: f32 = 4.0 / 3.0;
+
+/// How the 3D view fits windows that are wider or narrower than the safe area.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ViewFit {
+    /// The 3D view fills the whole window, however wide it is
+    #[default]
+    FullWidth,
+    /// The 3D view is pillarboxed to the HUD's maximum aspect ratio and letterboxed below
+    /// [`MIN_VIEW_ASPECT`], with black bars around it
+    Boxed,
+}
+
+/// An axis-aligned region of the window in pixels, measured from the top-left corner.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SafeArea {
+    /// Left edge in pixels
+    pub x: f32,
+    /// Top edge in pixels
+    pub y: f32,
+    /// Width in pixels
+    pub width: f32,
+    /// Height in pixels
+    pub height: f32,
+}
+
+impl SafeArea {
+    /// Returns the whole window as a region.
+    ///
+    /// # Arguments
+    /// * `width` - Window width in pixels, treated as at least one pixel
+    /// * `height` - Window height in pixels, treated as at least one pixel
+    pub fn full(width: f32, height: f32) -> Self {
+        Self {
+            x: 0.0,
+            y: 0.0,
+            width: width.max(1.0),
+            height: height.max(1.0),
+        }
+    }
+
+    /// Returns the full-height region centered in the window whose aspect ratio is at most
+    /// `max_aspect`.
+    ///
+    /// # Arguments
+    /// * `width` - Window width in pixels
+    /// * `height` - Window height in pixels
+    /// * `max_aspect` - Widest allowed width / height; clamped to at least
+    ///   [`MIN_HUD_MAX_ASPECT`]
+    ///
+    /// # Returns
+    /// The whole window if it is not wider than `max_aspect`, otherwise a centered pillarbox
+    pub fn centered(width: f32, height: f32, max_aspect: f32) -> Self {
+        let max_width = height * max_aspect.max(MIN_HUD_MAX_ASPECT);
+        if width <= max_width {
+            return Self::full(width, height);
+        }
+        Self {
+            x: ((width - max_width) / 2.0).floor(),
+            y: 0.0,
+            width: max_width.floor().max(1.0),
+            height: height.max(1.0),
+        }
+    }
+
+    /// Returns the region the 3D view is drawn in.
+    ///
+    /// # Arguments
+    /// * `width` - Window width in pixels
+    /// * `height` - Window height in pixels
+    /// * `max_aspect` - Widest aspect ratio of a boxed view
+    /// * `fit` - Whether the view fills the window or is boxed
+    pub fn for_view(width: f32, height: f32, max_aspect: f32, fit: ViewFit) -> Self {
+        match fit {
+            ViewFit::FullWidth => Self::full(width, height),
+            ViewFit::Boxed => {
+                let area = Self::centered(width, height, max_aspect);
+                let min_height = area.width / MIN_VIEW_ASPECT;
+                if area.height <= min_height {
+                    return area;
+                }
+                Self {
+                    y: ((area.height - min_height) / 2.0).floor(),
+                    height: min_height.floor().max(1.0),
+                    ..area
+                }
+            }
+        }
+    }
+
+    /// Returns the region's width divided by its height.
+    pub fn aspect(&self) -> f32 {
+        if self.height > 0.0 {
+            self.width / self.height
+        } else {
+            1.0
+        }
+    }
+
+    /// Returns the x coordinate of the region's right edge.
+    pub fn right(&self) -> f32 {
+        self.x + self.width
+    }
+
+    /// Returns the y coordinate of the region's bottom edge.
+    pub fn bottom(&self) -> f32 {
+        self.y + self.height
+    }
+
+    /// Returns the x coordinate of the region's horizontal center.
+    pub fn center_x(&self) -> f32 {
+        self.x + self.width / 2.0
+    }
+
+    /// Checks whether a rectangle lies entirely inside the region.
+    ///
+    /// # Arguments
+    /// * `x`, `y` - Top-left corner of the rectangle in pixels
+    /// * `width`, `height` - Size of the rectangle in pixels
+    pub fn contains(&self, x: f32, y: f32, width: f32, height: f32) -> bool {
+        x >= self.x && y >= self.y && x + width <= self.right() && y + height <= self.bottom()
+    }
+
+    /// Returns the parts of the window outside the region as scissor rectangles.
+    ///
+    /// # Arguments
+    /// * `width` - Window width in pixels
+    /// * `height` - Window height in pixels
+    ///
+    /// # Returns
+    /// Up to four `(x, y, width, height)` rectangles; empty when the region fills the window
+    pub fn bars(&self, width: u32, height: u32) -> Vec<(u32, u32, u32, u32)> {
+        let left = (self.x.max(0.0) as u32).min(width);
+        let top = (self.y.max(0.0) as u32).min(height);
+        let right = (self.right().max(0.0) as u32).clamp(left, width);
+        let bottom = (self.bottom().max(0.0) as u32).clamp(top, height);
+
+        [
+            (0, 0, left, height),
+            (right, 0, width - right, height),
+            (left, 0, right - left, top),
+            (left, bottom, right - left, height - bottom),
+        ]
+        .into_iter()
+        .filter(|&(_, _, w, h)| w > 0 && h > 0)
+        .collect()
+    }
+}
+
+/// Returns the height of the thin bars drawn along the top of the screen.
+///
+/// The loading and stamina bars are 1.25% of the height they span, and never thinner
+/// than one pixel so they stay visible on very short windows.
+///
+/// # Arguments
+/// * `height` - Height of the area the bar spans in pixels
+pub fn top_bar_height(height: f32) -> u32 {
+    ((height * 0.0125).ceil() as u32).max(1)
+}
+
+/// Clamps a scissor rectangle to the surface it is applied to.
+///
+/// wgpu rejects scissor rectangles that reach past the render target, which rounding in
+/// size-derived layout math can produce on very small windows.
+///
+/// # Arguments
+/// * `x`, `y` - Top-left corner of the rectangle in pixels
+/// * `width`, `height` - Size of the rectangle in pixels
+/// * `surface_width`, `surface_height` - Size of the render target in pixels
+///
+/// # Returns
+/// The part of the rectangle inside the surface, or `None` if none of it is
+pub fn clamp_scissor(
+    x: u32,
+    y: u32,
+    width: u32,
+    height: u32,
+    surface_width: u32,
+    surface_height: u32,
+) -> Option<(u32, u32, u32, u32)> {
+    let left = x.min(surface_width);
+    let top = y.min(surface_height);
+    let right = x.saturating_add(width).min(surface_width);
+    let bottom = y.saturating_add(height).min(surface_height);
+    if right <= left || bottom <= top {
+        return None;
+    }
+    Some((left, top, right - left, bottom - top))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hud_stays_within_the_safe_area() {
+        // 32:9 is pillarboxed to 21:9, 21:9 and narrower use the whole window
+        let super_wide = SafeArea::centered(3840.0, 1080.0, DEFAULT_HUD_MAX_ASPECT);
+        assert!((super_wide.aspect() - DEFAULT_HUD_MAX_ASPECT).abs() < 0.01);
+        assert_eq!(super_wide.center_x(), 1920.0);
+        assert_eq!(
+            SafeArea::centered(2520.0, 1080.0, DEFAULT_HUD_MAX_ASPECT),
+            SafeArea::full(2520.0, 1080.0)
+        );
+        assert_eq!(
+            SafeArea::centered(1440.0, 1080.0, DEFAULT_HUD_MAX_ASPECT),
+            SafeArea::full(1440.0, 1080.0)
+        );
+
+        // A score anchored 32 px into the safe area is inside it, not at the window edge
+        let score_x = super_wide.x + 32.0;
+        assert!(super_wide.contains(score_x, 24.0, 200.0, 50.0));
+        assert!(!super_wide.contains(32.0, 24.0, 200.0, 50.0));
+    }
+
+    #[test]
+    fn test_boxed_view_caps_the_aspect_ratio() {
+        let sizes = [
+            (3840.0, 1080.0),
+            (2520.0, 1080.0),
+            (1920.0, 1080.0),
+            (1024.0, 768.0),
+        ];
+        for (width, height) in sizes {
+            let full =
+                SafeArea::for_view(width, height, DEFAULT_HUD_MAX_ASPECT, ViewFit::FullWidth);
+            assert_eq!(full, SafeArea::full(width, height));
+            assert!(full.bars(width as u32, height as u32).is_empty());
+
+            let boxed = SafeArea::for_view(width, height, DEFAULT_HUD_MAX_ASPECT, ViewFit::Boxed);
+            assert!(boxed.aspect() <= DEFAULT_HUD_MAX_ASPECT + 0.01);
+            assert!(boxed.aspect() >= MIN_VIEW_ASPECT - 0.01);
+            assert!(SafeArea::full(width, height).contains(
+                boxed.x,
+                boxed.y,
+                boxed.width,
+                boxed.height
+            ));
+        }
+
+        // A 32:9 window gets a bar on each side, a portrait window one above and below
+        let boxed = SafeArea::for_view(3840.0, 1080.0, DEFAULT_HUD_MAX_ASPECT, ViewFit::Boxed);
+        assert_eq!(boxed.bars(3840, 1080).len(), 2);
+        let portrait = SafeArea::for_view(1080.0, 1920.0, DEFAULT_HUD_MAX_ASPECT, ViewFit::Boxed);
+        assert_eq!(portrait.y, 555.0);
+        assert_eq!(portrait.bars(1080, 1920).len(), 2);
+    }
+
+    #[test]
+    fn test_extreme_window_sizes_stay_valid() {
+        for (width, height) in [(1u32, 1u32), (30, 2000), (2000, 30), (0, 0)] {
+            let (w, h) = (width as f32, height as f32);
+            let areas = [
+                SafeArea::centered(w, h, DEFAULT_HUD_MAX_ASPECT),
+                SafeArea::for_view(w, h, DEFAULT_HUD_MAX_ASPECT, ViewFit::FullWidth),
+                SafeArea::for_view(w, h, DEFAULT_HUD_MAX_ASPECT, ViewFit::Boxed),
+            ];
+            for area in areas {
+                // Viewports must be at least a pixel in each direction
+                assert!(area.x >= 0.0 && area.y >= 0.0, "{:?}", area);
+                assert!(area.width >= 1.0 && area.height >= 1.0, "{:?}", area);
+                assert!(area.aspect().is_finite());
+            }
+
+            let bar_height = top_bar_height(h);
+            assert!(bar_height >= 1);
+            if let Some((x, y, bar_w, bar_h)) =
+                clamp_scissor(0, 0, width, bar_height, width, height)
+            {
+                assert!(x + bar_w <= width && y + bar_h <= height);
+            }
+        }
+
+        // A 30 px tall window still gets a one pixel stamina bar
+        assert_eq!(top_bar_height(30.0), 1);
+        assert_eq!(top_bar_height(1080.0), 14);
+
+        // Scissor rectangles are cut to the surface, or dropped when fully outside it
+        assert_eq!(clamp_scissor(10, 0, 100, 8, 50, 4), Some((10, 0, 40, 4)));
+        assert_eq!(clamp_scissor(60, 0, 100, 8, 50, 4), None);
+        assert_eq!(clamp_scissor(0, 0, 0, 8, 50, 4), None);
+        assert_eq!(clamp_scissor(u32::MAX, 1, u32::MAX, 1, 50, 4), None);
+    }
+}
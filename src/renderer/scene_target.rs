@@ -0,0 +1,244 @@
+//! Offscreen textures the 3D view is drawn into when a post effect needs to read it.
+//!
+//! Effects such as the heartbeat distortion and the exit's light shafts sample the finished
+//! scene, so while they are active the scene is drawn into a [`SceneTarget`] the size of the
+//! surface instead of the surface itself. Each effect owns its own target, so effects chain:
+//! one draws the scene it read into the next one's target rather than onto the surface.
+//!
+//! Targets are created the first time they are needed and count against the texture memory
+//! budget. A target that would go over the budget, or that the device can't allocate, is
+//! skipped until the window is resized, and the effect is left out rather than failing the
+//! frame.
+//!
+//! # Usage
+//!
+//! ```rust,ignore
+//! let mut target = SceneTarget::new(&device, "heartbeat", &surface_config, &gpu_memory);
+//!
+//! // Bind the scene as group 1 of the effect's pipeline
+//! let pipeline = PipelineBuilder::new(&device, format)
+//!     .with_bind_group_layout(&uniform_layout)
+//!     .with_bind_group_layout(target.bind_group_layout())
+//!     .build();
+//!
+//! if let Some(scene_view) = target.view(&device) {
+//!     // Draw the scene into scene_view, then the effect with target.bind_group()
+//! }
+//! ```
+
+use crate::renderer::gpu_memory::{
+    GpuMemoryTracker, TextureCategory, TrackedTexture, create_tracked_texture, texture_size_bytes,
+};
+use crate::renderer::pipeline_builder::BindGroupLayoutBuilder;
+use wgpu;
+
+/// The texture of a target once it has been allocated.
+struct Allocated {
+    /// The texture; kept alive for the view and the bind group
+    texture: TrackedTexture,
+    /// View the scene is drawn into
+    view: wgpu::TextureView,
+    /// Bind group of the view and the sampler, for the effect's pass
+    bind_group: wgpu::BindGroup,
+}
+
+/// An offscreen copy of the 3D view, created when an effect first needs it.
+pub struct SceneTarget {
+    /// Name of the effect the target belongs to, for labels and the log
+    name: &'static str,
+    /// Layout of the scene bind group: the texture at binding 0 and the sampler at 1
+    bind_group_layout: wgpu::BindGroupLayout,
+    /// Sampler the scene is read with; clamps so samples past the edges never wrap around
+    sampler: wgpu::Sampler,
+    /// Format of the surface, which the texture matches
+    format: wgpu::TextureFormat,
+    /// Usages of the texture besides being drawn into and sampled
+    extra_usage: wgpu::TextureUsages,
+    /// Tracker the texture is counted in
+    gpu_memory: GpuMemoryTracker,
+    /// Size of the surface, and of the texture, in pixels
+    size: (u32, u32),
+    /// The texture, once it has been needed
+    allocated: Option<Allocated>,
+    /// Whether the texture couldn't be allocated, so the effect is skipped
+    allocation_failed: bool,
+}
+
+impl SceneTarget {
+    /// Creates a target; its texture is created the first time it is needed.
+    ///
+    /// # Arguments
+    /// * `device` - WebGPU device for creating GPU resources
+    /// * `name` - Name of the effect, such as `"heartbeat"`
+    /// * `surface_config` - Surface configuration containing the format and size
+    /// * `gpu_memory` - Tracker the texture is counted in
+    pub fn new(
+        device: &wgpu::Device,
+        name: &'static str,
+        surface_config: &wgpu::SurfaceConfiguration,
+        gpu_memory: &GpuMemoryTracker,
+    ) -> Self {
+        let bind_group_layout = BindGroupLayoutBuilder::new(device)
+            .with_label("Scene Target Bind Group Layout")
+            .with_texture(0, wgpu::ShaderStages::FRAGMENT)
+            .with_sampler(1, wgpu::ShaderStages::FRAGMENT)
+            .build();
+        let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+            label: Some("Scene Target Sampler"),
+            address_mode_u: wgpu::AddressMode::ClampToEdge,
+            address_mode_v: wgpu::AddressMode::ClampToEdge,
+            mag_filter: wgpu::FilterMode::Linear,
+            min_filter: wgpu::FilterMode::Linear,
+            ..Default::default()
+        });
+        Self {
+            name,
+            bind_group_layout,
+            sampler,
+            format: surface_config.format,
+            extra_usage: wgpu::TextureUsages::empty(),
+            gpu_memory: gpu_memory.clone(),
+            size: (surface_config.width.max(1), surface_config.height.max(1)),
+            allocated: None,
+            allocation_failed: false,
+        }
+    }
+
+    /// Lets the texture also be used in other ways, such as copied from or into.
+    ///
+    /// # Arguments
+    /// * `usage` - Usages added to drawing into and sampling the texture
+    pub fn with_usage(mut self, usage: wgpu::TextureUsages) -> Self {
+        self.extra_usage |= usage;
+        self
+    }
+
+    /// Returns the layout of [`SceneTarget::bind_group`], for building the effect's pipeline.
+    pub fn bind_group_layout(&self) -> &wgpu::BindGroupLayout {
+        &self.bind_group_layout
+    }
+
+    /// Returns the size of the surface, and of the texture, in pixels.
+    pub fn size(&self) -> (u32, u32) {
+        self.size
+    }
+
+    /// Matches a new surface size; the texture is recreated at the new size the next time it
+    /// is needed.
+    ///
+    /// # Arguments
+    /// * `width` - New surface width in pixels
+    /// * `height` - New surface height in pixels
+    pub fn resize(&mut self, width: u32, height: u32) {
+        self.size = (width.max(1), height.max(1));
+        let stale = self.allocated.as_ref().is_some_and(|allocated| {
+            (allocated.texture.width(), allocated.texture.height()) != self.size
+        });
+        if stale {
+            self.allocated = None;
+        }
+        // A smaller window may leave room for it
+        self.allocation_failed = false;
+    }
+
+    /// Returns the texture to draw this frame's scene into, creating it if needed.
+    ///
+    /// # Arguments
+    /// * `device` - WebGPU device for creating the texture
+    ///
+    /// # Returns
+    /// A view of the texture, or `None` if it can't be allocated, in which case the scene
+    /// should be drawn where it would have gone without the effect
+    pub fn view(&mut self, device: &wgpu::Device) -> Option<wgpu::TextureView> {
+        if self.allocated.is_none() && !self.allocation_failed {
+            self.allocated = self.allocate(device);
+            self.allocation_failed = self.allocated.is_none();
+        }
+        self.allocated
+            .as_ref()
+            .map(|allocated| allocated.view.clone())
+    }
+
+    /// Returns the bind group the effect reads the scene through, once the texture exists.
+    pub fn bind_group(&self) -> Option<&wgpu::BindGroup> {
+        self.allocated
+            .as_ref()
+            .map(|allocated| &allocated.bind_group)
+    }
+
+    /// Returns the texture, once it has been allocated, for copying to or from.
+    pub fn texture(&self) -> Option<&wgpu::Texture> {
+        self.allocated.as_ref().map(|allocated| &*allocated.texture)
+    }
+
+    /// Frees the texture until it is next needed.
+    pub fn release(&mut self) {
+        self.allocated = None;
+    }
+
+    /// Allocates the texture at the surface size.
+    ///
+    /// # Returns
+    /// The texture, or `None` if it would go over the texture memory budget or the device is
+    /// out of memory
+    fn allocate(&self, device: &wgpu::Device) -> Option<Allocated> {
+        let (width, height) = self.size;
+        let label = format!("Scene Texture ({})", self.name);
+        let desc = wgpu::TextureDescriptor {
+            label: Some(&label),
+            size: wgpu::Extent3d {
+                width,
+                height,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: self.format,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT
+                | wgpu::TextureUsages::TEXTURE_BINDING
+                | self.extra_usage,
+            view_formats: &[],
+        };
+        if self.gpu_memory.total() + texture_size_bytes(&desc) > self.gpu_memory.budget() {
+            eprintln!(
+                "Skipping the {} effect: its texture would go over the memory budget",
+                self.name
+            );
+            return None;
+        }
+
+        device.push_error_scope(wgpu::ErrorFilter::OutOfMemory);
+        let texture = create_tracked_texture(
+            device,
+            &self.gpu_memory,
+            TextureCategory::SceneTargets,
+            &desc,
+        );
+        if let Some(e) = pollster::block_on(device.pop_error_scope()) {
+            eprintln!("Skipping the {} effect: {}", self.name, e);
+            return None;
+        }
+
+        let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            layout: &self.bind_group_layout,
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: wgpu::BindingResource::TextureView(&view),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: wgpu::BindingResource::Sampler(&self.sampler),
+                },
+            ],
+            label: Some("Scene Target Bind Group"),
+        });
+        Some(Allocated {
+            texture,
+            view,
+            bind_group,
+        })
+    }
+}
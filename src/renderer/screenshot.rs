@@ -0,0 +1,226 @@
+//! Screenshots of the game window.
+//!
+//! A screenshot is taken from the frame being drawn. [`PendingScreenshot::record`] adds a copy
+//! of the surface texture to the frame's commands; once they are submitted,
+//! [`PendingScreenshot::save_in_background`] reads the copy back and hands the pixels to a
+//! thread that writes them as a PNG, so the frame never waits on the encoder or the disk.
+//!
+//! Copying needs a surface that can be copied from. [`WgpuRenderer`] asks for one when the
+//! platform allows it; where it doesn't, [`WgpuRenderer::can_screenshot`] is false.
+//!
+//! [`WgpuRenderer`]: crate::renderer::wgpu_lib::WgpuRenderer
+//! [`WgpuRenderer::can_screenshot`]: crate::renderer::wgpu_lib::WgpuRenderer::can_screenshot
+//!
+//! # Usage
+//!
+//! ```rust,ignore
+//! // While recording the frame
+//! let screenshot = PendingScreenshot::record(&device, &mut encoder, &surface_texture.texture);
+//!
+//! // After submitting it
+//! queue.submit(Some(encoder.finish()));
+//! if let Some(screenshot) = screenshot {
+//!     screenshot.save_in_background(&device, paths::get().screenshots_dir(), toasts.sender());
+//! }
+//! ```
+
+use crate::game::toasts::ToastSender;
+use chrono::Local;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::thread;
+
+/// A copy of one frame on its way from the GPU to a file.
+pub struct PendingScreenshot {
+    /// Buffer the frame is copied into
+    buffer: wgpu::Buffer,
+    /// Frame width in pixels
+    width: u32,
+    /// Frame height in pixels
+    height: u32,
+    /// Bytes per row in the buffer, padded to [`wgpu::COPY_BYTES_PER_ROW_ALIGNMENT`]
+    padded_row_bytes: u32,
+    /// Whether the frame is stored blue first, as most surfaces are
+    bgra: bool,
+}
+
+impl PendingScreenshot {
+    /// Adds a copy of a frame to the frame's commands.
+    ///
+    /// # Arguments
+    /// * `device` - Device the frame is drawn with
+    /// * `encoder` - The frame's commands, after everything is drawn
+    /// * `texture` - The surface texture the frame is drawn to
+    ///
+    /// # Returns
+    /// The screenshot to save once the commands are submitted, or `None` if the texture
+    /// isn't 8-bit RGBA or BGRA or can't be copied from
+    pub fn record(
+        device: &wgpu::Device,
+        encoder: &mut wgpu::CommandEncoder,
+        texture: &wgpu::Texture,
+    ) -> Option<Self> {
+        let bgra = match texture.format() {
+            wgpu::TextureFormat::Bgra8Unorm | wgpu::TextureFormat::Bgra8UnormSrgb => true,
+            wgpu::TextureFormat::Rgba8Unorm | wgpu::TextureFormat::Rgba8UnormSrgb => false,
+            _ => return None,
+        };
+        if !texture.usage().contains(wgpu::TextureUsages::COPY_SRC) {
+            return None;
+        }
+
+        // Copied rows have to start on 256-byte boundaries
+        let (width, height) = (texture.width(), texture.height());
+        let padded_row_bytes = (width * 4).div_ceil(wgpu::COPY_BYTES_PER_ROW_ALIGNMENT)
+            * wgpu::COPY_BYTES_PER_ROW_ALIGNMENT;
+        let buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Screenshot Readback"),
+            size: (padded_row_bytes * height) as u64,
+            usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+            mapped_at_creation: false,
+        });
+        encoder.copy_texture_to_buffer(
+            texture.as_image_copy(),
+            wgpu::TexelCopyBufferInfo {
+                buffer: &buffer,
+                layout: wgpu::TexelCopyBufferLayout {
+                    offset: 0,
+                    bytes_per_row: Some(padded_row_bytes),
+                    rows_per_image: Some(height),
+                },
+            },
+            texture.size(),
+        );
+        Some(Self {
+            buffer,
+            width,
+            height,
+            padded_row_bytes,
+            bgra,
+        })
+    }
+
+    /// Reads the frame back and writes it to its own file on a background thread.
+    ///
+    /// Waits for the GPU to finish the frame. Posts a toast naming the file once it is
+    /// written, or saying it couldn't be.
+    ///
+    /// # Arguments
+    /// * `device` - Device the frame was drawn with
+    /// * `dir` - Directory to write the screenshot to, created if missing
+    /// * `toasts` - Where to post the outcome
+    pub fn save_in_background(self, device: &wgpu::Device, dir: PathBuf, toasts: ToastSender) {
+        let slice = self.buffer.slice(..);
+        slice.map_async(wgpu::MapMode::Read, |_| {});
+        device.poll(wgpu::Maintain::Wait);
+        let pixels = to_rgba(
+            &slice.get_mapped_range(),
+            self.width,
+            self.padded_row_bytes,
+            self.bgra,
+        );
+        let (width, height) = (self.width, self.height);
+
+        let spawned = thread::Builder::new()
+            .name("screenshot-writer".to_string())
+            .spawn(move || match write_png(&pixels, width, height, &dir) {
+                Ok(path) => {
+                    println!("Screenshot saved to {}", path.display());
+                    let name = path.file_name().unwrap_or(path.as_os_str());
+                    toasts.system(format!("Screenshot saved as {}", name.to_string_lossy()));
+                }
+                Err(e) => {
+                    eprintln!("{}", e);
+                    toasts.system("Couldn't save screenshot");
+                }
+            });
+        if let Err(e) = spawned {
+            eprintln!("Failed to start screenshot writer: {}", e);
+        }
+    }
+}
+
+/// Turns copied rows into tightly packed, opaque RGBA pixels.
+///
+/// # Arguments
+/// * `data` - The copied rows, top row first
+/// * `width` - Frame width in pixels
+/// * `padded_row_bytes` - Bytes per copied row, including padding
+/// * `bgra` - Whether the pixels are stored blue first
+///
+/// # Returns
+/// Four bytes per pixel, red first; alpha is always opaque, since the surface's own alpha
+/// isn't what the player sees
+fn to_rgba(data: &[u8], width: u32, padded_row_bytes: u32, bgra: bool) -> Vec<u8> {
+    data.chunks(padded_row_bytes as usize)
+        .flat_map(|row| row[..width as usize * 4].chunks_exact(4))
+        .flat_map(|pixel| {
+            let [r, g, b] = if bgra {
+                [pixel[2], pixel[1], pixel[0]]
+            } else {
+                [pixel[0], pixel[1], pixel[2]]
+            };
+            [r, g, b, u8::MAX]
+        })
+        .collect()
+}
+
+/// Writes a screenshot to its own file.
+///
+/// # Arguments
+/// * `pixels` - RGBA pixels, top row first
+/// * `width` - Width in pixels
+/// * `height` - Height in pixels
+/// * `dir` - Directory to write it to, created if missing
+///
+/// # Returns
+/// The path of the new file, or an error message if it could not be written
+fn write_png(pixels: &[u8], width: u32, height: u32, dir: &Path) -> Result<PathBuf, String> {
+    fs::create_dir_all(dir).map_err(|e| format!("Failed to create screenshot directory: {}", e))?;
+
+    let stamp = Local::now().format("%Y%m%d-%H%M%S");
+    let mut path = dir.join(format!("screenshot-{}.png", stamp));
+    let mut suffix = 1;
+    while path.exists() {
+        path = dir.join(format!("screenshot-{}-{}.png", stamp, suffix));
+        suffix += 1;
+    }
+    image::save_buffer(&path, pixels, width, height, image::ColorType::Rgba8)
+        .map_err(|e| format!("Failed to write screenshot to {}: {}", path.display(), e))?;
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_copied_rows_lose_their_padding_and_turn_red_first() {
+        // Two pixels a row, padded to three
+        let data = [
+            3, 2, 1, 0, 6, 5, 4, 0, 99, 99, 99, 99, //
+            9, 8, 7, 0, 12, 11, 10, 0, 99, 99, 99, 99,
+        ];
+        assert_eq!(
+            to_rgba(&data, 2, 12, true),
+            [1, 2, 3, 255, 4, 5, 6, 255, 7, 8, 9, 255, 10, 11, 12, 255]
+        );
+        assert_eq!(
+            to_rgba(&data[..8], 2, 12, false),
+            [3, 2, 1, 255, 6, 5, 4, 255]
+        );
+    }
+
+    #[test]
+    fn test_screenshots_never_overwrite_each_other() {
+        let dir = std::env::temp_dir().join(format!("mirador_screenshots_{}", std::process::id()));
+        let first = write_png(&[0, 0, 0, 255], 1, 1, &dir).unwrap();
+        let second = write_png(&[255, 255, 255, 255], 1, 1, &dir).unwrap();
+        assert_ne!(first, second);
+        assert_eq!(
+            image::open(&second).unwrap().to_rgba8().get_pixel(0, 0).0,
+            [255; 4]
+        );
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -17,13 +17,132 @@ fn vs_main(model: VertexInput) -> VertexOutput {
     return out;
 }
 
-// Fragment shader
+// GPU preview: colors are derived per pixel from the maze's wall grid
+
+struct MazeUniforms {
+    // Colors, already converted for the render target
+    wall_color: vec4<f32>,
+    open_color: vec4<f32>,
+    exit_color: vec4<f32>,
+    glow_color: vec4<f32>,
+    path_color: vec4<f32>,
+    // Maze size in cells (width, height)
+    maze_size: vec2<f32>,
+    // Cell currently being carved (col, row), negative when there is none
+    front_cell: vec2<f32>,
+    // Seconds since the preview was created
+    time: f32,
+    // Seconds a newly carved cell keeps pulsing
+    pulse_duration: f32,
+    // How far the path pulse has swept (0 to 1), negative when the path is hidden
+    path_progress: f32,
+    // 1.0 to light the whole path at once instead of pulsing it
+    path_static: f32,
+}
+
+// One byte per wall grid entry, matching `PreviewTile`
 @group(0) @binding(0)
-var t_diffuse: texture_2d<f32>;
+var grid: texture_2d<f32>;
+// Time each wall grid entry is fully revealed, negative if it has not been carved yet
 @group(0) @binding(1)
-var s_diffuse: sampler;
+var<storage, read> carve_times: array<f32>;
+@group(0) @binding(2)
+var<uniform> maze: MazeUniforms;
+// How far along the solution path each wall grid entry is (0 to 1), negative off the path
+@group(0) @binding(5)
+var<storage, read> path_orders: array<f32>;
+
+const TILE_WALL: u32 = 0u;
+const TILE_PASSAGE: u32 = 1u;
+const TILE_UNCONNECTED_CELL: u32 = 2u;
+const TILE_CONNECTED_CELL: u32 = 3u;
+const TILE_EXIT: u32 = 4u;
+
+// Same layout as the CPU preview: 4 pixel cells separated by 1 pixel walls
+const CELL_PX: f32 = 4.0;
+const WALL_PX: f32 = 1.0;
+
+// Seconds an entry takes to fade in before it is fully revealed
+const FADE_IN: f32 = 0.08;
+// Half-width of the band sweeping along the solution path, as a fraction of the path
+const PATH_BAND: f32 = 0.12;
+// Strength of the steady path highlight when effects are reduced
+const PATH_STATIC_STRENGTH: f32 = 0.6;
+
+// Maps a preview pixel coordinate to its wall grid index along one axis
+fn grid_index(px: f32, cells: f32) -> u32 {
+    let stride = CELL_PX + WALL_PX;
+    let block = min(floor(px / stride), cells);
+    let within = px - block * stride;
+    if within < WALL_PX || block >= cells {
+        return u32(block) * 2u;
+    }
+    return u32(block) * 2u + 1u;
+}
 
 @fragment
 fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    let render_size = maze.maze_size * (CELL_PX + WALL_PX) + WALL_PX;
+    let px = in.tex_coords * render_size;
+    let col = grid_index(px.x, maze.maze_size.x);
+    let row = grid_index(px.y, maze.maze_size.y);
+
+    let tile = u32(round(textureLoad(grid, vec2<u32>(col, row), 0).r * 255.0));
+    let index = row * (u32(maze.maze_size.x) * 2u + 1u) + col;
+
+    var color = maze.wall_color;
+    if tile == TILE_PASSAGE || tile == TILE_CONNECTED_CELL {
+        color = maze.open_color;
+    } else if tile == TILE_EXIT {
+        color = maze.exit_color;
+    }
+
+    // Carved entries fade in one after another in carve order, and newly carved
+    // cells flash with the glow color before settling on open
+    let revealed_at = carve_times[index];
+    if revealed_at >= 0.0 && (tile == TILE_PASSAGE || tile == TILE_CONNECTED_CELL) {
+        let age = maze.time - revealed_at;
+        if tile == TILE_CONNECTED_CELL {
+            let pulse = 1.0 - clamp(age / maze.pulse_duration, 0.0, 1.0);
+            color = mix(color, maze.glow_color, pulse * pulse);
+        }
+        let fade = clamp(1.0 + age / FADE_IN, 0.0, 1.0);
+        color = mix(maze.wall_color, color, fade);
+    }
+
+    // Bright animated glow around the cell currently being carved, hotter at its core
+    if maze.front_cell.x >= 0.0 {
+        let cell_px = px / (CELL_PX + WALL_PX);
+        let distance = length(cell_px - (maze.front_cell + 0.5));
+        let flicker = 0.85 + 0.15 * sin(maze.time * 12.0);
+        let glow = exp(-distance * distance * 0.45) * flicker;
+        let core = mix(maze.glow_color, vec4<f32>(1.0), 0.5);
+        color = mix(color, core, clamp(glow * 1.2, 0.0, 1.0));
+    }
+
+    // The solution path lights up from the entrance to the exit once the maze is done
+    let order = path_orders[index];
+    if order >= 0.0 && maze.path_progress >= 0.0 && tile != TILE_EXIT {
+        var strength = PATH_STATIC_STRENGTH;
+        if maze.path_static < 0.5 {
+            let head = maze.path_progress * (1.0 + 2.0 * PATH_BAND) - PATH_BAND;
+            let offset = (order - head) / PATH_BAND;
+            strength = exp(-offset * offset * 3.0);
+        }
+        color = mix(color, maze.path_color, strength);
+    }
+
+    return vec4<f32>(color.rgb, 1.0);
+}
+
+// CPU fallback: samples a texture rasterized by `Maze::get_render_data`
+
+@group(0) @binding(3)
+var t_diffuse: texture_2d<f32>;
+@group(0) @binding(4)
+var s_diffuse: sampler;
+
+@fragment
+fn fs_texture(in: VertexOutput) -> @location(0) vec4<f32> {
     return textureSample(t_diffuse, s_diffuse, in.tex_coords);
 }
@@ -0,0 +1,23 @@
+// Alpha stamp shader
+//
+// Covers the frame with a full-screen triangle and outputs an alpha of 1.0. The pipeline only
+// writes the alpha channel, so the frame's colors are left as they are.
+
+// Vertex shader
+@vertex
+fn vs_main(@builtin(vertex_index) vertex_index: u32) -> @builtin(position) vec4<f32> {
+    // Full-screen triangle
+    var pos: vec2<f32>;
+    switch (vertex_index) {
+        case 0u: { pos = vec2(-1.0, -1.0); }
+        case 1u: { pos = vec2(3.0, -1.0); }
+        default: { pos = vec2(-1.0, 3.0); }
+    }
+    return vec4<f32>(pos, 0.0, 1.0);
+}
+
+// Fragment shader
+@fragment
+fn fs_main() -> @location(0) vec4<f32> {
+    return vec4<f32>(0.0, 0.0, 0.0, 1.0);
+}
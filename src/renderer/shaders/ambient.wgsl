@@ -0,0 +1,138 @@
+struct AmbientUniforms {
+    // Linear particle color, with the strongest opacity in alpha
+    color: vec4<f32>,
+    // Size of the 3D view in pixels
+    resolution: vec2<f32>,
+    // Top-left corner of the 3D view on the surface, in pixels
+    origin: vec2<f32>,
+    // Drift in view heights per second, positive y is up
+    velocity: vec2<f32>,
+    // Animation time in seconds
+    time: f32,
+    // Camera yaw and pitch in radians
+    yaw: f32,
+    pitch: f32,
+    // Chance that a grid cell holds a particle
+    density: f32,
+    // Particle length along the drift in view heights, 0 for round motes
+    streak: f32,
+    // View heights the scene moves per radian of camera rotation
+    view_per_radian: f32,
+};
+
+@group(0) @binding(0)
+var<uniform> uniforms: AmbientUniforms;
+
+const TAU: f32 = 6.2831853;
+
+// Layers of particles, far to near
+const LAYER_COUNT: u32 = 3u;
+
+// Vertex shader
+@vertex
+fn vs_main(@builtin(vertex_index) vertex_index: u32) -> @builtin(position) vec4<f32> {
+    // Full-screen triangle
+    var pos: vec2<f32>;
+    switch (vertex_index) {
+        case 0u: { pos = vec2(-1.0, -1.0); }
+        case 1u: { pos = vec2(3.0, -1.0); }
+        default: { pos = vec2(-1.0, 3.0); }
+    }
+    return vec4<f32>(pos, 0.0, 1.0);
+}
+
+// PCG integer hash
+fn pcg(v: u32) -> u32 {
+    let state = v * 747796405u + 2891336453u;
+    let word = ((state >> ((state >> 28u) + 4u)) ^ state) * 277803737u;
+    return (word >> 22u) ^ word;
+}
+
+// Three uniform random numbers in [0, 1] for a grid cell of a layer
+fn hash3(cell: vec2<f32>, layer: u32) -> vec3<f32> {
+    let ids = vec2<i32>(cell);
+    let a = pcg(bitcast<u32>(ids.x) ^ pcg(bitcast<u32>(ids.y) ^ pcg(layer)));
+    let b = pcg(a);
+    let c = pcg(b);
+    return vec3<f32>(f32(a), f32(b), f32(c)) / 4294967295.0;
+}
+
+// Distance from a point to the segment between a and b
+fn segment_distance(p: vec2<f32>, a: vec2<f32>, b: vec2<f32>) -> f32 {
+    let ab = b - a;
+    let t = clamp(dot(p - a, ab) / max(dot(ab, ab), 1e-6), 0.0, 1.0);
+    return length(p - a - ab * t);
+}
+
+// Opacity of one layer's particle at a point of the view
+fn layer_alpha(p: vec2<f32>, layer: u32) -> f32 {
+    let depth = f32(layer) / f32(LAYER_COUNT - 1u);
+    // Near layers have fewer, larger cells, move faster and swing a little further when the
+    // camera turns, which reads as depth even though a pure rotation has no real parallax
+    let cells = mix(36.0, 13.0, depth);
+    let speed = mix(0.55, 1.0, depth);
+    let parallax = mix(0.9, 1.15, depth);
+    var brightness = mix(0.45, 1.0, depth);
+    let pixel = cells / uniforms.resolution.y;
+
+    // A full turn spans a whole number of cells, so the grid wraps without a seam
+    let turn_cells = max(round(TAU * uniforms.view_per_radian * parallax * cells), 1.0);
+    let drift = uniforms.velocity * uniforms.time * speed;
+    var domain = vec2<f32>(
+        (p.x - drift.x) * cells - uniforms.yaw * turn_cells / TAU,
+        (p.y - drift.y + uniforms.pitch * uniforms.view_per_radian * parallax) * cells,
+    );
+
+    // Shift every column by its own amount so the particles don't line up in rows
+    let column = floor(domain.x);
+    let wrapped_column = column - turn_cells * floor(column / turn_cells);
+    domain.y += hash3(vec2<f32>(wrapped_column, -1.0), layer).x * 17.0;
+
+    let cell = vec2<f32>(wrapped_column, floor(domain.y));
+    let random = hash3(cell, layer);
+    if (random.x > uniforms.density) {
+        return 0.0;
+    }
+    let local = fract(domain) - 0.5;
+
+    var offset: f32;
+    var radius: f32;
+    if (uniforms.streak > 0.0) {
+        // Rain: a thin streak along the drift, kept inside its cell
+        let half_length = min(uniforms.streak * cells * 0.5, 0.4);
+        let direction = normalize(uniforms.velocity + vec2<f32>(0.0, -1e-4));
+        let room = 0.5 - half_length;
+        let center = (random.yz - 0.5) * vec2<f32>(0.8, 2.0 * room);
+        let tip = direction * half_length;
+        offset = segment_distance(local, center - tip, center + tip);
+        radius = mix(0.6, 1.1, depth) * pixel;
+    } else {
+        // Dust: a soft round mote that slowly twinkles
+        radius = mix(1.0, 2.6, depth) * (uniforms.resolution.y / 1080.0) * pixel;
+        let center = (random.yz - 0.5) * (0.8 - 2.0 * radius);
+        offset = length(local - center);
+        brightness *= 0.55 + 0.45 * sin(uniforms.time * mix(0.6, 1.4, random.y) + random.z * TAU);
+    }
+
+    let edge = 1.0 - smoothstep(radius, radius + pixel, offset);
+    return edge * brightness;
+}
+
+// Fragment shader
+@fragment
+fn fs_main(@builtin(position) frag: vec4<f32>) -> @location(0) vec4<f32> {
+    // Position in view heights from the view center, y up
+    let pixel = frag.xy - uniforms.origin - 0.5 * uniforms.resolution;
+    let p = vec2<f32>(pixel.x, -pixel.y) / uniforms.resolution.y;
+
+    var transparency = 1.0;
+    for (var layer = 0u; layer < LAYER_COUNT; layer++) {
+        transparency *= 1.0 - layer_alpha(p, layer);
+    }
+
+    let alpha = (1.0 - transparency) * uniforms.color.a;
+    if (alpha < 1.0 / 255.0) {
+        discard;
+    }
+    return vec4<f32>(uniforms.color.rgb, alpha);
+}
@@ -0,0 +1,105 @@
+// Anomaly shader - a glowing tetrahedron turning slowly in a dead end
+
+struct AnomalyUniforms {
+    view_proj_matrix: mat4x4<f32>,
+    // xyz = center, w = distance from the center to the corners
+    center_size: vec4<f32>,
+    // Linear RGB glow color
+    color: vec4<f32>,
+    time: f32,
+    _pad0: f32,
+    _pad1: f32,
+    _pad2: f32,
+}
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    // Barycentric coordinates within the face, for the glowing edges
+    @location(0) barycentric: vec3<f32>,
+    // How directly the face looks up, from 0.0 to 1.0
+    @location(1) facing: f32,
+}
+
+@group(0) @binding(0)
+var<uniform> uniforms: AnomalyUniforms;
+
+// Corners of a regular tetrahedron inscribed in the unit sphere
+fn corner(index: u32) -> vec3<f32> {
+    switch (index) {
+        case 0u: { return vec3<f32>(1.0, 1.0, 1.0) * 0.57735; }
+        case 1u: { return vec3<f32>(1.0, -1.0, -1.0) * 0.57735; }
+        case 2u: { return vec3<f32>(-1.0, 1.0, -1.0) * 0.57735; }
+        default: { return vec3<f32>(-1.0, -1.0, 1.0) * 0.57735; }
+    }
+}
+
+// Corner of a face's triangle; every face winds the same way
+fn face_corner(face: u32, slot: u32) -> u32 {
+    var faces = array<vec3<u32>, 4>(
+        vec3<u32>(0u, 1u, 2u),
+        vec3<u32>(0u, 3u, 1u),
+        vec3<u32>(0u, 2u, 3u),
+        vec3<u32>(1u, 3u, 2u),
+    );
+    return faces[face][slot];
+}
+
+// Turns the tetrahedron about the vertical axis and tips it a little
+fn spin(p: vec3<f32>, time: f32) -> vec3<f32> {
+    let yaw = time * 0.9;
+    let tilt = 0.5;
+    let turned = vec3<f32>(
+        p.x * cos(yaw) - p.z * sin(yaw),
+        p.y,
+        p.x * sin(yaw) + p.z * cos(yaw),
+    );
+    return vec3<f32>(
+        turned.x,
+        turned.y * cos(tilt) - turned.z * sin(tilt),
+        turned.y * sin(tilt) + turned.z * cos(tilt),
+    );
+}
+
+@vertex
+fn vs_main(@builtin(vertex_index) vertex_index: u32) -> VertexOutput {
+    var out: VertexOutput;
+
+    let face = vertex_index / 3u;
+    let slot = vertex_index % 3u;
+    let time = uniforms.time;
+
+    // Flat shading needs the whole face, so spin all three corners
+    let a = spin(corner(face_corner(face, 0u)), time);
+    let b = spin(corner(face_corner(face, 1u)), time);
+    let c = spin(corner(face_corner(face, 2u)), time);
+    let normal = normalize(cross(b - a, c - a));
+
+    var local = a;
+    var barycentric = vec3<f32>(1.0, 0.0, 0.0);
+    if (slot == 1u) {
+        local = b;
+        barycentric = vec3<f32>(0.0, 1.0, 0.0);
+    } else if (slot == 2u) {
+        local = c;
+        barycentric = vec3<f32>(0.0, 0.0, 1.0);
+    }
+
+    // Bob gently so it reads as floating rather than placed
+    let bob = vec3<f32>(0.0, sin(time * 1.7) * 0.15, 0.0);
+    let world_position = uniforms.center_size.xyz + (local + bob) * uniforms.center_size.w;
+
+    out.clip_position = uniforms.view_proj_matrix * vec4<f32>(world_position, 1.0);
+    out.barycentric = barycentric;
+    out.facing = abs(normal.y);
+    return out;
+}
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    // Lit from within: faces stay bright whichever way they turn, edges glow brightest
+    let pulse = 0.8 + 0.2 * sin(uniforms.time * 3.0);
+    let edge = 1.0 - smoothstep(0.0, 0.08, min(in.barycentric.x, min(in.barycentric.y, in.barycentric.z)));
+    let shade = 0.55 + 0.25 * in.facing;
+    let color = uniforms.color.rgb * (shade * pulse + edge * 0.6);
+    return vec4<f32>(min(color, vec3<f32>(1.0)), 1.0);
+}
@@ -27,10 +27,9 @@ struct VertexOutput {
 fn vs_main(input: VertexInput) -> VertexOutput {
     var out: VertexOutput;
 
-    // Simplified: just put a small quad in bottom-right corner
-    // Scale the quad down and position it
-    let scaled_pos = input.position * 0.25; // Make it smaller
-    let positioned = scaled_pos + vec2<f32>(0.7, -0.7); // Bottom-right in NDC
+    // Scale the -1..1 quad to the compass size and move it to its center in NDC
+    let scaled_pos = input.position * uniforms.compass_size;
+    let positioned = scaled_pos + (uniforms.screen_position * 2.0 - 1.0);
 
     out.clip_position = vec4<f32>(positioned.x, positioned.y, 0.0, 1.0);
     out.tex_coords = input.tex_coords;
@@ -0,0 +1,44 @@
+// Death fade shader - draws the frozen moment of death over the live scene, scaled to fit
+
+struct DeathFadeUniforms {
+    // Half-size of the quad in clip space, so the copy keeps its aspect ratio between bars
+    scale: vec2<f32>,
+    // Opacity of the frozen copy, from 0.0 to 1.0
+    alpha: f32,
+    _pad0: f32,
+};
+
+@group(0) @binding(0)
+var<uniform> uniforms: DeathFadeUniforms;
+
+@group(1) @binding(0)
+var frozen_texture: texture_2d<f32>;
+@group(1) @binding(1)
+var frozen_sampler: sampler;
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+};
+
+// Vertex shader
+@vertex
+fn vs_main(@builtin(vertex_index) vertex_index: u32) -> VertexOutput {
+    // Two triangles covering the quad
+    var corners = array<vec2<f32>, 6>(
+        vec2(-1.0, -1.0), vec2(1.0, -1.0), vec2(1.0, 1.0),
+        vec2(-1.0, -1.0), vec2(1.0, 1.0), vec2(-1.0, 1.0),
+    );
+    let corner = corners[vertex_index];
+    var out: VertexOutput;
+    out.clip_position = vec4<f32>(corner * uniforms.scale, 0.0, 1.0);
+    out.uv = vec2<f32>(corner.x * 0.5 + 0.5, 0.5 - corner.y * 0.5);
+    return out;
+}
+
+// Fragment shader
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    let color = textureSample(frozen_texture, frozen_sampler, in.uv);
+    return vec4<f32>(color.rgb, uniforms.alpha);
+}
@@ -0,0 +1,68 @@
+// Wall sign shader - painted arrows on the maze walls pointing the way on
+
+struct DecalUniforms {
+    view_proj_matrix: mat4x4<f32>,
+    color: vec4<f32>,
+}
+
+struct VertexInput {
+    @location(0) corner: vec2<f32>,
+}
+
+struct InstanceInput {
+    // xyz = sign center just in front of the wall, w = size in world units
+    @location(1) center_size: vec4<f32>,
+    // xy = x and z of the wall normal, z = 1.0 when the arrow points right for someone
+    // facing the wall, -1.0 when it points left
+    @location(2) normal_arrow: vec4<f32>,
+}
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) local: vec2<f32>,
+}
+
+@group(0) @binding(0)
+var<uniform> uniforms: DecalUniforms;
+
+@vertex
+fn vs_main(vertex: VertexInput, instance: InstanceInput) -> VertexOutput {
+    var out: VertexOutput;
+
+    // Facing the wall means looking along -normal, so the viewer's right is (normal.z, -normal.x)
+    let normal = instance.normal_arrow.xy;
+    let right = vec3<f32>(normal.y, 0.0, -normal.x);
+    let size = instance.center_size.w;
+
+    let world_position = instance.center_size.xyz
+        + right * vertex.corner.x * size
+        + vec3<f32>(0.0, vertex.corner.y * size, 0.0);
+
+    out.clip_position = uniforms.view_proj_matrix * vec4<f32>(world_position, 1.0);
+    // Mirror the quad for left arrows so the arrow is always drawn pointing +x
+    out.local = vec2<f32>(vertex.corner.x * instance.normal_arrow.z, vertex.corner.y) * 2.0;
+
+    return out;
+}
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    let p = in.local;
+
+    // Shaft from the left edge to the base of the head
+    let shaft = step(abs(p.y), 0.18) * step(-0.8, p.x) * step(p.x, 0.1);
+
+    // Triangular head with its tip at the right edge
+    let head = step(0.1, p.x) * step(abs(p.y), (0.8 - p.x) * 0.85);
+
+    let shape = max(shaft, head);
+
+    // Slightly ragged edges so the arrow reads as painted rather than printed
+    let grain = fract(sin(dot(floor(p * 24.0), vec2<f32>(12.9898, 78.233))) * 43758.5453);
+    let alpha = uniforms.color.a * shape * (0.8 + 0.2 * grain);
+    if (alpha <= 0.001) {
+        discard;
+    }
+
+    return vec4<f32>(uniforms.color.rgb, alpha);
+}
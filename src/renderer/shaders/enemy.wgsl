@@ -5,7 +5,12 @@ struct EnemyUniforms {
     enemy_position: vec3<f32>,
     enemy_size: f32,
     player_position: vec3<f32>,
-    _padding: f32,
+    bob_offset: f32,
+    tint: vec4<f32>,
+    roll: f32,
+    _padding0: f32,
+    _padding1: f32,
+    _padding2: f32,
 }
 
 struct VertexInput {
@@ -44,8 +49,18 @@ fn vs_main(model: VertexInput) -> VertexOutput {
         sin_y,  0.0, cos_y    // Changed: -sin_y to sin_y
     );
 
+    // Roll the skin's mesh in its own plane, then bob it up and down
+    let roll = radians(uniforms.roll);
+    let cos_r = cos(roll);
+    let sin_r = sin(roll);
+    let rolled_position = vec3<f32>(
+        model.position.x * cos_r - model.position.y * sin_r,
+        model.position.x * sin_r + model.position.y * cos_r + uniforms.bob_offset,
+        model.position.z
+    );
+
     // Scale the vertex by enemy size
-    let scaled_position = model.position * uniforms.enemy_size;
+    let scaled_position = rolled_position * uniforms.enemy_size;
 
     // Apply rotation to the scaled position
     let rotated_position = rotation_matrix * scaled_position;
@@ -69,5 +84,5 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
         discard;
     }
 
-    return texture_color;
+    return texture_color * uniforms.tint;
 }
@@ -0,0 +1,83 @@
+struct EnemyFaceUniforms {
+    // Seconds since the capture started, drives the distortion
+    time: f32,
+    // Opacity of the whole overlay, from 0.0 to 1.0
+    intensity: f32,
+    // Surface width divided by height
+    screen_aspect: f32,
+    // Face texture width divided by height
+    image_aspect: f32,
+};
+
+@group(0) @binding(0)
+var<uniform> uniforms: EnemyFaceUniforms;
+@group(0) @binding(1)
+var face_texture: texture_2d<f32>;
+@group(0) @binding(2)
+var face_sampler: sampler;
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) ndc: vec2<f32>,
+};
+
+// Vertex shader
+@vertex
+fn vs_main(@builtin(vertex_index) vertex_index: u32) -> VertexOutput {
+    // Full-screen triangle
+    var pos: vec2<f32>;
+    switch (vertex_index) {
+        case 0u: { pos = vec2(-1.0, -1.0); }
+        case 1u: { pos = vec2(3.0, -1.0); }
+        default: { pos = vec2(-1.0, 3.0); }
+    }
+    var out: VertexOutput;
+    out.clip_position = vec4<f32>(pos, 0.0, 1.0);
+    out.ndc = pos;
+    return out;
+}
+
+// Cheap hash noise in [0, 1)
+fn hash(p: vec2<f32>) -> f32 {
+    return fract(sin(dot(p, vec2<f32>(127.1, 311.7))) * 43758.5453);
+}
+
+// Fragment shader
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    let t = uniforms.time;
+
+    // Fit the face so it covers the screen, pushing in slowly as if still lunging
+    var p = in.ndc;
+    if (uniforms.screen_aspect > uniforms.image_aspect) {
+        p.y = p.y * uniforms.image_aspect / uniforms.screen_aspect;
+    } else {
+        p.x = p.x * uniforms.screen_aspect / uniforms.image_aspect;
+    }
+    p = p / (1.0 + 0.25 * t);
+
+    // Horizontal tearing: each band jumps sideways a few times a second
+    let band = floor(in.ndc.y * 24.0);
+    let jitter_step = floor(t * 18.0);
+    let tear = (hash(vec2<f32>(band, jitter_step)) - 0.5) * 0.08;
+    let wobble = sin(in.ndc.y * 14.0 + t * 30.0) * 0.015;
+    p.x = p.x + tear + wobble;
+
+    let uv = vec2<f32>(p.x * 0.5 + 0.5, 0.5 - p.y * 0.5);
+
+    // Split the color channels apart for a glitchy look
+    let split = vec2<f32>(0.012 + 0.01 * sin(t * 40.0), 0.0);
+    let r = textureSample(face_texture, face_sampler, uv + split);
+    let g = textureSample(face_texture, face_sampler, uv);
+    let b = textureSample(face_texture, face_sampler, uv - split);
+    let inside = all(uv >= vec2<f32>(0.0)) && all(uv <= vec2<f32>(1.0));
+    let coverage = select(0.0, g.a, inside);
+    var color = vec3<f32>(r.r, g.g, b.b) * coverage;
+
+    // Grain and a flickering red wash over the black around the face
+    let grain = hash(in.clip_position.xy + vec2<f32>(jitter_step, jitter_step * 0.37));
+    let flicker = 0.75 + 0.25 * sin(t * 55.0);
+    color = color * flicker + vec3<f32>(0.25, 0.0, 0.0) * (1.0 - coverage) + (grain - 0.5) * 0.12;
+
+    return vec4<f32>(clamp(color, vec3<f32>(0.0), vec3<f32>(1.0)), uniforms.intensity);
+}
@@ -108,5 +108,6 @@ fn fs_main(@builtin(position) frag_position: vec4<f32>) -> @location(0) vec4<f32
     let scaled_uv = uv * 8.0; // Increase this value for even finer detail
 
     let shade: f32 = pattern(scaled_uv);
-    return vec4<f32>(colormap(shade).rgb, shade);
+    // Drawn without blending, so any alpha below 1.0 would show the desktop through the window
+    return vec4<f32>(colormap(shade).rgb, 1.0);
 }
@@ -0,0 +1,78 @@
+// Heartbeat shader - pulses the finished 3D view outward and splits its colors at the edges
+
+struct HeartbeatUniforms {
+    // Strength of the effect, from 0.0 to 1.0 as the timer runs out
+    intensity: f32,
+    // How far through the current heartbeat, from 0.0 to 1.0
+    phase: f32,
+    // Surface width divided by height, so the pulse is round rather than stretched
+    aspect: f32,
+    _pad0: f32,
+};
+
+@group(0) @binding(0)
+var<uniform> uniforms: HeartbeatUniforms;
+
+@group(1) @binding(0)
+var scene_texture: texture_2d<f32>;
+@group(1) @binding(1)
+var scene_sampler: sampler;
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+};
+
+// Largest barrel push at the screen corners, as a fraction of the screen
+const MAX_BARREL: f32 = 0.045;
+// Largest color split at the screen edges, as a fraction of the screen
+const MAX_ABERRATION: f32 = 0.008;
+// Where in the beat the second, softer thump lands
+const DUB_PHASE: f32 = 0.22;
+
+// Vertex shader
+@vertex
+fn vs_main(@builtin(vertex_index) vertex_index: u32) -> VertexOutput {
+    // Full-screen triangle
+    var pos: vec2<f32>;
+    switch (vertex_index) {
+        case 0u: { pos = vec2(-1.0, -1.0); }
+        case 1u: { pos = vec2(3.0, -1.0); }
+        default: { pos = vec2(-1.0, 3.0); }
+    }
+    var out: VertexOutput;
+    out.clip_position = vec4<f32>(pos, 0.0, 1.0);
+    out.uv = vec2<f32>(pos.x * 0.5 + 0.5, 0.5 - pos.y * 0.5);
+    return out;
+}
+
+// "Lub-dub": a sharp thump at the start of the beat and a softer one just after, each decaying fast
+fn pulse(phase: f32) -> f32 {
+    let lub = exp(-phase * 14.0);
+    let dub = 0.6 * exp(-max(phase - DUB_PHASE, 0.0) * 14.0) * step(DUB_PHASE, phase);
+    return max(lub, dub);
+}
+
+// Fragment shader
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    let beat = pulse(uniforms.phase) * uniforms.intensity;
+
+    // Offset from the center, with x scaled so distances are round on screen
+    let centered = in.uv - vec2<f32>(0.5);
+    let round = vec2<f32>(centered.x * uniforms.aspect, centered.y);
+    let radius = length(round) / length(vec2<f32>(uniforms.aspect * 0.5, 0.5));
+
+    // Barrel: pull the samples toward the center so the picture bulges out with each beat
+    let barrel = 1.0 - MAX_BARREL * beat * radius * radius;
+    let uv = vec2<f32>(0.5) + centered * barrel;
+
+    // Split red and blue apart along the radius, only toward the edges
+    let edge = smoothstep(0.35, 1.0, radius);
+    let split = centered * (MAX_ABERRATION * uniforms.intensity * (0.4 + 0.6 * beat) * edge);
+
+    let red = textureSample(scene_texture, scene_sampler, uv + split).r;
+    let green = textureSample(scene_texture, scene_sampler, uv).g;
+    let blue = textureSample(scene_texture, scene_sampler, uv - split).b;
+    return vec4<f32>(red, green, blue, 1.0);
+}
@@ -0,0 +1,49 @@
+// Frame heatmap shader - translucent tiles colored by each cell's frame cost
+
+struct HeatmapUniforms {
+    view_proj_matrix: mat4x4<f32>,
+    // Width of a tile, a little under the cell size so neighbouring cells stay distinct
+    tile_size: f32,
+    // Lift above the floor so tiles don't z-fight with it
+    lift: f32,
+    _padding: vec2<f32>,
+}
+
+struct VertexInput {
+    @location(0) corner: vec2<f32>,
+}
+
+struct InstanceInput {
+    // xyz = cell center on the floor, w unused
+    @location(1) position: vec4<f32>,
+    @location(2) color: vec4<f32>,
+}
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) color: vec4<f32>,
+}
+
+@group(0) @binding(0)
+var<uniform> uniforms: HeatmapUniforms;
+
+@vertex
+fn vs_main(vertex: VertexInput, instance: InstanceInput) -> VertexOutput {
+    var out: VertexOutput;
+
+    let world_position = vec3<f32>(
+        instance.position.x + vertex.corner.x * uniforms.tile_size,
+        instance.position.y + uniforms.lift,
+        instance.position.z + vertex.corner.y * uniforms.tile_size,
+    );
+
+    out.clip_position = uniforms.view_proj_matrix * vec4<f32>(world_position, 1.0);
+    out.color = instance.color;
+
+    return out;
+}
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    return in.color;
+}
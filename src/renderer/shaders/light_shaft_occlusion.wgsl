@@ -0,0 +1,71 @@
+// Light shaft occlusion shader - measures how much of the exit's beacon the scene hides and
+// blends it into a one-pixel running average
+
+struct LightShaftUniforms {
+    // Beacon position on the surface, from 0.0 to 1.0 with y down
+    beacon_uv: vec2<f32>,
+    // Beacon depth as the depth buffer stores it
+    beacon_depth: f32,
+    // Brightness of the shafts, from 0.0 to 1.0
+    intensity: f32,
+    // Top-left corner of the 3D view on the surface
+    view_min: vec2<f32>,
+    // Bottom-right corner of the 3D view on the surface
+    view_max: vec2<f32>,
+    // Surface width divided by height, so the glow is round rather than stretched
+    aspect: f32,
+    // Radius of the beacon's glow as a fraction of the surface height
+    glow_radius: f32,
+    _pad0: f32,
+    _pad1: f32,
+    // Linear color of the light
+    color: vec4<f32>,
+};
+
+@group(0) @binding(0)
+var<uniform> uniforms: LightShaftUniforms;
+
+@group(1) @binding(0)
+var depth_texture: texture_depth_2d;
+
+// Samples on the ring around the beacon's center
+const RING_SAMPLES: i32 = 12;
+// Radius of the ring as a fraction of the glow radius
+const RING_RADIUS: f32 = 0.6;
+
+// Vertex shader
+@vertex
+fn vs_main(@builtin(vertex_index) vertex_index: u32) -> @builtin(position) vec4<f32> {
+    // Full-screen triangle over the one-pixel target
+    var pos: vec2<f32>;
+    switch (vertex_index) {
+        case 0u: { pos = vec2(-1.0, -1.0); }
+        case 1u: { pos = vec2(3.0, -1.0); }
+        default: { pos = vec2(-1.0, 3.0); }
+    }
+    return vec4<f32>(pos, 0.0, 1.0);
+}
+
+// 1.0 where nothing in the scene is in front of the beacon, 0.0 where something is
+fn open_at(uv: vec2<f32>) -> f32 {
+    let size = vec2<f32>(textureDimensions(depth_texture));
+    let inside = clamp(uv, uniforms.view_min, uniforms.view_max);
+    let pixel = vec2<i32>(clamp(inside * size, vec2<f32>(0.0), size - 1.0));
+    let depth = textureLoad(depth_texture, pixel, 0);
+    return select(0.0, 1.0, depth >= uniforms.beacon_depth);
+}
+
+// Fragment shader
+@fragment
+fn fs_main() -> @location(0) vec4<f32> {
+    // The center counts as much as the whole ring, so a wall edge crossing it dims the light
+    // smoothly instead of snapping it off
+    var open = open_at(uniforms.beacon_uv) * f32(RING_SAMPLES);
+    let radius = uniforms.glow_radius * RING_RADIUS;
+    for (var i = 0; i < RING_SAMPLES; i = i + 1) {
+        let angle = 6.2831853 * f32(i) / f32(RING_SAMPLES);
+        let offset = vec2<f32>(cos(angle) / uniforms.aspect, sin(angle)) * radius;
+        open = open + open_at(uniforms.beacon_uv + offset);
+    }
+    return vec4<f32>(open / f32(2 * RING_SAMPLES), 0.0, 0.0, 1.0);
+}
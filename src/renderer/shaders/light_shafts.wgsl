@@ -0,0 +1,128 @@
+// Light shafts shader - streaks light from the exit's beacon across the finished 3D view,
+// cut off wherever the scene stands in front of it
+
+struct LightShaftUniforms {
+    // Beacon position on the surface, from 0.0 to 1.0 with y down
+    beacon_uv: vec2<f32>,
+    // Beacon depth as the depth buffer stores it
+    beacon_depth: f32,
+    // Brightness of the shafts, from 0.0 to 1.0
+    intensity: f32,
+    // Top-left corner of the 3D view on the surface
+    view_min: vec2<f32>,
+    // Bottom-right corner of the 3D view on the surface
+    view_max: vec2<f32>,
+    // Surface width divided by height, so the glow is round rather than stretched
+    aspect: f32,
+    // Radius of the beacon's glow as a fraction of the surface height
+    glow_radius: f32,
+    _pad0: f32,
+    _pad1: f32,
+    // Linear color of the light
+    color: vec4<f32>,
+};
+
+@group(0) @binding(0)
+var<uniform> uniforms: LightShaftUniforms;
+
+// Running average of how much of the beacon is in view, from the occlusion pass
+@group(1) @binding(0)
+var visibility_texture: texture_2d<f32>;
+
+@group(2) @binding(0)
+var scene_texture: texture_2d<f32>;
+@group(2) @binding(1)
+var scene_sampler: sampler;
+
+@group(3) @binding(0)
+var depth_texture: texture_depth_2d;
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+};
+
+// Samples taken along each pixel's ray toward the beacon
+const SAMPLES: i32 = 24;
+// How much of the way to the beacon the samples cover
+const DENSITY: f32 = 0.92;
+// Brightness kept from one sample to the next, so far samples count less
+const DECAY: f32 = 0.95;
+// Brightness each sample adds
+const WEIGHT: f32 = 0.06;
+// How many glow radii the light source spreads over
+const SOURCE_SPREAD: f32 = 3.0;
+// Brightness of the glow around the beacon itself
+const HALO: f32 = 0.5;
+
+// Vertex shader
+@vertex
+fn vs_main(@builtin(vertex_index) vertex_index: u32) -> VertexOutput {
+    // Full-screen triangle
+    var pos: vec2<f32>;
+    switch (vertex_index) {
+        case 0u: { pos = vec2(-1.0, -1.0); }
+        case 1u: { pos = vec2(3.0, -1.0); }
+        default: { pos = vec2(-1.0, 3.0); }
+    }
+    var out: VertexOutput;
+    out.clip_position = vec4<f32>(pos, 0.0, 1.0);
+    out.uv = vec2<f32>(pos.x * 0.5 + 0.5, 0.5 - pos.y * 0.5);
+    return out;
+}
+
+// How bright the beacon's glow is at a point, with distances round on screen
+fn glow_at(uv: vec2<f32>, spread: f32) -> f32 {
+    let offset = (uv - uniforms.beacon_uv) * vec2<f32>(uniforms.aspect, 1.0);
+    let d = length(offset) / (uniforms.glow_radius * spread);
+    return exp(-d * d);
+}
+
+// 1.0 inside the 3D view, 0.0 in the letterbox around it
+fn in_view(uv: vec2<f32>) -> f32 {
+    let inside = step(uniforms.view_min, uv) * step(uv, uniforms.view_max);
+    return inside.x * inside.y;
+}
+
+// The light a point adds to the rays: the glow, wherever nothing stands in front of the beacon
+fn source_at(uv: vec2<f32>) -> f32 {
+    let size = vec2<f32>(textureDimensions(depth_texture));
+    let pixel = vec2<i32>(clamp(uv * size, vec2<f32>(0.0), size - 1.0));
+    let depth = textureLoad(depth_texture, pixel, 0);
+    let open = select(0.0, 1.0, depth >= uniforms.beacon_depth);
+    return glow_at(uv, SOURCE_SPREAD) * open * in_view(uv);
+}
+
+fn visibility() -> f32 {
+    return textureLoad(visibility_texture, vec2<i32>(0, 0), 0).r;
+}
+
+// Fragment shader: the scene with the shafts added
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    let scene = textureSample(scene_texture, scene_sampler, in.uv);
+    let strength = uniforms.intensity * visibility() * in_view(in.uv);
+
+    // March toward the beacon, gathering the light that isn't blocked on the way
+    let step_uv = (uniforms.beacon_uv - in.uv) * (DENSITY / f32(SAMPLES));
+    var uv = in.uv;
+    var falloff = 1.0;
+    var rays = 0.0;
+    for (var i = 0; i < SAMPLES; i = i + 1) {
+        uv = uv + step_uv;
+        rays = rays + source_at(uv) * falloff * WEIGHT;
+        falloff = falloff * DECAY;
+    }
+
+    let light = (rays + glow_at(in.uv, 1.0) * HALO) * strength;
+    return vec4<f32>(scene.rgb + uniforms.color.rgb * light, scene.a);
+}
+
+// Fragment shader for reduced effects: only a brighter glow around the beacon, added over
+// the scene
+@fragment
+fn fs_glow(in: VertexOutput) -> @location(0) vec4<f32> {
+    let strength = uniforms.intensity * visibility() * in_view(in.uv);
+    let light = glow_at(in.uv, 1.5) * strength;
+    return vec4<f32>(uniforms.color.rgb * light, 0.0);
+}
@@ -1,8 +1,10 @@
 struct LoadingBarUniforms {
     progress: f32,
     time: f32,
+    // Size of the region the bar spans
     resolution: vec2<f32>,
-    _padding: vec2<f32>,
+    // Top-left corner of that region in pixels
+    origin: vec2<f32>,
 }
 
 @group(0) @binding(0)
@@ -114,7 +116,7 @@ fn pattern(p: vec2<f32>) -> f32 {
 @fragment
 fn fs_main(@builtin(position) frag_position: vec4<f32>) -> @location(0) vec4<f32> {
     let fragCoord = frag_position.xy;
-    let uv: vec2<f32> = fragCoord / uniforms.resolution.x;
+    let uv: vec2<f32> = (fragCoord - uniforms.origin) / uniforms.resolution.x;
 
     // Calculate if we're in the progress area
     let progress_mask = step(uv.x, uniforms.progress);
@@ -12,9 +12,11 @@
 //!   clip-space position, color, world-space XZ position, and material ID.
 //!
 //! ## Entry Points
-//! - `vs_main`: Vertex shader. Transforms vertex positions by the MVP matrix, passes color and material.
+//! - `vs_main`: Vertex shader. Breathes walls along their normal, transforms vertex positions by the
+//!   MVP matrix, passes color and material.
 //! - `fs_main`: Fragment shader. Applies material-based coloring. Walls are solid maroon; the floor
-//!   uses a checkerboard pattern based on world position.
+//!   uses a checkerboard pattern based on world position. The ambient multiplier and distance fog
+//!   are applied afterward.
 //!
 //! ## Material Logic
 //! - `material == 0`: Floor cell, colored with a checkerboard pattern alternating between tan and purple.
@@ -30,6 +32,8 @@ struct VertexInput {
     @location(2) material: u32,
     /// Texture coordinates for texturing (used for ceiling).
     @location(3) tex_coords: vec2<f32>,
+    /// Axis the vertex breathes along; zero for surfaces that stay still.
+    @location(4) normal: vec3<f32>,
 };
 
 struct VertexOutput {
@@ -43,15 +47,30 @@ struct VertexOutput {
     @location(2) material: u32,
     /// Texture coordinates for texturing (used for ceiling).
     @location(3) tex_coords: vec2<f32>,
+    /// Distance from the camera along the view direction, used for fog.
+    @location(4) view_depth: f32,
 };
 
-/// Uniforms structure now includes time for animation
+/// Uniforms structure includes time for animation and distance fog settings
 struct Uniforms {
     mvp_matrix: mat4x4<f32>,
     time: f32,
-    _padding: vec3<f32>, // Padding for alignment
+    fog_start: f32,
+    fog_end: f32,
+    fog_strength: f32,
+    fog_color: vec4<f32>,
+    /// How far walls breathe, in world units. Bounded by `MAX_WALL_BREATH` on the CPU side.
+    wall_breath: f32,
+    /// 1.0 when debug bounding boxes breathe with the walls.
+    bounding_box_breath: f32,
+    /// Multiplier on the shaded color, set by the time of day. 1.0 leaves it unchanged.
+    ambient: f32,
+    _pad0: f32,
 };
 
+/// Angular speed of the breathing, in radians per second (one breath every ~8 seconds).
+const BREATH_SPEED: f32 = 0.8;
+
 /// Updated uniform binding
 @group(0) @binding(0)
 var<uniform> uniforms: Uniforms;
@@ -69,14 +88,30 @@ var ceiling_sampler: sampler;
 @vertex
 fn vs_main(in: VertexInput) -> VertexOutput {
     var out: VertexOutput;
-    out.clip_position = uniforms.mvp_matrix * vec4<f32>(in.position, 1.0);
+    let position = in.position + breath_offset(in);
+    out.clip_position = uniforms.mvp_matrix * vec4<f32>(position, 1.0);
     out.fragment_color = in.color;
     out.world_position = in.position.xz;
     out.material = in.material;
     out.tex_coords = in.tex_coords;
+    out.view_depth = out.clip_position.w;
     return out;
 }
 
+/// How far breathing pushes a vertex off its resting position.
+/// Only walls breathe (and bounding boxes, when asked to); the floor and ceiling stay put.
+/// The phase is hashed from the rounded XZ corner, so every vertex on a wall corner, and the
+/// collision face box drawn over it, swells together.
+fn breath_offset(in: VertexInput) -> vec3<f32> {
+    let breathes = in.material == 1u
+        || (in.material == 2u && uniforms.bounding_box_breath > 0.5);
+    if (!breathes || uniforms.wall_breath <= 0.0) {
+        return vec3<f32>(0.0);
+    }
+    let phase = rand(floor(in.position.xz + 0.5)) * 6.2831853;
+    return in.normal * uniforms.wall_breath * sin(uniforms.time * BREATH_SPEED + phase);
+}
+
 // Portal effect functions (from your portal shader)
 fn colormap_red(x: f32) -> f32 {
     return 0.0; // Minimal red for green output
@@ -154,9 +189,28 @@ fn pattern(p: vec2<f32>) -> f32 {
 }
 
 /// Fragment shader entry point.
-/// Applies material-based coloring: walls are maroon, floor is a checkerboard, exit gets portal effect.
+/// Shades the fragment by material and scales it by the ambient multiplier, then blends it
+/// toward the fog color by view distance. Debug bounding boxes are never darkened or fogged.
 @fragment
 fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    let shaded = material_color(in);
+    if (in.material == 2u) {
+        return shaded;
+    }
+    let color = vec4<f32>(shaded.rgb * uniforms.ambient, shaded.a);
+    if (uniforms.fog_strength <= 0.0) {
+        return color;
+    }
+
+    let fog_range = max(uniforms.fog_end - uniforms.fog_start, 0.001);
+    let fog_amount = clamp((in.view_depth - uniforms.fog_start) / fog_range, 0.0, 1.0)
+        * uniforms.fog_strength;
+    return vec4<f32>(mix(color.rgb, uniforms.fog_color.rgb, fog_amount), color.a);
+}
+
+/// Returns the unfogged color for the fragment's material.
+/// Walls are maroon, floor is a checkerboard, exit gets portal effect.
+fn material_color(in: VertexOutput) -> vec4<f32> {
     // Material-based coloring
     if (in.material == 1u) {
         // Wall: Maroon
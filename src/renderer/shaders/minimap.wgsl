@@ -0,0 +1,104 @@
+struct MinimapUniforms {
+    // Left edge, top edge, width, and height of the map in pixels
+    rect: vec4<f32>,
+    // Columns and rows of the maze grid
+    grid_size: vec2<f32>,
+    // Where the player stands, in cells
+    player: vec2<f32>,
+    // Unit vector the player faces, in cells
+    heading: vec2<f32>,
+    // 1.0 when rendering to an sRGB target, which expects linear colors
+    linear_output: f32,
+    // Length of the player arrow in pixels
+    marker_size: f32,
+};
+
+@group(0) @binding(0)
+var<uniform> uniforms: MinimapUniforms;
+
+// One texel per grid cell: 0 unexplored, 1 wall, 2 visited, 3 exit
+@group(0) @binding(1)
+var cells: texture_2d<f32>;
+
+// Width of the frame around the map in pixels
+const FRAME_WIDTH: f32 = 3.0;
+
+const PANEL_COLOR: vec4<f32> = vec4<f32>(0.04, 0.05, 0.07, 0.55);
+const FRAME_COLOR: vec4<f32> = vec4<f32>(0.75, 0.65, 0.4, 0.8);
+const WALL_COLOR: vec4<f32> = vec4<f32>(0.55, 0.57, 0.62, 0.9);
+const VISITED_COLOR: vec4<f32> = vec4<f32>(0.2, 0.26, 0.34, 0.85);
+const EXIT_COLOR: vec4<f32> = vec4<f32>(0.3, 0.95, 0.45, 1.0);
+const MARKER_COLOR: vec4<f32> = vec4<f32>(1.0, 0.85, 0.2, 1.0);
+const MARKER_OUTLINE_COLOR: vec4<f32> = vec4<f32>(0.0, 0.0, 0.0, 0.9);
+
+// Vertex shader
+@vertex
+fn vs_main(@builtin(vertex_index) vertex_index: u32) -> @builtin(position) vec4<f32> {
+    // Full-screen triangle
+    var pos: vec2<f32>;
+    switch (vertex_index) {
+        case 0u: { pos = vec2(-1.0, -1.0); }
+        case 1u: { pos = vec2(3.0, -1.0); }
+        default: { pos = vec2(-1.0, 3.0); }
+    }
+    return vec4<f32>(pos, 0.0, 1.0);
+}
+
+// All colors in this shader are sRGB design values; convert them for the target once on output
+fn srgb_to_linear(c: vec3<f32>) -> vec3<f32> {
+    let low = c / 12.92;
+    let high = pow((c + 0.055) / 1.055, vec3<f32>(2.4));
+    return select(high, low, c <= vec3<f32>(0.04045));
+}
+
+fn to_target(color: vec4<f32>) -> vec4<f32> {
+    if (uniforms.linear_output > 0.5) {
+        return vec4<f32>(srgb_to_linear(clamp(color.rgb, vec3<f32>(0.0), vec3<f32>(1.0))), color.a);
+    }
+    return color;
+}
+
+// Whether a point is inside an arrow of the given length pointing along the heading
+fn in_arrow(offset: vec2<f32>, length: f32) -> bool {
+    let heading = uniforms.heading;
+    let along = dot(offset, heading);
+    let side = dot(offset, vec2<f32>(-heading.y, heading.x));
+    // 0.0 at the tip, 1.0 at the base, which is 0.6 of the length behind the player
+    let t = (0.6 * length - along) / (1.2 * length);
+    return t >= 0.0 && t <= 1.0 && abs(side) <= t * 0.45 * length;
+}
+
+// Fragment shader
+@fragment
+fn fs_main(@builtin(position) frag_coord: vec4<f32>) -> @location(0) vec4<f32> {
+    let origin = uniforms.rect.xy;
+    let size = uniforms.rect.zw;
+    let local = frag_coord.xy - origin;
+    if (any(local < vec2<f32>(-FRAME_WIDTH)) || any(local > size + vec2<f32>(FRAME_WIDTH))) {
+        discard;
+    }
+
+    var color = FRAME_COLOR;
+    if (all(local >= vec2<f32>(0.0)) && all(local < size)) {
+        let grid_size = vec2<i32>(uniforms.grid_size);
+        let cell = clamp(vec2<i32>(floor(local / size * uniforms.grid_size)), vec2<i32>(0), grid_size - 1);
+        let state = u32(round(textureLoad(cells, cell, 0).r * 255.0));
+        switch (state) {
+            case 1u: { color = WALL_COLOR; }
+            case 2u: { color = VISITED_COLOR; }
+            case 3u: { color = EXIT_COLOR; }
+            default: { color = PANEL_COLOR; }
+        }
+    }
+
+    // The player arrow goes over everything, frame included, with a dark outline
+    let player = origin + uniforms.player / uniforms.grid_size * size;
+    let offset = frag_coord.xy - player;
+    if (in_arrow(offset, uniforms.marker_size)) {
+        color = MARKER_COLOR;
+    } else if (in_arrow(offset, uniforms.marker_size + 3.0)) {
+        color = MARKER_OUTLINE_COLOR;
+    }
+
+    return to_target(color);
+}
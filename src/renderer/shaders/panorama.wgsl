@@ -0,0 +1,64 @@
+struct PanoramaUniforms {
+    // Inverse of the rotation-only view-projection, clip space to world directions
+    inverse_view_proj: mat4x4<f32>,
+};
+
+@group(0) @binding(0)
+var<uniform> uniforms: PanoramaUniforms;
+
+@group(1) @binding(0)
+var panorama_texture: texture_2d<f32>;
+@group(1) @binding(1)
+var panorama_sampler: sampler;
+
+const PI: f32 = 3.14159265;
+const TAU: f32 = 6.2831853;
+
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) ndc: vec2<f32>,
+};
+
+// Vertex shader
+@vertex
+fn vs_main(@builtin(vertex_index) vertex_index: u32) -> VertexOutput {
+    // Full-screen triangle
+    var pos: vec2<f32>;
+    switch (vertex_index) {
+        case 0u: { pos = vec2(-1.0, -1.0); }
+        case 1u: { pos = vec2(3.0, -1.0); }
+        default: { pos = vec2(-1.0, 3.0); }
+    }
+    var out: VertexOutput;
+    out.position = vec4<f32>(pos, 0.0, 1.0);
+    out.ndc = pos;
+    return out;
+}
+
+// Fragment shader
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    // Same reconstruction as math::ray::view_ray, through the far plane
+    let world = uniforms.inverse_view_proj * vec4<f32>(in.ndc, 1.0, 1.0);
+    let direction = normalize(world.xyz / world.w);
+
+    // Same mapping as math::ray::equirect_uv
+    let u = 0.5 + atan2(direction.x, -direction.z) / TAU;
+    let v = 0.5 - asin(clamp(direction.y, -1.0, 1.0)) / PI;
+
+    // u jumps from 1 to 0 straight behind the camera, which would pick the smallest mip along
+    // that column; take the gradient of a copy whose jump is straight ahead instead
+    let u_shifted = fract(u + 0.5);
+    let du = vec2<f32>(dpdx(u), dpdy(u));
+    let du_shifted = vec2<f32>(dpdx(u_shifted), dpdy(u_shifted));
+    let du_dx = select(du.x, du_shifted.x, abs(du_shifted.x) < abs(du.x));
+    let du_dy = select(du.y, du_shifted.y, abs(du_shifted.y) < abs(du.y));
+
+    return textureSampleGrad(
+        panorama_texture,
+        panorama_sampler,
+        vec2<f32>(u, v),
+        vec2<f32>(du_dx, dpdx(v)),
+        vec2<f32>(du_dy, dpdy(v)),
+    );
+}
@@ -0,0 +1,59 @@
+// Particle shader - soft round billboards that always face the camera
+
+struct ParticleUniforms {
+    view_proj_matrix: mat4x4<f32>,
+    camera_right: vec4<f32>,
+    camera_up: vec4<f32>,
+}
+
+struct VertexInput {
+    @location(0) corner: vec2<f32>,
+}
+
+struct InstanceInput {
+    // xyz = particle position, w = billboard width
+    @location(1) position_size: vec4<f32>,
+    // rgb = color, a = opacity already faded by age
+    @location(2) color: vec4<f32>,
+}
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) local: vec2<f32>,
+    @location(1) color: vec4<f32>,
+}
+
+@group(0) @binding(0)
+var<uniform> uniforms: ParticleUniforms;
+
+@vertex
+fn vs_main(vertex: VertexInput, instance: InstanceInput) -> VertexOutput {
+    var out: VertexOutput;
+
+    let size = instance.position_size.w;
+
+    // Spread the quad along the camera axes so it always faces the viewer
+    let world_position = instance.position_size.xyz
+        + uniforms.camera_right.xyz * vertex.corner.x * size
+        + uniforms.camera_up.xyz * vertex.corner.y * size;
+
+    out.clip_position = uniforms.view_proj_matrix * vec4<f32>(world_position, 1.0);
+    out.local = vertex.corner * 2.0;
+    out.color = instance.color;
+
+    return out;
+}
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    // Soft round falloff from the center of the quad
+    let distance = length(in.local);
+    let softness = 1.0 - smoothstep(0.3, 1.0, distance);
+
+    let alpha = in.color.a * softness;
+    if (alpha <= 0.001) {
+        discard;
+    }
+
+    return vec4<f32>(in.color.rgb, alpha);
+}
@@ -0,0 +1,67 @@
+// Click-to-move path shader - small soft markers lying flat on the floor
+
+struct PathUniforms {
+    view_proj_matrix: mat4x4<f32>,
+    color: vec4<f32>,
+    base_size: f32,
+    // Lift above the floor so markers don't z-fight with it
+    lift: f32,
+    _padding: vec2<f32>,
+}
+
+struct VertexInput {
+    @location(0) corner: vec2<f32>,
+}
+
+struct InstanceInput {
+    // xyz = marker position on the floor, w = 1.0 for the destination, 0.0 otherwise
+    @location(1) position_destination: vec4<f32>,
+}
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) local: vec2<f32>,
+    @location(1) destination: f32,
+}
+
+@group(0) @binding(0)
+var<uniform> uniforms: PathUniforms;
+
+@vertex
+fn vs_main(vertex: VertexInput, instance: InstanceInput) -> VertexOutput {
+    var out: VertexOutput;
+
+    let destination = instance.position_destination.w;
+
+    // The destination marker is drawn larger than the ones along the way
+    let size = uniforms.base_size * (1.0 + 1.5 * destination);
+
+    let world_position = vec3<f32>(
+        instance.position_destination.x + vertex.corner.x * size,
+        instance.position_destination.y + uniforms.lift,
+        instance.position_destination.z + vertex.corner.y * size,
+    );
+
+    out.clip_position = uniforms.view_proj_matrix * vec4<f32>(world_position, 1.0);
+    out.local = vertex.corner * 2.0;
+    out.destination = destination;
+
+    return out;
+}
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    let distance = length(in.local);
+
+    // A soft dot along the way; the destination is a ring
+    let dot = 1.0 - smoothstep(0.5, 1.0, distance);
+    let ring = 1.0 - smoothstep(0.1, 0.25, abs(distance - 0.75));
+    let shape = mix(dot, ring, in.destination);
+
+    let alpha = uniforms.color.a * shape;
+    if (alpha <= 0.001) {
+        discard;
+    }
+
+    return vec4<f32>(uniforms.color.rgb, alpha);
+}
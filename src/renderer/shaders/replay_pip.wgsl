@@ -0,0 +1,73 @@
+struct PipUniforms {
+    // Left, top, width, and height of the replay in pixels
+    rect: vec4<f32>,
+    // Surface width and height in pixels
+    screen_size: vec2<f32>,
+    corner_radius: f32,
+    marker_radius: f32,
+    // Color of the dot marking the player
+    marker_color: vec4<f32>,
+};
+
+@group(0) @binding(0)
+var<uniform> uniforms: PipUniforms;
+
+@group(1) @binding(0)
+var t_replay: texture_2d<f32>;
+@group(1) @binding(1)
+var s_replay: sampler;
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    // Position inside the replay rectangle in pixels, from its top-left corner
+    @location(0) local: vec2<f32>,
+};
+
+@vertex
+fn vs_main(@builtin(vertex_index) vertex_index: u32) -> VertexOutput {
+    // Two triangles covering the rectangle
+    var corner: vec2<f32>;
+    switch (vertex_index) {
+        case 0u, 3u: { corner = vec2(0.0, 0.0); }
+        case 1u: { corner = vec2(1.0, 0.0); }
+        case 2u, 4u: { corner = vec2(1.0, 1.0); }
+        default: { corner = vec2(0.0, 1.0); }
+    }
+    let size = uniforms.rect.zw;
+    let pixel = uniforms.rect.xy + corner * size;
+    let ndc = vec2<f32>(
+        pixel.x / uniforms.screen_size.x * 2.0 - 1.0,
+        1.0 - pixel.y / uniforms.screen_size.y * 2.0,
+    );
+
+    var out: VertexOutput;
+    out.clip_position = vec4<f32>(ndc, 0.0, 1.0);
+    out.local = corner * size;
+    return out;
+}
+
+// Signed distance from a point to a rounded rectangle with its top-left corner at the origin
+fn sdf_rounded_rect(p: vec2<f32>, size: vec2<f32>, radius: f32) -> f32 {
+    let half_size = size * 0.5;
+    let d = abs(p - half_size) - half_size + radius;
+    return length(max(d, vec2<f32>(0.0))) + min(max(d.x, d.y), 0.0) - radius;
+}
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    let size = uniforms.rect.zw;
+    var color = textureSample(t_replay, s_replay, in.local / size).rgb;
+
+    // The replay camera is centered on the player, so the marker sits in the middle
+    let marker = 1.0 - smoothstep(
+        uniforms.marker_radius - 1.0,
+        uniforms.marker_radius + 1.0,
+        length(in.local - size * 0.5),
+    );
+    color = mix(color, uniforms.marker_color.rgb, marker * uniforms.marker_color.a);
+
+    // Mask the corners, antialiased over about a pixel
+    let distance = sdf_rounded_rect(in.local, size, uniforms.corner_radius);
+    let alpha = 1.0 - smoothstep(-1.0, 1.0, distance);
+    return vec4<f32>(color, alpha);
+}
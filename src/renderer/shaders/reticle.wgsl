@@ -0,0 +1,72 @@
+struct ReticleUniforms {
+    // Already converted for the render target
+    color: vec4<f32>,
+    // Center of the reticle in pixels from the top-left corner
+    center: vec2<f32>,
+    size: f32,
+    // 0.0 at rest, 1.0 while an interactable is targeted
+    expansion: f32,
+    // 1.0 for a dot, 2.0 for a cross, 3.0 for a ring
+    style: f32,
+    _pad0: f32,
+    _pad1: f32,
+    _pad2: f32,
+};
+
+@group(0) @binding(0)
+var<uniform> uniforms: ReticleUniforms;
+
+// Vertex shader
+@vertex
+fn vs_main(@builtin(vertex_index) vertex_index: u32) -> @builtin(position) vec4<f32> {
+    // Full-screen triangle; the scissor rect keeps the work to the reticle's square
+    var pos: vec2<f32>;
+    switch (vertex_index) {
+        case 0u: { pos = vec2(-1.0, -1.0); }
+        case 1u: { pos = vec2(3.0, -1.0); }
+        default: { pos = vec2(-1.0, 3.0); }
+    }
+    return vec4<f32>(pos, 0.0, 1.0);
+}
+
+// Signed distance to an axis-aligned box centered on the origin
+fn sd_box(p: vec2<f32>, half_size: vec2<f32>) -> f32 {
+    let d = abs(p) - half_size;
+    return length(max(d, vec2(0.0))) + min(max(d.x, d.y), 0.0);
+}
+
+// One pixel of antialiasing around a signed distance
+fn coverage(distance: f32) -> f32 {
+    return clamp(0.5 - distance, 0.0, 1.0);
+}
+
+// Fragment shader
+@fragment
+fn fs_main(@builtin(position) frag_coord: vec4<f32>) -> @location(0) vec4<f32> {
+    let p = frag_coord.xy - uniforms.center;
+    let scale = 1.0 + 0.35 * uniforms.expansion;
+    let half_size = uniforms.size * 0.5 * scale;
+
+    var shape: f32;
+    if (uniforms.style > 2.5) {
+        let thickness = max(uniforms.size * 0.15, 0.75);
+        shape = abs(length(p) - half_size) - thickness;
+    } else if (uniforms.style > 1.5) {
+        let thickness = max(uniforms.size * 0.15, 0.75);
+        let horizontal = sd_box(p, vec2(half_size, thickness));
+        let vertical = sd_box(p, vec2(thickness, half_size));
+        shape = min(horizontal, vertical);
+    } else {
+        shape = length(p) - half_size;
+    }
+
+    // Interaction glyph: a thin ring that grows out of the reticle as it expands
+    let ring_radius = uniforms.size * (0.9 + 0.9 * uniforms.expansion) + 1.5;
+    let ring = abs(length(p) - ring_radius) - 0.75;
+
+    let alpha = max(coverage(shape), coverage(ring) * uniforms.expansion);
+    if (alpha <= 0.0) {
+        discard;
+    }
+    return vec4<f32>(uniforms.color.rgb, uniforms.color.a * alpha);
+}
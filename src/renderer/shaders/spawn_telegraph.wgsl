@@ -0,0 +1,117 @@
+// Spawn telegraph shader - a dark column of light over the cell a delayed enemy arrives in
+
+struct TelegraphUniforms {
+    view_proj_matrix: mat4x4<f32>,
+    // xyz = center of the spawn cell on the floor, w = full radius of the column
+    center_radius: vec4<f32>,
+    // Linear RGB of the column, a = opacity at full strength
+    color: vec4<f32>,
+    // How far the telegraph has built, from 0.0 to 1.0
+    progress: f32,
+    // 1.0 while the column collapses after the enemy arrived, else 0.0
+    collapsing: f32,
+    // Animation time in seconds
+    time: f32,
+    // Full height of the column in world units
+    height: f32,
+}
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    // x = around the column or across the stain, y = up the column or across the stain
+    @location(0) local: vec2<f32>,
+    // 0.0 for the column, 1.0 for the stain on the floor
+    @location(1) is_stain: f32,
+}
+
+@group(0) @binding(0)
+var<uniform> uniforms: TelegraphUniforms;
+
+// Sides of the column; must match COLUMN_SIDES in spawn_telegraph.rs
+const SIDES: u32 = 12u;
+const TAU: f32 = 6.2831853;
+
+// Corner of a two-triangle quad: x and y each 0.0 or 1.0
+fn quad_corner(slot: u32) -> vec2<f32> {
+    var corners = array<vec2<f32>, 6>(
+        vec2<f32>(0.0, 0.0),
+        vec2<f32>(1.0, 0.0),
+        vec2<f32>(0.0, 1.0),
+        vec2<f32>(1.0, 0.0),
+        vec2<f32>(1.0, 1.0),
+        vec2<f32>(0.0, 1.0),
+    );
+    return corners[slot];
+}
+
+// Radius of the column at this frame: it swells as it builds and pinches shut as it collapses
+fn column_radius() -> f32 {
+    let full = uniforms.center_radius.w;
+    if (uniforms.collapsing > 0.5) {
+        return full * uniforms.progress * uniforms.progress;
+    }
+    return full * (0.25 + 0.75 * uniforms.progress);
+}
+
+@vertex
+fn vs_main(@builtin(vertex_index) vertex_index: u32) -> VertexOutput {
+    var out: VertexOutput;
+
+    let quad = vertex_index / 6u;
+    let corner = quad_corner(vertex_index % 6u);
+    let center = uniforms.center_radius.xyz;
+    var world_position: vec3<f32>;
+
+    if (quad < SIDES) {
+        // One side of the column, from the floor up to its current height
+        let angle = (f32(quad) + corner.x) / f32(SIDES) * TAU;
+        let radius = column_radius();
+        let height = uniforms.height * uniforms.progress;
+        world_position = vec3<f32>(
+            center.x + cos(angle) * radius,
+            center.y + corner.y * height,
+            center.z + sin(angle) * radius,
+        );
+        out.local = vec2<f32>((f32(quad) + corner.x) / f32(SIDES), corner.y);
+        out.is_stain = 0.0;
+    } else {
+        // The stain spreading on the floor under the column
+        let size = uniforms.center_radius.w * (1.0 + 2.5 * uniforms.progress);
+        let xz = (corner * 2.0 - 1.0) * size;
+        world_position = vec3<f32>(center.x + xz.x, center.y + 1.0, center.z + xz.y);
+        out.local = corner * 2.0 - 1.0;
+        out.is_stain = 1.0;
+    }
+
+    out.clip_position = uniforms.view_proj_matrix * vec4<f32>(world_position, 1.0);
+    return out;
+}
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    let time = uniforms.time;
+    var alpha: f32;
+    var color = uniforms.color.rgb;
+
+    if (in.is_stain > 0.5) {
+        // A dark, slowly turning stain with ripples pulled in toward the center
+        let distance = length(in.local);
+        let angle = atan2(in.local.y, in.local.x);
+        let ripple = 0.75 + 0.25 * sin(distance * 18.0 + time * 4.0 + angle * 3.0);
+        alpha = (1.0 - smoothstep(0.3, 1.0, distance)) * ripple;
+        color = color * 0.35;
+    } else {
+        // Bands streaming down the column, fading out toward its top
+        let bands = 0.6 + 0.4 * sin(in.local.y * 24.0 + time * 7.0 + in.local.x * TAU * 2.0);
+        let fade = 1.0 - smoothstep(0.35, 1.0, in.local.y);
+        alpha = bands * fade;
+        // The collapse flares once before the column pinches shut
+        color = color * (1.0 + uniforms.collapsing * 1.5);
+    }
+
+    alpha = alpha * uniforms.color.a * uniforms.progress;
+    if (alpha <= 0.001) {
+        discard;
+    }
+    return vec4<f32>(min(color, vec3<f32>(1.0)), alpha);
+}
@@ -17,6 +17,10 @@ var<uniform> time: f32;
 @group(0) @binding(1)
 var<uniform> background_color: vec4<f32>;
 
+// RGB tint, and in alpha how far the stars are blended toward it
+@group(0) @binding(2)
+var<uniform> star_tint: vec4<f32>;
+
 @vertex
 fn vs_main(vertex: VertexInput) -> VertexOutput {
     var out: VertexOutput;
@@ -129,8 +133,8 @@ fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
     let base_brightness = in.brightness * combined_twinkle;
     let final_brightness = base_brightness + flash_intensity * 0.5;
 
-    // Calculate star color as opposite of background
-    let star_color = opposite_color(background_color.rgb);
+    // Calculate star color as opposite of background, blended toward the tint
+    let star_color = mix(opposite_color(background_color.rgb), star_tint.rgb, star_tint.a);
 
     // Apply brightness to the star color
     let final_color = star_color * final_brightness;
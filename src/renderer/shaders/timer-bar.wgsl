@@ -1,8 +1,12 @@
 struct TimerBarUniforms {
     progress: f32,
     time: f32,
+    // Size of the HUD safe area the bar is laid out in
     resolution: vec2<f32>,
-    _padding: vec2<f32>,
+    // 1.0 when rendering to an sRGB target, which expects linear colors
+    linear_output: f32,
+    // Left edge of the HUD safe area in pixels
+    origin_x: f32,
 };
 
 @group(0) @binding(0)
@@ -21,6 +25,20 @@ fn vs_main(@builtin(vertex_index) vertex_index: u32) -> @builtin(position) vec4<
     return vec4<f32>(pos, 0.0, 1.0);
 }
 
+// All colors in this shader are sRGB design values; convert them for the target once on output
+fn srgb_to_linear(c: vec3<f32>) -> vec3<f32> {
+    let low = c / 12.92;
+    let high = pow((c + 0.055) / 1.055, vec3<f32>(2.4));
+    return select(high, low, c <= vec3<f32>(0.04045));
+}
+
+fn to_target(color: vec4<f32>) -> vec4<f32> {
+    if (uniforms.linear_output > 0.5) {
+        return vec4<f32>(srgb_to_linear(clamp(color.rgb, vec3<f32>(0.0), vec3<f32>(1.0))), color.a);
+    }
+    return color;
+}
+
 // Enhanced contrast colormap with warmer highlights and cooler shadows
 fn colormap_red(x: f32) -> f32 {
     // Enhance contrast curve and add warmth to highlights
@@ -181,7 +199,7 @@ fn fs_main(@builtin(position) frag_position: vec4<f32>) -> @location(0) vec4<f32
     let bar_width = res.x / 3.0;
     let bar_height = res.y * 0.04;
     let margin_top = res.y * 0.04;
-    let bar_left = (res.x - bar_width) / 2.0;
+    let bar_left = uniforms.origin_x + (res.x - bar_width) / 2.0;
     let bar_top = margin_top;
     
     // --- Convert to local coordinates centered at bar center ---
@@ -317,9 +335,9 @@ fn fs_main(@builtin(position) frag_position: vec4<f32>) -> @location(0) vec4<f32
     // --- Combine shadow and bar ---
     if (mask > 0.01) {
         // We're in the bar area - show the bar only (no shadow blending)
-        return bar_color;
+        return to_target(bar_color);
     } else {
         // We're in the shadow area only
-        return vec4<f32>(shadow_color, final_shadow_alpha);
+        return to_target(vec4<f32>(shadow_color, final_shadow_alpha));
     }
 }
\ No newline at end of file
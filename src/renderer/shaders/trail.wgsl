@@ -0,0 +1,63 @@
+// Enemy trail shader - soft fading splotches lying flat on the floor
+
+struct TrailUniforms {
+    view_proj_matrix: mat4x4<f32>,
+    color: vec4<f32>,
+    base_size: f32,
+    floor_height: f32,
+    _padding: vec2<f32>,
+}
+
+struct VertexInput {
+    @location(0) corner: vec2<f32>,
+}
+
+struct InstanceInput {
+    // xyz = breadcrumb position, w = fade (1.0 fresh, 0.0 expired)
+    @location(1) position_fade: vec4<f32>,
+}
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) local: vec2<f32>,
+    @location(1) fade: f32,
+}
+
+@group(0) @binding(0)
+var<uniform> uniforms: TrailUniforms;
+
+@vertex
+fn vs_main(vertex: VertexInput, instance: InstanceInput) -> VertexOutput {
+    var out: VertexOutput;
+
+    let fade = instance.position_fade.w;
+
+    // Older breadcrumbs shrink as they fade
+    let size = uniforms.base_size * (0.4 + 0.6 * fade);
+
+    let world_position = vec3<f32>(
+        instance.position_fade.x + vertex.corner.x * size,
+        uniforms.floor_height,
+        instance.position_fade.z + vertex.corner.y * size,
+    );
+
+    out.clip_position = uniforms.view_proj_matrix * vec4<f32>(world_position, 1.0);
+    out.local = vertex.corner * 2.0;
+    out.fade = fade;
+
+    return out;
+}
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    // Soft round falloff from the center of the quad
+    let distance = length(in.local);
+    let softness = 1.0 - smoothstep(0.2, 1.0, distance);
+
+    let alpha = uniforms.color.a * in.fade * softness;
+    if (alpha <= 0.001) {
+        discard;
+    }
+
+    return vec4<f32>(uniforms.color.rgb, alpha);
+}
@@ -0,0 +1,50 @@
+struct VignetteUniforms {
+    // How dark the screen edges are, from 0.0 to 1.0
+    strength: f32,
+    // How far the whole screen has faded to black, from 0.0 to 1.0
+    fade: f32,
+    // Surface width divided by height, so the darkening is round rather than stretched
+    aspect: f32,
+    _pad0: f32,
+};
+
+@group(0) @binding(0)
+var<uniform> uniforms: VignetteUniforms;
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) ndc: vec2<f32>,
+};
+
+// Vertex shader
+@vertex
+fn vs_main(@builtin(vertex_index) vertex_index: u32) -> VertexOutput {
+    // Full-screen triangle
+    var pos: vec2<f32>;
+    switch (vertex_index) {
+        case 0u: { pos = vec2(-1.0, -1.0); }
+        case 1u: { pos = vec2(3.0, -1.0); }
+        default: { pos = vec2(-1.0, 3.0); }
+    }
+    var out: VertexOutput;
+    out.clip_position = vec4<f32>(pos, 0.0, 1.0);
+    out.ndc = pos;
+    return out;
+}
+
+// Fragment shader
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0) vec4<f32> {
+    let p = vec2<f32>(in.ndc.x * uniforms.aspect, in.ndc.y);
+    let dist = length(p) / length(vec2<f32>(uniforms.aspect, 1.0));
+
+    // The dark border closes in toward the center as the strength rises
+    let inner = mix(0.95, 0.15, uniforms.strength);
+    let edge = smoothstep(inner, inner + 0.55, dist) * uniforms.strength;
+
+    let alpha = max(edge, uniforms.fade);
+    if (alpha <= 0.0) {
+        discard;
+    }
+    return vec4<f32>(0.0, 0.0, 0.0, alpha);
+}
@@ -0,0 +1,135 @@
+//! How the window's surface is composited with the desktop behind it.
+//!
+//! A surface's [`wgpu::CompositeAlphaMode`] decides whether the compositor uses the frame's alpha
+//! channel. Some Wayland compositors list a premultiplied mode first, and with it every pixel
+//! drawn with alpha below 1.0 lets the desktop show through the window. [`select_alpha_mode`]
+//! prefers a mode that ignores alpha and only falls back to one that reads it when the surface
+//! supports nothing else.
+//!
+//! With such a mode, [`AlphaStampRenderer`] runs as the last pass of every frame and sets the
+//! alpha of every pixel to 1.0 without touching its color, so the window stays opaque however
+//! the passes before it blended alpha.
+//!
+//! # Usage
+//!
+//! ```rust,ignore
+//! let alpha_mode = select_alpha_mode(&capabilities.alpha_modes);
+//! let alpha_stamp = needs_alpha_stamp(alpha_mode)
+//!     .then(|| AlphaStampRenderer::new(&device, surface_config.format));
+//!
+//! // Last, before submitting the frame
+//! if let Some(stamp) = &alpha_stamp {
+//!     stamp.render(&mut encoder, &surface_view);
+//! }
+//! ```
+
+use crate::renderer::pipeline_builder::PipelineBuilder;
+use wgpu::CompositeAlphaMode;
+
+/// Alpha modes in the order they are preferred; the later ones let the desktop show through
+/// pixels whose alpha is below 1.0.
+const PREFERRED_ALPHA_MODES: [CompositeAlphaMode; 5] = [
+    CompositeAlphaMode::Opaque,
+    CompositeAlphaMode::Auto,
+    CompositeAlphaMode::Inherit,
+    CompositeAlphaMode::PreMultiplied,
+    CompositeAlphaMode::PostMultiplied,
+];
+
+/// Picks how the surface is composited.
+///
+/// # Arguments
+/// * `supported` - The alpha modes the surface supports, in the order the platform lists them
+///
+/// # Returns
+/// `Opaque` when supported, else `Auto`, and only otherwise a mode that reads the frame's
+/// alpha; `Auto` when the list is empty
+pub fn select_alpha_mode(supported: &[CompositeAlphaMode]) -> CompositeAlphaMode {
+    PREFERRED_ALPHA_MODES
+        .into_iter()
+        .find(|mode| supported.contains(mode))
+        .unwrap_or(CompositeAlphaMode::Auto)
+}
+
+/// Checks whether frames have to be stamped opaque before they are presented.
+///
+/// Only `Opaque` is certain to ignore the frame's alpha; `Auto` falls back to `Inherit` on
+/// surfaces without `Opaque`, which is the only time [`select_alpha_mode`] picks it.
+///
+/// # Arguments
+/// * `mode` - The alpha mode the surface is configured with
+pub fn needs_alpha_stamp(mode: CompositeAlphaMode) -> bool {
+    mode != CompositeAlphaMode::Opaque
+}
+
+/// Sets the alpha of every pixel of a finished frame to 1.0.
+///
+/// Drawn with a procedural full-screen triangle whose pipeline only writes the alpha channel,
+/// so it needs no vertex buffer or bindings and leaves the frame's colors alone.
+pub struct AlphaStampRenderer {
+    /// The WebGPU render pipeline for the stamp
+    pub pipeline: wgpu::RenderPipeline,
+}
+
+impl AlphaStampRenderer {
+    /// Creates a new alpha stamp renderer.
+    ///
+    /// # Arguments
+    /// * `device` - WebGPU device for creating GPU resources
+    /// * `format` - Format of the surface the stamp is drawn to
+    pub fn new(device: &wgpu::Device, format: wgpu::TextureFormat) -> Self {
+        let pipeline = PipelineBuilder::new(device, format)
+            .with_label("Alpha Stamp Pipeline")
+            .with_shader(include_str!("shaders/alpha_stamp.wgsl"))
+            .with_write_mask(wgpu::ColorWrites::ALPHA)
+            .build();
+        Self { pipeline }
+    }
+
+    /// Records the stamp as its own pass over the finished frame.
+    ///
+    /// # Arguments
+    /// * `encoder` - The frame's command encoder, after every other pass
+    /// * `view` - View of the surface texture the frame was drawn to
+    pub fn render(&self, encoder: &mut wgpu::CommandEncoder, view: &wgpu::TextureView) {
+        let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some("Alpha Stamp Pass"),
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Load,
+                    store: wgpu::StoreOp::Store,
+                },
+            })],
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            occlusion_query_set: None,
+        });
+        pass.set_pipeline(&self.pipeline);
+        pass.draw(0..3, 0..1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_opaque_and_auto_are_preferred_over_modes_that_read_alpha() {
+        use CompositeAlphaMode::*;
+        // The Wayland case: premultiplied listed first
+        assert_eq!(select_alpha_mode(&[PreMultiplied, Opaque]), Opaque);
+        assert_eq!(select_alpha_mode(&[PostMultiplied, Auto, Inherit]), Auto);
+        assert_eq!(
+            select_alpha_mode(&[PostMultiplied, PreMultiplied]),
+            PreMultiplied
+        );
+        assert_eq!(select_alpha_mode(&[]), Auto);
+
+        assert!(!needs_alpha_stamp(Opaque));
+        for mode in [Auto, Inherit, PreMultiplied, PostMultiplied] {
+            assert!(needs_alpha_stamp(mode));
+        }
+    }
+}
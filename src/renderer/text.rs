@@ -9,16 +9,18 @@
 //! - Custom font loading and management
 //! - Dynamic text styling (font size, color, weight, style)
 //! - Flexible positioning with constraints
-//! - Text measurement and layout calculation
+//! - Text measurement and layout calculation, with cached tabular widths for counters
 //! - Game-specific UI elements (game over screens, score displays)
 //! - DPI-aware scaling for different screen sizes
 //! - Multiple text buffer management with unique IDs
+//! - Glyph warm-up during loading screens, with a log of styles first drawn after it
 //!
 //! ## Usage
 //!
-//! ```rust
+//! ```rust,ignore
 //! // Create a text renderer
-//! let mut text_renderer = TextRenderer::new(&device, &queue, surface_format, &window);
+//! let mut text_renderer =
+//!     TextRenderer::new(&device, &queue, surface_format, window.inner_size(), &gpu_memory);
 //!
 //! // Create a text buffer
 //! text_renderer.create_text_buffer(
@@ -34,14 +36,87 @@
 //! ```
 
 use crate::assets;
+use crate::game::journal::{self, JournalEvent};
+use crate::renderer::gpu_memory::{GpuMemoryTracker, MemoryRegistration, TextureCategory};
+use crate::renderer::safe_area::SafeArea;
 use glyphon::{
     Attrs, Buffer, Cache, Color, Family, FontSystem, Metrics, Resolution, Shaping, Style,
     SwashCache, TextArea, TextAtlas, TextBounds, TextRenderer as GlyphonTextRenderer, Viewport,
     Weight,
 };
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use wgpu::{self, Device, Queue, RenderPass, SurfaceConfiguration};
-use winit::window::Window;
+use winit::dpi::PhysicalSize;
+
+/// Estimated size of a new glyph atlas in bytes.
+///
+/// Glyphon starts its color (RGBA8) and mask (R8) atlases at 256x256 texels each. The atlases
+/// grow as more glyphs are cached, but their size isn't exposed, so only the initial size is
+/// counted.
+pub const GLYPH_ATLAS_BYTES: u64 = 256 * 256 * (4 + 1);
+
+/// Subtitle shown under "Game Over!" when the level can be retried.
+pub const GAME_OVER_SUBTITLE: &str = "Retry this level or start a new run.";
+
+/// How far the game over subtitle's top sits below the window center at 1080p, in pixels.
+const GAME_OVER_SUBTITLE_OFFSET: f32 = 60.0;
+
+/// Most widths [`TextRenderer::measure_figures`] keeps before starting over.
+const MAX_FIGURE_WIDTHS: usize = 256;
+
+/// Font family, size (as bits) and weight a figure width was measured with.
+type FigureFont = (String, u32, u16);
+
+/// Characters rasterized for every style by [`TextRenderer::warm_glyphs`]: printable ASCII,
+/// which covers the digits of counters and nearly every UI string.
+const WARM_UP_GLYPHS: &str = " !\"#$%&'()*+,-./0123456789:;<=>?@ABCDEFGHIJKLMNOPQRSTUVWXYZ[\\]^_`abcdefghijklmnopqrstuvwxyz{|}~";
+
+/// Font family, drawn size (as bits), weight and slant; glyphs are rasterized once per style.
+type GlyphStyle = (String, u32, u16, Style);
+
+/// Returns the font size and line height of the game over subtitle for a window size.
+///
+/// # Arguments
+/// * `width` - Window width in pixels
+/// * `height` - Window height in pixels
+fn game_over_subtitle_size(width: f32, height: f32) -> (f32, f32) {
+    let scale = (height / 1080.0).clamp(0.7, 2.0);
+    let font_size = (width * 0.025 * scale).clamp(16.0, 120.0); // 2.5% of width, min 16, max 120
+    let line_height = (font_size * 1.3).clamp(20.0, 156.0);
+    (font_size, line_height)
+}
+
+/// Returns where the game over subtitle ends, in pixels from the top of the window.
+///
+/// The game over buttons are laid out below it.
+///
+/// # Arguments
+/// * `width` - Window width in pixels
+/// * `height` - Window height in pixels
+pub fn game_over_subtitle_bottom(width: f32, height: f32) -> f32 {
+    let scale = (height / 1080.0).clamp(0.7, 2.0);
+    let (_, line_height) = game_over_subtitle_size(width, height);
+    height / 2.0 + GAME_OVER_SUBTITLE_OFFSET * scale + line_height
+}
+
+/// Returns the style glyphs of a buffer are rasterized in, with its scale applied.
+fn glyph_style(style: &TextStyle, scale: f32) -> GlyphStyle {
+    (
+        style.font_family.clone(),
+        (style.font_size * scale).to_bits(),
+        style.weight.0,
+        style.style,
+    )
+}
+
+/// Widths measured by [`TextRenderer::measure_figures`], kept between frames.
+#[derive(Debug, Default)]
+struct FigureWidths {
+    /// The widest digit of each font
+    widest_digits: HashMap<FigureFont, char>,
+    /// Width of each digit pattern: text with every digit swapped for the font's widest one
+    widths: HashMap<(FigureFont, String), f32>,
+}
 
 /// Defines the visual styling properties for text rendering.
 ///
@@ -95,6 +170,23 @@ pub struct TextPosition {
     pub max_height: Option<f32>,
 }
 
+impl TextPosition {
+    /// Returns the width and height text at this position may occupy.
+    ///
+    /// Missing constraints fall back to the window size. Negative or NaN constraints, which
+    /// size-derived layout math can produce on tiny windows, saturate at zero.
+    ///
+    /// # Arguments
+    ///
+    /// * `window_width` - Window width used when there is no width constraint
+    /// * `window_height` - Window height used when there is no height constraint
+    pub fn bounded_size(&self, window_width: f32, window_height: f32) -> (f32, f32) {
+        let width = self.max_width.unwrap_or(window_width);
+        let height = self.max_height.unwrap_or(window_height);
+        (width.max(0.0), height.max(0.0))
+    }
+}
+
 impl Default for TextPosition {
     /// Creates a default position at the origin (0,0) with no size constraints.
     fn default() -> Self {
@@ -125,6 +217,9 @@ pub struct TextBuffer {
     pub visible: bool,
     /// The original text content (stored for re-styling)
     pub text_content: String,
+    /// Whether this buffer is drawn by [`TextRenderer::render_overlay`] instead of
+    /// [`TextRenderer::render`], so it can sit above things drawn in between
+    pub overlay: bool,
 }
 
 /// The main text rendering system that manages fonts, text buffers, and rendering.
@@ -143,12 +238,24 @@ pub struct TextRenderer {
     pub atlas: TextAtlas,
     /// The underlying Glyphon renderer
     pub glyph_renderer: GlyphonTextRenderer,
+    /// Second Glyphon renderer sharing the atlas, for buffers drawn as an overlay
+    pub overlay_renderer: GlyphonTextRenderer,
     /// Collection of all text buffers indexed by unique IDs
     pub text_buffers: HashMap<String, TextBuffer>,
     /// Current window size for layout calculations
     pub window_size: winit::dpi::PhysicalSize<u32>,
     /// List of successfully loaded custom font names
     pub loaded_fonts: Vec<String>,
+    /// Keeps the glyph atlas counted in the GPU memory totals
+    _atlas_memory: MemoryRegistration,
+    /// Cached widths of counters such as the timer and score
+    figure_widths: FigureWidths,
+    /// Styles whose glyphs are in the atlas, tracked once a warm-up has begun
+    warm_styles: HashSet<GlyphStyle>,
+    /// Name of the renderer in warm-up logs; `None` until a warm-up has begun
+    warm_up_label: Option<&'static str>,
+    /// Whether the first warm-up is over, so styles drawn for the first time are logged
+    warm_up_finished: bool,
 }
 
 impl TextRenderer {
@@ -163,7 +270,8 @@ impl TextRenderer {
     /// * `device` - WGPU device for GPU operations
     /// * `queue` - WGPU command queue
     /// * `surface_format` - The texture format of the render surface
-    /// * `window` - Window reference for getting dimensions
+    /// * `window_size` - The window size, for sizing calculations
+    /// * `gpu_memory` - Tracker the glyph atlas is counted in
     ///
     /// # Returns
     ///
@@ -172,7 +280,8 @@ impl TextRenderer {
         device: &Device,
         queue: &Queue,
         surface_format: wgpu::TextureFormat,
-        window: &Window,
+        window_size: PhysicalSize<u32>,
+        gpu_memory: &GpuMemoryTracker,
     ) -> Self {
         use crate::benchmarks::{BenchmarkConfig, Profiler};
 
@@ -183,6 +292,8 @@ impl TextRenderer {
             write_to_file: false,
             min_duration_threshold: std::time::Duration::from_micros(1),
             max_samples: 1000,
+            autosave_interval: std::time::Duration::from_secs(60),
+            max_sections: 256,
         });
 
         // Benchmark font system initialization
@@ -214,19 +325,25 @@ impl TextRenderer {
         init_profiler.start_section("glyph_renderer_creation");
         let glyph_renderer =
             GlyphonTextRenderer::new(&mut atlas, device, wgpu::MultisampleState::default(), None);
+        let overlay_renderer =
+            GlyphonTextRenderer::new(&mut atlas, device, wgpu::MultisampleState::default(), None);
         init_profiler.end_section("glyph_renderer_creation");
 
-        let size = window.inner_size();
-
         let mut renderer = Self {
             font_system,
             swash_cache,
             viewport,
             atlas,
             glyph_renderer,
+            overlay_renderer,
             text_buffers: HashMap::new(),
-            window_size: size,
+            window_size,
             loaded_fonts: Vec::new(),
+            _atlas_memory: gpu_memory.register(TextureCategory::Text, GLYPH_ATLAS_BYTES),
+            figure_widths: FigureWidths::default(),
+            warm_styles: HashSet::new(),
+            warm_up_label: None,
+            warm_up_finished: false,
         };
 
         // Benchmark custom font loading
@@ -245,7 +362,7 @@ impl TextRenderer {
     ///
     /// # Example
     ///
-    /// ```rust
+    /// ```rust,ignore
     /// renderer.load_embedded_fonts();
     /// ```
     pub fn load_embedded_fonts(&mut self) {
@@ -277,7 +394,7 @@ impl TextRenderer {
     ///
     /// # Example
     ///
-    /// ```rust
+    /// ```rust,ignore
     /// let style = TextStyle {
     ///     font_size: 24.0,
     ///     color: Color::rgb(255, 0, 0),
@@ -311,10 +428,10 @@ impl TextRenderer {
         let mut buffer = Buffer::new(&mut self.font_system, metrics);
 
         // Set buffer size based on position constraints or window size
-        let width = position.max_width.unwrap_or(self.window_size.width as f32);
-        let height = position
-            .max_height
-            .unwrap_or(self.window_size.height as f32);
+        let (width, height) = position.bounded_size(
+            self.window_size.width as f32,
+            self.window_size.height as f32,
+        );
 
         buffer.set_size(&mut self.font_system, Some(width), Some(height));
 
@@ -333,6 +450,7 @@ impl TextRenderer {
             scale: 1.0,
             visible: true,
             text_content: text.to_string(),
+            overlay: false,
         };
 
         self.text_buffers.insert(id.to_string(), text_buffer);
@@ -362,7 +480,7 @@ impl TextRenderer {
     ///
     /// # Example
     ///
-    /// ```rust
+    /// ```rust,ignore
     /// let new_style = TextStyle {
     ///     font_size: 32.0,
     ///     color: Color::rgb(0, 255, 0),
@@ -435,7 +553,7 @@ impl TextRenderer {
     ///
     /// # Example
     ///
-    /// ```rust
+    /// ```rust,ignore
     /// let new_position = TextPosition {
     ///     x: 200.0,
     ///     y: 100.0,
@@ -454,10 +572,10 @@ impl TextRenderer {
         if text_buffer.position.max_width != position.max_width
             || text_buffer.position.max_height != position.max_height
         {
-            let width = position.max_width.unwrap_or(self.window_size.width as f32);
-            let height = position
-                .max_height
-                .unwrap_or(self.window_size.height as f32);
+            let (width, height) = position.bounded_size(
+                self.window_size.width as f32,
+                self.window_size.height as f32,
+            );
             text_buffer
                 .buffer
                 .set_size(&mut self.font_system, Some(width), Some(height));
@@ -486,7 +604,7 @@ impl TextRenderer {
     ///
     /// # Example
     ///
-    /// ```rust
+    /// ```rust,ignore
     /// // In your window resize event handler
     /// renderer.resize(&queue, Resolution::new(new_width, new_height));
     /// ```
@@ -519,7 +637,7 @@ impl TextRenderer {
     ///
     /// # Example
     ///
-    /// ```rust
+    /// ```rust,ignore
     /// // In your render loop
     /// renderer.prepare(&device, &queue, &surface_config)?;
     /// renderer.render(&mut render_pass)?;
@@ -531,25 +649,22 @@ impl TextRenderer {
         _surface_config: &SurfaceConfiguration,
     ) -> Result<(), glyphon::PrepareError> {
         let mut text_areas = Vec::new();
+        let mut overlay_areas = Vec::new();
 
         for text_buffer in self.text_buffers.values() {
             if !text_buffer.visible {
                 continue;
             }
 
+            let (width, height) = text_buffer.position.bounded_size(
+                self.window_size.width as f32,
+                self.window_size.height as f32,
+            );
             let bounds = TextBounds {
                 left: text_buffer.position.x as i32,
                 top: text_buffer.position.y as i32,
-                right: (text_buffer.position.x
-                    + text_buffer
-                        .position
-                        .max_width
-                        .unwrap_or(self.window_size.width as f32)) as i32,
-                bottom: (text_buffer.position.y
-                    + text_buffer
-                        .position
-                        .max_height
-                        .unwrap_or(self.window_size.height as f32)) as i32,
+                right: (text_buffer.position.x + width) as i32,
+                bottom: (text_buffer.position.y + height) as i32,
             };
 
             let text_area = TextArea {
@@ -562,7 +677,37 @@ impl TextRenderer {
                 custom_glyphs: &[],
             };
 
-            text_areas.push(text_area);
+            if text_buffer.overlay {
+                overlay_areas.push(text_area);
+            } else {
+                text_areas.push(text_area);
+            }
+        }
+
+        if let Some(label) = self.warm_up_label {
+            for (id, text_buffer) in &self.text_buffers {
+                if text_buffer.visible
+                    && self
+                        .warm_styles
+                        .insert(glyph_style(&text_buffer.style, text_buffer.scale))
+                    && self.warm_up_finished
+                {
+                    let style = format!(
+                        "{} {}px",
+                        text_buffer.style.font_family,
+                        text_buffer.style.font_size * text_buffer.scale
+                    );
+                    println!(
+                        "[WARMUP] {}: '{}' rasterized {} on first use",
+                        label, id, style
+                    );
+                    journal::session().record(JournalEvent::ColdGlyphs {
+                        renderer: label.to_string(),
+                        buffer: id.clone(),
+                        style,
+                    });
+                }
+            }
         }
 
         self.glyph_renderer.prepare(
@@ -573,9 +718,132 @@ impl TextRenderer {
             &self.viewport,
             text_areas,
             &mut self.swash_cache,
+        )?;
+        self.overlay_renderer.prepare(
+            device,
+            queue,
+            &mut self.font_system,
+            &mut self.atlas,
+            &self.viewport,
+            overlay_areas,
+            &mut self.swash_cache,
         )
     }
 
+    /// Starts tracking which styles have their glyphs rasterized and lists the ones that don't.
+    ///
+    /// From now on, [`prepare`](Self::prepare) remembers every style it draws. Once
+    /// [`finish_warm_up`](Self::finish_warm_up) is called, it also logs each style it draws for
+    /// the first time to the console and the session journal, so a style missing from the
+    /// warm-up shows up as a first-use rasterization.
+    ///
+    /// # Arguments
+    ///
+    /// * `label` - Name of the renderer in the logs, e.g. "pause menu"
+    ///
+    /// # Returns
+    ///
+    /// One style per set of glyphs not rasterized yet, sized as drawn, to pass to
+    /// [`warm_glyphs`](Self::warm_glyphs)
+    pub fn begin_warm_up(&mut self, label: &'static str) -> Vec<TextStyle> {
+        self.warm_up_label = Some(label);
+
+        let mut cold = HashSet::new();
+        let mut styles = Vec::new();
+        for text_buffer in self.text_buffers.values() {
+            let key = glyph_style(&text_buffer.style, text_buffer.scale);
+            if !self.warm_styles.contains(&key) && cold.insert(key) {
+                styles.push(TextStyle {
+                    font_size: text_buffer.style.font_size * text_buffer.scale,
+                    line_height: text_buffer.style.line_height * text_buffer.scale,
+                    ..text_buffer.style.clone()
+                });
+            }
+        }
+        styles
+    }
+
+    /// Starts logging styles drawn for the first time, once the warm-up is done or cancelled.
+    pub fn finish_warm_up(&mut self) {
+        self.warm_up_finished = true;
+    }
+
+    /// Rasterizes the glyphs of a style into the atlas without drawing anything.
+    ///
+    /// Prepares a throwaway buffer holding printable ASCII plus any other characters the
+    /// renderer's buffers show in this style, through a throwaway glyph renderer so the
+    /// buffers prepared for the next frame are left alone.
+    ///
+    /// # Arguments
+    ///
+    /// * `device` - WGPU device for GPU operations
+    /// * `queue` - WGPU command queue the glyphs are uploaded through
+    /// * `style` - The style to rasterize, as returned by [`begin_warm_up`](Self::begin_warm_up)
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` if the glyphs are in the atlas
+    /// * `Err(glyphon::PrepareError)` if the atlas couldn't take them
+    pub fn warm_glyphs(
+        &mut self,
+        device: &Device,
+        queue: &Queue,
+        style: &TextStyle,
+    ) -> Result<(), glyphon::PrepareError> {
+        let key = glyph_style(style, 1.0);
+        let mut text = WARM_UP_GLYPHS.to_string();
+        for text_buffer in self.text_buffers.values() {
+            if glyph_style(&text_buffer.style, text_buffer.scale) == key {
+                text.extend(
+                    text_buffer
+                        .text_content
+                        .chars()
+                        .filter(|c| !c.is_ascii() && !text.contains(*c))
+                        .collect::<Vec<_>>(),
+                );
+            }
+        }
+
+        let mut buffer = Buffer::new(
+            &mut self.font_system,
+            Metrics::new(style.font_size, style.line_height),
+        );
+        buffer.set_size(&mut self.font_system, None, None);
+        let attrs = Attrs::new()
+            .family(Family::Name(&style.font_family))
+            .weight(style.weight)
+            .style(style.style);
+        buffer.set_text(&mut self.font_system, &text, attrs, Shaping::Advanced);
+        buffer.shape_until_scroll(&mut self.font_system, false);
+
+        let text_area = TextArea {
+            buffer: &buffer,
+            left: 0.0,
+            top: 0.0,
+            scale: 1.0,
+            bounds: TextBounds::default(),
+            default_color: style.color,
+            custom_glyphs: &[],
+        };
+        let mut warm_renderer = GlyphonTextRenderer::new(
+            &mut self.atlas,
+            device,
+            wgpu::MultisampleState::default(),
+            None,
+        );
+        warm_renderer.prepare(
+            device,
+            queue,
+            &mut self.font_system,
+            &mut self.atlas,
+            &self.viewport,
+            [text_area],
+            &mut self.swash_cache,
+        )?;
+        self.warm_styles.insert(key);
+        Ok(())
+    }
+
     /// Renders all prepared text to the current render pass.
     ///
     /// This method should be called during your render loop after calling `prepare()`.
@@ -592,7 +860,7 @@ impl TextRenderer {
     ///
     /// # Example
     ///
-    /// ```rust
+    /// ```rust,ignore
     /// // In your render loop
     /// let mut render_pass = encoder.begin_render_pass(&render_pass_descriptor);
     /// renderer.render(&mut render_pass)?;
@@ -602,6 +870,28 @@ impl TextRenderer {
             .render(&self.atlas, &self.viewport, render_pass)
     }
 
+    /// Renders the prepared overlay buffers to the current render pass.
+    ///
+    /// Buffers marked with [`set_buffer_overlay`](Self::set_buffer_overlay) are left out of
+    /// `render()` and drawn here instead, so anything drawn between the two calls ends up
+    /// underneath them.
+    ///
+    /// # Arguments
+    ///
+    /// * `render_pass` - The active WGPU render pass to draw into
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` if rendering was successful
+    /// * `Err(glyphon::RenderError)` if rendering failed
+    pub fn render_overlay(
+        &mut self,
+        render_pass: &mut RenderPass,
+    ) -> Result<(), glyphon::RenderError> {
+        self.overlay_renderer
+            .render(&self.atlas, &self.viewport, render_pass)
+    }
+
     /// Measures the dimensions of text without creating a buffer.
     ///
     /// This utility method calculates how much space text will occupy when
@@ -628,14 +918,106 @@ impl TextRenderer {
     ///
     /// # Example
     ///
-    /// ```rust
+    /// ```rust,ignore
     /// let style = TextStyle::default();
     /// let (min_x, width, height) = renderer.measure_text("Hello World", &style);
     /// println!("Text dimensions: {}x{} at x={}", width, height, min_x);
     /// ```
     pub fn measure_text(&mut self, text: &str, style: &TextStyle) -> (f32, f32, f32) {
+        self.measure_text_within(text, style, None)
+    }
+
+    /// Measures the dimensions of text wrapped to a maximum width.
+    ///
+    /// Works like [`measure_text`](Self::measure_text), but lines longer than `max_width`
+    /// wrap the same way they would in a buffer of that width.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - The text content to measure
+    /// * `style` - The text style to use for measurement
+    /// * `max_width` - Width the text wraps at
+    ///
+    /// # Returns
+    ///
+    /// A tuple containing `(min_x, width, height)` of the wrapped text
+    pub fn measure_wrapped_text(
+        &mut self,
+        text: &str,
+        style: &TextStyle,
+        max_width: f32,
+    ) -> (f32, f32, f32) {
+        self.measure_text_within(text, style, Some(max_width.max(0.0)))
+    }
+
+    /// Measures the width of text as if every digit were as wide as the widest one.
+    ///
+    /// glyphon can't request a font's tabular figures (the `tnum` feature), so this stands
+    /// in for them: the widest digit of each font is found once, every digit in `text` is
+    /// swapped for it, and that is measured. The bundled Hanken Grotesk already draws all
+    /// its digits at one width, so the result matches what is rendered. Widths are cached by
+    /// digit pattern, so a counter is only measured again when its digit count changes.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - The text content to measure
+    /// * `style` - The text style to use for measurement
+    ///
+    /// # Returns
+    ///
+    /// The width [`measure_text`](Self::measure_text) gives for the text with tabular digits
+    pub fn measure_figures(&mut self, text: &str, style: &TextStyle) -> f32 {
+        let font = (
+            style.font_family.clone(),
+            style.font_size.to_bits(),
+            style.weight.0,
+        );
+        let widest = match self.figure_widths.widest_digits.get(&font) {
+            Some(&digit) => digit,
+            None => {
+                let mut widest = ('0', 0.0);
+                for digit in '0'..='9' {
+                    let (_min_x, width, _h) =
+                        self.measure_text(digit.encode_utf8(&mut [0; 4]), style);
+                    if width > widest.1 {
+                        widest = (digit, width);
+                    }
+                }
+                self.figure_widths
+                    .widest_digits
+                    .insert(font.clone(), widest.0);
+                widest.0
+            }
+        };
+
+        let pattern: String = text
+            .chars()
+            .map(|ch| if ch.is_ascii_digit() { widest } else { ch })
+            .collect();
+        let key = (font, pattern);
+        if let Some(&width) = self.figure_widths.widths.get(&key) {
+            return width;
+        }
+        if self.figure_widths.widths.len() >= MAX_FIGURE_WIDTHS {
+            self.figure_widths.widths.clear();
+        }
+        let (_min_x, width, _h) = self.measure_text(&key.1, style);
+        self.figure_widths.widths.insert(key, width);
+        width
+    }
+
+    /// Measures text, wrapping it at `max_width` if one is given.
+    fn measure_text_within(
+        &mut self,
+        text: &str,
+        style: &TextStyle,
+        max_width: Option<f32>,
+    ) -> (f32, f32, f32) {
         let metrics = Metrics::new(style.font_size, style.line_height);
         let mut buffer = Buffer::new(&mut self.font_system, metrics);
+        if max_width.is_some() {
+            buffer.set_size(&mut self.font_system, max_width, None);
+        }
 
         let attrs = Attrs::new()
             .family(Family::Name(&style.font_family))
@@ -667,6 +1049,11 @@ impl TextRenderer {
             height = style.line_height;
         }
 
+        // Empty text has no glyphs; measure it as zero wide instead of hugely negative
+        if min_x == f32::MAX {
+            min_x = 0.0;
+        }
+
         let width = max_x - min_x;
         (min_x, width, height)
     }
@@ -693,7 +1080,7 @@ impl TextRenderer {
     ///
     /// # Example
     ///
-    /// ```rust
+    /// ```rust,ignore
     /// // Create the game over display
     /// renderer.create_game_over_display(1920, 1080);
     ///
@@ -747,7 +1134,7 @@ impl TextRenderer {
         };
         self.create_text_buffer(
             "game_over_restart",
-            "Click anywhere to play again.",
+            GAME_OVER_SUBTITLE,
             Some(restart_style),
             Some(restart_position),
         );
@@ -774,7 +1161,7 @@ impl TextRenderer {
     ///
     /// # Example
     ///
-    /// ```rust
+    /// ```rust,ignore
     /// // When the game ends
     /// renderer.show_game_over_display();
     /// ```
@@ -806,7 +1193,7 @@ impl TextRenderer {
     ///
     /// # Example
     ///
-    /// ```rust
+    /// ```rust,ignore
     /// // When starting a new game
     /// renderer.hide_game_over_display();
     /// ```
@@ -819,36 +1206,6 @@ impl TextRenderer {
         }
     }
 
-    /// Checks if the game over display is currently visible.
-    ///
-    /// This method can be used to determine the current state of the game over
-    /// display, which is useful for game state management and UI logic.
-    ///
-    /// # Returns
-    ///
-    /// `true` if the game over title is visible, `false` otherwise
-    ///
-    /// # Behavior
-    ///
-    /// - Checks the visibility state of the "game_over_title" buffer
-    /// - Returns false if the buffer doesn't exist
-    /// - Assumes that both title and restart text have the same visibility state
-    ///
-    /// # Example
-    ///
-    /// ```rust
-    /// // Check if game over screen is currently shown
-    /// if renderer.is_game_over_visible() {
-    ///     // Handle game over state logic
-    /// }
-    /// ```
-    pub fn is_game_over_visible(&self) -> bool {
-        self.text_buffers
-            .get("game_over_title")
-            .map(|buffer| buffer.visible)
-            .unwrap_or(false)
-    }
-
     /// Updates game over display positioning for different screen sizes.
     ///
     /// This method should be called when the window is resized to ensure
@@ -943,7 +1300,7 @@ impl TextRenderer {
     ///
     /// # Example
     ///
-    /// ```rust
+    /// ```rust,ignore
     /// // Call when window is resized or game over display is shown
     /// renderer.handle_game_over_text(1920, 1080);
     /// ```
@@ -958,8 +1315,7 @@ impl TextRenderer {
         // Dynamically scale font sizes with DPI scaling
         let title_font_size = (width * 0.12 * scale).clamp(48.0, 240.0); // 12% of width, min 48, max 240
         let title_line_height = (title_font_size * 1.25).clamp(60.0, 300.0);
-        let subtitle_font_size = (width * 0.025 * scale).clamp(16.0, 120.0); // 2.5% of width, min 16, max 120
-        let subtitle_line_height = (subtitle_font_size * 1.3).clamp(20.0, 156.0);
+        let (subtitle_font_size, subtitle_line_height) = game_over_subtitle_size(width, height);
 
         // Update game over title
         if let Some(title_buffer) = self.text_buffers.get_mut("game_over_title") {
@@ -992,7 +1348,7 @@ impl TextRenderer {
 
             let pos = TextPosition {
                 x: (width / 2.0) - (text_width / 2.0),
-                y: (height / 2.0) + 60.0 * scale,
+                y: (height / 2.0) + GAME_OVER_SUBTITLE_OFFSET * scale,
                 max_width: Some(text_width + 60.0 * scale), // Add more padding for subtitle to prevent clipping
                 max_height: Some(text_height + 30.0 * scale),
             };
@@ -1009,14 +1365,13 @@ impl TextRenderer {
     ///
     /// # Arguments
     ///
-    /// * `width` - Current window width in pixels
-    /// * `height` - Current window height in pixels
+    /// * `hud` - HUD safe area of the window in pixels
     ///
     /// # Behavior
     ///
     /// - Applies DPI scaling based on a 1080p reference resolution
-    /// - Scales font size to 2.2% of window width (clamped between 16-48px)
-    /// - Positions score text in top-left corner with consistent padding
+    /// - Scales font size to 2.2% of the safe area's width (clamped between 16-48px)
+    /// - Positions score text in the safe area's top-left corner with consistent padding
     /// - Positions level text below score text with appropriate spacing
     /// - Adds padding to prevent text clipping
     /// - Uses consistent spacing and alignment for UI consistency
@@ -1027,20 +1382,20 @@ impl TextRenderer {
     ///
     /// # Example
     ///
-    /// ```rust
+    /// ```rust,ignore
     /// // Call when window is resized or score/level changes
-    /// renderer.handle_score_and_level_text(1920, 1080);
+    /// renderer.handle_score_and_level_text(SafeArea::full(1920.0, 1080.0));
     /// ```
-    pub fn handle_score_and_level_text(&mut self, width: u32, height: u32) {
-        let width = width as f32;
-        let height = height as f32;
+    pub fn handle_score_and_level_text(&mut self, hud: SafeArea) {
+        let width = hud.width;
+        let height = hud.height;
         let reference_height = 1080.0;
         let scale = (height / reference_height).clamp(0.7, 2.0);
         // Make this text smaller than subtitles, but more legible on high-DPI
         let font_size = (width * 0.022 * scale).clamp(16.0, 48.0); // 2.2% of width, min 16, max 48
         let line_height = (font_size * 1.25).clamp(20.0, 60.0);
-        let padding_x = 32.0 * scale;
-        let padding_y = 24.0 * scale;
+        let padding_x = hud.x + 32.0 * scale;
+        let padding_y = hud.y + 24.0 * scale;
         // Score text
         if let Some(score_buffer) = self.text_buffers.get_mut("score") {
             let mut style = score_buffer.style.clone();
@@ -1099,7 +1454,7 @@ impl TextRenderer {
     ///
     /// # Example
     ///
-    /// ```rust
+    /// ```rust,ignore
     /// renderer.update_text("score", "Score: 1500")?;
     /// ```
     pub fn update_text(&mut self, id: &str, text: &str) -> Result<(), String> {
@@ -1151,7 +1506,7 @@ impl TextRenderer {
     ///
     /// # Example
     ///
-    /// ```rust
+    /// ```rust,ignore
     /// let style = renderer.get_style("title")?;
     /// println!("Font size: {}", style.font_size);
     /// ```
@@ -1181,7 +1536,7 @@ impl TextRenderer {
     ///
     /// # Example
     ///
-    /// ```rust
+    /// ```rust,ignore
     /// let position = renderer.get_position("title")?;
     /// println!("X position: {}", position.x);
     /// ```
@@ -1211,7 +1566,7 @@ impl TextRenderer {
     ///
     /// # Example
     ///
-    /// ```rust
+    /// ```rust,ignore
     /// let content = renderer.get_text_content("score")?;
     /// println!("Current text: {}", content);
     /// ```
@@ -1226,100 +1581,6 @@ impl TextRenderer {
             .ok_or_else(|| format!("Text buffer '{}' not found", id))
     }
 
-    /// Updates both text content and style in a single operation.
-    ///
-    /// This method efficiently updates both the text content and styling properties
-    /// of an existing text buffer, re-shaping the text only once.
-    ///
-    /// # Arguments
-    ///
-    /// * `id` - The unique identifier of the text buffer to update
-    /// * `text` - The new text content to display
-    /// * `style` - The new text style to apply
-    ///
-    /// # Returns
-    ///
-    /// * `Ok(())` if the update was successful
-    /// * `Err(String)` if the text buffer with the given ID was not found
-    ///
-    /// # Behavior
-    ///
-    /// - Updates both text content and style in one operation
-    /// - Re-shapes the text with new attributes
-    /// - Maintains current position
-    /// - Validates input parameters
-    ///
-    /// # Example
-    ///
-    /// ```rust
-    /// let new_style = TextStyle { font_size: 24.0, ..Default::default() };
-    /// renderer.update_text_and_style("title", "New Title", new_style)?;
-    /// ```
-    pub fn update_text_and_style(
-        &mut self,
-        id: &str,
-        text: &str,
-        mut style: TextStyle,
-    ) -> Result<(), String> {
-        // Validate input parameters
-        if id.is_empty() {
-            return Err("Text buffer ID cannot be empty".to_string());
-        }
-
-        if text.is_empty() {
-            return Err("Text content cannot be empty".to_string());
-        }
-
-        // Validate style parameters
-        if style.font_size <= 0.0 {
-            return Err("Font size must be greater than 0".to_string());
-        }
-
-        if style.line_height <= 0.0 {
-            return Err("Line height must be greater than 0".to_string());
-        }
-
-        let text_buffer = self
-            .text_buffers
-            .get_mut(id)
-            .ok_or_else(|| format!("Text buffer '{}' not found", id))?;
-
-        // If the requested font isn't loaded, fall back to a system font
-        if !self.loaded_fonts.contains(&style.font_family) && style.font_family == "Hanken Grotesk"
-        {
-            style.font_family = "DejaVu Sans".to_string();
-        }
-
-        // Update metrics if font size or line height changed
-        if text_buffer.style.font_size != style.font_size
-            || text_buffer.style.line_height != style.line_height
-        {
-            let metrics = Metrics::new(style.font_size, style.line_height);
-            text_buffer
-                .buffer
-                .set_metrics(&mut self.font_system, metrics);
-        }
-
-        // Update both content and style
-        text_buffer.text_content = text.to_string();
-        text_buffer.style = style;
-
-        // Re-apply text with new attributes
-        let attrs = Attrs::new()
-            .family(Family::Name(&text_buffer.style.font_family))
-            .weight(text_buffer.style.weight)
-            .style(text_buffer.style.style);
-
-        text_buffer
-            .buffer
-            .set_text(&mut self.font_system, text, attrs, Shaping::Advanced);
-        text_buffer
-            .buffer
-            .shape_until_scroll(&mut self.font_system, false);
-
-        Ok(())
-    }
-
     /// Updates text content, style, and position in a single operation.
     ///
     /// This method efficiently updates all properties of a text buffer in one operation,
@@ -1346,7 +1607,7 @@ impl TextRenderer {
     ///
     /// # Example
     ///
-    /// ```rust
+    /// ```rust,ignore
     /// let new_style = TextStyle { font_size: 24.0, ..Default::default() };
     /// let new_position = TextPosition { x: 100.0, y: 200.0, ..Default::default() };
     /// renderer.update_text_style_and_position("title", "New Title", new_style, new_position)?;
@@ -1410,10 +1671,10 @@ impl TextRenderer {
         if text_buffer.position.max_width != position.max_width
             || text_buffer.position.max_height != position.max_height
         {
-            let width = position.max_width.unwrap_or(self.window_size.width as f32);
-            let height = position
-                .max_height
-                .unwrap_or(self.window_size.height as f32);
+            let (width, height) = position.bounded_size(
+                self.window_size.width as f32,
+                self.window_size.height as f32,
+            );
             text_buffer
                 .buffer
                 .set_size(&mut self.font_system, Some(width), Some(height));
@@ -1440,95 +1701,6 @@ impl TextRenderer {
         Ok(())
     }
 
-    /// Checks if a text buffer exists.
-    ///
-    /// This method provides a simple way to check if a text buffer with the given ID exists.
-    ///
-    /// # Arguments
-    ///
-    /// * `id` - The unique identifier of the text buffer to check
-    ///
-    /// # Returns
-    ///
-    /// `true` if the buffer exists, `false` otherwise
-    ///
-    /// # Example
-    ///
-    /// ```rust
-    /// if renderer.has_buffer("score") {
-    ///     renderer.update_text("score", "New Score")?;
-    /// }
-    /// ```
-    pub fn has_buffer(&self, id: &str) -> bool {
-        if id.is_empty() {
-            return false;
-        }
-        self.text_buffers.contains_key(id)
-    }
-
-    /// Removes a text buffer from the renderer.
-    ///
-    /// This method completely removes a text buffer from the renderer, freeing up
-    /// associated resources. The buffer will no longer be rendered.
-    ///
-    /// # Arguments
-    ///
-    /// * `id` - The unique identifier of the text buffer to remove
-    ///
-    /// # Returns
-    ///
-    /// * `Ok(())` if the buffer was removed successfully
-    /// * `Err(String)` if the text buffer with the given ID was not found
-    ///
-    /// # Example
-    ///
-    /// ```rust
-    /// renderer.remove_buffer("temporary_text")?;
-    /// ```
-    pub fn remove_buffer(&mut self, id: &str) -> Result<(), String> {
-        if id.is_empty() {
-            return Err("Text buffer ID cannot be empty".to_string());
-        }
-
-        self.text_buffers
-            .remove(id)
-            .map(|_| ())
-            .ok_or_else(|| format!("Text buffer '{}' not found", id))
-    }
-
-    /// Gets a list of all text buffer IDs.
-    ///
-    /// This method returns a vector of all text buffer identifiers currently
-    /// managed by the renderer.
-    ///
-    /// # Returns
-    ///
-    /// A vector of text buffer IDs
-    ///
-    /// # Example
-    ///
-    /// ```rust
-    /// let buffer_ids = renderer.get_buffer_ids();
-    /// println!("Active buffers: {:?}", buffer_ids);
-    /// ```
-    pub fn get_buffer_ids(&self) -> Vec<String> {
-        self.text_buffers.keys().cloned().collect()
-    }
-
-    /// Clears all text buffers from the renderer.
-    ///
-    /// This method removes all text buffers, freeing up all associated resources.
-    /// No text will be rendered until new buffers are created.
-    ///
-    /// # Example
-    ///
-    /// ```rust
-    /// renderer.clear_all_buffers();
-    /// ```
-    pub fn clear_all_buffers(&mut self) {
-        self.text_buffers.clear();
-    }
-
     /// Sets the visibility of a text buffer.
     ///
     /// This method allows you to show or hide a text buffer without removing it.
@@ -1546,7 +1718,7 @@ impl TextRenderer {
     ///
     /// # Example
     ///
-    /// ```rust
+    /// ```rust,ignore
     /// renderer.set_buffer_visibility("debug_info", false)?;
     /// ```
     pub fn set_buffer_visibility(&mut self, id: &str, visible: bool) -> Result<(), String> {
@@ -1562,219 +1734,26 @@ impl TextRenderer {
         Ok(())
     }
 
-    /// Gets the visibility of a text buffer.
+    /// Moves a text buffer to or from the overlay layer.
     ///
-    /// This method retrieves the current visibility state of a text buffer.
+    /// Overlay buffers are drawn by [`render_overlay`](Self::render_overlay) rather than
+    /// `render()`.
     ///
     /// # Arguments
     ///
     /// * `id` - The unique identifier of the text buffer
+    /// * `overlay` - Whether the buffer should be drawn as an overlay
     ///
     /// # Returns
     ///
-    /// * `Ok(bool)` if the buffer exists and visibility was retrieved
+    /// * `Ok(())` if the layer was set successfully
     /// * `Err(String)` if the text buffer with the given ID was not found
-    ///
-    /// # Example
-    ///
-    /// ```rust
-    /// let is_visible = renderer.get_buffer_visibility("score")?;
-    /// println!("Score buffer is visible: {}", is_visible);
-    /// ```
-    pub fn get_buffer_visibility(&self, id: &str) -> Result<bool, String> {
-        if id.is_empty() {
-            return Err("Text buffer ID cannot be empty".to_string());
-        }
-
-        self.text_buffers
-            .get(id)
-            .map(|buffer| buffer.visible)
-            .ok_or_else(|| format!("Text buffer '{}' not found", id))
-    }
-
-    /// Sets the scale factor of a text buffer.
-    ///
-    /// This method allows you to scale the text rendering size without changing
-    /// the font size. Useful for animations and effects.
-    ///
-    /// # Arguments
-    ///
-    /// * `id` - The unique identifier of the text buffer
-    /// * `scale` - The scale factor (1.0 = normal size, 2.0 = double size, etc.)
-    ///
-    /// # Returns
-    ///
-    /// * `Ok(())` if the scale was set successfully
-    /// * `Err(String)` if the text buffer with the given ID was not found
-    ///
-    /// # Example
-    ///
-    /// ```rust
-    /// renderer.set_buffer_scale("title", 1.5)?; // 50% larger
-    /// ```
-    pub fn set_buffer_scale(&mut self, id: &str, scale: f32) -> Result<(), String> {
-        if id.is_empty() {
-            return Err("Text buffer ID cannot be empty".to_string());
-        }
-
-        if scale <= 0.0 {
-            return Err("Scale factor must be greater than 0".to_string());
-        }
-
+    pub fn set_buffer_overlay(&mut self, id: &str, overlay: bool) -> Result<(), String> {
         let text_buffer = self
             .text_buffers
             .get_mut(id)
             .ok_or_else(|| format!("Text buffer '{}' not found", id))?;
-        text_buffer.scale = scale;
-        Ok(())
-    }
-
-    /// Gets the scale factor of a text buffer.
-    ///
-    /// This method retrieves the current scale factor of a text buffer.
-    ///
-    /// # Arguments
-    ///
-    /// * `id` - The unique identifier of the text buffer
-    ///
-    /// # Returns
-    ///
-    /// * `Ok(f32)` if the buffer exists and scale was retrieved
-    /// * `Err(String)` if the text buffer with the given ID was not found
-    ///
-    /// # Example
-    ///
-    /// ```rust
-    /// let scale = renderer.get_buffer_scale("title")?;
-    /// println!("Title scale: {}", scale);
-    /// ```
-    pub fn get_buffer_scale(&self, id: &str) -> Result<f32, String> {
-        if id.is_empty() {
-            return Err("Text buffer ID cannot be empty".to_string());
-        }
-
-        self.text_buffers
-            .get(id)
-            .map(|buffer| buffer.scale)
-            .ok_or_else(|| format!("Text buffer '{}' not found", id))
-    }
-
-    /// Validates that all text buffers have valid configurations.
-    ///
-    /// This method checks all text buffers for potential issues such as:
-    /// - Invalid font families
-    /// - Negative positions
-    /// - Invalid font sizes
-    /// - Missing text content
-    ///
-    /// # Returns
-    ///
-    /// * `Ok(())` if all buffers are valid
-    /// * `Err(String)` with details about the first validation error found
-    ///
-    /// # Example
-    ///
-    /// ```rust
-    /// if let Err(e) = renderer.validate_buffers() {
-    ///     println!("Validation error: {}", e);
-    /// }
-    /// ```
-    pub fn validate_buffers(&self) -> Result<(), String> {
-        for (id, buffer) in &self.text_buffers {
-            // Check font family
-            if buffer.style.font_family.is_empty() {
-                return Err(format!("Buffer '{}' has empty font family", id));
-            }
-
-            // Check font size
-            if buffer.style.font_size <= 0.0 {
-                return Err(format!(
-                    "Buffer '{}' has invalid font size: {}",
-                    id, buffer.style.font_size
-                ));
-            }
-
-            // Check line height
-            if buffer.style.line_height <= 0.0 {
-                return Err(format!(
-                    "Buffer '{}' has invalid line height: {}",
-                    id, buffer.style.line_height
-                ));
-            }
-
-            // Check position
-            if buffer.position.x < 0.0 {
-                return Err(format!(
-                    "Buffer '{}' has negative X position: {}",
-                    id, buffer.position.x
-                ));
-            }
-
-            if buffer.position.y < 0.0 {
-                return Err(format!(
-                    "Buffer '{}' has negative Y position: {}",
-                    id, buffer.position.y
-                ));
-            }
-
-            // Check scale
-            if buffer.scale <= 0.0 {
-                return Err(format!(
-                    "Buffer '{}' has invalid scale: {}",
-                    id, buffer.scale
-                ));
-            }
-
-            // Check text content
-            if buffer.text_content.is_empty() {
-                return Err(format!("Buffer '{}' has empty text content", id));
-            }
-        }
-
+        text_buffer.overlay = overlay;
         Ok(())
     }
-
-    /// Gets statistics about the text renderer.
-    ///
-    /// This method returns useful information about the current state of the text renderer.
-    ///
-    /// # Returns
-    ///
-    /// A struct containing various statistics
-    ///
-    /// # Example
-    ///
-    /// ```rust
-    /// let stats = renderer.get_statistics();
-    /// println!("Total buffers: {}", stats.total_buffers);
-    /// println!("Visible buffers: {}", stats.visible_buffers);
-    /// ```
-    pub fn get_statistics(&self) -> TextRendererStats {
-        let total_buffers = self.text_buffers.len();
-        let visible_buffers = self.text_buffers.values().filter(|b| b.visible).count();
-        let loaded_fonts = self.loaded_fonts.len();
-
-        TextRendererStats {
-            total_buffers,
-            visible_buffers,
-            loaded_fonts,
-            window_width: self.window_size.width,
-            window_height: self.window_size.height,
-        }
-    }
-}
-
-/// Statistics about the text renderer
-#[derive(Debug, Clone)]
-pub struct TextRendererStats {
-    /// Total number of text buffers
-    pub total_buffers: usize,
-    /// Number of visible text buffers
-    pub visible_buffers: usize,
-    /// Number of loaded fonts
-    pub loaded_fonts: usize,
-    /// Current window width
-    pub window_width: u32,
-    /// Current window height
-    pub window_height: u32,
 }
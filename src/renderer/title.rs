@@ -1,22 +1,12 @@
 use crate::app::AppState;
 use crate::assets;
-use crate::renderer::pipeline_builder::{
-    BindGroupLayoutBuilder, PipelineBuilder, create_uniform_buffer,
-};
-use crate::renderer::text::TextPosition;
+use crate::paths;
+use crate::renderer::pipeline_builder::{BindGroupLayoutBuilder, PipelineBuilder};
+use crate::renderer::text::{TextPosition, TextRenderer, TextStyle};
 use glyphon::Color;
 use image;
 // use std::time::Instant; // Temporarily unused
 use wgpu::{self, util::DeviceExt};
-use winit::window::Window;
-
-#[repr(C)]
-/// Uniform data for title screen rendering.
-#[derive(Copy, Clone, Debug, bytemuck::Pod, bytemuck::Zeroable)]
-struct TitleUniforms {
-    /// View-projection matrix for title screen transformations.
-    view_proj_matrix: [[f32; 4]; 4],
-}
 
 /// Renderer for the title screen with texture and shader support.
 pub struct TitleRenderer {
@@ -24,8 +14,6 @@ pub struct TitleRenderer {
     pub pipeline: wgpu::RenderPipeline,
     /// Vertex buffer containing the fullscreen quad geometry.
     pub vertex_buffer: wgpu::Buffer,
-    /// Uniform buffer for transformation data.
-    pub uniform_buffer: wgpu::Buffer,
     /// Bind group containing texture and sampler bindings.
     pub bind_group: wgpu::BindGroup,
 }
@@ -48,12 +36,6 @@ impl TitleRenderer {
         // Load title texture
         let title_texture = Self::load_title_texture(device, queue);
 
-        let uniforms = TitleUniforms {
-            view_proj_matrix: [[0.0; 4]; 4],
-        };
-
-        let uniform_buffer = create_uniform_buffer(device, &uniforms, "Title Uniform Buffer");
-
         // Create bind group layout for texture + sampler (no uniforms needed)
         let bind_group_layout = BindGroupLayoutBuilder::new(device)
             .with_label("Title Bind Group Layout")
@@ -120,7 +102,6 @@ impl TitleRenderer {
         Self {
             pipeline,
             vertex_buffer,
-            uniform_buffer, // still created, but not used in bind group
             bind_group,
         }
     }
@@ -206,13 +187,6 @@ impl TitleRenderer {
         })
     }
 
-    /// Update the title renderer with the current view projection matrix
-    pub fn update(&mut self, queue: &wgpu::Queue, view_proj_matrix: [[f32; 4]; 4]) {
-        let uniforms = TitleUniforms { view_proj_matrix };
-
-        queue.write_buffer(&self.uniform_buffer, 0, bytemuck::cast_slice(&[uniforms]));
-    }
-
     /// Render the title
     pub fn render(&self, render_pass: &mut wgpu::RenderPass) {
         render_pass.set_pipeline(&self.pipeline);
@@ -222,26 +196,71 @@ impl TitleRenderer {
     }
 }
 
-/// Handles the title screen rendering and animation logic.
-pub fn handle_title(state: &mut AppState, window: &Window) {
-    // Explicitly hide game UI overlays on the title screen
-    if let Err(e) = state
-        .text_renderer
-        .set_buffer_visibility("main_timer", false)
-    {
-        eprintln!("Failed to hide main_timer: {}", e);
-    }
-    if let Err(e) = state.text_renderer.set_buffer_visibility("level", false) {
-        eprintln!("Failed to hide level: {}", e);
-    }
-    if let Err(e) = state.text_renderer.set_buffer_visibility("score", false) {
-        eprintln!("Failed to hide score: {}", e);
-    }
-
-    // --- Dynamic placement for title and subtitle overlays ---
-    let width = state.wgpu_renderer.surface_config.width as f32;
-    let height = state.wgpu_renderer.surface_config.height as f32;
+/// Creates the title screen's "Mirador" title and its subtitle in the text renderer.
+///
+/// They start at rough places; [`layout_title_text`] sizes and places them every frame.
+///
+/// # Arguments
+/// * `text_renderer` - The text renderer to create the buffers in
+/// * `width` - Window width in pixels
+/// * `height` - Window height in pixels
+pub fn create_title_text(text_renderer: &mut TextRenderer, width: f32, height: f32) {
+    let mirador_style = TextStyle {
+        font_family: "Hanken Grotesk".to_string(),
+        font_size: 125.0,
+        line_height: 150.0,
+        color: Color::rgb(58, 53, 70), // #3
+        weight: glyphon::Weight::BOLD,
+        style: glyphon::Style::Normal,
+    };
+    // Estimate text width for right alignment
+    let text_width = 620.0; // Conservative estimate for large text
+    let text_height = 1500.0; // Let's assume a large height for the title
+    let mirador_position = TextPosition {
+        x: width - text_width - 200.0, // 20px margin from right
+        y: 100.0,                      // 100px margin from top
+        max_width: Some(text_width),
+        max_height: Some(text_height),
+    };
+    text_renderer.create_text_buffer(
+        "title_mirador_overlay",
+        "Mirador",
+        Some(mirador_style),
+        Some(mirador_position.clone()),
+    );
+    // Add subtitle text at the same x, 60px from the bottom
+    let subtitle_style = TextStyle {
+        font_family: "Hanken Grotesk".to_string(),
+        font_size: 48.0,
+        line_height: 72.0,
+        color: Color::rgb(58, 53, 70), // #3
+        weight: glyphon::Weight::MEDIUM,
+        style: glyphon::Style::Normal,
+    };
+    let subtitle_text = "Click anywhere to get lost.";
+    let subtitle_text_height = 72.0;
+    let subtitle_position = TextPosition {
+        x: mirador_position.x - 1200.0,
+        y: height + 1000.0,
+        max_width: Some(text_width),
+        max_height: Some(subtitle_text_height),
+    };
+    text_renderer.create_text_buffer(
+        "title_subtitle_overlay",
+        subtitle_text,
+        Some(subtitle_style),
+        Some(subtitle_position),
+    );
+}
 
+/// Sizes and places the title and subtitle for the window, and pulses the subtitle's gray.
+///
+/// # Arguments
+/// * `text_renderer` - The text renderer holding the buffers from [`create_title_text`]
+/// * `width` - Window width in pixels
+/// * `height` - Window height in pixels
+/// * `elapsed` - Seconds since the game started, which drive the subtitle's pulse
+pub fn layout_title_text(text_renderer: &mut TextRenderer, width: f32, height: f32, elapsed: f32) {
     // Apply DPI scaling based on height (consistent with other UI elements)
     let reference_height = 1080.0;
     let scale = (height / reference_height).clamp(0.7, 2.0);
@@ -253,14 +272,13 @@ pub fn handle_title(state: &mut AppState, window: &Window) {
     let subtitle_line_height = (subtitle_font_size * 1.3).clamp(18.0, 128.0); // increased max
 
     // Update title text with dynamic positioning
-    if let Ok(current_style) = state.text_renderer.get_style("title_mirador_overlay") {
+    if let Ok(current_style) = text_renderer.get_style("title_mirador_overlay") {
         let mut new_style = current_style;
         new_style.font_size = title_font_size;
         new_style.line_height = title_line_height;
 
         let title_text = "Mirador";
-        let (_min_x, text_width, text_height) =
-            state.text_renderer.measure_text(title_text, &new_style);
+        let (_min_x, text_width, text_height) = text_renderer.measure_text(title_text, &new_style);
 
         let title_position = TextPosition {
             x: width - text_width - 200.0, // 20px margin from right
@@ -269,7 +287,7 @@ pub fn handle_title(state: &mut AppState, window: &Window) {
             max_height: Some(text_height),
         };
 
-        let _ = state.text_renderer.update_text_style_and_position(
+        let _ = text_renderer.update_text_style_and_position(
             "title_mirador_overlay",
             title_text,
             new_style,
@@ -278,14 +296,13 @@ pub fn handle_title(state: &mut AppState, window: &Window) {
     }
 
     // Update subtitle text with dynamic positioning and color animation
-    if let Ok(current_style) = state.text_renderer.get_style("title_subtitle_overlay") {
+    if let Ok(current_style) = text_renderer.get_style("title_subtitle_overlay") {
         let mut new_style = current_style;
         new_style.font_size = subtitle_font_size;
         new_style.line_height = subtitle_line_height;
 
         // Animate subtitle color with a smooth sine wave
-        let elapsed_time = state.start_time.elapsed().as_secs_f32();
-        let color_shift = (elapsed_time * 1.5).sin() * 0.5 + 0.5; // Oscillate between 0.0 and 1.0
+        let color_shift = (elapsed * 1.5).sin() * 0.5 + 0.5; // Oscillate between 0.0 and 1.0
 
         // Create a color that shifts from a dark gray to a lighter gray
         let base_color = 100.0; // Base gray value
@@ -295,7 +312,7 @@ pub fn handle_title(state: &mut AppState, window: &Window) {
 
         let subtitle_text = "Click anywhere to get lost.";
         let (_min_x, text_width, text_height) =
-            state.text_renderer.measure_text(subtitle_text, &new_style);
+            text_renderer.measure_text(subtitle_text, &new_style);
 
         let subtitle_position = TextPosition {
             x: width - text_width - 200.0,   // Same x as title
@@ -304,13 +321,82 @@ pub fn handle_title(state: &mut AppState, window: &Window) {
             max_height: Some(text_height),
         };
 
-        let _ = state.text_renderer.update_text_style_and_position(
+        let _ = text_renderer.update_text_style_and_position(
             "title_subtitle_overlay",
             subtitle_text,
             new_style,
             subtitle_position,
         );
     }
+}
+
+/// Handles the title screen rendering and animation logic.
+///
+/// # Returns
+/// `true` if a frame was drawn, so the next one should be requested; `false` while there is
+/// no surface texture, as while the window is minimized
+pub fn handle_title(state: &mut AppState) -> bool {
+    // Explicitly hide game UI overlays on the title screen
+    if let Err(e) = state
+        .text_renderer
+        .set_buffer_visibility("main_timer", false)
+    {
+        eprintln!("Failed to hide main_timer: {}", e);
+    }
+    if let Err(e) = state.text_renderer.set_buffer_visibility("level", false) {
+        eprintln!("Failed to hide level: {}", e);
+    }
+    if let Err(e) = state.text_renderer.set_buffer_visibility("score", false) {
+        eprintln!("Failed to hide score: {}", e);
+    }
+    if let Err(e) = state.text_renderer.set_buffer_visibility("combo", false) {
+        eprintln!("Failed to hide combo: {}", e);
+    }
+    if let Err(e) = state.text_renderer.set_buffer_visibility("run_mode", false) {
+        eprintln!("Failed to hide run_mode: {}", e);
+    }
+    if let Err(e) = state
+        .text_renderer
+        .set_buffer_visibility("interaction_hint", false)
+    {
+        eprintln!("Failed to hide interaction_hint: {}", e);
+    }
+    if let Err(e) = state
+        .text_renderer
+        .set_buffer_visibility("event_announcement", false)
+    {
+        eprintln!("Failed to hide event_announcement: {}", e);
+    }
+    if let Err(e) = state
+        .text_renderer
+        .set_buffer_visibility("quick_confirmation", false)
+    {
+        eprintln!("Failed to hide quick_confirmation: {}", e);
+    }
+
+    // --- Dynamic placement for title and subtitle overlays ---
+    let width = state.wgpu_renderer.surface_config.width as f32;
+    let height = state.wgpu_renderer.surface_config.height as f32;
+    layout_title_text(
+        &mut state.text_renderer,
+        width,
+        height,
+        state.start_time.elapsed().as_secs_f32(),
+    );
+
+    state.title_menu.refresh_seed_field(
+        state.game_state.chosen_seed,
+        state.game_state.challenge.as_ref(),
+    );
+    state.refresh_challenge_notice();
+    state
+        .title_menu
+        .refresh_hardcore_toggle(state.game_state.hardcore);
+    state
+        .title_menu
+        .refresh_profile_label(paths::get().profile());
+    let continue_level = state.saved_game.as_ref().map(|saved| saved.level);
+    state.title_menu.refresh_continue_button(continue_level);
 
     // Render the title screen
     let mut encoder = state
@@ -318,15 +404,16 @@ pub fn handle_title(state: &mut AppState, window: &Window) {
         .device
         .create_command_encoder(&wgpu::CommandEncoderDescriptor { label: None });
     let (surface_view, surface_texture) = match state.wgpu_renderer.get_surface_texture_and_view() {
-        Ok((surface_texture, surface_view)) => (surface_view, surface_texture),
+        Ok(Some((surface_texture, surface_view))) => (surface_view, surface_texture),
+        Ok(None) => return false,
         Err(e) => {
             eprintln!("Failed to get surface texture: {}", e);
-            return;
+            return false;
         }
     };
     state
         .wgpu_renderer
-        .render_title_screen(&mut encoder, &surface_view, window);
+        .render_title_screen(&mut encoder, &surface_view);
     // Render overlay text
     state
         .text_renderer
@@ -339,11 +426,242 @@ pub fn handle_title(state: &mut AppState, window: &Window) {
     state
         .wgpu_renderer
         .render_text(&mut encoder, &surface_view, &mut state.text_renderer);
-    window.request_redraw();
+
+    // Render the daily challenge button over the title
+    if let Err(e) = state.title_menu.prepare(
+        &state.wgpu_renderer.device,
+        &state.wgpu_renderer.queue,
+        &state.wgpu_renderer.surface_config,
+    ) {
+        eprintln!("Failed to prepare title menu: {}", e);
+    }
+    {
+        let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view: &surface_view,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Load,
+                    store: wgpu::StoreOp::Store,
+                },
+            })],
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            label: Some("title menu render pass"),
+            occlusion_query_set: None,
+        });
+        if let Err(e) = state
+            .title_menu
+            .render(&state.wgpu_renderer.device, &mut render_pass)
+        {
+            eprintln!("Failed to render title menu: {}", e);
+        }
+    }
+    state.render_toasts(&mut encoder, &surface_view);
+    state
+        .wgpu_renderer
+        .stamp_opaque_alpha(&mut encoder, &surface_view);
     state.wgpu_renderer.queue.submit(Some(encoder.finish()));
     surface_texture.present();
 
     // Poll the device to process any pending operations
     // This helps ensure resources are properly cleaned up
     state.wgpu_renderer.device.poll(wgpu::Maintain::Poll);
+    true
+}
+
+/// Handles the maze codex screen: updates the cards and renders them over the title background.
+///
+/// # Arguments
+/// * `state` - The application state
+///
+/// # Returns
+/// `true` if a frame was drawn, as for [`handle_title`]
+pub fn handle_codex(state: &mut AppState) -> bool {
+    state.codex_menu.update(
+        &state.wgpu_renderer.device,
+        &state.wgpu_renderer.queue,
+        &state.codex,
+    );
+
+    let mut encoder = state
+        .wgpu_renderer
+        .device
+        .create_command_encoder(&wgpu::CommandEncoderDescriptor { label: None });
+    let (surface_view, surface_texture) = match state.wgpu_renderer.get_surface_texture_and_view() {
+        Ok(Some((surface_texture, surface_view))) => (surface_view, surface_texture),
+        Ok(None) => return false,
+        Err(e) => {
+            eprintln!("Failed to get surface texture: {}", e);
+            return false;
+        }
+    };
+    state
+        .wgpu_renderer
+        .render_title_screen(&mut encoder, &surface_view);
+
+    if let Err(e) = state.codex_menu.prepare(
+        &state.wgpu_renderer.device,
+        &state.wgpu_renderer.queue,
+        &state.wgpu_renderer.surface_config,
+    ) {
+        eprintln!("Failed to prepare codex menu: {}", e);
+    }
+    {
+        let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view: &surface_view,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Load,
+                    store: wgpu::StoreOp::Store,
+                },
+            })],
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            label: Some("codex menu render pass"),
+            occlusion_query_set: None,
+        });
+        if let Err(e) = state
+            .codex_menu
+            .render(&state.wgpu_renderer.device, &mut render_pass)
+        {
+            eprintln!("Failed to render codex menu: {}", e);
+        }
+    }
+    state.render_toasts(&mut encoder, &surface_view);
+    state
+        .wgpu_renderer
+        .stamp_opaque_alpha(&mut encoder, &surface_view);
+    state.wgpu_renderer.queue.submit(Some(encoder.finish()));
+    surface_texture.present();
+    state.wgpu_renderer.device.poll(wgpu::Maintain::Poll);
+    true
+}
+
+/// Handles the profile picker: renders the profile list and its buttons over the title
+/// background.
+///
+/// # Arguments
+/// * `state` - The application state
+///
+/// # Returns
+/// `true` if a frame was drawn, as for [`handle_title`]
+pub fn handle_profiles(state: &mut AppState) -> bool {
+    let mut encoder = state
+        .wgpu_renderer
+        .device
+        .create_command_encoder(&wgpu::CommandEncoderDescriptor { label: None });
+    let (surface_view, surface_texture) = match state.wgpu_renderer.get_surface_texture_and_view() {
+        Ok(Some((surface_texture, surface_view))) => (surface_view, surface_texture),
+        Ok(None) => return false,
+        Err(e) => {
+            eprintln!("Failed to get surface texture: {}", e);
+            return false;
+        }
+    };
+    state
+        .wgpu_renderer
+        .render_title_screen(&mut encoder, &surface_view);
+
+    if let Err(e) = state.profile_menu.prepare(
+        &state.wgpu_renderer.device,
+        &state.wgpu_renderer.queue,
+        &state.wgpu_renderer.surface_config,
+    ) {
+        eprintln!("Failed to prepare profile menu: {}", e);
+    }
+    {
+        let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view: &surface_view,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Load,
+                    store: wgpu::StoreOp::Store,
+                },
+            })],
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            label: Some("profile menu render pass"),
+            occlusion_query_set: None,
+        });
+        if let Err(e) = state
+            .profile_menu
+            .render(&state.wgpu_renderer.device, &mut render_pass)
+        {
+            eprintln!("Failed to render profile menu: {}", e);
+        }
+    }
+    state.render_toasts(&mut encoder, &surface_view);
+    state
+        .wgpu_renderer
+        .stamp_opaque_alpha(&mut encoder, &surface_view);
+    state.wgpu_renderer.queue.submit(Some(encoder.finish()));
+    surface_texture.present();
+    state.wgpu_renderer.device.poll(wgpu::Maintain::Poll);
+    true
+}
+
+/// Handles the error screen: renders the panic message and its button over the title background.
+///
+/// # Arguments
+/// * `state` - The application state
+///
+/// # Returns
+/// `true` if a frame was drawn, as for [`handle_title`]
+pub fn handle_error_screen(state: &mut AppState) -> bool {
+    let mut encoder = state
+        .wgpu_renderer
+        .device
+        .create_command_encoder(&wgpu::CommandEncoderDescriptor { label: None });
+    let (surface_view, surface_texture) = match state.wgpu_renderer.get_surface_texture_and_view() {
+        Ok(Some((surface_texture, surface_view))) => (surface_view, surface_texture),
+        Ok(None) => return false,
+        Err(e) => {
+            eprintln!("Failed to get surface texture: {}", e);
+            return false;
+        }
+    };
+    state
+        .wgpu_renderer
+        .render_title_screen(&mut encoder, &surface_view);
+
+    if let Err(e) = state.error_menu.prepare(
+        &state.wgpu_renderer.device,
+        &state.wgpu_renderer.queue,
+        &state.wgpu_renderer.surface_config,
+    ) {
+        eprintln!("Failed to prepare error menu: {}", e);
+    }
+    {
+        let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view: &surface_view,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Load,
+                    store: wgpu::StoreOp::Store,
+                },
+            })],
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            label: Some("error menu render pass"),
+            occlusion_query_set: None,
+        });
+        if let Err(e) = state
+            .error_menu
+            .render(&state.wgpu_renderer.device, &mut render_pass)
+        {
+            eprintln!("Failed to render error menu: {}", e);
+        }
+    }
+    state.render_toasts(&mut encoder, &surface_view);
+    state
+        .wgpu_renderer
+        .stamp_opaque_alpha(&mut encoder, &surface_view);
+    state.wgpu_renderer.queue.submit(Some(encoder.finish()));
+    surface_texture.present();
+    state.wgpu_renderer.device.poll(wgpu::Maintain::Poll);
+    true
 }
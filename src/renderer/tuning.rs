@@ -0,0 +1,141 @@
+//! Live-tunable renderer parameters for Mirador.
+//!
+//! This module provides [`RenderTuning`], the single source of truth for renderer values that
+//! are tweaked by eye rather than derived from game logic: the background clear color, the
+//! starfield, distance fog, how brightly the maze is shaded, and how smoothly the HUD compass turns. [`WgpuRenderer`](crate::renderer::wgpu_lib::WgpuRenderer) reads
+//! these values every frame, so changes made through the debug tuning panel apply immediately.
+//!
+//! Once a look has been dialed in, [`RenderTuning::to_rust_snippet`] produces a `Default`
+//! implementation that can be pasted back into this file to make the values permanent.
+
+/// Most stars the starfield may be tuned up to.
+///
+/// Star quads are indexed with `u16`, so the count must stay below `u16::MAX / 4`.
+pub const MAX_STAR_COUNT: usize = 2000;
+
+/// Renderer parameters that can be adjusted at runtime.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RenderTuning {
+    /// Clear color behind the maze and starfield, as linear RGBA
+    pub background_color: [f32; 4],
+    /// Number of stars in the background starfield
+    pub star_count: usize,
+    /// Multiplier applied to the starfield animation time (0.0 freezes the twinkle)
+    pub star_twinkle_speed: f32,
+    /// Color that distant geometry fades toward, as linear RGBA
+    pub fog_color: [f32; 4],
+    /// View distance at which fog begins
+    pub fog_start: f32,
+    /// View distance at which fog reaches full strength
+    pub fog_end: f32,
+    /// Maximum fog blend factor (0.0 disables fog, 1.0 fully hides distant geometry)
+    pub fog_strength: f32,
+    /// Multiplier on the maze's shading (1.0 leaves it unchanged, lower darkens it)
+    pub ambient: f32,
+    /// Seconds the compass needle takes to cover about two thirds of a turn (0.0 snaps)
+    pub compass_smoothing: f32,
+}
+
+impl Default for RenderTuning {
+    fn default() -> Self {
+        Self {
+            background_color: [0.003, 0.0003, 0.007, 1.0],
+            star_count: 100,
+            star_twinkle_speed: 1.0,
+            fog_color: [0.003, 0.0003, 0.007, 1.0],
+            fog_start: 200.0,
+            fog_end: 1500.0,
+            fog_strength: 0.0,
+            ambient: 1.0,
+            compass_smoothing: 0.08,
+        }
+    }
+}
+
+impl RenderTuning {
+    /// Returns the star count limited to [`MAX_STAR_COUNT`].
+    pub fn clamped_star_count(&self) -> usize {
+        self.star_count.min(MAX_STAR_COUNT)
+    }
+
+    /// Formats the current values as Rust source for committing as new defaults.
+    ///
+    /// # Arguments
+    /// * `fov` - The player's current field of view in degrees
+    ///
+    /// # Returns
+    /// A `Default` implementation for [`RenderTuning`] followed by the matching
+    /// `Player` field of view line
+    pub fn to_rust_snippet(self, fov: f32) -> String {
+        format!(
+            "impl Default for RenderTuning {{\n    \
+             fn default() -> Self {{\n        \
+             Self {{\n            \
+             background_color: {},\n            \
+             star_count: {},\n            \
+             star_twinkle_speed: {:?},\n            \
+             fog_color: {},\n            \
+             fog_start: {:?},\n            \
+             fog_end: {:?},\n            \
+             fog_strength: {:?},\n            \
+             ambient: {:?},\n            \
+             compass_smoothing: {:?},\n        \
+             }}\n    \
+             }}\n\
+             }}\n\
+             \n\
+             // Player::new\n\
+             fov: {:?},",
+            format_color(self.background_color),
+            self.star_count,
+            self.star_twinkle_speed,
+            format_color(self.fog_color),
+            self.fog_start,
+            self.fog_end,
+            self.fog_strength,
+            self.ambient,
+            self.compass_smoothing,
+            fov,
+        )
+    }
+}
+
+/// Formats an RGBA color as a Rust array literal, keeping a decimal point on whole numbers.
+fn format_color(color: [f32; 4]) -> String {
+    format!(
+        "[{:?}, {:?}, {:?}, {:?}]",
+        color[0], color[1], color[2], color[3]
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_snippet_contains_current_values() {
+        let tuning = RenderTuning {
+            background_color: [0.1, 0.2, 0.3, 1.0],
+            star_count: 250,
+            fog_strength: 0.75,
+            ambient: 0.5,
+            ..RenderTuning::default()
+        };
+        let snippet = tuning.to_rust_snippet(90.0);
+        assert!(snippet.contains("background_color: [0.1, 0.2, 0.3, 1.0],"));
+        assert!(snippet.contains("star_count: 250,"));
+        assert!(snippet.contains("fog_strength: 0.75,"));
+        assert!(snippet.contains("ambient: 0.5,"));
+        assert!(snippet.contains("compass_smoothing: 0.08,"));
+        assert!(snippet.contains("fov: 90.0,"));
+    }
+
+    #[test]
+    fn test_star_count_is_clamped() {
+        let tuning = RenderTuning {
+            star_count: 1_000_000,
+            ..RenderTuning::default()
+        };
+        assert_eq!(tuning.clamped_star_count(), MAX_STAR_COUNT);
+    }
+}
@@ -0,0 +1,301 @@
+//! Announcement Overlay
+//!
+//! This module draws the accessibility announcer's line: the last thing the
+//! [`Announcer`](crate::game::announcer::Announcer) said, in extra-large text on a dark panel
+//! centered at the bottom of the HUD safe area. It fades out as the line expires.
+//!
+//! The panel is kept between the toast stacks' columns when the HUD is wide enough, so
+//! toasts and announcements can be up at the same time. Long lines wrap rather than run past
+//! the HUD. The text is only measured again when it changes, so an announcement that stays
+//! up costs no more than any other line of text.
+
+use crate::renderer::color;
+use crate::renderer::gpu_memory::GpuMemoryTracker;
+use crate::renderer::rectangle::{Rectangle, RectangleRenderer};
+use crate::renderer::safe_area::{SafeArea, clamp_scissor};
+use crate::renderer::text::{TextPosition, TextRenderer, TextStyle};
+use crate::renderer::ui::toast_overlay::{toast_scale, toast_slots};
+use glyphon::{Color, Resolution};
+use wgpu::{Device, Queue, RenderPass, SurfaceConfiguration};
+use winit::dpi::PhysicalSize;
+
+/// Text buffer ID of the announcement line.
+const LINE_TEXT_ID: &str = "announcement_line";
+
+/// Where the announcement panel sits, in pixels from the top-left.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LineBox {
+    /// Left edge
+    pub x: f32,
+    /// Top edge
+    pub y: f32,
+    /// Panel width
+    pub width: f32,
+    /// Panel height
+    pub height: f32,
+}
+
+/// Returns the font size of the announcement line, well above every other line on the HUD.
+///
+/// # Arguments
+/// * `hud` - HUD safe area in pixels
+pub fn line_font_size(hud: &SafeArea) -> f32 {
+    (44.0 * toast_scale(hud)).clamp(28.0, 88.0)
+}
+
+/// Returns the padding between the announcement panel's edge and its text.
+///
+/// # Arguments
+/// * `hud` - HUD safe area in pixels
+fn line_padding(hud: &SafeArea) -> f32 {
+    16.0 * toast_scale(hud)
+}
+
+/// Returns how wide the announcement text may be before it wraps.
+///
+/// Leaves room for the toast stack on both sides, so the panel stays centered, unless that
+/// would leave less than half the HUD.
+///
+/// # Arguments
+/// * `hud` - HUD safe area in pixels
+pub fn line_max_width(hud: &SafeArea) -> f32 {
+    let gap = 10.0 * toast_scale(hud);
+    let toast_column = toast_slots(hud, 1)
+        .first()
+        .map_or(0.0, |slot| slot.x + slot.width - hud.x + gap);
+    let panel = (hud.width - 2.0 * toast_column).max(hud.width * 0.5);
+    panel - 2.0 * line_padding(hud)
+}
+
+/// Lays out the announcement panel around its text.
+///
+/// # Arguments
+/// * `hud` - HUD safe area in pixels
+/// * `text_size` - Width and height of the wrapped text
+///
+/// # Returns
+/// The panel, centered at the bottom of the HUD
+pub fn line_box(hud: &SafeArea, text_size: (f32, f32)) -> LineBox {
+    let padding = line_padding(hud);
+    let margin = 24.0 * toast_scale(hud);
+    let width = text_size.0.min(line_max_width(hud)) + 2.0 * padding;
+    let height = text_size.1 + 2.0 * padding;
+    LineBox {
+        x: hud.x + (hud.width - width) / 2.0,
+        y: hud.bottom() - margin - height,
+        width,
+        height,
+    }
+}
+
+/// Draws the announcement line on screen.
+pub struct AnnouncementOverlay {
+    /// Draws the panel
+    rectangle_renderer: RectangleRenderer,
+    /// Draws the line of text
+    text_renderer: TextRenderer,
+    /// Current window size
+    window_size: PhysicalSize<u32>,
+    /// HUD safe area the line was last laid out in, for clipping
+    hud: SafeArea,
+    /// The text last laid out, the HUD it was measured for, and its wrapped size
+    measured: Option<(String, SafeArea, (f32, f32))>,
+}
+
+impl AnnouncementOverlay {
+    /// Creates the overlay with nothing on screen.
+    ///
+    /// # Arguments
+    ///
+    /// * `device` - The WGPU device for rendering
+    /// * `queue` - The WGPU command queue
+    /// * `surface_format` - The surface texture format
+    /// * `window_size` - The window size, for sizing calculations
+    /// * `gpu_memory` - Tracker the glyph textures are counted in
+    pub fn new(
+        device: &Device,
+        queue: &Queue,
+        surface_format: wgpu::TextureFormat,
+        window_size: PhysicalSize<u32>,
+        gpu_memory: &GpuMemoryTracker,
+    ) -> Self {
+        let mut text_renderer =
+            TextRenderer::new(device, queue, surface_format, window_size, gpu_memory);
+        text_renderer.create_text_buffer(LINE_TEXT_ID, "", None, None);
+        let (width, height) = (window_size.width as f32, window_size.height as f32);
+        let mut rectangle_renderer = RectangleRenderer::new(device, surface_format);
+        rectangle_renderer.resize(width, height);
+        Self {
+            rectangle_renderer,
+            text_renderer,
+            window_size,
+            hud: SafeArea::full(width, height),
+            measured: None,
+        }
+    }
+
+    /// Handles window resize events.
+    ///
+    /// # Arguments
+    ///
+    /// * `queue` - The WGPU command queue
+    /// * `resolution` - The new window resolution
+    pub fn resize(&mut self, queue: &Queue, resolution: Resolution) {
+        self.text_renderer.resize(queue, resolution);
+        self.window_size = PhysicalSize::new(resolution.width, resolution.height);
+        self.rectangle_renderer
+            .resize(resolution.width as f32, resolution.height as f32);
+    }
+
+    /// Lays out the announcement line this frame and prepares its text.
+    ///
+    /// # Arguments
+    ///
+    /// * `device` - The WGPU device
+    /// * `queue` - The WGPU command queue
+    /// * `surface_config` - The surface configuration
+    /// * `hud` - HUD safe area in pixels
+    /// * `text` - The announcement to show
+    /// * `opacity` - How faded in the line is, from `0.0` to `1.0`
+    ///
+    /// # Returns
+    ///
+    /// `Ok(())` on success, or a `PrepareError` if text preparation fails
+    pub fn prepare(
+        &mut self,
+        device: &Device,
+        queue: &Queue,
+        surface_config: &SurfaceConfiguration,
+        hud: SafeArea,
+        text: &str,
+        opacity: f32,
+    ) -> Result<(), glyphon::PrepareError> {
+        self.hud = hud;
+        self.rectangle_renderer.clear_rectangles();
+
+        let font_size = line_font_size(&hud);
+        let style = TextStyle {
+            font_family: "Hanken Grotesk".to_string(),
+            font_size,
+            line_height: font_size * 1.25,
+            color: Color::rgba(245, 245, 250, (opacity * 255.0) as u8),
+            weight: glyphon::Weight::BOLD,
+            ..TextStyle::default()
+        };
+        let max_width = line_max_width(&hud);
+        let stale = self
+            .measured
+            .as_ref()
+            .is_none_or(|(shown, measured_hud, _)| shown != text || *measured_hud != hud);
+        if stale {
+            let (_, width, height) = self
+                .text_renderer
+                .measure_wrapped_text(text, &style, max_width);
+            self.measured = Some((text.to_string(), hud, (width, height)));
+        }
+        let text_size = self.measured.as_ref().map_or((0.0, 0.0), |m| m.2);
+        let panel = line_box(&hud, text_size);
+
+        self.rectangle_renderer.add_rectangle(
+            Rectangle::new(
+                panel.x,
+                panel.y,
+                panel.width,
+                panel.height,
+                color::srgba_from_u8([12, 12, 16, (opacity * 235.0) as u8]),
+            )
+            .with_corner_radius(12.0 * toast_scale(&hud)),
+        );
+
+        let padding = line_padding(&hud);
+        let position = TextPosition {
+            x: panel.x + padding,
+            y: panel.y + padding,
+            // A pixel of slack so rounding never wraps a line that was measured to fit
+            max_width: Some(text_size.0.min(max_width) + 1.0),
+            max_height: Some(text_size.1 + 1.0),
+        };
+        let _ =
+            self.text_renderer
+                .update_text_style_and_position(LINE_TEXT_ID, text, style, position);
+        self.text_renderer.prepare(device, queue, surface_config)
+    }
+
+    /// Draws the line prepared by [`prepare`](Self::prepare), clipped to the HUD.
+    ///
+    /// # Arguments
+    ///
+    /// * `device` - The WGPU device
+    /// * `render_pass` - The render pass to draw into
+    ///
+    /// # Returns
+    ///
+    /// `Ok(())` on success, or a `RenderError` if rendering fails
+    pub fn render(
+        &mut self,
+        device: &Device,
+        render_pass: &mut RenderPass,
+    ) -> Result<(), glyphon::RenderError> {
+        let Some((x, y, width, height)) = clamp_scissor(
+            self.hud.x as u32,
+            self.hud.y as u32,
+            self.hud.width as u32,
+            self.hud.height as u32,
+            self.window_size.width,
+            self.window_size.height,
+        ) else {
+            return Ok(());
+        };
+        render_pass.set_scissor_rect(x, y, width, height);
+        self.rectangle_renderer.render(device, render_pass);
+        self.text_renderer.render(render_pass)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::renderer::safe_area::DEFAULT_HUD_MAX_ASPECT;
+
+    #[test]
+    fn test_line_stays_in_the_hud_and_beside_the_toasts() {
+        let windows = [
+            (1920.0, 1080.0),
+            (3840.0, 1080.0),
+            (1280.0, 720.0),
+            (800.0, 1200.0),
+            (320.0, 200.0),
+            (3840.0, 2160.0),
+        ];
+        for (width, height) in windows {
+            let hud = SafeArea::centered(width, height, DEFAULT_HUD_MAX_ASPECT);
+            let font_size = line_font_size(&hud);
+            assert!(font_size >= 28.0);
+
+            // The widest line there is room for, wrapped onto two lines
+            let text_size = (line_max_width(&hud), font_size * 2.5);
+            let panel = line_box(&hud, text_size);
+            assert!(
+                hud.contains(panel.x, panel.y, panel.width, panel.height),
+                "{}x{}: {:?} outside {:?}",
+                width,
+                height,
+                panel,
+                hud
+            );
+            assert!((panel.x + panel.width / 2.0 - (hud.x + hud.width / 2.0)).abs() < 0.01);
+
+            // Landscape windows the size of a laptop screen or larger keep clear of toasts
+            if hud.width >= 1280.0 && hud.width > hud.height {
+                for slot in toast_slots(&hud, 3) {
+                    assert!(
+                        slot.x + slot.width < panel.x,
+                        "{}x{}: line reaches the toasts",
+                        width,
+                        height
+                    );
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,234 @@
+//! Audio Debug Overlay
+//!
+//! This module draws the audio manager's introspection snapshot: every source that is
+//! playing with its channel, volume, state, and distance from the listener, followed by the
+//! last few play, stop, duck, and occlusion events. It sits on a dark panel in the top-left
+//! corner of the HUD and is only laid out while it is open, so a closed overlay costs nothing.
+
+use crate::game::audio_debug::AudioDebugSnapshot;
+use crate::renderer::color;
+use crate::renderer::gpu_memory::GpuMemoryTracker;
+use crate::renderer::rectangle::{Rectangle, RectangleRenderer};
+use crate::renderer::safe_area::{SafeArea, clamp_scissor};
+use crate::renderer::text::{TextPosition, TextRenderer, TextStyle};
+use crate::renderer::ui::announcement_overlay::LineBox;
+use crate::renderer::ui::toast_overlay::toast_scale;
+use glyphon::{Color, Resolution};
+use wgpu::{Device, Queue, RenderPass, SurfaceConfiguration};
+use winit::dpi::PhysicalSize;
+
+/// Text buffer ID of the snapshot table.
+const SNAPSHOT_TEXT_ID: &str = "audio_debug_snapshot";
+
+/// Returns the font size of the snapshot table.
+///
+/// # Arguments
+/// * `hud` - HUD safe area in pixels
+pub fn table_font_size(hud: &SafeArea) -> f32 {
+    (14.0 * toast_scale(hud)).clamp(11.0, 28.0)
+}
+
+/// Returns the padding between the panel's edge and its text.
+///
+/// # Arguments
+/// * `hud` - HUD safe area in pixels
+fn table_padding(hud: &SafeArea) -> f32 {
+    10.0 * toast_scale(hud)
+}
+
+/// Lays out the snapshot panel around its text.
+///
+/// # Arguments
+/// * `hud` - HUD safe area in pixels
+/// * `text_size` - Width and height of the wrapped text
+///
+/// # Returns
+/// The panel, in the top-left corner of the HUD and no larger than it
+pub fn table_box(hud: &SafeArea, text_size: (f32, f32)) -> LineBox {
+    let padding = table_padding(hud);
+    let margin = 16.0 * toast_scale(hud);
+    LineBox {
+        x: hud.x + margin,
+        y: hud.y + margin,
+        width: (text_size.0 + 2.0 * padding).min(hud.width - 2.0 * margin),
+        height: (text_size.1 + 2.0 * padding).min(hud.height - 2.0 * margin),
+    }
+}
+
+/// Draws the audio debug snapshot on screen.
+pub struct AudioDebugOverlay {
+    /// Whether the overlay is open
+    pub visible: bool,
+    /// Draws the panel
+    rectangle_renderer: RectangleRenderer,
+    /// Draws the snapshot table
+    text_renderer: TextRenderer,
+    /// Current window size
+    window_size: PhysicalSize<u32>,
+    /// HUD safe area the table was last laid out in, for clipping
+    hud: SafeArea,
+}
+
+impl AudioDebugOverlay {
+    /// Creates the overlay, closed.
+    ///
+    /// # Arguments
+    ///
+    /// * `device` - The WGPU device for rendering
+    /// * `queue` - The WGPU command queue
+    /// * `surface_format` - The surface texture format
+    /// * `window_size` - The window size, for sizing calculations
+    /// * `gpu_memory` - Tracker the glyph textures are counted in
+    pub fn new(
+        device: &Device,
+        queue: &Queue,
+        surface_format: wgpu::TextureFormat,
+        window_size: PhysicalSize<u32>,
+        gpu_memory: &GpuMemoryTracker,
+    ) -> Self {
+        let mut text_renderer =
+            TextRenderer::new(device, queue, surface_format, window_size, gpu_memory);
+        text_renderer.create_text_buffer(SNAPSHOT_TEXT_ID, "", None, None);
+        let (width, height) = (window_size.width as f32, window_size.height as f32);
+        let mut rectangle_renderer = RectangleRenderer::new(device, surface_format);
+        rectangle_renderer.resize(width, height);
+        Self {
+            visible: false,
+            rectangle_renderer,
+            text_renderer,
+            window_size,
+            hud: SafeArea::full(width, height),
+        }
+    }
+
+    /// Handles window resize events.
+    ///
+    /// # Arguments
+    ///
+    /// * `queue` - The WGPU command queue
+    /// * `resolution` - The new window resolution
+    pub fn resize(&mut self, queue: &Queue, resolution: Resolution) {
+        self.text_renderer.resize(queue, resolution);
+        self.window_size = PhysicalSize::new(resolution.width, resolution.height);
+        self.rectangle_renderer
+            .resize(resolution.width as f32, resolution.height as f32);
+    }
+
+    /// Lays out the snapshot this frame and prepares its text.
+    ///
+    /// # Arguments
+    ///
+    /// * `device` - The WGPU device
+    /// * `queue` - The WGPU command queue
+    /// * `surface_config` - The surface configuration
+    /// * `hud` - HUD safe area in pixels
+    /// * `snapshot` - What the audio manager is playing
+    ///
+    /// # Returns
+    ///
+    /// `Ok(())` on success, or a `PrepareError` if text preparation fails
+    pub fn prepare(
+        &mut self,
+        device: &Device,
+        queue: &Queue,
+        surface_config: &SurfaceConfiguration,
+        hud: SafeArea,
+        snapshot: &AudioDebugSnapshot,
+    ) -> Result<(), glyphon::PrepareError> {
+        self.hud = hud;
+        self.rectangle_renderer.clear_rectangles();
+
+        let text = snapshot.to_string();
+        let font_size = table_font_size(&hud);
+        let style = TextStyle {
+            font_size,
+            line_height: font_size * 1.3,
+            color: Color::rgb(220, 235, 225),
+            ..TextStyle::default()
+        };
+        let padding = table_padding(&hud);
+        let (_, width, height) =
+            self.text_renderer
+                .measure_wrapped_text(&text, &style, hud.width * 0.6);
+        let panel = table_box(&hud, (width, height));
+
+        self.rectangle_renderer.add_rectangle(
+            Rectangle::new(
+                panel.x,
+                panel.y,
+                panel.width,
+                panel.height,
+                color::srgba_from_u8([8, 12, 10, 220]),
+            )
+            .with_corner_radius(8.0 * toast_scale(&hud)),
+        );
+
+        let position = TextPosition {
+            x: panel.x + padding,
+            y: panel.y + padding,
+            // A pixel of slack so rounding never wraps a line that was measured to fit
+            max_width: Some(panel.width - 2.0 * padding + 1.0),
+            max_height: Some(panel.height - 2.0 * padding),
+        };
+        let _ = self.text_renderer.update_text_style_and_position(
+            SNAPSHOT_TEXT_ID,
+            &text,
+            style,
+            position,
+        );
+        self.text_renderer.prepare(device, queue, surface_config)
+    }
+
+    /// Draws the table prepared by [`prepare`](Self::prepare), clipped to the HUD.
+    ///
+    /// # Arguments
+    ///
+    /// * `device` - The WGPU device
+    /// * `render_pass` - The render pass to draw into
+    ///
+    /// # Returns
+    ///
+    /// `Ok(())` on success, or a `RenderError` if rendering fails
+    pub fn render(
+        &mut self,
+        device: &Device,
+        render_pass: &mut RenderPass,
+    ) -> Result<(), glyphon::RenderError> {
+        let Some((x, y, width, height)) = clamp_scissor(
+            self.hud.x as u32,
+            self.hud.y as u32,
+            self.hud.width as u32,
+            self.hud.height as u32,
+            self.window_size.width,
+            self.window_size.height,
+        ) else {
+            return Ok(());
+        };
+        render_pass.set_scissor_rect(x, y, width, height);
+        self.rectangle_renderer.render(device, render_pass);
+        self.text_renderer.render(render_pass)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::renderer::safe_area::DEFAULT_HUD_MAX_ASPECT;
+
+    #[test]
+    fn test_table_stays_in_the_hud() {
+        for (width, height) in [(1920.0, 1080.0), (1280.0, 720.0), (320.0, 200.0)] {
+            let hud = SafeArea::centered(width, height, DEFAULT_HUD_MAX_ASPECT);
+            // A table far larger than any HUD
+            let panel = table_box(&hud, (width * 2.0, height * 2.0));
+            assert!(
+                hud.contains(panel.x, panel.y, panel.width, panel.height),
+                "{}x{}: {:?} outside {:?}",
+                width,
+                height,
+                panel,
+                hud
+            );
+        }
+    }
+}
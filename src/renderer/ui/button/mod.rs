@@ -6,7 +6,8 @@
 //! - Text rendering with multiple text elements (main text, level text, tooltips)
 //! - Icon support for upgrade buttons
 //! - Responsive positioning and scaling
-//! - Mouse input handling
+//! - Mouse input handling, and keyboard and gamepad focus
+//! - Delayed, fading tooltips, optionally floating in a panel by the cursor
 //!
 //! The button system supports various button types:
 //! - Standard buttons with text
@@ -16,17 +17,13 @@
 //!
 //! # Examples
 //!
-//! ```rust
+//! ```rust,ignore
 //! // Create a basic button
-//! let button = Button::new("start_game", "Start Game")
-//!     .with_style(ButtonStyle::default())
+//! let button = Button::primary("start_game", "Start Game")
 //!     .with_position(ButtonPosition::new(100.0, 100.0, 200.0, 50.0));
 //!
 //! // Create an upgrade button with level text and tooltip
-//! let upgrade_button = Button::new("speed_upgrade", "Speed Boost")
-//!     .with_style(upgrade_style)
-//!     .with_level_text()
-//!     .with_tooltip_text();
+//! let upgrade_button = Button::upgrade_card("speed_upgrade", "Speed Boost");
 //! ```
 
 // Button module - contains all button-related functionality
@@ -40,18 +37,219 @@ pub mod utils;
 // Re-export types for convenience
 pub use styles::*;
 pub use types::{ButtonAnchor, ButtonPosition, ButtonSpacing, ButtonState, ButtonStyle, TextAlign};
-pub use utils::ColorExt;
 
 use crate::assets;
+use crate::game::clock::GameClock;
+use crate::game::input::MenuNav;
+use crate::renderer::color;
+use crate::renderer::gpu_memory::GpuMemoryTracker;
 use crate::renderer::icon::{Icon, IconRenderer};
 use crate::renderer::rectangle::{Rectangle, RectangleRenderer};
 use crate::renderer::text::{TextPosition, TextRenderer, TextStyle};
 use glyphon::{Color, Style, Weight};
 use std::collections::{HashMap, HashSet};
+use std::time::{Duration, Instant};
 use wgpu::{self, Device, Queue, RenderPass, SurfaceConfiguration};
 use winit::dpi::PhysicalSize;
 use winit::event::{ElementState, MouseButton, WindowEvent};
-use winit::window::Window;
+
+/// Narrowest window, in pixels, buttons are laid out in
+pub const MIN_USABLE_WIDTH: u32 = 320;
+
+/// Shortest window, in pixels, buttons are laid out in
+pub const MIN_USABLE_HEIGHT: u32 = 240;
+
+/// ID of the text buffer shown in place of the buttons when the window is too small
+const WINDOW_TOO_SMALL_TEXT_ID: &str = "window_too_small";
+
+/// Cursor position used while it isn't known, outside every button
+const NO_CURSOR: (f32, f32) = (f32::NEG_INFINITY, f32::NEG_INFINITY);
+
+/// Seconds the hover scale takes to grow from normal size to the hovered scale in
+/// [`BUTTON_VISUALS`]
+const HOVER_SCALE_DURATION: f32 = 0.1;
+
+/// How long the cursor has to rest on a button before its tooltip starts to appear
+const TOOLTIP_DELAY: Duration = Duration::from_millis(400);
+
+/// How long a tooltip takes to fade in once [`TOOLTIP_DELAY`] has passed
+const TOOLTIP_FADE: Duration = Duration::from_millis(100);
+
+/// Widest a floating tooltip's text wraps at, in pixels at 1080p
+const TOOLTIP_PANEL_WIDTH: f32 = 340.0;
+
+/// Space between a floating tooltip panel's edge and its text, in pixels at 1080p
+const TOOLTIP_PANEL_PADDING: f32 = 12.0;
+
+/// Gap between the cursor and a floating tooltip panel, in pixels at 1080p
+const TOOLTIP_CURSOR_OFFSET: f32 = 18.0;
+
+/// Background of a floating tooltip panel (sRGB, near-black slate)
+const TOOLTIP_PANEL_COLOR: [f32; 4] = [0.11, 0.12, 0.15, 0.94];
+
+/// Where an upgrade button's icon ends and its level text starts, as a fraction of the
+/// button height from the top, while the tooltip is drawn inside the button
+const IN_CARD_LEVEL_OFFSET: f32 = 0.55;
+
+/// The same offset with floating tooltips, where the icon grows into the tooltip's space
+const FLOATING_LEVEL_OFFSET: f32 = 0.75;
+
+/// Moves a button's hover scale toward its target at a constant rate
+///
+/// The rate covers the full normal-to-hover range in [`HOVER_SCALE_DURATION`], so
+/// smaller steps such as hover to pressed finish sooner.
+///
+/// # Arguments
+/// * `current` - The scale drawn last frame
+/// * `target` - The scale for the button's current state
+/// * `dt` - Seconds since the last update
+///
+/// # Returns
+/// The new scale, never overshooting `target`
+pub fn step_hover_scale(current: f32, target: f32, dt: f32) -> f32 {
+    let max_step = (BUTTON_VISUALS.hover_scale - 1.0) / HOVER_SCALE_DURATION * dt.max(0.0);
+    current + (target - current).clamp(-max_step, max_step)
+}
+
+/// Returns how visible a button's tooltip should be
+///
+/// A focused button shows its tooltip straight away. A hovered one waits for
+/// [`TOOLTIP_DELAY`] and then fades in over [`TOOLTIP_FADE`].
+///
+/// # Arguments
+/// * `hovered_for` - How long the cursor has rested on the button, or `None` if it isn't on it
+/// * `focused` - Whether the button has keyboard focus
+///
+/// # Returns
+/// The tooltip opacity, from 0.0 (hidden) to 1.0
+pub fn tooltip_opacity(hovered_for: Option<Duration>, focused: bool) -> f32 {
+    if focused {
+        return 1.0;
+    }
+    match hovered_for {
+        Some(hovered_for) => (hovered_for.saturating_sub(TOOLTIP_DELAY).as_secs_f32()
+            / TOOLTIP_FADE.as_secs_f32())
+        .min(1.0),
+        None => 0.0,
+    }
+}
+
+/// Places a floating tooltip panel next to a point, keeping it on screen
+///
+/// The panel goes below and to the right of the point, flips to the left or above when it
+/// would run off that edge, and is finally clamped inside the window.
+///
+/// # Arguments
+/// * `anchor` - The point the panel is placed by, usually the cursor
+/// * `size` - Width and height of the panel
+/// * `window` - Width and height of the window
+/// * `offset` - Gap between the anchor and the panel
+///
+/// # Returns
+/// The top-left corner of the panel
+pub fn place_tooltip_panel(
+    anchor: (f32, f32),
+    size: (f32, f32),
+    window: (f32, f32),
+    offset: f32,
+) -> (f32, f32) {
+    let place = |anchor: f32, size: f32, window: f32| {
+        let start = if anchor + offset + size > window {
+            anchor - offset - size
+        } else {
+            anchor + offset
+        };
+        start.min(window - size).max(0.0)
+    };
+    (
+        place(anchor.0, size.0, window.0),
+        place(anchor.1, size.1, window.1),
+    )
+}
+
+/// Steps a focus index through a list, wrapping around both ends
+///
+/// # Arguments
+/// * `len` - Number of focusable items
+/// * `current` - Index of the focused item, if any
+/// * `step` - How many items to move; positive moves forward
+///
+/// # Returns
+/// The new index, or `None` if there is nothing to focus. With nothing focused, stepping
+/// forward lands on the first item and stepping back on the last.
+pub fn step_focus(len: usize, current: Option<usize>, step: isize) -> Option<usize> {
+    if len == 0 {
+        return None;
+    }
+    let len = len as isize;
+    let index = match current {
+        Some(current) => (current as isize + step).rem_euclid(len),
+        None if step < 0 => len - 1,
+        None => 0,
+    };
+    Some(index as usize)
+}
+
+/// Builds the text style for a button's tooltip
+///
+/// # Arguments
+/// * `style` - The button's style
+/// * `floating` - Whether the tooltip is drawn in a floating panel instead of the button
+pub fn tooltip_text_style(style: &ButtonStyle, floating: bool) -> TextStyle {
+    let mut tooltip_style = style.text_style.clone();
+    tooltip_style.style = Style::Normal;
+    if floating {
+        // Light text on the dark panel, a little smaller than in-card tooltips
+        tooltip_style.font_size = style.text_style.font_size * 0.6;
+        tooltip_style.color = Color::rgb(236, 238, 242);
+        tooltip_style.weight = Weight::NORMAL;
+    } else {
+        tooltip_style.font_size = style.text_style.font_size * 0.7; // 70% of main text size
+        // Same color as main text
+        tooltip_style.color = BUTTON_VISUALS.text_color(style, ButtonState::Normal);
+    }
+    tooltip_style.line_height = tooltip_style.font_size * 1.05;
+    tooltip_style
+}
+
+/// Checks whether a window is large enough to lay buttons out in
+///
+/// Below this size padding and fixed minimum button sizes no longer fit, so layout
+/// math starts producing overlapping or negative sizes.
+///
+/// # Arguments
+/// * `width` - Window width in pixels
+/// * `height` - Window height in pixels
+pub fn is_usable_window_size(width: u32, height: u32) -> bool {
+    width >= MIN_USABLE_WIDTH && height >= MIN_USABLE_HEIGHT
+}
+
+/// Finds the topmost visible button under a point
+///
+/// Disabled buttons count, so a click on one goes nowhere rather than through to whatever
+/// is behind it.
+///
+/// # Arguments
+/// * `order` - Button IDs in the order they are drawn
+/// * `buttons` - The buttons, by ID
+/// * `x` - X coordinate in physical pixels
+/// * `y` - Y coordinate in physical pixels
+///
+/// # Returns
+/// The ID of the last-drawn button containing the point, if any
+pub fn topmost_button_at<'a>(
+    order: &'a [String],
+    buttons: &'a HashMap<String, Button>,
+    x: f32,
+    y: f32,
+) -> Option<&'a str> {
+    order
+        .iter()
+        .rev()
+        .filter_map(|id| buttons.get(id))
+        .find(|button| button.visible && button.covers_point(x, y))
+        .map(|button| button.id.as_str())
+}
 
 /// Represents a UI button with text, styling, and interactive behavior
 ///
@@ -87,6 +285,12 @@ pub struct Button {
     pub tooltip_text_id: Option<String>,
     /// ID of the icon to display (for upgrade buttons)
     pub icon_id: Option<String>,
+    /// Scale the button is currently drawn at, eased toward [`Button::target_hover_scale`]
+    pub hover_scale: f32,
+    /// Hover clock reading when the cursor came onto the button, `None` while it's elsewhere
+    pub hover_started: Option<Duration>,
+    /// Opacity the tooltip is currently drawn at
+    pub tooltip_opacity: f32,
 }
 
 impl Button {
@@ -112,9 +316,51 @@ impl Button {
             level_text_id: None,
             tooltip_text_id: None,
             icon_id: None,
+            hover_scale: 1.0,
+            hover_started: None,
+            tooltip_opacity: 0.0,
         }
     }
 
+    /// Creates a button for the main action on a screen
+    ///
+    /// # Arguments
+    /// * `id` - Unique identifier for the button
+    /// * `text` - The text to display on the button
+    pub fn primary(id: &str, text: &str) -> Self {
+        Self::new(id, text).with_style(ButtonPreset::Primary.style())
+    }
+
+    /// Creates a button for an action that needs a moment's thought
+    ///
+    /// # Arguments
+    /// * `id` - Unique identifier for the button
+    /// * `text` - The text to display on the button
+    pub fn secondary(id: &str, text: &str) -> Self {
+        Self::new(id, text).with_style(ButtonPreset::Secondary.style())
+    }
+
+    /// Creates a button for a destructive or irreversible action
+    ///
+    /// # Arguments
+    /// * `id` - Unique identifier for the button
+    /// * `text` - The text to display on the button
+    pub fn destructive(id: &str, text: &str) -> Self {
+        Self::new(id, text).with_style(ButtonPreset::Destructive.style())
+    }
+
+    /// Creates an upgrade card with level text and a tooltip
+    ///
+    /// # Arguments
+    /// * `id` - Unique identifier for the button
+    /// * `text` - The text to display on the button
+    pub fn upgrade_card(id: &str, text: &str) -> Self {
+        Self::new(id, text)
+            .with_style(ButtonPreset::UpgradeCard.style())
+            .with_level_text()
+            .with_tooltip_text()
+    }
+
     /// Sets the button's visual style
     ///
     /// # Arguments
@@ -139,6 +385,21 @@ impl Button {
         self
     }
 
+    /// Sets the style of the button's main text
+    ///
+    /// Screens size their text for the window with this, leaving the rest of the style
+    /// to its preset.
+    ///
+    /// # Arguments
+    /// * `text_style` - The TextStyle to draw the main text in
+    ///
+    /// # Returns
+    /// Self for method chaining
+    pub fn with_text_style(mut self, text_style: TextStyle) -> Self {
+        self.style.text_style = text_style;
+        self
+    }
+
     /// Sets the text alignment within the button
     ///
     /// # Arguments
@@ -168,7 +429,9 @@ impl Button {
     ///
     /// Tooltip text provides additional information about the button's function.
     /// It's displayed in a smaller font below the level text (if present) or
-    /// below the main text.
+    /// below the main text, or in a floating panel by the cursor when the manager has
+    /// [`floating_tooltips`](ButtonManager::floating_tooltips) on. Either way it stays
+    /// hidden until the button has been hovered for a moment or gets keyboard focus.
     ///
     /// # Returns
     /// Self for method chaining
@@ -191,16 +454,21 @@ impl Button {
     /// Only visible and enabled buttons can be hit.
     ///
     /// # Arguments
-    /// * `x` - X coordinate of the point to test
-    /// * `y` - Y coordinate of the point to test
+    /// * `x` - X coordinate of the point to test, in physical pixels
+    /// * `y` - Y coordinate of the point to test, in physical pixels
     ///
     /// # Returns
     /// `true` if the point is within the button's bounds, `false` otherwise
     pub fn contains_point(&self, x: f32, y: f32) -> bool {
-        if !self.visible || !self.enabled {
-            return false;
-        }
+        self.visible && self.enabled && self.covers_point(x, y)
+    }
 
+    /// Checks if a point is within the button's bounds, whether or not it can be clicked
+    ///
+    /// # Arguments
+    /// * `x` - X coordinate to test
+    /// * `y` - Y coordinate to test
+    fn covers_point(&self, x: f32, y: f32) -> bool {
         let (actual_x, actual_y) = self.position.calculate_actual_position();
 
         x >= actual_x
@@ -208,6 +476,51 @@ impl Button {
             && y >= actual_y
             && y <= actual_y + self.position.height
     }
+
+    /// Returns the scale the button should settle at for its current state
+    ///
+    /// Only upgrade buttons (`ButtonSpacing::Tall`) grow on hover and press.
+    ///
+    /// # Returns
+    /// The target scale, where 1.0 is the button's normal size
+    pub fn target_hover_scale(&self) -> f32 {
+        if let ButtonSpacing::Tall(_) = self.style.spacing {
+            match self.state {
+                ButtonState::Hover => BUTTON_VISUALS.hover_scale,
+                ButtonState::Pressed => BUTTON_VISUALS.pressed_scale,
+                _ => 1.0,
+            }
+        } else {
+            1.0
+        }
+    }
+
+    /// Returns whether the hover scale is still easing toward its target
+    pub fn is_scaling(&self) -> bool {
+        self.hover_scale != self.target_hover_scale()
+    }
+
+    /// Returns the text color and weight for the button's current state
+    pub fn text_appearance(&self) -> (Color, Weight) {
+        let weight = match self.state {
+            ButtonState::Normal => self.style.text_style.weight,
+            ButtonState::Hover => Weight::BOLD,
+            ButtonState::Pressed => Weight::MEDIUM,
+            ButtonState::Disabled => Weight::NORMAL,
+        };
+        (BUTTON_VISUALS.text_color(&self.style, self.state), weight)
+    }
+
+    /// Returns how much the button's text is scaled at its current hover scale
+    ///
+    /// Only upgrade buttons (`ButtonSpacing::Tall`) grow their text.
+    pub fn text_scale(&self) -> f32 {
+        if let ButtonSpacing::Tall(_) = self.style.spacing {
+            BUTTON_VISUALS.text_scale(self.hover_scale)
+        } else {
+            1.0
+        }
+    }
 }
 
 /// Manages a collection of buttons and handles their rendering and interaction
@@ -234,7 +547,10 @@ pub struct ButtonManager {
     pub icon_renderer: IconRenderer,
     /// Current window dimensions for responsive positioning
     pub window_size: PhysicalSize<u32>,
-    /// Current mouse cursor position
+    /// Current mouse cursor position in physical pixels
+    ///
+    /// Button positions, window sizes and the cursor are all kept in physical pixels, the
+    /// unit winit reports `CursorMoved` in, so hit testing never converts between units.
     pub mouse_position: (f32, f32),
     /// Whether the left mouse button is currently pressed
     pub mouse_pressed: bool,
@@ -249,6 +565,29 @@ pub struct ButtonManager {
     /// Set of buttons that were pressed during the current mouse press cycle
     /// This helps handle platform-specific timing differences in mouse event processing
     pub pressed_buttons: std::collections::HashSet<String>,
+    /// Whether the window is below the minimum usable size
+    ///
+    /// While set, every button is treated as invisible and layout is skipped; a
+    /// "window too small" message is drawn instead.
+    pub window_too_small: bool,
+    /// When the hover scales were last advanced, used to time the hover animation
+    pub last_hover_update: Instant,
+    /// Clock tooltip delays are timed on
+    ///
+    /// It advances by the same clamped step as the hover animation, so time spent paused or
+    /// on another screen doesn't count toward a tooltip's delay.
+    pub hover_clock: GameClock,
+    /// ID of the button with keyboard focus, which is drawn hovered and shows its tooltip
+    /// without a delay
+    pub focused_button: Option<String>,
+    /// Whether tooltips are drawn in a panel by the cursor instead of inside their button
+    ///
+    /// Set this before adding buttons, as it decides where their tooltip text is laid out.
+    pub floating_tooltips: bool,
+    /// Background of the floating tooltip currently shown, if any
+    pub tooltip_panel: Option<Rectangle>,
+    /// Rectangle renderer for the tooltip panel, drawn after the buttons so it covers them
+    pub tooltip_renderer: RectangleRenderer,
 }
 
 impl ButtonManager {
@@ -264,7 +603,8 @@ impl ButtonManager {
     /// * `device` - WGPU device for creating render resources
     /// * `queue` - WGPU queue for uploading resources
     /// * `surface_format` - Texture format for the render surface
-    /// * `window` - Window reference for size information
+    /// * `window_size` - The window size, for sizing calculations
+    /// * `gpu_memory` - Tracker the icon and glyph textures are counted in
     ///
     /// # Returns
     /// A new ButtonManager instance ready for use
@@ -272,17 +612,35 @@ impl ButtonManager {
         device: &Device,
         queue: &Queue,
         surface_format: wgpu::TextureFormat,
-        window: &Window,
+        window_size: PhysicalSize<u32>,
+        gpu_memory: &GpuMemoryTracker,
     ) -> Self {
-        let text_renderer = TextRenderer::new(device, queue, surface_format, window);
+        let mut text_renderer =
+            TextRenderer::new(device, queue, surface_format, window_size, gpu_memory);
         let rectangle_renderer = RectangleRenderer::new(device, surface_format);
-        let mut icon_renderer = IconRenderer::new(device, surface_format);
-        let window_size = window.inner_size();
+        let tooltip_renderer = RectangleRenderer::new(device, surface_format);
+        let mut icon_renderer = IconRenderer::new(device, queue, surface_format, gpu_memory);
+
+        // Shown in place of the buttons while the window is too small to lay them out
+        text_renderer.create_text_buffer(
+            WINDOW_TOO_SMALL_TEXT_ID,
+            "Window too small",
+            Some(TextStyle {
+                font_size: 14.0,
+                line_height: 18.0,
+                ..TextStyle::default()
+            }),
+            None,
+        );
+        let _ = text_renderer.set_buffer_visibility(WINDOW_TOO_SMALL_TEXT_ID, false);
 
         // Load all upgrade icons from embedded assets
         for (id, texture_data) in assets::icon_textures() {
             if let Err(e) = icon_renderer.load_texture_from_data(device, queue, texture_data, id) {
-                println!("Failed to load icon texture {}: {}", id, e);
+                eprintln!(
+                    "Failed to load icon texture {}: {}, using missing icon",
+                    id, e
+                );
             }
         }
 
@@ -300,7 +658,127 @@ impl ButtonManager {
             last_mouse_position: (0.0, 0.0),
             last_mouse_pressed: false,
             pressed_buttons: HashSet::new(),
+            window_too_small: false,
+            last_hover_update: Instant::now(),
+            hover_clock: GameClock::new(),
+            focused_button: None,
+            floating_tooltips: false,
+            tooltip_panel: None,
+            tooltip_renderer,
+        }
+    }
+
+    /// Hides or restores the buttons as the window crosses the minimum usable size
+    ///
+    /// Below [`MIN_USABLE_WIDTH`] x [`MIN_USABLE_HEIGHT`] all button text is hidden,
+    /// buttons can't be hovered or clicked, and the "window too small" message is
+    /// centered in the window. Growing the window back restores the button text.
+    ///
+    /// # Returns
+    /// `true` if the window is large enough to lay the buttons out
+    fn apply_minimum_window_size(&mut self) -> bool {
+        let too_small = !is_usable_window_size(self.window_size.width, self.window_size.height);
+        if !too_small && !self.window_too_small {
+            return true;
+        }
+
+        // Buttons added while the window was too small need hiding too, so sweep every
+        // button on each layout rather than only when the size limit is crossed
+        for button in self.buttons.values() {
+            let text_ids = std::iter::once(&button.text_id).chain(button.level_text_id.as_ref());
+            for text_id in text_ids {
+                let _ = self
+                    .text_renderer
+                    .set_buffer_visibility(text_id, !too_small);
+            }
+            // Tooltips only come back if they were showing
+            if let Some(tooltip_id) = &button.tooltip_text_id {
+                let _ = self
+                    .text_renderer
+                    .set_buffer_visibility(tooltip_id, !too_small && button.tooltip_opacity > 0.0);
+            }
         }
+        let _ = self
+            .text_renderer
+            .set_buffer_visibility(WINDOW_TOO_SMALL_TEXT_ID, too_small);
+        self.window_too_small = too_small;
+
+        if too_small {
+            self.mouse_pressed = false;
+            self.pressed_buttons.clear();
+            self.just_clicked = None;
+
+            let style = self
+                .text_renderer
+                .get_style(WINDOW_TOO_SMALL_TEXT_ID)
+                .unwrap_or_default();
+            let (_min_x, text_width, text_height) =
+                self.text_renderer.measure_text("Window too small", &style);
+            let position = TextPosition {
+                x: ((self.window_size.width as f32 - text_width) / 2.0).max(0.0),
+                y: ((self.window_size.height as f32 - text_height) / 2.0).max(0.0),
+                max_width: Some(text_width),
+                max_height: Some(text_height),
+            };
+            let _ = self
+                .text_renderer
+                .update_position(WINDOW_TOO_SMALL_TEXT_ID, position);
+        }
+        !too_small
+    }
+
+    /// Registers an icon texture from raw RGBA8 pixels after construction
+    ///
+    /// Existing icon IDs are left untouched, so this is safe to call at any point
+    /// during a session (e.g. when new upgrades are unlocked). Icons referencing an
+    /// ID that has not been registered render with the missing icon placeholder.
+    ///
+    /// # Arguments
+    /// * `device` - WGPU device for creating render resources
+    /// * `queue` - WGPU queue for uploading the texture
+    /// * `id` - Icon texture ID referenced by buttons
+    /// * `rgba_bytes` - Tightly packed RGBA8 pixel data
+    /// * `dimensions` - Width and height of the image in pixels
+    ///
+    /// # Returns
+    /// `Ok(())` on success, or an error message if the pixel data is invalid
+    pub fn register_icon(
+        &mut self,
+        device: &Device,
+        queue: &Queue,
+        id: &str,
+        rgba_bytes: &[u8],
+        dimensions: (u32, u32),
+    ) -> Result<(), String> {
+        self.icon_renderer
+            .register_texture(device, queue, id, rgba_bytes, dimensions)
+    }
+
+    /// Registers an icon texture from encoded image bytes (PNG, JPEG, etc.)
+    ///
+    /// Lets upgrade definitions ship their own embedded icon via `include_bytes!()`
+    /// without adding them to the asset bundle loaded at startup.
+    ///
+    /// # Arguments
+    /// * `device` - WGPU device for creating render resources
+    /// * `queue` - WGPU queue for uploading the texture
+    /// * `id` - Icon texture ID referenced by buttons
+    /// * `image_bytes` - Encoded image file contents
+    ///
+    /// # Returns
+    /// `Ok(())` on success, or an error message if decoding or upload fails
+    pub fn register_icon_from_data(
+        &mut self,
+        device: &Device,
+        queue: &Queue,
+        id: &str,
+        image_bytes: &[u8],
+    ) -> Result<(), String> {
+        let rgba = image::load_from_memory(image_bytes)
+            .map_err(|e| format!("Failed to decode icon '{}': {}", id, e))?
+            .to_rgba8();
+        let dimensions = rgba.dimensions();
+        self.register_icon(device, queue, id, &rgba, dimensions)
     }
 
     /// Adds a button to the manager and sets up its text buffers
@@ -315,9 +793,12 @@ impl ButtonManager {
     /// For upgrade buttons (ButtonSpacing::Tall), this method also:
     /// - Positions text at the top of the button
     /// - Sets up level text below the main text
-    /// - Configures tooltip text at the bottom
+    /// - Configures tooltip text at the bottom, or on the overlay layer for floating tooltips
     /// - Prepares icon positioning
     ///
+    /// Tooltip text starts hidden; [`update_button_states`](Self::update_button_states)
+    /// fades it in once the button has been hovered long enough.
+    ///
     /// # Arguments
     /// * `button` - The Button instance to add
     pub fn add_button(&mut self, button: Button) {
@@ -328,8 +809,8 @@ impl ButtonManager {
         let level_text_id = button.level_text_id.clone();
         let tooltip_text_id = button.tooltip_text_id.clone();
 
-        let horizontal_padding = style.padding.0;
-        let vertical_padding = style.padding.1;
+        let (horizontal_padding, vertical_padding) =
+            utils::scaled_padding(style.padding, self.window_size.height as f32);
         let window_width = self.window_size.width as f32;
 
         // Measure the actual text size for positioning, allowing wrapping
@@ -391,7 +872,7 @@ impl ButtonManager {
             &text_id,
             &text,
             Some(TextStyle {
-                color: style.background_color.darken(0.35), // Use proper color, not transparent
+                color: BUTTON_VISUALS.text_color(&style, ButtonState::Normal), // Use proper color, not transparent
                 ..style.text_style.clone()
             }),
             Some(text_position),
@@ -404,7 +885,7 @@ impl ButtonManager {
             level_style.font_size = style.text_style.font_size * 0.7; // 70% of main text size
             level_style.line_height = style.text_style.line_height * 0.7;
             level_style.style = Style::Italic;
-            level_style.color = style.background_color.darken(0.35); // Use same color as main text, not transparent
+            level_style.color = BUTTON_VISUALS.text_color(&style, ButtonState::Normal); // Use same color as main text, not transparent
 
             // Use the actual initial text for the buffer ("Level 1" or "New Upgrade")
             let level_text = "Level 1";
@@ -416,7 +897,7 @@ impl ButtonManager {
                 TextAlign::Right => actual_x + button_width - horizontal_padding - level_text_width,
                 TextAlign::Center => actual_x + (button_width - level_text_width) / 2.0,
             };
-            let level_text_y = actual_y + button_height * 0.55; // Slightly higher, still below the icon
+            let level_text_y = actual_y + button_height * self.level_text_offset(); // Below the icon
 
             let level_text_position = TextPosition {
                 x: level_text_x,
@@ -434,38 +915,48 @@ impl ButtonManager {
         }
 
         // Create tooltip text if specified
-        if let Some(tooltip_id) = tooltip_text_id {
-            // Create a larger style for tooltip text
-            let mut tooltip_style = style.text_style.clone();
-            tooltip_style.font_size = style.text_style.font_size * 0.7; // Increased from 0.55 to 0.7 (70% of main text size)
-            tooltip_style.line_height = tooltip_style.font_size * 1.05;
-            tooltip_style.style = Style::Normal;
-            tooltip_style.color = style.background_color.darken(0.35); // Use same color as main text, not transparent
-
-            // Position tooltip text below the level text
-            let tooltip_text = "This is a place to describe an upgrade, and what effects it has on the game in a little more detail.";
-            let extra_tooltip_padding = 20.0; // Increased from 10.0 to 20.0 for more margin
-            let tooltip_horizontal_padding = horizontal_padding + extra_tooltip_padding;
-            let tooltip_text_x = match style.text_align {
-                TextAlign::Left => actual_x + tooltip_horizontal_padding,
-                TextAlign::Right => actual_x + button_width - tooltip_horizontal_padding,
-                TextAlign::Center => actual_x + tooltip_horizontal_padding, // Start from left padding, let text wrap
-            };
-            let tooltip_text_y = actual_y + button_height * 0.68; // Higher up than before
+        match tooltip_text_id {
+            Some(tooltip_id) if self.floating_tooltips => {
+                // Floating tooltips are placed by the cursor when they appear
+                self.text_renderer.create_text_buffer(
+                    &tooltip_id,
+                    "",
+                    Some(tooltip_text_style(&style, true)),
+                    None,
+                );
+                let _ = self.text_renderer.set_buffer_overlay(&tooltip_id, true);
+                let _ = self.text_renderer.set_buffer_visibility(&tooltip_id, false);
+            }
+            Some(tooltip_id) => {
+                let tooltip_style = tooltip_text_style(&style, false);
 
-            let tooltip_text_position = TextPosition {
-                x: tooltip_text_x,
-                y: tooltip_text_y,
-                max_width: Some(button_width - 2.0 * tooltip_horizontal_padding),
-                max_height: Some(button_height * 0.28), // Allow for more lines
-            };
+                // Position tooltip text below the level text
+                let tooltip_text = "This is a place to describe an upgrade, and what effects it has on the game in a little more detail.";
+                let extra_tooltip_padding = 20.0; // Increased from 10.0 to 20.0 for more margin
+                let tooltip_horizontal_padding = horizontal_padding + extra_tooltip_padding;
+                let tooltip_text_x = match style.text_align {
+                    TextAlign::Left => actual_x + tooltip_horizontal_padding,
+                    TextAlign::Right => actual_x + button_width - tooltip_horizontal_padding,
+                    TextAlign::Center => actual_x + tooltip_horizontal_padding, // Start from left padding, let text wrap
+                };
+                let tooltip_text_y = actual_y + button_height * 0.68; // Higher up than before
 
-            self.text_renderer.create_text_buffer(
-                &tooltip_id,
-                tooltip_text,
-                Some(tooltip_style),
-                Some(tooltip_text_position),
-            );
+                let tooltip_text_position = TextPosition {
+                    x: tooltip_text_x,
+                    y: tooltip_text_y,
+                    max_width: Some(button_width - 2.0 * tooltip_horizontal_padding),
+                    max_height: Some(button_height * 0.28), // Allow for more lines
+                };
+
+                self.text_renderer.create_text_buffer(
+                    &tooltip_id,
+                    tooltip_text,
+                    Some(tooltip_style),
+                    Some(tooltip_text_position),
+                );
+                let _ = self.text_renderer.set_buffer_visibility(&tooltip_id, false);
+            }
+            None => {}
         }
 
         // Track button order
@@ -477,6 +968,27 @@ impl ButtonManager {
             .insert(button_with_size.id.clone(), button_with_size);
     }
 
+    /// Changes the label of an existing button
+    ///
+    /// Updates both the stored button text and its text buffer so the new label
+    /// is drawn on the next frame. Call `update_button_positions()` afterwards if
+    /// the new label changes the text width.
+    ///
+    /// # Arguments
+    /// * `id` - ID of the button to relabel
+    /// * `text` - The new button label
+    ///
+    /// # Returns
+    /// `Ok(())` on success, or an error message if the button or its text buffer is missing
+    pub fn set_button_text(&mut self, id: &str, text: &str) -> Result<(), String> {
+        let button = self
+            .buttons
+            .get_mut(id)
+            .ok_or_else(|| format!("Button '{}' not found", id))?;
+        button.text = text.to_string();
+        self.text_renderer.update_text(&button.text_id, text)
+    }
+
     /// Updates icon positions for all visible upgrade buttons
     ///
     /// This method:
@@ -487,11 +999,17 @@ impl ButtonManager {
     /// - Applies hover scaling to icons to match button scaling
     ///
     /// Icons are positioned at the center of the button with appropriate margins
-    /// and scale with the button during hover/press states.
+    /// and scale with the button during hover/press states. With floating tooltips the
+    /// icon also takes the space the tooltip would have used inside the button.
     pub fn update_icon_positions(&mut self) {
         // Clear existing icons
         self.icon_renderer.clear_icons();
 
+        // The icon ends a little above the level text
+        let icon_bottom = self.level_text_offset() - 0.05;
+        let icon_height = if self.floating_tooltips { 0.55 } else { 0.4 };
+        let window_height = self.window_size.height as f32;
+
         // Only add icons to buttons with ButtonSpacing::Tall (upgrade menu buttons)
         for button_id in &self.button_order {
             if let Some(button) = self.buttons.get(button_id) {
@@ -500,12 +1018,8 @@ impl ButtonManager {
                     if let ButtonSpacing::Tall(_) = button.style.spacing {
                         let (actual_x, actual_y) = button.position.calculate_actual_position();
 
-                        // Calculate scale for hover effect on upgrade buttons
-                        let scale = match button.state {
-                            ButtonState::Hover => 1.1,    // 10% bigger on hover
-                            ButtonState::Pressed => 1.05, // 5% bigger when pressed
-                            _ => 1.0,                     // Normal size
-                        };
+                        // Follow the button's eased hover scale
+                        let scale = button.hover_scale;
 
                         // Calculate scaled button dimensions
                         let scaled_width = button.position.width * scale;
@@ -516,15 +1030,17 @@ impl ButtonManager {
                         // Calculate icon size and position with scaling
                         let margin = 16.0 * scale; // Scale margin too
                         let max_icon_width = scaled_width - 2.0 * margin;
-                        let max_icon_height = scaled_height * 0.4;
+                        let max_icon_height = scaled_height * icon_height;
 
                         // Calculate icon size (square, fit within constraints)
                         let icon_size = max_icon_width.min(max_icon_height);
 
-                        // Position icon at center of scaled button
+                        // Center the icon horizontally; icon y runs bottom-up from the window's
+                        // bottom edge, so flip the button's top-down offset
                         let icon_x = scaled_x + (scaled_width - icon_size) / 2.0;
-                        let icon_y = scaled_y + scaled_height * 0.5;
+                        let icon_y = window_height - (scaled_y + scaled_height * icon_bottom);
 
+                        // Snap to whole pixels at rest; keep sub-pixel motion while scaling
                         let icon = Icon::new(
                             icon_x,
                             icon_y,
@@ -534,7 +1050,8 @@ impl ButtonManager {
                                 .icon_id
                                 .clone()
                                 .unwrap_or_else(|| "blank_icon".to_string()),
-                        );
+                        )
+                        .with_pixel_snap(!button.is_scaling());
                         self.icon_renderer.add_icon(icon);
                     }
                 }
@@ -590,25 +1107,60 @@ impl ButtonManager {
         false
     }
 
+    /// Finds the button under a point without changing any button state
+    ///
+    /// Unlike [`Self::handle_input`], this doesn't move the tracked cursor, press, hover or
+    /// click anything, so it can be asked before deciding whether the buttons get an event at
+    /// all. Where buttons overlap, the one drawn last, on top, is returned.
+    ///
+    /// # Arguments
+    /// * `x` - X coordinate in physical pixels
+    /// * `y` - Y coordinate in physical pixels
+    ///
+    /// # Returns
+    /// The ID of the visible button under the point, if any
+    pub fn hit_test(&self, x: f32, y: f32) -> Option<&str> {
+        if self.window_too_small {
+            return None;
+        }
+        topmost_button_at(&self.button_order, &self.buttons, x, y)
+    }
+
+    /// Returns whether the buttons want the pointer's next click
+    ///
+    /// That is when the cursor is over a button, or a press that began on one hasn't been
+    /// released yet, so its release reaches the button wherever the cursor went.
+    pub fn claims_pointer(&self) -> bool {
+        let (x, y) = self.mouse_position;
+        self.hit_test(x, y).is_some() || (self.mouse_pressed && !self.pressed_buttons.is_empty())
+    }
+
     /// Handles window events for button interaction
     ///
     /// This method processes mouse input events to:
     /// - Track mouse button press/release states
     /// - Update mouse cursor position
     /// - Detect button clicks when mouse is released over a pressed button
-    /// - Handle window resize events
+    /// - Handle window resize and scale factor change events
     /// - Trigger button state updates
     ///
     /// # Arguments
     /// * `event` - The window event to process
     pub fn handle_input(&mut self, event: &WindowEvent) {
+        if self.window_too_small && matches!(event, WindowEvent::MouseInput { .. }) {
+            return;
+        }
+
         match event {
             WindowEvent::MouseInput {
                 state: ElementState::Pressed,
                 button: MouseButton::Left,
                 ..
             } => {
-                println!("[DEBUG] Mouse pressed at position: {:?}", self.mouse_position);
+                println!(
+                    "[DEBUG] Mouse pressed at position: {:?}",
+                    self.mouse_position
+                );
                 self.mouse_pressed = true;
                 self.pressed_buttons.clear(); // Clear previous press cycle
                 self.update_button_states();
@@ -618,13 +1170,16 @@ impl ButtonManager {
                 button: MouseButton::Left,
                 ..
             } => {
-                println!("[DEBUG] Mouse released at position: {:?}", self.mouse_position);
+                println!(
+                    "[DEBUG] Mouse released at position: {:?}",
+                    self.mouse_position
+                );
                 println!("[DEBUG] Mouse pressed state: {}", self.mouse_pressed);
-                
+
                 // Force update button states before checking for clicks
                 // This ensures button states are current on Windows
                 self.update_button_states();
-                
+
                 // Check for button clicks using both current state and pressed_buttons set
                 // This handles platform-specific timing differences in mouse event processing
                 let mut clicked_button = None;
@@ -641,14 +1196,20 @@ impl ButtonManager {
                 // If no button found in current state, check the pressed_buttons set
                 // This handles cases where the mouse moved outside the button during press
                 if clicked_button.is_none() {
-                    println!("[DEBUG] No pressed button found, checking pressed_buttons set: {:?}", self.pressed_buttons);
+                    println!(
+                        "[DEBUG] No pressed button found, checking pressed_buttons set: {:?}",
+                        self.pressed_buttons
+                    );
                     for button_id in &self.pressed_buttons {
                         if let Some(button) = self.buttons.get(button_id) {
                             if button.visible && button.enabled {
                                 // Check if mouse is still over the button or was over it during press
                                 let is_hovered = button
                                     .contains_point(self.mouse_position.0, self.mouse_position.1);
-                                println!("[DEBUG] Checking button {}: hovered = {}", button_id, is_hovered);
+                                println!(
+                                    "[DEBUG] Checking button {}: hovered = {}",
+                                    button_id, is_hovered
+                                );
                                 if is_hovered {
                                     clicked_button = Some(button_id.clone());
                                     break;
@@ -663,8 +1224,8 @@ impl ButtonManager {
                     println!("[DEBUG] No button found in pressed state, checking hover state");
                     for button in self.buttons.values() {
                         if button.visible && button.enabled {
-                            let is_hovered = button
-                                .contains_point(self.mouse_position.0, self.mouse_position.1);
+                            let is_hovered =
+                                button.contains_point(self.mouse_position.0, self.mouse_position.1);
                             if is_hovered {
                                 println!("[DEBUG] Found hovered button: {}", button.id);
                                 clicked_button = Some(button.id.clone());
@@ -694,6 +1255,13 @@ impl ButtonManager {
                 self.window_size = *size;
                 self.update_button_positions();
             }
+            WindowEvent::ScaleFactorChanged { .. } => {
+                // The cursor stays put on screen but its physical position in the window
+                // changes, and winit doesn't send a CursorMoved for it. Forget it until the
+                // next one rather than hit testing a stale position.
+                self.mouse_position = NO_CURSOR;
+                self.update_button_states();
+            }
             _ => {}
         }
     }
@@ -707,16 +1275,37 @@ impl ButtonManager {
     /// - Handles text positioning for hover scaling effects
     /// - Updates level text and tooltip text styling and positioning
     /// - Manages visibility for disabled/invisible buttons
+    /// - Tracks how long each button has been hovered and fades tooltips in and out
     /// - Triggers icon position updates for upgrade buttons
     ///
     /// For upgrade buttons (ButtonSpacing::Tall), this method also:
     /// - Scales text size on hover (20% larger) and press (10% larger)
     /// - Adjusts text positioning to account for button scaling
     /// - Updates level text and tooltip text with proper scaling
+    ///
+    /// A focused button is drawn as hovered whether or not the cursor is on it.
     pub fn update_button_states(&mut self) {
-        // Early exit if mouse state hasn't changed
+        // Buttons can't be hovered or pressed while the window is too small to show them
+        if self.window_too_small {
+            return;
+        }
+
+        // Advance the hover animation; clamp the step so a long pause doesn't skip it
+        let now = Instant::now();
+        let dt = now
+            .duration_since(self.last_hover_update)
+            .as_secs_f32()
+            .min(1.0 / 30.0);
+        self.last_hover_update = now;
+        self.hover_clock.advance_secs(dt);
+        let animating = self.buttons.values().any(|button| {
+            button.is_scaling() || button.tooltip_opacity != self.target_tooltip_opacity(button)
+        });
+
+        // Early exit if mouse state hasn't changed and nothing is animating
         if self.mouse_position == self.last_mouse_position
             && self.mouse_pressed == self.last_mouse_pressed
+            && !animating
         {
             return;
         }
@@ -725,6 +1314,9 @@ impl ButtonManager {
         self.last_mouse_position = self.mouse_position;
         self.last_mouse_pressed = self.mouse_pressed;
 
+        let level_text_offset = self.level_text_offset();
+        let window_height = self.window_size.height as f32;
+
         // To avoid borrow checker issues, first collect level text content for each button
         let mut level_texts: Vec<(String, String)> = Vec::new();
         for button in self.buttons.values() {
@@ -738,15 +1330,26 @@ impl ButtonManager {
             }
         }
 
+        let mut hover_began = false;
         for button in self.buttons.values_mut() {
             if !button.visible || !button.enabled {
-                if button.state != ButtonState::Disabled {
-                    button.state = ButtonState::Disabled;
-                    // Hide text if not visible
+                // Hide text if not visible; a visible but disabled button keeps muted text
+                let was_disabled = button.state == ButtonState::Disabled;
+                button.state = ButtonState::Disabled;
+                let text_color = if button.visible {
+                    button.text_appearance().0
+                } else {
+                    Color::rgba(0, 0, 0, 0)
+                };
+                let color_changed = self
+                    .text_renderer
+                    .get_style(&button.text_id)
+                    .is_ok_and(|style| style.color != text_color);
+                if !was_disabled || color_changed {
                     let _ = self.text_renderer.update_style(
                         &button.text_id,
                         TextStyle {
-                            color: Color::rgba(0, 0, 0, 0),
+                            color: text_color,
                             ..button.style.text_style.clone()
                         },
                     );
@@ -760,26 +1363,27 @@ impl ButtonManager {
                             },
                         );
                     }
-                    // Hide tooltip text if not visible
-                    if let Some(tooltip_id) = &button.tooltip_text_id {
-                        let _ = self.text_renderer.update_style(
-                            tooltip_id,
-                            TextStyle {
-                                color: Color::rgba(0, 0, 0, 0),
-                                ..button.style.text_style.clone()
-                            },
-                        );
-                    }
                 }
+                button.hover_scale = 1.0;
+                button.hover_started = None;
                 continue;
             }
 
             let is_hovered = button.contains_point(self.mouse_position.0, self.mouse_position.1);
+            let is_focused = self.focused_button.as_deref() == Some(button.id.as_str());
+
+            // Restart the tooltip delay every time the cursor comes onto the button
+            if !is_hovered {
+                button.hover_started = None;
+            } else if button.hover_started.is_none() {
+                button.hover_started = Some(self.hover_clock.now());
+                hover_began = true;
+            }
 
             // Determine new state
             let new_state = if self.mouse_pressed && is_hovered {
                 ButtonState::Pressed
-            } else if is_hovered {
+            } else if is_hovered || is_focused {
                 ButtonState::Hover
             } else {
                 ButtonState::Normal
@@ -790,48 +1394,25 @@ impl ButtonManager {
                 self.pressed_buttons.insert(button.id.clone());
             }
 
-            // Only update if state actually changed
-            if button.state == new_state {
+            // Only update if state changed or the hover scale is still easing
+            if button.state == new_state && !button.is_scaling() {
                 continue;
             }
 
             button.state = new_state;
+            button.hover_scale =
+                step_hover_scale(button.hover_scale, button.target_hover_scale(), dt);
 
             // Calculate actual position and paddings at the start of the loop
             let (actual_x, actual_y) = button.position.calculate_actual_position();
-            let horizontal_padding = button.style.padding.0;
-            let vertical_padding = button.style.padding.1;
+            let (horizontal_padding, vertical_padding) =
+                utils::scaled_padding(button.style.padding, window_height);
 
             // Update text color and weight based on button state
-            let (text_color, text_weight) = match button.state {
-                ButtonState::Normal => (
-                    button.style.background_color.darken(0.35), // 35% darker than bg
-                    button.style.text_style.weight,
-                ),
-                ButtonState::Hover => (
-                    button.style.hover_color.saturate(0.90), // much brighter and more saturated
-                    Weight::BOLD,
-                ),
-                ButtonState::Pressed => (
-                    button.style.pressed_color.brighten(0.15).saturate(0.35), // brighter and more saturated
-                    Weight::MEDIUM,
-                ),
-                ButtonState::Disabled => (
-                    Color::rgb(100, 116, 139), // slate-500 - muted text
-                    Weight::NORMAL,
-                ),
-            };
+            let (text_color, text_weight) = button.text_appearance();
 
             // Update text size based on hover state for upgrade buttons
-            let text_size_scale = if let ButtonSpacing::Tall(_) = button.style.spacing {
-                match button.state {
-                    ButtonState::Hover => 1.2,   // 20% bigger on hover
-                    ButtonState::Pressed => 1.1, // 10% bigger when pressed
-                    _ => 1.0,                    // Normal size
-                }
-            } else {
-                1.0 // No scaling for non-tall buttons
-            };
+            let text_size_scale = button.text_scale();
 
             // Only update style if color, weight, or size changed
             let mut new_style = button.style.text_style.clone();
@@ -852,11 +1433,7 @@ impl ButtonManager {
                     self.text_renderer.measure_text(&button.text, &new_style);
 
                 // Use the button's scale for position transformation
-                let button_scale = match button.state {
-                    ButtonState::Hover => 1.1,
-                    ButtonState::Pressed => 1.05,
-                    _ => 1.0,
-                };
+                let button_scale = button.hover_scale;
 
                 // Calculate scaled button dimensions and position
                 let scaled_width = button.position.width * button_scale;
@@ -912,11 +1489,7 @@ impl ButtonManager {
                     self.text_renderer.measure_text(level_text, &level_style);
 
                 // Use the button's scale for position transformation
-                let button_scale = match button.state {
-                    ButtonState::Hover => 1.1,
-                    ButtonState::Pressed => 1.05,
-                    _ => 1.0,
-                };
+                let button_scale = button.hover_scale;
 
                 // Calculate scaled button dimensions
                 let scaled_width = button.position.width * button_scale;
@@ -934,7 +1507,7 @@ impl ButtonManager {
                     }
                     TextAlign::Center => scaled_x + (scaled_width - level_text_width) / 2.0,
                 };
-                let base_level_y = scaled_y + scaled_height * 0.55; // Just below the icon
+                let base_level_y = scaled_y + scaled_height * level_text_offset; // Just below the icon
 
                 // Position level text directly (no need for offset calculation since we're using scaled coordinates)
                 let scaled_level_x = base_level_x;
@@ -955,25 +1528,229 @@ impl ButtonManager {
                 }
             }
             // --- End level text update logic ---
+        }
 
-            // Update tooltip text if it exists
-            if let Some(tooltip_id) = &button.tooltip_text_id {
-                // Create tooltip text style with larger size
-                let mut tooltip_style = button.style.text_style.clone();
-                tooltip_style.font_size = button.style.text_style.font_size * 0.7 * text_size_scale; // Increased from 0.6 to 0.7
-                tooltip_style.line_height = tooltip_style.font_size * 1.05;
-                tooltip_style.style = Style::Normal;
-                tooltip_style.color = text_color; // Use same color as main text
-                tooltip_style.weight = text_weight;
-
-                let _ = self.text_renderer.update_style(tooltip_id, tooltip_style);
-            }
+        // Moving the cursor onto a button hands control back to the mouse
+        if hover_began {
+            self.focused_button = None;
         }
 
+        self.update_tooltips();
+
         // Update icon positions to match button scaling
         self.update_icon_positions();
     }
 
+    /// Returns where upgrade buttons' level text starts, as a fraction of the button height
+    fn level_text_offset(&self) -> f32 {
+        if self.floating_tooltips {
+            FLOATING_LEVEL_OFFSET
+        } else {
+            IN_CARD_LEVEL_OFFSET
+        }
+    }
+
+    /// Returns the opacity a button's tooltip should be drawn at right now
+    ///
+    /// While a button has keyboard focus, only its tooltip is shown.
+    ///
+    /// # Arguments
+    /// * `button` - The button whose tooltip to check
+    fn target_tooltip_opacity(&self, button: &Button) -> f32 {
+        if !button.visible || !button.enabled || button.tooltip_text_id.is_none() {
+            return 0.0;
+        }
+        match self.focused_button.as_deref() {
+            Some(focused) => tooltip_opacity(None, focused == button.id),
+            None => tooltip_opacity(
+                button
+                    .hover_started
+                    .map(|started| self.hover_clock.now().saturating_sub(started)),
+                false,
+            ),
+        }
+    }
+
+    /// Fades tooltips in and out and places the floating tooltip panel
+    ///
+    /// Every tooltip follows [`tooltip_opacity`] for its button, and hidden tooltips are
+    /// left out of rendering entirely. With floating tooltips, the most visible one is also
+    /// given a panel by the cursor.
+    fn update_tooltips(&mut self) {
+        let targets: Vec<(String, f32)> = self
+            .button_order
+            .iter()
+            .filter_map(|id| self.buttons.get(id))
+            .filter(|button| button.tooltip_text_id.is_some())
+            .map(|button| (button.id.clone(), self.target_tooltip_opacity(button)))
+            .collect();
+
+        let mut floating: Option<(String, f32)> = None;
+        for (button_id, opacity) in targets {
+            let Some(button) = self.buttons.get_mut(&button_id) else {
+                continue;
+            };
+            let Some(tooltip_id) = &button.tooltip_text_id else {
+                continue;
+            };
+            button.tooltip_opacity = opacity;
+            let _ = self
+                .text_renderer
+                .set_buffer_visibility(tooltip_id, opacity > 0.0);
+            if opacity <= 0.0 {
+                continue;
+            }
+
+            let mut style = tooltip_text_style(&button.style, self.floating_tooltips);
+            if !self.floating_tooltips {
+                // In-card tooltips match the main text, and grow with it on upgrade buttons
+                let (text_color, text_weight) = button.text_appearance();
+                if let ButtonSpacing::Tall(_) = button.style.spacing {
+                    style.font_size *= button.text_scale();
+                    style.line_height = style.font_size * 1.05;
+                }
+                style.color = text_color;
+                style.weight = text_weight;
+            }
+            let alpha = (style.color.a() as f32 * opacity).round() as u8;
+            style.color = Color::rgba(style.color.r(), style.color.g(), style.color.b(), alpha);
+            let _ = self.text_renderer.update_style(tooltip_id, style);
+
+            if self.floating_tooltips && floating.as_ref().is_none_or(|(_, best)| opacity > *best) {
+                floating = Some((button_id, opacity));
+            }
+        }
+
+        self.tooltip_panel = floating
+            .and_then(|(button_id, opacity)| self.place_floating_tooltip(&button_id, opacity));
+    }
+
+    /// Moves a floating tooltip next to the cursor and builds the panel behind it
+    ///
+    /// A tooltip showing because of keyboard focus is placed by the center of its button
+    /// instead, since the cursor may be anywhere.
+    ///
+    /// # Arguments
+    /// * `button_id` - The button whose tooltip is shown
+    /// * `opacity` - How far the tooltip has faded in
+    ///
+    /// # Returns
+    /// The panel to draw behind the tooltip text, or `None` if the button has no tooltip
+    fn place_floating_tooltip(&mut self, button_id: &str, opacity: f32) -> Option<Rectangle> {
+        let button = self.buttons.get(button_id)?;
+        let tooltip_id = button.tooltip_text_id.clone()?;
+        let anchor = if self.focused_button.as_deref() == Some(button_id) {
+            let (x, y) = button.position.calculate_actual_position();
+            (
+                x + button.position.width / 2.0,
+                y + button.position.height / 2.0,
+            )
+        } else {
+            self.mouse_position
+        };
+
+        let window = (
+            self.window_size.width as f32,
+            self.window_size.height as f32,
+        );
+        let scale = utils::dpi_scale(window.1);
+        let padding = TOOLTIP_PANEL_PADDING * scale;
+        let wrap_width = (TOOLTIP_PANEL_WIDTH * scale).min(window.0 - 2.0 * padding);
+        let text = self.text_renderer.get_text_content(&tooltip_id).ok()?;
+        let style = self.text_renderer.get_style(&tooltip_id).ok()?;
+        let (_min_x, text_width, text_height) = self
+            .text_renderer
+            .measure_wrapped_text(&text, &style, wrap_width);
+
+        let size = (text_width + 2.0 * padding, text_height + 2.0 * padding);
+        let (x, y) = place_tooltip_panel(anchor, size, window, TOOLTIP_CURSOR_OFFSET * scale);
+        let text_position = TextPosition {
+            x: x + padding,
+            y: y + padding,
+            max_width: Some(wrap_width),
+            max_height: Some(text_height + padding),
+        };
+        if let Err(e) = self
+            .text_renderer
+            .update_position(&tooltip_id, text_position)
+        {
+            println!("Failed to update tooltip text position: {}", e);
+        }
+
+        let mut color = TOOLTIP_PANEL_COLOR;
+        color[3] *= opacity;
+        Some(Rectangle::new(x, y, size.0, size.1, color).with_corner_radius(6.0 * scale))
+    }
+
+    /// Moves keyboard focus to the next or previous visible, enabled button
+    ///
+    /// Focus wraps around the ends of the button order. The focused button is drawn as
+    /// hovered and shows its tooltip straight away.
+    ///
+    /// # Arguments
+    /// * `step` - `1` for the next button, `-1` for the previous one
+    pub fn move_focus(&mut self, step: isize) {
+        let focusable: Vec<&String> = self
+            .button_order
+            .iter()
+            .filter(|id| {
+                self.buttons
+                    .get(*id)
+                    .is_some_and(|button| button.visible && button.enabled)
+            })
+            .collect();
+        let current = self
+            .focused_button
+            .as_ref()
+            .and_then(|focused| focusable.iter().position(|id| *id == focused));
+        self.focused_button =
+            step_focus(focusable.len(), current, step).map(|index| focusable[index].clone());
+    }
+
+    /// Clicks the focused button, as if it had been clicked with the mouse
+    ///
+    /// The click is picked up by [`is_button_clicked`](Self::is_button_clicked) like any other.
+    pub fn activate_focus(&mut self) {
+        let Some(focused) = &self.focused_button else {
+            return;
+        };
+        if self
+            .buttons
+            .get(focused)
+            .is_some_and(|button| button.visible && button.enabled)
+        {
+            self.just_clicked = Some(focused.clone());
+        }
+    }
+
+    /// Takes a gamepad's step through the menu, moving the focus or clicking the focused button
+    ///
+    /// # Arguments
+    /// * `nav` - The step the gamepad made
+    pub fn navigate(&mut self, nav: MenuNav) {
+        match nav {
+            MenuNav::Previous => self.move_focus(-1),
+            MenuNav::Next => self.move_focus(1),
+            MenuNav::Activate => self.activate_focus(),
+        }
+    }
+
+    /// Hides every tooltip and forgets hover timers and keyboard focus
+    ///
+    /// Call this when the buttons are shown or hidden, so a cursor left resting on a button
+    /// doesn't bring its tooltip straight back.
+    pub fn reset_tooltips(&mut self) {
+        self.focused_button = None;
+        self.tooltip_panel = None;
+        for button in self.buttons.values_mut() {
+            button.hover_started = None;
+            button.tooltip_opacity = 0.0;
+            if let Some(tooltip_id) = &button.tooltip_text_id {
+                let _ = self.text_renderer.set_buffer_visibility(tooltip_id, false);
+            }
+        }
+    }
+
     /// Updates button positions and text layout after window resize or other changes
     ///
     /// This method recalculates:
@@ -993,7 +1770,17 @@ impl ButtonManager {
     /// - Places level text below the main text
     /// - Positions tooltip text at the bottom
     /// - Applies hover scaling transformations
+    ///
+    /// Layout is skipped entirely while the window is below the minimum usable size.
     pub fn update_button_positions(&mut self) {
+        if !self.apply_minimum_window_size() {
+            return;
+        }
+
+        let level_text_offset = self.level_text_offset();
+        let floating_tooltips = self.floating_tooltips;
+        let window_height = self.window_size.height as f32;
+
         // To avoid borrow checker issues, first collect level text content for each button
         let mut level_texts: Vec<(String, String)> = Vec::new();
         for button in self.buttons.values() {
@@ -1009,19 +1796,11 @@ impl ButtonManager {
         // Now update positions
         for button in self.buttons.values_mut() {
             let (actual_x, actual_y) = button.position.calculate_actual_position();
-            let horizontal_padding = button.style.padding.0;
-            let vertical_padding = button.style.padding.1;
+            let (horizontal_padding, vertical_padding) =
+                utils::scaled_padding(button.style.padding, window_height);
 
             // Calculate scale for hover effect on upgrade buttons
-            let scale = if let ButtonSpacing::Tall(_) = button.style.spacing {
-                match button.state {
-                    ButtonState::Hover => 1.1,    // 10% bigger on hover
-                    ButtonState::Pressed => 1.05, // 5% bigger when pressed
-                    _ => 1.0,                     // Normal size
-                }
-            } else {
-                1.0 // No scaling for non-tall buttons
-            };
+            let scale = button.hover_scale;
 
             let scaled_max_text_width = (button.position.width - 2.0 * horizontal_padding) * scale;
             let (_min_x, wrap_width, wrap_height) = self
@@ -1117,7 +1896,7 @@ impl ButtonManager {
                         TextAlign::Center => scaled_x + (scaled_width - level_text_width) / 2.0,
                     };
 
-                    let level_text_y = scaled_y + scaled_height * 0.55; // Just below the icon
+                    let level_text_y = scaled_y + scaled_height * level_text_offset; // Just below the icon
                     (level_text_x, level_text_y)
                 } else {
                     // For non-tall buttons, use original positioning
@@ -1151,8 +1930,13 @@ impl ButtonManager {
                 }
             }
 
-            // Update tooltip text position if it exists
-            if let Some(tooltip_id) = &button.tooltip_text_id {
+            // Update tooltip text position if it exists; floating tooltips follow the cursor
+            // instead and are placed by update_tooltips
+            if let Some(tooltip_id) = button
+                .tooltip_text_id
+                .as_ref()
+                .filter(|_| !floating_tooltips)
+            {
                 // Get the existing tooltip text from the buffer for measurement
                 let tooltip_text = if let Some(buffer) =
                     self.text_renderer.text_buffers.get(tooltip_id)
@@ -1163,10 +1947,7 @@ impl ButtonManager {
                 };
 
                 // Create tooltip text style for measurement - use the same style as in add_button
-                let mut tooltip_style = button.style.text_style.clone();
-                tooltip_style.font_size = button.style.text_style.font_size * 0.7; // Increased from 0.55 to 0.7 (70% of main text size)
-                tooltip_style.line_height = tooltip_style.font_size * 1.05;
-                tooltip_style.style = Style::Normal;
+                let tooltip_style = tooltip_text_style(&button.style, false);
 
                 let (_min_x, _tooltip_text_width, tooltip_text_height) = self
                     .text_renderer
@@ -1240,6 +2021,9 @@ impl ButtonManager {
             }
         }
 
+        // Keep a floating tooltip on screen after the window changes size
+        self.update_tooltips();
+
         // Update icon positions to match button positions
         self.update_icon_positions();
     }
@@ -1249,8 +2033,9 @@ impl ButtonManager {
     /// This method updates:
     /// - Window size for positioning calculations
     /// - Text renderer resolution
-    /// - Rectangle renderer dimensions
+    /// - Rectangle renderer dimensions, including the tooltip panel's
     /// - Icon renderer dimensions
+    /// - Whether the buttons are hidden for a too-small window
     ///
     /// # Arguments
     /// * `queue` - WGPU queue for uploading new resources
@@ -1265,14 +2050,18 @@ impl ButtonManager {
         self.text_renderer.resize(queue, resolution);
         self.rectangle_renderer
             .resize(resolution.width as f32, resolution.height as f32);
+        self.tooltip_renderer
+            .resize(resolution.width as f32, resolution.height as f32);
         self.icon_renderer
             .resize(resolution.width as f32, resolution.height as f32);
+        self.apply_minimum_window_size();
     }
 
     /// Prepares the text renderer for rendering
     ///
     /// This method delegates to the text renderer's prepare method to set up
-    /// text buffers and resources for the current frame.
+    /// text buffers and resources for the current frame. A hidden button's label
+    /// and level text are hidden with it, so a menu can hide single buttons.
     ///
     /// # Arguments
     /// * `device` - WGPU device for creating resources
@@ -1287,6 +2076,14 @@ impl ButtonManager {
         queue: &Queue,
         surface_config: &SurfaceConfiguration,
     ) -> Result<(), glyphon::PrepareError> {
+        for button in self.buttons.values() {
+            let text_ids = std::iter::once(&button.text_id).chain(button.level_text_id.as_ref());
+            for text_id in text_ids {
+                let _ = self
+                    .text_renderer
+                    .set_buffer_visibility(text_id, button.visible && !self.window_too_small);
+            }
+        }
         self.text_renderer.prepare(device, queue, surface_config)
     }
 
@@ -1296,12 +2093,14 @@ impl ButtonManager {
     /// 1. Container rectangle (if present, for upgrade menu background)
     /// 2. Button background rectangles with proper colors and scaling
     /// 3. Button icons (for upgrade buttons)
-    /// 4. Button text elements (main text, level text, tooltips)
+    /// 4. Button text elements (main text, level text, in-card tooltips)
+    /// 5. The floating tooltip panel and its text, if one is showing
     ///
     /// The rendering order ensures proper layering:
     /// - Backgrounds are rendered first
     /// - Icons are rendered on top of backgrounds
-    /// - Text is rendered last for proper visibility
+    /// - Text is rendered on top of both for proper visibility
+    /// - A floating tooltip goes last so it covers neighboring buttons
     ///
     /// For upgrade buttons, hover scaling is applied to both the background
     /// rectangle and the corner radius for smooth visual effects.
@@ -1320,6 +2119,11 @@ impl ButtonManager {
         // Clear previous rectangles
         self.rectangle_renderer.clear_rectangles();
 
+        // Only the "window too small" message is drawn while the buttons are hidden
+        if self.window_too_small {
+            return self.text_renderer.render(render_pass);
+        }
+
         // Render container rectangle first (if it exists)
         if let Some(container_rect) = &self.container_rect {
             self.rectangle_renderer
@@ -1343,23 +2147,12 @@ impl ButtonManager {
                             ButtonState::Disabled => button.style.disabled_color,
                         }
                     };
-                    let color_array = [
-                        color.r() as f32 / 255.0,
-                        color.g() as f32 / 255.0,
-                        color.b() as f32 / 255.0,
-                        color.a() as f32 / 255.0,
-                    ];
+                    // Style colors are sRGB; the rectangle renderer converts them for the target
+                    let color_array =
+                        color::srgba_from_u8([color.r(), color.g(), color.b(), color.a()]);
 
                     // Calculate scale for hover effect on upgrade buttons
-                    let scale = if let ButtonSpacing::Tall(_) = button.style.spacing {
-                        match button.state {
-                            ButtonState::Hover => 1.1,    // 10% bigger on hover
-                            ButtonState::Pressed => 1.05, // 5% bigger when pressed
-                            _ => 1.0,                     // Normal size
-                        }
-                    } else {
-                        1.0 // No scaling for non-tall buttons
-                    };
+                    let scale = button.hover_scale;
 
                     // Calculate scaled dimensions and position
                     let scaled_width = button.position.width * scale;
@@ -1387,7 +2180,169 @@ impl ButtonManager {
         // Then render the icons
         self.icon_renderer.render(device, render_pass);
 
-        // Finally render the text on top
-        self.text_renderer.render(render_pass)
+        // Then render the text on top
+        self.text_renderer.render(render_pass)?;
+
+        // Finally the floating tooltip, above everything else
+        self.tooltip_renderer.clear_rectangles();
+        if let Some(panel) = &self.tooltip_panel {
+            self.tooltip_renderer.add_rectangle(panel.clone());
+        }
+        self.tooltip_renderer.render(device, render_pass);
+        self.text_renderer.render_overlay(render_pass)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tiny_windows_skip_button_layout() {
+        for (width, height) in [(1, 1), (30, 2000), (2000, 30), (0, 0)] {
+            assert!(!is_usable_window_size(width, height));
+        }
+        assert!(is_usable_window_size(MIN_USABLE_WIDTH, MIN_USABLE_HEIGHT));
+        assert!(is_usable_window_size(1920, 1080));
+
+        // Padding wider than the button would ask text for a negative or NaN size
+        let (button_width, padding) = (20.0, 16.0);
+        let text_position = TextPosition {
+            max_width: Some(button_width - 2.0 * padding),
+            max_height: Some(f32::NAN),
+            ..TextPosition::default()
+        };
+        assert_eq!(text_position.bounded_size(1.0, 1.0), (0.0, 0.0));
+        assert_eq!(
+            TextPosition::default().bounded_size(30.0, 2000.0),
+            (30.0, 2000.0)
+        );
+    }
+
+    #[test]
+    fn test_hover_scale_eases_over_a_tenth_of_a_second() {
+        let mut scale = 1.0;
+        for _ in 0..5 {
+            scale = step_hover_scale(scale, BUTTON_VISUALS.hover_scale, 0.01);
+        }
+        assert!((scale - 1.05).abs() < 1e-4);
+
+        // Never overshoots, and reaches the target exactly
+        for _ in 0..10 {
+            scale = step_hover_scale(scale, BUTTON_VISUALS.hover_scale, 0.01);
+        }
+        assert_eq!(scale, BUTTON_VISUALS.hover_scale);
+
+        // Un-hovering eases back down the same way
+        let scale = step_hover_scale(BUTTON_VISUALS.hover_scale, 1.0, 0.05);
+        assert!((scale - 1.05).abs() < 1e-4);
+        assert_eq!(step_hover_scale(scale, 1.0, 1.0), 1.0);
+    }
+
+    #[test]
+    fn test_tooltips_wait_then_fade_in_unless_focused() {
+        assert_eq!(tooltip_opacity(None, false), 0.0);
+        assert_eq!(
+            tooltip_opacity(Some(Duration::from_millis(399)), false),
+            0.0
+        );
+        assert_eq!(tooltip_opacity(Some(TOOLTIP_DELAY), false), 0.0);
+        let halfway = tooltip_opacity(Some(Duration::from_millis(450)), false);
+        assert!((halfway - 0.5).abs() < 1e-4);
+        assert_eq!(
+            tooltip_opacity(Some(Duration::from_millis(500)), false),
+            1.0
+        );
+        assert_eq!(tooltip_opacity(Some(Duration::from_secs(60)), false), 1.0);
+
+        // Keyboard focus skips the delay
+        assert_eq!(tooltip_opacity(None, true), 1.0);
+        assert_eq!(tooltip_opacity(Some(Duration::ZERO), true), 1.0);
+    }
+
+    #[test]
+    fn test_tooltip_panel_stays_on_screen() {
+        let window = (800.0, 600.0);
+        let size = (200.0, 100.0);
+
+        // Below and to the right of the cursor when there's room
+        assert_eq!(
+            place_tooltip_panel((100.0, 100.0), size, window, 10.0),
+            (110.0, 110.0)
+        );
+        // Flips left and up near the far edges
+        assert_eq!(
+            place_tooltip_panel((750.0, 580.0), size, window, 10.0),
+            (540.0, 470.0)
+        );
+        // A panel wider than the room on either side is pinned inside the window
+        assert_eq!(
+            place_tooltip_panel((100.0, 50.0), (750.0, 100.0), window, 10.0),
+            (0.0, 60.0)
+        );
+        assert_eq!(
+            place_tooltip_panel((700.0, 50.0), (750.0, 100.0), window, 10.0),
+            (0.0, 60.0)
+        );
+        assert_eq!(
+            place_tooltip_panel((5.0, 5.0), (900.0, 700.0), window, 10.0),
+            (0.0, 0.0)
+        );
+    }
+
+    #[test]
+    fn test_focus_wraps_around_the_buttons() {
+        assert_eq!(step_focus(0, None, 1), None);
+        assert_eq!(step_focus(3, None, 1), Some(0));
+        assert_eq!(step_focus(3, None, -1), Some(2));
+        assert_eq!(step_focus(3, Some(0), 1), Some(1));
+        assert_eq!(step_focus(3, Some(2), 1), Some(0));
+        assert_eq!(step_focus(3, Some(0), -1), Some(2));
+    }
+
+    #[test]
+    fn test_padding_follows_the_window_across_scale_factors() {
+        let padding = (16.0, 10.0);
+        assert_eq!(utils::scaled_padding(padding, 1080.0), padding);
+        // The same window moved from a 100% to a 150% display is half as tall again
+        assert_eq!(utils::scaled_padding(padding, 1620.0), (24.0, 15.0));
+
+        // A button's rect is in physical pixels, and the forgotten cursor hits nothing
+        let button = Button::new("play", "Play")
+            .with_position(ButtonPosition::new(100.0, 100.0, 200.0, 50.0));
+        assert!(button.contains_point(150.0, 120.0));
+        assert!(!button.contains_point(NO_CURSOR.0, NO_CURSOR.1));
+    }
+
+    #[test]
+    fn test_hit_test_finds_the_topmost_live_button() {
+        let mut buttons = HashMap::new();
+        for (id, x) in [("back", 100.0), ("front", 150.0), ("hidden", 100.0)] {
+            let button =
+                Button::new(id, id).with_position(ButtonPosition::new(x, 100.0, 200.0, 50.0));
+            buttons.insert(id.to_string(), button);
+        }
+        buttons.get_mut("hidden").unwrap().visible = false;
+        let order: Vec<String> = ["back", "front", "hidden"].map(String::from).into();
+
+        // Where buttons overlap the one drawn last wins, and hidden buttons aren't there
+        assert_eq!(
+            topmost_button_at(&order, &buttons, 200.0, 120.0),
+            Some("front")
+        );
+        assert_eq!(
+            topmost_button_at(&order, &buttons, 120.0, 120.0),
+            Some("back")
+        );
+        assert_eq!(topmost_button_at(&order, &buttons, 120.0, 300.0), None);
+
+        // A disabled button still takes the point, though it can't be clicked
+        let front = buttons.get_mut("front").unwrap();
+        front.enabled = false;
+        assert!(!front.contains_point(200.0, 120.0));
+        assert_eq!(
+            topmost_button_at(&order, &buttons, 200.0, 120.0),
+            Some("front")
+        );
     }
 }
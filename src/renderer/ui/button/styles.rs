@@ -7,198 +7,248 @@
 //! ## Design Philosophy
 //!
 //! The button styles follow modern design principles:
-//! - **Semantic Colors**: Each button type (primary, warning, danger) uses colors
+//! - **Semantic Colors**: Each preset (primary, secondary, destructive) uses colors
 //!   that communicate their intended purpose to users
 //! - **Accessibility**: High contrast ratios between text and background colors
 //! - **Consistency**: Uniform padding, border radius, and typography across all styles
-//! - **Interactive States**: Distinct visual feedback for hover, pressed, and disabled states
-//! - **DPI Awareness**: Automatic scaling based on display density
+//! - **Interactive States**: Distinct visual feedback for hover, pressed, and disabled states,
+//!   derived from the style's colors by [`ButtonVisualParams`]
 //!
 //! ## Color Palette
 //!
 //! The module uses a professional slate-based color scheme with semantic variants:
 //! - **Primary (Green)**: For primary actions and positive confirmations
-//! - **Warning (Orange)**: For actions that require caution
-//! - **Danger (Red)**: For destructive or irreversible actions
+//! - **Secondary (Orange)**: For actions that require caution
+//! - **Destructive (Red)**: For destructive or irreversible actions
+//! - **Upgrade Card (Light Grey)**: For the tall cards of the upgrade menu
+//!
+//! ## Text Sizing
+//!
+//! Presets carry their text at its 1080p size and never look at the window. Screens that
+//! size their text for the window build that text style themselves and apply it with
+//! [`Button::with_text_style`](super::Button::with_text_style).
 //!
 //! ## Usage Example
 //!
-//! ```rust
-//! use crate::button_styles::{create_primary_button_style, create_warning_button_style};
+//! ```rust,ignore
+//! use crate::renderer::ui::button::{Button, ButtonPreset};
 //!
-//! let primary_style = create_primary_button_style();
-//! let warning_style = create_warning_button_style();
+//! let resume = Button::primary("pause_resume", "Resume Game");
+//! let quit = Button::new("pause_quit_menu", "Quit App")
+//!     .with_style(ButtonPreset::Destructive.style());
 //! ```
 
 use crate::renderer::text::TextStyle;
-use crate::renderer::ui::button::utils::dpi_scale;
-use crate::renderer::ui::button::{ButtonSpacing, ButtonStyle, TextAlign};
+use crate::renderer::ui::button::utils::ColorExt;
+use crate::renderer::ui::button::{ButtonSpacing, ButtonState, ButtonStyle, TextAlign};
 use glyphon::{Color, Style, Weight};
 
-// Professional color palette based on modern design systems
-// Using a cohesive slate-based color scheme with semantic variants
+/// Hover and press feedback shared by every button.
+///
+/// The text of a button is drawn in a color derived from the background color of its
+/// current state, and upgrade cards (`ButtonSpacing::Tall`) grow while hovered or pressed.
+/// Both the state update and the renderer read these from [`BUTTON_VISUALS`], so a tweak
+/// shows up everywhere at once.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ButtonVisualParams {
+    /// Scale of a hovered upgrade card
+    pub hover_scale: f32,
+    /// Scale of a pressed upgrade card
+    pub pressed_scale: f32,
+    /// How much faster an upgrade card's text grows than the card itself
+    pub text_growth: f32,
+    /// How much darker than the background the text is at rest, from 0.0 to 1.0
+    pub text_darken: f32,
+    /// Saturation added to the hover color for the text of a hovered button
+    pub hover_saturate: f32,
+    /// How much the pressed color is brightened for the text of a pressed button
+    pub pressed_brighten: f32,
+    /// Saturation added to the brightened pressed color
+    pub pressed_saturate: f32,
+}
 
-/// Creates a primary button style with a mint green color scheme.
-///
-/// Primary buttons are used for the main action on a screen or form.
-/// They should be used sparingly - typically only one primary button per view.
-///
-/// ## Visual Characteristics
-/// - **Base Color**: Dark mint green (`rgb(30, 110, 30)`)
-/// - **Hover State**: Darker mint green for visual feedback
-/// - **Pressed State**: Darkest mint green to indicate active press
-/// - **Disabled State**: Muted light green to indicate unavailability
-/// - **Typography**: Medium weight, white text for high contrast
-///
-/// ## Design Rationale
-/// The green color palette communicates positive action and success.
-/// The slightly desaturated tones provide a professional appearance while
-/// maintaining accessibility standards.
-///
-/// ## Returns
-/// A `ButtonStyle` struct configured for primary actions with DPI-aware scaling.
-///
-/// ## Example
-/// ```rust
-/// let submit_button_style = create_primary_button_style();
-/// ```
-pub fn create_primary_button_style() -> ButtonStyle {
-    let scale = dpi_scale(1080.0); // Assuming a default window height for default values
-    ButtonStyle {
-        background_color: Color::rgb(30, 110, 30), // Slightly less saturated, dark mint green
-        hover_color: Color::rgb(25, 85, 25),       // Even darker, maintaining hue
-        pressed_color: Color::rgb(20, 65, 20),     // Darkest mint for pressed state
-        disabled_color: Color::rgb(110, 140, 110), // Muted, lighter mint for disabled state
-        border_color: Color::rgb(25, 85, 25),      // Matches hover color
-        border_width: 1.0,
-        corner_radius: 8.0,
-        padding: (16.0, 10.0),
-        text_style: TextStyle {
-            font_family: "Hanken Grotesk".to_string(),
-            font_size: 18.0 * scale,
-            line_height: 20.0 * scale,
-            color: Color::rgb(255, 255, 255), // white
-            weight: Weight::MEDIUM,
-            style: Style::Normal,
-        },
-        text_align: TextAlign::Center,
-        spacing: ButtonSpacing::Hbar(0.3),
+/// The hover and press feedback every button uses.
+pub const BUTTON_VISUALS: ButtonVisualParams = ButtonVisualParams {
+    hover_scale: 1.1,     // 10% bigger
+    pressed_scale: 1.05,  // 5% bigger
+    text_growth: 2.0,     // 20% bigger text on hover, 10% when pressed
+    text_darken: 0.35,    // 35% darker than the background
+    hover_saturate: 0.90, // much brighter and more saturated
+    pressed_brighten: 0.15,
+    pressed_saturate: 0.35,
+};
+
+impl Default for ButtonVisualParams {
+    fn default() -> Self {
+        BUTTON_VISUALS
     }
 }
 
-/// Creates a warning button style with an orange color scheme.
-///
-/// Warning buttons are used for actions that require user attention or caution.
-/// They indicate that the action might have significant consequences or requires
-/// careful consideration before proceeding.
-///
-/// ## Visual Characteristics
-/// - **Base Color**: Dark orange (`rgb(170, 100, 10)`)
-/// - **Hover State**: Deeper orange with slight intensity increase
-/// - **Pressed State**: Darkest, richest orange for pressed feedback
-/// - **Disabled State**: Muted warm yellow-gray for disabled state
-/// - **Typography**: Medium weight, white text for readability
-///
-/// ## Design Rationale
-/// Orange is universally recognized as a cautionary color, making it ideal
-/// for warning actions. The warm tones maintain approachability while
-/// clearly communicating the need for attention.
-///
-/// ## Use Cases
-/// - Form validation warnings
-/// - Actions with potential data loss
-/// - Confirmation dialogs for significant changes
-/// - Temporary or reversible destructive actions
-///
-/// ## Returns
-/// A `ButtonStyle` struct configured for warning actions with DPI-aware scaling.
-///
-/// ## Example
-/// ```rust
-/// let reset_form_button_style = create_warning_button_style();
-/// ```
-pub fn create_warning_button_style() -> ButtonStyle {
-    let scale = dpi_scale(1080.0); // Assuming a default window height for default values
-    ButtonStyle {
-        background_color: Color::rgb(170, 100, 10), // Slightly less saturated, dark orange
-        hover_color: Color::rgb(140, 80, 5),        // Deeper, slightly more intense
-        pressed_color: Color::rgb(110, 60, 0),      // Darkest, richest for pressed
-        disabled_color: Color::rgb(160, 140, 115), // Muted, desaturated warm yellow-gray for disabled
-        border_color: Color::rgb(140, 80, 5),      // Matches hover color
-        border_width: 1.0,
-        corner_radius: 8.0,
-        padding: (16.0, 10.0),
-        text_style: TextStyle {
-            font_family: "Hanken Grotesk".to_string(),
-            font_size: 18.0 * scale,
-            line_height: 20.0 * scale,
-            color: Color::rgb(255, 255, 255), // white
-            weight: Weight::MEDIUM,
-            style: Style::Normal,
-        },
-        text_align: TextAlign::Center,
-        spacing: ButtonSpacing::Hbar(0.3),
+impl ButtonVisualParams {
+    /// Returns the text color of a button in the given state.
+    ///
+    /// # Arguments
+    /// * `style` - The button's style
+    /// * `state` - The button's current state
+    pub fn text_color(&self, style: &ButtonStyle, state: ButtonState) -> Color {
+        match state {
+            ButtonState::Normal => style.background_color.darken(self.text_darken),
+            ButtonState::Hover => style.hover_color.saturate(self.hover_saturate),
+            ButtonState::Pressed => style
+                .pressed_color
+                .brighten(self.pressed_brighten)
+                .saturate(self.pressed_saturate),
+            ButtonState::Disabled => Color::rgb(100, 116, 139), // slate-500 - muted text
+        }
+    }
+
+    /// Returns how much an upgrade card's text is scaled at a given card scale.
+    ///
+    /// # Arguments
+    /// * `hover_scale` - The scale the card is drawn at
+    pub fn text_scale(&self, hover_scale: f32) -> f32 {
+        1.0 + (hover_scale - 1.0) * self.text_growth
     }
 }
 
-/// Creates a danger button style with a red color scheme.
-///
-/// Danger buttons are reserved for destructive or irreversible actions.
-/// They should be used only when the action will result in data loss,
-/// account deletion, or other permanent changes that cannot be undone.
-///
-/// ## Visual Characteristics
-/// - **Base Color**: Dark red (`rgb(110, 20, 10)`)
-/// - **Hover State**: Even darker, more intense red for hover feedback
-/// - **Pressed State**: Darkest, most saturated red for pressed state
-/// - **Disabled State**: Muted slate color to indicate unavailability
-/// - **Typography**: Medium weight, white text for maximum contrast
-///
-/// ## Design Rationale
-/// Red is the universal color for danger and stop actions. The dark,
-/// saturated tones convey seriousness while maintaining professional
-/// appearance. The high contrast ensures accessibility compliance.
-///
-/// ## Use Cases
-/// - Delete account buttons
-/// - Permanent data removal
-/// - System shutdown/reset actions
-/// - Irreversible configuration changes
-///
-/// ## UX Considerations
-/// Danger buttons should typically be:
-/// - Accompanied by confirmation dialogs
-/// - Placed away from primary actions
-/// - Used sparingly to maintain their impact
-/// - Clearly labeled with specific action names
-///
-/// ## Returns
-/// A `ButtonStyle` struct configured for dangerous actions with DPI-aware scaling.
-///
-/// ## Example
-/// ```rust
-/// let delete_account_button_style = create_danger_button_style();
-/// ```
-pub fn create_danger_button_style() -> ButtonStyle {
-    let scale = dpi_scale(1080.0); // Assuming a default window height for default values
-    ButtonStyle {
-        background_color: Color::rgb(110, 20, 10), // Slightly less saturated, dark red
-        hover_color: Color::rgb(90, 15, 5),        // Even darker, more intense red
-        pressed_color: Color::rgb(70, 10, 0),      // Darkest, most saturated red
-        disabled_color: Color::rgb(80, 96, 119),   // Slightly darker slate-500, muted
-        border_color: Color::rgb(90, 15, 5),       // Match hover color
-        border_width: 1.0,
-        corner_radius: 8.0,
-        padding: (16.0, 10.0),
-        text_style: TextStyle {
-            font_family: "Hanken Grotesk".to_string(),
-            font_size: 18.0 * scale,
-            line_height: 20.0 * scale,
-            color: Color::rgb(255, 255, 255), // white
-            weight: Weight::MEDIUM,
-            style: Style::Normal,
-        },
-        text_align: TextAlign::Center,
-        spacing: ButtonSpacing::Hbar(0.3),
+/// The styles buttons are built from.
+///
+/// Every preset uses a 1px border, 16px by 10px padding and medium-weight white
+/// Hanken Grotesk at 18px, centered, in a bar 30% of the window wide, except where noted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ButtonPreset {
+    /// The main action on a screen, in dark mint green (`rgb(30, 110, 30)`).
+    ///
+    /// The green communicates positive action; it should be used sparingly, typically only
+    /// once or twice per view.
+    Primary,
+    /// An action that needs a moment's thought, in dark orange (`rgb(170, 100, 10)`).
+    ///
+    /// Used for actions with significant but recoverable consequences, such as
+    /// restarting a run.
+    Secondary,
+    /// A destructive or irreversible action, in dark red (`rgb(110, 20, 10)`).
+    ///
+    /// Best placed away from primary actions and clearly labeled with what it does.
+    Destructive,
+    /// A tall upgrade card in light grey (`rgb(200, 200, 200)`), with 12px corners and 8px
+    /// padding.
+    ///
+    /// The upgrade menu sets the card's height proportion for the window it lays out in.
+    UpgradeCard,
+}
+
+impl ButtonPreset {
+    /// Builds the preset's style.
+    ///
+    /// # Returns
+    /// A `ButtonStyle` with the preset's colors and its text at the 1080p size
+    pub fn style(self) -> ButtonStyle {
+        // (background, hover, pressed, disabled, border)
+        let (background, hover, pressed, disabled, border) = match self {
+            ButtonPreset::Primary => (
+                Color::rgb(30, 110, 30),   // Slightly less saturated, dark mint green
+                Color::rgb(25, 85, 25),    // Even darker, maintaining hue
+                Color::rgb(20, 65, 20),    // Darkest mint for pressed state
+                Color::rgb(110, 140, 110), // Muted, lighter mint for disabled state
+                Color::rgb(25, 85, 25),    // Matches hover color
+            ),
+            ButtonPreset::Secondary => (
+                Color::rgb(170, 100, 10),  // Slightly less saturated, dark orange
+                Color::rgb(140, 80, 5),    // Deeper, slightly more intense
+                Color::rgb(110, 60, 0),    // Darkest, richest for pressed
+                Color::rgb(160, 140, 115), // Muted, desaturated warm yellow-gray for disabled
+                Color::rgb(140, 80, 5),    // Matches hover color
+            ),
+            ButtonPreset::Destructive => (
+                Color::rgb(110, 20, 10), // Slightly less saturated, dark red
+                Color::rgb(90, 15, 5),   // Even darker, more intense red
+                Color::rgb(70, 10, 0),   // Darkest, most saturated red
+                Color::rgb(80, 96, 119), // Slightly darker slate-500, muted
+                Color::rgb(90, 15, 5),   // Match hover color
+            ),
+            ButtonPreset::UpgradeCard => (
+                Color::rgb(200, 200, 200), // Light grey
+                Color::rgb(180, 180, 180), // Slightly darker on hover
+                Color::rgb(160, 160, 160), // Even darker when pressed
+                Color::rgb(110, 140, 110), // Same as primary
+                Color::rgb(25, 85, 25),    // Same as primary
+            ),
+        };
+        let (corner_radius, padding, spacing) = match self {
+            ButtonPreset::UpgradeCard => (12.0, (8.0, 8.0), ButtonSpacing::Tall(0.8)),
+            _ => (8.0, (16.0, 10.0), ButtonSpacing::Hbar(0.3)),
+        };
+
+        ButtonStyle {
+            background_color: background,
+            hover_color: hover,
+            pressed_color: pressed,
+            disabled_color: disabled,
+            border_color: border,
+            border_width: 1.0,
+            corner_radius,
+            padding,
+            text_style: TextStyle {
+                font_family: "Hanken Grotesk".to_string(),
+                font_size: 18.0,
+                line_height: 20.0,
+                color: Color::rgb(255, 255, 255), // white
+                weight: Weight::MEDIUM,
+                style: Style::Normal,
+            },
+            text_align: TextAlign::Center,
+            spacing,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Text colors of every preset at rest, hovered and pressed.
+    ///
+    /// Pinned so any change to a preset or to [`BUTTON_VISUALS`] shows up in review.
+    #[test]
+    fn test_preset_text_colors_are_pinned() {
+        let expected = [
+            (
+                ButtonPreset::Primary,
+                [(19, 71, 19), (0, 115, 0), (43, 104, 43)],
+            ),
+            (
+                ButtonPreset::Secondary,
+                [(110, 65, 6), (207, 87, 0), (172, 93, 0)],
+            ),
+            (
+                ButtonPreset::Destructive,
+                [(71, 13, 6), (132, 0, 0), (120, 29, 14)],
+            ),
+            (
+                ButtonPreset::UpgradeCard,
+                [(130, 130, 130), (180, 180, 180), (174, 174, 174)],
+            ),
+        ];
+        for (preset, colors) in expected {
+            let style = preset.style();
+            let actual = [
+                ButtonState::Normal,
+                ButtonState::Hover,
+                ButtonState::Pressed,
+            ]
+            .map(|state| {
+                let color = BUTTON_VISUALS.text_color(&style, state);
+                (color.r(), color.g(), color.b())
+            });
+            assert_eq!(actual, colors, "{:?}", preset);
+        }
+    }
+
+    #[test]
+    fn test_upgrade_card_text_grows_twice_as_fast_as_the_card() {
+        assert_eq!(BUTTON_VISUALS.text_scale(1.0), 1.0);
+        assert!((BUTTON_VISUALS.text_scale(BUTTON_VISUALS.hover_scale) - 1.2).abs() < 1e-5);
+        assert!((BUTTON_VISUALS.text_scale(BUTTON_VISUALS.pressed_scale) - 1.1).abs() < 1e-5);
     }
 }
@@ -13,7 +13,7 @@
 //!
 //! ## Usage Example
 //!
-//! ```rust
+//! ```rust,ignore
 //! use crate::button_config::*;
 //! use glyphon::Color;
 //!
@@ -40,7 +40,7 @@ use glyphon::{Color, Style, Weight};
 ///
 /// # Examples
 ///
-/// ```rust
+/// ```rust,ignore
 /// let left_aligned = TextAlign::Left;   // Text aligned to left edge
 /// let right_aligned = TextAlign::Right; // Text aligned to right edge  
 /// let centered = TextAlign::Center;     // Text centered (default)
@@ -70,7 +70,7 @@ pub enum TextAlign {
 ///
 /// # Examples
 ///
-/// ```rust
+/// ```rust,ignore
 /// let compact = ButtonSpacing::Wrap;        // Minimal size, wraps content
 /// let half_width = ButtonSpacing::Hbar(0.5); // 50% of container width
 /// let tall = ButtonSpacing::Tall(10.0);     // Full height with 10px margin
@@ -121,7 +121,7 @@ pub enum ButtonSpacing {
 ///
 /// # Examples
 ///
-/// ```rust
+/// ```rust,ignore
 /// // Use default styling
 /// let default_style = ButtonStyle::default();
 ///
@@ -245,7 +245,7 @@ impl Default for ButtonStyle {
 ///
 /// # Examples
 ///
-/// ```rust
+/// ```rust,ignore
 /// // Position button at top-left corner
 /// let top_left = ButtonPosition::new(10.0, 10.0, 100.0, 30.0);
 ///
@@ -323,7 +323,7 @@ impl ButtonPosition {
     ///
     /// # Examples
     ///
-    /// ```rust
+    /// ```rust,ignore
     /// // Create a 100x30 button at position (50, 20)
     /// let position = ButtonPosition::new(50.0, 20.0, 100.0, 30.0);
     /// assert_eq!(position.anchor, ButtonAnchor::TopLeft);
@@ -353,7 +353,7 @@ impl ButtonPosition {
     ///
     /// # Examples
     ///
-    /// ```rust
+    /// ```rust,ignore
     /// let centered = ButtonPosition::new(200.0, 100.0, 80.0, 25.0)
     ///     .with_anchor(ButtonAnchor::Center);
     /// ```
@@ -380,7 +380,7 @@ impl ButtonPosition {
     ///
     /// # Examples
     ///
-    /// ```rust
+    /// ```rust,ignore
     /// // Top-left anchored button
     /// let tl_pos = ButtonPosition::new(10.0, 20.0, 100.0, 30.0);
     /// let (x, y) = tl_pos.calculate_actual_position();
@@ -432,7 +432,7 @@ impl ButtonPosition {
 ///
 /// # Examples
 ///
-/// ```rust
+/// ```rust,ignore
 /// let mut state = ButtonState::Normal;
 ///
 /// // Handle mouse hover
@@ -450,7 +450,7 @@ impl ButtonPosition {
 ///     state = ButtonState::Disabled;
 /// }
 /// ```
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum ButtonState {
     /// Default state when button is not being interacted with
     Normal,
@@ -94,3 +94,20 @@ impl ColorExt for Color {
 pub fn dpi_scale(window_height: f32) -> f32 {
     (window_height / 1080.0).clamp(0.7, 2.0)
 }
+
+/// Scales a style's padding with the window the same way as [`dpi_scale`].
+///
+/// Styles give their padding as it looks in a 1080p window, so buttons keep their
+/// proportions when the window is resized or moved to a display with a different scale
+/// factor.
+///
+/// # Arguments
+/// * `padding` - The style's horizontal and vertical padding at 1080p
+/// * `window_height` - The height of the window in physical pixels
+///
+/// # Returns
+/// The horizontal and vertical padding in physical pixels
+pub fn scaled_padding(padding: (f32, f32), window_height: f32) -> (f32, f32) {
+    let scale = dpi_scale(window_height);
+    (padding.0 * scale, padding.1 * scale)
+}
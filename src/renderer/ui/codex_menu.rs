@@ -0,0 +1,994 @@
+//! Maze Codex Menu
+//!
+//! This module provides the codex screen reached from the title menu. It shows one card per
+//! kind of maze in the [`Codex`], with the thumbnail of the fastest run and the kind's stats,
+//! in a grid that scrolls with the arrow keys, Page Up/Down, Home/End and the mouse wheel.
+//! Clicking a card selects it, and Escape or the back button returns to the title screen.
+//!
+//! The codex can hold hundreds of kinds, so only the cards that fit on screen are drawn, at
+//! most [`MAX_VISIBLE_CARDS`], and each card slot keeps one text buffer that is rewritten as
+//! the grid scrolls. Thumbnails are read from disk as their cards come into view, a few per
+//! frame, and the ones that haven't been on screen for a while are dropped again. Missing or
+//! corrupt thumbnails show a placeholder.
+
+use crate::game::audio::GameAudioManager;
+use crate::game::bindings::Action;
+use crate::game::codex::{self, Codex, CodexEntry};
+use crate::paths;
+use crate::renderer::gpu_memory::GpuMemoryTracker;
+use crate::renderer::icon::{Icon, IconRenderer};
+use crate::renderer::rectangle::{Rectangle, RectangleRenderer};
+use crate::renderer::text::{TextPosition, TextStyle};
+use crate::renderer::ui::button::{
+    Button, ButtonAnchor, ButtonManager, ButtonPosition, ButtonPreset, TextAlign,
+};
+use glyphon::{Resolution, Weight};
+use std::collections::HashMap;
+use std::ops::Range;
+use wgpu::{Device, Queue, RenderPass, SurfaceConfiguration};
+use winit::dpi::PhysicalSize;
+use winit::event::{ElementState, KeyEvent, MouseButton, MouseScrollDelta, WindowEvent};
+
+/// Most cards drawn at once, however large the window is.
+pub const MAX_VISIBLE_CARDS: usize = 24;
+
+/// Thumbnails read from disk per frame, so scrolling never stalls on file reads.
+const THUMBNAILS_PER_FRAME: usize = 2;
+
+/// Thumbnails kept on the GPU; the least recently shown are dropped past this.
+const MAX_LOADED_THUMBNAILS: usize = 48;
+
+/// Texture ID of the picture shown while a thumbnail is loading, missing or corrupt.
+const PLACEHOLDER_TEXTURE_ID: &str = "codex_placeholder";
+
+/// Button ID of the back button.
+const BACK_BUTTON_ID: &str = "codex_back";
+
+/// Text buffer ID of the screen title.
+const TITLE_TEXT_ID: &str = "codex_title";
+
+/// Text buffer ID of the selected card's position, or the empty codex message.
+const STATUS_TEXT_ID: &str = "codex_status";
+
+/// Actions that can be triggered from the codex menu
+#[derive(Debug, Clone, PartialEq)]
+pub enum CodexAction {
+    /// Go back to the title screen
+    Back,
+    /// No action has been taken
+    None,
+}
+
+/// Which cards of the codex are on screen, and which one is selected.
+///
+/// Cards are laid out in rows of `columns`, and `rows` of them fit on screen at a time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CodexGrid {
+    /// Cards per row
+    columns: usize,
+    /// Rows on screen at once
+    rows: usize,
+    /// Index of the top row on screen
+    first_row: usize,
+    /// Index of the selected card
+    selected: usize,
+    /// Number of cards in the codex
+    count: usize,
+}
+
+impl Default for CodexGrid {
+    fn default() -> Self {
+        Self {
+            columns: 1,
+            rows: 1,
+            first_row: 0,
+            selected: 0,
+            count: 0,
+        }
+    }
+}
+
+impl CodexGrid {
+    /// Sets how many cards fit on screen, capped at [`MAX_VISIBLE_CARDS`].
+    ///
+    /// # Arguments
+    ///
+    /// * `columns` - Cards that fit in a row
+    /// * `rows` - Rows that fit on screen
+    pub fn set_shape(&mut self, columns: usize, rows: usize) {
+        self.columns = columns.clamp(1, MAX_VISIBLE_CARDS);
+        self.rows = rows.clamp(1, (MAX_VISIBLE_CARDS / self.columns).max(1));
+        self.reveal_selected();
+    }
+
+    /// Sets the number of cards, keeping the selection on a card.
+    ///
+    /// # Arguments
+    ///
+    /// * `count` - Number of cards in the codex
+    pub fn set_count(&mut self, count: usize) {
+        self.count = count;
+        self.selected = self.selected.min(count.saturating_sub(1));
+        self.reveal_selected();
+    }
+
+    /// Returns the indices of the cards on screen.
+    pub fn visible(&self) -> Range<usize> {
+        let start = (self.first_row * self.columns).min(self.count);
+        let end = ((self.first_row + self.rows) * self.columns).min(self.count);
+        start..end
+    }
+
+    /// Returns the index of the selected card, if there are any cards.
+    pub fn selected(&self) -> Option<usize> {
+        (self.count > 0).then_some(self.selected)
+    }
+
+    /// Selects a card and scrolls it into view.
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - The card to select, clamped to the last card
+    pub fn select(&mut self, index: usize) {
+        self.selected = index.min(self.count.saturating_sub(1));
+        self.reveal_selected();
+    }
+
+    /// Moves the selection by a number of cards, stopping at the first and last card.
+    ///
+    /// # Arguments
+    ///
+    /// * `delta` - Cards to move by; a row is `columns` cards
+    pub fn move_selection(&mut self, delta: isize) {
+        self.select(self.selected.saturating_add_signed(delta));
+    }
+
+    /// Scrolls the view without moving the selection.
+    ///
+    /// # Arguments
+    ///
+    /// * `rows` - Rows to scroll by; positive scrolls down
+    pub fn scroll(&mut self, rows: isize) {
+        self.first_row = self
+            .first_row
+            .saturating_add_signed(rows)
+            .min(self.max_first_row());
+    }
+
+    /// Returns the number of cards in one row.
+    pub fn columns(&self) -> usize {
+        self.columns
+    }
+
+    /// Returns the number of cards a page moves the selection by.
+    pub fn page(&self) -> usize {
+        self.columns * self.rows
+    }
+
+    /// Returns the last row that can be at the top of the screen.
+    fn max_first_row(&self) -> usize {
+        self.count.div_ceil(self.columns).saturating_sub(self.rows)
+    }
+
+    /// Scrolls just far enough for the selected card to be on screen.
+    fn reveal_selected(&mut self) {
+        let row = self.selected / self.columns;
+        if row < self.first_row {
+            self.first_row = row;
+        } else if row >= self.first_row + self.rows {
+            self.first_row = row + 1 - self.rows;
+        }
+        self.first_row = self.first_row.min(self.max_first_row());
+    }
+}
+
+/// Pads a picture with transparent pixels so it fits inside the circle icons are clipped to.
+///
+/// # Arguments
+///
+/// * `rgba` - Tightly packed RGBA8 pixels, row-major from the top-left
+/// * `dimensions` - Width and height of the picture in pixels
+///
+/// # Returns
+///
+/// The picture centered in a transparent square, and the square's dimensions
+pub fn pad_for_icon(rgba: &[u8], dimensions: (u32, u32)) -> (Vec<u8>, (u32, u32)) {
+    let (width, height) = (dimensions.0 as usize, dimensions.1 as usize);
+    // The square's inscribed circle holds the picture's corners
+    let side = (width.max(height) as f32 * std::f32::consts::SQRT_2).ceil() as usize + 2;
+    let (left, top) = ((side - width) / 2, (side - height) / 2);
+
+    let mut padded = vec![0u8; side * side * 4];
+    for row in 0..height {
+        let source = &rgba[row * width * 4..(row + 1) * width * 4];
+        let start = ((top + row) * side + left) * 4;
+        padded[start..start + width * 4].copy_from_slice(source);
+    }
+    (padded, (side as u32, side as u32))
+}
+
+/// Builds the picture shown in place of a missing thumbnail: a dark gray checkerboard.
+fn placeholder_thumbnail() -> (Vec<u8>, (u32, u32)) {
+    let size = 32u32;
+    let pixels: Vec<u8> = (0..size * size)
+        .flat_map(|i| {
+            let (x, y) = (i % size, i / size);
+            let shade = if (x / 4 + y / 4) % 2 == 0 { 90 } else { 60 };
+            [shade, shade, shade, 255]
+        })
+        .collect();
+    pad_for_icon(&pixels, (size, size))
+}
+
+/// Returns the texture ID a kind of maze's thumbnail is loaded under.
+///
+/// # Arguments
+///
+/// * `slug` - The maze kind's file name
+fn thumbnail_texture_id(slug: &str) -> String {
+    format!("codex_thumb_{}", slug)
+}
+
+/// Returns the text buffer ID of a card slot.
+///
+/// # Arguments
+///
+/// * `slot` - Position of the card on screen, from the top-left
+fn card_text_id(slot: usize) -> String {
+    format!("codex_card_{}", slot)
+}
+
+/// Writes the text shown on a card.
+///
+/// # Arguments
+///
+/// * `entry` - The codex entry the card shows
+fn card_text(entry: &CodexEntry) -> String {
+    let best = entry
+        .best_time
+        .map_or("none yet".to_string(), codex::format_duration);
+    format!(
+        "{}\nEscapes {}   Deaths {}\nBest {}   Longest {}\n{}",
+        entry.kind.label(),
+        entry.completions,
+        entry.deaths,
+        best,
+        codex::format_duration(entry.longest_survival),
+        entry.fun_fact()
+    )
+}
+
+/// A thumbnail the menu has tried to load.
+#[derive(Debug, Clone, Copy)]
+struct Thumbnail {
+    /// Whether the texture is on the GPU; `false` shows the placeholder
+    loaded: bool,
+    /// Frame the thumbnail's card was last on screen
+    last_shown: u64,
+}
+
+/// Where the parts of the codex screen go for the current window size.
+#[derive(Debug, Clone, Default)]
+struct CodexLayout {
+    /// Height-based UI scale, the same one the other menus use
+    scale: f32,
+    /// Top-left corner of each card slot, in reading order
+    slots: Vec<(f32, f32)>,
+    /// Width and height of a card
+    card_size: (f32, f32),
+}
+
+/// The codex screen: a grid of cards over the title background, and a back button.
+pub struct CodexMenu {
+    /// Manages the back button; its text renderer also draws the card text
+    pub button_manager: ButtonManager,
+    /// Draws the card backgrounds
+    rectangle_renderer: RectangleRenderer,
+    /// Draws the thumbnails
+    icon_renderer: IconRenderer,
+    /// Which cards are on screen and selected
+    grid: CodexGrid,
+    /// Where the cards go for the current window size
+    layout: CodexLayout,
+    /// Current window size
+    window_size: PhysicalSize<u32>,
+    /// Thumbnails tried so far, by maze kind file name
+    thumbnails: HashMap<String, Thumbnail>,
+    /// Number of card text buffers created so far
+    card_buffers: usize,
+    /// Frames updated since the menu was created, for dropping old thumbnails
+    frame: u64,
+    /// Mouse wheel movement not yet turned into whole rows
+    scroll_remainder: f32,
+    /// Whether the cards need to be redrawn
+    dirty: bool,
+    /// The last action that was triggered by the menu
+    pub last_action: CodexAction,
+}
+
+impl CodexMenu {
+    /// Creates a new codex menu instance.
+    ///
+    /// # Arguments
+    ///
+    /// * `device` - The WGPU device for rendering
+    /// * `queue` - The WGPU command queue
+    /// * `surface_format` - The surface texture format
+    /// * `window_size` - The window size, for sizing calculations
+    /// * `gpu_memory` - Tracker the menu's thumbnails and glyph textures are counted in
+    ///
+    /// # Returns
+    ///
+    /// A new `CodexMenu` with no cards, laid out for the window
+    pub fn new(
+        device: &Device,
+        queue: &Queue,
+        surface_format: wgpu::TextureFormat,
+        window_size: PhysicalSize<u32>,
+        gpu_memory: &GpuMemoryTracker,
+    ) -> Self {
+        let mut button_manager =
+            ButtonManager::new(device, queue, surface_format, window_size, gpu_memory);
+        button_manager
+            .add_button(Button::primary(BACK_BUTTON_ID, "Back").with_text_align(TextAlign::Center));
+        let text_renderer = &mut button_manager.text_renderer;
+        text_renderer.create_text_buffer(TITLE_TEXT_ID, "Maze Codex", None, None);
+        text_renderer.create_text_buffer(STATUS_TEXT_ID, "", None, None);
+
+        let mut icon_renderer = IconRenderer::new(device, queue, surface_format, gpu_memory);
+        let (placeholder, dimensions) = placeholder_thumbnail();
+        if let Err(e) = icon_renderer.register_texture(
+            device,
+            queue,
+            PLACEHOLDER_TEXTURE_ID,
+            &placeholder,
+            dimensions,
+        ) {
+            eprintln!("Failed to create codex placeholder: {}", e);
+        }
+        let mut menu = Self {
+            button_manager,
+            rectangle_renderer: RectangleRenderer::new(device, surface_format),
+            icon_renderer,
+            grid: CodexGrid::default(),
+            layout: CodexLayout::default(),
+            window_size,
+            thumbnails: HashMap::new(),
+            card_buffers: 0,
+            frame: 0,
+            scroll_remainder: 0.0,
+            dirty: true,
+            last_action: CodexAction::None,
+        };
+        menu.rectangle_renderer
+            .resize(window_size.width as f32, window_size.height as f32);
+        menu.icon_renderer
+            .resize(window_size.width as f32, window_size.height as f32);
+        menu.layout();
+        menu
+    }
+
+    /// Opens the menu on the codex as it is now.
+    ///
+    /// Thumbnails loaded on an earlier visit are dropped, since a new best run may have
+    /// replaced them since.
+    ///
+    /// # Arguments
+    ///
+    /// * `codex` - The codex to show
+    pub fn open(&mut self, codex: &Codex) {
+        for slug in self.thumbnails.keys() {
+            self.icon_renderer
+                .remove_texture(&thumbnail_texture_id(slug));
+        }
+        self.thumbnails.clear();
+        self.grid.set_count(codex.entries.len());
+        self.last_action = CodexAction::None;
+        self.scroll_remainder = 0.0;
+        self.dirty = true;
+    }
+
+    /// Sizes the grid and the back button for the window size.
+    fn layout(&mut self) {
+        let (width, height) = (
+            self.window_size.width as f32,
+            self.window_size.height as f32,
+        );
+        let scale = (height / 1080.0).clamp(0.7, 2.0);
+        let margin = 60.0 * scale;
+        let gap = 20.0 * scale;
+        let header = 110.0 * scale;
+        let footer = 110.0 * scale;
+
+        let area_width = (width - 2.0 * margin).max(1.0);
+        let area_height = (height - margin - header - footer).max(1.0);
+        let min_card_width = 380.0 * scale;
+        let card_height = 150.0 * scale;
+
+        let columns = ((area_width + gap) / (min_card_width + gap)).floor() as usize;
+        let rows = ((area_height + gap) / (card_height + gap)).floor() as usize;
+        self.grid.set_shape(columns, rows);
+        let (columns, rows) = (self.grid.columns, self.grid.rows);
+        let card_width = (area_width - gap * (columns - 1) as f32) / columns as f32;
+
+        self.layout = CodexLayout {
+            scale,
+            slots: (0..columns * rows)
+                .map(|slot| {
+                    let (row, column) = (slot / columns, slot % columns);
+                    (
+                        margin + column as f32 * (card_width + gap),
+                        margin + header + row as f32 * (card_height + gap),
+                    )
+                })
+                .collect(),
+            card_size: (card_width, card_height),
+        };
+
+        let button_height = (60.0 * scale).clamp(32.0, 110.0);
+        let mut style = ButtonPreset::Primary.style();
+        style.text_style.font_size = (24.0 * scale).clamp(16.0, 44.0);
+        style.text_style.line_height = (30.0 * scale).clamp(20.0, 56.0);
+        if let Some(button) = self.button_manager.get_button_mut(BACK_BUTTON_ID) {
+            button.style = style;
+            button.position = ButtonPosition::new(
+                margin,
+                height - margin - button_height,
+                (200.0 * scale).clamp(120.0, 400.0),
+                button_height,
+            )
+            .with_anchor(ButtonAnchor::TopLeft);
+        }
+        self.button_manager.update_button_positions();
+        self.dirty = true;
+    }
+
+    /// Handles input events for the codex menu.
+    ///
+    /// # Arguments
+    ///
+    /// * `event` - The window event to handle
+    /// * `action` - What the key is bound to in the menu context, for a keyboard event
+    /// * `audio_manager` - The audio manager for playing button click sounds
+    ///
+    /// # Returns
+    ///
+    /// `true` if the event was a key the codex uses, so nothing else should act on it
+    pub fn handle_input(
+        &mut self,
+        event: &WindowEvent,
+        action: Option<Action>,
+        audio_manager: &mut GameAudioManager,
+    ) -> bool {
+        self.button_manager.handle_input(event);
+        if self.button_manager.is_button_clicked(BACK_BUTTON_ID) {
+            self.last_action = CodexAction::Back;
+            let _ = audio_manager.play_select();
+        }
+
+        match event {
+            WindowEvent::KeyboardInput {
+                event: key_event, ..
+            } => self.handle_key(key_event, action, audio_manager),
+            WindowEvent::MouseWheel { delta, .. } => {
+                self.scroll_remainder -= match delta {
+                    MouseScrollDelta::LineDelta(_, lines) => *lines,
+                    MouseScrollDelta::PixelDelta(pixels) => {
+                        pixels.y as f32 / self.layout.card_size.1.max(1.0)
+                    }
+                };
+                let rows = self.scroll_remainder.trunc();
+                if rows != 0.0 {
+                    self.scroll_remainder -= rows;
+                    self.grid.scroll(rows as isize);
+                    self.dirty = true;
+                }
+                false
+            }
+            WindowEvent::MouseInput {
+                state: ElementState::Pressed,
+                button: MouseButton::Left,
+                ..
+            } => {
+                if let Some(index) = self.card_at(self.button_manager.mouse_position) {
+                    self.grid.select(index);
+                    self.dirty = true;
+                }
+                false
+            }
+            _ => false,
+        }
+    }
+
+    /// Moves the selection for the navigation actions, and goes back on Back.
+    ///
+    /// # Arguments
+    ///
+    /// * `event` - The keyboard event
+    /// * `action` - What the key is bound to in the menu context, if anything
+    /// * `audio_manager` - The audio manager for playing the back sound
+    ///
+    /// # Returns
+    ///
+    /// `true` if the key is one the codex uses
+    fn handle_key(
+        &mut self,
+        event: &KeyEvent,
+        action: Option<Action>,
+        audio_manager: &mut GameAudioManager,
+    ) -> bool {
+        let Some(action) = action else {
+            return false;
+        };
+        let columns = self.grid.columns() as isize;
+        let page = self.grid.page() as isize;
+        let delta = match action {
+            Action::NavigateLeft => -1,
+            Action::NavigateRight => 1,
+            Action::NavigateUp => -columns,
+            Action::NavigateDown => columns,
+            Action::NavigatePageUp => -page,
+            Action::NavigatePageDown => page,
+            Action::NavigateFirst => isize::MIN,
+            Action::NavigateLast => isize::MAX,
+            Action::Back => 0,
+            _ => return false,
+        };
+        if event.state != ElementState::Pressed {
+            return true;
+        }
+
+        if action == Action::Back {
+            if !event.repeat {
+                self.last_action = CodexAction::Back;
+                let _ = audio_manager.play_select();
+            }
+        } else {
+            self.grid.move_selection(delta);
+            self.dirty = true;
+        }
+        true
+    }
+
+    /// Returns the card under a point on screen.
+    ///
+    /// # Arguments
+    ///
+    /// * `point` - The point in window pixels
+    fn card_at(&self, (x, y): (f32, f32)) -> Option<usize> {
+        let (width, height) = self.layout.card_size;
+        self.grid
+            .visible()
+            .zip(&self.layout.slots)
+            .find(|(_, (left, top))| {
+                x >= *left && x <= left + width && y >= *top && y <= top + height
+            })
+            .map(|(index, _)| index)
+    }
+
+    /// Gets the last action that was triggered and resets it to `None`.
+    ///
+    /// # Returns
+    ///
+    /// The last `CodexAction` that was triggered, or `None` if no action occurred
+    pub fn get_last_action(&mut self) -> CodexAction {
+        let action = self.last_action.clone();
+        self.last_action = CodexAction::None;
+        action
+    }
+
+    /// Loads thumbnails for the cards on screen and redraws the cards if anything changed.
+    ///
+    /// # Arguments
+    ///
+    /// * `device` - The WGPU device
+    /// * `queue` - The WGPU command queue
+    /// * `codex` - The codex being shown
+    pub fn update(&mut self, device: &Device, queue: &Queue, codex: &Codex) {
+        self.frame += 1;
+        if self.grid.count != codex.entries.len() {
+            self.grid.set_count(codex.entries.len());
+            self.dirty = true;
+        }
+        self.load_thumbnails(device, queue, codex);
+        if self.dirty {
+            self.dirty = false;
+            self.refresh_cards(codex);
+        }
+    }
+
+    /// Loads a few thumbnails of the cards on screen, and drops ones not shown for a while.
+    ///
+    /// # Arguments
+    ///
+    /// * `device` - The WGPU device
+    /// * `queue` - The WGPU command queue
+    /// * `codex` - The codex being shown
+    fn load_thumbnails(&mut self, device: &Device, queue: &Queue, codex: &Codex) {
+        let mut loads = 0;
+        for index in self.grid.visible() {
+            let entry = &codex.entries[index];
+            let slug = entry.kind.slug();
+            if let Some(thumbnail) = self.thumbnails.get_mut(&slug) {
+                thumbnail.last_shown = self.frame;
+                continue;
+            }
+            if loads == THUMBNAILS_PER_FRAME {
+                continue;
+            }
+            loads += 1;
+
+            // Kinds never completed have no thumbnail yet
+            let loaded = entry.best_time.is_some() && self.load_thumbnail(device, queue, &slug);
+            self.thumbnails.insert(
+                slug,
+                Thumbnail {
+                    loaded,
+                    last_shown: self.frame,
+                },
+            );
+            self.dirty = true;
+        }
+
+        let mut loaded: Vec<(u64, String)> = self
+            .thumbnails
+            .iter()
+            .filter(|(_, thumbnail)| thumbnail.loaded)
+            .map(|(slug, thumbnail)| (thumbnail.last_shown, slug.clone()))
+            .collect();
+        if loaded.len() <= MAX_LOADED_THUMBNAILS {
+            return;
+        }
+        loaded.sort();
+        let excess = loaded.len() - MAX_LOADED_THUMBNAILS;
+        for (last_shown, slug) in loaded.into_iter().take(excess) {
+            if last_shown == self.frame {
+                break;
+            }
+            self.icon_renderer
+                .remove_texture(&thumbnail_texture_id(&slug));
+            self.thumbnails.remove(&slug);
+        }
+    }
+
+    /// Reads one thumbnail from disk and uploads it.
+    ///
+    /// # Arguments
+    ///
+    /// * `device` - The WGPU device
+    /// * `queue` - The WGPU command queue
+    /// * `slug` - The maze kind's file name
+    ///
+    /// # Returns
+    ///
+    /// `true` if the thumbnail was uploaded, `false` if the placeholder should be shown
+    fn load_thumbnail(&mut self, device: &Device, queue: &Queue, slug: &str) -> bool {
+        let result = codex::load_thumbnail(&paths::get().codex_thumbnail(slug)).and_then(
+            |(rgba, dimensions)| {
+                let (padded, dimensions) = pad_for_icon(&rgba, dimensions);
+                self.icon_renderer.register_texture(
+                    device,
+                    queue,
+                    &thumbnail_texture_id(slug),
+                    &padded,
+                    dimensions,
+                )
+            },
+        );
+        match result {
+            Ok(()) => true,
+            Err(e) => {
+                eprintln!("Codex thumbnail unavailable, using placeholder: {}", e);
+                false
+            }
+        }
+    }
+
+    /// Rebuilds the card backgrounds, thumbnails and text for the cards on screen.
+    ///
+    /// # Arguments
+    ///
+    /// * `codex` - The codex being shown
+    fn refresh_cards(&mut self, codex: &Codex) {
+        let scale = self.layout.scale;
+        let (card_width, card_height) = self.layout.card_size;
+        let padding = 12.0 * scale;
+        let thumbnail_side = card_height - 2.0 * padding;
+        let window_height = self.window_size.height as f32;
+        let text_style = TextStyle {
+            font_size: (18.0 * scale).clamp(12.0, 36.0),
+            line_height: (24.0 * scale).clamp(16.0, 46.0),
+            ..ButtonPreset::Primary.style().text_style
+        };
+
+        self.rectangle_renderer.clear_rectangles();
+        self.icon_renderer.clear_icons();
+        // Dim the title background behind the cards
+        self.rectangle_renderer.add_rectangle(Rectangle::new(
+            0.0,
+            0.0,
+            self.window_size.width as f32,
+            window_height,
+            [0.0, 0.0, 0.0, 0.6],
+        ));
+        let visible = self.grid.visible();
+        let shown = visible.len();
+        for (slot, index) in visible.enumerate() {
+            let entry = &codex.entries[index];
+            let (left, top) = self.layout.slots[slot];
+
+            if self.grid.selected() == Some(index) {
+                let border = 3.0 * scale;
+                self.rectangle_renderer.add_rectangle(
+                    Rectangle::new(
+                        left - border,
+                        top - border,
+                        card_width + 2.0 * border,
+                        card_height + 2.0 * border,
+                        [0.45, 0.8, 0.45, 1.0],
+                    )
+                    .with_corner_radius(12.0 * scale + border),
+                );
+            }
+            self.rectangle_renderer.add_rectangle(
+                Rectangle::new(left, top, card_width, card_height, [0.1, 0.1, 0.12, 0.92])
+                    .with_corner_radius(12.0 * scale),
+            );
+
+            let slug = entry.kind.slug();
+            let texture_id = match self.thumbnails.get(&slug) {
+                Some(thumbnail) if thumbnail.loaded => thumbnail_texture_id(&slug),
+                _ => PLACEHOLDER_TEXTURE_ID.to_string(),
+            };
+            // Icons are placed from the bottom of the window
+            self.icon_renderer.add_icon(Icon::new(
+                left + padding,
+                window_height - top - padding - thumbnail_side,
+                thumbnail_side,
+                thumbnail_side,
+                texture_id,
+            ));
+
+            let id = card_text_id(slot);
+            if slot == self.card_buffers {
+                self.button_manager
+                    .text_renderer
+                    .create_text_buffer(&id, "", None, None);
+                self.card_buffers += 1;
+            }
+            let text_left = left + 2.0 * padding + thumbnail_side;
+            let position = TextPosition {
+                x: text_left,
+                y: top + padding,
+                max_width: Some((left + card_width - padding - text_left).max(0.0)),
+                max_height: Some(card_height - 2.0 * padding),
+            };
+            let text_renderer = &mut self.button_manager.text_renderer;
+            if let Err(e) = text_renderer.update_text_style_and_position(
+                &id,
+                &card_text(entry),
+                text_style.clone(),
+                position,
+            ) {
+                eprintln!("Failed to update codex card: {}", e);
+            }
+            let _ = text_renderer.set_buffer_visibility(&id, true);
+        }
+        for slot in shown..self.card_buffers {
+            let _ = self
+                .button_manager
+                .text_renderer
+                .set_buffer_visibility(&card_text_id(slot), false);
+        }
+
+        self.refresh_headings(codex);
+    }
+
+    /// Places the title, and shows which card is selected or that the codex is empty.
+    ///
+    /// # Arguments
+    ///
+    /// * `codex` - The codex being shown
+    fn refresh_headings(&mut self, codex: &Codex) {
+        let scale = self.layout.scale;
+        let margin = 60.0 * scale;
+        let width = self.window_size.width as f32;
+        let title_style = TextStyle {
+            font_size: (48.0 * scale).clamp(28.0, 96.0),
+            line_height: (58.0 * scale).clamp(34.0, 116.0),
+            weight: Weight::BOLD,
+            ..ButtonPreset::Primary.style().text_style
+        };
+        let status_style = TextStyle {
+            font_size: (20.0 * scale).clamp(14.0, 40.0),
+            line_height: (26.0 * scale).clamp(18.0, 52.0),
+            ..ButtonPreset::Primary.style().text_style
+        };
+        let status = match self.grid.selected() {
+            Some(index) => format!("{} of {}", index + 1, codex.entries.len()),
+            None => "No mazes yet. Finish or lose a level to start the codex.".to_string(),
+        };
+
+        let text_renderer = &mut self.button_manager.text_renderer;
+        let title_position = TextPosition {
+            x: margin,
+            y: margin,
+            max_width: Some((width - 2.0 * margin).max(0.0)),
+            max_height: Some(title_style.line_height),
+        };
+        let _ = text_renderer.update_text_style_and_position(
+            TITLE_TEXT_ID,
+            "Maze Codex",
+            title_style.clone(),
+            title_position,
+        );
+
+        let (_, status_width, _) = text_renderer.measure_text(&status, &status_style);
+        let status_position = TextPosition {
+            x: (width - margin - status_width).max(margin),
+            y: margin + (title_style.line_height - status_style.line_height) / 2.0,
+            max_width: Some(status_width.min(width - 2.0 * margin).max(0.0)),
+            max_height: Some(status_style.line_height),
+        };
+        let _ = text_renderer.update_text_style_and_position(
+            STATUS_TEXT_ID,
+            &status,
+            status_style,
+            status_position,
+        );
+    }
+
+    /// Handles window resize events by laying the grid out again.
+    ///
+    /// # Arguments
+    ///
+    /// * `queue` - The WGPU command queue
+    /// * `resolution` - The new window resolution
+    pub fn resize(&mut self, queue: &Queue, resolution: Resolution) {
+        self.button_manager.resize(queue, resolution);
+        self.window_size = PhysicalSize::new(resolution.width, resolution.height);
+        self.rectangle_renderer
+            .resize(resolution.width as f32, resolution.height as f32);
+        self.icon_renderer
+            .resize(resolution.width as f32, resolution.height as f32);
+        self.layout();
+    }
+
+    /// Prepares the codex menu for rendering by updating text layout.
+    ///
+    /// # Arguments
+    ///
+    /// * `device` - The WGPU device
+    /// * `queue` - The WGPU command queue
+    /// * `surface_config` - The surface configuration
+    ///
+    /// # Returns
+    ///
+    /// `Ok(())` on success, or a `PrepareError` if text preparation fails
+    pub fn prepare(
+        &mut self,
+        device: &Device,
+        queue: &Queue,
+        surface_config: &SurfaceConfiguration,
+    ) -> Result<(), glyphon::PrepareError> {
+        self.button_manager.prepare(device, queue, surface_config)
+    }
+
+    /// Renders the cards, then the back button and all text.
+    ///
+    /// # Arguments
+    ///
+    /// * `device` - The WGPU device
+    /// * `render_pass` - The render pass to draw into
+    ///
+    /// # Returns
+    ///
+    /// `Ok(())` on success, or a `RenderError` if rendering fails
+    pub fn render(
+        &mut self,
+        device: &Device,
+        render_pass: &mut RenderPass,
+    ) -> Result<(), glyphon::RenderError> {
+        if !self.button_manager.window_too_small {
+            self.rectangle_renderer.render(device, render_pass);
+            self.icon_renderer.render(device, render_pass);
+        }
+        self.button_manager.render(device, render_pass)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid_of(columns: usize, rows: usize, count: usize) -> CodexGrid {
+        let mut grid = CodexGrid::default();
+        grid.set_count(count);
+        grid.set_shape(columns, rows);
+        grid
+    }
+
+    #[test]
+    fn test_selection_scrolls_the_grid_and_stops_at_the_ends() {
+        let mut grid = grid_of(3, 2, 20);
+        assert_eq!(grid.visible(), 0..6);
+
+        grid.move_selection(-1);
+        assert_eq!(grid.selected(), Some(0));
+
+        // Moving down a row past the screen scrolls by one row
+        grid.move_selection(3 * 2);
+        assert_eq!(grid.selected(), Some(6));
+        assert_eq!(grid.visible(), 3..9);
+
+        grid.move_selection(grid.page() as isize);
+        assert_eq!(grid.visible(), 9..15);
+
+        // The last rows are partly filled and never scroll past the end
+        grid.move_selection(isize::MAX);
+        assert_eq!(grid.selected(), Some(19));
+        assert_eq!(grid.visible(), 15..20);
+        grid.scroll(5);
+        assert_eq!(grid.visible(), 15..20);
+
+        grid.move_selection(isize::MIN);
+        assert_eq!(grid.selected(), Some(0));
+        assert_eq!(grid.visible(), 0..6);
+
+        // Scrolling leaves the selection where it was
+        grid.scroll(2);
+        assert_eq!((grid.visible(), grid.selected()), (6..12, Some(0)));
+
+        let mut empty = grid_of(3, 2, 0);
+        empty.move_selection(1);
+        assert_eq!((empty.visible(), empty.selected()), (0..0, None));
+    }
+
+    #[test]
+    fn test_hundreds_of_entries_draw_a_capped_number_of_cards() {
+        let mut grid = grid_of(8, 10, 500);
+        assert!(grid.visible().len() <= MAX_VISIBLE_CARDS);
+        assert_eq!(grid.visible().len(), 24);
+
+        grid.select(499);
+        assert!(grid.visible().contains(&499));
+        assert!(grid.visible().len() <= MAX_VISIBLE_CARDS);
+
+        // Shrinking the codex keeps the selection on a card
+        grid.set_count(10);
+        assert_eq!(grid.selected(), Some(9));
+        assert!(grid.visible().contains(&9));
+
+        // A window with no room still shows one card
+        assert_eq!(grid_of(0, 0, 5).visible(), 0..1);
+    }
+
+    #[test]
+    fn test_padding_fits_the_picture_inside_the_icon_circle() {
+        let rgba: Vec<u8> = (0..2 * 3 * 4).map(|i| i as u8 + 1).collect();
+        let (padded, (width, height)) = pad_for_icon(&rgba, (2, 3));
+        assert_eq!(width, height);
+        assert_eq!(padded.len(), (width * height * 4) as usize);
+
+        // Every corner of the picture is inside the inscribed circle
+        let side = width as f32;
+        let (left, top) = ((width - 2) / 2, (height - 3) / 2);
+        for (x, y) in [(0, 0), (2, 0), (0, 3), (2, 3)] {
+            let dx = (left + x) as f32 - side / 2.0;
+            let dy = (top + y) as f32 - side / 2.0;
+            assert!((dx * dx + dy * dy).sqrt() <= side / 2.0);
+        }
+
+        // The picture is copied unchanged, and the rest is transparent
+        let row = |y: u32| ((top + y) * width + left) as usize * 4;
+        assert_eq!(&padded[row(1)..row(1) + 8], &rgba[8..16]);
+        let opaque = padded.chunks(4).filter(|pixel| pixel[3] != 0).count();
+        assert_eq!(opaque, 6);
+
+        let (placeholder, dimensions) = placeholder_thumbnail();
+        assert_eq!(
+            placeholder.len(),
+            (dimensions.0 * dimensions.1 * 4) as usize
+        );
+    }
+}
@@ -0,0 +1,279 @@
+//! Error Menu
+//!
+//! This module provides the screen shown after the frame update panicked: a heading, the panic
+//! message, and a "Return to Title" button that starts over with a fresh game state. It is drawn
+//! over the title background, and the message wraps to fit the window.
+
+use crate::game::audio::GameAudioManager;
+use crate::renderer::gpu_memory::GpuMemoryTracker;
+use crate::renderer::text::{TextPosition, TextStyle};
+use crate::renderer::ui::button::{
+    Button, ButtonAnchor, ButtonManager, ButtonPosition, ButtonPreset, TextAlign,
+};
+use glyphon::{Resolution, Weight};
+use wgpu::{Device, Queue, RenderPass, SurfaceConfiguration};
+use winit::dpi::PhysicalSize;
+use winit::event::WindowEvent;
+
+/// Button ID of the return to title button.
+const RETURN_BUTTON_ID: &str = "error_return_to_title";
+
+/// Text buffer ID of the screen heading.
+const HEADING_TEXT_ID: &str = "error_heading";
+
+/// Text buffer ID of the panic message.
+const MESSAGE_TEXT_ID: &str = "error_message";
+
+/// Heading shown above the panic message.
+const HEADING: &str = "Something went wrong";
+
+/// Actions that can be triggered from the error menu
+#[derive(Debug, Clone, PartialEq)]
+pub enum ErrorAction {
+    /// Go back to the title screen with a fresh game state
+    ReturnToTitle,
+    /// No action has been taken
+    None,
+}
+
+/// The error screen: a heading, the panic message and a button back to the title screen.
+pub struct ErrorMenu {
+    /// Manages the return button; its text renderer also draws the heading and message
+    pub button_manager: ButtonManager,
+    /// The panic message being shown
+    message: String,
+    /// Current window size
+    window_size: PhysicalSize<u32>,
+    /// Whether the error menu is currently visible
+    pub visible: bool,
+    /// The last action that was triggered by the menu
+    pub last_action: ErrorAction,
+}
+
+impl ErrorMenu {
+    /// Creates a new error menu instance.
+    ///
+    /// # Arguments
+    ///
+    /// * `device` - The WGPU device for rendering
+    /// * `queue` - The WGPU command queue
+    /// * `surface_format` - The surface texture format
+    /// * `window_size` - The window size, for sizing calculations
+    /// * `gpu_memory` - Tracker the menu's glyph textures are counted in
+    ///
+    /// # Returns
+    ///
+    /// A new, hidden `ErrorMenu` laid out for the window
+    pub fn new(
+        device: &Device,
+        queue: &Queue,
+        surface_format: wgpu::TextureFormat,
+        window_size: PhysicalSize<u32>,
+        gpu_memory: &GpuMemoryTracker,
+    ) -> Self {
+        let mut button_manager =
+            ButtonManager::new(device, queue, surface_format, window_size, gpu_memory);
+        button_manager.add_button(
+            Button::primary(RETURN_BUTTON_ID, "Return to Title").with_text_align(TextAlign::Center),
+        );
+        let text_renderer = &mut button_manager.text_renderer;
+        text_renderer.create_text_buffer(HEADING_TEXT_ID, HEADING, None, None);
+        text_renderer.create_text_buffer(MESSAGE_TEXT_ID, "", None, None);
+
+        let mut menu = Self {
+            button_manager,
+            message: String::new(),
+            window_size,
+            visible: true,
+            last_action: ErrorAction::None,
+        };
+        menu.layout();
+        menu.hide();
+        menu
+    }
+
+    /// Shows the error menu with a panic message.
+    ///
+    /// # Arguments
+    ///
+    /// * `message` - The message of the panic that was caught
+    pub fn show(&mut self, message: &str) {
+        self.message = message.to_string();
+        self.visible = true;
+        self.last_action = ErrorAction::None;
+        self.set_visible(true);
+        self.layout();
+    }
+
+    /// Hides the error menu.
+    pub fn hide(&mut self) {
+        self.visible = false;
+        self.last_action = ErrorAction::None;
+        self.set_visible(false);
+    }
+
+    /// Shows or hides the button and text.
+    ///
+    /// # Arguments
+    ///
+    /// * `visible` - Whether the menu should be drawn
+    fn set_visible(&mut self, visible: bool) {
+        for button in self.button_manager.buttons.values_mut() {
+            button.set_visible(visible);
+        }
+        for id in [HEADING_TEXT_ID, MESSAGE_TEXT_ID] {
+            let _ = self
+                .button_manager
+                .text_renderer
+                .set_buffer_visibility(id, visible);
+        }
+        self.button_manager.update_button_states();
+    }
+
+    /// Places the heading, the message and the button for the window size.
+    fn layout(&mut self) {
+        let (width, height) = (
+            self.window_size.width as f32,
+            self.window_size.height as f32,
+        );
+        let scale = (height / 1080.0).clamp(0.7, 2.0);
+        let margin = 60.0 * scale;
+        let text_width = (width - 2.0 * margin).clamp(1.0, 1100.0 * scale);
+        let left = (width - text_width) / 2.0;
+
+        let heading_style = TextStyle {
+            font_size: (48.0 * scale).clamp(28.0, 96.0),
+            line_height: (58.0 * scale).clamp(34.0, 116.0),
+            weight: Weight::BOLD,
+            ..ButtonPreset::Primary.style().text_style
+        };
+        let message_style = TextStyle {
+            font_size: (20.0 * scale).clamp(14.0, 40.0),
+            line_height: (26.0 * scale).clamp(18.0, 52.0),
+            ..ButtonPreset::Primary.style().text_style
+        };
+        let heading_top = height * 0.3;
+        let message_top = heading_top + heading_style.line_height + 20.0 * scale;
+        let message_height = (message_style.line_height * 6.0).min(height * 0.3);
+
+        let text_renderer = &mut self.button_manager.text_renderer;
+        let _ = text_renderer.update_text_style_and_position(
+            HEADING_TEXT_ID,
+            HEADING,
+            heading_style.clone(),
+            TextPosition {
+                x: left,
+                y: heading_top,
+                max_width: Some(text_width),
+                max_height: Some(heading_style.line_height),
+            },
+        );
+        let _ = text_renderer.update_text_style_and_position(
+            MESSAGE_TEXT_ID,
+            &self.message,
+            message_style,
+            TextPosition {
+                x: left,
+                y: message_top,
+                max_width: Some(text_width),
+                max_height: Some(message_height),
+            },
+        );
+
+        let button_height = (60.0 * scale).clamp(32.0, 110.0);
+        let mut style = ButtonPreset::Primary.style();
+        style.text_style.font_size = (24.0 * scale).clamp(16.0, 44.0);
+        style.text_style.line_height = (30.0 * scale).clamp(20.0, 56.0);
+        if let Some(button) = self.button_manager.get_button_mut(RETURN_BUTTON_ID) {
+            button.style = style;
+            button.position = ButtonPosition::new(
+                left,
+                message_top + message_height + 30.0 * scale,
+                (280.0 * scale).clamp(180.0, 560.0),
+                button_height,
+            )
+            .with_anchor(ButtonAnchor::TopLeft);
+        }
+        self.button_manager.update_button_positions();
+    }
+
+    /// Handles input events for the error menu.
+    ///
+    /// # Arguments
+    ///
+    /// * `event` - The window event to handle
+    /// * `audio_manager` - The audio manager for playing button click sounds
+    pub fn handle_input(&mut self, event: &WindowEvent, audio_manager: &mut GameAudioManager) {
+        if !self.visible {
+            return;
+        }
+
+        self.button_manager.handle_input(event);
+
+        if self.button_manager.is_button_clicked(RETURN_BUTTON_ID) {
+            self.last_action = ErrorAction::ReturnToTitle;
+            let _ = audio_manager.play_select();
+        }
+    }
+
+    /// Gets the last action that was triggered and resets it to `None`.
+    ///
+    /// # Returns
+    ///
+    /// The last `ErrorAction` that was triggered, or `None` if no action occurred
+    pub fn get_last_action(&mut self) -> ErrorAction {
+        let action = self.last_action.clone();
+        self.last_action = ErrorAction::None;
+        action
+    }
+
+    /// Handles window resize events by laying the menu out again.
+    ///
+    /// # Arguments
+    ///
+    /// * `queue` - The WGPU command queue
+    /// * `resolution` - The new window resolution
+    pub fn resize(&mut self, queue: &Queue, resolution: Resolution) {
+        self.button_manager.resize(queue, resolution);
+        self.window_size = PhysicalSize::new(resolution.width, resolution.height);
+        self.layout();
+    }
+
+    /// Prepares the error menu for rendering by updating text layout.
+    ///
+    /// # Arguments
+    ///
+    /// * `device` - The WGPU device
+    /// * `queue` - The WGPU command queue
+    /// * `surface_config` - The surface configuration
+    ///
+    /// # Returns
+    ///
+    /// `Ok(())` on success, or a `PrepareError` if text preparation fails
+    pub fn prepare(
+        &mut self,
+        device: &Device,
+        queue: &Queue,
+        surface_config: &SurfaceConfiguration,
+    ) -> Result<(), glyphon::PrepareError> {
+        self.button_manager.prepare(device, queue, surface_config)
+    }
+
+    /// Renders the button and all text.
+    ///
+    /// # Arguments
+    ///
+    /// * `device` - The WGPU device
+    /// * `render_pass` - The render pass to draw into
+    ///
+    /// # Returns
+    ///
+    /// `Ok(())` on success, or a `RenderError` if rendering fails
+    pub fn render(
+        &mut self,
+        device: &Device,
+        render_pass: &mut RenderPass,
+    ) -> Result<(), glyphon::RenderError> {
+        self.button_manager.render(device, render_pass)
+    }
+}
@@ -0,0 +1,459 @@
+//! Game Over Menu
+//!
+//! This module provides the buttons shown beneath the "Game Over!" text, letting the
+//! player either retry the level they just lost on the same maze or start a new run, and
+//! the player's marker shown as a badge above the text.
+//!
+//! When a hardcore run dies there is no retry: only "New Run" is shown, and a small skull
+//! marks the badge.
+
+use crate::assets::custom_marker::{MARKER_TEXTURE_ID, MarkerImage, default_marker};
+use crate::game::audio::GameAudioManager;
+use crate::game::input::MenuNav;
+use crate::renderer::gpu_memory::GpuMemoryTracker;
+use crate::renderer::icon::{Icon, IconRenderer};
+use crate::renderer::text::game_over_subtitle_bottom;
+use crate::renderer::ui::button::{
+    Button, ButtonAnchor, ButtonManager, ButtonPosition, ButtonPreset, TextAlign,
+};
+use glyphon::Resolution;
+use wgpu::{Device, Queue, RenderPass, SurfaceConfiguration};
+use winit::dpi::PhysicalSize;
+use winit::event::WindowEvent;
+
+/// Texture ID of the skull drawn on the badge after a hardcore death.
+const SKULL_TEXTURE_ID: &str = "hardcore_skull";
+
+/// The skull as pixel art; `#` is bone, `o` is an eye or the nose, anything else is clear.
+const SKULL_PIXELS: [&str; 11] = [
+    "  #######  ",
+    " ######### ",
+    "###########",
+    "###########",
+    "##ooo#ooo##",
+    "##ooo#ooo##",
+    "#####o#####",
+    " ######### ",
+    "  #######  ",
+    "  # # # #  ",
+    "  #######  ",
+];
+
+/// How many texture pixels each skull pixel covers, so the linear sampler keeps its edges hard.
+const SKULL_UPSCALE: usize = 8;
+
+/// Actions that can be triggered from the game over menu
+#[derive(Debug, Clone, PartialEq)]
+pub enum GameOverAction {
+    /// Restart the lost level without regenerating its maze
+    RetryLevel,
+    /// Start a new run from level 1
+    NewRun,
+    /// No action has been taken
+    None,
+}
+
+/// The buttons and badge shown on the game over screen.
+///
+/// The two buttons sit side by side below the game over text and scale with the
+/// window size the same way the pause menu does. The player's marker sits above the text.
+/// After a hardcore death only the new run button is shown, centered, and a skull is drawn
+/// on the badge's corner.
+pub struct GameOverMenu {
+    /// Manages the game over buttons
+    pub button_manager: ButtonManager,
+    /// Draws the player's marker as a badge above the game over text
+    badge_renderer: IconRenderer,
+    /// Width divided by height of the marker shown in the badge
+    badge_aspect: f32,
+    /// Current window size, used to lay out the badge
+    window_size: PhysicalSize<u32>,
+    /// Whether the screen is showing the end of a hardcore run
+    hardcore: bool,
+    /// Whether the game over menu is currently visible
+    pub visible: bool,
+    /// The last action that was triggered by the menu
+    pub last_action: GameOverAction,
+}
+
+impl GameOverMenu {
+    /// Creates a new game over menu instance.
+    ///
+    /// # Arguments
+    ///
+    /// * `device` - The WGPU device for rendering
+    /// * `queue` - The WGPU command queue
+    /// * `surface_format` - The surface texture format
+    /// * `window_size` - The window size, for sizing calculations
+    /// * `gpu_memory` - Tracker the menu's icon and glyph textures are counted in
+    ///
+    /// # Returns
+    ///
+    /// A new, hidden `GameOverMenu` with its buttons configured and positioned
+    pub fn new(
+        device: &Device,
+        queue: &Queue,
+        surface_format: wgpu::TextureFormat,
+        window_size: PhysicalSize<u32>,
+        gpu_memory: &GpuMemoryTracker,
+    ) -> Self {
+        let mut button_manager =
+            ButtonManager::new(device, queue, surface_format, window_size, gpu_memory);
+        Self::create_menu_buttons(&mut button_manager, window_size);
+        Self::layout_buttons(&mut button_manager, window_size, false);
+
+        let badge_renderer = IconRenderer::new(device, queue, surface_format, gpu_memory);
+
+        let mut menu = Self {
+            button_manager,
+            badge_renderer,
+            badge_aspect: 1.0,
+            window_size,
+            hardcore: false,
+            visible: true,
+            last_action: GameOverAction::None,
+        };
+        let (skull, skull_dimensions) = skull_texture();
+        if let Err(e) = menu.badge_renderer.register_texture(
+            device,
+            queue,
+            SKULL_TEXTURE_ID,
+            &skull,
+            skull_dimensions,
+        ) {
+            eprintln!("Failed to upload hardcore skull: {}", e);
+        }
+        menu.set_badge(device, queue, &default_marker());
+        menu.hide();
+        menu
+    }
+
+    /// Replaces the marker shown in the badge.
+    ///
+    /// # Arguments
+    ///
+    /// * `device` - The WGPU device for creating the texture
+    /// * `queue` - The WGPU command queue for uploading the pixels
+    /// * `marker` - The player's custom marker, or the default one
+    pub fn set_badge(&mut self, device: &Device, queue: &Queue, marker: &MarkerImage) {
+        if let Err(e) = self.badge_renderer.register_texture(
+            device,
+            queue,
+            MARKER_TEXTURE_ID,
+            &marker.rgba,
+            marker.dimensions,
+        ) {
+            eprintln!("Failed to upload player marker: {}", e);
+            return;
+        }
+        let (width, height) = marker.dimensions;
+        self.badge_aspect = width as f32 / height.max(1) as f32;
+        self.layout_badge();
+    }
+
+    /// Switches between the regular game over screen and the hardcore one.
+    ///
+    /// The hardcore screen hides the retry button, since a hardcore run can't retry a
+    /// level, and marks the badge with a skull.
+    ///
+    /// # Arguments
+    ///
+    /// * `hardcore` - Whether the run that just ended was hardcore
+    pub fn set_hardcore(&mut self, hardcore: bool) {
+        if self.hardcore == hardcore {
+            return;
+        }
+        self.hardcore = hardcore;
+        Self::layout_buttons(&mut self.button_manager, self.window_size, hardcore);
+        self.layout_badge();
+        if self.visible {
+            self.show();
+        }
+    }
+
+    /// Sizes and positions the badge above the game over text for the current window size.
+    fn layout_badge(&mut self) {
+        let (width, height) = (
+            self.window_size.width as f32,
+            self.window_size.height as f32,
+        );
+        let scale = (height / 1080.0).clamp(0.7, 2.0);
+
+        // Fit the marker in a square, keeping its shape
+        let side = 96.0 * scale;
+        let badge_width = side * self.badge_aspect.min(1.0);
+        let badge_height = side * (1.0 / self.badge_aspect).min(1.0);
+        // Clear of the "Game Over!" title, whose top is about 95 pixels above the center at 1080p.
+        // Icons are placed from the bottom of the window, so this is the badge's bottom edge
+        // measured upward.
+        let bottom = height / 2.0 + 110.0 * scale;
+
+        self.badge_renderer.resize(width, height);
+        self.badge_renderer.clear_icons();
+        self.badge_renderer.add_icon(Icon::new(
+            (width - badge_width) / 2.0,
+            bottom + (side - badge_height) / 2.0,
+            badge_width,
+            badge_height,
+            MARKER_TEXTURE_ID.to_string(),
+        ));
+        if self.hardcore {
+            // Over the badge's lower right corner
+            let skull_side = 40.0 * scale;
+            self.badge_renderer.add_icon(Icon::new(
+                (width + side) / 2.0 - skull_side * 0.6,
+                bottom - skull_side * 0.2,
+                skull_side,
+                skull_side,
+                SKULL_TEXTURE_ID.to_string(),
+            ));
+        }
+    }
+
+    /// Creates a scaled text style based on the window height.
+    ///
+    /// # Arguments
+    ///
+    /// * `window_height` - The current window height in pixels
+    ///
+    /// # Returns
+    ///
+    /// A `TextStyle` with font size and line height scaled from 1080p
+    fn scaled_text_style(window_height: f32) -> crate::renderer::text::TextStyle {
+        let reference_height = 1080.0;
+        let scale = (window_height / reference_height).clamp(0.7, 2.0);
+
+        crate::renderer::text::TextStyle {
+            font_size: (28.0 * scale).clamp(16.0, 44.0),
+            line_height: (36.0 * scale).clamp(24.0, 56.0),
+            ..ButtonPreset::Primary.style().text_style
+        }
+    }
+
+    /// Adds the retry and new run buttons to the button manager.
+    ///
+    /// # Arguments
+    ///
+    /// * `button_manager` - The button manager to add buttons to
+    /// * `window_size` - The current window size, used to scale the button text
+    fn create_menu_buttons(button_manager: &mut ButtonManager, window_size: PhysicalSize<u32>) {
+        let text_style = Self::scaled_text_style(window_size.height as f32);
+
+        // Retry Level button - Restarts the lost level on the same maze
+        let retry_button = Button::primary("game_over_retry_level", "Retry Level")
+            .with_text_style(text_style.clone())
+            .with_text_align(TextAlign::Center);
+
+        // New Run button - Starts over from level 1
+        let new_run_button = Button::secondary("game_over_new_run", "New Run")
+            .with_text_style(text_style)
+            .with_text_align(TextAlign::Center);
+
+        button_manager.add_button(retry_button);
+        button_manager.add_button(new_run_button);
+    }
+
+    /// Sizes and positions the buttons for the given window size.
+    ///
+    /// # Arguments
+    ///
+    /// * `button_manager` - The button manager holding the game over buttons
+    /// * `window_size` - The current window size for positioning calculations
+    /// * `hardcore` - Whether only the new run button is shown, centered on its own
+    fn layout_buttons(
+        button_manager: &mut ButtonManager,
+        window_size: PhysicalSize<u32>,
+        hardcore: bool,
+    ) {
+        let reference_height = 1080.0;
+        let scale = (window_size.height as f32 / reference_height).clamp(0.7, 2.0);
+
+        // Sized like the pause menu buttons, but narrower so two fit side by side
+        let button_width = (window_size.width as f32 * 0.18 * scale).clamp(160.0, 360.0);
+        let button_height = (window_size.height as f32 * 0.07 * scale).clamp(32.0, 110.0);
+        let button_gap = (24.0 * scale).clamp(12.0, 48.0);
+        let center_x = window_size.width as f32 / 2.0;
+        // Below the "Game Over!" title and its subtitle
+        let subtitle_bottom =
+            game_over_subtitle_bottom(window_size.width as f32, window_size.height as f32);
+        let center_y = subtitle_bottom + 20.0 * scale + button_height / 2.0;
+
+        let text_style = Self::scaled_text_style(window_size.height as f32);
+
+        let offset = if hardcore {
+            0.0
+        } else {
+            (button_width + button_gap) / 2.0
+        };
+        for (id, x) in [
+            ("game_over_retry_level", center_x - offset),
+            ("game_over_new_run", center_x + offset),
+        ] {
+            if let Some(button) = button_manager.get_button_mut(id) {
+                button.style.text_style = text_style.clone();
+                button.position = ButtonPosition::new(x, center_y, button_width, button_height)
+                    .with_anchor(ButtonAnchor::Center);
+            }
+        }
+
+        // Update text positions after all changes
+        button_manager.update_button_positions();
+    }
+
+    /// Shows the game over menu and makes its buttons visible.
+    pub fn show(&mut self) {
+        self.visible = true;
+        self.last_action = GameOverAction::None;
+
+        for (id, button) in self.button_manager.buttons.iter_mut() {
+            button.set_visible(!self.hardcore || id != "game_over_retry_level");
+        }
+        self.button_manager.update_button_states();
+    }
+
+    /// Hides the game over menu and makes its buttons invisible.
+    pub fn hide(&mut self) {
+        self.visible = false;
+        self.last_action = GameOverAction::None;
+
+        for button in self.button_manager.buttons.values_mut() {
+            button.set_visible(false);
+        }
+    }
+
+    /// Returns whether the game over menu is currently visible.
+    pub fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    /// Handles input events for the game over menu.
+    ///
+    /// # Arguments
+    ///
+    /// * `event` - The window event to handle
+    /// * `audio_manager` - The audio manager for playing button click sounds
+    pub fn handle_input(&mut self, event: &WindowEvent, audio_manager: &mut GameAudioManager) {
+        if !self.visible {
+            return;
+        }
+
+        self.button_manager.handle_input(event);
+        self.take_clicks(audio_manager);
+    }
+
+    /// Takes a gamepad's step through the game over menu's buttons.
+    ///
+    /// # Arguments
+    ///
+    /// * `nav` - The step the gamepad made
+    /// * `audio_manager` - The audio manager for playing button click sounds
+    pub fn navigate(&mut self, nav: MenuNav, audio_manager: &mut GameAudioManager) {
+        if !self.visible {
+            return;
+        }
+        self.button_manager.navigate(nav);
+        self.take_clicks(audio_manager);
+    }
+
+    /// Turns a click on one of the buttons, by mouse or gamepad, into the menu's action.
+    fn take_clicks(&mut self, audio_manager: &mut GameAudioManager) {
+        if self
+            .button_manager
+            .is_button_clicked("game_over_retry_level")
+        {
+            self.last_action = GameOverAction::RetryLevel;
+            let _ = audio_manager.play_select();
+        }
+
+        if self.button_manager.is_button_clicked("game_over_new_run") {
+            self.last_action = GameOverAction::NewRun;
+            let _ = audio_manager.play_select();
+        }
+    }
+
+    /// Gets the last action that was triggered and resets it to `None`.
+    ///
+    /// # Returns
+    ///
+    /// The last `GameOverAction` that was triggered, or `None` if no action occurred
+    pub fn get_last_action(&mut self) -> GameOverAction {
+        let action = self.last_action.clone();
+        self.last_action = GameOverAction::None;
+        action
+    }
+
+    /// Handles window resize events by updating button positions and text rendering.
+    ///
+    /// # Arguments
+    ///
+    /// * `queue` - The WGPU command queue
+    /// * `resolution` - The new window resolution
+    pub fn resize(&mut self, queue: &Queue, resolution: Resolution) {
+        self.button_manager.resize(queue, resolution);
+        self.window_size = PhysicalSize::new(resolution.width, resolution.height);
+        Self::layout_buttons(&mut self.button_manager, self.window_size, self.hardcore);
+        self.layout_badge();
+    }
+
+    /// Prepares the game over menu for rendering by updating text layout.
+    ///
+    /// # Arguments
+    ///
+    /// * `device` - The WGPU device
+    /// * `queue` - The WGPU command queue
+    /// * `surface_config` - The surface configuration
+    ///
+    /// # Returns
+    ///
+    /// `Ok(())` on success, or a `PrepareError` if text preparation fails
+    pub fn prepare(
+        &mut self,
+        device: &Device,
+        queue: &Queue,
+        surface_config: &SurfaceConfiguration,
+    ) -> Result<(), glyphon::PrepareError> {
+        self.button_manager.prepare(device, queue, surface_config)
+    }
+
+    /// Renders the game over menu and badge to the screen.
+    ///
+    /// # Arguments
+    ///
+    /// * `device` - The WGPU device
+    /// * `render_pass` - The render pass to draw into
+    ///
+    /// # Returns
+    ///
+    /// `Ok(())` on success, or a `RenderError` if rendering fails
+    pub fn render(
+        &mut self,
+        device: &Device,
+        render_pass: &mut RenderPass,
+    ) -> Result<(), glyphon::RenderError> {
+        self.badge_renderer.render(device, render_pass);
+        self.button_manager.render(device, render_pass)
+    }
+}
+
+/// Builds the hardcore skull texture from [`SKULL_PIXELS`].
+///
+/// # Returns
+///
+/// The RGBA pixels and their dimensions
+fn skull_texture() -> (Vec<u8>, (u32, u32)) {
+    let rows = SKULL_PIXELS.len() * SKULL_UPSCALE;
+    let cols = SKULL_PIXELS[0].len() * SKULL_UPSCALE;
+    let mut rgba = Vec::with_capacity(rows * cols * 4);
+    for y in 0..rows {
+        let row = SKULL_PIXELS[y / SKULL_UPSCALE].as_bytes();
+        for x in 0..cols {
+            let pixel = match row[x / SKULL_UPSCALE] {
+                b'#' => [235, 228, 210, 255],
+                b'o' => [25, 20, 25, 255],
+                _ => [0, 0, 0, 0],
+            };
+            rgba.extend_from_slice(&pixel);
+        }
+    }
+    (rgba, (cols as u32, rows as u32))
+}
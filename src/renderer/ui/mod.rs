@@ -2,10 +2,45 @@
 //!
 //! This module provides UI components and integration for the Mirador game, including:
 //!
-//! - `sliders`: UI controls for adjusting game parameters (such as colors, FOV, etc.).
+//! - `announcement_overlay`: The accessibility announcer's extra-large line at the bottom of the HUD
+//! - `audio_debug_overlay`: The audio debug table of playing sources and recent audio events
+//! - `button`: Buttons and the shared button manager used by the menus
+//! - `codex_menu`: The maze codex screen, reached from the title screen
+//! - `error_menu`: The error screen shown after the game recovers from a panic
+//! - `game_over_menu`: The retry and new run buttons on the game over screen
+//! - `pause_menu`: The pause menu overlay
+//! - `profile_menu`: The profile picker, reached from the title screen
+//! - `quick_radial_menu`: The quick-settings ring shown while its key is held
+//! - `sandbox_panel`: The practice sandbox's maze settings, driven from the keyboard
+//! - `title_menu`: The daily challenge button on the title screen
+//! - `toast_overlay`: The stack of toast notifications in the corner of the HUD
+//! - `tuning_panel`: Debug panel for adjusting renderer parameters (colors, fog, FOV, etc.)
+//! - `upgrade_menu`: The upgrade selection overlay
+/// Accessibility announcement line.
+pub mod announcement_overlay;
+/// Audio debug overlay.
+pub mod audio_debug_overlay;
 /// Button UI components and utilities.
 pub mod button;
+/// Maze codex screen UI components.
+pub mod codex_menu;
+/// Error screen UI components.
+pub mod error_menu;
+/// Game over menu UI components.
+pub mod game_over_menu;
 /// Pause menu UI components.
 pub mod pause_menu;
+/// Profile picker UI components.
+pub mod profile_menu;
+/// Quick-settings radial menu UI components.
+pub mod quick_radial_menu;
+/// Practice sandbox settings panel.
+pub mod sandbox_panel;
+/// Title screen UI components.
+pub mod title_menu;
+/// Toast notification overlay.
+pub mod toast_overlay;
+/// Debug renderer tuning panel.
+pub mod tuning_panel;
 /// Upgrade menu UI components.
 pub mod upgrade_menu;
@@ -1,19 +1,78 @@
+use crate::app::frame_limiter::FpsCap;
+use crate::app::settings_registry::{self, Capabilities, SettingDescriptor};
+use crate::game::anomaly::Cosmetic;
 use crate::game::audio::GameAudioManager;
+use crate::game::bindings::{self, Action, BindingContext, Conflict, InputRouter};
+use crate::game::input::MenuNav;
+use crate::game::seed::SeedField;
+use crate::game::spatial_audio::OutputProfile;
+use crate::game::time_of_day::TimeOfDayPin;
+use crate::renderer::game_renderer::panorama::BackgroundMode;
+use crate::renderer::gpu_memory::GpuMemoryTracker;
+use crate::renderer::present_mode::PresentModePreference;
+use crate::renderer::text::{TextPosition, TextStyle};
 use crate::renderer::ui::button::{
-    Button, ButtonAnchor, ButtonManager, ButtonPosition, TextAlign, create_danger_button_style,
-    create_primary_button_style, create_warning_button_style,
+    Button, ButtonAnchor, ButtonManager, ButtonPosition, ButtonPreset, TextAlign,
 };
 use glyphon::Resolution;
 use wgpu::{Device, Queue, RenderPass, SurfaceConfiguration};
 use winit::dpi::PhysicalSize;
-use winit::event::WindowEvent;
-use winit::window::Window;
+use winit::event::{ElementState, KeyEvent, MouseButton, WindowEvent};
+use winit::keyboard::{Key, KeyCode, PhysicalKey};
+
+/// Gap in pixels between the small buttons in the bottom-left corner
+const CORNER_BUTTON_GAP: f32 = 12.0;
+
+/// Label of a cosmetic button while its cosmetic is still locked
+const LOCKED_COSMETIC_LABEL: &str = "Found\nin the\nmaze...";
+
+/// Text buffer ID of the note explaining why the custom marker was not used
+const MARKER_NOTE_TEXT_ID: &str = "pause_marker_note";
+
+/// Button ID of the settings search field
+const FILTER_BUTTON_ID: &str = "pause_settings_filter";
+
+/// Most characters the settings search holds
+const FILTER_MAX_LENGTH: usize = 24;
+
+/// Button ID of the button that puts every setting back to its default
+const RESTORE_DEFAULTS_ID: &str = "pause_restore_defaults";
+
+/// Label of the restore defaults button
+const RESTORE_DEFAULTS_LABEL: &str = "Restore\nDefaults";
+
+/// Label of the restore defaults button while it waits for a second click
+const RESTORE_DEFAULTS_CONFIRM_LABEL: &str = "Click\nagain to\nconfirm";
+
+/// Button ID of the button that opens the controls page
+const CONTROLS_BUTTON_ID: &str = "pause_controls";
+
+/// Start of the ID of every button on the controls page
+const CONTROLS_PAGE_PREFIX: &str = "pause_controls_";
+
+/// Button ID of the button that leaves the controls page
+const CONTROLS_BACK_ID: &str = "pause_controls_back";
+
+/// Buttons that change a setting, with the ID of the setting in the settings registry;
+/// every cosmetic button changes the `cosmetics` setting
+const SETTING_BUTTONS: [(&str, &str); 8] = [
+    ("pause_menu_fps", "menu_fps_cap"),
+    ("pause_gameplay_fps", "gameplay_fps_cap"),
+    ("pause_present_mode", "present_mode"),
+    ("pause_background", "background"),
+    ("pause_ambience", "ambience_volume"),
+    ("pause_time_of_day", "time_of_day"),
+    ("pause_audio_output", "audio_output"),
+    ("pause_hrtf", "hrtf"),
+];
 
 /// Actions that can be triggered from the pause menu
 #[derive(Debug, Clone, PartialEq)]
 pub enum PauseMenuAction {
     /// Resume the current game
     Resume,
+    /// Restart the current level without regenerating its maze
+    RetryLevel,
     /// Restart the current run/game
     Restart,
     /// Quit to the main menu/lobby
@@ -22,6 +81,34 @@ pub enum PauseMenuAction {
     QuitApp,
     /// Toggle test mode on/off
     ToggleTestMode,
+    /// Cycle the frame rate cap used on menu screens
+    CycleMenuFpsCap,
+    /// Cycle the frame rate cap used during gameplay
+    CycleGameplayFpsCap,
+    /// Switch vsync on or off, or to the next way of presenting frames without it
+    CyclePresentMode,
+    /// Load the custom player marker from the data directory again
+    ReloadMarker,
+    /// Switch between the starfield and the sky panorama background
+    CycleBackground,
+    /// Turn the ambience volume up a step, wrapping from full to off
+    CycleAmbienceVolume,
+    /// Pin the scene's time of day to the next hour, or let it follow the run again
+    CycleTimeOfDay,
+    /// Switch to the next audio output profile
+    CycleAudioOutput,
+    /// Switch HRTF on or off
+    ToggleHrtf,
+    /// Play a sound circling the player to check how enemies will sound
+    PlayAudioTest,
+    /// Switch an unlocked cosmetic on or off
+    ToggleCosmetic(Cosmetic),
+    /// Put one setting, named by its settings registry ID, back to its default
+    ResetSetting(&'static str),
+    /// Put every setting back to its default, after a confirming second click
+    RestoreDefaults,
+    /// Move a rebindable action of a context to the key pressed on the controls page
+    Rebind(BindingContext, Action, KeyCode),
     /// No action has been taken
     None,
 }
@@ -30,11 +117,25 @@ pub enum PauseMenuAction {
 ///
 /// The pause menu provides several options to the player:
 /// - Resume the game
+/// - Retry the current level
 /// - Restart the current run
 /// - Toggle test mode
 /// - Quit to lobby
 /// - Quit the application
 /// - Toggle debug panel visibility
+/// - Cycle the menu and gameplay frame rate caps
+/// - Switch vsync on or off
+/// - Reload the custom player marker, with a note when it could not be used
+/// - Switch between the starfield and the sky panorama background
+/// - Step the ambience volume
+/// - Pin the time of day or let it follow the run
+/// - Switch unlocked cosmetics on or off; locked ones are greyed out with a hint
+/// - Search the setting buttons: clicking the search field or pressing `/` opens it, and
+///   buttons whose setting doesn't match what is typed are hidden
+/// - Right-click a setting button to put that setting back to its default
+/// - Restore every setting to its default, clicking a second time to confirm
+/// - Open the controls page, where clicking an action and pressing a key moves the action to
+///   that key; an action sharing its key with another of its context is flagged on its button
 ///
 /// The menu automatically scales its buttons and text based on the window size
 /// to maintain consistent appearance across different resolutions.
@@ -47,6 +148,18 @@ pub struct PauseMenu {
     pub last_action: PauseMenuAction,
     /// Whether the debug panel should be shown
     pub show_debug_panel: bool,
+    /// The settings search
+    filter: SeedField,
+    /// Whether the settings search is open for typing
+    editing_filter: bool,
+    /// Whether the restore defaults button was clicked once and waits for a second click
+    confirming_restore: bool,
+    /// Whether the controls page is shown in place of the menu
+    showing_controls: bool,
+    /// Action on the controls page waiting for a key press to be bound to it, with its context
+    capturing: Option<(BindingContext, Action)>,
+    /// Key shown for each rebindable action, in [`bindings::REBINDABLE`] order
+    binding_labels: Vec<String>,
 }
 
 impl PauseMenu {
@@ -57,7 +170,8 @@ impl PauseMenu {
     /// * `device` - The WGPU device for rendering
     /// * `queue` - The WGPU command queue
     /// * `surface_format` - The surface texture format
-    /// * `window` - The window reference for sizing calculations
+    /// * `window_size` - The window size, for sizing calculations
+    /// * `gpu_memory` - Tracker the menu's icon and glyph textures are counted in
     ///
     /// # Returns
     ///
@@ -66,19 +180,48 @@ impl PauseMenu {
         device: &Device,
         queue: &Queue,
         surface_format: wgpu::TextureFormat,
-        window: &Window,
+        window_size: PhysicalSize<u32>,
+        gpu_memory: &GpuMemoryTracker,
     ) -> Self {
-        let mut button_manager = ButtonManager::new(device, queue, surface_format, window);
+        let mut button_manager =
+            ButtonManager::new(device, queue, surface_format, window_size, gpu_memory);
 
         // Create pause menu buttons with proper scaling and positioning
-        Self::create_menu_buttons(&mut button_manager, window.inner_size());
+        Self::create_menu_buttons(&mut button_manager, window_size);
+
+        // Hidden until a custom marker is rejected
+        button_manager.text_renderer.create_text_buffer(
+            MARKER_NOTE_TEXT_ID,
+            "",
+            Some(TextStyle {
+                font_size: 16.0,
+                line_height: 20.0,
+                ..ButtonPreset::Secondary.style().text_style
+            }),
+            None,
+        );
+        let _ = button_manager
+            .text_renderer
+            .set_buffer_visibility(MARKER_NOTE_TEXT_ID, false);
 
-        Self {
+        let mut menu = Self {
             button_manager,
             visible: false,
             last_action: PauseMenuAction::None,
             show_debug_panel: false,
-        }
+            filter: Self::empty_filter(),
+            editing_filter: false,
+            confirming_restore: false,
+            showing_controls: false,
+            capturing: None,
+            binding_labels: Vec::new(),
+        };
+        menu.layout_corner_buttons();
+        menu.layout_controls_page();
+        menu.update_binding_buttons(&InputRouter::new());
+        menu.update_filter_text();
+        menu.layout_marker_note();
+        menu
     }
 
     /// Creates a scaled text style based on the window height.
@@ -104,9 +247,7 @@ impl PauseMenu {
             font_family: "Hanken Grotesk".to_string(),
             font_size,
             line_height,
-            color: crate::renderer::ui::button::create_primary_button_style()
-                .text_style
-                .color,
+            color: ButtonPreset::Primary.style().text_style.color,
             weight: glyphon::Weight::MEDIUM,
             style: glyphon::Style::Normal,
         }
@@ -129,7 +270,7 @@ impl PauseMenu {
         let button_width = (window_size.width as f32 * 0.38 * scale).clamp(180.0, 600.0);
         let button_height = (window_size.height as f32 * 0.09 * scale).clamp(32.0, 140.0);
         let button_spacing = (window_size.height as f32 * 0.015 * scale).clamp(2.0, 24.0);
-        let total_height = button_height * 5.0 + button_spacing * 4.0;
+        let total_height = button_height * 6.0 + button_spacing * 5.0;
         let center_x = window_size.width as f32 / 2.0;
         let start_y = (window_size.height as f32 - total_height) / 2.0;
         let text_style = Self::scaled_text_style(window_size.height as f32);
@@ -139,62 +280,61 @@ impl PauseMenu {
             |i: usize| start_y + button_height / 2.0 + i as f32 * (button_height + button_spacing);
 
         // Resume button - Primary action to continue the game
-        let mut resume_style = create_primary_button_style();
-        resume_style.text_style = text_style.clone();
-        let resume_button = Button::new("pause_resume", "Resume Game")
-            .with_style(resume_style)
+        let resume_button = Button::primary("pause_resume", "Resume Game")
+            .with_text_style(text_style.clone())
             .with_text_align(TextAlign::Center)
             .with_position(
                 ButtonPosition::new(center_x, y(0), button_width, button_height)
                     .with_anchor(ButtonAnchor::Center),
             );
 
-        // Restart Run button - Restarts the current game session
-        let mut restart_run_style = create_warning_button_style();
-        restart_run_style.text_style = text_style.clone();
-        let restart_run_button = Button::new("pause_restart_run", "Restart Run")
-            .with_style(restart_run_style)
+        // Retry Level button - Restarts the current level on the same maze
+        let retry_level_button = Button::primary("pause_retry_level", "Retry Level")
+            .with_text_style(text_style.clone())
             .with_text_align(TextAlign::Center)
             .with_position(
                 ButtonPosition::new(center_x, y(1), button_width, button_height)
                     .with_anchor(ButtonAnchor::Center),
             );
 
-        // Toggle Test Mode button - Switches between normal and test modes
-        let mut test_mode_style = create_warning_button_style();
-        test_mode_style.text_style = text_style.clone();
-        let test_mode_button = Button::new("pause_toggle_test_mode", "Toggle Test Mode")
-            .with_style(test_mode_style)
+        // Restart Run button - Restarts the current game session
+        let restart_run_button = Button::secondary("pause_restart_run", "Restart Run")
+            .with_text_style(text_style.clone())
             .with_text_align(TextAlign::Center)
             .with_position(
                 ButtonPosition::new(center_x, y(2), button_width, button_height)
                     .with_anchor(ButtonAnchor::Center),
             );
 
-        // Quit to Lobby button - Returns to the main lobby/menu
-        let mut quit_lobby_style = create_danger_button_style();
-        quit_lobby_style.text_style = text_style.clone();
-        let quit_lobby_button = Button::new("pause_quit_lobby", "Quit to Lobby")
-            .with_style(quit_lobby_style)
+        // Toggle Test Mode button - Switches between normal and test modes
+        let test_mode_button = Button::secondary("pause_toggle_test_mode", "Toggle Test Mode")
+            .with_text_style(text_style.clone())
             .with_text_align(TextAlign::Center)
             .with_position(
                 ButtonPosition::new(center_x, y(3), button_width, button_height)
                     .with_anchor(ButtonAnchor::Center),
             );
 
-        // Quit App button - Exits the entire application
-        let mut quit_style = create_danger_button_style();
-        quit_style.text_style = text_style.clone();
-        let quit_menu_button = Button::new("pause_quit_menu", "Quit App")
-            .with_style(quit_style)
+        // Quit to Lobby button - Returns to the main lobby/menu
+        let quit_lobby_button = Button::destructive("pause_quit_lobby", "Quit to Lobby")
+            .with_text_style(text_style.clone())
             .with_text_align(TextAlign::Center)
             .with_position(
                 ButtonPosition::new(center_x, y(4), button_width, button_height)
                     .with_anchor(ButtonAnchor::Center),
             );
 
+        // Quit App button - Exits the entire application
+        let quit_menu_button = Button::destructive("pause_quit_menu", "Quit App")
+            .with_text_style(text_style.clone())
+            .with_text_align(TextAlign::Center)
+            .with_position(
+                ButtonPosition::new(center_x, y(5), button_width, button_height)
+                    .with_anchor(ButtonAnchor::Center),
+            );
+
         // Debug button - Small button in bottom-left corner to toggle debug info
-        let mut debug_style = create_warning_button_style();
+        let mut debug_style = ButtonPreset::Secondary.style();
         debug_style.text_style.font_size = text_style.font_size * 0.5;
         debug_style.text_style.line_height = text_style.line_height * 0.5;
         debug_style.padding = (2.0 * scale, 6.0 * scale); // minimal horizontal, some vertical padding
@@ -206,7 +346,7 @@ impl PauseMenu {
             .measure_text(" Show\nDebug\n  Info", &debug_style.text_style);
         let debug_button_side = text_width.max(text_height) + 2.0 * debug_style.padding.1;
         let debug_button = Button::new("pause_debug", " Show\nDebug\n  Info")
-            .with_style(debug_style)
+            .with_style(debug_style.clone())
             .with_text_align(TextAlign::Center)
             .with_position(ButtonPosition {
                 x: 60.0,
@@ -216,13 +356,108 @@ impl PauseMenu {
                 anchor: ButtonAnchor::TopLeft,
             });
 
+        // Frame rate cap, marker, background, and cosmetic buttons - Same size as the debug
+        // button, placed to its right
+        let corner_button = |id: &str, text: &str, index: usize| {
+            Button::new(id, text)
+                .with_style(debug_style.clone())
+                .with_text_align(TextAlign::Center)
+                .with_position(ButtonPosition {
+                    x: 60.0 + index as f32 * (debug_button_side + CORNER_BUTTON_GAP),
+                    y: window_size.height as f32 - debug_button_side - 16.0,
+                    width: debug_button_side,
+                    height: debug_button_side,
+                    anchor: ButtonAnchor::TopLeft,
+                })
+        };
+        let menu_fps_button = corner_button(
+            "pause_menu_fps",
+            &Self::fps_cap_label("Menu", FpsCap::Fps60),
+            1,
+        );
+        let gameplay_fps_button = corner_button(
+            "pause_gameplay_fps",
+            &Self::fps_cap_label("Game", FpsCap::Off),
+            2,
+        );
+        let present_mode_button = corner_button(
+            "pause_present_mode",
+            &Self::present_mode_label(PresentModePreference::default()),
+            3,
+        );
+        let reload_marker_button = corner_button("pause_reload_marker", "Reload\nMarker", 4);
+        let background_button = corner_button(
+            "pause_background",
+            &Self::background_label(BackgroundMode::default()),
+            5,
+        );
+        let ambience_button = corner_button("pause_ambience", &Self::ambience_label(1.0), 6);
+        let time_of_day_button = corner_button(
+            "pause_time_of_day",
+            &Self::time_of_day_label(TimeOfDayPin::default()),
+            7,
+        );
+        let audio_output_button = corner_button(
+            "pause_audio_output",
+            &Self::audio_output_label(OutputProfile::default()),
+            8,
+        );
+        let hrtf_button = corner_button("pause_hrtf", &Self::hrtf_label(true, false), 9);
+        let audio_test_button = corner_button("pause_audio_test", "Audio\nTest", 10);
+        let controls_button = corner_button(CONTROLS_BUTTON_ID, "Rebind\nKeys", 11);
+        let restore_button = corner_button(
+            RESTORE_DEFAULTS_ID,
+            RESTORE_DEFAULTS_LABEL,
+            12 + Cosmetic::ALL.len(),
+        );
+
+        // Settings search - A wide field above the small buttons, placed with them
+        let filter_button = Button::new(FILTER_BUTTON_ID, "")
+            .with_style(debug_style.clone())
+            .with_text_align(TextAlign::Left);
+
         // Add all buttons to the button manager
         button_manager.add_button(resume_button);
+        button_manager.add_button(retry_level_button);
         button_manager.add_button(restart_run_button);
         button_manager.add_button(test_mode_button);
         button_manager.add_button(quit_lobby_button);
         button_manager.add_button(quit_menu_button);
         button_manager.add_button(debug_button);
+        button_manager.add_button(menu_fps_button);
+        button_manager.add_button(gameplay_fps_button);
+        button_manager.add_button(present_mode_button);
+        button_manager.add_button(reload_marker_button);
+        button_manager.add_button(background_button);
+        button_manager.add_button(ambience_button);
+        button_manager.add_button(time_of_day_button);
+        button_manager.add_button(audio_output_button);
+        button_manager.add_button(hrtf_button);
+        button_manager.add_button(audio_test_button);
+        button_manager.add_button(controls_button);
+        for (index, cosmetic) in Cosmetic::ALL.into_iter().enumerate() {
+            let mut button = corner_button(
+                &Self::cosmetic_button_id(cosmetic),
+                LOCKED_COSMETIC_LABEL,
+                12 + index,
+            );
+            button.enabled = false;
+            button_manager.add_button(button);
+        }
+        button_manager.add_button(restore_button);
+        button_manager.add_button(filter_button);
+
+        // Controls page - One button per rebindable action and a way back, placed and labelled
+        // once the menu exists
+        for (_, action) in bindings::REBINDABLE {
+            button_manager.add_button(
+                Button::secondary(&Self::binding_button_id(action), action.label())
+                    .with_text_align(TextAlign::Center),
+            );
+        }
+        button_manager.add_button(
+            Button::primary(CONTROLS_BACK_ID, "Back").with_text_align(TextAlign::Center),
+        );
 
         // Update button positions to ensure text is properly centered
         button_manager.update_button_positions();
@@ -238,10 +473,8 @@ impl PauseMenu {
         self.visible = true;
         self.last_action = PauseMenuAction::None;
 
-        // Make all buttons visible
-        for button in self.button_manager.buttons.values_mut() {
-            button.set_visible(true);
-        }
+        // Make the buttons of the page on show visible
+        self.show_page();
 
         // Ensure button text is made visible and styled immediately
         self.button_manager.update_button_states();
@@ -254,6 +487,13 @@ impl PauseMenu {
     pub fn hide(&mut self) {
         self.visible = false;
         self.last_action = PauseMenuAction::None;
+        self.filter = Self::empty_filter();
+        self.editing_filter = false;
+        self.set_confirming_restore(false);
+        self.showing_controls = false;
+        if self.capturing.take().is_some() {
+            self.update_binding_labels();
+        }
 
         // Hide all buttons
         for button in self.button_manager.buttons.values_mut() {
@@ -261,20 +501,6 @@ impl PauseMenu {
         }
     }
 
-    /// Toggles the pause menu visibility.
-    ///
-    /// # Arguments
-    ///
-    /// * `is_test_mode` - Whether the game is currently in test mode,
-    ///   used when showing the menu to update button text
-    pub fn toggle(&mut self, is_test_mode: bool) {
-        if self.visible {
-            self.hide();
-        } else {
-            self.show(is_test_mode);
-        }
-    }
-
     /// Returns whether the pause menu is currently visible.
     ///
     /// # Returns
@@ -300,12 +526,105 @@ impl PauseMenu {
 
         self.button_manager.handle_input(event);
 
+        // A right-click on a setting's button puts that setting back to its default
+        if let WindowEvent::MouseInput {
+            state: ElementState::Pressed,
+            button: MouseButton::Right,
+            ..
+        } = event
+            && let Some(setting) = self.hovered_setting()
+        {
+            self.last_action = PauseMenuAction::ResetSetting(setting.id);
+            let _ = audio_manager.play_select();
+        }
+
+        // Any click other than the confirming one calls off restoring the defaults, and any
+        // click other than on the search field closes it
+        let released = matches!(
+            event,
+            WindowEvent::MouseInput {
+                state: ElementState::Released,
+                button: MouseButton::Left,
+                ..
+            }
+        );
+        self.take_clicks(released, audio_manager);
+    }
+
+    /// Takes a gamepad's step through the pause menu's buttons.
+    ///
+    /// Pressing A counts as a finished click, so it calls off restoring the defaults and
+    /// closes the search field like a mouse click would.
+    ///
+    /// # Arguments
+    ///
+    /// * `nav` - The step the gamepad made
+    /// * `audio_manager` - The audio manager for playing button click sounds
+    pub fn navigate(&mut self, nav: MenuNav, audio_manager: &mut GameAudioManager) {
+        if !self.visible {
+            return;
+        }
+        self.button_manager.navigate(nav);
+        self.take_clicks(nav == MenuNav::Activate, audio_manager);
+    }
+
+    /// Turns a click on one of the buttons, by mouse or gamepad, into the menu's action.
+    ///
+    /// # Arguments
+    ///
+    /// * `released` - Whether a click finished with this input, clicked on a button or not
+    /// * `audio_manager` - The audio manager for playing button click sounds
+    fn take_clicks(&mut self, released: bool, audio_manager: &mut GameAudioManager) {
+        if self.button_manager.is_button_clicked(RESTORE_DEFAULTS_ID) {
+            if self.confirming_restore {
+                self.last_action = PauseMenuAction::RestoreDefaults;
+            }
+            self.set_confirming_restore(!self.confirming_restore);
+            let _ = audio_manager.play_select();
+        } else if released {
+            self.set_confirming_restore(false);
+        }
+        let editing_filter = self.button_manager.is_button_clicked(FILTER_BUTTON_ID);
+        if released && editing_filter != self.editing_filter {
+            self.editing_filter = editing_filter;
+            self.update_filter_text();
+        }
+
+        // Clicking an action on the controls page waits for its new key; any other click
+        // stops waiting
+        let capturing = bindings::REBINDABLE.into_iter().find(|&(_, action)| {
+            self.button_manager
+                .is_button_clicked(&Self::binding_button_id(action))
+        });
+        if capturing.is_some() || (released && self.capturing.is_some()) {
+            self.capturing = capturing;
+            self.update_binding_labels();
+        }
+        if capturing.is_some() {
+            let _ = audio_manager.play_select();
+        }
+
+        if self.button_manager.is_button_clicked(CONTROLS_BUTTON_ID) {
+            self.set_showing_controls(true);
+            let _ = audio_manager.play_select();
+        }
+
+        if self.button_manager.is_button_clicked(CONTROLS_BACK_ID) {
+            self.set_showing_controls(false);
+            let _ = audio_manager.play_select();
+        }
+
         // Check for button clicks and play select sound for each action
         if self.button_manager.is_button_clicked("pause_resume") {
             self.last_action = PauseMenuAction::Resume;
             let _ = audio_manager.play_select();
         }
 
+        if self.button_manager.is_button_clicked("pause_retry_level") {
+            self.last_action = PauseMenuAction::RetryLevel;
+            let _ = audio_manager.play_select();
+        }
+
         if self.button_manager.is_button_clicked("pause_restart_run") {
             self.last_action = PauseMenuAction::Restart;
             let _ = audio_manager.play_select();
@@ -333,6 +652,66 @@ impl PauseMenu {
             self.show_debug_panel = !self.show_debug_panel;
             let _ = audio_manager.play_select();
         }
+
+        if self.button_manager.is_button_clicked("pause_menu_fps") {
+            self.last_action = PauseMenuAction::CycleMenuFpsCap;
+            let _ = audio_manager.play_select();
+        }
+
+        if self.button_manager.is_button_clicked("pause_gameplay_fps") {
+            self.last_action = PauseMenuAction::CycleGameplayFpsCap;
+            let _ = audio_manager.play_select();
+        }
+
+        if self.button_manager.is_button_clicked("pause_present_mode") {
+            self.last_action = PauseMenuAction::CyclePresentMode;
+            let _ = audio_manager.play_select();
+        }
+
+        if self.button_manager.is_button_clicked("pause_reload_marker") {
+            self.last_action = PauseMenuAction::ReloadMarker;
+            let _ = audio_manager.play_select();
+        }
+
+        if self.button_manager.is_button_clicked("pause_background") {
+            self.last_action = PauseMenuAction::CycleBackground;
+            let _ = audio_manager.play_select();
+        }
+
+        if self.button_manager.is_button_clicked("pause_ambience") {
+            self.last_action = PauseMenuAction::CycleAmbienceVolume;
+            let _ = audio_manager.play_select();
+        }
+
+        if self.button_manager.is_button_clicked("pause_time_of_day") {
+            self.last_action = PauseMenuAction::CycleTimeOfDay;
+            let _ = audio_manager.play_select();
+        }
+
+        if self.button_manager.is_button_clicked("pause_audio_output") {
+            self.last_action = PauseMenuAction::CycleAudioOutput;
+            let _ = audio_manager.play_select();
+        }
+
+        if self.button_manager.is_button_clicked("pause_hrtf") {
+            self.last_action = PauseMenuAction::ToggleHrtf;
+            let _ = audio_manager.play_select();
+        }
+
+        // No select sound, so it doesn't play over the start of the test
+        if self.button_manager.is_button_clicked("pause_audio_test") {
+            self.last_action = PauseMenuAction::PlayAudioTest;
+        }
+
+        for cosmetic in Cosmetic::ALL {
+            if self
+                .button_manager
+                .is_button_clicked(&Self::cosmetic_button_id(cosmetic))
+            {
+                self.last_action = PauseMenuAction::ToggleCosmetic(cosmetic);
+                let _ = audio_manager.play_select();
+            }
+        }
     }
 
     /// Gets the last action that was triggered and resets it to `None`.
@@ -381,7 +760,7 @@ impl PauseMenu {
         let button_width = (window_size.width as f32 * 0.38 * scale).clamp(180.0, 600.0);
         let button_height = (window_size.height as f32 * 0.09 * scale).clamp(32.0, 140.0);
         let button_spacing = (window_size.height as f32 * 0.015 * scale).clamp(2.0, 24.0);
-        let total_height = button_height * 5.0 + button_spacing * 4.0;
+        let total_height = button_height * 6.0 + button_spacing * 5.0;
         let center_x = window_size.width as f32 / 2.0;
         let start_y = (window_size.height as f32 - total_height) / 2.0;
         let text_style = Self::scaled_text_style(window_size.height as f32);
@@ -398,12 +777,22 @@ impl PauseMenu {
             resume_button.style.text_style = text_style.clone();
         }
 
+        if let Some(retry_level_button) = self.button_manager.get_button_mut("pause_retry_level") {
+            retry_level_button.style = ButtonPreset::Primary.style();
+            retry_level_button.style.text_style = text_style.clone();
+            retry_level_button.position.x = center_x;
+            retry_level_button.position.y = y(1);
+            retry_level_button.position.width = button_width;
+            retry_level_button.position.height = button_height;
+            retry_level_button.position.anchor = ButtonAnchor::Center;
+        }
+
         if let Some(restart_run_button) = self.button_manager.get_button_mut("pause_restart_run") {
             restart_run_button.text = "Restart Run".to_string();
-            restart_run_button.style = create_warning_button_style();
+            restart_run_button.style = ButtonPreset::Secondary.style();
             restart_run_button.style.text_style = text_style.clone();
             restart_run_button.position.x = center_x;
-            restart_run_button.position.y = y(1);
+            restart_run_button.position.y = y(2);
             restart_run_button.position.width = button_width;
             restart_run_button.position.height = button_height;
             restart_run_button.position.anchor = ButtonAnchor::Center;
@@ -412,10 +801,10 @@ impl PauseMenu {
         if let Some(test_mode_button) = self.button_manager.get_button_mut("pause_toggle_test_mode")
         {
             test_mode_button.text = "Toggle Test Mode".to_string();
-            test_mode_button.style = create_warning_button_style();
+            test_mode_button.style = ButtonPreset::Secondary.style();
             test_mode_button.style.text_style = text_style.clone();
             test_mode_button.position.x = center_x;
-            test_mode_button.position.y = y(2);
+            test_mode_button.position.y = y(3);
             test_mode_button.position.width = button_width;
             test_mode_button.position.height = button_height;
             test_mode_button.position.anchor = ButtonAnchor::Center;
@@ -423,20 +812,20 @@ impl PauseMenu {
 
         if let Some(quit_lobby_button) = self.button_manager.get_button_mut("pause_quit_lobby") {
             quit_lobby_button.text = "Quit to Lobby".to_string();
-            quit_lobby_button.style = create_danger_button_style();
+            quit_lobby_button.style = ButtonPreset::Destructive.style();
             quit_lobby_button.style.text_style = text_style.clone();
             quit_lobby_button.position.x = center_x;
-            quit_lobby_button.position.y = y(3);
+            quit_lobby_button.position.y = y(4);
             quit_lobby_button.position.width = button_width;
             quit_lobby_button.position.height = button_height;
             quit_lobby_button.position.anchor = ButtonAnchor::Center;
         }
 
         if let Some(quit_menu_button) = self.button_manager.get_button_mut("pause_quit_menu") {
-            quit_menu_button.style = create_danger_button_style();
+            quit_menu_button.style = ButtonPreset::Destructive.style();
             quit_menu_button.style.text_style = text_style.clone();
             quit_menu_button.position.x = center_x;
-            quit_menu_button.position.y = y(4);
+            quit_menu_button.position.y = y(5);
             quit_menu_button.position.width = button_width;
             quit_menu_button.position.height = button_height;
             quit_menu_button.position.anchor = ButtonAnchor::Center;
@@ -451,7 +840,7 @@ impl PauseMenu {
                     debug_button.style.padding,
                 )
             } else {
-                (create_warning_button_style().text_style, (2.0, 6.0))
+                (ButtonPreset::Secondary.style().text_style, (2.0, 6.0))
             };
         let (_min_x, text_width, text_height) = self
             .button_manager
@@ -465,9 +854,431 @@ impl PauseMenu {
             debug_button.position.height = side;
             debug_button.position.anchor = ButtonAnchor::TopLeft;
         }
+        self.layout_corner_buttons();
+        self.layout_controls_page();
 
         // Update text positions after all changes
         self.button_manager.update_button_positions();
+        self.layout_marker_note();
+    }
+
+    /// Lines up the small buttons to the right of the debug button, skipping the setting
+    /// buttons the search hides, and places the search field above them.
+    fn layout_corner_buttons(&mut self) {
+        let Some(debug_position) = self
+            .button_manager
+            .buttons
+            .get("pause_debug")
+            .map(|button| button.position.clone())
+        else {
+            return;
+        };
+        let side = debug_position.width;
+        let corner_ids = [
+            "pause_menu_fps",
+            "pause_gameplay_fps",
+            "pause_present_mode",
+            "pause_reload_marker",
+            "pause_background",
+            "pause_ambience",
+            "pause_time_of_day",
+            "pause_audio_output",
+            "pause_hrtf",
+            "pause_audio_test",
+            CONTROLS_BUTTON_ID,
+        ]
+        .map(String::from)
+        .into_iter()
+        .chain(Cosmetic::ALL.map(Self::cosmetic_button_id))
+        .chain([RESTORE_DEFAULTS_ID.to_string()]);
+        let shown_ids: Vec<String> = corner_ids.filter(|id| self.shown_by_filter(id)).collect();
+        for (index, id) in shown_ids.iter().enumerate() {
+            if let Some(button) = self.button_manager.get_button_mut(id) {
+                button.position.x =
+                    debug_position.x + (index + 1) as f32 * (side + CORNER_BUTTON_GAP);
+                button.position.y = debug_position.y;
+                button.position.width = side;
+                button.position.height = side;
+                button.position.anchor = ButtonAnchor::TopLeft;
+            }
+        }
+
+        // The marker note sits between the search field and the small buttons
+        let window_width = self.button_manager.window_size.width as f32;
+        if let Some(filter_button) = self.button_manager.get_button_mut(FILTER_BUTTON_ID) {
+            let height = side * 0.4;
+            filter_button.position.x = debug_position.x;
+            filter_button.position.y = debug_position.y - 36.0 - height;
+            filter_button.position.width = (window_width * 0.4).clamp(200.0, 640.0);
+            filter_button.position.height = height;
+            filter_button.position.anchor = ButtonAnchor::TopLeft;
+        }
+    }
+
+    /// Stacks the controls page's buttons in the middle of the window, where the menu's
+    /// buttons are while the page is closed.
+    fn layout_controls_page(&mut self) {
+        let window_size = self.button_manager.window_size;
+        let reference_height = 1080.0;
+        let scale = (window_size.height as f32 / reference_height).clamp(0.7, 2.0);
+
+        let button_width = (window_size.width as f32 * 0.38 * scale).clamp(180.0, 600.0);
+        let button_height = (window_size.height as f32 * 0.06 * scale).clamp(28.0, 90.0);
+        let button_spacing = (window_size.height as f32 * 0.01 * scale).clamp(2.0, 16.0);
+        let ids: Vec<String> = bindings::REBINDABLE
+            .into_iter()
+            .map(|(_, action)| Self::binding_button_id(action))
+            .chain([CONTROLS_BACK_ID.to_string()])
+            .collect();
+        let total_height =
+            button_height * ids.len() as f32 + button_spacing * (ids.len() - 1) as f32;
+        let center_x = window_size.width as f32 / 2.0;
+        let start_y = (window_size.height as f32 - total_height) / 2.0;
+        let mut text_style = Self::scaled_text_style(window_size.height as f32);
+        text_style.font_size *= 0.75;
+        text_style.line_height *= 0.75;
+
+        for (index, id) in ids.iter().enumerate() {
+            if let Some(button) = self.button_manager.get_button_mut(id) {
+                button.style.text_style = text_style.clone();
+                button.position = ButtonPosition::new(
+                    center_x,
+                    start_y + button_height / 2.0 + index as f32 * (button_height + button_spacing),
+                    button_width,
+                    button_height,
+                )
+                .with_anchor(ButtonAnchor::Center);
+            }
+        }
+        self.button_manager.update_button_positions();
+    }
+
+    /// Returns whether a button belongs to the controls page rather than the menu.
+    fn on_controls_page(id: &str) -> bool {
+        id.starts_with(CONTROLS_PAGE_PREFIX)
+    }
+
+    /// Shows the buttons of the page on show, either the menu or the controls page, and
+    /// hides the other page's.
+    fn show_page(&mut self) {
+        let showing_controls = self.showing_controls;
+        for button in self.button_manager.buttons.values_mut() {
+            button.set_visible(Self::on_controls_page(&button.id) == showing_controls);
+        }
+
+        // Keep hiding the setting buttons the search rules out
+        self.apply_filter();
+        self.button_manager.reset_tooltips();
+    }
+
+    /// Opens or closes the controls page.
+    ///
+    /// # Arguments
+    ///
+    /// * `showing` - Whether the controls page is shown in place of the menu
+    fn set_showing_controls(&mut self, showing: bool) {
+        self.showing_controls = showing;
+        self.capturing = None;
+        self.editing_filter = false;
+        self.set_confirming_restore(false);
+        self.update_binding_labels();
+        if self.visible {
+            self.show_page();
+        }
+    }
+
+    /// Returns the ID of a rebindable action's button on the controls page.
+    fn binding_button_id(action: Action) -> String {
+        format!(
+            "{}{}",
+            CONTROLS_PAGE_PREFIX,
+            action.label().to_lowercase().replace(' ', "_")
+        )
+    }
+
+    /// Updates the controls page to show the keys the actions are bound to.
+    ///
+    /// # Arguments
+    ///
+    /// * `router` - The router holding the player's key bindings
+    pub fn update_binding_buttons(&mut self, router: &InputRouter) {
+        self.binding_labels = bindings::REBINDABLE
+            .into_iter()
+            .map(|(context, action)| {
+                let key = router.bindings(context).keys_for(action).next();
+                Self::binding_label(action, key)
+            })
+            .collect();
+        self.update_binding_labels();
+    }
+
+    /// Shows on an action's button why the key pressed for it wasn't bound.
+    ///
+    /// The action keeps its old key; the next update of the controls page shows that again.
+    ///
+    /// # Arguments
+    ///
+    /// * `conflict` - The refused rebinding
+    pub fn show_refused_binding(&mut self, conflict: &Conflict) {
+        let index = bindings::REBINDABLE
+            .into_iter()
+            .position(|rebindable| rebindable == (conflict.context, conflict.action));
+        if let Some(label) = index.and_then(|index| self.binding_labels.get_mut(index)) {
+            *label = format!("{}: {}", conflict.action.label(), conflict);
+        }
+        self.update_binding_labels();
+    }
+
+    /// Labels each action's button with its key, or a prompt while it waits for one.
+    fn update_binding_labels(&mut self) {
+        for ((context, action), label) in bindings::REBINDABLE.into_iter().zip(&self.binding_labels)
+        {
+            let id = Self::binding_button_id(action);
+            let label = if self.capturing == Some((context, action)) {
+                format!("{}: press a key...", action.label())
+            } else {
+                label.clone()
+            };
+            if let Err(e) = self.button_manager.set_button_text(&id, &label) {
+                eprintln!("Failed to update {} label: {}", id, e);
+            }
+        }
+        self.button_manager.update_button_positions();
+    }
+
+    /// Builds the label shown on an action's button on the controls page.
+    ///
+    /// # Arguments
+    ///
+    /// * `action` - The rebindable action
+    /// * `key` - The key the action is bound to, if any
+    ///
+    /// # Returns
+    ///
+    /// A label such as `"Interact: E"`
+    fn binding_label(action: Action, key: Option<KeyCode>) -> String {
+        let key = key.map_or_else(|| "unbound".to_string(), bindings::key_label);
+        format!("{}: {}", action.label(), key)
+    }
+
+    /// Returns whether the controls page waits for a key press to bind.
+    pub fn is_capturing_key(&self) -> bool {
+        self.visible && self.capturing.is_some()
+    }
+
+    /// Binds the pressed key to the action waiting on the controls page.
+    ///
+    /// Every key can be bound, Escape included, so clicking anywhere else is how to stop
+    /// waiting without binding one.
+    ///
+    /// # Arguments
+    ///
+    /// * `event` - The keyboard event
+    ///
+    /// # Returns
+    ///
+    /// `true` if the controls page used the key, so nothing else should act on it
+    pub fn handle_binding_key(&mut self, event: &KeyEvent) -> bool {
+        let Some((context, action)) = self.capturing.filter(|_| self.visible) else {
+            return false;
+        };
+        if event.state != ElementState::Pressed || event.repeat {
+            return true;
+        }
+        if let PhysicalKey::Code(key) = event.physical_key {
+            self.capturing = None;
+            self.last_action = PauseMenuAction::Rebind(context, action, key);
+        }
+        true
+    }
+
+    /// Returns an empty settings search.
+    fn empty_filter() -> SeedField {
+        SeedField::new(None).with_max_length(FILTER_MAX_LENGTH)
+    }
+
+    /// Returns whether a button is shown under the current settings search.
+    ///
+    /// Buttons that don't change a setting are always shown.
+    fn shown_by_filter(&self, id: &str) -> bool {
+        Self::button_setting(id).is_none_or(|setting| setting.matches(self.filter.text()))
+    }
+
+    /// Shows the setting buttons that match the settings search and hides the rest.
+    fn apply_filter(&mut self) {
+        let ids: Vec<String> = self
+            .button_manager
+            .buttons
+            .keys()
+            .filter(|id| Self::button_setting(id).is_some())
+            .cloned()
+            .collect();
+        for id in ids {
+            let shown = self.visible && !self.showing_controls && self.shown_by_filter(&id);
+            if let Some(button) = self.button_manager.get_button_mut(&id) {
+                button.set_visible(shown);
+            }
+        }
+        self.layout_corner_buttons();
+        self.update_filter_text();
+        self.button_manager.update_button_states();
+        self.button_manager.update_button_positions();
+    }
+
+    /// Shows the typed search, with a caret while it is open, on the search field.
+    fn update_filter_text(&mut self) {
+        let label = if self.editing_filter {
+            format!("Search settings: {}", self.filter.display())
+        } else if self.filter.text().is_empty() {
+            "Search settings (/)  \u{2022}  right-click a setting to reset it".to_string()
+        } else {
+            format!("Search settings: {}", self.filter.text())
+        };
+        if let Err(e) = self
+            .button_manager
+            .set_button_text(FILTER_BUTTON_ID, &label)
+        {
+            eprintln!("Failed to update {} label: {}", FILTER_BUTTON_ID, e);
+        }
+    }
+
+    /// Returns whether the settings search is open for typing.
+    pub fn is_editing_filter(&self) -> bool {
+        self.visible && self.editing_filter
+    }
+
+    /// Handles a key for the settings search.
+    ///
+    /// `/` opens the search. While it is open, Submit (Enter) closes it and keeps the search,
+    /// Cancel (Escape) clears and closes it, and other keys edit the text. The setting buttons are filtered
+    /// as the player types.
+    ///
+    /// # Arguments
+    ///
+    /// * `event` - The keyboard event
+    /// * `action` - What the key is bound to in the text entry context, if anything
+    ///
+    /// # Returns
+    ///
+    /// `true` if the search used the key, so nothing else should act on it
+    pub fn handle_filter_key(&mut self, event: &KeyEvent, action: Option<Action>) -> bool {
+        if !self.visible {
+            return false;
+        }
+        if !self.editing_filter {
+            let opens = event.state == ElementState::Pressed
+                && !event.repeat
+                && matches!(&event.logical_key, Key::Character(c) if c == "/");
+            if opens {
+                self.editing_filter = true;
+                self.update_filter_text();
+            }
+            return opens;
+        }
+        if event.state != ElementState::Pressed {
+            return true;
+        }
+        match action {
+            Some(Action::Submit) => self.editing_filter = false,
+            Some(Action::Cancel) => {
+                self.filter = Self::empty_filter();
+                self.editing_filter = false;
+            }
+            _ => self.filter.edit_key(event, action),
+        }
+        self.apply_filter();
+        true
+    }
+
+    /// Returns the setting under the cursor, if the cursor is over a setting button.
+    fn hovered_setting(&self) -> Option<&'static SettingDescriptor> {
+        let (x, y) = self.button_manager.mouse_position;
+        self.button_manager
+            .buttons
+            .values()
+            .filter(|button| button.visible && button.contains_point(x, y))
+            .find_map(|button| Self::button_setting(&button.id))
+    }
+
+    /// Returns the setting a button changes.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The button's ID
+    ///
+    /// # Returns
+    ///
+    /// The setting from the settings registry, or `None` for a button that isn't a setting
+    fn button_setting(id: &str) -> Option<&'static SettingDescriptor> {
+        let setting_id = SETTING_BUTTONS
+            .iter()
+            .find(|(button_id, _)| *button_id == id)
+            .map(|(_, setting_id)| *setting_id)
+            .or_else(|| id.starts_with("pause_cosmetic_").then_some("cosmetics"))?;
+        settings_registry::find(setting_id)
+    }
+
+    /// Arms or disarms the restore defaults button, showing which on its label.
+    ///
+    /// # Arguments
+    ///
+    /// * `confirming` - Whether the next click restores the defaults
+    fn set_confirming_restore(&mut self, confirming: bool) {
+        if confirming == self.confirming_restore {
+            return;
+        }
+        self.confirming_restore = confirming;
+        let label = if confirming {
+            RESTORE_DEFAULTS_CONFIRM_LABEL
+        } else {
+            RESTORE_DEFAULTS_LABEL
+        };
+        if let Err(e) = self
+            .button_manager
+            .set_button_text(RESTORE_DEFAULTS_ID, label)
+        {
+            eprintln!("Failed to update {} label: {}", RESTORE_DEFAULTS_ID, e);
+        }
+        self.button_manager.update_button_positions();
+    }
+
+    /// Shows or clears the note explaining why the custom player marker was not used.
+    ///
+    /// # Arguments
+    ///
+    /// * `note` - The reason the marker was rejected, or `None` to hide the note
+    pub fn set_marker_note(&mut self, note: Option<&str>) {
+        let text_renderer = &mut self.button_manager.text_renderer;
+        if let Err(e) = text_renderer.update_text(MARKER_NOTE_TEXT_ID, note.unwrap_or_default()) {
+            eprintln!("Failed to update marker note: {}", e);
+        }
+        let _ = text_renderer.set_buffer_visibility(MARKER_NOTE_TEXT_ID, note.is_some());
+    }
+
+    /// Places the marker note just above the small buttons in the bottom-left corner.
+    fn layout_marker_note(&mut self) {
+        let window_width = self.button_manager.window_size.width as f32;
+        let Some(corner_top) = self
+            .button_manager
+            .buttons
+            .get("pause_debug")
+            .map(|button| button.position.y)
+        else {
+            return;
+        };
+        let position = TextPosition {
+            x: 60.0,
+            y: corner_top - 28.0,
+            max_width: Some((window_width - 120.0).max(0.0)),
+            max_height: Some(24.0),
+        };
+        if let Err(e) = self
+            .button_manager
+            .text_renderer
+            .update_position(MARKER_NOTE_TEXT_ID, position)
+        {
+            eprintln!("Failed to position marker note: {}", e);
+        }
     }
 
     /// Prepares the pause menu for rendering by updating text layout.
@@ -533,4 +1344,264 @@ impl PauseMenu {
             }
         }
     }
+
+    /// Updates the frame rate cap buttons to show the current settings.
+    ///
+    /// # Arguments
+    ///
+    /// * `menu_cap` - The cap applied on menu screens
+    /// * `gameplay_cap` - The cap applied during gameplay
+    pub fn update_fps_cap_button_text(&mut self, menu_cap: FpsCap, gameplay_cap: FpsCap) {
+        let labels = [
+            ("pause_menu_fps", Self::fps_cap_label("Menu", menu_cap)),
+            (
+                "pause_gameplay_fps",
+                Self::fps_cap_label("Game", gameplay_cap),
+            ),
+        ];
+        for (id, label) in labels {
+            if let Err(e) = self.button_manager.set_button_text(id, &label) {
+                eprintln!("Failed to update {} label: {}", id, e);
+            }
+        }
+        self.button_manager.update_button_positions();
+    }
+
+    /// Updates the vsync button to show the current setting.
+    ///
+    /// # Arguments
+    ///
+    /// * `mode` - How frames are presented
+    pub fn update_present_mode_button_text(&mut self, mode: PresentModePreference) {
+        if let Err(e) = self
+            .button_manager
+            .set_button_text("pause_present_mode", &Self::present_mode_label(mode))
+        {
+            eprintln!("Failed to update pause_present_mode label: {}", e);
+        }
+        self.button_manager.update_button_positions();
+    }
+
+    /// Updates the background button to show the current setting.
+    ///
+    /// # Arguments
+    ///
+    /// * `mode` - What is drawn behind the maze
+    pub fn update_background_button_text(&mut self, mode: BackgroundMode) {
+        if let Err(e) = self
+            .button_manager
+            .set_button_text("pause_background", &Self::background_label(mode))
+        {
+            eprintln!("Failed to update pause_background label: {}", e);
+        }
+        self.button_manager.update_button_positions();
+    }
+
+    /// Updates the ambience button to show the current volume.
+    ///
+    /// # Arguments
+    ///
+    /// * `volume` - Ambience volume from 0.0 (off) to 1.0 (full)
+    pub fn update_ambience_button_text(&mut self, volume: f32) {
+        if let Err(e) = self
+            .button_manager
+            .set_button_text("pause_ambience", &Self::ambience_label(volume))
+        {
+            eprintln!("Failed to update pause_ambience label: {}", e);
+        }
+        self.button_manager.update_button_positions();
+    }
+
+    /// Updates the cosmetic buttons to show which cosmetics are unlocked and switched on.
+    ///
+    /// Locked cosmetics are disabled, which greys their buttons out, and show a hint
+    /// about where to find them instead of their name.
+    ///
+    /// # Arguments
+    ///
+    /// * `is_unlocked` - Whether a cosmetic has been unlocked
+    /// * `is_on` - Whether an unlocked cosmetic is switched on
+    pub fn update_cosmetic_buttons(
+        &mut self,
+        is_unlocked: impl Fn(Cosmetic) -> bool,
+        is_on: impl Fn(Cosmetic) -> bool,
+    ) {
+        for cosmetic in Cosmetic::ALL {
+            let id = Self::cosmetic_button_id(cosmetic);
+            let unlocked = is_unlocked(cosmetic);
+            let label = if unlocked {
+                let state = if is_on(cosmetic) { "On" } else { "Off" };
+                format!("{}\n{}", cosmetic.name(), state)
+            } else {
+                LOCKED_COSMETIC_LABEL.to_string()
+            };
+            if let Some(button) = self.button_manager.get_button_mut(&id) {
+                button.enabled = unlocked;
+            }
+            if let Err(e) = self.button_manager.set_button_text(&id, &label) {
+                eprintln!("Failed to update {} label: {}", id, e);
+            }
+        }
+        self.button_manager.update_button_states();
+        self.button_manager.update_button_positions();
+    }
+
+    /// Returns the ID of a cosmetic's button.
+    fn cosmetic_button_id(cosmetic: Cosmetic) -> String {
+        format!("pause_cosmetic_{}", cosmetic.name().to_lowercase())
+    }
+
+    /// Builds the two-line label shown on the background button.
+    ///
+    /// # Arguments
+    ///
+    /// * `mode` - What is drawn behind the maze
+    ///
+    /// # Returns
+    ///
+    /// A label such as `"Sky\nStars"`
+    fn background_label(mode: BackgroundMode) -> String {
+        format!("Sky\n{}", mode.label())
+    }
+
+    /// Builds the two-line label shown on the vsync button.
+    ///
+    /// # Arguments
+    ///
+    /// * `mode` - How frames are presented
+    ///
+    /// # Returns
+    ///
+    /// A label such as `"Vsync\nOn"`
+    fn present_mode_label(mode: PresentModePreference) -> String {
+        format!("Vsync\n{}", mode.label())
+    }
+
+    /// Updates the time of day button to show the current setting.
+    ///
+    /// # Arguments
+    ///
+    /// * `pin` - Whether the time of day follows the run or is pinned
+    pub fn update_time_of_day_button_text(&mut self, pin: TimeOfDayPin) {
+        if let Err(e) = self
+            .button_manager
+            .set_button_text("pause_time_of_day", &Self::time_of_day_label(pin))
+        {
+            eprintln!("Failed to update pause_time_of_day label: {}", e);
+        }
+        self.button_manager.update_button_positions();
+    }
+
+    /// Updates the audio output and HRTF buttons to show the current settings.
+    ///
+    /// The HRTF button is disabled, which greys it out, and shows that HRTF is unavailable
+    /// when the audio backend can't render it.
+    ///
+    /// # Arguments
+    ///
+    /// * `profile` - The device the player listens through
+    /// * `hrtf` - Whether the HRTF setting is on
+    /// * `capabilities` - What this machine supports
+    pub fn update_spatial_audio_buttons(
+        &mut self,
+        profile: OutputProfile,
+        hrtf: bool,
+        capabilities: &Capabilities,
+    ) {
+        if let Err(e) = self
+            .button_manager
+            .set_button_text("pause_audio_output", &Self::audio_output_label(profile))
+        {
+            eprintln!("Failed to update pause_audio_output label: {}", e);
+        }
+        let available = settings_registry::find("hrtf")
+            .is_some_and(|setting| setting.is_available(capabilities));
+        if let Some(button) = self.button_manager.get_button_mut("pause_hrtf") {
+            button.enabled = available;
+        }
+        if let Err(e) = self
+            .button_manager
+            .set_button_text("pause_hrtf", &Self::hrtf_label(hrtf, available))
+        {
+            eprintln!("Failed to update pause_hrtf label: {}", e);
+        }
+        self.button_manager.update_button_states();
+        self.button_manager.update_button_positions();
+    }
+
+    /// Builds the two-line label shown on the audio output button.
+    ///
+    /// # Arguments
+    ///
+    /// * `profile` - The device the player listens through
+    ///
+    /// # Returns
+    ///
+    /// A label such as `"Output\nSpeakers"`
+    fn audio_output_label(profile: OutputProfile) -> String {
+        format!("Output\n{}", profile.label())
+    }
+
+    /// Builds the two-line label shown on the HRTF button.
+    ///
+    /// # Arguments
+    ///
+    /// * `on` - Whether the HRTF setting is on
+    /// * `available` - Whether the audio backend can render HRTF
+    ///
+    /// # Returns
+    ///
+    /// A label such as `"HRTF\nOn"`, or `"HRTF\nN/A"` when unavailable
+    fn hrtf_label(on: bool, available: bool) -> String {
+        let state = match (available, on) {
+            (false, _) => "N/A",
+            (true, true) => "On",
+            (true, false) => "Off",
+        };
+        format!("HRTF\n{}", state)
+    }
+
+    /// Builds the two-line label shown on the time of day button.
+    ///
+    /// # Arguments
+    ///
+    /// * `pin` - Whether the time of day follows the run or is pinned
+    ///
+    /// # Returns
+    ///
+    /// A label such as `"Hour\nNight"`
+    fn time_of_day_label(pin: TimeOfDayPin) -> String {
+        format!("Hour\n{}", pin.label())
+    }
+
+    /// Builds the two-line label shown on the ambience button.
+    ///
+    /// # Arguments
+    ///
+    /// * `volume` - Ambience volume from 0.0 (off) to 1.0 (full)
+    ///
+    /// # Returns
+    ///
+    /// A label such as `"Ambience\n75%"`, or `"Ambience\nOff"` at zero
+    fn ambience_label(volume: f32) -> String {
+        if volume <= 0.0 {
+            "Ambience\nOff".to_string()
+        } else {
+            format!("Ambience\n{:.0}%", volume * 100.0)
+        }
+    }
+
+    /// Builds the three-line label shown on a frame rate cap button.
+    ///
+    /// # Arguments
+    ///
+    /// * `scope` - Which screens the cap applies to ("Menu" or "Game")
+    /// * `cap` - The current cap
+    ///
+    /// # Returns
+    ///
+    /// A label such as `"Menu\nFPS\n60"`
+    fn fps_cap_label(scope: &str, cap: FpsCap) -> String {
+        format!("{}\nFPS\n{}", scope, cap.label())
+    }
 }
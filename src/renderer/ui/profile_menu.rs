@@ -0,0 +1,806 @@
+//! Profile Menu
+//!
+//! This module provides the screen for picking who is playing: one row per profile with its
+//! name and a line of stats, and buttons to play as the selected profile, make a new one,
+//! rename it, or delete it. It is drawn over the title background, opened from the title
+//! screen's "Switch Profile" button, and on a first launch before there are any profiles.
+//!
+//! Naming a new profile, renaming one, and deleting one all go through the same text field
+//! below the list. Deleting asks for the profile's name to be typed again. While the field is
+//! open it takes every key; Enter confirms and Escape closes it. The menu itself only reports
+//! what the player asked for as a [`ProfileAction`]; the caller does the work and reports
+//! back with [`ProfileMenu::refresh`] and [`ProfileMenu::set_message`].
+
+use crate::app::profiles::{self, ProfileSummary};
+use crate::game::audio::GameAudioManager;
+use crate::game::bindings::Action;
+use crate::game::seed::SeedField;
+use crate::renderer::gpu_memory::GpuMemoryTracker;
+use crate::renderer::text::{TextPosition, TextStyle};
+use crate::renderer::ui::button::{
+    Button, ButtonAnchor, ButtonManager, ButtonPosition, ButtonPreset, ButtonStyle, TextAlign,
+};
+use glyphon::{Color, Resolution, Weight};
+use wgpu::{Device, Queue, RenderPass, SurfaceConfiguration};
+use winit::dpi::PhysicalSize;
+use winit::event::{ElementState, Ime, KeyEvent, WindowEvent};
+use winit::window::Window;
+
+/// Button ID of the play as button.
+const PLAY_BUTTON_ID: &str = "profile_play";
+
+/// Button ID of the new profile button.
+const NEW_BUTTON_ID: &str = "profile_new";
+
+/// Button ID of the rename button.
+const RENAME_BUTTON_ID: &str = "profile_rename";
+
+/// Button ID of the delete button.
+const DELETE_BUTTON_ID: &str = "profile_delete";
+
+/// Button ID of the back button.
+const BACK_BUTTON_ID: &str = "profile_back";
+
+/// Button ID of the text field.
+const FIELD_BUTTON_ID: &str = "profile_field";
+
+/// Text buffer ID of the screen heading.
+const HEADING_TEXT_ID: &str = "profile_heading";
+
+/// Text buffer ID of the line below the buttons.
+const MESSAGE_TEXT_ID: &str = "profile_message";
+
+/// Heading shown above the profile list.
+const HEADING: &str = "Who's playing?";
+
+/// Color of the line below the buttons when something went wrong.
+const ERROR_COLOR: Color = Color::rgb(230, 110, 95);
+
+/// Actions that can be triggered from the profile menu
+#[derive(Debug, Clone, PartialEq)]
+pub enum ProfileAction {
+    /// Play as a profile
+    Switch(String),
+    /// Make a new profile with the typed name
+    Create(String),
+    /// Rename a profile
+    Rename {
+        /// The profile's current name
+        from: String,
+        /// The name typed for it
+        to: String,
+    },
+    /// Delete a profile
+    Delete {
+        /// The profile's name
+        name: String,
+        /// The name typed again to confirm
+        confirmation: String,
+    },
+    /// Go back to the title screen
+    Back,
+    /// No action has been taken
+    None,
+}
+
+/// What the text field is open for.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Entry {
+    /// Naming a new profile
+    Create,
+    /// Typing a new name for a profile
+    Rename(String),
+    /// Typing a profile's name to confirm deleting it
+    Delete(String),
+}
+
+impl Entry {
+    /// Returns the line shown below the buttons while the field is open.
+    fn prompt(&self) -> String {
+        match self {
+            Entry::Create => "Type a name for the new profile and press Enter".to_string(),
+            Entry::Rename(name) => format!("Type a new name for {} and press Enter", name),
+            Entry::Delete(name) => format!(
+                "Deleting {} can't be undone. Type {} and press Enter to delete it",
+                name, name
+            ),
+        }
+    }
+
+    /// Returns the label shown before the typed text.
+    fn prefix(&self) -> &'static str {
+        match self {
+            Entry::Create | Entry::Rename(_) => "Name: ",
+            Entry::Delete(_) => "Confirm: ",
+        }
+    }
+}
+
+/// The profile screen: the list of profiles and the buttons that act on them.
+pub struct ProfileMenu {
+    /// Manages the rows and buttons; its text renderer also draws the heading and message
+    pub button_manager: ButtonManager,
+    /// One summary per profile, in list order
+    summaries: Vec<ProfileSummary>,
+    /// Name of the profile being played, if any
+    active: Option<String>,
+    /// Index of the selected row
+    selected: Option<usize>,
+    /// What the text field is open for, while it is open
+    entry: Option<Entry>,
+    /// The text being typed, while the field is open
+    field: SeedField,
+    /// The last result reported by the caller, and whether it was an error
+    message: Option<(String, bool)>,
+    /// Whether the window's input method is switched on for the field
+    ime_allowed: bool,
+    /// Current window size
+    window_size: PhysicalSize<u32>,
+    /// Whether the profile menu is currently visible
+    pub visible: bool,
+    /// The last action that was triggered by the menu
+    pub last_action: ProfileAction,
+}
+
+impl ProfileMenu {
+    /// Creates a new profile menu instance.
+    ///
+    /// # Arguments
+    ///
+    /// * `device` - The WGPU device for rendering
+    /// * `queue` - The WGPU command queue
+    /// * `surface_format` - The surface texture format
+    /// * `window_size` - The window size, for sizing calculations
+    /// * `gpu_memory` - Tracker the menu's glyph textures are counted in
+    ///
+    /// # Returns
+    ///
+    /// A new, hidden `ProfileMenu` laid out for the window
+    pub fn new(
+        device: &Device,
+        queue: &Queue,
+        surface_format: wgpu::TextureFormat,
+        window_size: PhysicalSize<u32>,
+        gpu_memory: &GpuMemoryTracker,
+    ) -> Self {
+        let mut button_manager =
+            ButtonManager::new(device, queue, surface_format, window_size, gpu_memory);
+        for index in 0..profiles::MAX_PROFILES {
+            button_manager.add_button(
+                Button::primary(&row_button_id(index), "").with_text_align(TextAlign::Left),
+            );
+        }
+        button_manager
+            .add_button(Button::primary(FIELD_BUTTON_ID, "").with_text_align(TextAlign::Left));
+        for (id, text) in [
+            (PLAY_BUTTON_ID, "Play"),
+            (NEW_BUTTON_ID, "New"),
+            (RENAME_BUTTON_ID, "Rename"),
+            (BACK_BUTTON_ID, "Back"),
+        ] {
+            button_manager.add_button(Button::primary(id, text).with_text_align(TextAlign::Center));
+        }
+        button_manager.add_button(
+            Button::destructive(DELETE_BUTTON_ID, "Delete").with_text_align(TextAlign::Center),
+        );
+        let text_renderer = &mut button_manager.text_renderer;
+        text_renderer.create_text_buffer(HEADING_TEXT_ID, HEADING, None, None);
+        text_renderer.create_text_buffer(MESSAGE_TEXT_ID, "", None, None);
+
+        let mut menu = Self {
+            button_manager,
+            summaries: Vec::new(),
+            active: None,
+            selected: None,
+            entry: None,
+            field: new_field(),
+            message: None,
+            ime_allowed: false,
+            window_size,
+            visible: true,
+            last_action: ProfileAction::None,
+        };
+        menu.hide();
+        menu
+    }
+
+    /// Shows the profile menu with the profiles on this machine.
+    ///
+    /// With no profiles yet, as on a first launch, the field opens straight away for the
+    /// first profile's name and there is no way back until one exists.
+    ///
+    /// # Arguments
+    ///
+    /// * `summaries` - One summary per profile, in list order
+    /// * `active` - Name of the profile being played, if any
+    pub fn open(&mut self, summaries: Vec<ProfileSummary>, active: Option<&str>) {
+        self.visible = true;
+        self.last_action = ProfileAction::None;
+        self.message = None;
+        self.entry = None;
+        self.selected = None;
+        self.refresh(summaries, active);
+        if self.summaries.is_empty() {
+            self.begin_entry(Entry::Create);
+        }
+    }
+
+    /// Hides the profile menu.
+    pub fn hide(&mut self) {
+        self.visible = false;
+        self.last_action = ProfileAction::None;
+        self.entry = None;
+        self.sync_visibility();
+    }
+
+    /// Returns whether the text field is open for typing.
+    pub fn is_typing(&self) -> bool {
+        self.visible && self.entry.is_some()
+    }
+
+    /// Shows a new list of profiles after one was made, renamed or deleted.
+    ///
+    /// The selection stays on the same profile if it's still there, and otherwise moves to
+    /// the one being played.
+    ///
+    /// # Arguments
+    ///
+    /// * `summaries` - One summary per profile, in list order
+    /// * `active` - Name of the profile being played, if any
+    pub fn refresh(&mut self, summaries: Vec<ProfileSummary>, active: Option<&str>) {
+        let selected_name = self.selected_name().map(str::to_string);
+        self.summaries = summaries;
+        self.summaries.truncate(profiles::MAX_PROFILES);
+        self.active = active.map(str::to_string);
+        self.selected = selected_name
+            .as_deref()
+            .or(active)
+            .and_then(|name| self.index_of(name))
+            .or(if self.summaries.is_empty() {
+                None
+            } else {
+                Some(0)
+            });
+        self.sync_visibility();
+    }
+
+    /// Selects a profile by name, if it's in the list.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The profile's name
+    pub fn select(&mut self, name: &str) {
+        if let Some(index) = self.index_of(name) {
+            self.selected = Some(index);
+            self.sync_visibility();
+        }
+    }
+
+    /// Shows a line below the buttons, such as why an action failed.
+    ///
+    /// # Arguments
+    ///
+    /// * `message` - The line to show
+    /// * `error` - Whether it reports something that went wrong
+    pub fn set_message(&mut self, message: &str, error: bool) {
+        self.message = Some((message.to_string(), error));
+        self.layout();
+    }
+
+    /// Closes the text field, once the caller has acted on what was typed.
+    pub fn end_entry(&mut self) {
+        self.entry = None;
+        self.sync_visibility();
+    }
+
+    /// Returns the index of a profile in the list.
+    fn index_of(&self, name: &str) -> Option<usize> {
+        self.summaries
+            .iter()
+            .position(|summary| summary.name == name)
+    }
+
+    /// Returns the name of the selected profile.
+    fn selected_name(&self) -> Option<&str> {
+        self.selected
+            .and_then(|index| self.summaries.get(index))
+            .map(|summary| summary.name.as_str())
+    }
+
+    /// Opens the text field, empty.
+    ///
+    /// # Arguments
+    ///
+    /// * `entry` - What the field is for
+    fn begin_entry(&mut self, entry: Entry) {
+        self.field = new_field();
+        if let Entry::Rename(name) = &entry {
+            self.field.paste(name);
+        }
+        self.entry = Some(entry);
+        self.message = None;
+        self.sync_visibility();
+    }
+
+    /// Shows the rows in use and the buttons that apply, then lays everything out again.
+    fn sync_visibility(&mut self) {
+        let visible = self.visible;
+        let has_selection = self.selected.is_some();
+        let typing = self.entry.is_some();
+        let can_add = self.summaries.len() < profiles::MAX_PROFILES;
+        // Without a profile to go back to, the only way forward is making one
+        let can_leave = self.active.is_some();
+
+        for index in 0..profiles::MAX_PROFILES {
+            let label = self.summaries.get(index).map(|summary| {
+                let playing = if Some(&summary.name) == self.active.as_ref() {
+                    " (playing)"
+                } else {
+                    ""
+                };
+                format!("{}{} - {}", summary.name, playing, summary.stats_line())
+            });
+            let id = row_button_id(index);
+            let style = if self.selected == Some(index) {
+                ButtonPreset::Secondary.style()
+            } else {
+                ButtonPreset::Primary.style()
+            };
+            if let Some(button) = self.button_manager.get_button_mut(&id) {
+                button.set_visible(visible && label.is_some());
+                button.style.background_color = style.background_color;
+                button.style.hover_color = style.hover_color;
+                button.style.pressed_color = style.pressed_color;
+                button.style.border_color = style.border_color;
+            }
+            if let Some(label) = label {
+                self.set_text(&id, &label);
+            }
+        }
+
+        let shown = [
+            (PLAY_BUTTON_ID, has_selection && !typing),
+            (NEW_BUTTON_ID, can_add && !typing),
+            (RENAME_BUTTON_ID, has_selection && !typing),
+            (DELETE_BUTTON_ID, has_selection && !typing),
+            (BACK_BUTTON_ID, can_leave && !typing),
+            (FIELD_BUTTON_ID, typing),
+        ];
+        for (id, show) in shown {
+            if let Some(button) = self.button_manager.get_button_mut(id) {
+                button.set_visible(visible && show);
+            }
+        }
+        for id in [HEADING_TEXT_ID, MESSAGE_TEXT_ID] {
+            let _ = self
+                .button_manager
+                .text_renderer
+                .set_buffer_visibility(id, visible);
+        }
+        self.refresh_field();
+        self.layout();
+        self.button_manager.update_button_states();
+    }
+
+    /// Shows the text being typed on the field.
+    fn refresh_field(&mut self) {
+        if let Some(entry) = &self.entry {
+            let text = format!("{}{}", entry.prefix(), self.field.display());
+            self.set_text(FIELD_BUTTON_ID, &text);
+        }
+    }
+
+    /// Changes a button's label if it differs.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The button's ID
+    /// * `text` - The new label
+    fn set_text(&mut self, id: &str, text: &str) {
+        let unchanged = self
+            .button_manager
+            .buttons
+            .get(id)
+            .is_some_and(|button| button.text == text);
+        if !unchanged {
+            self.button_manager
+                .set_button_text(id, text)
+                .unwrap_or_else(|e| eprintln!("Failed to update profile menu: {}", e));
+        }
+    }
+
+    /// Places the heading, the rows, the field, the buttons and the message for the window
+    /// size.
+    fn layout(&mut self) {
+        let (width, height) = (
+            self.window_size.width as f32,
+            self.window_size.height as f32,
+        );
+        let scale = (height / 1080.0).clamp(0.7, 2.0);
+        let margin = 60.0 * scale;
+        let content_width = (width - 2.0 * margin).clamp(1.0, 1000.0 * scale);
+        let left = (width - content_width) / 2.0;
+        let gap = 10.0 * scale;
+
+        let heading_style = TextStyle {
+            font_size: (48.0 * scale).clamp(28.0, 96.0),
+            line_height: (58.0 * scale).clamp(34.0, 116.0),
+            weight: Weight::BOLD,
+            ..ButtonPreset::Primary.style().text_style
+        };
+        let heading_top = height * 0.12;
+        let _ = self
+            .button_manager
+            .text_renderer
+            .update_text_style_and_position(
+                HEADING_TEXT_ID,
+                HEADING,
+                heading_style.clone(),
+                TextPosition {
+                    x: left,
+                    y: heading_top,
+                    max_width: Some(content_width),
+                    max_height: Some(heading_style.line_height),
+                },
+            );
+
+        let mut style = ButtonPreset::Primary.style();
+        style.text_style.font_size = (22.0 * scale).clamp(14.0, 40.0);
+        style.text_style.line_height = (28.0 * scale).clamp(18.0, 52.0);
+        let row_height = (52.0 * scale).clamp(30.0, 96.0);
+        let mut top = heading_top + heading_style.line_height + 24.0 * scale;
+
+        let rows = self.summaries.len();
+        for index in 0..profiles::MAX_PROFILES {
+            let row_top = top + index.min(rows) as f32 * (row_height + gap);
+            self.place(
+                &row_button_id(index),
+                &style,
+                left,
+                row_top,
+                content_width,
+                row_height,
+            );
+        }
+        top += rows as f32 * (row_height + gap) + 14.0 * scale;
+
+        self.place(
+            FIELD_BUTTON_ID,
+            &style,
+            left,
+            top,
+            content_width,
+            row_height,
+        );
+        if self.entry.is_some() {
+            top += row_height + gap;
+        }
+
+        // The action buttons share one row, with delete kept apart at the far end
+        let actions = [
+            PLAY_BUTTON_ID,
+            NEW_BUTTON_ID,
+            RENAME_BUTTON_ID,
+            BACK_BUTTON_ID,
+            DELETE_BUTTON_ID,
+        ];
+        let action_width = (content_width - gap * actions.len() as f32) / actions.len() as f32;
+        for (index, id) in actions.into_iter().enumerate() {
+            let mut x = left + index as f32 * (action_width + gap);
+            if id == DELETE_BUTTON_ID {
+                x = left + content_width - action_width;
+            }
+            self.place(id, &style, x, top, action_width.max(1.0), row_height);
+        }
+        top += row_height + 20.0 * scale;
+
+        let (message, error) = match (&self.message, &self.entry) {
+            (Some((message, error)), _) => (message.clone(), *error),
+            (None, Some(entry)) => (entry.prompt(), false),
+            (None, None) => (String::new(), false),
+        };
+        let mut message_style = TextStyle {
+            font_size: (20.0 * scale).clamp(14.0, 40.0),
+            line_height: (26.0 * scale).clamp(18.0, 52.0),
+            ..ButtonPreset::Primary.style().text_style
+        };
+        if error {
+            message_style.color = ERROR_COLOR;
+        }
+        let _ = self
+            .button_manager
+            .text_renderer
+            .update_text_style_and_position(
+                MESSAGE_TEXT_ID,
+                &message,
+                message_style.clone(),
+                TextPosition {
+                    x: left,
+                    y: top,
+                    max_width: Some(content_width),
+                    max_height: Some(message_style.line_height * 2.0),
+                },
+            );
+
+        self.button_manager.update_button_positions();
+    }
+
+    /// Sizes and positions one button.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The button's ID
+    /// * `style` - The style to give it; only the text style is kept from it
+    /// * `x` - Left edge
+    /// * `y` - Top edge
+    /// * `width` - Width
+    /// * `height` - Height
+    fn place(&mut self, id: &str, style: &ButtonStyle, x: f32, y: f32, width: f32, height: f32) {
+        if let Some(button) = self.button_manager.get_button_mut(id) {
+            button.style.text_style = style.text_style.clone();
+            button.position =
+                ButtonPosition::new(x, y, width, height).with_anchor(ButtonAnchor::TopLeft);
+        }
+    }
+
+    /// Handles input events for the profile menu.
+    ///
+    /// # Arguments
+    ///
+    /// * `event` - The window event to handle
+    /// * `action` - What the key is bound to in the menu context, for a keyboard event
+    /// * `audio_manager` - The audio manager for playing button click sounds
+    pub fn handle_input(
+        &mut self,
+        event: &WindowEvent,
+        action: Option<Action>,
+        audio_manager: &mut GameAudioManager,
+    ) {
+        if !self.visible {
+            return;
+        }
+
+        self.button_manager.handle_input(event);
+
+        for index in 0..self.summaries.len() {
+            if self.button_manager.is_button_clicked(&row_button_id(index)) {
+                self.selected = Some(index);
+                self.message = None;
+                self.sync_visibility();
+                let _ = audio_manager.play_select();
+            }
+        }
+        let selected = self.selected_name().map(str::to_string);
+        if self.button_manager.is_button_clicked(PLAY_BUTTON_ID)
+            && let Some(name) = selected.clone()
+        {
+            self.last_action = ProfileAction::Switch(name);
+            let _ = audio_manager.play_select();
+        }
+        if self.button_manager.is_button_clicked(NEW_BUTTON_ID) {
+            self.begin_entry(Entry::Create);
+            let _ = audio_manager.play_select();
+        }
+        if self.button_manager.is_button_clicked(RENAME_BUTTON_ID)
+            && let Some(name) = selected.clone()
+        {
+            self.begin_entry(Entry::Rename(name));
+            let _ = audio_manager.play_select();
+        }
+        if self.button_manager.is_button_clicked(DELETE_BUTTON_ID)
+            && let Some(name) = selected
+        {
+            self.begin_entry(Entry::Delete(name));
+            let _ = audio_manager.play_select();
+        }
+        if self.button_manager.is_button_clicked(BACK_BUTTON_ID) {
+            self.last_action = ProfileAction::Back;
+            let _ = audio_manager.play_select();
+        }
+
+        if let WindowEvent::KeyboardInput {
+            event: key_event, ..
+        } = event
+            && self.entry.is_none()
+        {
+            self.handle_list_key(key_event, action);
+        }
+    }
+
+    /// Moves the selection up and down the list, plays as the selected profile on Confirm, and
+    /// goes back on Back.
+    ///
+    /// # Arguments
+    ///
+    /// * `event` - The keyboard event
+    /// * `action` - What the key is bound to in the menu context, if anything
+    fn handle_list_key(&mut self, event: &KeyEvent, action: Option<Action>) {
+        if event.state != ElementState::Pressed {
+            return;
+        }
+        match action {
+            Some(Action::NavigateUp) => self.move_selection(-1),
+            Some(Action::NavigateDown) => self.move_selection(1),
+            Some(Action::Confirm) if !event.repeat => {
+                if let Some(name) = self.selected_name() {
+                    self.last_action = ProfileAction::Switch(name.to_string());
+                }
+            }
+            Some(Action::Back) if !event.repeat && self.active.is_some() => {
+                self.last_action = ProfileAction::Back;
+            }
+            _ => {}
+        }
+    }
+
+    /// Moves the selection up or down the list, stopping at the ends.
+    ///
+    /// # Arguments
+    ///
+    /// * `step` - Rows to move; negative moves up
+    fn move_selection(&mut self, step: isize) {
+        if self.summaries.is_empty() {
+            return;
+        }
+        let last = self.summaries.len() as isize - 1;
+        let current = self.selected.map_or(0, |index| index as isize);
+        self.selected = Some((current + step).clamp(0, last) as usize);
+        self.sync_visibility();
+    }
+
+    /// Handles a key while the text field is open.
+    ///
+    /// Submit (Enter) reports what was typed as an action, leaving the field open until the
+    /// caller closes it with [`end_entry`](Self::end_entry), so a refused name can be
+    /// corrected. Cancel (Escape) closes the field, and other keys edit the text.
+    ///
+    /// # Arguments
+    ///
+    /// * `event` - The keyboard event
+    /// * `action` - What the key is bound to in the text entry context, if anything
+    ///
+    /// # Returns
+    ///
+    /// `true` if the field is open and used the key, so nothing else should act on it
+    pub fn handle_key(&mut self, event: &KeyEvent, action: Option<Action>) -> bool {
+        let Some(entry) = self.entry.clone().filter(|_| self.visible) else {
+            return false;
+        };
+        // While the input method is composing, its keys belong to it
+        if event.state != ElementState::Pressed || self.field.is_composing() {
+            return true;
+        }
+
+        match action {
+            Some(Action::Submit) if !event.repeat => {
+                let typed = self.field.text().to_string();
+                self.last_action = match entry {
+                    Entry::Create => ProfileAction::Create(typed),
+                    Entry::Rename(from) => ProfileAction::Rename { from, to: typed },
+                    Entry::Delete(name) => ProfileAction::Delete {
+                        name,
+                        confirmation: typed,
+                    },
+                };
+            }
+            Some(Action::Cancel) => {
+                self.message = None;
+                self.end_entry();
+            }
+            _ => {
+                self.field.edit_key(event, action);
+                self.message = None;
+                self.refresh_field();
+                self.layout();
+            }
+        }
+        true
+    }
+
+    /// Passes an input method event to the open text field.
+    ///
+    /// # Arguments
+    ///
+    /// * `event` - The input method event from the window
+    ///
+    /// # Returns
+    ///
+    /// `true` if the field is open and took the event
+    pub fn handle_ime(&mut self, event: &Ime) -> bool {
+        if !self.is_typing() {
+            return false;
+        }
+        self.field.apply_ime(event);
+        self.refresh_field();
+        true
+    }
+
+    /// Switches the window's input method on while the field is open, and off again once
+    /// it closes.
+    ///
+    /// Call this every frame; the window is only told when something changed.
+    ///
+    /// # Arguments
+    ///
+    /// * `window` - The window whose input method is switched
+    pub fn sync_ime(&mut self, window: &Window) {
+        let allowed = self.is_typing();
+        if allowed != self.ime_allowed {
+            window.set_ime_allowed(allowed);
+            self.ime_allowed = allowed;
+        }
+    }
+
+    /// Gets the last action that was triggered and resets it to `None`.
+    ///
+    /// # Returns
+    ///
+    /// The last `ProfileAction` that was triggered, or `None` if no action occurred
+    pub fn get_last_action(&mut self) -> ProfileAction {
+        std::mem::replace(&mut self.last_action, ProfileAction::None)
+    }
+
+    /// Handles window resize events by laying the menu out again.
+    ///
+    /// # Arguments
+    ///
+    /// * `queue` - The WGPU command queue
+    /// * `resolution` - The new window resolution
+    pub fn resize(&mut self, queue: &Queue, resolution: Resolution) {
+        self.button_manager.resize(queue, resolution);
+        self.window_size = PhysicalSize::new(resolution.width, resolution.height);
+        self.layout();
+    }
+
+    /// Prepares the profile menu for rendering by updating text layout.
+    ///
+    /// # Arguments
+    ///
+    /// * `device` - The WGPU device
+    /// * `queue` - The WGPU command queue
+    /// * `surface_config` - The surface configuration
+    ///
+    /// # Returns
+    ///
+    /// `Ok(())` on success, or a `PrepareError` if text preparation fails
+    pub fn prepare(
+        &mut self,
+        device: &Device,
+        queue: &Queue,
+        surface_config: &SurfaceConfiguration,
+    ) -> Result<(), glyphon::PrepareError> {
+        self.button_manager.prepare(device, queue, surface_config)
+    }
+
+    /// Renders the rows, buttons and all text.
+    ///
+    /// # Arguments
+    ///
+    /// * `device` - The WGPU device
+    /// * `render_pass` - The render pass to draw into
+    ///
+    /// # Returns
+    ///
+    /// `Ok(())` on success, or a `RenderError` if rendering fails
+    pub fn render(
+        &mut self,
+        device: &Device,
+        render_pass: &mut RenderPass,
+    ) -> Result<(), glyphon::RenderError> {
+        self.button_manager.render(device, render_pass)
+    }
+}
+
+/// Returns the button ID of one row of the profile list.
+///
+/// # Arguments
+///
+/// * `index` - The row, from the top
+fn row_button_id(index: usize) -> String {
+    format!("profile_row_{}", index)
+}
+
+/// Returns an empty text field sized for a profile name.
+fn new_field() -> SeedField {
+    SeedField::new(None).with_max_length(profiles::MAX_NAME_LENGTH)
+}
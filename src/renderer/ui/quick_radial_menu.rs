@@ -0,0 +1,213 @@
+//! Quick-settings radial menu
+//!
+//! This module draws the ring of quick-settings slots shown while the quick-settings key is
+//! held. Each slot is a button placed around the center of the screen; the slot the virtual
+//! pointer selects is drawn hovered. The selection itself lives in
+//! [`QuickRadial`](crate::game::quick_radial::QuickRadial), since the cursor is captured
+//! during play and never reaches the buttons.
+
+use crate::game::quick_radial::{QuickRadialConfig, slot_direction};
+use crate::renderer::gpu_memory::GpuMemoryTracker;
+use crate::renderer::ui::button::{
+    Button, ButtonAnchor, ButtonManager, ButtonPosition, ButtonPreset, TextAlign,
+};
+use glyphon::Resolution;
+use wgpu::{Device, Queue, RenderPass, SurfaceConfiguration};
+use winit::dpi::PhysicalSize;
+
+/// The slot buttons drawn around the center of the screen while the radial is open.
+///
+/// The buttons scale with the window height the same way the game over buttons do.
+pub struct QuickRadialMenu {
+    /// Manages the slot buttons
+    pub button_manager: ButtonManager,
+    /// Number of slots, one button each
+    slot_count: usize,
+    /// Current window size, used to lay out the ring
+    window_size: PhysicalSize<u32>,
+    /// Whether the radial menu is currently visible
+    pub visible: bool,
+}
+
+impl QuickRadialMenu {
+    /// Creates a new quick-settings radial menu.
+    ///
+    /// # Arguments
+    ///
+    /// * `device` - The WGPU device for rendering
+    /// * `queue` - The WGPU command queue
+    /// * `surface_format` - The surface texture format
+    /// * `window_size` - The window size, for sizing calculations
+    /// * `gpu_memory` - Tracker the menu's glyph textures are counted in
+    /// * `config` - The actions laid out around the ring
+    ///
+    /// # Returns
+    ///
+    /// A new, hidden `QuickRadialMenu` with one button per slot
+    pub fn new(
+        device: &Device,
+        queue: &Queue,
+        surface_format: wgpu::TextureFormat,
+        window_size: PhysicalSize<u32>,
+        gpu_memory: &GpuMemoryTracker,
+        config: &QuickRadialConfig,
+    ) -> Self {
+        let mut button_manager =
+            ButtonManager::new(device, queue, surface_format, window_size, gpu_memory);
+        let text_style = Self::scaled_text_style(window_size.height as f32);
+        for (index, action) in config.slots.iter().enumerate() {
+            button_manager.add_button(
+                Button::secondary(&Self::slot_id(index), action.label())
+                    .with_text_style(text_style.clone())
+                    .with_text_align(TextAlign::Center),
+            );
+        }
+
+        let mut menu = Self {
+            button_manager,
+            slot_count: config.slots.len(),
+            window_size,
+            visible: true,
+        };
+        menu.layout_buttons();
+        menu.hide();
+        menu
+    }
+
+    /// Returns the button ID of a slot.
+    fn slot_id(index: usize) -> String {
+        format!("quick_radial_{}", index)
+    }
+
+    /// Creates a scaled text style based on the window height.
+    ///
+    /// # Arguments
+    ///
+    /// * `window_height` - The current window height in pixels
+    ///
+    /// # Returns
+    ///
+    /// A `TextStyle` with font size and line height scaled from 1080p
+    fn scaled_text_style(window_height: f32) -> crate::renderer::text::TextStyle {
+        let scale = (window_height / 1080.0).clamp(0.7, 2.0);
+
+        crate::renderer::text::TextStyle {
+            font_size: (22.0 * scale).clamp(14.0, 36.0),
+            line_height: (28.0 * scale).clamp(20.0, 46.0),
+            ..ButtonPreset::Secondary.style().text_style
+        }
+    }
+
+    /// Sizes and places the slot buttons on a ring around the center of the window.
+    fn layout_buttons(&mut self) {
+        let scale = (self.window_size.height as f32 / 1080.0).clamp(0.7, 2.0);
+        let button_width = 190.0 * scale;
+        let button_height = 52.0 * scale;
+        // Far enough out that the side slots clear the reticle and each other
+        let radius = 200.0 * scale;
+        let center_x = self.window_size.width as f32 / 2.0;
+        let center_y = self.window_size.height as f32 / 2.0;
+        let text_style = Self::scaled_text_style(self.window_size.height as f32);
+
+        for index in 0..self.slot_count {
+            let [dx, dy] = slot_direction(index, self.slot_count);
+            if let Some(button) = self.button_manager.get_button_mut(&Self::slot_id(index)) {
+                button.style.text_style = text_style.clone();
+                button.position = ButtonPosition::new(
+                    center_x + dx * radius,
+                    center_y + dy * radius,
+                    button_width,
+                    button_height,
+                )
+                .with_anchor(ButtonAnchor::Center);
+            }
+        }
+
+        self.button_manager.update_button_positions();
+    }
+
+    /// Highlights the selected slot, or none.
+    ///
+    /// # Arguments
+    ///
+    /// * `selected` - The slot the pointer is on, if any
+    pub fn set_selected(&mut self, selected: Option<usize>) {
+        self.button_manager.focused_button = selected.map(Self::slot_id);
+        self.button_manager.update_button_states();
+    }
+
+    /// Shows the radial menu with no slot highlighted.
+    pub fn show(&mut self) {
+        self.visible = true;
+        // The real cursor is captured, so keep it from hovering a slot
+        self.button_manager.mouse_position = (-1.0, -1.0);
+        for button in self.button_manager.buttons.values_mut() {
+            button.set_visible(true);
+        }
+        self.set_selected(None);
+    }
+
+    /// Hides the radial menu and its buttons.
+    pub fn hide(&mut self) {
+        self.visible = false;
+        self.button_manager.focused_button = None;
+        for button in self.button_manager.buttons.values_mut() {
+            button.set_visible(false);
+        }
+    }
+
+    /// Returns whether the radial menu is currently visible.
+    pub fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    /// Handles window resize events by updating button positions and text rendering.
+    ///
+    /// # Arguments
+    ///
+    /// * `queue` - The WGPU command queue
+    /// * `resolution` - The new window resolution
+    pub fn resize(&mut self, queue: &Queue, resolution: Resolution) {
+        self.button_manager.resize(queue, resolution);
+        self.window_size = PhysicalSize::new(resolution.width, resolution.height);
+        self.layout_buttons();
+    }
+
+    /// Prepares the radial menu for rendering by updating text layout.
+    ///
+    /// # Arguments
+    ///
+    /// * `device` - The WGPU device
+    /// * `queue` - The WGPU command queue
+    /// * `surface_config` - The surface configuration
+    ///
+    /// # Returns
+    ///
+    /// `Ok(())` on success, or a `PrepareError` if text preparation fails
+    pub fn prepare(
+        &mut self,
+        device: &Device,
+        queue: &Queue,
+        surface_config: &SurfaceConfiguration,
+    ) -> Result<(), glyphon::PrepareError> {
+        self.button_manager.prepare(device, queue, surface_config)
+    }
+
+    /// Renders the radial menu to the screen.
+    ///
+    /// # Arguments
+    ///
+    /// * `device` - The WGPU device
+    /// * `render_pass` - The render pass to draw into
+    ///
+    /// # Returns
+    ///
+    /// `Ok(())` on success, or a `RenderError` if rendering fails
+    pub fn render(
+        &mut self,
+        device: &Device,
+        render_pass: &mut RenderPass,
+    ) -> Result<(), glyphon::RenderError> {
+        self.button_manager.render(device, render_pass)
+    }
+}
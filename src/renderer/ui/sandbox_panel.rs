@@ -0,0 +1,408 @@
+//! Practice sandbox settings panel.
+//!
+//! This module provides [`SandboxPanel`], an egui window drawn over the sandbox that edits the
+//! [`SandboxSettings`] the next maze is generated with. The mouse looks around while exploring,
+//! so the panel is driven from the keyboard: Tab switches between look mode and UI mode, and in
+//! UI mode, where the keys follow the menu bindings, Up and Down pick a row, Left and Right
+//! change it, and Enter or Space activates it. Escape leaves the sandbox from either mode. In UI mode the cursor is free, so the panel's
+//! widgets can be clicked as well.
+
+use crate::game::bindings::Action;
+use crate::game::codex::MazeAlgorithm;
+use crate::game::keys::GameKey;
+use crate::game::sandbox::{
+    MAX_SANDBOX_SIZE, MIN_SANDBOX_SIZE, SandboxControl, SandboxFocus, SandboxSession,
+    SandboxSettings,
+};
+use crate::game::seed;
+use crate::renderer::color;
+use wgpu::{CommandEncoder, Device, Queue, TextureView};
+use winit::event::{ElementState, KeyEvent, WindowEvent};
+use winit::window::Window;
+
+/// Actions that can be triggered from the sandbox panel
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SandboxAction {
+    /// Generate a new maze from the panel's settings
+    Generate,
+    /// Use the panel's seed for the next regular run
+    UseSeed,
+    /// Leave the sandbox for the title screen
+    Leave,
+    /// Tab switched between look mode and UI mode
+    FocusChanged,
+    /// No action has been taken
+    None,
+}
+
+/// An egui overlay for changing the sandbox's maze settings.
+pub struct SandboxPanel {
+    /// The egui context holding UI state between frames
+    context: egui::Context,
+    /// Translates winit events into egui input
+    winit_state: egui_winit::State,
+    /// Draws egui output into a wgpu render pass
+    renderer: egui_wgpu::Renderer,
+    /// The last action that was triggered by the panel
+    last_action: SandboxAction,
+}
+
+impl SandboxPanel {
+    /// Creates a new sandbox panel.
+    ///
+    /// # Arguments
+    ///
+    /// * `device` - The WGPU device for rendering
+    /// * `surface_format` - The surface texture format; the panel renders with its
+    ///   non-sRGB counterpart (see [`color::ui_format`])
+    /// * `window` - The window the panel receives input from
+    ///
+    /// # Returns
+    ///
+    /// A new `SandboxPanel`, drawn whenever a sandbox is open
+    pub fn new(device: &Device, surface_format: wgpu::TextureFormat, window: &Window) -> Self {
+        let context = egui::Context::default();
+        let winit_state = egui_winit::State::new(
+            context.clone(),
+            egui::ViewportId::ROOT,
+            window,
+            Some(window.scale_factor() as f32),
+            None,
+            Some(device.limits().max_texture_dimension_2d as usize),
+        );
+        let renderer =
+            egui_wgpu::Renderer::new(device, color::ui_format(surface_format), None, 1, false);
+
+        Self {
+            context,
+            winit_state,
+            renderer,
+            last_action: SandboxAction::None,
+        }
+    }
+
+    /// Returns the last action triggered by the panel and clears it.
+    pub fn take_action(&mut self) -> SandboxAction {
+        std::mem::replace(&mut self.last_action, SandboxAction::None)
+    }
+
+    /// Returns whether the panel wants the pointer: in UI mode, with the cursor over it or
+    /// dragging in it.
+    ///
+    /// # Arguments
+    ///
+    /// * `session` - The open sandbox
+    pub fn wants_pointer(&self, session: &SandboxSession) -> bool {
+        session.focus == SandboxFocus::Ui && self.context.wants_pointer_input()
+    }
+
+    /// Returns whether the panel wants the keyboard, which it does for every key in UI mode.
+    ///
+    /// Tab and Escape are taken in look mode too, but only as they arrive; see
+    /// [`Self::handle_input`].
+    ///
+    /// # Arguments
+    ///
+    /// * `session` - The open sandbox
+    pub fn wants_keyboard(&self, session: &SandboxSession) -> bool {
+        session.focus == SandboxFocus::Ui
+    }
+
+    /// Handles a window event for the sandbox.
+    ///
+    /// Tab and Escape are always taken. In look mode everything else is left to the game;
+    /// in UI mode the panel takes every key, and mouse events go to egui.
+    ///
+    /// # Arguments
+    ///
+    /// * `window` - The window the event was received from
+    /// * `event` - The window event to process
+    /// * `action` - What the key is bound to, in the gameplay context in look mode and the
+    ///   menu context in UI mode
+    /// * `session` - The open sandbox, whose settings and focus the keys change
+    ///
+    /// # Returns
+    ///
+    /// `true` if the panel consumed the event and the game should ignore it
+    pub fn handle_input(
+        &mut self,
+        window: &Window,
+        event: &WindowEvent,
+        action: Option<Action>,
+        session: &mut SandboxSession,
+    ) -> bool {
+        let key_event = match event {
+            WindowEvent::KeyboardInput { event, .. } => Some(event),
+            _ => None,
+        };
+        let pressed =
+            key_event.is_some_and(|key| key.state == ElementState::Pressed && !key.repeat);
+        // In look mode Tab arrives as the map key; the sandbox has no map, so it switches to
+        // the panel instead
+        match action.filter(|_| pressed) {
+            Some(Action::SwitchFocus | Action::Game(GameKey::MapView)) => {
+                session.toggle_focus();
+                self.last_action = SandboxAction::FocusChanged;
+                return true;
+            }
+            Some(Action::Back | Action::Game(GameKey::Escape)) => {
+                self.last_action = SandboxAction::Leave;
+                return true;
+            }
+            _ => {}
+        }
+
+        if session.focus == SandboxFocus::Look {
+            return false;
+        }
+        match key_event {
+            Some(key_event) => {
+                self.handle_key(key_event, action, session);
+                true
+            }
+            None => self.winit_state.on_window_event(window, event).consumed,
+        }
+    }
+
+    /// Moves through and changes the panel's rows from the keyboard.
+    ///
+    /// # Arguments
+    ///
+    /// * `event` - The keyboard event
+    /// * `action` - What the key is bound to in the menu context, if anything
+    /// * `session` - The open sandbox
+    fn handle_key(
+        &mut self,
+        event: &KeyEvent,
+        action: Option<Action>,
+        session: &mut SandboxSession,
+    ) {
+        if event.state != ElementState::Pressed {
+            return;
+        }
+        match action {
+            Some(Action::NavigateUp) => session.select(-1),
+            Some(Action::NavigateDown) => session.select(1),
+            Some(Action::NavigateLeft) => session.adjust(-1),
+            Some(Action::NavigateRight) => session.adjust(1),
+            Some(Action::Confirm) if !event.repeat => {
+                self.last_action = Self::activate(session);
+            }
+            _ => {}
+        }
+    }
+
+    /// Activates the selected row: buttons are pressed, the seed is rerolled, and the
+    /// algorithm and switches flip.
+    ///
+    /// # Arguments
+    ///
+    /// * `session` - The open sandbox
+    ///
+    /// # Returns
+    ///
+    /// The action the row triggers, if it is a button
+    fn activate(session: &mut SandboxSession) -> SandboxAction {
+        match session.selected_control() {
+            SandboxControl::Generate => SandboxAction::Generate,
+            SandboxControl::UseSeed => SandboxAction::UseSeed,
+            SandboxControl::Leave => SandboxAction::Leave,
+            SandboxControl::Seed => {
+                session.settings.seed = rand::random();
+                SandboxAction::None
+            }
+            _ => {
+                session.adjust(1);
+                SandboxAction::None
+            }
+        }
+    }
+
+    /// Builds the panel UI and records its draw commands.
+    ///
+    /// # Arguments
+    ///
+    /// * `device` - The WGPU device for rendering
+    /// * `queue` - The WGPU command queue
+    /// * `encoder` - The command encoder for the current frame
+    /// * `surface_view` - A view of the surface in [`color::ui_format`] to draw the panel onto
+    /// * `window` - The window the panel is shown in
+    /// * `session` - The open sandbox, whose settings the panel edits
+    pub fn render(
+        &mut self,
+        device: &Device,
+        queue: &Queue,
+        encoder: &mut CommandEncoder,
+        surface_view: &TextureView,
+        window: &Window,
+        session: &mut SandboxSession,
+    ) {
+        let raw_input = self.winit_state.take_egui_input(window);
+        let mut action = SandboxAction::None;
+        let full_output = self.context.run(raw_input, |ctx| {
+            action = Self::build_ui(ctx, session);
+        });
+        if action != SandboxAction::None {
+            self.last_action = action;
+        }
+        self.winit_state
+            .handle_platform_output(window, full_output.platform_output);
+
+        let paint_jobs = self
+            .context
+            .tessellate(full_output.shapes, full_output.pixels_per_point);
+        let window_size = window.inner_size();
+        let screen_descriptor = egui_wgpu::ScreenDescriptor {
+            size_in_pixels: [window_size.width, window_size.height],
+            pixels_per_point: full_output.pixels_per_point,
+        };
+
+        for (id, image_delta) in &full_output.textures_delta.set {
+            self.renderer
+                .update_texture(device, queue, *id, image_delta);
+        }
+        self.renderer
+            .update_buffers(device, queue, encoder, &paint_jobs, &screen_descriptor);
+
+        {
+            let mut render_pass = encoder
+                .begin_render_pass(&wgpu::RenderPassDescriptor {
+                    label: Some("Sandbox Panel Render Pass"),
+                    color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                        view: surface_view,
+                        resolve_target: None,
+                        ops: wgpu::Operations {
+                            load: wgpu::LoadOp::Load,
+                            store: wgpu::StoreOp::Store,
+                        },
+                    })],
+                    depth_stencil_attachment: None,
+                    timestamp_writes: None,
+                    occlusion_query_set: None,
+                })
+                .forget_lifetime();
+            self.renderer
+                .render(&mut render_pass, &paint_jobs, &screen_descriptor);
+        }
+
+        for id in &full_output.textures_delta.free {
+            self.renderer.free_texture(id);
+        }
+    }
+
+    /// Lays out the panel widgets.
+    ///
+    /// The widgets only respond in UI mode; in look mode the panel just shows the settings.
+    ///
+    /// # Returns
+    ///
+    /// The action of a button clicked this frame
+    fn build_ui(ctx: &egui::Context, session: &mut SandboxSession) -> SandboxAction {
+        let mut action = SandboxAction::None;
+        let editing = session.focus == SandboxFocus::Ui;
+        let selected = session.selected_control();
+        let settings: &mut SandboxSettings = &mut session.settings;
+
+        egui::Window::new("Sandbox")
+            .default_pos([20.0, 20.0])
+            .resizable(false)
+            .show(ctx, |ui| {
+                ui.label(if editing {
+                    "Tab: back to exploring"
+                } else {
+                    "Tab: edit the maze"
+                });
+                ui.separator();
+
+                ui.add_enabled_ui(editing, |ui| {
+                    let row = |control| editing && selected == control;
+
+                    control_row(ui, row(SandboxControl::Width), |ui| {
+                        ui.add(
+                            egui::Slider::new(
+                                &mut settings.width,
+                                MIN_SANDBOX_SIZE..=MAX_SANDBOX_SIZE,
+                            )
+                            .text("Width"),
+                        );
+                    });
+                    control_row(ui, row(SandboxControl::Height), |ui| {
+                        ui.add(
+                            egui::Slider::new(
+                                &mut settings.height,
+                                MIN_SANDBOX_SIZE..=MAX_SANDBOX_SIZE,
+                            )
+                            .text("Height"),
+                        );
+                    });
+                    control_row(ui, row(SandboxControl::Algorithm), |ui| {
+                        for algorithm in MazeAlgorithm::ALL {
+                            ui.selectable_value(
+                                &mut settings.algorithm,
+                                algorithm,
+                                algorithm.name(),
+                            );
+                        }
+                    });
+                    control_row(ui, row(SandboxControl::Seed), |ui| {
+                        if ui.small_button("-").clicked() {
+                            settings.seed = settings.seed.wrapping_sub(1);
+                        }
+                        ui.monospace(seed::format_seed(settings.seed));
+                        if ui.small_button("+").clicked() {
+                            settings.seed = settings.seed.wrapping_add(1);
+                        }
+                        if ui.button("Random").clicked() {
+                            settings.seed = rand::random();
+                        }
+                    });
+                    control_row(ui, row(SandboxControl::Enemy), |ui| {
+                        ui.checkbox(&mut settings.enemy, "Enemy");
+                    });
+                    control_row(ui, row(SandboxControl::Fog), |ui| {
+                        ui.checkbox(&mut settings.fog, "Fog");
+                    });
+                    control_row(ui, row(SandboxControl::Animate), |ui| {
+                        ui.checkbox(&mut settings.animate, "Show carving");
+                    });
+
+                    ui.separator();
+                    for (control, label, pressed) in [
+                        (
+                            SandboxControl::Generate,
+                            "Generate",
+                            SandboxAction::Generate,
+                        ),
+                        (
+                            SandboxControl::UseSeed,
+                            "Use seed for next run",
+                            SandboxAction::UseSeed,
+                        ),
+                        (SandboxControl::Leave, "Back to title", SandboxAction::Leave),
+                    ] {
+                        control_row(ui, row(control), |ui| {
+                            if ui.button(label).clicked() {
+                                action = pressed;
+                            }
+                        });
+                    }
+                });
+            });
+        action
+    }
+}
+
+/// Lays out one row of the panel, marked while it is selected from the keyboard.
+///
+/// # Arguments
+///
+/// * `ui` - The panel's UI
+/// * `selected` - Whether the keyboard selection is on this row
+/// * `add_contents` - Adds the row's widgets
+fn control_row(ui: &mut egui::Ui, selected: bool, add_contents: impl FnOnce(&mut egui::Ui)) {
+    ui.horizontal(|ui| {
+        let marker = egui::RichText::new(if selected { ">" } else { " " }).monospace();
+        ui.label(marker);
+        add_contents(ui);
+    });
+}
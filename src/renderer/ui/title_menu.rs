@@ -0,0 +1,935 @@
+//! Title Menu
+//!
+//! This module provides the "Daily Challenge" button, the seed field, the challenge code
+//! field, the "Maze Codex" button, the "Sandbox" button, the hardcore toggle, the "Continue"
+//! button and the "Switch Profile" button on the title screen. Clicking anywhere else on the
+//! title screen still starts a regular run.
+//!
+//! The "Continue" button is only shown while there is a saved run to resume, and names the
+//! level it resumes at.
+//!
+//! The seed field shows the seed chosen for the next run. Clicking it or pressing S opens it
+//! for typing; Enter keeps the typed seed and Escape goes back to a random one. While it is
+//! open it takes every key, and it turns red while the text can't be read as a seed.
+//! The window's input method is switched on while the field is open, with its candidate
+//! window at the caret, and text still being composed is underlined.
+//!
+//! The challenge code field works the same way for a [`Challenge`] code pasted from a
+//! friend. Enter reads the code and, if it is valid, sets up the next run from it; a code
+//! that can't be read keeps the field open and says why below the menu.
+
+use crate::game::audio::GameAudioManager;
+use crate::game::bindings::Action;
+use crate::game::challenge::{self, Challenge, ChallengeError};
+use crate::game::input::MenuNav;
+use crate::game::seed::{self, SeedError, SeedField};
+use crate::renderer::gpu_memory::GpuMemoryTracker;
+use crate::renderer::rectangle::{Rectangle, RectangleRenderer};
+use crate::renderer::text::{TextPosition, TextStyle};
+use crate::renderer::ui::button::{
+    Button, ButtonAnchor, ButtonManager, ButtonPosition, ButtonPreset, TextAlign,
+};
+use glyphon::{Color, Resolution};
+use wgpu::{Device, Queue, RenderPass, SurfaceConfiguration};
+use winit::dpi::{PhysicalPosition, PhysicalSize};
+use winit::event::{ElementState, Ime, KeyEvent, WindowEvent};
+use winit::keyboard::Key;
+use winit::window::Window;
+
+/// Color of the line under input method text that hasn't been committed yet.
+const PREEDIT_UNDERLINE_COLOR: [f32; 4] = [1.0, 1.0, 1.0, 0.85];
+
+/// Text buffer ID of the active profile's name, above the profile button.
+const PROFILE_LABEL_TEXT_ID: &str = "title_profile_label";
+
+/// Color of the active profile's name, kept quiet next to the buttons.
+const PROFILE_LABEL_COLOR: Color = Color::rgb(150, 146, 160);
+
+/// Actions that can be triggered from the title menu
+#[derive(Debug, Clone, PartialEq)]
+pub enum TitleAction {
+    /// Start today's daily challenge
+    StartDaily,
+    /// Open the seed field for typing
+    EditSeed,
+    /// Open the challenge code field for typing
+    EditCode,
+    /// Open the maze codex screen
+    OpenCodex,
+    /// Open the practice sandbox
+    OpenSandbox,
+    /// Turn hardcore mode on or off for the next run
+    ToggleHardcore,
+    /// Open the profile picker
+    SwitchProfile,
+    /// Resume the saved run
+    Continue,
+    /// No action has been taken
+    None,
+}
+
+/// Which title screen field is open for typing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FieldTarget {
+    /// The seed field
+    Seed,
+    /// The challenge code field
+    Code,
+}
+
+impl FieldTarget {
+    /// Returns the ID of the field's button.
+    fn button_id(self) -> &'static str {
+        match self {
+            FieldTarget::Seed => "title_seed",
+            FieldTarget::Code => "title_code",
+        }
+    }
+
+    /// Returns the label shown before the typed text.
+    fn prefix(self) -> &'static str {
+        match self {
+            FieldTarget::Seed => "Seed: ",
+            FieldTarget::Code => "Code: ",
+        }
+    }
+}
+
+/// The buttons shown on the title screen.
+///
+/// The daily challenge button sits above the "Click anywhere" subtitle, right-aligned
+/// with it, with the seed field, the challenge code field, the codex button, the sandbox
+/// button, the hardcore toggle and the continue button stacked above it. The profile button sits level with the
+/// daily challenge button in the bottom-left corner, out of the way of the title text. All
+/// of them scale with the window size the same way the other menus do.
+pub struct TitleMenu {
+    /// Manages the title screen buttons
+    pub button_manager: ButtonManager,
+    /// Whether the title menu is currently visible
+    pub visible: bool,
+    /// The last action that was triggered by the menu
+    pub last_action: TitleAction,
+    /// The seed or challenge code being typed, while its field is open
+    seed_field: Option<SeedField>,
+    /// Which field [`seed_field`](Self::seed_field) belongs to
+    field_target: FieldTarget,
+    /// Why the last challenge code entered couldn't be read, until the field closes
+    code_error: Option<ChallengeError>,
+    /// Draws the underline below input method text still being composed
+    preedit_underline: RectangleRenderer,
+    /// Whether the window's input method is switched on for the seed field
+    ime_allowed: bool,
+    /// Where the input method's candidate window was last placed, as `[x, y, width, height]`
+    ime_cursor_area: Option<[f32; 4]>,
+    /// The line naming the active profile, empty while there is none
+    profile_label: String,
+    /// Level the saved run resumes at, or `None` to hide the continue button
+    continue_level: Option<i32>,
+}
+
+impl TitleMenu {
+    /// Creates a new title menu instance.
+    ///
+    /// # Arguments
+    ///
+    /// * `device` - The WGPU device for rendering
+    /// * `queue` - The WGPU command queue
+    /// * `surface_format` - The surface texture format
+    /// * `window_size` - The window size, for sizing calculations
+    /// * `gpu_memory` - Tracker the menu's icon and glyph textures are counted in
+    ///
+    /// # Returns
+    ///
+    /// A new, visible `TitleMenu` with its button configured and positioned
+    pub fn new(
+        device: &Device,
+        queue: &Queue,
+        surface_format: wgpu::TextureFormat,
+        window_size: PhysicalSize<u32>,
+        gpu_memory: &GpuMemoryTracker,
+    ) -> Self {
+        let mut button_manager =
+            ButtonManager::new(device, queue, surface_format, window_size, gpu_memory);
+        Self::create_menu_buttons(&mut button_manager, window_size);
+        Self::layout_buttons(&mut button_manager, window_size);
+
+        let mut preedit_underline = RectangleRenderer::new(device, surface_format);
+        preedit_underline.resize(window_size.width as f32, window_size.height as f32);
+
+        let mut menu = Self {
+            button_manager,
+            visible: false,
+            last_action: TitleAction::None,
+            seed_field: None,
+            field_target: FieldTarget::Seed,
+            code_error: None,
+            preedit_underline,
+            ime_allowed: false,
+            ime_cursor_area: None,
+            profile_label: String::new(),
+            continue_level: None,
+        };
+        menu.show();
+        menu
+    }
+
+    /// Creates a scaled text style based on the window height.
+    ///
+    /// # Arguments
+    ///
+    /// * `window_height` - The current window height in pixels
+    ///
+    /// # Returns
+    ///
+    /// A `TextStyle` with font size and line height scaled from 1080p
+    fn scaled_text_style(window_height: f32) -> crate::renderer::text::TextStyle {
+        let reference_height = 1080.0;
+        let scale = (window_height / reference_height).clamp(0.7, 2.0);
+
+        crate::renderer::text::TextStyle {
+            font_size: (28.0 * scale).clamp(16.0, 44.0),
+            line_height: (36.0 * scale).clamp(24.0, 56.0),
+            ..ButtonPreset::Primary.style().text_style
+        }
+    }
+
+    /// Creates the quiet text style of the active profile's name.
+    ///
+    /// # Arguments
+    ///
+    /// * `window_height` - The current window height in pixels
+    fn profile_label_style(window_height: f32) -> TextStyle {
+        let scale = (window_height / 1080.0).clamp(0.7, 2.0);
+        TextStyle {
+            font_size: (20.0 * scale).clamp(13.0, 36.0),
+            line_height: (26.0 * scale).clamp(17.0, 46.0),
+            color: PROFILE_LABEL_COLOR,
+            ..ButtonPreset::Primary.style().text_style
+        }
+    }
+
+    /// Adds the daily challenge button, the seed field, the challenge code field, the codex
+    /// button, the sandbox button, the hardcore toggle, the continue button and the profile
+    /// button to the button manager.
+    ///
+    /// # Arguments
+    ///
+    /// * `button_manager` - The button manager to add buttons to
+    /// * `window_size` - The current window size, used to scale the button text
+    fn create_menu_buttons(button_manager: &mut ButtonManager, window_size: PhysicalSize<u32>) {
+        let daily_button = Button::primary("title_daily", "Daily Challenge")
+            .with_text_style(Self::scaled_text_style(window_size.height as f32))
+            .with_text_align(TextAlign::Center);
+
+        button_manager.add_button(daily_button);
+
+        let seed_button = Button::primary("title_seed", "Seed: random")
+            .with_text_style(Self::scaled_text_style(window_size.height as f32))
+            .with_text_align(TextAlign::Center);
+
+        button_manager.add_button(seed_button);
+
+        let code_button = Button::primary("title_code", "Challenge Code")
+            .with_text_style(Self::scaled_text_style(window_size.height as f32))
+            .with_text_align(TextAlign::Center);
+
+        button_manager.add_button(code_button);
+
+        let codex_button = Button::primary("title_codex", "Maze Codex")
+            .with_text_style(Self::scaled_text_style(window_size.height as f32))
+            .with_text_align(TextAlign::Center);
+
+        button_manager.add_button(codex_button);
+
+        let sandbox_button = Button::primary("title_sandbox", "Sandbox")
+            .with_text_style(Self::scaled_text_style(window_size.height as f32))
+            .with_text_align(TextAlign::Center);
+
+        button_manager.add_button(sandbox_button);
+
+        let hardcore_button = Button::primary("title_hardcore", "Hardcore: Off")
+            .with_text_style(Self::scaled_text_style(window_size.height as f32))
+            .with_text_align(TextAlign::Center);
+
+        button_manager.add_button(hardcore_button);
+
+        let continue_button = Button::primary("title_continue", "Continue")
+            .with_text_style(Self::scaled_text_style(window_size.height as f32))
+            .with_text_align(TextAlign::Center);
+
+        button_manager.add_button(continue_button);
+
+        let profile_button = Button::primary("title_profile", "Switch Profile")
+            .with_text_style(Self::scaled_text_style(window_size.height as f32))
+            .with_text_align(TextAlign::Center);
+
+        button_manager.add_button(profile_button);
+        button_manager
+            .text_renderer
+            .create_text_buffer(PROFILE_LABEL_TEXT_ID, "", None, None);
+    }
+
+    /// Sizes and positions the buttons for the given window size.
+    ///
+    /// # Arguments
+    ///
+    /// * `button_manager` - The button manager holding the title buttons
+    /// * `window_size` - The current window size for positioning calculations
+    fn layout_buttons(button_manager: &mut ButtonManager, window_size: PhysicalSize<u32>) {
+        let reference_height = 1080.0;
+        let scale = (window_size.height as f32 / reference_height).clamp(0.7, 2.0);
+
+        let button_width = (window_size.width as f32 * 0.18 * scale).clamp(200.0, 400.0);
+        let button_height = (window_size.height as f32 * 0.07 * scale).clamp(32.0, 110.0);
+        // Right edge lines up with the title text, which keeps a 200px margin
+        let x = window_size.width as f32 - 200.0 - button_width;
+        // Above the subtitle, which keeps a 100px margin from the bottom
+        let y = window_size.height as f32 - 100.0 - 80.0 * scale - button_height;
+
+        let text_style = Self::scaled_text_style(window_size.height as f32);
+        if let Some(button) = button_manager.get_button_mut("title_daily") {
+            button.style.text_style = text_style.clone();
+            button.position =
+                ButtonPosition::new(x.max(0.0), y.max(0.0), button_width, button_height)
+                    .with_anchor(ButtonAnchor::TopLeft);
+        }
+        // The seed field sits just above the daily challenge button
+        let seed_y = y - button_height - 16.0 * scale;
+        if let Some(button) = button_manager.get_button_mut("title_seed") {
+            button.style.text_style = text_style.clone();
+            button.position =
+                ButtonPosition::new(x.max(0.0), seed_y.max(0.0), button_width, button_height)
+                    .with_anchor(ButtonAnchor::TopLeft);
+        }
+        // The challenge code field sits above the seed field
+        let code_y = seed_y - button_height - 16.0 * scale;
+        if let Some(button) = button_manager.get_button_mut("title_code") {
+            button.style.text_style = text_style.clone();
+            button.position =
+                ButtonPosition::new(x.max(0.0), code_y.max(0.0), button_width, button_height)
+                    .with_anchor(ButtonAnchor::TopLeft);
+        }
+        // The codex button sits above the challenge code field
+        let codex_y = code_y - button_height - 16.0 * scale;
+        if let Some(button) = button_manager.get_button_mut("title_codex") {
+            button.style.text_style = text_style.clone();
+            button.position =
+                ButtonPosition::new(x.max(0.0), codex_y.max(0.0), button_width, button_height)
+                    .with_anchor(ButtonAnchor::TopLeft);
+        }
+        // The sandbox button sits above the codex button
+        let sandbox_y = codex_y - button_height - 16.0 * scale;
+        if let Some(button) = button_manager.get_button_mut("title_sandbox") {
+            button.style.text_style = text_style.clone();
+            button.position =
+                ButtonPosition::new(x.max(0.0), sandbox_y.max(0.0), button_width, button_height)
+                    .with_anchor(ButtonAnchor::TopLeft);
+        }
+        // The hardcore toggle sits above the sandbox button
+        let hardcore_y = sandbox_y - button_height - 16.0 * scale;
+        if let Some(button) = button_manager.get_button_mut("title_hardcore") {
+            button.style.text_style = text_style.clone();
+            button.position =
+                ButtonPosition::new(x.max(0.0), hardcore_y.max(0.0), button_width, button_height)
+                    .with_anchor(ButtonAnchor::TopLeft);
+        }
+        // The continue button tops the stack, above the hardcore toggle
+        let continue_y = hardcore_y - button_height - 16.0 * scale;
+        if let Some(button) = button_manager.get_button_mut("title_continue") {
+            button.style.text_style = text_style.clone();
+            button.position =
+                ButtonPosition::new(x.max(0.0), continue_y.max(0.0), button_width, button_height)
+                    .with_anchor(ButtonAnchor::TopLeft);
+        }
+        // The profile button mirrors the daily challenge button on the left
+        if let Some(button) = button_manager.get_button_mut("title_profile") {
+            button.style.text_style = text_style;
+            button.position = ButtonPosition::new(200.0, y.max(0.0), button_width, button_height)
+                .with_anchor(ButtonAnchor::TopLeft);
+        }
+        // The active profile's name sits just above it
+        let label_style = Self::profile_label_style(window_size.height as f32);
+        if let Ok(label) = button_manager
+            .text_renderer
+            .get_text_content(PROFILE_LABEL_TEXT_ID)
+        {
+            let _ = button_manager.text_renderer.update_text_style_and_position(
+                PROFILE_LABEL_TEXT_ID,
+                &label,
+                label_style.clone(),
+                TextPosition {
+                    x: 200.0,
+                    y: (y - label_style.line_height - 8.0 * scale).max(0.0),
+                    max_width: Some(button_width * 2.0),
+                    max_height: Some(label_style.line_height),
+                },
+            );
+        }
+
+        // Update text positions after all changes
+        button_manager.update_button_positions();
+    }
+
+    /// Shows the title menu and makes its buttons visible.
+    pub fn show(&mut self) {
+        self.visible = true;
+        self.last_action = TitleAction::None;
+
+        for button in self.button_manager.buttons.values_mut() {
+            button.set_visible(true);
+        }
+        if let Some(button) = self.button_manager.get_button_mut("title_continue") {
+            button.set_visible(self.continue_level.is_some());
+        }
+        let _ = self
+            .button_manager
+            .text_renderer
+            .set_buffer_visibility(PROFILE_LABEL_TEXT_ID, true);
+        self.button_manager.update_button_states();
+    }
+
+    /// Returns whether the title menu is currently visible.
+    pub fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    /// Handles input events for the title menu.
+    ///
+    /// # Arguments
+    ///
+    /// * `event` - The window event to handle
+    /// * `audio_manager` - The audio manager for playing button click sounds
+    pub fn handle_input(&mut self, event: &WindowEvent, audio_manager: &mut GameAudioManager) {
+        if !self.visible {
+            return;
+        }
+
+        self.button_manager.handle_input(event);
+        self.take_clicks(audio_manager);
+    }
+
+    /// Takes a gamepad's step through the title menu's buttons.
+    ///
+    /// # Arguments
+    ///
+    /// * `nav` - The step the gamepad made
+    /// * `audio_manager` - The audio manager for playing button click sounds
+    pub fn navigate(&mut self, nav: MenuNav, audio_manager: &mut GameAudioManager) {
+        if !self.visible || self.is_editing_seed() {
+            return;
+        }
+        self.button_manager.navigate(nav);
+        self.take_clicks(audio_manager);
+    }
+
+    /// Turns a click on one of the buttons, by mouse or gamepad, into the menu's action.
+    fn take_clicks(&mut self, audio_manager: &mut GameAudioManager) {
+        if self.button_manager.is_button_clicked("title_daily") {
+            self.last_action = TitleAction::StartDaily;
+            let _ = audio_manager.play_select();
+        }
+        if self.button_manager.is_button_clicked("title_seed") {
+            self.last_action = TitleAction::EditSeed;
+            let _ = audio_manager.play_select();
+        }
+        if self.button_manager.is_button_clicked("title_code") {
+            self.last_action = TitleAction::EditCode;
+            let _ = audio_manager.play_select();
+        }
+        if self.button_manager.is_button_clicked("title_codex") {
+            self.last_action = TitleAction::OpenCodex;
+            let _ = audio_manager.play_select();
+        }
+        if self.button_manager.is_button_clicked("title_sandbox") {
+            self.last_action = TitleAction::OpenSandbox;
+            let _ = audio_manager.play_select();
+        }
+        if self.button_manager.is_button_clicked("title_hardcore") {
+            self.last_action = TitleAction::ToggleHardcore;
+            let _ = audio_manager.play_select();
+        }
+        if self.button_manager.is_button_clicked("title_profile") {
+            self.last_action = TitleAction::SwitchProfile;
+            let _ = audio_manager.play_select();
+        }
+        if self.button_manager.is_button_clicked("title_continue") {
+            self.last_action = TitleAction::Continue;
+            let _ = audio_manager.play_select();
+        }
+    }
+
+    /// Returns whether the seed field or the challenge code field is open for typing.
+    pub fn is_editing_seed(&self) -> bool {
+        self.seed_field.is_some()
+    }
+
+    /// Opens the seed field, prefilled with the chosen seed. Does nothing if a field is
+    /// already open.
+    ///
+    /// # Arguments
+    ///
+    /// * `chosen_seed` - The seed chosen for the next run, or `None` for a random one
+    pub fn begin_seed_entry(&mut self, chosen_seed: Option<u64>) {
+        if self.seed_field.is_none() {
+            self.seed_field = Some(SeedField::new(chosen_seed));
+            self.field_target = FieldTarget::Seed;
+        }
+    }
+
+    /// Opens the challenge code field, prefilled with the entered challenge's code. Does
+    /// nothing if a field is already open.
+    ///
+    /// # Arguments
+    ///
+    /// * `challenge` - The challenge entered for the next run, if any
+    pub fn begin_code_entry(&mut self, challenge: Option<&Challenge>) {
+        if self.seed_field.is_none() {
+            let mut field = SeedField::new(None).with_max_length(challenge::CODE_LENGTH);
+            if let Some(challenge) = challenge {
+                field.paste(&challenge.encode());
+            }
+            self.seed_field = Some(field);
+            self.field_target = FieldTarget::Code;
+        }
+    }
+
+    /// Closes whichever field is open without changing the chosen seed or challenge.
+    pub fn end_seed_entry(&mut self) {
+        self.seed_field = None;
+        self.code_error = None;
+    }
+
+    /// Handles a key for the seed field or the challenge code field.
+    ///
+    /// S opens the seed field. While a field is open, Submit (Enter) keeps the typed seed or
+    /// code (an empty field means random, or no challenge), Cancel (Escape) goes back to a
+    /// random seed or no challenge, and other keys edit the text. Entering a code also chooses its seed;
+    /// a code that can't be read keeps the field open.
+    ///
+    /// # Arguments
+    ///
+    /// * `event` - The keyboard event
+    /// * `action` - What the key is bound to in the text entry context, if anything
+    /// * `chosen_seed` - The seed chosen for the next run, updated when the field closes
+    /// * `challenge` - The challenge entered for the next run, updated when the code field
+    ///   closes
+    ///
+    /// # Returns
+    ///
+    /// `true` if the field used the key, so nothing else should act on it
+    pub fn handle_seed_key(
+        &mut self,
+        event: &KeyEvent,
+        action: Option<Action>,
+        chosen_seed: &mut Option<u64>,
+        challenge: &mut Option<Challenge>,
+    ) -> bool {
+        if !self.visible {
+            return false;
+        }
+        let Some(field) = self.seed_field.as_mut() else {
+            let opens = event.state == ElementState::Pressed
+                && !event.repeat
+                && matches!(&event.logical_key, Key::Character(c) if c.eq_ignore_ascii_case("s"));
+            if opens {
+                self.begin_seed_entry(*chosen_seed);
+            }
+            return opens;
+        };
+        // While the input method is composing, its keys belong to it
+        if event.state != ElementState::Pressed || field.is_composing() {
+            return true;
+        }
+
+        if self.field_target == FieldTarget::Code {
+            match action {
+                Some(Action::Submit) => match Challenge::decode(field.text()) {
+                    Ok(entered) => {
+                        *chosen_seed = Some(entered.seed);
+                        *challenge = Some(entered);
+                        self.end_seed_entry();
+                    }
+                    Err(ChallengeError::Empty) => {
+                        *challenge = None;
+                        self.end_seed_entry();
+                    }
+                    Err(e) => {
+                        println!("{}", e);
+                        self.code_error = Some(e);
+                    }
+                },
+                Some(Action::Cancel) => {
+                    *challenge = None;
+                    self.end_seed_entry();
+                }
+                _ => {
+                    field.edit_key(event, action);
+                    self.code_error = None;
+                }
+            }
+            return true;
+        }
+
+        match action {
+            Some(Action::Submit) => match field.parse() {
+                Ok(seed) => {
+                    *chosen_seed = Some(seed);
+                    self.seed_field = None;
+                }
+                Err(SeedError::Empty) => {
+                    *chosen_seed = None;
+                    self.seed_field = None;
+                }
+                Err(e) => println!("{}", e),
+            },
+            Some(Action::Cancel) => {
+                *chosen_seed = None;
+                self.seed_field = None;
+            }
+            _ => field.edit_key(event, action),
+        }
+        true
+    }
+
+    /// Returns the line shown below the title menu about the challenge code.
+    ///
+    /// # Arguments
+    ///
+    /// * `challenge` - The challenge entered for the next run, if any
+    ///
+    /// # Returns
+    ///
+    /// Why the last code couldn't be read and `true`, or what the entered challenge
+    /// contains and `false`; `None` when there is nothing to say
+    pub fn code_notice(&self, challenge: Option<&Challenge>) -> Option<(String, bool)> {
+        match (&self.code_error, challenge) {
+            (Some(e), _) => Some((e.to_string(), true)),
+            (None, Some(challenge)) => Some((format!("Challenge: {}", challenge.summary()), false)),
+            (None, None) => None,
+        }
+    }
+
+    /// Passes an input method event to the open seed or challenge code field.
+    ///
+    /// # Arguments
+    ///
+    /// * `event` - The input method event from the window
+    ///
+    /// # Returns
+    ///
+    /// `true` if the field is open and took the event
+    pub fn handle_seed_ime(&mut self, event: &Ime) -> bool {
+        match self.seed_field.as_mut().filter(|_| self.visible) {
+            Some(field) => {
+                field.apply_ime(event);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Switches the window's input method on while a field is open, and off again once it
+    /// closes.
+    ///
+    /// While it is on, the input method's candidate window is kept at the field's caret.
+    /// Call this every frame; the window is only told when something changed.
+    ///
+    /// # Arguments
+    ///
+    /// * `window` - The window whose input method is switched
+    pub fn sync_ime(&mut self, window: &Window) {
+        let allowed = self.visible && self.seed_field.is_some();
+        if allowed != self.ime_allowed {
+            window.set_ime_allowed(allowed);
+            self.ime_allowed = allowed;
+            self.ime_cursor_area = None;
+        }
+        if !allowed {
+            return;
+        }
+        match self.caret_area() {
+            Some(area) if Some(area) != self.ime_cursor_area => {
+                let [x, y, width, height] = area;
+                window.set_ime_cursor_area(
+                    PhysicalPosition::new(x, y),
+                    PhysicalSize::new(width, height),
+                );
+                self.ime_cursor_area = Some(area);
+            }
+            _ => {}
+        }
+    }
+
+    /// Measures where a byte offset into the open field's text falls on screen.
+    ///
+    /// # Arguments
+    ///
+    /// * `offset` - Byte offset into the field's display text
+    ///
+    /// # Returns
+    ///
+    /// The x position and the field label's top and line height, or `None` when no field
+    /// is open
+    fn seed_text_x(&mut self, offset: usize) -> Option<(f32, f32, f32)> {
+        let field = self.seed_field.as_ref()?;
+        let button = self
+            .button_manager
+            .buttons
+            .get(self.field_target.button_id())?;
+        let style = button.style.text_style.clone();
+        let position = self
+            .button_manager
+            .text_renderer
+            .get_position(&button.text_id)
+            .ok()?;
+        let before = format!(
+            "{}{}",
+            self.field_target.prefix(),
+            &field.display()[..offset]
+        );
+        let (_min_x, width, _height) = self
+            .button_manager
+            .text_renderer
+            .measure_text(&before, &style);
+        Some((position.x + width, position.y, style.line_height))
+    }
+
+    /// Returns the area around the open field's caret, as `[x, y, width, height]`.
+    fn caret_area(&mut self) -> Option<[f32; 4]> {
+        let caret = self.seed_field.as_ref()?.display_caret();
+        let (x, y, line_height) = self.seed_text_x(caret)?;
+        Some([x, y, 1.0, line_height])
+    }
+
+    /// Places the underline below input method text that hasn't been committed, or clears it.
+    fn layout_preedit_underline(&mut self) {
+        self.preedit_underline.clear_rectangles();
+        let Some(range) = self.seed_field.as_ref().and_then(SeedField::preedit_range) else {
+            return;
+        };
+        let (Some((start, y, line_height)), Some((end, _, _))) =
+            (self.seed_text_x(range.start), self.seed_text_x(range.end))
+        else {
+            return;
+        };
+        let thickness = (line_height / 18.0).max(1.0);
+        self.preedit_underline.add_rectangle(Rectangle::new(
+            start,
+            y + line_height - thickness,
+            end - start,
+            thickness,
+            PREEDIT_UNDERLINE_COLOR,
+        ));
+    }
+
+    /// Shows the chosen seed and challenge, or the text being typed, on their fields.
+    ///
+    /// The seed field turns red while the typed text has a character that can't be in a
+    /// seed or is too large to be one, and the code field while its text has a character
+    /// that can't be in a code or was refused.
+    ///
+    /// # Arguments
+    ///
+    /// * `chosen_seed` - The seed chosen for the next run, or `None` for a random one
+    /// * `challenge` - The challenge entered for the next run, if any
+    pub fn refresh_seed_field(&mut self, chosen_seed: Option<u64>, challenge: Option<&Challenge>) {
+        let typing_code = self.field_target == FieldTarget::Code;
+        let (code_text, code_invalid) = match self.seed_field.as_ref().filter(|_| typing_code) {
+            Some(field) => (
+                format!("{}{}", FieldTarget::Code.prefix(), field.display()),
+                self.code_error.is_some()
+                    || matches!(
+                        Challenge::decode(field.text()),
+                        Err(ChallengeError::InvalidCharacter { .. })
+                    ),
+            ),
+            None if challenge.is_some() => ("Challenge: Loaded".to_string(), false),
+            None => ("Challenge Code".to_string(), false),
+        };
+        self.set_field_button("title_code", &code_text, code_invalid);
+
+        let (text, invalid) = match self.seed_field.as_ref().filter(|_| !typing_code) {
+            Some(field) => (
+                format!("Seed: {}", field.display()),
+                matches!(
+                    field.parse(),
+                    Err(SeedError::InvalidCharacter { .. } | SeedError::Overflow)
+                ),
+            ),
+            None => (
+                format!(
+                    "Seed: {}",
+                    chosen_seed.map_or("random".to_string(), seed::format_seed)
+                ),
+                false,
+            ),
+        };
+        self.set_field_button("title_seed", &text, invalid);
+        self.layout_preedit_underline();
+    }
+
+    /// Updates a field's text, turning it red while its text is invalid.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The field's button ID
+    /// * `text` - The text to show
+    /// * `invalid` - Whether the text can't be used
+    fn set_field_button(&mut self, id: &str, text: &str, invalid: bool) {
+        let colors = if invalid {
+            ButtonPreset::Destructive.style()
+        } else {
+            ButtonPreset::Primary.style()
+        };
+
+        let Some(button) = self.button_manager.get_button_mut(id) else {
+            return;
+        };
+        button.style.background_color = colors.background_color;
+        button.style.hover_color = colors.hover_color;
+        button.style.pressed_color = colors.pressed_color;
+        button.style.border_color = colors.border_color;
+        if button.text != text {
+            self.button_manager
+                .set_button_text(id, text)
+                .unwrap_or_else(|e| eprintln!("Failed to update title field: {}", e));
+        }
+    }
+
+    /// Shows whether hardcore mode is on, turning the toggle red while it is.
+    ///
+    /// # Arguments
+    ///
+    /// * `hardcore` - Whether the next run is hardcore
+    pub fn refresh_hardcore_toggle(&mut self, hardcore: bool) {
+        let (text, colors) = if hardcore {
+            ("Hardcore: On", ButtonPreset::Destructive.style())
+        } else {
+            ("Hardcore: Off", ButtonPreset::Primary.style())
+        };
+
+        let Some(button) = self.button_manager.get_button_mut("title_hardcore") else {
+            return;
+        };
+        button.style.background_color = colors.background_color;
+        button.style.hover_color = colors.hover_color;
+        button.style.pressed_color = colors.pressed_color;
+        button.style.border_color = colors.border_color;
+        if button.text == text {
+            return;
+        }
+        if let Err(e) = self.button_manager.set_button_text("title_hardcore", text) {
+            eprintln!("Failed to update hardcore toggle: {}", e);
+        }
+    }
+
+    /// Shows the continue button while there is a saved run, naming the level it resumes at.
+    ///
+    /// # Arguments
+    ///
+    /// * `level` - Level the saved run resumes at, or `None` if there is no saved run
+    pub fn refresh_continue_button(&mut self, level: Option<i32>) {
+        if level == self.continue_level {
+            return;
+        }
+        self.continue_level = level;
+        if let Some(level) = level
+            && let Err(e) = self
+                .button_manager
+                .set_button_text("title_continue", &format!("Continue: Level {}", level))
+        {
+            eprintln!("Failed to update continue button: {}", e);
+        }
+        let visible = self.visible && level.is_some();
+        if let Some(button) = self.button_manager.get_button_mut("title_continue") {
+            button.set_visible(visible);
+        }
+    }
+
+    /// Shows which profile is playing above the profile button.
+    ///
+    /// # Arguments
+    ///
+    /// * `profile` - Name of the active profile, if any
+    pub fn refresh_profile_label(&mut self, profile: Option<&str>) {
+        let label = profile.map_or(String::new(), |name| format!("Playing as {}", name));
+        if label == self.profile_label {
+            return;
+        }
+        if let Err(e) = self
+            .button_manager
+            .text_renderer
+            .update_text(PROFILE_LABEL_TEXT_ID, &label)
+        {
+            eprintln!("Failed to update profile label: {}", e);
+        }
+        self.profile_label = label;
+    }
+
+    /// Gets the last action that was triggered and resets it to `None`.
+    ///
+    /// # Returns
+    ///
+    /// The last `TitleAction` that was triggered, or `None` if no action occurred
+    pub fn get_last_action(&mut self) -> TitleAction {
+        let action = self.last_action.clone();
+        self.last_action = TitleAction::None;
+        action
+    }
+
+    /// Handles window resize events by updating button positions and text rendering.
+    ///
+    /// # Arguments
+    ///
+    /// * `queue` - The WGPU command queue
+    /// * `resolution` - The new window resolution
+    pub fn resize(&mut self, queue: &Queue, resolution: Resolution) {
+        self.button_manager.resize(queue, resolution);
+        self.preedit_underline
+            .resize(resolution.width as f32, resolution.height as f32);
+        Self::layout_buttons(
+            &mut self.button_manager,
+            PhysicalSize::new(resolution.width, resolution.height),
+        );
+    }
+
+    /// Prepares the title menu for rendering by updating text layout.
+    ///
+    /// # Arguments
+    ///
+    /// * `device` - The WGPU device
+    /// * `queue` - The WGPU command queue
+    /// * `surface_config` - The surface configuration
+    ///
+    /// # Returns
+    ///
+    /// `Ok(())` on success, or a `PrepareError` if text preparation fails
+    pub fn prepare(
+        &mut self,
+        device: &Device,
+        queue: &Queue,
+        surface_config: &SurfaceConfiguration,
+    ) -> Result<(), glyphon::PrepareError> {
+        self.button_manager.prepare(device, queue, surface_config)
+    }
+
+    /// Renders the title menu to the screen.
+    ///
+    /// # Arguments
+    ///
+    /// * `device` - The WGPU device
+    /// * `render_pass` - The render pass to draw into
+    ///
+    /// # Returns
+    ///
+    /// `Ok(())` on success, or a `RenderError` if rendering fails
+    pub fn render(
+        &mut self,
+        device: &Device,
+        render_pass: &mut RenderPass,
+    ) -> Result<(), glyphon::RenderError> {
+        self.button_manager.render(device, render_pass)?;
+        self.preedit_underline.render(device, render_pass);
+        Ok(())
+    }
+}
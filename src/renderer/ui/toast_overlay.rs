@@ -0,0 +1,403 @@
+//! Toast Overlay
+//!
+//! This module draws the toasts queued in a [`ToastManager`]: a stack of rounded panels in
+//! the bottom-left corner of the HUD safe area, each with an icon and one line of text. The
+//! oldest toast sits in the corner and newer ones stack above it. Toasts slide in from and
+//! out to the left edge of the safe area, and are clipped to it so they never spill into the
+//! pillarbox on very wide windows.
+//!
+//! The stack is sized from the safe area's height the same way the score and level text
+//! are, and never rises into the top [`TOP_CLEARANCE`] of the HUD, where the timer, stamina
+//! bar, score, and level sit. On a window too short for every toast, the ones that would
+//! cross it are left out until there is room.
+
+use crate::game::toasts::{MAX_VISIBLE, ToastKind, ToastManager};
+use crate::renderer::color;
+use crate::renderer::gpu_memory::GpuMemoryTracker;
+use crate::renderer::icon::{Icon, IconRenderer};
+use crate::renderer::rectangle::{Rectangle, RectangleRenderer};
+use crate::renderer::safe_area::{SafeArea, clamp_scissor};
+use crate::renderer::text::{TextPosition, TextRenderer, TextStyle};
+use glyphon::{Color, Resolution};
+use wgpu::{Device, Queue, RenderPass, SurfaceConfiguration};
+use winit::dpi::PhysicalSize;
+
+/// Fraction of the HUD's height, from the top, that toasts never enter.
+pub const TOP_CLEARANCE: f32 = 0.3;
+
+/// Texture ID of the icon on system toasts.
+const SYSTEM_ICON_ID: &str = "toast_system";
+
+/// Texture ID of the icon on gameplay toasts.
+const GAMEPLAY_ICON_ID: &str = "toast_gameplay";
+
+/// The system icon as pixel art; `#` is the disc, `o` is the letter, anything else is clear.
+const SYSTEM_ICON_PIXELS: [&str; 11] = [
+    "   #####   ",
+    "  ###o###  ",
+    " ######### ",
+    "####ooo####",
+    "#####o#####",
+    "#####o#####",
+    "#####o#####",
+    "#####o#####",
+    " ###ooo### ",
+    "  #######  ",
+    "   #####   ",
+];
+
+/// The gameplay icon as pixel art, in the same format as [`SYSTEM_ICON_PIXELS`].
+const GAMEPLAY_ICON_PIXELS: [&str; 11] = [
+    "   #####   ",
+    "  ###o###  ",
+    " ####o#### ",
+    "#####o#####",
+    "#####o#####",
+    "#####o#####",
+    "###########",
+    "#####o#####",
+    " ######### ",
+    "  #######  ",
+    "   #####   ",
+];
+
+/// How many texture pixels each icon pixel covers, so the linear sampler keeps its edges hard.
+const ICON_UPSCALE: usize = 8;
+
+/// Where one toast's panel sits when fully slid in, in pixels from the top-left.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ToastSlot {
+    /// Left edge
+    pub x: f32,
+    /// Top edge
+    pub y: f32,
+    /// Panel width
+    pub width: f32,
+    /// Panel height
+    pub height: f32,
+}
+
+/// Returns the scale toasts are drawn at, the same one the score and level text use.
+///
+/// # Arguments
+/// * `hud` - HUD safe area in pixels
+pub fn toast_scale(hud: &SafeArea) -> f32 {
+    (hud.height / 1080.0).clamp(0.7, 2.0)
+}
+
+/// Lays out the toast stack in the bottom-left corner of the HUD.
+///
+/// # Arguments
+/// * `hud` - HUD safe area in pixels
+/// * `count` - Number of toasts on screen
+///
+/// # Returns
+/// One slot per toast that fits, oldest (in the corner) first. Slots that would rise into
+/// the top [`TOP_CLEARANCE`] of the HUD are left out.
+pub fn toast_slots(hud: &SafeArea, count: usize) -> Vec<ToastSlot> {
+    let scale = toast_scale(hud);
+    let margin = 24.0 * scale;
+    let gap = 10.0 * scale;
+    let height = 44.0 * scale;
+    let width = (380.0 * scale).min(hud.width * 0.45);
+    let top_limit = hud.y + hud.height * TOP_CLEARANCE;
+
+    (0..count.min(MAX_VISIBLE))
+        .map(|index| ToastSlot {
+            x: hud.x + margin,
+            y: hud.bottom() - margin - height - index as f32 * (height + gap),
+            width,
+            height,
+        })
+        .take_while(|slot| slot.y >= top_limit)
+        .collect()
+}
+
+/// Returns the text buffer ID of a toast slot.
+fn slot_text_id(index: usize) -> String {
+    format!("toast_{}", index)
+}
+
+/// Turns icon pixel art into an RGBA texture.
+///
+/// # Arguments
+/// * `pixels` - Rows of pixel art
+/// * `disc` - sRGB color of the disc
+///
+/// # Returns
+/// The RGBA pixels and their dimensions
+fn icon_texture(pixels: &[&str], disc: [u8; 4]) -> (Vec<u8>, (u32, u32)) {
+    let rows = pixels.len() * ICON_UPSCALE;
+    let cols = pixels[0].len() * ICON_UPSCALE;
+    let mut rgba = Vec::with_capacity(rows * cols * 4);
+    for y in 0..rows {
+        let row = pixels[y / ICON_UPSCALE].as_bytes();
+        for x in 0..cols {
+            let pixel = match row[x / ICON_UPSCALE] {
+                b'#' => disc,
+                b'o' => [245, 245, 250, 255],
+                _ => [0, 0, 0, 0],
+            };
+            rgba.extend_from_slice(&pixel);
+        }
+    }
+    (rgba, (cols as u32, rows as u32))
+}
+
+/// Draws the toasts on screen.
+pub struct ToastOverlay {
+    /// Draws the panels
+    rectangle_renderer: RectangleRenderer,
+    /// Draws the icons
+    icon_renderer: IconRenderer,
+    /// Draws the lines of text, one buffer per slot
+    text_renderer: TextRenderer,
+    /// Current window size
+    window_size: PhysicalSize<u32>,
+    /// HUD safe area the toasts were last laid out in, for clipping
+    hud: SafeArea,
+}
+
+impl ToastOverlay {
+    /// Creates the overlay with nothing on screen.
+    ///
+    /// # Arguments
+    ///
+    /// * `device` - The WGPU device for rendering
+    /// * `queue` - The WGPU command queue
+    /// * `surface_format` - The surface texture format
+    /// * `window_size` - The window size, for sizing calculations
+    /// * `gpu_memory` - Tracker the icon and glyph textures are counted in
+    pub fn new(
+        device: &Device,
+        queue: &Queue,
+        surface_format: wgpu::TextureFormat,
+        window_size: PhysicalSize<u32>,
+        gpu_memory: &GpuMemoryTracker,
+    ) -> Self {
+        let mut text_renderer =
+            TextRenderer::new(device, queue, surface_format, window_size, gpu_memory);
+        for index in 0..MAX_VISIBLE {
+            let id = slot_text_id(index);
+            text_renderer.create_text_buffer(&id, "", None, None);
+            let _ = text_renderer.set_buffer_visibility(&id, false);
+        }
+
+        let mut icon_renderer = IconRenderer::new(device, queue, surface_format, gpu_memory);
+        for (id, pixels, disc) in [
+            (SYSTEM_ICON_ID, &SYSTEM_ICON_PIXELS, [90, 140, 220, 255]),
+            (GAMEPLAY_ICON_ID, &GAMEPLAY_ICON_PIXELS, [225, 150, 60, 255]),
+        ] {
+            let (rgba, dimensions) = icon_texture(pixels, disc);
+            if let Err(e) = icon_renderer.register_texture(device, queue, id, &rgba, dimensions) {
+                eprintln!("Failed to create toast icon {}: {}", id, e);
+            }
+        }
+        let (width, height) = (window_size.width as f32, window_size.height as f32);
+        let mut rectangle_renderer = RectangleRenderer::new(device, surface_format);
+        rectangle_renderer.resize(width, height);
+        icon_renderer.resize(width, height);
+        Self {
+            rectangle_renderer,
+            icon_renderer,
+            text_renderer,
+            window_size,
+            hud: SafeArea::full(width, height),
+        }
+    }
+
+    /// Handles window resize events.
+    ///
+    /// # Arguments
+    ///
+    /// * `queue` - The WGPU command queue
+    /// * `resolution` - The new window resolution
+    pub fn resize(&mut self, queue: &Queue, resolution: Resolution) {
+        self.text_renderer.resize(queue, resolution);
+        self.window_size = PhysicalSize::new(resolution.width, resolution.height);
+        let (width, height) = (resolution.width as f32, resolution.height as f32);
+        self.rectangle_renderer.resize(width, height);
+        self.icon_renderer.resize(width, height);
+    }
+
+    /// Lays out the toasts on screen this frame and prepares their text.
+    ///
+    /// # Arguments
+    ///
+    /// * `device` - The WGPU device
+    /// * `queue` - The WGPU command queue
+    /// * `surface_config` - The surface configuration
+    /// * `hud` - HUD safe area in pixels
+    /// * `toasts` - The toasts to draw
+    ///
+    /// # Returns
+    ///
+    /// `Ok(())` on success, or a `PrepareError` if text preparation fails
+    pub fn prepare(
+        &mut self,
+        device: &Device,
+        queue: &Queue,
+        surface_config: &SurfaceConfiguration,
+        hud: SafeArea,
+        toasts: &ToastManager,
+    ) -> Result<(), glyphon::PrepareError> {
+        self.hud = hud;
+        self.rectangle_renderer.clear_rectangles();
+        self.icon_renderer.clear_icons();
+
+        let scale = toast_scale(&hud);
+        let padding = 12.0 * scale;
+        let icon_side = 22.0 * scale;
+        let font_size = (18.0 * scale).clamp(12.0, 32.0);
+        let line_height = font_size * 1.3;
+        let visible: Vec<_> = toasts.visible().collect();
+        let slots = toast_slots(&hud, visible.len());
+
+        for index in 0..MAX_VISIBLE {
+            let id = slot_text_id(index);
+            let (Some(toast), Some(slot)) = (visible.get(index), slots.get(index)) else {
+                let _ = self.text_renderer.set_buffer_visibility(&id, false);
+                continue;
+            };
+            // Slides in from past the safe area's left edge
+            let x = slot.x - (1.0 - toast.slide) * (slot.x - hud.x + slot.width);
+            self.rectangle_renderer.add_rectangle(
+                Rectangle::new(
+                    x,
+                    slot.y,
+                    slot.width,
+                    slot.height,
+                    color::srgba_from_u8([24, 26, 32, 230]),
+                )
+                .with_corner_radius(10.0 * scale),
+            );
+
+            let texture_id = match toast.kind {
+                ToastKind::System => SYSTEM_ICON_ID,
+                ToastKind::Gameplay => GAMEPLAY_ICON_ID,
+            };
+            // Icon positions are measured from the bottom of the window
+            let icon_top = slot.y + (slot.height - icon_side) / 2.0;
+            self.icon_renderer.add_icon(
+                Icon::new(
+                    x + padding,
+                    self.window_size.height as f32 - icon_top - icon_side,
+                    icon_side,
+                    icon_side,
+                    texture_id.to_string(),
+                )
+                .with_pixel_snap(toast.slide >= 1.0),
+            );
+
+            let text_x = x + 2.0 * padding + icon_side;
+            let style = TextStyle {
+                font_family: "Hanken Grotesk".to_string(),
+                font_size,
+                line_height,
+                color: Color::rgb(235, 235, 240),
+                weight: glyphon::Weight::MEDIUM,
+                ..TextStyle::default()
+            };
+            let position = TextPosition {
+                x: text_x,
+                y: slot.y + (slot.height - line_height) / 2.0,
+                max_width: Some(x + slot.width - padding - text_x),
+                max_height: Some(line_height),
+            };
+            let _ = self
+                .text_renderer
+                .update_text_style_and_position(&id, toast.text, style, position);
+            let _ = self.text_renderer.set_buffer_visibility(&id, true);
+        }
+
+        self.text_renderer.prepare(device, queue, surface_config)
+    }
+
+    /// Draws the toasts prepared by [`prepare`](Self::prepare), clipped to the HUD.
+    ///
+    /// # Arguments
+    ///
+    /// * `device` - The WGPU device
+    /// * `render_pass` - The render pass to draw into
+    ///
+    /// # Returns
+    ///
+    /// `Ok(())` on success, or a `RenderError` if rendering fails
+    pub fn render(
+        &mut self,
+        device: &Device,
+        render_pass: &mut RenderPass,
+    ) -> Result<(), glyphon::RenderError> {
+        let Some((x, y, width, height)) = clamp_scissor(
+            self.hud.x as u32,
+            self.hud.y as u32,
+            self.hud.width as u32,
+            self.hud.height as u32,
+            self.window_size.width,
+            self.window_size.height,
+        ) else {
+            return Ok(());
+        };
+        render_pass.set_scissor_rect(x, y, width, height);
+        self.rectangle_renderer.render(device, render_pass);
+        self.icon_renderer.render(device, render_pass);
+        self.text_renderer.render(render_pass)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::renderer::safe_area::{DEFAULT_HUD_MAX_ASPECT, top_bar_height};
+
+    #[test]
+    fn test_toasts_stay_in_the_hud_and_clear_of_the_top_bar() {
+        let windows = [
+            (1920.0, 1080.0),
+            (3840.0, 1080.0),
+            (1280.0, 720.0),
+            (800.0, 1200.0),
+            (320.0, 200.0),
+            (3840.0, 2160.0),
+        ];
+        for (width, height) in windows {
+            let hud = SafeArea::centered(width, height, DEFAULT_HUD_MAX_ASPECT);
+            let slots = toast_slots(&hud, MAX_VISIBLE + 2);
+            assert!(!slots.is_empty() && slots.len() <= MAX_VISIBLE);
+            // The score and level lines under the timer and stamina bars, laid out as in
+            // `TextRenderer::handle_score_and_level_text`
+            let scale = toast_scale(&hud);
+            let font_size = (hud.width * 0.022 * scale).clamp(16.0, 48.0);
+            let line_height = (font_size * 1.25).clamp(20.0, 60.0);
+            let score_bottom = (hud.y + 2.0 * top_bar_height(hud.height) as f32)
+                .max(hud.y + 24.0 * scale + 2.0 * line_height + 18.0 * scale);
+            for (index, slot) in slots.iter().enumerate() {
+                assert!(
+                    hud.contains(slot.x, slot.y, slot.width, slot.height),
+                    "{}x{} slot {}: {:?} outside {:?}",
+                    width,
+                    height,
+                    index,
+                    slot,
+                    hud
+                );
+                assert!(
+                    slot.y > score_bottom,
+                    "{}x{} slot {} reaches the score",
+                    width,
+                    height,
+                    index
+                );
+            }
+            // Newer toasts stack upward without overlapping
+            for pair in slots.windows(2) {
+                assert!(pair[1].y + pair[1].height < pair[0].y);
+            }
+        }
+
+        // All three fit on an ordinary window
+        let hud = SafeArea::full(1920.0, 1080.0);
+        assert_eq!(toast_slots(&hud, MAX_VISIBLE).len(), MAX_VISIBLE);
+        assert!(toast_slots(&hud, 0).is_empty());
+    }
+}
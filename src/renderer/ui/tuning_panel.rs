@@ -0,0 +1,282 @@
+//! Debug tuning panel for live renderer adjustments.
+//!
+//! This module provides [`TuningPanel`], an egui window drawn on top of the game that edits
+//! [`RenderTuning`], the player's field of view, and the player's [`MovementConfig`] in place. Every change is picked up by the
+//! renderer on the next frame, so colors and fog can be dialed in without recompiling.
+//!
+//! The panel is toggled with the `C` key in debug builds. Its "Export as defaults" button prints
+//! the current values to the log as a Rust snippet that can be pasted into
+//! [`crate::renderer::tuning`].
+
+use crate::game::player::MovementConfig;
+use crate::renderer::color;
+use crate::renderer::tuning::{MAX_STAR_COUNT, RenderTuning};
+use wgpu::{CommandEncoder, Device, Queue, TextureView};
+use winit::event::WindowEvent;
+use winit::window::Window;
+
+/// Smallest field of view offered by the panel, in degrees.
+const MIN_FOV: f32 = 30.0;
+
+/// Largest field of view offered by the panel, in degrees.
+const MAX_FOV: f32 = 150.0;
+
+/// Farthest fog distance offered by the panel, in world units.
+const MAX_FOG_DISTANCE: f32 = 3000.0;
+
+/// Highest acceleration or deceleration offered by the panel, in world units per second squared.
+const MAX_ACCELERATION: f32 = 5000.0;
+
+/// An egui overlay for adjusting renderer parameters while the game runs.
+pub struct TuningPanel {
+    /// The egui context holding UI state between frames
+    context: egui::Context,
+    /// Translates winit events into egui input
+    winit_state: egui_winit::State,
+    /// Draws egui output into a wgpu render pass
+    renderer: egui_wgpu::Renderer,
+    /// Whether the panel is currently shown
+    visible: bool,
+}
+
+impl TuningPanel {
+    /// Creates a new, hidden tuning panel.
+    ///
+    /// # Arguments
+    ///
+    /// * `device` - The WGPU device for rendering
+    /// * `surface_format` - The surface texture format; the panel renders with its
+    ///   non-sRGB counterpart (see [`color::ui_format`])
+    /// * `window` - The window the panel receives input from
+    ///
+    /// # Returns
+    ///
+    /// A new `TuningPanel` instance ready to be toggled on
+    pub fn new(device: &Device, surface_format: wgpu::TextureFormat, window: &Window) -> Self {
+        let context = egui::Context::default();
+        let winit_state = egui_winit::State::new(
+            context.clone(),
+            egui::ViewportId::ROOT,
+            window,
+            Some(window.scale_factor() as f32),
+            None,
+            Some(device.limits().max_texture_dimension_2d as usize),
+        );
+        let renderer =
+            egui_wgpu::Renderer::new(device, color::ui_format(surface_format), None, 1, false);
+
+        Self {
+            context,
+            winit_state,
+            renderer,
+            visible: false,
+        }
+    }
+
+    /// Returns whether the panel is currently shown.
+    pub fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    /// Shows the panel if hidden, or hides it if shown.
+    pub fn toggle(&mut self) {
+        self.visible = !self.visible;
+    }
+
+    /// Returns whether the panel wants the pointer: the cursor is over it or dragging in it.
+    pub fn wants_pointer(&self) -> bool {
+        self.visible && self.context.wants_pointer_input()
+    }
+
+    /// Returns whether the panel wants the keyboard: one of its text fields has the focus.
+    pub fn wants_keyboard(&self) -> bool {
+        self.visible && self.context.wants_keyboard_input()
+    }
+
+    /// Passes a window event to egui.
+    ///
+    /// Whether the game sees the event too is settled before it gets here, by
+    /// [`Self::wants_pointer`] and [`Self::wants_keyboard`].
+    ///
+    /// # Arguments
+    ///
+    /// * `window` - The window the event was received from
+    /// * `event` - The window event to process
+    pub fn handle_input(&mut self, window: &Window, event: &WindowEvent) {
+        if self.visible {
+            let _ = self.winit_state.on_window_event(window, event);
+        }
+    }
+
+    /// Builds the panel UI and records its draw commands.
+    ///
+    /// Does nothing while the panel is hidden.
+    ///
+    /// # Arguments
+    ///
+    /// * `device` - The WGPU device for rendering
+    /// * `queue` - The WGPU command queue
+    /// * `encoder` - The command encoder for the current frame
+    /// * `surface_view` - A view of the surface in [`color::ui_format`] to draw the panel onto
+    /// * `window` - The window the panel is shown in
+    /// * `tuning` - Renderer parameters edited by the panel
+    /// * `fov` - The player's field of view in degrees, edited by the panel
+    /// * `movement` - The player's acceleration and friction, edited by the panel
+    #[allow(clippy::too_many_arguments)]
+    pub fn render(
+        &mut self,
+        device: &Device,
+        queue: &Queue,
+        encoder: &mut CommandEncoder,
+        surface_view: &TextureView,
+        window: &Window,
+        tuning: &mut RenderTuning,
+        fov: &mut f32,
+        movement: &mut MovementConfig,
+    ) {
+        if !self.visible {
+            return;
+        }
+
+        let raw_input = self.winit_state.take_egui_input(window);
+        let full_output = self.context.run(raw_input, |ctx| {
+            Self::build_ui(ctx, tuning, fov, movement);
+        });
+        self.winit_state
+            .handle_platform_output(window, full_output.platform_output);
+
+        let paint_jobs = self
+            .context
+            .tessellate(full_output.shapes, full_output.pixels_per_point);
+        let window_size = window.inner_size();
+        let screen_descriptor = egui_wgpu::ScreenDescriptor {
+            size_in_pixels: [window_size.width, window_size.height],
+            pixels_per_point: full_output.pixels_per_point,
+        };
+
+        for (id, image_delta) in &full_output.textures_delta.set {
+            self.renderer
+                .update_texture(device, queue, *id, image_delta);
+        }
+        self.renderer
+            .update_buffers(device, queue, encoder, &paint_jobs, &screen_descriptor);
+
+        {
+            let mut render_pass = encoder
+                .begin_render_pass(&wgpu::RenderPassDescriptor {
+                    label: Some("Tuning Panel Render Pass"),
+                    color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                        view: surface_view,
+                        resolve_target: None,
+                        ops: wgpu::Operations {
+                            load: wgpu::LoadOp::Load,
+                            store: wgpu::StoreOp::Store,
+                        },
+                    })],
+                    depth_stencil_attachment: None,
+                    timestamp_writes: None,
+                    occlusion_query_set: None,
+                })
+                .forget_lifetime();
+            self.renderer
+                .render(&mut render_pass, &paint_jobs, &screen_descriptor);
+        }
+
+        for id in &full_output.textures_delta.free {
+            self.renderer.free_texture(id);
+        }
+    }
+
+    /// Lays out the panel widgets.
+    fn build_ui(
+        ctx: &egui::Context,
+        tuning: &mut RenderTuning,
+        fov: &mut f32,
+        movement: &mut MovementConfig,
+    ) {
+        egui::Window::new("Renderer Tuning")
+            .default_pos([20.0, 20.0])
+            .resizable(false)
+            .show(ctx, |ui| {
+                ui.heading("Background");
+                ui.horizontal(|ui| {
+                    ui.label("Clear color");
+                    ui.color_edit_button_rgba_unmultiplied(&mut tuning.background_color);
+                });
+
+                ui.separator();
+                ui.heading("Stars");
+                ui.add(egui::Slider::new(&mut tuning.star_count, 0..=MAX_STAR_COUNT).text("Count"));
+                ui.add(
+                    egui::Slider::new(&mut tuning.star_twinkle_speed, 0.0..=5.0)
+                        .text("Twinkle speed"),
+                );
+
+                ui.separator();
+                ui.heading("Fog");
+                ui.horizontal(|ui| {
+                    ui.label("Color");
+                    ui.color_edit_button_rgba_unmultiplied(&mut tuning.fog_color);
+                    if ui.button("Match background").clicked() {
+                        tuning.fog_color = tuning.background_color;
+                    }
+                });
+                ui.add(egui::Slider::new(&mut tuning.fog_strength, 0.0..=1.0).text("Strength"));
+                ui.add(
+                    egui::Slider::new(&mut tuning.fog_start, 0.0..=MAX_FOG_DISTANCE).text("Start"),
+                );
+                ui.add(egui::Slider::new(&mut tuning.fog_end, 0.0..=MAX_FOG_DISTANCE).text("End"));
+                if tuning.fog_end <= tuning.fog_start {
+                    tuning.fog_end = tuning.fog_start + 1.0;
+                }
+
+                ui.separator();
+                ui.heading("Shading");
+                ui.add(egui::Slider::new(&mut tuning.ambient, 0.0..=2.0).text("Ambient"));
+
+                ui.separator();
+                ui.heading("Camera");
+                ui.add(egui::Slider::new(fov, MIN_FOV..=MAX_FOV).text("FOV"));
+
+                ui.separator();
+                ui.heading("Compass");
+                ui.add(
+                    egui::Slider::new(&mut tuning.compass_smoothing, 0.0..=0.5)
+                        .text("Smoothing (s)"),
+                );
+
+                ui.separator();
+                ui.heading("Movement");
+                ui.add(
+                    egui::Slider::new(&mut movement.acceleration, 0.0..=MAX_ACCELERATION)
+                        .text("Acceleration"),
+                );
+                ui.add(
+                    egui::Slider::new(&mut movement.sprint_acceleration, 0.0..=MAX_ACCELERATION)
+                        .text("Sprint acceleration"),
+                );
+                ui.add(
+                    egui::Slider::new(&mut movement.deceleration, 0.0..=MAX_ACCELERATION)
+                        .text("Friction"),
+                );
+                ui.add(
+                    egui::Slider::new(&mut movement.turn_penalty, 0.0..=1.0).text("Turn penalty"),
+                );
+
+                ui.separator();
+                ui.horizontal(|ui| {
+                    if ui.button("Reset").clicked() {
+                        *tuning = RenderTuning::default();
+                        *movement = MovementConfig::default();
+                    }
+                    if ui.button("Export as defaults").clicked() {
+                        println!(
+                            "[TUNING] Current renderer values:\n{}",
+                            tuning.to_rust_snippet(*fov)
+                        );
+                        println!("[TUNING] Current movement values: {:?}", movement);
+                    }
+                });
+            });
+    }
+}
@@ -3,33 +3,23 @@
 //! This module provides a comprehensive upgrade menu interface for the game,
 //! allowing players to select from randomly presented upgrades between levels.
 //! The menu displays three upgrade options in a visually appealing layout with
-//! buttons, icons, and tooltips.
+//! buttons, icons, and tooltips. Tooltips appear in a floating panel by the cursor
+//! after a card has been hovered for a moment, or straight away for the card picked
+//! with the arrow keys.
 
+use crate::game::bindings::Action;
+use crate::game::dimensions::PlayerDimensions;
+use crate::game::input::MenuNav;
 use crate::game::upgrades::{AvailableUpgrade, Upgrade, UpgradeManager};
-use crate::renderer::ui::button::{
-    Button, ButtonAnchor, ButtonManager, ButtonPosition, TextAlign, create_primary_button_style,
-};
+use crate::renderer::gpu_memory::GpuMemoryTracker;
+use crate::renderer::ui::button::{Button, ButtonAnchor, ButtonManager, ButtonPosition, TextAlign};
 use glyphon::{Color, Resolution};
 use wgpu::{self, Device, Queue, RenderPass, SurfaceConfiguration};
 use winit::dpi::PhysicalSize;
-use winit::event::WindowEvent;
-use winit::window::Window;
+use winit::event::{ElementState, KeyEvent, WindowEvent};
 
-/// Represents the possible actions that can be taken in the upgrade menu.
-///
-/// This enum is used to track which upgrade slot was selected by the player,
-/// allowing the game loop to respond appropriately to user choices.
-#[derive(Debug, Clone, PartialEq)]
-pub enum UpgradeMenuAction {
-    /// Player selected the first upgrade option
-    SelectUpgrade1,
-    /// Player selected the second upgrade option
-    SelectUpgrade2,
-    /// Player selected the third upgrade option
-    SelectUpgrade3,
-    /// No action was taken or action was reset
-    None,
-}
+/// Color of the translucent overlay that dims the scene behind the upgrade cards.
+pub const OVERLAY_COLOR: [f32; 4] = [0.08, 0.09, 0.11, 0.88];
 
 /// The main upgrade menu system that handles display and interaction logic.
 ///
@@ -51,8 +41,6 @@ pub struct UpgradeMenu {
     pub current_upgrades: Vec<Upgrade>,
     /// Whether the upgrade menu is currently visible and active
     pub visible: bool,
-    /// The last action performed by the player (which upgrade was selected)
-    pub last_action: UpgradeMenuAction,
     /// Prevents content from being reinitialized after first setup
     ///
     /// This flag ensures that upgrade text, icons, and tooltips remain stable
@@ -67,32 +55,48 @@ impl UpgradeMenu {
     /// * `device` - WGPU device for GPU operations
     /// * `queue` - WGPU command queue for rendering operations
     /// * `surface_format` - The texture format of the rendering surface
-    /// * `window` - Window reference for layout calculations
+    /// * `window_size` - The window size, for sizing calculations
+    /// * `gpu_memory` - Tracker the menu's icon and glyph textures are counted in
     ///
     /// # Returns
     /// A new `UpgradeMenu` instance with initialized button layout but hidden by default.
     ///
     /// # Example
-    /// ```rust
-    /// let upgrade_menu = UpgradeMenu::new(&device, &queue, surface_format, &window);
+    /// ```rust,ignore
+    /// let upgrade_menu = UpgradeMenu::new(&device, &queue, surface_format, window.inner_size(), &gpu_memory);
     /// ```
     pub fn new(
         device: &Device,
         queue: &Queue,
         surface_format: wgpu::TextureFormat,
-        window: &Window,
+        window_size: PhysicalSize<u32>,
+        gpu_memory: &GpuMemoryTracker,
     ) -> Self {
-        let mut button_manager = ButtonManager::new(device, queue, surface_format, window);
+        let mut button_manager =
+            ButtonManager::new(device, queue, surface_format, window_size, gpu_memory);
+        // Descriptions float by the cursor, leaving the cards room for a bigger icon
+        button_manager.floating_tooltips = true;
+
+        // Upgrades carry their own icons rather than coming with the startup icon bundle
+        for icon in AvailableUpgrade::ALL
+            .iter()
+            .filter_map(|upgrade| upgrade.to_upgrade().icon)
+        {
+            if let Err(e) =
+                button_manager.register_icon_from_data(device, queue, icon.id, icon.bytes)
+            {
+                eprintln!("{}, using missing icon", e);
+            }
+        }
 
         // Create upgrade menu layout
-        Self::create_upgrade_layout(&mut button_manager, window.inner_size());
+        Self::create_upgrade_layout(&mut button_manager, window_size);
 
         Self {
             button_manager,
             upgrade_manager: UpgradeManager::new(),
             current_upgrades: Vec::new(),
             visible: false,
-            last_action: UpgradeMenuAction::None,
             content_initialized: false,
         }
     }
@@ -174,15 +178,6 @@ impl UpgradeMenu {
         for i in 0..3 {
             let slot_x = slots_start_x + i as f32 * (slot_width + slot_spacing);
 
-            // Create a custom style for the upgrade slots (lighter grey)
-            let mut slot_style = create_primary_button_style();
-            slot_style.background_color = Color::rgb(200, 200, 200); // Light grey
-            slot_style.hover_color = Color::rgb(180, 180, 180); // Slightly darker on hover
-            slot_style.pressed_color = Color::rgb(160, 160, 160); // Even darker when pressed
-            slot_style.corner_radius = 12.0; // Rounded corners
-            slot_style.padding = (8.0, 8.0); // Minimal padding
-            slot_style.text_style = text_style.clone(); // Use scaled text style
-
             let upgrade_text = match i {
                 0 => "Upgrade 1",
                 1 => "Upgrade 2",
@@ -193,14 +188,10 @@ impl UpgradeMenu {
             // Calculate height proportion for tall buttons
             let margin = 0.1; // 10% margin
             let height_proportion = (container_height * (1.0 - 2.0 * margin)) / window_height;
-            slot_style.spacing =
-                crate::renderer::ui::button::ButtonSpacing::Tall(height_proportion);
 
-            let button = Button::new(&format!("upgrade_{}", i + 1), upgrade_text)
-                .with_style(slot_style)
+            let mut button = Button::upgrade_card(&format!("upgrade_{}", i + 1), upgrade_text)
+                .with_text_style(text_style.clone()) // Use scaled text style
                 .with_text_align(TextAlign::Center)
-                .with_level_text()
-                .with_tooltip_text()
                 .with_position(
                     ButtonPosition::new(
                         slot_x,
@@ -210,6 +201,8 @@ impl UpgradeMenu {
                     ) // Width set, height will be calculated by ButtonManager
                     .with_anchor(ButtonAnchor::TopLeft),
                 );
+            button.style.spacing =
+                crate::renderer::ui::button::ButtonSpacing::Tall(height_proportion);
 
             button_manager.add_button(button);
         }
@@ -222,26 +215,33 @@ impl UpgradeMenu {
     ///
     /// This method:
     /// 1. Sets the menu to visible state
-    /// 2. Resets the last action to None
-    /// 3. Selects 3 random upgrades from the available pool
-    /// 4. Makes all UI buttons visible
-    /// 5. Updates button content with upgrade information
+    /// 2. Selects 3 random upgrades from the available pool
+    /// 3. Makes all UI buttons visible
+    /// 4. Updates button content with upgrade information
     ///
     /// # Side Effects
-    /// - Modifies `self.visible`, `self.last_action`, and `self.current_upgrades`
+    /// - Modifies `self.visible` and `self.current_upgrades`
     /// - Updates button text, icons, and tooltips through the button manager
     /// - Triggers content initialization if not already done
     pub fn show(&mut self) {
-        self.visible = true;
-        self.last_action = UpgradeMenuAction::None;
-
         // Get 3 random upgrades
-        self.current_upgrades = self.upgrade_manager.select_random_upgrades(3);
+        let upgrades = self.upgrade_manager.select_random_upgrades(3);
+        self.show_upgrades(upgrades);
+    }
+
+    /// Shows the upgrade menu offering the given upgrades instead of a random pick.
+    ///
+    /// # Arguments
+    /// * `upgrades` - The upgrades to offer, one per card
+    pub fn show_upgrades(&mut self, upgrades: Vec<Upgrade>) {
+        self.visible = true;
+        self.current_upgrades = upgrades;
 
         // Show all buttons first
         for button in self.button_manager.buttons.values_mut() {
             button.set_visible(true);
         }
+        self.button_manager.reset_tooltips();
 
         // Update upgrade buttons separately to avoid borrow conflicts
         self.update_upgrade_buttons();
@@ -262,7 +262,7 @@ impl UpgradeMenu {
     ///
     /// # Content Updates
     /// - Button text: Set to upgrade name (e.g., "Speed Up", "Dash")
-    /// - Icons: Matched to upgrade type using `get_icon_id_for_upgrade_name`
+    /// - Icons: The upgrade's own icon, or the blank icon if it has none
     /// - Level text: Shows current upgrade level (e.g., "Level 2")
     /// - Tooltips: Displays upgrade description and effects
     fn update_upgrade_buttons(&mut self) {
@@ -281,8 +281,9 @@ impl UpgradeMenu {
                 // Update button text to the upgrade name
                 button.text = upgrade.name.clone();
 
-                // Set the correct icon for this upgrade
-                button.icon_id = Some(Self::get_icon_id_for_upgrade_name(&upgrade.name));
+                // Show the upgrade's own icon, registered when the menu was created
+                let icon_id = upgrade.icon.map_or("blank_icon", |icon| icon.id);
+                button.icon_id = Some(icon_id.to_string());
 
                 // Get display info (level text and tooltip)
                 let (level_text, tooltip_text) =
@@ -348,58 +349,23 @@ impl UpgradeMenu {
         self.content_initialized = true;
     }
 
-    /// Maps upgrade names to their corresponding icon identifiers.
-    ///
-    /// This function provides a centralized mapping between upgrade names
-    /// and their visual icon representations in the UI.
-    ///
-    /// # Arguments
-    /// * `upgrade_name` - The name of the upgrade (e.g., "Speed Up", "Dash")
-    ///
-    /// # Returns
-    /// A `String` containing the icon identifier for the upgrade.
-    /// Returns "blank_icon" for unknown upgrade names.
-    ///
-    /// # Supported Upgrades
-    /// - "Speed Up" → "speed_up_icon"
-    /// - "Slow Time" → "slow_down_icon"
-    /// - "Silent Step" → "silent_step_icon"
-    /// - "Tall Boots" → "tall_boots_icon"
-    /// - "Head Start" → "head_start_icon"
-    /// - "Dash" → "dash_icon"
-    /// - "Unknown" → "unknown_icon"
-    /// - Others → "blank_icon"
-    fn get_icon_id_for_upgrade_name(upgrade_name: &str) -> String {
-        match upgrade_name {
-            "Speed Up" => "speed_up_icon".to_string(),
-            "Slow Time" => "slower_seconds_icon".to_string(),
-            "Silent Step" => "silent_step_icon".to_string(),
-            "Tall Boots" => "tall_boots_icon".to_string(),
-            "Head Start" => "head_start_icon".to_string(),
-            "Dash" => "dash_icon".to_string(),
-            "Unknown" => "unknown_icon".to_string(),
-            _ => "blank_icon".to_string(),
-        }
-    }
-
     /// Hides the upgrade menu and resets its state for the next use.
     ///
     /// This method:
     /// - Sets visibility to false
-    /// - Resets the last action to None
     /// - Clears the content initialization flag for next display
     /// - Hides all UI buttons
     ///
     /// After calling this method, the menu can be shown again with new upgrade options.
     pub fn hide(&mut self) {
         self.visible = false;
-        self.last_action = UpgradeMenuAction::None;
         self.content_initialized = false; // Reset flag so content can be reinitialized
 
         // Hide all buttons
         for button in self.button_manager.buttons.values_mut() {
             button.set_visible(false);
         }
+        self.button_manager.reset_tooltips();
     }
 
     /// Returns whether the upgrade menu is currently visible.
@@ -414,79 +380,117 @@ impl UpgradeMenu {
     ///
     /// This method processes window events when the menu is visible, including:
     /// - Mouse clicks on upgrade buttons
-    /// - Keyboard input for upgrade selection
+    /// - Keyboard input for upgrade selection: the arrow keys and Tab move focus between
+    ///   the cards, and Enter or Space picks the focused one
     /// - Playing appropriate sound effects
     /// - Applying selected upgrades to the game state
     /// - Automatically hiding the menu after selection
     ///
     /// # Arguments
     /// * `event` - The window event to process
+    /// * `action` - What the key is bound to in the menu context, for a keyboard event
     /// * `game_state` - Mutable reference to the game state for applying upgrades
     ///
     /// # Side Effects
-    /// - Updates `self.last_action` based on user interaction
     /// - Applies upgrade effects to the player and game state
     /// - Plays upgrade selection sound effects
     /// - Hides the menu after successful upgrade selection
     /// - Prints confirmation message to console
-    pub fn handle_input(&mut self, event: &WindowEvent, game_state: &mut crate::game::GameState) {
+    pub fn handle_input(
+        &mut self,
+        event: &WindowEvent,
+        action: Option<Action>,
+        game_state: &mut crate::game::GameState,
+    ) {
         if !self.visible {
             return;
         }
 
         self.button_manager.handle_input(event);
-
-        // Check for button clicks and apply upgrades
-        let mut upgrade_selected = false;
-        let mut selected_upgrade_name = String::new();
-
-        if self.button_manager.is_button_clicked("upgrade_1") {
-            // Play upgrade sound
-            let _ = game_state.audio_manager.play_upgrade();
-
-            if let Some(upgrade) = self.current_upgrades.get(0) {
-                selected_upgrade_name = upgrade.name.clone();
-                self.apply_upgrade_by_name(&selected_upgrade_name, game_state);
-                upgrade_selected = true;
-            }
-            self.last_action = UpgradeMenuAction::SelectUpgrade1;
+        if let WindowEvent::KeyboardInput {
+            event: key_event, ..
+        } = event
+        {
+            self.handle_key(key_event, action);
         }
+        self.take_clicks(game_state);
+    }
 
-        if self.button_manager.is_button_clicked("upgrade_2") {
-            // Play upgrade sound
-            let _ = game_state.audio_manager.play_upgrade();
+    /// Takes a gamepad's step between the cards, picking the focused one on A.
+    ///
+    /// # Arguments
+    /// * `nav` - The step the gamepad made
+    /// * `game_state` - Mutable reference to the game state for applying upgrades
+    pub fn navigate(&mut self, nav: MenuNav, game_state: &mut crate::game::GameState) {
+        if !self.visible {
+            return;
+        }
+        self.button_manager.navigate(nav);
+        self.take_clicks(game_state);
+    }
 
-            if let Some(upgrade) = self.current_upgrades.get(1) {
-                selected_upgrade_name = upgrade.name.clone();
-                self.apply_upgrade_by_name(&selected_upgrade_name, game_state);
-                upgrade_selected = true;
+    /// Picks the card that was clicked, by mouse, keyboard, or gamepad.
+    fn take_clicks(&mut self, game_state: &mut crate::game::GameState) {
+        for slot in 0..3 {
+            if self
+                .button_manager
+                .is_button_clicked(&format!("upgrade_{}", slot + 1))
+            {
+                self.pick(slot, game_state);
             }
-            self.last_action = UpgradeMenuAction::SelectUpgrade2;
         }
+    }
 
-        if self.button_manager.is_button_clicked("upgrade_3") {
-            // Play upgrade sound
-            let _ = game_state.audio_manager.play_upgrade();
+    /// Picks one of the offered upgrades, as clicking its card does.
+    ///
+    /// Plays the upgrade sound, applies the upgrade, records it in the journal, and hides
+    /// the menu. A slot without an upgrade only plays the sound.
+    ///
+    /// # Arguments
+    /// * `slot` - Which card to pick, from 0 for the leftmost
+    /// * `game_state` - The game state to apply the upgrade to
+    pub fn pick(&mut self, slot: usize, game_state: &mut crate::game::GameState) {
+        // Play upgrade sound
+        let _ = game_state.audio_manager.play_upgrade();
+        let Some(upgrade) = self.current_upgrades.get(slot) else {
+            return;
+        };
 
-            if let Some(upgrade) = self.current_upgrades.get(2) {
-                selected_upgrade_name = upgrade.name.clone();
-                self.apply_upgrade_by_name(&selected_upgrade_name, game_state);
-                upgrade_selected = true;
-            }
-            self.last_action = UpgradeMenuAction::SelectUpgrade3;
-        }
+        let selected_upgrade_name = upgrade.name.clone();
+        self.apply_upgrade_by_name(&selected_upgrade_name, game_state);
+        println!(
+            "Upgrade '{}' applied! Menu will close.",
+            selected_upgrade_name
+        );
+        game_state
+            .journal
+            .record(crate::game::journal::JournalEvent::UpgradePicked {
+                name: selected_upgrade_name,
+            });
+        self.hide();
+
+        // Force a redraw to ensure the menu disappears immediately
+        // This helps prevent freezing on Windows
+        println!("[DEBUG] Upgrade selected, requesting redraw");
+    }
 
-        // If an upgrade was selected, hide the menu
-        if upgrade_selected {
-            println!(
-                "Upgrade '{}' applied! Menu will close.",
-                selected_upgrade_name
-            );
-            self.hide();
-            
-            // Force a redraw to ensure the menu disappears immediately
-            // This helps prevent freezing on Windows
-            println!("[DEBUG] Upgrade selected, requesting redraw");
+    /// Moves keyboard focus between the cards, and picks the focused card on Confirm.
+    ///
+    /// A pick is reported as a click on the focused card's button, so it goes through the
+    /// same path as a mouse click.
+    ///
+    /// # Arguments
+    /// * `event` - The keyboard event
+    /// * `action` - What the key is bound to in the menu context, if anything
+    fn handle_key(&mut self, event: &KeyEvent, action: Option<Action>) {
+        if event.state != ElementState::Pressed {
+            return;
+        }
+        match action {
+            Some(Action::NavigateRight | Action::SwitchFocus) => self.button_manager.move_focus(1),
+            Some(Action::NavigateLeft) => self.button_manager.move_focus(-1),
+            Some(Action::Confirm) if !event.repeat => self.button_manager.activate_focus(),
+            _ => {}
         }
     }
 
@@ -518,10 +522,13 @@ impl UpgradeMenu {
     /// # Upgrade Effects
     /// - **Speed Up**: +10% movement and sprint speed per level (multiplicative)
     /// - **Dash**: +10% max stamina per level (multiplicative)
+    /// - **Lightfoot**: +25% acceleration per level (multiplicative)
     /// - **Tall Boots**: +3 height units per level (additive)
     /// - **Slow Time**: +5 seconds to level timer per level (additive)
     /// - **Silent Step**: 5% worse enemy pathfinding per level
     /// - **Head Start**: +3 seconds enemy lock delay per level
+    /// - **Slim**: -10% collision radius per level (multiplicative), see
+    ///   [`UpgradeMenu::upgraded_dimensions`]
     ///
     /// # Implementation Notes
     /// - Multiplicative effects use `powi()` for proper stacking
@@ -532,6 +539,7 @@ impl UpgradeMenu {
         // Reset affected player fields to base values
         game_state.player.base_speed = 100.0;
         game_state.player.max_stamina = 2.0;
+        game_state.player.acceleration_multiplier = 1.0;
         game_state.player.position[1] = crate::math::coordinates::constants::PLAYER_HEIGHT;
         // TODO: Reset any other affected fields as needed
 
@@ -546,6 +554,10 @@ impl UpgradeMenu {
                     // +10% max stamina per instance
                     game_state.player.max_stamina *= 1.1_f32.powi(*count as i32);
                 }
+                AvailableUpgrade::Lightfoot => {
+                    // +25% acceleration per instance
+                    game_state.player.acceleration_multiplier *= 1.25_f32.powi(*count as i32);
+                }
                 AvailableUpgrade::TallBoots => {
                     // +3 height per instance
                     game_state.player.position[1] =
@@ -571,6 +583,23 @@ impl UpgradeMenu {
         }
         // After applying, update current speed to base
         game_state.player.speed = game_state.player.base_speed;
+        game_state.set_dimensions(self.upgraded_dimensions(game_state.is_test_mode));
+    }
+
+    /// Returns the player's dimensions with every owned upgrade applied.
+    ///
+    /// # Arguments
+    /// * `is_test_mode` - Whether to start from test mode's dimensions
+    ///
+    /// # Upgrade Effects
+    /// - **Slim**: -10% collision radius per level (multiplicative)
+    pub fn upgraded_dimensions(&self, is_test_mode: bool) -> PlayerDimensions {
+        let mut dimensions = PlayerDimensions::for_mode(is_test_mode);
+        let slim = self
+            .upgrade_manager
+            .get_upgrade_count(&AvailableUpgrade::Slim);
+        dimensions.collision_radius *= 0.9_f32.powi(slim as i32);
+        dimensions
     }
 
     /// Applies a specific upgrade by name to the player's upgrade collection.
@@ -588,7 +617,7 @@ impl UpgradeMenu {
     /// If an unknown upgrade name is provided, defaults to "Speed Up".
     ///
     /// # Example
-    /// ```rust
+    /// ```rust,ignore
     /// upgrade_menu.apply_upgrade_by_name("Dash", &mut game_state);
     /// ```
     fn apply_upgrade_by_name(
@@ -603,6 +632,8 @@ impl UpgradeMenu {
             "Tall Boots" => AvailableUpgrade::TallBoots,
             "Head Start" => AvailableUpgrade::HeadStart,
             "Dash" => AvailableUpgrade::Dash,
+            "Slim" => AvailableUpgrade::Slim,
+            "Lightfoot" => AvailableUpgrade::Lightfoot,
             "Unknown" => AvailableUpgrade::Unknown,
             _ => AvailableUpgrade::SpeedUp, // Fallback
         };
@@ -610,31 +641,6 @@ impl UpgradeMenu {
         self.apply_upgrade_effects(game_state);
     }
 
-    /// Retrieves and resets the last action performed in the upgrade menu.
-    ///
-    /// This method implements a "consume-on-read" pattern, returning the
-    /// last action and immediately resetting it to `None`. This prevents
-    /// the same action from being processed multiple times.
-    ///
-    /// # Returns
-    /// The last `UpgradeMenuAction` that was performed, or `None` if no
-    /// action was taken or the action was already consumed.
-    ///
-    /// # Usage Pattern
-    /// ```rust
-    /// match upgrade_menu.get_last_action() {
-    ///     UpgradeMenuAction::SelectUpgrade1 => { /* handle upgrade 1 */ },
-    ///     UpgradeMenuAction::SelectUpgrade2 => { /* handle upgrade 2 */ },
-    ///     UpgradeMenuAction::SelectUpgrade3 => { /* handle upgrade 3 */ },
-    ///     UpgradeMenuAction::None => { /* no action */ },
-    /// }
-    /// ```
-    pub fn get_last_action(&mut self) -> UpgradeMenuAction {
-        let action = self.last_action.clone();
-        self.last_action = UpgradeMenuAction::None;
-        action
-    }
-
     /// Handles window resize events by updating the button manager and recreating the layout.
     ///
     /// This method ensures the upgrade menu remains properly sized and positioned
@@ -710,7 +716,7 @@ impl UpgradeMenu {
     /// * `Err(PrepareError)` - Text preparation failed (e.g., layout issues, GPU errors)
     ///
     /// # Usage
-    /// ```rust
+    /// ```rust,ignore
     /// upgrade_menu.prepare(&device, &queue, &surface_config)?;
     /// upgrade_menu.render(&device, &mut render_pass)?;
     /// ```
@@ -0,0 +1,49 @@
+//! Checks that a uniform struct matches the layout its shader declares.
+//!
+//! Uniform structs are uploaded with `bytemuck`, so a Rust struct that drifts from its WGSL
+//! counterpart (a field added on one side only, or padding that WGSL's alignment rules place
+//! differently) draws garbage instead of failing. [`assert_matches_shader`] parses the shader
+//! with naga and compares the size of its uniform buffer with the Rust struct's.
+//!
+//! # Usage
+//!
+//! ```rust,ignore
+//! assert_matches_shader::<DeathFadeUniforms>(include_str!("../shaders/death_fade.wgsl"));
+//! ```
+
+/// Asserts that a struct has the size of the uniform buffer declared by a shader.
+///
+/// # Arguments
+/// * `source` - The WGSL source, which must declare exactly one uniform buffer
+///
+/// # Panics
+/// If the shader doesn't parse, doesn't declare exactly one uniform buffer, or declares one of
+/// a different size than `T`
+pub fn assert_matches_shader<T: bytemuck::Pod>(source: &str) {
+    let module = naga::front::wgsl::parse_str(source)
+        .unwrap_or_else(|err| panic!("shader doesn't parse:\n{}", err.emit_to_string(source)));
+    let mut uniforms = module
+        .global_variables
+        .iter()
+        .filter(|(_, global)| global.space == naga::AddressSpace::Uniform);
+    let (_, uniform) = uniforms.next().expect("shader declares no uniform buffer");
+    assert!(
+        uniforms.next().is_none(),
+        "shader declares more than one uniform buffer"
+    );
+
+    let mut layouter = naga::proc::Layouter::default();
+    layouter
+        .update(module.to_ctx())
+        .expect("shader types have a layout");
+    assert_eq!(
+        std::mem::size_of::<T>(),
+        layouter[uniform.ty].size as usize,
+        "{} doesn't match the shader's {}",
+        std::any::type_name::<T>(),
+        module.types[uniform.ty]
+            .name
+            .as_deref()
+            .unwrap_or("uniform buffer"),
+    );
+}
@@ -0,0 +1,172 @@
+//! Warm-up work scheduled into loading screen frames.
+//!
+//! The first time a text style is drawn, glyphon rasterizes its glyphs into the atlas in the
+//! middle of that frame, so the first upgrade menu, the first game over screen, and the first
+//! timer at a new size each hitch. Every menu has its own text renderer and atlas, so each one
+//! hitches separately. Render pipelines don't need this: every renderer creates its pipelines up
+//! front when it is created.
+//!
+//! [`WarmUp`] runs a queue of such tasks during the loading screen in slices of
+//! [`WARM_UP_SLICE`] per frame, so the loading animation keeps its frame rate. It stops for good
+//! once [`WARM_UP_BUDGET`] has been spent, and is cancelled if loading finishes first; what it
+//! didn't get to is rasterized on first use as before, and logged when it is.
+//!
+//! # Usage
+//!
+//! ```rust,ignore
+//! let mut warm_up = WarmUp::new(tasks);
+//!
+//! // Each loading screen frame
+//! warm_up.run_slice(|task| {
+//!     let started = Instant::now();
+//!     task.run();
+//!     started.elapsed()
+//! });
+//!
+//! // When loading is done or the screen changes
+//! if let Some(report) = warm_up.finish() {
+//!     journal.record(JournalEvent::GlyphsWarmed { ... });
+//! }
+//! ```
+
+use std::collections::VecDeque;
+use std::fmt;
+use std::time::Duration;
+
+/// Longest a warm-up spends in total, so it never holds up loading by more than this.
+pub const WARM_UP_BUDGET: Duration = Duration::from_millis(100);
+
+/// Longest a warm-up spends in one loading screen frame before yielding to the next.
+pub const WARM_UP_SLICE: Duration = Duration::from_millis(6);
+
+/// Tasks run ahead of time, a few each frame, until done, over budget, or cancelled.
+#[derive(Debug)]
+pub struct WarmUp<T> {
+    /// Tasks not run yet, in the order they run
+    pending: VecDeque<T>,
+    /// Labels of the tasks run so far
+    warmed: Vec<String>,
+    /// Time spent running tasks so far
+    spent: Duration,
+    /// Whether [`WarmUp::finish`] has returned the report
+    finished: bool,
+}
+
+/// What a warm-up got done, for the session journal.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WarmUpReport {
+    /// Labels of the tasks that ran
+    pub warmed: Vec<String>,
+    /// Labels of the tasks that didn't
+    pub skipped: Vec<String>,
+    /// Time spent running tasks
+    pub spent: Duration,
+}
+
+impl<T: fmt::Display> WarmUp<T> {
+    /// Creates a warm-up that will run the given tasks in order.
+    ///
+    /// # Arguments
+    /// * `tasks` - The work to do ahead of time; each should take well under [`WARM_UP_SLICE`]
+    pub fn new(tasks: impl IntoIterator<Item = T>) -> Self {
+        Self {
+            pending: tasks.into_iter().collect(),
+            warmed: Vec::new(),
+            spent: Duration::ZERO,
+            finished: false,
+        }
+    }
+
+    /// Whether no more tasks will run, because all ran, the budget is spent, or it finished.
+    pub fn is_done(&self) -> bool {
+        self.finished || self.pending.is_empty() || self.spent >= WARM_UP_BUDGET
+    }
+
+    /// Runs tasks until this frame's slice or the total budget is spent.
+    ///
+    /// A task that runs long is not interrupted; the next one simply waits for the next frame.
+    ///
+    /// # Arguments
+    /// * `warm` - Runs one task and returns how long it took
+    pub fn run_slice(&mut self, mut warm: impl FnMut(&T) -> Duration) {
+        let mut slice = Duration::ZERO;
+        while slice < WARM_UP_SLICE && !self.is_done() {
+            let Some(task) = self.pending.pop_front() else {
+                break;
+            };
+            let took = warm(&task);
+            slice += took;
+            self.spent += took;
+            self.warmed.push(task.to_string());
+        }
+    }
+
+    /// Stops the warm-up, cancelling any tasks left.
+    ///
+    /// # Returns
+    /// What was warmed and skipped the first time it is called, then `None`
+    pub fn finish(&mut self) -> Option<WarmUpReport> {
+        if self.finished {
+            return None;
+        }
+        self.finished = true;
+        Some(WarmUpReport {
+            warmed: std::mem::take(&mut self.warmed),
+            skipped: self
+                .pending
+                .drain(..)
+                .map(|task| task.to_string())
+                .collect(),
+            spent: self.spent,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_slices_stop_at_the_frame_slice_and_total_budget() {
+        let mut warm_up = WarmUp::new(0..40);
+        let mut ran = Vec::new();
+
+        // 4 ms tasks: two fit in a 6 ms slice
+        warm_up.run_slice(|&task| {
+            ran.push(task);
+            Duration::from_millis(4)
+        });
+        assert_eq!(ran, vec![0, 1]);
+        assert!(!warm_up.is_done());
+
+        // 25 frames later the 100 ms budget is spent with tasks left over
+        for _ in 0..24 {
+            warm_up.run_slice(|&task| {
+                ran.push(task);
+                Duration::from_millis(4)
+            });
+        }
+        assert_eq!(ran.len(), 25);
+        assert!(warm_up.is_done());
+        warm_up.run_slice(|_| panic!("ran past the budget"));
+
+        let report = warm_up.finish().unwrap();
+        assert_eq!(report.warmed.len(), 25);
+        assert_eq!(report.skipped.len(), 15);
+        assert_eq!(report.spent, WARM_UP_BUDGET);
+        assert_eq!(warm_up.finish(), None);
+    }
+
+    #[test]
+    fn test_cancelling_skips_the_rest() {
+        let mut warm_up = WarmUp::new(["hud", "pause menu", "upgrade menu"]);
+        // Loading finishes after one frame
+        warm_up.run_slice(|_| Duration::from_millis(5));
+
+        let report = warm_up.finish().unwrap();
+        assert_eq!(report.warmed, vec!["hud", "pause menu"]);
+        assert_eq!(report.skipped, vec!["upgrade menu"]);
+        assert!(warm_up.is_done());
+        warm_up.run_slice(|_| panic!("ran after being cancelled"));
+    }
+}
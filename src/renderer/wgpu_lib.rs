@@ -7,22 +7,73 @@
 //!
 //! # Features
 //! - Loads maze geometry and floor/wall vertices
-//! - Renders a starfield background and animated loading screen
+//! - Renders a starfield or sky panorama background and animated loading screen
+//! - Reads background, starfield, and fog parameters from [`RenderTuning`] every frame
 //! - Handles depth buffering and uniform updates for camera/player movement
+//! - Draws the 3D view with MSAA when the adapter supports the requested sample count
 //! - Integrates with custom UI system for overlays
 //!
 //! # Usage
-//! Create a [`WgpuRenderer`] via [`WgpuRenderer::new`] and call [`WgpuRenderer::update_canvas`]
-//! each frame to render the current game state.
+//! Create a [`WgpuRenderer`] via [`WgpuRenderer::new`]. Each frame, call
+//! [`WgpuRenderer::update_animations`] to advance everything that animates, then
+//! [`WgpuRenderer::update_canvas`] to render the current game state. The render path
+//! only uploads what the update step computed, so it has no side effects besides GPU writes.
 
 use crate::game::CurrentScreen;
 use crate::game::GameState;
+use crate::game::anomaly::Cosmetic;
+use crate::game::time_of_day::TimeOfDayPin;
+use crate::renderer::animation::FrameAnimations;
+use crate::renderer::backend;
+use crate::renderer::color;
 use crate::renderer::game_renderer::GameRenderer;
+use crate::renderer::game_renderer::ambient::AmbientRenderer;
+use crate::renderer::game_renderer::camera_projection;
+use crate::renderer::game_renderer::death_fade::DeathFadeRenderer;
+use crate::renderer::game_renderer::enemy_face::EnemyFaceRenderer;
 use crate::renderer::game_renderer::game_over::GameOverRenderer;
+use crate::renderer::game_renderer::heartbeat::HeartbeatRenderer;
+use crate::renderer::game_renderer::light_shafts::LightShaftRenderer;
+use crate::renderer::game_renderer::minimap::MinimapRenderer;
+use crate::renderer::game_renderer::panorama::{BackgroundMode, PanoramaRenderer};
+use crate::renderer::game_renderer::reticle::{ReticleSettings, ReticleStyle};
+use crate::renderer::game_renderer::stars::create_star_renderer;
+use crate::renderer::game_renderer::vignette::VignetteRenderer;
+use crate::renderer::gpu_memory::GpuMemoryTracker;
+use crate::renderer::icon::{Icon, IconRenderer};
 use crate::renderer::loading_renderer::LoadingRenderer;
+use crate::renderer::msaa::{self, MsaaTarget};
+use crate::renderer::present_mode::{self, PresentModePreference};
+use crate::renderer::rectangle::Rectangle;
+use crate::renderer::replay_pip::ReplayPip;
+use crate::renderer::safe_area::{
+    DEFAULT_HUD_MAX_ASPECT, SafeArea, ViewFit, clamp_scissor, top_bar_height,
+};
+use crate::renderer::screenshot::PendingScreenshot;
+use crate::renderer::surface_alpha::{self, AlphaStampRenderer};
 use crate::renderer::text::TextRenderer;
+use crate::renderer::tuning::RenderTuning;
 use wgpu;
 use wgpu::{SurfaceTexture, TextureView};
+use winit::dpi::PhysicalSize;
+
+/// Title screen clear color as sRGBA.
+pub(crate) const TITLE_CLEAR_COLOR: [u8; 4] = [0, 0, 0, 255];
+
+/// Loading screen clear color as sRGBA.
+pub(crate) const LOADING_CLEAR_COLOR: [u8; 4] = [26, 51, 77, 255];
+
+/// Seconds after which the ambient particles' animation time starts over.
+const AMBIENT_TIME_WRAP: f32 = 1000.0;
+
+/// Linear background color of the dusk palette cosmetic, a deep violet.
+const DUSK_BACKGROUND_COLOR: [f32; 4] = [0.02, 0.006, 0.045, 1.0];
+
+/// Linear star tint of the ember stars cosmetic: warm amber, mostly replacing the stars' color.
+const EMBER_STAR_TINT: [f32; 4] = [1.0, 0.45, 0.08, 0.85];
+
+/// Texture ID of the icon shown while the player is crouching.
+const CROUCH_ICON_ID: &str = "crouch_icon";
 
 /// Main WGPU renderer for the Mirador game.
 ///
@@ -33,27 +84,96 @@ pub struct WgpuRenderer {
     pub surface: wgpu::Surface<'static>,
     /// The surface configuration (format, size, etc.).
     pub surface_config: wgpu::SurfaceConfiguration,
+    /// Whether the window is minimized, leaving the surface nothing to draw at.
+    pub minimized: bool,
     /// The WGPU device for resource creation.
     pub device: wgpu::Device,
     /// The WGPU queue for submitting commands.
     pub queue: wgpu::Queue,
+    /// The adapter the device was created on, kept to configure surfaces for other windows.
+    pub adapter: wgpu::Adapter,
+    /// Backend, name, and driver of the adapter the device was created on.
+    pub adapter_info: wgpu::AdapterInfo,
     /// Main render pipeline for the maze and floor.
     pub game_renderer: GameRenderer,
     /// Renderer for the loading screen maze and loading bar.
     pub loading_screen_renderer: LoadingRenderer,
     /// Renderer for the game over screen.
     pub game_over_renderer: GameOverRenderer,
+    /// Renderer for the level theme's dust or rain over the 3D view.
+    pub ambient_renderer: AmbientRenderer,
+    /// Renderer for the sky panorama drawn in place of the starfield.
+    pub panorama_renderer: PanoramaRenderer,
+    /// Renderer for the darkened edges and fade to black of the capture sequence.
+    pub vignette_renderer: VignetteRenderer,
+    /// Renderer for the enemy face flashed at the end of the capture sequence.
+    pub enemy_face_renderer: EnemyFaceRenderer,
+    /// Renderer for the heartbeat distortion of the timer's last seconds.
+    pub heartbeat_renderer: HeartbeatRenderer,
+    /// Renderer for the light shafts cast from the exit's beacon.
+    pub light_shaft_renderer: LightShaftRenderer,
+    /// Renderer for the crossfade from the live view into the frozen game over screen.
+    pub death_fade_renderer: DeathFadeRenderer,
+    /// Renderer for the map of the explored maze in the corner of the HUD.
+    pub minimap_renderer: MinimapRenderer,
+    /// Renderer for the level-end replay of the player's path.
+    pub replay_pip: ReplayPip,
     /// Renderer for the title screen.
     pub title_renderer: crate::renderer::title::TitleRenderer,
+    /// Multisampled color texture the 3D view is drawn into, when MSAA is on.
+    pub msaa: MsaaTarget,
+    /// Estimated texture memory in use, shared with the renderers that create textures.
+    pub gpu_memory: GpuMemoryTracker,
+    /// Live-tunable background, starfield, and fog parameters.
+    pub tuning: RenderTuning,
+    /// Widest aspect ratio the HUD is laid out in.
+    pub hud_max_aspect: f32,
+    /// Whether the 3D view fills the window or is boxed to the safe area.
+    pub view_fit: ViewFit,
+    /// What is drawn behind the maze, checked every frame.
+    pub background: BackgroundMode,
+    /// Look of the center-screen reticle.
+    pub reticle: ReticleSettings,
+    /// Unlocked cosmetics the player has switched on, applied over the tuning and reticle.
+    pub cosmetics: Vec<Cosmetic>,
+    /// Whether the player has asked for reduced visual effects, which swaps the capture
+    /// sequence's face flash for a fade to black, hides the ambient dust and rain, and turns
+    /// off the heartbeat distortion, and trades the exit's light shafts for a plain glow.
+    pub reduce_effects: bool,
+    /// Whether the scene's time of day follows the run or is pinned to one hour.
+    pub time_of_day: TimeOfDayPin,
+    /// Animation values for the current frame, advanced by [`WgpuRenderer::update_animations`].
+    pub animations: FrameAnimations,
+    /// Whether the next frame drawn should be saved as a screenshot.
+    pub screenshot_requested: bool,
+    /// Gray level last applied to the game over restart prompt, so its style is only
+    /// updated when the color actually changes.
+    restart_text_gray: Option<u8>,
+    /// Icon shown under the stamina bar while the player is crouching.
+    crouch_icon: IconRenderer,
+    /// HUD area and surface size the crouch icon was last placed for.
+    crouch_icon_layout: Option<(SafeArea, (u32, u32))>,
+    /// Last pass of every frame when the surface's alpha mode reads alpha, keeping the
+    /// window opaque; `None` when the surface ignores alpha.
+    alpha_stamp: Option<AlphaStampRenderer>,
 }
 
 impl WgpuRenderer {
     /// Initializes a new [`WgpuRenderer`] and all associated GPU resources.
+    ///
+    /// # Arguments
+    /// * `adapter` - The adapter to render with, from [`WgpuRenderer::request_adapter`]
+    /// * `surface` - The window's surface, created by the same instance as the adapter
+    /// * `width` - Initial surface width in pixels
+    /// * `height` - Initial surface height in pixels
+    /// * `msaa_samples` - Samples per pixel to draw the 3D view with; lowered to what the
+    ///   adapter supports, and 1 turns MSAA off
     pub async fn new(
-        instance: &wgpu::Instance,
+        adapter: wgpu::Adapter,
         surface: wgpu::Surface<'static>,
         width: u32,
         height: u32,
+        msaa_samples: u32,
     ) -> Self {
         use crate::benchmarks::{BenchmarkConfig, Profiler};
 
@@ -64,12 +184,12 @@ impl WgpuRenderer {
             write_to_file: false,
             min_duration_threshold: std::time::Duration::from_micros(1),
             max_samples: 1000,
+            autosave_interval: std::time::Duration::from_secs(60),
+            max_sections: 256,
         });
 
-        // Benchmark adapter creation
-        init_profiler.start_section("wgpu_adapter_creation");
-        let adapter = Self::create_adapter(instance, &surface).await;
-        init_profiler.end_section("wgpu_adapter_creation");
+        let adapter_info = adapter.get_info();
+        println!("{}", backend::adapter_summary(&adapter_info));
 
         // Benchmark device and queue creation
         init_profiler.start_section("wgpu_device_queue_creation");
@@ -81,22 +201,30 @@ impl WgpuRenderer {
         let surface_config = Self::create_surface_config(&surface, &adapter, width, height);
         surface.configure(&device, &surface_config);
         init_profiler.end_section("wgpu_surface_configuration");
+        let alpha_stamp = surface_alpha::needs_alpha_stamp(surface_config.alpha_mode)
+            .then(|| AlphaStampRenderer::new(&device, surface_config.format));
+
+        let gpu_memory = GpuMemoryTracker::default();
+        let sample_count =
+            msaa::supported_sample_count(&adapter, surface_config.format, msaa_samples);
+        let msaa = MsaaTarget::new(&surface_config, sample_count, &gpu_memory);
 
         // Benchmark GameRenderer initialization
         init_profiler.start_section("game_renderer_initialization");
-        let mut game_renderer = GameRenderer::new(&device, &queue, &surface_config);
+        let mut game_renderer =
+            GameRenderer::with_sample_count(&device, &queue, &surface_config, sample_count);
         init_profiler.end_section("game_renderer_initialization");
 
         // Benchmark ceiling texture loading
         init_profiler.start_section("ceiling_texture_loading");
-        if let Err(e) = game_renderer.load_ceiling_texture(&device, &queue) {
+        if let Err(e) = game_renderer.load_ceiling_texture(&device, &queue, &gpu_memory) {
             eprintln!("Failed to load ceiling texture: {}", e);
         }
         init_profiler.end_section("ceiling_texture_loading");
 
         // Benchmark LoadingRenderer initialization
         init_profiler.start_section("loading_renderer_init");
-        let loading_screen_renderer = LoadingRenderer::new(&device, &surface_config);
+        let loading_screen_renderer = LoadingRenderer::new(&device, &surface_config, &gpu_memory);
         init_profiler.end_section("loading_renderer_init");
 
         // Benchmark GameOverRenderer initialization
@@ -104,39 +232,227 @@ impl WgpuRenderer {
         let game_over_renderer = GameOverRenderer::new(&device, &surface_config);
         init_profiler.end_section("game_over_renderer_init");
 
+        let ambient_renderer = AmbientRenderer::new(&device, &surface_config);
+        let panorama_renderer = PanoramaRenderer::new(&device, &surface_config, sample_count);
+        let vignette_renderer = VignetteRenderer::new(&device, &surface_config);
+        let enemy_face_renderer =
+            EnemyFaceRenderer::new(&device, &queue, &surface_config, &gpu_memory);
+        let heartbeat_renderer = HeartbeatRenderer::new(&device, &surface_config, &gpu_memory);
+        let light_shaft_renderer =
+            LightShaftRenderer::new(&device, &queue, &surface_config, &gpu_memory, sample_count);
+        let death_fade_renderer = DeathFadeRenderer::new(&device, &surface_config, &gpu_memory);
+
+        init_profiler.start_section("replay_pip_init");
+        let minimap_renderer = MinimapRenderer::new(&device, &surface_config, &gpu_memory);
+        let replay_pip = ReplayPip::new(&device, &queue, &surface_config, &gpu_memory);
+        init_profiler.end_section("replay_pip_init");
+
         // Benchmark TitleRenderer initialization
         init_profiler.start_section("title_renderer_initialization");
         let title_renderer =
             crate::renderer::title::TitleRenderer::new(&device, &queue, &surface_config);
         init_profiler.end_section("title_renderer_initialization");
 
+        let mut crouch_icon =
+            IconRenderer::new(&device, &queue, surface_config.format, &gpu_memory);
+        if let Err(e) = crouch_icon.load_texture_from_data(
+            &device,
+            &queue,
+            crate::assets::SILENT_STEP_ICON,
+            CROUCH_ICON_ID,
+        ) {
+            eprintln!("Failed to load crouch icon: {}", e);
+        }
+
         Self {
             surface,
             surface_config,
+            minimized: false,
             device,
             queue,
+            adapter,
+            adapter_info,
             game_renderer,
             loading_screen_renderer,
             game_over_renderer,
+            ambient_renderer,
+            panorama_renderer,
+            vignette_renderer,
+            enemy_face_renderer,
+            heartbeat_renderer,
+            light_shaft_renderer,
+            death_fade_renderer,
+            minimap_renderer,
+            replay_pip,
             title_renderer,
+            msaa,
+            gpu_memory,
+            tuning: RenderTuning::default(),
+            hud_max_aspect: DEFAULT_HUD_MAX_ASPECT,
+            view_fit: ViewFit::default(),
+            background: BackgroundMode::default(),
+            reticle: ReticleSettings::default(),
+            cosmetics: Vec::new(),
+            reduce_effects: false,
+            time_of_day: TimeOfDayPin::default(),
+            animations: FrameAnimations::default(),
+            screenshot_requested: false,
+            restart_text_gray: None,
+            crouch_icon,
+            crouch_icon_layout: None,
+            alpha_stamp,
+        }
+    }
+
+    /// Returns the region of the surface the HUD is laid out in.
+    pub fn hud_area(&self) -> SafeArea {
+        SafeArea::centered(
+            self.surface_config.width as f32,
+            self.surface_config.height as f32,
+            self.hud_max_aspect,
+        )
+    }
+
+    /// Returns the region of the surface the 3D view is drawn in.
+    pub fn view_area(&self) -> SafeArea {
+        SafeArea::for_view(
+            self.surface_config.width as f32,
+            self.surface_config.height as f32,
+            self.hud_max_aspect,
+            self.view_fit,
+        )
+    }
+
+    /// Advances every renderer animation by one frame.
+    ///
+    /// Must be called once per frame before [`WgpuRenderer::update_canvas`]. Updates the
+    /// star, bar, compass, reticle, enemy and light shaft animations, rebuilds the starfield
+    /// if its tuned star count changed, starts or finishes loading the sky panorama once it
+    /// is chosen, and lays out and colors the HUD and game over text.
+    ///
+    /// # Arguments
+    /// * `game_state` - The current game state
+    /// * `text_renderer` - The text renderer holding the HUD and game over text
+    /// * `delta_time` - Seconds since the last frame
+    pub fn update_animations(
+        &mut self,
+        game_state: &GameState,
+        text_renderer: &mut TextRenderer,
+        delta_time: f32,
+    ) {
+        self.animations
+            .advance(delta_time, self.tuning.star_twinkle_speed);
+        self.animations.set_timer(game_state.game_ui.timer.as_ref());
+        self.animations.stamina_progress = game_state.player.stamina_ratio();
+
+        // Rebuild the starfield if the tuned star count or the time of day changed it
+        let star_count = self.scene_tuning(game_state).clamped_star_count();
+        if self.game_renderer.star_renderer.star_count != star_count {
+            self.game_renderer.star_renderer = create_star_renderer(
+                &self.device,
+                &self.surface_config,
+                star_count,
+                self.game_renderer.sample_count,
+            );
+        }
+        self.panorama_renderer.update(
+            &self.device,
+            &self.queue,
+            &self.gpu_memory,
+            self.background == BackgroundMode::Panorama,
+        );
+
+        self.game_renderer
+            .animate(game_state, self.animations.clock);
+        self.game_renderer
+            .reticle_renderer
+            .animate(game_state.interaction_target().is_some(), delta_time);
+        // The beacon only shines over a level being played
+        let in_level = matches!(
+            game_state.current_screen,
+            CurrentScreen::Game | CurrentScreen::Pause | CurrentScreen::ExitReached
+        );
+        let view = self.view_area();
+        self.light_shaft_renderer.update(
+            &self.device,
+            game_state,
+            self.game_renderer.exit_position.filter(|_| in_level),
+            view,
+            self.reduce_effects,
+            delta_time,
+        );
+        self.death_fade_renderer.update(
+            game_state.current_screen == CurrentScreen::GameOver,
+            delta_time,
+        );
+        if let Some(exit_position) = self.game_renderer.exit_position {
+            let compass = &mut self.game_renderer.compass_renderer;
+            compass.set_smoothing_time(self.tuning.compass_smoothing);
+            compass.update_compass_with_yaw(
+                (game_state.player.position[0], game_state.player.position[2]),
+                game_state.compass_target(exit_position),
+                game_state.player.yaw,
+                delta_time,
+            );
+        }
+
+        match game_state.current_screen {
+            CurrentScreen::Game | CurrentScreen::Pause | CurrentScreen::ExitReached => {
+                // Auto-size and position score and level text
+                text_renderer.handle_score_and_level_text(self.hud_area());
+            }
+            CurrentScreen::GameOver => {
+                // Apply auto-sizing logic to game over text (similar to title screen)
+                text_renderer
+                    .handle_game_over_text(self.surface_config.width, self.surface_config.height);
+                self.update_restart_text_color(text_renderer);
+            }
+            _ => {}
+        }
+    }
+
+    /// Pulses the game over restart prompt between dark gray and white.
+    ///
+    /// The style is only updated when the gray level changed since the last frame.
+    fn update_restart_text_color(&mut self, text_renderer: &mut TextRenderer) {
+        let gray = self.animations.restart_text_gray;
+        if self.restart_text_gray == Some(gray) {
+            return;
+        }
+        if let Ok(mut style) = text_renderer.get_style("game_over_restart") {
+            style.color = glyphon::Color::rgb(gray, gray, gray);
+            if text_renderer
+                .update_style("game_over_restart", style)
+                .is_ok()
+            {
+                self.restart_text_gray = Some(gray);
+            }
         }
     }
 
     /// Renders the current frame to the surface.
+    ///
+    /// Only uploads the animation values computed by [`WgpuRenderer::update_animations`],
+    /// so rendering twice without an update in between draws the same frame.
+    ///
+    /// # Returns
+    /// The frame's view and texture, or `None` if there was no surface texture to draw to,
+    /// as while the window is minimized
     pub fn update_canvas(
         &mut self,
-        window: &winit::window::Window,
         encoder: &mut wgpu::CommandEncoder,
         game_state: &GameState,
         text_renderer: &mut TextRenderer,
-        app_start_time: std::time::Instant,
-    ) -> Result<(TextureView, SurfaceTexture), String> {
-        let (surface_texture, surface_view) = self.get_surface_texture_and_view()?;
+    ) -> Result<Option<(TextureView, SurfaceTexture)>, String> {
+        let Some((surface_texture, surface_view)) = self.get_surface_texture_and_view()? else {
+            return Ok(None);
+        };
         let depth_texture_view = self.update_depth_texture();
+        self.msaa.prepare(&self.device);
 
         match game_state.current_screen {
             CurrentScreen::Loading => {
-                self.render_loading_screen(encoder, &surface_view, window);
+                self.render_loading_screen(encoder, &surface_view);
             }
             CurrentScreen::GameOver => {
                 self.render_game_over_screen(
@@ -145,8 +461,6 @@ impl WgpuRenderer {
                     &depth_texture_view,
                     game_state,
                     text_renderer,
-                    window,
-                    app_start_time,
                 );
             }
             CurrentScreen::Game | CurrentScreen::Pause | CurrentScreen::ExitReached => {
@@ -156,13 +470,12 @@ impl WgpuRenderer {
                     &depth_texture_view,
                     game_state,
                     text_renderer,
-                    window,
                 );
             }
             _ => {}
         }
 
-        Ok((surface_view, surface_texture))
+        Ok(Some((surface_view, surface_texture)))
     }
 
     /// Renders the title screen.
@@ -170,7 +483,6 @@ impl WgpuRenderer {
         &mut self,
         encoder: &mut wgpu::CommandEncoder,
         surface_view: &TextureView,
-        _window: &winit::window::Window,
     ) {
         let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
             label: Some("Title Screen Render Pass"),
@@ -178,12 +490,7 @@ impl WgpuRenderer {
                 view: surface_view,
                 resolve_target: None,
                 ops: wgpu::Operations {
-                    load: wgpu::LoadOp::Clear(wgpu::Color {
-                        r: 0.0,
-                        g: 0.0,
-                        b: 0.0,
-                        a: 1.0,
-                    }),
+                    load: wgpu::LoadOp::Clear(self.srgb_clear_color(TITLE_CLEAR_COLOR)),
                     store: wgpu::StoreOp::Store,
                 },
             })],
@@ -191,18 +498,35 @@ impl WgpuRenderer {
             occlusion_query_set: None,
             timestamp_writes: None,
         });
-        render_pass.set_pipeline(&self.title_renderer.pipeline);
-        render_pass.set_bind_group(0, &self.title_renderer.bind_group, &[]);
-        render_pass.set_vertex_buffer(0, self.title_renderer.vertex_buffer.slice(..));
-        render_pass.draw(0..6, 0..1);
+        self.title_renderer.render(&mut render_pass);
     }
 
-    // Private helper methods
+    /// Returns the features and limits the game asks an adapter's device for.
+    ///
+    /// # Arguments
+    /// * `adapter` - The adapter the device will be created on
+    pub(crate) fn device_descriptor(adapter: &wgpu::Adapter) -> wgpu::DeviceDescriptor<'static> {
+        wgpu::DeviceDescriptor {
+            label: None,
+            // Timestamps time the light shafts pass where the adapter can write them
+            required_features: adapter.features() & wgpu::Features::TIMESTAMP_QUERY,
+            required_limits: Default::default(),
+            memory_hints: Default::default(),
+        }
+    }
 
-    async fn create_adapter(
+    /// Finds an adapter that can draw to the window.
+    ///
+    /// # Arguments
+    /// * `instance` - The instance, limited to the backends the player asked for
+    /// * `surface` - The window's surface, created by `instance`
+    ///
+    /// # Returns
+    /// The adapter, or `None` if no backend allowed by `instance` can drive the surface
+    pub async fn request_adapter(
         instance: &wgpu::Instance,
         surface: &wgpu::Surface<'static>,
-    ) -> wgpu::Adapter {
+    ) -> Option<wgpu::Adapter> {
         instance
             .request_adapter(&wgpu::RequestAdapterOptions {
                 power_preference: wgpu::PowerPreference::default(),
@@ -210,20 +534,13 @@ impl WgpuRenderer {
                 compatible_surface: Some(surface),
             })
             .await
-            .expect("Failed to find an appropriate adapter")
     }
 
+    // Private helper methods
+
     async fn create_device(adapter: &wgpu::Adapter) -> (wgpu::Device, wgpu::Queue) {
         adapter
-            .request_device(
-                &wgpu::DeviceDescriptor {
-                    label: None,
-                    required_features: wgpu::Features::empty(),
-                    required_limits: Default::default(),
-                    memory_hints: Default::default(),
-                },
-                None,
-            )
+            .request_device(&Self::device_descriptor(adapter), None)
             .await
             .expect("Failed to create device")
     }
@@ -242,16 +559,176 @@ impl WgpuRenderer {
             .copied()
             .expect("Failed to select proper surface texture format");
 
+        let alpha_mode = surface_alpha::select_alpha_mode(&capabilities.alpha_modes);
+        println!(
+            "Surface alpha mode: {:?} (supported: {:?}){}",
+            alpha_mode,
+            capabilities.alpha_modes,
+            if surface_alpha::needs_alpha_stamp(alpha_mode) {
+                ", frames are stamped opaque"
+            } else {
+                ""
+            }
+        );
+
+        // Copying the frame out is only needed for screenshots, so it is asked for when offered
+        let usage = wgpu::TextureUsages::RENDER_ATTACHMENT
+            | (capabilities.usages & wgpu::TextureUsages::COPY_SRC);
+
         wgpu::SurfaceConfiguration {
-            usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+            usage,
             format,
             width,
             height,
             present_mode: wgpu::PresentMode::AutoVsync,
             desired_maximum_frame_latency: 0,
-            alpha_mode: capabilities.alpha_modes[0],
-            view_formats: vec![],
+            alpha_mode,
+            // egui draws through a non-sRGB view of the surface
+            view_formats: if format.is_srgb() {
+                vec![color::ui_format(format)]
+            } else {
+                vec![]
+            },
+        }
+    }
+
+    /// Switches vsync on or off by reconfiguring the surface with a new present mode.
+    ///
+    /// Only the surface is reconfigured, so the change takes effect on the next frame without
+    /// recreating the device. Logs a warning if the surface doesn't support the mode asked for.
+    ///
+    /// # Arguments
+    /// * `mode` - How the player wants frames presented
+    ///
+    /// # Returns
+    /// Whether the surface's present mode changed
+    pub fn set_present_mode(&mut self, mode: PresentModePreference) -> bool {
+        let capabilities = self.surface.get_capabilities(&self.adapter);
+        let present_mode = present_mode::select_present_mode(mode, &capabilities.present_modes);
+        if present_mode != mode.present_mode() {
+            eprintln!(
+                "Warning: {:?} presentation isn't supported by the surface (supported: {:?}), using {:?}",
+                mode.present_mode(),
+                capabilities.present_modes,
+                present_mode
+            );
+        }
+        if present_mode == self.surface_config.present_mode {
+            return false;
+        }
+        self.surface_config.present_mode = present_mode;
+        self.surface.configure(&self.device, &self.surface_config);
+        true
+    }
+
+    /// Matches the surface and everything sized to it to a new window size.
+    ///
+    /// A window resized to nothing, as when it is minimized, leaves the surface configured at
+    /// its last size and no frames are drawn until it has a size again.
+    ///
+    /// # Arguments
+    /// * `width` - New window width in pixels
+    /// * `height` - New window height in pixels
+    ///
+    /// # Returns
+    /// Whether the surface was configured at the new size
+    pub fn resize(&mut self, width: u32, height: u32) -> bool {
+        self.minimized = width == 0 || height == 0;
+        if self.minimized {
+            return false;
+        }
+        self.surface_config.width = width;
+        self.surface_config.height = height;
+        self.surface.configure(&self.device, &self.surface_config);
+
+        // Recreated at the new size on the next frame
+        self.game_renderer.depth_texture = None;
+        self.game_renderer.compass_renderer.update_uniforms(
+            &self.queue,
+            [0.75, 0.75],
+            [4.75, 4.75],
+        );
+        self.msaa.resize(width, height);
+        self.replay_pip.resize(width, height);
+        self.heartbeat_renderer.resize(width, height);
+        self.light_shaft_renderer.resize(width, height);
+        self.death_fade_renderer.resize(width, height);
+        true
+    }
+
+    /// Returns whether frames can be saved as screenshots on this platform.
+    pub fn can_screenshot(&self) -> bool {
+        self.surface_config
+            .usage
+            .contains(wgpu::TextureUsages::COPY_SRC)
+    }
+
+    /// Copies a finished frame for a screenshot, if one was requested.
+    ///
+    /// Must be recorded after every other pass of the frame, so the screenshot shows what
+    /// the player sees.
+    ///
+    /// # Arguments
+    /// * `encoder` - The frame's command encoder
+    /// * `surface_texture` - The surface texture the frame was drawn to
+    ///
+    /// # Returns
+    /// The screenshot to save once the frame is submitted, or `None` if none was requested
+    /// or the surface can't be copied from
+    pub fn take_screenshot(
+        &mut self,
+        encoder: &mut wgpu::CommandEncoder,
+        surface_texture: &SurfaceTexture,
+    ) -> Option<PendingScreenshot> {
+        if !std::mem::take(&mut self.screenshot_requested) {
+            return None;
         }
+        PendingScreenshot::record(&self.device, encoder, &surface_texture.texture)
+    }
+
+    /// Sets the alpha of every pixel of a finished frame to 1.0 when the surface's alpha mode
+    /// would otherwise let the desktop show through.
+    ///
+    /// Must be recorded after every other pass of the frame.
+    ///
+    /// # Arguments
+    /// * `encoder` - The frame's command encoder
+    /// * `surface_view` - View of the surface texture the frame was drawn to
+    pub fn stamp_opaque_alpha(
+        &self,
+        encoder: &mut wgpu::CommandEncoder,
+        surface_view: &TextureView,
+    ) {
+        if let Some(alpha_stamp) = &self.alpha_stamp {
+            alpha_stamp.render(encoder, surface_view);
+        }
+    }
+
+    /// Converts an sRGBA design color to a clear color for the surface.
+    fn srgb_clear_color(&self, srgba: [u8; 4]) -> wgpu::Color {
+        color::clear_color(color::srgb_for_target(
+            color::srgba_from_u8(srgba),
+            self.surface_config.format,
+        ))
+    }
+
+    /// Creates a view of the surface texture for drawing egui.
+    ///
+    /// egui outputs sRGB-encoded colors, so it draws through a view in
+    /// [`color::ui_format`] instead of the surface's own sRGB format.
+    ///
+    /// # Arguments
+    /// * `surface_texture` - The surface texture acquired for this frame
+    ///
+    /// # Returns
+    /// A texture view in the egui-compatible format
+    pub fn create_ui_view(&self, surface_texture: &SurfaceTexture) -> TextureView {
+        surface_texture
+            .texture
+            .create_view(&wgpu::TextureViewDescriptor {
+                format: Some(color::ui_format(self.surface_config.format)),
+                ..Default::default()
+            })
     }
 
     /// Gets the current surface texture and creates a view for rendering.
@@ -260,20 +737,40 @@ impl WgpuRenderer {
     /// a texture view for use in render passes.
     ///
     /// # Returns
-    /// A Result containing the surface texture and texture view, or an error string
+    /// The surface texture and texture view, or `None` when there is nothing to draw this
+    /// frame: while the window is minimized, or while the surface is still outdated or timing
+    /// out after being configured again, which the window's next resize puts right
+    ///
+    /// A surface that is lost or outdated, such as after its present mode changed or the
+    /// window was resized, is configured again and the texture acquired a second time.
     ///
     /// # Errors
-    /// Returns an error if the surface is outdated or texture acquisition fails
+    /// Returns an error if texture acquisition fails for any other reason
     pub fn get_surface_texture_and_view(
         &mut self,
-    ) -> Result<(SurfaceTexture, TextureView), String> {
+    ) -> Result<Option<(SurfaceTexture, TextureView)>, String> {
+        if self.minimized {
+            return Ok(None);
+        }
         let surface_texture = match self.surface.get_current_texture() {
             Ok(texture) => texture,
-            Err(wgpu::SurfaceError::Outdated) => {
-                return Err("WGPU surface outdated".to_string());
+            Err(wgpu::SurfaceError::Lost | wgpu::SurfaceError::Outdated) => {
+                self.surface.configure(&self.device, &self.surface_config);
+                match self.surface.get_current_texture() {
+                    Ok(texture) => texture,
+                    Err(
+                        wgpu::SurfaceError::Lost
+                        | wgpu::SurfaceError::Outdated
+                        | wgpu::SurfaceError::Timeout,
+                    ) => return Ok(None),
+                    Err(e) => {
+                        return Err(format!("Failed to acquire next swap chain texture: {}", e));
+                    }
+                }
             }
-            Err(_) => {
-                return Err("Failed to acquire next swap chain texture".to_string());
+            Err(wgpu::SurfaceError::Timeout) => return Ok(None),
+            Err(e) => {
+                return Err(format!("Failed to acquire next swap chain texture: {}", e));
             }
         };
 
@@ -281,7 +778,7 @@ impl WgpuRenderer {
             .texture
             .create_view(&wgpu::TextureViewDescriptor::default());
 
-        Ok((surface_texture, surface_view))
+        Ok(Some((surface_texture, surface_view)))
     }
 
     /// Clean up GPU resources before dropping the renderer
@@ -294,14 +791,13 @@ impl WgpuRenderer {
     fn update_depth_texture(&mut self) -> TextureView {
         let (width, height) = (self.surface_config.width, self.surface_config.height);
         self.game_renderer
-            .update_depth_texture(&self.device, width, height)
+            .update_depth_texture(&self.device, &self.gpu_memory, width, height)
     }
 
     fn render_loading_screen(
         &mut self,
         encoder: &mut wgpu::CommandEncoder,
         surface_view: &TextureView,
-        window: &winit::window::Window,
     ) {
         let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
             label: Some("Loading Screen Render Pass"),
@@ -309,12 +805,7 @@ impl WgpuRenderer {
                 view: surface_view,
                 resolve_target: None,
                 ops: wgpu::Operations {
-                    load: wgpu::LoadOp::Clear(wgpu::Color {
-                        r: 0.1,
-                        g: 0.2,
-                        b: 0.3,
-                        a: 1.0,
-                    }),
+                    load: wgpu::LoadOp::Clear(self.srgb_clear_color(LOADING_CLEAR_COLOR)),
                     store: wgpu::StoreOp::Store,
                 },
             })],
@@ -323,10 +814,14 @@ impl WgpuRenderer {
             timestamp_writes: None,
         });
 
-        self.loading_screen_renderer
-            .render(&mut render_pass, window);
+        let size = PhysicalSize::new(self.surface_config.width, self.surface_config.height);
+        self.loading_screen_renderer.render(&mut render_pass, size);
     }
 
+    /// Renders the game over screen.
+    ///
+    /// For the first moments the live scene is drawn under the frozen copy of the moment of
+    /// death as it fades in; after that only the frozen copy is drawn under the overlay.
     #[allow(clippy::too_many_arguments)]
     fn render_game_over_screen(
         &mut self,
@@ -335,49 +830,35 @@ impl WgpuRenderer {
         depth_texture_view: &TextureView,
         game_state: &GameState,
         text_renderer: &mut TextRenderer,
-        window: &winit::window::Window,
-        app_start_time: std::time::Instant,
     ) {
-        let aspect = self.surface_config.width as f32 / self.surface_config.height as f32;
-        let background_color = [0.003, 0.0003, 0.007, 1.0];
+        if self.death_fade_renderer.draws_live_scene() {
+            let background_color = self.background_color(game_state);
 
-        // Clear pass
-        self.clear_render_target(encoder, surface_view, depth_texture_view, background_color);
+            // On the first frame the scene goes offscreen, to be copied as the frozen frame
+            let capture_view = self.death_fade_renderer.capture_view(&self.device);
+            let scene_view = capture_view.as_ref().unwrap_or(surface_view);
 
-        // Render stars
-        self.render_stars(encoder, surface_view, background_color);
+            // Clear pass
+            self.clear_render_target(encoder, scene_view, depth_texture_view, background_color);
 
-        // Render game objects (frozen state)
-        self.render_game_objects(
-            encoder,
-            surface_view,
-            depth_texture_view,
-            game_state,
-            aspect,
-        );
-
-        // Render game over overlay
-        self.render_game_over_overlay(encoder, surface_view, window);
-
-        // Apply auto-sizing logic to game over text (similar to title screen)
-        text_renderer.handle_game_over_text(self.surface_config.width, self.surface_config.height);
+            // Render the starfield or the sky panorama
+            self.render_background(encoder, scene_view, background_color, game_state);
 
-        // Animate the game over restart text color with a smooth sine wave
-        if let Ok(current_style) = text_renderer.get_style("game_over_restart") {
-            let mut new_style = current_style;
+            // Render game objects (frozen state)
+            self.render_game_objects(encoder, scene_view, depth_texture_view, game_state);
 
-            // Animate restart text color with a smooth sine wave
-            let elapsed_time = app_start_time.elapsed().as_secs_f32();
-            let color_shift = (elapsed_time * 2.0).sin() * 0.5 + 0.5; // Oscillate between 0.0 and 1.0
+            if capture_view.is_some() {
+                self.death_fade_renderer.capture(encoder);
+            }
+        }
 
-            // Create a color that shifts from a dark gray to white
-            let base_color = 100.0; // Base gray value
-            let color_range = 155.0; // Range of color variation (to reach white)
-            let animated_color = (base_color + color_range * color_shift) as u8;
-            new_style.color = glyphon::Color::rgb(animated_color, animated_color, animated_color);
+        // Fade the frozen frame in over the live scene, or draw it alone
+        let surface_size = (self.surface_config.width, self.surface_config.height);
+        self.death_fade_renderer
+            .render(&self.queue, encoder, surface_view, surface_size);
 
-            let _ = text_renderer.update_style("game_over_restart", new_style);
-        }
+        // Render game over overlay
+        self.render_game_over_overlay(encoder, surface_view);
 
         // Render text
         self.render_game_over_text(encoder, surface_view, text_renderer);
@@ -388,31 +869,20 @@ impl WgpuRenderer {
         encoder: &mut wgpu::CommandEncoder,
         surface_view: &TextureView,
         game_state: &GameState,
-        window: &winit::window::Window,
     ) {
-        if game_state.current_screen != crate::game::CurrentScreen::Game {
+        // Levels without a timer, like the sandbox's, have no bar at all
+        if game_state.current_screen != crate::game::CurrentScreen::Game
+            || game_state.game_ui.timer.is_none()
+        {
             return;
         }
-        let (progress, time) = if let Some(timer) = &game_state.game_ui.timer {
-            let remaining = timer.get_remaining_time().as_secs_f32();
-            let total = timer.config.duration.as_secs_f32();
-            let progress = if total > 0.0 {
-                (remaining / total).clamp(0.0, 1.0)
-            } else {
-                0.0
-            };
-            let time = timer.start_time.elapsed().as_secs_f32();
-            (progress, time)
-        } else {
-            (1.0, 0.0)
-        };
-        let window_size = window.inner_size();
-        let resolution = [window_size.width as f32, window_size.height as f32];
-        self.game_renderer.timer_bar_renderer.update_uniforms(
+        let hud = self.hud_area();
+        let timer_bar = &self.game_renderer.timer_bar_renderer;
+        timer_bar.update_uniforms(
             &self.queue,
-            progress,
-            resolution,
-            time,
+            &self
+                .animations
+                .timer_bar_uniforms(&hud, timer_bar.linear_output()),
         );
         let mut overlay_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
             label: Some("Timer Bar Overlay Pass"),
@@ -439,30 +909,28 @@ impl WgpuRenderer {
         encoder: &mut wgpu::CommandEncoder,
         surface_view: &TextureView,
         game_state: &GameState,
-        window: &winit::window::Window,
     ) {
         if game_state.current_screen != crate::game::CurrentScreen::Game {
             return;
         }
-        let progress = game_state.player.stamina_ratio();
-        let time = self
-            .game_renderer
-            .stamina_bar_renderer
-            .start_time
-            .elapsed()
-            .as_secs_f32();
-        let window_size = window.inner_size();
-        let resolution = [window_size.width as f32, window_size.height as f32];
-        let bar_height = (window_size.height as f32 * 0.0125).ceil() as u32; // 1.25% of window height, matches loading bar style
-        let bar_width = window_size.width;
-        let bar_x = 0u32;
+        let hud = self.hud_area();
+        let bar_height = top_bar_height(hud.height); // 1.25% of window height, matches loading bar style
+        let bar_width = hud.width as u32;
+        let bar_x = hud.x as u32; // Spans the HUD safe area, not the whole window
         let bar_y = 0u32; // Very top of the screen
-        self.game_renderer.stamina_bar_renderer.update_uniforms(
-            &self.queue,
-            progress,
-            resolution,
-            time,
-        );
+        let Some((bar_x, bar_y, bar_width, bar_height)) = clamp_scissor(
+            bar_x,
+            bar_y,
+            bar_width,
+            bar_height,
+            self.surface_config.width,
+            self.surface_config.height,
+        ) else {
+            return;
+        };
+        self.game_renderer
+            .stamina_bar_renderer
+            .update_uniforms(&self.queue, &self.animations.stamina_bar_uniforms(&hud));
         let mut overlay_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
             label: Some("Stamina Bar Overlay Pass"),
             color_attachments: &[Some(wgpu::RenderPassColorAttachment {
@@ -483,6 +951,58 @@ impl WgpuRenderer {
             .render(&mut overlay_pass);
     }
 
+    /// Draws a small icon in the top-right corner of the HUD, under the stamina bar,
+    /// while the player is crouching.
+    fn render_crouch_indicator(
+        &mut self,
+        encoder: &mut wgpu::CommandEncoder,
+        surface_view: &TextureView,
+        game_state: &GameState,
+    ) {
+        if game_state.current_screen != crate::game::CurrentScreen::Game
+            || !game_state.player.crouching
+        {
+            return;
+        }
+
+        let hud = self.hud_area();
+        let surface_size = (self.surface_config.width, self.surface_config.height);
+        if self.crouch_icon_layout != Some((hud, surface_size)) {
+            self.crouch_icon_layout = Some((hud, surface_size));
+            let (width, height) = (surface_size.0 as f32, surface_size.1 as f32);
+            let side = (hud.height * 0.045).max(16.0);
+            let margin = side * 0.4;
+            // Clear of the timer and stamina bars along the top edge
+            let top = hud.y + 2.0 * top_bar_height(hud.height) as f32 + margin;
+            self.crouch_icon.resize(width, height);
+            self.crouch_icon.clear_icons();
+            // Icon positions are measured from the bottom of the window
+            self.crouch_icon.add_icon(Icon::new(
+                hud.right() - side - margin,
+                height - top - side,
+                side,
+                side,
+                CROUCH_ICON_ID.to_string(),
+            ));
+        }
+
+        let mut icon_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some("Crouch Icon Pass"),
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view: surface_view,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Load,
+                    store: wgpu::StoreOp::Store,
+                },
+            })],
+            depth_stencil_attachment: None,
+            occlusion_query_set: None,
+            timestamp_writes: None,
+        });
+        self.crouch_icon.render(&self.device, &mut icon_pass);
+    }
+
     #[allow(clippy::too_many_arguments)]
     fn render_game_screen(
         &mut self,
@@ -491,42 +1011,78 @@ impl WgpuRenderer {
         depth_texture_view: &TextureView,
         game_state: &GameState,
         text_renderer: &mut TextRenderer,
-        window: &winit::window::Window,
     ) {
-        let aspect = self.surface_config.width as f32 / self.surface_config.height as f32;
-        let background_color = [0.003, 0.0003, 0.007, 1.0];
+        let background_color = self.background_color(game_state);
+
+        // In the timer's last seconds the scene is drawn offscreen, then distorted onto the
+        // surface under the HUD; without the texture it is drawn to the surface undistorted
+        let heartbeat = self.animations.heartbeat;
+        let heartbeat_view = if heartbeat > 0.0 && !self.reduce_effects {
+            self.heartbeat_renderer.scene_view(&self.device)
+        } else {
+            None
+        };
+        let heartbeat_target = heartbeat_view.as_ref().unwrap_or(surface_view);
+
+        // While the exit's beacon is in view the scene is drawn offscreen again, so its light
+        // shafts can be cast over it on the way to the heartbeat texture or the surface
+        let shaft_view = self.light_shaft_renderer.scene_view(&self.device);
+        let scene_view = shaft_view.as_ref().unwrap_or(heartbeat_target);
 
         // Clear pass
-        self.clear_render_target(encoder, surface_view, depth_texture_view, background_color);
+        self.clear_render_target(encoder, scene_view, depth_texture_view, background_color);
 
-        // Render stars
-        self.render_stars(encoder, surface_view, background_color);
+        // Render the starfield or the sky panorama
+        self.render_background(encoder, scene_view, background_color, game_state);
 
         // Render game objects
-        self.render_game_objects(
+        self.render_game_objects(encoder, scene_view, depth_texture_view, game_state);
+
+        // Render the level theme's dust or rain over the maze and under the HUD
+        self.render_ambient(encoder, scene_view, game_state);
+
+        self.light_shaft_renderer.render(
+            &self.device,
+            &self.queue,
             encoder,
-            surface_view,
             depth_texture_view,
-            game_state,
-            aspect,
+            heartbeat_target,
         );
 
+        if heartbeat_view.is_some() {
+            self.heartbeat_renderer.render(
+                &self.queue,
+                encoder,
+                surface_view,
+                heartbeat,
+                self.animations.heartbeat_phase,
+            );
+        }
+
         // Render timer bar overlay (after main pass, no depth)
-        self.render_timer_bar_overlay(encoder, surface_view, game_state, window);
+        self.render_timer_bar_overlay(encoder, surface_view, game_state);
         // Render stamina bar overlay below timer bar
-        self.render_stamina_bar_overlay(encoder, surface_view, game_state, window);
+        self.render_stamina_bar_overlay(encoder, surface_view, game_state);
+        self.render_crouch_indicator(encoder, surface_view, game_state);
 
-        // Render compass
-        self.render_compass(encoder, surface_view, game_state, window);
+        // Render compass and minimap, except while the overhead map view shows the whole maze
+        if !game_state.map_view.is_engaged() {
+            self.render_compass(encoder, surface_view);
+            self.render_minimap(encoder, surface_view, game_state);
+        }
 
-        // Auto-size and position score and level text
-        text_renderer
-            .handle_score_and_level_text(self.surface_config.width, self.surface_config.height);
+        // Render the reticle at the center of the 3D view
+        self.render_reticle(encoder, surface_view, game_state);
 
         // Render text
         self.render_text(encoder, surface_view, text_renderer);
+
+        // The capture sequence draws over everything, HUD included
+        self.render_capture_overlay(encoder, surface_view, game_state);
     }
 
+    /// Clears the surface, or the MSAA texture while MSAA is on, to the linear
+    /// `background_color` and resets the depth buffer.
     fn clear_render_target(
         &self,
         encoder: &mut wgpu::CommandEncoder,
@@ -536,19 +1092,14 @@ impl WgpuRenderer {
     ) {
         let _clear_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
             label: Some("Clear Pass"),
-            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
-                view: surface_view,
-                resolve_target: None,
-                ops: wgpu::Operations {
-                    load: wgpu::LoadOp::Clear(wgpu::Color {
-                        r: background_color[0] as f64,
-                        g: background_color[1] as f64,
-                        b: background_color[2] as f64,
-                        a: background_color[3] as f64,
-                    }),
-                    store: wgpu::StoreOp::Store,
-                },
-            })],
+            color_attachments: &[Some(self.msaa.color_attachment(
+                surface_view,
+                wgpu::LoadOp::Clear(color::clear_color(color::linear_for_target(
+                    background_color,
+                    self.surface_config.format,
+                ))),
+                false,
+            ))],
             depth_stencil_attachment: Some(wgpu::RenderPassDepthStencilAttachment {
                 view: depth_texture_view,
                 depth_ops: Some(wgpu::Operations {
@@ -562,45 +1113,126 @@ impl WgpuRenderer {
         });
     }
 
-    fn render_stars(
+    /// Returns the tuning with the level's time of day applied.
+    ///
+    /// # Arguments
+    /// * `game_state` - The game state holding the time of day of the level being played
+    fn scene_tuning(&self, game_state: &GameState) -> RenderTuning {
+        self.time_of_day
+            .apply(game_state.time_of_day)
+            .apply_to_tuning(&self.tuning)
+    }
+
+    /// Returns the linear color the 3D view is cleared to.
+    ///
+    /// The dusk palette cosmetic, while switched on, replaces the tuned background color;
+    /// otherwise it is tinted by the level's time of day.
+    ///
+    /// # Arguments
+    /// * `game_state` - The game state holding the time of day of the level being played
+    fn background_color(&self, game_state: &GameState) -> [f32; 4] {
+        if self.cosmetics.contains(&Cosmetic::DuskPalette) {
+            DUSK_BACKGROUND_COLOR
+        } else {
+            self.scene_tuning(game_state).background_color
+        }
+    }
+
+    /// Draws the chosen background: the sky panorama once it has loaded, otherwise the
+    /// starfield.
+    ///
+    /// # Arguments
+    /// * `encoder` - The command encoder for recording render commands
+    /// * `surface_view` - The texture view to render to
+    /// * `background_color` - Linear clear color the starfield is drawn over
+    /// * `game_state` - The game state holding the camera
+    fn render_background(
         &mut self,
         encoder: &mut wgpu::CommandEncoder,
         surface_view: &TextureView,
         background_color: [f32; 4],
+        game_state: &GameState,
     ) {
-        let elapsed_time = std::time::Instant::now().elapsed().as_secs_f32();
+        if self.background == BackgroundMode::Panorama && self.panorama_renderer.is_ready() {
+            self.render_panorama(encoder, surface_view, game_state);
+        } else {
+            self.render_stars(encoder, surface_view, background_color);
+        }
+    }
 
+    /// Draws the sky panorama over the 3D view, seen from the same camera as the maze.
+    ///
+    /// # Arguments
+    /// * `encoder` - The command encoder for recording render commands
+    /// * `surface_view` - The texture view to render to
+    /// * `game_state` - The game state holding the camera
+    fn render_panorama(
+        &mut self,
+        encoder: &mut wgpu::CommandEncoder,
+        surface_view: &TextureView,
+        game_state: &GameState,
+    ) {
+        let view = self.view_area();
+        self.panorama_renderer.update_uniforms(
+            &self.queue,
+            &game_state.map_view.view_matrix(&game_state.player),
+            &camera_projection(game_state.player.fov, view.aspect()),
+        );
+
+        let mut panorama_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some("Panorama Pass"),
+            color_attachments: &[Some(self.msaa.color_attachment(
+                surface_view,
+                wgpu::LoadOp::Load,
+                false,
+            ))],
+            depth_stencil_attachment: None,
+            occlusion_query_set: None,
+            timestamp_writes: None,
+        });
+        panorama_pass.set_viewport(view.x, view.y, view.width, view.height, 0.0, 1.0);
+        self.panorama_renderer.render(&mut panorama_pass);
+    }
+
+    fn render_stars(
+        &mut self,
+        encoder: &mut wgpu::CommandEncoder,
+        surface_view: &TextureView,
+        background_color: [f32; 4],
+    ) {
         // Update star renderer state
         self.game_renderer
             .star_renderer
             .update_background_color(&self.queue, background_color);
         self.game_renderer
             .star_renderer
-            .update_star_time(&self.queue, elapsed_time);
+            .update_star_time(&self.queue, self.animations.star_time);
+        let star_tint = if self.cosmetics.contains(&Cosmetic::EmberStars) {
+            EMBER_STAR_TINT
+        } else {
+            [0.0; 4]
+        };
+        self.game_renderer
+            .star_renderer
+            .update_star_tint(&self.queue, star_tint);
+
+        if self.game_renderer.star_renderer.num_indices == 0 {
+            return;
+        }
 
         let mut star_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
             label: Some("Star Pass"),
-            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
-                view: surface_view,
-                resolve_target: None,
-                ops: wgpu::Operations {
-                    load: wgpu::LoadOp::Load,
-                    store: wgpu::StoreOp::Store,
-                },
-            })],
+            color_attachments: &[Some(self.msaa.color_attachment(
+                surface_view,
+                wgpu::LoadOp::Load,
+                false,
+            ))],
             depth_stencil_attachment: None,
             occlusion_query_set: None,
             timestamp_writes: None,
         });
 
-        star_pass.set_pipeline(&self.game_renderer.star_renderer.pipeline);
-        star_pass.set_bind_group(0, &self.game_renderer.star_renderer.uniform_bind_group, &[]);
-        star_pass.set_vertex_buffer(0, self.game_renderer.star_renderer.vertex_buffer.slice(..));
-        star_pass.set_index_buffer(
-            self.game_renderer.star_renderer.index_buffer.slice(..),
-            wgpu::IndexFormat::Uint16,
-        );
-        star_pass.draw_indexed(0..self.game_renderer.star_renderer.num_indices, 0, 0..1);
+        self.game_renderer.star_renderer.render(&mut star_pass);
     }
 
     fn render_game_objects(
@@ -609,18 +1241,16 @@ impl WgpuRenderer {
         surface_view: &TextureView,
         depth_texture_view: &TextureView,
         game_state: &GameState,
-        aspect: f32,
     ) {
+        let view = self.view_area();
         let mut main_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
             label: Some("Main Render Pass"),
-            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
-                view: surface_view,
-                resolve_target: None,
-                ops: wgpu::Operations {
-                    load: wgpu::LoadOp::Load,
-                    store: wgpu::StoreOp::Store,
-                },
-            })],
+            // The last pass into the multisampled texture resolves it into the scene
+            color_attachments: &[Some(self.msaa.color_attachment(
+                surface_view,
+                wgpu::LoadOp::Load,
+                true,
+            ))],
             depth_stencil_attachment: Some(wgpu::RenderPassDepthStencilAttachment {
                 view: depth_texture_view,
                 depth_ops: Some(wgpu::Operations {
@@ -632,26 +1262,149 @@ impl WgpuRenderer {
             occlusion_query_set: None,
             timestamp_writes: None,
         });
+        main_pass.set_viewport(view.x, view.y, view.width, view.height, 0.0, 1.0);
+
+        // Against the panorama, distant geometry fades into the sky's average color
+        let panorama_fog = self
+            .panorama_renderer
+            .average_color()
+            .filter(|_| self.background == BackgroundMode::Panorama);
+        let tuning = self.scene_tuning(game_state);
+        let tuning = RenderTuning {
+            fog_color: panorama_fog.unwrap_or(tuning.fog_color),
+            ..tuning
+        };
+
+        // Level events change the fog, and the sandbox can switch it on or off
+        let tuning = game_state.events.effects().apply_to_tuning(&tuning);
+        let tuning = match &game_state.sandbox {
+            Some(session) => session.generated.apply_to_tuning(&tuning),
+            None => tuning,
+        };
 
         // Use benchmark macro for game rendering timing
         crate::debug_benchmark!("game_objects_rendering", {
-            self.game_renderer
-                .render_game(&self.queue, game_state, &mut main_pass, aspect);
+            self.game_renderer.render_game(
+                &self.queue,
+                game_state,
+                &mut main_pass,
+                view.aspect(),
+                &tuning,
+                self.reduce_effects,
+            );
         });
+        drop(main_pass);
+
+        self.render_letterbox(encoder, surface_view, view);
     }
 
-    fn render_compass(
+    /// Draws the level theme's ambient particles over the 3D view.
+    ///
+    /// Skipped when the player has asked for reduced effects, and while the overhead map
+    /// view looks down at the floor.
+    ///
+    /// # Arguments
+    /// * `encoder` - The command encoder for recording render commands
+    /// * `surface_view` - The texture view to render to
+    /// * `game_state` - The game state holding the level theme and the camera
+    fn render_ambient(
         &mut self,
         encoder: &mut wgpu::CommandEncoder,
         surface_view: &TextureView,
         game_state: &GameState,
-        window: &winit::window::Window,
     ) {
-        if let Some(exit_position) = self.game_renderer.exit_position {
-            self.game_renderer.compass_renderer.update_compass_with_yaw(
-                (game_state.player.position[0], game_state.player.position[2]),
-                exit_position,
-                game_state.player.yaw,
+        if self.reduce_effects || game_state.map_view.is_engaged() {
+            return;
+        }
+
+        let view = self.view_area();
+        let ambience = game_state.theme.ambience();
+        // Wrapped so the particle drift keeps its precision in long sessions
+        let time = self.animations.clock % AMBIENT_TIME_WRAP;
+        self.ambient_renderer.update(
+            &self.queue,
+            &ambience,
+            color::linear_for_target(ambience.color, self.surface_config.format),
+            &view,
+            &game_state.player,
+            time,
+        );
+
+        let mut ambient_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some("Ambient Pass"),
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view: surface_view,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Load,
+                    store: wgpu::StoreOp::Store,
+                },
+            })],
+            depth_stencil_attachment: None,
+            occlusion_query_set: None,
+            timestamp_writes: None,
+        });
+        ambient_pass.set_viewport(view.x, view.y, view.width, view.height, 0.0, 1.0);
+        crate::debug_benchmark!("ambient_rendering", {
+            self.ambient_renderer.render(&mut ambient_pass);
+        });
+    }
+
+    /// Covers the parts of the surface outside a boxed 3D view with black bars.
+    ///
+    /// # Arguments
+    /// * `encoder` - The command encoder for recording render commands
+    /// * `surface_view` - The texture view to render to
+    /// * `view` - The region the 3D view was drawn in
+    fn render_letterbox(
+        &mut self,
+        encoder: &mut wgpu::CommandEncoder,
+        surface_view: &TextureView,
+        view: SafeArea,
+    ) {
+        let (width, height) = (self.surface_config.width, self.surface_config.height);
+        let bars = view.bars(width, height);
+        if bars.is_empty() {
+            return;
+        }
+
+        let letterbox = &mut self.game_renderer.letterbox_renderer;
+        letterbox.resize(width as f32, height as f32);
+        letterbox.clear_rectangles();
+        for (x, y, w, h) in bars {
+            letterbox.add_rectangle(Rectangle::new(
+                x as f32,
+                y as f32,
+                w as f32,
+                h as f32,
+                [0.0, 0.0, 0.0, 1.0],
+            ));
+        }
+
+        let mut letterbox_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some("Letterbox Pass"),
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view: surface_view,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Load,
+                    store: wgpu::StoreOp::Store,
+                },
+            })],
+            depth_stencil_attachment: None,
+            occlusion_query_set: None,
+            timestamp_writes: None,
+        });
+        letterbox.render(&self.device, &mut letterbox_pass);
+    }
+
+    fn render_compass(&mut self, encoder: &mut wgpu::CommandEncoder, surface_view: &TextureView) {
+        if self.game_renderer.exit_position.is_some() {
+            self.game_renderer.compass_renderer.update_layout(
+                &self.queue,
+                &self.hud_area(),
+                self.surface_config.width as f32,
+                self.surface_config.height as f32,
             );
 
             let mut compass_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
@@ -671,16 +1424,172 @@ impl WgpuRenderer {
 
             self.game_renderer
                 .compass_renderer
-                .render(&mut compass_pass, window);
+                .render(&mut compass_pass);
+        }
+    }
+
+    /// Draws the map of the explored maze in the corner of the HUD.
+    ///
+    /// Does nothing unless the map is shown and a level is being played.
+    fn render_minimap(
+        &mut self,
+        encoder: &mut wgpu::CommandEncoder,
+        surface_view: &TextureView,
+        game_state: &GameState,
+    ) {
+        if !self.minimap_renderer.visible || game_state.current_screen != CurrentScreen::Game {
+            return;
+        }
+        let hud = self.hud_area();
+        self.minimap_renderer
+            .update(&self.device, &self.queue, game_state, &hud);
+
+        let mut minimap_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some("Minimap Render Pass"),
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view: surface_view,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Load,
+                    store: wgpu::StoreOp::Store,
+                },
+            })],
+            depth_stencil_attachment: None,
+            occlusion_query_set: None,
+            timestamp_writes: None,
+        });
+        self.minimap_renderer.render(&mut minimap_pass);
+    }
+
+    /// Draws the capture sequence's vignette, fade to black, and enemy face.
+    ///
+    /// Does nothing unless the enemy has just caught the player.
+    fn render_capture_overlay(
+        &mut self,
+        encoder: &mut wgpu::CommandEncoder,
+        surface_view: &TextureView,
+        game_state: &GameState,
+    ) {
+        let Some(capture) = &game_state.capture else {
+            return;
+        };
+        let overlay = capture.overlay(self.reduce_effects);
+        let aspect = self.surface_config.width as f32 / self.surface_config.height.max(1) as f32;
+        self.vignette_renderer
+            .update(&self.queue, overlay.vignette, overlay.fade, aspect);
+        self.enemy_face_renderer
+            .update(&self.queue, overlay.time, overlay.face, aspect);
+
+        let mut capture_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some("Capture Overlay Render Pass"),
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view: surface_view,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Load,
+                    store: wgpu::StoreOp::Store,
+                },
+            })],
+            depth_stencil_attachment: None,
+            occlusion_query_set: None,
+            timestamp_writes: None,
+        });
+        self.vignette_renderer.render(&mut capture_pass);
+        if overlay.face > 0.0 {
+            self.enemy_face_renderer.render(&mut capture_pass);
         }
     }
 
+    /// Draws the replay of the level just escaped in the corner of the screen.
+    ///
+    /// Should be called once per frame while the level summary or the upgrade menu is up,
+    /// after the UI the replay is drawn over.
+    ///
+    /// # Arguments
+    /// * `encoder` - The command encoder for recording render commands
+    /// * `surface_view` - The texture view to render to
+    /// * `game_state` - The game state holding the recorded path
+    /// * `pointer` - The cursor position, if the cursor is free; hovering pauses the replay
+    pub fn render_replay_pip(
+        &mut self,
+        encoder: &mut wgpu::CommandEncoder,
+        surface_view: &TextureView,
+        game_state: &GameState,
+        pointer: Option<(f32, f32)>,
+    ) {
+        self.replay_pip.update(
+            game_state.delta_time,
+            pointer,
+            game_state.run_recording.duration(),
+        );
+        let tuning = game_state
+            .events
+            .effects()
+            .apply_to_tuning(&self.scene_tuning(game_state));
+        self.replay_pip
+            .sync_scene(&self.game_renderer, tuning, self.reduce_effects);
+        self.replay_pip
+            .render(&self.device, &self.queue, encoder, surface_view, game_state);
+    }
+
+    fn render_reticle(
+        &mut self,
+        encoder: &mut wgpu::CommandEncoder,
+        surface_view: &TextureView,
+        game_state: &GameState,
+    ) {
+        if !game_state.reticle_visible() || !self.reticle.is_enabled() {
+            return;
+        }
+        let view = self.view_area();
+        let center = [view.center_x(), view.y + view.height / 2.0];
+        let half_extent = self.reticle.half_extent();
+        let Some((x, y, width, height)) = clamp_scissor(
+            (center[0] - half_extent).max(0.0) as u32,
+            (center[1] - half_extent).max(0.0) as u32,
+            (half_extent * 2.0).ceil() as u32,
+            (half_extent * 2.0).ceil() as u32,
+            self.surface_config.width,
+            self.surface_config.height,
+        ) else {
+            return;
+        };
+        let mut reticle = self.reticle;
+        if self.cosmetics.contains(&Cosmetic::RingReticle) {
+            reticle.style = ReticleStyle::Ring;
+        }
+        self.game_renderer
+            .reticle_renderer
+            .update(&self.queue, &reticle, center);
+
+        let mut reticle_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some("Reticle Render Pass"),
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view: surface_view,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Load,
+                    store: wgpu::StoreOp::Store,
+                },
+            })],
+            depth_stencil_attachment: None,
+            occlusion_query_set: None,
+            timestamp_writes: None,
+        });
+        reticle_pass.set_scissor_rect(x, y, width, height);
+        self.game_renderer
+            .reticle_renderer
+            .render(&mut reticle_pass);
+    }
+
     fn render_game_over_overlay(
         &mut self,
         encoder: &mut wgpu::CommandEncoder,
         surface_view: &TextureView,
-        window: &winit::window::Window,
     ) {
+        // The overlay pulses with the time since death, on the same clock as the fade
+        self.game_over_renderer
+            .update_time(&self.queue, self.death_fade_renderer.elapsed());
         let mut game_over_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
             label: Some("Game Over Overlay Pass"),
             color_attachments: &[Some(wgpu::RenderPassColorAttachment {
@@ -696,7 +1605,7 @@ impl WgpuRenderer {
             timestamp_writes: None,
         });
 
-        self.game_over_renderer.render(&mut game_over_pass, window);
+        self.game_over_renderer.render(&mut game_over_pass);
     }
 
     /// Renders text elements to the surface.
@@ -743,7 +1652,6 @@ impl WgpuRenderer {
         text_renderer: &mut TextRenderer,
     ) {
         self.prepare_text_renderer(text_renderer);
-        text_renderer.show_game_over_display();
 
         let mut text_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
             label: Some("Game Over Text Pass"),
@@ -0,0 +1,374 @@
+//! Test mode functionality for Mirador.
+//!
+//! This module provides a simplified test environment when TEST_MODE is enabled.
+//! It creates a small maze with only perimeter walls, places the exit in the center,
+//! locks the enemy in a fixed position, and freezes the timer at 420.00 seconds.
+//!
+//! The layout is read from [`TEST_MAZE_PATH`] so it can be hand-edited for level design. The file
+//! is created from the built-in perimeter layout the first time test mode runs, and
+//! [`reload_test_maze`] rebuilds the level in place whenever the file changes.
+//!
+//! The [`soak`] test, which lets a bot play the full game for a long session while watching
+//! for leaks, and the [`self_test`] run for bug reports, which checks the GPU, shaders, assets
+//! and audio without starting a session, live here too.
+
+pub mod self_test;
+pub mod soak;
+
+use crate::game::GameState;
+use crate::game::PauseSource;
+use crate::game::TimerConfig;
+use crate::game::enemy::Enemy;
+use crate::game::maze::generator::Cell;
+use crate::game::maze::try_parse_maze_file;
+use crate::math::coordinates::world_to_maze;
+use crate::renderer::primitives::Vertex;
+use crate::renderer::text::{TextPosition, TextRenderer, TextStyle};
+use crate::renderer::wgpu_lib::WgpuRenderer;
+use glyphon::Color;
+use std::fs;
+use std::path::Path;
+use std::time::Duration;
+use wgpu::util::DeviceExt;
+use winit::window::Window;
+
+/// Test maze dimensions (smaller than normal maze)
+const TEST_MAZE_WIDTH: usize = 6;
+const TEST_MAZE_HEIGHT: usize = 6;
+
+/// Hand-editable maze file loaded (and watched for changes) in test mode
+pub const TEST_MAZE_PATH: &str = "test_maze.mz";
+
+/// Text buffer id for the maze reload error banner
+const MAZE_ERROR_BANNER_ID: &str = "test_maze_error";
+
+/// Creates a test maze grid with only perimeter walls
+pub fn create_test_maze_grid() -> Vec<Vec<bool>> {
+    let mut maze_grid = vec![vec![false; TEST_MAZE_WIDTH * 2 + 1]; TEST_MAZE_HEIGHT * 2 + 1];
+
+    // Add perimeter walls
+    let width = maze_grid[0].len();
+    let height = maze_grid.len();
+
+    // Top and bottom walls
+    for col in 0..width {
+        maze_grid[0][col] = true; // Top wall
+        maze_grid[height - 1][col] = true; // Bottom wall
+    }
+
+    // Left and right walls
+    for row in 0..height {
+        maze_grid[row][0] = true; // Left wall
+        maze_grid[row][width - 1] = true; // Right wall
+    }
+
+    // Debug: Print the maze grid
+    println!("Test maze grid ({}x{}):", width, height);
+    for (i, row) in maze_grid.iter().enumerate() {
+        let row_str: String = row
+            .iter()
+            .map(|&cell| if cell { '#' } else { ' ' })
+            .collect();
+        println!("Row {:2}: {}", i, row_str);
+    }
+
+    maze_grid
+}
+
+/// Gets the center cell of the test maze for the exit
+pub fn get_test_exit_cell() -> Cell {
+    // For even-sized mazes, pick the cell closest to the world center
+    // (for 6x6, this is (2,2) or (3,3); we'll use (2,2) for logic)
+    Cell::new((TEST_MAZE_HEIGHT - 1) / 2, (TEST_MAZE_WIDTH - 1) / 2)
+}
+
+/// Returns the world coordinates of the center of the room for exit patch rendering
+pub fn get_world_center_for_exit_patch(maze_grid: &[Vec<bool>]) -> (f32, f32) {
+    let maze_width = maze_grid[0].len();
+    let maze_height = maze_grid.len();
+    let max_dimension = maze_width.max(maze_height) as f32;
+    let floor_size =
+        crate::math::coordinates::constants::get_floor_size((maze_width, maze_height), true); // Test mode floor size
+    let cell_size = floor_size / max_dimension;
+    let origin_x = -(maze_width as f32 * cell_size) / 2.0;
+    let origin_z = -(maze_height as f32 * cell_size) / 2.0;
+    let center_x = origin_x + (maze_width as f32) * cell_size / 2.0;
+    let center_z = origin_z + (maze_height as f32) * cell_size / 2.0;
+    (center_x, center_z)
+}
+
+/// Converts a wall grid to `.mz` text, the inverse of [`crate::game::maze::parse_maze_str`]
+pub fn maze_grid_to_string(maze_grid: &[Vec<bool>]) -> String {
+    let mut contents = String::new();
+    for row in maze_grid {
+        contents.extend(row.iter().map(|&is_wall| if is_wall { '#' } else { ' ' }));
+        contents.push('\n');
+    }
+    contents
+}
+
+/// Loads the test maze layout from [`TEST_MAZE_PATH`].
+///
+/// If the file does not exist it is created from the built-in perimeter layout so there is
+/// something to edit. If it exists but cannot be parsed, the built-in layout is used and the
+/// error is returned alongside it so it can be shown to the player.
+///
+/// # Returns
+/// The wall grid, the exit cell marked in the file (if any), and any parse error
+pub fn load_test_maze_grid() -> (Vec<Vec<bool>>, Option<Cell>, Option<String>) {
+    let path = Path::new(TEST_MAZE_PATH);
+    if !path.exists() {
+        let maze_grid = create_test_maze_grid();
+        if let Err(e) = fs::write(path, maze_grid_to_string(&maze_grid)) {
+            eprintln!("Failed to write {}: {}", TEST_MAZE_PATH, e);
+        }
+        return (maze_grid, None, None);
+    }
+
+    match try_parse_maze_file(path) {
+        Ok((maze_grid, exit_cell)) => (maze_grid, exit_cell, None),
+        Err(e) => {
+            eprintln!("Failed to load {}: {}", TEST_MAZE_PATH, e);
+            (create_test_maze_grid(), None, Some(e))
+        }
+    }
+}
+
+/// Creates a locked enemy positioned further from the exit
+///
+/// # Arguments
+/// * `exit_patch_position` - World position of the exit patch
+/// * `wall_grid_size` - The larger dimension of the wall grid, used to size one cell
+pub fn create_test_enemy(exit_patch_position: [f32; 3], wall_grid_size: usize) -> Enemy {
+    // Move the enemy 2 cells away in both X and Z directions
+    let floor_size =
+        crate::math::coordinates::constants::get_floor_size((wall_grid_size, wall_grid_size), true); // Test mode floor size
+    let patch_size = floor_size / wall_grid_size as f32;
+    let enemy_position = [
+        exit_patch_position[0] - 4.0 * patch_size, // 2 cells to the left
+        30.0,                                      // Same height
+        exit_patch_position[2] - 4.0 * patch_size, // 2 cells back
+    ];
+
+    let mut enemy = Enemy::new(enemy_position, 150.0);
+    // Lock the enemy in place
+    enemy.pathfinder.locked = true;
+
+    enemy
+}
+
+/// Creates a test timer configuration with frozen time
+pub fn create_test_timer_config() -> TimerConfig {
+    TimerConfig {
+        duration: Duration::from_secs_f32(420.0), // 420.00 seconds
+        warning_threshold: Duration::from_secs_f32(420.0), // Never warn
+        critical_threshold: Duration::from_secs_f32(420.0), // Never critical
+        normal_color: Color::rgb(100, 255, 100),
+        warning_color: Color::rgb(255, 255, 100),
+        critical_color: Color::rgb(255, 100, 100),
+    }
+}
+
+/// Sets up the test environment
+///
+/// # Returns
+/// The error from parsing [`TEST_MAZE_PATH`], if any. The built-in layout is used in that case.
+pub fn setup_test_environment(
+    game_state: &mut GameState,
+    wgpu_renderer: &mut WgpuRenderer,
+) -> Option<String> {
+    // Load the test maze grid, falling back to the built-in layout
+    let (maze_grid, exit_cell, load_error) = load_test_maze_grid();
+
+    build_test_level(game_state, wgpu_renderer, &maze_grid, exit_cell);
+
+    // Spawn player at the entrance (bottom-left corner)
+    game_state.player.spawn_at_maze_entrance(&maze_grid, true); // Test mode
+
+    reset_test_timer(game_state);
+
+    println!("Test mode initialized:");
+    println!(
+        "  - Maze size: {}x{}",
+        maze_grid[0].len() / 2,
+        maze_grid.len() / 2
+    );
+    println!("  - Exit at: {:?}", game_state.exit_cell);
+    println!("  - Timer frozen at 420.00 seconds");
+    println!(
+        "  - Enemy locked at position: {:?}",
+        game_state.enemy.pathfinder.position
+    );
+
+    load_error
+}
+
+/// Re-reads [`TEST_MAZE_PATH`] and rebuilds the running test level from it.
+///
+/// On success the geometry, collision, exit, and enemy are rebuilt, the timer is reset, the
+/// error banner is hidden, and the player is moved to the entrance only if they would
+/// otherwise be inside a wall or outside the new maze. On failure the current level is left
+/// untouched and the error is shown in a banner until the next successful reload.
+///
+/// # Arguments
+/// * `game_state` - The game state to rebuild
+/// * `wgpu_renderer` - The renderer holding the level geometry
+/// * `text_renderer` - Used to show or hide the error banner
+/// * `window` - Used to size the error banner
+pub fn reload_test_maze(
+    game_state: &mut GameState,
+    wgpu_renderer: &mut WgpuRenderer,
+    text_renderer: &mut TextRenderer,
+    window: &Window,
+) {
+    let (maze_grid, exit_cell) = match try_parse_maze_file(Path::new(TEST_MAZE_PATH)) {
+        Ok(parsed) => parsed,
+        Err(e) => {
+            eprintln!("Keeping previous test maze: {}", e);
+            show_maze_error_banner(text_renderer, window, &e);
+            game_state
+                .toasts
+                .system("Test maze has errors, keeping the previous one");
+            return;
+        }
+    };
+
+    build_test_level(game_state, wgpu_renderer, &maze_grid, exit_cell);
+
+    let position = game_state.player.position;
+    let maze_dimensions = (maze_grid[0].len(), maze_grid.len());
+    let cell = world_to_maze(position, maze_dimensions, true);
+    if !game_state.collision_system.is_within_world_bounds(position)
+        || maze_grid[cell.row][cell.col]
+    {
+        println!("Player cell no longer exists, moving to entrance");
+        game_state.player.spawn_at_maze_entrance(&maze_grid, true);
+    }
+
+    reset_test_timer(game_state);
+
+    hide_maze_error_banner(text_renderer);
+    println!("Reloaded {}", TEST_MAZE_PATH);
+    game_state.toasts.system("Test maze reloaded");
+}
+
+/// Builds the geometry, collision, exit, and enemy for a test maze layout.
+///
+/// Uses the exit marked in the file if there is one, otherwise places the exit in the
+/// center of the room as the built-in layout does.
+fn build_test_level(
+    game_state: &mut GameState,
+    wgpu_renderer: &mut WgpuRenderer,
+    maze_grid: &[Vec<bool>],
+    exit_cell: Option<Cell>,
+) {
+    // Generate geometry from the test maze
+    let (mut floor_vertices, exit_patch_position, exit_cell) = match exit_cell {
+        Some(exit_cell) => {
+            let (floor_vertices, exit_position) =
+                Vertex::create_floor_vertices(maze_grid, Some(exit_cell), true);
+            (floor_vertices, exit_position, exit_cell)
+        }
+        None => {
+            // Use the world center for the exit patch
+            let exit_patch_position = get_world_center_for_exit_patch(maze_grid);
+            let (mut floor_vertices, _) = Vertex::create_floor_vertices(maze_grid, None, true); // Test mode floor size
+            // Add a green exit patch at the world center
+            floor_vertices.extend(Vertex::create_exit_patch_at_world_position(
+                exit_patch_position,
+                true, // Test mode
+            ));
+            (floor_vertices, exit_patch_position, get_test_exit_cell())
+        }
+    };
+
+    // Set exit position in renderer (as tuple)
+    wgpu_renderer.game_renderer.exit_position = Some(exit_patch_position);
+
+    // Add wall vertices (test mode always uses perimeter walls)
+    floor_vertices.append(&mut Vertex::create_wall_vertices(maze_grid, true));
+
+    // Add ceiling vertices
+    floor_vertices.append(&mut Vertex::create_ceiling_vertices(maze_grid, true));
+
+    // Create vertex buffer
+    wgpu_renderer.game_renderer.vertex_buffer =
+        wgpu_renderer
+            .device
+            .create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                label: Some("Test Maze Vertex Buffer"),
+                contents: bytemuck::cast_slice(&floor_vertices),
+                usage: wgpu::BufferUsages::VERTEX,
+            });
+
+    // Update vertex count so the renderer knows how many vertices to draw
+    wgpu_renderer.game_renderer.vertex_count = floor_vertices.len() as u32;
+    wgpu_renderer.game_renderer.upper_floor_start = None;
+
+    println!(
+        "Debug: Created {} vertices for test maze",
+        floor_vertices.len()
+    );
+    println!(
+        "Debug: Maze grid dimensions: {}x{}",
+        maze_grid.len(),
+        maze_grid[0].len()
+    );
+    println!(
+        "Debug: Wall count: {}",
+        maze_grid
+            .iter()
+            .flatten()
+            .filter(|&&is_wall| is_wall)
+            .count()
+    );
+
+    // Set exit cell in game state
+    game_state.exit_cell = Some(exit_cell);
+
+    // Create and place locked enemy (convert tuple to array for enemy position)
+    let enemy_position = [exit_patch_position.0, 30.0, exit_patch_position.1];
+    let wall_grid_size = maze_grid.len().max(maze_grid[0].len());
+    game_state.enemy = create_test_enemy(enemy_position, wall_grid_size);
+
+    // Build collision system from test maze (test mode always uses perimeter walls)
+    game_state.collision_system.build_from_maze(maze_grid, true);
+}
+
+/// Starts the test timer and immediately freezes it at 420.00 seconds.
+fn reset_test_timer(game_state: &mut GameState) {
+    let timer_config = create_test_timer_config();
+    game_state.start_game_timer(Some(timer_config));
+    game_state.game_ui.pause_timer(PauseSource::Menu);
+}
+
+/// Shows a maze parse error in a banner along the top of the screen.
+pub fn show_maze_error_banner(text_renderer: &mut TextRenderer, window: &Window, message: &str) {
+    let window_size = window.inner_size();
+    let style = TextStyle {
+        font_family: "Hanken Grotesk".to_string(),
+        font_size: 22.0,
+        line_height: 26.0,
+        color: Color::rgb(255, 100, 100),
+        weight: glyphon::Weight::BOLD,
+        style: glyphon::Style::Normal,
+    };
+    let position = TextPosition {
+        x: 20.0,
+        y: 40.0,
+        max_width: Some(window_size.width as f32 - 40.0),
+        max_height: Some(60.0),
+    };
+    text_renderer.create_text_buffer(
+        MAZE_ERROR_BANNER_ID,
+        &format!("{}: {}", TEST_MAZE_PATH, message),
+        Some(style),
+        Some(position),
+    );
+}
+
+/// Hides the maze error banner, for example when leaving test mode.
+pub fn hide_maze_error_banner(text_renderer: &mut TextRenderer) {
+    // The banner only exists after a failed reload
+    let _ = text_renderer.set_buffer_visibility(MAZE_ERROR_BANNER_ID, false);
+}
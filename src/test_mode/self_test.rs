@@ -0,0 +1,738 @@
+//! Startup self-test for bug reports.
+//!
+//! `mirador --self-test` checks everything the game needs before it can open a window, prints a
+//! report meant to be pasted into a bug report, and exits without starting a session. It:
+//!
+//! - lists every adapter the chosen graphics backend can see, with its driver
+//! - creates a device the way the game does and checks the adapter meets the game's limits
+//! - compiles every embedded WGSL shader, reporting validation errors per file
+//! - validates the embedded assets through the [`AssetRegistry`]
+//! - opens the default audio output and closes it again
+//! - draws the title screen and the maze into a [`TARGET_SIZE`] square offscreen target
+//!
+//! Each check is isolated: a panic or device error is caught and reported as that check's
+//! failure, and checks that need a device are skipped rather than failed when there isn't one.
+//! The report starts with the crate version and the effective data paths so it describes the
+//! install it came from, and the game exits with status 1 if a critical check failed.
+//!
+//! # Usage
+//!
+//! ```rust,ignore
+//! if self_test::requested(&args) {
+//!     let report = self_test::run(settings.graphics_backend, &paths::get());
+//!     println!("{}", report);
+//!     std::process::exit(if report.passed() { 0 } else { 1 });
+//! }
+//! ```
+
+use crate::assets::registry::{self, AssetRegistry};
+use crate::game::GameState;
+use crate::game::maze::generator::MazeGenerator;
+use crate::paths::Paths;
+use crate::renderer::backend::{GraphicsBackend, adapter_summary};
+use crate::renderer::maze_view::MazeView;
+use crate::renderer::title::TitleRenderer;
+use crate::renderer::wgpu_lib::WgpuRenderer;
+use cpal::traits::{DeviceTrait, HostTrait};
+use kira::{AudioManager, AudioManagerSettings, DefaultBackend};
+use std::fmt;
+use std::panic::{self, AssertUnwindSafe};
+use std::time::{Duration, Instant};
+
+/// Command-line flag that runs the self-test instead of the game.
+pub const SELF_TEST_FLAG: &str = "--self-test";
+
+/// Width and height of the offscreen render check's target, in pixels.
+pub const TARGET_SIZE: u32 = 256;
+
+/// Format of the offscreen target, the sRGB format most surfaces use.
+const TARGET_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Rgba8UnormSrgb;
+
+/// Device errors caught around each piece of GPU work.
+const ERROR_FILTERS: [wgpu::ErrorFilter; 3] = [
+    wgpu::ErrorFilter::OutOfMemory,
+    wgpu::ErrorFilter::Validation,
+    wgpu::ErrorFilter::Internal,
+];
+
+/// Every WGSL shader embedded in the game, by file name.
+pub const SHADERS: [(&str, &str); 31] = [
+    (
+        "2D-maze-shader.wgsl",
+        include_str!("../renderer/shaders/2D-maze-shader.wgsl"),
+    ),
+    (
+        "alpha_stamp.wgsl",
+        include_str!("../renderer/shaders/alpha_stamp.wgsl"),
+    ),
+    (
+        "ambient.wgsl",
+        include_str!("../renderer/shaders/ambient.wgsl"),
+    ),
+    (
+        "anomaly.wgsl",
+        include_str!("../renderer/shaders/anomaly.wgsl"),
+    ),
+    (
+        "compass.wgsl",
+        include_str!("../renderer/shaders/compass.wgsl"),
+    ),
+    (
+        "death_fade.wgsl",
+        include_str!("../renderer/shaders/death_fade.wgsl"),
+    ),
+    ("decal.wgsl", include_str!("../renderer/shaders/decal.wgsl")),
+    ("enemy.wgsl", include_str!("../renderer/shaders/enemy.wgsl")),
+    (
+        "enemy_face.wgsl",
+        include_str!("../renderer/shaders/enemy_face.wgsl"),
+    ),
+    (
+        "exit_shader.wgsl",
+        include_str!("../renderer/shaders/exit_shader.wgsl"),
+    ),
+    (
+        "game-over.wgsl",
+        include_str!("../renderer/shaders/game-over.wgsl"),
+    ),
+    (
+        "heartbeat.wgsl",
+        include_str!("../renderer/shaders/heartbeat.wgsl"),
+    ),
+    (
+        "heatmap.wgsl",
+        include_str!("../renderer/shaders/heatmap.wgsl"),
+    ),
+    ("icon.wgsl", include_str!("../renderer/shaders/icon.wgsl")),
+    (
+        "light_shaft_occlusion.wgsl",
+        include_str!("../renderer/shaders/light_shaft_occlusion.wgsl"),
+    ),
+    (
+        "light_shafts.wgsl",
+        include_str!("../renderer/shaders/light_shafts.wgsl"),
+    ),
+    (
+        "loading-bar.wgsl",
+        include_str!("../renderer/shaders/loading-bar.wgsl"),
+    ),
+    (
+        "main-shader.wgsl",
+        include_str!("../renderer/shaders/main-shader.wgsl"),
+    ),
+    (
+        "minimap.wgsl",
+        include_str!("../renderer/shaders/minimap.wgsl"),
+    ),
+    (
+        "panorama.wgsl",
+        include_str!("../renderer/shaders/panorama.wgsl"),
+    ),
+    (
+        "particles.wgsl",
+        include_str!("../renderer/shaders/particles.wgsl"),
+    ),
+    ("path.wgsl", include_str!("../renderer/shaders/path.wgsl")),
+    (
+        "rectangle.wgsl",
+        include_str!("../renderer/shaders/rectangle.wgsl"),
+    ),
+    (
+        "replay_pip.wgsl",
+        include_str!("../renderer/shaders/replay_pip.wgsl"),
+    ),
+    (
+        "reticle.wgsl",
+        include_str!("../renderer/shaders/reticle.wgsl"),
+    ),
+    (
+        "spawn_telegraph.wgsl",
+        include_str!("../renderer/shaders/spawn_telegraph.wgsl"),
+    ),
+    (
+        "star_shader.wgsl",
+        include_str!("../renderer/shaders/star_shader.wgsl"),
+    ),
+    (
+        "timer-bar.wgsl",
+        include_str!("../renderer/shaders/timer-bar.wgsl"),
+    ),
+    ("title.wgsl", include_str!("../renderer/shaders/title.wgsl")),
+    ("trail.wgsl", include_str!("../renderer/shaders/trail.wgsl")),
+    (
+        "vignette.wgsl",
+        include_str!("../renderer/shaders/vignette.wgsl"),
+    ),
+];
+
+/// A check's status and what it found, one line each.
+type Outcome = (CheckStatus, Vec<String>);
+
+/// How one check came out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CheckStatus {
+    /// Everything the check looked at works
+    Pass,
+    /// Something is wrong that the game works around
+    Warn,
+    /// Something the check looked at is broken
+    Fail,
+    /// The check couldn't run because an earlier one failed
+    Skip,
+}
+
+impl CheckStatus {
+    /// Returns the status as it appears in the report.
+    pub fn label(self) -> &'static str {
+        match self {
+            CheckStatus::Pass => "PASS",
+            CheckStatus::Warn => "WARN",
+            CheckStatus::Fail => "FAIL",
+            CheckStatus::Skip => "SKIP",
+        }
+    }
+}
+
+/// The result of one self-test check.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CheckResult {
+    /// What was checked, e.g. `"Shaders"`
+    pub category: &'static str,
+    /// How the check came out
+    pub status: CheckStatus,
+    /// Whether the game can't run when this check fails
+    pub critical: bool,
+    /// What the check found, one line each
+    pub details: Vec<String>,
+    /// How long the check took
+    pub elapsed: Duration,
+}
+
+/// Everything the self-test found, printed as the report.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SelfTestReport {
+    /// The crate version that ran the checks
+    pub version: &'static str,
+    /// The effective data paths, one line each
+    pub paths: Vec<String>,
+    /// Each check, in the order they ran
+    pub checks: Vec<CheckResult>,
+    /// How long the whole run took
+    pub elapsed: Duration,
+}
+
+impl SelfTestReport {
+    /// Returns `true` if no critical check failed.
+    pub fn passed(&self) -> bool {
+        !self
+            .checks
+            .iter()
+            .any(|check| check.critical && check.status == CheckStatus::Fail)
+    }
+}
+
+impl fmt::Display for SelfTestReport {
+    /// Formats the report as the version and paths, each check's findings, and a summary.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Mirador {} self-test", self.version)?;
+        for line in &self.paths {
+            writeln!(f, "  {}", line)?;
+        }
+        for check in &self.checks {
+            writeln!(
+                f,
+                "\n[{}] {} ({} ms)",
+                check.status.label(),
+                check.category,
+                check.elapsed.as_millis()
+            )?;
+            for detail in &check.details {
+                // Device errors span several lines; keep them under their check
+                for line in detail.lines() {
+                    writeln!(f, "  {}", line)?;
+                }
+            }
+        }
+        writeln!(f, "\nSummary")?;
+        for check in &self.checks {
+            writeln!(
+                f,
+                "  {:<16} {}{}",
+                check.category,
+                check.status.label(),
+                if check.critical {
+                    ""
+                } else {
+                    " (not critical)"
+                }
+            )?;
+        }
+        write!(
+            f,
+            "{} in {} ms",
+            if self.passed() { "PASSED" } else { "FAILED" },
+            self.elapsed.as_millis()
+        )
+    }
+}
+
+/// Returns `true` if the command line asks for the self-test.
+///
+/// # Arguments
+/// * `args` - The command line, starting with the executable
+pub fn requested(args: &[String]) -> bool {
+    args.iter().any(|arg| arg == SELF_TEST_FLAG)
+}
+
+/// Runs every check and collects the report.
+///
+/// # Arguments
+/// * `backend` - The graphics backend the game would use
+/// * `paths` - The effective data paths
+pub fn run(backend: GraphicsBackend, paths: &Paths) -> SelfTestReport {
+    let started = Instant::now();
+    // Panics are caught and reported per check, so keep the default hook from printing them too
+    let hook = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+
+    let instance = wgpu::Instance::new(&backend.instance_descriptor());
+    let mut checks = vec![isolated("Adapters", true, || {
+        check_adapters(&instance, backend)
+    })];
+    let mut gpu = None;
+    checks.push(isolated("Device", true, || {
+        let (outcome, device) = check_device(&instance);
+        gpu = device;
+        outcome
+    }));
+    checks.push(match &gpu {
+        Some((device, _)) => isolated("Shaders", true, || check_shaders(device)),
+        None => skipped("Shaders"),
+    });
+    checks.push(isolated("Assets", true, check_assets));
+    checks.push(isolated("Audio", true, check_audio));
+    checks.push(match &gpu {
+        Some((device, queue)) => isolated("Offscreen render", true, || {
+            check_offscreen_render(device, queue)
+        }),
+        None => skipped("Offscreen render"),
+    });
+
+    panic::set_hook(hook);
+    SelfTestReport {
+        version: env!("CARGO_PKG_VERSION"),
+        paths: describe_paths(paths),
+        checks,
+        elapsed: started.elapsed(),
+    }
+}
+
+/// Lists the data directory and the files a report is most often about.
+///
+/// # Arguments
+/// * `paths` - The effective data paths
+fn describe_paths(paths: &Paths) -> Vec<String> {
+    let root = if paths.root().as_os_str().is_empty() {
+        std::env::current_dir().map(|dir| format!("{} (working directory)", dir.display()))
+    } else {
+        std::path::absolute(paths.root()).map(|dir| dir.display().to_string())
+    }
+    .unwrap_or_else(|_| paths.root().display().to_string());
+    vec![
+        format!("Data directory: {}", root),
+        format!("Profile: {}", paths.profile().unwrap_or("none")),
+        format!("Settings: {}", paths.settings().display()),
+        format!("Journals: {}", paths.journal_dir().display()),
+        format!("Benchmarks: {}", paths.benchmarks_dir().display()),
+    ]
+}
+
+/// Runs one check, turning a panic into its failure.
+///
+/// # Arguments
+/// * `category` - What the check looks at
+/// * `critical` - Whether the game can't run when the check fails
+/// * `check` - The check, returning its status and findings
+fn isolated(
+    category: &'static str,
+    critical: bool,
+    check: impl FnOnce() -> Outcome,
+) -> CheckResult {
+    let started = Instant::now();
+    let (status, details) = match panic::catch_unwind(AssertUnwindSafe(check)) {
+        Ok(outcome) => outcome,
+        Err(payload) => {
+            let message = payload
+                .downcast_ref::<&str>()
+                .map(|message| message.to_string())
+                .or_else(|| payload.downcast_ref::<String>().cloned())
+                .unwrap_or_else(|| "unknown panic".to_string());
+            (CheckStatus::Fail, vec![format!("Panicked: {}", message)])
+        }
+    };
+    CheckResult {
+        category,
+        status,
+        critical,
+        details,
+        elapsed: started.elapsed(),
+    }
+}
+
+/// Records a check that needs the device as skipped.
+///
+/// # Arguments
+/// * `category` - What the check would have looked at
+fn skipped(category: &'static str) -> CheckResult {
+    CheckResult {
+        category,
+        status: CheckStatus::Skip,
+        critical: true,
+        details: vec!["No device to run on".to_string()],
+        elapsed: Duration::ZERO,
+    }
+}
+
+/// Runs some GPU work and collects every device error it raised.
+///
+/// # Arguments
+/// * `device` - The device the work runs on
+/// * `work` - The work to run
+///
+/// # Returns
+/// What the work returned and each error's message
+fn device_errors<T>(device: &wgpu::Device, work: impl FnOnce() -> T) -> (T, Vec<String>) {
+    for filter in ERROR_FILTERS {
+        device.push_error_scope(filter);
+    }
+    let value = work();
+    let errors = ERROR_FILTERS
+        .iter()
+        .filter_map(|_| pollster::block_on(device.pop_error_scope()))
+        .map(|error| error.to_string())
+        .collect();
+    (value, errors)
+}
+
+/// Lists every adapter the backend can see.
+fn check_adapters(instance: &wgpu::Instance, backend: GraphicsBackend) -> Outcome {
+    let mut details = vec![format!("Requested backend: {}", backend.name())];
+    let adapters = instance.enumerate_adapters(backend.backends());
+    if adapters.is_empty() {
+        details.push("No adapters found".to_string());
+        return (CheckStatus::Fail, details);
+    }
+    details.extend(adapters.iter().map(|adapter| {
+        let info = adapter.get_info();
+        format!("{} ({:?})", adapter_summary(&info), info.device_type)
+    }));
+    (CheckStatus::Pass, details)
+}
+
+/// Creates a device as the game does, after checking the adapter meets its limits.
+///
+/// # Returns
+/// The outcome, and the device and its queue if one was created
+fn check_device(instance: &wgpu::Instance) -> (Outcome, Option<(wgpu::Device, wgpu::Queue)>) {
+    let adapter = pollster::block_on(instance.request_adapter(&wgpu::RequestAdapterOptions {
+        power_preference: wgpu::PowerPreference::default(),
+        force_fallback_adapter: false,
+        compatible_surface: None,
+    }));
+    let Some(adapter) = adapter else {
+        let details = vec!["No adapter could be requested".to_string()];
+        return ((CheckStatus::Fail, details), None);
+    };
+    let mut details = vec![adapter_summary(&adapter.get_info())];
+
+    let descriptor = WgpuRenderer::device_descriptor(&adapter);
+    let mut short = Vec::new();
+    descriptor.required_limits.check_limits_with_fail_fn(
+        &adapter.limits(),
+        false,
+        |name, required, allowed| {
+            short.push(format!(
+                "Limit {} is {}, the game needs {}",
+                name, allowed, required
+            ))
+        },
+    );
+    if !short.is_empty() {
+        details.extend(short);
+        return ((CheckStatus::Fail, details), None);
+    }
+    details.push("Adapter meets the required limits".to_string());
+    details.push(format!(
+        "Timestamp queries: {}",
+        if descriptor
+            .required_features
+            .contains(wgpu::Features::TIMESTAMP_QUERY)
+        {
+            "available"
+        } else {
+            "unavailable, pass timings are off"
+        }
+    ));
+
+    match pollster::block_on(adapter.request_device(&descriptor, None)) {
+        Ok(gpu) => ((CheckStatus::Pass, details), Some(gpu)),
+        Err(e) => {
+            details.push(format!("Device creation failed: {}", e));
+            ((CheckStatus::Fail, details), None)
+        }
+    }
+}
+
+/// Compiles every embedded shader, reporting each one that fails validation.
+fn check_shaders(device: &wgpu::Device) -> Outcome {
+    let failures: Vec<String> = SHADERS
+        .iter()
+        .filter_map(|(name, source)| {
+            let (_, errors) = device_errors(device, || {
+                device.create_shader_module(wgpu::ShaderModuleDescriptor {
+                    label: Some(name),
+                    source: wgpu::ShaderSource::Wgsl((*source).into()),
+                })
+            });
+            (!errors.is_empty()).then(|| format!("{}: {}", name, errors.join("\n")))
+        })
+        .collect();
+    if failures.is_empty() {
+        let details = vec![format!("{} shaders compiled", SHADERS.len())];
+        (CheckStatus::Pass, details)
+    } else {
+        (CheckStatus::Fail, failures)
+    }
+}
+
+/// Validates every embedded asset.
+fn check_assets() -> Outcome {
+    let registry = AssetRegistry::validate_embedded();
+    let mut details = vec![format!(
+        "{} assets, {} broken",
+        registry::manifest().len(),
+        registry.problems().len()
+    )];
+    details.extend(registry.problems().iter().map(|problem| {
+        format!(
+            "{} {} ({:?}): {}",
+            if problem.critical { "!" } else { "-" },
+            problem.name,
+            problem.kind,
+            problem.message
+        )
+    }));
+    let status = if registry.has_critical_problems() {
+        CheckStatus::Fail
+    } else if registry.problems().is_empty() {
+        CheckStatus::Pass
+    } else {
+        CheckStatus::Warn
+    };
+    (status, details)
+}
+
+/// Opens the default audio output as the game does, then closes it again.
+fn check_audio() -> Outcome {
+    let host = cpal::default_host();
+    let mut details = vec![format!("Host: {}", host.id().name())];
+    match host.default_output_device() {
+        Some(device) => {
+            let name = device.name().unwrap_or_else(|_| "unnamed".to_string());
+            match device.default_output_config() {
+                Ok(config) => details.push(format!(
+                    "Output device: {} ({} Hz, {} channels)",
+                    name,
+                    config.sample_rate().0,
+                    config.channels()
+                )),
+                Err(e) => details.push(format!("Output device: {} ({})", name, e)),
+            }
+        }
+        None => details.push("No default output device".to_string()),
+    }
+
+    match AudioManager::<DefaultBackend>::new(AudioManagerSettings::default()) {
+        Ok(manager) => {
+            drop(manager);
+            details.push("Audio backend started and shut down".to_string());
+            (CheckStatus::Pass, details)
+        }
+        Err(e) => {
+            details.push(format!("Audio backend failed to start: {}", e));
+            (CheckStatus::Fail, details)
+        }
+    }
+}
+
+/// Draws the title screen and a small maze offscreen, reporting any device errors.
+fn check_offscreen_render(device: &wgpu::Device, queue: &wgpu::Queue) -> Outcome {
+    let texture = device.create_texture(&wgpu::TextureDescriptor {
+        label: Some("Self-Test Target"),
+        size: wgpu::Extent3d {
+            width: TARGET_SIZE,
+            height: TARGET_SIZE,
+            depth_or_array_layers: 1,
+        },
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension: wgpu::TextureDimension::D2,
+        format: TARGET_FORMAT,
+        usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+        view_formats: &[],
+    });
+    let target = texture.create_view(&wgpu::TextureViewDescriptor::default());
+    let surface_config = wgpu::SurfaceConfiguration {
+        usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+        format: TARGET_FORMAT,
+        width: TARGET_SIZE,
+        height: TARGET_SIZE,
+        present_mode: wgpu::PresentMode::Fifo,
+        desired_maximum_frame_latency: 2,
+        alpha_mode: wgpu::CompositeAlphaMode::Auto,
+        view_formats: vec![],
+    };
+
+    let mut details = Vec::new();
+    let mut status = CheckStatus::Pass;
+    let ((), title_errors) = device_errors(device, || {
+        let title = TitleRenderer::new(device, queue, &surface_config);
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("Self-Test Title Encoder"),
+        });
+        {
+            let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                label: Some("Self-Test Title Pass"),
+                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                    view: &target,
+                    resolve_target: None,
+                    ops: wgpu::Operations {
+                        load: wgpu::LoadOp::Clear(wgpu::Color::BLACK),
+                        store: wgpu::StoreOp::Store,
+                    },
+                })],
+                depth_stencil_attachment: None,
+                occlusion_query_set: None,
+                timestamp_writes: None,
+            });
+            render_pass.set_pipeline(&title.pipeline);
+            render_pass.set_bind_group(0, &title.bind_group, &[]);
+            render_pass.set_vertex_buffer(0, title.vertex_buffer.slice(..));
+            render_pass.draw(0..6, 0..1);
+        }
+        queue.submit(Some(encoder.finish()));
+        device.poll(wgpu::Maintain::Wait);
+    });
+    report_render("Title", title_errors, &mut status, &mut details);
+
+    let ((), game_errors) = device_errors(device, || {
+        let (mut generator, maze) = MazeGenerator::with_seed(4, 4, 1);
+        while !generator.is_complete() {
+            generator.step();
+        }
+        let (grid, exit_cell) = maze.lock().expect("Failed to lock maze").level_grid();
+        let mut game_state = GameState::builder()
+            .without_audio()
+            .build()
+            .expect("Failed to build game state");
+        game_state.collision_system.build_from_maze(&grid, false);
+        game_state.player.spawn_at_maze_entrance(&grid, false);
+
+        let mut view = MazeView::new(device, queue, TARGET_FORMAT, TARGET_SIZE, TARGET_SIZE);
+        view.load_maze(device, &grid, exit_cell);
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("Self-Test Game Encoder"),
+        });
+        view.render(device, queue, &mut encoder, &target, &game_state, 0.0);
+        queue.submit(Some(encoder.finish()));
+        device.poll(wgpu::Maintain::Wait);
+    });
+    report_render("Game", game_errors, &mut status, &mut details);
+    (status, details)
+}
+
+/// Adds one offscreen frame's outcome to the render check.
+///
+/// # Arguments
+/// * `screen` - Which screen was drawn
+/// * `errors` - Device errors raised while drawing it
+/// * `status` - The check's status, failed if there were errors
+/// * `details` - The check's findings
+fn report_render(
+    screen: &str,
+    errors: Vec<String>,
+    status: &mut CheckStatus,
+    details: &mut Vec<String>,
+) {
+    if errors.is_empty() {
+        details.push(format!(
+            "{} frame drawn at {}x{}",
+            screen, TARGET_SIZE, TARGET_SIZE
+        ));
+    } else {
+        *status = CheckStatus::Fail;
+        details.extend(errors.iter().map(|error| format!("{}: {}", screen, error)));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_every_shader_file_is_checked() {
+        let dir = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("src/renderer/shaders");
+        let mut files: Vec<String> = std::fs::read_dir(dir)
+            .expect("Failed to read the shader directory")
+            .filter_map(|entry| entry.ok()?.file_name().into_string().ok())
+            .filter(|name| name.ends_with(".wgsl"))
+            .collect();
+        files.sort();
+        let mut checked: Vec<String> = SHADERS.iter().map(|(name, _)| name.to_string()).collect();
+        checked.sort();
+        assert_eq!(checked, files);
+    }
+
+    #[test]
+    fn test_a_critical_failure_fails_the_report() {
+        let check = |status, critical| CheckResult {
+            category: "Check",
+            status,
+            critical,
+            details: vec!["line one\nline two".to_string()],
+            elapsed: Duration::from_millis(3),
+        };
+        let mut report = SelfTestReport {
+            version: "0.0.0",
+            paths: vec!["Data directory: /tmp".to_string()],
+            checks: vec![
+                check(CheckStatus::Pass, true),
+                check(CheckStatus::Warn, true),
+                check(CheckStatus::Fail, false),
+            ],
+            elapsed: Duration::from_millis(9),
+        };
+        assert!(report.passed());
+        let text = report.to_string();
+        assert!(text.starts_with("Mirador 0.0.0 self-test\n  Data directory: /tmp"));
+        assert!(text.contains("\n  line two\n"));
+        assert!(text.ends_with("PASSED in 9 ms"));
+
+        report.checks.push(check(CheckStatus::Fail, true));
+        assert!(!report.passed());
+        assert!(report.to_string().ends_with("FAILED in 9 ms"));
+    }
+
+    #[test]
+    fn test_a_panicking_check_fails_on_its_own() {
+        let failed = isolated("Broken", true, || panic!("driver exploded"));
+        let passed = isolated("Fine", true, || (CheckStatus::Pass, Vec::new()));
+
+        assert_eq!(failed.status, CheckStatus::Fail);
+        assert_eq!(
+            failed.details,
+            vec!["Panicked: driver exploded".to_string()]
+        );
+        assert_eq!(passed.status, CheckStatus::Pass);
+    }
+}
@@ -0,0 +1,1102 @@
+//! Soak testing for Mirador.
+//!
+//! Leaks in text buffers, icon registrations, or transient effects only show up after long
+//! sessions. Starting a debug build with `--soak <minutes>` lets the game play itself for that
+//! long with rendering on: a [`SoakBot`] walks the solution path of each level through
+//! click-to-move's [`AutoWalk`](crate::game::auto_walk::AutoWalk), picks upgrades, pauses and
+//! unpauses, resizes the window through [`RESIZE_CYCLE`], and toggles overlays, while a
+//! [`SoakWatchdog`] samples memory, text buffers, tracked GPU texture bytes, and frame times
+//! every [`SAMPLE_INTERVAL`].
+//!
+//! Each size is only held for [`RESIZE_HOLD`] frames before the window is maximized again, and
+//! samples wait until it has settled, so every sample sees the same render targets and a
+//! resize never looks like GPU memory growth.
+//!
+//! Memory is the process's resident size, or the live heap when the `dhat-heap` feature is on.
+//! Samples from the first [`WARM_UP`] are reported but left out of the growth checks, since
+//! caches fill up then.
+//!
+//! When the time is up the report is written to
+//! [`Paths::soak_reports_dir`](crate::paths::Paths::soak_reports_dir) and the game exits with
+//! status 1 if any metric grew faster than its [`SoakLimits`] slope, a gameplay frame took
+//! longer than the spike limit, or the frame update panicked.
+//!
+//! Every run plays the levels of one seed, from `--seed` or picked at random, and the bot's
+//! choices come from the same seed and are scheduled in frames rather than seconds, so a leak
+//! can be re-run with the command line the report prints and bisected.
+//!
+//! # Usage
+//!
+//! ```rust,ignore
+//! if let Some(config) = soak::soak_request(&args)? {
+//!     soak::enable(config);
+//! }
+//!
+//! // When the app starts
+//! let mut soak = soak::config().cloned().map(SoakRun::new);
+//!
+//! // After each frame
+//! if let Some(report) = soak.frame(state, window, frame_time) {
+//!     soak::write_report(&report, &paths::get().soak_reports_dir())?;
+//! }
+//! ```
+
+use crate::app::AppState;
+use crate::benchmarks::latency;
+use crate::game::CurrentScreen;
+use crate::game::keys::GameKey;
+use crate::game::navmesh::SEED_FLAG;
+use crate::game::seed;
+use crate::renderer::ui::profile_menu::ProfileAction;
+use chrono::Local;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::fmt;
+use std::fs;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
+use winit::dpi::PhysicalSize;
+use winit::window::Window;
+
+/// Command-line flag that starts a soak test, followed by its length in minutes.
+pub const SOAK_FLAG: &str = "--soak";
+
+/// Time between watchdog samples.
+pub const SAMPLE_INTERVAL: Duration = Duration::from_secs(30);
+
+/// How long after the start samples are left out of the growth checks.
+pub const WARM_UP: Duration = Duration::from_secs(60);
+
+/// Window sizes the bot cycles through, in physical pixels.
+pub const RESIZE_CYCLE: [(u32, u32); 4] = [(1280, 720), (1920, 1080), (1024, 768), (1600, 900)];
+
+/// Frames between window resizes.
+pub const RESIZE_EVERY: u64 = 1200;
+
+/// Frames the window keeps a size from [`RESIZE_CYCLE`] before it is maximized again.
+pub const RESIZE_HOLD: u64 = 120;
+
+/// Frames after the window is maximized again before a sample is taken.
+pub const RESIZE_SETTLE: u64 = 30;
+
+/// Frames of play between pauses, picked at random from this range.
+pub const PAUSE_GAP: Range<u64> = 600..1800;
+
+/// Frames the game stays paused, picked at random from this range.
+pub const PAUSE_LENGTH: Range<u64> = 30..120;
+
+/// Frames of play between overlay toggles, picked at random from this range.
+pub const OVERLAY_GAP: Range<u64> = 300..900;
+
+/// Frames the map view is held once the bot brings it up.
+pub const MAP_VIEW_HOLD: u64 = 90;
+
+/// Profile the bot makes when the game starts on the profile picker.
+pub const SOAK_PROFILE: &str = "Soak";
+
+/// What the memory figures measure.
+#[cfg(feature = "dhat-heap")]
+const MEMORY_SOURCE: &str = "Live heap";
+#[cfg(not(feature = "dhat-heap"))]
+const MEMORY_SOURCE: &str = "Resident memory";
+
+/// The soak test asked for on the command line, set by [`enable`].
+static SOAK: OnceLock<SoakConfig> = OnceLock::new();
+
+/// How fast each metric may grow before the soak test fails.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SoakLimits {
+    /// Bytes of memory a minute
+    pub memory_per_minute: f64,
+    /// Text buffers a minute
+    pub text_buffers_per_minute: f64,
+    /// Bytes of tracked GPU textures a minute
+    pub gpu_bytes_per_minute: f64,
+    /// Longest a gameplay frame may take; loading frames aren't held to it
+    pub frame_spike: Duration,
+}
+
+impl Default for SoakLimits {
+    fn default() -> Self {
+        Self {
+            memory_per_minute: 4.0 * 1024.0 * 1024.0,
+            text_buffers_per_minute: 0.5,
+            gpu_bytes_per_minute: 1024.0 * 1024.0,
+            frame_spike: Duration::from_millis(500),
+        }
+    }
+}
+
+/// A soak test asked for on the command line.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SoakConfig {
+    /// Length of the soak test in minutes
+    pub minutes: u64,
+    /// Seed of the run the bot plays and of the bot's own choices
+    pub seed: u64,
+    /// Growth the watchdog allows
+    pub limits: SoakLimits,
+}
+
+impl SoakConfig {
+    /// Returns how long the soak test runs.
+    pub fn duration(&self) -> Duration {
+        Duration::from_secs(self.minutes * 60)
+    }
+}
+
+/// Reads a soak test request from the process arguments.
+///
+/// # Arguments
+/// * `args` - The process arguments, including the program name
+///
+/// # Returns
+/// The request if [`SOAK_FLAG`] was given, with a random seed unless `--seed` picks one, or an
+/// error message if the minutes or the seed are missing or invalid
+pub fn soak_request(args: &[String]) -> Result<Option<SoakConfig>, String> {
+    let Some(index) = args.iter().position(|arg| arg == SOAK_FLAG) else {
+        return Ok(None);
+    };
+    let usage = || {
+        format!(
+            "Usage: mirador {} <minutes> [{} <seed>]",
+            SOAK_FLAG, SEED_FLAG
+        )
+    };
+    let minutes = match args.get(index + 1).map(|text| text.parse::<u64>()) {
+        Some(Ok(minutes)) if minutes > 0 => minutes,
+        _ => return Err(usage()),
+    };
+    let seed = match args.iter().position(|arg| arg == SEED_FLAG) {
+        Some(index) => {
+            let text = args
+                .get(index + 1)
+                .filter(|text| !text.starts_with("--"))
+                .ok_or_else(usage)?;
+            seed::parse_seed(text).map_err(|e| format!("Invalid seed {:?}: {}", text, e))?
+        }
+        None => rand::random(),
+    };
+    Ok(Some(SoakConfig {
+        minutes,
+        seed,
+        limits: SoakLimits::default(),
+    }))
+}
+
+/// Turns on the soak test for the `--soak` flag. Only the first call has any effect.
+pub fn enable(config: SoakConfig) {
+    let _ = SOAK.set(config);
+}
+
+/// Returns the soak test turned on by [`enable`], if any.
+pub fn config() -> Option<&'static SoakConfig> {
+    SOAK.get()
+}
+
+/// An overlay the bot switches on and off.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SoakOverlay {
+    /// The collision bounding boxes, cycled through their modes
+    BoundingBoxes,
+    /// The frame heatmap on the floor
+    FrameHeatmap,
+    /// The overhead map view, held for [`MAP_VIEW_HOLD`] frames
+    MapView,
+}
+
+impl SoakOverlay {
+    /// Every overlay, in the order the bot's random pick indexes them.
+    pub const ALL: [SoakOverlay; 3] = [
+        SoakOverlay::BoundingBoxes,
+        SoakOverlay::FrameHeatmap,
+        SoakOverlay::MapView,
+    ];
+}
+
+/// Something the bot does, as a player would with the mouse and keyboard.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BotAction {
+    /// Make [`SOAK_PROFILE`] and play as it, when the game starts on the profile picker
+    PickProfile,
+    /// Start a run of the soak seed from the title screen
+    StartRun,
+    /// Walk the solution path to the exit
+    WalkToExit,
+    /// Pick the upgrade card in this slot
+    PickUpgrade(usize),
+    /// Start a new run from the game over screen
+    NewRun,
+    /// Open the pause menu
+    Pause,
+    /// Close the pause menu
+    Resume,
+    /// Resize the window to this width and height
+    Resize(u32, u32),
+    /// Maximize the window again after a resize
+    RestoreSize,
+    /// Switch an overlay on or off
+    ToggleOverlay(SoakOverlay),
+}
+
+/// Plays the game for the soak test.
+///
+/// Everything it does is decided from its seed and the number of frames so far, so two bots
+/// with the same seed that see the same screens do the same things.
+#[derive(Debug, Clone)]
+pub struct SoakBot {
+    /// Random source for pause, overlay, and upgrade choices
+    rng: StdRng,
+    /// Frames stepped so far
+    frame: u64,
+    /// Frame of play to pause on
+    next_pause: u64,
+    /// Frame to leave the pause menu on, while paused by the bot
+    resume_at: Option<u64>,
+    /// Frame to resize the window on
+    next_resize: u64,
+    /// Index into [`RESIZE_CYCLE`] of the next size
+    resize_index: usize,
+    /// Frame to maximize the window again on, while it has a size from the cycle
+    restore_at: Option<u64>,
+    /// Frame the window is settled on after being maximized again
+    settled_at: u64,
+    /// Frame of play to toggle an overlay on
+    next_overlay: u64,
+    /// Frame to let go of the map view on, while the bot holds it
+    map_view_release: Option<u64>,
+}
+
+impl SoakBot {
+    /// Creates a bot whose choices come from a seed.
+    ///
+    /// # Arguments
+    /// * `seed` - The soak test's seed
+    pub fn new(seed: u64) -> Self {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let next_pause = rng.gen_range(PAUSE_GAP);
+        let next_overlay = rng.gen_range(OVERLAY_GAP);
+        Self {
+            rng,
+            frame: 0,
+            next_pause,
+            resume_at: None,
+            next_resize: RESIZE_EVERY,
+            resize_index: 0,
+            restore_at: None,
+            settled_at: 0,
+            next_overlay,
+            map_view_release: None,
+        }
+    }
+
+    /// Decides what to do after a frame.
+    ///
+    /// # Arguments
+    /// * `screen` - The screen the frame showed
+    /// * `walking` - Whether the player is already walking somewhere
+    ///
+    /// # Returns
+    /// The actions to take, in order
+    pub fn step(&mut self, screen: CurrentScreen, walking: bool) -> Vec<BotAction> {
+        self.frame += 1;
+        let mut actions = Vec::new();
+        match screen {
+            CurrentScreen::Profiles => actions.push(BotAction::PickProfile),
+            CurrentScreen::Title => actions.push(BotAction::StartRun),
+            CurrentScreen::GameOver => actions.push(BotAction::NewRun),
+            CurrentScreen::UpgradeMenu => {
+                actions.push(BotAction::PickUpgrade(self.rng.gen_range(0..3)));
+            }
+            // A pause the bot didn't make is left straight away
+            CurrentScreen::Pause if self.resume_at.is_none_or(|frame| self.frame >= frame) => {
+                self.resume_at = None;
+                actions.push(BotAction::Resume);
+            }
+            CurrentScreen::Game if self.frame >= self.next_pause => {
+                self.next_pause = self.frame + self.rng.gen_range(PAUSE_GAP);
+                self.resume_at = Some(self.frame + self.rng.gen_range(PAUSE_LENGTH));
+                actions.push(BotAction::Pause);
+            }
+            CurrentScreen::Game => {
+                if !walking {
+                    actions.push(BotAction::WalkToExit);
+                }
+                if self.frame >= self.next_overlay && self.map_view_release.is_none() {
+                    self.next_overlay = self.frame + self.rng.gen_range(OVERLAY_GAP);
+                    let overlay = SoakOverlay::ALL[self.rng.gen_range(0..SoakOverlay::ALL.len())];
+                    if overlay == SoakOverlay::MapView {
+                        self.map_view_release = Some(self.frame + MAP_VIEW_HOLD);
+                    }
+                    actions.push(BotAction::ToggleOverlay(overlay));
+                }
+            }
+            _ => {}
+        }
+
+        // The map view and the window size don't wait for the game screen
+        if self
+            .map_view_release
+            .is_some_and(|frame| self.frame >= frame)
+        {
+            self.map_view_release = None;
+            actions.push(BotAction::ToggleOverlay(SoakOverlay::MapView));
+        }
+        if self.restore_at.is_some_and(|frame| self.frame >= frame) {
+            self.restore_at = None;
+            self.settled_at = self.frame + RESIZE_SETTLE;
+            actions.push(BotAction::RestoreSize);
+        } else if self.frame >= self.next_resize {
+            self.next_resize = self.frame + RESIZE_EVERY;
+            self.restore_at = Some(self.frame + RESIZE_HOLD);
+            let (width, height) = RESIZE_CYCLE[self.resize_index % RESIZE_CYCLE.len()];
+            self.resize_index += 1;
+            actions.push(BotAction::Resize(width, height));
+        }
+        actions
+    }
+
+    /// Returns whether the window is away from its maximized size, or still settling back.
+    pub fn is_resizing(&self) -> bool {
+        self.restore_at.is_some() || self.frame < self.settled_at
+    }
+}
+
+/// The metrics the watchdog reads from the game at each sample.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SoakMetrics {
+    /// Memory in bytes, see [`memory_bytes`]
+    pub memory_bytes: Option<u64>,
+    /// Text buffers held by the text renderer
+    pub text_buffers: usize,
+    /// Bytes of GPU textures registered with the memory tracker
+    pub gpu_texture_bytes: u64,
+}
+
+impl SoakMetrics {
+    /// Reads the metrics from the running game.
+    ///
+    /// # Arguments
+    /// * `state` - The application state
+    pub fn measure(state: &AppState) -> Self {
+        Self {
+            memory_bytes: memory_bytes(),
+            text_buffers: state.text_renderer.text_buffers.len(),
+            gpu_texture_bytes: state.wgpu_renderer.gpu_memory.total(),
+        }
+    }
+}
+
+/// Frame time percentiles over one sample interval.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FrameTimes {
+    /// Frames drawn in the interval
+    pub count: usize,
+    /// Median frame time
+    pub p50: Duration,
+    /// 95th percentile frame time
+    pub p95: Duration,
+    /// 99th percentile frame time
+    pub p99: Duration,
+    /// Longest frame time
+    pub max: Duration,
+}
+
+impl FrameTimes {
+    /// Summarizes the frame times of an interval.
+    ///
+    /// # Returns
+    /// `None` if no frames were drawn
+    pub fn from_samples(mut samples: Vec<Duration>) -> Option<Self> {
+        samples.sort();
+        Some(Self {
+            count: samples.len(),
+            p50: latency::percentile(&samples, 0.50)?,
+            p95: latency::percentile(&samples, 0.95)?,
+            p99: latency::percentile(&samples, 0.99)?,
+            max: *samples.last()?,
+        })
+    }
+}
+
+/// One reading taken by the watchdog.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SoakSample {
+    /// Time since the soak test started
+    pub elapsed: Duration,
+    /// The game's metrics at that time
+    pub metrics: SoakMetrics,
+    /// Frame times since the previous sample
+    pub frames: Option<FrameTimes>,
+}
+
+/// Samples the game's metrics and judges them against the [`SoakLimits`].
+#[derive(Debug, Clone)]
+pub struct SoakWatchdog {
+    /// Growth allowed for each metric
+    limits: SoakLimits,
+    /// Readings so far, oldest first
+    samples: Vec<SoakSample>,
+    /// Frame times since the last sample
+    frame_times: Vec<Duration>,
+    /// Frames drawn since the start
+    frames: u64,
+    /// Longest gameplay frame so far and when it was drawn
+    worst_frame: Option<(Duration, Duration)>,
+    /// Problems found outside the samples, such as a panic
+    failures: Vec<String>,
+}
+
+impl SoakWatchdog {
+    /// Creates a watchdog with no samples yet.
+    ///
+    /// # Arguments
+    /// * `limits` - Growth allowed for each metric
+    pub fn new(limits: SoakLimits) -> Self {
+        Self {
+            limits,
+            samples: Vec::new(),
+            frame_times: Vec::new(),
+            frames: 0,
+            worst_frame: None,
+            failures: Vec::new(),
+        }
+    }
+
+    /// Counts a frame toward the next sample's frame times.
+    ///
+    /// # Arguments
+    /// * `elapsed` - Time since the soak test started
+    /// * `frame_time` - How long the frame took
+    /// * `gameplay` - Whether the frame was of the game screen; only those can spike
+    pub fn record_frame(&mut self, elapsed: Duration, frame_time: Duration, gameplay: bool) {
+        self.frames += 1;
+        self.frame_times.push(frame_time);
+        if gameplay && self.worst_frame.is_none_or(|(worst, _)| frame_time > worst) {
+            self.worst_frame = Some((frame_time, elapsed));
+        }
+    }
+
+    /// Takes a reading.
+    ///
+    /// # Arguments
+    /// * `elapsed` - Time since the soak test started
+    /// * `metrics` - The game's metrics now
+    pub fn sample(&mut self, elapsed: Duration, metrics: SoakMetrics) {
+        let frames = FrameTimes::from_samples(std::mem::take(&mut self.frame_times));
+        self.samples.push(SoakSample {
+            elapsed,
+            metrics,
+            frames,
+        });
+    }
+
+    /// Fails the soak test for a problem the samples can't show.
+    ///
+    /// # Arguments
+    /// * `reason` - What went wrong, for the report
+    pub fn fail(&mut self, reason: String) {
+        self.failures.push(reason);
+    }
+
+    /// Judges the samples so far.
+    ///
+    /// # Arguments
+    /// * `config` - The soak test being run
+    /// * `elapsed` - Time since the soak test started
+    pub fn report(&self, config: &SoakConfig, elapsed: Duration) -> SoakReport {
+        let mut failures = self.failures.clone();
+        let settled: Vec<&SoakSample> = self
+            .samples
+            .iter()
+            .filter(|sample| sample.elapsed >= WARM_UP)
+            .collect();
+        let growth = |value: &dyn Fn(&SoakSample) -> Option<f64>| {
+            let points: Vec<(f64, f64)> = settled
+                .iter()
+                .filter_map(|sample| Some((sample.elapsed.as_secs_f64() / 60.0, value(sample)?)))
+                .collect();
+            slope_per_minute(&points)
+        };
+
+        let memory = growth(&|sample| sample.metrics.memory_bytes.map(|bytes| bytes as f64));
+        if let Some(slope) = memory.filter(|&slope| slope > self.limits.memory_per_minute) {
+            failures.push(format!(
+                "{} grew {} a minute, over the {} limit",
+                MEMORY_SOURCE,
+                format_bytes(slope),
+                format_bytes(self.limits.memory_per_minute)
+            ));
+        }
+        let text_buffers = growth(&|sample| Some(sample.metrics.text_buffers as f64));
+        if let Some(slope) =
+            text_buffers.filter(|&slope| slope > self.limits.text_buffers_per_minute)
+        {
+            failures.push(format!(
+                "Text buffers grew {:.2} a minute, over the {:.2} limit",
+                slope, self.limits.text_buffers_per_minute
+            ));
+        }
+        let gpu = growth(&|sample| Some(sample.metrics.gpu_texture_bytes as f64));
+        if let Some(slope) = gpu.filter(|&slope| slope > self.limits.gpu_bytes_per_minute) {
+            failures.push(format!(
+                "GPU textures grew {} a minute, over the {} limit",
+                format_bytes(slope),
+                format_bytes(self.limits.gpu_bytes_per_minute)
+            ));
+        }
+        if let Some((frame_time, at)) = self
+            .worst_frame
+            .filter(|&(frame_time, _)| frame_time > self.limits.frame_spike)
+        {
+            failures.push(format!(
+                "A {} ms frame at {}, over the {} ms spike limit",
+                frame_time.as_millis(),
+                format_elapsed(at),
+                self.limits.frame_spike.as_millis()
+            ));
+        }
+
+        SoakReport {
+            config: config.clone(),
+            elapsed,
+            frames: self.frames,
+            samples: self.samples.clone(),
+            growth: [memory, text_buffers, gpu],
+            failures,
+        }
+    }
+}
+
+/// What a soak test found.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SoakReport {
+    /// The soak test that was run
+    pub config: SoakConfig,
+    /// How long it ran
+    pub elapsed: Duration,
+    /// Frames drawn
+    pub frames: u64,
+    /// Every reading, oldest first
+    pub samples: Vec<SoakSample>,
+    /// Growth a minute of memory, text buffers, and GPU texture bytes after the warm-up, where
+    /// there were enough samples to tell
+    pub growth: [Option<f64>; 3],
+    /// Why the soak test failed; empty if it passed
+    pub failures: Vec<String>,
+}
+
+impl SoakReport {
+    /// Returns whether every metric stayed within its limits.
+    pub fn passed(&self) -> bool {
+        self.failures.is_empty()
+    }
+
+    /// Returns the command line that repeats this soak test.
+    pub fn rerun_command(&self) -> String {
+        format!(
+            "mirador {} {} {} {}",
+            SOAK_FLAG,
+            self.config.minutes,
+            SEED_FLAG,
+            seed::format_seed(self.config.seed)
+        )
+    }
+}
+
+impl fmt::Display for SoakReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "Soak test {} after {} ({} frames)",
+            if self.passed() { "passed" } else { "FAILED" },
+            format_elapsed(self.elapsed),
+            self.frames
+        )?;
+        writeln!(f, "Re-run with: {}", self.rerun_command())?;
+        for failure in &self.failures {
+            writeln!(f, "  - {}", failure)?;
+        }
+
+        let [memory, text_buffers, gpu] = self.growth;
+        writeln!(
+            f,
+            "Growth a minute after the first {}:",
+            format_elapsed(WARM_UP)
+        )?;
+        let growth = |slope: Option<f64>, format: &dyn Fn(f64) -> String| {
+            slope.map_or("not enough samples".to_string(), format)
+        };
+        writeln!(f, "  {}: {}", MEMORY_SOURCE, growth(memory, &format_bytes))?;
+        writeln!(
+            f,
+            "  Text buffers: {}",
+            growth(text_buffers, &|slope| format!("{:.2}", slope))
+        )?;
+        writeln!(f, "  GPU textures: {}", growth(gpu, &format_bytes))?;
+
+        writeln!(f)?;
+        writeln!(
+            f,
+            "{:>8} {:>12} {:>7} {:>12} {:>7} {:>7} {:>7} {:>7} {:>7}",
+            "Time", "Memory", "Text", "GPU", "Frames", "p50 ms", "p95 ms", "p99 ms", "max ms"
+        )?;
+        for sample in &self.samples {
+            let metrics = sample.metrics;
+            write!(
+                f,
+                "{:>8} {:>12} {:>7} {:>12}",
+                format_elapsed(sample.elapsed),
+                metrics
+                    .memory_bytes
+                    .map_or("-".to_string(), |bytes| format_bytes(bytes as f64)),
+                metrics.text_buffers,
+                format_bytes(metrics.gpu_texture_bytes as f64)
+            )?;
+            match sample.frames {
+                Some(frames) => {
+                    let ms = |time: Duration| time.as_secs_f64() * 1000.0;
+                    writeln!(
+                        f,
+                        " {:>7} {:>7.1} {:>7.1} {:>7.1} {:>7.1}",
+                        frames.count,
+                        ms(frames.p50),
+                        ms(frames.p95),
+                        ms(frames.p99),
+                        ms(frames.max)
+                    )?;
+                }
+                None => writeln!(f, " {:>7}", 0)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A soak test in progress: the bot at the controls and the watchdog taking readings.
+#[derive(Debug)]
+pub struct SoakRun {
+    /// The soak test being run
+    config: SoakConfig,
+    /// Plays the game
+    bot: SoakBot,
+    /// Takes the readings
+    watchdog: SoakWatchdog,
+    /// When the soak test started
+    started: Instant,
+    /// Time since the start of the next reading
+    next_sample: Duration,
+}
+
+impl SoakRun {
+    /// Starts a soak test.
+    ///
+    /// # Arguments
+    /// * `config` - The soak test to run
+    pub fn new(config: SoakConfig) -> Self {
+        Self {
+            bot: SoakBot::new(config.seed),
+            watchdog: SoakWatchdog::new(config.limits),
+            config,
+            started: Instant::now(),
+            next_sample: SAMPLE_INTERVAL,
+        }
+    }
+
+    /// Lets the bot act on the frame just drawn and takes a reading when one is due.
+    ///
+    /// # Arguments
+    /// * `state` - The application state
+    /// * `window` - The game window, for resizing
+    /// * `frame_time` - How long the frame took to update and draw
+    ///
+    /// # Returns
+    /// The report once the soak test is over, because its time is up or the game panicked
+    pub fn frame(
+        &mut self,
+        state: &mut AppState,
+        window: &Window,
+        frame_time: Duration,
+    ) -> Option<SoakReport> {
+        let elapsed = self.started.elapsed();
+        let screen = state.game_state.current_screen;
+        self.watchdog
+            .record_frame(elapsed, frame_time, screen == CurrentScreen::Game);
+
+        // A caught panic leaves the error screen up; there's nothing more to soak
+        if screen == CurrentScreen::Error {
+            self.watchdog.fail(format!(
+                "The frame update panicked at {}; see the session journal",
+                format_elapsed(elapsed)
+            ));
+            return Some(self.finish(state, elapsed));
+        }
+        if elapsed >= self.config.duration() {
+            return Some(self.finish(state, elapsed));
+        }
+
+        let walking = state.game_state.auto_walk.is_active();
+        for action in self.bot.step(screen, walking) {
+            self.apply(action, state, window);
+        }
+        if elapsed >= self.next_sample && !self.bot.is_resizing() {
+            self.watchdog.sample(elapsed, SoakMetrics::measure(state));
+            self.next_sample += SAMPLE_INTERVAL;
+        }
+        None
+    }
+
+    /// Takes the last reading and judges the soak test.
+    fn finish(&mut self, state: &AppState, elapsed: Duration) -> SoakReport {
+        self.watchdog.sample(elapsed, SoakMetrics::measure(state));
+        self.watchdog.report(&self.config, elapsed)
+    }
+
+    /// Carries out one of the bot's actions through the same calls the menus and keys use.
+    fn apply(&self, action: BotAction, state: &mut AppState, window: &Window) {
+        match action {
+            BotAction::PickProfile => {
+                state.apply_profile_action(ProfileAction::Create(SOAK_PROFILE.to_string()));
+                state.apply_profile_action(ProfileAction::Back);
+            }
+            BotAction::StartRun => {
+                state.game_state.chosen_seed = Some(self.config.seed);
+                state.game_state.hardcore = false;
+                state.game_state.challenge = None;
+                state.leave_title_screen();
+                state.triage_mouse(window);
+            }
+            BotAction::WalkToExit => {
+                state.game_state.auto_walk_to_exit();
+            }
+            BotAction::PickUpgrade(slot) => {
+                state.upgrade_menu.pick(slot, &mut state.game_state);
+            }
+            BotAction::NewRun => {
+                state.start_new_run();
+                state.triage_mouse(window);
+            }
+            BotAction::Pause => {
+                state.pause_game();
+                state.triage_mouse(window);
+            }
+            BotAction::Resume => {
+                state.resume_from_pause();
+                state.triage_mouse(window);
+            }
+            BotAction::Resize(width, height) => {
+                // A maximized window ignores size requests
+                window.set_maximized(false);
+                let _ = window.request_inner_size(PhysicalSize::new(width, height));
+            }
+            BotAction::RestoreSize => window.set_maximized(true),
+            BotAction::ToggleOverlay(SoakOverlay::BoundingBoxes) => {
+                state
+                    .wgpu_renderer
+                    .game_renderer
+                    .debug_renderer
+                    .cycle_bounding_boxes();
+            }
+            BotAction::ToggleOverlay(SoakOverlay::FrameHeatmap) => {
+                let heatmap = &mut state.wgpu_renderer.game_renderer.heatmap_renderer;
+                heatmap.visible = !heatmap.visible;
+            }
+            BotAction::ToggleOverlay(SoakOverlay::MapView) => {
+                if state.key_state.is_pressed(GameKey::MapView) {
+                    state.key_state.release_key(GameKey::MapView);
+                } else {
+                    state.key_state.press_key(GameKey::MapView);
+                }
+            }
+        }
+    }
+}
+
+/// Fits a straight line through readings by least squares.
+///
+/// # Arguments
+/// * `points` - `(minutes, value)` readings
+///
+/// # Returns
+/// How much the value grows a minute, or `None` with fewer than two distinct times
+pub fn slope_per_minute(points: &[(f64, f64)]) -> Option<f64> {
+    if points.len() < 2 {
+        return None;
+    }
+    let count = points.len() as f64;
+    let mean_x = points.iter().map(|&(x, _)| x).sum::<f64>() / count;
+    let mean_y = points.iter().map(|&(_, y)| y).sum::<f64>() / count;
+    let spread: f64 = points.iter().map(|&(x, _)| (x - mean_x).powi(2)).sum();
+    if spread <= f64::EPSILON {
+        return None;
+    }
+    let covariance: f64 = points
+        .iter()
+        .map(|&(x, y)| (x - mean_x) * (y - mean_y))
+        .sum();
+    Some(covariance / spread)
+}
+
+/// Returns the process's memory in bytes: the live heap with the `dhat-heap` feature.
+#[cfg(feature = "dhat-heap")]
+pub fn memory_bytes() -> Option<u64> {
+    Some(dhat::HeapStats::get().curr_bytes as u64)
+}
+
+/// Returns the process's memory in bytes: its resident size, where the system reports it.
+#[cfg(not(feature = "dhat-heap"))]
+pub fn memory_bytes() -> Option<u64> {
+    // Only Linux reports it this way; elsewhere the soak test goes by the other metrics
+    fs::read_to_string("/proc/self/status")
+        .ok()
+        .and_then(|status| resident_bytes(&status))
+}
+
+/// Reads the resident size from the contents of `/proc/self/status`.
+///
+/// # Returns
+/// The `VmRSS` line in bytes, or `None` if there isn't one
+pub fn resident_bytes(status: &str) -> Option<u64> {
+    let line = status
+        .lines()
+        .find_map(|line| line.strip_prefix("VmRSS:"))?;
+    let kilobytes = line
+        .trim()
+        .trim_end_matches("kB")
+        .trim()
+        .parse::<u64>()
+        .ok()?;
+    Some(kilobytes * 1024)
+}
+
+/// Writes a report to its own file.
+///
+/// # Arguments
+/// * `report` - The report to write
+/// * `dir` - Directory to write into, created if necessary; see
+///   [`Paths::soak_reports_dir`](crate::paths::Paths::soak_reports_dir)
+///
+/// # Returns
+/// Where the report was written, or an error message
+pub fn write_report(report: &SoakReport, dir: &Path) -> Result<PathBuf, String> {
+    fs::create_dir_all(dir)
+        .map_err(|e| format!("Failed to create soak report directory: {}", e))?;
+    let stamp = Local::now().format("%Y%m%d-%H%M%S");
+    let path = dir.join(format!("soak-{}.txt", stamp));
+    fs::write(&path, report.to_string())
+        .map_err(|e| format!("Failed to write soak report to {}: {}", path.display(), e))?;
+    Ok(path)
+}
+
+/// Writes a duration as minutes and seconds, like `12:05`.
+fn format_elapsed(elapsed: Duration) -> String {
+    let seconds = elapsed.as_secs();
+    format!("{}:{:02}", seconds / 60, seconds % 60)
+}
+
+/// Writes a byte count in mebibytes.
+fn format_bytes(bytes: f64) -> String {
+    format!("{:.1} MB", bytes / (1024.0 * 1024.0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    fn metrics(memory_mb: u64, text_buffers: usize) -> SoakMetrics {
+        SoakMetrics {
+            memory_bytes: Some(memory_mb * 1024 * 1024),
+            text_buffers,
+            gpu_texture_bytes: 64 * 1024 * 1024,
+        }
+    }
+
+    fn config() -> SoakConfig {
+        SoakConfig {
+            minutes: 10,
+            seed: 42,
+            limits: SoakLimits::default(),
+        }
+    }
+
+    #[test]
+    fn test_soak_request_reads_minutes_and_seed() {
+        assert_eq!(soak_request(&args(&["mirador"])), Ok(None));
+
+        let config = soak_request(&args(&["mirador", "--soak", "30", "--seed", "abc"]))
+            .unwrap()
+            .unwrap();
+        assert_eq!(config.minutes, 30);
+        assert_eq!(config.seed, seed::parse_seed("abc").unwrap());
+        assert_eq!(config.duration(), Duration::from_secs(30 * 60));
+
+        for bad in [
+            &["mirador", "--soak"][..],
+            &["mirador", "--soak", "0"],
+            &["mirador", "--soak", "soon"],
+            &["mirador", "--soak", "5", "--seed"],
+            &["mirador", "--soak", "5", "--seed", "a-b"],
+        ] {
+            assert!(soak_request(&args(bad)).is_err(), "{:?}", bad);
+        }
+    }
+
+    #[test]
+    fn test_bot_plays_every_screen() {
+        let mut bot = SoakBot::new(7);
+        assert_eq!(
+            bot.step(CurrentScreen::Profiles, false),
+            [BotAction::PickProfile]
+        );
+        assert_eq!(bot.step(CurrentScreen::Title, false), [BotAction::StartRun]);
+        assert_eq!(
+            bot.step(CurrentScreen::Game, false),
+            [BotAction::WalkToExit]
+        );
+        assert_eq!(bot.step(CurrentScreen::Game, true), []);
+        assert_eq!(
+            bot.step(CurrentScreen::GameOver, false),
+            [BotAction::NewRun]
+        );
+        assert_eq!(bot.step(CurrentScreen::Loading, false), []);
+        assert!(matches!(
+            bot.step(CurrentScreen::UpgradeMenu, false)[..],
+            [BotAction::PickUpgrade(slot)] if slot < 3
+        ));
+        // A pause the bot didn't make is left straight away
+        assert_eq!(bot.step(CurrentScreen::Pause, false), [BotAction::Resume]);
+    }
+
+    #[test]
+    fn test_bot_does_the_same_things_for_the_same_seed() {
+        let play = |seed| {
+            let mut bot = SoakBot::new(seed);
+            let mut screen = CurrentScreen::Game;
+            let mut actions = Vec::new();
+            for _ in 0..(RESIZE_EVERY * 3) {
+                let step = bot.step(screen, true);
+                for action in &step {
+                    match action {
+                        BotAction::Pause => screen = CurrentScreen::Pause,
+                        BotAction::Resume => screen = CurrentScreen::Game,
+                        _ => {}
+                    }
+                }
+                actions.push(step);
+            }
+            actions
+        };
+
+        let actions = play(11);
+        assert_eq!(actions, play(11));
+        assert_ne!(actions, play(12));
+
+        // Every map view the bot brings up is let go of after the hold
+        let map_view = BotAction::ToggleOverlay(SoakOverlay::MapView);
+        let map_view_frames: Vec<usize> = (0..actions.len())
+            .filter(|&frame| actions[frame].contains(&map_view))
+            .collect();
+        for pair in map_view_frames.chunks(2) {
+            if let &[press, release] = pair {
+                assert_eq!(release - press, MAP_VIEW_HOLD as usize);
+            }
+        }
+
+        let flat: Vec<BotAction> = actions.into_iter().flatten().collect();
+        let pauses = flat.iter().filter(|&&a| a == BotAction::Pause).count();
+        assert!(pauses >= 2);
+        assert_eq!(
+            flat.iter().filter(|&&a| a == BotAction::Resume).count(),
+            pauses
+        );
+        let sizes: Vec<BotAction> = flat
+            .iter()
+            .copied()
+            .filter(|action| matches!(action, BotAction::Resize(..)))
+            .collect();
+        let expected: Vec<BotAction> = RESIZE_CYCLE[..3]
+            .iter()
+            .map(|&(width, height)| BotAction::Resize(width, height))
+            .collect();
+        assert_eq!(sizes, expected);
+    }
+
+    #[test]
+    fn test_bot_maximizes_the_window_again_before_samples() {
+        let step_for = |bot: &mut SoakBot, frames: u64| -> Vec<BotAction> {
+            (0..frames)
+                .flat_map(|_| bot.step(CurrentScreen::Loading, false))
+                .collect()
+        };
+        let mut bot = SoakBot::new(3);
+        assert_eq!(step_for(&mut bot, RESIZE_EVERY - 1), []);
+        assert!(!bot.is_resizing());
+        let (width, height) = RESIZE_CYCLE[0];
+        assert_eq!(step_for(&mut bot, 1), [BotAction::Resize(width, height)]);
+        assert_eq!(step_for(&mut bot, RESIZE_HOLD - 1), []);
+        assert_eq!(step_for(&mut bot, 1), [BotAction::RestoreSize]);
+        assert!(bot.is_resizing());
+        step_for(&mut bot, RESIZE_SETTLE);
+        assert!(!bot.is_resizing());
+    }
+
+    #[test]
+    fn test_slope_per_minute_fits_a_line() {
+        assert_eq!(slope_per_minute(&[]), None);
+        assert_eq!(slope_per_minute(&[(1.0, 5.0)]), None);
+        assert_eq!(slope_per_minute(&[(1.0, 5.0), (1.0, 9.0)]), None);
+        let slope = slope_per_minute(&[(1.0, 10.0), (2.0, 12.0), (3.0, 14.0)]).unwrap();
+        assert!((slope - 2.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_watchdog_passes_steady_runs_and_fails_leaks_and_spikes() {
+        let minutes = |m: u64| Duration::from_secs(m * 60);
+
+        let mut steady = SoakWatchdog::new(SoakLimits::default());
+        for interval in 1..=20 {
+            let elapsed = SAMPLE_INTERVAL * interval;
+            steady.record_frame(elapsed, Duration::from_millis(16), true);
+            // Caches filling in the warm-up don't count
+            let memory = if elapsed < WARM_UP { 100 } else { 300 };
+            steady.sample(elapsed, metrics(memory, 40));
+        }
+        // Slow loading frames can't spike
+        steady.record_frame(minutes(10), Duration::from_secs(3), false);
+        let report = steady.report(&config(), minutes(10));
+        assert!(report.passed(), "{}", report);
+        assert_eq!(report.frames, 21);
+        assert_eq!(report.samples[0].frames.unwrap().count, 1);
+
+        let mut leaking = SoakWatchdog::new(SoakLimits::default());
+        for minute in 1..=10 {
+            leaking.sample(
+                minutes(minute),
+                metrics(200 + minute * 10, 40 + minute as usize),
+            );
+        }
+        leaking.record_frame(minutes(10), Duration::from_secs(1), true);
+        let report = leaking.report(&config(), minutes(10));
+        assert_eq!(report.failures.len(), 3, "{:?}", report.failures);
+        assert!(report.failures[0].contains("grew 10.0 MB a minute"));
+        assert!(report.failures[1].starts_with("Text buffers grew 1.00"));
+        assert!(report.failures[2].contains("1000 ms frame at 10:00"));
+
+        let text = report.to_string();
+        assert!(text.starts_with("Soak test FAILED after 10:00"));
+        assert!(text.contains("Re-run with: mirador --soak 10 --seed 42"));
+    }
+
+    #[test]
+    fn test_resident_bytes_reads_the_status_file() {
+        let status = "Name:\tmirador\nVmPeak:\t  900 kB\nVmRSS:\t  2048 kB\nThreads:\t12\n";
+        assert_eq!(resident_bytes(status), Some(2048 * 1024));
+        assert_eq!(resident_bytes("Name:\tmirador\n"), None);
+    }
+}